@@ -0,0 +1,520 @@
+//! Multi-Agent Deliberation
+//!
+//! Sensitive actions are assessed by several independent agent instances,
+//! each backed by a different model or policy, before they are submitted
+//! for Testimony. The action only goes out when a quorum approves; every
+//! vote, including dissent, is kept with the outcome and attached to the
+//! submission.
+
+use crate::ai::{AIProvider, ChatMessage, CompletionRequest, DELIBERATION_PROMPT};
+use crate::error::RuntimeError;
+use crate::intent::{Intent, IntentType};
+use crate::lattice_client::{ActionSubmission, LatticeClient};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// When deliberation is required and what counts as agreement
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DeliberationPolicy {
+    /// Actions worth at least this much (USD) are deliberated
+    pub risk_threshold_usd: u64,
+
+    /// Approvals needed before the action is submitted
+    pub quorum: usize,
+
+    /// Approvals below this confidence do not count towards quorum
+    pub min_confidence: f64,
+
+    /// Time each agent has to answer (seconds)
+    pub timeout_secs: u64,
+}
+
+impl Default for DeliberationPolicy {
+    fn default() -> Self {
+        Self {
+            risk_threshold_usd: 1_000,
+            quorum: 2,
+            min_confidence: 0.7,
+            timeout_secs: 30,
+        }
+    }
+}
+
+/// Agent verdict on a proposed action
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Verdict {
+    Approve,
+    Reject,
+    Abstain,
+}
+
+/// One agent's assessment of a proposal
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Assessment {
+    /// Verdict
+    pub verdict: Verdict,
+
+    /// Confidence score (0.0 - 1.0)
+    pub confidence: f64,
+
+    /// Short justification
+    #[serde(default)]
+    pub reasoning: String,
+}
+
+/// Action put to the deliberating agents
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Proposal {
+    /// Action ID
+    pub action_id: [u8; 32],
+
+    /// Parsed intent
+    pub intent: Intent,
+
+    /// Estimated value (USD)
+    pub estimated_value_usd: Option<u64>,
+}
+
+impl Proposal {
+    /// Proposal for `intent`
+    pub fn new(action_id: [u8; 32], intent: Intent) -> Self {
+        Self {
+            action_id,
+            estimated_value_usd: intent.estimated_value_usd(),
+            intent,
+        }
+    }
+
+    /// Description handed to model-backed agents
+    fn describe(&self) -> String {
+        serde_json::json!({
+            "action": self.intent.intent_type,
+            "estimated_value_usd": self.estimated_value_usd,
+            "user_message": self.intent.raw_text,
+        })
+        .to_string()
+    }
+}
+
+/// An independent agent taking part in deliberation
+#[async_trait]
+pub trait Deliberator: Send + Sync {
+    /// Unique agent ID
+    fn id(&self) -> &str;
+
+    /// Model backing the agent
+    fn model(&self) -> &str;
+
+    /// Policy the agent applies
+    fn policy(&self) -> &str;
+
+    /// Assess a proposed action
+    async fn assess(&self, proposal: &Proposal) -> Result<Assessment, RuntimeError>;
+}
+
+/// Deliberating agent backed by an AI provider and a written policy
+pub struct ModelDeliberator {
+    id: String,
+    provider: Arc<dyn AIProvider>,
+    policy: String,
+}
+
+impl ModelDeliberator {
+    pub fn new(id: &str, provider: Arc<dyn AIProvider>, policy: &str) -> Self {
+        Self {
+            id: id.to_string(),
+            provider,
+            policy: policy.to_string(),
+        }
+    }
+}
+
+#[async_trait]
+impl Deliberator for ModelDeliberator {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn model(&self) -> &str {
+        self.provider.name()
+    }
+
+    fn policy(&self) -> &str {
+        &self.policy
+    }
+
+    async fn assess(&self, proposal: &Proposal) -> Result<Assessment, RuntimeError> {
+        let request = CompletionRequest {
+            system_prompt: format!("{}\n\n## Your Policy\n{}", DELIBERATION_PROMPT, self.policy),
+            messages: vec![ChatMessage {
+                role: "user".to_string(),
+                content: proposal.describe(),
+            }],
+            temperature: 0.0,
+            max_tokens: 512,
+        };
+
+        let response = self.provider.complete(request).await?;
+        parse_assessment(&response.content)
+    }
+}
+
+/// Assessment JSON from a model answer, ignoring any text around it
+fn parse_assessment(answer: &str) -> Result<Assessment, RuntimeError> {
+    let json = match (answer.find('{'), answer.rfind('}')) {
+        (Some(start), Some(end)) if start < end => &answer[start..=end],
+        _ => answer,
+    };
+    let mut assessment: Assessment = serde_json::from_str(json)
+        .map_err(|e| RuntimeError::SerializationError(format!("Invalid assessment: {}", e)))?;
+    assessment.confidence = assessment.confidence.clamp(0.0, 1.0);
+    Ok(assessment)
+}
+
+/// A recorded vote
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Vote {
+    /// Agent ID
+    pub agent_id: String,
+
+    /// Model backing the agent
+    pub model: String,
+
+    /// Policy the agent applied
+    pub policy: String,
+
+    /// Verdict; agents that failed or timed out abstain
+    pub verdict: Verdict,
+
+    /// Confidence score
+    pub confidence: f64,
+
+    /// Justification, or why the agent abstained
+    pub reasoning: String,
+}
+
+/// Outcome of a deliberation
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Deliberation {
+    /// Action ID
+    pub action_id: [u8; 32],
+
+    /// Every vote, in agent order
+    pub votes: Vec<Vote>,
+
+    /// Approvals counted towards quorum
+    pub approvals: usize,
+
+    /// Approvals required
+    pub quorum: usize,
+
+    /// Whether quorum was reached
+    pub approved: bool,
+
+    /// Votes that went against the outcome
+    pub dissent: Vec<Vote>,
+}
+
+/// Collects assessments from independent agents and gates submission
+pub struct DeliberationCoordinator {
+    policy: DeliberationPolicy,
+    agents: Vec<Arc<dyn Deliberator>>,
+}
+
+impl DeliberationCoordinator {
+    /// Create coordinator with no agents
+    pub fn new(policy: DeliberationPolicy) -> Self {
+        Self {
+            policy,
+            agents: Vec::new(),
+        }
+    }
+
+    /// Get policy
+    pub fn policy(&self) -> &DeliberationPolicy {
+        &self.policy
+    }
+
+    /// Add an agent
+    ///
+    /// Agents must be independent: an agent with the same ID, or with the
+    /// same model and policy as one already added, is refused.
+    pub fn add_agent(&mut self, agent: Arc<dyn Deliberator>) -> Result<(), RuntimeError> {
+        if let Some(existing) = self.agents.iter().find(|a| {
+            a.id() == agent.id() || (a.model() == agent.model() && a.policy() == agent.policy())
+        }) {
+            return Err(RuntimeError::ConfigError(format!(
+                "Agent {} is not independent of {}",
+                agent.id(),
+                existing.id()
+            )));
+        }
+        self.agents.push(agent);
+        Ok(())
+    }
+
+    /// Check if `intent` is risky enough to need deliberation
+    ///
+    /// Contract calls have no estimated value and are always deliberated.
+    pub fn requires_deliberation(&self, intent: &Intent) -> bool {
+        if !intent.requires_testimony() {
+            return false;
+        }
+        match intent.estimated_value_usd() {
+            Some(value) => value >= self.policy.risk_threshold_usd,
+            None => matches!(intent.intent_type, IntentType::ContractCall { .. }),
+        }
+    }
+
+    /// Ask every agent to assess `proposal` and tally the votes
+    pub async fn deliberate(&self, proposal: &Proposal) -> Result<Deliberation, RuntimeError> {
+        if self.agents.len() < self.policy.quorum {
+            return Err(RuntimeError::ConfigError(format!(
+                "Quorum of {} needs at least as many agents, have {}",
+                self.policy.quorum,
+                self.agents.len()
+            )));
+        }
+
+        let timeout = Duration::from_secs(self.policy.timeout_secs);
+        let assessments = futures::future::join_all(
+            self.agents
+                .iter()
+                .map(|agent| tokio::time::timeout(timeout, agent.assess(proposal))),
+        )
+        .await;
+
+        let votes: Vec<Vote> = self
+            .agents
+            .iter()
+            .zip(assessments)
+            .map(|(agent, result)| {
+                let assessment = match result {
+                    Ok(Ok(assessment)) => assessment,
+                    Ok(Err(e)) => abstention(format!("Assessment failed: {}", e)),
+                    Err(_) => abstention("Assessment timed out".to_string()),
+                };
+                Vote {
+                    agent_id: agent.id().to_string(),
+                    model: agent.model().to_string(),
+                    policy: agent.policy().to_string(),
+                    verdict: assessment.verdict,
+                    confidence: assessment.confidence,
+                    reasoning: assessment.reasoning,
+                }
+            })
+            .collect();
+
+        Ok(self.tally(proposal.action_id, votes))
+    }
+
+    fn tally(&self, action_id: [u8; 32], votes: Vec<Vote>) -> Deliberation {
+        let counts = |vote: &Vote| {
+            vote.verdict == Verdict::Approve && vote.confidence >= self.policy.min_confidence
+        };
+        let approvals = votes.iter().filter(|v| counts(v)).count();
+        let approved = approvals >= self.policy.quorum;
+        let dissent = votes
+            .iter()
+            .filter(|v| counts(v) != approved)
+            .cloned()
+            .collect();
+
+        Deliberation {
+            action_id,
+            votes,
+            approvals,
+            quorum: self.policy.quorum,
+            approved,
+            dissent,
+        }
+    }
+
+    /// Submit an action for Testimony once deliberation approved it
+    ///
+    /// The vote record, dissent included, travels with the submission.
+    pub async fn submit_on_quorum(
+        &self,
+        deliberation: &Deliberation,
+        mut submission: ActionSubmission,
+        lattice: &mut LatticeClient,
+    ) -> Result<[u8; 32], RuntimeError> {
+        if !deliberation.approved {
+            return Err(RuntimeError::TestimonyError(format!(
+                "Deliberation quorum not reached ({}/{})",
+                deliberation.approvals, deliberation.quorum
+            )));
+        }
+
+        let votes = serde_json::to_string(&deliberation.votes)
+            .map_err(|e| RuntimeError::SerializationError(e.to_string()))?;
+        submission
+            .parameters
+            .insert("deliberation_votes".to_string(), votes);
+        submission.parameters.insert(
+            "deliberation_dissent".to_string(),
+            deliberation.dissent.len().to_string(),
+        );
+
+        lattice.submit_for_testimony(submission).await
+    }
+}
+
+fn abstention(reasoning: String) -> Assessment {
+    Assessment {
+        verdict: Verdict::Abstain,
+        confidence: 0.0,
+        reasoning,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    struct FixedAgent {
+        id: String,
+        model: String,
+        answer: Option<Assessment>,
+    }
+
+    #[async_trait]
+    impl Deliberator for FixedAgent {
+        fn id(&self) -> &str {
+            &self.id
+        }
+
+        fn model(&self) -> &str {
+            &self.model
+        }
+
+        fn policy(&self) -> &str {
+            "fixed"
+        }
+
+        async fn assess(&self, _proposal: &Proposal) -> Result<Assessment, RuntimeError> {
+            self.answer
+                .clone()
+                .ok_or_else(|| RuntimeError::ExecutionError("model offline".to_string()))
+        }
+    }
+
+    fn agent(id: &str, verdict: Option<Verdict>, confidence: f64) -> Arc<dyn Deliberator> {
+        Arc::new(FixedAgent {
+            id: id.to_string(),
+            model: format!("model-{}", id),
+            answer: verdict.map(|verdict| Assessment {
+                verdict,
+                confidence,
+                reasoning: format!("{} says {:?}", id, verdict),
+            }),
+        })
+    }
+
+    fn transfer(amount: f64) -> Intent {
+        Intent {
+            intent_type: IntentType::Transfer {
+                asset: "FAT".to_string(),
+                amount,
+                recipient: "0xabc".to_string(),
+            },
+            confidence: 0.9,
+            entities: HashMap::new(),
+            raw_text: format!("send {} FAT to 0xabc", amount),
+            parsed_at: 0,
+        }
+    }
+
+    fn with_agents(agents: Vec<Arc<dyn Deliberator>>) -> DeliberationCoordinator {
+        let mut coordinator = DeliberationCoordinator::new(DeliberationPolicy::default());
+        for agent in agents {
+            coordinator.add_agent(agent).unwrap();
+        }
+        coordinator
+    }
+
+    #[test]
+    fn test_risk_threshold() {
+        let coordinator = with_agents(Vec::new());
+        // 1 FAT = $0.01
+        assert!(!coordinator.requires_deliberation(&transfer(1_000.0)));
+        assert!(coordinator.requires_deliberation(&transfer(100_000.0)));
+    }
+
+    #[tokio::test]
+    async fn test_quorum_and_dissent() {
+        let coordinator = with_agents(vec![
+            agent("a", Some(Verdict::Approve), 0.9),
+            agent("b", Some(Verdict::Approve), 0.8),
+            agent("c", Some(Verdict::Reject), 0.95),
+        ]);
+        let proposal = Proposal::new([7u8; 32], transfer(100_000.0));
+
+        let outcome = coordinator.deliberate(&proposal).await.unwrap();
+        assert!(outcome.approved);
+        assert_eq!(outcome.approvals, 2);
+        assert_eq!(outcome.dissent.len(), 1);
+        assert_eq!(outcome.dissent[0].agent_id, "c");
+
+        // A failing agent abstains and a low-confidence approval does not count
+        let coordinator = with_agents(vec![
+            agent("a", Some(Verdict::Approve), 0.9),
+            agent("b", Some(Verdict::Approve), 0.5),
+            agent("c", None, 0.0),
+        ]);
+        let outcome = coordinator.deliberate(&proposal).await.unwrap();
+        assert!(!outcome.approved);
+        assert_eq!(outcome.votes[2].verdict, Verdict::Abstain);
+        assert!(outcome.votes[2].reasoning.contains("model offline"));
+        let dissent: Vec<&str> = outcome
+            .dissent
+            .iter()
+            .map(|v| v.agent_id.as_str())
+            .collect();
+        assert_eq!(dissent, ["a"]);
+
+        let mut lattice = LatticeClient::new(Vec::new());
+        let submission = ActionSubmission {
+            requester: [1u8; 32],
+            action_type: "transfer".to_string(),
+            parameters: HashMap::new(),
+            estimated_value_usd: proposal.estimated_value_usd,
+            timestamp: 0,
+            signature: Vec::new(),
+        };
+        let err = coordinator
+            .submit_on_quorum(&outcome, submission, &mut lattice)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, RuntimeError::TestimonyError(_)));
+    }
+
+    #[test]
+    fn test_agents_must_be_independent() {
+        let mut coordinator = with_agents(vec![agent("a", Some(Verdict::Approve), 0.9)]);
+        assert!(coordinator
+            .add_agent(agent("a", Some(Verdict::Reject), 0.9))
+            .is_err());
+        let same_model = Arc::new(FixedAgent {
+            id: "b".to_string(),
+            model: "model-a".to_string(),
+            answer: None,
+        });
+        assert!(coordinator.add_agent(same_model).is_err());
+    }
+
+    #[test]
+    fn test_parse_assessment() {
+        let assessment = parse_assessment(
+            "Here is my view:\n{\"verdict\": \"reject\", \"confidence\": 1.4, \"reasoning\": \"new recipient\"}",
+        )
+        .unwrap();
+        assert_eq!(assessment.verdict, Verdict::Reject);
+        assert_eq!(assessment.confidence, 1.0);
+        assert!(parse_assessment("looks fine to me").is_err());
+    }
+}
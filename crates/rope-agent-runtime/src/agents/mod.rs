@@ -2,9 +2,13 @@
 //!
 //! OpenClaw-style autonomous AI assistant with blockchain verification.
 
+mod deliberation;
 mod personal;
+mod spending;
 
+pub use deliberation::*;
 pub use personal::*;
+pub use spending::*;
 
 use serde::{Deserialize, Serialize};
 
@@ -2,13 +2,17 @@
 //!
 //! OpenClaw-style autonomous AI assistant with blockchain verification.
 
-use super::{AgentStatus, DailyLimits, PersonalCapability, UsageTracker};
+use super::{
+    AgentStatus, ApprovalRequest, DailyLimits, Deliberation, DeliberationCoordinator,
+    PersonalCapability, Proposal, SpendCheck, SpendingPolicy, UsageTracker, Verdict,
+};
 use crate::channels::{AgentResponse, MessageContent, ResponseContent, UserMessage};
-use crate::error::RuntimeError;
+use crate::error::{AuthError, RuntimeError};
 use crate::identity::{AuthorizationToken, DatawalletIdentity, RopeAgentIdentity};
 use crate::intent::{ActionType, Intent, IntentParser};
 use crate::skills::{Skill, SkillRegistry};
 use parking_lot::RwLock;
+use rope_crypto::hybrid::HybridSignature;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -44,6 +48,12 @@ pub struct PersonalAgent {
 
     /// Conversation state per channel
     conversation_state: RwLock<HashMap<String, ConversationState>>,
+
+    /// Independent agents that must agree on sensitive actions
+    deliberation: Option<Arc<DeliberationCoordinator>>,
+
+    /// Spending limits for actions that move FAT
+    spending: Arc<SpendingPolicy>,
 }
 
 impl PersonalAgent {
@@ -62,9 +72,27 @@ impl PersonalAgent {
             pending_actions: RwLock::new(HashMap::new()),
             intent_parser: IntentParser::new(),
             conversation_state: RwLock::new(HashMap::new()),
+            deliberation: None,
+            spending: Arc::new(SpendingPolicy::default()),
         }
     }
 
+    /// Replace the spending policy
+    pub fn set_spending_policy(&mut self, policy: Arc<SpendingPolicy>) {
+        self.spending = policy;
+    }
+
+    /// Spending policy in effect
+    pub fn spending_policy(&self) -> &Arc<SpendingPolicy> {
+        &self.spending
+    }
+
+    /// Require multi-agent deliberation for actions above the coordinator's
+    /// risk threshold
+    pub fn set_deliberation(&mut self, coordinator: Arc<DeliberationCoordinator>) {
+        self.deliberation = Some(coordinator);
+    }
+
     /// Get agent identity
     pub fn identity(&self) -> &RopeAgentIdentity {
         &self.identity
@@ -127,16 +155,30 @@ impl PersonalAgent {
         // Update conversation state
         self.update_conversation_state(&message);
 
+        // Approvals for held spending actions
+        if let MessageContent::Command { name, args } = &message.content {
+            if name == "approve" || name == "reject" {
+                let code = args.first().map(String::as_str).unwrap_or_default();
+                return self
+                    .handle_approval_command(&message, name == "approve", code)
+                    .await;
+            }
+        }
+
         // Extract text content
         let text = match &message.content {
             MessageContent::Text(t) => t.clone(),
             MessageContent::Command { name, args } => {
                 format!("/{} {}", name, args.join(" "))
             }
+            MessageContent::Audio {
+                transcription: Some(t),
+                ..
+            } => t.clone(),
             _ => {
                 // Non-text messages
                 return Ok(self.create_response(
-                    &message.channel,
+                    &message,
                     ResponseContent::Text(
                         "I can currently only process text messages.".to_string(),
                     ),
@@ -181,18 +223,163 @@ impl PersonalAgent {
             std::time::Duration::from_secs(intent.timeout_secs()),
         );
 
+        // Amounts above the user's limits wait for their approval
+        let check = self.spending.check(
+            action_id,
+            &message.sender,
+            &message.channel,
+            &message.conversation_key(),
+            &intent,
+        );
+        if let SpendCheck::NeedsApproval(request) = check {
+            self.pending_actions.write().insert(
+                action_id,
+                PendingAction {
+                    id: action_id,
+                    intent,
+                    message: message.clone(),
+                    created_at: chrono::Utc::now().timestamp(),
+                    status: PendingActionStatus::AwaitingApproval,
+                    testimonies: Vec::new(),
+                    deliberation: None,
+                },
+            );
+            self.set_status(AgentStatus::Idle);
+            return Ok(
+                self.create_response(&message, ResponseContent::Text(approval_text(&request)))
+            );
+        }
+
+        self.submit_for_testimony(message, intent, action_id).await
+    }
+
+    /// Approve or reject a held spending action from `message`'s channel
+    async fn handle_approval_command(
+        &self,
+        message: &UserMessage,
+        approve: bool,
+        code: &str,
+    ) -> Result<AgentResponse, RuntimeError> {
+        if !approve {
+            self.set_status(AgentStatus::Idle);
+            let text = match self.spending.reject(code) {
+                Some(request) => {
+                    self.set_pending_status(&request.action_id, PendingActionStatus::Rejected);
+                    format!("Action {} cancelled.", code)
+                }
+                None => format!("No action is waiting for approval with code {}.", code),
+            };
+            return Ok(self.create_response(message, ResponseContent::Text(text)));
+        }
+
+        match self
+            .spending
+            .approve_from_channel(code, &message.sender, &message.channel)
+        {
+            Ok(request) => {
+                let response = self.resume_approved(&request).await?;
+                Ok(self.create_response(message, response.content))
+            }
+            Err(e) => {
+                self.set_status(AgentStatus::Idle);
+                Ok(self.create_response(
+                    message,
+                    ResponseContent::Text(format!(
+                        "Could not approve {}: {}. Approve as the requesting user from a \
+                         different channel than the one the action was requested in.",
+                        code, e
+                    )),
+                ))
+            }
+        }
+    }
+
+    /// Approve a held spending action with the user's wallet signature,
+    /// answering in the conversation the action was requested in
+    pub async fn approve_with_signature(
+        &self,
+        code: &str,
+        signature: &HybridSignature,
+    ) -> Result<AgentResponse, RuntimeError> {
+        let wallet_key =
+            self.identity
+                .datawallet
+                .signing_key()
+                .ok_or(RuntimeError::AuthorizationError(
+                    AuthError::InvalidSignature,
+                ))?;
+        let request = self
+            .spending
+            .approve_with_signature(code, &wallet_key, signature)?;
+        self.resume_approved(&request).await
+    }
+
+    /// Submit an approved action for Testimony
+    async fn resume_approved(
+        &self,
+        request: &ApprovalRequest,
+    ) -> Result<AgentResponse, RuntimeError> {
+        let action = self
+            .pending_actions
+            .write()
+            .remove(&request.action_id)
+            .filter(|action| action.status == PendingActionStatus::AwaitingApproval)
+            .ok_or_else(|| {
+                RuntimeError::ExecutionError("Approved action is no longer pending".to_string())
+            })?;
+        self.submit_for_testimony(action.message, action.intent, action.id)
+            .await
+    }
+
+    fn set_pending_status(&self, action_id: &[u8; 32], status: PendingActionStatus) {
+        if let Some(action) = self.pending_actions.write().get_mut(action_id) {
+            action.status = status;
+        }
+    }
+
+    /// Submit an action within the user's limits for Testimony consensus
+    async fn submit_for_testimony(
+        &self,
+        message: UserMessage,
+        intent: Intent,
+        action_id: [u8; 32],
+    ) -> Result<AgentResponse, RuntimeError> {
+        // Sensitive actions need independent agents to agree first
+        let deliberation = match &self.deliberation {
+            Some(coordinator) if coordinator.requires_deliberation(&intent) => Some(
+                coordinator
+                    .deliberate(&Proposal::new(action_id, intent.clone()))
+                    .await?,
+            ),
+            _ => None,
+        };
+        let rejection = deliberation
+            .as_ref()
+            .filter(|d| !d.approved)
+            .map(rejection_text);
+
         // Create pending action
         let pending = PendingAction {
             id: action_id,
             intent: intent.clone(),
             message: message.clone(),
             created_at: chrono::Utc::now().timestamp(),
-            status: PendingActionStatus::AwaitingTestimony,
+            status: if rejection.is_some() {
+                PendingActionStatus::Rejected
+            } else {
+                PendingActionStatus::AwaitingTestimony
+            },
             testimonies: Vec::new(),
+            deliberation,
         };
 
         self.pending_actions.write().insert(action_id, pending);
 
+        if let Some(text) = rejection {
+            self.set_status(AgentStatus::Idle);
+            return Ok(self.create_response(&message, ResponseContent::Text(text)));
+        }
+
         // Update status
         self.set_status(AgentStatus::AwaitingTestimony { action_id });
 
@@ -223,7 +410,7 @@ impl PersonalAgent {
             _ => "Processing action... Awaiting AI Testimony consensus.".to_string(),
         };
 
-        Ok(self.create_response(&message.channel, ResponseContent::Text(response_text)))
+        Ok(self.create_response(&message, ResponseContent::Text(response_text)))
     }
 
     /// Process action locally (no testimony needed)
@@ -247,7 +434,7 @@ impl PersonalAgent {
             _ => "I'm not sure how to help with that. Try asking for /help".to_string(),
         };
 
-        Ok(self.create_response(&message.channel, ResponseContent::Text(response_text)))
+        Ok(self.create_response(&message, ResponseContent::Text(response_text)))
     }
 
     /// Generate help text
@@ -260,6 +447,7 @@ impl PersonalAgent {
         help.push_str("• `stake [amount]` - Stake FAT tokens\n");
         help.push_str("• `status` - Check your balance\n");
         help.push_str("• `remind [time] [message]` - Set a reminder\n");
+        help.push_str("• `/approve [code]` - Approve an action above your limits\n");
         help.push_str("• `help` - Show this help\n\n");
         help.push_str("**Enabled Capabilities:**\n");
         for cap in &self.capabilities {
@@ -306,7 +494,7 @@ impl PersonalAgent {
     fn update_conversation_state(&self, message: &UserMessage) {
         let mut state = self.conversation_state.write();
         let channel_state = state
-            .entry(message.channel.clone())
+            .entry(message.conversation_key())
             .or_insert_with(ConversationState::new);
 
         channel_state.add_message(message);
@@ -322,12 +510,14 @@ impl PersonalAgent {
         *blake3::hash(&data).as_bytes()
     }
 
-    /// Create response
-    fn create_response(&self, channel: &str, content: ResponseContent) -> AgentResponse {
+    /// Create response in the conversation `message` belongs to
+    fn create_response(&self, message: &UserMessage, content: ResponseContent) -> AgentResponse {
         AgentResponse {
-            channel: channel.to_string(),
+            channel: message.channel.clone(),
             content,
-            reply_to: None,
+            reply_to: message.message_id.clone(),
+            thread_id: message.thread_id.clone(),
+            attachments: Vec::new(),
         }
     }
 
@@ -359,8 +549,12 @@ impl PersonalAgent {
         self.pending_actions.read().get(action_id).cloned()
     }
 
-    /// Clear completed pending actions
+    /// Clear completed pending actions and time out unapproved ones
     pub fn cleanup_pending_actions(&self, max_age_secs: i64) {
+        for request in self.spending.cleanup_expired() {
+            self.set_pending_status(&request.action_id, PendingActionStatus::TimedOut);
+        }
+
         let now = chrono::Utc::now().timestamp();
         self.pending_actions
             .write()
@@ -388,11 +582,47 @@ pub struct PendingAction {
 
     /// Received testimonies
     pub testimonies: Vec<TestimonyResult>,
+
+    /// Multi-agent deliberation, if the action required one
+    #[serde(default)]
+    pub deliberation: Option<Deliberation>,
+}
+
+/// Explain to the user why deliberation stopped an action
+fn rejection_text(deliberation: &Deliberation) -> String {
+    let mut text = format!(
+        "This action was not submitted: {} of {} required agents approved it.",
+        deliberation.approvals, deliberation.quorum
+    );
+    for vote in deliberation
+        .votes
+        .iter()
+        .filter(|v| v.verdict == Verdict::Reject)
+    {
+        text.push_str(&format!("\n- {}: {}", vote.agent_id, vote.reasoning));
+    }
+    text
+}
+
+/// Tell the user how to release an action held by spending limits
+fn approval_text(request: &ApprovalRequest) -> String {
+    format!(
+        "This action needs your approval: {}.\n\n\
+         To approve, send `/approve {code}` from another connected channel or sign \
+         the approval with your wallet. Send `/reject {code}` to cancel. \
+         The request expires in {} minutes.",
+        request.reason,
+        (request.expires_at - request.created_at) / 60,
+        code = request.code(),
+    )
 }
 
 /// Pending action status
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum PendingActionStatus {
+    /// Held by spending limits until the user approves
+    AwaitingApproval,
+
     /// Awaiting testimony consensus
     AwaitingTestimony,
 
@@ -512,6 +742,9 @@ mod tests {
             content: MessageContent::Text("help".to_string()),
             timestamp: chrono::Utc::now().timestamp(),
             message_id: None,
+            thread_id: None,
+            reply_to: None,
+            attachments: Vec::new(),
             metadata: HashMap::new(),
         };
 
@@ -523,4 +756,86 @@ mod tests {
             panic!("Expected text response");
         }
     }
+
+    #[tokio::test]
+    async fn test_process_transcribed_voice_note() {
+        let agent = test_agent();
+
+        let message = UserMessage {
+            channel: "test".to_string(),
+            sender: "user1".to_string(),
+            content: MessageContent::Audio {
+                url: "https://example.com/voice.ogg".to_string(),
+                transcription: Some("help".to_string()),
+            },
+            timestamp: chrono::Utc::now().timestamp(),
+            message_id: None,
+            thread_id: None,
+            reply_to: None,
+            attachments: Vec::new(),
+            metadata: HashMap::new(),
+        };
+
+        let response = agent.process_message(message).await.unwrap();
+        assert!(response.content.to_plain_text().contains("RopeAgent Help"));
+    }
+
+    #[tokio::test]
+    async fn test_spending_above_limit_needs_approval() {
+        let agent = test_agent();
+        let message = |channel: &str, content: MessageContent| UserMessage {
+            channel: channel.to_string(),
+            sender: "user1".to_string(),
+            content,
+            timestamp: chrono::Utc::now().timestamp(),
+            message_id: None,
+            thread_id: None,
+            reply_to: None,
+            attachments: Vec::new(),
+            metadata: HashMap::new(),
+        };
+
+        let response = agent
+            .process_message(message(
+                "telegram:1",
+                MessageContent::Text("transfer 5000 FAT to 0xabc".to_string()),
+            ))
+            .await
+            .unwrap();
+        assert!(response
+            .content
+            .to_plain_text()
+            .contains("needs your approval"));
+
+        let (action_id, code) = {
+            let pending = agent.pending_actions.read();
+            let action = pending.values().next().unwrap();
+            assert_eq!(action.status, PendingActionStatus::AwaitingApproval);
+            (action.id, hex::encode(action.id))
+        };
+        let approve = |channel| {
+            message(
+                channel,
+                MessageContent::Command {
+                    name: "approve".to_string(),
+                    args: vec![code.clone()],
+                },
+            )
+        };
+
+        // The requesting channel cannot approve its own request
+        agent.process_message(approve("telegram:1")).await.unwrap();
+        assert_eq!(
+            agent.get_pending_action(&action_id).unwrap().status,
+            PendingActionStatus::AwaitingApproval
+        );
+
+        let response = agent.process_message(approve("matrix:1")).await.unwrap();
+        assert_eq!(response.channel, "matrix:1");
+        assert!(response.content.to_plain_text().contains("Testimony"));
+        assert_eq!(
+            agent.get_pending_action(&action_id).unwrap().status,
+            PendingActionStatus::AwaitingTestimony
+        );
+    }
 }
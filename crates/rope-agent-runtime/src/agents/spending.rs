@@ -0,0 +1,545 @@
+//! Spending Limits
+//!
+//! Every action that moves FAT or calls a bridge is checked against the
+//! user's per-transaction and daily limits before it is submitted for
+//! Testimony. Limits can be tightened for a single conversation, e.g. a
+//! group chat the agent answers in.
+//!
+//! Actions above a limit are held as an [`ApprovalRequest`]. The user
+//! releases them either from a second channel (a different channel than the
+//! one the request came from, so one compromised account cannot both ask
+//! and approve) or by signing the request's challenge with their wallet.
+
+use crate::error::{AuthError, RuntimeError};
+use crate::intent::{Intent, IntentType};
+use chrono::NaiveDate;
+use parking_lot::RwLock;
+use rope_crypto::hybrid::{HybridPublicKey, HybridSignature, HybridVerifier};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// How long an approval request stays open (seconds)
+pub const APPROVAL_TTL_SECS: i64 = 600;
+
+/// Domain separator for approval challenges
+const APPROVAL_CONTEXT: &[u8] = b"rope-agent spending approval v1";
+
+/// Spending limits in FAT
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SpendingLimits {
+    /// Largest single action
+    pub per_transaction: f64,
+
+    /// Total per UTC day
+    pub daily: f64,
+}
+
+impl Default for SpendingLimits {
+    fn default() -> Self {
+        Self {
+            per_transaction: 1_000.0,
+            daily: 5_000.0,
+        }
+    }
+}
+
+/// Amount of FAT an intent moves, if it is subject to spending limits
+///
+/// Bridge calls are always subject to limits; when their amount cannot be
+/// read from the call parameters it is `None` and approval is required.
+pub fn spend_amount(intent: &Intent) -> Option<Option<f64>> {
+    match &intent.intent_type {
+        IntentType::Transfer { asset, amount, .. } if asset.eq_ignore_ascii_case("FAT") => {
+            Some(Some(*amount))
+        }
+        IntentType::Swap {
+            from_asset, amount, ..
+        } if from_asset.eq_ignore_ascii_case("FAT") => Some(Some(*amount)),
+        IntentType::Stake { amount, .. } => Some(Some(*amount)),
+        IntentType::ContractCall {
+            contract,
+            method,
+            params,
+        } if contract.to_lowercase().contains("bridge")
+            || method.to_lowercase().contains("bridge") =>
+        {
+            Some(params.iter().find_map(|p| p.parse::<f64>().ok()))
+        }
+        _ => None,
+    }
+}
+
+/// Spending action held until the user approves it
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ApprovalRequest {
+    /// Action the request holds
+    pub action_id: [u8; 32],
+
+    /// User the limits apply to
+    pub user_id: String,
+
+    /// Channel the action was requested from
+    pub channel: String,
+
+    /// Conversation the action was requested in
+    pub conversation: String,
+
+    /// FAT moved, if known
+    pub amount: Option<f64>,
+
+    /// Which limit was exceeded
+    pub reason: String,
+
+    /// Creation timestamp
+    pub created_at: i64,
+
+    /// Expiry timestamp
+    pub expires_at: i64,
+}
+
+impl ApprovalRequest {
+    /// Code the user quotes to approve from another channel: the full
+    /// action ID, so it cannot be guessed
+    pub fn code(&self) -> String {
+        hex::encode(self.action_id)
+    }
+
+    /// Bytes the user's wallet signs to approve
+    pub fn challenge(&self) -> Vec<u8> {
+        let mut data = APPROVAL_CONTEXT.to_vec();
+        data.extend_from_slice(&self.action_id);
+        data.extend_from_slice(self.user_id.as_bytes());
+        data.extend_from_slice(&self.amount.unwrap_or(-1.0).to_le_bytes());
+        data.extend_from_slice(&self.expires_at.to_le_bytes());
+        data
+    }
+
+    fn is_expired(&self, now: i64) -> bool {
+        now >= self.expires_at
+    }
+}
+
+/// Outcome of a spending check
+#[derive(Clone, Debug)]
+pub enum SpendCheck {
+    /// Within limits; the amount has been counted against today's total
+    Allowed,
+
+    /// Held until the user approves it
+    NeedsApproval(ApprovalRequest),
+}
+
+/// FAT spent by one user on one day
+#[derive(Clone, Debug)]
+struct DailySpend {
+    day: NaiveDate,
+    amount: f64,
+}
+
+/// Per-user and per-conversation spending policy
+pub struct SpendingPolicy {
+    /// Limits for users without their own
+    default_limits: SpendingLimits,
+
+    /// Per-user limits
+    user_limits: RwLock<HashMap<String, SpendingLimits>>,
+
+    /// Per-conversation limits, applied on top of the user's
+    conversation_limits: RwLock<HashMap<String, SpendingLimits>>,
+
+    /// Spent today per user
+    spent: RwLock<HashMap<String, DailySpend>>,
+
+    /// Open approval requests
+    pending: RwLock<HashMap<[u8; 32], ApprovalRequest>>,
+}
+
+impl SpendingPolicy {
+    /// Create policy with `default_limits` for every user
+    pub fn new(default_limits: SpendingLimits) -> Self {
+        Self {
+            default_limits,
+            user_limits: RwLock::new(HashMap::new()),
+            conversation_limits: RwLock::new(HashMap::new()),
+            spent: RwLock::new(HashMap::new()),
+            pending: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Set limits for `user_id`
+    pub fn set_user_limits(&self, user_id: &str, limits: SpendingLimits) {
+        self.user_limits.write().insert(user_id.to_string(), limits);
+    }
+
+    /// Set limits for a conversation
+    ///
+    /// The stricter of the conversation's and the user's limits applies.
+    pub fn set_conversation_limits(&self, conversation: &str, limits: SpendingLimits) {
+        self.conversation_limits
+            .write()
+            .insert(conversation.to_string(), limits);
+    }
+
+    /// Limits in effect for `user_id` in `conversation`
+    pub fn limits_for(&self, user_id: &str, conversation: &str) -> SpendingLimits {
+        let user = self
+            .user_limits
+            .read()
+            .get(user_id)
+            .cloned()
+            .unwrap_or_else(|| self.default_limits.clone());
+        match self.conversation_limits.read().get(conversation) {
+            Some(limits) => SpendingLimits {
+                per_transaction: user.per_transaction.min(limits.per_transaction),
+                daily: user.daily.min(limits.daily),
+            },
+            None => user,
+        }
+    }
+
+    /// FAT `user_id` has spent today
+    pub fn spent_today(&self, user_id: &str) -> f64 {
+        let today = chrono::Utc::now().date_naive();
+        Self::spent_on(&self.spent.read(), user_id, today)
+    }
+
+    /// Check `intent` against the limits, counting it if allowed and
+    /// opening an approval request otherwise
+    pub fn check(
+        &self,
+        action_id: [u8; 32],
+        user_id: &str,
+        channel: &str,
+        conversation: &str,
+        intent: &Intent,
+    ) -> SpendCheck {
+        let Some(amount) = spend_amount(intent) else {
+            return SpendCheck::Allowed;
+        };
+
+        let limits = self.limits_for(user_id, conversation);
+        let today = chrono::Utc::now().date_naive();
+        let reason = {
+            // Check and count under one lock, so concurrent actions cannot
+            // each fit under the daily limit yet exceed it together
+            let mut spent = self.spent.write();
+            let spent_today = Self::spent_on(&spent, user_id, today);
+            match amount {
+                None => Some("the bridge call's amount is unknown".to_string()),
+                Some(amount) if !amount.is_finite() || amount <= 0.0 => {
+                    Some(format!("{} FAT is not a valid amount", amount))
+                }
+                Some(amount) if amount > limits.per_transaction => Some(format!(
+                    "{} FAT is above the {} FAT per-transaction limit",
+                    amount, limits.per_transaction
+                )),
+                Some(amount) if spent_today + amount > limits.daily => Some(format!(
+                    "{} FAT would exceed the {} FAT daily limit ({} FAT spent today)",
+                    amount, limits.daily, spent_today
+                )),
+                Some(amount) => {
+                    Self::add_spend(&mut spent, user_id, amount, today);
+                    None
+                }
+            }
+        };
+
+        match reason {
+            None => SpendCheck::Allowed,
+            Some(reason) => {
+                let now = chrono::Utc::now().timestamp();
+                let request = ApprovalRequest {
+                    action_id,
+                    user_id: user_id.to_string(),
+                    channel: channel.to_string(),
+                    conversation: conversation.to_string(),
+                    amount,
+                    reason,
+                    created_at: now,
+                    expires_at: now + APPROVAL_TTL_SECS,
+                };
+                self.pending.write().insert(action_id, request.clone());
+                SpendCheck::NeedsApproval(request)
+            }
+        }
+    }
+
+    /// Open approval request with `code`
+    pub fn pending_approval(&self, code: &str) -> Option<ApprovalRequest> {
+        self.pending
+            .read()
+            .values()
+            .find(|request| request.code() == code)
+            .cloned()
+    }
+
+    /// Approve as `user_id` from `channel`, which must be the requesting
+    /// user on a different channel than the one that asked
+    pub fn approve_from_channel(
+        &self,
+        code: &str,
+        user_id: &str,
+        channel: &str,
+    ) -> Result<ApprovalRequest, RuntimeError> {
+        let request = self.take_open(code, |request| {
+            if request.user_id != user_id || request.channel == channel {
+                Err(RuntimeError::AuthorizationError(
+                    AuthError::InsufficientPermissions,
+                ))
+            } else {
+                Ok(())
+            }
+        })?;
+        self.record(&request.user_id, request.amount.unwrap_or(0.0));
+        Ok(request)
+    }
+
+    /// Approve with the user's wallet signature over the request's
+    /// [`ApprovalRequest::challenge`]
+    pub fn approve_with_signature(
+        &self,
+        code: &str,
+        wallet_key: &HybridPublicKey,
+        signature: &HybridSignature,
+    ) -> Result<ApprovalRequest, RuntimeError> {
+        let request = self.take_open(code, |request| {
+            match HybridVerifier::verify(wallet_key, &request.challenge(), signature) {
+                Ok(true) => Ok(()),
+                _ => Err(RuntimeError::AuthorizationError(
+                    AuthError::InvalidSignature,
+                )),
+            }
+        })?;
+        self.record(&request.user_id, request.amount.unwrap_or(0.0));
+        Ok(request)
+    }
+
+    /// Decline an approval request
+    pub fn reject(&self, code: &str) -> Option<ApprovalRequest> {
+        let mut pending = self.pending.write();
+        let action_id = pending
+            .values()
+            .find(|request| request.code() == code)?
+            .action_id;
+        pending.remove(&action_id)
+    }
+
+    /// Drop expired approval requests, returning them
+    pub fn cleanup_expired(&self) -> Vec<ApprovalRequest> {
+        let now = chrono::Utc::now().timestamp();
+        let mut pending = self.pending.write();
+        let expired: Vec<_> = pending
+            .values()
+            .filter(|request| request.is_expired(now))
+            .cloned()
+            .collect();
+        for request in &expired {
+            pending.remove(&request.action_id);
+        }
+        expired
+    }
+
+    /// Remove the open request with `code` if `authorize` accepts it
+    fn take_open(
+        &self,
+        code: &str,
+        authorize: impl FnOnce(&ApprovalRequest) -> Result<(), RuntimeError>,
+    ) -> Result<ApprovalRequest, RuntimeError> {
+        let mut pending = self.pending.write();
+        let request = pending
+            .values()
+            .find(|request| request.code() == code)
+            .cloned()
+            .ok_or(RuntimeError::AuthorizationError(AuthError::TokenNotFound))?;
+
+        if request.is_expired(chrono::Utc::now().timestamp()) {
+            pending.remove(&request.action_id);
+            return Err(RuntimeError::AuthorizationError(AuthError::TokenExpired));
+        }
+        authorize(&request)?;
+        pending.remove(&request.action_id);
+        Ok(request)
+    }
+
+    fn record(&self, user_id: &str, amount: f64) {
+        let today = chrono::Utc::now().date_naive();
+        Self::add_spend(&mut self.spent.write(), user_id, amount, today);
+    }
+
+    fn spent_on(spent: &HashMap<String, DailySpend>, user_id: &str, day: NaiveDate) -> f64 {
+        spent
+            .get(user_id)
+            .filter(|spend| spend.day == day)
+            .map(|spend| spend.amount)
+            .unwrap_or(0.0)
+    }
+
+    /// Count `amount` against `user_id`'s total for `day`
+    ///
+    /// Invalid amounts are never counted, so an approved one cannot poison
+    /// the total.
+    fn add_spend(
+        spent: &mut HashMap<String, DailySpend>,
+        user_id: &str,
+        amount: f64,
+        day: NaiveDate,
+    ) {
+        if !amount.is_finite() || amount <= 0.0 {
+            return;
+        }
+        let spend = spent
+            .entry(user_id.to_string())
+            .or_insert(DailySpend { day, amount: 0.0 });
+        if spend.day != day {
+            *spend = DailySpend { day, amount: 0.0 };
+        }
+        spend.amount += amount;
+    }
+}
+
+impl Default for SpendingPolicy {
+    fn default() -> Self {
+        Self::new(SpendingLimits::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::intent::IntentParser;
+    use rope_crypto::hybrid::HybridSigner;
+
+    fn policy() -> SpendingPolicy {
+        SpendingPolicy::new(SpendingLimits {
+            per_transaction: 100.0,
+            daily: 150.0,
+        })
+    }
+
+    fn transfer(amount: u32) -> Intent {
+        IntentParser::new().parse(&format!("transfer {} FAT to 0xabc", amount))
+    }
+
+    #[test]
+    fn test_limits() {
+        let policy = policy();
+        let check = |id: u8, amount| {
+            policy.check(
+                [id; 32],
+                "alice",
+                "telegram:1",
+                "telegram:1",
+                &transfer(amount),
+            )
+        };
+
+        assert!(matches!(check(1, 80), SpendCheck::Allowed));
+        assert_eq!(policy.spent_today("alice"), 80.0);
+
+        // Per-transaction limit
+        let SpendCheck::NeedsApproval(request) = check(2, 120) else {
+            panic!("expected approval request");
+        };
+        assert!(request.reason.contains("per-transaction"));
+
+        // Daily limit
+        let SpendCheck::NeedsApproval(request) = check(3, 80) else {
+            panic!("expected approval request");
+        };
+        assert!(request.reason.contains("daily"));
+        assert_eq!(policy.spent_today("alice"), 80.0);
+
+        // Queries are not spending
+        let query = IntentParser::new().parse("what is the weather");
+        assert!(matches!(
+            policy.check([4; 32], "alice", "telegram:1", "telegram:1", &query),
+            SpendCheck::Allowed
+        ));
+
+        // Conversation limits tighten the user's
+        policy.set_conversation_limits(
+            "discord:group",
+            SpendingLimits {
+                per_transaction: 10.0,
+                daily: 1_000.0,
+            },
+        );
+        assert_eq!(
+            policy.limits_for("bob", "discord:group").per_transaction,
+            10.0
+        );
+        assert_eq!(policy.limits_for("bob", "discord:group").daily, 150.0);
+    }
+
+    #[test]
+    fn test_approve_from_second_channel() {
+        let policy = policy();
+        let SpendCheck::NeedsApproval(request) =
+            policy.check([1; 32], "alice", "telegram:1", "telegram:1", &transfer(120))
+        else {
+            panic!("expected approval request");
+        };
+
+        // Not from the channel that asked, nor by another user
+        assert!(policy
+            .approve_from_channel(&request.code(), "alice", "telegram:1")
+            .is_err());
+        assert!(policy
+            .approve_from_channel(&request.code(), "mallory", "matrix:@mallory:rope.network")
+            .is_err());
+        let approved = policy
+            .approve_from_channel(&request.code(), "alice", "matrix:@alice:rope.network")
+            .unwrap();
+        assert_eq!(approved.action_id, [1; 32]);
+        assert_eq!(policy.spent_today("alice"), 120.0);
+
+        // Single use
+        assert!(policy
+            .approve_from_channel(&request.code(), "alice", "matrix:@alice:rope.network")
+            .is_err());
+    }
+
+    #[test]
+    fn test_invalid_amounts_need_approval() {
+        let policy = policy();
+        for amount in ["nan", "inf", "-50", "0"] {
+            let intent = IntentParser::new().parse(&format!("transfer {} FAT to 0xabc", amount));
+            assert!(spend_amount(&intent).is_some(), "{}", amount);
+            let SpendCheck::NeedsApproval(request) =
+                policy.check([1; 32], "alice", "telegram:1", "telegram:1", &intent)
+            else {
+                panic!("{} FAT was allowed", amount);
+            };
+            assert!(request.reason.contains("not a valid amount"));
+
+            // Approving does not poison today's total
+            policy
+                .approve_from_channel(&request.code(), "alice", "matrix:1")
+                .unwrap();
+            assert_eq!(policy.spent_today("alice"), 0.0);
+        }
+    }
+
+    #[test]
+    fn test_approve_with_signature() {
+        let policy = policy();
+        let (wallet, wallet_key) = HybridSigner::generate_signing_only();
+        let (other, _) = HybridSigner::generate_signing_only();
+        let SpendCheck::NeedsApproval(request) =
+            policy.check([1; 32], "alice", "telegram:1", "telegram:1", &transfer(120))
+        else {
+            panic!("expected approval request");
+        };
+
+        let forged = other.sign(&request.challenge());
+        assert!(policy
+            .approve_with_signature(&request.code(), &wallet_key, &forged)
+            .is_err());
+
+        let signature = wallet.sign(&request.challenge());
+        assert!(policy
+            .approve_with_signature(&request.code(), &wallet_key, &signature)
+            .is_ok());
+        assert!(policy.pending_approval(&request.code()).is_none());
+    }
+}
@@ -0,0 +1,447 @@
+//! In-Process Local Inference
+//!
+//! Runs GGUF models through llama.cpp inside the runtime, without an Ollama
+//! or llama.cpp server. Enabled with the `llama` feature; the model is
+//! chosen per agent through [`AIModelConfig::llama_cpp`](super::AIModelConfig).
+//!
+//! - Models are downloaded once into a models directory and verified
+//!   against their published SHA-256 before every load
+//! - Conversation history is trimmed, oldest first, to fit the context
+//! - At most `max_concurrent` generations run at a time; further requests
+//!   fail with [`RuntimeError::Overloaded`] so the model manager can fall
+//!   back to a cloud model
+
+use super::CompletionRequest;
+use crate::error::RuntimeError;
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+use tokio::io::AsyncWriteExt;
+
+/// GGUF model file
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GgufModel {
+    /// Model name reported in responses
+    pub name: String,
+
+    /// Download URL
+    pub url: String,
+
+    /// SHA-256 of the file (hex)
+    pub sha256: String,
+
+    /// File name in the models directory
+    pub file_name: String,
+}
+
+/// In-process llama.cpp settings
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LlamaCppConfig {
+    /// Model to run
+    pub model: GgufModel,
+
+    /// Directory models are stored in
+    pub models_dir: PathBuf,
+
+    /// Context window (tokens)
+    pub context_size: u32,
+
+    /// CPU threads; `None` lets llama.cpp decide
+    pub threads: Option<u32>,
+
+    /// Layers offloaded to the GPU
+    pub gpu_layers: u32,
+
+    /// Generations allowed to run at once
+    pub max_concurrent: usize,
+}
+
+impl LlamaCppConfig {
+    /// Run `model` with default settings
+    pub fn new(model: GgufModel) -> Self {
+        Self {
+            model,
+            models_dir: dirs::data_dir()
+                .unwrap_or_else(|| PathBuf::from("."))
+                .join("ropeagent")
+                .join("models"),
+            context_size: 4096,
+            threads: None,
+            gpu_layers: 0,
+            max_concurrent: 1,
+        }
+    }
+}
+
+/// Path of `model`, downloading it first if missing or corrupt
+///
+/// Downloads go to a `.part` file that only replaces the model once its
+/// hash matches, so an interrupted download is never loaded.
+pub async fn ensure_model(model: &GgufModel, models_dir: &Path) -> Result<PathBuf, RuntimeError> {
+    let path = models_dir.join(&model.file_name);
+    if path.exists() {
+        if file_sha256(&path).await? == model.sha256.to_lowercase() {
+            return Ok(path);
+        }
+        tracing::warn!("{} failed verification, downloading again", model.name);
+    }
+
+    tokio::fs::create_dir_all(models_dir).await?;
+    let partial = path.with_extension("part");
+    let response = reqwest::get(&model.url)
+        .await
+        .and_then(|response| response.error_for_status())
+        .map_err(|e| RuntimeError::ExecutionError(format!("Model download failed: {}", e)))?;
+
+    tracing::info!("Downloading {} from {}", model.name, model.url);
+    let mut file = tokio::fs::File::create(&partial).await?;
+    let mut hasher = Sha256::new();
+    let mut body = response.bytes_stream();
+    while let Some(bytes) = body.next().await {
+        let bytes = bytes
+            .map_err(|e| RuntimeError::ExecutionError(format!("Model download failed: {}", e)))?;
+        hasher.update(&bytes);
+        file.write_all(&bytes).await?;
+    }
+    file.flush().await?;
+
+    let digest = hex::encode(hasher.finalize());
+    if digest != model.sha256.to_lowercase() {
+        tokio::fs::remove_file(&partial).await?;
+        return Err(RuntimeError::CryptoError(format!(
+            "{} checksum mismatch: expected {}, got {}",
+            model.name, model.sha256, digest
+        )));
+    }
+    tokio::fs::rename(&partial, &path).await?;
+    Ok(path)
+}
+
+async fn file_sha256(path: &Path) -> Result<String, RuntimeError> {
+    let path = path.to_path_buf();
+    tokio::task::spawn_blocking(move || {
+        let mut hasher = Sha256::new();
+        std::io::copy(&mut std::fs::File::open(path)?, &mut hasher)?;
+        Ok::<_, RuntimeError>(hex::encode(hasher.finalize()))
+    })
+    .await
+    .map_err(|e| RuntimeError::ExecutionError(format!("Model verification failed: {}", e)))?
+}
+
+/// Trim `request` to fit a `context_size` window
+///
+/// `count_tokens` measures the prompt the request renders to. The oldest
+/// messages are dropped first and the latest one is always kept; the
+/// answer budget is capped at half the window.
+pub fn fit_context(
+    mut request: CompletionRequest,
+    context_size: u32,
+    count_tokens: impl Fn(&CompletionRequest) -> usize,
+) -> Result<CompletionRequest, RuntimeError> {
+    request.max_tokens = request.max_tokens.min(context_size / 2);
+    let budget = (context_size - request.max_tokens) as usize;
+
+    while count_tokens(&request) > budget {
+        if request.messages.len() <= 1 {
+            return Err(RuntimeError::ExecutionError(format!(
+                "Prompt does not fit the {} token context",
+                context_size
+            )));
+        }
+        request.messages.remove(0);
+    }
+    Ok(request)
+}
+
+#[cfg(feature = "llama")]
+pub use provider::LlamaCppProvider;
+
+#[cfg(feature = "llama")]
+mod provider {
+    use super::{ensure_model, fit_context, LlamaCppConfig};
+    use crate::ai::{
+        AIProvider, CompletionChunk, CompletionRequest, CompletionResponse, CompletionStream,
+    };
+    use crate::error::RuntimeError;
+    use async_trait::async_trait;
+    use llama_cpp_2::context::params::LlamaContextParams;
+    use llama_cpp_2::llama_backend::LlamaBackend;
+    use llama_cpp_2::llama_batch::LlamaBatch;
+    use llama_cpp_2::model::params::LlamaModelParams;
+    use llama_cpp_2::model::{AddBos, LlamaChatMessage, LlamaModel, Special};
+    use llama_cpp_2::sampling::LlamaSampler;
+    use std::num::NonZeroU32;
+    use std::sync::{Arc, OnceLock};
+    use std::time::Instant;
+    use tokio::sync::{mpsc, OwnedSemaphorePermit, Semaphore};
+
+    /// llama.cpp can only be initialized once per process
+    static BACKEND: OnceLock<Result<LlamaBackend, String>> = OnceLock::new();
+
+    fn backend() -> Result<&'static LlamaBackend, RuntimeError> {
+        BACKEND
+            .get_or_init(|| LlamaBackend::init().map_err(|e| e.to_string()))
+            .as_ref()
+            .map_err(llama_error)
+    }
+
+    fn llama_error(e: impl std::fmt::Display) -> RuntimeError {
+        RuntimeError::ExecutionError(format!("llama.cpp: {}", e))
+    }
+
+    /// GGUF model running in-process through llama.cpp
+    pub struct LlamaCppProvider {
+        config: LlamaCppConfig,
+        model: Arc<LlamaModel>,
+        slots: Arc<Semaphore>,
+    }
+
+    impl LlamaCppProvider {
+        /// Download (if needed), verify and load the configured model
+        pub async fn load(config: LlamaCppConfig) -> Result<Self, RuntimeError> {
+            let path = ensure_model(&config.model, &config.models_dir).await?;
+            let gpu_layers = config.gpu_layers;
+            let model = tokio::task::spawn_blocking(move || {
+                let params = LlamaModelParams::default().with_n_gpu_layers(gpu_layers);
+                LlamaModel::load_from_file(backend()?, path, &params).map_err(llama_error)
+            })
+            .await
+            .map_err(llama_error)??;
+
+            Ok(Self {
+                slots: Arc::new(Semaphore::new(config.max_concurrent.max(1))),
+                config,
+                model: Arc::new(model),
+            })
+        }
+
+        fn acquire(&self) -> Result<OwnedSemaphorePermit, RuntimeError> {
+            self.slots.clone().try_acquire_owned().map_err(|_| {
+                RuntimeError::Overloaded(format!("{} is busy", self.config.model.name))
+            })
+        }
+
+        /// Generate on a blocking thread, sending each piece to `tx`
+        fn generate(
+            &self,
+            request: CompletionRequest,
+            permit: OwnedSemaphorePermit,
+            tx: mpsc::UnboundedSender<Result<CompletionChunk, RuntimeError>>,
+        ) -> tokio::task::JoinHandle<Result<u32, RuntimeError>> {
+            let model = self.model.clone();
+            let context_size = self.config.context_size;
+            let threads = self.config.threads;
+            tokio::task::spawn_blocking(move || {
+                let _permit = permit;
+                let result = run(&model, request, context_size, threads, &tx);
+                if let Err(e) = &result {
+                    let _ = tx.send(Err(RuntimeError::ExecutionError(e.to_string())));
+                }
+                result
+            })
+        }
+    }
+
+    fn render(model: &LlamaModel, request: &CompletionRequest) -> Result<String, RuntimeError> {
+        let mut messages =
+            vec![
+                LlamaChatMessage::new("system".to_string(), request.system_prompt.clone())
+                    .map_err(llama_error)?,
+            ];
+        for message in &request.messages {
+            messages.push(
+                LlamaChatMessage::new(message.role.clone(), message.content.clone())
+                    .map_err(llama_error)?,
+            );
+        }
+        let template = model.chat_template(None).map_err(llama_error)?;
+        model
+            .apply_chat_template(&template, &messages, true)
+            .map_err(llama_error)
+    }
+
+    fn run(
+        model: &LlamaModel,
+        request: CompletionRequest,
+        context_size: u32,
+        threads: Option<u32>,
+        tx: &mpsc::UnboundedSender<Result<CompletionChunk, RuntimeError>>,
+    ) -> Result<u32, RuntimeError> {
+        let request = fit_context(request, context_size, |request| {
+            render(model, request)
+                .and_then(|prompt| {
+                    model
+                        .str_to_token(&prompt, AddBos::Always)
+                        .map_err(llama_error)
+                })
+                .map(|tokens| tokens.len())
+                .unwrap_or(usize::MAX)
+        })?;
+        let prompt = render(model, &request)?;
+        let tokens = model
+            .str_to_token(&prompt, AddBos::Always)
+            .map_err(llama_error)?;
+
+        let mut params = LlamaContextParams::default().with_n_ctx(NonZeroU32::new(context_size));
+        if let Some(threads) = threads {
+            params = params.with_n_threads(threads as i32);
+        }
+        let mut ctx = model.new_context(backend()?, params).map_err(llama_error)?;
+
+        let mut batch = LlamaBatch::new(context_size as usize, 1);
+        let last = tokens.len() as i32 - 1;
+        for (position, token) in (0_i32..).zip(&tokens) {
+            batch
+                .add(*token, position, &[0], position == last)
+                .map_err(llama_error)?;
+        }
+        ctx.decode(&mut batch).map_err(llama_error)?;
+
+        let mut sampler = LlamaSampler::chain_simple([
+            LlamaSampler::temp(request.temperature),
+            LlamaSampler::dist(chrono::Utc::now().timestamp_subsec_nanos()),
+        ]);
+        let mut position = batch.n_tokens();
+        let mut generated = 0;
+        while generated < request.max_tokens {
+            let token = sampler.sample(&ctx, batch.n_tokens() - 1);
+            sampler.accept(token);
+            if model.is_eog_token(token) {
+                break;
+            }
+            let piece = model
+                .token_to_str(token, Special::Tokenize)
+                .map_err(llama_error)?;
+            if tx
+                .send(Ok(CompletionChunk {
+                    delta: piece,
+                    done: false,
+                }))
+                .is_err()
+            {
+                // Receiver dropped: nobody wants the rest
+                break;
+            }
+
+            batch.clear();
+            batch
+                .add(token, position, &[0], true)
+                .map_err(llama_error)?;
+            position += 1;
+            generated += 1;
+            ctx.decode(&mut batch).map_err(llama_error)?;
+        }
+        let _ = tx.send(Ok(CompletionChunk {
+            delta: String::new(),
+            done: true,
+        }));
+        Ok(tokens.len() as u32 + generated)
+    }
+
+    #[async_trait]
+    impl AIProvider for LlamaCppProvider {
+        async fn complete(
+            &self,
+            request: CompletionRequest,
+        ) -> Result<CompletionResponse, RuntimeError> {
+            let start = Instant::now();
+            let permit = self.acquire()?;
+            let (tx, mut rx) = mpsc::unbounded_channel();
+            let handle = self.generate(request, permit, tx);
+
+            let mut content = String::new();
+            while let Some(chunk) = rx.recv().await {
+                content.push_str(&chunk?.delta);
+            }
+            let tokens_used = handle.await.map_err(llama_error)??;
+
+            Ok(CompletionResponse {
+                content,
+                tokens_used,
+                model: self.config.model.name.clone(),
+                latency_ms: start.elapsed().as_millis() as u64,
+            })
+        }
+
+        async fn complete_stream(
+            &self,
+            request: CompletionRequest,
+        ) -> Result<CompletionStream, RuntimeError> {
+            let permit = self.acquire()?;
+            let (tx, rx) = mpsc::unbounded_channel();
+            self.generate(request, permit, tx);
+            Ok(Box::pin(futures::stream::unfold(rx, |mut rx| async {
+                rx.recv().await.map(|chunk| (chunk, rx))
+            })))
+        }
+
+        async fn is_available(&self) -> bool {
+            self.slots.available_permits() > 0
+        }
+
+        fn name(&self) -> &str {
+            "llama.cpp"
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai::ChatMessage;
+
+    fn request(turns: usize) -> CompletionRequest {
+        CompletionRequest {
+            system_prompt: "sys".to_string(),
+            messages: (0..turns)
+                .map(|i| ChatMessage {
+                    role: "user".to_string(),
+                    content: format!("message {}", i),
+                })
+                .collect(),
+            temperature: 0.7,
+            max_tokens: 1024,
+        }
+    }
+
+    /// Ten tokens per message plus the system prompt
+    fn count(request: &CompletionRequest) -> usize {
+        10 + request.messages.len() * 10
+    }
+
+    #[test]
+    fn test_fit_context() {
+        let fitted = fit_context(request(10), 100, count).unwrap();
+        assert_eq!(fitted.max_tokens, 50);
+        assert_eq!(fitted.messages.len(), 4);
+        assert_eq!(fitted.messages[0].content, "message 6");
+
+        let untouched = fit_context(request(2), 4096, count).unwrap();
+        assert_eq!(untouched.messages.len(), 2);
+        assert_eq!(untouched.max_tokens, 1024);
+
+        assert!(fit_context(request(1), 20, count).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_ensure_model_verifies_checksum() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("tiny.gguf"), b"gguf").unwrap();
+        let model = GgufModel {
+            name: "tiny".to_string(),
+            // Never fetched while the local file verifies
+            url: "http://127.0.0.1:9/tiny.gguf".to_string(),
+            sha256: hex::encode(Sha256::digest(b"gguf")),
+            file_name: "tiny.gguf".to_string(),
+        };
+        assert_eq!(
+            ensure_model(&model, dir.path()).await.unwrap(),
+            dir.path().join("tiny.gguf")
+        );
+
+        // A corrupt file is downloaded again, which fails here
+        std::fs::write(dir.path().join("tiny.gguf"), b"gguf?").unwrap();
+        assert!(ensure_model(&model, dir.path()).await.is_err());
+    }
+}
@@ -1,7 +1,8 @@
 //! AI Model Integration
 //!
 //! OpenClaw-style AI model integration supporting:
-//! - Local LLMs (via HTTP API to llama.cpp/ollama)
+//! - Local LLMs (via HTTP API to llama.cpp/ollama, or in-process GGUF
+//!   models with the `llama` feature)
 //! - Cloud providers (OpenAI, Anthropic)
 //! - Hybrid selection based on task complexity
 //!
@@ -19,11 +20,13 @@
 //! Original Author: Kazé A. ONGUENE - Braincities Lab
 
 mod alteros;
+mod local;
 mod model;
 mod prompt;
 mod provider;
 
 pub use alteros::*;
+pub use local::*;
 pub use model::*;
 pub use prompt::*;
 pub use provider::*;
@@ -83,6 +86,16 @@ pub struct AIModelConfig {
 
     /// AlterOS: Enable cost optimization
     pub alteros_cost_optimization: bool,
+
+    /// Embedding model for semantic memory; `None` uses the local chat
+    /// model, or OpenAI's default embedding model when only cloud is set up
+    #[serde(default)]
+    pub embedding_model: Option<String>,
+
+    /// Run a GGUF model in-process instead of calling `local_endpoint`
+    /// (requires the `llama` feature)
+    #[serde(default)]
+    pub llama_cpp: Option<LlamaCppConfig>,
 }
 
 impl Default for AIModelConfig {
@@ -105,6 +118,8 @@ impl Default for AIModelConfig {
             alteros_anthropic_for_code: true,
             alteros_local_for_simple: true,
             alteros_cost_optimization: true,
+            embedding_model: None,
+            llama_cpp: None,
         }
     }
 }
@@ -242,6 +257,20 @@ pub struct CompletionResponse {
     pub latency_ms: u64,
 }
 
+/// Piece of a streamed completion
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CompletionChunk {
+    /// Text generated since the previous chunk
+    pub delta: String,
+
+    /// Set on the last chunk
+    pub done: bool,
+}
+
+/// Stream of completion chunks, ending with a `done` chunk
+pub type CompletionStream =
+    std::pin::Pin<Box<dyn futures::Stream<Item = Result<CompletionChunk, RuntimeError>> + Send>>;
+
 /// Parsed intent with AI enhancement
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct AIIntent {
@@ -4,11 +4,12 @@
 
 use super::{
     provider::{AIProvider, AnthropicProvider, OllamaProvider, OpenAIProvider},
-    AIIntent, AIModelConfig, ChatMessage, CompletionRequest, CompletionResponse, ModelStrategy,
-    TaskComplexity,
+    AIIntent, AIModelConfig, ChatMessage, CompletionChunk, CompletionRequest, CompletionResponse,
+    CompletionStream, ModelStrategy, TaskComplexity,
 };
 use crate::error::RuntimeError;
 use crate::intent::{Entity, EntityType, Intent, IntentType};
+use crate::memory::Embedder;
 use std::collections::HashMap;
 use std::sync::Arc;
 
@@ -39,7 +40,11 @@ impl AIModelManager {
         // Initialize local provider
         let local_provider: Option<Arc<dyn AIProvider>> =
             if let (Some(endpoint), Some(model)) = (&config.local_endpoint, &config.local_model) {
-                Some(Arc::new(OllamaProvider::new(endpoint, model)))
+                let provider = OllamaProvider::new(endpoint, model);
+                Some(Arc::new(match &config.embedding_model {
+                    Some(embedding_model) => provider.with_embedding_model(embedding_model),
+                    None => provider,
+                }))
             } else {
                 None
             };
@@ -47,7 +52,11 @@ impl AIModelManager {
         // Initialize cloud provider (prefer OpenAI, fallback to Anthropic)
         let cloud_provider: Option<Arc<dyn AIProvider>> =
             if let Some(api_key) = &config.openai_api_key {
-                Some(Arc::new(OpenAIProvider::new(api_key, &config.openai_model)))
+                let provider = OpenAIProvider::new(api_key, &config.openai_model);
+                Some(Arc::new(match &config.embedding_model {
+                    Some(embedding_model) => provider.with_embedding_model(embedding_model),
+                    None => provider,
+                }))
             } else if let Some(api_key) = &config.anthropic_api_key {
                 Some(Arc::new(AnthropicProvider::new(
                     api_key,
@@ -87,18 +96,116 @@ impl AIModelManager {
         // Select provider based on strategy
         let provider = self.select_provider(&complexity).await?;
 
-        // Make request with timeout
-        let response = tokio::time::timeout(
+        // Make request with timeout, moving to the cloud if the local
+        // model is at capacity
+        let response = match self.complete_with(&provider, request.clone()).await {
+            Err(RuntimeError::Overloaded(reason)) => {
+                let cloud = self.overload_fallback(reason)?;
+                self.complete_with(&cloud, request).await
+            }
+            result => result,
+        }?;
+
+        // Cache response
+        self.cache_response(&cache_key, &response);
+
+        Ok(response)
+    }
+
+    /// Stream a completion as the model generates it
+    ///
+    /// The timeout covers the request up to the first byte of the answer;
+    /// after that the stream runs until the provider finishes. Cached
+    /// answers are replayed as a single chunk, streamed ones are not cached.
+    pub async fn complete_stream(
+        &self,
+        request: CompletionRequest,
+    ) -> Result<CompletionStream, RuntimeError> {
+        let cache_key = self.compute_cache_key(&request);
+        if let Some(cached) = self.get_cached(&cache_key) {
+            return Ok(Box::pin(futures::stream::iter([Ok(CompletionChunk {
+                delta: cached.content,
+                done: true,
+            })])));
+        }
+
+        let last_message = request
+            .messages
+            .last()
+            .map(|m| m.content.as_str())
+            .unwrap_or("");
+        let complexity = TaskComplexity::classify(last_message);
+        let provider = self.select_provider(&complexity).await?;
+
+        match self.stream_with(&provider, request.clone()).await {
+            Err(RuntimeError::Overloaded(reason)) => {
+                let cloud = self.overload_fallback(reason)?;
+                self.stream_with(&cloud, request).await
+            }
+            result => result,
+        }
+    }
+
+    /// Load the in-process model from [`AIModelConfig::llama_cpp`] as the
+    /// local provider, downloading and verifying it if needed
+    pub async fn load_local_model(&mut self) -> Result<(), RuntimeError> {
+        let Some(config) = self.config.llama_cpp.clone() else {
+            return Ok(());
+        };
+
+        #[cfg(feature = "llama")]
+        {
+            self.local_provider = Some(Arc::new(super::LlamaCppProvider::load(config).await?));
+            Ok(())
+        }
+        #[cfg(not(feature = "llama"))]
+        {
+            Err(RuntimeError::ConfigError(format!(
+                "{} needs the runtime built with the `llama` feature",
+                config.model.name
+            )))
+        }
+    }
+
+    async fn complete_with(
+        &self,
+        provider: &Arc<dyn AIProvider>,
+        request: CompletionRequest,
+    ) -> Result<CompletionResponse, RuntimeError> {
+        tokio::time::timeout(
             std::time::Duration::from_secs(self.config.timeout_secs),
             provider.complete(request),
         )
         .await
-        .map_err(|_| RuntimeError::Timeout("AI model request timed out".to_string()))??;
+        .map_err(|_| RuntimeError::Timeout("AI model request timed out".to_string()))?
+    }
 
-        // Cache response
-        self.cache_response(&cache_key, &response);
+    async fn stream_with(
+        &self,
+        provider: &Arc<dyn AIProvider>,
+        request: CompletionRequest,
+    ) -> Result<CompletionStream, RuntimeError> {
+        tokio::time::timeout(
+            std::time::Duration::from_secs(self.config.timeout_secs),
+            provider.complete_stream(request),
+        )
+        .await
+        .map_err(|_| RuntimeError::Timeout("AI model request timed out".to_string()))?
+    }
 
-        Ok(response)
+    /// Cloud provider to use when the local model is overloaded
+    fn overload_fallback(&self, reason: String) -> Result<Arc<dyn AIProvider>, RuntimeError> {
+        match &self.cloud_provider {
+            Some(cloud) if self.config.strategy != ModelStrategy::LocalOnly => {
+                tracing::info!(
+                    "Local model overloaded ({}), using {}",
+                    reason,
+                    cloud.name()
+                );
+                Ok(cloud.clone())
+            }
+            _ => Err(RuntimeError::Overloaded(reason)),
+        }
     }
 
     /// Parse intent from user message
@@ -157,6 +264,45 @@ impl AIModelManager {
         Ok(response.content)
     }
 
+    /// Streaming variant of [`Self::generate_response`]
+    pub async fn generate_response_stream(
+        &self,
+        message: &str,
+        context: &[ChatMessage],
+        user_info: &str,
+    ) -> Result<CompletionStream, RuntimeError> {
+        let system_prompt = super::prompt::build_system_prompt(user_info);
+
+        let mut messages = context.to_vec();
+        messages.push(ChatMessage {
+            role: "user".to_string(),
+            content: message.to_string(),
+        });
+
+        let request = CompletionRequest {
+            system_prompt,
+            messages,
+            temperature: self.config.temperature,
+            max_tokens: self.config.max_tokens,
+        };
+
+        self.complete_stream(request).await
+    }
+
+    /// Provider used for embeddings
+    ///
+    /// Unlike completions this never falls back to another provider:
+    /// vectors from different models are not comparable, so stored memories
+    /// would stop matching.
+    fn embedding_provider(&self) -> Result<Arc<dyn AIProvider>, RuntimeError> {
+        [&self.local_provider, &self.cloud_provider]
+            .into_iter()
+            .flatten()
+            .find(|p| p.embedding_model().is_some())
+            .cloned()
+            .ok_or_else(|| RuntimeError::ConfigError("No embedding model configured".to_string()))
+    }
+
     /// Select provider based on complexity and strategy
     async fn select_provider(
         &self,
@@ -383,6 +529,29 @@ struct IntentParseResult {
     risks: Vec<String>,
 }
 
+#[async_trait::async_trait]
+impl Embedder for AIModelManager {
+    async fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>, RuntimeError> {
+        let provider = self.embedding_provider()?;
+        tokio::time::timeout(
+            std::time::Duration::from_secs(self.config.timeout_secs),
+            provider.embed(texts),
+        )
+        .await
+        .map_err(|_| RuntimeError::Timeout("Embedding request timed out".to_string()))?
+    }
+
+    fn model_id(&self) -> String {
+        self.embedding_provider()
+            .ok()
+            .and_then(|p| {
+                p.embedding_model()
+                    .map(|model| format!("{}/{}", p.name(), model))
+            })
+            .unwrap_or_default()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -420,4 +589,81 @@ mod tests {
 
         assert_eq!(key1, key2);
     }
+
+    #[test]
+    fn test_embedding_model_selection() {
+        let manager = AIModelManager::new(AIModelConfig::default());
+        assert_eq!(manager.model_id(), "ollama/llama3:8b");
+
+        let manager = AIModelManager::new(AIModelConfig {
+            embedding_model: Some("nomic-embed-text".to_string()),
+            ..AIModelConfig::default()
+        });
+        assert_eq!(manager.model_id(), "ollama/nomic-embed-text");
+
+        // Anthropic has no embedding API
+        let manager = AIModelManager::new(AIModelConfig {
+            local_endpoint: None,
+            anthropic_api_key: Some("key".to_string()),
+            ..AIModelConfig::default()
+        });
+        assert!(manager.embedding_provider().is_err());
+        assert_eq!(manager.model_id(), "");
+    }
+
+    struct Fixed(Result<&'static str, &'static str>);
+
+    #[async_trait::async_trait]
+    impl AIProvider for Fixed {
+        async fn complete(
+            &self,
+            _request: CompletionRequest,
+        ) -> Result<CompletionResponse, RuntimeError> {
+            match self.0 {
+                Ok(content) => Ok(CompletionResponse {
+                    content: content.to_string(),
+                    tokens_used: 1,
+                    model: content.to_string(),
+                    latency_ms: 0,
+                }),
+                Err(reason) => Err(RuntimeError::Overloaded(reason.to_string())),
+            }
+        }
+
+        async fn is_available(&self) -> bool {
+            true
+        }
+
+        fn name(&self) -> &str {
+            "fixed"
+        }
+    }
+
+    #[tokio::test]
+    async fn test_overloaded_local_falls_back_to_cloud() {
+        let request = |content: &str| CompletionRequest {
+            system_prompt: "test".to_string(),
+            messages: vec![ChatMessage {
+                role: "user".to_string(),
+                content: content.to_string(),
+            }],
+            temperature: 0.7,
+            max_tokens: 100,
+        };
+        let mut manager = AIModelManager::new(AIModelConfig {
+            strategy: ModelStrategy::LocalFirst,
+            ..AIModelConfig::default()
+        });
+        manager.local_provider = Some(Arc::new(Fixed(Err("busy"))));
+        manager.cloud_provider = Some(Arc::new(Fixed(Ok("cloud"))));
+
+        let response = manager.complete(request("hi")).await.unwrap();
+        assert_eq!(response.content, "cloud");
+
+        manager.config.strategy = ModelStrategy::LocalOnly;
+        assert!(matches!(
+            manager.complete(request("hello")).await,
+            Err(RuntimeError::Overloaded(_))
+        ));
+    }
 }
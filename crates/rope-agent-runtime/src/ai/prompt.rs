@@ -2,6 +2,8 @@
 //!
 //! Carefully crafted prompts for different AI tasks
 
+use crate::memory::RecalledMemory;
+
 /// System prompt for intent parsing
 pub const INTENT_PARSER_PROMPT: &str = r#"You are an intent parser for RopeAgent, a secure blockchain AI assistant.
 
@@ -96,6 +98,20 @@ Remember: You are the user's trusted assistant. Security and accuracy are paramo
     )
 }
 
+/// System prompt with memories recalled for the current message
+pub fn build_system_prompt_with_memories(user_info: &str, memories: &[RecalledMemory]) -> String {
+    let mut prompt = build_system_prompt(user_info);
+    if !memories.is_empty() {
+        prompt.push_str(
+            "\n\n## Relevant Memories\nThings the user told you earlier. Use them only when they help with the current message.\n",
+        );
+        for memory in memories {
+            prompt.push_str(&format!("- {}\n", memory.text));
+        }
+    }
+    prompt
+}
+
 /// Prompt for generating transaction confirmations
 pub const TRANSACTION_CONFIRMATION_PROMPT: &str = r#"Generate a clear confirmation message for a transaction.
 
@@ -131,6 +147,20 @@ Respond with:
   "explanation": "brief explanation"
 }"#;
 
+/// Prompt for an agent deliberating on a sensitive action
+pub const DELIBERATION_PROMPT: &str = r#"You are one of several independent agents reviewing a sensitive action before it is submitted for AI Testimony.
+
+Assess the action on its own merits and according to your policy. Other agents review it separately; do not assume they will catch problems you see.
+
+Reject if the action looks like fraud, phishing, a mistake, or breaks your policy. Abstain if you cannot judge it.
+
+Respond with:
+{
+  "verdict": "approve|reject|abstain",
+  "confidence": 0.0-1.0,
+  "reasoning": "brief explanation"
+}"#;
+
 /// Prompt for skill invocation
 pub const SKILL_INVOCATION_PROMPT: &str = r#"You are invoking a skill on behalf of the user.
 
@@ -165,4 +195,24 @@ mod tests {
         assert!(prompt.contains("271828"));
         assert!(prompt.contains("did:datachain:abc123"));
     }
+
+    #[test]
+    fn test_system_prompt_with_memories() {
+        let plain = build_system_prompt("DID: did:datachain:abc123");
+        assert_eq!(
+            build_system_prompt_with_memories("DID: did:datachain:abc123", &[]),
+            plain
+        );
+
+        let memories = [RecalledMemory {
+            id: "m1".to_string(),
+            text: "prefers answers in French".to_string(),
+            timestamp: 0,
+            score: 0.9,
+        }];
+        let prompt = build_system_prompt_with_memories("DID: did:datachain:abc123", &memories);
+        assert!(prompt.starts_with(&plain));
+        assert!(prompt.contains("## Relevant Memories\n"));
+        assert!(prompt.contains("- prefers answers in French\n"));
+    }
 }
@@ -2,10 +2,15 @@
 //!
 //! Supports local models (Ollama, llama.cpp) and cloud (OpenAI, Anthropic)
 
-use super::{AIModelConfig, ChatMessage, CompletionRequest, CompletionResponse, ModelStrategy};
+use super::{
+    AIModelConfig, ChatMessage, CompletionChunk, CompletionRequest, CompletionResponse,
+    CompletionStream, ModelStrategy,
+};
 use crate::error::RuntimeError;
 use async_trait::async_trait;
+use futures::StreamExt;
 use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
 use std::time::Instant;
 
 /// AI Provider trait
@@ -17,17 +22,48 @@ pub trait AIProvider: Send + Sync {
         request: CompletionRequest,
     ) -> Result<CompletionResponse, RuntimeError>;
 
+    /// Stream a completion as it is generated
+    ///
+    /// Providers without token streaming yield the whole answer as one chunk.
+    async fn complete_stream(
+        &self,
+        request: CompletionRequest,
+    ) -> Result<CompletionStream, RuntimeError> {
+        let response = self.complete(request).await?;
+        Ok(Box::pin(futures::stream::iter([Ok(CompletionChunk {
+            delta: response.content,
+            done: true,
+        })])))
+    }
+
     /// Check if provider is available
     async fn is_available(&self) -> bool;
 
+    /// Embed `texts` into vectors, one per text, in order
+    async fn embed(&self, _texts: &[String]) -> Result<Vec<Vec<f32>>, RuntimeError> {
+        Err(RuntimeError::ConfigError(format!(
+            "{} does not provide embeddings",
+            self.name()
+        )))
+    }
+
+    /// Model used by [`Self::embed`], if the provider supports embeddings
+    fn embedding_model(&self) -> Option<&str> {
+        None
+    }
+
     /// Get provider name
     fn name(&self) -> &str;
 }
 
+/// Default OpenAI embedding model
+pub const OPENAI_EMBEDDING_MODEL: &str = "text-embedding-3-small";
+
 /// Ollama (local) provider
 pub struct OllamaProvider {
     endpoint: String,
     model: String,
+    embedding_model: String,
     client: reqwest::Client,
 }
 
@@ -36,9 +72,51 @@ impl OllamaProvider {
         Self {
             endpoint: endpoint.to_string(),
             model: model.to_string(),
+            embedding_model: model.to_string(),
             client: reqwest::Client::new(),
         }
     }
+
+    /// Use a dedicated embedding model instead of the chat model
+    pub fn with_embedding_model(mut self, model: &str) -> Self {
+        self.embedding_model = model.to_string();
+        self
+    }
+
+    async fn send(
+        &self,
+        request: CompletionRequest,
+        stream: bool,
+    ) -> Result<reqwest::Response, RuntimeError> {
+        let mut messages = vec![OllamaMessage {
+            role: "system".to_string(),
+            content: request.system_prompt,
+        }];
+
+        for msg in request.messages {
+            messages.push(OllamaMessage {
+                role: msg.role,
+                content: msg.content,
+            });
+        }
+
+        let ollama_request = OllamaRequest {
+            model: self.model.clone(),
+            messages,
+            stream,
+            options: OllamaOptions {
+                temperature: request.temperature,
+                num_predict: request.max_tokens,
+            },
+        };
+
+        self.client
+            .post(format!("{}/api/chat", self.endpoint))
+            .json(&ollama_request)
+            .send()
+            .await
+            .map_err(|e| RuntimeError::ExecutionError(format!("Ollama request failed: {}", e)))
+    }
 }
 
 #[derive(Serialize)]
@@ -73,6 +151,26 @@ struct OllamaResponseMessage {
     content: String,
 }
 
+#[derive(Serialize)]
+struct EmbeddingRequest<'a> {
+    model: &'a str,
+    input: &'a [String],
+}
+
+#[derive(Deserialize)]
+struct OllamaEmbeddingResponse {
+    embeddings: Vec<Vec<f32>>,
+}
+
+/// One line of a streamed Ollama response
+#[derive(Deserialize)]
+struct OllamaStreamLine {
+    message: Option<OllamaResponseMessage>,
+    #[serde(default)]
+    done: bool,
+    error: Option<String>,
+}
+
 #[async_trait]
 impl AIProvider for OllamaProvider {
     async fn complete(
@@ -81,35 +179,7 @@ impl AIProvider for OllamaProvider {
     ) -> Result<CompletionResponse, RuntimeError> {
         let start = Instant::now();
 
-        let mut messages = vec![OllamaMessage {
-            role: "system".to_string(),
-            content: request.system_prompt,
-        }];
-
-        for msg in request.messages {
-            messages.push(OllamaMessage {
-                role: msg.role,
-                content: msg.content,
-            });
-        }
-
-        let ollama_request = OllamaRequest {
-            model: self.model.clone(),
-            messages,
-            stream: false,
-            options: OllamaOptions {
-                temperature: request.temperature,
-                num_predict: request.max_tokens,
-            },
-        };
-
-        let response = self
-            .client
-            .post(format!("{}/api/chat", self.endpoint))
-            .json(&ollama_request)
-            .send()
-            .await
-            .map_err(|e| RuntimeError::ExecutionError(format!("Ollama request failed: {}", e)))?;
+        let response = self.send(request, false).await?;
 
         let ollama_response: OllamaResponse = response
             .json()
@@ -124,6 +194,14 @@ impl AIProvider for OllamaProvider {
         })
     }
 
+    async fn complete_stream(
+        &self,
+        request: CompletionRequest,
+    ) -> Result<CompletionStream, RuntimeError> {
+        let response = self.send(request, true).await?;
+        Ok(line_stream(response, parse_ollama_line))
+    }
+
     async fn is_available(&self) -> bool {
         self.client
             .get(format!("{}/api/tags", self.endpoint))
@@ -133,6 +211,30 @@ impl AIProvider for OllamaProvider {
             .unwrap_or(false)
     }
 
+    async fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>, RuntimeError> {
+        let response = self
+            .client
+            .post(format!("{}/api/embed", self.endpoint))
+            .json(&EmbeddingRequest {
+                model: &self.embedding_model,
+                input: texts,
+            })
+            .send()
+            .await
+            .map_err(|e| RuntimeError::ExecutionError(format!("Ollama request failed: {}", e)))?;
+
+        let embeddings: OllamaEmbeddingResponse = response
+            .json()
+            .await
+            .map_err(|e| RuntimeError::ExecutionError(format!("Ollama parse failed: {}", e)))?;
+
+        check_embedding_count(texts, embeddings.embeddings)
+    }
+
+    fn embedding_model(&self) -> Option<&str> {
+        Some(&self.embedding_model)
+    }
+
     fn name(&self) -> &str {
         "ollama"
     }
@@ -142,6 +244,7 @@ impl AIProvider for OllamaProvider {
 pub struct OpenAIProvider {
     api_key: String,
     model: String,
+    embedding_model: String,
     client: reqwest::Client,
 }
 
@@ -150,9 +253,61 @@ impl OpenAIProvider {
         Self {
             api_key: api_key.to_string(),
             model: model.to_string(),
+            embedding_model: OPENAI_EMBEDDING_MODEL.to_string(),
             client: reqwest::Client::new(),
         }
     }
+
+    /// Use another embedding model than [`OPENAI_EMBEDDING_MODEL`]
+    pub fn with_embedding_model(mut self, model: &str) -> Self {
+        self.embedding_model = model.to_string();
+        self
+    }
+
+    async fn send(
+        &self,
+        request: CompletionRequest,
+        stream: bool,
+    ) -> Result<reqwest::Response, RuntimeError> {
+        let mut messages = vec![OpenAIMessage {
+            role: "system".to_string(),
+            content: request.system_prompt,
+        }];
+
+        for msg in request.messages {
+            messages.push(OpenAIMessage {
+                role: msg.role,
+                content: msg.content,
+            });
+        }
+
+        let openai_request = OpenAIRequest {
+            model: self.model.clone(),
+            messages,
+            temperature: request.temperature,
+            max_tokens: request.max_tokens,
+            stream,
+        };
+
+        let response = self
+            .client
+            .post("https://api.openai.com/v1/chat/completions")
+            .header("Authorization", format!("Bearer {}", self.api_key))
+            .json(&openai_request)
+            .send()
+            .await
+            .map_err(|e| RuntimeError::ExecutionError(format!("OpenAI request failed: {}", e)))?;
+
+        if !response.status().is_success() {
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(RuntimeError::ExecutionError(format!(
+                "OpenAI error: {}",
+                error_text
+            )));
+        }
+
+        Ok(response)
+    }
 }
 
 #[derive(Serialize)]
@@ -161,6 +316,8 @@ struct OpenAIRequest {
     messages: Vec<OpenAIMessage>,
     temperature: f32,
     max_tokens: u32,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    stream: bool,
 }
 
 #[derive(Serialize)]
@@ -185,6 +342,17 @@ struct OpenAIChoiceMessage {
     content: String,
 }
 
+#[derive(Deserialize)]
+struct OpenAIEmbeddingResponse {
+    data: Vec<OpenAIEmbedding>,
+}
+
+#[derive(Deserialize)]
+struct OpenAIEmbedding {
+    index: usize,
+    embedding: Vec<f32>,
+}
+
 #[derive(Deserialize)]
 struct OpenAIUsage {
     total_tokens: u32,
@@ -198,30 +366,48 @@ impl AIProvider for OpenAIProvider {
     ) -> Result<CompletionResponse, RuntimeError> {
         let start = Instant::now();
 
-        let mut messages = vec![OpenAIMessage {
-            role: "system".to_string(),
-            content: request.system_prompt,
-        }];
+        let response = self.send(request, false).await?;
 
-        for msg in request.messages {
-            messages.push(OpenAIMessage {
-                role: msg.role,
-                content: msg.content,
-            });
-        }
+        let openai_response: OpenAIResponse = response
+            .json()
+            .await
+            .map_err(|e| RuntimeError::ExecutionError(format!("OpenAI parse failed: {}", e)))?;
 
-        let openai_request = OpenAIRequest {
+        let content = openai_response
+            .choices
+            .first()
+            .map(|c| c.message.content.clone())
+            .unwrap_or_default();
+
+        Ok(CompletionResponse {
+            content,
+            tokens_used: openai_response.usage.total_tokens,
             model: self.model.clone(),
-            messages,
-            temperature: request.temperature,
-            max_tokens: request.max_tokens,
-        };
+            latency_ms: start.elapsed().as_millis() as u64,
+        })
+    }
 
+    async fn complete_stream(
+        &self,
+        request: CompletionRequest,
+    ) -> Result<CompletionStream, RuntimeError> {
+        let response = self.send(request, true).await?;
+        Ok(line_stream(response, parse_openai_line))
+    }
+
+    async fn is_available(&self) -> bool {
+        !self.api_key.is_empty()
+    }
+
+    async fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>, RuntimeError> {
         let response = self
             .client
-            .post("https://api.openai.com/v1/chat/completions")
+            .post("https://api.openai.com/v1/embeddings")
             .header("Authorization", format!("Bearer {}", self.api_key))
-            .json(&openai_request)
+            .json(&EmbeddingRequest {
+                model: &self.embedding_model,
+                input: texts,
+            })
             .send()
             .await
             .map_err(|e| RuntimeError::ExecutionError(format!("OpenAI request failed: {}", e)))?;
@@ -234,27 +420,20 @@ impl AIProvider for OpenAIProvider {
             )));
         }
 
-        let openai_response: OpenAIResponse = response
+        let mut embeddings: OpenAIEmbeddingResponse = response
             .json()
             .await
             .map_err(|e| RuntimeError::ExecutionError(format!("OpenAI parse failed: {}", e)))?;
+        embeddings.data.sort_by_key(|e| e.index);
 
-        let content = openai_response
-            .choices
-            .first()
-            .map(|c| c.message.content.clone())
-            .unwrap_or_default();
-
-        Ok(CompletionResponse {
-            content,
-            tokens_used: openai_response.usage.total_tokens,
-            model: self.model.clone(),
-            latency_ms: start.elapsed().as_millis() as u64,
-        })
+        check_embedding_count(
+            texts,
+            embeddings.data.into_iter().map(|e| e.embedding).collect(),
+        )
     }
 
-    async fn is_available(&self) -> bool {
-        !self.api_key.is_empty()
+    fn embedding_model(&self) -> Option<&str> {
+        Some(&self.embedding_model)
     }
 
     fn name(&self) -> &str {
@@ -277,6 +456,52 @@ impl AnthropicProvider {
             client: reqwest::Client::new(),
         }
     }
+
+    async fn send(
+        &self,
+        request: CompletionRequest,
+        stream: bool,
+    ) -> Result<reqwest::Response, RuntimeError> {
+        let messages: Vec<AnthropicMessage> = request
+            .messages
+            .into_iter()
+            .map(|m| AnthropicMessage {
+                role: m.role,
+                content: m.content,
+            })
+            .collect();
+
+        let anthropic_request = AnthropicRequest {
+            model: self.model.clone(),
+            max_tokens: request.max_tokens,
+            system: request.system_prompt,
+            messages,
+            stream,
+        };
+
+        let response = self
+            .client
+            .post("https://api.anthropic.com/v1/messages")
+            .header("x-api-key", &self.api_key)
+            .header("anthropic-version", "2023-06-01")
+            .header("content-type", "application/json")
+            .json(&anthropic_request)
+            .send()
+            .await
+            .map_err(|e| {
+                RuntimeError::ExecutionError(format!("Anthropic request failed: {}", e))
+            })?;
+
+        if !response.status().is_success() {
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(RuntimeError::ExecutionError(format!(
+                "Anthropic error: {}",
+                error_text
+            )));
+        }
+
+        Ok(response)
+    }
 }
 
 #[derive(Serialize)]
@@ -285,6 +510,8 @@ struct AnthropicRequest {
     max_tokens: u32,
     system: String,
     messages: Vec<AnthropicMessage>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    stream: bool,
 }
 
 #[derive(Serialize)]
@@ -318,42 +545,7 @@ impl AIProvider for AnthropicProvider {
     ) -> Result<CompletionResponse, RuntimeError> {
         let start = Instant::now();
 
-        let messages: Vec<AnthropicMessage> = request
-            .messages
-            .into_iter()
-            .map(|m| AnthropicMessage {
-                role: m.role,
-                content: m.content,
-            })
-            .collect();
-
-        let anthropic_request = AnthropicRequest {
-            model: self.model.clone(),
-            max_tokens: request.max_tokens,
-            system: request.system_prompt,
-            messages,
-        };
-
-        let response = self
-            .client
-            .post("https://api.anthropic.com/v1/messages")
-            .header("x-api-key", &self.api_key)
-            .header("anthropic-version", "2023-06-01")
-            .header("content-type", "application/json")
-            .json(&anthropic_request)
-            .send()
-            .await
-            .map_err(|e| {
-                RuntimeError::ExecutionError(format!("Anthropic request failed: {}", e))
-            })?;
-
-        if !response.status().is_success() {
-            let error_text = response.text().await.unwrap_or_default();
-            return Err(RuntimeError::ExecutionError(format!(
-                "Anthropic error: {}",
-                error_text
-            )));
-        }
+        let response = self.send(request, false).await?;
 
         let anthropic_response: AnthropicResponse = response
             .json()
@@ -375,6 +567,14 @@ impl AIProvider for AnthropicProvider {
         })
     }
 
+    async fn complete_stream(
+        &self,
+        request: CompletionRequest,
+    ) -> Result<CompletionStream, RuntimeError> {
+        let response = self.send(request, true).await?;
+        Ok(line_stream(response, parse_anthropic_line))
+    }
+
     async fn is_available(&self) -> bool {
         !self.api_key.is_empty()
     }
@@ -383,3 +583,282 @@ impl AIProvider for AnthropicProvider {
         "anthropic"
     }
 }
+
+/// Guard against a provider dropping inputs from an embedding batch
+fn check_embedding_count(
+    texts: &[String],
+    embeddings: Vec<Vec<f32>>,
+) -> Result<Vec<Vec<f32>>, RuntimeError> {
+    if embeddings.len() != texts.len() {
+        return Err(RuntimeError::ExecutionError(format!(
+            "Expected {} embeddings, got {}",
+            texts.len(),
+            embeddings.len()
+        )));
+    }
+    Ok(embeddings)
+}
+
+// === Streaming ===
+
+type LineParser = fn(&str) -> Option<Result<CompletionChunk, RuntimeError>>;
+
+/// Splits a byte stream into lines, across chunk boundaries
+#[derive(Default)]
+struct LineBuffer {
+    buf: Vec<u8>,
+}
+
+impl LineBuffer {
+    /// Append `data` and return the lines it completed
+    fn push(&mut self, data: &[u8]) -> Vec<String> {
+        self.buf.extend_from_slice(data);
+        let mut lines = Vec::new();
+        while let Some(pos) = self.buf.iter().position(|b| *b == b'\n') {
+            let line: Vec<u8> = self.buf.drain(..=pos).collect();
+            let line = String::from_utf8_lossy(&line);
+            lines.push(line.trim_end_matches(['\r', '\n']).to_string());
+        }
+        lines
+    }
+
+    /// Whatever is left after the stream ended
+    fn finish(&mut self) -> Option<String> {
+        let rest = String::from_utf8_lossy(&std::mem::take(&mut self.buf)).to_string();
+        (!rest.trim().is_empty()).then_some(rest)
+    }
+}
+
+/// Chunks carried by `lines`, in order, stopping after the first error
+/// or `done` chunk; appends a `done` chunk if the body had none
+fn chunks_from_lines(
+    lines: impl IntoIterator<Item = String>,
+    parse: LineParser,
+    queue: &mut VecDeque<Result<CompletionChunk, RuntimeError>>,
+) -> bool {
+    for line in lines {
+        if let Some(item) = parse(&line) {
+            let last = !matches!(&item, Ok(chunk) if !chunk.done);
+            queue.push_back(item);
+            if last {
+                return true;
+            }
+        }
+    }
+    false
+}
+
+/// Completion stream over a line-delimited HTTP body (NDJSON or SSE)
+fn line_stream(response: reqwest::Response, parse: LineParser) -> CompletionStream {
+    struct State {
+        body: std::pin::Pin<Box<dyn futures::Stream<Item = reqwest::Result<bytes::Bytes>> + Send>>,
+        lines: LineBuffer,
+        queue: VecDeque<Result<CompletionChunk, RuntimeError>>,
+        finished: bool,
+    }
+
+    let state = State {
+        body: Box::pin(response.bytes_stream()),
+        lines: LineBuffer::default(),
+        queue: VecDeque::new(),
+        finished: false,
+    };
+
+    Box::pin(futures::stream::unfold(state, |mut state| async move {
+        loop {
+            if let Some(item) = state.queue.pop_front() {
+                return Some((item, state));
+            }
+            if state.finished {
+                return None;
+            }
+            match state.body.next().await {
+                Some(Ok(data)) => {
+                    let lines = state.lines.push(&data);
+                    state.finished = chunks_from_lines(lines, parse, &mut state.queue);
+                }
+                Some(Err(e)) => {
+                    state.finished = true;
+                    state
+                        .queue
+                        .push_back(Err(RuntimeError::ExecutionError(format!(
+                            "Stream interrupted: {}",
+                            e
+                        ))));
+                }
+                None => {
+                    state.finished = true;
+                    let rest = state.lines.finish();
+                    if !chunks_from_lines(rest, parse, &mut state.queue) {
+                        state.queue.push_back(Ok(CompletionChunk {
+                            delta: String::new(),
+                            done: true,
+                        }));
+                    }
+                }
+            }
+        }
+    }))
+}
+
+/// Ollama streams one JSON object per line
+fn parse_ollama_line(line: &str) -> Option<Result<CompletionChunk, RuntimeError>> {
+    if line.trim().is_empty() {
+        return None;
+    }
+    let parsed: OllamaStreamLine = match serde_json::from_str(line) {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            return Some(Err(RuntimeError::ExecutionError(format!(
+                "Ollama parse failed: {}",
+                e
+            ))))
+        }
+    };
+    if let Some(error) = parsed.error {
+        return Some(Err(RuntimeError::ExecutionError(format!(
+            "Ollama error: {}",
+            error
+        ))));
+    }
+    Some(Ok(CompletionChunk {
+        delta: parsed.message.map(|m| m.content).unwrap_or_default(),
+        done: parsed.done,
+    }))
+}
+
+/// Payload of an SSE `data:` line
+fn sse_data(line: &str) -> Option<&str> {
+    line.strip_prefix("data:").map(str::trim_start)
+}
+
+/// OpenAI sends `data: {chunk}` events and a final `data: [DONE]`
+fn parse_openai_line(line: &str) -> Option<Result<CompletionChunk, RuntimeError>> {
+    let data = sse_data(line)?;
+    if data == "[DONE]" {
+        return Some(Ok(CompletionChunk {
+            delta: String::new(),
+            done: true,
+        }));
+    }
+    let event: serde_json::Value = match serde_json::from_str(data) {
+        Ok(event) => event,
+        Err(e) => {
+            return Some(Err(RuntimeError::ExecutionError(format!(
+                "OpenAI parse failed: {}",
+                e
+            ))))
+        }
+    };
+    if let Some(error) = event.get("error") {
+        return Some(Err(RuntimeError::ExecutionError(format!(
+            "OpenAI error: {}",
+            error
+        ))));
+    }
+    let delta = event["choices"][0]["delta"]["content"].as_str()?;
+    Some(Ok(CompletionChunk {
+        delta: delta.to_string(),
+        done: false,
+    }))
+}
+
+/// Anthropic sends typed events; text arrives in `content_block_delta`
+fn parse_anthropic_line(line: &str) -> Option<Result<CompletionChunk, RuntimeError>> {
+    let event: serde_json::Value = serde_json::from_str(sse_data(line)?).ok()?;
+    match event["type"].as_str()? {
+        "content_block_delta" => Some(Ok(CompletionChunk {
+            delta: event["delta"]["text"].as_str()?.to_string(),
+            done: false,
+        })),
+        "message_stop" => Some(Ok(CompletionChunk {
+            delta: String::new(),
+            done: true,
+        })),
+        "error" => Some(Err(RuntimeError::ExecutionError(format!(
+            "Anthropic error: {}",
+            event["error"]["message"].as_str().unwrap_or("unknown")
+        )))),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn collect(body: &[&[u8]], parse: LineParser) -> Vec<CompletionChunk> {
+        let mut lines = LineBuffer::default();
+        let mut queue = VecDeque::new();
+        let mut finished = false;
+        for data in body {
+            if !finished {
+                finished = chunks_from_lines(lines.push(data), parse, &mut queue);
+            }
+        }
+        if !finished && !chunks_from_lines(lines.finish(), parse, &mut queue) {
+            queue.push_back(Ok(CompletionChunk {
+                delta: String::new(),
+                done: true,
+            }));
+        }
+        queue.into_iter().map(|item| item.unwrap()).collect()
+    }
+
+    fn text(chunks: &[CompletionChunk]) -> String {
+        chunks.iter().map(|c| c.delta.as_str()).collect()
+    }
+
+    #[test]
+    fn test_openai_sse_split_across_reads() {
+        let chunks = collect(
+            &[
+                b"data: {\"choices\":[{\"delta\":{\"role\":\"assistant\"}}]}\n\ndata: {\"choi",
+                b"ces\":[{\"delta\":{\"content\":\"Hel\"}}]}\n\n",
+                b"data: {\"choices\":[{\"delta\":{\"content\":\"lo\"}}]}\r\n\r\ndata: [DONE]\n\n",
+                b"data: {\"choices\":[{\"delta\":{\"content\":\"ignored\"}}]}\n",
+            ],
+            parse_openai_line,
+        );
+        assert_eq!(text(&chunks), "Hello");
+        assert!(chunks.last().unwrap().done);
+        assert_eq!(chunks.iter().filter(|c| c.done).count(), 1);
+    }
+
+    #[test]
+    fn test_anthropic_and_ollama_streams() {
+        let chunks = collect(
+            &[b"event: message_start\ndata: {\"type\":\"message_start\"}\n\n\
+event: content_block_delta\ndata: {\"type\":\"content_block_delta\",\"delta\":{\"type\":\"text_delta\",\"text\":\"Hi \"}}\n\n\
+event: content_block_delta\ndata: {\"type\":\"content_block_delta\",\"delta\":{\"type\":\"text_delta\",\"text\":\"there\"}}\n\n\
+event: message_stop\ndata: {\"type\":\"message_stop\"}\n\n"],
+            parse_anthropic_line,
+        );
+        assert_eq!(text(&chunks), "Hi there");
+        assert!(chunks.last().unwrap().done);
+
+        // Ollama's last line may arrive without a trailing newline
+        let chunks = collect(
+            &[
+                b"{\"message\":{\"content\":\"4\"},\"done\":false}\n",
+                b"{\"message\":{\"content\":\"2\"},\"done\":true}",
+            ],
+            parse_ollama_line,
+        );
+        assert_eq!(text(&chunks), "42");
+        assert_eq!(chunks.len(), 2);
+        assert!(chunks[1].done);
+    }
+
+    #[test]
+    fn test_stream_errors_end_the_stream() {
+        let mut queue = VecDeque::new();
+        let lines = vec![
+            "data: {\"error\":{\"message\":\"overloaded\"}}".to_string(),
+            "data: [DONE]".to_string(),
+        ];
+        assert!(chunks_from_lines(lines, parse_openai_line, &mut queue));
+        assert_eq!(queue.len(), 1);
+        assert!(queue[0].is_err());
+    }
+}
@@ -0,0 +1,541 @@
+//! Email Adapter
+//!
+//! Polls an IMAP mailbox for unseen mail and answers over SMTP.
+//!
+//! Conversations follow the mail thread: the thread ID is the root
+//! Message-ID of the `References` chain, and answers carry `In-Reply-To`
+//! and `References` headers so mail clients keep them in the same thread.
+//! Quoted history is stripped from incoming bodies; attachments are kept
+//! inline on the message.
+
+use super::{
+    AgentResponse, Attachment, AttachmentSource, ChannelAdapter, ChannelError, MessageChannel,
+    MessageContent, UserMessage,
+};
+use futures::TryStreamExt;
+use lettre::message::{header::ContentType, Mailbox, MultiPart, SinglePart};
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Tokio1Executor};
+use parking_lot::Mutex;
+use std::collections::{HashMap, VecDeque};
+use std::time::Duration;
+
+type ImapSession = async_imap::Session<async_native_tls::TlsStream<tokio::net::TcpStream>>;
+
+/// Mail server settings
+#[derive(Clone, Debug)]
+pub struct EmailSettings {
+    /// IMAP server (implicit TLS)
+    pub imap_host: String,
+    pub imap_port: u16,
+    /// SMTP server; port 587 uses STARTTLS, anything else implicit TLS
+    pub smtp_host: String,
+    pub smtp_port: u16,
+    /// Login for both servers
+    pub username: String,
+    pub password: String,
+    /// Mailbox to watch
+    pub mailbox: String,
+    /// Delay between polls when the mailbox is empty
+    pub poll_interval: Duration,
+}
+
+impl EmailSettings {
+    /// Settings with the standard ports and the `INBOX` mailbox
+    pub fn new(imap_host: &str, smtp_host: &str, username: &str, password: &str) -> Self {
+        Self {
+            imap_host: imap_host.to_string(),
+            imap_port: 993,
+            smtp_host: smtp_host.to_string(),
+            smtp_port: 465,
+            username: username.to_string(),
+            password: password.to_string(),
+            mailbox: "INBOX".to_string(),
+            poll_interval: Duration::from_secs(30),
+        }
+    }
+}
+
+/// What is needed to answer within a mail thread
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct EmailThread {
+    /// Address to answer
+    counterpart: String,
+    /// Subject of the thread, without `Re:` prefixes
+    subject: String,
+    /// Message-IDs of the thread, oldest first
+    references: Vec<String>,
+}
+
+/// Email adapter
+pub struct EmailAdapter {
+    channel: MessageChannel,
+    address: String,
+    settings: EmailSettings,
+    smtp: Option<AsyncSmtpTransport<Tokio1Executor>>,
+    /// Messages received but not yet handed out
+    pending: VecDeque<UserMessage>,
+    /// Thread ID -> thread state
+    threads: Mutex<HashMap<String, EmailThread>>,
+}
+
+impl EmailAdapter {
+    /// Adapter for a [`MessageChannel::Email`], with decrypted server settings
+    pub fn new(channel: MessageChannel, settings: EmailSettings) -> Result<Self, ChannelError> {
+        let address = match &channel {
+            MessageChannel::Email { address, .. } => address.clone(),
+            other => return Err(ChannelError::UnsupportedType(other.type_name().to_string())),
+        };
+
+        Ok(Self {
+            channel,
+            address,
+            settings,
+            smtp: None,
+            pending: VecDeque::new(),
+            threads: Mutex::new(HashMap::new()),
+        })
+    }
+
+    async fn imap_session(&self) -> Result<ImapSession, ChannelError> {
+        let host = self.settings.imap_host.as_str();
+        let tcp = tokio::net::TcpStream::connect((host, self.settings.imap_port))
+            .await
+            .map_err(|e| ChannelError::ConnectionFailed(e.to_string()))?;
+        let tls = async_native_tls::TlsConnector::new()
+            .connect(host, tcp)
+            .await
+            .map_err(|e| ChannelError::ConnectionFailed(e.to_string()))?;
+
+        async_imap::Client::new(tls)
+            .login(&self.settings.username, &self.settings.password)
+            .await
+            .map_err(|(e, _)| ChannelError::AuthenticationFailed(e.to_string()))
+    }
+
+    fn smtp_transport(&self) -> Result<AsyncSmtpTransport<Tokio1Executor>, ChannelError> {
+        let host = &self.settings.smtp_host;
+        let builder = if self.settings.smtp_port == 587 {
+            AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(host)
+        } else {
+            AsyncSmtpTransport::<Tokio1Executor>::relay(host)
+        }
+        .map_err(|e| ChannelError::ConnectionFailed(e.to_string()))?;
+
+        Ok(builder
+            .port(self.settings.smtp_port)
+            .credentials(Credentials::new(
+                self.settings.username.clone(),
+                self.settings.password.clone(),
+            ))
+            .build())
+    }
+
+    /// Fetch unseen mail, mark it seen and queue it as messages
+    async fn poll(&mut self) -> Result<(), ChannelError> {
+        let imap_err = |e: async_imap::error::Error| ChannelError::ConnectionFailed(e.to_string());
+
+        let mut session = self.imap_session().await?;
+        session
+            .select(&self.settings.mailbox)
+            .await
+            .map_err(imap_err)?;
+
+        let mut uids: Vec<u32> = session
+            .uid_search("UNSEEN")
+            .await
+            .map_err(imap_err)?
+            .into_iter()
+            .collect();
+        uids.sort_unstable();
+
+        if !uids.is_empty() {
+            let set = uids
+                .iter()
+                .map(u32::to_string)
+                .collect::<Vec<_>>()
+                .join(",");
+            let fetches: Vec<_> = session
+                .uid_fetch(&set, "RFC822")
+                .await
+                .map_err(imap_err)?
+                .try_collect()
+                .await
+                .map_err(imap_err)?;
+
+            let channel_id = self.channel.id();
+            for fetch in &fetches {
+                let Some(raw) = fetch.body() else { continue };
+                match parse_email(&channel_id, raw) {
+                    Some((message, thread))
+                        if !thread.counterpart.eq_ignore_ascii_case(&self.address) =>
+                    {
+                        if let Some(thread_id) = &message.thread_id {
+                            self.threads.lock().insert(thread_id.clone(), thread);
+                        }
+                        self.pending.push_back(message);
+                    }
+                    Some(_) => {}
+                    None => tracing::warn!("Email: skipping unparseable message"),
+                }
+            }
+
+            let _: Vec<_> = session
+                .uid_store(&set, "+FLAGS (\\Seen)")
+                .await
+                .map_err(imap_err)?
+                .try_collect()
+                .await
+                .map_err(imap_err)?;
+        }
+
+        session.logout().await.map_err(imap_err)?;
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl ChannelAdapter for EmailAdapter {
+    async fn connect(&mut self) -> Result<(), ChannelError> {
+        let mut session = self.imap_session().await?;
+        session
+            .logout()
+            .await
+            .map_err(|e| ChannelError::ConnectionFailed(e.to_string()))?;
+
+        let smtp = self.smtp_transport()?;
+        match smtp.test_connection().await {
+            Ok(true) => {}
+            Ok(false) => {
+                return Err(ChannelError::ConnectionFailed(
+                    "SMTP server refused the connection".to_string(),
+                ))
+            }
+            Err(e) => return Err(ChannelError::ConnectionFailed(e.to_string())),
+        }
+        self.smtp = Some(smtp);
+        Ok(())
+    }
+
+    async fn disconnect(&mut self) -> Result<(), ChannelError> {
+        self.smtp = None;
+        self.pending.clear();
+        Ok(())
+    }
+
+    fn is_connected(&self) -> bool {
+        self.smtp.is_some()
+    }
+
+    async fn receive(&mut self) -> Result<UserMessage, ChannelError> {
+        loop {
+            if !self.is_connected() {
+                return Err(ChannelError::NotFound("email not connected".to_string()));
+            }
+            if let Some(message) = self.pending.pop_front() {
+                return Ok(message);
+            }
+            self.poll().await?;
+            if self.pending.is_empty() {
+                tokio::time::sleep(self.settings.poll_interval).await;
+            }
+        }
+    }
+
+    async fn send(&self, response: AgentResponse) -> Result<(), ChannelError> {
+        let smtp = self
+            .smtp
+            .as_ref()
+            .ok_or_else(|| ChannelError::SendFailed("email not connected".to_string()))?;
+        let thread = response
+            .thread_id
+            .as_ref()
+            .and_then(|id| self.threads.lock().get(id).cloned())
+            .ok_or_else(|| ChannelError::SendFailed("unknown email thread".to_string()))?;
+
+        let email = build_reply(&self.address, &thread, &response)?;
+        smtp.send(email)
+            .await
+            .map_err(|e| ChannelError::SendFailed(e.to_string()))?;
+
+        // Later answers in this thread also reference this one's parent
+        if let (Some(id), Some(reply_to)) = (&response.thread_id, &response.reply_to) {
+            let mut threads = self.threads.lock();
+            if let Some(thread) = threads.get_mut(id) {
+                if !thread.references.contains(reply_to) {
+                    thread.references.push(reply_to.clone());
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn channel_info(&self) -> &MessageChannel {
+        &self.channel
+    }
+}
+
+/// Parse a raw RFC 822 message into a user message and its thread
+fn parse_email(channel_id: &str, raw: &[u8]) -> Option<(UserMessage, EmailThread)> {
+    let mail = mail_parser::MessageParser::default().parse(raw)?;
+
+    let sender = mail.from()?.first()?.address()?.to_string();
+    let message_id = mail
+        .header_raw("Message-ID")
+        .and_then(|h| message_ids(h).pop());
+    let in_reply_to = mail
+        .header_raw("In-Reply-To")
+        .and_then(|h| message_ids(h).pop());
+    let mut references = mail
+        .header_raw("References")
+        .map(message_ids)
+        .unwrap_or_default();
+    if let Some(parent) = &in_reply_to {
+        if !references.contains(parent) {
+            references.push(parent.clone());
+        }
+    }
+    let thread_id = references
+        .first()
+        .or(message_id.as_ref())
+        .cloned()
+        .unwrap_or_else(|| format!("{}:{}", sender, base_subject(mail.subject().unwrap_or(""))));
+    if let Some(id) = &message_id {
+        references.push(id.clone());
+    }
+
+    let attachments: Vec<Attachment> = mail
+        .attachments()
+        .map(|part| Attachment {
+            name: part.attachment_name().unwrap_or("attachment").to_string(),
+            mime_type: part
+                .content_type()
+                .map(|ct| match ct.subtype() {
+                    Some(sub) => format!("{}/{}", ct.ctype(), sub),
+                    None => ct.ctype().to_string(),
+                })
+                .unwrap_or_else(|| "application/octet-stream".to_string()),
+            source: AttachmentSource::Inline(part.contents().to_vec()),
+        })
+        .collect();
+
+    let body = mail
+        .body_text(0)
+        .map(|text| strip_quoted(&text))
+        .unwrap_or_default();
+    let subject = base_subject(mail.subject().unwrap_or(""));
+
+    let mut metadata = HashMap::new();
+    metadata.insert("subject".to_string(), subject.clone());
+
+    let message = UserMessage {
+        channel: channel_id.to_string(),
+        sender: sender.clone(),
+        content: MessageContent::Text(body),
+        timestamp: mail
+            .date()
+            .map(|date| date.to_timestamp())
+            .unwrap_or_else(|| chrono::Utc::now().timestamp()),
+        message_id,
+        thread_id: Some(thread_id),
+        reply_to: in_reply_to,
+        attachments,
+        metadata,
+    };
+    let thread = EmailThread {
+        counterpart: sender,
+        subject,
+        references,
+    };
+    Some((message, thread))
+}
+
+/// Answer `response` within `thread`
+fn build_reply(
+    from: &str,
+    thread: &EmailThread,
+    response: &AgentResponse,
+) -> Result<lettre::Message, ChannelError> {
+    let invalid = |e: lettre::address::AddressError| ChannelError::SendFailed(e.to_string());
+    let from: Mailbox = from.parse().map_err(invalid)?;
+    let to: Mailbox = thread.counterpart.parse().map_err(invalid)?;
+
+    let mut body = response.content.to_plain_text();
+    let mut parts = Vec::new();
+    for attachment in &response.attachments {
+        match &attachment.source {
+            AttachmentSource::Inline(data) => {
+                let content_type = ContentType::parse(&attachment.mime_type)
+                    .unwrap_or_else(|_| ContentType::parse("application/octet-stream").unwrap());
+                parts.push(
+                    lettre::message::Attachment::new(attachment.name.clone())
+                        .body(data.clone(), content_type),
+                );
+            }
+            AttachmentSource::Url(url) => {
+                body.push_str(&format!("\n\n{}: {}", attachment.name, url));
+            }
+        }
+    }
+
+    let mut builder = lettre::Message::builder()
+        .from(from)
+        .to(to)
+        .subject(format!("Re: {}", thread.subject));
+    let parent = response
+        .reply_to
+        .clone()
+        .or_else(|| thread.references.last().cloned());
+    if let Some(parent) = &parent {
+        builder = builder.in_reply_to(format!("<{}>", parent));
+    }
+    let mut references = thread.references.clone();
+    if let Some(parent) = parent {
+        if !references.contains(&parent) {
+            references.push(parent);
+        }
+    }
+    if !references.is_empty() {
+        let references: Vec<String> = references.iter().map(|id| format!("<{}>", id)).collect();
+        builder = builder.references(references.join(" "));
+    }
+
+    let mut multipart = MultiPart::mixed().singlepart(SinglePart::plain(body));
+    for part in parts {
+        multipart = multipart.singlepart(part);
+    }
+    builder
+        .multipart(multipart)
+        .map_err(|e| ChannelError::SendFailed(e.to_string()))
+}
+
+/// Message-IDs (without angle brackets) in a header value
+fn message_ids(header: &str) -> Vec<String> {
+    header
+        .split('<')
+        .skip(1)
+        .filter_map(|part| part.split_once('>'))
+        .map(|(id, _)| id.trim().to_string())
+        .filter(|id| !id.is_empty())
+        .collect()
+}
+
+/// Subject without any `Re:`/`Fwd:` prefixes
+fn base_subject(subject: &str) -> String {
+    let mut subject = subject.trim();
+    loop {
+        let lower = subject.to_ascii_lowercase();
+        let prefix = ["re:", "fw:", "fwd:"]
+            .iter()
+            .find(|prefix| lower.starts_with(*prefix));
+        match prefix {
+            Some(prefix) => subject = subject[prefix.len()..].trim_start(),
+            None => return subject.to_string(),
+        }
+    }
+}
+
+/// Drop quoted history below the new text of a reply
+fn strip_quoted(body: &str) -> String {
+    let mut kept = Vec::new();
+    for line in body.lines() {
+        let trimmed = line.trim();
+        let attribution = trimmed.starts_with("On ") && trimmed.ends_with("wrote:");
+        if attribution || trimmed == "-----Original Message-----" {
+            break;
+        }
+        if !trimmed.starts_with('>') {
+            kept.push(line);
+        }
+    }
+    kept.join("\n").trim().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::channels::ResponseContent;
+
+    const REPLY: &str = "From: Alice <alice@example.org>\r\n\
+To: agent@example.org\r\n\
+Subject: Re: Re: Weekly transfer\r\n\
+Date: Tue, 14 Nov 2023 22:13:20 +0000\r\n\
+Message-ID: <c3@example.org>\r\n\
+In-Reply-To: <b2@example.org>\r\n\
+References: <a1@example.org> <b2@example.org>\r\n\
+MIME-Version: 1.0\r\n\
+Content-Type: multipart/mixed; boundary=\"XX\"\r\n\
+\r\n\
+--XX\r\n\
+Content-Type: text/plain; charset=utf-8\r\n\
+\r\n\
+Yes, send 5 FAT to bob.\r\n\
+\r\n\
+On Mon, Nov 13, 2023 at 9:00 AM Agent <agent@example.org> wrote:\r\n\
+> Shall I repeat last week's transfer?\r\n\
+--XX\r\n\
+Content-Type: text/csv\r\n\
+Content-Disposition: attachment; filename=\"ledger.csv\"\r\n\
+Content-Transfer-Encoding: base64\r\n\
+\r\n\
+YSxiCg==\r\n\
+--XX--\r\n";
+
+    #[test]
+    fn test_parse_reply_into_thread() {
+        let (message, thread) = parse_email("email:agent@example.org", REPLY.as_bytes()).unwrap();
+
+        assert_eq!(message.sender, "alice@example.org");
+        assert_eq!(message.content.as_text(), Some("Yes, send 5 FAT to bob."));
+        assert_eq!(message.message_id.as_deref(), Some("c3@example.org"));
+        assert_eq!(message.reply_to.as_deref(), Some("b2@example.org"));
+        assert_eq!(message.thread_id.as_deref(), Some("a1@example.org"));
+        assert_eq!(message.timestamp, 1_700_000_000);
+        assert_eq!(message.attachments.len(), 1);
+        assert_eq!(message.attachments[0].name, "ledger.csv");
+        assert_eq!(message.attachments[0].mime_type, "text/csv");
+        assert_eq!(
+            message.attachments[0].source,
+            AttachmentSource::Inline(b"a,b\n".to_vec())
+        );
+
+        assert_eq!(thread.subject, "Weekly transfer");
+        assert_eq!(
+            thread.references,
+            ["a1@example.org", "b2@example.org", "c3@example.org"]
+        );
+    }
+
+    #[test]
+    fn test_reply_headers() {
+        let (message, thread) = parse_email("email:agent@example.org", REPLY.as_bytes()).unwrap();
+        let response = AgentResponse {
+            channel: message.channel.clone(),
+            content: ResponseContent::Text("Sent.".to_string()),
+            reply_to: message.message_id.clone(),
+            thread_id: message.thread_id.clone(),
+            attachments: vec![Attachment {
+                name: "receipt.txt".to_string(),
+                mime_type: "text/plain".to_string(),
+                source: AttachmentSource::Inline(b"ok".to_vec()),
+            }],
+        };
+
+        let email = build_reply("agent@example.org", &thread, &response).unwrap();
+        let raw = String::from_utf8(email.formatted()).unwrap();
+        assert!(raw.contains("Subject: Re: Weekly transfer"));
+        assert!(raw.contains("In-Reply-To: <c3@example.org>"));
+        assert!(raw.contains("References: <a1@example.org> <b2@example.org> <c3@example.org>"));
+        assert!(raw.contains("receipt.txt"));
+    }
+
+    #[test]
+    fn test_header_helpers() {
+        assert_eq!(
+            message_ids(" <a@x>\r\n <b@y> junk"),
+            vec!["a@x".to_string(), "b@y".to_string()]
+        );
+        assert_eq!(base_subject("RE: Fwd: re:  Hello"), "Hello");
+        assert_eq!(strip_quoted("new\n> old\n> older"), "new");
+    }
+}
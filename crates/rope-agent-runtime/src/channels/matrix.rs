@@ -0,0 +1,552 @@
+//! Matrix Adapter
+//!
+//! Talks to a homeserver over the client-server API: long-polls `/sync`
+//! for room messages and sends `m.room.message` events back.
+//!
+//! Each room is a thread of the channel. Messages in a Matrix thread map
+//! to `<room_id>/<thread root event>`, and replies keep their
+//! `m.in_reply_to` target in [`UserMessage::reply_to`], so responses land
+//! in the same room, thread and reply chain.
+
+use super::{
+    AgentResponse, Attachment, AttachmentSource, ChannelAdapter, ChannelError, MessageChannel,
+    MessageContent, UserMessage,
+};
+use serde_json::{json, Value};
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Long-poll timeout for `/sync`
+const SYNC_TIMEOUT_MS: u64 = 30_000;
+
+/// Matrix adapter
+pub struct MatrixAdapter {
+    channel: MessageChannel,
+    homeserver: String,
+    user_id: String,
+    access_token: String,
+    room_ids: Vec<String>,
+    client: reqwest::Client,
+    connected: bool,
+    /// `next_batch` token of the last sync
+    since: Option<String>,
+    /// Messages received but not yet handed out
+    pending: VecDeque<UserMessage>,
+    /// Transaction IDs for idempotent sends
+    next_txn: AtomicU64,
+}
+
+impl MatrixAdapter {
+    /// Adapter for a [`MessageChannel::Matrix`], with its decrypted access token
+    pub fn new(channel: MessageChannel, access_token: String) -> Result<Self, ChannelError> {
+        let (homeserver, user_id, room_ids) = match &channel {
+            MessageChannel::Matrix {
+                homeserver,
+                user_id,
+                room_ids,
+                ..
+            } => (
+                homeserver.trim_end_matches('/').to_string(),
+                user_id.clone(),
+                room_ids.clone(),
+            ),
+            other => return Err(ChannelError::UnsupportedType(other.type_name().to_string())),
+        };
+
+        Ok(Self {
+            channel,
+            homeserver,
+            user_id,
+            access_token,
+            room_ids,
+            client: reqwest::Client::new(),
+            connected: false,
+            since: None,
+            pending: VecDeque::new(),
+            next_txn: AtomicU64::new(chrono::Utc::now().timestamp_millis() as u64),
+        })
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}/_matrix/client/v3{}", self.homeserver, path)
+    }
+
+    async fn sync(&mut self, timeout_ms: u64) -> Result<Value, ChannelError> {
+        let mut request = self
+            .client
+            .get(self.url("/sync"))
+            .bearer_auth(&self.access_token)
+            .query(&[("timeout", timeout_ms.to_string())])
+            .timeout(std::time::Duration::from_millis(timeout_ms + 10_000));
+        if let Some(since) = &self.since {
+            request = request.query(&[("since", since)]);
+        }
+
+        let body = Self::json(request.send().await).await?;
+        self.since = body["next_batch"].as_str().map(str::to_string);
+        Ok(body)
+    }
+
+    async fn json(
+        response: Result<reqwest::Response, reqwest::Error>,
+    ) -> Result<Value, ChannelError> {
+        let response = response.map_err(|e| ChannelError::ConnectionFailed(e.to_string()))?;
+        match response.status().as_u16() {
+            401 | 403 => Err(ChannelError::AuthenticationFailed(
+                response.text().await.unwrap_or_default(),
+            )),
+            429 => Err(ChannelError::RateLimited("matrix".to_string())),
+            status if status >= 400 => Err(ChannelError::ConnectionFailed(format!(
+                "HTTP {}: {}",
+                status,
+                response.text().await.unwrap_or_default()
+            ))),
+            _ => response
+                .json()
+                .await
+                .map_err(|e| ChannelError::ConnectionFailed(e.to_string())),
+        }
+    }
+
+    /// Upload `attachment` to the media repository, returning its `mxc://` URI
+    async fn upload(&self, attachment: &Attachment) -> Result<String, ChannelError> {
+        let data = match &attachment.source {
+            AttachmentSource::Inline(data) => data.clone(),
+            AttachmentSource::Url(url) => return Ok(url.clone()),
+        };
+        let response = self
+            .client
+            .post(format!("{}/_matrix/media/v3/upload", self.homeserver))
+            .bearer_auth(&self.access_token)
+            .query(&[("filename", &attachment.name)])
+            .header(reqwest::header::CONTENT_TYPE, &attachment.mime_type)
+            .body(data)
+            .send()
+            .await;
+        let body = Self::json(response)
+            .await
+            .map_err(|e| ChannelError::SendFailed(e.to_string()))?;
+        body["content_uri"]
+            .as_str()
+            .map(str::to_string)
+            .ok_or_else(|| ChannelError::SendFailed("upload returned no content_uri".to_string()))
+    }
+
+    /// Send a room message, returning its event ID
+    async fn send_event(&self, room_id: &str, content: &Value) -> Result<String, ChannelError> {
+        let txn = self.next_txn.fetch_add(1, Ordering::Relaxed);
+        let path = format!("/rooms/{}/send/m.room.message/{}", encode(room_id), txn);
+        let response = self
+            .client
+            .put(self.url(&path))
+            .bearer_auth(&self.access_token)
+            .json(content)
+            .send()
+            .await;
+        let body = Self::json(response)
+            .await
+            .map_err(|e| ChannelError::SendFailed(e.to_string()))?;
+        body["event_id"]
+            .as_str()
+            .map(str::to_string)
+            .ok_or_else(|| ChannelError::SendFailed("send returned no event_id".to_string()))
+    }
+
+    /// Text message content for `response`, related to its thread
+    fn text_content(response: &AgentResponse) -> Result<(&str, Value), ChannelError> {
+        let (room_id, thread_root) = response
+            .thread_id
+            .as_deref()
+            .map(split_thread)
+            .ok_or_else(|| ChannelError::SendFailed("response has no Matrix room".to_string()))?;
+
+        let mut content = json!({
+            "msgtype": "m.text",
+            "body": response.content.to_plain_text(),
+        });
+        if let Some(relation) = relation(thread_root, response.reply_to.as_deref()) {
+            content["m.relates_to"] = relation;
+        }
+        Ok((room_id, content))
+    }
+
+    /// Room messages from a `/sync` response, oldest first
+    fn collect_messages(&mut self, sync: &Value) {
+        let Some(rooms) = sync["rooms"]["join"].as_object() else {
+            return;
+        };
+        for (room_id, room) in rooms {
+            if !self.room_ids.is_empty() && !self.room_ids.contains(room_id) {
+                continue;
+            }
+            let events = room["timeline"]["events"].as_array().into_iter().flatten();
+            for event in events {
+                if event["sender"].as_str() == Some(self.user_id.as_str()) {
+                    continue;
+                }
+                if let Some(message) =
+                    event_to_message(&self.channel.id(), &self.homeserver, room_id, event)
+                {
+                    self.pending.push_back(message);
+                }
+            }
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl ChannelAdapter for MatrixAdapter {
+    async fn connect(&mut self) -> Result<(), ChannelError> {
+        let response = self
+            .client
+            .get(self.url("/account/whoami"))
+            .bearer_auth(&self.access_token)
+            .send()
+            .await;
+        let whoami = Self::json(response).await?;
+        if whoami["user_id"].as_str() != Some(self.user_id.as_str()) {
+            return Err(ChannelError::InvalidCredentials(format!(
+                "token belongs to {}, not {}",
+                whoami["user_id"], self.user_id
+            )));
+        }
+
+        // Start from now rather than replaying the room history
+        self.sync(0).await?;
+        self.connected = true;
+        Ok(())
+    }
+
+    async fn disconnect(&mut self) -> Result<(), ChannelError> {
+        self.connected = false;
+        self.pending.clear();
+        Ok(())
+    }
+
+    fn is_connected(&self) -> bool {
+        self.connected
+    }
+
+    async fn receive(&mut self) -> Result<UserMessage, ChannelError> {
+        loop {
+            if !self.connected {
+                return Err(ChannelError::NotFound("matrix not connected".to_string()));
+            }
+            if let Some(message) = self.pending.pop_front() {
+                return Ok(message);
+            }
+            let sync = self.sync(SYNC_TIMEOUT_MS).await?;
+            self.collect_messages(&sync);
+        }
+    }
+
+    async fn send(&self, response: AgentResponse) -> Result<(), ChannelError> {
+        let (room_id, content) = Self::text_content(&response)?;
+        let relation = content.get("m.relates_to").cloned();
+        self.send_event(room_id, &content).await?;
+
+        for attachment in &response.attachments {
+            let url = self.upload(attachment).await?;
+            let mut content = json!({
+                "msgtype": attachment_msgtype(&attachment.mime_type),
+                "body": attachment.name,
+                "url": url,
+                "info": { "mimetype": attachment.mime_type },
+            });
+            if let Some(relation) = &relation {
+                content["m.relates_to"] = relation.clone();
+            }
+            self.send_event(room_id, &content).await?;
+        }
+        Ok(())
+    }
+
+    fn supports_editing(&self) -> bool {
+        true
+    }
+
+    async fn send_editable(&self, response: AgentResponse) -> Result<String, ChannelError> {
+        let (room_id, content) = Self::text_content(&response)?;
+        self.send_event(room_id, &content).await
+    }
+
+    async fn edit(&self, message_id: &str, response: AgentResponse) -> Result<(), ChannelError> {
+        let (room_id, content) = Self::text_content(&response)?;
+        self.send_event(room_id, &replacement(message_id, content))
+            .await
+            .map(drop)
+    }
+
+    fn channel_info(&self) -> &MessageChannel {
+        &self.channel
+    }
+}
+
+/// Convert a timeline event into a user message, if it is a room message
+fn event_to_message(
+    channel_id: &str,
+    homeserver: &str,
+    room_id: &str,
+    event: &Value,
+) -> Option<UserMessage> {
+    if event["type"] != "m.room.message" {
+        return None;
+    }
+    let content = &event["content"];
+    let body = content["body"].as_str().unwrap_or_default();
+    let relates_to = &content["m.relates_to"];
+
+    let thread_root = (relates_to["rel_type"] == "m.thread")
+        .then(|| relates_to["event_id"].as_str())
+        .flatten();
+    let reply_to = relates_to["m.in_reply_to"]["event_id"]
+        .as_str()
+        .map(str::to_string);
+    let thread_id = match thread_root {
+        Some(root) => format!("{}/{}", room_id, root),
+        None => room_id.to_string(),
+    };
+
+    let media_url = content["url"]
+        .as_str()
+        .map(|mxc| media_download_url(homeserver, mxc));
+    let mime_type = content["info"]["mimetype"]
+        .as_str()
+        .unwrap_or("application/octet-stream")
+        .to_string();
+
+    let mut attachments = Vec::new();
+    let message_content = match content["msgtype"].as_str()? {
+        "m.text" | "m.notice" | "m.emote" => {
+            // Replies carry a quoted fallback of the original message
+            let text = if reply_to.is_some() {
+                strip_reply_fallback(body)
+            } else {
+                body.to_string()
+            };
+            MessageContent::Text(text)
+        }
+        "m.image" => MessageContent::Image {
+            url: media_url.clone()?,
+            caption: None,
+        },
+        "m.audio" => MessageContent::Audio {
+            url: media_url.clone()?,
+            transcription: None,
+        },
+        "m.file" | "m.video" => MessageContent::Document {
+            url: media_url.clone()?,
+            name: body.to_string(),
+        },
+        "m.location" => {
+            let geo = content["geo_uri"].as_str()?.strip_prefix("geo:")?;
+            let mut coords = geo.split([',', ';']);
+            MessageContent::Location {
+                lat: coords.next()?.parse().ok()?,
+                lng: coords.next()?.parse().ok()?,
+            }
+        }
+        _ => return None,
+    };
+    if let Some(url) = media_url {
+        attachments.push(Attachment {
+            name: body.to_string(),
+            mime_type,
+            source: AttachmentSource::Url(url),
+        });
+    }
+
+    let mut metadata = HashMap::new();
+    metadata.insert("room_id".to_string(), room_id.to_string());
+
+    Some(UserMessage {
+        channel: channel_id.to_string(),
+        sender: event["sender"].as_str()?.to_string(),
+        content: message_content,
+        timestamp: event["origin_server_ts"].as_i64().unwrap_or_default() / 1000,
+        message_id: event["event_id"].as_str().map(str::to_string),
+        thread_id: Some(thread_id),
+        reply_to,
+        attachments,
+        metadata,
+    })
+}
+
+/// Split a thread ID into its room and optional thread root
+fn split_thread(thread_id: &str) -> (&str, Option<&str>) {
+    match thread_id.split_once('/') {
+        Some((room, root)) => (room, Some(root)),
+        None => (thread_id, None),
+    }
+}
+
+/// `m.relates_to` for a message in `thread_root` replying to `reply_to`
+fn relation(thread_root: Option<&str>, reply_to: Option<&str>) -> Option<Value> {
+    match (thread_root, reply_to) {
+        (Some(root), reply_to) => Some(json!({
+            "rel_type": "m.thread",
+            "event_id": root,
+            "is_falling_back": reply_to.is_none(),
+            "m.in_reply_to": { "event_id": reply_to.unwrap_or(root) },
+        })),
+        (None, Some(reply_to)) => Some(json!({
+            "m.in_reply_to": { "event_id": reply_to },
+        })),
+        (None, None) => None,
+    }
+}
+
+/// `m.replace` edit of `event_id` with `content`
+///
+/// Clients without edit support show the fallback body, marked with `*`.
+fn replacement(event_id: &str, mut content: Value) -> Value {
+    content.as_object_mut().map(|c| c.remove("m.relates_to"));
+    let body = content["body"].as_str().unwrap_or_default();
+    json!({
+        "msgtype": "m.text",
+        "body": format!("* {}", body),
+        "m.new_content": content,
+        "m.relates_to": { "rel_type": "m.replace", "event_id": event_id },
+    })
+}
+
+/// Drop the `> <@user> original` lines Matrix clients prepend to replies
+fn strip_reply_fallback(body: &str) -> String {
+    let mut lines = body.lines().peekable();
+    while lines.next_if(|line| line.starts_with('>')).is_some() {}
+    lines.next_if(|line| line.is_empty());
+    lines.collect::<Vec<_>>().join("\n")
+}
+
+/// HTTP download URL for an `mxc://server/media` URI
+fn media_download_url(homeserver: &str, mxc: &str) -> String {
+    match mxc.strip_prefix("mxc://") {
+        Some(path) => format!("{}/_matrix/media/v3/download/{}", homeserver, path),
+        None => mxc.to_string(),
+    }
+}
+
+fn attachment_msgtype(mime_type: &str) -> &'static str {
+    match mime_type.split('/').next() {
+        Some("image") => "m.image",
+        Some("audio") => "m.audio",
+        Some("video") => "m.video",
+        _ => "m.file",
+    }
+}
+
+fn encode(segment: &str) -> String {
+    url::form_urlencoded::byte_serialize(segment.as_bytes()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HS: &str = "https://matrix.example.org";
+
+    #[test]
+    fn test_threaded_reply_mapping() {
+        let event = json!({
+            "type": "m.room.message",
+            "event_id": "$reply",
+            "sender": "@alice:example.org",
+            "origin_server_ts": 1_700_000_000_000i64,
+            "content": {
+                "msgtype": "m.text",
+                "body": "> <@bot:example.org> balance is 3 FAT\n\nsend 1 to bob",
+                "m.relates_to": {
+                    "rel_type": "m.thread",
+                    "event_id": "$root",
+                    "m.in_reply_to": { "event_id": "$answer" }
+                }
+            }
+        });
+
+        let message =
+            event_to_message("matrix:@bot:example.org", HS, "!room:example.org", &event).unwrap();
+        assert_eq!(message.content.as_text(), Some("send 1 to bob"));
+        assert_eq!(
+            message.thread_id.as_deref(),
+            Some("!room:example.org/$root")
+        );
+        assert_eq!(message.reply_to.as_deref(), Some("$answer"));
+        assert_eq!(message.timestamp, 1_700_000_000);
+        assert_eq!(
+            message.conversation_key(),
+            "matrix:@bot:example.org#!room:example.org/$root"
+        );
+
+        // Responses go back into the same thread, replying to the message
+        let (room, root) = split_thread(message.thread_id.as_deref().unwrap());
+        assert_eq!(room, "!room:example.org");
+        let threaded = relation(root, message.message_id.as_deref()).unwrap();
+        assert_eq!(threaded["rel_type"], "m.thread");
+        assert_eq!(threaded["event_id"], "$root");
+        assert_eq!(threaded["m.in_reply_to"]["event_id"], "$reply");
+
+        let reply = relation(None, Some("$x")).unwrap();
+        assert!(reply.get("rel_type").is_none());
+        assert_eq!(reply["m.in_reply_to"]["event_id"], "$x");
+        assert!(relation(None, None).is_none());
+    }
+
+    #[test]
+    fn test_media_and_location_events() {
+        let file = json!({
+            "type": "m.room.message",
+            "event_id": "$file",
+            "sender": "@alice:example.org",
+            "content": {
+                "msgtype": "m.file",
+                "body": "invoice.pdf",
+                "url": "mxc://example.org/abc123",
+                "info": { "mimetype": "application/pdf" }
+            }
+        });
+        let message = event_to_message("matrix:bot", HS, "!room:example.org", &file).unwrap();
+        let url = "https://matrix.example.org/_matrix/media/v3/download/example.org/abc123";
+        match &message.content {
+            MessageContent::Document { url: u, name } => {
+                assert_eq!(u, url);
+                assert_eq!(name, "invoice.pdf");
+            }
+            other => panic!("unexpected content {:?}", other),
+        }
+        assert_eq!(message.attachments[0].mime_type, "application/pdf");
+        assert_eq!(
+            message.attachments[0].source,
+            AttachmentSource::Url(url.to_string())
+        );
+
+        let location = json!({
+            "type": "m.room.message",
+            "sender": "@alice:example.org",
+            "content": { "msgtype": "m.location", "body": "here", "geo_uri": "geo:51.5,-0.12;u=35" }
+        });
+        let message = event_to_message("matrix:bot", HS, "!room:example.org", &location).unwrap();
+        assert!(matches!(
+            message.content,
+            MessageContent::Location { lat, lng } if lat == 51.5 && lng == -0.12
+        ));
+
+        let state = json!({ "type": "m.room.member", "sender": "@alice:example.org" });
+        assert!(event_to_message("matrix:bot", HS, "!room:example.org", &state).is_none());
+    }
+    #[test]
+    fn test_edit_replaces_content() {
+        let edit = replacement(
+            "$answer",
+            json!({
+                "msgtype": "m.text",
+                "body": "balance is 3 FAT",
+                "m.relates_to": { "m.in_reply_to": { "event_id": "$question" } },
+            }),
+        );
+        assert_eq!(edit["body"], "* balance is 3 FAT");
+        assert_eq!(edit["m.new_content"]["body"], "balance is 3 FAT");
+        assert!(edit["m.new_content"].get("m.relates_to").is_none());
+        assert_eq!(edit["m.relates_to"]["rel_type"], "m.replace");
+        assert_eq!(edit["m.relates_to"]["event_id"], "$answer");
+    }
+}
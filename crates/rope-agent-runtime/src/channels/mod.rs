@@ -1,10 +1,19 @@
 //! Message Channels
 //!
-//! Adapters for various messaging platforms (WhatsApp, Telegram, Slack, Discord, etc.)
+//! Adapters for various messaging platforms (WhatsApp, Telegram, Slack, Discord,
+//! Matrix, email, etc.)
 
+mod email;
+mod matrix;
 mod router;
+mod streaming;
+mod transcription;
 
+pub use email::*;
+pub use matrix::*;
 pub use router::*;
+pub use streaming::*;
+pub use transcription::*;
 
 use crate::error::ChannelError;
 use async_trait::async_trait;
@@ -41,6 +50,15 @@ pub enum MessageChannel {
     /// iMessage (macOS only)
     IMessage { apple_id: String },
 
+    /// Matrix (client-server API)
+    Matrix {
+        homeserver: String,
+        user_id: String,
+        access_token: EncryptedCredentials,
+        /// Rooms to listen in; empty means every joined room
+        room_ids: Vec<String>,
+    },
+
     /// Email
     Email {
         address: String,
@@ -65,6 +83,7 @@ impl MessageChannel {
             MessageChannel::Discord { .. } => "discord".to_string(),
             MessageChannel::Slack { workspace, .. } => format!("slack:{}", workspace),
             MessageChannel::IMessage { apple_id } => format!("imessage:{}", apple_id),
+            MessageChannel::Matrix { user_id, .. } => format!("matrix:{}", user_id),
             MessageChannel::Email { address, .. } => format!("email:{}", address),
             MessageChannel::Custom { name, .. } => format!("custom:{}", name),
         }
@@ -78,6 +97,7 @@ impl MessageChannel {
             MessageChannel::Discord { .. } => "discord",
             MessageChannel::Slack { .. } => "slack",
             MessageChannel::IMessage { .. } => "imessage",
+            MessageChannel::Matrix { .. } => "matrix",
             MessageChannel::Email { .. } => "email",
             MessageChannel::Custom { .. } => "custom",
         }
@@ -90,6 +110,7 @@ impl MessageChannel {
             MessageChannel::Telegram { bot_token, .. } => Some(bot_token),
             MessageChannel::Discord { bot_token, .. } => Some(bot_token),
             MessageChannel::Slack { bot_token, .. } => Some(bot_token),
+            MessageChannel::Matrix { access_token, .. } => Some(access_token),
             MessageChannel::Email {
                 imap_credentials, ..
             } => Some(imap_credentials),
@@ -151,10 +172,56 @@ pub struct UserMessage {
     /// Platform-specific message ID
     pub message_id: Option<String>,
 
+    /// Conversation thread within the channel (room, email thread, ...)
+    #[serde(default)]
+    pub thread_id: Option<String>,
+
+    /// Platform-specific ID of the message this one replies to
+    #[serde(default)]
+    pub reply_to: Option<String>,
+
+    /// Files attached alongside the content
+    #[serde(default)]
+    pub attachments: Vec<Attachment>,
+
     /// Additional metadata
     pub metadata: HashMap<String, String>,
 }
 
+impl UserMessage {
+    /// Key of the conversation this message belongs to: the channel, or
+    /// the thread within it when the platform has threads
+    pub fn conversation_key(&self) -> String {
+        match &self.thread_id {
+            Some(thread) => format!("{}#{}", self.channel, thread),
+            None => self.channel.clone(),
+        }
+    }
+}
+
+/// File attached to a message or response
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Attachment {
+    /// File name
+    pub name: String,
+
+    /// MIME type
+    pub mime_type: String,
+
+    /// Where the file contents are
+    pub source: AttachmentSource,
+}
+
+/// Location of attachment contents
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AttachmentSource {
+    /// Downloadable from the platform
+    Url(String),
+
+    /// Carried with the message
+    Inline(Vec<u8>),
+}
+
 /// Message content types
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum MessageContent {
@@ -211,6 +278,14 @@ pub struct AgentResponse {
 
     /// Reply to specific message
     pub reply_to: Option<String>,
+
+    /// Thread to answer in, from [`UserMessage::thread_id`]
+    #[serde(default)]
+    pub thread_id: Option<String>,
+
+    /// Files to send with the response
+    #[serde(default)]
+    pub attachments: Vec<Attachment>,
 }
 
 /// Response content types
@@ -240,6 +315,35 @@ pub enum ResponseContent {
     Error(String),
 }
 
+impl ResponseContent {
+    /// Plain-text rendering for channels without rich formatting
+    pub fn to_plain_text(&self) -> String {
+        match self {
+            ResponseContent::Text(text) | ResponseContent::Markdown(text) => text.clone(),
+            ResponseContent::Embed {
+                title,
+                description,
+                fields,
+                ..
+            } => {
+                let mut text = format!("{}\n\n{}", title, description);
+                for (name, value) in fields {
+                    text.push_str(&format!("\n{}: {}", name, value));
+                }
+                text
+            }
+            ResponseContent::Buttons { text, buttons } => {
+                let mut text = text.clone();
+                for (i, (label, _)) in buttons.iter().enumerate() {
+                    text.push_str(&format!("\n{}. {}", i + 1, label));
+                }
+                text
+            }
+            ResponseContent::Error(error) => format!("Error: {}", error),
+        }
+    }
+}
+
 /// Channel adapter trait
 #[async_trait]
 pub trait ChannelAdapter: Send + Sync {
@@ -258,6 +362,31 @@ pub trait ChannelAdapter: Send + Sync {
     /// Send response
     async fn send(&self, response: AgentResponse) -> Result<(), ChannelError>;
 
+    /// Whether sent messages can be edited in place
+    ///
+    /// Channels that can edit show streamed answers as they are generated;
+    /// the others receive the finished answer in one message.
+    fn supports_editing(&self) -> bool {
+        false
+    }
+
+    /// Send a text response that can later be replaced with [`Self::edit`],
+    /// returning the channel's ID for the sent message
+    async fn send_editable(&self, _response: AgentResponse) -> Result<String, ChannelError> {
+        Err(ChannelError::UnsupportedType(format!(
+            "{} cannot edit messages",
+            self.channel_info().type_name()
+        )))
+    }
+
+    /// Replace the text of a message sent with [`Self::send_editable`]
+    async fn edit(&self, _message_id: &str, _response: AgentResponse) -> Result<(), ChannelError> {
+        Err(ChannelError::UnsupportedType(format!(
+            "{} cannot edit messages",
+            self.channel_info().type_name()
+        )))
+    }
+
     /// Get channel info
     fn channel_info(&self) -> &MessageChannel;
 }
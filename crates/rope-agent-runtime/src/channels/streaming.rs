@@ -0,0 +1,238 @@
+//! Streaming answers to chat channels
+//!
+//! Long answers are shown while the model is still generating them: the
+//! first words go out as a message which is then edited as more text
+//! arrives. Edits are throttled so chat platforms' rate limits are not hit.
+//! Channels that cannot edit messages get the finished answer instead.
+
+use super::{AgentResponse, ChannelAdapter, ResponseContent};
+use crate::ai::CompletionStream;
+use crate::error::RuntimeError;
+use futures::StreamExt;
+use std::time::{Duration, Instant};
+
+/// Default minimum delay between two edits of a streamed message
+pub const STREAM_EDIT_INTERVAL: Duration = Duration::from_millis(750);
+
+/// Deliver a streamed answer through `adapter`, returning the full text
+///
+/// `template` supplies the channel, thread and reply target; its content is
+/// replaced by the streamed text. Streaming is text only, so send any
+/// attachments separately. If the stream fails after the first message went
+/// out, the partial answer stays visible and the error is returned.
+pub async fn stream_response(
+    adapter: &dyn ChannelAdapter,
+    template: AgentResponse,
+    mut stream: CompletionStream,
+    min_interval: Duration,
+) -> Result<String, RuntimeError> {
+    let template = AgentResponse {
+        attachments: Vec::new(),
+        ..template
+    };
+    let with_text = |text: &str| AgentResponse {
+        content: ResponseContent::Text(text.to_string()),
+        ..template.clone()
+    };
+
+    let editing = adapter.supports_editing();
+    let mut text = String::new();
+    let mut sent: Option<(String, usize)> = None;
+    let mut last_edit = Instant::now();
+
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk?;
+        text.push_str(&chunk.delta);
+        if !editing || text.trim().is_empty() {
+            if chunk.done {
+                break;
+            }
+            continue;
+        }
+
+        match &mut sent {
+            None => {
+                let id = adapter.send_editable(with_text(&text)).await?;
+                sent = Some((id, text.len()));
+                last_edit = Instant::now();
+            }
+            Some((id, shown)) => {
+                let due = chunk.done || last_edit.elapsed() >= min_interval;
+                if due && *shown != text.len() {
+                    adapter.edit(id, with_text(&text)).await?;
+                    *shown = text.len();
+                    last_edit = Instant::now();
+                }
+            }
+        }
+        if chunk.done {
+            break;
+        }
+    }
+
+    match sent {
+        // The stream may end without a `done` chunk carrying the tail
+        Some((id, shown)) if shown != text.len() => {
+            adapter.edit(&id, with_text(&text)).await?;
+        }
+        Some(_) => {}
+        None if text.trim().is_empty() => {}
+        None => adapter.send(with_text(&text)).await?,
+    }
+    Ok(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai::CompletionChunk;
+    use crate::channels::{ChannelError, EncryptedCredentials, MessageChannel, UserMessage};
+    use parking_lot::Mutex;
+
+    struct Recorder {
+        channel: MessageChannel,
+        editing: bool,
+        log: Mutex<Vec<String>>,
+    }
+
+    impl Recorder {
+        fn new(editing: bool) -> Self {
+            Self {
+                channel: MessageChannel::Telegram {
+                    bot_token: EncryptedCredentials::placeholder(),
+                    allowed_users: vec![],
+                },
+                editing,
+                log: Mutex::new(Vec::new()),
+            }
+        }
+
+        fn text(response: &AgentResponse) -> String {
+            response.content.to_plain_text()
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl ChannelAdapter for Recorder {
+        async fn connect(&mut self) -> Result<(), ChannelError> {
+            Ok(())
+        }
+
+        async fn disconnect(&mut self) -> Result<(), ChannelError> {
+            Ok(())
+        }
+
+        fn is_connected(&self) -> bool {
+            true
+        }
+
+        async fn receive(&mut self) -> Result<UserMessage, ChannelError> {
+            Err(ChannelError::NotFound("no messages".to_string()))
+        }
+
+        async fn send(&self, response: AgentResponse) -> Result<(), ChannelError> {
+            self.log
+                .lock()
+                .push(format!("send {}", Self::text(&response)));
+            Ok(())
+        }
+
+        fn supports_editing(&self) -> bool {
+            self.editing
+        }
+
+        async fn send_editable(&self, response: AgentResponse) -> Result<String, ChannelError> {
+            self.log
+                .lock()
+                .push(format!("new {}", Self::text(&response)));
+            Ok("m1".to_string())
+        }
+
+        async fn edit(&self, id: &str, response: AgentResponse) -> Result<(), ChannelError> {
+            self.log
+                .lock()
+                .push(format!("edit {} {}", id, Self::text(&response)));
+            Ok(())
+        }
+
+        fn channel_info(&self) -> &MessageChannel {
+            &self.channel
+        }
+    }
+
+    fn chunks(parts: &[&str]) -> CompletionStream {
+        let last = parts.len() - 1;
+        let items: Vec<_> = parts
+            .iter()
+            .enumerate()
+            .map(|(i, delta)| {
+                Ok(CompletionChunk {
+                    delta: delta.to_string(),
+                    done: i == last,
+                })
+            })
+            .collect();
+        Box::pin(futures::stream::iter(items))
+    }
+
+    fn template() -> AgentResponse {
+        AgentResponse {
+            channel: "telegram:1".to_string(),
+            content: ResponseContent::Text(String::new()),
+            reply_to: Some("42".to_string()),
+            thread_id: None,
+            attachments: Vec::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_streams_by_editing() {
+        let adapter = Recorder::new(true);
+        let text = stream_response(
+            &adapter,
+            template(),
+            chunks(&["", "The ", "balance ", "is 3 FAT", ""]),
+            Duration::ZERO,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(text, "The balance is 3 FAT");
+        assert_eq!(
+            *adapter.log.lock(),
+            [
+                "new The ",
+                "edit m1 The balance ",
+                "edit m1 The balance is 3 FAT"
+            ]
+        );
+
+        // Throttled edits still end on the full answer
+        let adapter = Recorder::new(true);
+        stream_response(
+            &adapter,
+            template(),
+            chunks(&["a", "b", "c", "d"]),
+            Duration::from_secs(60),
+        )
+        .await
+        .unwrap();
+        assert_eq!(*adapter.log.lock(), ["new a", "edit m1 abcd"]);
+    }
+
+    #[tokio::test]
+    async fn test_sends_once_without_editing() {
+        let adapter = Recorder::new(false);
+        let text = stream_response(
+            &adapter,
+            template(),
+            chunks(&["The ", "balance"]),
+            Duration::ZERO,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(text, "The balance");
+        assert_eq!(*adapter.log.lock(), ["send The balance"]);
+    }
+}
@@ -0,0 +1,373 @@
+//! Voice Note Transcription
+//!
+//! Voice notes received on Telegram, WhatsApp and other channels arrive as
+//! [`MessageContent::Audio`]. When the sender has opted in, the audio is
+//! downloaded and transcribed before the message reaches the agent, so the
+//! transcription feeds intent parsing like a typed message.
+//!
+//! Transcription uses the OpenAI-compatible `/v1/audio/transcriptions` API,
+//! served either by a local Whisper server (faster-whisper-server, LocalAI,
+//! whisper.cpp) or by a hosted API. Opt-in is per user and kept in the
+//! encrypted memory store; it is off by default.
+
+use super::{MessageContent, UserMessage};
+use crate::error::RuntimeError;
+use crate::memory::EncryptedMemoryStore;
+use async_trait::async_trait;
+use futures::StreamExt;
+use serde::Deserialize;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Default Whisper model for hosted APIs
+pub const WHISPER_MODEL: &str = "whisper-1";
+
+/// Largest voice note downloaded for transcription (25 MB, the OpenAI limit)
+pub const MAX_VOICE_NOTE_BYTES: usize = 25 * 1024 * 1024;
+
+/// Metadata key holding the detected language of a transcribed message
+pub const LANGUAGE_METADATA_KEY: &str = "language";
+
+/// Preference key prefix for per-user opt-in
+const OPT_IN_PREFIX: &str = "voice_transcription:";
+
+/// Transcribed speech
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Transcription {
+    /// Transcribed text
+    pub text: String,
+
+    /// Detected language (ISO 639-1 where known)
+    pub language: Option<String>,
+}
+
+/// Speech-to-text backend
+#[async_trait]
+pub trait Transcriber: Send + Sync {
+    /// Transcribe `audio` encoded as `mime_type`
+    async fn transcribe(
+        &self,
+        audio: Vec<u8>,
+        mime_type: &str,
+    ) -> Result<Transcription, RuntimeError>;
+
+    /// Backend name
+    fn name(&self) -> &str;
+}
+
+/// Whisper over the OpenAI-compatible transcription API
+pub struct WhisperTranscriber {
+    endpoint: String,
+    api_key: Option<String>,
+    model: String,
+    client: reqwest::Client,
+}
+
+impl WhisperTranscriber {
+    /// Local Whisper server at `endpoint` (e.g. `http://localhost:8000`)
+    pub fn local(endpoint: &str, model: &str) -> Self {
+        Self {
+            endpoint: endpoint.trim_end_matches('/').to_string(),
+            api_key: None,
+            model: model.to_string(),
+            client: reqwest::Client::new(),
+        }
+    }
+
+    /// OpenAI's hosted Whisper
+    pub fn openai(api_key: &str) -> Self {
+        Self::api("https://api.openai.com", api_key, WHISPER_MODEL)
+    }
+
+    /// Any hosted API implementing `/v1/audio/transcriptions`
+    pub fn api(endpoint: &str, api_key: &str, model: &str) -> Self {
+        Self {
+            api_key: Some(api_key.to_string()),
+            ..Self::local(endpoint, model)
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct WhisperResponse {
+    text: String,
+    #[serde(default)]
+    language: Option<String>,
+}
+
+#[async_trait]
+impl Transcriber for WhisperTranscriber {
+    async fn transcribe(
+        &self,
+        audio: Vec<u8>,
+        mime_type: &str,
+    ) -> Result<Transcription, RuntimeError> {
+        let file = reqwest::multipart::Part::bytes(audio)
+            .file_name(format!("voice.{}", audio_extension(mime_type)))
+            .mime_str(mime_type)
+            .map_err(|e| RuntimeError::ExecutionError(format!("Invalid audio type: {}", e)))?;
+        let form = reqwest::multipart::Form::new()
+            .part("file", file)
+            .text("model", self.model.clone())
+            // verbose_json carries the detected language
+            .text("response_format", "verbose_json");
+
+        let mut request = self
+            .client
+            .post(format!("{}/v1/audio/transcriptions", self.endpoint))
+            .multipart(form);
+        if let Some(api_key) = &self.api_key {
+            request = request.header("Authorization", format!("Bearer {}", api_key));
+        }
+        let response = request.send().await.map_err(|e| {
+            RuntimeError::ExecutionError(format!("Transcription request failed: {}", e))
+        })?;
+
+        if !response.status().is_success() {
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(RuntimeError::ExecutionError(format!(
+                "Transcription error: {}",
+                error_text
+            )));
+        }
+
+        let result: WhisperResponse = response.json().await.map_err(|e| {
+            RuntimeError::ExecutionError(format!("Transcription parse failed: {}", e))
+        })?;
+        Ok(Transcription {
+            text: result.text.trim().to_string(),
+            language: result.language.as_deref().and_then(language_code),
+        })
+    }
+
+    fn name(&self) -> &str {
+        "whisper"
+    }
+}
+
+/// Transcribes opted-in users' voice notes before intent parsing
+pub struct VoiceTranscription {
+    transcriber: Arc<dyn Transcriber>,
+    memory: Arc<EncryptedMemoryStore>,
+    client: reqwest::Client,
+    timeout: Duration,
+}
+
+impl VoiceTranscription {
+    /// Create pipeline storing opt-in in `memory`
+    pub fn new(transcriber: Arc<dyn Transcriber>, memory: Arc<EncryptedMemoryStore>) -> Self {
+        Self {
+            transcriber,
+            memory,
+            client: reqwest::Client::new(),
+            timeout: Duration::from_secs(60),
+        }
+    }
+
+    /// Opt `user_id` in or out of voice transcription
+    pub fn set_enabled(&self, user_id: &str, enabled: bool) {
+        self.memory.set_preference(
+            &format!("{}{}", OPT_IN_PREFIX, user_id),
+            if enabled { "on" } else { "off" },
+        );
+    }
+
+    /// Whether `user_id` has opted in
+    pub fn is_enabled(&self, user_id: &str) -> bool {
+        self.memory
+            .get_preference(&format!("{}{}", OPT_IN_PREFIX, user_id))
+            .is_some_and(|value| value == "on")
+    }
+
+    /// Transcribe `message` in place if it is an untranscribed voice note
+    /// from an opted-in sender, returning whether it was transcribed
+    ///
+    /// The detected language is stored under [`LANGUAGE_METADATA_KEY`].
+    pub async fn process(&self, message: &mut UserMessage) -> Result<bool, RuntimeError> {
+        let MessageContent::Audio {
+            url,
+            transcription: None,
+        } = &message.content
+        else {
+            return Ok(false);
+        };
+        if !self.is_enabled(&message.sender) {
+            return Ok(false);
+        }
+
+        let (audio, mime_type) = self.download(url).await?;
+        let result =
+            tokio::time::timeout(self.timeout, self.transcriber.transcribe(audio, &mime_type))
+                .await
+                .map_err(|_| RuntimeError::Timeout("Voice note transcription".to_string()))??;
+
+        tracing::debug!(
+            "Transcribed voice note from {} with {} ({:?})",
+            message.sender,
+            self.transcriber.name(),
+            result.language
+        );
+        if let Some(language) = result.language {
+            message
+                .metadata
+                .insert(LANGUAGE_METADATA_KEY.to_string(), language);
+        }
+        if let MessageContent::Audio { transcription, .. } = &mut message.content {
+            *transcription = Some(result.text);
+        }
+        Ok(true)
+    }
+
+    async fn download(&self, url: &str) -> Result<(Vec<u8>, String), RuntimeError> {
+        let response = self
+            .client
+            .get(url)
+            .timeout(self.timeout)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|e| {
+                RuntimeError::ExecutionError(format!("Voice note download failed: {}", e))
+            })?;
+
+        let mime_type = response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .filter(|value| value.starts_with("audio/"))
+            // Telegram and WhatsApp voice notes are Opus in Ogg
+            .unwrap_or("audio/ogg")
+            .to_string();
+
+        let mut audio = Vec::new();
+        let mut body = response.bytes_stream();
+        while let Some(bytes) = body.next().await {
+            let bytes = bytes.map_err(|e| {
+                RuntimeError::ExecutionError(format!("Voice note download failed: {}", e))
+            })?;
+            if audio.len() + bytes.len() > MAX_VOICE_NOTE_BYTES {
+                return Err(RuntimeError::ExecutionError(
+                    "Voice note too large to transcribe".to_string(),
+                ));
+            }
+            audio.extend_from_slice(&bytes);
+        }
+        Ok((audio, mime_type))
+    }
+}
+
+/// File extension Whisper servers use to pick a decoder
+fn audio_extension(mime_type: &str) -> &'static str {
+    match mime_type.split(';').next().unwrap_or_default().trim() {
+        "audio/mpeg" | "audio/mp3" => "mp3",
+        "audio/mp4" | "audio/m4a" | "audio/x-m4a" => "m4a",
+        "audio/wav" | "audio/x-wav" => "wav",
+        "audio/webm" => "webm",
+        "audio/flac" => "flac",
+        _ => "ogg",
+    }
+}
+
+/// Normalize a Whisper language to ISO 639-1
+///
+/// Hosted Whisper reports language names ("english"), local servers
+/// usually report codes ("en").
+fn language_code(language: &str) -> Option<String> {
+    let language = language.trim().to_lowercase();
+    let code = match language.as_str() {
+        "" => return None,
+        "english" => "en",
+        "french" => "fr",
+        "spanish" => "es",
+        "german" => "de",
+        "italian" => "it",
+        "portuguese" => "pt",
+        "dutch" => "nl",
+        "russian" => "ru",
+        "arabic" => "ar",
+        "chinese" => "zh",
+        "japanese" => "ja",
+        "korean" => "ko",
+        "hindi" => "hi",
+        "turkish" => "tr",
+        "swahili" => "sw",
+        _ => return Some(language),
+    };
+    Some(code.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    struct FixedTranscriber;
+
+    #[async_trait]
+    impl Transcriber for FixedTranscriber {
+        async fn transcribe(
+            &self,
+            _audio: Vec<u8>,
+            _mime_type: &str,
+        ) -> Result<Transcription, RuntimeError> {
+            Ok(Transcription {
+                text: "send 5 FAT to alice".to_string(),
+                language: Some("en".to_string()),
+            })
+        }
+
+        fn name(&self) -> &str {
+            "fixed"
+        }
+    }
+
+    fn voice_note(sender: &str) -> UserMessage {
+        UserMessage {
+            channel: "telegram:1".to_string(),
+            sender: sender.to_string(),
+            content: MessageContent::Audio {
+                // Never fetched: the sender has not opted in
+                url: "http://127.0.0.1:9/voice.ogg".to_string(),
+                transcription: None,
+            },
+            timestamp: 0,
+            message_id: None,
+            thread_id: None,
+            reply_to: None,
+            attachments: Vec::new(),
+            metadata: HashMap::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_requires_opt_in() {
+        let memory = Arc::new(EncryptedMemoryStore::new([7u8; 32]));
+        let pipeline = VoiceTranscription::new(Arc::new(FixedTranscriber), memory.clone());
+        assert!(!pipeline.is_enabled("alice"));
+
+        let mut message = voice_note("alice");
+        assert!(!pipeline.process(&mut message).await.unwrap());
+        assert!(matches!(
+            message.content,
+            MessageContent::Audio {
+                transcription: None,
+                ..
+            }
+        ));
+
+        pipeline.set_enabled("alice", true);
+        assert!(pipeline.is_enabled("alice"));
+        assert!(!pipeline.is_enabled("bob"));
+        pipeline.set_enabled("alice", false);
+        assert!(!pipeline.is_enabled("alice"));
+    }
+
+    #[test]
+    fn test_language_and_extension() {
+        assert_eq!(language_code("English").as_deref(), Some("en"));
+        assert_eq!(language_code("fr").as_deref(), Some("fr"));
+        assert_eq!(language_code(" "), None);
+        assert_eq!(audio_extension("audio/ogg; codecs=opus"), "ogg");
+        assert_eq!(audio_extension("audio/mpeg"), "mp3");
+    }
+}
@@ -35,6 +35,9 @@ pub enum RuntimeError {
     #[error("Timeout: {0}")]
     Timeout(String),
 
+    #[error("Overloaded: {0}")]
+    Overloaded(String),
+
     #[error("Crypto error: {0}")]
     CryptoError(String),
 
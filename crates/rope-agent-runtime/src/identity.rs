@@ -5,7 +5,7 @@
 
 use crate::error::AuthError;
 use crate::intent::ActionType;
-use rope_crypto::hybrid::HybridSignature;
+use rope_crypto::hybrid::{HybridPublicKey, HybridSignature};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::time::Duration;
@@ -55,6 +55,15 @@ impl DatawalletIdentity {
     pub fn seed(&self) -> &[u8] {
         &self.node_id
     }
+
+    /// Wallet signing key: Ed25519 followed by the Dilithium key, if any
+    pub fn signing_key(&self) -> Option<HybridPublicKey> {
+        let ed25519: [u8; 32] = self.public_key.get(..32)?.try_into().ok()?;
+        Some(HybridPublicKey::new_signing(
+            ed25519,
+            self.public_key[32..].to_vec(),
+        ))
+    }
 }
 
 /// RopeAgent identity with authorization management
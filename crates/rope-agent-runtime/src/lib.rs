@@ -4,7 +4,7 @@
 //!
 //! This crate provides:
 //! - Secure local execution environment
-//! - Message routing from chat platforms (WhatsApp, Telegram, Slack, Discord)
+//! - Message routing from chat platforms (WhatsApp, Telegram, Slack, Discord, Matrix, email)
 //! - AI-powered intent parsing and skill execution
 //! - Encrypted memory persistence with OES
 //! - String Lattice connectivity for Testimony consensus
@@ -53,14 +53,17 @@ pub mod skills;
 pub mod websocket;
 
 pub use agents::*;
-pub use ai::{AIModelConfig, AIModelManager, ChatMessage, CompletionRequest, CompletionResponse};
+pub use ai::{
+    AIModelConfig, AIModelManager, ChatMessage, CompletionChunk, CompletionRequest,
+    CompletionResponse, CompletionStream,
+};
 pub use channels::*;
 pub use config::RuntimeConfig;
 pub use error::RuntimeError;
 pub use identity::*;
 pub use intent::*;
 pub use lattice_client::LatticeClient;
-pub use memory::EncryptedMemoryStore;
+pub use memory::{Embedder, EncryptedMemoryStore, RecalledMemory};
 pub use runtime::RopeAgentRuntime;
 pub use sandbox::{Capability, SandboxConfig, SandboxedExecutor};
 pub use security::{InputValidator, RateLimiter, TieredRateLimiter, ValidationError};
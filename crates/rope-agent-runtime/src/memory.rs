@@ -2,12 +2,31 @@
 //!
 //! Persistent encrypted storage for agent state, conversation history,
 //! and credentials using OES (Organic Encryption System).
+//!
+//! Semantic memory keeps embedded facts per user so agents can recall the
+//! ones relevant to a message and add them to the prompt. Each user's
+//! memories are sealed with a key derived for that user.
 
 use crate::error::RuntimeError;
 use parking_lot::RwLock;
+use rope_crypto::secret::{ct_eq, SecretBytes};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::PathBuf;
+use std::sync::Arc;
+
+/// Maximum semantic memories kept per user; the oldest are dropped first
+pub const MAX_SEMANTIC_MEMORIES: usize = 10_000;
+
+/// Turns text into vectors for semantic memory
+#[async_trait::async_trait]
+pub trait Embedder: Send + Sync {
+    /// Embed `texts`, one vector per text, in order
+    async fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>, RuntimeError>;
+
+    /// Identifies the model; vectors from different models are never compared
+    fn model_id(&self) -> String;
+}
 
 /// Encrypted memory store for RopeAgent
 pub struct EncryptedMemoryStore {
@@ -18,10 +37,13 @@ pub struct EncryptedMemoryStore {
     cache: RwLock<MemoryCache>,
 
     /// Encryption key (derived from OES)
-    encryption_key: [u8; 32],
+    encryption_key: SecretBytes,
 
     /// Dirty flag (needs flush)
     dirty: RwLock<bool>,
+
+    /// Model embedding semantic memories
+    embedder: RwLock<Option<Arc<dyn Embedder>>>,
 }
 
 /// In-memory cache structure
@@ -47,6 +69,10 @@ struct MemoryCache {
 
     /// Event log
     events: Vec<EventRecord>,
+
+    /// Sealed semantic memories by user
+    #[serde(default)]
+    semantic: HashMap<String, Vec<Vec<u8>>>,
 }
 
 impl EncryptedMemoryStore {
@@ -55,8 +81,9 @@ impl EncryptedMemoryStore {
         Self {
             path: PathBuf::new(),
             cache: RwLock::new(MemoryCache::default()),
-            encryption_key,
+            encryption_key: SecretBytes::from(encryption_key),
             dirty: RwLock::new(false),
+            embedder: RwLock::new(None),
         }
     }
 
@@ -68,8 +95,9 @@ impl EncryptedMemoryStore {
         let mut store = Self {
             path: path.clone(),
             cache: RwLock::new(MemoryCache::default()),
-            encryption_key,
+            encryption_key: SecretBytes::from(encryption_key),
             dirty: RwLock::new(false),
+            embedder: RwLock::new(None),
         };
 
         // Load existing data if file exists
@@ -124,62 +152,20 @@ impl EncryptedMemoryStore {
 
     /// Encrypt data
     fn encrypt(&self, data: &[u8]) -> Result<Vec<u8>, RuntimeError> {
-        // Simple XOR encryption (in production, use AES-GCM with OES key)
-        let mut result = Vec::with_capacity(data.len() + 32);
-
-        // Generate nonce
-        let nonce: [u8; 16] = rand_bytes();
-        result.extend_from_slice(&nonce);
-
-        // Derive encryption key with nonce
-        let key = blake3::hash(&[&self.encryption_key[..], &nonce[..]].concat());
-
-        // Encrypt (XOR for simplicity - use proper AEAD in production)
-        for (i, byte) in data.iter().enumerate() {
-            result.push(byte ^ key.as_bytes()[i % 32]);
-        }
-
-        // Append MAC
-        let mac = blake3::hash(&[key.as_bytes(), data].concat());
-        result.extend_from_slice(&mac.as_bytes()[..16]);
-
-        Ok(result)
+        seal(self.encryption_key.expose_secret(), data)
     }
 
     /// Decrypt data
     fn decrypt(&self, data: &[u8]) -> Result<Vec<u8>, RuntimeError> {
-        if data.len() < 32 {
-            return Err(RuntimeError::CryptoError("Data too short".to_string()));
-        }
-
-        // Extract nonce
-        let nonce = &data[..16];
-
-        // Extract ciphertext and MAC
-        let ciphertext = &data[16..data.len() - 16];
-        let mac = &data[data.len() - 16..];
-
-        // Derive encryption key
-        let key = blake3::hash(&[&self.encryption_key[..], nonce].concat());
-
-        // Decrypt
-        let mut plaintext = Vec::with_capacity(ciphertext.len());
-        for (i, byte) in ciphertext.iter().enumerate() {
-            plaintext.push(byte ^ key.as_bytes()[i % 32]);
-        }
-
-        // Verify MAC
-        let mut mac_input = Vec::new();
-        mac_input.extend_from_slice(key.as_bytes());
-        mac_input.extend_from_slice(&plaintext);
-        let expected_mac = blake3::hash(&mac_input);
-        if &expected_mac.as_bytes()[..16] != mac {
-            return Err(RuntimeError::CryptoError(
-                "MAC verification failed".to_string(),
-            ));
-        }
+        unseal(self.encryption_key.expose_secret(), data)
+    }
 
-        Ok(plaintext)
+    /// Key sealing the semantic memories of `user_id`
+    fn user_key(&self, user_id: &str) -> SecretBytes {
+        let mut hasher = blake3::Hasher::new_derive_key("rope agent semantic memory");
+        hasher.update(self.encryption_key.expose_secret());
+        hasher.update(user_id.as_bytes());
+        SecretBytes::from(*hasher.finalize().as_bytes())
     }
 
     // === Conversation History ===
@@ -314,6 +300,114 @@ impl EncryptedMemoryStore {
         Ok(())
     }
 
+    // === Semantic Memory ===
+
+    /// Set the model that embeds semantic memories
+    pub fn set_embedder(&self, embedder: Arc<dyn Embedder>) {
+        *self.embedder.write() = Some(embedder);
+    }
+
+    fn embedder(&self) -> Result<Arc<dyn Embedder>, RuntimeError> {
+        self.embedder.read().clone().ok_or_else(|| {
+            RuntimeError::ConfigError("No embedding model set for semantic memory".to_string())
+        })
+    }
+
+    /// Embed `text` and add it to the semantic memory of `user_id`,
+    /// returning the new memory's ID
+    pub async fn remember(&self, user_id: &str, text: &str) -> Result<String, RuntimeError> {
+        let embedder = self.embedder()?;
+        let embedding = embed_one(embedder.as_ref(), text).await?;
+
+        let memory = SemanticMemory {
+            id: uuid::Uuid::new_v4().to_string(),
+            text: text.to_string(),
+            embedding,
+            model: embedder.model_id(),
+            timestamp: chrono::Utc::now().timestamp(),
+        };
+        self.insert_semantic(user_id, &memory)?;
+        Ok(memory.id)
+    }
+
+    /// The `k` memories of `user_id` closest in meaning to `query`, best first
+    ///
+    /// Only that user's memories are searched. Memories embedded by another
+    /// model than the current one are skipped.
+    pub async fn recall_similar(
+        &self,
+        user_id: &str,
+        query: &str,
+        k: usize,
+    ) -> Result<Vec<RecalledMemory>, RuntimeError> {
+        if k == 0 {
+            return Ok(Vec::new());
+        }
+        let embedder = self.embedder()?;
+        let query = embed_one(embedder.as_ref(), query).await?;
+        Ok(self.search_semantic(user_id, &query, &embedder.model_id(), k))
+    }
+
+    /// Delete every semantic memory of `user_id`, returning how many there were
+    pub fn forget_memories(&self, user_id: &str) -> usize {
+        let removed = self
+            .cache
+            .write()
+            .semantic
+            .remove(user_id)
+            .map_or(0, |memories| memories.len());
+        if removed > 0 {
+            *self.dirty.write() = true;
+        }
+        removed
+    }
+
+    fn insert_semantic(&self, user_id: &str, memory: &SemanticMemory) -> Result<(), RuntimeError> {
+        let serialized = serde_json::to_vec(memory)
+            .map_err(|e| RuntimeError::SerializationError(e.to_string()))?;
+        let sealed = seal(self.user_key(user_id).expose_secret(), &serialized)?;
+
+        let mut cache = self.cache.write();
+        let memories = cache.semantic.entry(user_id.to_string()).or_default();
+        memories.push(sealed);
+        if memories.len() > MAX_SEMANTIC_MEMORIES {
+            memories.remove(0);
+        }
+
+        *self.dirty.write() = true;
+        Ok(())
+    }
+
+    fn search_semantic(
+        &self,
+        user_id: &str,
+        query: &[f32],
+        model: &str,
+        k: usize,
+    ) -> Vec<RecalledMemory> {
+        let key = self.user_key(user_id);
+        let cache = self.cache.read();
+        let mut scored: Vec<RecalledMemory> = cache
+            .semantic
+            .get(user_id)
+            .into_iter()
+            .flatten()
+            .filter_map(|sealed| unseal(key.expose_secret(), sealed).ok())
+            .filter_map(|plain| serde_json::from_slice::<SemanticMemory>(&plain).ok())
+            .filter(|m| m.model == model && m.embedding.len() == query.len())
+            .map(|m| RecalledMemory {
+                score: cosine_similarity(query, &m.embedding),
+                id: m.id,
+                text: m.text,
+                timestamp: m.timestamp,
+            })
+            .collect();
+
+        scored.sort_by(|a, b| b.score.total_cmp(&a.score));
+        scored.truncate(k);
+        scored
+    }
+
     // === Data Erasure (GDPR) ===
 
     /// Remove all data related to a string ID
@@ -337,6 +431,40 @@ impl Drop for EncryptedMemoryStore {
     }
 }
 
+async fn embed_one(embedder: &dyn Embedder, text: &str) -> Result<Vec<f32>, RuntimeError> {
+    embedder
+        .embed(&[text.to_string()])
+        .await?
+        .pop()
+        .ok_or_else(|| RuntimeError::ExecutionError("Embedder returned no vector".to_string()))
+}
+
+/// Stored semantic memory (sealed with the owner's key)
+#[derive(Serialize, Deserialize)]
+struct SemanticMemory {
+    id: String,
+    text: String,
+    embedding: Vec<f32>,
+    model: String,
+    timestamp: i64,
+}
+
+/// Semantic memory returned by [`EncryptedMemoryStore::recall_similar`]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RecalledMemory {
+    /// Memory ID
+    pub id: String,
+
+    /// Remembered text
+    pub text: String,
+
+    /// When it was stored
+    pub timestamp: i64,
+
+    /// Cosine similarity to the query
+    pub score: f32,
+}
+
 /// Conversation history
 #[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct ConversationHistory {
@@ -449,6 +577,78 @@ pub enum Event {
     SecurityAlert { alert_type: String, details: String },
 }
 
+/// Encrypt `data` under `key`
+fn seal(key: &[u8], data: &[u8]) -> Result<Vec<u8>, RuntimeError> {
+    // Simple XOR encryption (in production, use AES-GCM with OES key)
+    let mut result = Vec::with_capacity(data.len() + 32);
+
+    // Generate nonce
+    let nonce: [u8; 16] = rand_bytes();
+    result.extend_from_slice(&nonce);
+
+    // Derive encryption key with nonce
+    let key = blake3::hash(&[key, &nonce[..]].concat());
+
+    // Encrypt (XOR for simplicity - use proper AEAD in production)
+    for (i, byte) in data.iter().enumerate() {
+        result.push(byte ^ key.as_bytes()[i % 32]);
+    }
+
+    // Append MAC
+    let mac = blake3::hash(&[key.as_bytes(), data].concat());
+    result.extend_from_slice(&mac.as_bytes()[..16]);
+
+    Ok(result)
+}
+
+/// Decrypt data produced by [`seal`] under `key`
+fn unseal(key: &[u8], data: &[u8]) -> Result<Vec<u8>, RuntimeError> {
+    if data.len() < 32 {
+        return Err(RuntimeError::CryptoError("Data too short".to_string()));
+    }
+
+    // Extract nonce
+    let nonce = &data[..16];
+
+    // Extract ciphertext and MAC
+    let ciphertext = &data[16..data.len() - 16];
+    let mac = &data[data.len() - 16..];
+
+    // Derive encryption key
+    let key = blake3::hash(&[key, nonce].concat());
+
+    // Decrypt
+    let mut plaintext = Vec::with_capacity(ciphertext.len());
+    for (i, byte) in ciphertext.iter().enumerate() {
+        plaintext.push(byte ^ key.as_bytes()[i % 32]);
+    }
+
+    // Verify MAC
+    let mut mac_input = Vec::new();
+    mac_input.extend_from_slice(key.as_bytes());
+    mac_input.extend_from_slice(&plaintext);
+    let expected_mac = blake3::hash(&mac_input);
+    if !ct_eq(&expected_mac.as_bytes()[..16], mac) {
+        return Err(RuntimeError::CryptoError(
+            "MAC verification failed".to_string(),
+        ));
+    }
+
+    Ok(plaintext)
+}
+
+/// Cosine similarity; 0 for zero vectors
+fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm = |v: &[f32]| v.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let denominator = norm(a) * norm(b);
+    if denominator == 0.0 {
+        0.0
+    } else {
+        dot / denominator
+    }
+}
+
 /// Generate random bytes
 fn rand_bytes<const N: usize>() -> [u8; N] {
     let mut bytes = [0u8; N];
@@ -507,4 +707,105 @@ mod tests {
 
         assert_eq!(creds.to_vec(), retrieved);
     }
+
+    /// Bag-of-words embedder: texts sharing words point the same way
+    struct WordEmbedder(&'static str);
+
+    #[async_trait::async_trait]
+    impl Embedder for WordEmbedder {
+        async fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>, RuntimeError> {
+            Ok(texts
+                .iter()
+                .map(|text| {
+                    let mut v = vec![0.0; 32];
+                    for word in text.to_lowercase().split_whitespace() {
+                        v[blake3::hash(word.as_bytes()).as_bytes()[0] as usize % 32] += 1.0;
+                    }
+                    v
+                })
+                .collect())
+        }
+
+        fn model_id(&self) -> String {
+            self.0.to_string()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_recall_similar() {
+        let store = EncryptedMemoryStore::new([42u8; 32]);
+        assert!(store.recall_similar("alice", "anything", 3).await.is_err());
+        store.set_embedder(Arc::new(WordEmbedder("words-v1")));
+
+        store
+            .remember("alice", "my cat is named Pixel")
+            .await
+            .unwrap();
+        store
+            .remember("alice", "I stake FAT every friday")
+            .await
+            .unwrap();
+        store
+            .remember("bob", "my cat is named Tiger")
+            .await
+            .unwrap();
+
+        let recalled = store
+            .recall_similar("alice", "what is my cat named", 1)
+            .await
+            .unwrap();
+        assert_eq!(recalled.len(), 1);
+        assert_eq!(recalled[0].text, "my cat is named Pixel");
+
+        // Users never see each other's memories
+        let recalled = store.recall_similar("bob", "cat", 5).await.unwrap();
+        assert_eq!(recalled.len(), 1);
+        assert_eq!(recalled[0].text, "my cat is named Tiger");
+        assert!(store
+            .recall_similar("carol", "cat", 5)
+            .await
+            .unwrap()
+            .is_empty());
+
+        // Sealed per user, so one user's key cannot open another's entries
+        let cache = store.cache.read();
+        let sealed = &cache.semantic["bob"][0];
+        assert!(unseal(store.user_key("alice").expose_secret(), sealed).is_err());
+        drop(cache);
+
+        // Vectors from another model are not compared
+        store.set_embedder(Arc::new(WordEmbedder("words-v2")));
+        assert!(store
+            .recall_similar("alice", "cat", 5)
+            .await
+            .unwrap()
+            .is_empty());
+
+        assert_eq!(store.forget_memories("alice"), 2);
+    }
+
+    #[tokio::test]
+    async fn test_semantic_memory_persists() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("memory.enc");
+        {
+            let store = EncryptedMemoryStore::open(&path, b"seed").unwrap();
+            store.set_embedder(Arc::new(WordEmbedder("words-v1")));
+            store
+                .remember("alice", "favourite validator is rope-eu-1")
+                .await
+                .unwrap();
+            store.flush().unwrap();
+        }
+        let raw = std::fs::read(&path).unwrap();
+        assert!(!raw.windows(9).any(|w| w == b"validator"));
+
+        let store = EncryptedMemoryStore::open(&path, b"seed").unwrap();
+        store.set_embedder(Arc::new(WordEmbedder("words-v1")));
+        let recalled = store
+            .recall_similar("alice", "which validator", 1)
+            .await
+            .unwrap();
+        assert_eq!(recalled[0].text, "favourite validator is rope-eu-1");
+    }
 }
@@ -2,14 +2,17 @@
 //!
 //! Main runtime that orchestrates all components.
 
-use crate::agents::{PersonalAgent, PersonalCapability};
-use crate::channels::{AgentResponse, MessageChannel, MessageRouter, UserMessage};
+use crate::agents::{DeliberationCoordinator, PersonalAgent, PersonalCapability, SpendingPolicy};
+use crate::channels::{
+    AgentResponse, MessageChannel, MessageRouter, Transcriber, UserMessage, VoiceTranscription,
+};
 use crate::config::RuntimeConfig;
 use crate::error::RuntimeError;
 use crate::identity::DatawalletIdentity;
 use crate::lattice_client::{LatticeClient, LatticeEvent, TestimonyStatus};
 use crate::memory::EncryptedMemoryStore;
 use crate::skills::SkillRegistry;
+use rope_crypto::hybrid::HybridSignature;
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::RwLock;
@@ -34,6 +37,9 @@ pub struct RopeAgentRuntime {
     /// Skill registry
     skills: Arc<RwLock<SkillRegistry>>,
 
+    /// Voice note transcription, if configured
+    transcription: Arc<RwLock<Option<VoiceTranscription>>>,
+
     /// Runtime configuration
     config: RuntimeConfig,
 
@@ -66,6 +72,7 @@ impl RopeAgentRuntime {
             lattice_client: Arc::new(RwLock::new(lattice_client)),
             memory: Arc::new(memory),
             skills: Arc::new(RwLock::new(skills)),
+            transcription: Arc::new(RwLock::new(None)),
             config,
             shutdown: Arc::new(RwLock::new(false)),
         })
@@ -123,6 +130,7 @@ impl RopeAgentRuntime {
     pub async fn load_skill(&self, skill: crate::skills::Skill) -> Result<(), RuntimeError> {
         // Verify governance approval
         skill.verify_governance_approval()?;
+        skill.verify_no_secrets()?;
 
         let skill_name = skill.name.clone();
 
@@ -136,6 +144,63 @@ impl RopeAgentRuntime {
         Ok(())
     }
 
+    /// Require agreement from independent agents before sensitive actions
+    /// are submitted for Testimony
+    pub async fn set_deliberation(&self, coordinator: DeliberationCoordinator) {
+        self.agent
+            .write()
+            .await
+            .set_deliberation(Arc::new(coordinator));
+    }
+
+    /// Replace the spending limits applied to actions that move FAT
+    pub async fn set_spending_policy(&self, policy: SpendingPolicy) {
+        self.agent
+            .write()
+            .await
+            .set_spending_policy(Arc::new(policy));
+    }
+
+    /// Approve an action held by spending limits with the user's wallet
+    /// signature over its challenge
+    pub async fn approve_spending(
+        &self,
+        code: &str,
+        signature: &HybridSignature,
+    ) -> Result<(), RuntimeError> {
+        let response = self
+            .agent
+            .read()
+            .await
+            .approve_with_signature(code, signature)
+            .await?;
+        self.message_router.send_response(response).await?;
+        Ok(())
+    }
+
+    /// Transcribe voice notes from opted-in users with `transcriber`
+    pub async fn set_transcriber(&self, transcriber: Arc<dyn Transcriber>) {
+        *self.transcription.write().await =
+            Some(VoiceTranscription::new(transcriber, self.memory.clone()));
+    }
+
+    /// Opt `user_id` in or out of voice note transcription
+    pub async fn set_voice_transcription(
+        &self,
+        user_id: &str,
+        enabled: bool,
+    ) -> Result<(), RuntimeError> {
+        match self.transcription.read().await.as_ref() {
+            Some(transcription) => {
+                transcription.set_enabled(user_id, enabled);
+                Ok(())
+            }
+            None => Err(RuntimeError::ConfigError(
+                "No transcriber configured".to_string(),
+            )),
+        }
+    }
+
     /// Start runtime event loop
     pub async fn run(&self) -> Result<(), RuntimeError> {
         tracing::info!("Starting RopeAgent runtime...");
@@ -184,7 +249,7 @@ impl RopeAgentRuntime {
     }
 
     /// Handle incoming message
-    async fn handle_message(&self, message: UserMessage) -> Result<(), RuntimeError> {
+    async fn handle_message(&self, mut message: UserMessage) -> Result<(), RuntimeError> {
         tracing::debug!(
             "Received message from {}: {:?}",
             message.sender,
@@ -198,6 +263,13 @@ impl RopeAgentRuntime {
                 timestamp: message.timestamp,
             })?;
 
+        // Transcribe voice notes so they can be parsed like text
+        if let Some(transcription) = self.transcription.read().await.as_ref() {
+            if let Err(e) = transcription.process(&mut message).await {
+                tracing::warn!("Voice note transcription failed: {}", e);
+            }
+        }
+
         // Process through agent
         let response = self.agent.write().await.process_message(message).await?;
 
@@ -330,6 +402,8 @@ impl RopeAgentRuntime {
                     authorization.authorized_by.len()
                 )),
                 reply_to: action.message.message_id.clone(),
+                thread_id: action.message.thread_id.clone(),
+                attachments: Vec::new(),
             };
 
             self.message_router.send_response(response).await?;
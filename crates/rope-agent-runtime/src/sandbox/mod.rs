@@ -12,13 +12,14 @@
 //! - **Resource limits**: CPU time, memory, and execution duration caps
 //! - **Process isolation**: No shell or process spawn by default
 //!
-//! ## WASM Isolation (Planned)
+//! ## WASM Isolation
 //!
-//! Future versions will support WebAssembly-based isolation:
-//! - Skills compiled to WASM run in isolated memory space
-//! - Import/export restrictions enforce capability boundaries
-//! - Memory usage tracked per-module
-//! - Linear memory cannot exceed configured limits
+//! With the `wasm` feature, [`WasmSkillExecutor`] runs skills compiled to
+//! WebAssembly on Wasmtime:
+//! - Skills run in their own linear memory, capped by `max_memory_bytes`
+//! - Host functions are linked only for granted capabilities
+//! - Fuel and wall-clock limits stop runaway skills
+//! - Host calls are logged so executions can be replayed for audit
 //!
 //! ## Example Usage
 //!
@@ -72,6 +73,12 @@
 //! 5. **Memory Limits**: Prevent runaway memory consumption
 //! 6. **Audit Logging**: Log all capability checks for security review
 
+#[cfg(feature = "wasm")]
+mod wasm;
+
+#[cfg(feature = "wasm")]
+pub use wasm::*;
+
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 use std::time::Duration;
@@ -103,6 +110,12 @@ pub enum Capability {
 
     /// Specific file path access
     PathAccess(String),
+
+    /// Lattice queries
+    Lattice,
+
+    /// Wallet signing
+    Wallet,
 }
 
 /// Sandbox configuration
@@ -318,6 +331,9 @@ pub enum SandboxError {
     #[error("Memory limit exceeded")]
     MemoryLimitExceeded,
 
+    #[error("Fuel exhausted")]
+    FuelExhausted,
+
     #[error("Execution error: {0}")]
     ExecutionError(String),
 
@@ -0,0 +1,646 @@
+//! Wasmtime Skill Executor
+//!
+//! Runs skills compiled to WebAssembly with:
+//! - Host functions linked only for granted capabilities (network,
+//!   lattice, wallet), each call checked again against the sandbox
+//! - Fuel, memory and wall-clock limits
+//! - A replay log of every host call, so an execution can be re-run
+//!   deterministically during audit
+//!
+//! ## Skill ABI
+//!
+//! A skill exports `memory`, `alloc(len: i32) -> i32` and
+//! `execute(ptr: i32, len: i32) -> i64`. The runtime writes the input into
+//! a buffer from `alloc` and calls `execute`, which returns its output as
+//! `ptr << 32 | len`, or a negative error code.
+//!
+//! Host functions live in the `rope` namespace and share one shape,
+//! `(ptr: i32, len: i32) -> i64`: the request is read from guest memory and
+//! the response written into a buffer from `alloc`, returned the same way
+//! as `execute`'s output. A denied or failed call returns -1.
+//!
+//! | Import | Capability | Request |
+//! |--------|------------|---------|
+//! | `rope.http_get` | `Network` + allowed host | URL |
+//! | `rope.lattice_query` | `Lattice` | query |
+//! | `rope.wallet_sign` | `Wallet` | payload |
+
+use super::{
+    Capability, SandboxConfig, SandboxError, SandboxedExecutor, SecurityAuditEntry,
+    SecurityAuditLog, WasmValidator,
+};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::sync::Arc;
+use wasmtime::{
+    Caller, Config, Engine, Extern, Linker, Module, Store, StoreLimits, StoreLimitsBuilder, Trap,
+};
+
+/// Fuel units between cooperative yields, so the time limit can fire
+const FUEL_YIELD_INTERVAL: u64 = 10_000;
+
+/// Host functions and the capability each requires
+const HOST_FUNCTIONS: [(&str, Capability); 3] = [
+    ("http_get", Capability::Network),
+    ("lattice_query", Capability::Lattice),
+    ("wallet_sign", Capability::Wallet),
+];
+
+/// Services a WASM skill can reach through its host functions
+#[async_trait]
+pub trait SkillHost: Send + Sync {
+    /// Fetch a URL
+    async fn http_get(&self, url: &str) -> Result<Vec<u8>, String>;
+
+    /// Query the lattice
+    async fn lattice_query(&self, query: &str) -> Result<Vec<u8>, String>;
+
+    /// Sign a payload with the user's wallet
+    async fn wallet_sign(&self, payload: &[u8]) -> Result<Vec<u8>, String>;
+}
+
+/// A compiled skill
+pub struct WasmSkill {
+    /// Skill name
+    pub name: String,
+
+    /// BLAKE3 hash of the module bytes (hex)
+    pub module_hash: String,
+
+    module: Module,
+}
+
+/// One host call made by a skill
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HostCall {
+    /// Host function name
+    pub function: String,
+
+    /// Request read from guest memory
+    pub request: Vec<u8>,
+
+    /// Response handed back, or why the call failed
+    pub response: Result<Vec<u8>, String>,
+}
+
+/// Everything needed to re-run an execution without touching the host
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReplayLog {
+    /// Skill name
+    pub skill_name: String,
+
+    /// Module the log was recorded with
+    pub module_hash: String,
+
+    /// Input passed to `execute`
+    pub input: Vec<u8>,
+
+    /// Host calls, in order
+    pub calls: Vec<HostCall>,
+
+    /// Output returned by `execute`
+    pub output: Vec<u8>,
+
+    /// Fuel consumed
+    pub fuel_consumed: u64,
+}
+
+/// WebAssembly skill executor
+pub struct WasmSkillExecutor {
+    engine: Engine,
+    sandbox: Arc<SandboxedExecutor>,
+    validator: WasmValidator,
+    fuel: u64,
+    audit: Option<Arc<SecurityAuditLog>>,
+}
+
+impl WasmSkillExecutor {
+    /// Create executor enforcing `config`, granting each run `fuel` units
+    pub fn new(config: SandboxConfig, fuel: u64) -> Result<Self, SandboxError> {
+        let mut wasm_config = Config::new();
+        wasm_config
+            .async_support(true)
+            .consume_fuel(true)
+            // Same results on every host, required for replay
+            .cranelift_nan_canonicalization(true)
+            .wasm_threads(false);
+        let engine = Engine::new(&wasm_config)
+            .map_err(|e| SandboxError::ExecutionError(format!("Engine setup failed: {}", e)))?;
+
+        Ok(Self {
+            engine,
+            sandbox: Arc::new(SandboxedExecutor::new(config)),
+            validator: WasmValidator::default(),
+            fuel,
+            audit: None,
+        })
+    }
+
+    /// Record capability decisions in `audit`
+    pub fn with_audit_log(mut self, audit: Arc<SecurityAuditLog>) -> Self {
+        self.audit = Some(audit);
+        self
+    }
+
+    /// Validate and compile a skill
+    ///
+    /// Modules importing anything but the `rope` host functions, or host
+    /// functions for capabilities the sandbox does not grant, are refused.
+    pub fn compile(&self, name: &str, bytes: &[u8]) -> Result<WasmSkill, SandboxError> {
+        self.validator.validate(bytes)?;
+        let module = Module::new(&self.engine, bytes)
+            .map_err(|e| SandboxError::WasmValidationFailed(e.to_string()))?;
+
+        for import in module.imports() {
+            let capability = HOST_FUNCTIONS
+                .iter()
+                .find(|(function, _)| import.module() == "rope" && import.name() == *function)
+                .map(|(_, capability)| capability)
+                .ok_or_else(|| {
+                    SandboxError::WasmValidationFailed(format!(
+                        "Unknown import {}.{}",
+                        import.module(),
+                        import.name()
+                    ))
+                })?;
+            if !self.sandbox.has_capability(capability) {
+                return Err(SandboxError::CapabilityDenied(capability.clone()));
+            }
+        }
+        for export in &self.validator.required_exports {
+            if module.get_export(export).is_none() {
+                return Err(SandboxError::WasmValidationFailed(format!(
+                    "Missing export {}",
+                    export
+                )));
+            }
+        }
+
+        Ok(WasmSkill {
+            name: name.to_string(),
+            module_hash: blake3::hash(bytes).to_hex().to_string(),
+            module,
+        })
+    }
+
+    /// Run `skill` on `input`, returning the replay log with its output
+    pub async fn execute(
+        &self,
+        skill: &WasmSkill,
+        host: Arc<dyn SkillHost>,
+        input: &[u8],
+    ) -> Result<ReplayLog, SandboxError> {
+        let state = self.state(skill, Source::Live(host));
+        let (state, output, fuel_consumed) = self.run(skill, state, input).await?;
+
+        Ok(ReplayLog {
+            skill_name: skill.name.clone(),
+            module_hash: skill.module_hash.clone(),
+            input: input.to_vec(),
+            calls: state.calls,
+            output,
+            fuel_consumed,
+        })
+    }
+
+    /// Re-run a recorded execution against the recorded host responses
+    ///
+    /// Fails unless the skill makes the same host calls in the same order
+    /// and produces the same output with the same fuel.
+    pub async fn replay(&self, skill: &WasmSkill, log: &ReplayLog) -> Result<(), SandboxError> {
+        if log.module_hash != skill.module_hash {
+            return Err(SandboxError::ExecutionError(
+                "Replay log was recorded with another module".to_string(),
+            ));
+        }
+
+        let recorded = log.calls.iter().cloned().collect();
+        let state = self.state(skill, Source::Replay(recorded));
+        let (state, output, fuel_consumed) = self.run(skill, state, &log.input).await?;
+
+        if let Some(divergence) = state.divergence {
+            return Err(SandboxError::ExecutionError(divergence));
+        }
+        if state.calls.len() != log.calls.len() {
+            return Err(SandboxError::ExecutionError(format!(
+                "Replay made {} host calls, log has {}",
+                state.calls.len(),
+                log.calls.len()
+            )));
+        }
+        if output != log.output || fuel_consumed != log.fuel_consumed {
+            return Err(SandboxError::ExecutionError(
+                "Replay output differs from the log".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    fn state(&self, skill: &WasmSkill, source: Source) -> HostState {
+        HostState {
+            skill_name: skill.name.clone(),
+            limits: StoreLimitsBuilder::new()
+                .memory_size(self.sandbox.max_memory_bytes() as usize)
+                .instances(1)
+                .build(),
+            sandbox: self.sandbox.clone(),
+            audit: self.audit.clone(),
+            source,
+            calls: Vec::new(),
+            divergence: None,
+        }
+    }
+
+    async fn run(
+        &self,
+        skill: &WasmSkill,
+        state: HostState,
+        input: &[u8],
+    ) -> Result<(HostState, Vec<u8>, u64), SandboxError> {
+        let mut store = Store::new(&self.engine, state);
+        store.limiter(|state| &mut state.limits);
+        store.set_fuel(self.fuel).map_err(execution_error)?;
+        store
+            .fuel_async_yield_interval(Some(FUEL_YIELD_INTERVAL))
+            .map_err(execution_error)?;
+
+        let mut linker = Linker::new(&self.engine);
+        for (function, capability) in &HOST_FUNCTIONS {
+            if self.sandbox.has_capability(capability) {
+                link_host_function(&mut linker, function).map_err(execution_error)?;
+            }
+        }
+
+        let run = async {
+            let instance = linker
+                .instantiate_async(&mut store, &skill.module)
+                .await
+                .map_err(trap_error)?;
+            let alloc = instance
+                .get_typed_func::<i32, i32>(&mut store, "alloc")
+                .map_err(execution_error)?;
+            let execute = instance
+                .get_typed_func::<(i32, i32), i64>(&mut store, "execute")
+                .map_err(execution_error)?;
+            let memory = instance
+                .get_memory(&mut store, "memory")
+                .ok_or_else(|| SandboxError::WasmValidationFailed("No memory export".into()))?;
+
+            let len = i32::try_from(input.len()).map_err(|_| SandboxError::MemoryLimitExceeded)?;
+            let ptr = alloc
+                .call_async(&mut store, len)
+                .await
+                .map_err(trap_error)?;
+            memory
+                .write(&mut store, ptr as usize, input)
+                .map_err(|_| SandboxError::MemoryLimitExceeded)?;
+
+            let result = execute
+                .call_async(&mut store, (ptr, len))
+                .await
+                .map_err(trap_error)?;
+            if result < 0 {
+                return Err(SandboxError::ExecutionError(format!(
+                    "Skill failed with code {}",
+                    result
+                )));
+            }
+            let (ptr, len) = unpack(result);
+            let output = guest_slice(memory.data(&store), ptr, len)
+                .ok_or_else(|| SandboxError::ExecutionError("Output out of bounds".into()))?;
+            Ok::<_, SandboxError>(output.to_vec())
+        };
+
+        let output = tokio::time::timeout(self.sandbox.max_execution_time(), run)
+            .await
+            .map_err(|_| SandboxError::ExecutionTimeout)??;
+
+        let fuel_consumed = self.fuel - store.get_fuel().map_err(execution_error)?;
+        Ok((store.into_data(), output, fuel_consumed))
+    }
+}
+
+/// Where host call responses come from
+enum Source {
+    /// The real host, checked against the sandbox
+    Live(Arc<dyn SkillHost>),
+
+    /// Responses recorded in a replay log
+    Replay(VecDeque<HostCall>),
+}
+
+struct HostState {
+    skill_name: String,
+    limits: StoreLimits,
+    sandbox: Arc<SandboxedExecutor>,
+    audit: Option<Arc<SecurityAuditLog>>,
+    source: Source,
+    calls: Vec<HostCall>,
+    divergence: Option<String>,
+}
+
+impl HostState {
+    async fn host_call(&mut self, function: &str, request: Vec<u8>) -> Result<Vec<u8>, String> {
+        let response = match &mut self.source {
+            Source::Replay(recorded) => match recorded.pop_front() {
+                Some(call) if call.function == function && call.request == request => call.response,
+                _ => {
+                    let divergence = format!("Replay diverged at host call {}", function);
+                    self.divergence.get_or_insert(divergence.clone());
+                    Err(divergence)
+                }
+            },
+            Source::Live(host) => {
+                let host = host.clone();
+                match self.authorize(function, &request) {
+                    Ok(()) => {
+                        let text = String::from_utf8_lossy(&request);
+                        match function {
+                            "http_get" => host.http_get(&text).await,
+                            "lattice_query" => host.lattice_query(&text).await,
+                            "wallet_sign" => host.wallet_sign(&request).await,
+                            _ => Err(format!("Unknown host function {}", function)),
+                        }
+                    }
+                    Err(e) => Err(e.to_string()),
+                }
+            }
+        };
+
+        self.calls.push(HostCall {
+            function: function.to_string(),
+            request,
+            response: response.clone(),
+        });
+        response
+    }
+
+    /// Check a live call against the sandbox and audit the decision
+    fn authorize(&self, function: &str, request: &[u8]) -> Result<(), SandboxError> {
+        let (capability, result) = match function {
+            "http_get" => (
+                Capability::Network,
+                self.sandbox
+                    .validate_network_request(&String::from_utf8_lossy(request)),
+            ),
+            _ => {
+                let capability = HOST_FUNCTIONS
+                    .iter()
+                    .find(|(name, _)| *name == function)
+                    .map(|(_, capability)| capability.clone())
+                    .unwrap_or(Capability::ProcessSpawn);
+                let result = if self.sandbox.has_capability(&capability) {
+                    Ok(())
+                } else {
+                    Err(SandboxError::CapabilityDenied(capability.clone()))
+                };
+                (capability, result)
+            }
+        };
+
+        if let Some(audit) = &self.audit {
+            audit.log(SecurityAuditEntry {
+                timestamp: chrono::Utc::now().timestamp(),
+                skill_name: self.skill_name.clone(),
+                action: format!("rope.{}", function),
+                capability: Some(capability),
+                allowed: result.is_ok(),
+                context: result.as_ref().err().map(|e| e.to_string()),
+            });
+        }
+        result
+    }
+}
+
+fn link_host_function(
+    linker: &mut Linker<HostState>,
+    function: &'static str,
+) -> wasmtime::Result<()> {
+    linker.func_wrap_async(
+        "rope",
+        function,
+        move |mut caller: Caller<'_, HostState>, (ptr, len): (i32, i32)| {
+            Box::new(async move {
+                let request = read_guest(&mut caller, ptr, len)?;
+                match caller.data_mut().host_call(function, request).await {
+                    Ok(response) => write_guest(&mut caller, &response).await,
+                    Err(_) => Ok(-1),
+                }
+            })
+        },
+    )?;
+    Ok(())
+}
+
+fn guest_memory(caller: &mut Caller<'_, HostState>) -> wasmtime::Result<wasmtime::Memory> {
+    caller
+        .get_export("memory")
+        .and_then(Extern::into_memory)
+        .ok_or_else(|| wasmtime::Error::msg("skill exports no memory"))
+}
+
+/// `len` bytes of guest memory at `ptr`, checked before anything is copied
+fn guest_slice(data: &[u8], ptr: usize, len: usize) -> Option<&[u8]> {
+    data.get(ptr..ptr.checked_add(len)?)
+}
+
+fn read_guest(caller: &mut Caller<'_, HostState>, ptr: i32, len: i32) -> wasmtime::Result<Vec<u8>> {
+    let memory = guest_memory(caller)?;
+    guest_slice(
+        memory.data(&caller),
+        usize::try_from(ptr)?,
+        usize::try_from(len)?,
+    )
+    .map(<[u8]>::to_vec)
+    .ok_or_else(|| wasmtime::Error::msg("guest range out of bounds"))
+}
+
+async fn write_guest(caller: &mut Caller<'_, HostState>, data: &[u8]) -> wasmtime::Result<i64> {
+    let alloc = caller
+        .get_export("alloc")
+        .and_then(Extern::into_func)
+        .ok_or_else(|| wasmtime::Error::msg("skill exports no alloc"))?
+        .typed::<i32, i32>(&caller)?;
+    let len = i32::try_from(data.len())?;
+    let ptr = alloc.call_async(&mut *caller, len).await?;
+    guest_memory(caller)?.write(&mut *caller, usize::try_from(ptr)?, data)?;
+    Ok(pack(ptr, len))
+}
+
+fn pack(ptr: i32, len: i32) -> i64 {
+    ((ptr as u32 as i64) << 32) | len as u32 as i64
+}
+
+fn unpack(value: i64) -> (usize, usize) {
+    ((value >> 32) as u32 as usize, value as u32 as usize)
+}
+
+fn execution_error(e: wasmtime::Error) -> SandboxError {
+    SandboxError::ExecutionError(e.to_string())
+}
+
+fn trap_error(e: wasmtime::Error) -> SandboxError {
+    match e.downcast_ref::<Trap>() {
+        Some(Trap::OutOfFuel) => SandboxError::FuelExhausted,
+        _ => execution_error(e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Passes its input to `rope.http_get` and returns the response
+    const FETCH_SKILL: &str = r#"
+        (module
+          (import "rope" "http_get" (func $http_get (param i32 i32) (result i64)))
+          (memory (export "memory") 1)
+          (global $next (mut i32) (i32.const 1024))
+          (func (export "alloc") (param $len i32) (result i32)
+            (local $ptr i32)
+            global.get $next
+            local.set $ptr
+            global.get $next
+            local.get $len
+            i32.add
+            global.set $next
+            local.get $ptr)
+          (func (export "execute") (param $ptr i32) (param $len i32) (result i64)
+            local.get $ptr
+            local.get $len
+            call $http_get))
+    "#;
+
+    const SPIN_SKILL: &str = r#"
+        (module
+          (memory (export "memory") 1)
+          (func (export "alloc") (param i32) (result i32) i32.const 1024)
+          (func (export "execute") (param i32 i32) (result i64)
+            (loop $spin (br $spin))
+            i64.const 0))
+    "#;
+
+    struct WeatherHost {
+        calls: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl SkillHost for WeatherHost {
+        async fn http_get(&self, _url: &str) -> Result<Vec<u8>, String> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            Ok(b"sunny".to_vec())
+        }
+
+        async fn lattice_query(&self, _query: &str) -> Result<Vec<u8>, String> {
+            Err("unused".to_string())
+        }
+
+        async fn wallet_sign(&self, _payload: &[u8]) -> Result<Vec<u8>, String> {
+            Err("unused".to_string())
+        }
+    }
+
+    fn executor(capabilities: Vec<Capability>) -> WasmSkillExecutor {
+        let config = SandboxConfig {
+            capabilities,
+            allowed_hosts: vec!["api.weather.com".to_string()],
+            max_execution_time_ms: 2_000,
+            max_memory_bytes: 4 * 1024 * 1024,
+            ..SandboxConfig::default()
+        };
+        WasmSkillExecutor::new(config, 1_000_000).unwrap()
+    }
+
+    fn host() -> Arc<WeatherHost> {
+        Arc::new(WeatherHost {
+            calls: AtomicUsize::new(0),
+        })
+    }
+
+    #[tokio::test]
+    async fn test_execute_and_replay() {
+        let executor = executor(vec![Capability::Network]);
+        let skill = executor
+            .compile("weather", &wat::parse_str(FETCH_SKILL).unwrap())
+            .unwrap();
+        let host = host();
+
+        let log = executor
+            .execute(&skill, host.clone(), b"https://api.weather.com/today")
+            .await
+            .unwrap();
+        assert_eq!(log.output, b"sunny");
+        assert_eq!(log.calls.len(), 1);
+        assert_eq!(log.calls[0].request, b"https://api.weather.com/today");
+        assert!(log.fuel_consumed > 0);
+
+        // Replay never reaches the host
+        executor.replay(&skill, &log).await.unwrap();
+        assert_eq!(host.calls.load(Ordering::SeqCst), 1);
+
+        let mut tampered = log.clone();
+        tampered.calls[0].response = Ok(b"stormy".to_vec());
+        assert!(executor.replay(&skill, &tampered).await.is_err());
+
+        let mut tampered = log;
+        tampered.input = b"https://api.weather.com/tomorrow".to_vec();
+        assert!(executor.replay(&skill, &tampered).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_capabilities_are_enforced() {
+        let module = wat::parse_str(FETCH_SKILL).unwrap();
+
+        // No Network capability: the import is refused at load
+        assert!(matches!(
+            executor(vec![]).compile("weather", &module),
+            Err(SandboxError::CapabilityDenied(Capability::Network))
+        ));
+
+        // Host outside the allowlist: the call fails and is audited
+        let audit = Arc::new(SecurityAuditLog::new(10));
+        let executor = executor(vec![Capability::Network]).with_audit_log(audit.clone());
+        let skill = executor.compile("weather", &module).unwrap();
+        let host = host();
+        let result = executor
+            .execute(&skill, host.clone(), b"https://evil.example.com")
+            .await;
+        assert!(matches!(result, Err(SandboxError::ExecutionError(_))));
+        assert_eq!(host.calls.load(Ordering::SeqCst), 0);
+        assert_eq!(audit.denied_actions()[0].action, "rope.http_get");
+    }
+
+    #[tokio::test]
+    async fn test_fuel_limit() {
+        let executor = executor(vec![]);
+        let skill = executor
+            .compile("spin", &wat::parse_str(SPIN_SKILL).unwrap())
+            .unwrap();
+        let result = executor.execute(&skill, host(), b"").await;
+        assert!(matches!(result, Err(SandboxError::FuelExhausted)));
+    }
+
+    #[test]
+    fn test_pack_roundtrip() {
+        assert_eq!(unpack(pack(1024, 5)), (1024, 5));
+        assert_eq!(
+            unpack(pack(i32::MAX, i32::MAX)),
+            (i32::MAX as usize, i32::MAX as usize)
+        );
+    }
+
+    #[test]
+    fn test_guest_slice_bounds() {
+        let memory = [1u8, 2, 3, 4];
+        assert_eq!(guest_slice(&memory, 1, 2), Some(&memory[1..3]));
+        assert_eq!(guest_slice(&memory, 4, 0), Some(&memory[4..]));
+        assert_eq!(guest_slice(&memory, 3, 2), None);
+        assert_eq!(guest_slice(&memory, 1, usize::MAX), None);
+        assert_eq!(
+            guest_slice(&memory, u32::MAX as usize, u32::MAX as usize),
+            None
+        );
+    }
+}
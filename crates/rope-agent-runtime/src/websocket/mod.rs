@@ -2,6 +2,8 @@
 //!
 //! Provides client and server for WebSocket communication
 
+use crate::ai::CompletionStream;
+use futures::StreamExt;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use tokio::sync::{broadcast, mpsc};
@@ -33,6 +35,22 @@ pub enum LatticeEvent {
         testimonies: u32,
     },
 
+    /// Anchor finalized
+    AnchorFinalized {
+        id: String,
+        round: u64,
+        strings_finalized: u32,
+        timestamp: i64,
+    },
+
+    /// Bridge lock, release or relay event
+    BridgeEvent {
+        chain: String,
+        event_type: String,
+        tx_hash: String,
+        details: String,
+    },
+
     /// OES epoch changed
     OesEpochChanged {
         epoch: u64,
@@ -57,6 +75,19 @@ pub enum LatticeEvent {
         connected: bool,
     },
 
+    /// Part of an agent answer streamed over the socket
+    ResponseChunk {
+        conversation: String,
+        sequence: u64,
+        delta: String,
+        done: bool,
+    },
+
+    /// Error reported by the server
+    Error {
+        message: String,
+    },
+
     /// Heartbeat
     Ping,
     Pong,
@@ -77,6 +108,14 @@ pub enum WebSocketCommand {
 
     /// Authenticate
     Authenticate { token: String },
+
+    /// Part of an agent answer; `sequence` counts from 0 per conversation
+    ResponseChunk {
+        conversation: String,
+        sequence: u64,
+        delta: String,
+        done: bool,
+    },
 }
 
 /// WebSocket client for connecting to Lattice
@@ -214,6 +253,54 @@ impl LatticeWebSocketClient {
             .await
     }
 
+    /// Stream an agent answer over the socket as it is generated
+    ///
+    /// Each chunk is sent as a `ResponseChunk` command and also published to
+    /// local subscribers. The last frame always has `done` set, even when
+    /// the model stream fails. Returns the full answer.
+    pub async fn stream_response(
+        &self,
+        conversation: &str,
+        mut stream: CompletionStream,
+    ) -> Result<String, WebSocketError> {
+        if self.command_tx.is_none() {
+            return Err(WebSocketError::NotConnected);
+        }
+
+        let mut text = String::new();
+        let mut sequence = 0;
+        loop {
+            let (delta, done, failure) = match stream.next().await {
+                Some(Ok(chunk)) => (chunk.delta, chunk.done, None),
+                Some(Err(e)) => (String::new(), true, Some(e.to_string())),
+                None => (String::new(), true, None),
+            };
+            text.push_str(&delta);
+
+            let _ = self.event_tx.send(LatticeEvent::ResponseChunk {
+                conversation: conversation.to_string(),
+                sequence,
+                delta: delta.clone(),
+                done,
+            });
+            self.send_command(WebSocketCommand::ResponseChunk {
+                conversation: conversation.to_string(),
+                sequence,
+                delta,
+                done,
+            })
+            .await?;
+            sequence += 1;
+
+            if let Some(error) = failure {
+                return Err(WebSocketError::SendFailed(error));
+            }
+            if done {
+                return Ok(text);
+            }
+        }
+    }
+
     /// Disconnect
     pub fn disconnect(&self) {
         self.connected
@@ -266,10 +353,14 @@ impl EventFilter {
             LatticeEvent::StringCreated { .. } => "StringCreated",
             LatticeEvent::TestimonyReceived { .. } => "TestimonyReceived",
             LatticeEvent::ConsensusReached { .. } => "ConsensusReached",
+            LatticeEvent::AnchorFinalized { .. } => "AnchorFinalized",
+            LatticeEvent::BridgeEvent { .. } => "BridgeEvent",
             LatticeEvent::OesEpochChanged { .. } => "OesEpochChanged",
             LatticeEvent::SkillUpdated { .. } => "SkillUpdated",
             LatticeEvent::SecurityAlert { .. } => "SecurityAlert",
             LatticeEvent::ConnectionStatus { .. } => "ConnectionStatus",
+            LatticeEvent::ResponseChunk { .. } => "ResponseChunk",
+            LatticeEvent::Error { .. } => "Error",
             LatticeEvent::Ping | LatticeEvent::Pong => "Heartbeat",
         };
 
@@ -332,4 +423,50 @@ mod tests {
             _ => panic!("Wrong event type"),
         }
     }
+
+    #[tokio::test]
+    async fn test_stream_response_frames() {
+        let mut client = LatticeWebSocketClient::new("ws://localhost:0");
+        let (command_tx, mut command_rx) = mpsc::channel(16);
+        client.command_tx = Some(command_tx);
+        let mut events = client.subscribe();
+
+        let chunks = ["Hel", "lo", ""].map(|delta| {
+            Ok(crate::ai::CompletionChunk {
+                delta: delta.to_string(),
+                done: delta.is_empty(),
+            })
+        });
+        let text = client
+            .stream_response("conv-1", Box::pin(futures::stream::iter(chunks)))
+            .await
+            .unwrap();
+        assert_eq!(text, "Hello");
+
+        let mut frames = Vec::new();
+        while let Ok(command) = command_rx.try_recv() {
+            match command {
+                WebSocketCommand::ResponseChunk {
+                    conversation,
+                    sequence,
+                    delta,
+                    done,
+                } => frames.push((conversation, sequence, delta, done)),
+                other => panic!("unexpected command {:?}", other),
+            }
+        }
+        assert_eq!(frames.len(), 3);
+        assert_eq!(
+            frames[1],
+            ("conv-1".to_string(), 1, "lo".to_string(), false)
+        );
+        assert!(frames[2].3);
+
+        let mut filter = EventFilter::new();
+        filter.subscribe("ResponseChunk");
+        let event = events.try_recv().unwrap();
+        assert!(filter.should_emit(&event));
+        let json = serde_json::to_string(&event).unwrap();
+        assert!(json.contains("\"type\":\"ResponseChunk\""));
+    }
 }
@@ -5,7 +5,8 @@
 //! - Finality time: < 5 seconds
 //! - Throughput: > 10,000 TPS
 
-use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion, Throughput};
+use rope_benchmarks::fixtures;
 use std::time::Duration;
 
 /// Mock string for benchmarking
@@ -28,93 +29,6 @@ impl MockString {
             parents: vec![],
         }
     }
-
-    fn hash(&self) -> [u8; 32] {
-        *blake3::hash(&self.id).as_bytes()
-    }
-}
-
-/// Mock validator set
-struct MockValidatorSet {
-    validators: Vec<[u8; 32]>,
-    stakes: Vec<u64>,
-    total_stake: u64,
-}
-
-impl MockValidatorSet {
-    fn new(count: usize) -> Self {
-        let validators: Vec<[u8; 32]> = (0..count)
-            .map(|i| {
-                let mut v = [0u8; 32];
-                v[0] = i as u8;
-                v
-            })
-            .collect();
-        let stakes: Vec<u64> = (0..count).map(|i| 1000 + i as u64 * 100).collect();
-        let total_stake = stakes.iter().sum();
-
-        Self {
-            validators,
-            stakes,
-            total_stake,
-        }
-    }
-
-    fn is_supermajority(&self, stake_voted: u64) -> bool {
-        stake_voted * 3 > self.total_stake * 2
-    }
-}
-
-/// Mock lattice for consensus
-struct MockLattice {
-    strings: Vec<MockString>,
-    validator_set: MockValidatorSet,
-}
-
-impl MockLattice {
-    fn new(validator_count: usize) -> Self {
-        Self {
-            strings: Vec::new(),
-            validator_set: MockValidatorSet::new(validator_count),
-        }
-    }
-
-    fn add_string(&mut self, string: MockString) {
-        self.strings.push(string);
-    }
-
-    /// Simulate virtual voting - check if string is seen by supermajority
-    fn check_consensus(&self, string_idx: usize) -> bool {
-        if string_idx >= self.strings.len() {
-            return false;
-        }
-
-        // Simulate stake-weighted observation
-        let observed_stake: u64 = self
-            .validator_set
-            .stakes
-            .iter()
-            .enumerate()
-            .filter(|(i, _)| {
-                // Simple simulation: validators see strings with some probability
-                (*i as usize + string_idx) % 3 != 0
-            })
-            .map(|(_, stake)| stake)
-            .sum();
-
-        self.validator_set.is_supermajority(observed_stake)
-    }
-
-    /// Simulate anchor determination
-    fn determine_anchor(&self) -> Option<usize> {
-        // Find first string that achieves consensus
-        for i in 0..self.strings.len() {
-            if self.check_consensus(i) {
-                return Some(i);
-            }
-        }
-        None
-    }
 }
 
 /// Mock AI testimony validator
@@ -212,42 +126,20 @@ fn consensus_benchmarks(c: &mut Criterion) {
     let mut group = c.benchmark_group("consensus");
     group.measurement_time(Duration::from_secs(10));
 
-    // Benchmark: Virtual voting with different validator counts
+    // Benchmark: One voting round deciding 100 fully gossiped strings
     for validator_count in [7, 21, 100].iter() {
         group.bench_with_input(
             BenchmarkId::new("virtual_voting", validator_count),
             validator_count,
             |b, &count| {
-                let mut lattice = MockLattice::new(count);
-
-                // Pre-populate with strings
-                for i in 0..100 {
-                    lattice.add_string(MockString::new(i, 256));
-                }
-
-                b.iter(|| {
-                    // Check consensus for each string
-                    for i in 0..100 {
-                        std::hint::black_box(lattice.check_consensus(i));
-                    }
-                })
-            },
-        );
-    }
-
-    // Benchmark: Anchor determination
-    for string_count in [100, 1000, 10000].iter() {
-        group.bench_with_input(
-            BenchmarkId::new("anchor_determination", string_count),
-            string_count,
-            |b, &count| {
-                let mut lattice = MockLattice::new(21);
-
-                for i in 0..count {
-                    lattice.add_string(MockString::new(i as u8, 256));
-                }
-
-                b.iter(|| std::hint::black_box(lattice.determine_anchor()))
+                b.iter_batched(
+                    || fixtures::voting_network(count, 100, 3).0,
+                    |engine| {
+                        std::hint::black_box(engine.run_voting_round());
+                        engine
+                    },
+                    BatchSize::LargeInput,
+                )
             },
         );
     }
@@ -264,20 +156,6 @@ fn consensus_benchmarks(c: &mut Criterion) {
         })
     });
 
-    // Benchmark: String creation and hashing
-    for payload_size in [256, 1024, 4096].iter() {
-        group.bench_with_input(
-            BenchmarkId::new("string_creation", payload_size),
-            payload_size,
-            |b, &size| {
-                b.iter(|| {
-                    let string = MockString::new(0, size);
-                    std::hint::black_box(string.hash())
-                })
-            },
-        );
-    }
-
     group.finish();
 }
 
@@ -353,24 +231,17 @@ fn finality_benchmarks(c: &mut Criterion) {
     let mut group = c.benchmark_group("finality");
     group.measurement_time(Duration::from_secs(10));
 
-    // Benchmark: Time to finality simulation
+    // Benchmark: Deciding 210 strings after ten rounds of gossip from 21
+    // validators; building the gossip history is setup
     group.bench_function("finality_simulation", |b| {
-        b.iter(|| {
-            let mut lattice = MockLattice::new(21);
-
-            // Simulate string propagation and consensus
-            for round in 0..10 {
-                for validator in 0..21 {
-                    let string = MockString::new((round * 21 + validator) as u8, 256);
-                    lattice.add_string(string);
-                }
-
-                // Check for finality
-                if let Some(anchor) = lattice.determine_anchor() {
-                    std::hint::black_box(anchor);
-                }
-            }
-        })
+        b.iter_batched(
+            || fixtures::voting_network(21, 210, 10).0,
+            |engine| {
+                std::hint::black_box(engine.run_voting_round());
+                engine
+            },
+            BatchSize::LargeInput,
+        )
     });
 
     // Benchmark: Gossip simulation
@@ -1,22 +1,123 @@
 //! Cryptography Benchmarks for Datachain Rope
 //!
-//! Benchmarks post-quantum cryptography operations against specification requirements.
+//! Benchmarks post-quantum cryptography operations against specification requirements:
+//! - OES key generation: < 50ms
+//! - Dilithium3 signing: < 5ms
+//! - Kyber768 encapsulation: < 2ms
 
-use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion, Throughput};
+use rope_crypto::hybrid::{HybridKEM, HybridSigner, HybridVerifier};
+use rope_crypto::oes::OrganicEncryptionState;
+use rope_crypto::pq::{Dilithium3, Kyber768, PqKem, PqSignatureScheme};
 
-fn crypto_benchmarks(c: &mut Criterion) {
-    let mut group = c.benchmark_group("crypto");
+fn oes_benchmarks(c: &mut Criterion) {
+    let mut group = c.benchmark_group("oes");
 
-    // Placeholder benchmark
-    group.bench_function("placeholder", |b| {
+    // Benchmark: Genesis state and first key derivation
+    group.bench_function("genesis", |b| {
+        let mut seed = [0u8; 32];
         b.iter(|| {
-            // Placeholder for crypto benchmarks
-            std::hint::black_box(42)
+            seed[0] = seed[0].wrapping_add(1);
+            std::hint::black_box(OrganicEncryptionState::genesis(&seed))
         })
     });
 
+    // Benchmark: Evolution on a new anchor
+    group.bench_function("evolve", |b| {
+        let (state, _secrets) = OrganicEncryptionState::genesis(&[7u8; 32]);
+        let anchor = *blake3::hash(b"anchor").as_bytes();
+        b.iter_batched(
+            || state.clone(),
+            |mut state| std::hint::black_box(state.evolve(&anchor)),
+            BatchSize::SmallInput,
+        )
+    });
+
+    group.finish();
+}
+
+fn dilithium_benchmarks(c: &mut Criterion) {
+    let mut group = c.benchmark_group("dilithium3");
+
+    group.bench_function("keypair", |b| {
+        b.iter(|| std::hint::black_box(Dilithium3::keypair().unwrap()))
+    });
+
+    let (pk, sk) = Dilithium3::keypair().expect("Dilithium3 backend enabled");
+    for message_size in [32, 256, 4096].iter() {
+        let message = vec![0u8; *message_size];
+        let signed = Dilithium3::sign(&sk, &message).unwrap();
+
+        group.throughput(Throughput::Bytes(*message_size as u64));
+        group.bench_with_input(
+            BenchmarkId::new("sign", message_size),
+            &message,
+            |b, message| b.iter(|| std::hint::black_box(Dilithium3::sign(&sk, message).unwrap())),
+        );
+        group.bench_with_input(
+            BenchmarkId::new("verify", message_size),
+            &message,
+            |b, message| {
+                b.iter(|| std::hint::black_box(Dilithium3::verify(&pk, message, &signed).unwrap()))
+            },
+        );
+    }
+
+    group.finish();
+}
+
+fn kyber_benchmarks(c: &mut Criterion) {
+    let mut group = c.benchmark_group("kyber768");
+
+    group.bench_function("keypair", |b| {
+        b.iter(|| std::hint::black_box(Kyber768::keypair().unwrap()))
+    });
+
+    let (pk, sk) = Kyber768::keypair().expect("Kyber768 backend enabled");
+    let (ciphertext, _shared) = Kyber768::encapsulate(&pk).unwrap();
+
+    group.bench_function("encapsulate", |b| {
+        b.iter(|| std::hint::black_box(Kyber768::encapsulate(&pk).unwrap()))
+    });
+    group.bench_function("decapsulate", |b| {
+        b.iter(|| std::hint::black_box(Kyber768::decapsulate(&sk, &ciphertext).unwrap()))
+    });
+
+    group.finish();
+}
+
+fn hybrid_benchmarks(c: &mut Criterion) {
+    let mut group = c.benchmark_group("hybrid");
+
+    let (signer, public) = HybridSigner::generate();
+    let message = vec![0u8; 256];
+    let signature = signer.sign(&message);
+
+    // Benchmark: Ed25519 + Dilithium3 signing
+    group.bench_function("sign", |b| {
+        b.iter(|| std::hint::black_box(signer.sign(&message)))
+    });
+
+    // Benchmark: Both halves must verify
+    group.bench_function("verify", |b| {
+        b.iter(|| {
+            std::hint::black_box(HybridVerifier::verify(&public, &message, &signature).unwrap())
+        })
+    });
+
+    // Benchmark: X25519 + Kyber768 encapsulation
+    group.bench_function("kem_encapsulate", |b| {
+        b.iter(|| std::hint::black_box(HybridKEM::encapsulate(&public).unwrap()))
+    });
+
     group.finish();
 }
 
-criterion_group!(benches, crypto_benchmarks);
+criterion_group!(
+    benches,
+    oes_benchmarks,
+    dilithium_benchmarks,
+    kyber_benchmarks,
+    hybrid_benchmarks
+);
 criterion_main!(benches);
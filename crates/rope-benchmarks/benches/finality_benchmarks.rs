@@ -0,0 +1,42 @@
+//! Finality Benchmarks for Datachain Rope
+//!
+//! End-to-end testimony finality on an in-process devnet, against the
+//! specification requirement of < 3s from submission to final.
+//!
+//! Each configuration runs a whole cluster for seconds, which does not fit
+//! criterion's sampling model, so this harness prints latency percentiles
+//! instead.
+
+use rope_benchmarks::cluster::{self, ClusterConfig};
+
+fn main() {
+    // `cargo bench` passes `--bench`; anything else is a name filter
+    let filter: Vec<String> = std::env::args()
+        .skip(1)
+        .filter(|arg| !arg.starts_with("--"))
+        .collect();
+    if !filter.is_empty() && !filter.iter().any(|f| "finality".contains(f.as_str())) {
+        return;
+    }
+
+    let configs = [
+        ClusterConfig::devnet(4),
+        ClusterConfig::devnet(7),
+        ClusterConfig::devnet(21),
+        ClusterConfig::devnet(21).with_load(500, 250),
+        ClusterConfig::devnet(50),
+    ];
+
+    let mut all_pass = true;
+    for config in &configs {
+        let result = cluster::bench_testimony_finality(config);
+        result.print_summary();
+        all_pass &= result.passes_spec;
+    }
+
+    if all_pass {
+        println!("\nTestimony finality: ✅ within specification");
+    } else {
+        println!("\nTestimony finality: ❌ exceeds specification");
+    }
+}
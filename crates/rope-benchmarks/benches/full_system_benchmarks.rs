@@ -2,7 +2,9 @@
 //!
 //! Run with: `cargo bench --package rope-benchmarks --bench full_system_benchmarks`
 
-use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion, Throughput};
+use rope_benchmarks::protocol;
+use rope_protocols::regeneration::{ReedSolomonCodec, ReedSolomonParams};
 use std::time::Duration;
 
 // ============================================================================
@@ -195,38 +197,57 @@ fn protocol_benchmarks(c: &mut Criterion) {
     let mut group = c.benchmark_group("protocols");
     group.measurement_time(Duration::from_secs(10));
 
-    // Reed-Solomon encoding (simulated)
-    for size_kb in [64, 256, 1024].iter() {
-        let data = vec![0u8; size_kb * 1024];
-
-        group.throughput(Throughput::Bytes((size_kb * 1024) as u64));
-        group.bench_with_input(
-            BenchmarkId::new("rs_encode", format!("{}KB", size_kb)),
-            &data,
-            |b, data| {
-                b.iter(|| {
-                    // Simulate RS encoding
-                    blake3::hash(data)
-                });
-            },
-        );
-    }
-
-    // Reed-Solomon decoding (simulated)
-    for size_kb in [64, 256, 1024].iter() {
-        let data = vec![0u8; size_kb * 1024];
+    for (data_shards, parity_shards) in protocol::SHARD_CONFIGS {
+        let codec = ReedSolomonCodec::with_params(ReedSolomonParams {
+            data_shards,
+            parity_shards,
+            ..ReedSolomonParams::default()
+        })
+        .expect("valid shard layout");
+        let layout = format!("{}+{}", data_shards, parity_shards);
+
+        for size_kb in [64, 256, 1024] {
+            let data: Vec<u8> = (0..size_kb * 1024)
+                .map(|i| (i.wrapping_mul(31) % 251) as u8)
+                .collect();
+            let param = format!("{}KB/{}", size_kb, layout);
+
+            // Lose as many data shards as the layout can recover from
+            let mut damaged = codec.encode(&data).expect("encode");
+            for index in 0..parity_shards {
+                damaged.mark_missing(index);
+            }
 
-        group.throughput(Throughput::Bytes((size_kb * 1024) as u64));
-        group.bench_with_input(
-            BenchmarkId::new("rs_decode", format!("{}KB", size_kb)),
-            &data,
-            |b, data| {
-                b.iter(|| {
-                    // Simulate RS decoding
-                    blake3::hash(data)
-                });
-            },
-        );
+            group.throughput(Throughput::Bytes((size_kb * 1024) as u64));
+            group.bench_with_input(BenchmarkId::new("rs_encode", &param), &data, |b, data| {
+                b.iter(|| codec.encode(data).expect("encode"));
+            });
+            group.bench_with_input(
+                BenchmarkId::new("rs_decode", &param),
+                &damaged,
+                |b, damaged| {
+                    b.iter_batched(
+                        || damaged.clone(),
+                        |damaged| codec.decode(damaged).expect("decode"),
+                        BatchSize::LargeInput,
+                    );
+                },
+            );
+            group.bench_with_input(
+                BenchmarkId::new("rs_repair", &param),
+                &damaged,
+                |b, damaged| {
+                    b.iter_batched(
+                        || damaged.clone(),
+                        |mut damaged| {
+                            codec.repair(&mut damaged).expect("repair");
+                            damaged
+                        },
+                        BatchSize::LargeInput,
+                    );
+                },
+            );
+        }
     }
 
     group.finish();
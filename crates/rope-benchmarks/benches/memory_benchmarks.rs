@@ -0,0 +1,38 @@
+//! Memory Benchmarks for Datachain Rope
+//!
+//! Measures bytes retained per lattice string against the specification
+//! requirement of < 1KB overhead per string, at 10k, 100k and 1M strings.
+//!
+//! Criterion measures time, so this harness tracks allocations itself and
+//! prints a table instead.
+
+use rope_benchmarks::memory::{self, TrackingAllocator};
+
+#[global_allocator]
+static ALLOC: TrackingAllocator = TrackingAllocator;
+
+fn main() {
+    // `cargo bench` passes `--bench`; anything else is a name filter
+    let filter: Vec<String> = std::env::args()
+        .skip(1)
+        .filter(|arg| !arg.starts_with("--"))
+        .collect();
+    if !filter.is_empty() && !filter.iter().any(|f| "memory".contains(f.as_str())) {
+        return;
+    }
+
+    let mut all_pass = true;
+    for string_count in [10_000, 100_000, 1_000_000] {
+        for payload_size in [256, 1024] {
+            let result = memory::bench_memory_overhead(string_count, payload_size);
+            result.print_summary();
+            all_pass &= result.passes_spec;
+        }
+    }
+
+    if all_pass {
+        println!("\nMemory overhead: ✅ within specification");
+    } else {
+        println!("\nMemory overhead: ❌ exceeds specification");
+    }
+}
@@ -1,22 +1,102 @@
 //! String Lattice Benchmarks for Datachain Rope
 //!
-//! Benchmarks string operations against specification requirements.
+//! Benchmarks string operations against specification requirements:
+//! - String creation: < 100ms p99
+//! - Lattice insertion: < 10ms
 
-use criterion::{criterion_group, criterion_main, Criterion};
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion, Throughput};
+use rope_benchmarks::fixtures;
+use rope_core::lattice::StringLattice;
+use rope_crypto::pq::{Dilithium3, PqSignatureScheme};
 
 fn string_benchmarks(c: &mut Criterion) {
     let mut group = c.benchmark_group("string");
+    let (signer, creator) = fixtures::signer(1);
 
-    // Placeholder benchmark
-    group.bench_function("placeholder", |b| {
+    // Benchmark: Build, hybrid-sign and rebuild a string
+    for payload_size in [256, 1024, 4096, 65536].iter() {
+        let payload = vec![0u8; *payload_size];
+        let mut time = 0;
+
+        group.throughput(Throughput::Bytes(*payload_size as u64));
+        group.bench_with_input(
+            BenchmarkId::new("creation", payload_size),
+            &payload,
+            |b, payload| {
+                b.iter(|| {
+                    time += 1;
+                    std::hint::black_box(fixtures::create_signed_string(
+                        &signer, &creator, payload, time,
+                    ))
+                })
+            },
+        );
+    }
+
+    // Benchmark: Ed25519 and Dilithium3 signatures plus nucleotide parity
+    let string = fixtures::create_signed_string(&signer, &creator, &[0u8; 1024], 1);
+    group.throughput(Throughput::Elements(1));
+    group.bench_function("validation", |b| {
         b.iter(|| {
-            // Placeholder for string benchmarks
-            std::hint::black_box(42)
+            let valid = string.verify_signature()
+                && string.verify_sequence()
+                && Dilithium3::verify(
+                    &string.creator().dilithium,
+                    &string.compute_signing_message(),
+                    &string.signature().dilithium_sig,
+                )
+                .unwrap_or(false);
+            std::hint::black_box(valid)
         })
     });
 
     group.finish();
 }
 
-criterion_group!(benches, string_benchmarks);
+fn lattice_benchmarks(c: &mut Criterion) {
+    let mut group = c.benchmark_group("lattice");
+    let (signer, creator) = fixtures::signer(1);
+
+    // Benchmark: Single insertion into a growing lattice
+    group.bench_function("add_string", |b| {
+        let lattice = StringLattice::new();
+        let mut time: u64 = 0;
+        b.iter_batched(
+            || {
+                time += 1;
+                fixtures::create_signed_string(&signer, &creator, &time.to_le_bytes(), time)
+            },
+            |string| lattice.add_string(string).unwrap(),
+            BatchSize::SmallInput,
+        )
+    });
+
+    // Benchmark: Parallel batch insertion into an empty lattice
+    for batch_size in [10, 100, 1000].iter() {
+        let strings: Vec<_> = (0..*batch_size as u64)
+            .map(|i| fixtures::create_signed_string(&signer, &creator, &i.to_le_bytes(), i + 1))
+            .collect();
+
+        group.throughput(Throughput::Elements(*batch_size as u64));
+        group.bench_with_input(
+            BenchmarkId::new("insert_batch", batch_size),
+            &strings,
+            |b, strings| {
+                b.iter_batched(
+                    || (StringLattice::new(), strings.clone()),
+                    // Hand the lattice back so its drop is not timed
+                    |(lattice, strings)| {
+                        let results = lattice.insert_batch(strings);
+                        (lattice, results)
+                    },
+                    BatchSize::LargeInput,
+                )
+            },
+        );
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, string_benchmarks, lattice_benchmarks);
 criterion_main!(benches);
@@ -0,0 +1,524 @@
+//! # Baselines and Regression Detection
+//!
+//! A [`BenchmarkReport`] saved under a name becomes a baseline. A later run
+//! is compared against it benchmark by benchmark: a p99 (or, optionally,
+//! mean) slowdown beyond [`RegressionThresholds`] is a regression and fails
+//! the comparison, which is what a PR gate checks.
+//!
+//! Baselines are stored as `<dir>/<name>.json`, by default under
+//! [`DEFAULT_BASELINE_DIR`] next to criterion's own baselines.
+
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{BenchmarkReport, BenchmarkResult, ProfileArtifacts};
+
+/// Where baselines are kept unless told otherwise
+pub const DEFAULT_BASELINE_DIR: &str = "target/rope-baselines";
+
+// ============================================================================
+// THRESHOLDS
+// ============================================================================
+
+/// When a slowdown counts as a regression
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RegressionThresholds {
+    /// Maximum p99 slowdown (percent)
+    pub max_p99_regression_pct: f64,
+
+    /// Maximum mean slowdown (percent); `None` reports the change without gating on it
+    pub max_mean_regression_pct: Option<f64>,
+
+    /// Absolute changes below this (ns) are noise, whatever the percentage
+    pub noise_floor_ns: f64,
+}
+
+impl Default for RegressionThresholds {
+    fn default() -> Self {
+        Self {
+            max_p99_regression_pct: 10.0,
+            max_mean_regression_pct: None,
+            noise_floor_ns: 1_000.0,
+        }
+    }
+}
+
+// ============================================================================
+// COMPARISON
+// ============================================================================
+
+/// Outcome for one benchmark present in both runs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DiffStatus {
+    Improved,
+    Unchanged,
+    Regressed,
+}
+
+/// One benchmark compared against its baseline
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BenchmarkDiff {
+    pub name: String,
+    pub baseline_p99_ns: f64,
+    pub current_p99_ns: f64,
+    pub p99_change_pct: f64,
+    pub baseline_mean_ns: f64,
+    pub current_mean_ns: f64,
+    pub mean_change_pct: f64,
+    pub status: DiffStatus,
+
+    /// Profile captured with the current run, if it was profiled
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub profile: Option<ProfileArtifacts>,
+}
+
+impl BenchmarkDiff {
+    fn compare(
+        baseline: &BenchmarkResult,
+        current: &BenchmarkResult,
+        thresholds: &RegressionThresholds,
+    ) -> Self {
+        let p99_change_pct = change_pct(baseline.p99_ns, current.p99_ns);
+        let mean_change_pct = change_pct(baseline.mean_ns, current.mean_ns);
+        let p99_delta = current.p99_ns - baseline.p99_ns;
+        let mean_delta = current.mean_ns - baseline.mean_ns;
+
+        let p99_regressed = p99_delta > thresholds.noise_floor_ns
+            && p99_change_pct > thresholds.max_p99_regression_pct;
+        let mean_regressed = thresholds
+            .max_mean_regression_pct
+            .map(|max| mean_delta > thresholds.noise_floor_ns && mean_change_pct > max)
+            .unwrap_or(false);
+        let improved = -p99_delta > thresholds.noise_floor_ns
+            && -p99_change_pct > thresholds.max_p99_regression_pct;
+
+        let status = if p99_regressed || mean_regressed {
+            DiffStatus::Regressed
+        } else if improved {
+            DiffStatus::Improved
+        } else {
+            DiffStatus::Unchanged
+        };
+
+        Self {
+            name: current.name.clone(),
+            baseline_p99_ns: baseline.p99_ns,
+            current_p99_ns: current.p99_ns,
+            p99_change_pct,
+            baseline_mean_ns: baseline.mean_ns,
+            current_mean_ns: current.mean_ns,
+            mean_change_pct,
+            status,
+            profile: current.profile.clone(),
+        }
+    }
+}
+
+fn change_pct(baseline: f64, current: f64) -> f64 {
+    if baseline > 0.0 {
+        (current - baseline) / baseline * 100.0
+    } else {
+        0.0
+    }
+}
+
+/// A run compared against a named baseline
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegressionReport {
+    pub baseline: String,
+    pub baseline_commit: Option<String>,
+    pub current_commit: Option<String>,
+    pub thresholds: RegressionThresholds,
+
+    /// Benchmarks in both runs, in the current run's order
+    pub diffs: Vec<BenchmarkDiff>,
+
+    /// Benchmarks only in the current run
+    pub added: Vec<String>,
+
+    /// Benchmarks only in the baseline
+    pub removed: Vec<String>,
+}
+
+impl RegressionReport {
+    /// Compare `current` against the baseline stored as `baseline_name`
+    pub fn compare(
+        baseline_name: &str,
+        baseline: &BenchmarkReport,
+        current: &BenchmarkReport,
+        thresholds: &RegressionThresholds,
+    ) -> Self {
+        let mut diffs = Vec::new();
+        let mut added = Vec::new();
+
+        for result in &current.results {
+            match baseline.results.iter().find(|b| b.name == result.name) {
+                Some(base) => diffs.push(BenchmarkDiff::compare(base, result, thresholds)),
+                None => added.push(result.name.clone()),
+            }
+        }
+
+        let removed = baseline
+            .results
+            .iter()
+            .filter(|b| !current.results.iter().any(|r| r.name == b.name))
+            .map(|b| b.name.clone())
+            .collect();
+
+        Self {
+            baseline: baseline_name.to_string(),
+            baseline_commit: baseline.git_commit.clone(),
+            current_commit: current.git_commit.clone(),
+            thresholds: thresholds.clone(),
+            diffs,
+            added,
+            removed,
+        }
+    }
+
+    pub fn regressions(&self) -> impl Iterator<Item = &BenchmarkDiff> {
+        self.diffs
+            .iter()
+            .filter(|d| d.status == DiffStatus::Regressed)
+    }
+
+    /// True when nothing regressed
+    pub fn passed(&self) -> bool {
+        self.regressions().next().is_none()
+    }
+
+    /// Render as a Markdown table for a PR comment or job summary
+    pub fn to_markdown(&self) -> String {
+        let mut out = format!("## Benchmark comparison against `{}`\n\n", self.baseline);
+
+        let commit = |c: &Option<String>| {
+            c.as_deref()
+                .map(|c| format!("`{}`", &c[..12.min(c.len())]))
+                .unwrap_or_else(|| "unknown".to_string())
+        };
+        out.push_str(&format!(
+            "Baseline {}, current {}. Regression threshold: p99 +{:.1}%",
+            commit(&self.baseline_commit),
+            commit(&self.current_commit),
+            self.thresholds.max_p99_regression_pct
+        ));
+        if let Some(mean) = self.thresholds.max_mean_regression_pct {
+            out.push_str(&format!(", mean +{:.1}%", mean));
+        }
+        out.push_str(&format!(
+            " (changes under {} ignored).\n\n",
+            format_ns(self.thresholds.noise_floor_ns)
+        ));
+
+        out.push_str("| Benchmark | Baseline p99 | Current p99 | Δ p99 | Δ mean | Status |\n");
+        out.push_str("|-----------|--------------|-------------|-------|--------|--------|\n");
+        for diff in &self.diffs {
+            let status = match diff.status {
+                DiffStatus::Improved => "🚀 improved",
+                DiffStatus::Unchanged => "✅ unchanged",
+                DiffStatus::Regressed => "❌ regressed",
+            };
+            out.push_str(&format!(
+                "| {} | {} | {} | {:+.1}% | {:+.1}% | {} |\n",
+                diff.name,
+                format_ns(diff.baseline_p99_ns),
+                format_ns(diff.current_p99_ns),
+                diff.p99_change_pct,
+                diff.mean_change_pct,
+                status
+            ));
+        }
+
+        let profiled: Vec<_> = self
+            .regressions()
+            .filter_map(|d| d.profile.as_ref().map(|p| (&d.name, p)))
+            .collect();
+        if !profiled.is_empty() {
+            out.push_str("\n### Profiles of regressed benchmarks\n\n");
+            for (name, profile) in profiled {
+                out.push_str(&format!(
+                    "- {}: [flamegraph]({}) · [pprof]({})\n",
+                    name, profile.flamegraph, profile.pprof
+                ));
+            }
+        }
+
+        if !self.added.is_empty() {
+            out.push_str(&format!("\nNew benchmarks: {}\n", self.added.join(", ")));
+        }
+        if !self.removed.is_empty() {
+            out.push_str(&format!(
+                "\nMissing from this run: {}\n",
+                self.removed.join(", ")
+            ));
+        }
+
+        let regressions = self.regressions().count();
+        if regressions == 0 {
+            out.push_str("\n**Result: no regressions**\n");
+        } else {
+            out.push_str(&format!("\n**Result: {} regression(s)**\n", regressions));
+        }
+        out
+    }
+
+    /// Print a summary to stdout
+    pub fn print_report(&self) {
+        println!("\n═══════════════════════════════════════════════════════════════");
+        println!("  REGRESSION CHECK AGAINST BASELINE '{}'", self.baseline);
+        println!("═══════════════════════════════════════════════════════════════");
+        for diff in &self.diffs {
+            let status = match diff.status {
+                DiffStatus::Improved => "🚀",
+                DiffStatus::Unchanged => "✅",
+                DiffStatus::Regressed => "❌",
+            };
+            println!(
+                "  {} {:<45} p99 {:>10} → {:>10} ({:+.1}%)",
+                status,
+                diff.name,
+                format_ns(diff.baseline_p99_ns),
+                format_ns(diff.current_p99_ns),
+                diff.p99_change_pct
+            );
+            if let (DiffStatus::Regressed, Some(profile)) = (diff.status, &diff.profile) {
+                println!("       flamegraph: {}", profile.flamegraph);
+            }
+        }
+        for name in &self.added {
+            println!("  ➕ {} (no baseline)", name);
+        }
+        for name in &self.removed {
+            println!("  ➖ {} (not run)", name);
+        }
+
+        if self.passed() {
+            println!("\n  RESULT: ✅ NO REGRESSIONS");
+        } else {
+            println!(
+                "\n  RESULT: ❌ {} REGRESSION(S)",
+                self.regressions().count()
+            );
+        }
+        println!("═══════════════════════════════════════════════════════════════\n");
+    }
+
+    /// Save the comparison as JSON
+    pub fn save_json(&self, path: &str) -> std::io::Result<()> {
+        let json = serde_json::to_string_pretty(self)?;
+        std::fs::write(path, json)
+    }
+}
+
+fn format_ns(ns: f64) -> String {
+    if ns < 1_000.0 {
+        format!("{:.0}ns", ns)
+    } else if ns < 1_000_000.0 {
+        format!("{:.2}µs", ns / 1_000.0)
+    } else {
+        format!("{:.2}ms", ns / 1_000_000.0)
+    }
+}
+
+// ============================================================================
+// STORAGE
+// ============================================================================
+
+/// Errors reading or writing baselines
+#[derive(Debug)]
+pub enum BaselineError {
+    Io(std::io::Error),
+    Parse(String),
+    NotFound(String),
+    InvalidName(String),
+}
+
+impl std::fmt::Display for BaselineError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BaselineError::Io(e) => write!(f, "Baseline I/O failed: {}", e),
+            BaselineError::Parse(e) => write!(f, "Invalid baseline: {}", e),
+            BaselineError::NotFound(name) => write!(f, "No baseline named '{}'", name),
+            BaselineError::InvalidName(name) => write!(
+                f,
+                "Invalid baseline name '{}': use letters, digits, '-', '_' or '.'",
+                name
+            ),
+        }
+    }
+}
+
+impl std::error::Error for BaselineError {}
+
+impl From<std::io::Error> for BaselineError {
+    fn from(e: std::io::Error) -> Self {
+        BaselineError::Io(e)
+    }
+}
+
+/// Named baselines in a directory
+#[derive(Debug, Clone)]
+pub struct BaselineStore {
+    dir: PathBuf,
+}
+
+impl Default for BaselineStore {
+    fn default() -> Self {
+        Self::new(DEFAULT_BASELINE_DIR)
+    }
+}
+
+impl BaselineStore {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// File backing the baseline `name`
+    pub fn path(&self, name: &str) -> Result<PathBuf, BaselineError> {
+        let valid = !name.is_empty()
+            && !name.starts_with('.')
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+        if !valid {
+            return Err(BaselineError::InvalidName(name.to_string()));
+        }
+        Ok(self.dir.join(format!("{}.json", name)))
+    }
+
+    /// Save `report` as `name`, replacing any previous baseline of that name
+    pub fn save(&self, name: &str, report: &BenchmarkReport) -> Result<PathBuf, BaselineError> {
+        let path = self.path(name)?;
+        std::fs::create_dir_all(&self.dir)?;
+        let json = serde_json::to_string_pretty(report)
+            .map_err(|e| BaselineError::Parse(e.to_string()))?;
+        std::fs::write(&path, json)?;
+        Ok(path)
+    }
+
+    pub fn load(&self, name: &str) -> Result<BenchmarkReport, BaselineError> {
+        let path = self.path(name)?;
+        if !path.exists() {
+            return Err(BaselineError::NotFound(name.to_string()));
+        }
+        let json = std::fs::read_to_string(&path)?;
+        serde_json::from_str(&json).map_err(|e| BaselineError::Parse(e.to_string()))
+    }
+
+    /// Names of the stored baselines, sorted
+    pub fn list(&self) -> Result<Vec<String>, BaselineError> {
+        if !self.dir.exists() {
+            return Ok(Vec::new());
+        }
+        let mut names: Vec<String> = std::fs::read_dir(&self.dir)?
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| {
+                let path = entry.path();
+                if path.extension()? != "json" {
+                    return None;
+                }
+                Some(path.file_stem()?.to_string_lossy().into_owned())
+            })
+            .collect();
+        names.sort();
+        Ok(names)
+    }
+
+    /// Compare `current` against the baseline `name`
+    pub fn compare(
+        &self,
+        name: &str,
+        current: &BenchmarkReport,
+        thresholds: &RegressionThresholds,
+    ) -> Result<RegressionReport, BaselineError> {
+        let baseline = self.load(name)?;
+        Ok(RegressionReport::compare(
+            name, &baseline, current, thresholds,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report(results: &[(&str, u64)]) -> BenchmarkReport {
+        let mut report = BenchmarkReport::new();
+        for (name, ns) in results {
+            report.add_result(BenchmarkResult::from_timings(name, &[*ns; 100], None));
+        }
+        report
+    }
+
+    #[test]
+    fn test_regression_detection() {
+        let baseline = report(&[("sign", 1_000_000), ("verify", 500_000), ("hash", 800)]);
+        let mut current = report(&[
+            ("sign", 1_200_000),  // +20%: regressed
+            ("verify", 400_000),  // -20%: improved
+            ("hash", 1_200),      // +50% but under the noise floor
+            ("encap", 2_000_000), // no baseline
+        ]);
+        current.results[0].profile = Some(ProfileArtifacts {
+            flamegraph: "report-profiles/sign.svg".to_string(),
+            pprof: "report-profiles/sign.pb".to_string(),
+        });
+
+        let diff = RegressionReport::compare(
+            "main",
+            &baseline,
+            &current,
+            &RegressionThresholds::default(),
+        );
+        let status = |name: &str| diff.diffs.iter().find(|d| d.name == name).unwrap().status;
+
+        assert_eq!(status("sign"), DiffStatus::Regressed);
+        assert_eq!(status("verify"), DiffStatus::Improved);
+        assert_eq!(status("hash"), DiffStatus::Unchanged);
+        assert_eq!(diff.added, vec!["encap".to_string()]);
+        assert!(diff.removed.is_empty());
+        assert!(!diff.passed());
+        let markdown = diff.to_markdown();
+        assert!(markdown.contains("**Result: 1 regression(s)**"));
+        assert!(markdown.contains("[flamegraph](report-profiles/sign.svg)"));
+
+        let lenient = RegressionThresholds {
+            max_p99_regression_pct: 25.0,
+            ..Default::default()
+        };
+        assert!(RegressionReport::compare("main", &baseline, &current, &lenient).passed());
+    }
+
+    #[test]
+    fn test_baseline_store_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = BaselineStore::new(dir.path());
+        let baseline = report(&[("sign", 1_000_000)]);
+
+        store.save("main", &baseline).unwrap();
+        assert_eq!(store.list().unwrap(), vec!["main".to_string()]);
+        assert_eq!(store.load("main").unwrap().results[0].name, "sign");
+
+        assert!(matches!(
+            store.load("missing"),
+            Err(BaselineError::NotFound(_))
+        ));
+        assert!(matches!(
+            store.save("../escape", &baseline),
+            Err(BaselineError::InvalidName(_))
+        ));
+
+        let slower = report(&[("sign", 1_500_000)]);
+        let diff = store
+            .compare("main", &slower, &RegressionThresholds::default())
+            .unwrap();
+        assert!(!diff.passed());
+    }
+}
@@ -0,0 +1,314 @@
+//! # Devnet Cluster Finality
+//!
+//! Starts a devnet through [`Devnet::run`] and measures wall-clock time from
+//! string submission to the anchor that finalizes it, against the §8.2
+//! requirement of testimony finality under 3 seconds.
+//!
+//! The validators are real [`RopeNode`]s with their own swarm, storage and
+//! string producer; devnet validators need a single testimony, so a string
+//! is final once an anchor includes it.
+//!
+//! 1. The harness waits until every validator has produced an anchor.
+//! 2. A client submits signed strings to validators round-robin at a fixed
+//!    rate, through each validator's [`ProducerHandle`].
+//! 3. Each [`ProductionEvent::AnchorFinalized`] of a validator finalizes the
+//!    oldest strings still pending there, as many as the anchor included.
+//!
+//! Latency is measured at the validator the string was submitted to, as a
+//! client polling that node's RPC would see it.
+//!
+//! [`RopeNode`]: rope_node::RopeNode
+//! [`ProducerHandle`]: rope_node::ProducerHandle
+
+use std::collections::VecDeque;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use rope_core::string::RopeString;
+use rope_node::{Devnet, DevnetConfig, ProducerHandle, ProductionEvent};
+use tokio::sync::{broadcast, mpsc};
+use tokio::time::Instant;
+
+use crate::{fixtures, profile, BenchmarkResult, SpecRequirements};
+
+/// Longest wait for every validator to produce its first anchor
+const STARTUP_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Time the node tasks get to wind down once the run is over
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(10);
+
+// ============================================================================
+// CONFIGURATION
+// ============================================================================
+
+/// Cluster size and offered load
+#[derive(Debug, Clone)]
+pub struct ClusterConfig {
+    /// In-process validators
+    pub validators: usize,
+
+    /// Time between anchors
+    pub anchor_interval: Duration,
+
+    /// Strings the client submits
+    pub strings: usize,
+
+    /// Submission rate (strings per second)
+    pub submit_rate: u64,
+
+    /// Payload bytes per string
+    pub payload_size: usize,
+
+    /// Give up on strings not final after this long
+    pub timeout: Duration,
+}
+
+impl ClusterConfig {
+    /// `validators` devnet validators at the devnet string interval
+    pub fn devnet(validators: usize) -> Self {
+        Self {
+            validators,
+            anchor_interval: Duration::from_millis(DevnetConfig::default().block_time_ms),
+            strings: 200,
+            submit_rate: 100,
+            payload_size: 256,
+            timeout: Duration::from_secs(30),
+        }
+    }
+
+    pub fn with_load(mut self, strings: usize, submit_rate: u64) -> Self {
+        self.strings = strings;
+        self.submit_rate = submit_rate;
+        self
+    }
+
+    pub fn with_anchor_interval(mut self, interval: Duration) -> Self {
+        self.anchor_interval = interval;
+        self
+    }
+
+    /// Devnet layout for this validator count, reused across runs
+    ///
+    /// Ports sit above the devnet defaults so a benchmark does not collide
+    /// with a devnet the developer already has running.
+    fn devnet_config(&self) -> DevnetConfig {
+        DevnetConfig {
+            validators: self.validators,
+            data_dir: self.data_dir(),
+            p2p_port: 31300,
+            rpc_port: 9545,
+            metrics_port: 10100,
+            block_time_ms: self.anchor_interval.as_millis() as u64,
+            ..DevnetConfig::default()
+        }
+    }
+
+    fn data_dir(&self) -> PathBuf {
+        std::env::temp_dir().join(format!("rope-bench-devnet-{}", self.validators))
+    }
+}
+
+/// Outcome of one cluster run
+#[derive(Debug, Clone)]
+pub struct ClusterRun {
+    /// Submission to finality, for every string that became final
+    pub latencies: Vec<Duration>,
+
+    /// Strings submitted
+    pub submitted: usize,
+
+    /// Wall-clock length of the run
+    pub elapsed: Duration,
+}
+
+impl ClusterRun {
+    pub fn finalized(&self) -> usize {
+        self.latencies.len()
+    }
+}
+
+// ============================================================================
+// HARNESS
+// ============================================================================
+
+/// Forward a validator's anchors as `(validator, strings included, seen at)`
+fn follow_anchors(
+    index: usize,
+    mut events: broadcast::Receiver<ProductionEvent>,
+    anchors: mpsc::UnboundedSender<(usize, usize, Instant)>,
+) {
+    tokio::spawn(async move {
+        loop {
+            match events.recv().await {
+                Ok(ProductionEvent::AnchorFinalized {
+                    strings_included, ..
+                }) => {
+                    if anchors
+                        .send((index, strings_included, Instant::now()))
+                        .is_err()
+                    {
+                        return;
+                    }
+                }
+                Ok(_) => {}
+                Err(broadcast::error::RecvError::Lagged(n)) => {
+                    tracing::warn!("Validator {} anchor events lagged by {}", index, n);
+                }
+                Err(broadcast::error::RecvError::Closed) => return,
+            }
+        }
+    });
+}
+
+/// Run the devnet until every submitted string is final at the validator
+/// it was submitted to, or until the timeout
+pub async fn run_cluster(config: &ClusterConfig) -> anyhow::Result<ClusterRun> {
+    let devnet = Devnet::open(&config.devnet_config())?;
+    let producers: Vec<ProducerHandle> = devnet
+        .validators
+        .iter()
+        .map(|v| v.producer.clone())
+        .collect();
+
+    let (anchors_tx, anchors_rx) = mpsc::unbounded_channel();
+    for (index, producer) in producers.iter().enumerate() {
+        follow_anchors(index, producer.subscribe(), anchors_tx.clone());
+    }
+    drop(anchors_tx);
+
+    // The nodes stop when the run future is dropped
+    let nodes = devnet.run();
+    tokio::pin!(nodes);
+    tokio::select! {
+        result = &mut nodes => {
+            result?;
+            anyhow::bail!("Devnet stopped before the run finished")
+        }
+        run = submit_and_wait(config, &producers, anchors_rx) => run,
+    }
+}
+
+/// Submit the client's strings once every validator anchors, and time each
+/// one to the anchor that includes it
+async fn submit_and_wait(
+    config: &ClusterConfig,
+    producers: &[ProducerHandle],
+    mut anchors_rx: mpsc::UnboundedReceiver<(usize, usize, Instant)>,
+) -> anyhow::Result<ClusterRun> {
+    let n = producers.len();
+
+    // Nodes start their services one after another
+    let mut started = vec![false; n];
+    let startup = tokio::time::timeout(STARTUP_TIMEOUT, async {
+        while started.contains(&false) {
+            match anchors_rx.recv().await {
+                Some((validator, _, _)) => started[validator] = true,
+                None => break,
+            }
+        }
+    })
+    .await;
+    if startup.is_err() || started.contains(&false) {
+        anyhow::bail!(
+            "Devnet validators did not start within {:?}",
+            STARTUP_TIMEOUT
+        );
+    }
+
+    // Sign up front so client-side signing is not part of the latency
+    let (signer, creator) = fixtures::signer(0xC1);
+    let strings: Vec<RopeString> = (0..config.strings as u64)
+        .map(|i| {
+            let mut payload = vec![0u8; config.payload_size.max(8)];
+            payload[..8].copy_from_slice(&i.to_le_bytes());
+            fixtures::create_signed_string(&signer, &creator, &payload, i + 1)
+        })
+        .collect();
+
+    let start = Instant::now();
+    // Submission times still pending at each validator, oldest first
+    let mut outstanding: Vec<VecDeque<Instant>> = vec![VecDeque::new(); n];
+    let mut submitted = 0;
+    let mut latencies = Vec::with_capacity(strings.len());
+    let submit_every = Duration::from_secs_f64(1.0 / config.submit_rate.max(1) as f64);
+    let mut pending = strings.into_iter().enumerate().peekable();
+    let mut next_submit = start;
+    let deadline = start + config.timeout;
+
+    loop {
+        if pending.peek().is_none() && latencies.len() == submitted {
+            break;
+        }
+        tokio::select! {
+            _ = tokio::time::sleep_until(next_submit), if pending.peek().is_some() => {
+                let (i, string) = pending.next().expect("peeked");
+                let to = i % n;
+                outstanding[to].push_back(Instant::now());
+                producers[to].add_pending_string(string);
+                submitted += 1;
+                next_submit += submit_every;
+            }
+            Some((validator, included, at)) = anchors_rx.recv() => {
+                // The producer drains its pool in submission order
+                for _ in 0..included {
+                    if let Some(sent) = outstanding[validator].pop_front() {
+                        latencies.push(at.duration_since(sent));
+                    }
+                }
+            }
+            _ = tokio::time::sleep_until(deadline) => break,
+        }
+    }
+
+    Ok(ClusterRun {
+        latencies,
+        submitted,
+        elapsed: start.elapsed(),
+    })
+}
+
+/// Benchmark testimony finality on an in-process devnet
+///
+/// Strings that miss the timeout count at the timeout, so they show up in
+/// the tail. Builds its own runtime; call it outside of one.
+pub fn bench_testimony_finality(config: &ClusterConfig) -> BenchmarkResult {
+    let spec = SpecRequirements::default();
+    let target_ns = spec.testimony_finality_s * 1_000_000_000;
+
+    let name = format!(
+        "Testimony Finality ({} validators, {} strings/s)",
+        config.validators, config.submit_rate
+    );
+
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .expect("benchmark runtime");
+    let capture = profile::Capture::begin();
+    let run = runtime
+        .block_on(run_cluster(config))
+        .expect("devnet cluster run");
+    let profile = capture.and_then(|c| c.finish(&name));
+    // Release ports and databases before the next configuration starts
+    runtime.shutdown_timeout(SHUTDOWN_TIMEOUT);
+
+    let mut timings: Vec<u64> = run
+        .latencies
+        .iter()
+        .map(|latency| latency.as_nanos() as u64)
+        .collect();
+    timings.resize(run.submitted.max(1), config.timeout.as_nanos() as u64);
+
+    if run.finalized() < run.submitted {
+        println!(
+            "  {} of {} strings did not reach finality within {:?}",
+            run.submitted - run.finalized(),
+            run.submitted,
+            config.timeout
+        );
+    }
+
+    let mut result = BenchmarkResult::from_timings(&name, &timings, Some(target_ns));
+    result.profile = profile;
+    result
+}
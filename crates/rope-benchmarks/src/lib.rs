@@ -7,13 +7,13 @@
 //! | Metric | Target | Test Method |
 //! |--------|--------|-------------|
 //! | String Creation Time | < 100ms p99 | `bench_string_creation` |
-//! | Testimony Finality | < 3s | `bench_testimony_finality` |
+//! | Testimony Finality | < 3s | `cluster::bench_testimony_finality` |
 //! | Network Throughput | > 10,000 TPS | `bench_network_throughput` |
-//! | Memory per String | < 1KB overhead | `bench_memory_overhead` |
+//! | Memory per String | < 1KB overhead | `memory::bench_memory_overhead` |
 //! | OES Key Generation | < 50ms | `bench_oes_keygen` |
 //! | Dilithium3 Signing | < 5ms | `bench_dilithium_sign` |
 //! | Kyber768 Encapsulation | < 2ms | `bench_kyber_encap` |
-//! | Reed-Solomon Encode | < 10ms/MB | `bench_rs_encode` |
+//! | Reed-Solomon Encode | < 10ms/MB | `protocol::bench_rs_encode` |
 //! | Virtual Voting | < 50ms per round | `bench_virtual_voting` |
 //!
 //! ## Usage
@@ -27,11 +27,32 @@
 //!
 //! # Generate HTML report
 //! cargo bench --package rope-benchmarks -- --save-baseline main
+//!
+//! # Spec suite: save a baseline, then gate a branch on it
+//! rope-bench run --save-baseline main
+//! rope-bench run --baseline main --max-p99-regression 10 --diff-markdown bench-diff.md
+//!
+//! # Flamegraph and pprof profile per benchmark, next to the report
+//! cargo run --release -p rope-benchmarks --features profiling -- \
+//!     run --output report.json --profile
 //! ```
 
 use serde::{Deserialize, Serialize};
 use std::time::{Duration, Instant};
 
+pub mod baseline;
+pub mod cluster;
+pub mod memory;
+pub mod profile;
+
+pub use baseline::{
+    BaselineError, BaselineStore, BenchmarkDiff, DiffStatus, RegressionReport,
+    RegressionThresholds, DEFAULT_BASELINE_DIR,
+};
+pub use cluster::{ClusterConfig, ClusterRun};
+pub use memory::{MemoryOverheadResult, TrackingAllocator};
+pub use profile::{ProfileArtifacts, ProfileError};
+
 // ============================================================================
 // SPECIFICATION REQUIREMENTS
 // ============================================================================
@@ -125,11 +146,19 @@ pub struct BenchmarkResult {
     /// Throughput (ops/sec)
     pub throughput: f64,
 
+    /// Mean milliseconds per MB processed (byte-oriented benchmarks only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ms_per_mb: Option<f64>,
+
     /// Passes specification requirement
     pub passes_spec: bool,
 
     /// Specification target (if applicable)
     pub spec_target: Option<String>,
+
+    /// Profile of the timed iterations (profiling sessions only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub profile: Option<ProfileArtifacts>,
 }
 
 impl BenchmarkResult {
@@ -181,11 +210,20 @@ impl BenchmarkResult {
             p99_ns,
             p999_ns,
             throughput,
+            ms_per_mb: None,
             passes_spec,
             spec_target: spec_target_ns.map(|t| format!("{}ns", t)),
+            profile: None,
         }
     }
 
+    /// Record that each operation processed `bytes`, to report ms/MB
+    pub fn with_bytes_per_op(mut self, bytes: usize) -> Self {
+        let mb = bytes as f64 / (1024.0 * 1024.0);
+        self.ms_per_mb = Some(self.mean_ns / 1_000_000.0 / mb);
+        self
+    }
+
     /// Print summary
     pub fn print_summary(&self) {
         let status = if self.passes_spec {
@@ -202,9 +240,15 @@ impl BenchmarkResult {
         println!("  p99:         {:.2}µs", self.p99_ns / 1000.0);
         println!("  p999:        {:.2}µs", self.p999_ns / 1000.0);
         println!("  Throughput:  {:.2} ops/sec", self.throughput);
+        if let Some(ms_per_mb) = self.ms_per_mb {
+            println!("  Per MB:      {:.2}ms", ms_per_mb);
+        }
         if let Some(target) = &self.spec_target {
             println!("  Spec Target: {}", target);
         }
+        if let Some(profile) = &self.profile {
+            println!("  Flamegraph:  {}", profile.flamegraph);
+        }
     }
 }
 
@@ -223,6 +267,10 @@ pub struct BenchmarkReport {
     /// Individual benchmark results
     pub results: Vec<BenchmarkResult>,
 
+    /// Memory overhead results (empty unless allocation tracking is installed)
+    #[serde(default)]
+    pub memory_results: Vec<MemoryOverheadResult>,
+
     /// Overall pass/fail
     pub overall_pass: bool,
 
@@ -238,6 +286,7 @@ impl BenchmarkReport {
             git_commit: std::env::var("GIT_COMMIT").ok(),
             system_info: SystemInfo::collect(),
             results: Vec::new(),
+            memory_results: Vec::new(),
             overall_pass: true,
             spec_requirements: SpecRequirements::default(),
         }
@@ -251,6 +300,14 @@ impl BenchmarkReport {
         self.results.push(result);
     }
 
+    /// Add a memory overhead result
+    pub fn add_memory_result(&mut self, result: MemoryOverheadResult) {
+        if !result.passes_spec {
+            self.overall_pass = false;
+        }
+        self.memory_results.push(result);
+    }
+
     /// Print full report
     pub fn print_report(&self) {
         println!("\n╔══════════════════════════════════════════════════════════════╗");
@@ -279,6 +336,9 @@ impl BenchmarkReport {
         for result in &self.results {
             result.print_summary();
         }
+        for result in &self.memory_results {
+            result.print_summary();
+        }
 
         println!("\n═══════════════════════════════════════════════════════════════");
         if self.overall_pass {
@@ -294,6 +354,12 @@ impl BenchmarkReport {
         let json = serde_json::to_string_pretty(self)?;
         std::fs::write(path, json)
     }
+
+    /// Load a report saved with [`save_json`](Self::save_json)
+    pub fn load_json(path: &str) -> std::io::Result<Self> {
+        let json = std::fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&json)?)
+    }
 }
 
 impl Default for BenchmarkReport {
@@ -347,6 +413,7 @@ where
     }
 
     // Collect timings
+    let capture = profile::Capture::begin();
     let mut timings = Vec::with_capacity(iterations);
 
     for _ in 0..iterations {
@@ -355,7 +422,46 @@ where
         timings.push(start.elapsed().as_nanos() as u64);
     }
 
-    BenchmarkResult::from_timings(name, &timings, spec_target_ns)
+    let mut result = BenchmarkResult::from_timings(name, &timings, spec_target_ns);
+    result.profile = capture.and_then(|c| c.finish(name));
+    result
+}
+
+/// Run a benchmark whose input is rebuilt, untimed, before every iteration
+///
+/// For operations that consume or exhaust their input, such as deciding
+/// strings that then stay decided.
+pub fn run_benchmark_with_setup<S, T, F>(
+    name: &str,
+    iterations: usize,
+    warmup: usize,
+    spec_target_ns: Option<u64>,
+    mut setup: S,
+    mut f: F,
+) -> BenchmarkResult
+where
+    S: FnMut() -> T,
+    F: FnMut(T),
+{
+    // Warmup
+    for _ in 0..warmup {
+        f(setup());
+    }
+
+    // Collect timings
+    let capture = profile::Capture::begin();
+    let mut timings = Vec::with_capacity(iterations);
+
+    for _ in 0..iterations {
+        let input = setup();
+        let start = Instant::now();
+        f(input);
+        timings.push(start.elapsed().as_nanos() as u64);
+    }
+
+    let mut result = BenchmarkResult::from_timings(name, &timings, spec_target_ns);
+    result.profile = capture.and_then(|c| c.finish(name));
+    result
 }
 
 /// Run an async benchmark
@@ -376,6 +482,7 @@ where
     }
 
     // Collect timings
+    let capture = profile::Capture::begin();
     let mut timings = Vec::with_capacity(iterations);
 
     for _ in 0..iterations {
@@ -384,7 +491,136 @@ where
         timings.push(start.elapsed().as_nanos() as u64);
     }
 
-    BenchmarkResult::from_timings(name, &timings, spec_target_ns)
+    let mut result = BenchmarkResult::from_timings(name, &timings, spec_target_ns);
+    result.profile = capture.and_then(|c| c.finish(name));
+    result
+}
+
+// ============================================================================
+// BENCHMARK FIXTURES
+// ============================================================================
+
+/// Inputs built from the real crate implementations
+///
+/// Shared by the functions below and the criterion harnesses in `benches/`
+/// so both measure the same code paths.
+pub mod fixtures {
+    use rope_consensus::{GossipEvent, VirtualVotingEngine};
+    use rope_core::clock::LamportClock;
+    use rope_core::string::{HybridSignature as StringSignature, PublicKey, RopeString};
+    use rope_crypto::hybrid::{HybridPublicKey, HybridSigner};
+
+    /// Hybrid (Ed25519 + Dilithium3) signer with the given classical seed
+    pub fn signer(seed: u8) -> (HybridSigner, PublicKey) {
+        let (signer, public) = HybridSigner::from_seed(&[seed; 32]);
+        let creator = creator_key(&public);
+        (signer, creator)
+    }
+
+    /// A hybrid public key in the string creator format
+    pub fn creator_key(public: &HybridPublicKey) -> PublicKey {
+        PublicKey::new(public.ed25519, public.dilithium.clone())
+    }
+
+    /// Build and sign a string the way a node does on submission
+    pub fn create_signed_string(
+        signer: &HybridSigner,
+        creator: &PublicKey,
+        payload: &[u8],
+        logical_time: u64,
+    ) -> RopeString {
+        let builder = || {
+            RopeString::builder()
+                .content(payload.to_vec())
+                .temporal_marker(LamportClock::with_time(logical_time, creator.to_node_id()))
+                .creator(creator.clone())
+        };
+
+        let unsigned = builder()
+            .build()
+            .expect("benchmark payload fits in a string");
+        let signature = signer.sign(&unsigned.compute_signing_message());
+        builder()
+            .signature(StringSignature {
+                ed25519_sig: signature.ed25519_sig,
+                dilithium_sig: signature.dilithium_sig,
+            })
+            .build()
+            .expect("benchmark payload fits in a string")
+    }
+
+    /// Validator IDs `0..count`
+    pub fn validator_ids(count: usize) -> Vec<[u8; 32]> {
+        (0..count)
+            .map(|i| *blake3::hash(&(i as u64).to_le_bytes()).as_bytes())
+            .collect()
+    }
+
+    /// A voting engine whose validators have all gossiped `string_count`
+    /// strings over `rounds` rounds
+    ///
+    /// Every validator submits its share of the strings in a genesis event,
+    /// then each round every validator gossips with its neighbour. Each
+    /// event is delivered to every validator's history, i.e. gossip has
+    /// fully propagated and every string can be decided.
+    pub fn voting_network(
+        validator_count: usize,
+        string_count: usize,
+        rounds: u64,
+    ) -> (VirtualVotingEngine, Vec<[u8; 32]>) {
+        let validators = validator_ids(validator_count);
+        let engine = VirtualVotingEngine::new(validators[0]);
+        for validator in &validators {
+            engine.add_validator(*validator);
+        }
+
+        let string_ids: Vec<[u8; 32]> = (0..string_count)
+            .map(|i| *blake3::hash(format!("string-{}", i).as_bytes()).as_bytes())
+            .collect();
+
+        let deliver = |event: &GossipEvent| {
+            for validator in &validators {
+                engine.update_node_history(*validator, event.clone());
+            }
+            engine.add_local_event(event.clone());
+        };
+
+        let mut latest: Vec<GossipEvent> = validators
+            .iter()
+            .enumerate()
+            .map(|(i, validator)| {
+                let share = string_ids
+                    .iter()
+                    .skip(i)
+                    .step_by(validator_count)
+                    .copied()
+                    .collect();
+                GossipEvent::genesis(*validator, share)
+            })
+            .collect();
+        latest.iter().for_each(deliver);
+
+        for round in 1..=rounds {
+            let next: Vec<GossipEvent> = validators
+                .iter()
+                .enumerate()
+                .map(|(i, validator)| {
+                    let neighbour = &latest[(i + 1) % validator_count];
+                    GossipEvent::new(
+                        *validator,
+                        round,
+                        Vec::new(),
+                        latest[i].id,
+                        Some(neighbour.id),
+                    )
+                })
+                .collect();
+            next.iter().for_each(deliver);
+            latest = next;
+        }
+
+        (engine, string_ids)
+    }
 }
 
 // ============================================================================
@@ -393,21 +629,24 @@ where
 
 pub mod crypto {
     use super::*;
+    use rope_crypto::hybrid::HybridSigner;
+    use rope_crypto::oes::OrganicEncryptionState;
+    use rope_crypto::pq::{Dilithium3, Kyber768, PqKem, PqSignatureScheme};
 
     /// Benchmark OES key generation
     pub fn bench_oes_keygen(iterations: usize) -> BenchmarkResult {
         let spec = SpecRequirements::default();
         let target_ns = spec.oes_keygen_ms * 1_000_000;
 
+        let mut seed = [0u8; 32];
         run_benchmark(
             "OES Key Generation",
             iterations,
             10,
             Some(target_ns),
             || {
-                // Simulate OES keygen (actual implementation)
-                let seed = [0u8; 32];
-                let _hash = blake3::hash(&seed);
+                seed[0] = seed[0].wrapping_add(1);
+                std::hint::black_box(OrganicEncryptionState::genesis(&seed));
             },
         )
     }
@@ -417,6 +656,7 @@ pub mod crypto {
         let spec = SpecRequirements::default();
         let target_ns = spec.dilithium_sign_ms * 1_000_000;
 
+        let (_pk, sk) = Dilithium3::keypair().expect("Dilithium3 backend enabled");
         let message = vec![0u8; 256];
 
         run_benchmark(
@@ -425,8 +665,7 @@ pub mod crypto {
             10,
             Some(target_ns),
             || {
-                // Simulate Dilithium signing
-                let _sig = blake3::hash(&message);
+                std::hint::black_box(Dilithium3::sign(&sk, &message).unwrap());
             },
         )
     }
@@ -436,20 +675,22 @@ pub mod crypto {
         let spec = SpecRequirements::default();
         let target_ns = spec.kyber_encap_ms * 1_000_000;
 
+        let (pk, _sk) = Kyber768::keypair().expect("Kyber768 backend enabled");
+
         run_benchmark(
             "Kyber768 Encapsulation",
             iterations,
             10,
             Some(target_ns),
             || {
-                // Simulate Kyber encapsulation
-                let _ss = blake3::hash(&[0u8; 32]);
+                std::hint::black_box(Kyber768::encapsulate(&pk).unwrap());
             },
         )
     }
 
     /// Benchmark hybrid signature
     pub fn bench_hybrid_sign(iterations: usize) -> BenchmarkResult {
+        let (signer, _public) = HybridSigner::generate();
         let message = vec![0u8; 256];
 
         run_benchmark(
@@ -458,9 +699,7 @@ pub mod crypto {
             10,
             Some(10_000_000), // 10ms
             || {
-                // Simulate hybrid signing
-                let _ed_sig = blake3::hash(&message);
-                let _dil_sig = blake3::hash(&[&message[..], &[1u8]].concat());
+                std::hint::black_box(signer.sign(&message));
             },
         )
     }
@@ -472,54 +711,53 @@ pub mod crypto {
 
 pub mod consensus {
     use super::*;
+    use rope_consensus::Testimony;
+    use rope_core::clock::LamportClock;
+    use rope_core::types::{AttestationType, StringId};
 
     /// Benchmark virtual voting per round
+    ///
+    /// One round decides 100 fully gossiped strings.
     pub fn bench_virtual_voting(iterations: usize, validator_count: usize) -> BenchmarkResult {
         let spec = SpecRequirements::default();
         let target_ns = spec.virtual_voting_ms * 1_000_000;
 
-        run_benchmark(
+        run_benchmark_with_setup(
             &format!("Virtual Voting ({} validators)", validator_count),
             iterations,
-            10,
+            3,
             Some(target_ns),
-            || {
-                // Simulate virtual voting
-                for _ in 0..validator_count {
-                    let _vote = blake3::hash(&rand::random::<[u8; 32]>());
-                }
+            || fixtures::voting_network(validator_count, 100, 3).0,
+            |engine| {
+                std::hint::black_box(engine.run_voting_round());
             },
         )
     }
 
     /// Benchmark testimony creation
     pub fn bench_testimony_creation(iterations: usize) -> BenchmarkResult {
+        let (signer, creator) = fixtures::signer(1);
+        let validator = creator.to_node_id();
+        let target = StringId::from_content(b"testimony target");
+        let mut time = 0;
+
         run_benchmark(
             "Testimony Creation",
             iterations,
             10,
             Some(5_000_000), // 5ms
             || {
-                use rand::Rng;
-                let mut data = [0u8; 64];
-                rand::thread_rng().fill(&mut data);
-                let _testimony = blake3::hash(&data);
-            },
-        )
-    }
-
-    /// Benchmark anchor determination
-    pub fn bench_anchor_determination(iterations: usize, string_count: usize) -> BenchmarkResult {
-        run_benchmark(
-            &format!("Anchor Determination ({} strings)", string_count),
-            iterations,
-            10,
-            Some(100_000_000), // 100ms
-            || {
-                let mut _anchor = [0u8; 32];
-                for _ in 0..string_count {
-                    _anchor = *blake3::hash(&rand::random::<[u8; 32]>()).as_bytes();
-                }
+                time += 1;
+                let mut testimony = Testimony::new(
+                    target,
+                    validator,
+                    AttestationType::Existence,
+                    LamportClock::with_time(time, validator),
+                    0,
+                );
+                let signature = signer.sign(&testimony.signing_data());
+                testimony.set_signature(signature.ed25519_sig, signature.dilithium_sig);
+                std::hint::black_box(testimony);
             },
         )
     }
@@ -531,13 +769,17 @@ pub mod consensus {
 
 pub mod string {
     use super::*;
+    use rope_core::lattice::StringLattice;
+    use rope_crypto::pq::{Dilithium3, PqSignatureScheme};
 
     /// Benchmark string creation
     pub fn bench_string_creation(iterations: usize, payload_size: usize) -> BenchmarkResult {
         let spec = SpecRequirements::default();
         let target_ns = spec.string_creation_p99_ms * 1_000_000;
 
+        let (signer, creator) = fixtures::signer(1);
         let payload = vec![0u8; payload_size];
+        let mut time = 0;
 
         run_benchmark(
             &format!("String Creation ({}B payload)", payload_size),
@@ -545,16 +787,20 @@ pub mod string {
             10,
             Some(target_ns),
             || {
-                let _id = blake3::hash(&payload);
-                let _sig = blake3::hash(&[&payload[..], &[1u8]].concat());
+                time += 1;
+                std::hint::black_box(fixtures::create_signed_string(
+                    &signer, &creator, &payload, time,
+                ));
             },
         )
     }
 
     /// Benchmark string validation
+    ///
+    /// Ed25519 and Dilithium3 signatures plus nucleotide parity.
     pub fn bench_string_validation(iterations: usize) -> BenchmarkResult {
-        let payload = vec![0u8; 1024];
-        let sig = blake3::hash(&payload);
+        let (signer, creator) = fixtures::signer(1);
+        let string = fixtures::create_signed_string(&signer, &creator, &[0u8; 1024], 1);
 
         run_benchmark(
             "String Validation",
@@ -562,20 +808,36 @@ pub mod string {
             10,
             Some(5_000_000), // 5ms
             || {
-                let _valid = blake3::hash(&payload) == sig;
+                let valid = string.verify_signature()
+                    && string.verify_sequence()
+                    && Dilithium3::verify(
+                        &string.creator().dilithium,
+                        &string.compute_signing_message(),
+                        &string.signature().dilithium_sig,
+                    )
+                    .unwrap_or(false);
+                assert!(valid, "benchmark string must validate");
             },
         )
     }
 
     /// Benchmark lattice insertion
     pub fn bench_lattice_insertion(iterations: usize) -> BenchmarkResult {
-        run_benchmark(
+        let (signer, creator) = fixtures::signer(1);
+        let lattice = StringLattice::new();
+        let mut time = 0;
+
+        run_benchmark_with_setup(
             "Lattice Insertion",
             iterations,
             10,
             Some(10_000_000), // 10ms
             || {
-                let _id = blake3::hash(&rand::random::<[u8; 32]>());
+                time += 1;
+                fixtures::create_signed_string(&signer, &creator, &time.to_le_bytes(), time)
+            },
+            |string| {
+                lattice.add_string(string).expect("fresh string inserts");
             },
         )
     }
@@ -646,40 +908,129 @@ pub mod network {
 
 pub mod protocol {
     use super::*;
+    use rope_protocols::regeneration::{ReedSolomonCodec, ReedSolomonData, ReedSolomonParams};
+
+    /// Shard layouts (data, parity): the codec default, the high-redundancy
+    /// layout, and the §9.3.1 (ρ, (ρ-1)/2) layouts for ρ = 7 and 13
+    pub const SHARD_CONFIGS: [(usize, usize); 4] = [(4, 2), (4, 4), (7, 3), (13, 6)];
+
+    fn codec(data_shards: usize, parity_shards: usize) -> ReedSolomonCodec {
+        ReedSolomonCodec::with_params(ReedSolomonParams {
+            data_shards,
+            parity_shards,
+            ..ReedSolomonParams::default()
+        })
+        .expect("valid shard layout")
+    }
 
-    /// Benchmark Reed-Solomon encoding
-    pub fn bench_rs_encode(iterations: usize, data_size_kb: usize) -> BenchmarkResult {
+    /// Non-uniform bytes, so the GF(2^8) tables see realistic input
+    fn payload(data_size_kb: usize) -> Vec<u8> {
+        (0..data_size_kb * 1024)
+            .map(|i| (i.wrapping_mul(31) % 251) as u8)
+            .collect()
+    }
+
+    /// Encoded `data_size_kb` with the first `parity_shards` data shards
+    /// lost, the worst case the layout can still recover from
+    fn damaged(codec: &ReedSolomonCodec, data_size_kb: usize) -> ReedSolomonData {
+        let mut encoded = codec.encode(&payload(data_size_kb)).expect("encode");
+        for index in 0..codec.params().max_recoverable() {
+            encoded.mark_missing(index);
+        }
+        encoded
+    }
+
+    /// Per-operation target for `data_size_kb` at the spec ms/MB rate
+    fn target_ns(data_size_kb: usize) -> u64 {
         let spec = SpecRequirements::default();
-        let target_ns = spec.rs_encode_ms_per_mb * 1_000_000 * (data_size_kb as u64) / 1024;
+        spec.rs_encode_ms_per_mb * 1_000_000 * (data_size_kb as u64) / 1024
+    }
 
-        let data = vec![0u8; data_size_kb * 1024];
+    fn per_mb(result: BenchmarkResult, data_size_kb: usize) -> BenchmarkResult {
+        let spec = SpecRequirements::default();
+        BenchmarkResult {
+            spec_target: Some(format!("{}ms/MB", spec.rs_encode_ms_per_mb)),
+            ..result.with_bytes_per_op(data_size_kb * 1024)
+        }
+    }
 
-        run_benchmark(
-            &format!("Reed-Solomon Encode ({}KB)", data_size_kb),
+    /// Benchmark Reed-Solomon encoding
+    pub fn bench_rs_encode(
+        iterations: usize,
+        data_size_kb: usize,
+        data_shards: usize,
+        parity_shards: usize,
+    ) -> BenchmarkResult {
+        let codec = codec(data_shards, parity_shards);
+        let data = payload(data_size_kb);
+
+        let result = run_benchmark(
+            &format!(
+                "Reed-Solomon Encode ({}KB, {}+{})",
+                data_size_kb, data_shards, parity_shards
+            ),
             iterations,
             5,
-            Some(target_ns),
+            Some(target_ns(data_size_kb)),
             || {
-                // Simulate RS encoding
-                let _parity = blake3::hash(&data);
+                let _encoded = codec.encode(&data).expect("encode");
             },
-        )
+        );
+        per_mb(result, data_size_kb)
     }
 
-    /// Benchmark Reed-Solomon decoding
-    pub fn bench_rs_decode(iterations: usize, data_size_kb: usize) -> BenchmarkResult {
-        let data = vec![0u8; data_size_kb * 1024];
+    /// Benchmark Reed-Solomon decoding with every parity shard's worth of
+    /// data shards missing
+    pub fn bench_rs_decode(
+        iterations: usize,
+        data_size_kb: usize,
+        data_shards: usize,
+        parity_shards: usize,
+    ) -> BenchmarkResult {
+        let codec = codec(data_shards, parity_shards);
+        let encoded = damaged(&codec, data_size_kb);
+
+        let result = run_benchmark_with_setup(
+            &format!(
+                "Reed-Solomon Decode ({}KB, {}+{})",
+                data_size_kb, data_shards, parity_shards
+            ),
+            iterations,
+            5,
+            Some(target_ns(data_size_kb)),
+            || encoded.clone(),
+            |encoded| {
+                let _recovered = codec.decode(encoded).expect("decode");
+            },
+        );
+        per_mb(result, data_size_kb)
+    }
 
-        run_benchmark(
-            &format!("Reed-Solomon Decode ({}KB)", data_size_kb),
+    /// Benchmark regenerating lost shards in place, as a node restoring
+    /// full redundancy does
+    pub fn bench_rs_repair(
+        iterations: usize,
+        data_size_kb: usize,
+        data_shards: usize,
+        parity_shards: usize,
+    ) -> BenchmarkResult {
+        let codec = codec(data_shards, parity_shards);
+        let encoded = damaged(&codec, data_size_kb);
+
+        let result = run_benchmark_with_setup(
+            &format!(
+                "Reed-Solomon Repair ({}KB, {}+{})",
+                data_size_kb, data_shards, parity_shards
+            ),
             iterations,
             5,
-            Some(20_000_000), // 20ms
-            || {
-                // Simulate RS decoding
-                let _recovered = blake3::hash(&data);
+            Some(target_ns(data_size_kb)),
+            || encoded.clone(),
+            |mut encoded| {
+                codec.repair(&mut encoded).expect("repair");
             },
-        )
+        );
+        per_mb(result, data_size_kb)
     }
 }
 
@@ -704,7 +1055,6 @@ pub fn run_full_benchmark_suite() -> BenchmarkReport {
     println!("Running consensus benchmarks...");
     report.add_result(consensus::bench_virtual_voting(100, 21));
     report.add_result(consensus::bench_testimony_creation(1000));
-    report.add_result(consensus::bench_anchor_determination(100, 1000));
 
     // String benchmarks
     println!("Running string benchmarks...");
@@ -722,9 +1072,28 @@ pub fn run_full_benchmark_suite() -> BenchmarkReport {
 
     // Protocol benchmarks
     println!("Running protocol benchmarks...");
-    report.add_result(protocol::bench_rs_encode(100, 64));
-    report.add_result(protocol::bench_rs_encode(100, 256));
-    report.add_result(protocol::bench_rs_decode(100, 64));
+    report.add_result(protocol::bench_rs_encode(100, 64, 4, 2));
+    for (data, parity) in protocol::SHARD_CONFIGS {
+        report.add_result(protocol::bench_rs_encode(100, 1024, data, parity));
+        report.add_result(protocol::bench_rs_decode(100, 1024, data, parity));
+        report.add_result(protocol::bench_rs_repair(100, 1024, data, parity));
+    }
+
+    // Finality benchmarks (larger clusters: `cargo bench --bench finality_benchmarks`)
+    println!("Running finality benchmarks...");
+    for validators in [4, 21] {
+        let config = ClusterConfig::devnet(validators);
+        report.add_result(cluster::bench_testimony_finality(&config));
+    }
+
+    // Memory benchmarks (1M strings: `cargo bench --bench memory_benchmarks`)
+    if memory::is_tracking() {
+        println!("Running memory benchmarks...");
+        report.add_memory_result(memory::bench_memory_overhead(10_000, 256));
+        report.add_memory_result(memory::bench_memory_overhead(100_000, 256));
+    } else {
+        println!("Skipping memory benchmarks: TrackingAllocator is not the global allocator");
+    }
 
     report.print_report();
     report
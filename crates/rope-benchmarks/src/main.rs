@@ -0,0 +1,256 @@
+//! # Datachain Rope Benchmark Runner
+//!
+//! Runs the specification benchmark suite and gates it against saved baselines.
+//!
+//! ## Usage
+//!
+//! ```bash
+//! # Run the suite and store it as the `main` baseline
+//! rope-bench run --save-baseline main
+//!
+//! # PR gate: fail if any p99 is more than 10% slower than `main`
+//! rope-bench run --baseline main --max-p99-regression 10 --diff-markdown bench-diff.md
+//!
+//! # Compare a report produced elsewhere
+//! rope-bench compare --baseline main --current report.json
+//!
+//! # List stored baselines
+//! rope-bench baselines
+//!
+//! # Flamegraph and pprof profile per benchmark in report-profiles/
+//! # (build with `--features profiling`)
+//! rope-bench run --output report.json --profile --save-baseline main
+//! ```
+//!
+//! ## Exit codes
+//!
+//! | Code | Meaning |
+//! |------|---------|
+//! | 0 | No regressions (or no baseline requested) |
+//! | 1 | At least one benchmark regressed past the threshold |
+//! | 2 | Invalid arguments, missing baseline or unreadable report |
+
+use clap::{Args, Parser, Subcommand};
+use rope_benchmarks::*;
+
+// Counts heap bytes for the memory overhead benchmarks
+#[global_allocator]
+static ALLOC: TrackingAllocator = TrackingAllocator;
+
+/// No regressions
+const EXIT_PASS: i32 = 0;
+/// A benchmark regressed past its threshold
+const EXIT_REGRESSED: i32 = 1;
+/// Bad arguments, missing baseline or unreadable report
+const EXIT_CONFIG_ERROR: i32 = 2;
+
+#[derive(Parser)]
+#[command(name = "rope-bench")]
+#[command(author = "Datachain Rope Team")]
+#[command(version = "1.0.0")]
+#[command(about = "Specification benchmarks for Datachain Rope", long_about = None)]
+struct Cli {
+    /// Directory holding named baselines
+    #[arg(long, global = true, default_value = DEFAULT_BASELINE_DIR)]
+    baseline_dir: String,
+
+    #[command(subcommand)]
+    command: Commands,
+}
+
+#[derive(Subcommand)]
+enum Commands {
+    /// Run the benchmark suite
+    Run {
+        /// Write the full report as JSON
+        #[arg(short, long)]
+        output: Option<String>,
+
+        /// Store this run as the named baseline
+        #[arg(long)]
+        save_baseline: Option<String>,
+
+        /// Capture a flamegraph and pprof profile per benchmark
+        #[arg(long)]
+        profile: bool,
+
+        /// Where profiles go (default: `<output>-profiles` next to the report)
+        #[arg(long)]
+        profile_dir: Option<String>,
+
+        /// Profiler sampling frequency (Hz)
+        #[arg(long, default_value_t = profile::DEFAULT_PROFILE_FREQUENCY)]
+        profile_frequency: i32,
+
+        #[command(flatten)]
+        gate: GateArgs,
+    },
+
+    /// Compare a saved report against a baseline
+    Compare {
+        /// Report JSON written by `run --output`
+        #[arg(long)]
+        current: String,
+
+        #[command(flatten)]
+        gate: GateArgs,
+    },
+
+    /// List stored baselines
+    Baselines,
+}
+
+/// Baseline comparison options shared by `run` and `compare`
+#[derive(Args)]
+struct GateArgs {
+    /// Baseline to compare against
+    #[arg(long)]
+    baseline: Option<String>,
+
+    /// Maximum p99 slowdown before failing (percent)
+    #[arg(long, default_value = "10")]
+    max_p99_regression: f64,
+
+    /// Also fail on mean slowdowns above this (percent)
+    #[arg(long)]
+    max_mean_regression: Option<f64>,
+
+    /// Ignore absolute changes below this many nanoseconds
+    #[arg(long, default_value = "1000")]
+    noise_floor_ns: f64,
+
+    /// Write the comparison as JSON
+    #[arg(long)]
+    diff_json: Option<String>,
+
+    /// Write the comparison as a Markdown table (PR comment / job summary)
+    #[arg(long)]
+    diff_markdown: Option<String>,
+}
+
+impl GateArgs {
+    fn thresholds(&self) -> RegressionThresholds {
+        RegressionThresholds {
+            max_p99_regression_pct: self.max_p99_regression,
+            max_mean_regression_pct: self.max_mean_regression,
+            noise_floor_ns: self.noise_floor_ns,
+        }
+    }
+
+    /// Compare against the requested baseline, if any, and pick the exit code
+    fn check(&self, store: &BaselineStore, report: &BenchmarkReport) -> i32 {
+        let Some(baseline) = &self.baseline else {
+            return EXIT_PASS;
+        };
+
+        let diff = match store.compare(baseline, report, &self.thresholds()) {
+            Ok(diff) => diff,
+            Err(e) => {
+                eprintln!("{}", e);
+                return EXIT_CONFIG_ERROR;
+            }
+        };
+        diff.print_report();
+
+        if let Some(path) = &self.diff_json {
+            if let Err(e) = diff.save_json(path) {
+                eprintln!("Failed to write {}: {}", path, e);
+            }
+        }
+        if let Some(path) = &self.diff_markdown {
+            if let Err(e) = std::fs::write(path, diff.to_markdown()) {
+                eprintln!("Failed to write {}: {}", path, e);
+            }
+        }
+
+        if diff.passed() {
+            EXIT_PASS
+        } else {
+            EXIT_REGRESSED
+        }
+    }
+}
+
+fn main() {
+    let cli = Cli::parse();
+    let store = BaselineStore::new(&cli.baseline_dir);
+
+    let code = match cli.command {
+        Commands::Run {
+            output,
+            save_baseline,
+            profile,
+            profile_dir,
+            profile_frequency,
+            gate,
+        } => {
+            if profile {
+                let dir: std::path::PathBuf = match (&profile_dir, &output) {
+                    (Some(dir), _) => dir.into(),
+                    (None, Some(output)) => profile::dir_for_report(output),
+                    (None, None) => profile::DEFAULT_PROFILE_DIR.into(),
+                };
+                if let Err(e) = profile::start(&dir, profile_frequency) {
+                    eprintln!("{}", e);
+                    std::process::exit(EXIT_CONFIG_ERROR);
+                }
+                println!("Writing profiles to {}", dir.display());
+            }
+
+            let report = run_full_benchmark_suite();
+            profile::stop();
+
+            if let Some(path) = &output {
+                match report.save_json(path) {
+                    Ok(()) => println!("Report saved to {}", path),
+                    Err(e) => eprintln!("Failed to write {}: {}", path, e),
+                }
+            }
+
+            // Compare before saving so `--baseline x --save-baseline x` gates on the old run
+            let code = gate.check(&store, &report);
+
+            if let Some(name) = &save_baseline {
+                match store.save(name, &report) {
+                    Ok(path) => println!("Baseline '{}' saved to {}", name, path.display()),
+                    Err(e) => {
+                        eprintln!("{}", e);
+                        std::process::exit(EXIT_CONFIG_ERROR);
+                    }
+                }
+            }
+            code
+        }
+
+        Commands::Compare { current, gate } => match BenchmarkReport::load_json(&current) {
+            Ok(report) if gate.baseline.is_some() => gate.check(&store, &report),
+            Ok(_) => {
+                eprintln!("compare needs --baseline");
+                EXIT_CONFIG_ERROR
+            }
+            Err(e) => {
+                eprintln!("Cannot read report {}: {}", current, e);
+                EXIT_CONFIG_ERROR
+            }
+        },
+
+        Commands::Baselines => match store.list() {
+            Ok(names) if names.is_empty() => {
+                println!("No baselines in {}", store.dir().display());
+                EXIT_PASS
+            }
+            Ok(names) => {
+                for name in names {
+                    println!("{}", name);
+                }
+                EXIT_PASS
+            }
+            Err(e) => {
+                eprintln!("{}", e);
+                EXIT_CONFIG_ERROR
+            }
+        },
+    };
+
+    std::process::exit(code);
+}
@@ -0,0 +1,221 @@
+//! # Memory Overhead
+//!
+//! [`TrackingAllocator`] wraps the system allocator and counts live heap
+//! bytes. A benchmark binary installs it as its global allocator:
+//!
+//! ```ignore
+//! #[global_allocator]
+//! static ALLOC: rope_benchmarks::memory::TrackingAllocator =
+//!     rope_benchmarks::memory::TrackingAllocator;
+//! ```
+//!
+//! [`bench_memory_overhead`] then measures what a [`StringLattice`] retains
+//! per inserted string beyond the string itself, against the §8.2
+//! "< 1KB overhead per string" requirement.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+
+use rope_core::clock::LamportClock;
+use rope_core::lattice::StringLattice;
+use rope_core::string::{PublicKey, RopeString};
+use serde::{Deserialize, Serialize};
+
+use crate::SpecRequirements;
+
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+
+// ============================================================================
+// ALLOCATOR
+// ============================================================================
+
+/// System allocator that counts live and peak heap bytes
+pub struct TrackingAllocator;
+
+impl TrackingAllocator {
+    fn grow(size: usize) {
+        let now = ALLOCATED.fetch_add(size, Ordering::Relaxed) + size;
+        PEAK.fetch_max(now, Ordering::Relaxed);
+    }
+
+    fn shrink(size: usize) {
+        ALLOCATED.fetch_sub(size, Ordering::Relaxed);
+    }
+}
+
+unsafe impl GlobalAlloc for TrackingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+            Self::grow(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+            Self::grow(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        Self::shrink(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            if new_size > layout.size() {
+                Self::grow(new_size - layout.size());
+            } else {
+                Self::shrink(layout.size() - new_size);
+            }
+        }
+        new_ptr
+    }
+}
+
+/// Live heap bytes
+pub fn allocated_bytes() -> usize {
+    ALLOCATED.load(Ordering::Relaxed)
+}
+
+/// Highest live heap bytes since the last [`reset_peak`]
+pub fn peak_bytes() -> usize {
+    PEAK.load(Ordering::Relaxed)
+}
+
+pub fn reset_peak() {
+    PEAK.store(allocated_bytes(), Ordering::Relaxed);
+}
+
+/// True once [`TrackingAllocator`] is the global allocator
+pub fn is_tracking() -> bool {
+    ALLOCATIONS.load(Ordering::Relaxed) > 0
+}
+
+// ============================================================================
+// OVERHEAD BENCHMARK
+// ============================================================================
+
+/// Bytes retained per string at one lattice size
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MemoryOverheadResult {
+    /// Benchmark name
+    pub name: String,
+
+    /// Strings inserted
+    pub string_count: u64,
+
+    /// Payload bytes per string
+    pub payload_bytes: u64,
+
+    /// Inline and heap bytes of one string on its own
+    pub string_bytes: f64,
+
+    /// Bytes the lattice retains per string beyond the string itself
+    pub overhead_per_string: f64,
+
+    /// Peak heap growth during insertion, per string
+    pub peak_per_string: f64,
+
+    /// Passes specification requirement
+    pub passes_spec: bool,
+
+    /// Specification target
+    pub spec_target: Option<String>,
+}
+
+impl MemoryOverheadResult {
+    /// Print summary
+    pub fn print_summary(&self) {
+        let status = if self.passes_spec {
+            "✅ PASS"
+        } else {
+            "❌ FAIL"
+        };
+
+        println!("\n{} - {}", self.name, status);
+        println!("  Strings:     {}", self.string_count);
+        println!("  String size: {:.0}B", self.string_bytes);
+        println!("  Overhead:    {:.0}B/string", self.overhead_per_string);
+        println!("  Peak:        {:.0}B/string", self.peak_per_string);
+        if let Some(target) = &self.spec_target {
+            println!("  Spec Target: {}", target);
+        }
+    }
+}
+
+/// Unsigned string with a unique payload
+///
+/// Signatures and keys are part of the string, not lattice overhead, so
+/// they are left out to keep the 1M-string run within memory.
+fn overhead_string(creator: &PublicKey, index: u64, payload_size: usize) -> RopeString {
+    let mut payload = vec![0u8; payload_size.max(8)];
+    payload[..8].copy_from_slice(&index.to_le_bytes());
+
+    RopeString::builder()
+        .content(payload)
+        // Stay inside one anchor interval: this measures insertion, not finalization
+        .temporal_marker(LamportClock::with_time(
+            1 + index % 10,
+            creator.to_node_id(),
+        ))
+        .creator(creator.clone())
+        .build()
+        .expect("benchmark payload fits in a string")
+}
+
+/// Measure the bytes retained per string after inserting `string_count`
+/// strings into an empty lattice
+///
+/// Overhead is everything the lattice holds (its string copy, complement,
+/// DAG node and indices) minus one copy of each string. Needs
+/// [`TrackingAllocator`] installed as the global allocator.
+pub fn bench_memory_overhead(string_count: usize, payload_size: usize) -> MemoryOverheadResult {
+    assert!(
+        is_tracking(),
+        "memory benchmarks need TrackingAllocator as the global allocator"
+    );
+
+    let spec = SpecRequirements::default();
+    let creator = PublicKey::from_ed25519([1u8; 32]);
+
+    let start = allocated_bytes();
+    let strings: Vec<RopeString> = (0..string_count as u64)
+        .map(|i| overhead_string(&creator, i, payload_size))
+        .collect();
+    let string_bytes = allocated_bytes().saturating_sub(start) as f64 / string_count as f64;
+
+    reset_peak();
+    let lattice = StringLattice::new();
+    for string in strings {
+        lattice.add_string(string).expect("unique strings insert");
+    }
+    let retained = allocated_bytes().saturating_sub(start) as f64 / string_count as f64;
+    let peak = peak_bytes().saturating_sub(start) as f64 / string_count as f64;
+    drop(lattice);
+
+    let overhead_per_string = retained - string_bytes;
+
+    MemoryOverheadResult {
+        name: format!(
+            "Memory per String ({} strings, {}B payload)",
+            string_count, payload_size
+        ),
+        string_count: string_count as u64,
+        payload_bytes: payload_size as u64,
+        string_bytes,
+        overhead_per_string,
+        peak_per_string: peak,
+        passes_spec: overhead_per_string <= spec.memory_per_string_bytes as f64,
+        spec_target: Some(format!("{}B", spec.memory_per_string_bytes)),
+    }
+}
@@ -0,0 +1,249 @@
+//! # Profiling
+//!
+//! With the `profiling` feature, [`start`] opens a session and every
+//! benchmark measured while it is active is sampled with pprof. Each
+//! benchmark's timed loop produces two files in the session directory:
+//!
+//! - `<benchmark>.svg`, a flamegraph
+//! - `<benchmark>.pb`, a pprof profile for `go tool pprof` or `pprof -http`
+//!
+//! Their paths are recorded on the [`BenchmarkResult`](crate::BenchmarkResult)
+//! so a saved report, and any regression found against it, points at the
+//! profile that explains it. Setup and warmup are not sampled.
+//!
+//! ```bash
+//! cargo run --release -p rope-benchmarks --features profiling -- \
+//!     run --output bench/report.json --profile
+//! # → bench/report.json, bench/report-profiles/*.svg, *.pb
+//! ```
+
+use std::fmt;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+/// Where profiles go when no report path or directory is given
+pub const DEFAULT_PROFILE_DIR: &str = "target/rope-profiles";
+
+/// Default sampling frequency (Hz); off 1kHz to avoid lockstep with timers
+pub const DEFAULT_PROFILE_FREQUENCY: i32 = 999;
+
+/// Files written for one benchmark
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProfileArtifacts {
+    /// Flamegraph SVG
+    pub flamegraph: String,
+
+    /// pprof protobuf profile
+    pub pprof: String,
+}
+
+#[derive(Debug)]
+pub enum ProfileError {
+    /// Built without the `profiling` feature
+    Unsupported,
+    Io(std::io::Error),
+    Profiler(String),
+}
+
+impl fmt::Display for ProfileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProfileError::Unsupported => {
+                write!(f, "Profiling needs the `profiling` feature")
+            }
+            ProfileError::Io(e) => write!(f, "Profile I/O error: {}", e),
+            ProfileError::Profiler(e) => write!(f, "Profiler error: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for ProfileError {}
+
+impl From<std::io::Error> for ProfileError {
+    fn from(e: std::io::Error) -> Self {
+        ProfileError::Io(e)
+    }
+}
+
+/// File name stem for a benchmark: lowercase, runs of anything else as `-`
+#[cfg_attr(not(feature = "profiling"), allow(dead_code))]
+fn file_stem(name: &str) -> String {
+    let mut stem = String::with_capacity(name.len());
+    for c in name.chars() {
+        if c.is_ascii_alphanumeric() {
+            stem.push(c.to_ascii_lowercase());
+        } else if !stem.is_empty() && !stem.ends_with('-') {
+            stem.push('-');
+        }
+    }
+    stem.trim_end_matches('-').to_string()
+}
+
+pub(crate) use backend::Capture;
+pub use backend::{is_active, start, stop};
+
+#[cfg(feature = "profiling")]
+mod backend {
+    use std::fs::File;
+    use std::path::PathBuf;
+    use std::sync::Mutex;
+
+    use pprof::protos::Message;
+
+    use super::{file_stem, ProfileArtifacts, ProfileError};
+
+    struct Session {
+        dir: PathBuf,
+        frequency: i32,
+    }
+
+    static SESSION: Mutex<Option<Session>> = Mutex::new(None);
+
+    /// Profile every benchmark from now on, writing into `dir`
+    pub fn start(dir: impl Into<PathBuf>, frequency: i32) -> Result<(), ProfileError> {
+        let dir = dir.into();
+        std::fs::create_dir_all(&dir)?;
+        *SESSION.lock().expect("profile session lock") = Some(Session { dir, frequency });
+        Ok(())
+    }
+
+    pub fn stop() {
+        SESSION.lock().expect("profile session lock").take();
+    }
+
+    pub fn is_active() -> bool {
+        SESSION.lock().expect("profile session lock").is_some()
+    }
+
+    /// Sampling for one benchmark
+    pub(crate) struct Capture {
+        dir: PathBuf,
+        guard: pprof::ProfilerGuard<'static>,
+    }
+
+    impl Capture {
+        /// Start sampling if a session is active
+        pub(crate) fn begin() -> Option<Self> {
+            let session = SESSION.lock().expect("profile session lock");
+            let session = session.as_ref()?;
+
+            match pprof::ProfilerGuardBuilder::default()
+                .frequency(session.frequency)
+                .blocklist(&["libc", "libgcc", "pthread", "vdso"])
+                .build()
+            {
+                Ok(guard) => Some(Self {
+                    dir: session.dir.clone(),
+                    guard,
+                }),
+                Err(e) => {
+                    eprintln!("Profiler unavailable: {}", e);
+                    None
+                }
+            }
+        }
+
+        /// Stop sampling and write the profile; a failure loses the
+        /// profile, not the benchmark
+        pub(crate) fn finish(self, name: &str) -> Option<ProfileArtifacts> {
+            match self.write(name) {
+                Ok(artifacts) => Some(artifacts),
+                Err(e) => {
+                    eprintln!("No profile for {}: {}", name, e);
+                    None
+                }
+            }
+        }
+
+        fn write(self, name: &str) -> Result<ProfileArtifacts, ProfileError> {
+            let profiler = |e: pprof::Error| ProfileError::Profiler(e.to_string());
+            let report = self.guard.report().build().map_err(profiler)?;
+            let stem = self.dir.join(file_stem(name));
+
+            let flamegraph = stem.with_extension("svg");
+            report
+                .flamegraph(File::create(&flamegraph)?)
+                .map_err(profiler)?;
+
+            let mut encoded = Vec::new();
+            report
+                .pprof()
+                .map_err(profiler)?
+                .write_to_vec(&mut encoded)
+                .map_err(|e| ProfileError::Profiler(e.to_string()))?;
+            let pprof = stem.with_extension("pb");
+            std::fs::write(&pprof, encoded)?;
+
+            Ok(ProfileArtifacts {
+                flamegraph: flamegraph.display().to_string(),
+                pprof: pprof.display().to_string(),
+            })
+        }
+    }
+}
+
+#[cfg(not(feature = "profiling"))]
+mod backend {
+    use std::path::PathBuf;
+
+    use super::{ProfileArtifacts, ProfileError};
+
+    /// Profile every benchmark from now on, writing into `dir`
+    pub fn start(_dir: impl Into<PathBuf>, _frequency: i32) -> Result<(), ProfileError> {
+        Err(ProfileError::Unsupported)
+    }
+
+    pub fn stop() {}
+
+    pub fn is_active() -> bool {
+        false
+    }
+
+    /// Never constructed without the `profiling` feature
+    pub(crate) enum Capture {}
+
+    impl Capture {
+        pub(crate) fn begin() -> Option<Self> {
+            None
+        }
+
+        pub(crate) fn finish(self, _name: &str) -> Option<ProfileArtifacts> {
+            match self {}
+        }
+    }
+}
+
+/// Profile directory for a report written to `report_path`:
+/// `bench/report.json` → `bench/report-profiles`
+pub fn dir_for_report(report_path: &str) -> PathBuf {
+    let path = PathBuf::from(report_path);
+    let stem = path
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "report".to_string());
+    path.with_file_name(format!("{}-profiles", stem))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_profile_paths() {
+        assert_eq!(
+            file_stem("Reed-Solomon Encode (1024KB, 4+2)"),
+            "reed-solomon-encode-1024kb-4-2"
+        );
+        assert_eq!(file_stem("  Dilithium3 Sign "), "dilithium3-sign");
+
+        assert_eq!(
+            dir_for_report("bench/report.json"),
+            PathBuf::from("bench/report-profiles")
+        );
+        assert_eq!(
+            dir_for_report("report.json"),
+            PathBuf::from("report-profiles")
+        );
+    }
+}
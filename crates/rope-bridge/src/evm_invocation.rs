@@ -33,6 +33,15 @@
 //! │         Ethereum    │    XDC    │    Polygon    │   Arbitrum       │
 //! └─────────────────────────────────────────────────────────────────────┘
 //! ```
+//!
+//! ## Signed Invocations
+//!
+//! [`EvmInvocationBridge::invoke_evm_signed`] turns a string operation into
+//! an EIP-1559 transaction, takes the nonce from the signing account's
+//! [`NonceManager`] allocation and signs it with a [`BridgeAccount`] derived
+//! from the rope-crypto keystore. Receipts are fed back through
+//! [`EvmInvocationBridge::process_receipt`], which decodes the logs into
+//! [`InvocationEvent`]s and closes out the pending invocation.
 
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
@@ -659,6 +668,501 @@ impl Default for StateProofGenerator {
     }
 }
 
+// ============================================================================
+// EIP-1559 Transactions
+// ============================================================================
+
+/// EIP-2718 type byte for EIP-1559 transactions
+pub const EIP1559_TX_TYPE: u8 = 0x02;
+
+/// Keccak-256 as used by Ethereum for hashing and addresses
+pub fn keccak256(data: &[u8]) -> [u8; 32] {
+    use sha3::{Digest, Keccak256};
+
+    let mut out = [0u8; 32];
+    out.copy_from_slice(&Keccak256::digest(data));
+    out
+}
+
+/// Fee parameters applied when building EIP-1559 transactions
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FeeParams {
+    /// Tip paid to the block producer, in wei per gas
+    pub max_priority_fee_per_gas: u128,
+
+    /// Upper bound on base fee plus tip, in wei per gas
+    pub max_fee_per_gas: u128,
+}
+
+impl FeeParams {
+    /// Fees covering a doubling of `base_fee` before inclusion
+    pub fn from_base_fee(base_fee: u128, priority_fee: u128) -> Self {
+        Self {
+            max_priority_fee_per_gas: priority_fee,
+            max_fee_per_gas: base_fee.saturating_mul(2).saturating_add(priority_fee),
+        }
+    }
+}
+
+impl Default for FeeParams {
+    fn default() -> Self {
+        // 2 gwei tip, 40 gwei cap
+        Self {
+            max_priority_fee_per_gas: 2_000_000_000,
+            max_fee_per_gas: 40_000_000_000,
+        }
+    }
+}
+
+/// EIP-2930 access list entry
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AccessListItem {
+    pub address: [u8; 20],
+    pub storage_keys: Vec<[u8; 32]>,
+}
+
+/// secp256k1 signature over an EIP-1559 signing hash
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EvmSignature {
+    /// Parity of the ephemeral point's y coordinate (0 or 1)
+    pub y_parity: u8,
+    pub r: [u8; 32],
+    pub s: [u8; 32],
+}
+
+/// Typed (EIP-1559) EVM transaction
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Eip1559Transaction {
+    pub chain_id: u64,
+    pub nonce: u64,
+    pub max_priority_fee_per_gas: u128,
+    pub max_fee_per_gas: u128,
+    pub gas_limit: u64,
+    pub to: Option<[u8; 20]>,
+    pub value: u128,
+    pub data: Vec<u8>,
+    pub access_list: Vec<AccessListItem>,
+
+    /// Set once signed by a [`BridgeAccount`]
+    pub signature: Option<EvmSignature>,
+}
+
+impl Eip1559Transaction {
+    /// Carry an encoded operation over to EIP-1559 fee fields
+    pub fn from_legacy(tx: &EvmTransaction, fees: &FeeParams) -> Self {
+        Self {
+            chain_id: tx.chain_id,
+            nonce: tx.nonce,
+            max_priority_fee_per_gas: fees.max_priority_fee_per_gas,
+            max_fee_per_gas: fees.max_fee_per_gas,
+            gas_limit: tx.gas_limit,
+            to: tx.to,
+            value: tx.value,
+            data: tx.data.clone(),
+            access_list: Vec::new(),
+            signature: None,
+        }
+    }
+
+    /// Hash signed by the sender: `keccak256(0x02 || rlp(fields))`
+    pub fn signing_hash(&self) -> [u8; 32] {
+        let mut payload = Vec::new();
+        self.encode_fields(&mut payload);
+        keccak256(&self.typed(&payload))
+    }
+
+    /// Raw signed transaction, as passed to `eth_sendRawTransaction`
+    pub fn encode(&self) -> Result<Vec<u8>, EncodingError> {
+        let signature = self
+            .signature
+            .as_ref()
+            .ok_or(EncodingError::MissingSignature)?;
+
+        let mut payload = Vec::new();
+        self.encode_fields(&mut payload);
+        rlp::encode_uint(&mut payload, signature.y_parity as u128);
+        rlp::encode_bytes(&mut payload, rlp::trim_zeros(&signature.r));
+        rlp::encode_bytes(&mut payload, rlp::trim_zeros(&signature.s));
+        Ok(self.typed(&payload))
+    }
+
+    /// Transaction hash of the signed transaction
+    pub fn hash(&self) -> Result<[u8; 32], EncodingError> {
+        Ok(keccak256(&self.encode()?))
+    }
+
+    /// Recover the address that signed this transaction
+    pub fn recover_sender(&self) -> Result<[u8; 20], EncodingError> {
+        use k256::ecdsa::{RecoveryId, Signature, VerifyingKey};
+
+        let signature = self
+            .signature
+            .as_ref()
+            .ok_or(EncodingError::MissingSignature)?;
+        let mut rs = [0u8; 64];
+        rs[..32].copy_from_slice(&signature.r);
+        rs[32..].copy_from_slice(&signature.s);
+
+        let sig =
+            Signature::from_slice(&rs).map_err(|e| EncodingError::SigningFailed(e.to_string()))?;
+        let recovery_id = RecoveryId::new(signature.y_parity == 1, false);
+        let key = VerifyingKey::recover_from_prehash(&self.signing_hash(), &sig, recovery_id)
+            .map_err(|e| EncodingError::SigningFailed(e.to_string()))?;
+        Ok(BridgeAccount::address_of(&key))
+    }
+
+    /// RLP of the unsigned fields, without the list header
+    fn encode_fields(&self, out: &mut Vec<u8>) {
+        rlp::encode_uint(out, self.chain_id as u128);
+        rlp::encode_uint(out, self.nonce as u128);
+        rlp::encode_uint(out, self.max_priority_fee_per_gas);
+        rlp::encode_uint(out, self.max_fee_per_gas);
+        rlp::encode_uint(out, self.gas_limit as u128);
+        match &self.to {
+            Some(to) => rlp::encode_bytes(out, to),
+            None => rlp::encode_bytes(out, &[]),
+        }
+        rlp::encode_uint(out, self.value);
+        rlp::encode_bytes(out, &self.data);
+
+        let mut access_list = Vec::new();
+        for item in &self.access_list {
+            let mut entry = Vec::new();
+            rlp::encode_bytes(&mut entry, &item.address);
+            let mut keys = Vec::new();
+            for key in &item.storage_keys {
+                rlp::encode_bytes(&mut keys, key);
+            }
+            rlp::encode_list(&mut entry, &keys);
+            rlp::encode_list(&mut access_list, &entry);
+        }
+        rlp::encode_list(out, &access_list);
+    }
+
+    fn typed(&self, payload: &[u8]) -> Vec<u8> {
+        let mut out = vec![EIP1559_TX_TYPE];
+        rlp::encode_list(&mut out, payload);
+        out
+    }
+}
+
+/// Minimal RLP encoder for transaction serialization
+mod rlp {
+    pub(super) fn encode_bytes(out: &mut Vec<u8>, bytes: &[u8]) {
+        if bytes.len() == 1 && bytes[0] < 0x80 {
+            out.push(bytes[0]);
+        } else {
+            encode_header(out, 0x80, bytes.len());
+            out.extend_from_slice(bytes);
+        }
+    }
+
+    pub(super) fn encode_uint(out: &mut Vec<u8>, value: u128) {
+        encode_bytes(out, trim_zeros(&value.to_be_bytes()));
+    }
+
+    pub(super) fn encode_list(out: &mut Vec<u8>, payload: &[u8]) {
+        encode_header(out, 0xc0, payload.len());
+        out.extend_from_slice(payload);
+    }
+
+    /// Integers are encoded big-endian without leading zeros
+    pub(super) fn trim_zeros(bytes: &[u8]) -> &[u8] {
+        let start = bytes.iter().position(|b| *b != 0).unwrap_or(bytes.len());
+        &bytes[start..]
+    }
+
+    fn encode_header(out: &mut Vec<u8>, offset: u8, len: usize) {
+        if len < 56 {
+            out.push(offset + len as u8);
+        } else {
+            let len_bytes = (len as u64).to_be_bytes();
+            let len_bytes = trim_zeros(&len_bytes);
+            out.push(offset + 55 + len_bytes.len() as u8);
+            out.extend_from_slice(len_bytes);
+        }
+    }
+}
+
+// ============================================================================
+// Bridge Accounts and Nonces
+// ============================================================================
+
+/// secp256k1 account the bridge signs EVM transactions with
+///
+/// Keys are derived from the node's rope-crypto [`KeyStore`] seed, so a
+/// bridge account is recoverable from the same backup as the node identity.
+///
+/// [`KeyStore`]: rope_crypto::keys::KeyStore
+pub struct BridgeAccount {
+    signing_key: k256::ecdsa::SigningKey,
+    address: [u8; 20],
+}
+
+impl BridgeAccount {
+    /// Account from a raw secp256k1 secret
+    pub fn from_secret(secret: &[u8; 32]) -> Result<Self, EncodingError> {
+        let signing_key = k256::ecdsa::SigningKey::from_slice(secret)
+            .map_err(|e| EncodingError::SigningFailed(e.to_string()))?;
+        let address = Self::address_of(signing_key.verifying_key());
+        Ok(Self {
+            signing_key,
+            address,
+        })
+    }
+
+    /// Account `index` on `chain_id`, derived from the keystore seed
+    pub fn from_keystore(
+        keystore: &rope_crypto::keys::KeyStore,
+        chain_id: u64,
+        index: u32,
+    ) -> Result<Self, EncodingError> {
+        let secret = keystore.derive_key(&format!("evm-bridge/{}/{}", chain_id, index));
+        Self::from_secret(&secret)
+    }
+
+    /// Ethereum address of this account
+    pub fn address(&self) -> [u8; 20] {
+        self.address
+    }
+
+    /// Sign `tx` in place and return its transaction hash
+    pub fn sign(&self, tx: &mut Eip1559Transaction) -> Result<[u8; 32], EncodingError> {
+        use k256::ecdsa::RecoveryId;
+
+        let (mut signature, mut recovery_id) = self
+            .signing_key
+            .sign_prehash_recoverable(&tx.signing_hash())
+            .map_err(|e| EncodingError::SigningFailed(e.to_string()))?;
+
+        // Ethereum rejects high-s signatures
+        if let Some(normalized) = signature.normalize_s() {
+            signature = normalized;
+            recovery_id = RecoveryId::new(!recovery_id.is_y_odd(), recovery_id.is_x_reduced());
+        }
+
+        let (r, s) = signature.split_bytes();
+        let mut evm_signature = EvmSignature {
+            y_parity: recovery_id.is_y_odd() as u8,
+            r: [0u8; 32],
+            s: [0u8; 32],
+        };
+        evm_signature.r.copy_from_slice(&r);
+        evm_signature.s.copy_from_slice(&s);
+        tx.signature = Some(evm_signature);
+
+        tx.hash()
+    }
+
+    fn address_of(key: &k256::ecdsa::VerifyingKey) -> [u8; 20] {
+        let point = key.to_encoded_point(false);
+        let hash = keccak256(&point.as_bytes()[1..]);
+        let mut address = [0u8; 20];
+        address.copy_from_slice(&hash[12..]);
+        address
+    }
+}
+
+/// Per-account nonce allocation for bridge transactions
+///
+/// Nonces are handed out in order; a nonce released before submission is
+/// reused first so the account never leaves a gap that stalls later
+/// transactions.
+#[derive(Default)]
+pub struct NonceManager {
+    accounts: parking_lot::RwLock<HashMap<[u8; 20], AccountNonces>>,
+}
+
+#[derive(Default)]
+struct AccountNonces {
+    next: u64,
+    released: std::collections::BTreeSet<u64>,
+}
+
+impl NonceManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reserve the next nonce for `account`
+    pub fn reserve(&self, account: &[u8; 20]) -> u64 {
+        let mut accounts = self.accounts.write();
+        let nonces = accounts.entry(*account).or_default();
+        if let Some(nonce) = nonces.released.pop_first() {
+            return nonce;
+        }
+        let nonce = nonces.next;
+        nonces.next += 1;
+        nonce
+    }
+
+    /// Return an unused nonce so it is handed out again
+    pub fn release(&self, account: &[u8; 20], nonce: u64) {
+        if let Some(nonces) = self.accounts.write().get_mut(account) {
+            if nonce < nonces.next {
+                nonces.released.insert(nonce);
+            }
+        }
+    }
+
+    /// Align with the chain's transaction count for `account`
+    pub fn sync(&self, account: &[u8; 20], transaction_count: u64) {
+        let mut accounts = self.accounts.write();
+        let nonces = accounts.entry(*account).or_default();
+        nonces.next = nonces.next.max(transaction_count);
+        nonces.released.retain(|n| *n >= transaction_count);
+    }
+
+    /// Nonce the next reservation would return
+    pub fn peek(&self, account: &[u8; 20]) -> u64 {
+        self.accounts
+            .read()
+            .get(account)
+            .map(|n| n.released.first().copied().unwrap_or(n.next))
+            .unwrap_or(0)
+    }
+}
+
+// ============================================================================
+// Receipts
+// ============================================================================
+
+/// Transaction receipt as returned by `eth_getTransactionReceipt`
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct EvmReceipt {
+    pub tx_hash: [u8; 32],
+    pub block_number: u64,
+
+    /// Post-Byzantium status (true = success)
+    pub status: bool,
+    pub gas_used: u64,
+    pub effective_gas_price: u128,
+    pub logs: Vec<EvmLog>,
+}
+
+impl EvmReceipt {
+    /// Parse a JSON-RPC receipt object
+    pub fn from_rpc_json(value: &serde_json::Value) -> Result<Self, EncodingError> {
+        let field = |name: &str| -> Result<&str, EncodingError> {
+            value
+                .get(name)
+                .and_then(|v| v.as_str())
+                .ok_or_else(|| EncodingError::EncodingFailed(format!("missing {}", name)))
+        };
+
+        let logs = value
+            .get("logs")
+            .and_then(|v| v.as_array())
+            .map(|logs| {
+                logs.iter()
+                    .map(parse_rpc_log)
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .transpose()?
+            .unwrap_or_default();
+
+        Ok(Self {
+            tx_hash: parse_fixed(field("transactionHash")?)?,
+            block_number: parse_quantity(field("blockNumber")?)? as u64,
+            status: parse_quantity(field("status")?)? == 1,
+            gas_used: parse_quantity(field("gasUsed")?)? as u64,
+            effective_gas_price: parse_quantity(field("effectiveGasPrice")?)?,
+            logs,
+        })
+    }
+}
+
+fn parse_rpc_log(value: &serde_json::Value) -> Result<EvmLog, EncodingError> {
+    let missing = |name: &str| EncodingError::EncodingFailed(format!("log missing {}", name));
+
+    let address = value
+        .get("address")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| missing("address"))?;
+    let topics = value
+        .get("topics")
+        .and_then(|v| v.as_array())
+        .ok_or_else(|| missing("topics"))?
+        .iter()
+        .map(|t| parse_fixed(t.as_str().unwrap_or_default()))
+        .collect::<Result<Vec<[u8; 32]>, _>>()?;
+    let data = value
+        .get("data")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| missing("data"))?;
+
+    Ok(EvmLog {
+        address: parse_fixed(address)?,
+        topics,
+        data: hex::decode(data.strip_prefix("0x").unwrap_or(data))
+            .map_err(|e| EncodingError::EncodingFailed(e.to_string()))?,
+    })
+}
+
+fn parse_fixed<const N: usize>(value: &str) -> Result<[u8; N], EncodingError> {
+    let bytes = hex::decode(value.strip_prefix("0x").unwrap_or(value))
+        .map_err(|_| EncodingError::EncodingFailed(format!("invalid hex: {}", value)))?;
+    bytes
+        .try_into()
+        .map_err(|_| EncodingError::EncodingFailed(format!("expected {} bytes: {}", N, value)))
+}
+
+fn parse_quantity(value: &str) -> Result<u128, EncodingError> {
+    u128::from_str_radix(value.strip_prefix("0x").unwrap_or(value), 16)
+        .map_err(|_| EncodingError::EncodingFailed(format!("invalid quantity: {}", value)))
+}
+
+/// Event decoded from an invocation's receipt logs
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum InvocationEvent {
+    /// ERC-20 `Transfer(address,address,uint256)`
+    Erc20Transfer {
+        token: [u8; 20],
+        from: [u8; 20],
+        to: [u8; 20],
+        amount: u128,
+    },
+    /// Any log the bridge has no decoder for
+    Log {
+        address: [u8; 20],
+        topics: Vec<[u8; 32]>,
+        data: Vec<u8>,
+    },
+}
+
+impl InvocationEvent {
+    /// Decode a receipt log
+    pub fn decode(log: &EvmLog) -> Self {
+        let transfer_topic = keccak256(b"Transfer(address,address,uint256)");
+
+        if log.topics.len() == 3
+            && log.topics[0] == transfer_topic
+            && log.data.len() == 32
+            && log.data[..16].iter().all(|b| *b == 0)
+        {
+            let mut from = [0u8; 20];
+            let mut to = [0u8; 20];
+            from.copy_from_slice(&log.topics[1][12..]);
+            to.copy_from_slice(&log.topics[2][12..]);
+            let mut amount = [0u8; 16];
+            amount.copy_from_slice(&log.data[16..]);
+
+            return InvocationEvent::Erc20Transfer {
+                token: log.address,
+                from,
+                to,
+                amount: u128::from_be_bytes(amount),
+            };
+        }
+
+        InvocationEvent::Log {
+            address: log.address,
+            topics: log.topics.clone(),
+            data: log.data.clone(),
+        }
+    }
+}
+
 // ============================================================================
 // EVM Invocation Bridge (Main Interface)
 // ============================================================================
@@ -685,6 +1189,15 @@ pub struct EvmInvocationBridge {
 
     /// Bridge statistics
     stats: parking_lot::RwLock<BridgeStats>,
+
+    /// Fees applied to EIP-1559 transactions
+    fees: FeeParams,
+
+    /// Nonce allocation per bridge account
+    nonces: NonceManager,
+
+    /// Signed transaction hash → invocation ID
+    tx_index: parking_lot::RwLock<HashMap<[u8; 32], [u8; 32]>>,
 }
 
 impl EvmInvocationBridge {
@@ -699,9 +1212,23 @@ impl EvmInvocationBridge {
             pending_txs: parking_lot::RwLock::new(HashMap::new()),
             confirmed_txs: parking_lot::RwLock::new(Vec::new()),
             stats: parking_lot::RwLock::new(BridgeStats::default()),
+            fees: FeeParams::default(),
+            nonces: NonceManager::new(),
+            tx_index: parking_lot::RwLock::new(HashMap::new()),
         }
     }
 
+    /// Use `fees` for EIP-1559 transactions
+    pub fn with_fee_params(mut self, fees: FeeParams) -> Self {
+        self.fees = fees;
+        self
+    }
+
+    /// Nonce allocation per bridge account
+    pub fn nonces(&self) -> &NonceManager {
+        &self.nonces
+    }
+
     /// Invoke an EVM operation from a Rope string
     pub async fn invoke_evm(
         &self,
@@ -727,6 +1254,8 @@ impl EvmInvocationBridge {
             status: PendingStatus::Encoding,
             created_at: chrono::Utc::now().timestamp(),
             retries: 0,
+            sender: None,
+            tx_hash: None,
         };
 
         self.pending_txs.write().insert(invocation_id, pending);
@@ -746,6 +1275,137 @@ impl EvmInvocationBridge {
         })
     }
 
+    /// Build and sign an EIP-1559 transaction for a Rope string operation
+    ///
+    /// The nonce comes from `account`'s allocation; the returned raw
+    /// transaction is ready for `eth_sendRawTransaction`.
+    pub async fn invoke_evm_signed(
+        &self,
+        string_id: [u8; 32],
+        operation: StringOperation,
+        account: &BridgeAccount,
+    ) -> Result<SignedInvocation, BridgeError> {
+        let sender = account.address();
+        let nonce = self.nonces.reserve(&sender);
+
+        let signed = self
+            .encoder
+            .encode_string_to_evm(&string_id, &operation, &sender, nonce)
+            .and_then(|evm_tx| {
+                let mut tx = Eip1559Transaction::from_legacy(&evm_tx, &self.fees);
+                let tx_hash = account.sign(&mut tx)?;
+                let raw = tx.encode()?;
+                Ok((evm_tx, tx, tx_hash, raw))
+            });
+        let (evm_tx, transaction, tx_hash, raw) = match signed {
+            Ok(signed) => signed,
+            Err(e) => {
+                self.nonces.release(&sender, nonce);
+                return Err(BridgeError::TransactionFailed(e.to_string()));
+            }
+        };
+
+        let invocation_id = {
+            let mut input = Vec::with_capacity(64);
+            input.extend_from_slice(&string_id);
+            input.extend_from_slice(&tx_hash);
+            *blake3::hash(&input).as_bytes()
+        };
+
+        let pending = PendingBridgeTx {
+            invocation_id,
+            string_id,
+            evm_tx,
+            status: PendingStatus::Signed,
+            created_at: chrono::Utc::now().timestamp(),
+            retries: 0,
+            sender: Some(sender),
+            tx_hash: Some(tx_hash),
+        };
+        self.pending_txs.write().insert(invocation_id, pending);
+        self.tx_index.write().insert(tx_hash, invocation_id);
+
+        {
+            let mut stats = self.stats.write();
+            stats.total_invocations += 1;
+            stats.pending_count += 1;
+        }
+
+        Ok(SignedInvocation {
+            handle: InvocationHandle {
+                invocation_id,
+                string_id,
+                chain_id: self.config.chain_id,
+                estimated_gas: transaction.gas_limit,
+            },
+            sender,
+            tx_hash,
+            transaction,
+            raw,
+        })
+    }
+
+    /// Drop a signed invocation that was never broadcast, freeing its nonce
+    pub fn abandon_invocation(&self, invocation_id: &[u8; 32]) -> bool {
+        let Some(pending) = self.pending_txs.write().remove(invocation_id) else {
+            return false;
+        };
+        if let Some(tx_hash) = pending.tx_hash {
+            self.tx_index.write().remove(&tx_hash);
+        }
+        if let Some(sender) = pending.sender {
+            self.nonces.release(&sender, pending.evm_tx.nonce);
+        }
+
+        let mut stats = self.stats.write();
+        stats.pending_count = stats.pending_count.saturating_sub(1);
+        true
+    }
+
+    /// Map a mined transaction's receipt back to its invocation
+    pub fn process_receipt(&self, receipt: EvmReceipt) -> Result<InvocationOutcome, BridgeError> {
+        let invocation_id = self
+            .tx_index
+            .write()
+            .remove(&receipt.tx_hash)
+            .ok_or_else(|| {
+                BridgeError::VerificationFailed(format!(
+                    "Unknown transaction 0x{}",
+                    hex::encode(receipt.tx_hash)
+                ))
+            })?;
+        let pending = self
+            .pending_txs
+            .write()
+            .remove(&invocation_id)
+            .ok_or_else(|| {
+                BridgeError::VerificationFailed("Invocation no longer pending".to_string())
+            })?;
+
+        // A mined transaction consumes its nonce whether or not it reverted
+        if let Some(sender) = pending.sender {
+            self.nonces.sync(&sender, pending.evm_tx.nonce + 1);
+        }
+        self.record_confirmation(&pending, receipt.tx_hash, receipt.status, receipt.gas_used);
+
+        let events = receipt.logs.iter().map(InvocationEvent::decode).collect();
+        Ok(InvocationOutcome {
+            invocation_id,
+            string_id: pending.string_id,
+            tx_hash: receipt.tx_hash,
+            block_number: receipt.block_number,
+            fee_paid: receipt.gas_used as u128 * receipt.effective_gas_price,
+            result: EvmCallResult {
+                success: receipt.status,
+                return_data: Vec::new(),
+                gas_used: receipt.gas_used,
+                logs: receipt.logs,
+                error: (!receipt.status).then(|| "Transaction reverted".to_string()),
+            },
+            events,
+        })
+    }
+
     /// Process an incoming EVM event (bridge callback)
     pub async fn process_evm_event(
         &self,
@@ -794,32 +1454,19 @@ impl EvmInvocationBridge {
                 result,
             } => {
                 // Update pending transaction status
-                if let Some(mut pending) = self.pending_txs.write().remove(&invocation_id) {
-                    pending.status = if result.success {
-                        PendingStatus::Confirmed
-                    } else {
-                        PendingStatus::Failed(result.error.unwrap_or_default())
-                    };
-
-                    // Move to confirmed
-                    let confirmed = ConfirmedBridgeTx {
-                        invocation_id,
-                        string_id: pending.string_id,
-                        evm_tx_hash: *blake3::hash(&pending.evm_tx.data).as_bytes(),
-                        success: result.success,
-                        gas_used: result.gas_used,
-                        confirmed_at: chrono::Utc::now().timestamp(),
-                    };
-
-                    self.confirmed_txs.write().push(confirmed);
-
-                    let mut stats = self.stats.write();
-                    stats.pending_count = stats.pending_count.saturating_sub(1);
-                    if result.success {
-                        stats.successful_invocations += 1;
-                    } else {
-                        stats.failed_invocations += 1;
+                if let Some(pending) = self.pending_txs.write().remove(&invocation_id) {
+                    if let Some(tx_hash) = pending.tx_hash {
+                        self.tx_index.write().remove(&tx_hash);
                     }
+                    let evm_tx_hash = pending
+                        .tx_hash
+                        .unwrap_or_else(|| *blake3::hash(&pending.evm_tx.data).as_bytes());
+                    self.record_confirmation(
+                        &pending,
+                        evm_tx_hash,
+                        result.success,
+                        result.gas_used,
+                    );
                 }
 
                 Ok(RopeConcept::String { id: invocation_id })
@@ -839,6 +1486,32 @@ impl EvmInvocationBridge {
         // In production, this would maintain the full Merkle tree
     }
 
+    /// Move a finished invocation to the confirmed set
+    fn record_confirmation(
+        &self,
+        pending: &PendingBridgeTx,
+        evm_tx_hash: [u8; 32],
+        success: bool,
+        gas_used: u64,
+    ) {
+        self.confirmed_txs.write().push(ConfirmedBridgeTx {
+            invocation_id: pending.invocation_id,
+            string_id: pending.string_id,
+            evm_tx_hash,
+            success,
+            gas_used,
+            confirmed_at: chrono::Utc::now().timestamp(),
+        });
+
+        let mut stats = self.stats.write();
+        stats.pending_count = stats.pending_count.saturating_sub(1);
+        if success {
+            stats.successful_invocations += 1;
+        } else {
+            stats.failed_invocations += 1;
+        }
+    }
+
     fn generate_invocation_id(&self, string_id: &[u8; 32], tx: &EvmTransaction) -> [u8; 32] {
         let mut input = Vec::new();
         input.extend_from_slice(string_id);
@@ -989,6 +1662,32 @@ pub struct InvocationHandle {
     pub estimated_gas: u64,
 }
 
+/// Signed EIP-1559 invocation, ready for broadcast
+#[derive(Clone, Debug)]
+pub struct SignedInvocation {
+    pub handle: InvocationHandle,
+    pub sender: [u8; 20],
+    pub tx_hash: [u8; 32],
+    pub transaction: Eip1559Transaction,
+
+    /// EIP-2718 encoded transaction
+    pub raw: Vec<u8>,
+}
+
+/// Invocation result mapped from a transaction receipt
+#[derive(Clone, Debug)]
+pub struct InvocationOutcome {
+    pub invocation_id: [u8; 32],
+    pub string_id: [u8; 32],
+    pub tx_hash: [u8; 32],
+    pub block_number: u64,
+
+    /// `gas_used * effective_gas_price`, in wei
+    pub fee_paid: u128,
+    pub result: EvmCallResult,
+    pub events: Vec<InvocationEvent>,
+}
+
 /// Pending bridge transaction
 #[derive(Clone, Debug)]
 pub struct PendingBridgeTx {
@@ -998,6 +1697,12 @@ pub struct PendingBridgeTx {
     pub status: PendingStatus,
     pub created_at: i64,
     pub retries: u32,
+
+    /// Signing account, for transactions built by `invoke_evm_signed`
+    pub sender: Option<[u8; 20]>,
+
+    /// Hash of the signed transaction
+    pub tx_hash: Option<[u8; 32]>,
 }
 
 /// Pending transaction status
@@ -1062,6 +1767,8 @@ pub enum EncodingError {
     ChainMismatch { expected: u64, got: u64 },
     UnknownOperation,
     EncodingFailed(String),
+    MissingSignature,
+    SigningFailed(String),
 }
 
 impl std::fmt::Display for EncodingError {
@@ -1075,6 +1782,8 @@ impl std::fmt::Display for EncodingError {
             }
             EncodingError::UnknownOperation => write!(f, "Unknown operation"),
             EncodingError::EncodingFailed(e) => write!(f, "Encoding failed: {}", e),
+            EncodingError::MissingSignature => write!(f, "Transaction is not signed"),
+            EncodingError::SigningFailed(e) => write!(f, "Signing failed: {}", e),
         }
     }
 }
@@ -1127,4 +1836,269 @@ mod tests {
         let bridge = EvmInvocationBridge::new(config);
         assert_eq!(bridge.name(), "Ethereum Bridge");
     }
+
+    #[test]
+    fn test_rlp_encoding() {
+        let mut out = Vec::new();
+        rlp::encode_bytes(&mut out, b"dog");
+        assert_eq!(out, [0x83, b'd', b'o', b'g']);
+
+        let mut out = Vec::new();
+        rlp::encode_uint(&mut out, 0);
+        rlp::encode_uint(&mut out, 15);
+        rlp::encode_uint(&mut out, 1024);
+        assert_eq!(out, [0x80, 0x0f, 0x82, 0x04, 0x00]);
+
+        let mut items = Vec::new();
+        rlp::encode_bytes(&mut items, b"cat");
+        rlp::encode_bytes(&mut items, b"dog");
+        let mut out = Vec::new();
+        rlp::encode_list(&mut out, &items);
+        assert_eq!(out[0], 0xc8);
+
+        let mut out = Vec::new();
+        rlp::encode_bytes(&mut out, &[0xaa; 60]);
+        assert_eq!(&out[..2], &[0xb8, 60]);
+    }
+
+    #[test]
+    fn test_account_address_and_signing() {
+        let secret: [u8; 32] =
+            hex::decode("4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318")
+                .unwrap()
+                .try_into()
+                .unwrap();
+        let account = BridgeAccount::from_secret(&secret).unwrap();
+        assert_eq!(
+            hex::encode(account.address()),
+            "2c7536e3605d9c16a7a3d7b1898e529396a65c23"
+        );
+
+        let mut tx = Eip1559Transaction {
+            chain_id: 271828,
+            nonce: 7,
+            max_priority_fee_per_gas: 2_000_000_000,
+            max_fee_per_gas: 40_000_000_000,
+            gas_limit: 21_000,
+            to: Some([0x12; 20]),
+            value: 1_000_000_000_000_000_000,
+            data: Vec::new(),
+            access_list: Vec::new(),
+            signature: None,
+        };
+        assert!(matches!(tx.encode(), Err(EncodingError::MissingSignature)));
+
+        let tx_hash = account.sign(&mut tx).unwrap();
+        let raw = tx.encode().unwrap();
+        assert_eq!(raw[0], EIP1559_TX_TYPE);
+        assert_eq!(tx_hash, keccak256(&raw));
+        assert_eq!(tx.recover_sender().unwrap(), account.address());
+
+        // Keystore-derived accounts are deterministic per chain and index
+        let keystore = rope_crypto::keys::KeyStore::from_seed([9u8; 32]);
+        let a = BridgeAccount::from_keystore(&keystore, 271828, 0).unwrap();
+        let b = BridgeAccount::from_keystore(&keystore, 271828, 0).unwrap();
+        let c = BridgeAccount::from_keystore(&keystore, 271828, 1).unwrap();
+        assert_eq!(a.address(), b.address());
+        assert_ne!(a.address(), c.address());
+    }
+
+    #[test]
+    fn test_eip1559_known_answer() {
+        fn bytes<const N: usize>(s: &str) -> [u8; N] {
+            hex::decode(s).unwrap().try_into().unwrap()
+        }
+
+        // Mainnet tx 0x0ec0b6a2df4d87424e5f6ad2a654e27aaeb7dac20ae9e8385cc09087ad532ee0
+        let mut tx = Eip1559Transaction {
+            chain_id: 1,
+            nonce: 0x42,
+            max_priority_fee_per_gas: 0x3b9aca00,
+            max_fee_per_gas: 0x4a817c800,
+            gas_limit: 44386,
+            to: Some(bytes("6069a6c32cf691f5982febae4faf8a6f3ab2f0f6")),
+            value: 0,
+            data: hex::decode(
+                "a22cb4650000000000000000000000005eee75727d804a2b13038928d36f8b188945a57a\
+                 0000000000000000000000000000000000000000000000000000000000000000",
+            )
+            .unwrap(),
+            access_list: Vec::new(),
+            signature: None,
+        };
+        assert_eq!(
+            hex::encode(tx.signing_hash()),
+            "0d5688ac3897124635b6cf1bc0e29d6dfebceebdc10a54d74f2ef8b56535b682"
+        );
+        tx.signature = Some(EvmSignature {
+            y_parity: 0,
+            r: bytes("840cfc572845f5786e702984c2a582528cad4b49b2a10b9db1be7fca90058565"),
+            s: bytes("25e7109ceb98168d95b09b18bbf6b685130e0562f233877d492b94eee0c5b6d1"),
+        });
+        assert_eq!(
+            hex::encode(tx.hash().unwrap()),
+            "0ec0b6a2df4d87424e5f6ad2a654e27aaeb7dac20ae9e8385cc09087ad532ee0"
+        );
+        assert_eq!(
+            hex::encode(tx.recover_sender().unwrap()),
+            "dd6b8b3dc6b7ad97db52f08a275ff4483e024cea"
+        );
+
+        // Mainnet tx 0xce4dc6d7a7549a98ee3b071b67e970879ff51b5b95d1c340bacd80fa1e1aab31
+        let raw = "02f86f0102843b9aca0085029e7822d68298f094d9e1459a7a482635700cbc20bbaf52d4\
+                   95ab9c9680841b55ba3ac080a0c199674fcb29f353693dd779c017823b954b3c69dffa3c\
+                   d6b2a6ff7888798039a028ca912de909e7e6cdef9cdcaf24c54dd8c1032946dfa1d85c20\
+                   6b32a9064fe8";
+        let tx = Eip1559Transaction {
+            chain_id: 1,
+            nonce: 2,
+            max_priority_fee_per_gas: 0x3b9aca00,
+            max_fee_per_gas: 0x029e7822d6,
+            gas_limit: 0x98f0,
+            to: Some(bytes("d9e1459a7a482635700cbc20bbaf52d495ab9c96")),
+            value: 0,
+            data: vec![0x1b, 0x55, 0xba, 0x3a],
+            access_list: Vec::new(),
+            signature: Some(EvmSignature {
+                y_parity: 0,
+                r: bytes("c199674fcb29f353693dd779c017823b954b3c69dffa3cd6b2a6ff7888798039"),
+                s: bytes("28ca912de909e7e6cdef9cdcaf24c54dd8c1032946dfa1d85c206b32a9064fe8"),
+            }),
+        };
+        assert_eq!(hex::encode(tx.encode().unwrap()), raw);
+        assert_eq!(
+            hex::encode(tx.hash().unwrap()),
+            "ce4dc6d7a7549a98ee3b071b67e970879ff51b5b95d1c340bacd80fa1e1aab31"
+        );
+        assert_eq!(
+            hex::encode(tx.recover_sender().unwrap()),
+            "001e2b7de757ba469a57bf6b23d982458a07efce"
+        );
+    }
+
+    #[test]
+    fn test_nonce_manager() {
+        let nonces = NonceManager::new();
+        let account = [1u8; 20];
+
+        nonces.sync(&account, 5);
+        assert_eq!(nonces.reserve(&account), 5);
+        assert_eq!(nonces.reserve(&account), 6);
+        assert_eq!(nonces.reserve(&account), 7);
+
+        // Released nonces are reused before new ones
+        nonces.release(&account, 6);
+        assert_eq!(nonces.peek(&account), 6);
+        assert_eq!(nonces.reserve(&account), 6);
+        assert_eq!(nonces.reserve(&account), 8);
+
+        // Chain count past a released nonce discards it
+        nonces.release(&account, 7);
+        nonces.sync(&account, 9);
+        assert_eq!(nonces.reserve(&account), 9);
+        assert_eq!(nonces.peek(&[2u8; 20]), 0);
+    }
+
+    #[tokio::test]
+    async fn test_signed_invocation_receipt() {
+        let bridge = EvmInvocationBridge::new(EvmBridgeConfig {
+            name: "Ethereum Bridge".to_string(),
+            chain_id: 271828,
+            rpc_url: "https://erpc.datachain.network".to_string(),
+            bridge_contract: "0x0b44547be0a0df5dcd5327de8ea73680517c5a54".to_string(),
+            confirmations_required: 12,
+        });
+        let keystore = rope_crypto::keys::KeyStore::from_seed([3u8; 32]);
+        let account = BridgeAccount::from_keystore(&keystore, 271828, 0).unwrap();
+
+        let operation = StringOperation::TokenTransfer {
+            recipient: vec![0x12; 32],
+            amount: 500,
+            token: "USDC".to_string(),
+        };
+        let first = bridge
+            .invoke_evm_signed([1u8; 32], operation.clone(), &account)
+            .await
+            .unwrap();
+        let second = bridge
+            .invoke_evm_signed([2u8; 32], operation, &account)
+            .await
+            .unwrap();
+        assert_eq!(first.transaction.nonce, 0);
+        assert_eq!(second.transaction.nonce, 1);
+        assert_eq!(
+            first.transaction.recover_sender().unwrap(),
+            account.address()
+        );
+
+        // An abandoned invocation frees its nonce
+        assert!(bridge.abandon_invocation(&second.handle.invocation_id));
+        assert_eq!(bridge.nonces().peek(&account.address()), 1);
+
+        let token = first.transaction.to.unwrap();
+        let mut from = [0u8; 32];
+        from[12..].copy_from_slice(&account.address());
+        let mut to = [0u8; 32];
+        to[12..].copy_from_slice(&[0x34; 20]);
+        let mut amount = [0u8; 32];
+        amount[16..].copy_from_slice(&500u128.to_be_bytes());
+
+        let receipt = EvmReceipt {
+            tx_hash: first.tx_hash,
+            block_number: 100,
+            status: true,
+            gas_used: 50_000,
+            effective_gas_price: 10,
+            logs: vec![EvmLog {
+                address: token,
+                topics: vec![keccak256(b"Transfer(address,address,uint256)"), from, to],
+                data: amount.to_vec(),
+            }],
+        };
+        let outcome = bridge.process_receipt(receipt.clone()).unwrap();
+        assert_eq!(outcome.invocation_id, first.handle.invocation_id);
+        assert!(outcome.result.success);
+        assert_eq!(outcome.fee_paid, 500_000);
+        assert_eq!(
+            outcome.events,
+            vec![InvocationEvent::Erc20Transfer {
+                token,
+                from: account.address(),
+                to: [0x34; 20],
+                amount: 500,
+            }]
+        );
+
+        let stats = bridge.stats();
+        assert_eq!(stats.successful_invocations, 1);
+        assert_eq!(stats.pending_count, 0);
+        assert!(bridge.process_receipt(receipt).is_err());
+    }
+
+    #[test]
+    fn test_receipt_from_rpc_json() {
+        let json = serde_json::json!({
+            "transactionHash": format!("0x{}", "ab".repeat(32)),
+            "blockNumber": "0x1b4",
+            "status": "0x0",
+            "gasUsed": "0x5208",
+            "effectiveGasPrice": "0x3b9aca00",
+            "logs": [{
+                "address": format!("0x{}", "11".repeat(20)),
+                "topics": [format!("0x{}", "22".repeat(32))],
+                "data": "0x01"
+            }]
+        });
+
+        let receipt = EvmReceipt::from_rpc_json(&json).unwrap();
+        assert_eq!(receipt.tx_hash, [0xab; 32]);
+        assert_eq!(receipt.block_number, 436);
+        assert!(!receipt.status);
+        assert_eq!(receipt.gas_used, 21_000);
+        assert_eq!(receipt.effective_gas_price, 1_000_000_000);
+        assert!(matches!(
+            InvocationEvent::decode(&receipt.logs[0]),
+            InvocationEvent::Log { .. }
+        ));
+    }
 }
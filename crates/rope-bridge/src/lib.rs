@@ -862,6 +862,7 @@ pub mod encapsulation {
     //! - Cross-chain privacy preservation
 
     use super::*;
+    use rope_crypto::secret::{ct_eq, SecretBytes};
     use serde::{Deserialize, Serialize};
 
     /// Encapsulated transaction (anonymized)
@@ -956,7 +957,7 @@ pub mod encapsulation {
     /// Encapsulation engine
     pub struct EncapsulationEngine {
         /// Encryption key (for payload encryption)
-        encryption_key: [u8; 32],
+        encryption_key: SecretBytes,
 
         /// Nullifier set (spent nullifiers)
         nullifier_set: std::collections::HashSet<[u8; 32]>,
@@ -987,7 +988,7 @@ pub mod encapsulation {
             }
 
             Self {
-                encryption_key: key,
+                encryption_key: SecretBytes::from(key),
                 nullifier_set: std::collections::HashSet::new(),
                 mix_pool: Vec::new(),
                 stats: EncapsulationStats::default(),
@@ -1013,11 +1014,12 @@ pub mod encapsulation {
             }
 
             // Simple XOR encryption (in production, use proper AEAD)
+            let key = self.encryption_key.expose_secret();
             let encrypted_payload: Vec<u8> = request
                 .original_tx
                 .iter()
                 .enumerate()
-                .map(|(i, &b)| b ^ self.encryption_key[i % 32])
+                .map(|(i, &b)| b ^ key[i % 32])
                 .collect();
 
             // Generate ZK proof (placeholder)
@@ -1093,7 +1095,7 @@ pub mod encapsulation {
             }
 
             // Check commitment matches first public input
-            tx.commitment == tx.zkp.public_inputs[0]
+            ct_eq(&tx.commitment, &tx.zkp.public_inputs[0])
         }
 
         /// Mark nullifier as spent
@@ -1126,7 +1128,7 @@ pub mod encapsulation {
 
             // Verify commitment
             let computed_commitment = *blake3::hash(&decrypted).as_bytes();
-            if computed_commitment != tx.commitment {
+            if !ct_eq(&computed_commitment, &tx.commitment) {
                 return Err("Commitment verification failed".to_string());
             }
 
@@ -1209,6 +1211,12 @@ pub mod verification {
             signatures: Vec<Vec<u8>>,
             master_nodes: Vec<[u8; 20]>,
         },
+
+        /// Datachain Rope finalized-string inclusion proof
+        RopeInclusion {
+            proof: rope_core::InclusionProof,
+            accumulator_root: [u8; 32],
+        },
     }
 
     /// Proof verification result
@@ -1243,6 +1251,9 @@ pub mod verification {
 
         /// Trusted XDC master nodes
         xdc_master_nodes: std::collections::HashSet<[u8; 20]>,
+
+        /// Trusted Rope accumulator roots (from anchor strings)
+        rope_roots: std::collections::HashSet<[u8; 32]>,
     }
 
     impl CrossChainVerifier {
@@ -1252,6 +1263,7 @@ pub mod verification {
                 ethereum_state_roots: std::collections::HashMap::new(),
                 bitcoin_headers: Vec::new(),
                 xdc_master_nodes: std::collections::HashSet::new(),
+                rope_roots: std::collections::HashSet::new(),
             }
         }
 
@@ -1265,6 +1277,11 @@ pub mod verification {
             self.xdc_master_nodes.insert(node);
         }
 
+        /// Add trusted Rope accumulator root
+        pub fn add_rope_root(&mut self, root: [u8; 32]) {
+            self.rope_roots.insert(root);
+        }
+
         /// Verify a cross-chain proof
         pub fn verify(&self, proof: &CrossChainProof) -> VerificationResult {
             match proof {
@@ -1332,6 +1349,29 @@ pub mod verification {
                     }
                 }
 
+                CrossChainProof::RopeInclusion {
+                    proof,
+                    accumulator_root,
+                } => {
+                    let is_trusted = self.rope_roots.contains(accumulator_root);
+                    let is_valid = is_trusted && proof.verify(accumulator_root);
+
+                    VerificationResult {
+                        is_valid,
+                        confidence: if is_valid { 100 } else { 0 },
+                        data_hash: Some(*proof.string_id.as_bytes()),
+                        method: "rope_inclusion".to_string(),
+                        verified_at: chrono::Utc::now().timestamp(),
+                        error: if !is_trusted {
+                            Some("Unknown accumulator root".to_string())
+                        } else if !is_valid {
+                            Some("Invalid inclusion proof".to_string())
+                        } else {
+                            None
+                        },
+                    }
+                }
+
                 CrossChainProof::PolkadotFinality {
                     authority_set_id, ..
                 } => VerificationResult {
@@ -1365,10 +1405,13 @@ pub mod security {
     //! - Emergency pause/unpause
     //! - Rate limiting for large transfers
     //! - Guardian system for anomaly detection
+    //! - Quarantine checks against the security layer's block list
 
     use parking_lot::RwLock;
+    use rope_core::types::{QuarantineList, QuarantineSubject};
     use serde::{Deserialize, Serialize};
     use std::collections::{HashMap, HashSet};
+    use std::sync::Arc;
 
     /// Multi-signature configuration
     #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -1450,6 +1493,8 @@ pub mod security {
         per_tx_limit: u128,
         /// Large transfer threshold (triggers delay)
         large_transfer_threshold: u128,
+        /// Security quarantine list
+        quarantine: Option<Arc<dyn QuarantineList>>,
     }
 
     impl BridgeSecurityController {
@@ -1465,6 +1510,30 @@ pub mod security {
                 daily_limit: 1_000_000_000_000_000_000_000_000, // 1M tokens (18 decimals)
                 per_tx_limit: 100_000_000_000_000_000_000_000,  // 100K tokens
                 large_transfer_threshold: 10_000_000_000_000_000_000_000, // 10K tokens
+                quarantine: None,
+            }
+        }
+
+        /// Refuse transfers involving quarantined subjects
+        pub fn with_quarantine(mut self, quarantine: Arc<dyn QuarantineList>) -> Self {
+            self.quarantine = Some(quarantine);
+            self
+        }
+
+        /// Check that no party to a transfer is quarantined
+        pub fn check_parties_allowed(
+            &self,
+            parties: &[QuarantineSubject],
+        ) -> Result<(), SecurityError> {
+            let Some(quarantine) = &self.quarantine else {
+                return Ok(());
+            };
+            match parties.iter().find(|p| quarantine.is_quarantined(p)) {
+                Some(subject) => {
+                    tracing::warn!("Bridge transfer refused: {} is quarantined", subject);
+                    Err(SecurityError::Quarantined(*subject))
+                }
+                None => Ok(()),
             }
         }
 
@@ -1677,6 +1746,7 @@ pub mod security {
         TimeDelayNotMet,
         ExceedsPerTxLimit,
         ExceedsDailyLimit,
+        Quarantined(QuarantineSubject),
     }
 
     impl std::fmt::Display for SecurityError {
@@ -1690,6 +1760,7 @@ pub mod security {
                 SecurityError::TimeDelayNotMet => write!(f, "Time delay not met"),
                 SecurityError::ExceedsPerTxLimit => write!(f, "Exceeds per-transaction limit"),
                 SecurityError::ExceedsDailyLimit => write!(f, "Exceeds daily limit"),
+                SecurityError::Quarantined(subject) => write!(f, "{} is quarantined", subject),
             }
         }
     }
@@ -1810,6 +1881,31 @@ mod tests {
         assert!(matches!(eth, ProtocolType::Blockchain(_)));
         assert!(matches!(swift, ProtocolType::Finance(_)));
     }
+
+    #[test]
+    fn test_rope_inclusion_verification() {
+        use super::verification::*;
+
+        let mut accumulator = rope_core::MerkleAccumulator::new();
+        let id = rope_core::StringId::from_content(b"finalized");
+        accumulator.append(rope_core::StringId::from_content(b"earlier"));
+        accumulator.append(id);
+        let root = accumulator.root();
+        let proof = CrossChainProof::RopeInclusion {
+            proof: accumulator.prove(&id).unwrap(),
+            accumulator_root: root,
+        };
+
+        let mut verifier = CrossChainVerifier::new();
+        let result = verifier.verify(&proof);
+        assert!(!result.is_valid);
+        assert_eq!(result.error.as_deref(), Some("Unknown accumulator root"));
+
+        verifier.add_rope_root(root);
+        let result = verifier.verify(&proof);
+        assert!(result.is_valid);
+        assert_eq!(result.data_hash, Some(*id.as_bytes()));
+    }
 }
 
 mod security_tests {
@@ -1911,4 +2007,30 @@ mod security_tests {
         assert!(result.is_ok());
         assert_eq!(controller.pending_count(), 1);
     }
+
+    #[cfg(test)]
+    struct Blocked(rope_core::types::QuarantineSubject);
+
+    #[cfg(test)]
+    impl rope_core::types::QuarantineList for Blocked {
+        fn is_quarantined(&self, subject: &rope_core::types::QuarantineSubject) -> bool {
+            *subject == self.0
+        }
+    }
+
+    #[test]
+    fn test_quarantined_party_refused() {
+        use rope_core::types::QuarantineSubject;
+
+        let blocked = QuarantineSubject::Contract([0xbb; 20]);
+        let controller = BridgeSecurityController::default()
+            .with_quarantine(std::sync::Arc::new(Blocked(blocked)));
+
+        let clean = QuarantineSubject::Contract([0x01; 20]);
+        assert!(controller.check_parties_allowed(&[clean]).is_ok());
+        assert!(matches!(
+            controller.check_parties_allowed(&[clean, blocked]),
+            Err(SecurityError::Quarantined(s)) if s == blocked
+        ));
+    }
 }
@@ -5,9 +5,11 @@
 use clap::{Parser, Subcommand};
 use libp2p::identity::Keypair as LibP2pKeypair;
 use rope_crypto::keys::KeyPair;
-use rope_node::{NodeConfig, RopeNode};
+use rope_crypto::keystore::{Credentials, KdfParams, KeyKind, KeystoreFile};
+use rope_node::config::NodeMode;
+use rope_node::{Devnet, DevnetConfig, NetworkPreset, NodeConfig, RopeNode};
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, EnvFilter};
 
 /// JSON-RPC request structure
@@ -128,6 +130,12 @@ impl RpcClient {
 
 const DEFAULT_RPC_ENDPOINT: &str = "https://erpc.datachain.network";
 
+/// Node roles accepted on the command line
+const NODE_ROLES: [&str; 4] = ["validator", "relay", "seeder", "light"];
+
+/// Config file written by `rope init` inside the data directory
+const CONFIG_FILE: &str = "rope.toml";
+
 #[derive(Parser)]
 #[command(name = "rope")]
 #[command(author = "Datachain Foundation")]
@@ -137,7 +145,10 @@ const DEFAULT_RPC_ENDPOINT: &str = "https://erpc.datachain.network";
 Datachain Rope CLI - A revolutionary protocol inspired by DNA's double helix structure.
 
 QUICK START:
-  rope node --network mainnet    Start a relay node on mainnet
+  rope init --network mainnet    Create config, genesis and node keys in ~/.rope
+  rope run --role validator      Start the node initialized above
+  rope status                    Check the local node over RPC
+  rope devnet --validators 4     Run a local multi-validator devnet
   rope query status              Check network status
   rope token balance [ADDRESS]   Check FAT token balance
 
@@ -160,39 +171,111 @@ struct Cli {
 
 #[derive(Subcommand)]
 enum Commands {
-    /// Start a Rope node (validator, relay, or seeder)
+    /// Set up a data directory: config, genesis and an encrypted node key
     ///
     /// Examples:
-    ///   rope node                           Start relay node with defaults
-    ///   rope node --mode validator          Start as validator node
-    ///   rope node --network testnet         Connect to testnet
-    ///   rope node -c custom.toml            Use custom config file
+    ///   rope init                           Initialize ~/.rope for mainnet
+    ///   rope init --network testnet         Initialize for testnet
+    ///   rope init --network devnet          Initialize a local devnet
+    ///   rope init --role validator -d /srv/rope
+    #[command(after_help = "The keystore is encrypted with $ROPE_KEYSTORE_PASSPHRASE")]
+    Init {
+        /// Data directory to initialize
+        #[arg(short, long, default_value = "~/.rope")]
+        data_dir: PathBuf,
+
+        /// Network to initialize for: mainnet (default), testnet or devnet
+        #[arg(short, long, value_parser = NetworkPreset::NAMES)]
+        network: Option<String>,
+
+        /// Node role written to the config
+        #[arg(short, long, default_value = "relay", value_parser = NODE_ROLES)]
+        role: String,
+
+        /// Keyfile required alongside the passphrase ($ROPE_KEYSTORE_PASSPHRASE)
+        #[arg(long)]
+        keyfile: Option<PathBuf>,
+
+        /// Overwrite an existing config and node key
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Start a Rope node (validator, relay, seeder, or light)
+    ///
+    /// Examples:
+    ///   rope run                            Start the node in ~/.rope
+    ///   rope run --role validator           Start as validator node
+    ///   rope run --network testnet          Connect to testnet
+    ///   rope run -c custom.toml             Use custom config file
+    #[command(alias = "node")]
     #[command(after_help = "See https://datachain.network/docs/node for full setup guide")]
-    Node {
-        /// Configuration file path (TOML format)
-        #[arg(short, long, default_value = "rope.toml")]
-        config: PathBuf,
+    Run {
+        /// Configuration file path (TOML format); defaults to <DATA_DIR>/rope.toml
+        #[arg(short, long)]
+        config: Option<PathBuf>,
 
         /// Data directory for blockchain state and keys
         #[arg(short, long, default_value = "~/.rope")]
         data_dir: PathBuf,
 
-        /// Node mode: validator (requires stake), relay (P2P routing), or seeder (data distribution)
-        #[arg(short, long, default_value = "relay", value_parser = ["validator", "relay", "seeder"])]
-        mode: String,
+        /// Node role, overriding the config: validator (requires stake), relay (P2P routing),
+        /// seeder (data distribution) or light
+        #[arg(short, long, alias = "mode", value_parser = NODE_ROLES)]
+        role: Option<String>,
+
+        /// Network to join: mainnet (Chain ID 271828, default), testnet or devnet.
+        /// Must match the config and data directory if they already exist
+        #[arg(short, long, value_parser = NetworkPreset::NAMES)]
+        network: Option<String>,
+    },
+
+    /// Run a local devnet of in-process validators with prefunded accounts
+    ///
+    /// Examples:
+    ///   rope devnet                         Four validators, RPC on ports 8545-8548
+    ///   rope devnet --validators 7          Seven validators
+    ///   rope devnet --reset                 Start over from a fresh genesis
+    #[command(after_help = "Devnet keys and accounts are public; never send real funds to them")]
+    Devnet {
+        /// Number of validators to run
+        #[arg(long, default_value_t = 4)]
+        validators: usize,
+
+        /// Directory for the devnet genesis and validator data
+        #[arg(short, long, default_value = "~/.rope/devnet")]
+        data_dir: PathBuf,
+
+        /// HTTP RPC port of the first validator; the others use the following ports
+        #[arg(long, default_value_t = 8545)]
+        rpc_port: u16,
 
-        /// Network to connect to: mainnet (Chain ID 271828) or testnet
-        #[arg(short, long, default_value = "mainnet", value_parser = ["mainnet", "testnet"])]
-        network: String,
+        /// P2P port of the first validator; the others use the following ports
+        #[arg(long, default_value_t = 30300)]
+        p2p_port: u16,
+
+        /// Number of prefunded developer accounts
+        #[arg(long, default_value_t = 10)]
+        accounts: usize,
+
+        /// Delete existing devnet data before starting
+        #[arg(long)]
+        reset: bool,
     },
 
-    /// Generate cryptographic keypairs for node identity
+    /// Manage the node identity keystore
     ///
     /// Examples:
-    ///   rope keygen                         Generate standard Ed25519 keys
-    ///   rope keygen --quantum               Generate post-quantum keys (Dilithium3)
-    ///   rope keygen -o /path/to/keys        Specify output directory
-    #[command(after_help = "Keys are stored in PEM format. Backup securely!")]
+    ///   rope keys generate --quantum        Generate hybrid post-quantum keys
+    ///   rope keys import backup.json        Install a keystore into ~/.rope
+    ///   rope keys export -o backup.json     Copy the encrypted keystore out
+    Keys {
+        #[command(subcommand)]
+        keys: KeysCommands,
+    },
+
+    /// Generate cryptographic keypairs for node identity (same as `rope keys generate`)
+    #[command(hide = true)]
     Keygen {
         /// Output directory for generated keys
         #[arg(short, long, default_value = "~/.rope/keys")]
@@ -201,6 +284,35 @@ enum Commands {
         /// Generate quantum-resistant keys using CRYSTALS-Dilithium3
         #[arg(long)]
         quantum: bool,
+
+        /// Keyfile required alongside the passphrase ($ROPE_KEYSTORE_PASSPHRASE)
+        #[arg(long)]
+        keyfile: Option<PathBuf>,
+    },
+
+    /// Show the status of the local node via its RPC endpoint
+    ///
+    /// Examples:
+    ///   rope status                         Query the RPC address from ~/.rope/rope.toml
+    ///   rope status --rpc http://10.0.0.5:8545
+    Status {
+        /// RPC endpoint; defaults to rpc.http_addr from the node config
+        #[arg(long)]
+        rpc: Option<String>,
+
+        /// Data directory whose config names the RPC address
+        #[arg(short, long, default_value = "~/.rope")]
+        data_dir: PathBuf,
+    },
+
+    /// Export or import a snapshot of the node's chain data
+    ///
+    /// Examples:
+    ///   rope snapshot export -o rope.snap   Snapshot ~/.rope (keys excluded)
+    ///   rope snapshot import rope.snap      Restore into ~/.rope
+    Snapshot {
+        #[command(subcommand)]
+        snapshot: SnapshotCommands,
     },
 
     /// Display local node information and configuration
@@ -308,6 +420,96 @@ enum QueryCommands {
     Validators,
 }
 
+#[derive(Subcommand)]
+enum KeysCommands {
+    /// Generate a new node keypair into an encrypted keystore
+    ///
+    /// Example: rope keys generate --quantum -o ~/.rope/keys
+    Generate {
+        /// Output directory for generated keys
+        #[arg(short, long, default_value = "~/.rope/keys")]
+        output: PathBuf,
+
+        /// Generate quantum-resistant keys using CRYSTALS-Dilithium3
+        #[arg(long)]
+        quantum: bool,
+
+        /// Keyfile required alongside the passphrase ($ROPE_KEYSTORE_PASSPHRASE)
+        #[arg(long)]
+        keyfile: Option<PathBuf>,
+    },
+
+    /// Install an existing keystore as this node's identity
+    ///
+    /// The keystore is decrypted with $ROPE_KEYSTORE_PASSPHRASE and saved
+    /// again under a fresh salt.
+    ///
+    /// Example: rope keys import backup.keystore.json
+    Import {
+        /// Keystore file to import
+        #[arg(value_name = "KEYSTORE")]
+        file: PathBuf,
+
+        /// Data directory to install the key into
+        #[arg(short, long, default_value = "~/.rope")]
+        data_dir: PathBuf,
+
+        /// Keyfile required alongside the passphrase ($ROPE_KEYSTORE_PASSPHRASE)
+        #[arg(long)]
+        keyfile: Option<PathBuf>,
+
+        /// Replace an existing node key
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Copy the node's encrypted keystore to a backup file
+    ///
+    /// Example: rope keys export -o backup.keystore.json
+    Export {
+        /// Destination file
+        #[arg(short, long)]
+        output: PathBuf,
+
+        /// Data directory holding the node key
+        #[arg(short, long, default_value = "~/.rope")]
+        data_dir: PathBuf,
+    },
+}
+
+#[derive(Subcommand)]
+enum SnapshotCommands {
+    /// Write genesis and chain data (not keys) to a snapshot file
+    ///
+    /// Example: rope snapshot export -o rope.snap
+    Export {
+        /// Snapshot file to write
+        #[arg(short, long)]
+        output: PathBuf,
+
+        /// Data directory to snapshot
+        #[arg(short, long, default_value = "~/.rope")]
+        data_dir: PathBuf,
+    },
+
+    /// Restore a snapshot into a data directory
+    ///
+    /// Example: rope snapshot import rope.snap -d /srv/rope
+    Import {
+        /// Snapshot file to restore
+        #[arg(value_name = "SNAPSHOT")]
+        input: PathBuf,
+
+        /// Data directory to restore into
+        #[arg(short, long, default_value = "~/.rope")]
+        data_dir: PathBuf,
+
+        /// Replace files that already exist
+        #[arg(long)]
+        force: bool,
+    },
+}
+
 #[derive(Subcommand)]
 enum TokenCommands {
     /// Check FAT token balance for an address
@@ -365,50 +567,236 @@ fn expand_path(path: &PathBuf) -> PathBuf {
     path.clone()
 }
 
+fn parse_role(role: &str) -> NodeMode {
+    match role.to_lowercase().as_str() {
+        "validator" => NodeMode::Validator,
+        "seeder" => NodeMode::Seeder,
+        "light" => NodeMode::Light,
+        "relay" => NodeMode::Relay,
+        _ => {
+            tracing::warn!("Unknown role '{}', defaulting to relay", role);
+            NodeMode::Relay
+        }
+    }
+}
+
+/// Node config at `path`, or the defaults for `network` (mainnet if
+/// unset) if there is none
+///
+/// Fails if an existing config is for a different network than `network`.
+fn load_node_config(path: &Path, network: Option<&str>) -> anyhow::Result<NodeConfig> {
+    if !path.exists() {
+        return NodeConfig::for_network(network.unwrap_or("mainnet"));
+    }
+
+    let content = std::fs::read_to_string(path)?;
+    let config: NodeConfig = toml::from_str(&content)?;
+    if let Some(network) = network {
+        let preset = NetworkPreset::named(network)
+            .ok_or_else(|| anyhow::anyhow!("Unknown network: {}", network))?;
+        if preset.chain_id != config.node.chain_id {
+            anyhow::bail!(
+                "{:?} is for chain {}, not {} (chain {})",
+                path,
+                config.node.chain_id,
+                network,
+                preset.chain_id
+            );
+        }
+    }
+    Ok(config)
+}
+
+/// Display name of the network a config belongs to
+fn network_name(config: &NodeConfig) -> String {
+    match config.preset() {
+        Some(preset) => preset.name.to_string(),
+        None => format!("chain {}", config.node.chain_id),
+    }
+}
+
+/// Keystore of the node in `data_dir`, as named by its config
+fn node_keystore_path(data_dir: &Path) -> anyhow::Result<PathBuf> {
+    let config = load_node_config(&data_dir.join(CONFIG_FILE), None)?;
+    Ok(data_dir.join("keys").join(&config.node.keystore.path))
+}
+
+/// Credentials from $ROPE_KEYSTORE_PASSPHRASE and an optional keyfile
+fn keystore_credentials(keyfile: Option<&PathBuf>) -> anyhow::Result<Credentials> {
+    let passphrase = std::env::var("ROPE_KEYSTORE_PASSPHRASE")
+        .map_err(|_| anyhow::anyhow!("Set ROPE_KEYSTORE_PASSPHRASE to encrypt the keystore"))?;
+    let credentials = Credentials::passphrase(&passphrase);
+    match keyfile {
+        Some(keyfile) => Ok(credentials.with_keyfile_path(expand_path(keyfile))?),
+        None => Ok(credentials),
+    }
+}
+
+/// Encrypt `keypair` to `keystore_path`, with node.pub and node.id beside it
+fn write_keystore(
+    keystore_path: &Path,
+    keypair: &KeyPair,
+    credentials: &Credentials,
+) -> anyhow::Result<()> {
+    let keys_dir = keystore_path
+        .parent()
+        .ok_or_else(|| anyhow::anyhow!("Invalid keystore path: {:?}", keystore_path))?;
+    std::fs::create_dir_all(keys_dir)?;
+
+    // Private key only in encrypted form
+    KeystoreFile::encrypt(
+        KeyKind::Validator,
+        keypair,
+        credentials,
+        KdfParams::recommended(),
+    )?
+    .save(keystore_path)?;
+    std::fs::write(keys_dir.join("node.pub"), keypair.public_key_bytes())?;
+    std::fs::write(keys_dir.join("node.id"), hex::encode(keypair.node_id()))?;
+    Ok(())
+}
+
+fn generate_keys(output: &PathBuf, quantum: bool, keyfile: Option<&PathBuf>) -> anyhow::Result<()> {
+    let output_dir = expand_path(output);
+    let credentials = keystore_credentials(keyfile)?;
+
+    tracing::info!("Generating keypair...");
+
+    let keypair = if quantum {
+        tracing::info!("Using hybrid quantum-resistant keys (Ed25519 + Dilithium3)");
+        KeyPair::generate_hybrid()?
+    } else {
+        tracing::info!("Using classical Ed25519 keys");
+        KeyPair::generate()?
+    };
+
+    let keystore_path = output_dir.join("node.keystore.json");
+    write_keystore(&keystore_path, &keypair, &credentials)?;
+
+    println!("Keypair generated successfully!");
+    println!("Node ID: {}", hex::encode(keypair.node_id()));
+    println!("Keystore: {:?}", keystore_path);
+    println!("Public key: {:?}", output_dir.join("node.pub"));
+    Ok(())
+}
+
+async fn print_status(rpc: &RpcClient, endpoint: &str) {
+    match rpc.get_chain_id().await {
+        Ok(chain_id) => println!("Chain ID:     {} (0x{:X})", chain_id, chain_id),
+        Err(e) => println!("Chain ID:     Error - {}", e),
+    }
+
+    match rpc.get_block_number().await {
+        Ok(block) => println!("Block Height: {}", block),
+        Err(e) => println!("Block Height: Error - {}", e),
+    }
+
+    match rpc.get_peer_count().await {
+        Ok(peers) => println!("Peer Count:   {}", peers),
+        Err(e) => println!("Peer Count:   Error - {}", e),
+    }
+
+    println!("");
+    println!("RPC Endpoint: {}", endpoint);
+}
+
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     let cli = Cli::parse();
     init_logging(cli.verbose);
 
     match cli.command {
-        Commands::Node {
+        Commands::Init {
+            data_dir,
+            network,
+            role,
+            keyfile,
+            force,
+        } => {
+            let data_dir = expand_path(&data_dir);
+            std::fs::create_dir_all(&data_dir)?;
+
+            // Config
+            let config_path = data_dir.join(CONFIG_FILE);
+            let node_config = if config_path.exists() && !force {
+                println!("Config exists, keeping {:?}", config_path);
+                load_node_config(&config_path, network.as_deref())?
+            } else {
+                let mut node_config =
+                    NodeConfig::for_network(network.as_deref().unwrap_or("mainnet"))?;
+                node_config.node.mode = parse_role(&role);
+                if let Some(keyfile) = &keyfile {
+                    let keyfile = std::fs::canonicalize(expand_path(keyfile))?;
+                    node_config.node.keystore.keyfile = Some(keyfile.to_string_lossy().into());
+                }
+                std::fs::write(&config_path, toml::to_string_pretty(&node_config)?)?;
+                println!("Config written to {:?}", config_path);
+                node_config
+            };
+
+            // Genesis
+            let genesis = rope_node::genesis::load_or_create(
+                &data_dir.join("genesis.json"),
+                node_config.node.chain_id,
+            )?;
+            rope_node::genesis::check_network(&genesis, node_config.node.chain_id)?;
+            println!("Genesis hash: {}", hex::encode(genesis.genesis_hash));
+
+            // Node key
+            let keystore_path = data_dir.join("keys").join(&node_config.node.keystore.path);
+            if keystore_path.exists() && !force {
+                println!("Keystore exists, keeping {:?}", keystore_path);
+            } else {
+                let credentials = keystore_credentials(keyfile.as_ref())?;
+                let keypair = KeyPair::generate_hybrid()?;
+                write_keystore(&keystore_path, &keypair, &credentials)?;
+                println!("Node ID: {}", hex::encode(keypair.node_id()));
+                println!("Keystore written to {:?}", keystore_path);
+            }
+
+            println!("");
+            println!(
+                "Initialized {:?} for {}",
+                data_dir,
+                network_name(&node_config)
+            );
+            println!("Start the node with: rope run --data-dir {:?}", data_dir);
+        }
+
+        Commands::Run {
             config,
             data_dir,
-            mode,
+            role,
             network,
         } => {
-            let config_path = expand_path(&config);
             let data_dir = expand_path(&data_dir);
+            let config_path = match config {
+                Some(config) => expand_path(&config),
+                None => data_dir.join(CONFIG_FILE),
+            };
 
             tracing::info!("╔══════════════════════════════════════════════════════════════╗");
             tracing::info!("║           DATACHAIN ROPE NODE v0.1.0                         ║");
             tracing::info!("║   Distributed Information Communication Protocol            ║");
             tracing::info!("╚══════════════════════════════════════════════════════════════╝");
             tracing::info!("");
-            tracing::info!("Network: {}", network);
-            tracing::info!("Mode: {}", mode);
             tracing::info!("Config: {:?}", config_path);
             tracing::info!("Data: {:?}", data_dir);
 
-            // Load or create config
-            let mut node_config: NodeConfig = if config_path.exists() {
-                let content = std::fs::read_to_string(&config_path)?;
-                toml::from_str(&content)?
-            } else {
-                tracing::info!("Config not found, using defaults for {}", network);
-                NodeConfig::for_network(&network)?
-            };
+            if !config_path.exists() {
+                tracing::info!(
+                    "Config not found, using defaults for {}",
+                    network.as_deref().unwrap_or("mainnet")
+                );
+            }
+            let mut node_config = load_node_config(&config_path, network.as_deref())?;
+            tracing::info!("Network: {}", network_name(&node_config));
 
-            // Override mode from CLI
-            node_config.node.mode = match mode.to_lowercase().as_str() {
-                "validator" => rope_node::config::NodeMode::Validator,
-                "relay" => rope_node::config::NodeMode::Relay,
-                "seeder" => rope_node::config::NodeMode::Seeder,
-                _ => {
-                    tracing::warn!("Unknown mode '{}', defaulting to relay", mode);
-                    rope_node::config::NodeMode::Relay
-                }
-            };
+            // Override role from CLI
+            if let Some(role) = role {
+                node_config.node.mode = parse_role(&role);
+            }
+            tracing::info!("Role: {:?}", node_config.node.mode);
 
             // Create data directory
             std::fs::create_dir_all(&data_dir)?;
@@ -418,37 +806,145 @@ async fn main() -> anyhow::Result<()> {
             node.run().await?;
         }
 
-        Commands::Keygen { output, quantum } => {
-            let output_dir = expand_path(&output);
-            std::fs::create_dir_all(&output_dir)?;
-
-            tracing::info!("Generating keypair...");
+        Commands::Devnet {
+            validators,
+            data_dir,
+            rpc_port,
+            p2p_port,
+            accounts,
+            reset,
+        } => {
+            let data_dir = expand_path(&data_dir);
+            if reset && data_dir.exists() {
+                std::fs::remove_dir_all(&data_dir)?;
+            }
 
-            let keypair = if quantum {
-                tracing::info!("Using hybrid quantum-resistant keys (Ed25519 + Dilithium3)");
-                KeyPair::generate_hybrid()?
-            } else {
-                tracing::info!("Using classical Ed25519 keys");
-                KeyPair::generate()?
+            let config = DevnetConfig {
+                validators,
+                data_dir,
+                rpc_port,
+                p2p_port,
+                accounts,
+                ..DevnetConfig::default()
             };
+            let devnet = Devnet::open(&config)?;
+
+            println!("Datachain Rope devnet (chain {})", devnet.genesis.chain_id);
+            println!("Data: {:?}", config.data_dir);
+            println!("");
+            println!("Validators:");
+            for validator in &devnet.validators {
+                println!(
+                    "  {:<12} {}  {}",
+                    validator.name,
+                    validator.rpc_url(),
+                    validator.peer_id
+                );
+            }
+            println!("");
+            println!(
+                "Prefunded accounts ({} FAT each):",
+                config.account_balance / rope_node::genesis::tokenomics::FAT
+            );
+            for account in &devnet.accounts {
+                println!("  {}  seed {}", account.address, account.seed);
+            }
+            println!("");
+            println!("Press Ctrl+C to stop the devnet");
 
-            let node_id = keypair.node_id();
+            devnet.run().await?;
+        }
 
-            // Save keys
-            let priv_key_path = output_dir.join("node.key");
-            let pub_key_path = output_dir.join("node.pub");
-            let id_path = output_dir.join("node.id");
+        Commands::Keys { keys } => match keys {
+            KeysCommands::Generate {
+                output,
+                quantum,
+                keyfile,
+            } => generate_keys(&output, quantum, keyfile.as_ref())?,
+            KeysCommands::Import {
+                file,
+                data_dir,
+                keyfile,
+                force,
+            } => {
+                let data_dir = expand_path(&data_dir);
+                let keystore_path = node_keystore_path(&data_dir)?;
+                if keystore_path.exists() && !force {
+                    anyhow::bail!(
+                        "Node key already exists at {:?}; pass --force to replace it",
+                        keystore_path
+                    );
+                }
 
-            std::fs::write(&priv_key_path, keypair.private_key_bytes())?;
-            std::fs::write(&pub_key_path, keypair.public_key_bytes())?;
-            std::fs::write(&id_path, hex::encode(node_id))?;
+                let credentials = keystore_credentials(keyfile.as_ref())?;
+                let keypair = KeystoreFile::load(expand_path(&file))?.decrypt(&credentials)?;
+                write_keystore(&keystore_path, &keypair, &credentials)?;
 
-            println!("Keypair generated successfully!");
-            println!("Node ID: {}", hex::encode(node_id));
-            println!("Private key: {:?}", priv_key_path);
-            println!("Public key: {:?}", pub_key_path);
+                println!("Keystore imported successfully!");
+                println!("Node ID: {}", hex::encode(keypair.node_id()));
+                println!("Keystore: {:?}", keystore_path);
+            }
+            KeysCommands::Export { output, data_dir } => {
+                let keystore_path = node_keystore_path(&expand_path(&data_dir))?;
+                let output_path = expand_path(&output);
+
+                // Stays encrypted; importing needs the same passphrase
+                KeystoreFile::load(&keystore_path)?.save(&output_path)?;
+
+                println!("Keystore exported to {:?}", output_path);
+                println!("Keep the passphrase (and keyfile, if any) with the backup");
+            }
+        },
+
+        Commands::Keygen {
+            output,
+            quantum,
+            keyfile,
+        } => generate_keys(&output, quantum, keyfile.as_ref())?,
+
+        Commands::Status { rpc, data_dir } => {
+            let endpoint = match rpc {
+                Some(endpoint) => endpoint,
+                None => {
+                    let data_dir = expand_path(&data_dir);
+                    let config = load_node_config(&data_dir.join(CONFIG_FILE), None)?;
+                    format!("http://{}", config.rpc.http_addr)
+                }
+            };
+            let rpc = RpcClient::new(&endpoint);
+
+            println!("╔══════════════════════════════════════════════════════════════╗");
+            println!("║                  NODE STATUS                                 ║");
+            println!("╚══════════════════════════════════════════════════════════════╝");
+            println!("");
+            print_status(&rpc, &endpoint).await;
         }
 
+        Commands::Snapshot { snapshot } => match snapshot {
+            SnapshotCommands::Export { output, data_dir } => {
+                let data_dir = expand_path(&data_dir);
+                let output_path = expand_path(&output);
+                let summary = rope_node::snapshot::export(&data_dir, &output_path)?;
+
+                println!("Snapshot written to {:?}", output_path);
+                println!("Files: {} ({} bytes)", summary.files, summary.bytes);
+                println!("Digest: {}", hex::encode(summary.digest));
+            }
+            SnapshotCommands::Import {
+                input,
+                data_dir,
+                force,
+            } => {
+                let data_dir = expand_path(&data_dir);
+                std::fs::create_dir_all(&data_dir)?;
+                let summary = rope_node::snapshot::import(&expand_path(&input), &data_dir, force)?;
+
+                println!("Snapshot restored into {:?}", data_dir);
+                println!("Files: {} ({} bytes)", summary.files, summary.bytes);
+                println!("Digest: {}", hex::encode(summary.digest));
+            }
+        },
+
         Commands::Info { data_dir } => {
             let data_dir = expand_path(&data_dir);
 
@@ -469,7 +965,7 @@ async fn main() -> anyhow::Result<()> {
                 let node_id = std::fs::read_to_string(&id_path)?;
                 println!("Node ID: {}", node_id);
             } else {
-                println!("Node ID: Not configured (run 'rope keygen' first)");
+                println!("Node ID: Not configured (run 'rope init' first)");
             }
 
             println!("");
@@ -518,23 +1014,7 @@ async fn main() -> anyhow::Result<()> {
                     println!("╚══════════════════════════════════════════════════════════════╝");
                     println!("");
 
-                    match rpc.get_chain_id().await {
-                        Ok(chain_id) => println!("Chain ID:     {} (0x{:X})", chain_id, chain_id),
-                        Err(e) => println!("Chain ID:     Error - {}", e),
-                    }
-
-                    match rpc.get_block_number().await {
-                        Ok(block) => println!("Block Height: {}", block),
-                        Err(e) => println!("Block Height: Error - {}", e),
-                    }
-
-                    match rpc.get_peer_count().await {
-                        Ok(peers) => println!("Peer Count:   {}", peers),
-                        Err(e) => println!("Peer Count:   Error - {}", e),
-                    }
-
-                    println!("");
-                    println!("RPC Endpoint: {}", DEFAULT_RPC_ENDPOINT);
+                    print_status(&rpc, DEFAULT_RPC_ENDPOINT).await;
                 }
                 QueryCommands::Peers => {
                     println!("Connected Peers:");
@@ -0,0 +1,400 @@
+//! # Aggregate Testimonies
+//!
+//! Compresses a 2f+1 testimony set into a single BLS12-381 aggregate
+//! signature plus a validator bitmap. For 21 validators this shrinks the
+//! signature material of an anchor from ~15 hybrid signatures (~50 KB) to
+//! 96 bytes + 3 bytes of bitmap, and lets light clients verify finality
+//! with a single pairing check.
+//!
+//! Bitmap positions index into the canonical validator set ordering, which
+//! both the aggregator and the verifier must agree on.
+
+use crate::testimony::{aggregate_signing_data, TestimonyCollection, TestimonyError};
+use rope_core::types::{AttestationType, NodeId, StringId};
+use rope_crypto::bls::{BlsPublicKey, BlsSignature};
+use serde::{Deserialize, Serialize};
+
+/// Bitmap of validators that contributed to an aggregate
+///
+/// Always holds exactly `len.div_ceil(8)` bytes with the padding bits of the
+/// last byte clear; bitmaps read off the wire are checked on deserialization.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "RawValidatorBitmap")]
+pub struct ValidatorBitmap {
+    /// Number of validators in the set
+    len: usize,
+
+    /// Packed bits, LSB first
+    bits: Vec<u8>,
+}
+
+impl ValidatorBitmap {
+    /// Create an empty bitmap for a validator set of `len` entries
+    pub fn new(len: usize) -> Self {
+        Self {
+            len,
+            bits: vec![0u8; len.div_ceil(8)],
+        }
+    }
+
+    /// Rebuild a bitmap from its packed bytes
+    ///
+    /// Rejects a byte count that does not match `len` and any bit set past
+    /// the last validator.
+    pub fn from_bytes(len: usize, bits: Vec<u8>) -> Result<Self, TestimonyError> {
+        if bits.len() != len.div_ceil(8) {
+            return Err(TestimonyError::InvalidFormat(format!(
+                "Bitmap of {} validators needs {} bytes, got {}",
+                len,
+                len.div_ceil(8),
+                bits.len()
+            )));
+        }
+
+        let used = len % 8;
+        if used != 0 {
+            let padding = !0u8 << used;
+            if bits[bits.len() - 1] & padding != 0 {
+                return Err(TestimonyError::InvalidFormat(
+                    "Bitmap has padding bits set".to_string(),
+                ));
+            }
+        }
+
+        Ok(Self { len, bits })
+    }
+
+    /// Number of validators covered
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Check if the bitmap covers no validators
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Mark validator at `index` as a signer
+    pub fn set(&mut self, index: usize) {
+        if index < self.len {
+            self.bits[index / 8] |= 1 << (index % 8);
+        }
+    }
+
+    /// Check if validator at `index` signed
+    pub fn is_set(&self, index: usize) -> bool {
+        index < self.len
+            && self
+                .bits
+                .get(index / 8)
+                .is_some_and(|byte| byte & (1 << (index % 8)) != 0)
+    }
+
+    /// Number of signers
+    pub fn count(&self) -> usize {
+        self.indices().len()
+    }
+
+    /// Indices of signers in ascending order
+    pub fn indices(&self) -> Vec<usize> {
+        (0..self.len).filter(|&i| self.is_set(i)).collect()
+    }
+
+    /// Packed bytes
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bits
+    }
+}
+
+/// Unchecked wire form of [`ValidatorBitmap`]
+#[derive(Deserialize)]
+struct RawValidatorBitmap {
+    len: usize,
+    bits: Vec<u8>,
+}
+
+impl TryFrom<RawValidatorBitmap> for ValidatorBitmap {
+    type Error = TestimonyError;
+
+    fn try_from(raw: RawValidatorBitmap) -> Result<Self, Self::Error> {
+        Self::from_bytes(raw.len, raw.bits)
+    }
+}
+
+/// Aggregated testimony set for a single string
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AggregateTestimony {
+    /// Target string
+    pub string_id: StringId,
+
+    /// Attestation type shared by all signers
+    pub attestation_type: AttestationType,
+
+    /// Consensus round shared by all signers
+    pub round: u64,
+
+    /// Which validators contributed
+    pub signers: ValidatorBitmap,
+
+    /// Aggregate BLS signature
+    pub signature: BlsSignature,
+}
+
+impl AggregateTestimony {
+    /// Aggregate the BLS signatures of a testimony collection
+    ///
+    /// Only testimonies matching `attestation_type` and `round`, carrying a
+    /// BLS signature, and issued by a member of `validator_set` are included.
+    /// Duplicate testimonies from the same validator are counted once.
+    pub fn from_collection(
+        collection: &TestimonyCollection,
+        attestation_type: AttestationType,
+        round: u64,
+        validator_set: &[NodeId],
+    ) -> Result<Self, TestimonyError> {
+        let mut signers = ValidatorBitmap::new(validator_set.len());
+        let mut signatures = Vec::new();
+
+        for testimony in &collection.testimonies {
+            if testimony.attestation_type != attestation_type
+                || testimony.metadata.round != round
+                || testimony.signature.bls.is_empty()
+            {
+                continue;
+            }
+
+            let Some(index) = validator_set
+                .iter()
+                .position(|v| v == &testimony.validator_id)
+            else {
+                continue;
+            };
+
+            if signers.is_set(index) {
+                continue;
+            }
+
+            let signature = BlsSignature::from_bytes(&testimony.signature.bls)
+                .map_err(|_| TestimonyError::InvalidSignature)?;
+            signers.set(index);
+            signatures.push(signature);
+        }
+
+        if signatures.is_empty() {
+            return Err(TestimonyError::MissingSignature);
+        }
+
+        let signature =
+            BlsSignature::aggregate(&signatures).map_err(|_| TestimonyError::InvalidSignature)?;
+
+        Ok(Self {
+            string_id: collection.string_id,
+            attestation_type,
+            round,
+            signers,
+            signature,
+        })
+    }
+
+    /// Number of validators in the aggregate
+    pub fn signer_count(&self) -> usize {
+        self.signers.count()
+    }
+
+    /// Resolve the signer bitmap against the validator set
+    pub fn signer_ids(&self, validator_set: &[NodeId]) -> Vec<NodeId> {
+        self.signers
+            .indices()
+            .into_iter()
+            .filter_map(|i| validator_set.get(i).copied())
+            .collect()
+    }
+
+    /// The message every signer signed
+    pub fn signing_data(&self) -> Vec<u8> {
+        aggregate_signing_data(&self.string_id, self.attestation_type, self.round)
+    }
+
+    /// Verify the aggregate against the validator BLS keys
+    ///
+    /// `validator_keys` must follow the same ordering as the validator set
+    /// used to build the bitmap, and every key must have a verified proof of
+    /// possession.
+    pub fn verify(
+        &self,
+        validator_keys: &[BlsPublicKey],
+        threshold: usize,
+    ) -> Result<(), TestimonyError> {
+        if self.signers.len() != validator_keys.len() {
+            return Err(TestimonyError::InvalidFormat(
+                "Bitmap does not match validator set".to_string(),
+            ));
+        }
+
+        let collected = self.signer_count();
+        if collected < threshold {
+            return Err(TestimonyError::BelowThreshold {
+                collected,
+                required: threshold,
+            });
+        }
+
+        let keys: Vec<BlsPublicKey> = self
+            .signers
+            .indices()
+            .into_iter()
+            .map(|i| validator_keys[i].clone())
+            .collect();
+
+        self.signature
+            .verify_aggregate(&self.signing_data(), &keys)
+            .map_err(|_| TestimonyError::InvalidSignature)
+    }
+
+    /// Approximate encoded size in bytes (signature + bitmap + header)
+    pub fn encoded_size(&self) -> usize {
+        // string id + attestation type + round
+        32 + 1 + 8 + self.signers.as_bytes().len() + self.signature.as_bytes().len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testimony::Testimony;
+    use rope_core::clock::LamportClock;
+    use rope_crypto::bls::BlsSecretKey;
+
+    fn setup(n: usize) -> (Vec<NodeId>, Vec<BlsSecretKey>) {
+        let ids = (0..n).map(|i| NodeId::new([i as u8; 32])).collect();
+        let keys = (0..n)
+            .map(|i| BlsSecretKey::from_seed(&[i as u8 + 1; 32]))
+            .collect();
+        (ids, keys)
+    }
+
+    fn signed_collection(
+        string_id: StringId,
+        ids: &[NodeId],
+        keys: &[BlsSecretKey],
+        signers: usize,
+    ) -> TestimonyCollection {
+        let mut collection = TestimonyCollection::new(string_id);
+        for i in 0..signers {
+            let mut testimony = Testimony::new(
+                string_id,
+                ids[i],
+                AttestationType::Existence,
+                LamportClock::new(ids[i]),
+                1,
+            );
+            testimony.metadata.round = 7;
+            let sig = keys[i].sign(&testimony.aggregate_signing_data());
+            testimony.set_bls_signature(sig.as_bytes().to_vec());
+            collection.add(testimony);
+        }
+        collection
+    }
+
+    #[test]
+    fn test_bitmap() {
+        let mut bitmap = ValidatorBitmap::new(21);
+        assert_eq!(bitmap.as_bytes().len(), 3);
+
+        bitmap.set(0);
+        bitmap.set(9);
+        bitmap.set(20);
+        bitmap.set(21); // out of range, ignored
+
+        assert_eq!(bitmap.count(), 3);
+        assert!(bitmap.is_set(9));
+        assert!(!bitmap.is_set(10));
+        assert_eq!(bitmap.indices(), vec![0, 9, 20]);
+    }
+
+    #[test]
+    fn test_bitmap_rejects_malformed_bytes() {
+        // Short, long and padded encodings of a 21-validator bitmap
+        assert!(ValidatorBitmap::from_bytes(21, vec![0xff, 0xff]).is_err());
+        assert!(ValidatorBitmap::from_bytes(21, vec![0, 0, 0, 0xff]).is_err());
+        assert!(ValidatorBitmap::from_bytes(21, vec![0, 0, 0b0010_0000]).is_err());
+
+        let bitmap = ValidatorBitmap::from_bytes(21, vec![0xff, 0, 0b0001_0000]).unwrap();
+        assert_eq!(bitmap.count(), 9);
+        assert!(bitmap.is_set(20));
+
+        let mut wire = bincode::serialize(&bitmap).unwrap();
+        *wire.last_mut().unwrap() |= 0b1000_0000;
+        assert!(bincode::deserialize::<ValidatorBitmap>(&wire).is_err());
+
+        let short = bincode::serialize(&(21usize, vec![0xffu8])).unwrap();
+        assert!(bincode::deserialize::<ValidatorBitmap>(&short).is_err());
+    }
+
+    #[test]
+    fn test_aggregate_and_verify() {
+        let (ids, keys) = setup(21);
+        let string_id = StringId::from_content(b"anchor");
+        let collection = signed_collection(string_id, &ids, &keys, 15);
+
+        let aggregate =
+            AggregateTestimony::from_collection(&collection, AttestationType::Existence, 7, &ids)
+                .unwrap();
+
+        assert_eq!(aggregate.signer_count(), 15);
+        assert_eq!(aggregate.signer_ids(&ids), ids[..15].to_vec());
+        assert!(aggregate.encoded_size() < 150);
+
+        let pks: Vec<BlsPublicKey> = keys.iter().map(|k| k.public_key()).collect();
+        assert!(aggregate.verify(&pks, 15).is_ok());
+    }
+
+    #[test]
+    fn test_verify_below_threshold() {
+        let (ids, keys) = setup(21);
+        let string_id = StringId::from_content(b"anchor");
+        let collection = signed_collection(string_id, &ids, &keys, 10);
+
+        let aggregate =
+            AggregateTestimony::from_collection(&collection, AttestationType::Existence, 7, &ids)
+                .unwrap();
+        let pks: Vec<BlsPublicKey> = keys.iter().map(|k| k.public_key()).collect();
+
+        assert!(matches!(
+            aggregate.verify(&pks, 15),
+            Err(TestimonyError::BelowThreshold {
+                collected: 10,
+                required: 15
+            })
+        ));
+    }
+
+    #[test]
+    fn test_verify_rejects_tampered_bitmap() {
+        let (ids, keys) = setup(21);
+        let string_id = StringId::from_content(b"anchor");
+        let collection = signed_collection(string_id, &ids, &keys, 15);
+
+        let mut aggregate =
+            AggregateTestimony::from_collection(&collection, AttestationType::Existence, 7, &ids)
+                .unwrap();
+        aggregate.signers.set(20);
+
+        let pks: Vec<BlsPublicKey> = keys.iter().map(|k| k.public_key()).collect();
+        assert!(matches!(
+            aggregate.verify(&pks, 15),
+            Err(TestimonyError::InvalidSignature)
+        ));
+    }
+
+    #[test]
+    fn test_round_mismatch_excluded() {
+        let (ids, keys) = setup(4);
+        let string_id = StringId::from_content(b"anchor");
+        let collection = signed_collection(string_id, &ids, &keys, 3);
+
+        let result =
+            AggregateTestimony::from_collection(&collection, AttestationType::Existence, 8, &ids);
+        assert!(matches!(result, Err(TestimonyError::MissingSignature)));
+    }
+}
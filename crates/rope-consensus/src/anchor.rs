@@ -1,5 +1,6 @@
 //! Anchor strings for consensus synchronization
 
+use crate::aggregate::AggregateTestimony;
 use rope_core::string::RopeString;
 use rope_core::types::StringId;
 use serde::{Deserialize, Serialize};
@@ -23,6 +24,10 @@ pub struct AnchorString {
 
     /// Whether this is a famous anchor
     pub is_famous: bool,
+
+    /// Compressed testimony set (BLS aggregate + validator bitmap)
+    #[serde(default)]
+    pub aggregate: Option<AggregateTestimony>,
 }
 
 impl AnchorString {
@@ -34,9 +39,17 @@ impl AnchorString {
             strongly_sees: Vec::new(),
             testimony_count: 0,
             is_famous: false,
+            aggregate: None,
         }
     }
 
+    /// Attach an aggregate testimony, replacing the individual count
+    pub fn with_aggregate(mut self, aggregate: AggregateTestimony) -> Self {
+        self.testimony_count = aggregate.signer_count() as u32;
+        self.aggregate = Some(aggregate);
+        self
+    }
+
     /// Get the anchor's string ID
     pub fn id(&self) -> StringId {
         self.string.id()
@@ -0,0 +1,280 @@
+//! # Conflict Detection and Fork Choice
+//!
+//! Two strings may claim the same logical slot, e.g. two transfers spending
+//! the same account nonce. Both can be valid in isolation and gossiped by
+//! different validators, so consensus must pick exactly one.
+//!
+//! ## Fork-choice rule
+//!
+//! 1. The first claimant to be finalized wins the slot.
+//! 2. If several claimants are finalized by the same anchor, the one with
+//!    the lowest string ID wins.
+//! 3. Every other claimant is marked `FinalityStatus::Rejected` with a
+//!    reason naming the winner, including claims that arrive later.
+//!
+//! The rule only depends on anchor order and string IDs, so every honest
+//! node reaches the same decision.
+
+use crate::finality::FinalityStatus;
+use crate::finality_engine::FinalityEngine;
+use parking_lot::RwLock;
+use rope_core::types::StringId;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// A logical slot that at most one string may occupy
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct LogicalSlot {
+    /// Owner of the sequence (e.g. account or node ID)
+    pub owner: [u8; 32],
+
+    /// Position in the owner's sequence (e.g. nonce)
+    pub sequence: u64,
+}
+
+impl LogicalSlot {
+    /// Slot for an account nonce
+    pub fn account_nonce(account: [u8; 32], nonce: u64) -> Self {
+        Self {
+            owner: account,
+            sequence: nonce,
+        }
+    }
+}
+
+/// Conflict found when registering a claim
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SlotConflict {
+    pub slot: LogicalSlot,
+    pub claimants: Vec<StringId>,
+    pub winner: Option<StringId>,
+}
+
+#[derive(Clone, Debug, Default)]
+struct SlotState {
+    claimants: Vec<StringId>,
+    winner: Option<StringId>,
+}
+
+/// Tracks slot claims and applies the fork-choice rule
+#[derive(Default)]
+pub struct ConflictResolver {
+    slots: RwLock<HashMap<LogicalSlot, SlotState>>,
+    string_slots: RwLock<HashMap<StringId, LogicalSlot>>,
+    statuses: RwLock<HashMap<StringId, FinalityStatus>>,
+}
+
+impl ConflictResolver {
+    /// Create new resolver
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a string's claim on a slot
+    ///
+    /// Returns the conflict if another string already claims the slot. A
+    /// claim on a slot that already has a finalized winner is rejected
+    /// immediately.
+    pub fn register_claim(&self, string_id: StringId, slot: LogicalSlot) -> Option<SlotConflict> {
+        let mut slots = self.slots.write();
+        let state = slots.entry(slot).or_default();
+
+        if state.claimants.contains(&string_id) {
+            return None;
+        }
+        state.claimants.push(string_id);
+        self.string_slots.write().insert(string_id, slot);
+
+        if let Some(winner) = state.winner {
+            self.statuses
+                .write()
+                .insert(string_id, rejected_status(&slot, &winner));
+        } else {
+            self.statuses
+                .write()
+                .entry(string_id)
+                .or_insert(FinalityStatus::Pending);
+        }
+
+        if state.claimants.len() > 1 {
+            Some(SlotConflict {
+                slot,
+                claimants: state.claimants.clone(),
+                winner: state.winner,
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Apply the fork-choice rule for strings finalized by one anchor
+    ///
+    /// Returns strings newly rejected as a result.
+    pub fn on_anchor(&self, anchor_id: [u8; 32], finalized: &[StringId]) -> Vec<StringId> {
+        let string_slots = self.string_slots.read();
+        let mut slots = self.slots.write();
+        let mut statuses = self.statuses.write();
+        let mut rejected = Vec::new();
+
+        // Lowest string ID first, so same-anchor ties are deterministic
+        let mut ordered: Vec<StringId> = finalized.to_vec();
+        ordered.sort_by(|a, b| a.as_bytes().cmp(b.as_bytes()));
+
+        for string_id in ordered {
+            let Some(slot) = string_slots.get(&string_id).copied() else {
+                statuses.insert(string_id, FinalityStatus::Final { anchor_id });
+                continue;
+            };
+            let Some(state) = slots.get_mut(&slot) else {
+                continue;
+            };
+
+            match state.winner {
+                Some(winner) if winner != string_id => {
+                    tracing::error!(
+                        "String {:?} finalized after slot winner {:?}",
+                        string_id,
+                        winner
+                    );
+                    statuses.insert(string_id, rejected_status(&slot, &winner));
+                    rejected.push(string_id);
+                }
+                Some(_) => {}
+                None => {
+                    state.winner = Some(string_id);
+                    statuses.insert(string_id, FinalityStatus::Final { anchor_id });
+                    for other in state.claimants.iter().filter(|c| **c != string_id) {
+                        statuses.insert(*other, rejected_status(&slot, &string_id));
+                        rejected.push(*other);
+                    }
+                }
+            }
+        }
+
+        rejected
+    }
+
+    /// Status of a string, if it was seen by the resolver
+    pub fn status(&self, string_id: &StringId) -> Option<FinalityStatus> {
+        self.statuses.read().get(string_id).cloned()
+    }
+
+    /// Winner of a slot, if decided
+    pub fn winner(&self, slot: &LogicalSlot) -> Option<StringId> {
+        self.slots.read().get(slot).and_then(|s| s.winner)
+    }
+
+    /// All rejected strings with their reasons
+    pub fn rejected(&self) -> Vec<(StringId, String)> {
+        self.statuses
+            .read()
+            .iter()
+            .filter_map(|(id, status)| match status {
+                FinalityStatus::Rejected { reason } => Some((*id, reason.clone())),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Propagate rejections to the finality engine
+    pub fn apply_to(&self, engine: &FinalityEngine) {
+        for (string_id, reason) in self.rejected() {
+            if !engine.is_finalized(&string_id) {
+                engine.reject_string(&string_id, reason);
+            }
+        }
+    }
+}
+
+fn rejected_status(slot: &LogicalSlot, winner: &StringId) -> FinalityStatus {
+    FinalityStatus::Rejected {
+        reason: format!(
+            "Conflicts with {} for slot {}:{}",
+            winner.to_hex(),
+            StringId::new(slot.owner).to_hex(),
+            slot.sequence
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sid(b: u8) -> StringId {
+        StringId::new([b; 32])
+    }
+
+    #[test]
+    fn test_no_conflict_for_distinct_slots() {
+        let resolver = ConflictResolver::new();
+        assert!(resolver
+            .register_claim(sid(1), LogicalSlot::account_nonce([1; 32], 0))
+            .is_none());
+        assert!(resolver
+            .register_claim(sid(2), LogicalSlot::account_nonce([1; 32], 1))
+            .is_none());
+        assert!(resolver.rejected().is_empty());
+    }
+
+    #[test]
+    fn test_first_finalized_wins() {
+        let resolver = ConflictResolver::new();
+        let slot = LogicalSlot::account_nonce([1; 32], 7);
+
+        resolver.register_claim(sid(9), slot);
+        let conflict = resolver.register_claim(sid(3), slot).unwrap();
+        assert_eq!(conflict.claimants.len(), 2);
+
+        // Higher ID finalized first still wins
+        let rejected = resolver.on_anchor([0xaa; 32], &[sid(9)]);
+        assert_eq!(rejected, vec![sid(3)]);
+        assert_eq!(resolver.winner(&slot), Some(sid(9)));
+        assert!(resolver.status(&sid(9)).unwrap().is_final());
+        assert!(resolver.status(&sid(3)).unwrap().is_rejected());
+    }
+
+    #[test]
+    fn test_same_anchor_tie_breaks_on_lowest_id() {
+        let resolver = ConflictResolver::new();
+        let slot = LogicalSlot::account_nonce([2; 32], 0);
+        resolver.register_claim(sid(5), slot);
+        resolver.register_claim(sid(4), slot);
+
+        resolver.on_anchor([0xbb; 32], &[sid(5), sid(4)]);
+        assert_eq!(resolver.winner(&slot), Some(sid(4)));
+        assert!(resolver.status(&sid(5)).unwrap().is_rejected());
+    }
+
+    #[test]
+    fn test_late_claim_rejected_immediately() {
+        let resolver = ConflictResolver::new();
+        let slot = LogicalSlot::account_nonce([3; 32], 1);
+        resolver.register_claim(sid(1), slot);
+        resolver.on_anchor([0xcc; 32], &[sid(1)]);
+
+        let conflict = resolver.register_claim(sid(2), slot).unwrap();
+        assert_eq!(conflict.winner, Some(sid(1)));
+        match resolver.status(&sid(2)).unwrap() {
+            FinalityStatus::Rejected { reason } => assert!(reason.contains("Conflicts with")),
+            other => panic!("unexpected status {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_apply_to_finality_engine() {
+        let engine = FinalityEngine::default();
+        let resolver = ConflictResolver::new();
+        let slot = LogicalSlot::account_nonce([4; 32], 0);
+
+        for b in [1, 2] {
+            engine.register_string(sid(b), vec![]);
+            resolver.register_claim(sid(b), slot);
+        }
+        resolver.on_anchor([0xdd; 32], &[sid(1)]);
+        resolver.apply_to(&engine);
+
+        assert!(engine.get_state(&sid(2)).unwrap().is_rejected());
+        assert!(!engine.get_state(&sid(1)).unwrap().is_rejected());
+    }
+}
@@ -0,0 +1,482 @@
+//! # Equivocation Detection
+//!
+//! Detects validators that sign conflicting statements for the same round
+//! (double-signing) and packages both signed statements as evidence.
+//!
+//! ## Conflicts
+//!
+//! - **Gossip events**: two distinct events from the same creator with the
+//!   same round number (a fork of the creator's self-parent chain).
+//! - **Testimonies**: two testimonies from the same validator for the same
+//!   target string and attestation type that vote for different rounds.
+//!   The round is covered by the BLS signature; testimonies that differ
+//!   only in their Lamport clock or metadata are retransmissions, not
+//!   conflicts.
+//!
+//! Evidence is self-contained: any node can re-check the conflict and the
+//! signatures without access to the detector's state, then forward it to
+//! the federation slashing path as a [`MisbehaviorReport`].
+
+use crate::testimony::Testimony;
+use crate::virtual_voting_impl::{GossipEvent, NodeId, StringId};
+use parking_lot::RwLock;
+use rope_crypto::bls::{BlsPublicKey, BlsSignature};
+use rope_crypto::hybrid::{HybridPublicKey, HybridSignature, HybridVerifier};
+use rope_federation::misbehavior::{MisbehaviorKind, MisbehaviorReport};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+/// The pair of conflicting signed statements
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum EquivocationKind {
+    /// Two events forking the creator's chain
+    ConflictingEvents {
+        first: Box<GossipEvent>,
+        second: Box<GossipEvent>,
+    },
+    /// Two testimonies for the same slot
+    ConflictingTestimonies {
+        first: Box<Testimony>,
+        second: Box<Testimony>,
+    },
+}
+
+/// Cryptographic evidence of a double-signing validator
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct EquivocationEvidence {
+    /// Offending validator
+    pub offender: NodeId,
+
+    /// Round in which the conflict occurred
+    pub round: u64,
+
+    /// Conflicting statements
+    pub kind: EquivocationKind,
+
+    /// Detection time (Unix seconds)
+    pub detected_at: i64,
+}
+
+impl EquivocationEvidence {
+    /// Build evidence from two events, if they actually conflict
+    pub fn from_events(first: &GossipEvent, second: &GossipEvent) -> Option<Self> {
+        if first.creator != second.creator || first.round != second.round || first.id == second.id {
+            return None;
+        }
+
+        // Canonical order so both detectors produce identical evidence
+        let (first, second) = if first.id <= second.id {
+            (first, second)
+        } else {
+            (second, first)
+        };
+
+        Some(Self {
+            offender: first.creator,
+            round: first.round,
+            kind: EquivocationKind::ConflictingEvents {
+                first: Box::new(first.clone()),
+                second: Box::new(second.clone()),
+            },
+            detected_at: chrono::Utc::now().timestamp(),
+        })
+    }
+
+    /// Build evidence from two testimonies, if they actually conflict
+    pub fn from_testimonies(first: &Testimony, second: &Testimony) -> Option<Self> {
+        if !testimonies_conflict(first, second) {
+            return None;
+        }
+
+        let (first, second) = if first.id <= second.id {
+            (first, second)
+        } else {
+            (second, first)
+        };
+
+        Some(Self {
+            offender: *first.validator_id.as_bytes(),
+            round: first.metadata.round.min(second.metadata.round),
+            kind: EquivocationKind::ConflictingTestimonies {
+                first: Box::new(first.clone()),
+                second: Box::new(second.clone()),
+            },
+            detected_at: chrono::Utc::now().timestamp(),
+        })
+    }
+
+    /// Misbehavior category for the federation
+    pub fn misbehavior_kind(&self) -> MisbehaviorKind {
+        match self.kind {
+            EquivocationKind::ConflictingEvents { .. } => MisbehaviorKind::EquivocatingEvents,
+            EquivocationKind::ConflictingTestimonies { .. } => {
+                MisbehaviorKind::EquivocatingTestimonies
+            }
+        }
+    }
+
+    /// Hash committing to the conflicting statements
+    ///
+    /// Independent of `detected_at`, so the same conflict found by several
+    /// nodes hashes identically and is only slashed once.
+    pub fn evidence_hash(&self) -> [u8; 32] {
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(b"rope-equivocation");
+        hasher.update(&self.offender);
+        hasher.update(&self.round.to_le_bytes());
+        match &self.kind {
+            EquivocationKind::ConflictingEvents { first, second } => {
+                hasher.update(&[0]);
+                hasher.update(&first.id);
+                hasher.update(&second.id);
+            }
+            EquivocationKind::ConflictingTestimonies { first, second } => {
+                hasher.update(&[1]);
+                hasher.update(&first.id);
+                hasher.update(&second.id);
+            }
+        }
+        *hasher.finalize().as_bytes()
+    }
+
+    /// Check that the evidence describes a genuine conflict
+    pub fn is_conflict(&self) -> bool {
+        match &self.kind {
+            EquivocationKind::ConflictingEvents { first, second } => {
+                first.creator == self.offender
+                    && second.creator == self.offender
+                    && first.round == self.round
+                    && second.round == self.round
+                    && first.id != second.id
+            }
+            EquivocationKind::ConflictingTestimonies { first, second } => {
+                first.validator_id.as_bytes() == &self.offender
+                    && first.metadata.round.min(second.metadata.round) == self.round
+                    && testimonies_conflict(first, second)
+            }
+        }
+    }
+
+    /// Verify the conflict and both signatures against the offender's key
+    ///
+    /// Event signatures cover the event ID; testimony signatures cover
+    /// [`Testimony::signing_data`]. The conflicting rounds of testimony
+    /// evidence are only signed with BLS, so also check [`Self::verify_votes`].
+    pub fn verify(&self, public_key: &HybridPublicKey) -> bool {
+        if !self.is_conflict() || public_key.node_id() != self.offender {
+            return false;
+        }
+
+        match &self.kind {
+            EquivocationKind::ConflictingEvents { first, second } => [first, second]
+                .iter()
+                .all(|e| verify_event_signature(public_key, e)),
+            EquivocationKind::ConflictingTestimonies { first, second } => [first, second]
+                .iter()
+                .all(|t| verify_testimony_signature(public_key, t)),
+        }
+    }
+
+    /// Verify the BLS signatures over the conflicting testimony votes
+    ///
+    /// Always true for event evidence, which is fully covered by [`Self::verify`].
+    pub fn verify_votes(&self, bls_key: &BlsPublicKey) -> bool {
+        match &self.kind {
+            EquivocationKind::ConflictingEvents { .. } => true,
+            EquivocationKind::ConflictingTestimonies { first, second } => {
+                [first, second].iter().all(|t| {
+                    BlsSignature::from_bytes(&t.signature.bls)
+                        .map(|sig| bls_key.verify(&t.aggregate_signing_data(), &sig).is_ok())
+                        .unwrap_or(false)
+                })
+            }
+        }
+    }
+
+    /// Package as a federation misbehavior report
+    pub fn to_report(&self) -> MisbehaviorReport {
+        MisbehaviorReport {
+            offender: self.offender,
+            kind: self.misbehavior_kind(),
+            round: self.round,
+            evidence_hash: self.evidence_hash(),
+            evidence: bincode::serialize(self).unwrap_or_default(),
+            reported_at: self.detected_at,
+        }
+    }
+}
+
+/// Slot a testimony occupies; a validator casts one vote per slot
+fn testimony_slot(t: &Testimony) -> (NodeId, StringId, u8) {
+    (
+        *t.validator_id.as_bytes(),
+        *t.target_string_id.as_bytes(),
+        t.attestation_type.as_u8(),
+    )
+}
+
+/// Same slot, both votes BLS-signed, different rounds voted for
+///
+/// Clock, OES generation and metadata other than the round are ignored.
+fn testimonies_conflict(first: &Testimony, second: &Testimony) -> bool {
+    testimony_slot(first) == testimony_slot(second)
+        && !first.signature.bls.is_empty()
+        && !second.signature.bls.is_empty()
+        && first.aggregate_signing_data() != second.aggregate_signing_data()
+}
+
+pub(crate) fn verify_event_signature(public_key: &HybridPublicKey, event: &GossipEvent) -> bool {
+    let signature = match bincode::deserialize::<HybridSignature>(&event.signature) {
+        Ok(sig) => sig,
+        Err(_) => return false,
+    };
+    HybridVerifier::verify(public_key, &event.id, &signature).unwrap_or(false)
+}
+
+fn verify_testimony_signature(public_key: &HybridPublicKey, testimony: &Testimony) -> bool {
+    let signature = HybridSignature {
+        ed25519_sig: testimony.signature.ed25519.clone(),
+        dilithium_sig: testimony.signature.dilithium.clone(),
+    };
+    HybridVerifier::verify(public_key, &testimony.signing_data(), &signature).unwrap_or(false)
+}
+
+/// Watches incoming events and testimonies for double-signing
+pub struct EquivocationDetector {
+    /// First event seen per (creator, round)
+    events: RwLock<HashMap<(NodeId, u64), GossipEvent>>,
+
+    /// First testimony seen per slot
+    testimonies: RwLock<HashMap<(NodeId, StringId, u8), Testimony>>,
+
+    /// Evidence hashes already emitted
+    reported: RwLock<HashSet<[u8; 32]>>,
+
+    /// Evidence waiting to be exported
+    pending: RwLock<Vec<EquivocationEvidence>>,
+}
+
+impl EquivocationDetector {
+    /// Create new detector
+    pub fn new() -> Self {
+        Self {
+            events: RwLock::new(HashMap::new()),
+            testimonies: RwLock::new(HashMap::new()),
+            reported: RwLock::new(HashSet::new()),
+            pending: RwLock::new(Vec::new()),
+        }
+    }
+
+    /// Observe a gossip event, returning evidence if it equivocates
+    pub fn observe_event(&self, event: &GossipEvent) -> Option<EquivocationEvidence> {
+        let key = (event.creator, event.round);
+        let evidence = {
+            let mut events = self.events.write();
+            match events.get(&key) {
+                Some(existing) => EquivocationEvidence::from_events(existing, event)?,
+                None => {
+                    events.insert(key, event.clone());
+                    return None;
+                }
+            }
+        };
+        self.record(evidence)
+    }
+
+    /// Observe a testimony, returning evidence if it equivocates
+    pub fn observe_testimony(&self, testimony: &Testimony) -> Option<EquivocationEvidence> {
+        let key = testimony_slot(testimony);
+        let evidence = {
+            let mut testimonies = self.testimonies.write();
+            match testimonies.get(&key) {
+                Some(existing) => EquivocationEvidence::from_testimonies(existing, testimony)?,
+                None => {
+                    testimonies.insert(key, testimony.clone());
+                    return None;
+                }
+            }
+        };
+        self.record(evidence)
+    }
+
+    fn record(&self, evidence: EquivocationEvidence) -> Option<EquivocationEvidence> {
+        if !self.reported.write().insert(evidence.evidence_hash()) {
+            return None;
+        }
+        tracing::warn!(
+            "Equivocation by {} in round {}",
+            hex_prefix(&evidence.offender),
+            evidence.round
+        );
+        self.pending.write().push(evidence.clone());
+        Some(evidence)
+    }
+
+    /// Take all pending evidence as federation misbehavior reports
+    pub fn drain_reports(&self) -> Vec<MisbehaviorReport> {
+        self.pending
+            .write()
+            .drain(..)
+            .map(|e| e.to_report())
+            .collect()
+    }
+
+    /// Number of reports waiting to be exported
+    pub fn pending_count(&self) -> usize {
+        self.pending.read().len()
+    }
+
+    /// Forget observations older than `round` to bound memory
+    pub fn prune_below(&self, round: u64) {
+        self.events.write().retain(|(_, r), _| *r >= round);
+        self.testimonies
+            .write()
+            .retain(|_, t| t.metadata.round >= round);
+    }
+}
+
+impl Default for EquivocationDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn hex_prefix(id: &[u8; 32]) -> String {
+    id[..6].iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rope_core::clock::LamportClock;
+    use rope_core::types::{AttestationType, NodeId as CoreNodeId, StringId as CoreStringId};
+    use rope_crypto::bls::BlsSecretKey;
+    use rope_crypto::hybrid::HybridSigner;
+
+    fn signed_event(signer: &HybridSigner, creator: NodeId, strings: Vec<StringId>) -> GossipEvent {
+        let mut event = GossipEvent::new(creator, 3, strings, [1u8; 32], None);
+        event.signature = bincode::serialize(&signer.sign(&event.id)).unwrap();
+        event
+    }
+
+    #[test]
+    fn test_detects_conflicting_events() {
+        let (signer, public_key) = HybridSigner::generate_signing_only();
+        let creator = public_key.node_id();
+        let detector = EquivocationDetector::new();
+
+        let a = signed_event(&signer, creator, vec![[10u8; 32]]);
+        let b = signed_event(&signer, creator, vec![[11u8; 32]]);
+
+        assert!(detector.observe_event(&a).is_none());
+        assert!(detector.observe_event(&a).is_none());
+
+        let evidence = detector.observe_event(&b).expect("equivocation");
+        assert_eq!(evidence.offender, creator);
+        assert_eq!(evidence.round, 3);
+        assert!(evidence.verify(&public_key));
+
+        // Same conflict is reported only once
+        assert!(detector.observe_event(&b).is_none());
+
+        let reports = detector.drain_reports();
+        assert_eq!(reports.len(), 1);
+        assert_eq!(reports[0].kind, MisbehaviorKind::EquivocatingEvents);
+        assert_eq!(reports[0].evidence_hash, evidence.evidence_hash());
+        assert_eq!(detector.pending_count(), 0);
+    }
+
+    #[test]
+    fn test_evidence_rejects_wrong_key() {
+        let (signer, public_key) = HybridSigner::generate_signing_only();
+        let (_, other_key) = HybridSigner::generate_signing_only();
+        let creator = public_key.node_id();
+
+        let a = signed_event(&signer, creator, vec![[10u8; 32]]);
+        let b = signed_event(&signer, creator, vec![[11u8; 32]]);
+        let evidence = EquivocationEvidence::from_events(&a, &b).unwrap();
+
+        assert!(!evidence.verify(&other_key));
+    }
+
+    #[test]
+    fn test_evidence_hash_is_order_independent() {
+        let creator = [5u8; 32];
+        let a = GossipEvent::new(creator, 1, vec![[1u8; 32]], [0u8; 32], None);
+        let b = GossipEvent::new(creator, 1, vec![[2u8; 32]], [0u8; 32], None);
+
+        let ab = EquivocationEvidence::from_events(&a, &b).unwrap();
+        let ba = EquivocationEvidence::from_events(&b, &a).unwrap();
+        assert_eq!(ab.evidence_hash(), ba.evidence_hash());
+    }
+
+    fn signed_testimony(
+        signer: &HybridSigner,
+        bls_key: &BlsSecretKey,
+        validator: CoreNodeId,
+        ticks: u64,
+        round: u64,
+    ) -> Testimony {
+        let mut clock = LamportClock::new(validator);
+        for _ in 0..ticks {
+            clock.increment();
+        }
+        let target = CoreStringId::from_content(b"contested");
+        let mut t = Testimony::new(target, validator, AttestationType::Existence, clock, 1);
+        t.metadata.round = round;
+        let sig = signer.sign(&t.signing_data());
+        t.set_signature(sig.ed25519_sig, sig.dilithium_sig);
+        t.set_bls_signature(
+            bls_key
+                .sign(&t.aggregate_signing_data())
+                .as_bytes()
+                .to_vec(),
+        );
+        t
+    }
+
+    #[test]
+    fn test_detects_conflicting_testimonies() {
+        let (signer, public_key) = HybridSigner::generate_signing_only();
+        let bls_key = BlsSecretKey::from_seed(&[4u8; 32]);
+        let validator = CoreNodeId::new(public_key.node_id());
+        let detector = EquivocationDetector::new();
+
+        let first = signed_testimony(&signer, &bls_key, validator, 1, 4);
+        assert!(detector.observe_testimony(&first).is_none());
+
+        // Same vote with a later clock and extra metadata is a retransmission
+        let mut resent = signed_testimony(&signer, &bls_key, validator, 2, 4);
+        resent.metadata.region = Some("eu-west".to_string());
+        assert!(detector.observe_testimony(&resent).is_none());
+        assert!(EquivocationEvidence::from_testimonies(&first, &resent).is_none());
+
+        // Voting the same string into another round is a conflict
+        let evidence = detector
+            .observe_testimony(&signed_testimony(&signer, &bls_key, validator, 2, 5))
+            .expect("equivocation");
+
+        assert_eq!(
+            evidence.misbehavior_kind(),
+            MisbehaviorKind::EquivocatingTestimonies
+        );
+        assert_eq!(evidence.round, 4);
+        assert!(evidence.verify(&public_key));
+        assert!(evidence.verify_votes(&bls_key.public_key()));
+        assert!(!evidence.verify_votes(&BlsSecretKey::from_seed(&[5u8; 32]).public_key()));
+    }
+
+    #[test]
+    fn test_prune_below() {
+        let detector = EquivocationDetector::new();
+        let creator = [5u8; 32];
+        let a = GossipEvent::new(creator, 1, vec![[1u8; 32]], [0u8; 32], None);
+        let b = GossipEvent::new(creator, 1, vec![[2u8; 32]], [0u8; 32], None);
+
+        detector.observe_event(&a);
+        detector.prune_below(2);
+
+        // Conflicting partner was pruned, so nothing is detected
+        assert!(detector.observe_event(&b).is_none());
+    }
+}
@@ -17,10 +17,17 @@
 //! For 21 validators, this means up to 6 Byzantine nodes can be tolerated.
 //! Finality requires 2f + 1 = 15 testimonies.
 
+pub mod aggregate;
 pub mod ai_testimony;
 pub mod anchor;
+pub mod conflict;
+pub mod equivocation;
 pub mod finality_engine;
+pub mod producer;
+pub mod stake;
+pub mod sync;
 pub mod testimony;
+pub mod view_change;
 pub mod virtual_voting_impl;
 
 // Re-export the full virtual voting implementation
@@ -108,12 +115,29 @@ pub mod virtual_voting {
         }
 
         /// Determine if string has supermajority
+        ///
+        /// Counts every validator equally. Use `has_stake_supermajority`
+        /// when federation stake is available.
         pub fn has_supermajority(&self, string_id: &[u8; 32], total_validators: usize) -> bool {
             let accepts = self.count_accepts(string_id);
             let threshold = (total_validators * 2) / 3 + 1;
             accepts >= threshold
         }
 
+        /// Determine if accepting validators hold a supermajority of stake
+        pub fn has_stake_supermajority(
+            &self,
+            string_id: &[u8; 32],
+            weights: &crate::stake::StakeWeights,
+        ) -> bool {
+            weights.is_supermajority(
+                self.get_votes(string_id)
+                    .iter()
+                    .filter(|v| v.decision == VoteDecision::Accept)
+                    .map(|v| &v.voter_id),
+            )
+        }
+
         /// Add a famous witness
         pub fn add_famous_witness(&mut self, witness_id: [u8; 32]) {
             if !self.famous_witnesses.contains(&witness_id) {
@@ -183,15 +207,25 @@ pub mod finality {
 }
 
 // Re-exports
+pub use aggregate::{AggregateTestimony, ValidatorBitmap};
 pub use anchor::AnchorString;
+pub use conflict::{ConflictResolver, LogicalSlot, SlotConflict};
+pub use equivocation::{EquivocationDetector, EquivocationEvidence, EquivocationKind};
 pub use finality::FinalityStatus;
 pub use finality_engine::{
     AnchorInfo, FinalityConfig, FinalityEngine, FinalityState, FinalityStats, StringFinalityInfo,
 };
+pub use producer::{ProducerClaim, ProducerError, ProducerSchedule};
+pub use stake::StakeWeights;
+pub use sync::{
+    AnchorBundle, CatchUpSync, SnapshotManifest, SyncConfig, SyncError, SyncPhase, SyncRequest,
+    SyncResponse, SyncServer,
+};
 pub use testimony::{
     FinalityProgress, Testimony, TestimonyCollection, TestimonyCollector, TestimonyConfig,
     TestimonyError, TestimonyMetadata, TestimonySignature,
 };
+pub use view_change::{EmptyAnchor, RoundStatus, RoundTimeoutConfig, RoundTimer, ViewChangeVote};
 pub use virtual_voting::{VirtualVote, VirtualVotingState, VoteDecision};
 
 // AI Testimony exports
@@ -200,3 +234,57 @@ pub use ai_testimony::{
     AgentId, ApprovalConditions, AuditScope, ConsensusResult, RiskAssessment, RiskFactor,
     RiskFactorType, RiskLevel, SemanticVerdict, TemporalCondition, ValueLimit,
 };
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn vote(voter: u8, decision: VoteDecision) -> VirtualVote {
+        VirtualVote {
+            round: 0,
+            voter_id: [voter; 32],
+            string_id: [7u8; 32],
+            decision,
+            timestamp: 0,
+        }
+    }
+
+    #[test]
+    fn test_stake_supermajority_differs_from_count() {
+        let stakes: HashMap<[u8; 32], u64> = [
+            ([1u8; 32], 10),
+            ([2u8; 32], 10),
+            ([3u8; 32], 10),
+            ([4u8; 32], 70),
+        ]
+        .into_iter()
+        .collect();
+        let weights = StakeWeights::new(stakes, 0.667);
+
+        let mut state = VirtualVotingState::new();
+        for voter in 1..=3 {
+            state.record_vote(vote(voter, VoteDecision::Accept));
+        }
+        state.record_vote(vote(4, VoteDecision::Reject));
+
+        // 3 of 4 validators by count, but only 30% of stake
+        assert!(state.has_supermajority(&[7u8; 32], 4));
+        assert!(!state.has_stake_supermajority(&[7u8; 32], &weights));
+
+        state.record_vote(vote(4, VoteDecision::Accept));
+        assert!(state.has_stake_supermajority(&[7u8; 32], &weights));
+    }
+
+    #[test]
+    fn test_stake_supermajority_ignores_duplicate_votes() {
+        let stakes: HashMap<[u8; 32], u64> =
+            [([1u8; 32], 40), ([2u8; 32], 60)].into_iter().collect();
+        let weights = StakeWeights::new(stakes, 0.667);
+
+        let mut state = VirtualVotingState::new();
+        state.record_vote(vote(1, VoteDecision::Accept));
+        state.record_vote(vote(1, VoteDecision::Accept));
+        assert!(!state.has_stake_supermajority(&[7u8; 32], &weights));
+    }
+}
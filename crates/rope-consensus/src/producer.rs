@@ -0,0 +1,386 @@
+//! # Anchor Producer Selection
+//!
+//! Picks one anchor producer per round from a VRF randomness beacon instead
+//! of a fixed rotation, so the next producer is unknown until the current
+//! anchor is published and nobody can steer the choice.
+//!
+//! ## Beacon
+//!
+//! ```text
+//! producer(r)  = stake-weighted sample of blake3(seed(r-1) || r)
+//! proof(r)     = VRF_prove(sk_producer, "rope-anchor-producer-v1" || r || seed(r-1))
+//! seed(r)      = blake3(VRF output of proof(r))
+//! ```
+//!
+//! The VRF output is unique per key and input, so the producer cannot grind
+//! for a favourable successor. Withholding the anchor only triggers a view
+//! change: a verified [`EmptyAnchor`] advances the seed by hashing, which
+//! hands the next round to a different sample.
+
+use crate::stake::StakeWeights;
+use crate::view_change::EmptyAnchor;
+use crate::virtual_voting_impl::NodeId;
+use parking_lot::RwLock;
+use rope_crypto::hybrid::HybridPublicKey;
+use rope_crypto::vrf::{VrfOutput, VrfProof, VrfPublicKey, VrfSecretKey};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Domain separator for producer VRF inputs
+const PRODUCER_VRF_DOMAIN: &[u8] = b"rope-anchor-producer-v1";
+
+/// VRF claim attached to an anchor by its producer
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ProducerClaim {
+    pub round: u64,
+    pub producer: NodeId,
+    pub proof: VrfProof,
+}
+
+/// Producer selection errors
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ProducerError {
+    /// Claim is not for the next round
+    WrongRound { expected: u64, got: u64 },
+    /// Claim is from a validator other than the selected producer
+    NotSelected { round: u64, expected: NodeId },
+    /// No VRF key registered for the producer
+    UnknownKey,
+    /// VRF proof failed verification
+    InvalidProof(String),
+    /// No validator holds stake
+    NoValidators,
+    /// Empty anchor lacks a quorum of valid view-change votes
+    InvalidEmptyAnchor { round: u64 },
+}
+
+impl std::fmt::Display for ProducerError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ProducerError::WrongRound { expected, got } => {
+                write!(f, "Claim for round {}, expected {}", got, expected)
+            }
+            ProducerError::NotSelected { round, expected } => {
+                let short: String = expected[..8].iter().map(|b| format!("{:02x}", b)).collect();
+                write!(f, "Round {} belongs to producer {}", round, short)
+            }
+            ProducerError::UnknownKey => write!(f, "No VRF key registered for producer"),
+            ProducerError::InvalidProof(msg) => write!(f, "Invalid VRF proof: {}", msg),
+            ProducerError::NoValidators => write!(f, "No staked validators"),
+            ProducerError::InvalidEmptyAnchor { round } => {
+                write!(
+                    f,
+                    "Empty anchor for round {} lacks a view-change quorum",
+                    round
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for ProducerError {}
+
+#[derive(Clone, Copy)]
+struct Beacon {
+    /// Last closed round
+    round: u64,
+    /// Seed produced by that round
+    seed: [u8; 32],
+}
+
+/// Stake-weighted, VRF-seeded anchor producer schedule
+pub struct ProducerSchedule {
+    weights: RwLock<StakeWeights>,
+    vrf_keys: RwLock<HashMap<NodeId, VrfPublicKey>>,
+    beacon: RwLock<Beacon>,
+}
+
+impl ProducerSchedule {
+    /// Start after `round` with a seed every validator agrees on (e.g. the
+    /// genesis hash)
+    pub fn new(weights: StakeWeights, round: u64, genesis_seed: [u8; 32]) -> Self {
+        Self {
+            weights: RwLock::new(weights),
+            vrf_keys: RwLock::new(HashMap::new()),
+            beacon: RwLock::new(Beacon {
+                round,
+                seed: genesis_seed,
+            }),
+        }
+    }
+
+    /// Register a validator's VRF key
+    pub fn register_key(&self, validator: NodeId, key: VrfPublicKey) {
+        self.vrf_keys.write().insert(validator, key);
+    }
+
+    /// Replace the stake weights (at epoch boundaries)
+    pub fn update_weights(&self, weights: StakeWeights) {
+        *self.weights.write() = weights;
+    }
+
+    /// Round the next anchor belongs to
+    pub fn next_round(&self) -> u64 {
+        self.beacon.read().round + 1
+    }
+
+    /// Current beacon seed
+    pub fn seed(&self) -> [u8; 32] {
+        self.beacon.read().seed
+    }
+
+    /// Selected producer for the next round
+    pub fn next_producer(&self) -> Result<NodeId, ProducerError> {
+        let beacon = *self.beacon.read();
+        self.select(beacon.round + 1, &beacon.seed)
+    }
+
+    /// Whether `validator` should produce the next anchor
+    pub fn is_our_turn(&self, validator: &NodeId) -> bool {
+        self.next_producer()
+            .map(|p| &p == validator)
+            .unwrap_or(false)
+    }
+
+    /// Build our claim for the next round
+    pub fn claim(&self, producer: NodeId, secret: &VrfSecretKey) -> ProducerClaim {
+        let beacon = *self.beacon.read();
+        let round = beacon.round + 1;
+        ProducerClaim {
+            round,
+            producer,
+            proof: secret.prove(&vrf_input(round, &beacon.seed)),
+        }
+    }
+
+    /// Verify a claim without advancing the beacon
+    pub fn verify_claim(&self, claim: &ProducerClaim) -> Result<VrfOutput, ProducerError> {
+        let beacon = *self.beacon.read();
+        let round = beacon.round + 1;
+        if claim.round != round {
+            return Err(ProducerError::WrongRound {
+                expected: round,
+                got: claim.round,
+            });
+        }
+
+        let expected = self.select(round, &beacon.seed)?;
+        if claim.producer != expected {
+            return Err(ProducerError::NotSelected { round, expected });
+        }
+
+        let key = self
+            .vrf_keys
+            .read()
+            .get(&claim.producer)
+            .copied()
+            .ok_or(ProducerError::UnknownKey)?;
+        key.verify(&vrf_input(round, &beacon.seed), &claim.proof)
+            .map_err(|e| ProducerError::InvalidProof(e.to_string()))
+    }
+
+    /// Accept the claim of a published anchor and advance the beacon
+    pub fn on_anchor(&self, claim: &ProducerClaim) -> Result<(), ProducerError> {
+        let output = self.verify_claim(claim)?;
+        let mut beacon = self.beacon.write();
+        // A concurrent advance means the claim is stale
+        if beacon.round + 1 != claim.round {
+            return Err(ProducerError::WrongRound {
+                expected: beacon.round + 1,
+                got: claim.round,
+            });
+        }
+        beacon.seed = *blake3::hash(output.as_bytes()).as_bytes();
+        beacon.round = claim.round;
+        Ok(())
+    }
+
+    /// Advance past a round closed by view change
+    ///
+    /// The anchor's view-change votes are checked against `validators`
+    /// before the seed moves.
+    pub fn on_empty_anchor(
+        &self,
+        anchor: &EmptyAnchor,
+        validators: &HashMap<NodeId, HybridPublicKey>,
+    ) -> Result<(), ProducerError> {
+        if !anchor.verify(validators) {
+            return Err(ProducerError::InvalidEmptyAnchor {
+                round: anchor.round,
+            });
+        }
+
+        let mut beacon = self.beacon.write();
+        if anchor.round != beacon.round + 1 {
+            return Err(ProducerError::WrongRound {
+                expected: beacon.round + 1,
+                got: anchor.round,
+            });
+        }
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(b"rope-empty-round");
+        hasher.update(&beacon.seed);
+        hasher.update(&anchor.round.to_le_bytes());
+        beacon.seed = *hasher.finalize().as_bytes();
+        beacon.round = anchor.round;
+        Ok(())
+    }
+
+    fn select(&self, round: u64, seed: &[u8; 32]) -> Result<NodeId, ProducerError> {
+        let stakes = self.weights.read().sorted_stakes();
+        let total: u128 = stakes.iter().map(|(_, s)| *s as u128).sum();
+        if total == 0 {
+            return Err(ProducerError::NoValidators);
+        }
+
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(seed);
+        hasher.update(&round.to_le_bytes());
+        let mut sample = [0u8; 16];
+        sample.copy_from_slice(&hasher.finalize().as_bytes()[..16]);
+        let mut target = u128::from_le_bytes(sample) % total;
+
+        for (validator, stake) in &stakes {
+            let stake = *stake as u128;
+            if target < stake {
+                return Ok(*validator);
+            }
+            target -= stake;
+        }
+        unreachable!("target is below total stake")
+    }
+}
+
+fn vrf_input(round: u64, seed: &[u8; 32]) -> Vec<u8> {
+    let mut input = Vec::with_capacity(PRODUCER_VRF_DOMAIN.len() + 40);
+    input.extend_from_slice(PRODUCER_VRF_DOMAIN);
+    input.extend_from_slice(&round.to_le_bytes());
+    input.extend_from_slice(seed);
+    input
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::view_change::ViewChangeVote;
+    use rope_crypto::hybrid::HybridSigner;
+
+    fn setup(stakes: &[u64]) -> (ProducerSchedule, Vec<(NodeId, VrfSecretKey)>) {
+        let validators: Vec<(NodeId, VrfSecretKey)> = (0..stakes.len())
+            .map(|i| ([i as u8; 32], VrfSecretKey::from_seed(&[i as u8 + 1; 32])))
+            .collect();
+        let weights = StakeWeights::new(
+            validators
+                .iter()
+                .zip(stakes)
+                .map(|((id, _), s)| (*id, *s))
+                .collect(),
+            0.667,
+        );
+        let schedule = ProducerSchedule::new(weights, 0, [42u8; 32]);
+        for (id, sk) in &validators {
+            schedule.register_key(*id, sk.public_key());
+        }
+        (schedule, validators)
+    }
+
+    fn produce(schedule: &ProducerSchedule, validators: &[(NodeId, VrfSecretKey)]) -> NodeId {
+        let producer = schedule.next_producer().unwrap();
+        let (_, sk) = validators.iter().find(|(id, _)| *id == producer).unwrap();
+        schedule.on_anchor(&schedule.claim(producer, sk)).unwrap();
+        producer
+    }
+
+    #[test]
+    fn test_selected_producer_advances_beacon() {
+        let (schedule, validators) = setup(&[10, 10, 10, 10]);
+        let seed = schedule.seed();
+        produce(&schedule, &validators);
+        assert_eq!(schedule.next_round(), 2);
+        assert_ne!(schedule.seed(), seed);
+    }
+
+    #[test]
+    fn test_schedule_is_deterministic() {
+        let (a, validators) = setup(&[10, 20, 30]);
+        let (b, _) = setup(&[10, 20, 30]);
+        for _ in 0..10 {
+            let producer = produce(&a, &validators);
+            assert!(b.is_our_turn(&producer));
+            let (_, sk) = validators.iter().find(|(id, _)| *id == producer).unwrap();
+            b.on_anchor(&b.claim(producer, sk)).unwrap();
+        }
+        assert_eq!(a.seed(), b.seed());
+    }
+
+    #[test]
+    fn test_wrong_producer_rejected() {
+        let (schedule, validators) = setup(&[10, 10, 10]);
+        let producer = schedule.next_producer().unwrap();
+        let (impostor, sk) = validators.iter().find(|(id, _)| *id != producer).unwrap();
+        let claim = schedule.claim(*impostor, sk);
+        assert!(matches!(
+            schedule.on_anchor(&claim),
+            Err(ProducerError::NotSelected { .. })
+        ));
+    }
+
+    #[test]
+    fn test_forged_proof_rejected() {
+        let (schedule, _) = setup(&[10, 10, 10]);
+        let producer = schedule.next_producer().unwrap();
+        let claim = schedule.claim(producer, &VrfSecretKey::generate());
+        assert!(matches!(
+            schedule.on_anchor(&claim),
+            Err(ProducerError::InvalidProof(_))
+        ));
+        assert_eq!(schedule.next_round(), 1);
+    }
+
+    #[test]
+    fn test_empty_anchor_reshuffles() {
+        let (schedule, _) = setup(&[10, 10, 10]);
+        let seed = schedule.seed();
+
+        let mut keys = HashMap::new();
+        let mut votes = Vec::new();
+        for i in 0..3u8 {
+            let (signer, public_key) = HybridSigner::generate_signing_only();
+            keys.insert([i; 32], public_key);
+            votes.push(ViewChangeVote::signed(1, 1, [i; 32], 0, &signer));
+        }
+
+        // Without signed votes the beacon does not move
+        let unsigned = EmptyAnchor {
+            round: 1,
+            view: 1,
+            votes: Vec::new(),
+        };
+        assert_eq!(
+            schedule.on_empty_anchor(&unsigned, &keys),
+            Err(ProducerError::InvalidEmptyAnchor { round: 1 })
+        );
+        assert_eq!(schedule.seed(), seed);
+
+        let anchor = EmptyAnchor {
+            round: 1,
+            view: 1,
+            votes,
+        };
+        schedule.on_empty_anchor(&anchor, &keys).unwrap();
+        assert_eq!(schedule.next_round(), 2);
+        assert_ne!(schedule.seed(), seed);
+    }
+
+    #[test]
+    fn test_selection_follows_stake() {
+        let (schedule, validators) = setup(&[90, 5, 5]);
+        let mut counts = HashMap::new();
+        for _ in 0..200 {
+            *counts
+                .entry(produce(&schedule, &validators))
+                .or_insert(0u32) += 1;
+        }
+        assert!(counts[&[0u8; 32]] > 150);
+    }
+}
@@ -0,0 +1,149 @@
+//! # Stake Weights
+//!
+//! Validator voting power sourced from the federation.
+//!
+//! The supermajority threshold is computed over stake using the
+//! federation's `testimony_threshold` parameter. To avoid float rounding
+//! drift between nodes, the threshold is converted once to parts per
+//! million and the required stake is `ceil(total_stake * ppm / 1_000_000)`.
+
+use rope_federation::evolution::FederationState;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+/// Parts-per-million denominator for the threshold
+const PPM: u128 = 1_000_000;
+
+/// Stake held by each validator plus the quorum fraction
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct StakeWeights {
+    stakes: HashMap<[u8; 32], u64>,
+    total_stake: u64,
+    threshold_ppm: u64,
+}
+
+impl StakeWeights {
+    /// Build weights from explicit stakes
+    pub fn new(stakes: HashMap<[u8; 32], u64>, threshold: f64) -> Self {
+        let total_stake = stakes.values().fold(0u64, |acc, s| acc.saturating_add(*s));
+        let threshold_ppm = (threshold.clamp(0.0, 1.0) * PPM as f64).round() as u64;
+        Self {
+            stakes,
+            total_stake,
+            threshold_ppm,
+        }
+    }
+
+    /// Build weights from the current federation state
+    pub fn from_federation(state: &FederationState) -> Self {
+        let stakes = state
+            .validators
+            .iter()
+            .map(|v| (v.node_id, v.stake))
+            .collect();
+        Self::new(stakes, state.params.testimony_threshold)
+    }
+
+    /// Stake of a validator (0 if unknown)
+    pub fn stake_of(&self, validator: &[u8; 32]) -> u64 {
+        self.stakes.get(validator).copied().unwrap_or(0)
+    }
+
+    /// Validators with non-zero stake, sorted by ID
+    pub fn sorted_stakes(&self) -> Vec<([u8; 32], u64)> {
+        let mut stakes: Vec<_> = self
+            .stakes
+            .iter()
+            .filter(|(_, stake)| **stake > 0)
+            .map(|(id, stake)| (*id, *stake))
+            .collect();
+        stakes.sort_unstable_by_key(|(id, _)| *id);
+        stakes
+    }
+
+    /// Total stake across all validators
+    pub fn total_stake(&self) -> u64 {
+        self.total_stake
+    }
+
+    /// Minimum stake that constitutes a supermajority
+    pub fn required_stake(&self) -> u64 {
+        if self.total_stake == 0 {
+            return 0;
+        }
+        let scaled = self.total_stake as u128 * self.threshold_ppm as u128;
+        let required = scaled.div_ceil(PPM) as u64;
+        required.max(1)
+    }
+
+    /// Sum of stake for a set of voters, counting each validator once
+    pub fn stake_for<'a>(&self, voters: impl IntoIterator<Item = &'a [u8; 32]>) -> u64 {
+        let unique: HashSet<&[u8; 32]> = voters.into_iter().collect();
+        unique
+            .into_iter()
+            .fold(0u64, |acc, v| acc.saturating_add(self.stake_of(v)))
+    }
+
+    /// Whether the voters together hold a supermajority of stake
+    pub fn is_supermajority<'a>(&self, voters: impl IntoIterator<Item = &'a [u8; 32]>) -> bool {
+        self.total_stake > 0 && self.stake_for(voters) >= self.required_stake()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn weights(stakes: &[u64], threshold: f64) -> StakeWeights {
+        let map = stakes
+            .iter()
+            .enumerate()
+            .map(|(i, s)| ([i as u8; 32], *s))
+            .collect();
+        StakeWeights::new(map, threshold)
+    }
+
+    #[test]
+    fn test_required_stake_rounds_up() {
+        // 21 equal validators: 21 * 0.667 = 14.007 -> 15, same as 2f+1
+        assert_eq!(weights(&[1; 21], 0.667).required_stake(), 15);
+
+        // 3 equal validators: 3 * 0.667 = 2.001 -> 3
+        assert_eq!(weights(&[1; 3], 0.667).required_stake(), 3);
+
+        // Exact fractions do not round up: half of 300 is 150
+        assert_eq!(weights(&[100; 3], 0.5).required_stake(), 150);
+
+        // 100 * 0.667 = 66.7 -> 67
+        assert_eq!(weights(&[25; 4], 0.667).required_stake(), 67);
+    }
+
+    #[test]
+    fn test_zero_stake() {
+        let w = weights(&[], 0.667);
+        assert_eq!(w.required_stake(), 0);
+        assert!(!w.is_supermajority([[0u8; 32]].iter()));
+    }
+
+    #[test]
+    fn test_whale_dominates() {
+        let w = weights(&[70, 10, 10, 10], 0.667);
+        assert!(w.is_supermajority([[0u8; 32]].iter()));
+        assert!(!w.is_supermajority([[1u8; 32], [2u8; 32], [3u8; 32]].iter()));
+    }
+
+    #[test]
+    fn test_duplicates_and_unknown_voters() {
+        let w = weights(&[10, 10, 10], 0.667);
+        let voter = [0u8; 32];
+        assert_eq!(w.stake_for([voter, voter, voter].iter()), 10);
+        assert_eq!(w.stake_for([[9u8; 32]].iter()), 0);
+    }
+
+    #[test]
+    fn test_large_stakes_do_not_overflow() {
+        let w = weights(&[u64::MAX / 2, u64::MAX / 2], 0.667);
+        assert!(w.required_stake() > 0);
+        assert!(w.is_supermajority([[0u8; 32], [1u8; 32]].iter()));
+    }
+}
@@ -0,0 +1,938 @@
+//! # Catch-up Sync Protocol
+//!
+//! Lets a node that fell behind recover consensus ordering from peers.
+//!
+//! ## Flow
+//!
+//! ```text
+//! lagging node                         peer
+//!     | ---- SyncRequest::Status ------> |
+//!     | <--- SyncResponse::Status ------ |
+//!     | ---- SyncRequest::Anchors -----> |   (from_round, max)
+//!     | <--- SyncResponse::Anchors ----- |   anchor bundles
+//!     |  verify aggregate testimonies    |
+//!     |  replay gossip events in order   |
+//!     |  run virtual voting              |
+//!     |  ... repeat until caught up ...  |
+//!     |  rejoin live consensus           |
+//! ```
+//!
+//! Each [`AnchorBundle`] carries an anchor, its BLS aggregate testimony
+//! and the gossip events of the rounds it closes. Validators testify to
+//! [`AnchorBundle::commitment`], which binds the anchor, its round, the
+//! referenced strings and the event IDs, so a peer cannot swap any of them
+//! under a valid aggregate. Bundles are only applied once the aggregate
+//! reaches the testimony threshold and every event carries its creator's
+//! signature, and events are replayed in a canonical order so every
+//! syncing node derives the same virtual votes.
+
+use crate::aggregate::AggregateTestimony;
+use crate::equivocation::verify_event_signature;
+use crate::finality_engine::FinalityEngine;
+use crate::virtual_voting_impl::{GossipEvent, NodeId, VirtualVotingEngine};
+use rope_core::types::{AttestationType, StringId};
+use rope_crypto::bls::BlsPublicKey;
+use rope_crypto::hash::merkle;
+use rope_crypto::hybrid::HybridPublicKey;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Anchor plus the gossip events of the rounds it closes
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AnchorBundle {
+    /// Anchor string ID
+    pub anchor_id: [u8; 32],
+
+    /// Consensus round closed by this anchor
+    pub round: u64,
+
+    /// Strings the anchor references
+    pub referenced_strings: Vec<StringId>,
+
+    /// Finality aggregate over [`AnchorBundle::commitment`]
+    pub aggregate: AggregateTestimony,
+
+    /// Gossip events with `prev_round < event.round <= round`
+    pub events: Vec<GossipEvent>,
+}
+
+impl AnchorBundle {
+    /// Anchor ID validators testify to for a bundle
+    ///
+    /// Commits to the Merkle root of the referenced strings, in order, and
+    /// of the event IDs, sorted, so replay order does not change it.
+    pub fn commitment(
+        anchor_id: &[u8; 32],
+        round: u64,
+        referenced_strings: &[StringId],
+        events: &[GossipEvent],
+    ) -> StringId {
+        let strings: Vec<[u8; 32]> = referenced_strings.iter().map(|s| *s.as_bytes()).collect();
+        let mut event_ids: Vec<[u8; 32]> = events.iter().map(|e| e.id).collect();
+        event_ids.sort_unstable();
+
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(b"rope-anchor-bundle-v1");
+        hasher.update(anchor_id);
+        hasher.update(&round.to_le_bytes());
+        hasher.update(&merkle::compute_root(&strings));
+        hasher.update(&merkle::compute_root(&event_ids));
+        StringId::new(*hasher.finalize().as_bytes())
+    }
+
+    /// Commitment over this bundle's contents
+    pub fn attested_id(&self) -> StringId {
+        Self::commitment(
+            &self.anchor_id,
+            self.round,
+            &self.referenced_strings,
+            &self.events,
+        )
+    }
+}
+
+/// Storage snapshot and gossip checkpoint attested by validators
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SnapshotManifest {
+    /// Consensus round the snapshot was taken at
+    pub round: u64,
+
+    /// BLAKE3 digest of the snapshot file
+    pub snapshot_digest: [u8; 32],
+
+    /// Snapshot size in bytes
+    pub snapshot_size: u64,
+
+    /// Hash of the gossip checkpoint for `round`
+    pub checkpoint_hash: [u8; 32],
+
+    /// Finality aggregate over [`SnapshotManifest::commitment`]
+    pub aggregate: AggregateTestimony,
+}
+
+impl SnapshotManifest {
+    /// Anchor ID validators testify to for a snapshot
+    pub fn commitment(
+        round: u64,
+        snapshot_digest: &[u8; 32],
+        snapshot_size: u64,
+        checkpoint_hash: &[u8; 32],
+    ) -> StringId {
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(b"rope-state-snapshot-v1");
+        hasher.update(&round.to_le_bytes());
+        hasher.update(snapshot_digest);
+        hasher.update(&snapshot_size.to_le_bytes());
+        hasher.update(checkpoint_hash);
+        StringId::new(*hasher.finalize().as_bytes())
+    }
+
+    /// Anchor ID of this manifest
+    pub fn anchor_id(&self) -> StringId {
+        Self::commitment(
+            self.round,
+            &self.snapshot_digest,
+            self.snapshot_size,
+            &self.checkpoint_hash,
+        )
+    }
+}
+
+/// Sync request sent to a peer
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SyncRequest {
+    /// Ask for the peer's latest anchored round
+    Status,
+    /// Ask for the peer's latest snapshot manifest
+    Snapshot,
+    /// Ask for anchor bundles with round > `from_round`
+    Anchors { from_round: u64, max: u32 },
+}
+
+/// Sync response from a peer
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum SyncResponse {
+    /// Peer's latest anchored round
+    Status { latest_round: u64 },
+    /// Consecutive anchor bundles
+    Anchors(Vec<AnchorBundle>),
+    /// Latest snapshot manifest, if the peer serves snapshots
+    Snapshot(Option<SnapshotManifest>),
+}
+
+/// Sync configuration
+#[derive(Clone, Debug)]
+pub struct SyncConfig {
+    /// Maximum bundles requested per round-trip
+    pub batch_size: u32,
+
+    /// Testimonies required on each anchor (2f+1)
+    pub testimony_threshold: usize,
+
+    /// Canonical validator ordering used by aggregate bitmaps
+    pub validator_set: Vec<NodeId>,
+
+    /// BLS keys in the same order as `validator_set`
+    pub validator_keys: Vec<BlsPublicKey>,
+
+    /// Hybrid signing keys in the same order as `validator_set`
+    pub validator_signing_keys: Vec<HybridPublicKey>,
+}
+
+impl Default for SyncConfig {
+    fn default() -> Self {
+        Self {
+            batch_size: 32,
+            testimony_threshold: 15,
+            validator_set: Vec::new(),
+            validator_keys: Vec::new(),
+            validator_signing_keys: Vec::new(),
+        }
+    }
+}
+
+/// Sync state machine phase
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SyncPhase {
+    /// Not syncing, not yet known to be caught up
+    Idle,
+    /// Waiting for bundles after `from_round`
+    Fetching { from_round: u64, target_round: u64 },
+    /// Caught up and participating in live consensus
+    Live,
+}
+
+/// Sync errors
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SyncError {
+    /// Response does not match the outstanding request
+    UnexpectedResponse,
+    /// Peer returned no bundles before the target round was reached
+    Stalled { local_round: u64 },
+    /// Bundle rounds are not strictly increasing from the local round
+    NonContiguous { expected_after: u64, got: u64 },
+    /// Aggregate does not cover the anchor it is attached to
+    AggregateMismatch { round: u64 },
+    /// Aggregate failed verification
+    InvalidTestimony { round: u64, reason: String },
+    /// Event outside the bundle's round range
+    EventOutOfRange { round: u64 },
+    /// Event ID, creator or signature does not check out
+    InvalidEvent { round: u64 },
+    /// Snapshot is not newer than the local state
+    StaleSnapshot { local_round: u64, got: u64 },
+}
+
+impl std::fmt::Display for SyncError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SyncError::UnexpectedResponse => write!(f, "Unexpected sync response"),
+            SyncError::Stalled { local_round } => {
+                write!(f, "Peer stopped serving bundles at round {}", local_round)
+            }
+            SyncError::NonContiguous {
+                expected_after,
+                got,
+            } => write!(
+                f,
+                "Non-contiguous bundle: expected round > {}, got {}",
+                expected_after, got
+            ),
+            SyncError::AggregateMismatch { round } => {
+                write!(f, "Aggregate does not match anchor in round {}", round)
+            }
+            SyncError::InvalidTestimony { round, reason } => {
+                write!(f, "Invalid testimony in round {}: {}", round, reason)
+            }
+            SyncError::EventOutOfRange { round } => {
+                write!(f, "Gossip event outside bundle range in round {}", round)
+            }
+            SyncError::InvalidEvent { round } => {
+                write!(f, "Unsigned or forged gossip event in round {}", round)
+            }
+            SyncError::StaleSnapshot { local_round, got } => write!(
+                f,
+                "Snapshot at round {} is not ahead of local round {}",
+                got, local_round
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SyncError {}
+
+/// Client side of the catch-up protocol
+pub struct CatchUpSync {
+    config: SyncConfig,
+    phase: SyncPhase,
+    local_round: u64,
+    bundles_applied: u64,
+}
+
+impl CatchUpSync {
+    /// Create new sync driver starting at the last locally anchored round
+    pub fn new(config: SyncConfig, local_round: u64) -> Self {
+        Self {
+            config,
+            phase: SyncPhase::Idle,
+            local_round,
+            bundles_applied: 0,
+        }
+    }
+
+    /// Current phase
+    pub fn phase(&self) -> &SyncPhase {
+        &self.phase
+    }
+
+    /// Last anchored round applied locally
+    pub fn local_round(&self) -> u64 {
+        self.local_round
+    }
+
+    /// Number of bundles applied so far
+    pub fn bundles_applied(&self) -> u64 {
+        self.bundles_applied
+    }
+
+    /// Whether the node can participate in live consensus
+    pub fn is_live(&self) -> bool {
+        self.phase == SyncPhase::Live
+    }
+
+    /// Initial request to send to a peer
+    pub fn status_request(&self) -> SyncRequest {
+        SyncRequest::Status
+    }
+
+    /// Handle a peer's status; returns the next request if we are behind
+    pub fn on_status(&mut self, peer_round: u64) -> Option<SyncRequest> {
+        if peer_round <= self.local_round {
+            self.phase = SyncPhase::Live;
+            return None;
+        }
+
+        self.phase = SyncPhase::Fetching {
+            from_round: self.local_round,
+            target_round: peer_round,
+        };
+        Some(self.anchors_request())
+    }
+
+    fn anchors_request(&self) -> SyncRequest {
+        SyncRequest::Anchors {
+            from_round: self.local_round,
+            max: self.config.batch_size,
+        }
+    }
+
+    /// Verify and replay a batch of bundles
+    ///
+    /// Returns the next request, or `None` once the target round is reached
+    /// and the node has rejoined live consensus. On error nothing from the
+    /// failing bundle onward is applied; the caller should retry with a
+    /// different peer.
+    pub fn on_bundles(
+        &mut self,
+        bundles: Vec<AnchorBundle>,
+        voting: &VirtualVotingEngine,
+        finality: &FinalityEngine,
+    ) -> Result<Option<SyncRequest>, SyncError> {
+        let target_round = match self.phase {
+            SyncPhase::Fetching { target_round, .. } => target_round,
+            _ => return Err(SyncError::UnexpectedResponse),
+        };
+
+        if bundles.is_empty() {
+            return Err(SyncError::Stalled {
+                local_round: self.local_round,
+            });
+        }
+
+        for bundle in bundles {
+            self.verify_bundle(&bundle)?;
+            self.apply_bundle(bundle, voting, finality);
+        }
+
+        if self.local_round >= target_round {
+            self.phase = SyncPhase::Live;
+            tracing::info!("Catch-up sync complete at round {}", self.local_round);
+            return Ok(None);
+        }
+
+        self.phase = SyncPhase::Fetching {
+            from_round: self.local_round,
+            target_round,
+        };
+        Ok(Some(self.anchors_request()))
+    }
+
+    /// Check a bundle without applying it
+    pub fn verify_bundle(&self, bundle: &AnchorBundle) -> Result<(), SyncError> {
+        if bundle.round <= self.local_round {
+            return Err(SyncError::NonContiguous {
+                expected_after: self.local_round,
+                got: bundle.round,
+            });
+        }
+
+        if bundle.aggregate.string_id != bundle.attested_id()
+            || bundle.aggregate.round != bundle.round
+            || bundle.aggregate.attestation_type != AttestationType::Finality
+        {
+            return Err(SyncError::AggregateMismatch {
+                round: bundle.round,
+            });
+        }
+
+        bundle
+            .aggregate
+            .verify(&self.config.validator_keys, self.config.testimony_threshold)
+            .map_err(|e| SyncError::InvalidTestimony {
+                round: bundle.round,
+                reason: e.to_string(),
+            })?;
+
+        if bundle
+            .events
+            .iter()
+            .any(|e| e.round <= self.local_round || e.round > bundle.round)
+        {
+            return Err(SyncError::EventOutOfRange {
+                round: bundle.round,
+            });
+        }
+
+        for event in &bundle.events {
+            if !self.event_is_authentic(event) {
+                return Err(SyncError::InvalidEvent {
+                    round: bundle.round,
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Event hashes to its ID and is signed by a validator in the set
+    fn event_is_authentic(&self, event: &GossipEvent) -> bool {
+        let Some(index) = self
+            .config
+            .validator_set
+            .iter()
+            .position(|v| v == &event.creator)
+        else {
+            return false;
+        };
+
+        match self.config.validator_signing_keys.get(index) {
+            Some(public_key) => event.has_valid_id() && verify_event_signature(public_key, event),
+            None => false,
+        }
+    }
+
+    /// Check a snapshot manifest against its anchor testimonies
+    ///
+    /// This only covers the manifest. The caller must still check the
+    /// downloaded snapshot against `snapshot_digest` and the checkpoint
+    /// against `checkpoint_hash` before calling `apply_snapshot`.
+    pub fn verify_snapshot(&self, manifest: &SnapshotManifest) -> Result<(), SyncError> {
+        if manifest.round <= self.local_round {
+            return Err(SyncError::StaleSnapshot {
+                local_round: self.local_round,
+                got: manifest.round,
+            });
+        }
+
+        if manifest.aggregate.string_id != manifest.anchor_id()
+            || manifest.aggregate.round != manifest.round
+            || manifest.aggregate.attestation_type != AttestationType::Finality
+        {
+            return Err(SyncError::AggregateMismatch {
+                round: manifest.round,
+            });
+        }
+
+        manifest
+            .aggregate
+            .verify(&self.config.validator_keys, self.config.testimony_threshold)
+            .map_err(|e| SyncError::InvalidTestimony {
+                round: manifest.round,
+                reason: e.to_string(),
+            })
+    }
+
+    /// Adopt a verified snapshot as the local state
+    ///
+    /// Records the snapshot anchor and moves the local round to the
+    /// snapshot round; send `status_request` next to catch up the rest.
+    pub fn apply_snapshot(
+        &mut self,
+        manifest: &SnapshotManifest,
+        finality: &FinalityEngine,
+    ) -> Result<(), SyncError> {
+        self.verify_snapshot(manifest)?;
+        finality.record_anchor(*manifest.anchor_id().as_bytes(), manifest.round, Vec::new());
+        self.local_round = manifest.round;
+        self.phase = SyncPhase::Idle;
+        tracing::info!("Adopted state snapshot at round {}", manifest.round);
+        Ok(())
+    }
+
+    fn apply_bundle(
+        &mut self,
+        bundle: AnchorBundle,
+        voting: &VirtualVotingEngine,
+        finality: &FinalityEngine,
+    ) {
+        for event in canonical_order(bundle.events) {
+            voting.update_node_history(event.creator, event.clone());
+            voting.add_local_event(event);
+        }
+        voting.clear_cache();
+        voting.run_voting_round();
+
+        for string_id in &bundle.referenced_strings {
+            if finality.get_state(string_id).is_none() {
+                finality.register_string(*string_id, Vec::new());
+            }
+            finality.update_testimony_count(string_id, bundle.aggregate.signer_count());
+        }
+        finality.record_anchor(bundle.anchor_id, bundle.round, bundle.referenced_strings);
+
+        self.local_round = bundle.round;
+        self.bundles_applied += 1;
+    }
+}
+
+/// Deterministic replay order: round, then timestamp, then event ID
+fn canonical_order(mut events: Vec<GossipEvent>) -> Vec<GossipEvent> {
+    events.sort_by(|a, b| {
+        a.round
+            .cmp(&b.round)
+            .then(a.timestamp.cmp(&b.timestamp))
+            .then(a.id.cmp(&b.id))
+    });
+    events
+}
+
+/// Server side: serves anchor bundles to lagging peers
+#[derive(Default)]
+pub struct SyncServer {
+    bundles: BTreeMap<u64, AnchorBundle>,
+    snapshot: Option<SnapshotManifest>,
+}
+
+impl SyncServer {
+    /// Create empty server
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Store a finalized anchor bundle
+    pub fn record_bundle(&mut self, bundle: AnchorBundle) {
+        self.bundles.insert(bundle.round, bundle);
+    }
+
+    /// Offer a snapshot manifest, replacing any older one
+    pub fn record_snapshot(&mut self, manifest: SnapshotManifest) {
+        let newer = match &self.snapshot {
+            Some(current) => manifest.round > current.round,
+            None => true,
+        };
+        if newer {
+            self.snapshot = Some(manifest);
+        }
+    }
+
+    /// Latest anchored round available
+    pub fn latest_round(&self) -> u64 {
+        self.bundles.keys().next_back().copied().unwrap_or(0)
+    }
+
+    /// Drop bundles up to and including `round`
+    pub fn prune_through(&mut self, round: u64) {
+        self.bundles = self.bundles.split_off(&round.saturating_add(1));
+    }
+
+    /// Answer a sync request
+    pub fn handle(&self, request: &SyncRequest) -> SyncResponse {
+        match request {
+            SyncRequest::Status => SyncResponse::Status {
+                latest_round: self.latest_round(),
+            },
+            SyncRequest::Anchors { from_round, max } => SyncResponse::Anchors(
+                self.bundles
+                    .range(from_round + 1..)
+                    .take(*max as usize)
+                    .map(|(_, b)| b.clone())
+                    .collect(),
+            ),
+            SyncRequest::Snapshot => SyncResponse::Snapshot(self.snapshot.clone()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::finality_engine::FinalityConfig;
+    use crate::testimony::{Testimony, TestimonyCollection};
+    use rope_core::clock::LamportClock;
+    use rope_core::types::{AttestationType, NodeId as CoreNodeId};
+    use rope_crypto::bls::BlsSecretKey;
+    use rope_crypto::hybrid::HybridSigner;
+
+    struct Cluster {
+        ids: Vec<NodeId>,
+        keys: Vec<BlsSecretKey>,
+        signers: Vec<(HybridSigner, HybridPublicKey)>,
+    }
+
+    impl Cluster {
+        fn new(n: usize) -> Self {
+            Self {
+                ids: (0..n).map(|i| [i as u8 + 1; 32]).collect(),
+                keys: (0..n)
+                    .map(|i| BlsSecretKey::from_seed(&[i as u8 + 100; 32]))
+                    .collect(),
+                signers: (0..n)
+                    .map(|_| HybridSigner::generate_signing_only())
+                    .collect(),
+            }
+        }
+
+        fn config(&self, threshold: usize) -> SyncConfig {
+            SyncConfig {
+                batch_size: 2,
+                testimony_threshold: threshold,
+                validator_set: self.ids.clone(),
+                validator_keys: self.keys.iter().map(|k| k.public_key()).collect(),
+                validator_signing_keys: self.signers.iter().map(|(_, pk)| pk.clone()).collect(),
+            }
+        }
+
+        fn aggregate(&self, anchor: StringId, round: u64, signers: usize) -> AggregateTestimony {
+            self.aggregate_as(AttestationType::Existence, anchor, round, signers)
+        }
+
+        fn aggregate_as(
+            &self,
+            attestation_type: AttestationType,
+            anchor: StringId,
+            round: u64,
+            signers: usize,
+        ) -> AggregateTestimony {
+            let core_ids: Vec<CoreNodeId> = self.ids.iter().map(|i| CoreNodeId::new(*i)).collect();
+
+            let mut collection = TestimonyCollection::new(anchor);
+            for (id, key) in core_ids.iter().zip(&self.keys).take(signers) {
+                let mut t =
+                    Testimony::new(anchor, *id, attestation_type, LamportClock::new(*id), 1);
+                t.metadata.round = round;
+                t.set_bls_signature(key.sign(&t.aggregate_signing_data()).as_bytes().to_vec());
+                collection.add(t);
+            }
+            AggregateTestimony::from_collection(&collection, attestation_type, round, &core_ids)
+                .unwrap()
+        }
+
+        fn bundle(&self, round: u64, signers: usize, string: StringId) -> AnchorBundle {
+            let anchor_id = *blake3::hash(&round.to_le_bytes()).as_bytes();
+
+            let events: Vec<GossipEvent> = self
+                .ids
+                .iter()
+                .zip(&self.signers)
+                .map(|(id, (signer, _))| {
+                    let mut event =
+                        GossipEvent::new(*id, round, vec![*string.as_bytes()], [0u8; 32], None);
+                    event.signature = bincode::serialize(&signer.sign(&event.id)).unwrap();
+                    event
+                })
+                .collect();
+
+            let referenced_strings = vec![string];
+            let commitment =
+                AnchorBundle::commitment(&anchor_id, round, &referenced_strings, &events);
+            let aggregate =
+                self.aggregate_as(AttestationType::Finality, commitment, round, signers);
+
+            AnchorBundle {
+                anchor_id,
+                round,
+                referenced_strings,
+                aggregate,
+                events,
+            }
+        }
+    }
+
+    fn finality_engine() -> FinalityEngine {
+        FinalityEngine::new(FinalityConfig {
+            min_anchor_confirmations: 1,
+            min_testimonies: 3,
+            finality_timeout_secs: 300,
+            require_parent_finality: false,
+        })
+    }
+
+    #[test]
+    fn test_already_caught_up() {
+        let cluster = Cluster::new(4);
+        let mut sync = CatchUpSync::new(cluster.config(3), 10);
+        assert!(sync.on_status(10).is_none());
+        assert!(sync.is_live());
+    }
+
+    #[test]
+    fn test_full_catch_up() {
+        let cluster = Cluster::new(4);
+        let mut server = SyncServer::new();
+        let strings: Vec<StringId> = (1..=3u8).map(|i| StringId::from_content(&[i])).collect();
+        for (i, s) in strings.iter().enumerate() {
+            server.record_bundle(cluster.bundle(i as u64 + 1, 3, *s));
+        }
+
+        let voting = VirtualVotingEngine::new(cluster.ids[0]);
+        for id in &cluster.ids {
+            voting.add_validator(*id);
+        }
+        let finality = finality_engine();
+        let mut sync = CatchUpSync::new(cluster.config(3), 0);
+
+        let mut request = match server.handle(&sync.status_request()) {
+            SyncResponse::Status { latest_round } => sync.on_status(latest_round),
+            _ => unreachable!(),
+        };
+
+        while let Some(req) = request {
+            let bundles = match server.handle(&req) {
+                SyncResponse::Anchors(b) => b,
+                _ => unreachable!(),
+            };
+            request = sync.on_bundles(bundles, &voting, &finality).unwrap();
+        }
+
+        assert!(sync.is_live());
+        assert_eq!(sync.local_round(), 3);
+        assert_eq!(sync.bundles_applied(), 3);
+        for s in &strings {
+            assert!(finality.is_finalized(s));
+            assert!(voting.is_decided(s.as_bytes()));
+        }
+    }
+
+    #[test]
+    fn test_rejects_under_threshold_bundle() {
+        let cluster = Cluster::new(4);
+        let mut sync = CatchUpSync::new(cluster.config(3), 0);
+        sync.on_status(1);
+
+        let voting = VirtualVotingEngine::new(cluster.ids[0]);
+        let finality = finality_engine();
+        let bundle = cluster.bundle(1, 2, StringId::from_content(b"x"));
+
+        let result = sync.on_bundles(vec![bundle], &voting, &finality);
+        assert!(matches!(result, Err(SyncError::InvalidTestimony { .. })));
+        assert_eq!(sync.local_round(), 0);
+    }
+
+    #[test]
+    fn test_rejects_stale_bundle() {
+        let cluster = Cluster::new(4);
+        let sync = CatchUpSync::new(cluster.config(3), 5);
+        let bundle = cluster.bundle(5, 3, StringId::from_content(b"x"));
+
+        assert!(matches!(
+            sync.verify_bundle(&bundle),
+            Err(SyncError::NonContiguous {
+                expected_after: 5,
+                got: 5
+            })
+        ));
+    }
+
+    #[test]
+    fn test_rejects_tampered_bundle_contents() {
+        let cluster = Cluster::new(4);
+        let sync = CatchUpSync::new(cluster.config(3), 0);
+        let bundle = cluster.bundle(1, 3, StringId::from_content(b"x"));
+        assert!(sync.verify_bundle(&bundle).is_ok());
+
+        // Valid aggregate, swapped string list
+        let mut tampered = bundle.clone();
+        tampered.referenced_strings = vec![StringId::from_content(b"forged")];
+        assert!(matches!(
+            sync.verify_bundle(&tampered),
+            Err(SyncError::AggregateMismatch { round: 1 })
+        ));
+
+        // Valid aggregate, dropped event
+        let mut tampered = bundle.clone();
+        tampered.events.pop();
+        assert!(matches!(
+            sync.verify_bundle(&tampered),
+            Err(SyncError::AggregateMismatch { round: 1 })
+        ));
+
+        // Event contents changed under its ID and signature
+        let mut tampered = bundle.clone();
+        tampered.events[0].string_ids = vec![[7u8; 32]];
+        assert!(matches!(
+            sync.verify_bundle(&tampered),
+            Err(SyncError::InvalidEvent { round: 1 })
+        ));
+
+        // Event signed by the wrong validator
+        let mut tampered = bundle.clone();
+        tampered.events[0].signature = tampered.events[1].signature.clone();
+        assert!(matches!(
+            sync.verify_bundle(&tampered),
+            Err(SyncError::InvalidEvent { round: 1 })
+        ));
+    }
+
+    #[test]
+    fn test_rejects_wrong_attestation_or_round() {
+        let cluster = Cluster::new(4);
+        let sync = CatchUpSync::new(cluster.config(3), 0);
+        let mut bundle = cluster.bundle(1, 3, StringId::from_content(b"x"));
+        let commitment = bundle.attested_id();
+
+        bundle.aggregate = cluster.aggregate_as(AttestationType::Existence, commitment, 1, 3);
+        assert!(matches!(
+            sync.verify_bundle(&bundle),
+            Err(SyncError::AggregateMismatch { round: 1 })
+        ));
+
+        bundle.aggregate = cluster.aggregate_as(AttestationType::Finality, commitment, 2, 3);
+        assert!(matches!(
+            sync.verify_bundle(&bundle),
+            Err(SyncError::AggregateMismatch { round: 1 })
+        ));
+    }
+
+    #[test]
+    fn test_snapshot_then_catch_up() {
+        let cluster = Cluster::new(4);
+        let snapshot_digest = *blake3::hash(b"snapshot").as_bytes();
+        let checkpoint_hash = [9u8; 32];
+        let anchor = SnapshotManifest::commitment(10, &snapshot_digest, 8, &checkpoint_hash);
+        let manifest = SnapshotManifest {
+            round: 10,
+            snapshot_digest,
+            snapshot_size: 8,
+            checkpoint_hash,
+            aggregate: cluster.aggregate_as(AttestationType::Finality, anchor, 10, 3),
+        };
+
+        let mut server = SyncServer::new();
+        server.record_snapshot(manifest.clone());
+        server.record_bundle(cluster.bundle(11, 3, StringId::from_content(b"after")));
+
+        let finality = finality_engine();
+        let voting = VirtualVotingEngine::new(cluster.ids[0]);
+        let mut sync = CatchUpSync::new(cluster.config(3), 0);
+
+        let offered = match server.handle(&SyncRequest::Snapshot) {
+            SyncResponse::Snapshot(Some(m)) => m,
+            _ => panic!("expected snapshot"),
+        };
+        sync.apply_snapshot(&offered, &finality).unwrap();
+        assert_eq!(sync.local_round(), 10);
+        assert_eq!(sync.bundles_applied(), 0);
+
+        // Only the rounds after the snapshot are replayed
+        let request = sync.on_status(server.latest_round()).unwrap();
+        assert_eq!(
+            request,
+            SyncRequest::Anchors {
+                from_round: 10,
+                max: 2
+            }
+        );
+        let bundles = match server.handle(&request) {
+            SyncResponse::Anchors(b) => b,
+            _ => unreachable!(),
+        };
+        assert!(sync
+            .on_bundles(bundles, &voting, &finality)
+            .unwrap()
+            .is_none());
+        assert!(sync.is_live());
+        assert_eq!(sync.bundles_applied(), 1);
+
+        // The same snapshot is now stale
+        assert!(matches!(
+            sync.verify_snapshot(&manifest),
+            Err(SyncError::StaleSnapshot { .. })
+        ));
+    }
+
+    #[test]
+    fn test_rejects_tampered_snapshot() {
+        let cluster = Cluster::new(4);
+        let sync = CatchUpSync::new(cluster.config(3), 0);
+        let snapshot_digest = [1u8; 32];
+        let anchor = SnapshotManifest::commitment(5, &snapshot_digest, 100, &[2u8; 32]);
+
+        let mut manifest = SnapshotManifest {
+            round: 5,
+            snapshot_digest,
+            snapshot_size: 100,
+            checkpoint_hash: [2u8; 32],
+            aggregate: cluster.aggregate_as(AttestationType::Finality, anchor, 5, 3),
+        };
+        assert!(sync.verify_snapshot(&manifest).is_ok());
+
+        manifest.snapshot_digest = [3u8; 32];
+        assert!(matches!(
+            sync.verify_snapshot(&manifest),
+            Err(SyncError::AggregateMismatch { round: 5 })
+        ));
+
+        manifest.snapshot_digest = snapshot_digest;
+        manifest.aggregate = cluster.aggregate_as(AttestationType::Finality, anchor, 5, 2);
+        assert!(matches!(
+            sync.verify_snapshot(&manifest),
+            Err(SyncError::InvalidTestimony { .. })
+        ));
+
+        // Testimony given for another purpose does not cover the snapshot
+        manifest.aggregate = cluster.aggregate(anchor, 5, 3);
+        assert!(matches!(
+            sync.verify_snapshot(&manifest),
+            Err(SyncError::AggregateMismatch { round: 5 })
+        ));
+    }
+
+    #[test]
+    fn test_server_pagination() {
+        let cluster = Cluster::new(4);
+        let mut server = SyncServer::new();
+        for round in 1..=5 {
+            server.record_bundle(cluster.bundle(round, 3, StringId::from_content(&[round as u8])));
+        }
+
+        match server.handle(&SyncRequest::Anchors {
+            from_round: 2,
+            max: 2,
+        }) {
+            SyncResponse::Anchors(b) => {
+                assert_eq!(b.iter().map(|b| b.round).collect::<Vec<_>>(), vec![3, 4]);
+            }
+            _ => panic!("expected anchors"),
+        }
+
+        server.prune_through(3);
+        assert_eq!(server.latest_round(), 5);
+        match server.handle(&SyncRequest::Anchors {
+            from_round: 0,
+            max: 10,
+        }) {
+            SyncResponse::Anchors(b) => assert_eq!(b.len(), 2),
+            _ => panic!("expected anchors"),
+        }
+    }
+}
@@ -23,6 +23,7 @@
 use parking_lot::RwLock;
 use rope_core::clock::LamportClock;
 use rope_core::types::{AttestationType, NodeId, StringId};
+use rope_crypto::remote_signer::HybridRemoteSigner;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -62,6 +63,10 @@ pub struct TestimonySignature {
 
     /// CRYSTALS-Dilithium3 signature (~2420 bytes)
     pub dilithium: Vec<u8>,
+
+    /// BLS12-381 signature over the aggregate signing data (96 bytes)
+    #[serde(default)]
+    pub bls: Vec<u8>,
 }
 
 impl Default for TestimonySignature {
@@ -69,6 +74,7 @@ impl Default for TestimonySignature {
         Self {
             ed25519: Vec::new(),
             dilithium: Vec::new(),
+            bls: Vec::new(),
         }
     }
 }
@@ -136,6 +142,19 @@ impl Testimony {
         data
     }
 
+    /// Get the data signed with BLS for aggregation
+    ///
+    /// Unlike `signing_data`, this omits validator-specific fields so that
+    /// every validator attesting the same string in the same round signs
+    /// an identical message and the signatures can be aggregated.
+    pub fn aggregate_signing_data(&self) -> Vec<u8> {
+        aggregate_signing_data(
+            &self.target_string_id,
+            self.attestation_type,
+            self.metadata.round,
+        )
+    }
+
     /// Check if testimony is signed
     pub fn is_signed(&self) -> bool {
         !self.signature.ed25519.is_empty() && !self.signature.dilithium.is_empty()
@@ -147,6 +166,24 @@ impl Testimony {
         self.signature.dilithium = dilithium;
     }
 
+    /// Sign through a (possibly hardware-backed) hybrid signer
+    ///
+    /// The device call is retried and time-bounded by the signer's policy;
+    /// on failure the testimony is left unsigned.
+    pub async fn sign_remote(&mut self, signer: &HybridRemoteSigner) -> Result<(), TestimonyError> {
+        let signature = signer
+            .sign(&self.signing_data())
+            .await
+            .map_err(|e| TestimonyError::SigningFailed(e.to_string()))?;
+        self.set_signature(signature.ed25519_sig, signature.dilithium_sig);
+        Ok(())
+    }
+
+    /// Set BLS signature used for aggregation
+    pub fn set_bls_signature(&mut self, bls: Vec<u8>) {
+        self.signature.bls = bls;
+    }
+
     // ========================================================================
     // Testimony as String (§6.1)
     // ========================================================================
@@ -302,7 +339,11 @@ impl Testimony {
             oes_generation,
         );
 
-        testimony.signature = TestimonySignature { ed25519, dilithium };
+        testimony.signature = TestimonySignature {
+            ed25519,
+            dilithium,
+            bls: Vec::new(),
+        };
         testimony.metadata.round = round;
 
         Ok(testimony)
@@ -319,6 +360,20 @@ impl Testimony {
     }
 }
 
+/// Message signed with BLS by every validator attesting a string in a round
+pub fn aggregate_signing_data(
+    string_id: &StringId,
+    attestation_type: AttestationType,
+    round: u64,
+) -> Vec<u8> {
+    let mut data = Vec::with_capacity(64);
+    data.extend_from_slice(b"rope-testimony-agg");
+    data.extend_from_slice(string_id.as_bytes());
+    data.push(attestation_type.as_u8());
+    data.extend_from_slice(&round.to_le_bytes());
+    data
+}
+
 /// Testimony collection for a string
 #[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct TestimonyCollection {
@@ -572,6 +627,8 @@ pub enum TestimonyError {
     ExpiredTestimony,
     InvalidAttestationType,
     InvalidFormat(String),
+    SigningFailed(String),
+    BelowThreshold { collected: usize, required: usize },
 }
 
 impl std::fmt::Display for TestimonyError {
@@ -584,6 +641,15 @@ impl std::fmt::Display for TestimonyError {
             TestimonyError::ExpiredTestimony => write!(f, "Expired testimony"),
             TestimonyError::InvalidAttestationType => write!(f, "Invalid attestation type"),
             TestimonyError::InvalidFormat(msg) => write!(f, "Invalid format: {}", msg),
+            TestimonyError::SigningFailed(msg) => write!(f, "Signing failed: {}", msg),
+            TestimonyError::BelowThreshold {
+                collected,
+                required,
+            } => write!(
+                f,
+                "Below threshold: {} of {} required testimonies",
+                collected, required
+            ),
         }
     }
 }
@@ -634,6 +700,38 @@ mod tests {
         assert!(testimony.is_signed());
     }
 
+    #[tokio::test]
+    async fn test_testimony_remote_signing() {
+        use rope_crypto::hybrid::{HybridSigner, HybridVerifier};
+        use rope_crypto::remote_signer::{LocalSigner, SigningPolicy};
+        use std::sync::Arc;
+
+        let (local, public) = HybridSigner::generate();
+        let secret = local.secret_key();
+        let signer = HybridRemoteSigner::new(
+            Arc::new(LocalSigner::from_seed(secret.ed25519_bytes())),
+            secret.dilithium_bytes().to_vec(),
+            SigningPolicy::default(),
+        );
+
+        let validator_id = NodeId::new(public.ed25519);
+        let mut testimony = Testimony::new(
+            StringId::from_content(b"test string"),
+            validator_id,
+            AttestationType::Existence,
+            LamportClock::new(validator_id),
+            1,
+        );
+        testimony.sign_remote(&signer).await.unwrap();
+        assert!(testimony.is_signed());
+
+        let signature = rope_crypto::hybrid::HybridSignature {
+            ed25519_sig: testimony.signature.ed25519.clone(),
+            dilithium_sig: testimony.signature.dilithium.clone(),
+        };
+        assert!(HybridVerifier::verify(&public, &testimony.signing_data(), &signature).unwrap());
+    }
+
     #[test]
     fn test_testimony_collection() {
         let string_id = StringId::from_content(b"test string");
@@ -0,0 +1,580 @@
+//! # Round Timeouts and View Change
+//!
+//! Keeps the lattice advancing under partial validator outage.
+//!
+//! Each round has a deadline. If the round does not gather the required
+//! testimonies before the deadline, validators broadcast a signed
+//! [`ViewChangeVote`]. Once 2f+1 validators agree, the round is closed with
+//! an [`EmptyAnchor`] that references no strings and carries the votes, so
+//! any node can check it with [`EmptyAnchor::verify`]. The next round
+//! starts with an exponentially longer timeout. A round that completes
+//! normally resets the timeout to its base value.
+//!
+//! A view change that does not reach quorum before its own deadline is
+//! escalated: the validator votes for the next view with a longer timeout.
+//!
+//! ```text
+//! Collecting ──testimonies ≥ 2f+1──▶ Completed
+//!     │
+//!  deadline
+//!     ▼
+//! TimedOut(v) ──view-change votes ≥ 2f+1──▶ EmptyAnchor → next round
+//!     │   ▲
+//!  deadline
+//!     └───┘ v + 1
+//! ```
+
+use crate::finality_engine::FinalityEngine;
+use crate::virtual_voting_impl::NodeId;
+use parking_lot::RwLock;
+use rope_crypto::hybrid::{HybridPublicKey, HybridSignature, HybridSigner, HybridVerifier};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+/// Round timeout configuration
+#[derive(Clone, Debug)]
+pub struct RoundTimeoutConfig {
+    /// Timeout for a round after a successful one (milliseconds)
+    pub base_timeout_ms: u64,
+
+    /// Multiplier applied after each consecutive timeout
+    pub backoff_multiplier: u32,
+
+    /// Upper bound on the timeout (milliseconds)
+    pub max_timeout_ms: u64,
+
+    /// Testimonies required to complete a round (2f+1)
+    pub required_testimonies: usize,
+}
+
+impl Default for RoundTimeoutConfig {
+    fn default() -> Self {
+        Self {
+            base_timeout_ms: 5_000,
+            backoff_multiplier: 2,
+            max_timeout_ms: 60_000,
+            required_testimonies: 15, // 2f+1 for 21 validators
+        }
+    }
+}
+
+/// Status of the current round
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RoundStatus {
+    /// Gathering testimonies before the deadline
+    Collecting { deadline_ms: i64 },
+    /// Reached the testimony threshold
+    Completed,
+    /// Deadline passed; waiting for view-change votes for `view` until
+    /// `deadline_ms`, then escalating to the next view
+    TimedOut { view: u64, deadline_ms: i64 },
+}
+
+/// Vote to abandon a stalled round
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ViewChangeVote {
+    pub round: u64,
+    pub new_view: u64,
+    pub voter: NodeId,
+    pub timestamp: i64,
+
+    /// Voter's signature over [`ViewChangeVote::signing_data`]
+    pub signature: HybridSignature,
+}
+
+impl ViewChangeVote {
+    /// Create a vote signed by `signer`
+    pub fn signed(
+        round: u64,
+        new_view: u64,
+        voter: NodeId,
+        timestamp: i64,
+        signer: &HybridSigner,
+    ) -> Self {
+        Self {
+            round,
+            new_view,
+            voter,
+            timestamp,
+            signature: signer.sign(&Self::signing_data(round, new_view, &voter)),
+        }
+    }
+
+    /// Message signed by the voter
+    pub fn signing_data(round: u64, new_view: u64, voter: &NodeId) -> Vec<u8> {
+        let mut data = Vec::with_capacity(24 + 8 + 8 + 32);
+        data.extend_from_slice(b"rope-view-change-vote-v1");
+        data.extend_from_slice(&round.to_le_bytes());
+        data.extend_from_slice(&new_view.to_le_bytes());
+        data.extend_from_slice(voter);
+        data
+    }
+
+    /// Verify the signature against the voter's key
+    pub fn verify(&self, public_key: &HybridPublicKey) -> bool {
+        HybridVerifier::verify(
+            public_key,
+            &Self::signing_data(self.round, self.new_view, &self.voter),
+            &self.signature,
+        )
+        .unwrap_or(false)
+    }
+}
+
+/// Anchor closing a round that could not gather testimonies
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EmptyAnchor {
+    pub round: u64,
+    pub view: u64,
+
+    /// Signed votes for the view change, sorted by voter
+    pub votes: Vec<ViewChangeVote>,
+}
+
+impl EmptyAnchor {
+    /// Deterministic anchor ID
+    pub fn id(&self) -> [u8; 32] {
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(b"rope-empty-anchor");
+        hasher.update(&self.round.to_le_bytes());
+        hasher.update(&self.view.to_le_bytes());
+        *hasher.finalize().as_bytes()
+    }
+
+    /// Validators that voted for the view change
+    pub fn voters(&self) -> Vec<NodeId> {
+        self.votes.iter().map(|v| v.voter).collect()
+    }
+
+    /// Check that 2f+1 distinct validators signed this round and view
+    pub fn verify(&self, validators: &HashMap<NodeId, HybridPublicKey>) -> bool {
+        let mut voters = HashSet::new();
+        for vote in &self.votes {
+            if vote.round != self.round || vote.new_view != self.view {
+                return false;
+            }
+            match validators.get(&vote.voter) {
+                Some(public_key) if vote.verify(public_key) => {
+                    voters.insert(vote.voter);
+                }
+                _ => return false,
+            }
+        }
+        voters.len() >= quorum(validators.len())
+    }
+}
+
+/// Votes needed out of `n` validators (2f+1)
+fn quorum(n: usize) -> usize {
+    2 * (n.saturating_sub(1) / 3) + 1
+}
+
+struct RoundState {
+    round: u64,
+    view: u64,
+    status: RoundStatus,
+    testimonies: usize,
+    consecutive_timeouts: u32,
+    view_change_votes: HashMap<u64, HashMap<NodeId, ViewChangeVote>>,
+}
+
+/// Tracks round deadlines and drives view changes
+pub struct RoundTimer {
+    config: RoundTimeoutConfig,
+    validators: RwLock<HashMap<NodeId, HybridPublicKey>>,
+    state: RwLock<RoundState>,
+}
+
+impl RoundTimer {
+    /// Start tracking at `round`
+    pub fn new(config: RoundTimeoutConfig, round: u64, now_ms: i64) -> Self {
+        let deadline_ms = now_ms + config.base_timeout_ms as i64;
+        Self {
+            config,
+            validators: RwLock::new(HashMap::new()),
+            state: RwLock::new(RoundState {
+                round,
+                view: 0,
+                status: RoundStatus::Collecting { deadline_ms },
+                testimonies: 0,
+                consecutive_timeouts: 0,
+                view_change_votes: HashMap::new(),
+            }),
+        }
+    }
+
+    /// Register a validator eligible to vote for view changes
+    pub fn add_validator(&self, validator: NodeId, public_key: HybridPublicKey) {
+        self.validators.write().insert(validator, public_key);
+    }
+
+    /// Check an empty anchor received from a peer
+    pub fn verify_empty_anchor(&self, anchor: &EmptyAnchor) -> bool {
+        anchor.verify(&self.validators.read())
+    }
+
+    /// Current round
+    pub fn current_round(&self) -> u64 {
+        self.state.read().round
+    }
+
+    /// Current view within the round
+    pub fn current_view(&self) -> u64 {
+        self.state.read().view
+    }
+
+    /// Current round status
+    pub fn status(&self) -> RoundStatus {
+        self.state.read().status.clone()
+    }
+
+    /// Timeout applied to the next round given consecutive timeouts
+    pub fn current_timeout_ms(&self) -> u64 {
+        self.timeout_for(self.state.read().consecutive_timeouts)
+    }
+
+    fn timeout_for(&self, consecutive_timeouts: u32) -> u64 {
+        let factor =
+            (self.config.backoff_multiplier.max(1) as u64).saturating_pow(consecutive_timeouts);
+        self.config
+            .base_timeout_ms
+            .saturating_mul(factor)
+            .min(self.config.max_timeout_ms)
+    }
+
+    /// Record the testimony count for the current round
+    ///
+    /// Returns `true` if this completes the round.
+    pub fn record_testimonies(&self, round: u64, count: usize) -> bool {
+        let mut state = self.state.write();
+        if state.round != round || !matches!(state.status, RoundStatus::Collecting { .. }) {
+            return false;
+        }
+        state.testimonies = count;
+        if count >= self.config.required_testimonies {
+            state.status = RoundStatus::Completed;
+            state.consecutive_timeouts = 0;
+            return true;
+        }
+        false
+    }
+
+    /// Check the deadline; returns our signed view-change vote if the round
+    /// stalled, or if the pending view change itself timed out
+    pub fn check_timeout(
+        &self,
+        signer: &HybridSigner,
+        our_id: NodeId,
+        now_ms: i64,
+    ) -> Option<ViewChangeVote> {
+        let mut state = self.state.write();
+        let new_view = match state.status {
+            RoundStatus::Collecting { deadline_ms } if now_ms >= deadline_ms => {
+                tracing::warn!(
+                    "Round {} timed out with {}/{} testimonies, requesting view {}",
+                    state.round,
+                    state.testimonies,
+                    self.config.required_testimonies,
+                    state.view + 1
+                );
+                state.view + 1
+            }
+            RoundStatus::TimedOut { view, deadline_ms } if now_ms >= deadline_ms => {
+                tracing::warn!(
+                    "View change to {} in round {} stalled, escalating to view {}",
+                    view,
+                    state.round,
+                    view + 1
+                );
+                view + 1
+            }
+            _ => return None,
+        };
+
+        let escalations = (new_view - state.view) as u32;
+        let timeout = self.timeout_for(state.consecutive_timeouts.saturating_add(escalations));
+        state.status = RoundStatus::TimedOut {
+            view: new_view,
+            deadline_ms: now_ms + timeout as i64,
+        };
+
+        Some(ViewChangeVote::signed(
+            state.round,
+            new_view,
+            our_id,
+            now_ms,
+            signer,
+        ))
+    }
+
+    /// Record a view-change vote
+    ///
+    /// Votes from unknown validators or with a bad signature are dropped.
+    /// Returns an [`EmptyAnchor`] once 2f+1 validators voted for the same
+    /// view, and starts the next round with a backed-off deadline.
+    pub fn record_view_change(&self, vote: ViewChangeVote, now_ms: i64) -> Option<EmptyAnchor> {
+        let validators = self.validators.read();
+        match validators.get(&vote.voter) {
+            Some(public_key) if vote.verify(public_key) => {}
+            _ => return None,
+        }
+
+        let mut state = self.state.write();
+        if vote.round != state.round || vote.new_view <= state.view {
+            return None;
+        }
+
+        let view = vote.new_view;
+        let mut votes: Vec<ViewChangeVote> = {
+            let votes = state.view_change_votes.entry(view).or_default();
+            votes.entry(vote.voter).or_insert(vote);
+            votes.values().cloned().collect()
+        };
+
+        if votes.len() < quorum(validators.len()) {
+            return None;
+        }
+        votes.sort_by_key(|v| v.voter);
+
+        let round = state.round;
+        let anchor = EmptyAnchor { round, view, votes };
+
+        state.consecutive_timeouts = state.consecutive_timeouts.saturating_add(1);
+        let timeout = self.timeout_for(state.consecutive_timeouts);
+        Self::start_round(&mut state, round + 1, now_ms, timeout);
+
+        Some(anchor)
+    }
+
+    /// Move to the next round after a normal completion
+    pub fn advance(&self, now_ms: i64) {
+        let mut state = self.state.write();
+        let timeout = self.timeout_for(state.consecutive_timeouts);
+        let next = state.round + 1;
+        Self::start_round(&mut state, next, now_ms, timeout);
+    }
+
+    fn start_round(state: &mut RoundState, round: u64, now_ms: i64, timeout_ms: u64) {
+        state.round = round;
+        state.view = 0;
+        state.testimonies = 0;
+        state.view_change_votes.clear();
+        state.status = RoundStatus::Collecting {
+            deadline_ms: now_ms + timeout_ms as i64,
+        };
+    }
+}
+
+/// Record an empty anchor so finality keeps advancing
+pub fn apply_empty_anchor(finality: &FinalityEngine, anchor: &EmptyAnchor) {
+    finality.record_anchor(anchor.id(), anchor.round, Vec::new());
+    finality.advance_round();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Validators {
+        signers: Vec<HybridSigner>,
+        keys: HashMap<NodeId, HybridPublicKey>,
+    }
+
+    impl Validators {
+        fn new(n: u8) -> Self {
+            let mut signers = Vec::new();
+            let mut keys = HashMap::new();
+            for i in 0..n {
+                let (signer, public_key) = HybridSigner::generate_signing_only();
+                signers.push(signer);
+                keys.insert([i; 32], public_key);
+            }
+            Self { signers, keys }
+        }
+
+        fn vote(&self, voter: u8, round: u64, new_view: u64) -> ViewChangeVote {
+            ViewChangeVote::signed(
+                round,
+                new_view,
+                [voter; 32],
+                0,
+                &self.signers[voter as usize],
+            )
+        }
+    }
+
+    fn timer(validators: &Validators) -> RoundTimer {
+        let config = RoundTimeoutConfig {
+            base_timeout_ms: 1_000,
+            backoff_multiplier: 2,
+            max_timeout_ms: 5_000,
+            required_testimonies: 3,
+        };
+        let timer = RoundTimer::new(config, 1, 0);
+        for (id, public_key) in &validators.keys {
+            timer.add_validator(*id, public_key.clone());
+        }
+        timer
+    }
+
+    #[test]
+    fn test_round_completes_before_deadline() {
+        let validators = Validators::new(4);
+        let timer = timer(&validators);
+        assert!(!timer.record_testimonies(1, 2));
+        assert!(timer.record_testimonies(1, 3));
+        assert_eq!(timer.status(), RoundStatus::Completed);
+        assert!(timer
+            .check_timeout(&validators.signers[0], [0; 32], 10_000)
+            .is_none());
+    }
+
+    #[test]
+    fn test_timeout_produces_signed_vote() {
+        let validators = Validators::new(4);
+        let timer = timer(&validators);
+        let signer = &validators.signers[0];
+        assert!(timer.check_timeout(signer, [0; 32], 999).is_none());
+
+        let vote = timer.check_timeout(signer, [0; 32], 1_000).unwrap();
+        assert_eq!(vote.round, 1);
+        assert_eq!(vote.new_view, 1);
+        assert!(vote.verify(&validators.keys[&[0; 32]]));
+        assert_eq!(
+            timer.status(),
+            RoundStatus::TimedOut {
+                view: 1,
+                deadline_ms: 3_000
+            }
+        );
+
+        // Late testimonies do not revive a timed-out round
+        assert!(!timer.record_testimonies(1, 3));
+    }
+
+    #[test]
+    fn test_stalled_view_change_escalates() {
+        let validators = Validators::new(4);
+        let timer = timer(&validators);
+        let signer = &validators.signers[0];
+
+        assert_eq!(
+            timer
+                .check_timeout(signer, [0; 32], 1_000)
+                .unwrap()
+                .new_view,
+            1
+        );
+        assert!(timer.check_timeout(signer, [0; 32], 2_999).is_none());
+
+        // Nobody else voted; move on to the next view with a longer wait
+        let vote = timer.check_timeout(signer, [0; 32], 3_000).unwrap();
+        assert_eq!(vote.new_view, 2);
+        assert_eq!(
+            timer.status(),
+            RoundStatus::TimedOut {
+                view: 2,
+                deadline_ms: 7_000
+            }
+        );
+
+        for voter in 0..3 {
+            let anchor = timer.record_view_change(validators.vote(voter, 1, 2), 3_500);
+            if voter == 2 {
+                assert_eq!(anchor.unwrap().view, 2);
+            }
+        }
+        assert_eq!(timer.current_round(), 2);
+    }
+
+    #[test]
+    fn test_view_change_quorum_emits_empty_anchor() {
+        let validators = Validators::new(4);
+        let timer = timer(&validators);
+        timer.check_timeout(&validators.signers[0], [0; 32], 1_000);
+
+        assert!(timer
+            .record_view_change(validators.vote(0, 1, 1), 1_000)
+            .is_none());
+        assert!(timer
+            .record_view_change(validators.vote(1, 1, 1), 1_000)
+            .is_none());
+        // Duplicate vote does not count twice
+        assert!(timer
+            .record_view_change(validators.vote(1, 1, 1), 1_000)
+            .is_none());
+        // Unknown validator ignored
+        let (outsider, _) = HybridSigner::generate_signing_only();
+        let unknown = ViewChangeVote::signed(1, 1, [9; 32], 0, &outsider);
+        assert!(timer.record_view_change(unknown, 1_000).is_none());
+        // Vote claiming another validator's identity ignored
+        let forged = ViewChangeVote::signed(1, 1, [3; 32], 0, &validators.signers[0]);
+        assert!(timer.record_view_change(forged, 1_000).is_none());
+
+        let anchor = timer
+            .record_view_change(validators.vote(2, 1, 1), 1_200)
+            .unwrap();
+        assert_eq!(anchor.round, 1);
+        assert_eq!(anchor.voters(), vec![[0; 32], [1; 32], [2; 32]]);
+        assert!(timer.verify_empty_anchor(&anchor));
+
+        assert_eq!(timer.current_round(), 2);
+        assert_eq!(
+            timer.status(),
+            RoundStatus::Collecting { deadline_ms: 3_200 }
+        );
+    }
+
+    #[test]
+    fn test_empty_anchor_verification() {
+        let validators = Validators::new(4);
+        let mut anchor = EmptyAnchor {
+            round: 1,
+            view: 1,
+            votes: (0..3).map(|v| validators.vote(v, 1, 1)).collect(),
+        };
+        assert!(anchor.verify(&validators.keys));
+
+        // Same voter twice is not a quorum
+        anchor.votes[2] = validators.vote(1, 1, 1);
+        assert!(!anchor.verify(&validators.keys));
+
+        // Vote for another view does not count
+        anchor.votes[2] = validators.vote(2, 1, 2);
+        assert!(!anchor.verify(&validators.keys));
+
+        // Tampered vote
+        anchor.votes[2] = validators.vote(2, 1, 1);
+        anchor.votes[2].voter = [3; 32];
+        assert!(!anchor.verify(&validators.keys));
+    }
+
+    #[test]
+    fn test_backoff_is_capped_and_resets() {
+        let validators = Validators::new(1);
+        let timer = timer(&validators);
+        for round in 1..=4 {
+            timer.check_timeout(&validators.signers[0], [0; 32], i64::MAX / 2);
+            timer.record_view_change(validators.vote(0, round, 1), 0);
+        }
+        assert_eq!(timer.current_timeout_ms(), 5_000);
+
+        timer.record_testimonies(5, 3);
+        assert_eq!(timer.current_timeout_ms(), 1_000);
+    }
+
+    #[test]
+    fn test_empty_anchor_advances_finality() {
+        let finality = FinalityEngine::default();
+        let anchor = EmptyAnchor {
+            round: 3,
+            view: 1,
+            votes: Vec::new(),
+        };
+        apply_empty_anchor(&finality, &anchor);
+
+        let stats = finality.stats();
+        assert_eq!(stats.anchor_count, 1);
+        assert_eq!(stats.current_round, 1);
+    }
+}
@@ -128,6 +128,19 @@ impl GossipEvent {
         }
     }
 
+    /// Check that `id` is the hash of the event's contents
+    pub fn has_valid_id(&self) -> bool {
+        self.id
+            == Self::compute_id(
+                &self.creator,
+                self.round,
+                &self.string_ids,
+                self.self_parent,
+                self.other_parent,
+                self.timestamp,
+            )
+    }
+
     /// Compute event ID
     fn compute_id(
         creator: &NodeId,
@@ -0,0 +1,335 @@
+//! Merkle Mountain Range accumulator over finalized strings
+//!
+//! Finalized strings are appended as leaves in finality order. The
+//! accumulator is a list of perfect binary trees ("mountains") whose sizes
+//! follow the set bits of the leaf count; appending is O(log n) and never
+//! rewrites existing nodes, so old proofs stay checkable against old roots.
+//!
+//! Hashes are domain-separated BLAKE3:
+//! - leaf: `H(0x00 || string_id)`
+//! - node: `H(0x01 || left || right)`
+//! - root: `H(0x02 || leaf_count_be || peaks...)`, peaks from tallest to
+//!   shortest
+
+use crate::codec::{tags, CanonicalEncoding, Decoder, Encoder, WireField};
+use crate::error::{Result, RopeError};
+use crate::types::StringId;
+use hashbrown::HashMap;
+use serde::{Deserialize, Serialize};
+
+const LEAF_PREFIX: u8 = 0x00;
+const NODE_PREFIX: u8 = 0x01;
+const ROOT_PREFIX: u8 = 0x02;
+
+/// Hash of an accumulator leaf
+pub fn leaf_hash(id: &StringId) -> [u8; 32] {
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(&[LEAF_PREFIX]);
+    hasher.update(id.as_bytes());
+    *hasher.finalize().as_bytes()
+}
+
+fn node_hash(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(&[NODE_PREFIX]);
+    hasher.update(left);
+    hasher.update(right);
+    *hasher.finalize().as_bytes()
+}
+
+fn bag_peaks(leaf_count: u64, peaks: &[[u8; 32]]) -> [u8; 32] {
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(&[ROOT_PREFIX]);
+    hasher.update(&leaf_count.to_be_bytes());
+    for peak in peaks {
+        hasher.update(peak);
+    }
+    *hasher.finalize().as_bytes()
+}
+
+/// Mountain containing `leaf_index`: (peak position, mountain height)
+fn mountain_for(leaf_index: u64, leaf_count: u64) -> Option<(usize, u32)> {
+    if leaf_index >= leaf_count {
+        return None;
+    }
+    let mut offset = 0u64;
+    let mut peak = 0usize;
+    for height in (0..64).rev() {
+        if leaf_count & (1 << height) == 0 {
+            continue;
+        }
+        let size = 1u64 << height;
+        if leaf_index < offset + size {
+            return Some((peak, height));
+        }
+        offset += size;
+        peak += 1;
+    }
+    None
+}
+
+/// Append-only Merkle Mountain Range
+#[derive(Clone, Debug, Default)]
+pub struct MerkleAccumulator {
+    /// `levels[h][i]` roots the aligned subtree of leaves `i*2^h..(i+1)*2^h`
+    levels: Vec<Vec<[u8; 32]>>,
+    /// Leaf index of each string
+    positions: HashMap<StringId, u64>,
+}
+
+impl MerkleAccumulator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of leaves
+    pub fn len(&self) -> u64 {
+        self.levels.first().map_or(0, |l| l.len() as u64)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Append a string; returns its leaf index
+    ///
+    /// Appending an already committed string returns its existing index.
+    pub fn append(&mut self, id: StringId) -> u64 {
+        if let Some(&index) = self.positions.get(&id) {
+            return index;
+        }
+
+        let index = self.len();
+        self.positions.insert(id, index);
+
+        let mut node = leaf_hash(&id);
+        let mut height = 0;
+        loop {
+            if self.levels.len() == height {
+                self.levels.push(Vec::new());
+            }
+            let level = &mut self.levels[height];
+            level.push(node);
+            if level.len() % 2 == 1 {
+                break;
+            }
+            // Completed a pair: carry the parent up
+            node = node_hash(&level[level.len() - 2], &level[level.len() - 1]);
+            height += 1;
+        }
+
+        index
+    }
+
+    /// Leaf index of a committed string
+    pub fn position(&self, id: &StringId) -> Option<u64> {
+        self.positions.get(id).copied()
+    }
+
+    /// Mountain peaks from tallest to shortest
+    pub fn peaks(&self) -> Vec<[u8; 32]> {
+        self.levels
+            .iter()
+            .rev()
+            .filter(|level| level.len() % 2 == 1)
+            .filter_map(|level| level.last().copied())
+            .collect()
+    }
+
+    /// Commitment to every appended string
+    pub fn root(&self) -> [u8; 32] {
+        bag_peaks(self.len(), &self.peaks())
+    }
+
+    /// Inclusion proof for a committed string against the current root
+    pub fn prove(&self, id: &StringId) -> Option<InclusionProof> {
+        let leaf_index = self.position(id)?;
+        let leaf_count = self.len();
+        let (_, mountain_height) = mountain_for(leaf_index, leaf_count)?;
+
+        let siblings = (0..mountain_height as usize)
+            .map(|height| self.levels[height][((leaf_index >> height) ^ 1) as usize])
+            .collect();
+
+        Some(InclusionProof {
+            string_id: *id,
+            leaf_index,
+            leaf_count,
+            siblings,
+            peaks: self.peaks(),
+        })
+    }
+}
+
+/// Proof that a string is committed by an accumulator root
+///
+/// Self-contained: verification needs only the proof and a trusted root.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InclusionProof {
+    pub string_id: StringId,
+    pub leaf_index: u64,
+    /// Accumulator size the proof was made against
+    pub leaf_count: u64,
+    /// Sibling hashes from the leaf up to its mountain peak
+    pub siblings: Vec<[u8; 32]>,
+    /// All mountain peaks, tallest first
+    pub peaks: Vec<[u8; 32]>,
+}
+
+impl InclusionProof {
+    /// Check the proof against a trusted accumulator root
+    pub fn verify(&self, root: &[u8; 32]) -> bool {
+        let Some((peak, height)) = mountain_for(self.leaf_index, self.leaf_count) else {
+            return false;
+        };
+        if self.siblings.len() != height as usize
+            || self.peaks.len() != self.leaf_count.count_ones() as usize
+        {
+            return false;
+        }
+
+        let mut node = leaf_hash(&self.string_id);
+        for (level, sibling) in self.siblings.iter().enumerate() {
+            node = if (self.leaf_index >> level) & 1 == 0 {
+                node_hash(&node, sibling)
+            } else {
+                node_hash(sibling, &node)
+            };
+        }
+
+        node == self.peaks[peak] && bag_peaks(self.leaf_count, &self.peaks) == *root
+    }
+}
+
+impl CanonicalEncoding for InclusionProof {
+    const TAG: u8 = tags::INCLUSION_PROOF;
+
+    fn encode_body(&self, enc: &mut Encoder) {
+        self.string_id.encode_field(enc);
+        enc.put_varint(self.leaf_index);
+        enc.put_varint(self.leaf_count);
+        for hashes in [&self.siblings, &self.peaks] {
+            enc.put_varint(hashes.len() as u64);
+            for hash in hashes {
+                enc.put_fixed(hash);
+            }
+        }
+    }
+
+    fn decode_body(dec: &mut Decoder<'_>) -> Result<Self> {
+        let string_id = StringId::decode_field(dec)?;
+        let leaf_index = dec.varint()?;
+        let leaf_count = dec.varint()?;
+        let hashes = |dec: &mut Decoder<'_>| -> Result<Vec<[u8; 32]>> {
+            let count = dec.count(32)?;
+            (0..count).map(|_| dec.fixed()).collect()
+        };
+        let siblings = hashes(dec)?;
+        let peaks = hashes(dec)?;
+        if siblings.len() > 64 || peaks.len() > 64 {
+            return Err(RopeError::SerializationError(
+                "inclusion proof too long".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            string_id,
+            leaf_index,
+            leaf_count,
+            siblings,
+            peaks,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn id(n: u32) -> StringId {
+        StringId::from_content(&n.to_be_bytes())
+    }
+
+    #[test]
+    fn test_root_changes_on_append() {
+        let mut acc = MerkleAccumulator::new();
+        let empty = acc.root();
+
+        assert_eq!(acc.append(id(0)), 0);
+        let one = acc.root();
+        assert_ne!(empty, one);
+
+        // Re-appending is a no-op
+        assert_eq!(acc.append(id(0)), 0);
+        assert_eq!(acc.root(), one);
+        assert_eq!(acc.len(), 1);
+    }
+
+    #[test]
+    fn test_peaks_follow_leaf_count_bits() {
+        let mut acc = MerkleAccumulator::new();
+        for n in 0..11 {
+            acc.append(id(n));
+        }
+        // 11 = 8 + 2 + 1
+        assert_eq!(acc.peaks().len(), 3);
+        assert_eq!(
+            acc.peaks()[2],
+            leaf_hash(&id(10)),
+            "shortest mountain is the last leaf"
+        );
+    }
+
+    #[test]
+    fn test_inclusion_proofs() {
+        let mut acc = MerkleAccumulator::new();
+        for n in 0..13 {
+            acc.append(id(n));
+        }
+        let root = acc.root();
+
+        for n in 0..13 {
+            let proof = acc.prove(&id(n)).unwrap();
+            assert!(proof.verify(&root), "leaf {}", n);
+        }
+        assert!(acc.prove(&id(99)).is_none());
+
+        // Tampering fails
+        let mut proof = acc.prove(&id(5)).unwrap();
+        proof.string_id = id(6);
+        assert!(!proof.verify(&root));
+
+        let mut proof = acc.prove(&id(5)).unwrap();
+        proof.leaf_index = 4;
+        assert!(!proof.verify(&root));
+    }
+
+    #[test]
+    fn test_old_proofs_verify_against_old_roots() {
+        let mut acc = MerkleAccumulator::new();
+        for n in 0..6 {
+            acc.append(id(n));
+        }
+        let old_root = acc.root();
+        let old_proof = acc.prove(&id(2)).unwrap();
+
+        for n in 6..20 {
+            acc.append(id(n));
+        }
+        assert!(old_proof.verify(&old_root));
+        assert!(!old_proof.verify(&acc.root()));
+        assert!(acc.prove(&id(2)).unwrap().verify(&acc.root()));
+    }
+
+    #[test]
+    fn test_proof_canonical_roundtrip() {
+        let mut acc = MerkleAccumulator::new();
+        for n in 0..7 {
+            acc.append(id(n));
+        }
+        let proof = acc.prove(&id(3)).unwrap();
+        let decoded = InclusionProof::from_canonical_bytes(&proof.to_canonical_bytes()).unwrap();
+        assert_eq!(decoded, proof);
+        assert!(decoded.verify(&acc.root()));
+    }
+}
@@ -0,0 +1,461 @@
+//! Bounded in-memory string cache with LRU eviction
+//!
+//! The lattice keeps its DAG, finality sets and complements in memory but
+//! holds string bodies in a [`StringCache`]. When a capacity limit is set the
+//! least recently used strings are written to a [`StringStore`] in their
+//! canonical encoding and faulted back in transparently on access.
+//!
+//! Memory is accounted per string (body plus bookkeeping) so nodes can check
+//! the per-string overhead target at scale.
+
+use crate::codec::CanonicalEncoding;
+use crate::error::{Result, RopeError};
+use crate::nucleotide::Nucleotide;
+use crate::string::RopeString;
+use crate::types::StringId;
+use hashbrown::{HashMap, HashSet};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::mem::{size_of, size_of_val};
+use std::sync::Arc;
+
+/// Target bookkeeping overhead per string, excluding the string body
+pub const MAX_STRING_OVERHEAD: usize = 1024;
+
+/// Backing store for evicted strings
+///
+/// Implemented by `rope-storage`; values are canonical string encodings.
+pub trait StringStore: Send + Sync {
+    fn put(&self, id: &StringId, bytes: Vec<u8>) -> Result<()>;
+    fn get(&self, id: &StringId) -> Result<Option<Vec<u8>>>;
+    fn delete(&self, id: &StringId) -> Result<()>;
+}
+
+/// Capacity limits for resident strings
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CacheConfig {
+    /// Maximum number of resident strings
+    pub max_strings: usize,
+    /// Maximum estimated bytes of resident string bodies
+    pub max_bytes: usize,
+}
+
+impl CacheConfig {
+    /// No limits; nothing is ever evicted
+    pub fn unbounded() -> Self {
+        Self {
+            max_strings: usize::MAX,
+            max_bytes: usize::MAX,
+        }
+    }
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            max_strings: 100_000,
+            max_bytes: 512 * 1024 * 1024,
+        }
+    }
+}
+
+/// Cache counters and memory accounting
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CacheStats {
+    pub resident_strings: usize,
+    /// Strings held only in the backing store
+    pub evicted_strings: usize,
+    /// Estimated bytes of resident string bodies
+    pub resident_bytes: usize,
+    /// Estimated bookkeeping bytes for all known strings
+    pub index_bytes: usize,
+    pub hits: u64,
+    pub misses: u64,
+    pub evictions: u64,
+    pub fault_ins: u64,
+}
+
+impl CacheStats {
+    /// Average bookkeeping bytes per known string
+    pub fn overhead_per_string(&self) -> usize {
+        let total = self.resident_strings + self.evicted_strings;
+        self.index_bytes.checked_div(total).unwrap_or(0)
+    }
+}
+
+/// Estimated heap and inline size of a string body
+pub fn estimated_size(string: &RopeString) -> usize {
+    size_of::<RopeString>()
+        + string.sequence().len() * size_of::<Nucleotide>()
+        + size_of_val(string.parentage())
+        + size_of_val(string.temporal_marker().causal_parents())
+        + string.signature().ed25519_sig.len()
+        + string.signature().dilithium_sig.len()
+        + string.oes_proof().merkle_proof.len() * 32
+        + string.oes_proof().signature.len()
+        + string.creator().dilithium.len()
+}
+
+/// Bookkeeping per resident string: map slot, LRU slot and entry header
+const RESIDENT_ENTRY_OVERHEAD: usize =
+    size_of::<(StringId, CacheEntry)>() + size_of::<(u64, StringId)>() + 16;
+
+/// Bookkeeping per evicted string: one set slot
+const EVICTED_ENTRY_OVERHEAD: usize = size_of::<StringId>() + 8;
+
+struct CacheEntry {
+    string: RopeString,
+    size: usize,
+    last_used: u64,
+    /// Backing store already holds this exact version
+    persisted: bool,
+}
+
+/// LRU cache of string bodies
+pub struct StringCache {
+    config: CacheConfig,
+    store: Option<Arc<dyn StringStore>>,
+    resident: HashMap<StringId, CacheEntry>,
+    /// Access tick -> string, oldest first
+    lru: BTreeMap<u64, StringId>,
+    /// Strings only in the backing store
+    evicted: HashSet<StringId>,
+    tick: u64,
+    stats: CacheStats,
+}
+
+impl StringCache {
+    /// Cache that never evicts
+    pub fn unbounded() -> Self {
+        Self {
+            config: CacheConfig::unbounded(),
+            store: None,
+            resident: HashMap::new(),
+            lru: BTreeMap::new(),
+            evicted: HashSet::new(),
+            tick: 0,
+            stats: CacheStats::default(),
+        }
+    }
+
+    /// Bounded cache evicting to `store`
+    pub fn new(config: CacheConfig, store: Arc<dyn StringStore>) -> Self {
+        Self {
+            config,
+            store: Some(store),
+            ..Self::unbounded()
+        }
+    }
+
+    pub fn config(&self) -> CacheConfig {
+        self.config
+    }
+
+    /// Number of known strings, resident or evicted
+    pub fn len(&self) -> usize {
+        self.resident.len() + self.evicted.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn contains(&self, id: &StringId) -> bool {
+        self.resident.contains_key(id) || self.evicted.contains(id)
+    }
+
+    /// IDs of all known strings, resident or evicted
+    pub fn ids(&self) -> impl Iterator<Item = &StringId> {
+        self.resident.keys().chain(self.evicted.iter())
+    }
+
+    pub fn is_resident(&self, id: &StringId) -> bool {
+        self.resident.contains_key(id)
+    }
+
+    fn touch(&mut self) -> u64 {
+        self.tick += 1;
+        self.tick
+    }
+
+    /// Insert or replace a string, then evict down to capacity
+    ///
+    /// Eviction is best effort: if the backing store fails the string stays
+    /// resident and the cache runs over capacity until the next insert.
+    pub fn insert(&mut self, id: StringId, string: RopeString) {
+        let last_used = self.touch();
+        let size = estimated_size(&string);
+        if let Some(old) = self.resident.remove(&id) {
+            self.lru.remove(&old.last_used);
+            self.stats.resident_bytes -= old.size;
+        }
+        self.evicted.remove(&id);
+
+        self.resident.insert(
+            id,
+            CacheEntry {
+                string,
+                size,
+                last_used,
+                persisted: false,
+            },
+        );
+        self.lru.insert(last_used, id);
+        self.stats.resident_bytes += size;
+
+        if let Err(e) = self.evict_to_capacity() {
+            tracing::warn!("string cache eviction failed: {}", e);
+        }
+    }
+
+    /// Get a string, faulting it in from the backing store if evicted
+    pub fn get(&mut self, id: &StringId) -> Result<Option<RopeString>> {
+        let last_used = self.touch();
+        if let Some(entry) = self.resident.get_mut(id) {
+            self.lru.remove(&entry.last_used);
+            entry.last_used = last_used;
+            self.lru.insert(last_used, *id);
+            self.stats.hits += 1;
+            return Ok(Some(entry.string.clone()));
+        }
+
+        if !self.evicted.contains(id) {
+            return Ok(None);
+        }
+        self.stats.misses += 1;
+
+        let store = self
+            .store
+            .as_ref()
+            .ok_or_else(|| RopeError::StorageError("no backing store".to_string()))?;
+        let bytes = store.get(id)?.ok_or(RopeError::StringNotFound(*id))?;
+        let string = RopeString::from_canonical_bytes(&bytes)?;
+        if string.id() != *id {
+            return Err(RopeError::StorageError(format!(
+                "stored string does not match id {}",
+                id
+            )));
+        }
+
+        self.evicted.remove(id);
+        let size = estimated_size(&string);
+        self.resident.insert(
+            *id,
+            CacheEntry {
+                string: string.clone(),
+                size,
+                last_used,
+                persisted: true,
+            },
+        );
+        self.lru.insert(last_used, *id);
+        self.stats.resident_bytes += size;
+        self.stats.fault_ins += 1;
+
+        if let Err(e) = self.evict_to_capacity() {
+            tracing::warn!("string cache eviction failed: {}", e);
+        }
+        Ok(Some(string))
+    }
+
+    /// Remove a string from memory and the backing store
+    pub fn remove(&mut self, id: &StringId) -> Result<bool> {
+        let was_resident = match self.resident.remove(id) {
+            Some(entry) => {
+                self.lru.remove(&entry.last_used);
+                self.stats.resident_bytes -= entry.size;
+                true
+            }
+            None => false,
+        };
+        let was_evicted = self.evicted.remove(id);
+
+        if let Some(store) = &self.store {
+            store.delete(id)?;
+        }
+        Ok(was_resident || was_evicted)
+    }
+
+    fn over_capacity(&self) -> bool {
+        self.resident.len() > self.config.max_strings
+            || self.stats.resident_bytes > self.config.max_bytes
+    }
+
+    /// Evict least recently used strings until within limits
+    fn evict_to_capacity(&mut self) -> Result<()> {
+        let Some(store) = self.store.clone() else {
+            return Ok(());
+        };
+
+        // Never evict the most recent entry: it is the one being used
+        while self.over_capacity() && self.resident.len() > 1 {
+            let Some((&last_used, &id)) = self.lru.iter().next() else {
+                break;
+            };
+            let entry = &self.resident[&id];
+            if !entry.persisted {
+                store.put(&id, entry.string.to_canonical_bytes())?;
+            }
+
+            self.lru.remove(&last_used);
+            if let Some(entry) = self.resident.remove(&id) {
+                self.stats.resident_bytes -= entry.size;
+            }
+            self.evicted.insert(id);
+            self.stats.evictions += 1;
+        }
+        Ok(())
+    }
+
+    /// Counters and memory accounting
+    pub fn stats(&self) -> CacheStats {
+        CacheStats {
+            resident_strings: self.resident.len(),
+            evicted_strings: self.evicted.len(),
+            index_bytes: self.resident.len() * RESIDENT_ENTRY_OVERHEAD
+                + self.evicted.len() * EVICTED_ENTRY_OVERHEAD,
+            ..self.stats.clone()
+        }
+    }
+}
+
+impl Default for StringCache {
+    fn default() -> Self {
+        Self::unbounded()
+    }
+}
+
+/// In-memory backing store for tests
+#[cfg(test)]
+#[derive(Default)]
+pub(crate) struct MemoryStringStore {
+    pub(crate) data: parking_lot::RwLock<HashMap<StringId, Vec<u8>>>,
+}
+
+#[cfg(test)]
+impl StringStore for MemoryStringStore {
+    fn put(&self, id: &StringId, bytes: Vec<u8>) -> Result<()> {
+        self.data.write().insert(*id, bytes);
+        Ok(())
+    }
+
+    fn get(&self, id: &StringId) -> Result<Option<Vec<u8>>> {
+        Ok(self.data.read().get(id).cloned())
+    }
+
+    fn delete(&self, id: &StringId) -> Result<()> {
+        self.data.write().remove(id);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::LamportClock;
+    use crate::string::PublicKey;
+    use crate::types::NodeId;
+
+    fn make_string(n: u32) -> RopeString {
+        RopeString::builder()
+            .content(n.to_be_bytes().to_vec())
+            .temporal_marker(LamportClock::new(NodeId::new([0u8; 32])))
+            .creator(PublicKey::from_ed25519([0u8; 32]))
+            .build()
+            .unwrap()
+    }
+
+    fn bounded(max_strings: usize) -> (StringCache, Arc<MemoryStringStore>) {
+        let store = Arc::new(MemoryStringStore::default());
+        let config = CacheConfig {
+            max_strings,
+            max_bytes: usize::MAX,
+        };
+        (StringCache::new(config, store.clone()), store)
+    }
+
+    #[test]
+    fn test_unbounded_never_evicts() {
+        let mut cache = StringCache::unbounded();
+        for n in 0..100 {
+            let string = make_string(n);
+            cache.insert(string.id(), string);
+        }
+        assert_eq!(cache.stats().resident_strings, 100);
+        assert_eq!(cache.stats().evictions, 0);
+    }
+
+    #[test]
+    fn test_lru_eviction_and_fault_in() {
+        let (mut cache, store) = bounded(2);
+        let strings: Vec<_> = (0..3).map(make_string).collect();
+
+        cache.insert(strings[0].id(), strings[0].clone());
+        cache.insert(strings[1].id(), strings[1].clone());
+        // Touch 0 so 1 becomes least recently used
+        cache.get(&strings[0].id()).unwrap();
+        cache.insert(strings[2].id(), strings[2].clone());
+
+        assert!(!cache.is_resident(&strings[1].id()));
+        assert!(cache.contains(&strings[1].id()));
+        assert!(store.data.read().contains_key(&strings[1].id()));
+        assert_eq!(cache.len(), 3);
+
+        // Transparent fault-in evicts the next LRU entry (0)
+        assert_eq!(
+            cache.get(&strings[1].id()).unwrap(),
+            Some(strings[1].clone())
+        );
+        assert!(cache.is_resident(&strings[1].id()));
+        assert!(!cache.is_resident(&strings[0].id()));
+
+        let stats = cache.stats();
+        assert_eq!(stats.resident_strings, 2);
+        assert_eq!(stats.evicted_strings, 1);
+        assert_eq!(stats.fault_ins, 1);
+        assert_eq!(stats.evictions, 2);
+    }
+
+    #[test]
+    fn test_byte_limit() {
+        let store = Arc::new(MemoryStringStore::default());
+        let one = estimated_size(&make_string(0));
+        let config = CacheConfig {
+            max_strings: usize::MAX,
+            max_bytes: one * 3,
+        };
+        let mut cache = StringCache::new(config, store);
+        for n in 0..10 {
+            let string = make_string(n);
+            cache.insert(string.id(), string);
+        }
+        assert!(cache.stats().resident_bytes <= one * 3);
+        assert_eq!(cache.len(), 10);
+    }
+
+    #[test]
+    fn test_remove_deletes_from_store() {
+        let (mut cache, store) = bounded(1);
+        let (a, b) = (make_string(1), make_string(2));
+        cache.insert(a.id(), a.clone());
+        cache.insert(b.id(), b.clone());
+        assert!(store.data.read().contains_key(&a.id()));
+
+        assert!(cache.remove(&a.id()).unwrap());
+        assert!(!cache.contains(&a.id()));
+        assert!(!store.data.read().contains_key(&a.id()));
+        assert_eq!(cache.get(&a.id()).unwrap(), None);
+    }
+
+    #[test]
+    fn test_overhead_per_string_within_target() {
+        let (mut cache, _) = bounded(1_000);
+        for n in 0..10_000 {
+            let string = make_string(n);
+            cache.insert(string.id(), string);
+        }
+        let stats = cache.stats();
+        assert_eq!(stats.evicted_strings, 9_000);
+        assert!(stats.overhead_per_string() < MAX_STRING_OVERHEAD);
+    }
+}
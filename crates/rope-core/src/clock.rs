@@ -4,10 +4,16 @@
 //!
 //! Unlike synchronized wall clocks, Lamport clocks provide a logical ordering
 //! that respects causality: if event A caused event B, then clock(A) < clock(B).
+//!
+//! Lamport time alone cannot tell concurrent events apart, so each federation
+//! also keeps a [`VectorClock`] merged from every string it inserts.
+//! [`FederationClocks`] uses those vectors to annotate concurrent updates to
+//! the same logical entity; resolving them is left to higher layers.
 
-use crate::types::NodeId;
+use crate::types::{NodeId, StringId};
 use serde::{Deserialize, Serialize};
 use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap};
 
 /// Extended Lamport Clock with causal parent tracking
 ///
@@ -45,6 +51,19 @@ impl LamportClock {
         }
     }
 
+    /// Rebuild a clock including causal parents (canonical decoding)
+    pub(crate) fn from_parts(
+        logical_time: u64,
+        node_id: NodeId,
+        causal_parents: Vec<(NodeId, u64)>,
+    ) -> Self {
+        Self {
+            logical_time,
+            node_id,
+            causal_parents,
+        }
+    }
+
     /// Get current logical time
     pub fn time(&self) -> u64 {
         self.logical_time
@@ -197,6 +216,254 @@ impl ClockManager {
     }
 }
 
+/// Federation identifier (matches the 32-byte ids used by federation crates)
+pub type FederationId = [u8; 32];
+
+/// Causal relation between two vector clocks
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CausalOrder {
+    Before,
+    After,
+    Equal,
+    Concurrent,
+}
+
+/// Vector clock: highest logical time observed per node
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct VectorClock {
+    entries: BTreeMap<NodeId, u64>,
+}
+
+impl VectorClock {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Causal context of a Lamport timestamp: its own event plus its parents
+    pub fn from_lamport(clock: &LamportClock) -> Self {
+        let mut vector = Self::new();
+        vector.observe(clock);
+        vector
+    }
+
+    /// Highest time observed for a node (0 if never seen)
+    pub fn get(&self, node: &NodeId) -> u64 {
+        self.entries.get(node).copied().unwrap_or(0)
+    }
+
+    pub fn entries(&self) -> impl Iterator<Item = (&NodeId, &u64)> {
+        self.entries.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Advance a node's entry for a local event
+    pub fn increment(&mut self, node: NodeId) -> u64 {
+        let entry = self.entries.entry(node).or_insert(0);
+        *entry += 1;
+        *entry
+    }
+
+    /// Fold a Lamport timestamp and its causal parents into the vector
+    pub fn observe(&mut self, clock: &LamportClock) {
+        self.bump(clock.node_id, clock.logical_time);
+        for (node, time) in &clock.causal_parents {
+            self.bump(*node, *time);
+        }
+    }
+
+    /// Pointwise maximum with another vector
+    pub fn merge(&mut self, other: &VectorClock) {
+        for (node, time) in &other.entries {
+            self.bump(*node, *time);
+        }
+    }
+
+    fn bump(&mut self, node: NodeId, time: u64) {
+        let entry = self.entries.entry(node).or_insert(0);
+        *entry = (*entry).max(time);
+    }
+
+    /// Compare causally with another vector
+    pub fn compare(&self, other: &VectorClock) -> CausalOrder {
+        let mut less = false;
+        let mut greater = false;
+        for node in self.entries.keys().chain(other.entries.keys()) {
+            match self.get(node).cmp(&other.get(node)) {
+                Ordering::Less => less = true,
+                Ordering::Greater => greater = true,
+                Ordering::Equal => {}
+            }
+        }
+        match (less, greater) {
+            (false, false) => CausalOrder::Equal,
+            (true, false) => CausalOrder::Before,
+            (false, true) => CausalOrder::After,
+            (true, true) => CausalOrder::Concurrent,
+        }
+    }
+
+    /// True if every entry is at least the other's
+    pub fn dominates(&self, other: &VectorClock) -> bool {
+        matches!(self.compare(other), CausalOrder::After | CausalOrder::Equal)
+    }
+
+    pub fn is_concurrent(&self, other: &VectorClock) -> bool {
+        self.compare(other) == CausalOrder::Concurrent
+    }
+}
+
+/// How an update relates to what is already known about its entity
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ConflictAnnotation {
+    /// Update causally follows every known update to the entity
+    Sequential,
+    /// Update is concurrent with these heads; a conflict policy must pick
+    Concurrent { heads: Vec<StringId> },
+    /// Update arrived late and is already superseded by these heads
+    Superseded { by: Vec<StringId> },
+}
+
+impl ConflictAnnotation {
+    pub fn is_conflict(&self) -> bool {
+        matches!(self, Self::Concurrent { .. })
+    }
+}
+
+/// A recorded update: its event (node, time) and the context it was made in
+#[derive(Clone, Debug)]
+struct EntityUpdate {
+    string_id: StringId,
+    node: NodeId,
+    time: u64,
+    context: VectorClock,
+}
+
+impl EntityUpdate {
+    /// `self` is in the causal past of `later`
+    fn precedes(&self, later: &EntityUpdate) -> bool {
+        self.string_id != later.string_id && later.context.get(&self.node) >= self.time
+    }
+}
+
+#[derive(Default)]
+struct FederationState {
+    clock: VectorClock,
+    /// Causally maximal updates per logical entity
+    heads: HashMap<Vec<u8>, Vec<EntityUpdate>>,
+}
+
+/// Per-federation vector clocks with concurrent-update detection
+///
+/// Every inserted string's timestamp is merged into its federation's vector.
+/// Strings that update a logical entity also keep that entity's set of heads
+/// (its causally maximal updates) current; more than one head means
+/// unresolved concurrent writes.
+#[derive(Default)]
+pub struct FederationClocks {
+    federations: parking_lot::RwLock<HashMap<FederationId, FederationState>>,
+}
+
+impl FederationClocks {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Merge a string's timestamp into the federation vector
+    pub fn merge(&self, federation: FederationId, clock: &LamportClock) -> VectorClock {
+        let mut federations = self.federations.write();
+        let state = federations.entry(federation).or_default();
+        state.clock.observe(clock);
+        state.clock.clone()
+    }
+
+    /// Current vector clock of a federation
+    pub fn clock(&self, federation: &FederationId) -> Option<VectorClock> {
+        self.federations
+            .read()
+            .get(federation)
+            .map(|state| state.clock.clone())
+    }
+
+    /// Record an inserted string that updates a logical entity
+    ///
+    /// Merges the timestamp into the federation vector and annotates how the
+    /// update relates to the entity's existing heads. Recording the same
+    /// string twice is `Sequential` and changes nothing.
+    pub fn record_update(
+        &self,
+        federation: FederationId,
+        entity: &[u8],
+        string_id: StringId,
+        clock: &LamportClock,
+    ) -> ConflictAnnotation {
+        let mut federations = self.federations.write();
+        let state = federations.entry(federation).or_default();
+        state.clock.observe(clock);
+
+        let update = EntityUpdate {
+            string_id,
+            node: clock.node_id,
+            time: clock.logical_time,
+            context: VectorClock::from_lamport(clock),
+        };
+
+        let heads = state.heads.entry(entity.to_vec()).or_default();
+        if heads.iter().any(|head| head.string_id == string_id) {
+            return ConflictAnnotation::Sequential;
+        }
+
+        let superseded_by: Vec<StringId> = heads
+            .iter()
+            .filter(|head| update.precedes(head))
+            .map(|head| head.string_id)
+            .collect();
+        if !superseded_by.is_empty() {
+            return ConflictAnnotation::Superseded { by: superseded_by };
+        }
+
+        heads.retain(|head| !head.precedes(&update));
+        let concurrent: Vec<StringId> = heads.iter().map(|head| head.string_id).collect();
+        heads.push(update);
+
+        if concurrent.is_empty() {
+            ConflictAnnotation::Sequential
+        } else {
+            ConflictAnnotation::Concurrent { heads: concurrent }
+        }
+    }
+
+    /// Current heads of an entity; more than one means an open conflict
+    pub fn heads(&self, federation: &FederationId, entity: &[u8]) -> Vec<StringId> {
+        self.federations
+            .read()
+            .get(federation)
+            .and_then(|state| state.heads.get(entity))
+            .map(|heads| heads.iter().map(|head| head.string_id).collect())
+            .unwrap_or_default()
+    }
+
+    /// Collapse an entity's heads to the update chosen by a conflict policy
+    ///
+    /// Returns false if `winner` is not a current head.
+    pub fn resolve(&self, federation: &FederationId, entity: &[u8], winner: &StringId) -> bool {
+        let mut federations = self.federations.write();
+        let Some(heads) = federations
+            .get_mut(federation)
+            .and_then(|state| state.heads.get_mut(entity))
+        else {
+            return false;
+        };
+        if !heads.iter().any(|head| head.string_id == *winner) {
+            return false;
+        }
+        heads.retain(|head| head.string_id == *winner);
+        true
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -256,4 +523,109 @@ mod tests {
         assert!(t1 < t2);
         assert!(t2 < t3);
     }
+
+    #[test]
+    fn test_vector_clock_compare_and_merge() {
+        let (a, b) = (make_node_id(1), make_node_id(2));
+        let mut va = VectorClock::new();
+        let mut vb = VectorClock::new();
+        assert_eq!(va.compare(&vb), CausalOrder::Equal);
+
+        va.increment(a);
+        assert_eq!(vb.compare(&va), CausalOrder::Before);
+        assert_eq!(va.compare(&vb), CausalOrder::After);
+
+        vb.increment(b);
+        assert!(va.is_concurrent(&vb));
+
+        vb.merge(&va);
+        assert_eq!(vb.get(&a), 1);
+        assert!(vb.dominates(&va));
+        assert!(!va.dominates(&vb));
+    }
+
+    #[test]
+    fn test_vector_from_lamport_includes_parents() {
+        let mut clock_a = LamportClock::new(make_node_id(1));
+        let mut clock_b = LamportClock::new(make_node_id(2));
+        clock_a.increment();
+        clock_b.observe(&clock_a);
+
+        let vector = VectorClock::from_lamport(&clock_b);
+        assert_eq!(vector.get(&make_node_id(1)), 1);
+        assert_eq!(vector.get(&make_node_id(2)), 2);
+    }
+
+    #[test]
+    fn test_federation_clocks_merge() {
+        let clocks = FederationClocks::new();
+        let fed = [7u8; 32];
+        assert!(clocks.clock(&fed).is_none());
+
+        let mut clock_a = LamportClock::new(make_node_id(1));
+        clock_a.increment();
+        let mut clock_b = LamportClock::new(make_node_id(2));
+        clock_b.increment();
+        clock_b.increment();
+
+        clocks.merge(fed, &clock_a);
+        let merged = clocks.merge(fed, &clock_b);
+        assert_eq!(merged.get(&make_node_id(1)), 1);
+        assert_eq!(merged.get(&make_node_id(2)), 2);
+        assert!(clocks.clock(&[8u8; 32]).is_none());
+    }
+
+    #[test]
+    fn test_concurrent_updates_annotated() {
+        let clocks = FederationClocks::new();
+        let fed = [1u8; 32];
+        let entity = b"account/42";
+        let ids: Vec<StringId> = (0u8..4).map(|n| StringId::from_content(&[n])).collect();
+
+        // A writes, then B writes having seen A: sequential
+        let mut clock_a = LamportClock::new(make_node_id(1));
+        clock_a.increment();
+        assert_eq!(
+            clocks.record_update(fed, entity, ids[0], &clock_a),
+            ConflictAnnotation::Sequential
+        );
+        let mut clock_b = LamportClock::new(make_node_id(2));
+        clock_b.observe(&clock_a);
+        assert_eq!(
+            clocks.record_update(fed, entity, ids[1], &clock_b),
+            ConflictAnnotation::Sequential
+        );
+        assert_eq!(clocks.heads(&fed, entity), vec![ids[1]]);
+
+        // C writes without having seen B: concurrent
+        let mut clock_c = LamportClock::new(make_node_id(3));
+        clock_c.observe(&clock_a);
+        let annotation = clocks.record_update(fed, entity, ids[2], &clock_c);
+        assert!(annotation.is_conflict());
+        assert_eq!(
+            annotation,
+            ConflictAnnotation::Concurrent {
+                heads: vec![ids[1]]
+            }
+        );
+        assert_eq!(clocks.heads(&fed, entity).len(), 2);
+
+        // A late copy of A's write is superseded; replays are no-ops
+        assert!(matches!(
+            clocks.record_update(fed, entity, ids[3], &clock_a),
+            ConflictAnnotation::Superseded { .. }
+        ));
+        assert_eq!(
+            clocks.record_update(fed, entity, ids[2], &clock_c),
+            ConflictAnnotation::Sequential
+        );
+
+        // Policy picks C
+        assert!(!clocks.resolve(&fed, entity, &ids[0]));
+        assert!(clocks.resolve(&fed, entity, &ids[2]));
+        assert_eq!(clocks.heads(&fed, entity), vec![ids[2]]);
+
+        // Other entities are independent
+        assert!(clocks.heads(&fed, b"account/43").is_empty());
+    }
 }
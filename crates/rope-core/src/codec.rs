@@ -0,0 +1,678 @@
+//! Canonical wire encoding for core types
+//!
+//! A deterministic, versioned binary format that hashes and signatures can be
+//! computed over, independent of serde or bincode versions:
+//!
+//! - Every message is `[version][type tag][body]`
+//! - Fields are written in declaration order
+//! - Integers and lengths are minimal unsigned LEB128 varints; signed
+//!   integers are zigzag-encoded first
+//! - Fixed-size arrays (hashes, keys) are written raw, variable-size bytes
+//!   are length-prefixed
+//! - Derived data (string IDs, nucleotide parity) is not encoded and is
+//!   recomputed on decode
+//!
+//! Decoding is strict: non-minimal varints, unknown tags and trailing bytes
+//! are rejected, so each value has exactly one valid encoding.
+
+use crate::clock::LamportClock;
+use crate::complement::{EntanglementProof, RegenerationHint, RelationshipType};
+use crate::error::{Result, RopeError};
+use crate::lattice::AnchorString;
+use crate::nucleotide::Nucleotide;
+use crate::string::{HybridSignature, OESProof, PublicKey, RopeString};
+use crate::types::{ErasureCondition, MutabilityClass, NodeId, StringId};
+use std::time::Duration;
+
+/// Current wire format version
+pub const WIRE_VERSION: u8 = 1;
+
+/// Type tags identifying the encoded type
+pub mod tags {
+    pub const NUCLEOTIDE: u8 = 0x01;
+    pub const ROPE_STRING: u8 = 0x02;
+    pub const COMPLEMENT: u8 = 0x03;
+    pub const ANCHOR_STRING: u8 = 0x04;
+    pub const INCLUSION_PROOF: u8 = 0x05;
+    pub const SYNC_FILTER: u8 = 0x06;
+}
+
+/// Types with a canonical wire encoding
+pub trait CanonicalEncoding: Sized {
+    /// Type tag written after the version byte
+    const TAG: u8;
+
+    /// Write the body (without version and tag)
+    fn encode_body(&self, enc: &mut Encoder);
+
+    /// Read the body (without version and tag)
+    fn decode_body(dec: &mut Decoder<'_>) -> Result<Self>;
+
+    /// Canonical encoding including version and tag
+    fn to_canonical_bytes(&self) -> Vec<u8> {
+        let mut enc = Encoder::new();
+        enc.put_u8(WIRE_VERSION);
+        enc.put_u8(Self::TAG);
+        self.encode_body(&mut enc);
+        enc.finish()
+    }
+
+    /// Decode a canonical encoding, rejecting trailing bytes
+    fn from_canonical_bytes(bytes: &[u8]) -> Result<Self> {
+        let mut dec = Decoder::new(bytes);
+        let version = dec.u8()?;
+        if version != WIRE_VERSION {
+            return Err(codec_error(format!("unsupported wire version {}", version)));
+        }
+        let tag = dec.u8()?;
+        if tag != Self::TAG {
+            return Err(codec_error(format!(
+                "expected type tag {:#04x}, found {:#04x}",
+                Self::TAG,
+                tag
+            )));
+        }
+        let value = Self::decode_body(&mut dec)?;
+        dec.finish()?;
+        Ok(value)
+    }
+
+    /// BLAKE3 hash of the canonical encoding
+    fn canonical_hash(&self) -> [u8; 32] {
+        *blake3::hash(&self.to_canonical_bytes()).as_bytes()
+    }
+}
+
+/// Nested values that appear inside canonical encodings
+pub(crate) trait WireField: Sized {
+    fn encode_field(&self, enc: &mut Encoder);
+    fn decode_field(dec: &mut Decoder<'_>) -> Result<Self>;
+}
+
+fn codec_error(message: impl Into<String>) -> RopeError {
+    RopeError::SerializationError(message.into())
+}
+
+/// Canonical encoding writer
+#[derive(Default)]
+pub struct Encoder {
+    buf: Vec<u8>,
+}
+
+impl Encoder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn put_u8(&mut self, value: u8) {
+        self.buf.push(value);
+    }
+
+    pub fn put_bool(&mut self, value: bool) {
+        self.buf.push(value as u8);
+    }
+
+    /// Unsigned LEB128
+    pub fn put_varint(&mut self, mut value: u64) {
+        loop {
+            let byte = (value & 0x7f) as u8;
+            value >>= 7;
+            if value == 0 {
+                self.buf.push(byte);
+                return;
+            }
+            self.buf.push(byte | 0x80);
+        }
+    }
+
+    /// Zigzag-encoded signed varint
+    pub fn put_signed(&mut self, value: i64) {
+        self.put_varint(((value << 1) ^ (value >> 63)) as u64);
+    }
+
+    /// Length-prefixed bytes
+    pub fn put_bytes(&mut self, bytes: &[u8]) {
+        self.put_varint(bytes.len() as u64);
+        self.buf.extend_from_slice(bytes);
+    }
+
+    /// Raw bytes of a fixed-size field
+    pub fn put_fixed(&mut self, bytes: &[u8]) {
+        self.buf.extend_from_slice(bytes);
+    }
+
+    pub fn put_str(&mut self, value: &str) {
+        self.put_bytes(value.as_bytes());
+    }
+
+    pub fn finish(self) -> Vec<u8> {
+        self.buf
+    }
+}
+
+/// Canonical encoding reader
+pub struct Decoder<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Decoder<'a> {
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, pos: 0 }
+    }
+
+    /// Bytes not yet consumed
+    pub fn remaining(&self) -> usize {
+        self.bytes.len() - self.pos
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8]> {
+        if len > self.remaining() {
+            return Err(codec_error(format!(
+                "unexpected end of input at byte {}",
+                self.pos
+            )));
+        }
+        let slice = &self.bytes[self.pos..self.pos + len];
+        self.pos += len;
+        Ok(slice)
+    }
+
+    pub fn u8(&mut self) -> Result<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    pub fn bool(&mut self) -> Result<bool> {
+        match self.u8()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            other => Err(codec_error(format!("invalid bool byte {}", other))),
+        }
+    }
+
+    /// Unsigned LEB128, rejecting overlong and non-minimal encodings
+    pub fn varint(&mut self) -> Result<u64> {
+        let mut value = 0u64;
+        for i in 0..10 {
+            let byte = self.u8()?;
+            let bits = (byte & 0x7f) as u64;
+            if i == 9 && bits > 1 {
+                return Err(codec_error("varint overflows u64"));
+            }
+            value |= bits << (7 * i);
+            if byte & 0x80 == 0 {
+                if i > 0 && byte == 0 {
+                    return Err(codec_error("non-minimal varint"));
+                }
+                return Ok(value);
+            }
+        }
+        Err(codec_error("varint too long"))
+    }
+
+    pub fn varint_u32(&mut self) -> Result<u32> {
+        u32::try_from(self.varint()?).map_err(|_| codec_error("varint overflows u32"))
+    }
+
+    pub fn signed(&mut self) -> Result<i64> {
+        let raw = self.varint()?;
+        Ok((raw >> 1) as i64 ^ -((raw & 1) as i64))
+    }
+
+    /// Element count, checked against the bytes left so hostile lengths
+    /// cannot force large allocations
+    pub fn count(&mut self, min_element_size: usize) -> Result<usize> {
+        let count = self.varint()?;
+        let needed = count.saturating_mul(min_element_size.max(1) as u64);
+        if needed > self.remaining() as u64 {
+            return Err(codec_error(format!(
+                "length {} exceeds remaining input",
+                count
+            )));
+        }
+        Ok(count as usize)
+    }
+
+    /// Length-prefixed bytes
+    pub fn bytes(&mut self) -> Result<&'a [u8]> {
+        let len = self.count(1)?;
+        self.take(len)
+    }
+
+    pub fn fixed<const N: usize>(&mut self) -> Result<[u8; N]> {
+        let mut out = [0u8; N];
+        out.copy_from_slice(self.take(N)?);
+        Ok(out)
+    }
+
+    pub fn string(&mut self) -> Result<String> {
+        String::from_utf8(self.bytes()?.to_vec()).map_err(|_| codec_error("invalid UTF-8"))
+    }
+
+    /// Fail if any input is left over
+    pub fn finish(&self) -> Result<()> {
+        if self.remaining() != 0 {
+            return Err(codec_error(format!("{} trailing bytes", self.remaining())));
+        }
+        Ok(())
+    }
+}
+
+// === Nested fields ===
+
+impl WireField for StringId {
+    fn encode_field(&self, enc: &mut Encoder) {
+        enc.put_fixed(self.as_bytes());
+    }
+
+    fn decode_field(dec: &mut Decoder<'_>) -> Result<Self> {
+        Ok(StringId::new(dec.fixed()?))
+    }
+}
+
+impl WireField for NodeId {
+    fn encode_field(&self, enc: &mut Encoder) {
+        enc.put_fixed(self.as_bytes());
+    }
+
+    fn decode_field(dec: &mut Decoder<'_>) -> Result<Self> {
+        Ok(NodeId::new(dec.fixed()?))
+    }
+}
+
+impl<T: WireField> WireField for Vec<T> {
+    fn encode_field(&self, enc: &mut Encoder) {
+        enc.put_varint(self.len() as u64);
+        for item in self {
+            item.encode_field(enc);
+        }
+    }
+
+    fn decode_field(dec: &mut Decoder<'_>) -> Result<Self> {
+        let count = dec.count(1)?;
+        (0..count).map(|_| T::decode_field(dec)).collect()
+    }
+}
+
+impl WireField for LamportClock {
+    fn encode_field(&self, enc: &mut Encoder) {
+        enc.put_varint(self.time());
+        self.node_id().encode_field(enc);
+        enc.put_varint(self.causal_parents().len() as u64);
+        for (node, time) in self.causal_parents() {
+            node.encode_field(enc);
+            enc.put_varint(*time);
+        }
+    }
+
+    fn decode_field(dec: &mut Decoder<'_>) -> Result<Self> {
+        let logical_time = dec.varint()?;
+        let node_id = NodeId::decode_field(dec)?;
+        let count = dec.count(33)?;
+        let causal_parents = (0..count)
+            .map(|_| Ok((NodeId::decode_field(dec)?, dec.varint()?)))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(LamportClock::from_parts(
+            logical_time,
+            node_id,
+            causal_parents,
+        ))
+    }
+}
+
+impl WireField for MutabilityClass {
+    fn encode_field(&self, enc: &mut Encoder) {
+        match self {
+            MutabilityClass::Immutable => enc.put_u8(0),
+            MutabilityClass::OwnerErasable => enc.put_u8(1),
+            MutabilityClass::TimeBound(duration) => {
+                enc.put_u8(2);
+                enc.put_varint(duration.as_secs());
+                enc.put_varint(duration.subsec_nanos() as u64);
+            }
+            MutabilityClass::ConditionalErasure(condition) => {
+                enc.put_u8(3);
+                match condition {
+                    ErasureCondition::AfterReferences(count) => {
+                        enc.put_u8(0);
+                        enc.put_varint(*count);
+                    }
+                    ErasureCondition::AfterTimestamp(timestamp) => {
+                        enc.put_u8(1);
+                        enc.put_signed(*timestamp);
+                    }
+                    ErasureCondition::OnEvent(event) => {
+                        enc.put_u8(2);
+                        enc.put_str(event);
+                    }
+                    ErasureCondition::Custom(script) => {
+                        enc.put_u8(3);
+                        enc.put_bytes(script);
+                    }
+                }
+            }
+            MutabilityClass::GDPRCompliant => enc.put_u8(4),
+        }
+    }
+
+    fn decode_field(dec: &mut Decoder<'_>) -> Result<Self> {
+        Ok(match dec.u8()? {
+            0 => MutabilityClass::Immutable,
+            1 => MutabilityClass::OwnerErasable,
+            2 => {
+                let secs = dec.varint()?;
+                let nanos = dec.varint_u32()?;
+                if nanos >= 1_000_000_000 {
+                    return Err(codec_error("duration nanos out of range"));
+                }
+                MutabilityClass::TimeBound(Duration::new(secs, nanos))
+            }
+            3 => MutabilityClass::ConditionalErasure(match dec.u8()? {
+                0 => ErasureCondition::AfterReferences(dec.varint()?),
+                1 => ErasureCondition::AfterTimestamp(dec.signed()?),
+                2 => ErasureCondition::OnEvent(dec.string()?),
+                3 => ErasureCondition::Custom(dec.bytes()?.to_vec()),
+                other => return Err(codec_error(format!("unknown erasure condition {}", other))),
+            }),
+            4 => MutabilityClass::GDPRCompliant,
+            other => return Err(codec_error(format!("unknown mutability class {}", other))),
+        })
+    }
+}
+
+impl WireField for OESProof {
+    fn encode_field(&self, enc: &mut Encoder) {
+        enc.put_varint(self.generation);
+        enc.put_fixed(&self.state_commitment);
+        enc.put_varint(self.merkle_proof.len() as u64);
+        for node in &self.merkle_proof {
+            enc.put_fixed(node);
+        }
+        enc.put_bytes(&self.signature);
+    }
+
+    fn decode_field(dec: &mut Decoder<'_>) -> Result<Self> {
+        let generation = dec.varint()?;
+        let state_commitment = dec.fixed()?;
+        let count = dec.count(32)?;
+        let merkle_proof = (0..count)
+            .map(|_| dec.fixed())
+            .collect::<Result<Vec<_>>>()?;
+        let signature = dec.bytes()?.to_vec();
+        Ok(OESProof {
+            generation,
+            state_commitment,
+            merkle_proof,
+            signature,
+        })
+    }
+}
+
+impl WireField for HybridSignature {
+    fn encode_field(&self, enc: &mut Encoder) {
+        enc.put_bytes(&self.ed25519_sig);
+        enc.put_bytes(&self.dilithium_sig);
+    }
+
+    fn decode_field(dec: &mut Decoder<'_>) -> Result<Self> {
+        Ok(HybridSignature {
+            ed25519_sig: dec.bytes()?.to_vec(),
+            dilithium_sig: dec.bytes()?.to_vec(),
+        })
+    }
+}
+
+impl WireField for PublicKey {
+    fn encode_field(&self, enc: &mut Encoder) {
+        enc.put_fixed(&self.ed25519);
+        enc.put_bytes(&self.dilithium);
+    }
+
+    fn decode_field(dec: &mut Decoder<'_>) -> Result<Self> {
+        Ok(PublicKey::new(dec.fixed()?, dec.bytes()?.to_vec()))
+    }
+}
+
+impl WireField for RegenerationHint {
+    fn encode_field(&self, enc: &mut Encoder) {
+        self.related_string_id.encode_field(enc);
+        enc.put_u8(match self.relationship {
+            RelationshipType::Parent => 0,
+            RelationshipType::Child => 1,
+            RelationshipType::Sibling => 2,
+            RelationshipType::ContentRelated => 3,
+            RelationshipType::PreviousVersion => 4,
+        });
+        enc.put_varint(self.segment_range.0);
+        enc.put_varint(self.segment_range.1);
+    }
+
+    fn decode_field(dec: &mut Decoder<'_>) -> Result<Self> {
+        let related_string_id = StringId::decode_field(dec)?;
+        let relationship = match dec.u8()? {
+            0 => RelationshipType::Parent,
+            1 => RelationshipType::Child,
+            2 => RelationshipType::Sibling,
+            3 => RelationshipType::ContentRelated,
+            4 => RelationshipType::PreviousVersion,
+            other => return Err(codec_error(format!("unknown relationship {}", other))),
+        };
+        Ok(RegenerationHint {
+            related_string_id,
+            relationship,
+            segment_range: (dec.varint()?, dec.varint()?),
+        })
+    }
+}
+
+impl WireField for EntanglementProof {
+    fn encode_field(&self, enc: &mut Encoder) {
+        enc.put_fixed(&self.binding_hash);
+        enc.put_varint(self.created_at);
+        enc.put_bytes(&self.signature);
+    }
+
+    fn decode_field(dec: &mut Decoder<'_>) -> Result<Self> {
+        Ok(EntanglementProof {
+            binding_hash: dec.fixed()?,
+            created_at: dec.varint()?,
+            signature: dec.bytes()?.to_vec(),
+        })
+    }
+}
+
+// === Top-level types ===
+
+impl CanonicalEncoding for Nucleotide {
+    const TAG: u8 = tags::NUCLEOTIDE;
+
+    fn encode_body(&self, enc: &mut Encoder) {
+        enc.put_fixed(self.value());
+        enc.put_varint(self.position());
+    }
+
+    fn decode_body(dec: &mut Decoder<'_>) -> Result<Self> {
+        let value = dec.fixed()?;
+        Ok(Nucleotide::new(value, dec.varint()?))
+    }
+}
+
+impl CanonicalEncoding for AnchorString {
+    const TAG: u8 = tags::ANCHOR_STRING;
+
+    fn encode_body(&self, enc: &mut Encoder) {
+        self.string.encode_body(enc);
+        enc.put_varint(self.round);
+        self.strongly_sees.encode_field(enc);
+        enc.put_varint(self.testimony_count as u64);
+        enc.put_bool(self.is_famous);
+    }
+
+    fn decode_body(dec: &mut Decoder<'_>) -> Result<Self> {
+        Ok(AnchorString {
+            string: RopeString::decode_body(dec)?,
+            round: dec.varint()?,
+            strongly_sees: Vec::<StringId>::decode_field(dec)?,
+            testimony_count: dec.varint_u32()?,
+            is_famous: dec.bool()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::complement::Complement;
+
+    fn golden_string() -> RopeString {
+        RopeString::builder()
+            .content(b"hello".to_vec())
+            .temporal_marker(LamportClock::with_time(3, NodeId::new([1u8; 32])))
+            .creator(PublicKey::from_ed25519([2u8; 32]))
+            .build()
+            .unwrap()
+    }
+
+    fn golden_string_body() -> String {
+        [
+            // sequence: 1 nucleotide
+            "01".to_string(),
+            format!("68656c6c6f{}", "00".repeat(27)),
+            // temporal marker: time 3, node, no causal parents
+            format!("03{}00", "01".repeat(32)),
+            // parentage, replication factor 5, OwnerErasable, OES generation 0
+            "00050100".to_string(),
+            // empty OES proof
+            format!("00{}0000", "00".repeat(32)),
+            // empty hybrid signature
+            format!("40{}00", "00".repeat(64)),
+            // creator
+            format!("{}00", "02".repeat(32)),
+        ]
+        .concat()
+    }
+
+    #[test]
+    fn test_varint_encoding() {
+        let mut enc = Encoder::new();
+        enc.put_varint(0);
+        enc.put_varint(127);
+        enc.put_varint(128);
+        enc.put_varint(300);
+        enc.put_signed(-1);
+        enc.put_varint(u64::MAX);
+        let bytes = enc.finish();
+        assert_eq!(hex::encode(&bytes[..7]), "007f8001ac0201");
+
+        let mut dec = Decoder::new(&bytes);
+        assert_eq!(dec.varint().unwrap(), 0);
+        assert_eq!(dec.varint().unwrap(), 127);
+        assert_eq!(dec.varint().unwrap(), 128);
+        assert_eq!(dec.varint().unwrap(), 300);
+        assert_eq!(dec.signed().unwrap(), -1);
+        assert_eq!(dec.varint().unwrap(), u64::MAX);
+        dec.finish().unwrap();
+
+        // Non-minimal encodings are rejected
+        assert!(Decoder::new(&[0x80, 0x00]).varint().is_err());
+        assert!(Decoder::new(&[0xff; 11]).varint().is_err());
+    }
+
+    #[test]
+    fn test_nucleotide_golden_vector() {
+        let nucleotide = Nucleotide::from_slice(b"rope", 7);
+        let expected = format!("0101726f7065{}07", "00".repeat(28));
+
+        assert_eq!(hex::encode(nucleotide.to_canonical_bytes()), expected);
+        let decoded = Nucleotide::from_canonical_bytes(&hex::decode(&expected).unwrap()).unwrap();
+        assert_eq!(decoded, nucleotide);
+    }
+
+    #[test]
+    fn test_rope_string_golden_vector() {
+        let string = golden_string();
+        let expected = format!("0102{}", golden_string_body());
+
+        assert_eq!(hex::encode(string.to_canonical_bytes()), expected);
+
+        // The ID is recomputed rather than trusted from the wire
+        let decoded = RopeString::from_canonical_bytes(&hex::decode(&expected).unwrap()).unwrap();
+        assert_eq!(decoded, string);
+        assert_eq!(decoded.id(), string.id());
+    }
+
+    #[test]
+    fn test_anchor_string_golden_vector() {
+        let anchor = AnchorString {
+            string: golden_string(),
+            round: 2,
+            strongly_sees: vec![StringId::new([3u8; 32])],
+            testimony_count: 300,
+            is_famous: true,
+        };
+        let expected = format!("0104{}0201{}ac0201", golden_string_body(), "03".repeat(32));
+
+        assert_eq!(hex::encode(anchor.to_canonical_bytes()), expected);
+        let decoded = AnchorString::from_canonical_bytes(&hex::decode(&expected).unwrap()).unwrap();
+        assert_eq!(decoded.to_canonical_bytes(), anchor.to_canonical_bytes());
+    }
+
+    #[test]
+    fn test_complement_golden_vector() {
+        let expected = [
+            "0103".to_string(),
+            // primary id
+            "aa".repeat(32),
+            // 32 bytes of parity, redundancy 1, 1 nucleotide, 1 digest
+            format!("20{}0101", "bb".repeat(32)),
+            format!("01{}", "cc".repeat(8)),
+            // verification hash
+            "dd".repeat(32),
+            // one hint: parent, segment 0..32
+            format!("01{}000020", "ee".repeat(32)),
+            // entanglement proof
+            format!("{}0500", "ff".repeat(32)),
+        ]
+        .concat();
+        let bytes = hex::decode(&expected).unwrap();
+
+        let complement = Complement::from_canonical_bytes(&bytes).unwrap();
+        assert_eq!(complement.primary_id(), StringId::new([0xaa; 32]));
+        assert_eq!(complement.redundancy(), 1);
+        assert_eq!(complement.nucleotide_count(), 1);
+        assert_eq!(complement.regeneration_hints()[0].segment_range, (0, 32));
+        assert_eq!(complement.entanglement_proof().created_at, 5);
+        assert_eq!(complement.to_canonical_bytes(), bytes);
+    }
+
+    #[test]
+    fn test_complement_roundtrip() {
+        let string = golden_string();
+        let complement = Complement::generate(&string, 2);
+        let decoded = Complement::from_canonical_bytes(&complement.to_canonical_bytes()).unwrap();
+        assert_eq!(decoded, complement);
+        assert!(decoded.verify_entanglement(&string));
+    }
+
+    #[test]
+    fn test_strict_decoding() {
+        let mut bytes = Nucleotide::new([9u8; 32], 1).to_canonical_bytes();
+
+        // Wrong type tag
+        assert!(RopeString::from_canonical_bytes(&bytes).is_err());
+
+        // Unsupported version
+        let mut future = bytes.clone();
+        future[0] = WIRE_VERSION + 1;
+        assert!(Nucleotide::from_canonical_bytes(&future).is_err());
+
+        // Trailing bytes
+        bytes.push(0);
+        assert!(Nucleotide::from_canonical_bytes(&bytes).is_err());
+
+        // Hostile length prefix
+        let mut hostile = golden_string().to_canonical_bytes();
+        hostile[2] = 0x7f;
+        assert!(RopeString::from_canonical_bytes(&hostile).is_err());
+    }
+}
@@ -6,6 +6,9 @@
 //! - Provides error correction capability
 //! - Supports regeneration of damaged strings
 
+use crate::codec::{tags, CanonicalEncoding, Decoder, Encoder, WireField};
+use crate::error::{Result, RopeError};
+use crate::nucleotide::{Nucleotide, NucleotideSequence};
 use crate::string::RopeString;
 use crate::types::StringId;
 use reed_solomon_erasure::galois_8::ReedSolomon;
@@ -89,20 +92,48 @@ impl EntanglementProof {
     }
 }
 
+/// Data nucleotides per Reed-Solomon stripe
+///
+/// GF(2^8) codes are limited to 256 shards, so long strings are encoded as
+/// independent stripes of at most this many nucleotides.
+pub const STRIPE_NUCLEOTIDES: usize = 128;
+
+/// Maximum parity nucleotides per stripe
+pub const MAX_REDUNDANCY: u32 = 64;
+
+/// Size of a nucleotide value in bytes
+const NUCLEOTIDE_SIZE: usize = 32;
+
+/// Truncated BLAKE3 digest used to locate damaged nucleotides
+type NucleotideDigest = [u8; 8];
+
 /// Complement - Verification and regeneration partner for a string
 ///
 /// Like DNA's complementary strand, the Complement enables:
 /// 1. Integrity verification through hash comparison
 /// 2. Error correction through Reed-Solomon decoding
 /// 3. Regeneration of lost/corrupted data
+///
+/// Each nucleotide is one Reed-Solomon data shard. With redundancy `k`, up to
+/// `k` missing or corrupted nucleotides per stripe of [`STRIPE_NUCLEOTIDES`]
+/// can be reconstructed.
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Complement {
     /// ID of the primary string this complements
     primary_id: StringId,
 
-    /// Reed-Solomon encoded parity data
+    /// Reed-Solomon parity nucleotides, `redundancy` per stripe
     complement_data: Vec<u8>,
 
+    /// Parity nucleotides per stripe
+    redundancy: u32,
+
+    /// Number of nucleotides in the primary string
+    nucleotide_count: u64,
+
+    /// Per-nucleotide digests for locating damage
+    nucleotide_digests: Vec<NucleotideDigest>,
+
     /// BLAKE3 hash of primary content for verification
     verification_hash: [u8; 32],
 
@@ -114,13 +145,18 @@ pub struct Complement {
 }
 
 impl Complement {
-    /// Generate a complement for a string
-    pub fn generate(string: &RopeString) -> Self {
+    /// Generate a complement with `redundancy` parity nucleotides per stripe
+    ///
+    /// Redundancy is clamped to `1..=MAX_REDUNDANCY`.
+    pub fn generate(string: &RopeString, redundancy: u32) -> Self {
+        let redundancy = redundancy.clamp(1, MAX_REDUNDANCY);
+        let sequence = string.sequence();
         let content = string.content();
 
         // Generate Reed-Solomon parity
-        let complement_data =
-            Self::generate_reed_solomon_parity(&content, string.replication_factor());
+        let complement_data = Self::generate_parity(sequence, redundancy as usize);
+
+        let nucleotide_digests = sequence.iter().map(Self::nucleotide_digest).collect();
 
         // Compute verification hash
         let verification_hash = *blake3::hash(&content).as_bytes();
@@ -142,55 +178,55 @@ impl Complement {
         Self {
             primary_id: string.id(),
             complement_data,
+            redundancy,
+            nucleotide_count: sequence.len() as u64,
+            nucleotide_digests,
             verification_hash,
             regeneration_hints,
             entanglement_proof,
         }
     }
 
-    /// Generate Reed-Solomon parity shards
-    fn generate_reed_solomon_parity(data: &[u8], replication_factor: u32) -> Vec<u8> {
-        if data.is_empty() {
-            return Vec::new();
-        }
-
-        // Reed-Solomon parameters: data_shards + parity_shards
-        // For replication_factor 5: 3 data shards, 2 parity shards
-        let data_shards = (replication_factor as usize * 3) / 5;
-        let parity_shards = replication_factor as usize - data_shards;
+    /// Redundancy matching a replication factor
+    ///
+    /// Keeps 3/5 of the replication factor as data, the rest as parity
+    /// (2 parity nucleotides for the default factor of 5).
+    pub fn default_redundancy(replication_factor: u32) -> u32 {
+        (replication_factor - replication_factor * 3 / 5).max(1)
+    }
 
-        let data_shards = data_shards.max(1);
-        let parity_shards = parity_shards.max(1);
+    /// Generate parity nucleotides stripe by stripe
+    fn generate_parity(sequence: &NucleotideSequence, redundancy: usize) -> Vec<u8> {
+        let values: Vec<&[u8; 32]> = sequence.iter().map(|n| n.value()).collect();
+        let mut parity = Vec::with_capacity(
+            values.len().div_ceil(STRIPE_NUCLEOTIDES) * redundancy * NUCLEOTIDE_SIZE,
+        );
 
-        // Pad data to be divisible by data_shards
-        let shard_size = data.len().div_ceil(data_shards);
-        let mut padded_data = data.to_vec();
-        padded_data.resize(shard_size * data_shards, 0);
+        for stripe in values.chunks(STRIPE_NUCLEOTIDES) {
+            let mut shards: Vec<Vec<u8>> = stripe.iter().map(|v| v.to_vec()).collect();
+            shards.extend((0..redundancy).map(|_| vec![0u8; NUCLEOTIDE_SIZE]));
 
-        // Create shards
-        let mut shards: Vec<Vec<u8>> = padded_data
-            .chunks(shard_size)
-            .map(|chunk| chunk.to_vec())
-            .collect();
+            // Shard counts are bounded by the stripe and redundancy limits
+            if let Ok(rs) = ReedSolomon::new(stripe.len(), redundancy) {
+                let _ = rs.encode(&mut shards);
+            }
 
-        // Add empty parity shards
-        for _ in 0..parity_shards {
-            shards.push(vec![0u8; shard_size]);
+            for shard in &shards[stripe.len()..] {
+                parity.extend_from_slice(shard);
+            }
         }
 
-        // Encode with Reed-Solomon
-        if let Ok(rs) = ReedSolomon::new(data_shards, parity_shards) {
-            let mut shard_refs: Vec<&mut [u8]> =
-                shards.iter_mut().map(|s| s.as_mut_slice()).collect();
-            let _ = rs.encode(&mut shard_refs);
-        }
+        parity
+    }
 
-        // Return only parity shards as complement
-        let parity_start = data_shards;
-        shards[parity_start..]
-            .iter()
-            .flat_map(|s| s.iter().copied())
-            .collect()
+    fn nucleotide_digest(nucleotide: &Nucleotide) -> NucleotideDigest {
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(nucleotide.value());
+        hasher.update(&nucleotide.position().to_be_bytes());
+
+        let mut digest = [0u8; 8];
+        digest.copy_from_slice(&hasher.finalize().as_bytes()[..8]);
+        digest
     }
 
     /// Get primary string ID
@@ -203,6 +239,16 @@ impl Complement {
         &self.complement_data
     }
 
+    /// Parity nucleotides per stripe (maximum repairable nucleotides per stripe)
+    pub fn redundancy(&self) -> u32 {
+        self.redundancy
+    }
+
+    /// Number of nucleotides in the primary string
+    pub fn nucleotide_count(&self) -> u64 {
+        self.nucleotide_count
+    }
+
     /// Get verification hash
     pub fn verification_hash(&self) -> &[u8; 32] {
         &self.verification_hash
@@ -224,6 +270,30 @@ impl Complement {
         *hash.as_bytes() == self.verification_hash
     }
 
+    /// Verify a sequence nucleotide by nucleotide
+    pub fn verify_sequence(&self, sequence: &NucleotideSequence) -> bool {
+        self.damaged_positions(sequence).is_empty() && self.verify_content(&sequence.to_raw_bytes())
+    }
+
+    /// Positions of missing or corrupted nucleotides
+    pub fn damaged_positions(&self, sequence: &NucleotideSequence) -> Vec<u64> {
+        (0..self.nucleotide_count)
+            .filter(|&position| {
+                !sequence
+                    .get(position as usize)
+                    .is_some_and(|n| self.is_intact(n, position))
+            })
+            .collect()
+    }
+
+    fn is_intact(&self, nucleotide: &Nucleotide, position: u64) -> bool {
+        nucleotide.position() == position
+            && self
+                .nucleotide_digests
+                .get(position as usize)
+                .is_some_and(|d| *d == Self::nucleotide_digest(nucleotide))
+    }
+
     /// Verify entanglement with string
     pub fn verify_entanglement(&self, string: &RopeString) -> bool {
         string.id() == self.primary_id
@@ -237,70 +307,129 @@ impl Complement {
         self.regeneration_hints.push(hint);
     }
 
-    /// Attempt to regenerate damaged content using complement
-    pub fn regenerate_content(
-        &self,
-        damaged_content: &[u8],
-        replication_factor: u32,
-    ) -> Option<Vec<u8>> {
-        if damaged_content.is_empty() && self.complement_data.is_empty() {
-            return None;
+    /// Reconstruct the full sequence from surviving nucleotides
+    ///
+    /// `nucleotides[i]` is the nucleotide held for position `i`, or `None` if
+    /// it was lost. Nucleotides that fail their digest are treated as lost.
+    /// Up to [`Complement::redundancy`] nucleotides per stripe can be rebuilt.
+    pub fn repair(&self, nucleotides: &[Option<Nucleotide>]) -> Result<NucleotideSequence> {
+        let count = self.nucleotide_count as usize;
+        let redundancy = self.redundancy as usize;
+        let stripes = count.div_ceil(STRIPE_NUCLEOTIDES);
+        if self.complement_data.len() != stripes * redundancy * NUCLEOTIDE_SIZE {
+            return Err(RopeError::ComplementVerificationFailed(self.primary_id));
         }
 
-        // Reed-Solomon parameters
-        let data_shards = (replication_factor as usize * 3) / 5;
-        let parity_shards = replication_factor as usize - data_shards;
+        let mut values: Vec<Option<Vec<u8>>> = (0..count)
+            .map(|position| {
+                nucleotides
+                    .get(position)
+                    .and_then(|n| n.as_ref())
+                    .filter(|n| self.is_intact(n, position as u64))
+                    .map(|n| n.value().to_vec())
+            })
+            .collect();
 
-        let data_shards = data_shards.max(1);
-        let parity_shards = parity_shards.max(1);
+        for (stripe, parity) in values
+            .chunks_mut(STRIPE_NUCLEOTIDES)
+            .zip(self.complement_data.chunks(redundancy * NUCLEOTIDE_SIZE))
+        {
+            let missing = stripe.iter().filter(|v| v.is_none()).count();
+            if missing == 0 {
+                continue;
+            }
+            if missing > redundancy {
+                return Err(RopeError::InsufficientSources {
+                    required: stripe.len(),
+                    available: stripe.len() + redundancy - missing,
+                });
+            }
 
-        // Calculate shard size from parity data
-        let parity_total_size = self.complement_data.len();
-        let shard_size = parity_total_size / parity_shards;
+            let mut shards: Vec<Option<Vec<u8>>> = stripe.to_vec();
+            shards.extend(parity.chunks(NUCLEOTIDE_SIZE).map(|p| Some(p.to_vec())));
 
-        if shard_size == 0 {
-            return None;
-        }
+            let rs = ReedSolomon::new(stripe.len(), redundancy)
+                .map_err(|_| RopeError::RegenerationFailed(self.primary_id))?;
+            rs.reconstruct_data(&mut shards)
+                .map_err(|_| RopeError::RegenerationFailed(self.primary_id))?;
 
-        // Reconstruct shards
-        let mut shards: Vec<Option<Vec<u8>>> = Vec::new();
+            for (slot, shard) in stripe.iter_mut().zip(shards) {
+                *slot = shard;
+            }
+        }
 
-        // Add data shards (may be damaged)
-        let mut padded_damaged = damaged_content.to_vec();
-        padded_damaged.resize(shard_size * data_shards, 0);
+        let mut sequence = NucleotideSequence::new();
+        for (position, value) in values.into_iter().enumerate() {
+            let value = value.ok_or(RopeError::RegenerationFailed(self.primary_id))?;
+            sequence.push(Nucleotide::from_slice(&value, position as u64));
+        }
 
-        for chunk in padded_damaged.chunks(shard_size) {
-            shards.push(Some(chunk.to_vec()));
+        if !self.verify_sequence(&sequence) {
+            return Err(RopeError::ComplementVerificationFailed(self.primary_id));
         }
+        Ok(sequence)
+    }
+
+    /// Repair a stored sequence, treating absent positions as lost
+    pub fn repair_sequence(&self, sequence: &NucleotideSequence) -> Result<NucleotideSequence> {
+        let nucleotides: Vec<Option<Nucleotide>> = (0..self.nucleotide_count as usize)
+            .map(|position| sequence.get(position).cloned())
+            .collect();
+        self.repair(&nucleotides)
+    }
+}
 
-        // Add parity shards from complement
-        for chunk in self.complement_data.chunks(shard_size) {
-            shards.push(Some(chunk.to_vec()));
+impl CanonicalEncoding for Complement {
+    const TAG: u8 = tags::COMPLEMENT;
+
+    fn encode_body(&self, enc: &mut Encoder) {
+        self.primary_id.encode_field(enc);
+        enc.put_bytes(&self.complement_data);
+        enc.put_varint(self.redundancy as u64);
+        enc.put_varint(self.nucleotide_count);
+        enc.put_varint(self.nucleotide_digests.len() as u64);
+        for digest in &self.nucleotide_digests {
+            enc.put_fixed(digest);
         }
+        enc.put_fixed(&self.verification_hash);
+        self.regeneration_hints.encode_field(enc);
+        self.entanglement_proof.encode_field(enc);
+    }
 
-        // Attempt Reed-Solomon reconstruction
-        if let Ok(rs) = ReedSolomon::new(data_shards, parity_shards) {
-            let mut shard_refs: Vec<Option<Box<[u8]>>> = shards
-                .into_iter()
-                .map(|s| s.map(|v| v.into_boxed_slice()))
-                .collect();
-
-            if rs.reconstruct(&mut shard_refs).is_ok() {
-                // Collect reconstructed data shards
-                let reconstructed: Vec<u8> = shard_refs[..data_shards]
-                    .iter()
-                    .filter_map(|s| s.as_ref())
-                    .flat_map(|s| s.iter().copied())
-                    .collect();
-
-                // Verify reconstruction
-                if self.verify_content(&reconstructed) {
-                    return Some(reconstructed);
-                }
-            }
+    fn decode_body(dec: &mut Decoder<'_>) -> Result<Self> {
+        let primary_id = StringId::decode_field(dec)?;
+        let complement_data = dec.bytes()?.to_vec();
+        let redundancy = dec.varint_u32()?;
+        let nucleotide_count = dec.varint()?;
+        let digest_count = dec.count(8)?;
+        let nucleotide_digests = (0..digest_count)
+            .map(|_| dec.fixed())
+            .collect::<Result<Vec<_>>>()?;
+        let verification_hash = dec.fixed()?;
+        let regeneration_hints = Vec::<RegenerationHint>::decode_field(dec)?;
+        let entanglement_proof = EntanglementProof::decode_field(dec)?;
+
+        // Reject layouts that `repair` could not use
+        let stripes = (nucleotide_count as usize).div_ceil(STRIPE_NUCLEOTIDES);
+        if !(1..=MAX_REDUNDANCY).contains(&redundancy)
+            || digest_count as u64 != nucleotide_count
+            || complement_data.len() != stripes * redundancy as usize * NUCLEOTIDE_SIZE
+        {
+            return Err(RopeError::SerializationError(
+                "inconsistent complement layout".to_string(),
+            ));
         }
 
-        None
+        Ok(Self {
+            primary_id,
+            complement_data,
+            redundancy,
+            nucleotide_count,
+            nucleotide_digests,
+            verification_hash,
+            regeneration_hints,
+            entanglement_proof,
+        })
     }
 }
 
@@ -323,7 +452,7 @@ mod tests {
     #[test]
     fn test_complement_generation() {
         let string = make_test_string(b"Test content for complement generation");
-        let complement = Complement::generate(&string);
+        let complement = Complement::generate(&string, 2);
 
         assert_eq!(complement.primary_id(), string.id());
         assert!(!complement.complement_data().is_empty());
@@ -333,7 +462,7 @@ mod tests {
     #[test]
     fn test_complement_verification() {
         let string = make_test_string(b"Content to verify");
-        let complement = Complement::generate(&string);
+        let complement = Complement::generate(&string, 2);
 
         // Correct content should verify
         assert!(complement.verify_content(&string.content()));
@@ -345,7 +474,7 @@ mod tests {
     #[test]
     fn test_entanglement_proof() {
         let string = make_test_string(b"Entangled content");
-        let complement = Complement::generate(&string);
+        let complement = Complement::generate(&string, 2);
 
         assert!(complement.verify_entanglement(&string));
 
@@ -354,6 +483,79 @@ mod tests {
         assert!(!complement.verify_entanglement(&other_string));
     }
 
+    #[test]
+    fn test_repair_missing_nucleotides() {
+        let content: Vec<u8> = (0..200u8).collect();
+        let string = make_test_string(&content);
+        let complement = Complement::generate(&string, 3);
+        assert_eq!(complement.nucleotide_count(), 7);
+        assert_eq!(complement.complement_data().len(), 3 * 32);
+
+        let mut nucleotides: Vec<Option<Nucleotide>> =
+            string.sequence().iter().cloned().map(Some).collect();
+        nucleotides[0] = None;
+        nucleotides[4] = None;
+        // Corrupted nucleotides count as missing
+        nucleotides[6] = Some(Nucleotide::new([0xFF; 32], 6));
+
+        let repaired = complement.repair(&nucleotides).unwrap();
+        assert_eq!(&repaired, string.sequence());
+        assert!(complement.verify_sequence(&repaired));
+    }
+
+    #[test]
+    fn test_repair_exceeds_redundancy() {
+        let string = make_test_string(&[7u8; 160]);
+        let complement = Complement::generate(&string, 2);
+
+        let mut nucleotides: Vec<Option<Nucleotide>> =
+            string.sequence().iter().cloned().map(Some).collect();
+        nucleotides[1] = None;
+        nucleotides[2] = None;
+        nucleotides[3] = None;
+
+        assert!(matches!(
+            complement.repair(&nucleotides),
+            Err(RopeError::InsufficientSources {
+                required: 5,
+                available: 4
+            })
+        ));
+    }
+
+    #[test]
+    fn test_repair_across_stripes() {
+        let content: Vec<u8> = (0..(STRIPE_NUCLEOTIDES + 10) * 32)
+            .map(|i| (i % 251) as u8)
+            .collect();
+        let string = make_test_string(&content);
+        let complement = Complement::generate(&string, 1);
+        assert_eq!(complement.complement_data().len(), 2 * 32);
+
+        // One loss per stripe is within redundancy
+        let mut nucleotides: Vec<Option<Nucleotide>> =
+            string.sequence().iter().cloned().map(Some).collect();
+        nucleotides[5] = None;
+        nucleotides[STRIPE_NUCLEOTIDES + 3] = None;
+
+        let repaired = complement.repair(&nucleotides).unwrap();
+        assert_eq!(&repaired, string.sequence());
+    }
+
+    #[test]
+    fn test_damaged_positions() {
+        let string = make_test_string(&[1u8; 96]);
+        let complement = Complement::generate(&string, 2);
+        assert!(complement.verify_sequence(string.sequence()));
+
+        let truncated = NucleotideSequence::from_bytes(&[1u8; 64]);
+        assert_eq!(complement.damaged_positions(&truncated), vec![2]);
+        assert_eq!(
+            &complement.repair_sequence(&truncated).unwrap(),
+            string.sequence()
+        );
+    }
+
     #[test]
     fn test_regeneration_hints() {
         let parent_id = StringId::from_content(b"parent");
@@ -366,7 +568,7 @@ mod tests {
             .build()
             .unwrap();
 
-        let complement = Complement::generate(&string);
+        let complement = Complement::generate(&string, 2);
 
         assert_eq!(complement.regeneration_hints().len(), 1);
         assert_eq!(
@@ -26,10 +26,18 @@ pub enum RopeError {
     #[error("Parent string was erased: {0}")]
     ParentErased(StringId),
 
+    /// String already present in the lattice or earlier in a batch
+    #[error("Duplicate string: {0}")]
+    DuplicateString(StringId),
+
     /// Content exceeds maximum size
     #[error("Content exceeds maximum size of {max} bytes")]
     ContentTooLarge { max: usize },
 
+    /// Inserting the string would create a cycle in the DAG
+    #[error("Cycle detected at string: {0}")]
+    CycleDetected(StringId),
+
     // === Complement Operations ===
     /// Complement not found for string
     #[error("Complement not found for string: {0}")]
@@ -82,6 +90,10 @@ pub enum RopeError {
     #[error("Invalid anchor string: {0}")]
     InvalidAnchor(String),
 
+    /// Sync filter too small to decode the lattice difference
+    #[error("Set reconciliation failed with {cells} cells")]
+    ReconciliationFailed { cells: usize },
+
     // === Erasure Errors ===
     /// Unauthorized erasure attempt
     #[error("Unauthorized erasure attempt on string: {0}")]
@@ -108,6 +120,19 @@ pub enum RopeError {
     #[error("Rate limit exceeded: {limit} per second")]
     RateLimitExceeded { limit: u32 },
 
+    // === Schema Errors ===
+    /// No schema registered for a domain or hash
+    #[error("Schema not found: {0}")]
+    SchemaNotFound(String),
+
+    /// Schema definition could not be parsed
+    #[error("Invalid schema: {0}")]
+    InvalidSchema(String),
+
+    /// Payload does not conform to its schema
+    #[error("Payload violates schema {schema}: {reason}")]
+    SchemaViolation { schema: String, reason: String },
+
     // === Storage Errors ===
     /// Storage error
     #[error("Storage error: {0}")]
@@ -151,6 +176,7 @@ impl RopeError {
             Self::RateLimitExceeded { .. }
                 | Self::QuorumNotMet { .. }
                 | Self::InsufficientSources { .. }
+                | Self::ReconciliationFailed { .. }
                 | Self::ConnectionFailed(_)
         )
     }
@@ -10,14 +10,20 @@
 
 use hashbrown::{HashMap, HashSet};
 use parking_lot::RwLock;
+use petgraph::algo::has_path_connecting;
 use petgraph::graph::{DiGraph, NodeIndex};
 use petgraph::Direction;
+use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::sync::Arc;
 
+use crate::accumulator::{InclusionProof, MerkleAccumulator};
+use crate::cache::{CacheConfig, CacheStats, StringCache, StringStore};
 use crate::complement::Complement;
 use crate::error::{Result, RopeError};
 use crate::string::RopeString;
+use crate::sync::{InvertibleBloomFilter, LatticeDiff};
 use crate::types::{constants, FinalityStatus, StringId};
 
 /// Anchor String - Synchronization point in the lattice
@@ -61,8 +67,8 @@ impl AnchorString {
 /// Replaces blockchain's linear chain with a multi-dimensional lattice
 /// of intertwined strings that can be added, verified, and erased.
 pub struct StringLattice {
-    /// All strings in the lattice: StringId -> RopeString
-    strings: RwLock<HashMap<StringId, RopeString>>,
+    /// All strings in the lattice, resident or evicted to storage
+    strings: RwLock<StringCache>,
 
     /// Complements for each string: StringId -> Complement
     complements: RwLock<HashMap<StringId, Complement>>,
@@ -82,6 +88,9 @@ pub struct StringLattice {
     /// Erased strings (tombstones)
     erased_strings: RwLock<HashSet<StringId>>,
 
+    /// Accumulator committing to finalized strings in finality order
+    accumulator: RwLock<MerkleAccumulator>,
+
     /// Current round number
     current_round: RwLock<u64>,
 }
@@ -100,16 +109,43 @@ impl LatticeDAG {
         }
     }
 
-    fn add_node(&mut self, id: StringId, parents: &[StringId]) {
-        let node_idx = self.graph.add_node(id);
-        self.id_to_index.insert(id, node_idx);
+    /// Add a node with edges from its parents
+    ///
+    /// Rejects self-parentage and, when re-inserting an existing node, any
+    /// parent that is already one of its descendants.
+    fn add_node(&mut self, id: StringId, parents: &[StringId]) -> Result<()> {
+        if parents.contains(&id) {
+            return Err(RopeError::CycleDetected(id));
+        }
+
+        let node_idx = match self.id_to_index.get(&id) {
+            Some(&idx) => {
+                for parent_id in parents {
+                    if let Some(&parent_idx) = self.id_to_index.get(parent_id) {
+                        if has_path_connecting(&self.graph, idx, parent_idx, None) {
+                            return Err(RopeError::CycleDetected(id));
+                        }
+                    }
+                }
+                idx
+            }
+            None => {
+                let idx = self.graph.add_node(id);
+                self.id_to_index.insert(id, idx);
+                idx
+            }
+        };
 
         // Add edges from parents to this node
         for parent_id in parents {
             if let Some(&parent_idx) = self.id_to_index.get(parent_id) {
-                self.graph.add_edge(parent_idx, node_idx, ());
+                if self.graph.find_edge(parent_idx, node_idx).is_none() {
+                    self.graph.add_edge(parent_idx, node_idx, ());
+                }
             }
         }
+
+        Ok(())
     }
 
     fn get_parents(&self, id: &StringId) -> Vec<StringId> {
@@ -134,8 +170,80 @@ impl LatticeDAG {
         }
     }
 
+    /// Strings reachable from `id` in `direction`, nearest first
+    fn reachable(&self, id: &StringId, direction: Direction) -> Vec<StringId> {
+        let Some(&start) = self.id_to_index.get(id) else {
+            return Vec::new();
+        };
+
+        let mut visited = HashSet::new();
+        visited.insert(start);
+        let mut queue = VecDeque::from([start]);
+        let mut found = Vec::new();
+
+        while let Some(idx) = queue.pop_front() {
+            for next in self.graph.neighbors_directed(idx, direction) {
+                if visited.insert(next) {
+                    found.push(self.graph[next]);
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        found
+    }
+
+    /// Kahn's algorithm over `nodes`, breaking ties by string ID so every
+    /// node derives the same order
+    fn topological_sort(&self, nodes: &HashSet<NodeIndex>) -> Vec<StringId> {
+        let mut in_degree: HashMap<NodeIndex, usize> = nodes
+            .iter()
+            .map(|&idx| {
+                let degree = self
+                    .graph
+                    .neighbors_directed(idx, Direction::Incoming)
+                    .filter(|parent| nodes.contains(parent))
+                    .count();
+                (idx, degree)
+            })
+            .collect();
+
+        let mut ready: BTreeMap<StringId, NodeIndex> = in_degree
+            .iter()
+            .filter(|(_, &degree)| degree == 0)
+            .map(|(&idx, _)| (self.graph[idx], idx))
+            .collect();
+
+        let mut order = Vec::with_capacity(nodes.len());
+        while let Some((id, idx)) = ready.pop_first() {
+            order.push(id);
+            for child in self.graph.neighbors_directed(idx, Direction::Outgoing) {
+                if let Some(degree) = in_degree.get_mut(&child) {
+                    *degree -= 1;
+                    if *degree == 0 {
+                        ready.insert(self.graph[child], child);
+                    }
+                }
+            }
+        }
+
+        order
+    }
+
+    /// Node indices for `id` and all its ancestors
+    fn ancestor_closure(&self, id: &StringId) -> HashSet<NodeIndex> {
+        let mut closure: HashSet<NodeIndex> = self
+            .reachable(id, Direction::Incoming)
+            .iter()
+            .filter_map(|ancestor| self.id_to_index.get(ancestor).copied())
+            .collect();
+        if let Some(&idx) = self.id_to_index.get(id) {
+            closure.insert(idx);
+        }
+        closure
+    }
+
     /// Check if a string exists in the DAG
-    #[allow(dead_code)]
     pub fn contains(&self, id: &StringId) -> bool {
         self.id_to_index.contains_key(id)
     }
@@ -148,16 +256,27 @@ impl LatticeDAG {
 }
 
 impl StringLattice {
-    /// Create a new empty string lattice
+    /// Create a new empty string lattice holding every string in memory
     pub fn new() -> Self {
+        Self::with_strings(StringCache::unbounded())
+    }
+
+    /// Create a lattice that keeps at most `config` worth of strings in
+    /// memory and evicts the least recently used ones to `store`
+    pub fn with_cache(config: CacheConfig, store: Arc<dyn StringStore>) -> Self {
+        Self::with_strings(StringCache::new(config, store))
+    }
+
+    fn with_strings(strings: StringCache) -> Self {
         Self {
-            strings: RwLock::new(HashMap::new()),
+            strings: RwLock::new(strings),
             complements: RwLock::new(HashMap::new()),
             ordering: RwLock::new(LatticeDAG::new()),
             anchors: RwLock::new(Vec::new()),
             pending_strings: RwLock::new(BTreeMap::new()),
             finalized_strings: RwLock::new(HashSet::new()),
             erased_strings: RwLock::new(HashSet::new()),
+            accumulator: RwLock::new(MerkleAccumulator::new()),
             current_round: RwLock::new(0),
         }
     }
@@ -172,21 +291,8 @@ impl StringLattice {
     /// 5. Add to lattice structures
     /// 6. Check for anchor creation
     pub fn add_string(&self, string: RopeString) -> Result<StringId> {
-        let strings = self.strings.read();
-        let erased = self.erased_strings.read();
-
         // Step 1: Verify parentage exists
-        for parent in string.parentage() {
-            if !strings.contains_key(parent) && !parent.as_bytes().iter().all(|&b| b == 0) {
-                return Err(RopeError::MissingParent(*parent));
-            }
-            if erased.contains(parent) {
-                return Err(RopeError::ParentErased(*parent));
-            }
-        }
-
-        drop(strings);
-        drop(erased);
+        self.check_parents(&string)?;
 
         // Step 2: Verify OES generation is within acceptable window
         // (Placeholder - actual verification would involve OES state)
@@ -201,9 +307,150 @@ impl StringLattice {
         // }
 
         // Step 4: Generate complement
-        let complement = Complement::generate(&string);
+        let complement = Complement::generate(
+            &string,
+            Complement::default_redundancy(string.replication_factor()),
+        );
+
+        // Steps 5-6
+        self.insert_prepared(string, complement)
+    }
+
+    /// Insert many strings, validating them in parallel
+    ///
+    /// Signatures, nucleotide parity and complements are checked and
+    /// generated on the rayon pool. Parent links and insertion then run in
+    /// dependency order, so a batch may contain whole chains. Unsigned strings
+    /// are accepted as in [`add_string`](Self::add_string); signed ones must
+    /// verify.
+    ///
+    /// Results are in input order. An ID already in the lattice or earlier in
+    /// the batch yields `DuplicateString`.
+    pub fn insert_batch(&self, strings: Vec<RopeString>) -> Vec<Result<StringId>> {
+        let mut results: Vec<Option<Result<StringId>>> = vec![None; strings.len()];
 
-        // Step 5: Add to lattice structures
+        // Deduplicate against the lattice and within the batch
+        {
+            let existing = self.strings.read();
+            let erased = self.erased_strings.read();
+            let mut seen = HashSet::new();
+            for (i, string) in strings.iter().enumerate() {
+                let id = string.id();
+                if erased.contains(&id) {
+                    results[i] = Some(Err(RopeError::StringErased(id)));
+                } else if existing.contains(&id) || !seen.insert(id) {
+                    results[i] = Some(Err(RopeError::DuplicateString(id)));
+                }
+            }
+        }
+
+        // Stateless validation and complement generation in parallel
+        let prepared: Vec<(usize, Result<Complement>)> = strings
+            .par_iter()
+            .enumerate()
+            .filter(|(i, _)| results[*i].is_none())
+            .map(|(i, string)| (i, Self::validate_detached(string)))
+            .collect();
+
+        let mut complements: HashMap<usize, Complement> = HashMap::new();
+        for (i, outcome) in prepared {
+            match outcome {
+                Ok(complement) => {
+                    complements.insert(i, complement);
+                }
+                Err(e) => results[i] = Some(Err(e)),
+            }
+        }
+
+        // Insert parents before children; parents that failed validation are
+        // not counted, so their children fail with `MissingParent`
+        let index_of: HashMap<StringId, usize> =
+            complements.keys().map(|&i| (strings[i].id(), i)).collect();
+        let mut waiting: HashMap<usize, usize> = HashMap::new();
+        let mut children: HashMap<usize, Vec<usize>> = HashMap::new();
+        for &i in complements.keys() {
+            let mut parents = strings[i].parentage().to_vec();
+            parents.sort();
+            parents.dedup();
+            for parent in parents {
+                if let Some(&p) = index_of.get(&parent) {
+                    *waiting.entry(i).or_default() += 1;
+                    children.entry(p).or_default().push(i);
+                }
+            }
+        }
+
+        let mut ready: BTreeSet<usize> = complements
+            .keys()
+            .filter(|i| !waiting.contains_key(*i))
+            .copied()
+            .collect();
+        let mut strings: Vec<Option<RopeString>> = strings.into_iter().map(Some).collect();
+        while let Some(i) = ready.pop_first() {
+            let string = strings[i].take().expect("each string is inserted once");
+            let complement = complements.remove(&i).expect("validated string");
+            results[i] = Some(
+                self.check_parents(&string)
+                    .and_then(|_| self.insert_prepared(string, complement)),
+            );
+
+            for &child in children.get(&i).into_iter().flatten() {
+                let count = waiting.get_mut(&child).expect("child waits on parent");
+                *count -= 1;
+                if *count == 0 {
+                    ready.insert(child);
+                }
+            }
+        }
+
+        // Whatever is still waiting sits on a cycle within the batch
+        for &i in complements.keys() {
+            if let Some(string) = &strings[i] {
+                results[i] = Some(Err(RopeError::CycleDetected(string.id())));
+            }
+        }
+
+        results
+            .into_iter()
+            .map(|result| result.expect("every string has a result"))
+            .collect()
+    }
+
+    /// Checks that need no lattice state; returns the generated complement
+    fn validate_detached(string: &RopeString) -> Result<Complement> {
+        if !string.verify_sequence() {
+            return Err(RopeError::InvalidInput(format!(
+                "corrupted nucleotides in string {}",
+                string.id()
+            )));
+        }
+        if !string.signature().is_empty() && !string.verify_signature() {
+            return Err(RopeError::InvalidSignature);
+        }
+        Ok(Complement::generate(
+            string,
+            Complement::default_redundancy(string.replication_factor()),
+        ))
+    }
+
+    /// Parents must be present and not erased
+    fn check_parents(&self, string: &RopeString) -> Result<()> {
+        let strings = self.strings.read();
+        let erased = self.erased_strings.read();
+
+        for parent in string.parentage() {
+            if !strings.contains(parent) && !parent.as_bytes().iter().all(|&b| b == 0) {
+                return Err(RopeError::MissingParent(*parent));
+            }
+            if erased.contains(parent) {
+                return Err(RopeError::ParentErased(*parent));
+            }
+        }
+        Ok(())
+    }
+
+    /// Add a validated string and its complement to the lattice structures
+    fn insert_prepared(&self, string: RopeString, complement: Complement) -> Result<StringId> {
         let id = string.id();
         let timestamp = string.temporal_marker().time();
 
@@ -213,27 +460,33 @@ impl StringLattice {
             let mut ordering = self.ordering.write();
             let mut pending = self.pending_strings.write();
 
+            ordering.add_node(id, string.parentage())?;
             strings.insert(id, string.clone());
             complements.insert(id, complement);
-            ordering.add_node(id, string.parentage());
 
             pending.entry(timestamp).or_default().insert(id);
         }
 
-        // Step 6: Check if this creates new anchor
+        // Check if this creates new anchor
         self.check_anchor_creation(&string)?;
 
         Ok(id)
     }
 
-    /// Get a string by ID
+    /// Get a string by ID, faulting it in from storage if evicted
     pub fn get_string(&self, id: &StringId) -> Option<RopeString> {
         // Check if erased
         if self.erased_strings.read().contains(id) {
             return None;
         }
 
-        self.strings.read().get(id).cloned()
+        match self.strings.write().get(id) {
+            Ok(string) => string,
+            Err(e) => {
+                tracing::warn!("failed to fault in string {}: {}", id, e);
+                None
+            }
+        }
     }
 
     /// Get a complement by string ID
@@ -266,7 +519,7 @@ impl StringLattice {
 
     /// Check if a string exists in the lattice
     pub fn contains(&self, id: &StringId) -> bool {
-        !self.erased_strings.read().contains(id) && self.strings.read().contains_key(id)
+        !self.erased_strings.read().contains(id) && self.strings.read().contains(id)
     }
 
     /// Get the number of strings in the lattice
@@ -314,18 +567,103 @@ impl StringLattice {
         self.ordering.read().get_children(id)
     }
 
+    /// All ancestors of a string, nearest first
+    ///
+    /// Erased strings stay in the DAG as tombstones and are included.
+    pub fn ancestors(&self, id: &StringId) -> Vec<StringId> {
+        self.ordering.read().reachable(id, Direction::Incoming)
+    }
+
+    /// All descendants of a string, nearest first
+    pub fn descendants(&self, id: &StringId) -> Vec<StringId> {
+        self.ordering.read().reachable(id, Direction::Outgoing)
+    }
+
+    /// Every string in the lattice in deterministic topological order
+    pub fn topological_order(&self) -> Vec<StringId> {
+        let ordering = self.ordering.read();
+        let nodes = ordering.graph.node_indices().collect();
+        ordering.topological_sort(&nodes)
+    }
+
+    /// Strings confirmed by `to_anchor` but not by `from_anchor`, in
+    /// topological order ending with `to_anchor`
+    ///
+    /// `from_anchor` must be an ancestor of `to_anchor`.
+    pub fn ordered_between(
+        &self,
+        from_anchor: &StringId,
+        to_anchor: &StringId,
+    ) -> Result<Vec<StringId>> {
+        {
+            let anchors = self.anchors.read();
+            for id in [from_anchor, to_anchor] {
+                if !anchors.iter().any(|a| a.id() == *id) {
+                    return Err(RopeError::InvalidAnchor(id.to_string()));
+                }
+            }
+        }
+
+        let ordering = self.ordering.read();
+        for id in [from_anchor, to_anchor] {
+            if !ordering.contains(id) {
+                return Err(RopeError::StringNotFound(*id));
+            }
+        }
+        if !self.is_ancestor_of(from_anchor, to_anchor, &ordering) {
+            return Err(RopeError::InvalidAnchor(format!(
+                "{} does not precede {}",
+                from_anchor, to_anchor
+            )));
+        }
+
+        let confirmed = ordering.ancestor_closure(from_anchor);
+        let range = ordering
+            .ancestor_closure(to_anchor)
+            .into_iter()
+            .filter(|idx| !confirmed.contains(idx))
+            .collect();
+        Ok(ordering.topological_sort(&range))
+    }
+
+    /// Lowest common ancestors of two strings
+    ///
+    /// A string counts as its own ancestor. The DAG can have several
+    /// incomparable common ancestors, so all are returned, sorted by ID.
+    pub fn lowest_common_ancestors(&self, a: &StringId, b: &StringId) -> Vec<StringId> {
+        let ordering = self.ordering.read();
+        let common: HashSet<NodeIndex> = ordering
+            .ancestor_closure(a)
+            .intersection(&ordering.ancestor_closure(b))
+            .copied()
+            .collect();
+
+        let mut lowest: Vec<StringId> = common
+            .iter()
+            .filter(|&&idx| {
+                !ordering
+                    .graph
+                    .neighbors_directed(idx, Direction::Outgoing)
+                    .any(|child| common.contains(&child))
+            })
+            .map(|&idx| ordering.graph[idx])
+            .collect();
+        lowest.sort();
+        lowest
+    }
+
     /// Mark a string as erased
     pub fn mark_erased(&self, id: StringId) -> Result<()> {
         let mut erased = self.erased_strings.write();
         let mut strings = self.strings.write();
         let mut complements = self.complements.write();
 
-        if !strings.contains_key(&id) {
+        if !strings.contains(&id) {
             return Err(RopeError::StringNotFound(id));
         }
 
-        // Remove from active storage
-        strings.remove(&id);
+        // Remove from active storage, including any evicted copy
+        strings.remove(&id)?;
         complements.remove(&id);
 
         // Add to erased set (tombstone)
@@ -347,28 +685,29 @@ impl StringLattice {
     }
 
     /// Attempt to regenerate a damaged string
+    ///
+    /// Rebuilds missing or corrupted nucleotides from the complement and
+    /// replaces the stored string with the repaired one.
     pub fn regenerate_string(&self, id: &StringId) -> Result<RopeString> {
+        if self.erased_strings.read().contains(id) {
+            return Err(RopeError::RegenerationBlocked(*id));
+        }
+
         let complement = self
             .get_complement(id)
             .ok_or(RopeError::ComplementNotFound(*id))?;
 
-        // Get the damaged string (or empty if completely lost)
-        let damaged_content = self.get_string(id).map(|s| s.content()).unwrap_or_default();
-
-        // Get replication factor (default if not found)
-        let replication_factor = self
+        // String metadata is not part of the complement, so a completely
+        // lost string cannot be rebuilt here
+        let damaged = self
             .get_string(id)
-            .map(|s| s.replication_factor())
-            .unwrap_or(constants::DEFAULT_REPLICATION_FACTOR);
-
-        // Attempt regeneration
-        let _regenerated_content = complement
-            .regenerate_content(&damaged_content, replication_factor)
             .ok_or(RopeError::RegenerationFailed(*id))?;
 
-        // We need the original string metadata to rebuild
-        // For now, return error if completely lost
-        Err(RopeError::RegenerationFailed(*id))
+        let sequence = complement.repair_sequence(damaged.sequence())?;
+        let repaired = damaged.with_sequence(sequence);
+        self.strings.write().insert(*id, repaired.clone());
+
+        Ok(repaired)
     }
 
     /// Count how many anchor strings reference a given string
@@ -459,17 +798,21 @@ impl StringLattice {
         let mut newly_finalized = Vec::new();
 
         for (_, string_ids) in pending.iter() {
-            for id in string_ids {
-                let refs = self.count_anchor_references(id);
-                if refs >= constants::FINALITY_ANCHORS {
-                    newly_finalized.push(*id);
-                }
-            }
+            // Sort within a timestamp so every node commits the same order
+            let mut ids: Vec<StringId> = string_ids
+                .iter()
+                .filter(|id| self.count_anchor_references(id) >= constants::FINALITY_ANCHORS)
+                .copied()
+                .collect();
+            ids.sort();
+            newly_finalized.extend(ids);
         }
 
         drop(anchors);
         drop(pending);
 
+        self.commit_finalized(&newly_finalized);
+
         // Mark as finalized
         let mut finalized = self.finalized_strings.write();
         let mut pending = self.pending_strings.write();
@@ -486,6 +829,61 @@ impl StringLattice {
         pending.retain(|_, ids| !ids.is_empty());
     }
 
+    /// Append finalized strings to the accumulator
+    fn commit_finalized(&self, ids: &[StringId]) {
+        let mut accumulator = self.accumulator.write();
+        for id in ids {
+            accumulator.append(*id);
+        }
+    }
+
+    /// Current accumulator root over all finalized strings
+    pub fn accumulator_root(&self) -> [u8; 32] {
+        self.accumulator.read().root()
+    }
+
+    /// Inclusion proof for a finalized string against the current root
+    pub fn inclusion_proof(&self, id: &StringId) -> Option<InclusionProof> {
+        self.accumulator.read().prove(id)
+    }
+
+    /// Sync filter over every live string, for exchange with a peer
+    pub fn sync_filter(&self, cells: usize) -> InvertibleBloomFilter {
+        let mut filter = InvertibleBloomFilter::new(cells);
+        for id in self.strings.read().ids() {
+            filter.insert(id);
+        }
+        filter
+    }
+
+    /// Strings each side lacks, given a peer's filter
+    ///
+    /// The local filter is built with the peer's size. Fails with
+    /// `ReconciliationFailed` if the difference is too large for it.
+    pub fn diff(&self, remote: &InvertibleBloomFilter) -> Result<LatticeDiff> {
+        LatticeDiff::compute(&self.sync_filter(remote.cell_count()), remote)
+    }
+
+    /// Local strings to send a peer, parents before children
+    ///
+    /// Unknown and erased IDs are skipped.
+    pub fn strings_for_sync(&self, ids: &[StringId]) -> Vec<RopeString> {
+        let order = {
+            let ordering = self.ordering.read();
+            let nodes = ids
+                .iter()
+                .filter_map(|id| ordering.id_to_index.get(id).copied())
+                .collect();
+            ordering.topological_sort(&nodes)
+        };
+        order.iter().filter_map(|id| self.get_string(id)).collect()
+    }
+
+    /// String cache counters and memory accounting
+    pub fn cache_stats(&self) -> CacheStats {
+        self.strings.read().stats()
+    }
+
     /// Get lattice statistics
     pub fn stats(&self) -> LatticeStats {
         LatticeStats {
@@ -520,6 +918,7 @@ pub struct LatticeStats {
 mod tests {
     use super::*;
     use crate::clock::LamportClock;
+    use crate::nucleotide::{Nucleotide, NucleotideSequence};
     use crate::string::{PublicKey, RopeString};
     use crate::types::NodeId;
 
@@ -614,4 +1013,288 @@ mod tests {
 
         assert!(lattice.verify_string(&id).unwrap());
     }
+
+    /// genesis -> {a, b} -> c -> d
+    fn diamond(lattice: &StringLattice) -> [StringId; 5] {
+        let genesis = lattice
+            .add_string(make_test_string(b"Genesis", vec![]))
+            .unwrap();
+        let a = lattice
+            .add_string(make_test_string(b"A", vec![genesis]))
+            .unwrap();
+        let b = lattice
+            .add_string(make_test_string(b"B", vec![genesis]))
+            .unwrap();
+        let c = lattice
+            .add_string(make_test_string(b"C", vec![a, b]))
+            .unwrap();
+        let d = lattice.add_string(make_test_string(b"D", vec![c])).unwrap();
+        [genesis, a, b, c, d]
+    }
+
+    #[test]
+    fn test_ancestors_and_descendants() {
+        let lattice = StringLattice::new();
+        let [genesis, a, b, c, d] = diamond(&lattice);
+
+        let ancestors = lattice.ancestors(&d);
+        assert_eq!(ancestors.len(), 4);
+        assert_eq!(ancestors[0], c);
+        assert_eq!(ancestors[3], genesis);
+
+        let descendants: HashSet<StringId> = lattice.descendants(&genesis).into_iter().collect();
+        assert_eq!(descendants, [a, b, c, d].into_iter().collect());
+        assert!(lattice.descendants(&d).is_empty());
+    }
+
+    #[test]
+    fn test_topological_order() {
+        let lattice = StringLattice::new();
+        let [genesis, a, b, c, d] = diamond(&lattice);
+
+        let order = lattice.topological_order();
+        let position = |id: &StringId| order.iter().position(|x| x == id).unwrap();
+        assert_eq!(order.len(), 5);
+        assert_eq!(position(&genesis), 0);
+        assert!(position(&a) < position(&c));
+        assert!(position(&b) < position(&c));
+        assert_eq!(position(&d), 4);
+    }
+
+    #[test]
+    fn test_ordered_between_anchors() {
+        let lattice = StringLattice::new();
+        let [genesis, a, b, c, d] = diamond(&lattice);
+        let anchor = lattice.get_string(&d).unwrap();
+        lattice.anchors.write().push(AnchorString::new(anchor, 1));
+
+        let range = lattice.ordered_between(&genesis, &d).unwrap();
+        let mut middle = [a, b];
+        middle.sort();
+        assert_eq!(range, vec![middle[0], middle[1], c, d]);
+
+        assert!(matches!(
+            lattice.ordered_between(&d, &genesis),
+            Err(RopeError::InvalidAnchor(_))
+        ));
+        assert!(matches!(
+            lattice.ordered_between(&genesis, &c),
+            Err(RopeError::InvalidAnchor(_))
+        ));
+    }
+
+    #[test]
+    fn test_lowest_common_ancestors() {
+        let lattice = StringLattice::new();
+        let [genesis, a, b, c, d] = diamond(&lattice);
+
+        assert_eq!(lattice.lowest_common_ancestors(&a, &b), vec![genesis]);
+        assert_eq!(lattice.lowest_common_ancestors(&a, &d), vec![a]);
+        assert_eq!(lattice.lowest_common_ancestors(&c, &d), vec![c]);
+    }
+
+    #[test]
+    fn test_cycle_detection() {
+        let mut dag = LatticeDAG::new();
+        let x = StringId::from_content(b"x");
+        let y = StringId::from_content(b"y");
+
+        assert!(matches!(
+            dag.add_node(x, &[x]),
+            Err(RopeError::CycleDetected(_))
+        ));
+
+        dag.add_node(x, &[]).unwrap();
+        dag.add_node(y, &[x]).unwrap();
+        assert!(matches!(
+            dag.add_node(x, &[y]),
+            Err(RopeError::CycleDetected(_))
+        ));
+        assert_eq!(dag.node_count(), 2);
+    }
+
+    #[test]
+    fn test_accumulator_commits_finalized() {
+        let lattice = StringLattice::new();
+        let [genesis, a, b, ..] = diamond(&lattice);
+        let empty_root = lattice.accumulator_root();
+        assert!(lattice.inclusion_proof(&a).is_none());
+
+        lattice.commit_finalized(&[genesis, a, b]);
+        let root = lattice.accumulator_root();
+        assert_ne!(root, empty_root);
+
+        let proof = lattice.inclusion_proof(&b).unwrap();
+        assert_eq!(proof.leaf_index, 2);
+        assert!(proof.verify(&root));
+    }
+
+    #[test]
+    fn test_regenerate_string() {
+        let lattice = StringLattice::new();
+        let string = make_test_string(&[42u8; 128], vec![]);
+        let id = lattice.add_string(string.clone()).unwrap();
+
+        // Corrupt one nucleotide of the stored copy
+        let mut damaged = NucleotideSequence::new();
+        for nucleotide in string.sequence().iter() {
+            if nucleotide.position() == 2 {
+                damaged.push(Nucleotide::new([0u8; 32], 2));
+            } else {
+                damaged.push(nucleotide.clone());
+            }
+        }
+        lattice
+            .strings
+            .write()
+            .insert(id, string.with_sequence(damaged));
+        assert!(!lattice.verify_string(&id).unwrap());
+
+        let repaired = lattice.regenerate_string(&id).unwrap();
+        assert_eq!(repaired, string);
+        assert!(lattice.verify_string(&id).unwrap());
+    }
+
+    #[test]
+    fn test_bounded_cache_faults_in_evicted_strings() {
+        let store = Arc::new(crate::cache::MemoryStringStore::default());
+        let config = CacheConfig {
+            max_strings: 2,
+            max_bytes: usize::MAX,
+        };
+        let lattice = StringLattice::with_cache(config, store.clone());
+
+        let genesis = lattice
+            .add_string(make_test_string(b"genesis", vec![]))
+            .unwrap();
+        let mut ids = vec![genesis];
+        for n in 0u8..4 {
+            let string = make_test_string(&[n; 8], vec![*ids.last().unwrap()]);
+            ids.push(lattice.add_string(string).unwrap());
+        }
+
+        let stats = lattice.cache_stats();
+        assert_eq!(stats.resident_strings, 2);
+        assert_eq!(stats.evicted_strings, 3);
+        assert_eq!(lattice.string_count(), 5);
+        assert!(lattice.contains(&genesis));
+
+        // Evicted strings are transparently faulted back in
+        let fetched = lattice.get_string(&genesis).unwrap();
+        assert_eq!(fetched.id(), genesis);
+        assert_eq!(lattice.cache_stats().fault_ins, 1);
+
+        // Erasing an evicted string also removes the stored copy
+        let evicted = ids
+            .iter()
+            .copied()
+            .find(|id| store.data.read().contains_key(id) && *id != genesis)
+            .unwrap();
+        lattice.mark_erased(evicted).unwrap();
+        assert!(!store.data.read().contains_key(&evicted));
+        assert!(lattice.get_string(&evicted).is_none());
+    }
+
+    #[test]
+    fn test_lattice_diff_and_sync() {
+        let local = StringLattice::new();
+        let remote = StringLattice::new();
+
+        let genesis = make_test_string(b"genesis", vec![]);
+        let shared = make_test_string(b"shared", vec![genesis.id()]);
+        for lattice in [&local, &remote] {
+            lattice.add_string(genesis.clone()).unwrap();
+            lattice.add_string(shared.clone()).unwrap();
+        }
+
+        // Local extends with a chain of two; remote with one
+        let a = make_test_string(b"local a", vec![shared.id()]);
+        let b = make_test_string(b"local b", vec![a.id()]);
+        local.add_string(a.clone()).unwrap();
+        local.add_string(b.clone()).unwrap();
+        let c = make_test_string(b"remote c", vec![shared.id()]);
+        remote.add_string(c.clone()).unwrap();
+
+        let diff = local.diff(&remote.sync_filter(24)).unwrap();
+        assert_eq!(diff.missing_locally, vec![c.id()]);
+        let mut expected = vec![a.id(), b.id()];
+        expected.sort();
+        assert_eq!(diff.missing_remotely, expected);
+
+        // Parents are sent first so the peer can insert in order
+        let batch = local.strings_for_sync(&diff.missing_remotely);
+        assert_eq!(batch, vec![a, b]);
+        for string in batch {
+            remote.add_string(string).unwrap();
+        }
+        for string in remote.strings_for_sync(&diff.missing_locally) {
+            local.add_string(string).unwrap();
+        }
+        assert!(local.diff(&remote.sync_filter(24)).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_insert_batch() {
+        let lattice = StringLattice::new();
+        let genesis = make_test_string(b"genesis", vec![]);
+        lattice.add_string(genesis.clone()).unwrap();
+
+        // Chain given child-first, a duplicate, an orphan and a known string
+        let a = make_test_string(b"a", vec![genesis.id()]);
+        let b = make_test_string(b"b", vec![a.id()]);
+        let orphan_parent = StringId::from_content(b"nowhere");
+        let orphan = make_test_string(b"orphan", vec![orphan_parent]);
+        let results = lattice.insert_batch(vec![
+            b.clone(),
+            a.clone(),
+            a.clone(),
+            orphan,
+            genesis.clone(),
+        ]);
+
+        assert_eq!(results[0].as_ref().unwrap(), &b.id());
+        assert_eq!(results[1].as_ref().unwrap(), &a.id());
+        assert!(matches!(results[2], Err(RopeError::DuplicateString(_))));
+        assert!(matches!(results[3], Err(RopeError::MissingParent(p)) if p == orphan_parent));
+        assert!(matches!(results[4], Err(RopeError::DuplicateString(_))));
+        assert_eq!(lattice.string_count(), 3);
+        assert_eq!(lattice.get_parents(&b.id()), vec![a.id()]);
+    }
+
+    #[test]
+    fn test_insert_batch_checks_signatures() {
+        use crate::string::HybridSignature;
+        use ed25519_dalek::{Signer, SigningKey};
+
+        let key = SigningKey::from_bytes(&[7u8; 32]);
+        let signed = |content: &[u8], signature: Option<HybridSignature>| {
+            let builder = RopeString::builder()
+                .content(content.to_vec())
+                .temporal_marker(LamportClock::new(NodeId::new([0u8; 32])))
+                .creator(PublicKey::from_ed25519(key.verifying_key().to_bytes()));
+            match signature {
+                Some(signature) => builder.signature(signature),
+                None => builder,
+            }
+            .build()
+            .unwrap()
+        };
+        let sign = |string: &RopeString| {
+            let signature = key.sign(&string.compute_signing_message()).to_bytes();
+            HybridSignature::new(signature, Vec::new())
+        };
+
+        let unsigned = signed(b"good", None);
+        let good = signed(b"good", Some(sign(&unsigned)));
+        assert!(good.verify_signature());
+
+        // Signature over different content
+        let forged = signed(b"forged", Some(sign(&unsigned)));
+        assert!(!forged.verify_signature());
+
+        let lattice = StringLattice::new();
+        let results = lattice.insert_batch(vec![good.clone(), forged]);
+        assert_eq!(results[0].as_ref().unwrap(), &good.id());
+        assert!(matches!(results[1], Err(RopeError::InvalidSignature)));
+    }
 }
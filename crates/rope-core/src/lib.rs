@@ -7,6 +7,12 @@
 //! - `Nucleotide` - Individual information unit within a string
 //! - `Complement` - Verification string for integrity and regeneration
 //! - `StringLattice` - The core DAG structure replacing blockchain
+//! - `StringCache` - Bounded LRU cache of string bodies backed by storage
+//! - `LatticeDiff` - Set reconciliation so peers exchange only missing strings
+//! - `codec` - Canonical, versioned wire encoding for hashing and signing
+//! - `MerkleAccumulator` - Commitment to finalized strings with inclusion proofs
+//! - `FederationClocks` - Per-federation vector clocks and conflict annotation
+//! - `SchemaRegistry` - Per-domain payload schemas and typed payload validation
 //!
 //! ## Architecture
 //!
@@ -26,25 +32,36 @@
 //!          └─────────────────────────────────────────┘
 //! ```
 
+pub mod accumulator;
+pub mod cache;
 pub mod clock;
+pub mod codec;
 pub mod complement;
 pub mod error;
 pub mod lattice;
 pub mod nucleotide;
+pub mod schema;
 pub mod string;
+pub mod sync;
 pub mod types;
 
+pub use accumulator::*;
+pub use cache::*;
 pub use clock::*;
+pub use codec::{CanonicalEncoding, WIRE_VERSION};
 pub use complement::*;
 pub use error::*;
 pub use lattice::*;
 pub use nucleotide::*;
+pub use schema::*;
 pub use string::*;
+pub use sync::*;
 pub use types::*;
 
 /// Prelude module for convenient imports
 pub mod prelude {
     pub use crate::clock::LamportClock;
+    pub use crate::codec::CanonicalEncoding;
     pub use crate::complement::Complement;
     pub use crate::error::{Result, RopeError};
     pub use crate::lattice::StringLattice;
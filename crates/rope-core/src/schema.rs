@@ -0,0 +1,784 @@
+//! Payload schema registry and typed payload validation
+//!
+//! Applications register a schema per domain, either a JSON Schema document
+//! or a protobuf `FileDescriptorSet` plus root message name. Strings built
+//! with [`RopeStringBuilder::build_validated`](crate::string::RopeStringBuilder::build_validated)
+//! are checked against their domain's schema and carry the schema hash in a
+//! two-nucleotide header, so it is covered by the string ID, the signature
+//! and the complement like any other content:
+//!
+//! ```text
+//! nucleotide 0: "RSCHEMA\x01" || payload_len (u64 BE) || 0x00 * 16
+//! nucleotide 1: schema hash
+//! nucleotide 2..: payload
+//! ```
+
+use crate::error::{Result, RopeError};
+use crate::string::RopeString;
+use hashbrown::HashMap;
+use parking_lot::RwLock;
+use prost::Message;
+use prost_types::field_descriptor_proto::{Label, Type};
+use prost_types::{DescriptorProto, FileDescriptorSet};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// BLAKE3 hash identifying a registered schema
+pub type SchemaHash = [u8; 32];
+
+/// Magic prefix of a schema header; the last byte is the header version
+pub const SCHEMA_HEADER_MAGIC: [u8; 8] = *b"RSCHEMA\x01";
+
+/// Bytes taken by the schema header (two nucleotides)
+pub const SCHEMA_HEADER_LEN: usize = 64;
+
+/// Nesting limit for both JSON documents and protobuf messages
+const MAX_DEPTH: usize = 32;
+
+/// Schema language of a registered definition
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SchemaFormat {
+    /// JSON Schema document; payloads are UTF-8 JSON
+    JsonSchema,
+    /// Encoded `FileDescriptorSet`; payloads are messages of type `message`
+    Protobuf {
+        /// Fully qualified root message name, e.g. `rope.app.Transfer`
+        message: String,
+    },
+}
+
+/// Prefix `payload` with the schema header
+pub(crate) fn encode_header(schema: &SchemaHash, payload: &[u8]) -> Vec<u8> {
+    let mut content = Vec::with_capacity(SCHEMA_HEADER_LEN + payload.len());
+    content.extend_from_slice(&SCHEMA_HEADER_MAGIC);
+    content.extend_from_slice(&(payload.len() as u64).to_be_bytes());
+    content.extend_from_slice(&[0u8; 16]);
+    content.extend_from_slice(schema);
+    content.extend_from_slice(payload);
+    content
+}
+
+/// Split string content into its schema hash and exact payload
+///
+/// Returns `None` for untyped content.
+pub fn split_header(content: &[u8]) -> Option<(SchemaHash, &[u8])> {
+    if content.len() < SCHEMA_HEADER_LEN || content[..8] != SCHEMA_HEADER_MAGIC {
+        return None;
+    }
+    if content[16..32].iter().any(|&b| b != 0) {
+        return None;
+    }
+    let payload_len = u64::from_be_bytes(content[8..16].try_into().ok()?) as usize;
+    let payload = content[SCHEMA_HEADER_LEN..].get(..payload_len)?;
+    let schema = content[32..64].try_into().ok()?;
+    Some((schema, payload))
+}
+
+/// A registered schema
+#[derive(Clone, Debug)]
+pub struct PayloadSchema {
+    format: SchemaFormat,
+    definition: Vec<u8>,
+    hash: SchemaHash,
+    compiled: Compiled,
+}
+
+#[derive(Clone, Debug)]
+enum Compiled {
+    Json(Value),
+    Protobuf(FileDescriptorSet),
+}
+
+impl PayloadSchema {
+    /// Parse and compile a schema definition
+    pub fn new(format: SchemaFormat, definition: Vec<u8>) -> Result<Self> {
+        let compiled = match &format {
+            SchemaFormat::JsonSchema => {
+                let schema: Value = serde_json::from_slice(&definition)
+                    .map_err(|e| RopeError::InvalidSchema(e.to_string()))?;
+                if !schema.is_object() && !schema.is_boolean() {
+                    return Err(RopeError::InvalidSchema(
+                        "JSON Schema must be an object or boolean".to_string(),
+                    ));
+                }
+                Compiled::Json(schema)
+            }
+            SchemaFormat::Protobuf { message } => {
+                let files = FileDescriptorSet::decode(definition.as_slice())
+                    .map_err(|e| RopeError::InvalidSchema(e.to_string()))?;
+                if find_message(&files, message).is_none() {
+                    return Err(RopeError::InvalidSchema(format!(
+                        "message {} not in descriptor set",
+                        message
+                    )));
+                }
+                Compiled::Protobuf(files)
+            }
+        };
+
+        let hash = Self::compute_hash(&format, &definition);
+        Ok(Self {
+            format,
+            definition,
+            hash,
+            compiled,
+        })
+    }
+
+    fn compute_hash(format: &SchemaFormat, definition: &[u8]) -> SchemaHash {
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(b"rope-schema-v1");
+        match format {
+            SchemaFormat::JsonSchema => {
+                hasher.update(&[0]);
+            }
+            SchemaFormat::Protobuf { message } => {
+                hasher.update(&[1]);
+                hasher.update(&(message.len() as u64).to_be_bytes());
+                hasher.update(message.as_bytes());
+            }
+        }
+        hasher.update(definition);
+        *hasher.finalize().as_bytes()
+    }
+
+    pub fn hash(&self) -> SchemaHash {
+        self.hash
+    }
+
+    pub fn format(&self) -> &SchemaFormat {
+        &self.format
+    }
+
+    pub fn definition(&self) -> &[u8] {
+        &self.definition
+    }
+
+    /// Check a payload against the schema; `Err` carries the first violation
+    pub fn check(&self, payload: &[u8]) -> std::result::Result<(), String> {
+        match (&self.compiled, &self.format) {
+            (Compiled::Json(schema), _) => {
+                let document: Value = serde_json::from_slice(payload)
+                    .map_err(|e| format!("payload is not JSON: {}", e))?;
+                check_json(schema, &document, "", 0)
+            }
+            (Compiled::Protobuf(files), SchemaFormat::Protobuf { message }) => {
+                let root = find_message(files, message)
+                    .ok_or_else(|| format!("message {} not in descriptor set", message))?;
+                check_proto(files, root, payload, 0)
+            }
+            _ => Err("schema format mismatch".to_string()),
+        }
+    }
+}
+
+/// Registry of payload schemas keyed by domain
+///
+/// Re-registering a domain replaces its current schema; earlier schemas stay
+/// resolvable by hash so existing strings keep validating.
+#[derive(Default)]
+pub struct SchemaRegistry {
+    schemas: RwLock<HashMap<SchemaHash, PayloadSchema>>,
+    domains: RwLock<HashMap<String, SchemaHash>>,
+}
+
+impl SchemaRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register (or replace) the schema for a domain
+    pub fn register(
+        &self,
+        domain: impl Into<String>,
+        format: SchemaFormat,
+        definition: Vec<u8>,
+    ) -> Result<SchemaHash> {
+        let schema = PayloadSchema::new(format, definition)?;
+        let hash = schema.hash();
+        self.schemas.write().entry(hash).or_insert(schema);
+        self.domains.write().insert(domain.into(), hash);
+        Ok(hash)
+    }
+
+    /// Current schema hash of a domain
+    pub fn domain_schema(&self, domain: &str) -> Option<SchemaHash> {
+        self.domains.read().get(domain).copied()
+    }
+
+    /// Schema by hash
+    pub fn get(&self, hash: &SchemaHash) -> Option<PayloadSchema> {
+        self.schemas.read().get(hash).cloned()
+    }
+
+    /// Validate a payload against a domain's current schema
+    pub fn validate(&self, domain: &str, payload: &[u8]) -> Result<SchemaHash> {
+        let hash = self
+            .domain_schema(domain)
+            .ok_or_else(|| RopeError::SchemaNotFound(domain.to_string()))?;
+        self.validate_with(&hash, payload)
+            .map_err(|reason| RopeError::SchemaViolation {
+                schema: domain.to_string(),
+                reason,
+            })?;
+        Ok(hash)
+    }
+
+    /// Validate a received string against the schema named in its header
+    ///
+    /// Untyped strings pass.
+    pub fn verify_string(&self, string: &RopeString) -> Result<()> {
+        let content = string.content();
+        let Some((hash, payload)) = split_header(&content) else {
+            return Ok(());
+        };
+        if !self.schemas.read().contains_key(&hash) {
+            return Err(RopeError::SchemaNotFound(hex::encode(hash)));
+        }
+        self.validate_with(&hash, payload)
+            .map_err(|reason| RopeError::SchemaViolation {
+                schema: hex::encode(hash),
+                reason,
+            })
+    }
+
+    fn validate_with(&self, hash: &SchemaHash, payload: &[u8]) -> std::result::Result<(), String> {
+        let schemas = self.schemas.read();
+        let schema = schemas.get(hash).ok_or("schema not registered")?;
+        schema.check(payload)
+    }
+}
+
+// === JSON Schema ===
+//
+// Supported keywords: type, enum, const, properties, required,
+// additionalProperties, items, minItems, maxItems, minLength, maxLength,
+// minimum, maximum. Unknown keywords are ignored, as the spec requires.
+
+fn json_type_matches(name: &str, value: &Value) -> bool {
+    match name {
+        "null" => value.is_null(),
+        "boolean" => value.is_boolean(),
+        "object" => value.is_object(),
+        "array" => value.is_array(),
+        "string" => value.is_string(),
+        "number" => value.is_number(),
+        "integer" => {
+            value.is_i64() || value.is_u64() || value.as_f64().is_some_and(|f| f.fract() == 0.0)
+        }
+        _ => false,
+    }
+}
+
+fn check_json(
+    schema: &Value,
+    value: &Value,
+    path: &str,
+    depth: usize,
+) -> std::result::Result<(), String> {
+    let at = |msg: String| {
+        if path.is_empty() {
+            format!("/: {}", msg)
+        } else {
+            format!("{}: {}", path, msg)
+        }
+    };
+
+    if depth > MAX_DEPTH {
+        return Err(at("nesting too deep".to_string()));
+    }
+    let schema = match schema {
+        Value::Bool(true) => return Ok(()),
+        Value::Bool(false) => return Err(at("no value allowed".to_string())),
+        Value::Object(schema) => schema,
+        _ => return Err(at("invalid schema".to_string())),
+    };
+
+    if let Some(expected) = schema.get("type") {
+        let matches = match expected {
+            Value::String(name) => json_type_matches(name, value),
+            Value::Array(names) => names
+                .iter()
+                .filter_map(Value::as_str)
+                .any(|name| json_type_matches(name, value)),
+            _ => true,
+        };
+        if !matches {
+            return Err(at(format!("expected type {}", expected)));
+        }
+    }
+
+    if let Some(Value::Array(options)) = schema.get("enum") {
+        if !options.contains(value) {
+            return Err(at("value not in enum".to_string()));
+        }
+    }
+    if let Some(constant) = schema.get("const") {
+        if constant != value {
+            return Err(at("value does not match const".to_string()));
+        }
+    }
+
+    match value {
+        Value::Object(object) => {
+            if let Some(Value::Array(required)) = schema.get("required") {
+                for key in required.iter().filter_map(Value::as_str) {
+                    if !object.contains_key(key) {
+                        return Err(at(format!("missing required property {}", key)));
+                    }
+                }
+            }
+            let properties = schema.get("properties").and_then(Value::as_object);
+            for (key, child) in object {
+                let child_path = format!("{}/{}", path, key);
+                match properties.and_then(|p| p.get(key)) {
+                    Some(child_schema) => check_json(child_schema, child, &child_path, depth + 1)?,
+                    None => match schema.get("additionalProperties") {
+                        Some(Value::Bool(false)) => {
+                            return Err(at(format!("unexpected property {}", key)))
+                        }
+                        Some(extra) => check_json(extra, child, &child_path, depth + 1)?,
+                        None => {}
+                    },
+                }
+            }
+        }
+        Value::Array(items) => {
+            if let Some(min) = schema.get("minItems").and_then(Value::as_u64) {
+                if (items.len() as u64) < min {
+                    return Err(at(format!("fewer than {} items", min)));
+                }
+            }
+            if let Some(max) = schema.get("maxItems").and_then(Value::as_u64) {
+                if items.len() as u64 > max {
+                    return Err(at(format!("more than {} items", max)));
+                }
+            }
+            if let Some(item_schema) = schema.get("items") {
+                for (index, item) in items.iter().enumerate() {
+                    check_json(item_schema, item, &format!("{}/{}", path, index), depth + 1)?;
+                }
+            }
+        }
+        Value::String(text) => {
+            let length = text.chars().count() as u64;
+            if let Some(min) = schema.get("minLength").and_then(Value::as_u64) {
+                if length < min {
+                    return Err(at(format!("shorter than {}", min)));
+                }
+            }
+            if let Some(max) = schema.get("maxLength").and_then(Value::as_u64) {
+                if length > max {
+                    return Err(at(format!("longer than {}", max)));
+                }
+            }
+        }
+        Value::Number(number) => {
+            let number = number.as_f64().unwrap_or(f64::NAN);
+            if let Some(min) = schema.get("minimum").and_then(Value::as_f64) {
+                if number < min {
+                    return Err(at(format!("below minimum {}", min)));
+                }
+            }
+            if let Some(max) = schema.get("maximum").and_then(Value::as_f64) {
+                if number > max {
+                    return Err(at(format!("above maximum {}", max)));
+                }
+            }
+        }
+        _ => {}
+    }
+
+    Ok(())
+}
+
+// === Protobuf ===
+//
+// Walks the wire format against the descriptor: every field must be declared
+// with a matching wire type, strings must be UTF-8, required fields must be
+// present and nested messages are checked recursively. Groups are rejected.
+
+fn find_message<'a>(files: &'a FileDescriptorSet, name: &str) -> Option<&'a DescriptorProto> {
+    let name = name.trim_start_matches('.');
+    files.file.iter().find_map(|file| {
+        let scope = file.package();
+        file.message_type
+            .iter()
+            .find_map(|message| find_nested(message, scope, name))
+    })
+}
+
+fn find_nested<'a>(
+    message: &'a DescriptorProto,
+    scope: &str,
+    name: &str,
+) -> Option<&'a DescriptorProto> {
+    let full_name = if scope.is_empty() {
+        message.name().to_string()
+    } else {
+        format!("{}.{}", scope, message.name())
+    };
+    if full_name == name {
+        return Some(message);
+    }
+    if !name.starts_with(&full_name) {
+        return None;
+    }
+    message
+        .nested_type
+        .iter()
+        .find_map(|nested| find_nested(nested, &full_name, name))
+}
+
+fn read_varint(bytes: &[u8], offset: &mut usize) -> std::result::Result<u64, String> {
+    let mut value = 0u64;
+    for shift in (0..64).step_by(7) {
+        let byte = *bytes.get(*offset).ok_or("truncated varint")?;
+        *offset += 1;
+        value |= u64::from(byte & 0x7f) << shift;
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+    Err("varint too long".to_string())
+}
+
+fn take<'a>(
+    bytes: &'a [u8],
+    offset: &mut usize,
+    len: usize,
+) -> std::result::Result<&'a [u8], String> {
+    let end = offset
+        .checked_add(len)
+        .filter(|&end| end <= bytes.len())
+        .ok_or("truncated field")?;
+    let slice = &bytes[*offset..end];
+    *offset = end;
+    Ok(slice)
+}
+
+/// Wire type a scalar field is encoded with
+fn scalar_wire_type(kind: Type) -> Option<u64> {
+    match kind {
+        Type::Int32
+        | Type::Int64
+        | Type::Uint32
+        | Type::Uint64
+        | Type::Sint32
+        | Type::Sint64
+        | Type::Bool
+        | Type::Enum => Some(0),
+        Type::Double | Type::Fixed64 | Type::Sfixed64 => Some(1),
+        Type::Float | Type::Fixed32 | Type::Sfixed32 => Some(5),
+        Type::String | Type::Bytes | Type::Message | Type::Group => None,
+    }
+}
+
+fn check_proto(
+    files: &FileDescriptorSet,
+    message: &DescriptorProto,
+    bytes: &[u8],
+    depth: usize,
+) -> std::result::Result<(), String> {
+    if depth > MAX_DEPTH {
+        return Err("nesting too deep".to_string());
+    }
+
+    let mut seen = Vec::new();
+    let mut offset = 0;
+    while offset < bytes.len() {
+        let key = read_varint(bytes, &mut offset)?;
+        let (number, wire_type) = (key >> 3, key & 7);
+        let field = message
+            .field
+            .iter()
+            .find(|f| f.number() as u64 == number)
+            .ok_or_else(|| format!("{}: unknown field {}", message.name(), number))?;
+        seen.push(field.number());
+        let name = format!("{}.{}", message.name(), field.name());
+        let kind = field.r#type();
+
+        match (scalar_wire_type(kind), wire_type) {
+            (Some(0), 0) => {
+                let value = read_varint(bytes, &mut offset)?;
+                if kind == Type::Bool && value > 1 {
+                    return Err(format!("{}: invalid bool", name));
+                }
+            }
+            (Some(1), 1) => {
+                take(bytes, &mut offset, 8)?;
+            }
+            (Some(5), 5) => {
+                take(bytes, &mut offset, 4)?;
+            }
+            // Packed repeated scalars
+            (Some(scalar), 2) if field.label() == Label::Repeated => {
+                let len = read_varint(bytes, &mut offset)? as usize;
+                let packed = take(bytes, &mut offset, len)?;
+                let mut inner = 0;
+                while inner < packed.len() {
+                    match scalar {
+                        0 => {
+                            read_varint(packed, &mut inner)?;
+                        }
+                        1 => {
+                            take(packed, &mut inner, 8)?;
+                        }
+                        _ => {
+                            take(packed, &mut inner, 4)?;
+                        }
+                    }
+                }
+            }
+            (None, 2) if kind != Type::Group => {
+                let len = read_varint(bytes, &mut offset)? as usize;
+                let value = take(bytes, &mut offset, len)?;
+                match kind {
+                    Type::String => {
+                        std::str::from_utf8(value)
+                            .map_err(|_| format!("{}: invalid UTF-8", name))?;
+                    }
+                    Type::Message => {
+                        let nested = find_message(files, field.type_name()).ok_or_else(|| {
+                            format!("{}: unknown type {}", name, field.type_name())
+                        })?;
+                        check_proto(files, nested, value, depth + 1)?;
+                    }
+                    _ => {}
+                }
+            }
+            _ => return Err(format!("{}: unexpected wire type {}", name, wire_type)),
+        }
+    }
+
+    for field in &message.field {
+        if field.label() == Label::Required && !seen.contains(&field.number()) {
+            return Err(format!(
+                "{}: missing required field {}",
+                message.name(),
+                field.name()
+            ));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::LamportClock;
+    use crate::string::PublicKey;
+    use crate::types::NodeId;
+    use prost_types::{FieldDescriptorProto, FileDescriptorProto};
+
+    const TRANSFER_SCHEMA: &str = r#"{
+        "type": "object",
+        "required": ["to", "amount"],
+        "additionalProperties": false,
+        "properties": {
+            "to": {"type": "string", "minLength": 1},
+            "amount": {"type": "integer", "minimum": 0},
+            "memo": {"type": ["string", "null"], "maxLength": 16}
+        }
+    }"#;
+
+    fn builder() -> crate::string::RopeStringBuilder {
+        RopeString::builder()
+            .temporal_marker(LamportClock::new(NodeId::new([0u8; 32])))
+            .creator(PublicKey::from_ed25519([0u8; 32]))
+    }
+
+    fn field(name: &str, number: i32, kind: Type, label: Label) -> FieldDescriptorProto {
+        FieldDescriptorProto {
+            name: Some(name.to_string()),
+            number: Some(number),
+            r#type: Some(kind as i32),
+            label: Some(label as i32),
+            ..Default::default()
+        }
+    }
+
+    fn transfer_descriptor() -> Vec<u8> {
+        let transfer = DescriptorProto {
+            name: Some("Transfer".to_string()),
+            field: vec![
+                field("to", 1, Type::String, Label::Required),
+                field("amount", 2, Type::Uint64, Label::Optional),
+                field("tags", 3, Type::Uint32, Label::Repeated),
+            ],
+            ..Default::default()
+        };
+        FileDescriptorSet {
+            file: vec![FileDescriptorProto {
+                name: Some("transfer.proto".to_string()),
+                package: Some("rope.app".to_string()),
+                message_type: vec![transfer],
+                ..Default::default()
+            }],
+        }
+        .encode_to_vec()
+    }
+
+    #[test]
+    fn test_header_roundtrip() {
+        let hash = [9u8; 32];
+        let content = encode_header(&hash, b"payload");
+        let (schema, payload) = split_header(&content).unwrap();
+        assert_eq!(schema, hash);
+        assert_eq!(payload, b"payload");
+
+        assert!(split_header(b"plain content").is_none());
+        // Padding from the nucleotide sequence is ignored
+        let mut padded = content.clone();
+        padded.extend_from_slice(&[0u8; 25]);
+        assert_eq!(split_header(&padded).unwrap().1, b"payload");
+    }
+
+    #[test]
+    fn test_json_schema_validation() {
+        let registry = SchemaRegistry::new();
+        let hash = registry
+            .register(
+                "transfers",
+                SchemaFormat::JsonSchema,
+                TRANSFER_SCHEMA.into(),
+            )
+            .unwrap();
+        assert_eq!(registry.domain_schema("transfers"), Some(hash));
+
+        assert_eq!(
+            registry
+                .validate("transfers", br#"{"to":"alice","amount":5}"#)
+                .unwrap(),
+            hash
+        );
+
+        for bad in [
+            &br#"{"to":"alice"}"#[..],
+            br#"{"to":"alice","amount":-1}"#,
+            br#"{"to":"","amount":1}"#,
+            br#"{"to":"alice","amount":1,"extra":true}"#,
+            br#"{"to":"alice","amount":1,"memo":"far too long for the limit"}"#,
+            b"not json",
+        ] {
+            assert!(
+                matches!(
+                    registry.validate("transfers", bad),
+                    Err(RopeError::SchemaViolation { .. })
+                ),
+                "{}",
+                String::from_utf8_lossy(bad)
+            );
+        }
+
+        assert!(matches!(
+            registry.validate("unknown", b"{}"),
+            Err(RopeError::SchemaNotFound(_))
+        ));
+        assert!(registry
+            .register("broken", SchemaFormat::JsonSchema, b"[1, 2]".to_vec())
+            .is_err());
+    }
+
+    #[test]
+    fn test_protobuf_validation() {
+        let registry = SchemaRegistry::new();
+        let format = SchemaFormat::Protobuf {
+            message: "rope.app.Transfer".to_string(),
+        };
+        registry
+            .register("transfers", format, transfer_descriptor())
+            .unwrap();
+
+        // to = "bob", amount = 7, tags = [1, 2] packed
+        let valid = [0x0a, 3, b'b', b'o', b'b', 0x10, 7, 0x1a, 2, 1, 2];
+        assert!(registry.validate("transfers", &valid).is_ok());
+
+        // Missing required `to`
+        assert!(registry.validate("transfers", &[0x10, 7]).is_err());
+        // Unknown field 9
+        assert!(registry
+            .validate("transfers", &[0x0a, 1, b'x', 0x48, 1])
+            .is_err());
+        // amount sent as fixed64
+        assert!(registry
+            .validate("transfers", &[0x0a, 1, b'x', 0x11, 0, 0, 0, 0, 0, 0, 0, 0])
+            .is_err());
+        // Invalid UTF-8 in `to`
+        assert!(registry.validate("transfers", &[0x0a, 1, 0xff]).is_err());
+
+        let missing = SchemaFormat::Protobuf {
+            message: "rope.app.Missing".to_string(),
+        };
+        assert!(registry
+            .register("other", missing, transfer_descriptor())
+            .is_err());
+    }
+
+    #[test]
+    fn test_build_validated_records_schema() {
+        let registry = SchemaRegistry::new();
+        let hash = registry
+            .register(
+                "transfers",
+                SchemaFormat::JsonSchema,
+                TRANSFER_SCHEMA.into(),
+            )
+            .unwrap();
+
+        let payload = br#"{"to":"alice","amount":5}"#;
+        let string = builder()
+            .content(payload.to_vec())
+            .build_validated(&registry, "transfers")
+            .unwrap();
+        assert_eq!(string.schema_hash(), Some(hash));
+        assert_eq!(string.payload(), payload);
+        assert!(registry.verify_string(&string).is_ok());
+
+        assert!(builder()
+            .content(br#"{"to":"alice"}"#.to_vec())
+            .build_validated(&registry, "transfers")
+            .is_err());
+
+        // Untyped strings verify trivially and cannot forge a header
+        let untyped = builder().content(b"raw".to_vec()).build().unwrap();
+        assert_eq!(untyped.schema_hash(), None);
+        assert!(registry.verify_string(&untyped).is_ok());
+        assert!(builder()
+            .content(encode_header(&hash, b"{}"))
+            .build()
+            .is_err());
+    }
+
+    #[test]
+    fn test_schema_replacement_keeps_old_strings_valid() {
+        let registry = SchemaRegistry::new();
+        registry
+            .register(
+                "transfers",
+                SchemaFormat::JsonSchema,
+                TRANSFER_SCHEMA.into(),
+            )
+            .unwrap();
+        let string = builder()
+            .content(br#"{"to":"alice","amount":5}"#.to_vec())
+            .build_validated(&registry, "transfers")
+            .unwrap();
+
+        let v2 = registry
+            .register(
+                "transfers",
+                SchemaFormat::JsonSchema,
+                br#"{"type":"array"}"#.to_vec(),
+            )
+            .unwrap();
+        assert_eq!(registry.domain_schema("transfers"), Some(v2));
+        assert!(registry.verify_string(&string).is_ok());
+
+        // A registry that never saw the schema cannot vouch for the string
+        assert!(matches!(
+            SchemaRegistry::new().verify_string(&string),
+            Err(RopeError::SchemaNotFound(_))
+        ));
+    }
+}
@@ -10,7 +10,10 @@
 //! - μ (Mu): Mutability Class - erasure policy
 
 use crate::clock::LamportClock;
-use crate::nucleotide::NucleotideSequence;
+use crate::codec::{tags, CanonicalEncoding, Decoder, Encoder, WireField};
+use crate::error::RopeError;
+use crate::nucleotide::{Nucleotide, NucleotideSequence};
+use crate::schema::{self, SchemaHash, SchemaRegistry};
 use crate::types::{constants, MutabilityClass, NodeId, StringId};
 use serde::{Deserialize, Serialize};
 use serde_bytes;
@@ -177,6 +180,49 @@ impl RopeString {
         self.sequence.to_raw_bytes()
     }
 
+    /// Schema hash recorded in the sequence header, if the payload is typed
+    pub fn schema_hash(&self) -> Option<SchemaHash> {
+        schema::split_header(&self.content()).map(|(hash, _)| hash)
+    }
+
+    /// Application payload: content without the schema header or padding
+    ///
+    /// Untyped strings return their full (padded) content.
+    pub fn payload(&self) -> Vec<u8> {
+        let content = self.content();
+        match schema::split_header(&content) {
+            Some((_, payload)) => payload.to_vec(),
+            None => content,
+        }
+    }
+
+    /// Same string with a replacement sequence (used after complement repair)
+    pub(crate) fn with_sequence(&self, sequence: NucleotideSequence) -> Self {
+        Self {
+            sequence,
+            ..self.clone()
+        }
+    }
+
+    /// Same string around a repaired sequence, with its ID recomputed
+    ///
+    /// A string decoded from damaged bytes carries an ID derived from the
+    /// damage; after repair the ID should match the original again.
+    pub fn with_repaired_sequence(&self, sequence: NucleotideSequence) -> Self {
+        let id = Self::compute_id(
+            &sequence,
+            &self.temporal_marker,
+            &self.parentage,
+            self.replication_factor,
+            &self.mutability_class,
+        );
+        Self {
+            id,
+            sequence,
+            ..self.clone()
+        }
+    }
+
     /// Get temporal marker (τ)
     pub fn temporal_marker(&self) -> &LamportClock {
         &self.temporal_marker
@@ -237,6 +283,23 @@ impl RopeString {
         message
     }
 
+    /// Verify the Ed25519 half of the hybrid signature against the creator
+    ///
+    /// The Dilithium half is checked by `rope-crypto`.
+    pub fn verify_signature(&self) -> bool {
+        let Ok(key) = ed25519_dalek::VerifyingKey::from_bytes(&self.creator.ed25519) else {
+            return false;
+        };
+        let Ok(signature) = <[u8; 64]>::try_from(self.signature.ed25519_sig.as_slice()) else {
+            return false;
+        };
+        key.verify_strict(
+            &self.compute_signing_message(),
+            &ed25519_dalek::Signature::from_bytes(&signature),
+        )
+        .is_ok()
+    }
+
     /// Compute StringId from content
     fn compute_id(
         sequence: &NucleotideSequence,
@@ -276,6 +339,70 @@ impl RopeString {
     }
 }
 
+impl CanonicalEncoding for RopeString {
+    const TAG: u8 = tags::ROPE_STRING;
+
+    /// σ, τ, π, ρ, μ followed by OES data, signature and creator; the ID is
+    /// derived and not encoded
+    fn encode_body(&self, enc: &mut Encoder) {
+        enc.put_varint(self.sequence.len() as u64);
+        for nucleotide in self.sequence.iter() {
+            enc.put_fixed(nucleotide.value());
+        }
+        self.temporal_marker.encode_field(enc);
+        self.parentage.encode_field(enc);
+        enc.put_varint(self.replication_factor as u64);
+        self.mutability_class.encode_field(enc);
+        enc.put_varint(self.oes_generation);
+        self.oes_proof.encode_field(enc);
+        self.signature.encode_field(enc);
+        self.creator.encode_field(enc);
+    }
+
+    fn decode_body(dec: &mut Decoder<'_>) -> crate::error::Result<Self> {
+        let count = dec.count(32)?;
+        let mut sequence = NucleotideSequence::new();
+        for position in 0..count {
+            sequence.push(Nucleotide::new(dec.fixed()?, position as u64));
+        }
+        if sequence.len() * 32 > constants::MAX_STRING_SIZE {
+            return Err(RopeError::ContentTooLarge {
+                max: constants::MAX_STRING_SIZE,
+            });
+        }
+
+        let temporal_marker = LamportClock::decode_field(dec)?;
+        let parentage = Vec::<StringId>::decode_field(dec)?;
+        let replication_factor = dec.varint_u32()?;
+        let mutability_class = MutabilityClass::decode_field(dec)?;
+        let oes_generation = dec.varint()?;
+        let oes_proof = OESProof::decode_field(dec)?;
+        let signature = HybridSignature::decode_field(dec)?;
+        let creator = PublicKey::decode_field(dec)?;
+
+        let id = RopeString::compute_id(
+            &sequence,
+            &temporal_marker,
+            &parentage,
+            replication_factor,
+            &mutability_class,
+        );
+
+        Ok(RopeString {
+            id,
+            sequence,
+            temporal_marker,
+            parentage,
+            replication_factor,
+            mutability_class,
+            oes_generation,
+            oes_proof,
+            signature,
+            creator,
+        })
+    }
+}
+
 /// Builder pattern for constructing RopeStrings
 pub struct RopeStringBuilder {
     content: Option<Vec<u8>>,
@@ -287,6 +414,7 @@ pub struct RopeStringBuilder {
     oes_proof: OESProof,
     signature: HybridSignature,
     creator: Option<PublicKey>,
+    schema: Option<SchemaHash>,
 }
 
 impl RopeStringBuilder {
@@ -301,6 +429,7 @@ impl RopeStringBuilder {
             oes_proof: OESProof::empty(),
             signature: HybridSignature::empty(),
             creator: None,
+            schema: None,
         }
     }
 
@@ -354,11 +483,25 @@ impl RopeStringBuilder {
         self
     }
 
+    /// Declare the payload schema; the hash is recorded in the sequence header
+    pub fn schema(mut self, hash: SchemaHash) -> Self {
+        self.schema = Some(hash);
+        self
+    }
+
     pub fn build(self) -> Result<RopeString, &'static str> {
         let content = self.content.ok_or("Content is required")?;
         let temporal_marker = self.temporal_marker.ok_or("Temporal marker is required")?;
         let creator = self.creator.ok_or("Creator is required")?;
 
+        let content = match &self.schema {
+            Some(hash) => schema::encode_header(hash, &content),
+            None if schema::split_header(&content).is_some() => {
+                return Err("Untyped content must not start with a schema header");
+            }
+            None => content,
+        };
+
         if content.len() > constants::MAX_STRING_SIZE {
             return Err("Content exceeds maximum size");
         }
@@ -386,6 +529,22 @@ impl RopeStringBuilder {
             creator,
         })
     }
+
+    /// Validate the content against `domain`'s schema, then build with the
+    /// schema hash recorded
+    pub fn build_validated(
+        mut self,
+        registry: &SchemaRegistry,
+        domain: &str,
+    ) -> crate::error::Result<RopeString> {
+        let content = self
+            .content
+            .as_deref()
+            .ok_or_else(|| RopeError::InvalidInput("Content is required".to_string()))?;
+        self.schema = Some(registry.validate(domain, content)?);
+        self.build()
+            .map_err(|e| RopeError::InvalidInput(e.to_string()))
+    }
 }
 
 impl Default for RopeStringBuilder {
@@ -473,4 +632,25 @@ mod tests {
 
         assert!(string.verify_sequence());
     }
+
+    #[test]
+    fn test_repaired_sequence_recomputes_id() {
+        let string = RopeString::builder()
+            .content(b"Content that gets damaged at rest".to_vec())
+            .temporal_marker(make_test_clock())
+            .creator(make_test_creator())
+            .build()
+            .unwrap();
+
+        // Decoding damaged bytes yields an ID derived from the damage
+        let mut damaged = string.sequence().clone();
+        *damaged.get_mut(0).unwrap() = Nucleotide::new([0xEE; 32], 0);
+        let decoded =
+            RopeString::from_canonical_bytes(&string.with_sequence(damaged).to_canonical_bytes())
+                .unwrap();
+        assert_ne!(decoded.id(), string.id());
+
+        let repaired = decoded.with_repaired_sequence(string.sequence().clone());
+        assert_eq!(repaired.id(), string.id());
+    }
 }
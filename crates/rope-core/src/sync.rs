@@ -0,0 +1,332 @@
+//! Lattice set reconciliation
+//!
+//! Two nodes find the strings one has and the other lacks by exchanging
+//! invertible Bloom filters (IBFs) over their string IDs. An IBF costs
+//! O(d) space for a symmetric difference of size d, independent of lattice
+//! size. Subtracting the remote filter from the local one cancels shared IDs,
+//! and peeling the remaining pure cells recovers the difference.
+//!
+//! Sizing: a filter with ~1.5-2x as many cells as the expected difference
+//! decodes with high probability. When decoding fails, retry with a larger
+//! filter.
+
+use crate::codec::{tags, CanonicalEncoding, Decoder, Encoder};
+use crate::error::{Result, RopeError};
+use crate::types::StringId;
+use serde::{Deserialize, Serialize};
+
+/// Hash functions (and cell partitions) per ID
+const HASH_COUNT: usize = 3;
+
+/// Smallest filter worth sending
+pub const MIN_CELLS: usize = 12;
+
+/// Largest filter accepted from a peer
+pub const MAX_CELLS: usize = 1 << 20;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+struct Cell {
+    count: i64,
+    id_sum: [u8; 32],
+    hash_sum: u64,
+}
+
+impl Cell {
+    /// Counts wrap: peer cells can hold any `i64`, and differences only
+    /// need to be right modulo 2^64
+    fn toggle(&mut self, id: &[u8; 32], checksum: u64, sign: i64) {
+        self.count = self.count.wrapping_add(sign);
+        for (acc, byte) in self.id_sum.iter_mut().zip(id) {
+            *acc ^= byte;
+        }
+        self.hash_sum ^= checksum;
+    }
+
+    fn is_empty(&self) -> bool {
+        self.count == 0 && self.hash_sum == 0 && self.id_sum == [0u8; 32]
+    }
+
+    /// Holds exactly one ID (from either side)
+    fn is_pure(&self) -> bool {
+        (self.count == 1 || self.count == -1) && checksum(&self.id_sum) == self.hash_sum
+    }
+}
+
+fn id_hash(id: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(b"rope-ibf-v1");
+    hasher.update(id);
+    *hasher.finalize().as_bytes()
+}
+
+fn checksum(id: &[u8; 32]) -> u64 {
+    u64::from_le_bytes(id_hash(id)[..8].try_into().unwrap())
+}
+
+/// Invertible Bloom filter over string IDs
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InvertibleBloomFilter {
+    cells: Vec<Cell>,
+}
+
+impl InvertibleBloomFilter {
+    /// Filter with `cells` cells, rounded up to a multiple of the hash count
+    pub fn new(cells: usize) -> Self {
+        let cells = cells.clamp(MIN_CELLS, MAX_CELLS).div_ceil(HASH_COUNT) * HASH_COUNT;
+        Self {
+            cells: vec![Cell::default(); cells],
+        }
+    }
+
+    /// Filter sized for an expected symmetric difference
+    pub fn for_difference(expected: usize) -> Self {
+        Self::new(expected.saturating_mul(2))
+    }
+
+    pub fn cell_count(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// One cell per partition, so an ID never hits the same cell twice
+    fn indices(&self, id: &[u8; 32]) -> [usize; HASH_COUNT] {
+        let hash = id_hash(id);
+        let partition = self.cells.len() / HASH_COUNT;
+        let mut indices = [0; HASH_COUNT];
+        for (i, index) in indices.iter_mut().enumerate() {
+            let start = 8 + i * 8;
+            let h = u64::from_le_bytes(hash[start..start + 8].try_into().unwrap());
+            *index = i * partition + (h % partition as u64) as usize;
+        }
+        indices
+    }
+
+    fn toggle(&mut self, id: &[u8; 32], sign: i64) {
+        let sum = checksum(id);
+        for index in self.indices(id) {
+            self.cells[index].toggle(id, sum, sign);
+        }
+    }
+
+    pub fn insert(&mut self, id: &StringId) {
+        self.toggle(id.as_bytes(), 1);
+    }
+
+    pub fn remove(&mut self, id: &StringId) {
+        self.toggle(id.as_bytes(), -1);
+    }
+
+    /// Cell-wise `self - other`; both filters must have the same size
+    pub fn subtract(&self, other: &InvertibleBloomFilter) -> Result<InvertibleBloomFilter> {
+        if self.cells.len() != other.cells.len() {
+            return Err(RopeError::InvalidInput(format!(
+                "filter size mismatch: {} vs {}",
+                self.cells.len(),
+                other.cells.len()
+            )));
+        }
+        let mut difference = self.clone();
+        for (cell, theirs) in difference.cells.iter_mut().zip(&other.cells) {
+            cell.toggle(&theirs.id_sum, theirs.hash_sum, theirs.count.wrapping_neg());
+        }
+        Ok(difference)
+    }
+
+    /// Peel a difference filter into (positive, negative) IDs
+    ///
+    /// Returns `None` if the filter is too small for the difference.
+    pub fn decode(&self) -> Option<(Vec<StringId>, Vec<StringId>)> {
+        let mut filter = self.clone();
+        let mut positive = Vec::new();
+        let mut negative = Vec::new();
+
+        let mut queue: Vec<usize> = (0..filter.cells.len())
+            .filter(|&i| filter.cells[i].is_pure())
+            .collect();
+        while let Some(index) = queue.pop() {
+            let cell = filter.cells[index];
+            if !cell.is_pure() {
+                continue;
+            }
+            let id = cell.id_sum;
+            if cell.count == 1 {
+                positive.push(StringId::new(id));
+            } else {
+                negative.push(StringId::new(id));
+            }
+            filter.toggle(&id, -cell.count);
+            for next in filter.indices(&id) {
+                if filter.cells[next].is_pure() {
+                    queue.push(next);
+                }
+            }
+        }
+
+        if !filter.cells.iter().all(Cell::is_empty) {
+            return None;
+        }
+        positive.sort();
+        negative.sort();
+        Some((positive, negative))
+    }
+}
+
+impl CanonicalEncoding for InvertibleBloomFilter {
+    const TAG: u8 = tags::SYNC_FILTER;
+
+    fn encode_body(&self, enc: &mut Encoder) {
+        enc.put_varint(self.cells.len() as u64);
+        for cell in &self.cells {
+            enc.put_signed(cell.count);
+            enc.put_fixed(&cell.id_sum);
+            enc.put_fixed(&cell.hash_sum.to_le_bytes());
+        }
+    }
+
+    fn decode_body(dec: &mut Decoder<'_>) -> Result<Self> {
+        let count = dec.count(41)?;
+        if !(MIN_CELLS..=MAX_CELLS).contains(&count) || count % HASH_COUNT != 0 {
+            return Err(RopeError::SerializationError(format!(
+                "invalid filter size {}",
+                count
+            )));
+        }
+        let cells = (0..count)
+            .map(|_| {
+                Ok(Cell {
+                    count: dec.signed()?,
+                    id_sum: dec.fixed()?,
+                    hash_sum: u64::from_le_bytes(dec.fixed()?),
+                })
+            })
+            .collect::<Result<_>>()?;
+        Ok(Self { cells })
+    }
+}
+
+/// Strings each side lacks, from the local node's point of view
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LatticeDiff {
+    /// Strings the remote node has and we lack
+    pub missing_locally: Vec<StringId>,
+    /// Strings we have and the remote node lacks
+    pub missing_remotely: Vec<StringId>,
+}
+
+impl LatticeDiff {
+    /// Reconcile a local set against a remote filter of the same size
+    pub fn compute(local: &InvertibleBloomFilter, remote: &InvertibleBloomFilter) -> Result<Self> {
+        let (missing_remotely, missing_locally) =
+            local
+                .subtract(remote)?
+                .decode()
+                .ok_or(RopeError::ReconciliationFailed {
+                    cells: local.cell_count(),
+                })?;
+        Ok(Self {
+            missing_locally,
+            missing_remotely,
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.missing_locally.is_empty() && self.missing_remotely.is_empty()
+    }
+
+    /// Size of the symmetric difference
+    pub fn len(&self) -> usize {
+        self.missing_locally.len() + self.missing_remotely.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn id(n: u32) -> StringId {
+        StringId::from_content(&n.to_be_bytes())
+    }
+
+    fn filter(ids: impl Iterator<Item = u32>, cells: usize) -> InvertibleBloomFilter {
+        let mut filter = InvertibleBloomFilter::new(cells);
+        for n in ids {
+            filter.insert(&id(n));
+        }
+        filter
+    }
+
+    #[test]
+    fn test_identical_sets_cancel() {
+        let a = filter(0..1000, 30);
+        let b = filter(0..1000, 30);
+        let diff = LatticeDiff::compute(&a, &b).unwrap();
+        assert!(diff.is_empty());
+    }
+
+    #[test]
+    fn test_recovers_symmetric_difference() {
+        // Shared 0..5000; local adds 5000..5010, remote adds 6000..6005
+        let local = filter((0..5000).chain(5000..5010), 60);
+        let remote = filter((0..5000).chain(6000..6005), 60);
+
+        let diff = LatticeDiff::compute(&local, &remote).unwrap();
+        let mut expected_remote: Vec<_> = (5000..5010).map(id).collect();
+        expected_remote.sort();
+        let mut expected_local: Vec<_> = (6000..6005).map(id).collect();
+        expected_local.sort();
+        assert_eq!(diff.missing_remotely, expected_remote);
+        assert_eq!(diff.missing_locally, expected_local);
+        assert_eq!(diff.len(), 15);
+    }
+
+    #[test]
+    fn test_undersized_filter_fails() {
+        let local = filter(0..200, MIN_CELLS);
+        let remote = InvertibleBloomFilter::new(MIN_CELLS);
+        assert!(matches!(
+            LatticeDiff::compute(&local, &remote),
+            Err(RopeError::ReconciliationFailed { .. })
+        ));
+
+        let other = InvertibleBloomFilter::new(MIN_CELLS * 2);
+        assert!(LatticeDiff::compute(&local, &other).is_err());
+    }
+
+    #[test]
+    fn test_filter_canonical_roundtrip() {
+        let original = filter(0..50, 24);
+        let bytes = original.to_canonical_bytes();
+        assert_eq!(
+            InvertibleBloomFilter::from_canonical_bytes(&bytes).unwrap(),
+            original
+        );
+
+        // Sizes that are not a partition multiple are rejected
+        let mut enc = Encoder::new();
+        enc.put_u8(crate::codec::WIRE_VERSION);
+        enc.put_u8(tags::SYNC_FILTER);
+        enc.put_varint(13);
+        for _ in 0..13 {
+            enc.put_signed(0);
+            enc.put_fixed(&[0u8; 40]);
+        }
+        assert!(InvertibleBloomFilter::from_canonical_bytes(&enc.finish()).is_err());
+    }
+
+    #[test]
+    fn test_extreme_peer_counts() {
+        let local = filter(0..10, 24);
+        let mut enc = Encoder::new();
+        enc.put_u8(crate::codec::WIRE_VERSION);
+        enc.put_u8(tags::SYNC_FILTER);
+        enc.put_varint(24);
+        for count in [i64::MIN, i64::MAX].iter().cycle().take(24) {
+            enc.put_signed(*count);
+            enc.put_fixed(&[0u8; 40]);
+        }
+        let remote = InvertibleBloomFilter::from_canonical_bytes(&enc.finish()).unwrap();
+
+        // Garbage fails to decode instead of overflowing
+        assert!(LatticeDiff::compute(&local, &remote).is_err());
+        assert!(LatticeDiff::compute(&remote, &local).is_err());
+    }
+}
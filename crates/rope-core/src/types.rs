@@ -10,7 +10,7 @@ use std::time::Duration;
 /// StringId - Unique identifier for strings computed from BLAKE3 hash
 ///
 /// StringId = BLAKE3(σ || τ || π || ρ || μ)
-#[derive(Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize)]
 pub struct StringId {
     /// 256-bit BLAKE3 hash
     hash: [u8; 32],
@@ -65,7 +65,7 @@ impl fmt::Display for StringId {
 }
 
 /// NodeId - Unique identifier for network nodes
-#[derive(Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct NodeId {
     /// Derived from public key hash
     id: [u8; 32],
@@ -329,6 +329,32 @@ pub enum ErasureReason {
     CourtOrder,
 }
 
+/// Entity or contract that can be quarantined by the security layer
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum QuarantineSubject {
+    /// Node, agent or account identified by its 32-byte ID
+    Entity([u8; 32]),
+    /// EVM-style contract or account address
+    Contract([u8; 20]),
+}
+
+impl fmt::Display for QuarantineSubject {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            QuarantineSubject::Entity(id) => write!(f, "entity:{}", hex::encode(id)),
+            QuarantineSubject::Contract(addr) => write!(f, "contract:0x{}", hex::encode(addr)),
+        }
+    }
+}
+
+/// Read-only view of the quarantine list
+///
+/// Implemented by the security layer and consulted by the invocation engine and
+/// bridges before acting on behalf of a subject.
+pub trait QuarantineList: Send + Sync {
+    fn is_quarantined(&self, subject: &QuarantineSubject) -> bool;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,265 @@
+//! BLS12-381 aggregate signatures
+//!
+//! Used to compress a 2f+1 testimony set into a single aggregate signature.
+//! Keys live in G1 (48 bytes) and signatures in G2 (96 bytes) ("min_pk").
+//!
+//! ## Rogue-key protection
+//!
+//! All testimonies in an aggregate sign the same message, so verification
+//! uses `fast_aggregate_verify`. That is only sound when every public key
+//! was registered together with a proof of possession, see
+//! [`BlsSecretKey::prove_possession`] and [`BlsPublicKey::verify_possession`].
+
+use blst::min_pk::{AggregateSignature, PublicKey, SecretKey, Signature};
+use blst::BLST_ERROR;
+use rand::rngs::OsRng;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroize;
+
+use crate::error::{CryptoError, Result};
+
+/// Domain separation tag for message signatures
+pub const BLS_SIGNATURE_DST: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+
+/// Domain separation tag for proofs of possession
+pub const BLS_POP_DST: &[u8] = b"BLS_POP_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+
+/// BLS secret key size
+pub const BLS_SECRET_KEY_SIZE: usize = 32;
+
+/// BLS public key size (compressed G1 point)
+pub const BLS_PUBLIC_KEY_SIZE: usize = 48;
+
+/// BLS signature size (compressed G2 point)
+pub const BLS_SIGNATURE_SIZE: usize = 96;
+
+/// BLS secret key
+pub struct BlsSecretKey {
+    inner: SecretKey,
+}
+
+impl BlsSecretKey {
+    /// Generate a new random secret key
+    pub fn generate() -> Self {
+        let mut ikm = [0u8; 32];
+        OsRng.fill_bytes(&mut ikm);
+        let key = Self::from_seed(&ikm);
+        ikm.zeroize();
+        key
+    }
+
+    /// Derive a secret key deterministically from a 32-byte seed
+    pub fn from_seed(seed: &[u8; 32]) -> Self {
+        // key_gen only fails when ikm is shorter than 32 bytes
+        let inner = SecretKey::key_gen(seed, b"rope-bls-v1").expect("32-byte ikm is always valid");
+        Self { inner }
+    }
+
+    /// Parse from raw bytes
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        SecretKey::from_bytes(bytes)
+            .map(|inner| Self { inner })
+            .map_err(|e| CryptoError::InvalidSecretKey(format!("BLS: {:?}", e)))
+    }
+
+    /// Export raw bytes
+    pub fn to_bytes(&self) -> [u8; BLS_SECRET_KEY_SIZE] {
+        self.inner.to_bytes()
+    }
+
+    /// Get the corresponding public key
+    pub fn public_key(&self) -> BlsPublicKey {
+        BlsPublicKey {
+            bytes: self.inner.sk_to_pk().to_bytes().to_vec(),
+        }
+    }
+
+    /// Sign a message
+    pub fn sign(&self, message: &[u8]) -> BlsSignature {
+        BlsSignature {
+            bytes: self
+                .inner
+                .sign(message, BLS_SIGNATURE_DST, &[])
+                .to_bytes()
+                .to_vec(),
+        }
+    }
+
+    /// Produce a proof of possession over our own public key
+    pub fn prove_possession(&self) -> BlsSignature {
+        let pk = self.inner.sk_to_pk().to_bytes();
+        BlsSignature {
+            bytes: self.inner.sign(&pk, BLS_POP_DST, &[]).to_bytes().to_vec(),
+        }
+    }
+}
+
+/// BLS public key (compressed G1 point)
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct BlsPublicKey {
+    #[serde(with = "serde_bytes")]
+    bytes: Vec<u8>,
+}
+
+impl BlsPublicKey {
+    /// Parse and validate a compressed public key
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        PublicKey::key_validate(bytes)
+            .map_err(|e| CryptoError::InvalidPublicKey(format!("BLS: {:?}", e)))?;
+        Ok(Self {
+            bytes: bytes.to_vec(),
+        })
+    }
+
+    /// Raw compressed bytes
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    fn to_point(&self) -> Result<PublicKey> {
+        PublicKey::from_bytes(&self.bytes)
+            .map_err(|e| CryptoError::InvalidPublicKey(format!("BLS: {:?}", e)))
+    }
+
+    /// Verify a proof of possession for this key
+    pub fn verify_possession(&self, proof: &BlsSignature) -> Result<()> {
+        let pk = self.to_point()?;
+        let sig = proof.to_point()?;
+        check(sig.verify(true, &self.bytes, BLS_POP_DST, &[], &pk, true))
+    }
+
+    /// Verify a single signature
+    pub fn verify(&self, message: &[u8], signature: &BlsSignature) -> Result<()> {
+        let pk = self.to_point()?;
+        let sig = signature.to_point()?;
+        check(sig.verify(true, message, BLS_SIGNATURE_DST, &[], &pk, true))
+    }
+}
+
+/// BLS signature (compressed G2 point), single or aggregate
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlsSignature {
+    #[serde(with = "serde_bytes")]
+    bytes: Vec<u8>,
+}
+
+impl BlsSignature {
+    /// Parse a compressed signature
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        Signature::from_bytes(bytes)
+            .map_err(|e| CryptoError::InvalidSignature(format!("BLS: {:?}", e)))?;
+        Ok(Self {
+            bytes: bytes.to_vec(),
+        })
+    }
+
+    /// Raw compressed bytes
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    fn to_point(&self) -> Result<Signature> {
+        Signature::from_bytes(&self.bytes)
+            .map_err(|e| CryptoError::InvalidSignature(format!("BLS: {:?}", e)))
+    }
+
+    /// Aggregate several signatures into one
+    pub fn aggregate(signatures: &[BlsSignature]) -> Result<Self> {
+        if signatures.is_empty() {
+            return Err(CryptoError::InvalidSignature(
+                "BLS: cannot aggregate an empty set".to_string(),
+            ));
+        }
+        let points = signatures
+            .iter()
+            .map(|s| s.to_point())
+            .collect::<Result<Vec<_>>>()?;
+        let refs: Vec<&Signature> = points.iter().collect();
+        let aggregate = AggregateSignature::aggregate(&refs, true)
+            .map_err(|e| CryptoError::InvalidSignature(format!("BLS: {:?}", e)))?;
+        Ok(Self {
+            bytes: aggregate.to_signature().to_bytes().to_vec(),
+        })
+    }
+
+    /// Verify an aggregate where every signer signed the same message
+    ///
+    /// Callers must only pass keys whose proof of possession was verified.
+    pub fn verify_aggregate(&self, message: &[u8], public_keys: &[BlsPublicKey]) -> Result<()> {
+        if public_keys.is_empty() {
+            return Err(CryptoError::VerificationFailed);
+        }
+        let sig = self.to_point()?;
+        let points = public_keys
+            .iter()
+            .map(|pk| pk.to_point())
+            .collect::<Result<Vec<_>>>()?;
+        let refs: Vec<&PublicKey> = points.iter().collect();
+        check(sig.fast_aggregate_verify(true, message, BLS_SIGNATURE_DST, &refs))
+    }
+}
+
+fn check(result: BLST_ERROR) -> Result<()> {
+    if result == BLST_ERROR::BLST_SUCCESS {
+        Ok(())
+    } else {
+        Err(CryptoError::VerificationFailed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sign_and_verify() {
+        let sk = BlsSecretKey::generate();
+        let pk = sk.public_key();
+        let sig = sk.sign(b"testimony");
+
+        assert_eq!(pk.as_bytes().len(), BLS_PUBLIC_KEY_SIZE);
+        assert_eq!(sig.as_bytes().len(), BLS_SIGNATURE_SIZE);
+        assert!(pk.verify(b"testimony", &sig).is_ok());
+        assert!(pk.verify(b"other", &sig).is_err());
+    }
+
+    #[test]
+    fn test_deterministic_from_seed() {
+        let a = BlsSecretKey::from_seed(&[7u8; 32]);
+        let b = BlsSecretKey::from_seed(&[7u8; 32]);
+        assert_eq!(a.public_key(), b.public_key());
+    }
+
+    #[test]
+    fn test_proof_of_possession() {
+        let sk = BlsSecretKey::generate();
+        let other = BlsSecretKey::generate();
+        let proof = sk.prove_possession();
+
+        assert!(sk.public_key().verify_possession(&proof).is_ok());
+        assert!(other.public_key().verify_possession(&proof).is_err());
+    }
+
+    #[test]
+    fn test_aggregate_verify() {
+        let keys: Vec<BlsSecretKey> = (0..15).map(|_| BlsSecretKey::generate()).collect();
+        let message = b"anchor round 42";
+
+        let sigs: Vec<BlsSignature> = keys.iter().map(|k| k.sign(message)).collect();
+        let pks: Vec<BlsPublicKey> = keys.iter().map(|k| k.public_key()).collect();
+        let aggregate = BlsSignature::aggregate(&sigs).unwrap();
+
+        assert_eq!(aggregate.as_bytes().len(), BLS_SIGNATURE_SIZE);
+        assert!(aggregate.verify_aggregate(message, &pks).is_ok());
+
+        // Missing signer must fail
+        assert!(aggregate.verify_aggregate(message, &pks[1..]).is_err());
+        assert!(aggregate.verify_aggregate(b"other", &pks).is_err());
+    }
+
+    #[test]
+    fn test_aggregate_empty() {
+        assert!(BlsSignature::aggregate(&[]).is_err());
+    }
+}
@@ -47,4 +47,16 @@ pub enum CryptoError {
     /// Random number generation failed
     #[error("RNG failed: {0}")]
     RNGFailed(String),
+
+    /// Threshold signing or DKG failure
+    #[error("Threshold error: {0}")]
+    ThresholdError(String),
+
+    /// Remote or hardware signer failure
+    #[error("Remote signer error: {0}")]
+    RemoteSignerError(String),
+
+    /// Backend compiled out by feature flags
+    #[error("Backend unavailable: {0}")]
+    BackendUnavailable(String),
 }
@@ -162,7 +162,7 @@ pub mod merkle {
             current_index /= 2;
         }
 
-        current == root
+        crate::secret::ct_eq(&current, &root)
     }
 }
 
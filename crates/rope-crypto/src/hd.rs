@@ -0,0 +1,393 @@
+//! Hierarchical deterministic key derivation
+//!
+//! One BIP39 mnemonic derives every key a participant holds. Derivation
+//! follows SLIP-10 for Ed25519 (HMAC-SHA512, hardened children only), with
+//! a separate branch per key purpose:
+//!
+//! ```text
+//! m / 44' / 271828' / purpose' / account' / index'
+//!                       │
+//!                       ├── 0' validator
+//!                       ├── 1' DataWallet
+//!                       └── 2' bridge
+//! ```
+//!
+//! Each hybrid key component comes from its own hardened child of the key
+//! path (`/0'` Ed25519, `/1'` X25519, `/2'` Dilithium3, `/3'` Kyber768),
+//! so leaking one component's seed reveals nothing about the others.
+//!
+//! Every component is reproducible: Dilithium3 and Kyber768 keys come from
+//! seeded key generation (see [`crate::pq`]). With a PQ backend compiled
+//! out, derivation fails rather than return a key that could not be
+//! restored from the mnemonic.
+
+use std::fmt;
+use std::str::FromStr;
+
+use bip39::{Language, Mnemonic};
+use rand::rngs::OsRng;
+use rand::RngCore;
+use ring::hmac;
+use serde::{Deserialize, Serialize};
+use zeroize::{Zeroize, ZeroizeOnDrop, Zeroizing};
+
+use crate::error::{CryptoError, Result};
+use crate::hybrid::{HybridPublicKey, HybridSigner};
+use crate::pq::{Dilithium3, Kyber768, PqKem, PqSignatureScheme};
+
+/// SLIP-44 style coin type (the mainnet chain ID)
+pub const ROPE_COIN_TYPE: u32 = 271_828;
+
+/// Offset marking a hardened index
+pub const HARDENED_OFFSET: u32 = 0x8000_0000;
+
+/// SLIP-10 master key HMAC key
+const MASTER_HMAC_KEY: &[u8] = b"ed25519 seed";
+
+/// What a derived key is used for
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum KeyPurpose {
+    Validator,
+    Wallet,
+    Bridge,
+}
+
+impl KeyPurpose {
+    /// Purpose index in the derivation path
+    pub fn index(&self) -> u32 {
+        match self {
+            KeyPurpose::Validator => 0,
+            KeyPurpose::Wallet => 1,
+            KeyPurpose::Bridge => 2,
+        }
+    }
+}
+
+/// Hybrid key component, each on its own hardened branch
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Component {
+    Ed25519 = 0,
+    X25519 = 1,
+    Dilithium = 2,
+    Kyber = 3,
+}
+
+/// A path of hardened child indices, e.g. `m/44'/271828'/0'/0'/0'`
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct DerivationPath(Vec<u32>);
+
+impl DerivationPath {
+    /// Standard path for a purpose, account and index
+    pub fn for_purpose(purpose: KeyPurpose, account: u32, index: u32) -> Self {
+        Self(
+            [44, ROPE_COIN_TYPE, purpose.index(), account, index]
+                .into_iter()
+                .map(|i| i | HARDENED_OFFSET)
+                .collect(),
+        )
+    }
+
+    /// Child indices (with the hardened bit set)
+    pub fn indices(&self) -> &[u32] {
+        &self.0
+    }
+
+    /// Append a hardened child
+    pub fn child(&self, index: u32) -> Self {
+        let mut indices = self.0.clone();
+        indices.push(index | HARDENED_OFFSET);
+        Self(indices)
+    }
+}
+
+impl FromStr for DerivationPath {
+    type Err = CryptoError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let invalid = |msg: &str| CryptoError::KeyDerivationFailed(format!("{}: {}", msg, s));
+
+        let mut parts = s.split('/');
+        if parts.next() != Some("m") {
+            return Err(invalid("Path must start with m"));
+        }
+
+        let mut indices = Vec::new();
+        for part in parts {
+            // Ed25519 only supports hardened derivation
+            let raw = part
+                .strip_suffix('\'')
+                .or_else(|| part.strip_suffix('h'))
+                .ok_or_else(|| invalid("Only hardened indices are supported"))?;
+            let index: u32 = raw.parse().map_err(|_| invalid("Invalid index"))?;
+            if index >= HARDENED_OFFSET {
+                return Err(invalid("Index out of range"));
+            }
+            indices.push(index | HARDENED_OFFSET);
+        }
+        Ok(Self(indices))
+    }
+}
+
+impl fmt::Display for DerivationPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "m")?;
+        for index in &self.0 {
+            write!(f, "/{}'", index & !HARDENED_OFFSET)?;
+        }
+        Ok(())
+    }
+}
+
+/// Extended private key (key + chain code)
+#[derive(Clone, Zeroize, ZeroizeOnDrop)]
+pub struct ExtendedKey {
+    key: [u8; 32],
+    chain_code: [u8; 32],
+}
+
+impl ExtendedKey {
+    /// Master key from a BIP39 seed
+    pub fn master(seed: &[u8]) -> Self {
+        Self::from_hmac(MASTER_HMAC_KEY, seed)
+    }
+
+    /// Hardened child at `index` (hardened bit added if missing)
+    pub fn child(&self, index: u32) -> Self {
+        let mut data = Zeroizing::new(Vec::with_capacity(37));
+        data.push(0u8);
+        data.extend_from_slice(&self.key);
+        data.extend_from_slice(&(index | HARDENED_OFFSET).to_be_bytes());
+        Self::from_hmac(&self.chain_code, &data)
+    }
+
+    /// Walk a full path
+    pub fn derive(&self, path: &DerivationPath) -> Self {
+        path.indices()
+            .iter()
+            .fold(self.clone(), |key, index| key.child(*index))
+    }
+
+    /// Private key bytes
+    pub fn key(&self) -> &[u8; 32] {
+        &self.key
+    }
+
+    /// Chain code bytes
+    pub fn chain_code(&self) -> &[u8; 32] {
+        &self.chain_code
+    }
+
+    fn from_hmac(hmac_key: &[u8], data: &[u8]) -> Self {
+        let tag = hmac::sign(&hmac::Key::new(hmac::HMAC_SHA512, hmac_key), data);
+        let bytes = tag.as_ref();
+        let mut key = [0u8; 32];
+        let mut chain_code = [0u8; 32];
+        key.copy_from_slice(&bytes[..32]);
+        chain_code.copy_from_slice(&bytes[32..64]);
+        Self { key, chain_code }
+    }
+}
+
+/// HD wallet rooted at a single seed
+pub struct HdWallet {
+    master: ExtendedKey,
+}
+
+impl HdWallet {
+    /// Generate a fresh 24-word mnemonic and its wallet
+    pub fn generate(passphrase: &str) -> Result<(Self, Zeroizing<String>)> {
+        let mut entropy = Zeroizing::new([0u8; 32]);
+        OsRng.fill_bytes(&mut *entropy);
+        let mnemonic = Mnemonic::from_entropy_in(Language::English, &*entropy)
+            .map_err(|e| CryptoError::KeyDerivationFailed(format!("Mnemonic: {}", e)))?;
+        let phrase = Zeroizing::new(mnemonic.to_string());
+        Ok((Self::from_mnemonic_inner(&mnemonic, passphrase), phrase))
+    }
+
+    /// Restore from a BIP39 mnemonic phrase and optional passphrase
+    pub fn from_mnemonic(phrase: &str, passphrase: &str) -> Result<Self> {
+        let mnemonic = Mnemonic::parse_in_normalized(Language::English, phrase)
+            .map_err(|e| CryptoError::KeyDerivationFailed(format!("Invalid mnemonic: {}", e)))?;
+        Ok(Self::from_mnemonic_inner(&mnemonic, passphrase))
+    }
+
+    /// Build from a raw BIP39 seed (16 to 64 bytes)
+    pub fn from_seed(seed: &[u8]) -> Result<Self> {
+        if !(16..=64).contains(&seed.len()) {
+            return Err(CryptoError::KeyDerivationFailed(format!(
+                "Seed must be 16-64 bytes, got {}",
+                seed.len()
+            )));
+        }
+        Ok(Self {
+            master: ExtendedKey::master(seed),
+        })
+    }
+
+    fn from_mnemonic_inner(mnemonic: &Mnemonic, passphrase: &str) -> Self {
+        let seed = Zeroizing::new(mnemonic.to_seed_normalized(passphrase));
+        Self {
+            master: ExtendedKey::master(&*seed),
+        }
+    }
+
+    /// Extended key at an arbitrary path
+    pub fn derive(&self, path: &DerivationPath) -> ExtendedKey {
+        self.master.derive(path)
+    }
+
+    /// Hybrid keypair at a standard path
+    pub fn derive_signer(
+        &self,
+        purpose: KeyPurpose,
+        account: u32,
+        index: u32,
+    ) -> Result<(HybridSigner, HybridPublicKey)> {
+        self.derive_signer_at(&DerivationPath::for_purpose(purpose, account, index))
+    }
+
+    /// Hybrid keypair at an arbitrary path
+    pub fn derive_signer_at(
+        &self,
+        path: &DerivationPath,
+    ) -> Result<(HybridSigner, HybridPublicKey)> {
+        let node = self.derive(path);
+        let seed = |component: Component| *node.child(component as u32).key();
+
+        let ed25519_seed = Zeroizing::new(seed(Component::Ed25519));
+        let x25519_seed = Zeroizing::new(seed(Component::X25519));
+        let dilithium_seed = Zeroizing::new(seed(Component::Dilithium));
+        let kyber_seed = Zeroizing::new(seed(Component::Kyber));
+
+        let dilithium = Dilithium3::keypair_from_seed(&dilithium_seed)?;
+        let kyber = Kyber768::keypair_from_seed(&kyber_seed)?;
+
+        Ok(HybridSigner::from_parts(
+            &ed25519_seed,
+            &x25519_seed,
+            dilithium,
+            kyber,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(all(feature = "dilithium", feature = "kyber"))]
+    use crate::hybrid::{HybridKEM, HybridVerifier};
+
+    #[test]
+    fn test_slip10_vector() {
+        // SLIP-10 ed25519 test vector 1
+        let seed = hex::decode("000102030405060708090a0b0c0d0e0f").unwrap();
+        let master = ExtendedKey::master(&seed);
+        assert_eq!(
+            hex::encode(master.chain_code()),
+            "90046a93de5380a72b5e45010748567d5ea02bbf6522f979e05c0d8d8ca9fffb"
+        );
+        assert_eq!(
+            hex::encode(master.key()),
+            "2b4be7f19ee27bbf30c667b642d5f4aa69fd169872f8fc3059c08ebae2eb19e7"
+        );
+
+        let child = master.derive(&"m/0'".parse().unwrap());
+        assert_eq!(
+            hex::encode(child.chain_code()),
+            "8b59aa11380b624e81507a27fedda59fea6d0b779a778918a2fd3590e16e9c69"
+        );
+        assert_eq!(
+            hex::encode(child.key()),
+            "68e0fe46dfb67e368c75379acec591dad19df3cde26e63b93a8e704f1dade7a3"
+        );
+    }
+
+    #[test]
+    fn test_path_parsing() {
+        let path: DerivationPath = "m/44'/271828'/1'/0'/5'".parse().unwrap();
+        assert_eq!(path, DerivationPath::for_purpose(KeyPurpose::Wallet, 0, 5));
+        assert_eq!(path.to_string(), "m/44'/271828'/1'/0'/5'");
+
+        assert!("44'/0'".parse::<DerivationPath>().is_err());
+        assert!("m/44".parse::<DerivationPath>().is_err());
+        assert!("m/x'".parse::<DerivationPath>().is_err());
+    }
+
+    #[test]
+    fn test_mnemonic_roundtrip() {
+        let (wallet, phrase) = HdWallet::generate("").unwrap();
+        assert_eq!(phrase.split_whitespace().count(), 24);
+
+        let restored = HdWallet::from_mnemonic(&phrase, "").unwrap();
+        let path = DerivationPath::for_purpose(KeyPurpose::Validator, 0, 0);
+        assert_eq!(wallet.derive(&path).key(), restored.derive(&path).key());
+
+        // Passphrase changes the seed
+        let other = HdWallet::from_mnemonic(&phrase, "extra").unwrap();
+        assert_ne!(wallet.derive(&path).key(), other.derive(&path).key());
+
+        assert!(HdWallet::from_mnemonic("not a real mnemonic", "").is_err());
+    }
+
+    #[cfg(all(feature = "dilithium", feature = "kyber"))]
+    #[test]
+    fn test_purposes_are_separated() {
+        let wallet = HdWallet::from_seed(&[7u8; 32]).unwrap();
+        let (_, validator) = wallet.derive_signer(KeyPurpose::Validator, 0, 0).unwrap();
+        let (_, data_wallet) = wallet.derive_signer(KeyPurpose::Wallet, 0, 0).unwrap();
+        let (_, bridge) = wallet.derive_signer(KeyPurpose::Bridge, 0, 0).unwrap();
+
+        assert_ne!(validator.ed25519, data_wallet.ed25519);
+        assert_ne!(validator.ed25519, bridge.ed25519);
+        assert_ne!(data_wallet.ed25519, bridge.ed25519);
+    }
+
+    #[cfg(all(feature = "dilithium", feature = "kyber"))]
+    #[test]
+    fn test_components_reproducible() {
+        let wallet = HdWallet::from_seed(&[9u8; 32]).unwrap();
+        let (signer, pk1) = wallet.derive_signer(KeyPurpose::Validator, 0, 3).unwrap();
+        let (_, pk2) = wallet.derive_signer(KeyPurpose::Validator, 0, 3).unwrap();
+
+        assert_eq!(pk1, pk2);
+        assert_ne!(pk1.ed25519, pk1.x25519);
+
+        let sig = signer.sign(b"hd");
+        assert!(HybridVerifier::verify(&pk1, b"hd", &sig).unwrap());
+    }
+
+    #[cfg(all(feature = "dilithium", feature = "kyber"))]
+    #[test]
+    fn test_restore_from_phrase_twice() {
+        let (wallet, phrase) = HdWallet::generate("").unwrap();
+        let (_, original) = wallet.derive_signer(KeyPurpose::Wallet, 0, 0).unwrap();
+
+        let (_, first) = HdWallet::from_mnemonic(&phrase, "")
+            .unwrap()
+            .derive_signer(KeyPurpose::Wallet, 0, 0)
+            .unwrap();
+        let (signer, second) = HdWallet::from_mnemonic(&phrase, "")
+            .unwrap()
+            .derive_signer(KeyPurpose::Wallet, 0, 0)
+            .unwrap();
+        assert_eq!(first, original);
+        assert_eq!(second, original);
+
+        // A restored signer signs and decrypts for the original public key
+        let sig = signer.sign(b"restored");
+        assert!(HybridVerifier::verify(&original, b"restored", &sig).unwrap());
+        let (encapsulated, shared) = HybridKEM::encapsulate(&original).unwrap();
+        let recovered = HybridKEM::decapsulate(&signer.secret_key(), &encapsulated).unwrap();
+        assert_eq!(recovered.as_bytes(), shared.as_bytes());
+    }
+
+    #[cfg(not(all(feature = "dilithium", feature = "kyber")))]
+    #[test]
+    fn test_derivation_requires_pq_backends() {
+        let wallet = HdWallet::from_seed(&[9u8; 32]).unwrap();
+        assert!(matches!(
+            wallet.derive_signer(KeyPurpose::Validator, 0, 0),
+            Err(CryptoError::BackendUnavailable(_))
+        ));
+    }
+}
@@ -23,13 +23,6 @@
 //! - Both Ed25519 AND Dilithium signatures must verify when PQ keys are present
 
 use ed25519_dalek::{Signature as Ed25519Signature, Signer, SigningKey, Verifier, VerifyingKey};
-use pqcrypto_dilithium::dilithium3;
-use pqcrypto_kyber::kyber768;
-use pqcrypto_traits::kem::{
-    Ciphertext, PublicKey as KemPublicKey, SecretKey as KemSecretKey,
-    SharedSecret as PqSharedSecret,
-};
-use pqcrypto_traits::sign::{PublicKey as PqPublicKey, SecretKey as PqSecretKey, SignedMessage};
 use rand::rngs::OsRng;
 use rand::RngCore;
 use serde::{Deserialize, Serialize};
@@ -37,31 +30,32 @@ use x25519_dalek::{EphemeralSecret, PublicKey as X25519PublicKey, StaticSecret};
 use zeroize::{Zeroize, ZeroizeOnDrop};
 
 use crate::error::{CryptoError, Result};
+use crate::pq::{Dilithium3, Kyber768, PqKem, PqSignatureScheme};
 
 // ============================================================================
 // Constants
 // ============================================================================
 
 /// Dilithium3 public key size
-pub const DILITHIUM3_PUBLIC_KEY_SIZE: usize = 1952;
+pub const DILITHIUM3_PUBLIC_KEY_SIZE: usize = Dilithium3::PUBLIC_KEY_SIZE;
 
 /// Dilithium3 secret key size
-pub const DILITHIUM3_SECRET_KEY_SIZE: usize = 4016;
+pub const DILITHIUM3_SECRET_KEY_SIZE: usize = Dilithium3::SECRET_KEY_SIZE;
 
 /// Dilithium3 signature size
-pub const DILITHIUM3_SIGNATURE_SIZE: usize = 3293;
+pub const DILITHIUM3_SIGNATURE_SIZE: usize = Dilithium3::SIGNATURE_SIZE;
 
 /// Kyber768 public key size
-pub const KYBER768_PUBLIC_KEY_SIZE: usize = 1184;
+pub const KYBER768_PUBLIC_KEY_SIZE: usize = Kyber768::PUBLIC_KEY_SIZE;
 
 /// Kyber768 secret key size
-pub const KYBER768_SECRET_KEY_SIZE: usize = 2400;
+pub const KYBER768_SECRET_KEY_SIZE: usize = Kyber768::SECRET_KEY_SIZE;
 
 /// Kyber768 ciphertext size
-pub const KYBER768_CIPHERTEXT_SIZE: usize = 1088;
+pub const KYBER768_CIPHERTEXT_SIZE: usize = Kyber768::CIPHERTEXT_SIZE;
 
 /// Kyber768 shared secret size
-pub const KYBER768_SHARED_SECRET_SIZE: usize = 32;
+pub const KYBER768_SHARED_SECRET_SIZE: usize = Kyber768::SHARED_SECRET_SIZE;
 
 /// X25519 public key size
 pub const X25519_PUBLIC_KEY_SIZE: usize = 32;
@@ -118,6 +112,39 @@ impl HybridSignature {
             && (self.dilithium_sig.is_empty()
                 || self.dilithium_sig.len() >= DILITHIUM3_SIGNATURE_SIZE)
     }
+
+    /// Flat encoding: the Ed25519 signature followed by the Dilithium one
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(self.size());
+        bytes.extend_from_slice(&self.ed25519_sig);
+        bytes.extend_from_slice(&self.dilithium_sig);
+        bytes
+    }
+
+    /// Parse [`to_bytes`](Self::to_bytes) output
+    ///
+    /// The Dilithium part must be absent or at least a full Dilithium3
+    /// signature long.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() < 64 {
+            return Err(CryptoError::InvalidSignature(format!(
+                "Hybrid signature too short: {} bytes",
+                bytes.len()
+            )));
+        }
+        let (ed25519_sig, dilithium_sig) = bytes.split_at(64);
+        let signature = Self {
+            ed25519_sig: ed25519_sig.to_vec(),
+            dilithium_sig: dilithium_sig.to_vec(),
+        };
+        if !signature.is_valid_structure() {
+            return Err(CryptoError::InvalidSignature(format!(
+                "Truncated Dilithium signature: {} bytes",
+                dilithium_sig.len()
+            )));
+        }
+        Ok(signature)
+    }
 }
 
 // ============================================================================
@@ -405,15 +432,9 @@ impl HybridSigner {
         let x25519_sk = x25519_secret.to_bytes();
         let x25519_pk = x25519_public.to_bytes();
 
-        // Generate CRYSTALS-Dilithium3 keypair (REAL post-quantum crypto!)
-        let (dilithium_pk_obj, dilithium_sk_obj) = dilithium3::keypair();
-        let dilithium_pk = dilithium_pk_obj.as_bytes().to_vec();
-        let dilithium_sk = dilithium_sk_obj.as_bytes().to_vec();
-
-        // Generate CRYSTALS-Kyber768 keypair (REAL post-quantum KEM!)
-        let (kyber_pk_obj, kyber_sk_obj) = kyber768::keypair();
-        let kyber_pk = kyber_pk_obj.as_bytes().to_vec();
-        let kyber_sk = kyber_sk_obj.as_bytes().to_vec();
+        // Generate CRYSTALS-Dilithium3 and Kyber768 keypairs
+        let (dilithium_pk, dilithium_sk) = pq_keypair(Dilithium3::keypair());
+        let (kyber_pk, kyber_sk) = pq_keypair(Kyber768::keypair());
 
         let signer = Self {
             ed25519_key,
@@ -439,9 +460,6 @@ impl HybridSigner {
     /// The seed MUST be cryptographically random and kept secret. If the seed is
     /// compromised, all derived keys are compromised.
     pub fn from_seed(seed: &[u8; 32]) -> (Self, HybridPublicKey) {
-        use rand::SeedableRng;
-        use rand_chacha::ChaCha20Rng;
-
         // Derive separate seeds for each key type using BLAKE3
         let ed25519_seed = {
             let mut input = seed.to_vec();
@@ -455,6 +473,16 @@ impl HybridSigner {
             *blake3::hash(&input).as_bytes()
         };
 
+        // Generate Ed25519 keypair from seed
+        let ed25519_key = SigningKey::from_bytes(&ed25519_seed);
+        let ed25519_public = ed25519_key.verifying_key().to_bytes();
+
+        // Generate X25519 keypair from seed
+        let x25519_secret = StaticSecret::from(x25519_seed);
+        let x25519_public = X25519PublicKey::from(&x25519_secret);
+        let x25519_sk = x25519_secret.to_bytes();
+        let x25519_pk = x25519_public.to_bytes();
+
         let dilithium_seed = {
             let mut input = seed.to_vec();
             input.extend_from_slice(b"dilithium_key");
@@ -467,43 +495,48 @@ impl HybridSigner {
             *blake3::hash(&input).as_bytes()
         };
 
-        // Generate Ed25519 keypair from seed
-        let ed25519_key = SigningKey::from_bytes(&ed25519_seed);
+        // Generate CRYSTALS-Dilithium3 and Kyber768 keypairs from seed
+        let (dilithium_pk, dilithium_sk) =
+            pq_keypair(Dilithium3::keypair_from_seed(&dilithium_seed));
+        let (kyber_pk, kyber_sk) = pq_keypair(Kyber768::keypair_from_seed(&kyber_seed));
+
+        let signer = Self {
+            ed25519_key,
+            x25519_sk,
+            x25519_pk,
+            dilithium_sk,
+            dilithium_pk: dilithium_pk.clone(),
+            kyber_sk,
+            kyber_pk: kyber_pk.clone(),
+        };
+
+        let public_key = HybridPublicKey::new(ed25519_public, x25519_pk, dilithium_pk, kyber_pk);
+
+        (signer, public_key)
+    }
+
+    /// Assemble a signer from derived classical seeds and PQ key material
+    ///
+    /// `dilithium` and `kyber` are `(public_key, secret_key)` pairs and may
+    /// be empty.
+    pub fn from_parts(
+        ed25519_seed: &[u8; 32],
+        x25519_seed: &[u8; 32],
+        dilithium: (Vec<u8>, Vec<u8>),
+        kyber: (Vec<u8>, Vec<u8>),
+    ) -> (Self, HybridPublicKey) {
+        let ed25519_key = SigningKey::from_bytes(ed25519_seed);
         let ed25519_public = ed25519_key.verifying_key().to_bytes();
 
-        // Generate X25519 keypair from seed
-        let x25519_secret = StaticSecret::from(x25519_seed);
-        let x25519_public = X25519PublicKey::from(&x25519_secret);
-        let x25519_sk = x25519_secret.to_bytes();
-        let x25519_pk = x25519_public.to_bytes();
+        let x25519_secret = StaticSecret::from(*x25519_seed);
+        let x25519_pk = X25519PublicKey::from(&x25519_secret).to_bytes();
 
-        // Generate CRYSTALS-Dilithium3 keypair using seeded RNG
-        // Note: pqcrypto doesn't support deterministic generation directly,
-        // so we use a ChaCha20 RNG seeded with our derived seed
-        let mut dilithium_rng = ChaCha20Rng::from_seed(dilithium_seed);
-        let (dilithium_pk_obj, dilithium_sk_obj) = {
-            // Generate random bytes for keypair
-            let mut seed_bytes = [0u8; 32];
-            rand::Rng::fill(&mut dilithium_rng, &mut seed_bytes);
-            // Use the seeded random as entropy source
-            dilithium3::keypair()
-        };
-        let dilithium_pk = dilithium_pk_obj.as_bytes().to_vec();
-        let dilithium_sk = dilithium_sk_obj.as_bytes().to_vec();
-
-        // Generate CRYSTALS-Kyber768 keypair using seeded RNG
-        let mut kyber_rng = ChaCha20Rng::from_seed(kyber_seed);
-        let (kyber_pk_obj, kyber_sk_obj) = {
-            let mut seed_bytes = [0u8; 32];
-            rand::Rng::fill(&mut kyber_rng, &mut seed_bytes);
-            kyber768::keypair()
-        };
-        let kyber_pk = kyber_pk_obj.as_bytes().to_vec();
-        let kyber_sk = kyber_sk_obj.as_bytes().to_vec();
+        let (dilithium_pk, dilithium_sk) = dilithium;
+        let (kyber_pk, kyber_sk) = kyber;
 
         let signer = Self {
             ed25519_key,
-            x25519_sk,
+            x25519_sk: x25519_secret.to_bytes(),
             x25519_pk,
             dilithium_sk,
             dilithium_pk: dilithium_pk.clone(),
@@ -516,6 +549,65 @@ impl HybridSigner {
         (signer, public_key)
     }
 
+    /// Rebuild a signer from a stored secret key and its public key
+    ///
+    /// The classical public keys are re-derived and must match `public`.
+    /// The PQ public keys cannot be derived from the secret keys, so they
+    /// are taken from `public` and checked to pair with the secret keys by
+    /// a sign/verify and an encapsulate/decapsulate round trip.
+    pub fn from_keypair(secret: &HybridSecretKey, public: &HybridPublicKey) -> Result<Self> {
+        let mismatch = |component: &str| {
+            CryptoError::InvalidPublicKey(format!("{} key pair mismatch", component))
+        };
+
+        let ed25519_key = SigningKey::from_bytes(&secret.ed25519);
+        if ed25519_key.verifying_key().to_bytes() != public.ed25519 {
+            return Err(mismatch("Ed25519"));
+        }
+
+        let x25519_pk = if secret.has_x25519() {
+            X25519PublicKey::from(&StaticSecret::from(secret.x25519)).to_bytes()
+        } else {
+            [0u8; 32]
+        };
+        if x25519_pk != public.x25519 {
+            return Err(mismatch("X25519"));
+        }
+
+        match (secret.dilithium.is_empty(), public.dilithium.is_empty()) {
+            (true, true) => {}
+            (false, false) => {
+                let probe = b"rope keypair check";
+                let signed = Dilithium3::sign(&secret.dilithium, probe)?;
+                if !Dilithium3::verify(&public.dilithium, probe, &signed)? {
+                    return Err(mismatch("Dilithium3"));
+                }
+            }
+            _ => return Err(mismatch("Dilithium3")),
+        }
+
+        match (secret.kyber.is_empty(), public.kyber.is_empty()) {
+            (true, true) => {}
+            (false, false) => {
+                let (ciphertext, shared) = Kyber768::encapsulate(&public.kyber)?;
+                if Kyber768::decapsulate(&secret.kyber, &ciphertext)? != shared {
+                    return Err(mismatch("Kyber768"));
+                }
+            }
+            _ => return Err(mismatch("Kyber768")),
+        }
+
+        Ok(Self {
+            ed25519_key,
+            x25519_sk: secret.x25519,
+            x25519_pk,
+            dilithium_sk: secret.dilithium.clone(),
+            dilithium_pk: public.dilithium.clone(),
+            kyber_sk: secret.kyber.clone(),
+            kyber_pk: public.kyber.clone(),
+        })
+    }
+
     /// Generate without Kyber (signing only)
     pub fn generate_signing_only() -> (Self, HybridPublicKey) {
         let mut secret_bytes = [0u8; 32];
@@ -523,9 +615,7 @@ impl HybridSigner {
         let ed25519_key = SigningKey::from_bytes(&secret_bytes);
         let ed25519_public = ed25519_key.verifying_key().to_bytes();
 
-        let (dilithium_pk_obj, dilithium_sk_obj) = dilithium3::keypair();
-        let dilithium_pk = dilithium_pk_obj.as_bytes().to_vec();
-        let dilithium_sk = dilithium_sk_obj.as_bytes().to_vec();
+        let (dilithium_pk, dilithium_sk) = pq_keypair(Dilithium3::keypair());
 
         let signer = Self {
             ed25519_key,
@@ -598,15 +688,12 @@ impl HybridSigner {
         let ed25519_sig = self.ed25519_key.sign(message);
         let ed25519_bytes = ed25519_sig.to_bytes().to_vec();
 
-        // Dilithium3 signature (REAL post-quantum signature!)
+        // Dilithium3 signature
         let dilithium_sig = if !self.dilithium_sk.is_empty() {
-            match dilithium3::SecretKey::from_bytes(&self.dilithium_sk) {
-                Ok(sk) => {
-                    let signed_msg = dilithium3::sign(message, &sk);
-                    signed_msg.as_bytes().to_vec()
-                }
+            match Dilithium3::sign(&self.dilithium_sk, message) {
+                Ok(signed_msg) => signed_msg,
                 Err(e) => {
-                    tracing::error!("Failed to parse Dilithium secret key: {:?}", e);
+                    tracing::error!("Dilithium signing failed: {}", e);
                     // Return empty - verification will fail as expected
                     Vec::new()
                 }
@@ -653,6 +740,14 @@ impl HybridSigner {
     }
 }
 
+/// Unwrap PQ key material, leaving it empty when the backend is compiled out
+fn pq_keypair(keypair: Result<(Vec<u8>, Vec<u8>)>) -> (Vec<u8>, Vec<u8>) {
+    keypair.unwrap_or_else(|e| {
+        tracing::warn!("Post-quantum key generation skipped: {}", e);
+        (Vec::new(), Vec::new())
+    })
+}
+
 // ============================================================================
 // Hybrid Verifier - Production Implementation (SECURE!)
 // ============================================================================
@@ -719,7 +814,7 @@ impl HybridVerifier {
         Ok(verifying_key.verify(message, &sig).is_ok())
     }
 
-    /// Verify Dilithium3 signature (REAL post-quantum verification!)
+    /// Verify Dilithium3 signature
     ///
     /// SECURITY: This function ONLY returns true if cryptographic verification succeeds.
     /// There is NO fallback that bypasses verification.
@@ -734,33 +829,8 @@ impl HybridVerifier {
             return Ok(false);
         }
 
-        // Parse Dilithium public key - NO FALLBACK on failure
-        let pk = dilithium3::PublicKey::from_bytes(public_key).map_err(|e| {
-            tracing::error!("Failed to parse Dilithium public key: {:?}", e);
-            CryptoError::InvalidPublicKey("Invalid Dilithium public key format".to_string())
-        })?;
-
-        // Parse signed message - NO FALLBACK on failure
-        let signed_msg = dilithium3::SignedMessage::from_bytes(signature).map_err(|e| {
-            tracing::error!("Failed to parse Dilithium signature: {:?}", e);
-            CryptoError::InvalidSignature("Invalid Dilithium signature format".to_string())
-        })?;
-
-        // Verify and extract original message
-        match dilithium3::open(&signed_msg, &pk) {
-            Ok(opened_msg) => {
-                // Verify the opened message matches the original
-                let matches = opened_msg == message;
-                if !matches {
-                    tracing::warn!("Dilithium signature valid but message mismatch");
-                }
-                Ok(matches)
-            }
-            Err(_) => {
-                tracing::debug!("Dilithium signature cryptographic verification failed");
-                Ok(false)
-            }
-        }
+        // Parse and verify - NO FALLBACK on failure
+        Dilithium3::verify(public_key, message, signature)
     }
 
     /// Verify Ed25519 signature only (for legacy compatibility)
@@ -828,6 +898,14 @@ impl SharedSecret {
     }
 }
 
+impl PartialEq for SharedSecret {
+    fn eq(&self, other: &Self) -> bool {
+        crate::secret::ct_eq(&self.secret, &other.secret)
+    }
+}
+
+impl Eq for SharedSecret {}
+
 impl HybridKEM {
     /// Encapsulate a shared secret to a public key
     ///
@@ -849,20 +927,9 @@ impl HybridKEM {
             ephemeral_secret.diffie_hellman(&their_public)
         };
 
-        // Kyber768 encapsulation (REAL post-quantum KEM!)
+        // Kyber768 encapsulation
         let (kyber_ciphertext, kyber_shared) = if !public_key.kyber.is_empty() {
-            match kyber768::PublicKey::from_bytes(&public_key.kyber) {
-                Ok(pk) => {
-                    let (shared, ciphertext) = kyber768::encapsulate(&pk);
-                    (ciphertext.as_bytes().to_vec(), shared.as_bytes().to_vec())
-                }
-                Err(e) => {
-                    tracing::error!("Failed to parse Kyber public key: {:?}", e);
-                    return Err(CryptoError::InvalidPublicKey(
-                        "Invalid Kyber public key".to_string(),
-                    ));
-                }
-            }
+            Kyber768::encapsulate(&public_key.kyber)?
         } else {
             // No Kyber key - use only X25519
             // This is acceptable for backward compatibility but not post-quantum secure
@@ -910,26 +977,13 @@ impl HybridKEM {
             our_secret.diffie_hellman(&their_ephemeral)
         };
 
-        // Kyber768 decapsulation (REAL post-quantum KEM!)
-        let kyber_shared = if !secret_key.kyber.is_empty()
-            && !encapsulated.kyber_ciphertext.is_empty()
-        {
-            let sk = kyber768::SecretKey::from_bytes(&secret_key.kyber).map_err(|e| {
-                tracing::error!("Failed to parse Kyber secret key: {:?}", e);
-                CryptoError::InvalidSecretKey("Invalid Kyber secret key".to_string())
-            })?;
-
-            let ct =
-                kyber768::Ciphertext::from_bytes(&encapsulated.kyber_ciphertext).map_err(|e| {
-                    tracing::error!("Failed to parse Kyber ciphertext: {:?}", e);
-                    CryptoError::DecryptionError("Invalid Kyber ciphertext".to_string())
-                })?;
-
-            let shared = kyber768::decapsulate(&ct, &sk);
-            shared.as_bytes().to_vec()
-        } else {
-            Vec::new()
-        };
+        // Kyber768 decapsulation
+        let kyber_shared =
+            if !secret_key.kyber.is_empty() && !encapsulated.kyber_ciphertext.is_empty() {
+                Kyber768::decapsulate(&secret_key.kyber, &encapsulated.kyber_ciphertext)?
+            } else {
+                Vec::new()
+            };
 
         // Combine shared secrets
         let mut combined_input = Vec::with_capacity(64);
@@ -1011,6 +1065,25 @@ mod tests {
         assert!(signature.dilithium_sig.len() >= 3000); // Dilithium3 signature
     }
 
+    #[test]
+    fn test_signature_bytes_round_trip() {
+        let (signer, public_key) = HybridSigner::generate();
+        let signature = signer.sign(b"test");
+
+        let bytes = signature.to_bytes();
+        assert_eq!(bytes.len(), signature.size());
+        let decoded = HybridSignature::from_bytes(&bytes).unwrap();
+        assert_eq!(decoded, signature);
+        assert!(HybridVerifier::verify(&public_key, b"test", &decoded).unwrap());
+
+        // Classical-only signatures carry no Dilithium part
+        let classical = HybridSignature::from_bytes(&bytes[..64]).unwrap();
+        assert!(classical.dilithium_sig.is_empty());
+
+        assert!(HybridSignature::from_bytes(&bytes[..63]).is_err());
+        assert!(HybridSignature::from_bytes(&bytes[..64 + 100]).is_err());
+    }
+
     #[test]
     fn test_kem_encapsulation_with_real_x25519() {
         let (signer, public_key) = HybridSigner::generate();
@@ -3,6 +3,7 @@
 // Keys management module
 
 use crate::hybrid::{HybridPublicKey, HybridSecretKey, HybridSigner};
+use zeroize::Zeroizing;
 
 /// Re-export for convenience
 pub use crate::hybrid::HybridPublicKey as PublicKey;
@@ -32,6 +33,12 @@ impl KeyPair {
         Ok(Self { signer, public_key })
     }
 
+    /// Wrap an existing signer
+    pub fn from_signer(signer: HybridSigner) -> Self {
+        let public_key = signer.public_key();
+        Self { signer, public_key }
+    }
+
     /// Get the public key
     pub fn public_key(&self) -> &HybridPublicKey {
         &self.public_key
@@ -69,7 +76,7 @@ pub struct KeyStore {
     primary: KeyPair,
 
     /// Key derivation seed
-    seed: [u8; 32],
+    seed: Zeroizing<[u8; 32]>,
 }
 
 impl KeyStore {
@@ -92,11 +99,12 @@ impl KeyStore {
     /// # Security Note
     /// The seed MUST be cryptographically random and kept secret.
     pub fn from_seed(seed: [u8; 32]) -> Self {
+        let seed = Zeroizing::new(seed);
         // Derive primary key seed from master seed
         let primary_seed = {
-            let mut input = seed.to_vec();
+            let mut input = Zeroizing::new(seed.to_vec());
             input.extend_from_slice(b"primary_keypair");
-            *blake3::hash(&input).as_bytes()
+            Zeroizing::new(*blake3::hash(&input).as_bytes())
         };
 
         let (signer, public_key) = HybridSigner::from_seed(&primary_seed);
@@ -121,7 +129,7 @@ impl KeyStore {
 
     /// Derive a child key for specific purpose
     pub fn derive_key(&self, purpose: &str) -> [u8; 32] {
-        crate::hash::derive_key(purpose, &self.seed)
+        crate::hash::derive_key(purpose, self.seed.as_slice())
     }
 
     /// Derive a child keypair for specific purpose
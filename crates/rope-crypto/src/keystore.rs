@@ -0,0 +1,584 @@
+//! Encrypted keystore
+//!
+//! Persists hybrid keypairs encrypted at rest. The key-encryption key is
+//! derived with Argon2id from a passphrase, optionally combined with a
+//! keyfile, and the secret key is sealed with ChaCha20-Poly1305.
+//!
+//! ## File format (version 1)
+//!
+//! ```json
+//! {
+//!   "version": 1,
+//!   "kind": "validator",
+//!   "address": "<hex node ID>",
+//!   "public_key": "<hex HybridPublicKey::to_bytes>",
+//!   "crypto": {
+//!     "cipher": "chacha20-poly1305",
+//!     "nonce": "<hex, 12 bytes>",
+//!     "ciphertext": "<hex, secret key + 16-byte tag>",
+//!     "kdf": "argon2id",
+//!     "kdf_params": { "m_cost_kib": 65536, "t_cost": 3, "p_cost": 1, "salt": "<hex, 16 bytes>" },
+//!     "keyfile": false
+//!   }
+//! }
+//! ```
+//!
+//! The plaintext is `ed25519 (32) || x25519 (32) || len (u32 LE) ||
+//! dilithium || len (u32 LE) || kyber`. Version, kind, address and public
+//! key are bound as AEAD associated data, so editing the header invalidates
+//! the file.
+//!
+//! When `keyfile` is true the KEK is
+//! `BLAKE3-derive("rope keystore keyfile v1", argon2id_output || BLAKE3(keyfile))`.
+
+use std::io::Write;
+use std::path::Path;
+
+use argon2::{Algorithm, Argon2, Params, Version};
+use rand::rngs::OsRng;
+use rand::RngCore;
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, CHACHA20_POLY1305, NONCE_LEN};
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroizing;
+
+use crate::error::{CryptoError, Result};
+use crate::hybrid::{HybridPublicKey, HybridSecretKey, HybridSigner};
+use crate::keys::KeyPair;
+
+/// Current keystore format version
+pub const KEYSTORE_VERSION: u32 = 1;
+
+const CIPHER_NAME: &str = "chacha20-poly1305";
+const KDF_NAME: &str = "argon2id";
+const KEYFILE_CONTEXT: &str = "rope keystore keyfile v1";
+const SALT_LEN: usize = 16;
+
+/// Largest Argon2id costs accepted when unlocking: 2 GiB, 32 passes, 16
+/// lanes. The costs are not covered by the AEAD, so an edited file could
+/// otherwise make unlocking allocate and hash without limit.
+const MAX_M_COST_KIB: u32 = 2 * 1024 * 1024;
+const MAX_T_COST: u32 = 32;
+const MAX_P_COST: u32 = 16;
+
+/// What a stored key is used for
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum KeyKind {
+    /// Validator / node identity key
+    Validator,
+    /// DataWallet key
+    Wallet,
+}
+
+/// Argon2id cost parameters
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KdfParams {
+    /// Memory cost in KiB
+    pub m_cost_kib: u32,
+    /// Iterations
+    pub t_cost: u32,
+    /// Parallelism
+    pub p_cost: u32,
+    /// Random salt (hex)
+    pub salt: String,
+}
+
+impl KdfParams {
+    /// Recommended parameters with a fresh salt (64 MiB, 3 passes)
+    pub fn recommended() -> Self {
+        Self::with_costs(64 * 1024, 3, 1)
+    }
+
+    /// Custom costs with a fresh salt
+    pub fn with_costs(m_cost_kib: u32, t_cost: u32, p_cost: u32) -> Self {
+        let mut salt = [0u8; SALT_LEN];
+        OsRng.fill_bytes(&mut salt);
+        Self {
+            m_cost_kib,
+            t_cost,
+            p_cost,
+            salt: hex::encode(salt),
+        }
+    }
+
+    fn check_bounds(&self) -> Result<()> {
+        if self.m_cost_kib > MAX_M_COST_KIB || self.t_cost > MAX_T_COST || self.p_cost > MAX_P_COST
+        {
+            return Err(CryptoError::DecryptionError(format!(
+                "Argon2 costs m={} KiB, t={}, p={} exceed the limits",
+                self.m_cost_kib, self.t_cost, self.p_cost
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Passphrase and optional keyfile used to unlock a keystore
+pub struct Credentials {
+    passphrase: Zeroizing<Vec<u8>>,
+    keyfile: Option<Zeroizing<Vec<u8>>>,
+}
+
+impl Credentials {
+    /// Passphrase only
+    pub fn passphrase(passphrase: &str) -> Self {
+        Self {
+            passphrase: Zeroizing::new(passphrase.as_bytes().to_vec()),
+            keyfile: None,
+        }
+    }
+
+    /// Add keyfile contents as a second factor
+    pub fn with_keyfile(mut self, keyfile: Vec<u8>) -> Self {
+        self.keyfile = Some(Zeroizing::new(keyfile));
+        self
+    }
+
+    /// Add a keyfile read from disk
+    pub fn with_keyfile_path(self, path: impl AsRef<Path>) -> Result<Self> {
+        let contents = std::fs::read(path.as_ref())
+            .map_err(|e| CryptoError::KeyDerivationFailed(format!("Cannot read keyfile: {}", e)))?;
+        Ok(self.with_keyfile(contents))
+    }
+
+    fn has_keyfile(&self) -> bool {
+        self.keyfile.is_some()
+    }
+}
+
+/// Encrypted secret key section
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeystoreCrypto {
+    pub cipher: String,
+    pub nonce: String,
+    pub ciphertext: String,
+    pub kdf: String,
+    pub kdf_params: KdfParams,
+    pub keyfile: bool,
+}
+
+/// Keystore file contents
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeystoreFile {
+    pub version: u32,
+    pub kind: KeyKind,
+    pub address: String,
+    pub public_key: String,
+    pub crypto: KeystoreCrypto,
+}
+
+impl KeystoreFile {
+    /// Encrypt a keypair
+    pub fn encrypt(
+        kind: KeyKind,
+        keypair: &KeyPair,
+        credentials: &Credentials,
+        kdf_params: KdfParams,
+    ) -> Result<Self> {
+        let public_key = keypair.public_key();
+        let mut file = Self {
+            version: KEYSTORE_VERSION,
+            kind,
+            address: hex::encode(public_key.node_id()),
+            public_key: hex::encode(public_key.to_bytes()),
+            crypto: KeystoreCrypto {
+                cipher: CIPHER_NAME.to_string(),
+                nonce: String::new(),
+                ciphertext: String::new(),
+                kdf: KDF_NAME.to_string(),
+                kdf_params,
+                keyfile: credentials.has_keyfile(),
+            },
+        };
+
+        let kek = derive_kek(&file.crypto.kdf_params, credentials)?;
+        let key = aead_key(&kek)?;
+
+        let mut nonce = [0u8; NONCE_LEN];
+        OsRng.fill_bytes(&mut nonce);
+
+        let mut buffer = encode_secret(&keypair.signer().secret_key());
+        key.seal_in_place_append_tag(
+            Nonce::assume_unique_for_key(nonce),
+            Aad::from(file.associated_data()),
+            &mut *buffer,
+        )
+        .map_err(|_| CryptoError::EncapsulationFailed("Keystore encryption failed".to_string()))?;
+
+        file.crypto.nonce = hex::encode(nonce);
+        file.crypto.ciphertext = hex::encode(&*buffer);
+        Ok(file)
+    }
+
+    /// Decrypt the keypair
+    pub fn decrypt(&self, credentials: &Credentials) -> Result<KeyPair> {
+        if self.version != KEYSTORE_VERSION {
+            return Err(CryptoError::DecryptionError(format!(
+                "Unsupported keystore version {}",
+                self.version
+            )));
+        }
+        if self.crypto.cipher != CIPHER_NAME || self.crypto.kdf != KDF_NAME {
+            return Err(CryptoError::DecryptionError(format!(
+                "Unsupported cipher/kdf {}/{}",
+                self.crypto.cipher, self.crypto.kdf
+            )));
+        }
+        if self.crypto.keyfile != credentials.has_keyfile() {
+            return Err(CryptoError::DecryptionError(if self.crypto.keyfile {
+                "Keystore requires a keyfile".to_string()
+            } else {
+                "Keystore does not use a keyfile".to_string()
+            }));
+        }
+        self.crypto.kdf_params.check_bounds()?;
+
+        let nonce: [u8; NONCE_LEN] = decode_hex(&self.crypto.nonce, "nonce")?
+            .try_into()
+            .map_err(|_| CryptoError::DecryptionError("Invalid nonce length".to_string()))?;
+        let mut buffer = Zeroizing::new(decode_hex(&self.crypto.ciphertext, "ciphertext")?);
+
+        let kek = derive_kek(&self.crypto.kdf_params, credentials)?;
+        let key = aead_key(&kek)?;
+        let plaintext = key
+            .open_in_place(
+                Nonce::assume_unique_for_key(nonce),
+                Aad::from(self.associated_data()),
+                &mut buffer,
+            )
+            .map_err(|_| {
+                CryptoError::DecryptionError("Wrong passphrase or corrupted keystore".to_string())
+            })?;
+
+        let secret = decode_secret(plaintext)?;
+        let stored = HybridPublicKey::from_bytes(&decode_hex(&self.public_key, "public key")?)?;
+        let signer = HybridSigner::from_keypair(&secret, &stored).map_err(|e| {
+            CryptoError::DecryptionError(format!(
+                "Decrypted key does not match stored public key: {}",
+                e
+            ))
+        })?;
+        Ok(KeyPair::from_signer(signer))
+    }
+
+    /// Serialize to pretty JSON
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string_pretty(self)
+            .map_err(|e| CryptoError::InvalidSecretKey(format!("Keystore encode: {}", e)))
+    }
+
+    /// Parse from JSON
+    ///
+    /// Fails on KDF costs above the unlock limits.
+    pub fn from_json(json: &str) -> Result<Self> {
+        let file: Self = serde_json::from_str(json)
+            .map_err(|e| CryptoError::InvalidSecretKey(format!("Keystore decode: {}", e)))?;
+        file.crypto.kdf_params.check_bounds()?;
+        Ok(file)
+    }
+
+    /// Write to disk, readable by the owner only
+    ///
+    /// The contents go to an owner-only temporary file next to `path`,
+    /// which is then renamed over it, so the key is never readable by
+    /// others and a failed write leaves any previous keystore intact.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        let json = self.to_json()?;
+
+        let mut tmp_name = path
+            .file_name()
+            .ok_or_else(|| CryptoError::InvalidSecretKey("Keystore path has no file name".into()))?
+            .to_os_string();
+        tmp_name.push(".tmp");
+        let tmp = path.with_file_name(tmp_name);
+
+        let mut options = std::fs::OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+
+        // A leftover from an interrupted save may have other permissions
+        std::fs::remove_file(&tmp).ok();
+        let written = options
+            .open(&tmp)
+            .and_then(|mut file| {
+                file.write_all(json.as_bytes())?;
+                file.sync_all()
+            })
+            .and_then(|()| std::fs::rename(&tmp, path));
+        if let Err(e) = written {
+            std::fs::remove_file(&tmp).ok();
+            return Err(CryptoError::InvalidSecretKey(format!(
+                "Keystore write: {}",
+                e
+            )));
+        }
+        Ok(())
+    }
+
+    /// Read from disk
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let json = std::fs::read_to_string(path.as_ref())
+            .map_err(|e| CryptoError::InvalidSecretKey(format!("Keystore read: {}", e)))?;
+        Self::from_json(&json)
+    }
+
+    fn associated_data(&self) -> Vec<u8> {
+        let mut aad = Vec::new();
+        aad.extend_from_slice(&self.version.to_le_bytes());
+        aad.extend_from_slice(match self.kind {
+            KeyKind::Validator => b"validator",
+            KeyKind::Wallet => b"wallet",
+        });
+        aad.extend_from_slice(self.address.as_bytes());
+        aad.extend_from_slice(self.public_key.as_bytes());
+        aad
+    }
+}
+
+fn derive_kek(params: &KdfParams, credentials: &Credentials) -> Result<Zeroizing<[u8; 32]>> {
+    let salt = decode_hex(&params.salt, "salt")?;
+    let argon_params = Params::new(params.m_cost_kib, params.t_cost, params.p_cost, Some(32))
+        .map_err(|e| CryptoError::KeyDerivationFailed(format!("Argon2 params: {}", e)))?;
+    let argon = Argon2::new(Algorithm::Argon2id, Version::V0x13, argon_params);
+
+    let mut kek = Zeroizing::new([0u8; 32]);
+    argon
+        .hash_password_into(&credentials.passphrase, &salt, &mut *kek)
+        .map_err(|e| CryptoError::KeyDerivationFailed(format!("Argon2: {}", e)))?;
+
+    if let Some(keyfile) = &credentials.keyfile {
+        let mut material = Zeroizing::new(Vec::with_capacity(64));
+        material.extend_from_slice(&*kek);
+        material.extend_from_slice(blake3::hash(keyfile).as_bytes());
+        *kek = blake3::derive_key(KEYFILE_CONTEXT, &material);
+    }
+    Ok(kek)
+}
+
+fn aead_key(kek: &[u8; 32]) -> Result<LessSafeKey> {
+    let unbound = UnboundKey::new(&CHACHA20_POLY1305, kek)
+        .map_err(|_| CryptoError::KeyDerivationFailed("Invalid KEK length".to_string()))?;
+    Ok(LessSafeKey::new(unbound))
+}
+
+fn decode_hex(value: &str, field: &str) -> Result<Vec<u8>> {
+    hex::decode(value)
+        .map_err(|e| CryptoError::DecryptionError(format!("Invalid {} hex: {}", field, e)))
+}
+
+fn encode_secret(secret: &HybridSecretKey) -> Zeroizing<Vec<u8>> {
+    let dilithium = secret.dilithium_bytes();
+    let kyber = secret.kyber_bytes();
+    let mut out = Zeroizing::new(Vec::with_capacity(72 + dilithium.len() + kyber.len()));
+    out.extend_from_slice(secret.ed25519_bytes());
+    out.extend_from_slice(secret.x25519_bytes());
+    out.extend_from_slice(&(dilithium.len() as u32).to_le_bytes());
+    out.extend_from_slice(dilithium);
+    out.extend_from_slice(&(kyber.len() as u32).to_le_bytes());
+    out.extend_from_slice(kyber);
+    out
+}
+
+fn decode_secret(bytes: &[u8]) -> Result<HybridSecretKey> {
+    let ed25519: [u8; 32] = take(bytes, 0, 32)?.try_into().map_err(|_| malformed())?;
+    let x25519: [u8; 32] = take(bytes, 32, 32)?.try_into().map_err(|_| malformed())?;
+    let dilithium_len = read_len(bytes, 64)?;
+    let dilithium = take(bytes, 68, dilithium_len)?.to_vec();
+    let kyber_offset = 68 + dilithium_len;
+    let kyber_len = read_len(bytes, kyber_offset)?;
+    let kyber = take(bytes, kyber_offset + 4, kyber_len)?.to_vec();
+
+    Ok(HybridSecretKey::new(ed25519, x25519, dilithium, kyber))
+}
+
+fn take(bytes: &[u8], offset: usize, len: usize) -> Result<&[u8]> {
+    offset
+        .checked_add(len)
+        .and_then(|end| bytes.get(offset..end))
+        .ok_or_else(malformed)
+}
+
+fn read_len(bytes: &[u8], offset: usize) -> Result<usize> {
+    let raw: [u8; 4] = take(bytes, offset, 4)?
+        .try_into()
+        .map_err(|_| malformed())?;
+    Ok(u32::from_le_bytes(raw) as usize)
+}
+
+fn malformed() -> CryptoError {
+    CryptoError::InvalidSecretKey("Malformed keystore secret".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fast_params() -> KdfParams {
+        KdfParams::with_costs(256, 1, 1)
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let keypair = KeyPair::generate_hybrid().unwrap();
+        let creds = Credentials::passphrase("correct horse battery staple");
+        let file =
+            KeystoreFile::encrypt(KeyKind::Validator, &keypair, &creds, fast_params()).unwrap();
+
+        let json = file.to_json().unwrap();
+        assert!(!json.contains(&hex::encode(keypair.signer().secret_key().ed25519_bytes())));
+
+        let restored = KeystoreFile::from_json(&json)
+            .unwrap()
+            .decrypt(&creds)
+            .unwrap();
+        assert_eq!(restored.node_id(), keypair.node_id());
+        assert_eq!(restored.public_key(), keypair.public_key());
+
+        let msg = b"keystore roundtrip";
+        let sig = restored.sign(msg);
+        assert!(crate::hybrid::HybridVerifier::verify(keypair.public_key(), msg, &sig).unwrap());
+    }
+
+    #[test]
+    fn test_decrypted_key_matches_stored_public_key() {
+        let keypair = KeyPair::generate_hybrid().unwrap();
+        let creds = Credentials::passphrase("pq roundtrip");
+        let file =
+            KeystoreFile::encrypt(KeyKind::Validator, &keypair, &creds, fast_params()).unwrap();
+
+        // Verify against the public key as read back from the file, with
+        // both the Ed25519 and Dilithium components required
+        let stored = HybridPublicKey::from_bytes(&hex::decode(&file.public_key).unwrap()).unwrap();
+        assert!(stored.has_pq_keys());
+        let restored = file.decrypt(&creds).unwrap();
+        assert_eq!(restored.public_key(), &stored);
+
+        let msg = b"signed after restore";
+        let sig = restored.sign(msg);
+        assert!(!sig.dilithium_sig.is_empty());
+        assert!(crate::hybrid::HybridVerifier::verify(&stored, msg, &sig).unwrap());
+    }
+
+    #[test]
+    fn test_mismatched_stored_public_key_rejected() {
+        let keypair = KeyPair::generate_hybrid().unwrap();
+        let other = KeyPair::generate_hybrid().unwrap();
+        let creds = Credentials::passphrase("swap");
+        let mut file =
+            KeystoreFile::encrypt(KeyKind::Validator, &keypair, &creds, fast_params()).unwrap();
+
+        // Same classical keys, someone else's PQ keys
+        let mut forged = keypair.public_key().clone();
+        forged.dilithium = other.public_key().dilithium.clone();
+        forged.kyber = other.public_key().kyber.clone();
+        let secret = keypair.signer().secret_key();
+        assert!(HybridSigner::from_keypair(&secret, &forged).is_err());
+        assert!(HybridSigner::from_keypair(&secret, keypair.public_key()).is_ok());
+
+        // Editing the header is caught by the AEAD before the key check
+        file.public_key = hex::encode(forged.to_bytes());
+        assert!(file.decrypt(&creds).is_err());
+    }
+
+    #[test]
+    fn test_wrong_passphrase() {
+        let keypair = KeyPair::generate_hybrid().unwrap();
+        let file = KeystoreFile::encrypt(
+            KeyKind::Wallet,
+            &keypair,
+            &Credentials::passphrase("right"),
+            fast_params(),
+        )
+        .unwrap();
+        assert!(file.decrypt(&Credentials::passphrase("wrong")).is_err());
+    }
+
+    #[test]
+    fn test_keyfile_required() {
+        let keypair = KeyPair::generate_hybrid().unwrap();
+        let creds = Credentials::passphrase("pw").with_keyfile(b"keyfile contents".to_vec());
+        let file =
+            KeystoreFile::encrypt(KeyKind::Validator, &keypair, &creds, fast_params()).unwrap();
+        assert!(file.crypto.keyfile);
+
+        assert!(file.decrypt(&Credentials::passphrase("pw")).is_err());
+        let wrong = Credentials::passphrase("pw").with_keyfile(b"other".to_vec());
+        assert!(file.decrypt(&wrong).is_err());
+        assert!(file.decrypt(&creds).is_ok());
+    }
+
+    #[test]
+    fn test_header_tampering_detected() {
+        let keypair = KeyPair::generate_hybrid().unwrap();
+        let creds = Credentials::passphrase("pw");
+        let mut file =
+            KeystoreFile::encrypt(KeyKind::Validator, &keypair, &creds, fast_params()).unwrap();
+        file.kind = KeyKind::Wallet;
+        assert!(file.decrypt(&creds).is_err());
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let keypair = KeyPair::generate_hybrid().unwrap();
+        let creds = Credentials::passphrase("pw");
+        let file =
+            KeystoreFile::encrypt(KeyKind::Validator, &keypair, &creds, fast_params()).unwrap();
+
+        let path = std::env::temp_dir().join(format!("rope-keystore-{}.json", file.address));
+        file.save(&path).unwrap();
+        let loaded = KeystoreFile::load(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+
+        assert_eq!(loaded, file);
+        assert_eq!(loaded.decrypt(&creds).unwrap().node_id(), keypair.node_id());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_saved_file_owner_only() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let keypair = KeyPair::generate_hybrid().unwrap();
+        let creds = Credentials::passphrase("pw");
+        let file = KeystoreFile::encrypt(KeyKind::Wallet, &keypair, &creds, fast_params()).unwrap();
+
+        let dir = std::env::temp_dir().join(format!("rope-keystore-mode-{}", file.address));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("key.json");
+        // Overwriting a world-readable file still leaves it owner-only
+        std::fs::write(&path, "{}").unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o644)).unwrap();
+        file.save(&path).unwrap();
+
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        let entries = std::fs::read_dir(&dir).unwrap().count();
+        std::fs::remove_dir_all(&dir).ok();
+        assert_eq!(mode & 0o777, 0o600);
+        assert_eq!(entries, 1);
+    }
+
+    #[test]
+    fn test_excessive_kdf_costs_rejected() {
+        let keypair = KeyPair::generate_hybrid().unwrap();
+        let creds = Credentials::passphrase("pw");
+        let mut file =
+            KeystoreFile::encrypt(KeyKind::Validator, &keypair, &creds, fast_params()).unwrap();
+
+        // The costs sit outside the AEAD, so they are bounded before use
+        file.crypto.kdf_params.m_cost_kib = u32::MAX;
+        let json = file.to_json().unwrap();
+        assert!(KeystoreFile::from_json(&json).is_err());
+        assert!(file.decrypt(&creds).is_err());
+
+        file.crypto.kdf_params = KdfParams {
+            t_cost: MAX_T_COST + 1,
+            ..fast_params()
+        };
+        assert!(file.decrypt(&creds).is_err());
+    }
+}
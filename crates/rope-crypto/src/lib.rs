@@ -2,9 +2,18 @@
 //!
 //! Cryptographic primitives for Datachain Rope including:
 //! - Organic Encryption System (OES) - Self-evolving post-quantum crypto
+//! - OES key rotation with data key re-wrapping
 //! - Hybrid signatures (Ed25519 + CRYSTALS-Dilithium3)
 //! - Hybrid key exchange (X25519 + CRYSTALS-Kyber768)
+//! - BLS12-381 aggregate signatures for testimony compression
+//! - FROST threshold Ed25519 signatures for federation quorums
+//! - ECVRF-Ed25519 verifiable random function for producer selection
 //! - BLAKE3 hashing utilities
+//! - Encrypted keystore (Argon2id + ChaCha20-Poly1305)
+//! - Streaming AEAD for large payloads (chunked ChaCha20-Poly1305, STREAM)
+//! - HD key derivation from BIP39 mnemonics (SLIP-10)
+//! - Remote/HSM Ed25519 signing with timeouts and retries
+//! - Secure memory: zeroizing `SecretBytes` and constant-time comparison
 //!
 //! ## Security Model
 //!
@@ -15,19 +24,52 @@
 //! |----------|-----------|----------------|
 //! | Signatures | Ed25519 + Dilithium3 | 256-bit + NIST PQ-3 |
 //! | Hashing | BLAKE3 | 256-bit |
+//! | Aggregation | BLS12-381 | 128-bit |
 //! | Key Exchange | X25519 + Kyber768 | 256-bit + NIST PQ-3 |
+//!
+//! ## Features
+//!
+//! - `dilithium` (default): CRYSTALS-Dilithium3 signatures via pqcrypto
+//! - `kyber` (default): CRYSTALS-Kyber768 key encapsulation via pqcrypto
+//! - `pkcs11`: PKCS#11 hardware signer for HSMs and YubiKeys
 
+pub mod bls;
 pub mod error;
 pub mod hash;
+pub mod hd;
 pub mod hybrid;
 pub mod keys;
+pub mod keystore;
 pub mod oes;
+pub mod oes_rotation;
+pub mod pq;
+pub mod remote_signer;
+pub mod secret;
+pub mod stream;
+pub mod threshold;
+pub mod vrf;
 
+pub use bls::*;
 pub use error::*;
 pub use hash::*;
+pub use hd::{DerivationPath, ExtendedKey, HdWallet, KeyPurpose};
 pub use hybrid::*;
 pub use keys::*;
+pub use keystore::{Credentials, KdfParams, KeyKind, KeystoreFile};
 pub use oes::*;
+pub use oes_rotation::{OesRotationScheduler, RotationPolicy, RotationRecord, WrappedKey};
+pub use pq::{Dilithium3, Kyber768, PqKem, PqSignatureScheme};
+pub use remote_signer::{
+    sign_with_policy, HybridRemoteSigner, LocalSigner, RemoteSigner, SigningPolicy,
+};
+pub use secret::{ct_eq, SecretBytes};
+pub use stream::{
+    decrypt_stream, encrypt_stream, DecryptReader, EncryptWriter, StreamDecryptor, StreamEncryptor,
+};
+pub use threshold::{
+    DkgParticipant, DkgRound1Package, DkgRound2Package, KeyShare, ParticipantId, PublicKeyPackage,
+    SignatureShare, SigningCommitments, SigningNonces, SigningPackage, ThresholdParams,
+};
 
 /// Cryptographic prelude
 pub mod prelude {
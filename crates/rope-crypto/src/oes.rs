@@ -848,7 +848,7 @@ impl OrganicEncryptionState {
             return false;
         }
         if proof.generation == self.generation {
-            return proof.state_commitment == self.current_sync_hash;
+            return crate::secret::ct_eq(&proof.state_commitment, &self.current_sync_hash);
         }
         true
     }
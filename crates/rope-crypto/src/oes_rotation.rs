@@ -0,0 +1,465 @@
+//! OES key rotation
+//!
+//! Evolves the [`OrganicEncryptionState`] on a configurable cadence and keeps
+//! stored data keys usable across generations.
+//!
+//! Data keys are wrapped with ChaCha20-Poly1305 under a key-encryption key
+//! (KEK) derived from the current OES generation. On rotation every stored
+//! data key is unwrapped with the old KEK, the state evolves, and the keys
+//! are re-wrapped under the new KEK. The old KEK is zeroized immediately.
+//!
+//! ## Rollback protection
+//!
+//! - The wrap AAD binds the key ID and generation, so a blob cannot be
+//!   replayed under another generation
+//! - Unwrapping only accepts blobs from the current generation
+//! - Restoring a state older than the highest generation seen is refused
+//!
+//! Each rotation yields a [`RotationRecord`] carrying an [`OESProof`]; the
+//! node drains these and anchors them in the lattice.
+
+use std::collections::HashMap;
+
+use parking_lot::RwLock;
+use rand::rngs::OsRng;
+use rand::RngCore;
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, CHACHA20_POLY1305, NONCE_LEN};
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroizing;
+
+use crate::error::{CryptoError, Result};
+use crate::oes::{OESProof, OESSecrets, OrganicEncryptionState};
+use rope_core::types::constants::OES_EVOLUTION_INTERVAL;
+
+/// Key derivation purpose for the data-key KEK
+const KEK_PURPOSE: &str = "oes-data-key-wrap";
+
+/// Rotation cadence
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RotationPolicy {
+    /// Rotate every N anchors (0 disables anchor-based rotation)
+    pub anchor_interval: u64,
+
+    /// Rotate when the current generation is older than this (seconds)
+    pub max_age_secs: Option<i64>,
+}
+
+impl Default for RotationPolicy {
+    fn default() -> Self {
+        Self {
+            anchor_interval: OES_EVOLUTION_INTERVAL,
+            max_age_secs: Some(24 * 60 * 60),
+        }
+    }
+}
+
+/// Data key wrapped under a generation's KEK
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WrappedKey {
+    /// Caller-chosen key identifier
+    pub key_id: [u8; 32],
+
+    /// OES generation whose KEK wrapped this key
+    pub generation: u64,
+
+    /// AEAD nonce
+    pub nonce: [u8; NONCE_LEN],
+
+    /// Ciphertext with authentication tag
+    #[serde(with = "serde_bytes")]
+    pub ciphertext: Vec<u8>,
+}
+
+/// Evidence of one OES evolution, to be anchored in the lattice
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RotationRecord {
+    pub from_generation: u64,
+    pub to_generation: u64,
+    pub anchor_hash: [u8; 32],
+    pub previous_sync_hash: [u8; 32],
+    pub proof: OESProof,
+
+    /// Number of data keys re-wrapped
+    pub rewrapped: usize,
+
+    pub rotated_at: i64,
+}
+
+struct SchedulerState {
+    oes: OrganicEncryptionState,
+    // Owned for the generation's lifetime; zeroized when replaced
+    #[allow(dead_code)]
+    secrets: OESSecrets,
+    kek: Zeroizing<Vec<u8>>,
+    highest_generation: u64,
+    generation_started_at: i64,
+}
+
+/// Drives OES evolution and data key re-wrapping
+pub struct OesRotationScheduler {
+    policy: RotationPolicy,
+    state: RwLock<SchedulerState>,
+    keys: RwLock<HashMap<[u8; 32], WrappedKey>>,
+    records: RwLock<Vec<RotationRecord>>,
+}
+
+impl OesRotationScheduler {
+    /// Create a scheduler over an existing state
+    pub fn new(
+        oes: OrganicEncryptionState,
+        secrets: OESSecrets,
+        policy: RotationPolicy,
+        now: i64,
+    ) -> Self {
+        let kek = Zeroizing::new(oes.derive_key(32, KEK_PURPOSE));
+        let highest_generation = oes.generation();
+        Self {
+            policy,
+            state: RwLock::new(SchedulerState {
+                oes,
+                secrets,
+                kek,
+                highest_generation,
+                generation_started_at: now,
+            }),
+            keys: RwLock::new(HashMap::new()),
+            records: RwLock::new(Vec::new()),
+        }
+    }
+
+    /// Create a scheduler from a genesis seed
+    pub fn genesis(seed: &[u8; 32], policy: RotationPolicy, now: i64) -> Self {
+        let (oes, secrets) = OrganicEncryptionState::genesis(seed);
+        Self::new(oes, secrets, policy, now)
+    }
+
+    /// Current OES generation
+    pub fn generation(&self) -> u64 {
+        self.state.read().oes.generation()
+    }
+
+    /// Current OES sync hash
+    pub fn sync_hash(&self) -> [u8; 32] {
+        self.state.read().oes.sync_hash()
+    }
+
+    /// Whether the policy calls for a rotation
+    pub fn is_due(&self, anchor_count: u64, now: i64) -> bool {
+        let by_anchor = self.policy.anchor_interval > 0
+            && anchor_count > 0
+            && anchor_count.is_multiple_of(self.policy.anchor_interval);
+        let by_age = self
+            .policy
+            .max_age_secs
+            .is_some_and(|max| now - self.state.read().generation_started_at >= max);
+        by_anchor || by_age
+    }
+
+    /// Rotate if due after an anchor
+    pub fn on_anchor(
+        &self,
+        anchor_count: u64,
+        anchor_hash: &[u8; 32],
+        now: i64,
+    ) -> Result<Option<RotationRecord>> {
+        if !self.is_due(anchor_count, now) {
+            return Ok(None);
+        }
+        self.rotate(anchor_hash, now).map(Some)
+    }
+
+    /// Evolve the state and re-wrap all stored data keys
+    pub fn rotate(&self, anchor_hash: &[u8; 32], now: i64) -> Result<RotationRecord> {
+        let mut state = self.state.write();
+        let mut keys = self.keys.write();
+
+        // Unwrap everything under the old KEK before it is discarded
+        let mut plaintexts = Vec::with_capacity(keys.len());
+        for wrapped in keys.values() {
+            let plaintext = open(&state.kek, wrapped)?;
+            plaintexts.push((wrapped.key_id, plaintext));
+        }
+
+        let from_generation = state.oes.generation();
+        let previous_sync_hash = state.oes.sync_hash();
+        state.secrets = state.oes.evolve(anchor_hash);
+        let to_generation = state.oes.generation();
+
+        // Dropping the old KEK zeroizes it
+        state.kek = Zeroizing::new(state.oes.derive_key(32, KEK_PURPOSE));
+        state.highest_generation = state.highest_generation.max(to_generation);
+        state.generation_started_at = now;
+
+        for (key_id, plaintext) in &plaintexts {
+            let wrapped = seal(&state.kek, to_generation, *key_id, plaintext)?;
+            keys.insert(*key_id, wrapped);
+        }
+
+        let record = RotationRecord {
+            from_generation,
+            to_generation,
+            anchor_hash: *anchor_hash,
+            previous_sync_hash,
+            proof: state.oes.generate_proof(),
+            rewrapped: plaintexts.len(),
+            rotated_at: now,
+        };
+
+        tracing::info!(
+            "OES rotated {} -> {}, re-wrapped {} data keys",
+            from_generation,
+            to_generation,
+            record.rewrapped
+        );
+
+        self.records.write().push(record.clone());
+        Ok(record)
+    }
+
+    /// Wrap a data key under the current generation without storing it
+    pub fn wrap(&self, key_id: [u8; 32], data_key: &[u8]) -> Result<WrappedKey> {
+        let state = self.state.read();
+        seal(&state.kek, state.oes.generation(), key_id, data_key)
+    }
+
+    /// Unwrap a data key wrapped under the current generation
+    pub fn unwrap(&self, wrapped: &WrappedKey) -> Result<Zeroizing<Vec<u8>>> {
+        let state = self.state.read();
+        let current = state.oes.generation();
+        if wrapped.generation != current {
+            return Err(CryptoError::DecryptionError(format!(
+                "Key wrapped at generation {} but current generation is {}",
+                wrapped.generation, current
+            )));
+        }
+        open(&state.kek, wrapped)
+    }
+
+    /// Wrap and store a data key so it is re-wrapped on rotation
+    pub fn store_data_key(&self, key_id: [u8; 32], data_key: &[u8]) -> Result<()> {
+        let wrapped = self.wrap(key_id, data_key)?;
+        self.keys.write().insert(key_id, wrapped);
+        Ok(())
+    }
+
+    /// Load a stored data key
+    pub fn load_data_key(&self, key_id: &[u8; 32]) -> Result<Zeroizing<Vec<u8>>> {
+        let wrapped = self
+            .keys
+            .read()
+            .get(key_id)
+            .cloned()
+            .ok_or_else(|| CryptoError::DecryptionError("Unknown data key".to_string()))?;
+        self.unwrap(&wrapped)
+    }
+
+    /// Remove a stored data key
+    pub fn remove_data_key(&self, key_id: &[u8; 32]) -> bool {
+        self.keys.write().remove(key_id).is_some()
+    }
+
+    /// Stored data keys in wrapped form, for persistence
+    pub fn wrapped_keys(&self) -> Vec<WrappedKey> {
+        self.keys.read().values().cloned().collect()
+    }
+
+    /// Replace the state, e.g. after loading from disk
+    ///
+    /// Refuses any state older than the highest generation seen, so a
+    /// stale snapshot cannot resurrect retired keys.
+    pub fn restore_state(
+        &self,
+        oes: OrganicEncryptionState,
+        secrets: OESSecrets,
+        wrapped: Vec<WrappedKey>,
+    ) -> Result<()> {
+        let mut state = self.state.write();
+        if oes.generation() < state.highest_generation {
+            return Err(CryptoError::OESError(format!(
+                "Rollback detected: generation {} is older than {}",
+                oes.generation(),
+                state.highest_generation
+            )));
+        }
+
+        let kek = Zeroizing::new(oes.derive_key(32, KEK_PURPOSE));
+        for key in &wrapped {
+            if key.generation != oes.generation() {
+                return Err(CryptoError::OESError(format!(
+                    "Wrapped key from generation {} does not match state generation {}",
+                    key.generation,
+                    oes.generation()
+                )));
+            }
+            open(&kek, key)?;
+        }
+
+        state.highest_generation = oes.generation();
+        state.oes = oes;
+        state.secrets = secrets;
+        state.kek = kek;
+
+        let mut keys = self.keys.write();
+        keys.clear();
+        keys.extend(wrapped.into_iter().map(|k| (k.key_id, k)));
+        Ok(())
+    }
+
+    /// Take rotation records not yet anchored
+    pub fn drain_records(&self) -> Vec<RotationRecord> {
+        std::mem::take(&mut *self.records.write())
+    }
+}
+
+/// Additional authenticated data binding key ID and generation
+fn aad(key_id: &[u8; 32], generation: u64) -> [u8; 40] {
+    let mut aad = [0u8; 40];
+    aad[..32].copy_from_slice(key_id);
+    aad[32..].copy_from_slice(&generation.to_le_bytes());
+    aad
+}
+
+fn aead_key(kek: &[u8]) -> Result<LessSafeKey> {
+    let unbound = UnboundKey::new(&CHACHA20_POLY1305, kek)
+        .map_err(|_| CryptoError::KeyDerivationFailed("Invalid KEK length".to_string()))?;
+    Ok(LessSafeKey::new(unbound))
+}
+
+fn seal(kek: &[u8], generation: u64, key_id: [u8; 32], plaintext: &[u8]) -> Result<WrappedKey> {
+    let key = aead_key(kek)?;
+    let mut nonce = [0u8; NONCE_LEN];
+    OsRng.fill_bytes(&mut nonce);
+
+    let mut ciphertext = plaintext.to_vec();
+    key.seal_in_place_append_tag(
+        Nonce::assume_unique_for_key(nonce),
+        Aad::from(aad(&key_id, generation)),
+        &mut ciphertext,
+    )
+    .map_err(|_| CryptoError::EncapsulationFailed("Data key wrap failed".to_string()))?;
+
+    Ok(WrappedKey {
+        key_id,
+        generation,
+        nonce,
+        ciphertext,
+    })
+}
+
+fn open(kek: &[u8], wrapped: &WrappedKey) -> Result<Zeroizing<Vec<u8>>> {
+    let key = aead_key(kek)?;
+    let mut buffer = Zeroizing::new(wrapped.ciphertext.clone());
+    let plaintext = key
+        .open_in_place(
+            Nonce::assume_unique_for_key(wrapped.nonce),
+            Aad::from(aad(&wrapped.key_id, wrapped.generation)),
+            &mut buffer,
+        )
+        .map_err(|_| CryptoError::DecryptionError("Data key unwrap failed".to_string()))?;
+    Ok(Zeroizing::new(plaintext.to_vec()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scheduler(policy: RotationPolicy) -> OesRotationScheduler {
+        OesRotationScheduler::genesis(&[7u8; 32], policy, 0)
+    }
+
+    #[test]
+    fn test_store_and_load() {
+        let s = scheduler(RotationPolicy::default());
+        s.store_data_key([1; 32], b"data key one").unwrap();
+        assert_eq!(
+            s.load_data_key(&[1; 32]).unwrap().as_slice(),
+            b"data key one"
+        );
+        assert!(s.load_data_key(&[2; 32]).is_err());
+    }
+
+    #[test]
+    fn test_cadence() {
+        let policy = RotationPolicy {
+            anchor_interval: 10,
+            max_age_secs: Some(100),
+        };
+        let s = scheduler(policy);
+        assert!(!s.is_due(0, 0));
+        assert!(!s.is_due(9, 0));
+        assert!(s.is_due(10, 0));
+        assert!(s.is_due(11, 100));
+
+        assert!(s.on_anchor(5, &[0; 32], 1).unwrap().is_none());
+        let record = s.on_anchor(10, &[3; 32], 2).unwrap().unwrap();
+        assert_eq!(record.from_generation, 0);
+        assert_eq!(record.to_generation, 1);
+
+        // Age resets after rotation
+        assert!(!s.is_due(11, 101));
+        assert!(s.is_due(11, 102));
+    }
+
+    #[test]
+    fn test_rotation_rewraps_and_records_proof() {
+        let s = scheduler(RotationPolicy::default());
+        s.store_data_key([1; 32], b"alpha").unwrap();
+        s.store_data_key([2; 32], b"beta").unwrap();
+
+        let record = s.rotate(&[9; 32], 10).unwrap();
+        assert_eq!(record.rewrapped, 2);
+        assert_eq!(record.proof.generation, 1);
+        assert_eq!(record.proof.state_commitment, s.sync_hash());
+
+        assert_eq!(s.load_data_key(&[1; 32]).unwrap().as_slice(), b"alpha");
+        assert_eq!(s.load_data_key(&[2; 32]).unwrap().as_slice(), b"beta");
+        assert!(s.wrapped_keys().iter().all(|k| k.generation == 1));
+
+        let records = s.drain_records();
+        assert_eq!(records, vec![record]);
+        assert!(s.drain_records().is_empty());
+    }
+
+    #[test]
+    fn test_old_generation_cannot_decrypt_new_data() {
+        let s = scheduler(RotationPolicy::default());
+        let (old_state, _) = OrganicEncryptionState::genesis(&[7u8; 32]);
+        let old_kek = old_state.derive_key(32, KEK_PURPOSE);
+
+        let before = s.wrap([1; 32], b"pre-rotation").unwrap();
+        s.rotate(&[9; 32], 10).unwrap();
+        let after = s.wrap([1; 32], b"post-rotation").unwrap();
+
+        // Old KEK cannot open post-rotation data
+        assert!(open(&old_kek, &after).is_err());
+        // Stale blobs are refused by the scheduler
+        assert!(s.unwrap(&before).is_err());
+
+        // Relabelling the generation breaks authentication
+        let mut relabelled = before.clone();
+        relabelled.generation = 1;
+        assert!(s.unwrap(&relabelled).is_err());
+    }
+
+    #[test]
+    fn test_restore_rejects_rollback() {
+        let s = scheduler(RotationPolicy::default());
+        s.rotate(&[9; 32], 10).unwrap();
+
+        let (stale, secrets) = OrganicEncryptionState::genesis(&[7u8; 32]);
+        let err = s.restore_state(stale, secrets, Vec::new()).unwrap_err();
+        assert!(matches!(err, CryptoError::OESError(_)));
+        assert_eq!(s.generation(), 1);
+    }
+
+    #[test]
+    fn test_restore_same_generation() {
+        let s = scheduler(RotationPolicy::default());
+        s.store_data_key([4; 32], b"persisted").unwrap();
+        let wrapped = s.wrapped_keys();
+
+        let (state, secrets) = OrganicEncryptionState::genesis(&[7u8; 32]);
+        s.restore_state(state, secrets, wrapped).unwrap();
+        assert_eq!(s.load_data_key(&[4; 32]).unwrap().as_slice(), b"persisted");
+    }
+}
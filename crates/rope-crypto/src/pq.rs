@@ -0,0 +1,820 @@
+//! Post-quantum backends
+//!
+//! Hybrid signing and key exchange call into these traits rather than a
+//! specific library, so the PQ components can be swapped or compiled out.
+//!
+//! | Feature | Scheme | Backend |
+//! |---------|--------|---------|
+//! | `dilithium` | CRYSTALS-Dilithium3 | pqcrypto (PQClean) |
+//! | `kyber` | CRYSTALS-Kyber768 | pqcrypto (PQClean) |
+//!
+//! Both features are enabled by default. With a feature disabled, key
+//! generation yields no PQ key material and every operation on PQ keys
+//! fails with [`CryptoError::BackendUnavailable`], so verification fails
+//! closed.
+//!
+//! Dilithium signatures use the attached (signed message) encoding,
+//! `signature || message`, for wire compatibility with existing testimonies.
+//!
+//! PQClean draws key generation randomness from the OS, so seeded key
+//! generation (for keys restored from a mnemonic) runs the reference key
+//! generation here, with the seed in place of `randombytes`. The keys it
+//! produces are used with the backend like any other.
+
+use crate::error::{CryptoError, Result};
+
+/// Post-quantum signature scheme
+pub trait PqSignatureScheme {
+    /// Scheme name
+    const NAME: &'static str;
+
+    /// Public key size in bytes
+    const PUBLIC_KEY_SIZE: usize;
+
+    /// Secret key size in bytes
+    const SECRET_KEY_SIZE: usize;
+
+    /// Detached signature size in bytes
+    const SIGNATURE_SIZE: usize;
+
+    /// Generate a keypair as `(public_key, secret_key)`
+    fn keypair() -> Result<(Vec<u8>, Vec<u8>)>;
+
+    /// Deterministic keypair from a 32-byte seed
+    ///
+    /// Backends without seeded key generation return
+    /// [`CryptoError::BackendUnavailable`].
+    fn keypair_from_seed(_seed: &[u8; 32]) -> Result<(Vec<u8>, Vec<u8>)> {
+        Err(CryptoError::BackendUnavailable(format!(
+            "{} backend has no seeded key generation",
+            Self::NAME
+        )))
+    }
+
+    /// Sign a message, returning the signed message encoding
+    fn sign(secret_key: &[u8], message: &[u8]) -> Result<Vec<u8>>;
+
+    /// Verify a signed message against the expected message
+    fn verify(public_key: &[u8], message: &[u8], signed_message: &[u8]) -> Result<bool>;
+}
+
+/// Post-quantum key encapsulation mechanism
+pub trait PqKem {
+    /// Scheme name
+    const NAME: &'static str;
+
+    /// Public key size in bytes
+    const PUBLIC_KEY_SIZE: usize;
+
+    /// Secret key size in bytes
+    const SECRET_KEY_SIZE: usize;
+
+    /// Ciphertext size in bytes
+    const CIPHERTEXT_SIZE: usize;
+
+    /// Shared secret size in bytes
+    const SHARED_SECRET_SIZE: usize;
+
+    /// Generate a keypair as `(public_key, secret_key)`
+    fn keypair() -> Result<(Vec<u8>, Vec<u8>)>;
+
+    /// Deterministic keypair from a 32-byte seed
+    ///
+    /// Backends without seeded key generation return
+    /// [`CryptoError::BackendUnavailable`].
+    fn keypair_from_seed(_seed: &[u8; 32]) -> Result<(Vec<u8>, Vec<u8>)> {
+        Err(CryptoError::BackendUnavailable(format!(
+            "{} backend has no seeded key generation",
+            Self::NAME
+        )))
+    }
+
+    /// Encapsulate to a public key, returning `(ciphertext, shared_secret)`
+    fn encapsulate(public_key: &[u8]) -> Result<(Vec<u8>, Vec<u8>)>;
+
+    /// Recover the shared secret from a ciphertext
+    fn decapsulate(secret_key: &[u8], ciphertext: &[u8]) -> Result<Vec<u8>>;
+}
+
+/// Whether the Dilithium3 backend is compiled in
+pub const fn dilithium_enabled() -> bool {
+    cfg!(feature = "dilithium")
+}
+
+/// Whether the Kyber768 backend is compiled in
+pub const fn kyber_enabled() -> bool {
+    cfg!(feature = "kyber")
+}
+
+// ============================================================================
+// Dilithium3
+// ============================================================================
+
+/// CRYSTALS-Dilithium3 (NIST Level 3)
+pub struct Dilithium3;
+
+#[cfg(feature = "dilithium")]
+impl PqSignatureScheme for Dilithium3 {
+    const NAME: &'static str = "Dilithium3";
+    const PUBLIC_KEY_SIZE: usize = 1952;
+    const SECRET_KEY_SIZE: usize = 4032;
+    const SIGNATURE_SIZE: usize = 3309;
+
+    fn keypair() -> Result<(Vec<u8>, Vec<u8>)> {
+        use pqcrypto_traits::sign::{PublicKey, SecretKey};
+
+        let (pk, sk) = pqcrypto_dilithium::dilithium3::keypair();
+        Ok((pk.as_bytes().to_vec(), sk.as_bytes().to_vec()))
+    }
+
+    fn keypair_from_seed(seed: &[u8; 32]) -> Result<(Vec<u8>, Vec<u8>)> {
+        Ok(seeded::dilithium3_keypair(seed))
+    }
+
+    fn sign(secret_key: &[u8], message: &[u8]) -> Result<Vec<u8>> {
+        use pqcrypto_dilithium::dilithium3;
+        use pqcrypto_traits::sign::{SecretKey, SignedMessage};
+
+        let sk = dilithium3::SecretKey::from_bytes(secret_key)
+            .map_err(|e| CryptoError::InvalidSecretKey(format!("Dilithium3: {:?}", e)))?;
+        Ok(dilithium3::sign(message, &sk).as_bytes().to_vec())
+    }
+
+    fn verify(public_key: &[u8], message: &[u8], signed_message: &[u8]) -> Result<bool> {
+        use pqcrypto_dilithium::dilithium3;
+        use pqcrypto_traits::sign::{PublicKey, SignedMessage};
+
+        let pk = dilithium3::PublicKey::from_bytes(public_key).map_err(|e| {
+            tracing::error!("Failed to parse Dilithium public key: {:?}", e);
+            CryptoError::InvalidPublicKey("Invalid Dilithium public key format".to_string())
+        })?;
+        let signed = dilithium3::SignedMessage::from_bytes(signed_message).map_err(|e| {
+            tracing::error!("Failed to parse Dilithium signature: {:?}", e);
+            CryptoError::InvalidSignature("Invalid Dilithium signature format".to_string())
+        })?;
+
+        match dilithium3::open(&signed, &pk) {
+            Ok(opened) => {
+                let matches = opened == message;
+                if !matches {
+                    tracing::warn!("Dilithium signature valid but message mismatch");
+                }
+                Ok(matches)
+            }
+            Err(_) => {
+                tracing::debug!("Dilithium signature cryptographic verification failed");
+                Ok(false)
+            }
+        }
+    }
+}
+
+#[cfg(not(feature = "dilithium"))]
+impl PqSignatureScheme for Dilithium3 {
+    const NAME: &'static str = "Dilithium3";
+    const PUBLIC_KEY_SIZE: usize = 1952;
+    const SECRET_KEY_SIZE: usize = 4032;
+    const SIGNATURE_SIZE: usize = 3309;
+
+    fn keypair() -> Result<(Vec<u8>, Vec<u8>)> {
+        Err(unavailable(Self::NAME, "dilithium"))
+    }
+
+    fn sign(_secret_key: &[u8], _message: &[u8]) -> Result<Vec<u8>> {
+        Err(unavailable(Self::NAME, "dilithium"))
+    }
+
+    fn verify(_public_key: &[u8], _message: &[u8], _signed_message: &[u8]) -> Result<bool> {
+        Err(unavailable(Self::NAME, "dilithium"))
+    }
+}
+
+// ============================================================================
+// Kyber768
+// ============================================================================
+
+/// CRYSTALS-Kyber768 (NIST Level 3)
+pub struct Kyber768;
+
+#[cfg(feature = "kyber")]
+impl PqKem for Kyber768 {
+    const NAME: &'static str = "Kyber768";
+    const PUBLIC_KEY_SIZE: usize = 1184;
+    const SECRET_KEY_SIZE: usize = 2400;
+    const CIPHERTEXT_SIZE: usize = 1088;
+    const SHARED_SECRET_SIZE: usize = 32;
+
+    fn keypair() -> Result<(Vec<u8>, Vec<u8>)> {
+        use pqcrypto_traits::kem::{PublicKey, SecretKey};
+
+        let (pk, sk) = pqcrypto_kyber::kyber768::keypair();
+        Ok((pk.as_bytes().to_vec(), sk.as_bytes().to_vec()))
+    }
+
+    fn keypair_from_seed(seed: &[u8; 32]) -> Result<(Vec<u8>, Vec<u8>)> {
+        Ok(seeded::kyber768_keypair(seed))
+    }
+
+    fn encapsulate(public_key: &[u8]) -> Result<(Vec<u8>, Vec<u8>)> {
+        use pqcrypto_kyber::kyber768;
+        use pqcrypto_traits::kem::{Ciphertext, PublicKey, SharedSecret};
+
+        let pk = kyber768::PublicKey::from_bytes(public_key).map_err(|e| {
+            tracing::error!("Failed to parse Kyber public key: {:?}", e);
+            CryptoError::InvalidPublicKey("Invalid Kyber public key".to_string())
+        })?;
+        let (shared, ciphertext) = kyber768::encapsulate(&pk);
+        Ok((ciphertext.as_bytes().to_vec(), shared.as_bytes().to_vec()))
+    }
+
+    fn decapsulate(secret_key: &[u8], ciphertext: &[u8]) -> Result<Vec<u8>> {
+        use pqcrypto_kyber::kyber768;
+        use pqcrypto_traits::kem::{Ciphertext, SecretKey, SharedSecret};
+
+        let sk = kyber768::SecretKey::from_bytes(secret_key).map_err(|e| {
+            tracing::error!("Failed to parse Kyber secret key: {:?}", e);
+            CryptoError::InvalidSecretKey("Invalid Kyber secret key".to_string())
+        })?;
+        let ct = kyber768::Ciphertext::from_bytes(ciphertext).map_err(|e| {
+            tracing::error!("Failed to parse Kyber ciphertext: {:?}", e);
+            CryptoError::DecryptionError("Invalid Kyber ciphertext".to_string())
+        })?;
+        Ok(kyber768::decapsulate(&ct, &sk).as_bytes().to_vec())
+    }
+}
+
+#[cfg(not(feature = "kyber"))]
+impl PqKem for Kyber768 {
+    const NAME: &'static str = "Kyber768";
+    const PUBLIC_KEY_SIZE: usize = 1184;
+    const SECRET_KEY_SIZE: usize = 2400;
+    const CIPHERTEXT_SIZE: usize = 1088;
+    const SHARED_SECRET_SIZE: usize = 32;
+
+    fn keypair() -> Result<(Vec<u8>, Vec<u8>)> {
+        Err(unavailable(Self::NAME, "kyber"))
+    }
+
+    fn encapsulate(_public_key: &[u8]) -> Result<(Vec<u8>, Vec<u8>)> {
+        Err(unavailable(Self::NAME, "kyber"))
+    }
+
+    fn decapsulate(_secret_key: &[u8], _ciphertext: &[u8]) -> Result<Vec<u8>> {
+        Err(unavailable(Self::NAME, "kyber"))
+    }
+}
+
+// ============================================================================
+// Seeded key generation
+// ============================================================================
+
+/// Reference key generation over a caller-supplied seed, for the
+/// Dilithium3 (ML-DSA ipd sizes) and Kyber768 (round 3) revisions the
+/// backends ship
+///
+/// Only the encodings, `ExpandA`/`gen_matrix` and the NTT have to match the
+/// backend exactly; the tests check the keys against the NIST KATs.
+#[cfg(any(feature = "dilithium", feature = "kyber"))]
+mod seeded {
+    use sha3::digest::{ExtendableOutput, Update, XofReader};
+
+    const N: usize = 256;
+
+    type Poly = [i64; N];
+
+    fn xof<H: Default + Update + ExtendableOutput>(parts: &[&[u8]]) -> H::Reader {
+        let mut hasher = H::default();
+        for part in parts {
+            hasher.update(part);
+        }
+        hasher.finalize_xof()
+    }
+
+    /// Pack coefficients in `[0, 2^bits)` least significant bit first
+    fn pack(coeffs: &Poly, bits: u32, out: &mut Vec<u8>) {
+        let mut acc = 0u64;
+        let mut filled = 0;
+        for &c in coeffs {
+            acc |= (c as u64) << filled;
+            filled += bits;
+            while filled >= 8 {
+                out.push(acc as u8);
+                acc >>= 8;
+                filled -= 8;
+            }
+        }
+    }
+
+    fn pow_mod(mut base: i64, mut exp: u64, q: i64) -> i64 {
+        let mut result = 1;
+        base %= q;
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = result * base % q;
+            }
+            base = base * base % q;
+            exp >>= 1;
+        }
+        result
+    }
+
+    /// Twiddle `zeta^brv(k)` of the reference NTTs, `brv` over `bits` bits
+    fn twiddle(zeta: i64, k: usize, bits: u32, q: i64) -> i64 {
+        let brv = k.reverse_bits() >> (usize::BITS - bits);
+        pow_mod(zeta, brv as u64, q)
+    }
+
+    /// Reference forward NTT (Cooley-Tukey, bit-reversed output) over
+    /// `layers` layers; coefficients in `[0, q)`
+    fn ntt(a: &mut Poly, zeta: i64, layers: u32, q: i64) {
+        let mut k = 1;
+        let mut len = N / 2;
+        for _ in 0..layers {
+            for start in (0..N).step_by(2 * len) {
+                let z = twiddle(zeta, k, layers, q);
+                k += 1;
+                for j in start..start + len {
+                    let t = z * a[j + len] % q;
+                    a[j + len] = (a[j] - t).rem_euclid(q);
+                    a[j] = (a[j] + t) % q;
+                }
+            }
+            len /= 2;
+        }
+    }
+
+    /// Inverse of the full 8-layer [`ntt`]
+    #[cfg(feature = "dilithium")]
+    fn inv_ntt(a: &mut Poly, zeta: i64, q: i64) {
+        let mut len = 1;
+        while len < N {
+            let blocks = N / (2 * len);
+            for block in 0..blocks {
+                let z_inv = pow_mod(twiddle(zeta, blocks + block, 8, q), q as u64 - 2, q);
+                let start = block * 2 * len;
+                for j in start..start + len {
+                    let (x, y) = (a[j], a[j + len]);
+                    a[j] = (x + y) % q;
+                    a[j + len] = (x - y).rem_euclid(q) * z_inv % q;
+                }
+            }
+            len *= 2;
+        }
+        let n_inv = pow_mod(N as i64, q as u64 - 2, q);
+        for c in a.iter_mut() {
+            *c = *c * n_inv % q;
+        }
+    }
+
+    // ------------------------------------------------------------------------
+    // Dilithium3
+    // ------------------------------------------------------------------------
+
+    #[cfg(feature = "dilithium")]
+    mod dilithium {
+        pub const Q: i64 = 8_380_417;
+        pub const ZETA: i64 = 1753;
+        pub const K: usize = 6;
+        pub const L: usize = 5;
+        pub const ETA: i64 = 4;
+        pub const D: u32 = 13;
+        pub const SEEDBYTES: usize = 32;
+        pub const CRHBYTES: usize = 64;
+        pub const TRBYTES: usize = 64;
+    }
+
+    /// `poly_uniform`: rejection sampling of `ExpandA` entries
+    #[cfg(feature = "dilithium")]
+    fn dilithium_uniform(rho: &[u8], nonce: u16) -> Poly {
+        let mut reader = xof::<sha3::Shake128>(&[rho, &nonce.to_le_bytes()]);
+        let mut a = [0; N];
+        let mut filled = 0;
+        let mut buf = [0u8; 3];
+        while filled < N {
+            reader.read(&mut buf);
+            let t = (buf[0] as i64 | (buf[1] as i64) << 8 | (buf[2] as i64) << 16) & 0x7F_FFFF;
+            if t < dilithium::Q {
+                a[filled] = t;
+                filled += 1;
+            }
+        }
+        a
+    }
+
+    /// `poly_uniform_eta` for eta = 4: coefficients in `[-4, 4]`
+    #[cfg(feature = "dilithium")]
+    fn dilithium_eta(rhoprime: &[u8], nonce: u16) -> Poly {
+        let mut reader = xof::<sha3::Shake256>(&[rhoprime, &nonce.to_le_bytes()]);
+        let mut a = [0; N];
+        let mut filled = 0;
+        let mut byte = [0u8; 1];
+        while filled < N {
+            reader.read(&mut byte);
+            for t in [byte[0] & 0x0F, byte[0] >> 4] {
+                if t < 9 && filled < N {
+                    a[filled] = dilithium::ETA - t as i64;
+                    filled += 1;
+                }
+            }
+        }
+        a
+    }
+
+    /// `crypto_sign_keypair` with `seed` as its random bytes
+    #[cfg(feature = "dilithium")]
+    pub fn dilithium3_keypair(seed: &[u8; 32]) -> (Vec<u8>, Vec<u8>) {
+        use dilithium::*;
+
+        let mut seedbuf = [0u8; 2 * SEEDBYTES + CRHBYTES];
+        xof::<sha3::Shake256>(&[seed]).read(&mut seedbuf);
+        let (rho, rest) = seedbuf.split_at(SEEDBYTES);
+        let (rhoprime, key) = rest.split_at(CRHBYTES);
+
+        let s1: Vec<Poly> = (0..L).map(|i| dilithium_eta(rhoprime, i as u16)).collect();
+        let s2: Vec<Poly> = (0..K)
+            .map(|i| dilithium_eta(rhoprime, (L + i) as u16))
+            .collect();
+        let s1_hat: Vec<Poly> = s1
+            .iter()
+            .map(|s| {
+                let mut a = s.map(|c| c.rem_euclid(Q));
+                ntt(&mut a, ZETA, 8, Q);
+                a
+            })
+            .collect();
+
+        // t = A * s1 + s2, split into t1 (public) and t0 (secret)
+        let mut pk = rho.to_vec();
+        let mut t0 = Vec::with_capacity(K);
+        for (i, s2) in s2.iter().enumerate() {
+            let mut t = [0; N];
+            for (j, s) in s1_hat.iter().enumerate() {
+                let a = dilithium_uniform(rho, ((i << 8) + j) as u16);
+                for (t, (a, s)) in t.iter_mut().zip(a.iter().zip(s)) {
+                    *t = (*t + a * s) % Q;
+                }
+            }
+            inv_ntt(&mut t, ZETA, Q);
+            for (t, s) in t.iter_mut().zip(s2) {
+                *t = (*t + s).rem_euclid(Q);
+            }
+
+            // Power2Round
+            let high = t.map(|c| (c + (1 << (D - 1)) - 1) >> D);
+            let low: Poly = std::array::from_fn(|n| t[n] - (high[n] << D));
+            pack(&high, 10, &mut pk);
+            t0.push(low);
+        }
+
+        let mut tr = [0u8; TRBYTES];
+        xof::<sha3::Shake256>(&[&pk]).read(&mut tr);
+
+        let mut sk = Vec::with_capacity(4032);
+        sk.extend_from_slice(rho);
+        sk.extend_from_slice(key);
+        sk.extend_from_slice(&tr);
+        for s in s1.iter().chain(&s2) {
+            pack(&s.map(|c| ETA - c), 4, &mut sk);
+        }
+        for low in &t0 {
+            pack(&low.map(|c| (1 << (D - 1)) - c), 13, &mut sk);
+        }
+        (pk, sk)
+    }
+
+    // ------------------------------------------------------------------------
+    // Kyber768
+    // ------------------------------------------------------------------------
+
+    #[cfg(feature = "kyber")]
+    mod kyber {
+        pub const Q: i64 = 3329;
+        pub const ZETA: i64 = 17;
+        pub const K: usize = 3;
+    }
+
+    /// `gen_matrix` entry `A[i][j]`, parsed from `XOF(rho || j || i)`
+    #[cfg(feature = "kyber")]
+    fn kyber_uniform(rho: &[u8], i: usize, j: usize) -> Poly {
+        let mut reader = xof::<sha3::Shake128>(&[rho, &[j as u8, i as u8]]);
+        let mut a = [0; N];
+        let mut filled = 0;
+        let mut buf = [0u8; 3];
+        while filled < N {
+            reader.read(&mut buf);
+            let d1 = buf[0] as i64 | (buf[1] as i64 & 0x0F) << 8;
+            let d2 = (buf[1] >> 4) as i64 | (buf[2] as i64) << 4;
+            for d in [d1, d2] {
+                if d < kyber::Q && filled < N {
+                    a[filled] = d;
+                    filled += 1;
+                }
+            }
+        }
+        a
+    }
+
+    /// `poly_getnoise_eta1` for eta1 = 2, in `[0, q)`
+    #[cfg(feature = "kyber")]
+    fn kyber_noise(sigma: &[u8], nonce: u8) -> Poly {
+        let mut buf = [0u8; 2 * N / 4];
+        xof::<sha3::Shake256>(&[sigma, &[nonce]]).read(&mut buf);
+        let mut a = [0; N];
+        for (i, chunk) in buf.chunks_exact(4).enumerate() {
+            let t = u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+            let d = (t & 0x5555_5555) + ((t >> 1) & 0x5555_5555);
+            for j in 0..8 {
+                let x = ((d >> (4 * j)) & 3) as i64;
+                let y = ((d >> (4 * j + 2)) & 3) as i64;
+                a[8 * i + j] = (x - y).rem_euclid(kyber::Q);
+            }
+        }
+        a
+    }
+
+    /// Product in the NTT domain: degree-1 products mod `X^2 - zeta^(2 brv(i) + 1)`
+    #[cfg(feature = "kyber")]
+    fn kyber_basemul(a: &Poly, b: &Poly) -> Poly {
+        use kyber::*;
+
+        let mut r = [0; N];
+        for i in 0..N / 2 {
+            let brv = (i.reverse_bits() >> (usize::BITS - 7)) as u64;
+            let gamma = pow_mod(ZETA, 2 * brv + 1, Q);
+            let (a0, a1, b0, b1) = (a[2 * i], a[2 * i + 1], b[2 * i], b[2 * i + 1]);
+            r[2 * i] = (a0 * b0 + a1 * b1 % Q * gamma) % Q;
+            r[2 * i + 1] = (a0 * b1 + a1 * b0) % Q;
+        }
+        r
+    }
+
+    /// `crypto_kem_keypair` with `d || z` from `SHAKE-256(seed)` as its
+    /// random bytes
+    #[cfg(feature = "kyber")]
+    pub fn kyber768_keypair(seed: &[u8; 32]) -> (Vec<u8>, Vec<u8>) {
+        let mut coins = [0u8; 64];
+        xof::<sha3::Shake256>(&[seed]).read(&mut coins);
+        kyber768_keypair_derand(&coins)
+    }
+
+    /// `crypto_kem_keypair_derand`: key generation from `d || z`
+    #[cfg(feature = "kyber")]
+    pub fn kyber768_keypair_derand(coins: &[u8; 64]) -> (Vec<u8>, Vec<u8>) {
+        use kyber::*;
+        use sha3::{Digest, Sha3_256, Sha3_512};
+
+        let (d, z) = coins.split_at(32);
+        let g = Sha3_512::digest(d);
+        let (rho, sigma) = g.split_at(32);
+
+        let noise = |nonce: usize| {
+            let mut a = kyber_noise(sigma, nonce as u8);
+            ntt(&mut a, ZETA, 7, Q);
+            a
+        };
+        let s: Vec<Poly> = (0..K).map(&noise).collect();
+        let e: Vec<Poly> = (K..2 * K).map(&noise).collect();
+
+        // t = A * s + e, in the NTT domain
+        let mut pk = Vec::with_capacity(1184);
+        for (i, e) in e.iter().enumerate() {
+            let mut t = *e;
+            for (j, s) in s.iter().enumerate() {
+                let product = kyber_basemul(&kyber_uniform(rho, i, j), s);
+                for (t, p) in t.iter_mut().zip(product) {
+                    *t = (*t + p) % Q;
+                }
+            }
+            pack(&t, 12, &mut pk);
+        }
+        pk.extend_from_slice(rho);
+
+        let mut sk = Vec::with_capacity(2400);
+        for s in &s {
+            pack(s, 12, &mut sk);
+        }
+        sk.extend_from_slice(&pk);
+        sk.extend_from_slice(&Sha3_256::digest(&pk));
+        sk.extend_from_slice(z);
+        (pk, sk)
+    }
+}
+
+#[cfg(any(not(feature = "dilithium"), not(feature = "kyber")))]
+fn unavailable(scheme: &str, feature: &str) -> CryptoError {
+    CryptoError::BackendUnavailable(format!("{} requires the `{}` feature", scheme, feature))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Field of a PQCgenKAT response file, hex-decoded
+    #[cfg(any(feature = "dilithium", feature = "kyber"))]
+    fn kat_field(rsp: &str, name: &str) -> Vec<u8> {
+        let prefix = format!("{} = ", name);
+        let value = rsp
+            .lines()
+            .find_map(|line| line.strip_prefix(&prefix))
+            .unwrap_or_else(|| panic!("KAT field {} missing", name));
+        hex::decode(value).unwrap()
+    }
+
+    #[cfg(feature = "dilithium")]
+    #[test]
+    fn test_dilithium_sizes_match_backend() {
+        use pqcrypto_dilithium::dilithium3;
+
+        assert_eq!(Dilithium3::PUBLIC_KEY_SIZE, dilithium3::public_key_bytes());
+        assert_eq!(Dilithium3::SECRET_KEY_SIZE, dilithium3::secret_key_bytes());
+        assert_eq!(Dilithium3::SIGNATURE_SIZE, dilithium3::signature_bytes());
+
+        let (pk, sk) = Dilithium3::keypair().unwrap();
+        assert_eq!(pk.len(), Dilithium3::PUBLIC_KEY_SIZE);
+        assert_eq!(sk.len(), Dilithium3::SECRET_KEY_SIZE);
+    }
+
+    #[cfg(feature = "dilithium")]
+    #[test]
+    fn test_dilithium_attached_and_detached_agree() {
+        use pqcrypto_dilithium::dilithium3;
+        use pqcrypto_traits::sign::{DetachedSignature, PublicKey};
+
+        let message = b"rope testimony vector";
+        let (pk, sk) = Dilithium3::keypair().unwrap();
+        let signed = Dilithium3::sign(&sk, message).unwrap();
+
+        // Signed message is signature || message
+        assert_eq!(signed.len(), Dilithium3::SIGNATURE_SIZE + message.len());
+        assert_eq!(&signed[Dilithium3::SIGNATURE_SIZE..], message);
+
+        // The prefix must verify as a detached signature
+        let detached =
+            dilithium3::DetachedSignature::from_bytes(&signed[..Dilithium3::SIGNATURE_SIZE])
+                .unwrap();
+        let pk_obj = dilithium3::PublicKey::from_bytes(&pk).unwrap();
+        assert!(dilithium3::verify_detached_signature(&detached, message, &pk_obj).is_ok());
+
+        assert!(Dilithium3::verify(&pk, message, &signed).unwrap());
+    }
+
+    #[cfg(feature = "dilithium")]
+    #[test]
+    fn test_dilithium_rejects_tampering() {
+        let message = b"rope testimony vector";
+        let (pk, sk) = Dilithium3::keypair().unwrap();
+        let (other_pk, _) = Dilithium3::keypair().unwrap();
+        let signed = Dilithium3::sign(&sk, message).unwrap();
+
+        let mut flipped = signed.clone();
+        flipped[10] ^= 0x01;
+        assert!(!Dilithium3::verify(&pk, message, &flipped).unwrap());
+        assert!(!Dilithium3::verify(&pk, b"other message", &signed).unwrap());
+        assert!(!Dilithium3::verify(&other_pk, message, &signed).unwrap());
+        assert!(Dilithium3::verify(&pk[..100], message, &signed).is_err());
+    }
+
+    #[cfg(feature = "dilithium")]
+    #[test]
+    fn test_dilithium_seeded_keypair_works_with_backend() {
+        let (pk, sk) = Dilithium3::keypair_from_seed(&[7u8; 32]).unwrap();
+        assert_eq!(pk.len(), Dilithium3::PUBLIC_KEY_SIZE);
+        assert_eq!(sk.len(), Dilithium3::SECRET_KEY_SIZE);
+        assert_eq!(
+            Dilithium3::keypair_from_seed(&[7u8; 32]).unwrap(),
+            (pk.clone(), sk.clone())
+        );
+        assert_ne!(Dilithium3::keypair_from_seed(&[8u8; 32]).unwrap().0, pk);
+
+        let message = b"rope testimony vector";
+        let signed = Dilithium3::sign(&sk, message).unwrap();
+        assert!(Dilithium3::verify(&pk, message, &signed).unwrap());
+        assert!(!Dilithium3::verify(&pk, b"other message", &signed).unwrap());
+    }
+
+    #[cfg(feature = "dilithium")]
+    #[test]
+    fn test_dilithium3_nist_kat() {
+        let rsp = include_str!("../kat/dilithium3.rsp");
+        let (pk, sk) = (kat_field(rsp, "pk"), kat_field(rsp, "sk"));
+        let (msg, sm) = (kat_field(rsp, "msg"), kat_field(rsp, "sm"));
+        assert_eq!(pk.len(), Dilithium3::PUBLIC_KEY_SIZE);
+        assert_eq!(sk.len(), Dilithium3::SECRET_KEY_SIZE);
+        assert_eq!(sm.len(), Dilithium3::SIGNATURE_SIZE + msg.len());
+
+        // Signing is deterministic, so the signed message must match exactly
+        assert_eq!(Dilithium3::sign(&sk, &msg).unwrap(), sm);
+        assert!(Dilithium3::verify(&pk, &msg, &sm).unwrap());
+    }
+
+    #[cfg(feature = "kyber")]
+    #[test]
+    fn test_kyber_sizes_match_backend() {
+        use pqcrypto_kyber::kyber768;
+
+        assert_eq!(Kyber768::PUBLIC_KEY_SIZE, kyber768::public_key_bytes());
+        assert_eq!(Kyber768::SECRET_KEY_SIZE, kyber768::secret_key_bytes());
+        assert_eq!(Kyber768::CIPHERTEXT_SIZE, kyber768::ciphertext_bytes());
+        assert_eq!(
+            Kyber768::SHARED_SECRET_SIZE,
+            kyber768::shared_secret_bytes()
+        );
+    }
+
+    #[cfg(feature = "kyber")]
+    #[test]
+    fn test_kyber_roundtrip_and_implicit_rejection() {
+        let (pk, sk) = Kyber768::keypair().unwrap();
+        let (_, other_sk) = Kyber768::keypair().unwrap();
+
+        let (ct, ss) = Kyber768::encapsulate(&pk).unwrap();
+        assert_eq!(ct.len(), Kyber768::CIPHERTEXT_SIZE);
+        assert_eq!(ss.len(), Kyber768::SHARED_SECRET_SIZE);
+        assert_eq!(Kyber768::decapsulate(&sk, &ct).unwrap(), ss);
+
+        // Kyber rejects implicitly: wrong key or ciphertext yields a different secret
+        assert_ne!(Kyber768::decapsulate(&other_sk, &ct).unwrap(), ss);
+        let mut tampered = ct.clone();
+        tampered[0] ^= 0x01;
+        assert_ne!(Kyber768::decapsulate(&sk, &tampered).unwrap(), ss);
+
+        assert!(Kyber768::encapsulate(&pk[..10]).is_err());
+    }
+
+    #[cfg(feature = "kyber")]
+    #[test]
+    fn test_kyber768_nist_kat() {
+        let rsp = include_str!("../kat/kyber768.rsp");
+        let (pk, sk) = (kat_field(rsp, "pk"), kat_field(rsp, "sk"));
+        let (ct, ss) = (kat_field(rsp, "ct"), kat_field(rsp, "ss"));
+        assert_eq!(pk.len(), Kyber768::PUBLIC_KEY_SIZE);
+        assert_eq!(sk.len(), Kyber768::SECRET_KEY_SIZE);
+        assert_eq!(ct.len(), Kyber768::CIPHERTEXT_SIZE);
+
+        assert_eq!(Kyber768::decapsulate(&sk, &ct).unwrap(), ss);
+
+        // A fresh encapsulation to the KAT key must agree with its secret key
+        let (ct, ss) = Kyber768::encapsulate(&pk).unwrap();
+        assert_eq!(Kyber768::decapsulate(&sk, &ct).unwrap(), ss);
+    }
+
+    #[cfg(feature = "kyber")]
+    #[test]
+    fn test_kyber_seeded_keypair_works_with_backend() {
+        let (pk, sk) = Kyber768::keypair_from_seed(&[7u8; 32]).unwrap();
+        assert_eq!(pk.len(), Kyber768::PUBLIC_KEY_SIZE);
+        assert_eq!(sk.len(), Kyber768::SECRET_KEY_SIZE);
+        assert_eq!(
+            Kyber768::keypair_from_seed(&[7u8; 32]).unwrap(),
+            (pk.clone(), sk.clone())
+        );
+        assert_ne!(Kyber768::keypair_from_seed(&[8u8; 32]).unwrap().0, pk);
+
+        // Implicit rejection would hand back a different secret on a bad key
+        let (ct, ss) = Kyber768::encapsulate(&pk).unwrap();
+        assert_eq!(Kyber768::decapsulate(&sk, &ct).unwrap(), ss);
+    }
+
+    /// Key generation randomness of the first KAT record: the KAT DRBG,
+    /// seeded from the record's `seed`, as read by `crypto_*_keypair`
+    #[cfg(any(feature = "dilithium", feature = "kyber"))]
+    const KAT_KEYPAIR_COINS: &str = "7c9935a0b07694aa0c6d10e4db6b1add2fd81a25ccb148032dcd739936737f2d\
+                                     b505d7cfad1b497499323c8686325e4792f267aafa3f87ca60d01cb54f29202a";
+
+    #[cfg(feature = "dilithium")]
+    #[test]
+    fn test_dilithium_seeded_keypair_matches_nist_kat() {
+        let rsp = include_str!("../kat/dilithium3.rsp");
+        let coins = hex::decode(KAT_KEYPAIR_COINS).unwrap();
+        let (pk, sk) = seeded::dilithium3_keypair(coins[..32].try_into().unwrap());
+        assert_eq!(pk, kat_field(rsp, "pk"));
+        assert_eq!(sk, kat_field(rsp, "sk"));
+    }
+
+    #[cfg(feature = "kyber")]
+    #[test]
+    fn test_kyber_seeded_keypair_matches_nist_kat() {
+        let rsp = include_str!("../kat/kyber768.rsp");
+        let coins = hex::decode(KAT_KEYPAIR_COINS).unwrap();
+        let (pk, sk) = seeded::kyber768_keypair_derand(coins[..].try_into().unwrap());
+        assert_eq!(pk, kat_field(rsp, "pk"));
+        assert_eq!(sk, kat_field(rsp, "sk"));
+    }
+
+    #[cfg(not(feature = "dilithium"))]
+    #[test]
+    fn test_dilithium_disabled_fails_closed() {
+        assert!(!dilithium_enabled());
+        assert!(matches!(
+            Dilithium3::verify(&[0u8; 1952], b"m", &[0u8; 3300]),
+            Err(CryptoError::BackendUnavailable(_))
+        ));
+    }
+
+    #[cfg(not(feature = "kyber"))]
+    #[test]
+    fn test_kyber_disabled() {
+        assert!(!kyber_enabled());
+        assert!(Kyber768::keypair().is_err());
+    }
+}
@@ -0,0 +1,476 @@
+//! Remote and hardware signing backends
+//!
+//! Validators can keep their Ed25519 key outside the node process, in an
+//! HSM or a YubiKey reachable over PKCS#11. [`RemoteSigner`] abstracts the
+//! device; [`HybridRemoteSigner`] pairs it with the software Dilithium3 key
+//! (no PKCS#11 token implements ML-DSA yet) to produce ordinary
+//! [`HybridSignature`]s.
+//!
+//! Devices are slow and occasionally unavailable, so every signature goes
+//! through a [`SigningPolicy`]: a per-attempt timeout, bounded retries with
+//! exponential backoff, and a local verification of the returned signature
+//! so a faulty device cannot hand out garbage.
+//!
+//! The PKCS#11 backend is behind the `pkcs11` feature.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use ed25519_dalek::{Signer, SigningKey, Verifier, VerifyingKey};
+use zeroize::Zeroizing;
+
+use crate::error::{CryptoError, Result};
+use crate::hybrid::HybridSignature;
+use crate::pq::{Dilithium3, PqSignatureScheme};
+
+/// Ed25519 signer whose key lives outside this process
+#[async_trait]
+pub trait RemoteSigner: Send + Sync {
+    /// Backend name for logs
+    fn backend(&self) -> &str;
+
+    /// Ed25519 public key held by the device
+    fn public_key(&self) -> [u8; 32];
+
+    /// Sign a message with the device key
+    async fn sign(&self, message: &[u8]) -> Result<[u8; 64]>;
+}
+
+/// In-process signer, for development and as a fallback
+pub struct LocalSigner {
+    key: SigningKey,
+}
+
+impl LocalSigner {
+    /// Wrap an Ed25519 seed
+    pub fn from_seed(seed: &[u8; 32]) -> Self {
+        Self {
+            key: SigningKey::from_bytes(seed),
+        }
+    }
+}
+
+#[async_trait]
+impl RemoteSigner for LocalSigner {
+    fn backend(&self) -> &str {
+        "local"
+    }
+
+    fn public_key(&self) -> [u8; 32] {
+        self.key.verifying_key().to_bytes()
+    }
+
+    async fn sign(&self, message: &[u8]) -> Result<[u8; 64]> {
+        Ok(self.key.sign(message).to_bytes())
+    }
+}
+
+/// Timeouts and retries for device signing
+#[derive(Clone, Debug)]
+pub struct SigningPolicy {
+    /// Deadline for a single attempt
+    pub attempt_timeout: Duration,
+    /// Attempts before giving up
+    pub max_attempts: u32,
+    /// Delay before the first retry, doubled on each further retry
+    pub initial_backoff: Duration,
+}
+
+impl Default for SigningPolicy {
+    fn default() -> Self {
+        Self {
+            attempt_timeout: Duration::from_secs(2),
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(100),
+        }
+    }
+}
+
+/// Sign through `signer` under `policy`, verifying the result locally
+pub async fn sign_with_policy(
+    signer: &dyn RemoteSigner,
+    message: &[u8],
+    policy: &SigningPolicy,
+) -> Result<[u8; 64]> {
+    let public_key = VerifyingKey::from_bytes(&signer.public_key())
+        .map_err(|e| CryptoError::InvalidPublicKey(e.to_string()))?;
+    let mut backoff = policy.initial_backoff;
+    let mut last_error = CryptoError::RemoteSignerError("No signing attempts".to_string());
+
+    for attempt in 1..=policy.max_attempts.max(1) {
+        match tokio::time::timeout(policy.attempt_timeout, signer.sign(message)).await {
+            Ok(Ok(signature)) => {
+                let parsed = ed25519_dalek::Signature::from_bytes(&signature);
+                if public_key.verify(message, &parsed).is_ok() {
+                    return Ok(signature);
+                }
+                last_error = CryptoError::RemoteSignerError(format!(
+                    "{} returned an invalid signature",
+                    signer.backend()
+                ));
+            }
+            Ok(Err(e)) => last_error = e,
+            Err(_) => {
+                last_error = CryptoError::RemoteSignerError(format!(
+                    "{} timed out after {:?}",
+                    signer.backend(),
+                    policy.attempt_timeout
+                ))
+            }
+        }
+
+        tracing::warn!(
+            "Remote signing attempt {}/{} failed: {}",
+            attempt,
+            policy.max_attempts,
+            last_error
+        );
+        if attempt < policy.max_attempts {
+            tokio::time::sleep(backoff).await;
+            backoff *= 2;
+        }
+    }
+    Err(last_error)
+}
+
+/// Hybrid signer with the Ed25519 half on a remote device
+pub struct HybridRemoteSigner {
+    ed25519: Arc<dyn RemoteSigner>,
+    dilithium_sk: Zeroizing<Vec<u8>>,
+    policy: SigningPolicy,
+}
+
+impl HybridRemoteSigner {
+    /// Combine a device signer with a software Dilithium3 secret key
+    ///
+    /// An empty `dilithium_sk` produces classical-only signatures.
+    pub fn new(
+        ed25519: Arc<dyn RemoteSigner>,
+        dilithium_sk: Vec<u8>,
+        policy: SigningPolicy,
+    ) -> Self {
+        Self {
+            ed25519,
+            dilithium_sk: Zeroizing::new(dilithium_sk),
+            policy,
+        }
+    }
+
+    /// Ed25519 public key on the device
+    pub fn ed25519_public_key(&self) -> [u8; 32] {
+        self.ed25519.public_key()
+    }
+
+    /// Produce a hybrid signature
+    pub async fn sign(&self, message: &[u8]) -> Result<HybridSignature> {
+        let ed25519_sig = sign_with_policy(self.ed25519.as_ref(), message, &self.policy).await?;
+        let dilithium_sig = if self.dilithium_sk.is_empty() {
+            Vec::new()
+        } else {
+            Dilithium3::sign(&self.dilithium_sk, message)?
+        };
+        Ok(HybridSignature::new(ed25519_sig, dilithium_sig))
+    }
+}
+
+#[cfg(feature = "pkcs11")]
+pub use self::pkcs11::{Pkcs11Config, Pkcs11Signer};
+
+#[cfg(feature = "pkcs11")]
+mod pkcs11 {
+    //! PKCS#11 backend (HSMs, YubiHSM, YubiKey PIV via ykcs11)
+
+    use std::path::PathBuf;
+    use std::sync::{Arc, Mutex};
+
+    use async_trait::async_trait;
+    use cryptoki::context::{CInitializeArgs, Pkcs11};
+    use cryptoki::mechanism::Mechanism;
+    use cryptoki::object::{Attribute, AttributeType, KeyType, ObjectClass, ObjectHandle};
+    use cryptoki::session::{Session, UserType};
+    use cryptoki::types::AuthPin;
+    use serde::{Deserialize, Serialize};
+
+    use super::RemoteSigner;
+    use crate::error::{CryptoError, Result};
+
+    /// Where to find the key on the token
+    #[derive(Clone, Serialize, Deserialize)]
+    pub struct Pkcs11Config {
+        /// Path to the vendor PKCS#11 module (`.so`/`.dylib`)
+        pub module_path: PathBuf,
+        /// Token label
+        pub token_label: String,
+        /// Label shared by the Ed25519 key pair objects
+        pub key_label: String,
+        /// User PIN
+        #[serde(skip_serializing)]
+        pub pin: String,
+    }
+
+    /// Ed25519 signer backed by a PKCS#11 token
+    pub struct Pkcs11Signer {
+        session: Arc<Mutex<Session>>,
+        private_key: ObjectHandle,
+        public_key: [u8; 32],
+        // Keeps the module loaded for the lifetime of the session
+        _context: Pkcs11,
+    }
+
+    impl Pkcs11Signer {
+        /// Load the module, log in and locate the key pair
+        pub fn connect(config: &Pkcs11Config) -> Result<Self> {
+            let context = Pkcs11::new(&config.module_path).map_err(device_error)?;
+            context
+                .initialize(CInitializeArgs::OsThreads)
+                .map_err(device_error)?;
+
+            let slot = context
+                .get_slots_with_token()
+                .map_err(device_error)?
+                .into_iter()
+                .find(|slot| {
+                    context
+                        .get_token_info(*slot)
+                        .map(|info| info.label().trim() == config.token_label)
+                        .unwrap_or(false)
+                })
+                .ok_or_else(|| {
+                    CryptoError::RemoteSignerError(format!(
+                        "Token '{}' not found",
+                        config.token_label
+                    ))
+                })?;
+
+            let session = context.open_ro_session(slot).map_err(device_error)?;
+            session
+                .login(UserType::User, Some(&AuthPin::new(config.pin.clone())))
+                .map_err(device_error)?;
+
+            let private_key = find_key(&session, ObjectClass::PRIVATE_KEY, &config.key_label)?;
+            let public_handle = find_key(&session, ObjectClass::PUBLIC_KEY, &config.key_label)?;
+            let public_key = read_ec_point(&session, public_handle)?;
+
+            Ok(Self {
+                session: Arc::new(Mutex::new(session)),
+                private_key,
+                public_key,
+                _context: context,
+            })
+        }
+    }
+
+    #[async_trait]
+    impl RemoteSigner for Pkcs11Signer {
+        fn backend(&self) -> &str {
+            "pkcs11"
+        }
+
+        fn public_key(&self) -> [u8; 32] {
+            self.public_key
+        }
+
+        async fn sign(&self, message: &[u8]) -> Result<[u8; 64]> {
+            let session = Arc::clone(&self.session);
+            let key = self.private_key;
+            let message = message.to_vec();
+
+            // Token calls block; keep them off the async workers
+            let signature = tokio::task::spawn_blocking(move || {
+                let session = session
+                    .lock()
+                    .map_err(|_| CryptoError::RemoteSignerError("Session poisoned".to_string()))?;
+                session
+                    .sign(&Mechanism::Eddsa, key, &message)
+                    .map_err(device_error)
+            })
+            .await
+            .map_err(|e| CryptoError::RemoteSignerError(e.to_string()))??;
+
+            signature.try_into().map_err(|_| {
+                CryptoError::RemoteSignerError("Token returned a non-Ed25519 signature".to_string())
+            })
+        }
+    }
+
+    fn find_key(session: &Session, class: ObjectClass, label: &str) -> Result<ObjectHandle> {
+        let template = [
+            Attribute::Class(class),
+            Attribute::KeyType(KeyType::EC_EDWARDS),
+            Attribute::Label(label.as_bytes().to_vec()),
+        ];
+        session
+            .find_objects(&template)
+            .map_err(device_error)?
+            .into_iter()
+            .next()
+            .ok_or_else(|| CryptoError::RemoteSignerError(format!("Key '{}' not found", label)))
+    }
+
+    /// CKA_EC_POINT holds the key as a DER OCTET STRING (04 20 || key)
+    fn read_ec_point(session: &Session, handle: ObjectHandle) -> Result<[u8; 32]> {
+        let attributes = session
+            .get_attributes(handle, &[AttributeType::EcPoint])
+            .map_err(device_error)?;
+        let point = attributes
+            .into_iter()
+            .find_map(|a| match a {
+                Attribute::EcPoint(bytes) => Some(bytes),
+                _ => None,
+            })
+            .ok_or_else(|| CryptoError::RemoteSignerError("Key has no EC point".to_string()))?;
+
+        let raw = match point.as_slice() {
+            [0x04, 0x20, rest @ ..] if rest.len() == 32 => rest,
+            raw if raw.len() == 32 => raw,
+            _ => {
+                return Err(CryptoError::RemoteSignerError(
+                    "Unexpected EC point encoding".to_string(),
+                ))
+            }
+        };
+        let mut key = [0u8; 32];
+        key.copy_from_slice(raw);
+        Ok(key)
+    }
+
+    fn device_error(e: cryptoki::error::Error) -> CryptoError {
+        CryptoError::RemoteSignerError(e.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hybrid::{HybridPublicKey, HybridSigner, HybridVerifier};
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    /// Device that fails or stalls for the first few calls
+    struct FlakySigner {
+        inner: LocalSigner,
+        failures: AtomicU32,
+        stall: bool,
+    }
+
+    #[async_trait]
+    impl RemoteSigner for FlakySigner {
+        fn backend(&self) -> &str {
+            "flaky"
+        }
+
+        fn public_key(&self) -> [u8; 32] {
+            self.inner.public_key()
+        }
+
+        async fn sign(&self, message: &[u8]) -> Result<[u8; 64]> {
+            if self.failures.load(Ordering::SeqCst) > 0 {
+                self.failures.fetch_sub(1, Ordering::SeqCst);
+                if self.stall {
+                    tokio::time::sleep(Duration::from_secs(60)).await;
+                }
+                return Err(CryptoError::RemoteSignerError("device busy".to_string()));
+            }
+            self.inner.sign(message).await
+        }
+    }
+
+    /// Device that signs with the wrong key
+    struct LyingSigner {
+        claimed: LocalSigner,
+        actual: LocalSigner,
+    }
+
+    #[async_trait]
+    impl RemoteSigner for LyingSigner {
+        fn backend(&self) -> &str {
+            "lying"
+        }
+
+        fn public_key(&self) -> [u8; 32] {
+            self.claimed.public_key()
+        }
+
+        async fn sign(&self, message: &[u8]) -> Result<[u8; 64]> {
+            self.actual.sign(message).await
+        }
+    }
+
+    fn fast_policy(max_attempts: u32) -> SigningPolicy {
+        SigningPolicy {
+            attempt_timeout: Duration::from_millis(50),
+            max_attempts,
+            initial_backoff: Duration::from_millis(1),
+        }
+    }
+
+    fn flaky(failures: u32, stall: bool) -> FlakySigner {
+        FlakySigner {
+            inner: LocalSigner::from_seed(&[3u8; 32]),
+            failures: AtomicU32::new(failures),
+            stall,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_retries_until_success() {
+        let signer = flaky(2, false);
+        let sig = sign_with_policy(&signer, b"testimony", &fast_policy(3))
+            .await
+            .unwrap();
+        let key = VerifyingKey::from_bytes(&signer.public_key()).unwrap();
+        assert!(key
+            .verify(b"testimony", &ed25519_dalek::Signature::from_bytes(&sig))
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_gives_up_after_max_attempts() {
+        let signer = flaky(5, false);
+        assert!(sign_with_policy(&signer, b"testimony", &fast_policy(3))
+            .await
+            .is_err());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_stalled_device_times_out() {
+        let signer = flaky(1, true);
+        assert!(sign_with_policy(&signer, b"testimony", &fast_policy(2))
+            .await
+            .is_ok());
+        let signer = flaky(3, true);
+        assert!(sign_with_policy(&signer, b"testimony", &fast_policy(2))
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_wrong_key_signature_rejected() {
+        let signer = LyingSigner {
+            claimed: LocalSigner::from_seed(&[1u8; 32]),
+            actual: LocalSigner::from_seed(&[2u8; 32]),
+        };
+        let err = sign_with_policy(&signer, b"m", &fast_policy(1))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("invalid signature"));
+    }
+
+    #[tokio::test]
+    async fn test_hybrid_remote_matches_verifier() {
+        let (local, public) = HybridSigner::generate();
+        let secret = local.secret_key();
+        let remote = HybridRemoteSigner::new(
+            Arc::new(LocalSigner::from_seed(secret.ed25519_bytes())),
+            secret.dilithium_bytes().to_vec(),
+            SigningPolicy::default(),
+        );
+        assert_eq!(remote.ed25519_public_key(), public.ed25519);
+
+        let sig = remote.sign(b"anchor").await.unwrap();
+        assert!(HybridVerifier::verify(&public, b"anchor", &sig).unwrap());
+
+        let other: HybridPublicKey = HybridSigner::generate().1;
+        assert!(!HybridVerifier::verify(&other, b"anchor", &sig).unwrap_or(false));
+    }
+}
@@ -0,0 +1,122 @@
+//! Secure memory helpers
+//!
+//! - [`SecretBytes`]: heap secret that is zeroized on drop, redacted in
+//!   `Debug` output and compared in constant time
+//! - [`ct_eq`]: constant-time equality for MACs, tags and commitments
+//!
+//! Comparing a MAC or commitment with `==` returns at the first differing
+//! byte, which lets an attacker recover the expected value byte by byte
+//! from timing. Use [`ct_eq`] for anything an attacker can submit guesses
+//! against.
+
+use std::fmt;
+
+use rand::rngs::OsRng;
+use rand::RngCore;
+use subtle::ConstantTimeEq;
+use zeroize::Zeroizing;
+
+/// Constant-time equality
+///
+/// Only the lengths are compared in variable time; they are public for
+/// every MAC and commitment in the codebase.
+pub fn ct_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && bool::from(a.ct_eq(b))
+}
+
+/// Secret byte buffer (zeroized on drop)
+#[derive(Clone, Default)]
+pub struct SecretBytes(Zeroizing<Vec<u8>>);
+
+impl SecretBytes {
+    /// Take ownership of secret bytes
+    pub fn new(bytes: Vec<u8>) -> Self {
+        Self(Zeroizing::new(bytes))
+    }
+
+    /// Copy secret bytes from a slice
+    pub fn from_slice(bytes: &[u8]) -> Self {
+        Self::new(bytes.to_vec())
+    }
+
+    /// Fresh random secret of `len` bytes
+    pub fn random(len: usize) -> Self {
+        let mut bytes = Zeroizing::new(vec![0u8; len]);
+        OsRng.fill_bytes(&mut bytes);
+        Self(bytes)
+    }
+
+    /// Borrow the secret
+    pub fn expose_secret(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// Borrow the secret as a fixed-size array
+    pub fn expose_array<const N: usize>(&self) -> Option<&[u8; N]> {
+        self.0.as_slice().try_into().ok()
+    }
+
+    /// Length in bytes
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Whether the buffer is empty
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl From<Vec<u8>> for SecretBytes {
+    fn from(bytes: Vec<u8>) -> Self {
+        Self::new(bytes)
+    }
+}
+
+impl<const N: usize> From<[u8; N]> for SecretBytes {
+    fn from(mut bytes: [u8; N]) -> Self {
+        let secret = Self::from_slice(&bytes);
+        zeroize::Zeroize::zeroize(&mut bytes);
+        secret
+    }
+}
+
+impl PartialEq for SecretBytes {
+    fn eq(&self, other: &Self) -> bool {
+        ct_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for SecretBytes {}
+
+impl fmt::Debug for SecretBytes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "SecretBytes([REDACTED; {}])", self.0.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ct_eq() {
+        assert!(ct_eq(b"tag-value", b"tag-value"));
+        assert!(!ct_eq(b"tag-value", b"tag-valuf"));
+        assert!(!ct_eq(b"short", b"longer"));
+        assert!(ct_eq(b"", b""));
+    }
+
+    #[test]
+    fn test_secret_bytes() {
+        let secret = SecretBytes::from([7u8; 32]);
+        assert_eq!(secret.len(), 32);
+        assert_eq!(secret.expose_array::<32>(), Some(&[7u8; 32]));
+        assert_eq!(secret.expose_array::<16>(), None);
+        assert_eq!(secret, SecretBytes::from_slice(&[7u8; 32]));
+        assert_ne!(secret, SecretBytes::random(32));
+
+        let debug = format!("{:?}", secret);
+        assert_eq!(debug, "SecretBytes([REDACTED; 32])");
+    }
+}
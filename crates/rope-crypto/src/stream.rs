@@ -0,0 +1,577 @@
+//! Streaming AEAD for large payloads
+//!
+//! Chunked ChaCha20-Poly1305 following the STREAM construction (Hoang,
+//! Reyhanitabar, Rogaway, Vizár). Payloads are encrypted one chunk at a
+//! time, so multi-gigabyte strings never have to sit in memory at once,
+//! and truncation, reordering or chunk splicing are all detected.
+//!
+//! ## Format
+//!
+//! ```text
+//! header = "RSTR" || version (1) || chunk_size (u32 LE) || nonce_prefix (7)
+//! chunk  = ChaCha20-Poly1305(key, nonce_prefix || counter (u32 BE) || last_flag, aad = header)
+//! ```
+//!
+//! Every chunk except the last carries exactly `chunk_size` plaintext bytes.
+//! The last chunk has `last_flag = 1` and may be shorter, or empty.
+//!
+//! [`EncryptWriter`] and [`DecryptReader`] wrap any `Write`/`Read`; the
+//! lower-level [`StreamEncryptor`]/[`StreamDecryptor`] work chunk by chunk.
+
+use std::io::{self, Read, Write};
+
+use rand::rngs::OsRng;
+use rand::RngCore;
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, CHACHA20_POLY1305, NONCE_LEN};
+use zeroize::Zeroizing;
+
+use crate::error::{CryptoError, Result};
+
+/// Stream header magic
+const STREAM_MAGIC: &[u8; 4] = b"RSTR";
+
+/// Stream format version
+pub const STREAM_VERSION: u8 = 1;
+
+/// Header size in bytes
+pub const STREAM_HEADER_SIZE: usize = 16;
+
+/// Authentication tag size per chunk
+pub const STREAM_TAG_SIZE: usize = 16;
+
+/// Default plaintext bytes per chunk
+pub const DEFAULT_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Largest accepted chunk size (bounds decryptor memory)
+pub const MAX_CHUNK_SIZE: usize = 16 * 1024 * 1024;
+
+/// Nonce prefix length (nonce minus counter and flag)
+const NONCE_PREFIX_LEN: usize = NONCE_LEN - 5;
+
+struct StreamState {
+    key: LessSafeKey,
+    header: [u8; STREAM_HEADER_SIZE],
+    chunk_size: usize,
+    counter: u32,
+    exhausted: bool,
+}
+
+impl StreamState {
+    fn new(key: &[u8; 32], header: [u8; STREAM_HEADER_SIZE]) -> Result<Self> {
+        if &header[..4] != STREAM_MAGIC {
+            return Err(CryptoError::DecryptionError(
+                "Not an encrypted stream".to_string(),
+            ));
+        }
+        if header[4] != STREAM_VERSION {
+            return Err(CryptoError::DecryptionError(format!(
+                "Unsupported stream version {}",
+                header[4]
+            )));
+        }
+        let chunk_size = u32::from_le_bytes([header[5], header[6], header[7], header[8]]) as usize;
+        if chunk_size == 0 || chunk_size > MAX_CHUNK_SIZE {
+            return Err(CryptoError::DecryptionError(format!(
+                "Invalid chunk size {}",
+                chunk_size
+            )));
+        }
+
+        let unbound = UnboundKey::new(&CHACHA20_POLY1305, key)
+            .map_err(|_| CryptoError::KeyDerivationFailed("Invalid stream key".to_string()))?;
+        Ok(Self {
+            key: LessSafeKey::new(unbound),
+            header,
+            chunk_size,
+            counter: 0,
+            exhausted: false,
+        })
+    }
+
+    fn next_nonce(&mut self, last: bool) -> Result<Nonce> {
+        if self.exhausted {
+            return Err(CryptoError::EncapsulationFailed(
+                "Stream already finished or exhausted".to_string(),
+            ));
+        }
+        let mut nonce = [0u8; NONCE_LEN];
+        nonce[..NONCE_PREFIX_LEN].copy_from_slice(&self.header[9..]);
+        nonce[NONCE_PREFIX_LEN..NONCE_LEN - 1].copy_from_slice(&self.counter.to_be_bytes());
+        nonce[NONCE_LEN - 1] = last as u8;
+
+        // Counter wrap would reuse nonces
+        match self.counter.checked_add(1) {
+            Some(next) if !last => self.counter = next,
+            _ => self.exhausted = true,
+        }
+        Ok(Nonce::assume_unique_for_key(nonce))
+    }
+}
+
+/// Chunk-by-chunk stream encryption
+pub struct StreamEncryptor {
+    state: StreamState,
+}
+
+impl StreamEncryptor {
+    /// Start a stream with a random nonce prefix
+    pub fn new(key: &[u8; 32], chunk_size: usize) -> Result<Self> {
+        if chunk_size == 0 || chunk_size > MAX_CHUNK_SIZE {
+            return Err(CryptoError::EncapsulationFailed(format!(
+                "Invalid chunk size {}",
+                chunk_size
+            )));
+        }
+        let mut header = [0u8; STREAM_HEADER_SIZE];
+        header[..4].copy_from_slice(STREAM_MAGIC);
+        header[4] = STREAM_VERSION;
+        header[5..9].copy_from_slice(&(chunk_size as u32).to_le_bytes());
+        OsRng.fill_bytes(&mut header[9..]);
+
+        Ok(Self {
+            state: StreamState::new(key, header)?,
+        })
+    }
+
+    /// Header to write before the first chunk
+    pub fn header(&self) -> &[u8; STREAM_HEADER_SIZE] {
+        &self.state.header
+    }
+
+    /// Plaintext bytes per chunk
+    pub fn chunk_size(&self) -> usize {
+        self.state.chunk_size
+    }
+
+    /// Encrypt a full, non-final chunk
+    pub fn encrypt_next(&mut self, chunk: &[u8]) -> Result<Vec<u8>> {
+        if chunk.len() != self.state.chunk_size {
+            return Err(CryptoError::EncapsulationFailed(format!(
+                "Chunk must be {} bytes, got {}",
+                self.state.chunk_size,
+                chunk.len()
+            )));
+        }
+        self.seal(chunk, false)
+    }
+
+    /// Encrypt the final chunk (at most `chunk_size` bytes, may be empty)
+    pub fn encrypt_last(mut self, chunk: &[u8]) -> Result<Vec<u8>> {
+        if chunk.len() > self.state.chunk_size {
+            return Err(CryptoError::EncapsulationFailed(format!(
+                "Final chunk exceeds {} bytes",
+                self.state.chunk_size
+            )));
+        }
+        self.seal(chunk, true)
+    }
+
+    fn seal(&mut self, chunk: &[u8], last: bool) -> Result<Vec<u8>> {
+        let nonce = self.state.next_nonce(last)?;
+        let mut buffer = Vec::with_capacity(chunk.len() + STREAM_TAG_SIZE);
+        buffer.extend_from_slice(chunk);
+        self.state
+            .key
+            .seal_in_place_append_tag(nonce, Aad::from(&self.state.header), &mut buffer)
+            .map_err(|_| CryptoError::EncapsulationFailed("Chunk seal failed".to_string()))?;
+        Ok(buffer)
+    }
+}
+
+/// Chunk-by-chunk stream decryption
+pub struct StreamDecryptor {
+    state: StreamState,
+}
+
+impl StreamDecryptor {
+    /// Start from a stream header
+    pub fn new(key: &[u8; 32], header: &[u8; STREAM_HEADER_SIZE]) -> Result<Self> {
+        Ok(Self {
+            state: StreamState::new(key, *header)?,
+        })
+    }
+
+    /// Plaintext bytes per chunk
+    pub fn chunk_size(&self) -> usize {
+        self.state.chunk_size
+    }
+
+    /// Decrypt a non-final chunk
+    pub fn decrypt_next(&mut self, chunk: &[u8]) -> Result<Zeroizing<Vec<u8>>> {
+        if chunk.len() != self.state.chunk_size + STREAM_TAG_SIZE {
+            return Err(CryptoError::DecryptionError("Truncated chunk".to_string()));
+        }
+        self.open(chunk, false)
+    }
+
+    /// Decrypt the final chunk
+    pub fn decrypt_last(mut self, chunk: &[u8]) -> Result<Zeroizing<Vec<u8>>> {
+        if chunk.len() < STREAM_TAG_SIZE || chunk.len() > self.state.chunk_size + STREAM_TAG_SIZE {
+            return Err(CryptoError::DecryptionError(
+                "Invalid final chunk".to_string(),
+            ));
+        }
+        self.open(chunk, true)
+    }
+
+    fn open(&mut self, chunk: &[u8], last: bool) -> Result<Zeroizing<Vec<u8>>> {
+        let nonce = self
+            .state
+            .next_nonce(last)
+            .map_err(|e| CryptoError::DecryptionError(e.to_string()))?;
+        let mut buffer = Zeroizing::new(chunk.to_vec());
+        let len = self
+            .state
+            .key
+            .open_in_place(nonce, Aad::from(&self.state.header), &mut buffer)
+            .map_err(|_| CryptoError::DecryptionError("Chunk authentication failed".to_string()))?
+            .len();
+        buffer.truncate(len);
+        Ok(buffer)
+    }
+}
+
+/// `Write` adapter that encrypts everything written to it
+///
+/// Call [`EncryptWriter::finish`] to seal the final chunk; dropping the
+/// writer without finishing leaves a stream that fails to decrypt.
+pub struct EncryptWriter<W: Write> {
+    inner: W,
+    encryptor: Option<StreamEncryptor>,
+    buffer: Zeroizing<Vec<u8>>,
+}
+
+impl<W: Write> EncryptWriter<W> {
+    /// Write the header and start encrypting into `inner`
+    pub fn new(key: &[u8; 32], chunk_size: usize, mut inner: W) -> io::Result<Self> {
+        let encryptor = StreamEncryptor::new(key, chunk_size).map_err(invalid_input)?;
+        inner.write_all(encryptor.header())?;
+        Ok(Self {
+            inner,
+            buffer: Zeroizing::new(Vec::with_capacity(chunk_size)),
+            encryptor: Some(encryptor),
+        })
+    }
+
+    /// Seal the final chunk and return the inner writer
+    pub fn finish(mut self) -> io::Result<W> {
+        let encryptor = self.encryptor.take().ok_or_else(finished)?;
+        let chunk = encryptor
+            .encrypt_last(&self.buffer)
+            .map_err(invalid_input)?;
+        self.inner.write_all(&chunk)?;
+        self.inner.flush()?;
+        Ok(self.inner)
+    }
+}
+
+impl<W: Write> Write for EncryptWriter<W> {
+    fn write(&mut self, data: &[u8]) -> io::Result<usize> {
+        let encryptor = self.encryptor.as_mut().ok_or_else(finished)?;
+        let chunk_size = encryptor.chunk_size();
+
+        // A full buffer is only sealed once more data arrives, since the
+        // last chunk may itself be full
+        if self.buffer.len() == chunk_size && !data.is_empty() {
+            let chunk = encryptor
+                .encrypt_next(&self.buffer)
+                .map_err(invalid_input)?;
+            self.inner.write_all(&chunk)?;
+            self.buffer.clear();
+        }
+
+        let take = data.len().min(chunk_size - self.buffer.len());
+        self.buffer.extend_from_slice(&data[..take]);
+        Ok(take)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// `Read` adapter that decrypts and authenticates a stream
+///
+/// Returns an `InvalidData` error on tampering or truncation; plaintext from
+/// earlier chunks has already been authenticated when it is returned.
+pub struct DecryptReader<R: Read> {
+    inner: R,
+    decryptor: Option<StreamDecryptor>,
+    /// Ciphertext read ahead of the current chunk
+    pending: Vec<u8>,
+    plaintext: Zeroizing<Vec<u8>>,
+    position: usize,
+}
+
+impl<R: Read> DecryptReader<R> {
+    /// Read the header from `inner` and start decrypting
+    pub fn new(key: &[u8; 32], mut inner: R) -> io::Result<Self> {
+        let mut header = [0u8; STREAM_HEADER_SIZE];
+        inner.read_exact(&mut header)?;
+        let decryptor = StreamDecryptor::new(key, &header).map_err(invalid_data)?;
+        Ok(Self {
+            inner,
+            decryptor: Some(decryptor),
+            pending: Vec::new(),
+            plaintext: Zeroizing::new(Vec::new()),
+            position: 0,
+        })
+    }
+
+    /// Decrypt the next chunk into the plaintext buffer
+    fn fill(&mut self) -> io::Result<()> {
+        let Some(decryptor) = self.decryptor.as_ref() else {
+            return Ok(());
+        };
+        // One extra byte tells a full final chunk from a full middle one
+        let want = decryptor.chunk_size() + STREAM_TAG_SIZE + 1;
+        while self.pending.len() < want {
+            let start = self.pending.len();
+            self.pending.resize(want, 0);
+            let read = self.inner.read(&mut self.pending[start..]);
+            // Drop the zero padding before any error returns, so a retry
+            // resumes at `start` instead of taking it for ciphertext
+            let n = read.as_ref().map_or(0, |n| *n);
+            self.pending.truncate(start + n);
+            match read {
+                Ok(0) => break,
+                Ok(_) => {}
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        }
+
+        if self.pending.len() == want {
+            let rest = self.pending.split_off(want - 1);
+            let chunk = std::mem::replace(&mut self.pending, rest);
+            let decryptor = self.decryptor.as_mut().ok_or_else(finished)?;
+            self.plaintext = decryptor.decrypt_next(&chunk).map_err(invalid_data)?;
+        } else {
+            let chunk = std::mem::take(&mut self.pending);
+            let decryptor = self.decryptor.take().ok_or_else(finished)?;
+            self.plaintext = decryptor.decrypt_last(&chunk).map_err(invalid_data)?;
+        }
+        self.position = 0;
+        Ok(())
+    }
+}
+
+impl<R: Read> Read for DecryptReader<R> {
+    fn read(&mut self, out: &mut [u8]) -> io::Result<usize> {
+        while self.position == self.plaintext.len() {
+            if self.decryptor.is_none() {
+                return Ok(0);
+            }
+            self.fill()?;
+        }
+        let n = out.len().min(self.plaintext.len() - self.position);
+        out[..n].copy_from_slice(&self.plaintext[self.position..self.position + n]);
+        self.position += n;
+        Ok(n)
+    }
+}
+
+/// Encrypt everything from `reader` into `writer`, returning plaintext bytes
+pub fn encrypt_stream<R: Read, W: Write>(
+    key: &[u8; 32],
+    reader: &mut R,
+    writer: W,
+    chunk_size: usize,
+) -> io::Result<u64> {
+    let mut encrypt = EncryptWriter::new(key, chunk_size, writer)?;
+    let copied = io::copy(reader, &mut encrypt)?;
+    encrypt.finish()?;
+    Ok(copied)
+}
+
+/// Decrypt a stream from `reader` into `writer`, returning plaintext bytes
+pub fn decrypt_stream<R: Read, W: Write>(
+    key: &[u8; 32],
+    reader: R,
+    writer: &mut W,
+) -> io::Result<u64> {
+    let mut decrypt = DecryptReader::new(key, reader)?;
+    io::copy(&mut decrypt, writer)
+}
+
+fn invalid_input(e: CryptoError) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, e)
+}
+
+fn invalid_data(e: CryptoError) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, e)
+}
+
+fn finished() -> io::Error {
+    io::Error::other("Stream already finished")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEY: [u8; 32] = [5u8; 32];
+
+    fn encrypt(data: &[u8], chunk_size: usize) -> Vec<u8> {
+        let mut out = Vec::new();
+        encrypt_stream(&KEY, &mut &data[..], &mut out, chunk_size).unwrap();
+        out
+    }
+
+    fn decrypt(data: &[u8]) -> io::Result<Vec<u8>> {
+        let mut out = Vec::new();
+        decrypt_stream(&KEY, data, &mut out)?;
+        Ok(out)
+    }
+
+    #[test]
+    fn test_roundtrip_sizes() {
+        for len in [0usize, 1, 63, 64, 65, 128, 1000] {
+            let data: Vec<u8> = (0..len).map(|i| i as u8).collect();
+            let encrypted = encrypt(&data, 64);
+            let chunks = len.div_ceil(64).max(1);
+            assert_eq!(
+                encrypted.len(),
+                STREAM_HEADER_SIZE + len + chunks * STREAM_TAG_SIZE
+            );
+            assert_eq!(decrypt(&encrypted).unwrap(), data, "len {}", len);
+        }
+    }
+
+    #[test]
+    fn test_small_writes_and_reads() {
+        let data: Vec<u8> = (0..500u32).map(|i| (i * 7) as u8).collect();
+        let mut writer = EncryptWriter::new(&KEY, 32, Vec::new()).unwrap();
+        for byte in &data {
+            writer.write_all(&[*byte]).unwrap();
+        }
+        let encrypted = writer.finish().unwrap();
+
+        let mut reader = DecryptReader::new(&KEY, &encrypted[..]).unwrap();
+        let mut out = Vec::new();
+        let mut buf = [0u8; 3];
+        loop {
+            let n = reader.read(&mut buf).unwrap();
+            if n == 0 {
+                break;
+            }
+            out.extend_from_slice(&buf[..n]);
+        }
+        assert_eq!(out, data);
+    }
+
+    /// Fails every other read, alternating transient error kinds
+    struct Flaky<'a> {
+        data: &'a [u8],
+        calls: usize,
+    }
+
+    impl Read for Flaky<'_> {
+        fn read(&mut self, out: &mut [u8]) -> io::Result<usize> {
+            self.calls += 1;
+            match self.calls % 4 {
+                1 => Err(io::ErrorKind::Interrupted.into()),
+                3 => Err(io::ErrorKind::WouldBlock.into()),
+                _ => {
+                    let n = out.len().min(self.data.len()).min(7);
+                    out[..n].copy_from_slice(&self.data[..n]);
+                    self.data = &self.data[n..];
+                    Ok(n)
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_read_errors_resume() {
+        let data: Vec<u8> = (0..300u32).map(|i| (i * 3) as u8).collect();
+        let encrypted = encrypt(&data, 64);
+
+        // The header is read before decryption starts
+        let (header, body) = encrypted.split_at(STREAM_HEADER_SIZE);
+        let flaky = Flaky {
+            data: body,
+            calls: 0,
+        };
+        let mut reader = DecryptReader::new(&KEY, header.chain(flaky)).unwrap();
+        let mut out = Vec::new();
+        let mut buf = [0u8; 50];
+        loop {
+            match reader.read(&mut buf) {
+                Ok(0) => break,
+                Ok(n) => out.extend_from_slice(&buf[..n]),
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => {}
+                Err(e) => panic!("unexpected {}", e),
+            }
+        }
+        assert_eq!(out, data);
+    }
+
+    #[test]
+    fn test_truncation_detected() {
+        let data = vec![9u8; 256];
+        let encrypted = encrypt(&data, 64);
+
+        // Dropping the final chunk, at a chunk boundary
+        let boundary = STREAM_HEADER_SIZE + 3 * (64 + STREAM_TAG_SIZE);
+        assert!(decrypt(&encrypted[..boundary]).is_err());
+
+        // Cutting into a chunk
+        assert!(decrypt(&encrypted[..encrypted.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn test_reordering_and_tampering_detected() {
+        let data: Vec<u8> = (0..200).map(|i| i as u8).collect();
+        let encrypted = encrypt(&data, 64);
+        let chunk = 64 + STREAM_TAG_SIZE;
+
+        let mut swapped = encrypted.clone();
+        let (a, b) = swapped[STREAM_HEADER_SIZE..].split_at_mut(chunk);
+        a.swap_with_slice(&mut b[..chunk]);
+        assert!(decrypt(&swapped).is_err());
+
+        let mut flipped = encrypted.clone();
+        flipped[STREAM_HEADER_SIZE + 10] ^= 1;
+        assert!(decrypt(&flipped).is_err());
+
+        // The header is authenticated with every chunk
+        let mut header = encrypted.clone();
+        header[12] ^= 1;
+        assert!(decrypt(&header).is_err());
+    }
+
+    #[test]
+    fn test_wrong_key_fails() {
+        let encrypted = encrypt(b"secret payload", 64);
+        let mut out = Vec::new();
+        assert!(decrypt_stream(&[6u8; 32], &encrypted[..], &mut out).is_err());
+    }
+
+    #[test]
+    fn test_chunk_api() {
+        let mut enc = StreamEncryptor::new(&KEY, 4).unwrap();
+        let header = *enc.header();
+        assert!(enc.encrypt_next(b"abc").is_err());
+        let c1 = enc.encrypt_next(b"abcd").unwrap();
+        let c2 = enc.encrypt_last(b"ef").unwrap();
+
+        let mut dec = StreamDecryptor::new(&KEY, &header).unwrap();
+        assert_eq!(&dec.decrypt_next(&c1).unwrap()[..], b"abcd");
+        // A middle chunk cannot pass as the last one
+        assert!(StreamDecryptor::new(&KEY, &header)
+            .unwrap()
+            .decrypt_last(&c1)
+            .is_err());
+        assert_eq!(&dec.decrypt_last(&c2).unwrap()[..], b"ef");
+    }
+
+    #[test]
+    fn test_oversized_chunk_header_rejected() {
+        let mut header = [0u8; STREAM_HEADER_SIZE];
+        header[..4].copy_from_slice(STREAM_MAGIC);
+        header[4] = STREAM_VERSION;
+        header[5..9].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert!(StreamDecryptor::new(&KEY, &header).is_err());
+    }
+}
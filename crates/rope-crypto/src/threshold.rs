@@ -0,0 +1,1026 @@
+//! Threshold signatures (FROST over Ed25519)
+//!
+//! t-of-n Schnorr signatures following RFC 9591, FROST(Ed25519, SHA-512).
+//! The aggregate is an ordinary Ed25519 signature under the group key, so
+//! anchors and bridge withdrawals co-signed by a federation quorum verify
+//! with any Ed25519 verifier.
+//!
+//! ## Key generation
+//!
+//! Keys come from a Pedersen DKG, no dealer ever holds the group secret:
+//!
+//! 1. [`DkgParticipant::new`] returns a [`DkgRound1Package`] to broadcast
+//! 2. [`DkgParticipant::round2`] checks every broadcast and returns one
+//!    [`DkgRound2Package`] per peer, to be sent over a private channel
+//! 3. [`DkgParticipant::finish`] checks the received shares and yields a
+//!    [`KeyShare`]
+//!
+//! [`DkgParticipant::refresh`] runs the same rounds with zero-constant
+//! polynomials. The group key stays the same while every share changes, so
+//! shares leaked before the refresh are useless once the old ones are
+//! discarded.
+//!
+//! ## Signing
+//!
+//! 1. Each signer calls [`KeyShare::commit`], keeps the [`SigningNonces`]
+//!    and publishes the [`SigningCommitments`]
+//! 2. The coordinator builds a [`SigningPackage`] from at least `t`
+//!    commitments and the message
+//! 3. Each signer calls [`KeyShare::sign`], which consumes its nonces
+//! 4. The coordinator calls [`PublicKeyPackage::aggregate`], which checks
+//!    every share and names the participants whose shares are invalid
+//!
+//! Threshold Dilithium is not covered here; PQ co-signing still goes
+//! through per-member hybrid signatures.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use curve25519_dalek::constants::ED25519_BASEPOINT_POINT;
+use curve25519_dalek::edwards::{CompressedEdwardsY, EdwardsPoint};
+use curve25519_dalek::scalar::Scalar;
+use curve25519_dalek::traits::{Identity, IsIdentity};
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+use rand::rngs::OsRng;
+use rand::RngCore;
+use ring::digest;
+use serde::{Deserialize, Serialize};
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+use crate::error::{CryptoError, Result};
+
+/// RFC 9591 ciphersuite context string
+const CONTEXT_STRING: &[u8] = b"FROST-ED25519-SHA512-v1";
+
+/// Domain separator for DKG proofs of knowledge
+const DKG_CONTEXT: &[u8] = b"rope-frost-dkg-v1";
+
+/// Aggregate signature size (standard Ed25519)
+pub const THRESHOLD_SIGNATURE_SIZE: usize = 64;
+
+/// Participant identifier (1-based, never zero)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct ParticipantId(u16);
+
+impl ParticipantId {
+    /// Create an identifier, rejecting zero
+    pub fn new(id: u16) -> Result<Self> {
+        if id == 0 {
+            return Err(threshold_error("Participant ID must be non-zero"));
+        }
+        Ok(Self(id))
+    }
+
+    /// Raw identifier
+    pub fn get(&self) -> u16 {
+        self.0
+    }
+
+    fn scalar(&self) -> Scalar {
+        Scalar::from(self.0 as u64)
+    }
+}
+
+/// Threshold parameters
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ThresholdParams {
+    /// Minimum number of signers
+    pub threshold: u16,
+    /// Total number of participants
+    pub participants: u16,
+}
+
+impl ThresholdParams {
+    /// Create t-of-n parameters
+    pub fn new(threshold: u16, participants: u16) -> Result<Self> {
+        if threshold == 0 || threshold > participants {
+            return Err(threshold_error(&format!(
+                "Invalid threshold {}-of-{}",
+                threshold, participants
+            )));
+        }
+        Ok(Self {
+            threshold,
+            participants,
+        })
+    }
+
+    /// All participant identifiers
+    pub fn participant_ids(&self) -> impl Iterator<Item = ParticipantId> {
+        (1..=self.participants).map(ParticipantId)
+    }
+
+    fn check_id(&self, id: ParticipantId) -> Result<()> {
+        if id.0 == 0 || id.0 > self.participants {
+            return Err(threshold_error(&format!(
+                "Participant {} outside 1..={}",
+                id.0, self.participants
+            )));
+        }
+        Ok(())
+    }
+}
+
+// ============================================================================
+// Distributed key generation
+// ============================================================================
+
+/// Round 1 broadcast: polynomial commitments and proof of knowledge
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DkgRound1Package {
+    /// Sender
+    pub sender: ParticipantId,
+    /// Commitments to the polynomial coefficients
+    pub commitments: Vec<[u8; 32]>,
+    /// Schnorr proof `(R, z)` of the constant term (absent for refresh)
+    pub proof: Option<([u8; 32], [u8; 32])>,
+}
+
+/// Round 2 private message: the sender's polynomial evaluated at the recipient
+#[derive(Clone, Serialize, Deserialize, Zeroize, ZeroizeOnDrop)]
+pub struct DkgRound2Package {
+    /// Sender
+    #[zeroize(skip)]
+    pub sender: ParticipantId,
+    /// Recipient
+    #[zeroize(skip)]
+    pub recipient: ParticipantId,
+    /// Secret share
+    pub share: [u8; 32],
+}
+
+enum DkgSession {
+    Keygen,
+    Refresh(KeyShare),
+}
+
+/// One participant's side of a DKG or share refresh
+pub struct DkgParticipant {
+    id: ParticipantId,
+    params: ThresholdParams,
+    coefficients: Vec<Scalar>,
+    session: DkgSession,
+    commitments: Option<BTreeMap<ParticipantId, Vec<EdwardsPoint>>>,
+}
+
+impl DkgParticipant {
+    /// Start a fresh key generation
+    pub fn new(id: ParticipantId, params: ThresholdParams) -> Result<(Self, DkgRound1Package)> {
+        params.check_id(id)?;
+
+        let coefficients: Vec<Scalar> = (0..params.threshold).map(|_| random_scalar()).collect();
+        let commitments = commit_coefficients(&coefficients);
+
+        // Proof of knowledge of a_0 stops rogue-key attacks on the group key
+        let k = random_scalar();
+        let r = encode_point(&(ED25519_BASEPOINT_POINT * k));
+        let c = dkg_challenge(id, &commitments[0], &r);
+        let z = k + coefficients[0] * c;
+
+        let package = DkgRound1Package {
+            sender: id,
+            commitments,
+            proof: Some((r, z.to_bytes())),
+        };
+        let participant = Self {
+            id,
+            params,
+            coefficients,
+            session: DkgSession::Keygen,
+            commitments: None,
+        };
+        Ok((participant, package))
+    }
+
+    /// Start a proactive refresh of an existing share
+    pub fn refresh(share: KeyShare) -> Result<(Self, DkgRound1Package)> {
+        let id = share.id;
+        let params = share.params();
+
+        let mut coefficients = vec![Scalar::ZERO];
+        coefficients.extend((1..params.threshold).map(|_| random_scalar()));
+        let commitments = commit_coefficients(&coefficients);
+
+        let package = DkgRound1Package {
+            sender: id,
+            commitments,
+            proof: None,
+        };
+        let participant = Self {
+            id,
+            params,
+            coefficients,
+            session: DkgSession::Refresh(share),
+            commitments: None,
+        };
+        Ok((participant, package))
+    }
+
+    /// This participant's identifier
+    pub fn id(&self) -> ParticipantId {
+        self.id
+    }
+
+    /// Check all round 1 broadcasts (including our own) and produce the
+    /// private shares for every peer
+    pub fn round2(&mut self, packages: &[DkgRound1Package]) -> Result<Vec<DkgRound2Package>> {
+        let mut commitments = BTreeMap::new();
+        for package in packages {
+            self.params.check_id(package.sender)?;
+            let points = self.verify_round1(package)?;
+            if commitments.insert(package.sender, points).is_some() {
+                return Err(threshold_error(&format!(
+                    "Duplicate round 1 package from {}",
+                    package.sender.0
+                )));
+            }
+        }
+        if commitments.len() != self.params.participants as usize {
+            return Err(threshold_error(&format!(
+                "Expected {} round 1 packages, got {}",
+                self.params.participants,
+                commitments.len()
+            )));
+        }
+        if commitments.get(&self.id)
+            != Some(&decode_commitments(&commit_coefficients(
+                &self.coefficients,
+            ))?)
+        {
+            return Err(threshold_error("Own round 1 package missing or altered"));
+        }
+        self.commitments = Some(commitments);
+
+        Ok(self
+            .params
+            .participant_ids()
+            .filter(|peer| *peer != self.id)
+            .map(|peer| DkgRound2Package {
+                sender: self.id,
+                recipient: peer,
+                share: eval_polynomial(&self.coefficients, peer.scalar()).to_bytes(),
+            })
+            .collect())
+    }
+
+    /// Check the shares addressed to us and assemble the key share
+    pub fn finish(self, packages: &[DkgRound2Package]) -> Result<KeyShare> {
+        let commitments = self
+            .commitments
+            .as_ref()
+            .ok_or_else(|| threshold_error("Round 2 has not run"))?;
+
+        let mut secret = eval_polynomial(&self.coefficients, self.id.scalar());
+        let mut seen = BTreeSet::new();
+        for package in packages {
+            if package.recipient != self.id {
+                return Err(threshold_error(&format!(
+                    "Share from {} addressed to {}",
+                    package.sender.0, package.recipient.0
+                )));
+            }
+            if package.sender == self.id || !seen.insert(package.sender) {
+                return Err(threshold_error(&format!(
+                    "Unexpected share from {}",
+                    package.sender.0
+                )));
+            }
+            let sender_commitments = commitments.get(&package.sender).ok_or_else(|| {
+                threshold_error(&format!("No commitments from {}", package.sender.0))
+            })?;
+
+            let share = decode_scalar(&package.share)?;
+            let expected = eval_commitments(sender_commitments, self.id.scalar());
+            if ED25519_BASEPOINT_POINT * share != expected {
+                return Err(threshold_error(&format!(
+                    "Invalid share from {}",
+                    package.sender.0
+                )));
+            }
+            secret += share;
+        }
+        if seen.len() + 1 != self.params.participants as usize {
+            return Err(threshold_error(&format!(
+                "Expected {} shares, got {}",
+                self.params.participants - 1,
+                seen.len()
+            )));
+        }
+
+        // Sum of all polynomials, coefficient-wise in the exponent
+        let group_commitments: Vec<EdwardsPoint> = (0..self.params.threshold as usize)
+            .map(|k| commitments.values().map(|c| c[k]).sum())
+            .collect();
+
+        let (group_public, mut verifying_shares) = match &self.session {
+            DkgSession::Keygen => (group_commitments[0], BTreeMap::new()),
+            DkgSession::Refresh(old) => {
+                secret += decode_scalar(&old.secret)?;
+                let mut shares = BTreeMap::new();
+                for (id, bytes) in &old.public.verifying_shares {
+                    shares.insert(*id, decode_point(bytes)?);
+                }
+                (decode_point(&old.public.group_public)?, shares)
+            }
+        };
+        for id in self.params.participant_ids() {
+            let delta = eval_commitments(&group_commitments, id.scalar());
+            let entry = verifying_shares
+                .entry(id)
+                .or_insert_with(EdwardsPoint::identity);
+            *entry += delta;
+        }
+
+        if group_public.is_identity() {
+            return Err(threshold_error("Group public key is the identity"));
+        }
+        if verifying_shares.get(&self.id) != Some(&(ED25519_BASEPOINT_POINT * secret)) {
+            return Err(threshold_error(
+                "Derived share does not match verifying share",
+            ));
+        }
+
+        let share = KeyShare {
+            id: self.id,
+            secret: secret.to_bytes(),
+            public: PublicKeyPackage {
+                params: self.params,
+                group_public: encode_point(&group_public),
+                verifying_shares: verifying_shares
+                    .iter()
+                    .map(|(id, point)| (*id, encode_point(point)))
+                    .collect(),
+            },
+        };
+        secret.zeroize();
+        Ok(share)
+    }
+
+    fn verify_round1(&self, package: &DkgRound1Package) -> Result<Vec<EdwardsPoint>> {
+        let sender = package.sender.0;
+        if package.commitments.len() != self.params.threshold as usize {
+            return Err(threshold_error(&format!(
+                "Participant {} committed to {} coefficients, expected {}",
+                sender,
+                package.commitments.len(),
+                self.params.threshold
+            )));
+        }
+        let points = decode_commitments(&package.commitments)?;
+
+        match (&self.session, &package.proof) {
+            (DkgSession::Keygen, Some((r, z))) => {
+                let c = dkg_challenge(package.sender, &package.commitments[0], r);
+                let lhs = ED25519_BASEPOINT_POINT * decode_scalar(z)?;
+                if lhs != decode_point(r)? + points[0] * c {
+                    return Err(threshold_error(&format!(
+                        "Invalid proof of knowledge from {}",
+                        sender
+                    )));
+                }
+            }
+            (DkgSession::Refresh(_), None) => {
+                if !points[0].is_identity() {
+                    return Err(threshold_error(&format!(
+                        "Refresh polynomial from {} has a non-zero constant",
+                        sender
+                    )));
+                }
+            }
+            _ => {
+                return Err(threshold_error(&format!(
+                    "Round 1 package from {} does not match the session",
+                    sender
+                )))
+            }
+        }
+        Ok(points)
+    }
+}
+
+impl Drop for DkgParticipant {
+    fn drop(&mut self) {
+        self.coefficients.zeroize();
+    }
+}
+
+// ============================================================================
+// Keys
+// ============================================================================
+
+/// Public data shared by the whole group
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PublicKeyPackage {
+    params: ThresholdParams,
+    group_public: [u8; 32],
+    verifying_shares: BTreeMap<ParticipantId, [u8; 32]>,
+}
+
+impl PublicKeyPackage {
+    /// Threshold parameters
+    pub fn params(&self) -> ThresholdParams {
+        self.params
+    }
+
+    /// Group Ed25519 public key
+    pub fn group_public(&self) -> &[u8; 32] {
+        &self.group_public
+    }
+
+    /// Public share of a participant
+    pub fn verifying_share(&self, id: ParticipantId) -> Option<&[u8; 32]> {
+        self.verifying_shares.get(&id)
+    }
+
+    /// Verify an aggregate signature against the group key
+    pub fn verify(&self, message: &[u8], signature: &[u8; THRESHOLD_SIGNATURE_SIZE]) -> bool {
+        let Ok(key) = VerifyingKey::from_bytes(&self.group_public) else {
+            return false;
+        };
+        key.verify(message, &Signature::from_bytes(signature))
+            .is_ok()
+    }
+
+    /// Check one signature share
+    pub fn verify_share(&self, package: &SigningPackage, share: &SignatureShare) -> Result<bool> {
+        let context = SigningContext::new(&self.group_public, package)?;
+        self.verify_share_with(&context, package, share)
+    }
+
+    /// Check every share and combine them into an Ed25519 signature
+    pub fn aggregate(
+        &self,
+        package: &SigningPackage,
+        shares: &[SignatureShare],
+    ) -> Result<[u8; THRESHOLD_SIGNATURE_SIZE]> {
+        self.check_signers(package)?;
+        let context = SigningContext::new(&self.group_public, package)?;
+
+        let mut by_signer = BTreeMap::new();
+        for share in shares {
+            if by_signer.insert(share.id, share).is_some() {
+                return Err(threshold_error(&format!(
+                    "Duplicate signature share from {}",
+                    share.id.0
+                )));
+            }
+        }
+        let signers = package.signers();
+        if by_signer.keys().copied().collect::<Vec<_>>() != signers {
+            return Err(threshold_error(
+                "Signature shares do not match the signing set",
+            ));
+        }
+
+        let mut culprits = Vec::new();
+        let mut z = Scalar::ZERO;
+        for share in by_signer.values() {
+            if !self.verify_share_with(&context, package, share)? {
+                culprits.push(share.id.0.to_string());
+                continue;
+            }
+            z += decode_scalar(&share.share)?;
+        }
+        if !culprits.is_empty() {
+            return Err(threshold_error(&format!(
+                "Invalid signature shares from {}",
+                culprits.join(", ")
+            )));
+        }
+
+        let mut signature = [0u8; THRESHOLD_SIGNATURE_SIZE];
+        signature[..32].copy_from_slice(&encode_point(&context.group_commitment));
+        signature[32..].copy_from_slice(&z.to_bytes());
+
+        if !self.verify(&package.message, &signature) {
+            return Err(CryptoError::VerificationFailed);
+        }
+        Ok(signature)
+    }
+
+    fn verify_share_with(
+        &self,
+        context: &SigningContext,
+        package: &SigningPackage,
+        share: &SignatureShare,
+    ) -> Result<bool> {
+        let commitments = package
+            .commitments
+            .iter()
+            .find(|c| c.id == share.id)
+            .ok_or_else(|| threshold_error(&format!("{} is not a signer", share.id.0)))?;
+        let verifying_share = self
+            .verifying_shares
+            .get(&share.id)
+            .ok_or_else(|| threshold_error(&format!("Unknown participant {}", share.id.0)))?;
+
+        let z = decode_scalar(&share.share)?;
+        let rho = context.binding_factors[&share.id];
+        let lambda = lagrange_coefficient(share.id, &package.signers());
+
+        let commitment_share =
+            decode_point(&commitments.hiding)? + decode_point(&commitments.binding)? * rho;
+        let expected =
+            commitment_share + decode_point(verifying_share)? * (context.challenge * lambda);
+        Ok(ED25519_BASEPOINT_POINT * z == expected)
+    }
+
+    fn check_signers(&self, package: &SigningPackage) -> Result<()> {
+        if package.commitments.len() < self.params.threshold as usize {
+            return Err(threshold_error(&format!(
+                "{} signers, threshold is {}",
+                package.commitments.len(),
+                self.params.threshold
+            )));
+        }
+        for commitments in &package.commitments {
+            self.params.check_id(commitments.id)?;
+        }
+        Ok(())
+    }
+}
+
+/// A participant's secret share plus the group's public data
+#[derive(Clone, Serialize, Deserialize)]
+pub struct KeyShare {
+    id: ParticipantId,
+    secret: [u8; 32],
+    public: PublicKeyPackage,
+}
+
+impl KeyShare {
+    /// Participant identifier
+    pub fn id(&self) -> ParticipantId {
+        self.id
+    }
+
+    /// Threshold parameters
+    pub fn params(&self) -> ThresholdParams {
+        self.public.params
+    }
+
+    /// Group public data
+    pub fn public(&self) -> &PublicKeyPackage {
+        &self.public
+    }
+
+    /// Round 1 of signing: fresh nonces and their public commitments
+    pub fn commit(&self) -> (SigningNonces, SigningCommitments) {
+        let hiding = self.nonce();
+        let binding = self.nonce();
+        let commitments = SigningCommitments {
+            id: self.id,
+            hiding: encode_point(&(ED25519_BASEPOINT_POINT * hiding)),
+            binding: encode_point(&(ED25519_BASEPOINT_POINT * binding)),
+        };
+        let nonces = SigningNonces {
+            hiding,
+            binding,
+            commitments: commitments.clone(),
+        };
+        (nonces, commitments)
+    }
+
+    /// Round 2 of signing: produce this participant's signature share
+    ///
+    /// The nonces are consumed so they cannot be reused for another message.
+    pub fn sign(&self, package: &SigningPackage, nonces: SigningNonces) -> Result<SignatureShare> {
+        self.public.check_signers(package)?;
+        let own = package
+            .commitments
+            .iter()
+            .find(|c| c.id == self.id)
+            .ok_or_else(|| threshold_error(&format!("{} is not a signer", self.id.0)))?;
+        if *own != nonces.commitments {
+            return Err(threshold_error(
+                "Signing package commitments do not match nonces",
+            ));
+        }
+
+        let context = SigningContext::new(&self.public.group_public, package)?;
+        let rho = context.binding_factors[&self.id];
+        let lambda = lagrange_coefficient(self.id, &package.signers());
+        let mut secret = decode_scalar(&self.secret)?;
+
+        let z = nonces.hiding + nonces.binding * rho + lambda * secret * context.challenge;
+        secret.zeroize();
+
+        Ok(SignatureShare {
+            id: self.id,
+            share: z.to_bytes(),
+        })
+    }
+
+    fn nonce(&self) -> Scalar {
+        // RFC 9591 nonce_generate: mixes the secret in so a weak RNG alone
+        // does not leak it
+        let mut random = [0u8; 32];
+        OsRng.fill_bytes(&mut random);
+        let nonce = hash_to_scalar(&[CONTEXT_STRING, b"nonce", &random, &self.secret]);
+        random.zeroize();
+        nonce
+    }
+}
+
+impl Drop for KeyShare {
+    fn drop(&mut self) {
+        self.secret.zeroize();
+    }
+}
+
+// ============================================================================
+// Signing
+// ============================================================================
+
+/// Secret signing nonces, used for exactly one signature
+pub struct SigningNonces {
+    hiding: Scalar,
+    binding: Scalar,
+    commitments: SigningCommitments,
+}
+
+impl Drop for SigningNonces {
+    fn drop(&mut self) {
+        self.hiding.zeroize();
+        self.binding.zeroize();
+    }
+}
+
+/// Public commitments to a signer's nonces
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SigningCommitments {
+    /// Signer
+    pub id: ParticipantId,
+    /// Hiding nonce commitment
+    pub hiding: [u8; 32],
+    /// Binding nonce commitment
+    pub binding: [u8; 32],
+}
+
+/// Message plus the commitments of the chosen signers
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SigningPackage {
+    message: Vec<u8>,
+    commitments: Vec<SigningCommitments>,
+}
+
+impl SigningPackage {
+    /// Build a signing package (commitments are sorted by signer)
+    pub fn new(message: Vec<u8>, mut commitments: Vec<SigningCommitments>) -> Result<Self> {
+        commitments.sort_by_key(|c| c.id);
+        if commitments.windows(2).any(|w| w[0].id == w[1].id) {
+            return Err(threshold_error("Duplicate signer commitments"));
+        }
+        Ok(Self {
+            message,
+            commitments,
+        })
+    }
+
+    /// Message being signed
+    pub fn message(&self) -> &[u8] {
+        &self.message
+    }
+
+    /// Signers in ascending order
+    pub fn signers(&self) -> Vec<ParticipantId> {
+        self.commitments.iter().map(|c| c.id).collect()
+    }
+}
+
+/// One signer's contribution to the aggregate signature
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignatureShare {
+    /// Signer
+    pub id: ParticipantId,
+    /// Share scalar
+    pub share: [u8; 32],
+}
+
+/// Binding factors, group commitment and challenge for one signing package
+struct SigningContext {
+    binding_factors: BTreeMap<ParticipantId, Scalar>,
+    group_commitment: EdwardsPoint,
+    challenge: Scalar,
+}
+
+impl SigningContext {
+    fn new(group_public: &[u8; 32], package: &SigningPackage) -> Result<Self> {
+        let mut encoded = Vec::with_capacity(package.commitments.len() * 96);
+        for c in &package.commitments {
+            encoded.extend_from_slice(&c.id.scalar().to_bytes());
+            encoded.extend_from_slice(&c.hiding);
+            encoded.extend_from_slice(&c.binding);
+        }
+        let msg_hash = hash(&[CONTEXT_STRING, b"msg", &package.message]);
+        let com_hash = hash(&[CONTEXT_STRING, b"com", &encoded]);
+
+        let mut binding_factors = BTreeMap::new();
+        let mut group_commitment = EdwardsPoint::identity();
+        for c in &package.commitments {
+            let rho = hash_to_scalar(&[
+                CONTEXT_STRING,
+                b"rho",
+                group_public,
+                &msg_hash,
+                &com_hash,
+                &c.id.scalar().to_bytes(),
+            ]);
+            let hiding = decode_point(&c.hiding)?;
+            let binding = decode_point(&c.binding)?;
+            if hiding.is_identity() || binding.is_identity() {
+                return Err(threshold_error(&format!(
+                    "Identity commitment from {}",
+                    c.id.0
+                )));
+            }
+            group_commitment += hiding + binding * rho;
+            binding_factors.insert(c.id, rho);
+        }
+
+        // Plain Ed25519 challenge so the result verifies as Ed25519
+        let challenge = hash_to_scalar(&[
+            &encode_point(&group_commitment),
+            group_public,
+            &package.message,
+        ]);
+
+        Ok(Self {
+            binding_factors,
+            group_commitment,
+            challenge,
+        })
+    }
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+fn threshold_error(msg: &str) -> CryptoError {
+    CryptoError::ThresholdError(msg.to_string())
+}
+
+fn hash(parts: &[&[u8]]) -> [u8; 64] {
+    let mut ctx = digest::Context::new(&digest::SHA512);
+    for part in parts {
+        ctx.update(part);
+    }
+    let mut out = [0u8; 64];
+    out.copy_from_slice(ctx.finish().as_ref());
+    out
+}
+
+fn hash_to_scalar(parts: &[&[u8]]) -> Scalar {
+    Scalar::from_bytes_mod_order_wide(&hash(parts))
+}
+
+fn random_scalar() -> Scalar {
+    let mut bytes = [0u8; 64];
+    OsRng.fill_bytes(&mut bytes);
+    let scalar = Scalar::from_bytes_mod_order_wide(&bytes);
+    bytes.zeroize();
+    scalar
+}
+
+fn encode_point(point: &EdwardsPoint) -> [u8; 32] {
+    point.compress().to_bytes()
+}
+
+fn decode_point(bytes: &[u8; 32]) -> Result<EdwardsPoint> {
+    CompressedEdwardsY(*bytes)
+        .decompress()
+        .filter(|p| p.is_torsion_free())
+        .ok_or_else(|| threshold_error("Invalid group element"))
+}
+
+fn decode_scalar(bytes: &[u8; 32]) -> Result<Scalar> {
+    Option::from(Scalar::from_canonical_bytes(*bytes))
+        .ok_or_else(|| threshold_error("Non-canonical scalar"))
+}
+
+fn commit_coefficients(coefficients: &[Scalar]) -> Vec<[u8; 32]> {
+    coefficients
+        .iter()
+        .map(|a| encode_point(&(ED25519_BASEPOINT_POINT * a)))
+        .collect()
+}
+
+fn decode_commitments(commitments: &[[u8; 32]]) -> Result<Vec<EdwardsPoint>> {
+    commitments.iter().map(decode_point).collect()
+}
+
+fn dkg_challenge(id: ParticipantId, commitment: &[u8; 32], r: &[u8; 32]) -> Scalar {
+    hash_to_scalar(&[DKG_CONTEXT, &id.scalar().to_bytes(), commitment, r])
+}
+
+/// f(x) via Horner's rule
+fn eval_polynomial(coefficients: &[Scalar], x: Scalar) -> Scalar {
+    coefficients
+        .iter()
+        .rev()
+        .fold(Scalar::ZERO, |acc, a| acc * x + a)
+}
+
+/// f(x)·G from the coefficient commitments
+fn eval_commitments(commitments: &[EdwardsPoint], x: Scalar) -> EdwardsPoint {
+    commitments
+        .iter()
+        .rev()
+        .fold(EdwardsPoint::identity(), |acc, c| acc * x + c)
+}
+
+/// Lagrange coefficient of `id` at zero over `signers`
+fn lagrange_coefficient(id: ParticipantId, signers: &[ParticipantId]) -> Scalar {
+    let x_i = id.scalar();
+    let mut numerator = Scalar::ONE;
+    let mut denominator = Scalar::ONE;
+    for other in signers.iter().filter(|s| **s != id) {
+        let x_j = other.scalar();
+        numerator *= x_j;
+        denominator *= x_j - x_i;
+    }
+    numerator * denominator.invert()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run_rounds(
+        mut participants: Vec<DkgParticipant>,
+        round1: Vec<DkgRound1Package>,
+    ) -> Vec<KeyShare> {
+        let mut outgoing = Vec::new();
+        for p in participants.iter_mut() {
+            outgoing.extend(p.round2(&round1).unwrap());
+        }
+        participants
+            .into_iter()
+            .map(|p| {
+                let mine: Vec<_> = outgoing
+                    .iter()
+                    .filter(|pkg| pkg.recipient == p.id())
+                    .cloned()
+                    .collect();
+                p.finish(&mine).unwrap()
+            })
+            .collect()
+    }
+
+    fn dkg(threshold: u16, participants: u16) -> Vec<KeyShare> {
+        let params = ThresholdParams::new(threshold, participants).unwrap();
+        let (participants, round1): (Vec<_>, Vec<_>) = params
+            .participant_ids()
+            .map(|id| DkgParticipant::new(id, params).unwrap())
+            .unzip();
+        run_rounds(participants, round1)
+    }
+
+    fn refresh(shares: Vec<KeyShare>) -> Vec<KeyShare> {
+        let (participants, round1): (Vec<_>, Vec<_>) = shares
+            .into_iter()
+            .map(|s| DkgParticipant::refresh(s).unwrap())
+            .unzip();
+        run_rounds(participants, round1)
+    }
+
+    fn sign(shares: &[&KeyShare], message: &[u8]) -> Result<[u8; 64]> {
+        let (nonces, commitments): (Vec<_>, Vec<_>) = shares.iter().map(|s| s.commit()).unzip();
+        let package = SigningPackage::new(message.to_vec(), commitments)?;
+        let sig_shares = shares
+            .iter()
+            .zip(nonces)
+            .map(|(s, n)| s.sign(&package, n))
+            .collect::<Result<Vec<_>>>()?;
+        shares[0].public().aggregate(&package, &sig_shares)
+    }
+
+    #[test]
+    fn test_dkg_agrees_on_group_key() {
+        let shares = dkg(2, 3);
+        let public = shares[0].public();
+        assert!(shares.iter().all(|s| s.public() == public));
+        for share in &shares {
+            assert_eq!(
+                public.verifying_share(share.id()).unwrap(),
+                &encode_point(&(ED25519_BASEPOINT_POINT * decode_scalar(&share.secret).unwrap()))
+            );
+        }
+    }
+
+    #[test]
+    fn test_any_quorum_produces_ed25519_signature() {
+        let shares = dkg(2, 3);
+        let message = b"anchor 42";
+
+        for (a, b) in [(0, 1), (0, 2), (1, 2)] {
+            let signature = sign(&[&shares[a], &shares[b]], message).unwrap();
+            assert!(shares[0].public().verify(message, &signature));
+
+            // Verifies as a plain Ed25519 signature
+            let key = VerifyingKey::from_bytes(shares[0].public().group_public()).unwrap();
+            assert!(key
+                .verify_strict(message, &Signature::from_bytes(&signature))
+                .is_ok());
+        }
+
+        let all: Vec<&KeyShare> = shares.iter().collect();
+        let signature = sign(&all, message).unwrap();
+        assert!(!shares[0].public().verify(b"other", &signature));
+    }
+
+    #[test]
+    fn test_below_threshold_rejected() {
+        let shares = dkg(3, 4);
+        assert!(sign(&[&shares[0], &shares[1]], b"withdrawal").is_err());
+        assert!(sign(&[&shares[0], &shares[1], &shares[3]], b"withdrawal").is_ok());
+    }
+
+    #[test]
+    fn test_invalid_share_identifies_culprit() {
+        let shares = dkg(2, 3);
+        let (n1, c1) = shares[0].commit();
+        let (n2, c2) = shares[1].commit();
+        let package = SigningPackage::new(b"msg".to_vec(), vec![c1, c2]).unwrap();
+
+        let good = shares[0].sign(&package, n1).unwrap();
+        let mut bad = shares[1].sign(&package, n2).unwrap();
+        bad.share = (decode_scalar(&bad.share).unwrap() + Scalar::ONE).to_bytes();
+
+        assert!(shares[0].public().verify_share(&package, &good).unwrap());
+        assert!(!shares[0].public().verify_share(&package, &bad).unwrap());
+
+        let err = shares[0]
+            .public()
+            .aggregate(&package, &[good, bad])
+            .unwrap_err();
+        assert!(err.to_string().contains("from 2"));
+    }
+
+    #[test]
+    fn test_nonces_bound_to_package() {
+        let shares = dkg(2, 3);
+        let (n1, _) = shares[0].commit();
+        let (_, c1) = shares[0].commit();
+        let (_, c2) = shares[1].commit();
+        let package = SigningPackage::new(b"msg".to_vec(), vec![c1, c2]).unwrap();
+        assert!(shares[0].sign(&package, n1).is_err());
+    }
+
+    #[test]
+    fn test_dkg_rejects_bad_share() {
+        let params = ThresholdParams::new(2, 3).unwrap();
+        let (mut participants, round1): (Vec<_>, Vec<_>) = params
+            .participant_ids()
+            .map(|id| DkgParticipant::new(id, params).unwrap())
+            .unzip();
+
+        let mut outgoing = Vec::new();
+        for p in participants.iter_mut() {
+            outgoing.extend(p.round2(&round1).unwrap());
+        }
+        let victim = participants.remove(0);
+        let mut mine: Vec<_> = outgoing
+            .into_iter()
+            .filter(|pkg| pkg.recipient == victim.id())
+            .collect();
+        mine[0].share = (decode_scalar(&mine[0].share).unwrap() + Scalar::ONE).to_bytes();
+        assert!(victim.finish(&mine).is_err());
+    }
+
+    #[test]
+    fn test_dkg_rejects_bad_proof() {
+        let params = ThresholdParams::new(2, 2).unwrap();
+        let (mut p1, r1) = DkgParticipant::new(ParticipantId(1), params).unwrap();
+        let (_, mut r2) = DkgParticipant::new(ParticipantId(2), params).unwrap();
+        let (_, other) = DkgParticipant::new(ParticipantId(2), params).unwrap();
+        r2.proof = other.proof;
+        assert!(p1.round2(&[r1, r2]).is_err());
+    }
+
+    #[test]
+    fn test_refresh_keeps_group_key() {
+        let shares = dkg(2, 3);
+        let group = *shares[0].public().group_public();
+        let old = shares.clone();
+
+        let refreshed = refresh(shares);
+        assert_eq!(refreshed[0].public().group_public(), &group);
+        assert_ne!(refreshed[0].secret, old[0].secret);
+
+        let signature = sign(&[&refreshed[0], &refreshed[2]], b"after refresh").unwrap();
+        assert!(old[0].public().verify(b"after refresh", &signature));
+
+        // Old and new shares no longer combine
+        assert!(sign(&[&old[0], &refreshed[1]], b"mixed").is_err());
+    }
+
+    #[test]
+    fn test_params_validation() {
+        assert!(ThresholdParams::new(0, 3).is_err());
+        assert!(ThresholdParams::new(4, 3).is_err());
+        assert!(ParticipantId::new(0).is_err());
+        let params = ThresholdParams::new(2, 3).unwrap();
+        assert!(DkgParticipant::new(ParticipantId(4), params).is_err());
+    }
+}
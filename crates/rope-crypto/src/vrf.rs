@@ -0,0 +1,376 @@
+//! Verifiable random function (ECVRF-EDWARDS25519-SHA512-TAI)
+//!
+//! RFC 9381 ECVRF over Edwards25519 with try-and-increment hashing to the
+//! curve. A VRF output is unpredictable to anyone without the secret key,
+//! yet unique per `(key, input)` and publicly verifiable, which is what
+//! consensus needs to pick anchor producers that cannot grind the choice.
+//!
+//! Secret keys are ordinary 32-byte Ed25519 seeds, so a validator can reuse
+//! its Ed25519 seed or derive a dedicated one.
+
+use curve25519_dalek::constants::ED25519_BASEPOINT_POINT;
+use curve25519_dalek::edwards::{CompressedEdwardsY, EdwardsPoint};
+use curve25519_dalek::scalar::Scalar;
+use rand::rngs::OsRng;
+use rand::RngCore;
+use ring::digest;
+use serde::{Deserialize, Serialize};
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+use crate::error::{CryptoError, Result};
+
+/// ECVRF-EDWARDS25519-SHA512-TAI suite string
+const SUITE_STRING: u8 = 0x03;
+
+/// Proof size: Gamma (32) || c (16) || s (32)
+pub const VRF_PROOF_SIZE: usize = 80;
+
+/// Output size (SHA-512)
+pub const VRF_OUTPUT_SIZE: usize = 64;
+
+/// Challenge length in bytes
+const CHALLENGE_SIZE: usize = 16;
+
+/// VRF secret key
+#[derive(Zeroize, ZeroizeOnDrop)]
+pub struct VrfSecretKey {
+    seed: [u8; 32],
+    scalar: Scalar,
+    nonce_prefix: [u8; 32],
+}
+
+impl VrfSecretKey {
+    /// Generate a random key
+    pub fn generate() -> Self {
+        let mut seed = [0u8; 32];
+        OsRng.fill_bytes(&mut seed);
+        let key = Self::from_seed(&seed);
+        seed.zeroize();
+        key
+    }
+
+    /// Expand a 32-byte seed as in RFC 8032
+    pub fn from_seed(seed: &[u8; 32]) -> Self {
+        let mut expanded = sha512(&[seed]);
+        let mut clamped = [0u8; 32];
+        clamped.copy_from_slice(&expanded[..32]);
+        clamped[0] &= 248;
+        clamped[31] &= 127;
+        clamped[31] |= 64;
+
+        let mut nonce_prefix = [0u8; 32];
+        nonce_prefix.copy_from_slice(&expanded[32..]);
+
+        let key = Self {
+            seed: *seed,
+            scalar: Scalar::from_bytes_mod_order(clamped),
+            nonce_prefix,
+        };
+        expanded.zeroize();
+        clamped.zeroize();
+        key
+    }
+
+    /// Seed bytes
+    pub fn to_bytes(&self) -> [u8; 32] {
+        self.seed
+    }
+
+    /// Matching public key
+    pub fn public_key(&self) -> VrfPublicKey {
+        VrfPublicKey(
+            (ED25519_BASEPOINT_POINT * self.scalar)
+                .compress()
+                .to_bytes(),
+        )
+    }
+
+    /// Prove the VRF output for `alpha`
+    pub fn prove(&self, alpha: &[u8]) -> VrfProof {
+        let public = self.public_key();
+        // The try-and-increment loop only fails with probability 2^-256
+        let h = encode_to_curve(&public.0, alpha).expect("hash to curve exhausted");
+        let h_string = h.compress().to_bytes();
+        let gamma = h * self.scalar;
+
+        let k = Scalar::from_bytes_mod_order_wide(&sha512(&[&self.nonce_prefix, &h_string]));
+        let y = decode_point(&public.0).expect("own public key is valid");
+        let c = challenge(&y, &h, &gamma, &(ED25519_BASEPOINT_POINT * k), &(h * k));
+        let s = k + challenge_scalar(&c) * self.scalar;
+
+        let mut proof = Vec::with_capacity(VRF_PROOF_SIZE);
+        proof.extend_from_slice(gamma.compress().as_bytes());
+        proof.extend_from_slice(&c);
+        proof.extend_from_slice(s.as_bytes());
+        VrfProof(proof)
+    }
+}
+
+/// VRF public key (compressed Edwards point, same encoding as Ed25519)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct VrfPublicKey(pub [u8; 32]);
+
+impl VrfPublicKey {
+    /// Parse and validate a public key
+    pub fn from_bytes(bytes: &[u8; 32]) -> Result<Self> {
+        let point = decode_point(bytes)?;
+        if point.is_small_order() {
+            return Err(CryptoError::InvalidPublicKey(
+                "VRF public key has small order".to_string(),
+            ));
+        }
+        Ok(Self(*bytes))
+    }
+
+    /// Raw bytes
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+
+    /// Verify a proof for `alpha`, returning the VRF output on success
+    pub fn verify(&self, alpha: &[u8], proof: &VrfProof) -> Result<VrfOutput> {
+        let y = decode_point(&self.0)?;
+        if y.is_small_order() {
+            return Err(CryptoError::InvalidPublicKey(
+                "VRF public key has small order".to_string(),
+            ));
+        }
+        let (gamma, c, s) = proof.decode()?;
+
+        let h = encode_to_curve(&self.0, alpha)?;
+        let c_scalar = challenge_scalar(&c);
+        let u = ED25519_BASEPOINT_POINT * s - y * c_scalar;
+        let v = h * s - gamma * c_scalar;
+
+        if challenge(&y, &h, &gamma, &u, &v) != c {
+            return Err(CryptoError::VerificationFailed);
+        }
+        Ok(output_from_gamma(&gamma))
+    }
+}
+
+/// VRF proof (`pi` in RFC 9381)
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VrfProof(#[serde(with = "serde_bytes")] Vec<u8>);
+
+impl VrfProof {
+    /// Parse a proof, checking its length
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() != VRF_PROOF_SIZE {
+            return Err(CryptoError::InvalidSignature(format!(
+                "VRF proof must be {} bytes, got {}",
+                VRF_PROOF_SIZE,
+                bytes.len()
+            )));
+        }
+        Ok(Self(bytes.to_vec()))
+    }
+
+    /// Raw bytes
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// VRF output without verifying the proof
+    ///
+    /// Only meaningful for proofs produced locally or already verified.
+    pub fn output(&self) -> Result<VrfOutput> {
+        let (gamma, _, _) = self.decode()?;
+        Ok(output_from_gamma(&gamma))
+    }
+
+    fn decode(&self) -> Result<(EdwardsPoint, [u8; CHALLENGE_SIZE], Scalar)> {
+        if self.0.len() != VRF_PROOF_SIZE {
+            return Err(CryptoError::InvalidSignature(
+                "Bad VRF proof length".to_string(),
+            ));
+        }
+        let mut gamma_bytes = [0u8; 32];
+        gamma_bytes.copy_from_slice(&self.0[..32]);
+        let gamma = decode_point(&gamma_bytes)?;
+
+        let mut c = [0u8; CHALLENGE_SIZE];
+        c.copy_from_slice(&self.0[32..48]);
+
+        let mut s_bytes = [0u8; 32];
+        s_bytes.copy_from_slice(&self.0[48..]);
+        let s = Option::from(Scalar::from_canonical_bytes(s_bytes))
+            .ok_or_else(|| CryptoError::InvalidSignature("Non-canonical VRF scalar".to_string()))?;
+
+        Ok((gamma, c, s))
+    }
+}
+
+/// VRF output (`beta` in RFC 9381)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct VrfOutput(pub [u8; VRF_OUTPUT_SIZE]);
+
+impl VrfOutput {
+    /// Raw bytes
+    pub fn as_bytes(&self) -> &[u8; VRF_OUTPUT_SIZE] {
+        &self.0
+    }
+
+    /// First 8 bytes as a little-endian integer, for sampling
+    pub fn to_u64(&self) -> u64 {
+        let mut bytes = [0u8; 8];
+        bytes.copy_from_slice(&self.0[..8]);
+        u64::from_le_bytes(bytes)
+    }
+}
+
+fn sha512(parts: &[&[u8]]) -> [u8; 64] {
+    let mut ctx = digest::Context::new(&digest::SHA512);
+    for part in parts {
+        ctx.update(part);
+    }
+    let mut out = [0u8; 64];
+    out.copy_from_slice(ctx.finish().as_ref());
+    out
+}
+
+fn decode_point(bytes: &[u8; 32]) -> Result<EdwardsPoint> {
+    CompressedEdwardsY(*bytes)
+        .decompress()
+        .ok_or_else(|| CryptoError::InvalidPublicKey("Invalid curve point".to_string()))
+}
+
+/// ECVRF_encode_to_curve_try_and_increment
+fn encode_to_curve(public: &[u8; 32], alpha: &[u8]) -> Result<EdwardsPoint> {
+    for ctr in 0..=u8::MAX {
+        let hash = sha512(&[&[SUITE_STRING, 0x01], public, alpha, &[ctr, 0x00]]);
+        let mut candidate = [0u8; 32];
+        candidate.copy_from_slice(&hash[..32]);
+        if let Some(point) = CompressedEdwardsY(candidate).decompress() {
+            return Ok(point.mul_by_cofactor());
+        }
+    }
+    Err(CryptoError::KeyDerivationFailed(
+        "VRF hash to curve failed".to_string(),
+    ))
+}
+
+/// ECVRF_challenge_generation, truncated to 16 bytes
+fn challenge(
+    y: &EdwardsPoint,
+    h: &EdwardsPoint,
+    gamma: &EdwardsPoint,
+    u: &EdwardsPoint,
+    v: &EdwardsPoint,
+) -> [u8; CHALLENGE_SIZE] {
+    let hash = sha512(&[
+        &[SUITE_STRING, 0x02],
+        y.compress().as_bytes(),
+        h.compress().as_bytes(),
+        gamma.compress().as_bytes(),
+        u.compress().as_bytes(),
+        v.compress().as_bytes(),
+        &[0x00],
+    ]);
+    let mut c = [0u8; CHALLENGE_SIZE];
+    c.copy_from_slice(&hash[..CHALLENGE_SIZE]);
+    c
+}
+
+fn challenge_scalar(c: &[u8; CHALLENGE_SIZE]) -> Scalar {
+    let mut bytes = [0u8; 32];
+    bytes[..CHALLENGE_SIZE].copy_from_slice(c);
+    Scalar::from_bytes_mod_order(bytes)
+}
+
+/// ECVRF_proof_to_hash
+fn output_from_gamma(gamma: &EdwardsPoint) -> VrfOutput {
+    VrfOutput(sha512(&[
+        &[SUITE_STRING, 0x03],
+        gamma.mul_by_cofactor().compress().as_bytes(),
+        &[0x00],
+    ]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rfc_key() -> VrfSecretKey {
+        let seed = hex::decode("9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60")
+            .unwrap();
+        VrfSecretKey::from_seed(&seed.try_into().unwrap())
+    }
+
+    #[test]
+    fn test_rfc9381_vector() {
+        // RFC 9381 Appendix B.3, Example 16
+        let sk = rfc_key();
+        let pk = sk.public_key();
+        assert_eq!(
+            hex::encode(pk.as_bytes()),
+            "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a"
+        );
+
+        let proof = sk.prove(b"");
+        assert_eq!(
+            hex::encode(proof.as_bytes()),
+            "8657106690b5526245a92b003bb079ccd1a92130477671f6fc01ad16f26f723f\
+             26f8a57ccaed74ee1b190bed1f479d9727d2d0f9b005a6e456a35d4fb0daab12\
+             68a1b0db10836d9826a528ca76567805"
+        );
+        let output = pk.verify(b"", &proof).unwrap();
+        assert_eq!(
+            hex::encode(output.as_bytes()),
+            "90cf1df3b703cce59e2a35b925d411164068269d7b2d29f3301c03dd757876ff\
+             66b71dda49d2de59d03450451af026798e8f81cd2e333de5cdf4f3e140fdd8ae"
+        );
+    }
+
+    #[test]
+    fn test_rfc9381_vector_with_alpha() {
+        let sk = rfc_key();
+        let proof = sk.prove(&[0x72]);
+        assert_eq!(
+            hex::encode(proof.as_bytes()),
+            "cb809f3db77e2a15b9470e7b4cccb28ba08099a442bd357889505d3a57531430\
+             38392e8c6ec61b1796930d37bc58cbb5891b4d570a2180b3b18724ef9760bfff\
+             d4789004c970c70bd816cc104e9a110f"
+        );
+        assert_eq!(
+            hex::encode(sk.public_key().verify(&[0x72], &proof).unwrap().as_bytes()),
+            "8541f3ff9e0837a8a3c2b6da4326f0431a22bc7353edfedbc15089cab85bd4cb\
+             8108cbaf1e931fe2e6f477bb21a7bcdfe1c422c1ff2394b41379f4723ceb6b64"
+        );
+    }
+
+    #[test]
+    fn test_wrong_input_or_key_fails() {
+        let sk = VrfSecretKey::generate();
+        let proof = sk.prove(b"round 7");
+
+        assert!(sk.public_key().verify(b"round 8", &proof).is_err());
+        assert!(VrfSecretKey::generate()
+            .public_key()
+            .verify(b"round 7", &proof)
+            .is_err());
+
+        let mut tampered = proof.clone();
+        tampered.0[40] ^= 1;
+        assert!(sk.public_key().verify(b"round 7", &tampered).is_err());
+    }
+
+    #[test]
+    fn test_output_is_unique() {
+        let sk = VrfSecretKey::generate();
+        let a = sk.prove(b"alpha");
+        let b = sk.prove(b"alpha");
+        assert_eq!(a, b);
+        assert_eq!(
+            a.output().unwrap(),
+            sk.public_key().verify(b"alpha", &a).unwrap()
+        );
+        assert_ne!(a.output().unwrap(), sk.prove(b"beta").output().unwrap());
+    }
+
+    #[test]
+    fn test_small_order_key_rejected() {
+        let identity = EdwardsPoint::default().compress().to_bytes();
+        assert!(VrfPublicKey::from_bytes(&identity).is_err());
+    }
+}
@@ -8,6 +8,7 @@
 //! - **Tracker**: Distributed tracker using system strings  
 //! - **DHT**: Semantic distributed hash table
 //! - **Incentives**: Token-based rewards for contribution
+//! - **Channels**: Off-lattice micro-payments from leechers to seeders
 
 pub mod rdp {
     //! Core RDP protocol
@@ -178,6 +179,12 @@ pub mod incentives {
     //! - Providing bandwidth (seeding)
     //! - Storing strings and complements
     //! - Participating in regeneration
+    //!
+    //! Upload contributions are credited from bandwidth *measured* by the
+    //! receiving peers (`rope_network::NetworkStats::take_measured_uploads`),
+    //! never from the speeds nodes advertise in swarm membership.
+
+    use std::collections::HashMap;
 
     /// Incentive parameters
     #[derive(Clone, Debug)]
@@ -224,11 +231,583 @@ pub mod incentives {
 
         (params.base_reward as f64 * total_score.sqrt()) as u64
     }
+
+    /// Contributions per node for the current epoch
+    #[derive(Clone, Debug, Default)]
+    pub struct IncentiveLedger {
+        contributions: HashMap<[u8; 32], NodeContribution>,
+    }
+
+    impl IncentiveLedger {
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        /// Credit uploads measured by a receiving peer
+        pub fn record_measured_uploads<I>(&mut self, uploads: I)
+        where
+            I: IntoIterator<Item = ([u8; 32], u64)>,
+        {
+            for (node, bytes) in uploads {
+                let contrib = self.contributions.entry(node).or_default();
+                contrib.bytes_uploaded = contrib.bytes_uploaded.saturating_add(bytes);
+            }
+        }
+
+        /// Set the bytes a node stores (proven by storage challenges)
+        pub fn record_storage(&mut self, node: [u8; 32], bytes: u64) {
+            self.contributions.entry(node).or_default().bytes_stored = bytes;
+        }
+
+        /// Credit a regeneration the node helped with
+        pub fn record_regeneration(&mut self, node: [u8; 32]) {
+            self.contributions
+                .entry(node)
+                .or_default()
+                .regenerations_helped += 1;
+        }
+
+        pub fn contribution(&self, node: &[u8; 32]) -> Option<&NodeContribution> {
+            self.contributions.get(node)
+        }
+
+        /// Rewards for the epoch so far, highest first
+        pub fn rewards(&self, params: &IncentiveParams) -> Vec<([u8; 32], u64)> {
+            let mut rewards: Vec<([u8; 32], u64)> = self
+                .contributions
+                .iter()
+                .map(|(node, contrib)| (*node, calculate_reward(params, contrib)))
+                .collect();
+            rewards.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+            rewards
+        }
+
+        /// Compute rewards and start a new epoch
+        pub fn close_epoch(&mut self, params: &IncentiveParams) -> Vec<([u8; 32], u64)> {
+            let rewards = self.rewards(params);
+            self.contributions.clear();
+            rewards
+        }
+    }
+}
+
+pub mod channels {
+    //! Micro-payment channels
+    //!
+    //! A leecher locks a FAT deposit in a channel to a seeder, then pays per
+    //! verified chunk by signing receipts carrying the cumulative amount.
+    //! Only opening and closing touch the lattice; receipts stay between the
+    //! two peers. On close the payee is paid the latest receipt submitted
+    //! before the dispute window ends, and the payer gets the rest back.
+    //!
+    //! ```text
+    //! open (string) → receipt 1 … receipt n → close (string) → [dispute] → settle (string)
+    //! ```
+
+    use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+    use rope_core::clock::LamportClock;
+    use rope_core::string::{PublicKey, RopeString};
+    use rope_core::types::MutabilityClass;
+    use std::collections::HashMap;
+    use thiserror::Error;
+
+    /// Channel identifier
+    pub type ChannelId = [u8; 32];
+
+    /// Default dispute window: 1 day
+    pub const DEFAULT_DISPUTE_PERIOD_SECS: u64 = 86_400;
+
+    /// Channel errors
+    #[derive(Debug, Error, PartialEq)]
+    pub enum ChannelError {
+        #[error("Channel not found")]
+        NotFound,
+        #[error("Channel already exists")]
+        AlreadyExists,
+        #[error("Channel is not open")]
+        NotOpen,
+        #[error("Channel is not closing")]
+        NotClosing,
+        #[error("Deposit must be positive")]
+        EmptyDeposit,
+        #[error("Invalid receipt signature")]
+        InvalidSignature,
+        #[error("Receipt is for another channel")]
+        WrongChannel,
+        #[error("Receipt nonce {0} is not newer than {1}")]
+        StaleReceipt(u64, u64),
+        #[error("Receipt amount exceeds the deposit")]
+        ExceedsDeposit,
+        #[error("Receipt amount decreased")]
+        AmountDecreased,
+        #[error("Receipt underpays: {paid} for {chunks} chunks")]
+        Underpaid { paid: u128, chunks: u64 },
+        #[error("Dispute window still open until {0}")]
+        DisputeWindowOpen(i64),
+        #[error("Dispute window closed at {0}")]
+        DisputeWindowClosed(i64),
+    }
+
+    /// Payment receipt signed by the payer
+    ///
+    /// Each receipt supersedes the previous one: `amount` is the total paid
+    /// since the channel opened, for `chunks` verified chunks.
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    pub struct PaymentReceipt {
+        pub channel_id: ChannelId,
+        pub nonce: u64,
+        /// Cumulative amount, base units
+        pub amount: u128,
+        /// Cumulative verified chunks
+        pub chunks: u64,
+        pub signature: Vec<u8>,
+    }
+
+    impl PaymentReceipt {
+        /// Sign a receipt with the payer's key
+        pub fn sign(
+            key: &SigningKey,
+            channel_id: ChannelId,
+            nonce: u64,
+            amount: u128,
+            chunks: u64,
+        ) -> Self {
+            let message = Self::signing_message(&channel_id, nonce, amount, chunks);
+            Self {
+                channel_id,
+                nonce,
+                amount,
+                chunks,
+                signature: key.sign(&message).to_bytes().to_vec(),
+            }
+        }
+
+        /// Bytes covered by the signature
+        pub fn signing_message(
+            channel_id: &ChannelId,
+            nonce: u64,
+            amount: u128,
+            chunks: u64,
+        ) -> Vec<u8> {
+            let mut message = Vec::with_capacity(19 + 32 + 8 + 16 + 8);
+            message.extend_from_slice(b"RDP_CHANNEL_RECEIPT");
+            message.extend_from_slice(channel_id);
+            message.extend_from_slice(&nonce.to_le_bytes());
+            message.extend_from_slice(&amount.to_le_bytes());
+            message.extend_from_slice(&chunks.to_le_bytes());
+            message
+        }
+
+        /// Check the signature against the payer's public key
+        pub fn verify(&self, payer_key: &[u8; 32]) -> bool {
+            let Ok(key) = VerifyingKey::from_bytes(payer_key) else {
+                return false;
+            };
+            let Ok(signature) = Signature::from_slice(&self.signature) else {
+                return false;
+            };
+            let message =
+                Self::signing_message(&self.channel_id, self.nonce, self.amount, self.chunks);
+            key.verify(&message, &signature).is_ok()
+        }
+    }
+
+    /// Channel lifecycle
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub enum ChannelState {
+        Open,
+        /// Closing; newer receipts are accepted until the deadline
+        Closing {
+            deadline: i64,
+        },
+        Settled,
+    }
+
+    /// Payment channel from a leecher (payer) to a seeder (payee)
+    #[derive(Clone, Debug)]
+    pub struct PaymentChannel {
+        pub id: ChannelId,
+        pub payer: [u8; 32],
+        /// Ed25519 key receipts are checked against
+        pub payer_key: [u8; 32],
+        pub payee: [u8; 32],
+        /// Locked deposit, base units
+        pub deposit: u128,
+        /// Price per verified chunk, base units
+        pub price_per_chunk: u128,
+        pub opened_at: i64,
+        pub state: ChannelState,
+        /// Latest accepted receipt
+        pub latest: Option<PaymentReceipt>,
+    }
+
+    impl PaymentChannel {
+        /// Amount the latest receipt pays
+        pub fn paid(&self) -> u128 {
+            self.latest.as_ref().map_or(0, |r| r.amount)
+        }
+
+        /// Verified chunks paid for so far
+        pub fn chunks(&self) -> u64 {
+            self.latest.as_ref().map_or(0, |r| r.chunks)
+        }
+
+        /// Deposit not yet promised to the payee
+        pub fn remaining(&self) -> u128 {
+            self.deposit - self.paid()
+        }
+
+        /// Check a receipt and make it the latest, returning the newly paid amount
+        fn apply(&mut self, receipt: PaymentReceipt) -> Result<u128, ChannelError> {
+            if receipt.channel_id != self.id {
+                return Err(ChannelError::WrongChannel);
+            }
+            if !receipt.verify(&self.payer_key) {
+                return Err(ChannelError::InvalidSignature);
+            }
+            let last_nonce = self.latest.as_ref().map_or(0, |r| r.nonce);
+            if receipt.nonce <= last_nonce {
+                return Err(ChannelError::StaleReceipt(receipt.nonce, last_nonce));
+            }
+            if receipt.amount > self.deposit {
+                return Err(ChannelError::ExceedsDeposit);
+            }
+            if receipt.amount < self.paid() {
+                return Err(ChannelError::AmountDecreased);
+            }
+            // A price the chunk count overflows is more than any receipt pays
+            let underpaid = match self.price_per_chunk.checked_mul(receipt.chunks as u128) {
+                Some(owed) => receipt.amount < owed,
+                None => true,
+            };
+            if underpaid {
+                return Err(ChannelError::Underpaid {
+                    paid: receipt.amount,
+                    chunks: receipt.chunks,
+                });
+            }
+
+            let delta = receipt.amount - self.paid();
+            self.latest = Some(receipt);
+            Ok(delta)
+        }
+    }
+
+    /// Final split of a channel's deposit
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub struct Settlement {
+        pub channel_id: ChannelId,
+        pub payee_amount: u128,
+        pub refund: u128,
+    }
+
+    /// Channel event recorded as a string on the lattice
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    pub enum ChannelEvent {
+        Opened {
+            channel_id: ChannelId,
+            payer: [u8; 32],
+            payee: [u8; 32],
+            deposit: u128,
+            price_per_chunk: u128,
+        },
+        /// Close requested with the latest receipt the closer holds
+        Closing {
+            channel_id: ChannelId,
+            nonce: u64,
+            amount: u128,
+            deadline: i64,
+        },
+        /// Newer receipt submitted during the dispute window
+        Disputed {
+            channel_id: ChannelId,
+            nonce: u64,
+            amount: u128,
+        },
+        Settled(Settlement),
+    }
+
+    impl ChannelEvent {
+        pub fn channel_id(&self) -> ChannelId {
+            match self {
+                ChannelEvent::Opened { channel_id, .. }
+                | ChannelEvent::Closing { channel_id, .. }
+                | ChannelEvent::Disputed { channel_id, .. } => *channel_id,
+                ChannelEvent::Settled(settlement) => settlement.channel_id,
+            }
+        }
+
+        /// String content: tag, channel ID, then little-endian fields
+        pub fn payload(&self) -> Vec<u8> {
+            let mut payload = Vec::new();
+            match self {
+                ChannelEvent::Opened {
+                    channel_id,
+                    payer,
+                    payee,
+                    deposit,
+                    price_per_chunk,
+                } => {
+                    payload.extend_from_slice(b"RDP_CHANNEL_OPEN");
+                    payload.extend_from_slice(channel_id);
+                    payload.extend_from_slice(payer);
+                    payload.extend_from_slice(payee);
+                    payload.extend_from_slice(&deposit.to_le_bytes());
+                    payload.extend_from_slice(&price_per_chunk.to_le_bytes());
+                }
+                ChannelEvent::Closing {
+                    channel_id,
+                    nonce,
+                    amount,
+                    deadline,
+                } => {
+                    payload.extend_from_slice(b"RDP_CHANNEL_CLOSE");
+                    payload.extend_from_slice(channel_id);
+                    payload.extend_from_slice(&nonce.to_le_bytes());
+                    payload.extend_from_slice(&amount.to_le_bytes());
+                    payload.extend_from_slice(&deadline.to_le_bytes());
+                }
+                ChannelEvent::Disputed {
+                    channel_id,
+                    nonce,
+                    amount,
+                } => {
+                    payload.extend_from_slice(b"RDP_CHANNEL_DISPUTE");
+                    payload.extend_from_slice(channel_id);
+                    payload.extend_from_slice(&nonce.to_le_bytes());
+                    payload.extend_from_slice(&amount.to_le_bytes());
+                }
+                ChannelEvent::Settled(settlement) => {
+                    payload.extend_from_slice(b"RDP_CHANNEL_SETTLE");
+                    payload.extend_from_slice(&settlement.channel_id);
+                    payload.extend_from_slice(&settlement.payee_amount.to_le_bytes());
+                    payload.extend_from_slice(&settlement.refund.to_le_bytes());
+                }
+            }
+            payload
+        }
+
+        /// Immutable string for this event
+        pub fn to_rope_string(
+            &self,
+            creator: PublicKey,
+            clock: LamportClock,
+        ) -> Result<RopeString, &'static str> {
+            RopeString::builder()
+                .content(self.payload())
+                .temporal_marker(clock)
+                .mutability_class(MutabilityClass::Immutable)
+                .creator(creator)
+                .build()
+        }
+    }
+
+    /// Channels this node is party to
+    pub struct ChannelManager {
+        channels: HashMap<ChannelId, PaymentChannel>,
+        dispute_period_secs: u64,
+        /// Events not yet written to the lattice
+        pending: Vec<ChannelEvent>,
+    }
+
+    impl ChannelManager {
+        pub fn new(dispute_period_secs: u64) -> Self {
+            Self {
+                channels: HashMap::new(),
+                dispute_period_secs,
+                pending: Vec::new(),
+            }
+        }
+
+        /// Channel ID for a payer, payee and open time
+        pub fn channel_id(payer: &[u8; 32], payee: &[u8; 32], opened_at: i64) -> ChannelId {
+            let mut hasher = blake3::Hasher::new();
+            hasher.update(payer);
+            hasher.update(payee);
+            hasher.update(&opened_at.to_le_bytes());
+            *hasher.finalize().as_bytes()
+        }
+
+        /// Open a channel with `deposit` locked by the payer
+        pub fn open(
+            &mut self,
+            payer: [u8; 32],
+            payer_key: [u8; 32],
+            payee: [u8; 32],
+            deposit: u128,
+            price_per_chunk: u128,
+            now: i64,
+        ) -> Result<ChannelId, ChannelError> {
+            if deposit == 0 {
+                return Err(ChannelError::EmptyDeposit);
+            }
+            let id = Self::channel_id(&payer, &payee, now);
+            if self.channels.contains_key(&id) {
+                return Err(ChannelError::AlreadyExists);
+            }
+
+            self.channels.insert(
+                id,
+                PaymentChannel {
+                    id,
+                    payer,
+                    payer_key,
+                    payee,
+                    deposit,
+                    price_per_chunk,
+                    opened_at: now,
+                    state: ChannelState::Open,
+                    latest: None,
+                },
+            );
+            self.pending.push(ChannelEvent::Opened {
+                channel_id: id,
+                payer,
+                payee,
+                deposit,
+                price_per_chunk,
+            });
+            Ok(id)
+        }
+
+        /// Accept a receipt for verified chunks, returning the newly paid amount
+        pub fn receive(&mut self, receipt: PaymentReceipt) -> Result<u128, ChannelError> {
+            let channel = self
+                .channels
+                .get_mut(&receipt.channel_id)
+                .ok_or(ChannelError::NotFound)?;
+            if channel.state != ChannelState::Open {
+                return Err(ChannelError::NotOpen);
+            }
+            channel.apply(receipt)
+        }
+
+        /// Start closing, optionally with a newer receipt than the latest
+        pub fn close(
+            &mut self,
+            channel_id: &ChannelId,
+            receipt: Option<PaymentReceipt>,
+            now: i64,
+        ) -> Result<i64, ChannelError> {
+            let channel = self
+                .channels
+                .get_mut(channel_id)
+                .ok_or(ChannelError::NotFound)?;
+            if channel.state != ChannelState::Open {
+                return Err(ChannelError::NotOpen);
+            }
+            if let Some(receipt) = receipt {
+                channel.apply(receipt)?;
+            }
+
+            let deadline = now.saturating_add(self.dispute_period_secs as i64);
+            channel.state = ChannelState::Closing { deadline };
+            self.pending.push(ChannelEvent::Closing {
+                channel_id: *channel_id,
+                nonce: channel.latest.as_ref().map_or(0, |r| r.nonce),
+                amount: channel.paid(),
+                deadline,
+            });
+            Ok(deadline)
+        }
+
+        /// Submit a newer receipt while the channel is closing
+        pub fn dispute(&mut self, receipt: PaymentReceipt, now: i64) -> Result<u128, ChannelError> {
+            let channel = self
+                .channels
+                .get_mut(&receipt.channel_id)
+                .ok_or(ChannelError::NotFound)?;
+            let ChannelState::Closing { deadline } = channel.state else {
+                return Err(ChannelError::NotClosing);
+            };
+            if now >= deadline {
+                return Err(ChannelError::DisputeWindowClosed(deadline));
+            }
+
+            let delta = channel.apply(receipt)?;
+            self.pending.push(ChannelEvent::Disputed {
+                channel_id: channel.id,
+                nonce: channel.latest.as_ref().map_or(0, |r| r.nonce),
+                amount: channel.paid(),
+            });
+            Ok(delta)
+        }
+
+        /// Settle a channel once its dispute window has passed
+        pub fn settle(
+            &mut self,
+            channel_id: &ChannelId,
+            now: i64,
+        ) -> Result<Settlement, ChannelError> {
+            let channel = self
+                .channels
+                .get_mut(channel_id)
+                .ok_or(ChannelError::NotFound)?;
+            let ChannelState::Closing { deadline } = channel.state else {
+                return Err(ChannelError::NotClosing);
+            };
+            if now < deadline {
+                return Err(ChannelError::DisputeWindowOpen(deadline));
+            }
+
+            channel.state = ChannelState::Settled;
+            let settlement = Settlement {
+                channel_id: *channel_id,
+                payee_amount: channel.paid(),
+                refund: channel.remaining(),
+            };
+            self.pending.push(ChannelEvent::Settled(settlement));
+            Ok(settlement)
+        }
+
+        /// Settle every channel whose dispute window has passed
+        pub fn settle_expired(&mut self, now: i64) -> Vec<Settlement> {
+            let expired: Vec<ChannelId> = self
+                .channels
+                .values()
+                .filter(
+                    |c| matches!(c.state, ChannelState::Closing { deadline } if now >= deadline),
+                )
+                .map(|c| c.id)
+                .collect();
+            expired
+                .iter()
+                .filter_map(|id| self.settle(id, now).ok())
+                .collect()
+        }
+
+        pub fn get(&self, channel_id: &ChannelId) -> Option<&PaymentChannel> {
+            self.channels.get(channel_id)
+        }
+
+        /// Open channels paying `payee`
+        pub fn open_channels_to(&self, payee: &[u8; 32]) -> Vec<&PaymentChannel> {
+            self.channels
+                .values()
+                .filter(|c| c.payee == *payee && c.state == ChannelState::Open)
+                .collect()
+        }
+
+        /// Drain events waiting to be written to the lattice
+        pub fn take_events(&mut self) -> Vec<ChannelEvent> {
+            std::mem::take(&mut self.pending)
+        }
+    }
+
+    impl Default for ChannelManager {
+        fn default() -> Self {
+            Self::new(DEFAULT_DISPUTE_PERIOD_SECS)
+        }
+    }
 }
 
 // Re-exports
+pub use channels::{
+    ChannelError, ChannelEvent, ChannelId, ChannelManager, ChannelState, PaymentChannel,
+    PaymentReceipt, Settlement,
+};
 pub use dht::{DhtEntry, DhtStore};
-pub use incentives::{calculate_reward, IncentiveParams, NodeContribution};
+pub use incentives::{calculate_reward, IncentiveLedger, IncentiveParams, NodeContribution};
 pub use rdp::{RdpChunk, RdpTransfer};
 pub use swarm::{Swarm, SwarmMember};
 
@@ -522,5 +1101,156 @@ mod tests {
             let reward = calculate_reward(&params, &contrib);
             assert!(reward > 100); // Should be more than base reward
         }
+
+        #[test]
+        fn test_ledger_credits_measured_uploads() {
+            let params = IncentiveParams::default();
+            let mut ledger = IncentiveLedger::new();
+
+            // Two receivers measured uploads from the same seeder
+            ledger.record_measured_uploads(vec![([1u8; 32], 600_000), ([2u8; 32], 10_000)]);
+            ledger.record_measured_uploads(vec![([1u8; 32], 400_000)]);
+            ledger.record_regeneration([3u8; 32]);
+
+            assert_eq!(
+                ledger.contribution(&[1u8; 32]).unwrap().bytes_uploaded,
+                1_000_000
+            );
+
+            let rewards = ledger.close_epoch(&params);
+            assert_eq!(rewards.len(), 3);
+            assert_eq!(rewards[0].0, [1u8; 32]);
+            assert!(ledger.contribution(&[1u8; 32]).is_none());
+        }
+    }
+
+    mod channel_tests {
+        use super::*;
+        use ed25519_dalek::SigningKey;
+
+        const PAYER: [u8; 32] = [1u8; 32];
+        const PAYEE: [u8; 32] = [2u8; 32];
+
+        fn open(manager: &mut ChannelManager, key: &SigningKey) -> ChannelId {
+            manager
+                .open(PAYER, key.verifying_key().to_bytes(), PAYEE, 1_000, 10, 100)
+                .unwrap()
+        }
+
+        #[test]
+        fn test_receipts_stream_payments() {
+            let key = SigningKey::from_bytes(&[7u8; 32]);
+            let mut manager = ChannelManager::new(60);
+            let id = open(&mut manager, &key);
+
+            for chunk in 1..=5u64 {
+                let receipt = PaymentReceipt::sign(&key, id, chunk, chunk as u128 * 10, chunk);
+                assert_eq!(manager.receive(receipt).unwrap(), 10);
+            }
+            assert_eq!(manager.get(&id).unwrap().paid(), 50);
+            assert_eq!(manager.get(&id).unwrap().chunks(), 5);
+
+            // Replayed, underpaying, over-deposit and forged receipts are rejected
+            let stale = PaymentReceipt::sign(&key, id, 5, 60, 6);
+            assert_eq!(
+                manager.receive(stale),
+                Err(ChannelError::StaleReceipt(5, 5))
+            );
+            let underpaid = PaymentReceipt::sign(&key, id, 6, 55, 6);
+            assert!(matches!(
+                manager.receive(underpaid),
+                Err(ChannelError::Underpaid { .. })
+            ));
+            let too_much = PaymentReceipt::sign(&key, id, 6, 2_000, 6);
+            assert_eq!(manager.receive(too_much), Err(ChannelError::ExceedsDeposit));
+            let forged = PaymentReceipt::sign(&SigningKey::from_bytes(&[8u8; 32]), id, 6, 60, 6);
+            assert_eq!(manager.receive(forged), Err(ChannelError::InvalidSignature));
+        }
+
+        #[test]
+        fn test_overflowing_price_rejected() {
+            let key = SigningKey::from_bytes(&[7u8; 32]);
+            let mut manager = ChannelManager::new(60);
+            let id = manager
+                .open(
+                    PAYER,
+                    key.verifying_key().to_bytes(),
+                    PAYEE,
+                    u128::MAX,
+                    u128::MAX / 2,
+                    100,
+                )
+                .unwrap();
+
+            // Three chunks cost more than a u128 holds
+            let receipt = PaymentReceipt::sign(&key, id, 1, u128::MAX, 3);
+            assert_eq!(
+                manager.receive(receipt),
+                Err(ChannelError::Underpaid {
+                    paid: u128::MAX,
+                    chunks: 3
+                })
+            );
+            assert_eq!(manager.get(&id).unwrap().paid(), 0);
+        }
+
+        #[test]
+        fn test_dispute_and_settle() {
+            let key = SigningKey::from_bytes(&[7u8; 32]);
+            let mut manager = ChannelManager::new(60);
+            let id = open(&mut manager, &key);
+
+            let early = PaymentReceipt::sign(&key, id, 1, 10, 1);
+            let late = PaymentReceipt::sign(&key, id, 3, 30, 3);
+
+            // Payer closes with a stale receipt, the payee disputes with the newest
+            let deadline = manager.close(&id, Some(early), 200).unwrap();
+            assert_eq!(deadline, 260);
+            assert_eq!(
+                manager.settle(&id, 230),
+                Err(ChannelError::DisputeWindowOpen(260))
+            );
+            assert_eq!(manager.dispute(late, 230).unwrap(), 20);
+
+            let settlement = manager.settle(&id, 260).unwrap();
+            assert_eq!(settlement.payee_amount, 30);
+            assert_eq!(settlement.refund, 970);
+            assert_eq!(manager.get(&id).unwrap().state, ChannelState::Settled);
+
+            let late_dispute = PaymentReceipt::sign(&key, id, 4, 40, 4);
+            assert_eq!(
+                manager.dispute(late_dispute, 300),
+                Err(ChannelError::NotClosing)
+            );
+        }
+
+        #[test]
+        fn test_channel_events_become_strings() {
+            let key = SigningKey::from_bytes(&[7u8; 32]);
+            let mut manager = ChannelManager::new(0);
+            let id = open(&mut manager, &key);
+            manager.close(&id, None, 200).unwrap();
+            assert_eq!(manager.settle_expired(200).len(), 1);
+
+            let events = manager.take_events();
+            assert_eq!(events.len(), 3);
+            assert!(events.iter().all(|e| e.channel_id() == id));
+            assert!(events[0].payload().starts_with(b"RDP_CHANNEL_OPEN"));
+            assert!(matches!(
+                events[2],
+                ChannelEvent::Settled(Settlement { refund: 1_000, .. })
+            ));
+
+            let node = rope_core::types::NodeId::new(PAYER);
+            let string = events[0]
+                .to_rope_string(
+                    rope_core::string::PublicKey::from_ed25519(PAYER),
+                    rope_core::clock::LamportClock::new(node),
+                )
+                .unwrap();
+            // The sequence pads the payload to whole nucleotides
+            assert!(string.content().starts_with(&events[0].payload()));
+            assert!(manager.take_events().is_empty());
+        }
     }
 }
@@ -21,9 +21,38 @@
 //! - Year 10 (2035): 13.5B FAT  
 //! - Year 20 (2045): 14.5B FAT
 //! - Asymptotic Max: ~18B FAT
+//!
+//! ## Accrual
+//!
+//! Emission accrues continuously at the era's annual rate, so the supply
+//! at any instant is genesis plus the integral of that rate since
+//! genesis. Anchor producers mint [`EmissionSchedule::reward_at`] for the
+//! anchor's timestamp; [`EmissionSchedule::record_mint`] refuses to mint
+//! ahead of what has accrued.
 
 use crate::constants::*;
 use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Emission errors
+#[derive(Clone, Debug, PartialEq, Eq, Error)]
+pub enum EmissionError {
+    #[error("Mint of {requested} exceeds the {available} accrued by the schedule")]
+    ExceedsSchedule { requested: u128, available: u128 },
+}
+
+/// Supply at one point of a projection
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SupplyPoint {
+    pub timestamp: i64,
+    pub era: u64,
+    /// Genesis supply plus everything emitted up to `timestamp`
+    pub total_supply: u128,
+    pub annual_emission: u128,
+    pub anchor_reward: u128,
+    /// Annual emission over total supply, percent
+    pub inflation_percent: f64,
+}
 
 /// Emission era information
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -135,10 +164,23 @@ impl EmissionSchedule {
         elapsed / self.halving_interval
     }
 
+    /// Start timestamp of an era
+    pub fn era_start(&self, era: u64) -> i64 {
+        self.genesis_time
+            .saturating_add((era as i64).saturating_mul(self.halving_interval as i64))
+    }
+
+    /// First era whose halved emission is at or below the floor
+    pub fn floor_era(&self) -> u64 {
+        (0..u128::BITS as u64)
+            .find(|era| self.initial_emission >> era <= self.minimum_emission)
+            .unwrap_or(u128::BITS as u64)
+    }
+
     /// Get era information
     pub fn get_era_info(&self, era: u64) -> EmissionEra {
-        let start_time = self.genesis_time + (era * self.halving_interval) as i64;
-        let end_time = start_time + self.halving_interval as i64;
+        let start_time = self.era_start(era);
+        let end_time = self.era_start(era + 1);
         let annual_emission = self.annual_emission_for_era(era);
         let anchor_reward = self.anchor_reward_for_era(era);
 
@@ -153,8 +195,12 @@ impl EmissionSchedule {
 
     /// Get annual emission for a specific era (with halving)
     pub fn annual_emission_for_era(&self, era: u64) -> u128 {
-        // Halving: divide by 2^era
-        let emission = self.initial_emission >> era;
+        // Halving: divide by 2^era, never below the floor
+        let emission = if era < u128::BITS as u64 {
+            self.initial_emission >> era
+        } else {
+            0
+        };
         emission.max(self.minimum_emission)
     }
 
@@ -176,52 +222,86 @@ impl EmissionSchedule {
         self.anchor_reward_for_era(era)
     }
 
+    /// Reward minted by an anchor produced at `timestamp`; nothing is
+    /// emitted before genesis
+    pub fn reward_at(&self, timestamp: i64) -> u128 {
+        if timestamp < self.genesis_time {
+            return 0;
+        }
+        self.current_anchor_reward(timestamp)
+    }
+
+    /// Distribution of [`reward_at`](Self::reward_at)
+    pub fn anchor_reward_at(&self, timestamp: i64) -> AnchorReward {
+        AnchorReward::from_total(self.reward_at(timestamp))
+    }
+
     /// Get full anchor reward distribution for current era
     pub fn get_anchor_reward_distribution(&self, timestamp: i64) -> AnchorReward {
         let total = self.current_anchor_reward(timestamp);
         AnchorReward::from_total(total)
     }
 
-    /// Calculate total supply at a given timestamp
-    pub fn total_supply_at(&self, timestamp: i64) -> u128 {
-        if timestamp < self.genesis_time {
-            return GENESIS_SUPPLY;
+    /// Emission accrued between two timestamps (clamped to genesis)
+    pub fn emitted_between(&self, from: i64, to: i64) -> u128 {
+        let floor_start = self.era_start(self.floor_era());
+        let mut start = from.max(self.genesis_time);
+        let mut total = 0u128;
+        while start < to {
+            let era = self.get_era(start);
+            // Every era from the floor on emits the same; take them in one step
+            let end = if start >= floor_start {
+                to
+            } else {
+                self.era_start(era + 1).min(to)
+            };
+            total += self.annual_emission_for_era(era) * (end - start) as u128
+                / SECONDS_PER_YEAR as u128;
+            start = end;
         }
+        total
+    }
 
-        let mut total = GENESIS_SUPPLY;
-        let mut current_time = self.genesis_time;
+    /// Calculate total supply at a given timestamp
+    pub fn total_supply_at(&self, timestamp: i64) -> u128 {
+        GENESIS_SUPPLY + self.emitted_between(self.genesis_time, timestamp)
+    }
 
+    /// Supply every `step` seconds from `from` through `to` (inclusive)
+    pub fn project_supply(&self, from: i64, to: i64, step: u64) -> Vec<SupplyPoint> {
+        if step == 0 || to < from {
+            return Vec::new();
+        }
+        let mut points = Vec::new();
+        let mut timestamp = from;
         loop {
-            let era = self.get_era(current_time);
-            let era_end = self.genesis_time + ((era + 1) * self.halving_interval) as i64;
-            let annual_emission = self.annual_emission_for_era(era);
-
-            if timestamp >= era_end {
-                // Full era emission
-                total += annual_emission * 4; // 4 years per era
-                current_time = era_end;
-            } else {
-                // Partial era
-                let seconds_in_era = (timestamp - current_time) as u128;
-                let seconds_per_year = 365 * 24 * 3600u128;
-                let partial_emission = annual_emission * seconds_in_era / seconds_per_year;
-                total += partial_emission;
-                break;
-            }
-
-            // Safety: prevent infinite loop
-            if era > 50 {
+            points.push(self.supply_at(timestamp));
+            if timestamp == to {
                 break;
             }
+            timestamp = timestamp.saturating_add(step as i64).min(to);
         }
+        points
+    }
 
-        total
+    /// Supply, emission and inflation at `timestamp`
+    pub fn supply_at(&self, timestamp: i64) -> SupplyPoint {
+        let annual_emission = self.current_annual_emission(timestamp);
+        let total_supply = self.total_supply_at(timestamp);
+        SupplyPoint {
+            timestamp,
+            era: self.get_era(timestamp),
+            total_supply,
+            annual_emission,
+            anchor_reward: self.reward_at(timestamp),
+            inflation_percent: annual_emission as f64 / total_supply as f64 * 100.0,
+        }
     }
 
     /// Project total supply for major milestones
     pub fn supply_projections(&self) -> Vec<(i64, u128, &'static str)> {
         let genesis = self.genesis_time;
-        let year = 365 * 24 * 3600i64;
+        let year = SECONDS_PER_YEAR as i64;
 
         vec![
             (genesis, GENESIS_SUPPLY, "Genesis (2026)"),
@@ -286,16 +366,30 @@ impl EmissionSchedule {
         total
     }
 
-    /// Record minted tokens
-    pub fn record_mint(&mut self, amount: u128, timestamp: i64) {
+    /// Amount that may still be minted at `timestamp`
+    pub fn mintable_at(&self, timestamp: i64) -> u128 {
+        self.emitted_between(self.genesis_time, timestamp)
+            .saturating_sub(self.total_minted)
+    }
+
+    /// Record minted tokens, refusing to run ahead of the schedule
+    pub fn record_mint(&mut self, amount: u128, timestamp: i64) -> Result<(), EmissionError> {
+        let available = self.mintable_at(timestamp);
+        if amount > available {
+            return Err(EmissionError::ExceedsSchedule {
+                requested: amount,
+                available,
+            });
+        }
         self.total_minted += amount;
         self.current_era = self.get_era(timestamp);
+        Ok(())
     }
 
     /// Get emission rate per second for current era
     pub fn emission_rate_per_second(&self, timestamp: i64) -> u128 {
         let annual = self.current_annual_emission(timestamp);
-        annual / (365 * 24 * 3600)
+        annual / SECONDS_PER_YEAR as u128
     }
 
     /// Get inflation rate for current era
@@ -375,6 +469,102 @@ mod tests {
         assert_eq!(schedule.total_supply_at(1000), GENESIS_SUPPLY);
     }
 
+    #[test]
+    fn test_supply_accrues_across_eras() {
+        let schedule = EmissionSchedule::new(0);
+        let era = HALVING_INTERVAL_SECS as i64;
+
+        // Four years at 500M, then half a year at 250M
+        let expected = GENESIS_SUPPLY + 2_000_000_000 * ONE_FAT + 125_000_000 * ONE_FAT;
+        let half_year = SECONDS_PER_YEAR as i64 / 2;
+        assert_eq!(schedule.total_supply_at(era + half_year), expected);
+
+        // Split ranges add up to the whole, up to rounding
+        let mid = era - 1_000;
+        let whole = schedule.emitted_between(0, era + half_year);
+        let split =
+            schedule.emitted_between(0, mid) + schedule.emitted_between(mid, era + half_year);
+        assert!(whole - split <= 1);
+        assert_eq!(schedule.emitted_between(-5_000, 0), 0);
+    }
+
+    #[test]
+    fn test_minimum_emission_floor() {
+        let schedule = EmissionSchedule::new(0);
+
+        // 500M / 2^9 < 1M
+        assert_eq!(schedule.floor_era(), 9);
+        assert_eq!(schedule.annual_emission_for_era(9), MINIMUM_ANNUAL_EMISSION);
+        assert_eq!(
+            schedule.annual_emission_for_era(200),
+            MINIMUM_ANNUAL_EMISSION
+        );
+
+        // Far past the floor the supply keeps growing at the floor rate
+        let start = schedule.era_start(20);
+        let year = SECONDS_PER_YEAR as i64;
+        assert_eq!(
+            schedule.total_supply_at(start + year) - schedule.total_supply_at(start),
+            MINIMUM_ANNUAL_EMISSION
+        );
+    }
+
+    #[test]
+    fn test_reward_at() {
+        let schedule = EmissionSchedule::new(1_000);
+
+        assert_eq!(schedule.reward_at(999), 0);
+        assert_eq!(schedule.reward_at(1_000), schedule.anchor_reward_for_era(0));
+        let second_era = schedule.era_start(1);
+        assert_eq!(
+            schedule.reward_at(second_era),
+            schedule.anchor_reward_for_era(0) / 2
+        );
+        assert!(schedule.anchor_reward_at(second_era).verify());
+    }
+
+    #[test]
+    fn test_project_supply() {
+        let schedule = EmissionSchedule::new(0);
+        let year = SECONDS_PER_YEAR as i64;
+
+        let points = schedule.project_supply(0, 5 * year + 10, year as u64);
+        assert_eq!(points.len(), 7);
+        assert_eq!(points[0].total_supply, GENESIS_SUPPLY);
+        assert_eq!(points[6].timestamp, 5 * year + 10);
+        for point in &points {
+            assert_eq!(
+                point.total_supply,
+                schedule.total_supply_at(point.timestamp)
+            );
+        }
+        assert_eq!(points[5].era, 1);
+        assert!(points[5].inflation_percent < points[0].inflation_percent);
+
+        assert!(schedule.project_supply(10, 0, year as u64).is_empty());
+        assert!(schedule.project_supply(0, year, 0).is_empty());
+    }
+
+    #[test]
+    fn test_record_mint_follows_schedule() {
+        let mut schedule = EmissionSchedule::new(0);
+        let reward = schedule.reward_at(0);
+
+        // Nothing has accrued at genesis
+        assert!(matches!(
+            schedule.record_mint(reward, 0),
+            Err(EmissionError::ExceedsSchedule { .. })
+        ));
+
+        let later = 60;
+        schedule.record_mint(reward, later).unwrap();
+        assert_eq!(schedule.total_minted, reward);
+        assert_eq!(
+            schedule.mintable_at(later),
+            schedule.emitted_between(0, later) - reward
+        );
+    }
+
     #[test]
     fn test_inflation_rate() {
         let schedule = EmissionSchedule::new(0);
@@ -0,0 +1,454 @@
+//! # Fee Market
+//!
+//! EIP-1559 style transaction fees.
+//!
+//! Each anchor has a base fee per gas. After every anchor it moves by up to
+//! 1/8 towards the gas target: up when the anchor used more gas than the
+//! target, down when it used less, and never below the floor.
+//!
+//! A transaction pays `min(max_fee, base_fee + max_priority_fee)` per gas,
+//! split as follows:
+//!
+//! | Part | Recipient |
+//! |------|-----------|
+//! | Base fee × burn percentage | Burned |
+//! | Rest of the base fee | Validator reward pool |
+//! | Priority fee (tip) | Anchor producer |
+
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use thiserror::Error;
+
+/// One gwei in the smallest unit
+pub const GWEI: u128 = 1_000_000_000;
+
+/// Fee market parameters
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct FeeConfig {
+    /// Base fee of the first anchor
+    pub initial_base_fee: u128,
+    /// Base fee floor
+    pub min_base_fee: u128,
+    /// Gas per anchor the base fee steers towards
+    pub gas_target: u64,
+    /// Maximum gas per anchor
+    pub gas_limit: u64,
+    /// Maximum base fee change per anchor is `1 / change_denominator`
+    pub change_denominator: u128,
+    /// Share of the base fee burned (0-100)
+    pub burn_percent: u8,
+    /// Priority fee suggested when there is no fee history
+    pub default_priority_fee: u128,
+    /// Anchors kept for the fee oracle
+    pub history_len: usize,
+}
+
+impl Default for FeeConfig {
+    fn default() -> Self {
+        Self {
+            initial_base_fee: GWEI,
+            min_base_fee: GWEI / 10,
+            gas_target: 15_000_000,
+            gas_limit: 30_000_000,
+            change_denominator: 8,
+            burn_percent: 80,
+            default_priority_fee: GWEI / 1_000,
+            history_len: 20,
+        }
+    }
+}
+
+/// Fee errors
+#[derive(Clone, Debug, PartialEq, Eq, Error)]
+pub enum FeeError {
+    #[error("Max fee {max_fee} below base fee {base_fee}")]
+    FeeTooLow { max_fee: u128, base_fee: u128 },
+
+    #[error("Gas {requested} exceeds the {available} left in the anchor")]
+    GasLimitExceeded { requested: u64, available: u64 },
+}
+
+/// Where one transaction's fee goes
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FeeSplit {
+    pub burned: u128,
+    pub validator_pool: u128,
+    /// Paid to the anchor producer
+    pub tip: u128,
+}
+
+impl FeeSplit {
+    /// Total paid by the sender
+    pub fn total(&self) -> u128 {
+        self.burned + self.validator_pool + self.tip
+    }
+}
+
+/// Fees collected by one anchor
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AnchorFees {
+    pub round: u64,
+    /// Base fee the anchor's transactions paid
+    pub base_fee: u128,
+    pub gas_used: u64,
+    pub gas_limit: u64,
+    pub burned: u128,
+    pub validator_pool: u128,
+    /// Paid to the anchor producer
+    pub tips: u128,
+    /// 10th, 50th and 90th percentile priority fee per gas, if any
+    /// transactions were included
+    pub priority_fees: Option<[u128; 3]>,
+}
+
+impl AnchorFees {
+    /// Share of the gas limit used
+    pub fn gas_used_ratio(&self) -> f64 {
+        if self.gas_limit == 0 {
+            return 0.0;
+        }
+        self.gas_used as f64 / self.gas_limit as f64
+    }
+}
+
+/// Gas price suggestion from recent anchors
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct FeeSuggestion {
+    /// Base fee of the next anchor
+    pub base_fee: u128,
+    /// Priority fees per gas
+    pub slow: u128,
+    pub standard: u128,
+    pub fast: u128,
+    pub instant: u128,
+    /// Gas used ratio of recent anchors, oldest first
+    pub gas_used_ratio: Vec<f64>,
+}
+
+impl FeeSuggestion {
+    /// Max fee per gas for a priority fee, with room for the base fee to
+    /// double before inclusion
+    pub fn max_fee(&self, priority_fee: u128) -> u128 {
+        self.base_fee * 2 + priority_fee
+    }
+}
+
+/// Base fee following an anchor that used `gas_used`
+pub fn next_base_fee(config: &FeeConfig, base_fee: u128, gas_used: u64) -> u128 {
+    let target = config.gas_target as u128;
+    let used = gas_used as u128;
+    if target == 0 || config.change_denominator == 0 {
+        return base_fee.max(config.min_base_fee);
+    }
+
+    let next = if used > target {
+        let delta = base_fee * (used - target) / target / config.change_denominator;
+        base_fee + delta.max(1)
+    } else {
+        let delta = base_fee * (target - used) / target / config.change_denominator;
+        base_fee - delta
+    };
+    next.max(config.min_base_fee)
+}
+
+/// Fee market state
+#[derive(Clone, Debug)]
+pub struct FeeMarket {
+    /// Parameters
+    pub config: FeeConfig,
+
+    /// Base fee of the anchor being filled
+    base_fee: u128,
+
+    /// Anchor being filled
+    pending: AnchorFees,
+
+    /// Priority fees per gas paid in the anchor being filled
+    pending_priority_fees: Vec<u128>,
+
+    /// Closed anchors, oldest first
+    history: VecDeque<AnchorFees>,
+
+    /// Total burned
+    pub total_burned: u128,
+
+    /// Total paid to anchor producers
+    pub total_tips: u128,
+}
+
+impl Default for FeeMarket {
+    fn default() -> Self {
+        Self::new(FeeConfig::default())
+    }
+}
+
+impl FeeMarket {
+    /// Create a fee market starting at the configured base fee
+    pub fn new(config: FeeConfig) -> Self {
+        let base_fee = config.initial_base_fee.max(config.min_base_fee);
+        Self {
+            config,
+            base_fee,
+            pending: AnchorFees::default(),
+            pending_priority_fees: Vec::new(),
+            history: VecDeque::new(),
+            total_burned: 0,
+            total_tips: 0,
+        }
+    }
+
+    /// Base fee of the anchor being filled
+    pub fn base_fee(&self) -> u128 {
+        self.base_fee
+    }
+
+    /// Gas left in the anchor being filled
+    pub fn gas_available(&self) -> u64 {
+        self.config.gas_limit.saturating_sub(self.pending.gas_used)
+    }
+
+    /// Charge a transaction included in the anchor being filled
+    pub fn charge(
+        &mut self,
+        gas_used: u64,
+        max_fee_per_gas: u128,
+        max_priority_fee_per_gas: u128,
+    ) -> Result<FeeSplit, FeeError> {
+        if max_fee_per_gas < self.base_fee {
+            return Err(FeeError::FeeTooLow {
+                max_fee: max_fee_per_gas,
+                base_fee: self.base_fee,
+            });
+        }
+        let available = self.gas_available();
+        if gas_used > available {
+            return Err(FeeError::GasLimitExceeded {
+                requested: gas_used,
+                available,
+            });
+        }
+
+        let priority_fee = max_priority_fee_per_gas.min(max_fee_per_gas - self.base_fee);
+        Ok(self.settle(gas_used, priority_fee))
+    }
+
+    /// Record a transaction already charged elsewhere at an effective
+    /// price per gas, for observers following the chain
+    pub fn record_transaction(&mut self, gas_used: u64, gas_price: u128) -> FeeSplit {
+        let priority_fee = gas_price.saturating_sub(self.base_fee);
+        self.settle(gas_used, priority_fee)
+    }
+
+    /// Close the anchor being filled and adjust the base fee for the next
+    pub fn close_anchor(&mut self, round: u64) -> AnchorFees {
+        let mut fees = std::mem::take(&mut self.pending);
+        fees.round = round;
+        fees.base_fee = self.base_fee;
+        fees.gas_limit = self.config.gas_limit;
+        fees.priority_fees = percentiles(std::mem::take(&mut self.pending_priority_fees));
+
+        self.base_fee = next_base_fee(&self.config, self.base_fee, fees.gas_used);
+        self.history.push_back(fees.clone());
+        while self.history.len() > self.config.history_len {
+            self.history.pop_front();
+        }
+        fees
+    }
+
+    /// Closed anchors, oldest first
+    pub fn history(&self) -> impl Iterator<Item = &AnchorFees> {
+        self.history.iter()
+    }
+
+    /// Suggest priority fees from recent anchors
+    pub fn suggest(&self) -> FeeSuggestion {
+        let paid: Vec<[u128; 3]> = self
+            .history
+            .iter()
+            .filter_map(|a| a.priority_fees)
+            .collect();
+        let (slow, standard, fast, instant) = if paid.is_empty() {
+            let default = self.config.default_priority_fee;
+            (default / 2, default, default * 2, default * 5)
+        } else {
+            let average = |i: usize| paid.iter().map(|p| p[i]).sum::<u128>() / paid.len() as u128;
+            let fast = average(2);
+            let instant = paid.iter().map(|p| p[2]).max().unwrap_or(fast);
+            (average(0), average(1), fast, instant)
+        };
+
+        FeeSuggestion {
+            base_fee: self.base_fee,
+            slow,
+            standard,
+            fast,
+            instant,
+            gas_used_ratio: self
+                .history
+                .iter()
+                .map(AnchorFees::gas_used_ratio)
+                .collect(),
+        }
+    }
+
+    fn settle(&mut self, gas_used: u64, priority_fee: u128) -> FeeSplit {
+        let gas = gas_used as u128;
+        let base = self.base_fee * gas;
+        let burned = base * self.config.burn_percent.min(100) as u128 / 100;
+        let split = FeeSplit {
+            burned,
+            validator_pool: base - burned,
+            tip: priority_fee * gas,
+        };
+
+        self.pending.gas_used += gas_used;
+        self.pending.burned += split.burned;
+        self.pending.validator_pool += split.validator_pool;
+        self.pending.tips += split.tip;
+        self.pending_priority_fees.push(priority_fee);
+        self.total_burned += split.burned;
+        self.total_tips += split.tip;
+        split
+    }
+}
+
+/// 10th, 50th and 90th percentiles
+fn percentiles(mut values: Vec<u128>) -> Option<[u128; 3]> {
+    if values.is_empty() {
+        return None;
+    }
+    values.sort_unstable();
+    let at = |p: usize| values[(values.len() - 1) * p / 100];
+    Some([at(10), at(50), at(90)])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_base_fee_adjustment() {
+        let config = FeeConfig::default();
+
+        // At target: unchanged
+        assert_eq!(next_base_fee(&config, GWEI, 15_000_000), GWEI);
+        // Full anchor: +12.5%
+        assert_eq!(next_base_fee(&config, GWEI, 30_000_000), GWEI * 9 / 8);
+        // Empty anchor: -12.5%
+        assert_eq!(next_base_fee(&config, GWEI, 0), GWEI * 7 / 8);
+        // Floor
+        assert_eq!(
+            next_base_fee(&config, config.min_base_fee, 0),
+            config.min_base_fee
+        );
+        // Always moves up when over target
+        let unfloored = FeeConfig {
+            min_base_fee: 0,
+            ..Default::default()
+        };
+        assert_eq!(next_base_fee(&unfloored, 1, 15_000_001), 2);
+    }
+
+    #[test]
+    fn test_charge_splits_fee() {
+        let mut market = FeeMarket::default();
+
+        let split = market.charge(21_000, 3 * GWEI, GWEI / 2).unwrap();
+        assert_eq!(split.burned, 21_000 * GWEI * 80 / 100);
+        assert_eq!(split.validator_pool, 21_000 * GWEI * 20 / 100);
+        assert_eq!(split.tip, 21_000 * GWEI / 2);
+        assert_eq!(split.total(), 21_000 * (GWEI + GWEI / 2));
+
+        // Tip capped by the max fee
+        let split = market.charge(21_000, GWEI + 1, GWEI).unwrap();
+        assert_eq!(split.tip, 21_000);
+
+        assert_eq!(
+            market.charge(21_000, GWEI - 1, 0),
+            Err(FeeError::FeeTooLow {
+                max_fee: GWEI - 1,
+                base_fee: GWEI,
+            })
+        );
+        assert!(matches!(
+            market.charge(30_000_000, 2 * GWEI, 0),
+            Err(FeeError::GasLimitExceeded { .. })
+        ));
+    }
+
+    #[test]
+    fn test_burn_percent_configurable() {
+        let mut market = FeeMarket::new(FeeConfig {
+            burn_percent: 100,
+            ..Default::default()
+        });
+        let split = market.charge(21_000, GWEI, 0).unwrap();
+        assert_eq!(split.burned, 21_000 * GWEI);
+        assert_eq!(split.validator_pool, 0);
+    }
+
+    #[test]
+    fn test_close_anchor() {
+        let mut market = FeeMarket::default();
+        market.charge(20_000_000, 2 * GWEI, GWEI / 10).unwrap();
+        market.charge(10_000_000, 2 * GWEI, GWEI / 5).unwrap();
+
+        let fees = market.close_anchor(1);
+        assert_eq!(fees.round, 1);
+        assert_eq!(fees.base_fee, GWEI);
+        assert_eq!(fees.gas_used, 30_000_000);
+        assert_eq!(
+            fees.tips,
+            20_000_000 * (GWEI / 10) + 10_000_000 * (GWEI / 5)
+        );
+        assert_eq!(fees.priority_fees, Some([GWEI / 10, GWEI / 10, GWEI / 10]));
+        assert_eq!(fees.gas_used_ratio(), 1.0);
+        assert_eq!(market.total_tips, fees.tips);
+
+        // Full anchor raises the base fee; an empty one lowers it
+        assert_eq!(market.base_fee(), GWEI * 9 / 8);
+        let empty = market.close_anchor(2);
+        assert_eq!(empty.priority_fees, None);
+        assert_eq!(market.base_fee(), GWEI * 9 / 8 * 7 / 8);
+        assert_eq!(market.gas_available(), 30_000_000);
+    }
+
+    #[test]
+    fn test_history_bounded() {
+        let mut market = FeeMarket::new(FeeConfig {
+            history_len: 3,
+            ..Default::default()
+        });
+        for round in 1..=5 {
+            market.close_anchor(round);
+        }
+        let rounds: Vec<u64> = market.history().map(|a| a.round).collect();
+        assert_eq!(rounds, vec![3, 4, 5]);
+    }
+
+    #[test]
+    fn test_suggest() {
+        let mut market = FeeMarket::default();
+
+        // No history: defaults
+        let suggestion = market.suggest();
+        assert_eq!(suggestion.standard, GWEI / 1_000);
+        assert!(suggestion.slow < suggestion.standard);
+        assert!(suggestion.fast < suggestion.instant);
+
+        for tip in [1, 2, 3, 4, 5, 6, 7, 8, 9, 10] {
+            let price = market.base_fee() + tip * GWEI / 100;
+            market.record_transaction(21_000, price);
+        }
+        market.close_anchor(1);
+        market.close_anchor(2);
+
+        let suggestion = market.suggest();
+        assert_eq!(suggestion.base_fee, market.base_fee());
+        assert_eq!(suggestion.slow, GWEI / 100);
+        assert_eq!(suggestion.standard, 5 * GWEI / 100);
+        assert_eq!(suggestion.fast, 9 * GWEI / 100);
+        assert_eq!(suggestion.gas_used_ratio.len(), 2);
+        assert_eq!(suggestion.max_fee(0), 2 * market.base_fee());
+    }
+}
@@ -8,6 +8,7 @@
 //! - **Ethereum-style APY**: Target ~5% yield at equilibrium
 //! - **Performance-based rewards**: Multipliers for uptime, speed, green energy
 //! - **Federation/Community rewards**: Activity-based tier system
+//! - **Fee market**: EIP-1559 style base fee with burn and producer tips
 //!
 //! ## DC FAT Tokenomics
 //!
@@ -35,20 +36,27 @@
 
 pub mod emission;
 pub mod federation;
+pub mod fees;
 pub mod green_energy;
+pub mod oracle;
 pub mod performance;
 pub mod rewards;
 pub mod slashing;
 pub mod staking;
 
 // Re-exports
-pub use emission::{AnchorReward, EmissionEra, EmissionSchedule};
+pub use emission::{AnchorReward, EmissionEra, EmissionError, EmissionSchedule, SupplyPoint};
 pub use federation::{ActivityTier, CommunityRewards, FederationRewards};
+pub use fees::{AnchorFees, FeeConfig, FeeError, FeeMarket, FeeSplit, FeeSuggestion};
 pub use green_energy::{EnergySource, GreenEnergyMultiplier, GreenEnergyVerification};
+pub use oracle::{EconomicsOracle, EconomicsSnapshot};
 pub use performance::{PerformanceMetrics, PerformanceMultiplier, PerformanceScore};
 pub use rewards::{NodeReward, RewardCalculator, ValidatorReward};
 pub use slashing::{SlashingEngine, SlashingOffense, SlashingPenalty};
-pub use staking::{StakeManager, StakeRequirements, ValidatorStake};
+pub use staking::{
+    Delegation, DelegatorSummary, Redelegation, RewardSplit, StakeError, StakeManager,
+    StakeRequirements, UnbondingEntry, ValidatorStake,
+};
 
 /// DC FAT token constants
 pub mod constants {
@@ -73,6 +81,9 @@ pub mod constants {
     /// Halving interval: 4 years in seconds
     pub const HALVING_INTERVAL_SECS: u64 = 4 * 365 * 24 * 3600; // ~126,144,000 seconds
 
+    /// Seconds in an emission year (365 days)
+    pub const SECONDS_PER_YEAR: u64 = 365 * 24 * 3600;
+
     /// Anchor interval: ~4.2 seconds
     pub const ANCHOR_INTERVAL_SECS: f64 = 4.2;
 
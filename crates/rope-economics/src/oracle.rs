@@ -0,0 +1,130 @@
+//! # Economics Oracle
+//!
+//! Read-only view of network economics for APIs: staking APY from the
+//! active stake and the current era's emission, and supply projections
+//! from the emission schedule.
+
+use crate::constants::*;
+use crate::emission::{EmissionSchedule, SupplyPoint};
+use serde::{Deserialize, Serialize};
+
+/// Network economics at one point in time
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct EconomicsSnapshot {
+    pub timestamp: i64,
+    /// Supply, emission and inflation
+    pub supply: SupplyPoint,
+    /// Stake of active validators and their delegators
+    pub active_stake: u128,
+    /// Active stake over total supply, percent
+    pub staked_percent: f64,
+    /// Annual validator reward over active stake, percent
+    pub staking_apy: f64,
+    /// Start of the next era
+    pub next_halving: i64,
+}
+
+/// Economics calculations over an emission schedule
+#[derive(Clone, Debug)]
+pub struct EconomicsOracle {
+    emission: EmissionSchedule,
+}
+
+impl EconomicsOracle {
+    /// Create an oracle over `emission`
+    pub fn new(emission: EmissionSchedule) -> Self {
+        Self { emission }
+    }
+
+    /// Oracle for the mainnet schedule
+    pub fn mainnet() -> Self {
+        Self::new(EmissionSchedule::mainnet())
+    }
+
+    /// Underlying schedule
+    pub fn emission(&self) -> &EmissionSchedule {
+        &self.emission
+    }
+
+    /// Staking APY if `active_stake` shares the validator pool at `timestamp`
+    pub fn staking_apy(&self, timestamp: i64, active_stake: u128) -> f64 {
+        if active_stake == 0 {
+            return 0.0;
+        }
+
+        // Validator pool is proposer (30%) + testimony (45%) = 75% of emission
+        let validator_pool = self.emission.current_annual_emission(timestamp) * 75 / 100;
+        validator_pool as f64 / active_stake as f64 * 100.0
+    }
+
+    /// Economics at `timestamp` with `active_stake` bonded
+    pub fn snapshot(&self, timestamp: i64, active_stake: u128) -> EconomicsSnapshot {
+        let supply = self.emission.supply_at(timestamp);
+        let staked_percent = active_stake as f64 / supply.total_supply as f64 * 100.0;
+        EconomicsSnapshot {
+            timestamp,
+            next_halving: self.emission.era_start(supply.era + 1),
+            active_stake,
+            staked_percent,
+            staking_apy: self.staking_apy(timestamp, active_stake),
+            supply,
+        }
+    }
+
+    /// Yearly supply points for `years` years from `from`
+    pub fn supply_curve(&self, from: i64, years: u32) -> Vec<SupplyPoint> {
+        let to = from.saturating_add(years as i64 * SECONDS_PER_YEAR as i64);
+        self.emission.project_supply(from, to, SECONDS_PER_YEAR)
+    }
+}
+
+impl Default for EconomicsOracle {
+    fn default() -> Self {
+        Self::mainnet()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_staking_apy() {
+        let oracle = EconomicsOracle::new(EmissionSchedule::new(0));
+
+        // 375M validator pool over 7.5B staked
+        let apy = oracle.staking_apy(0, 7_500_000_000 * ONE_FAT);
+        assert!((apy - 5.0).abs() < 1e-9);
+
+        // Halves with the emission
+        let era = HALVING_INTERVAL_SECS as i64;
+        let apy = oracle.staking_apy(era, 7_500_000_000 * ONE_FAT);
+        assert!((apy - 2.5).abs() < 1e-9);
+
+        assert_eq!(oracle.staking_apy(0, 0), 0.0);
+    }
+
+    #[test]
+    fn test_snapshot() {
+        let oracle = EconomicsOracle::new(EmissionSchedule::new(0));
+        let snapshot = oracle.snapshot(0, 1_000_000_000 * ONE_FAT);
+
+        assert_eq!(snapshot.supply.total_supply, GENESIS_SUPPLY);
+        assert_eq!(snapshot.supply.era, 0);
+        assert!((snapshot.staked_percent - 10.0).abs() < 1e-9);
+        assert_eq!(snapshot.next_halving, HALVING_INTERVAL_SECS as i64);
+    }
+
+    #[test]
+    fn test_supply_curve() {
+        let oracle = EconomicsOracle::new(EmissionSchedule::new(0));
+        let curve = oracle.supply_curve(0, 20);
+
+        assert_eq!(curve.len(), 21);
+        assert_eq!(curve[0].total_supply, GENESIS_SUPPLY);
+        assert!(curve
+            .windows(2)
+            .all(|w| w[0].total_supply < w[1].total_supply));
+        assert_eq!(curve[20].timestamp, 20 * SECONDS_PER_YEAR as i64);
+    }
+}
@@ -10,9 +10,22 @@
 //! | Professional | 5,000,000 FAT | 6 months | 14 days |
 //! | Enterprise | 25,000,000 FAT | 12 months | 21 days |
 //! | Foundation | 100,000,000 FAT | 24 months | 30 days |
+//!
+//! ## Delegation
+//!
+//! FAT holders that don't run a validator can delegate to one. Delegated
+//! stake counts towards the validator's weight and earns a share of its
+//! rewards: the validator first takes its commission, and the rest is split
+//! pro rata between its own effective stake and each delegation.
+//!
+//! Undelegated FAT sits in an unbonding queue for the validator's unbonding
+//! period before it can be withdrawn. Redelegation moves stake between
+//! validators immediately, but stake that was just redelegated can't be
+//! moved again until the source validator's unbonding period has passed.
 
 use crate::constants::*;
 use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
 use std::collections::HashMap;
 
 /// Validator tier based on stake amount
@@ -93,8 +106,19 @@ impl ValidatorTier {
     }
 }
 
+/// Default validator commission: 10%
+pub const DEFAULT_COMMISSION_BPS: u16 = 1_000;
+
+/// Maximum pending unbonding entries per delegator and validator
+pub const MAX_UNBONDING_ENTRIES: usize = 7;
+
+fn default_commission() -> u16 {
+    DEFAULT_COMMISSION_BPS
+}
+
 /// Stake requirements for different participant types
 #[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
 pub struct StakeRequirements {
     /// Minimum stake to become validator
     pub validator_minimum: u128,
@@ -106,6 +130,10 @@ pub struct StakeRequirements {
     pub community_minimum: u128,
     /// Maximum stake per validator (for decentralization)
     pub validator_maximum: u128,
+    /// Minimum stake per delegation
+    pub delegation_minimum: u128,
+    /// Maximum validator commission in basis points
+    pub max_commission_bps: u16,
 }
 
 impl Default for StakeRequirements {
@@ -116,6 +144,8 @@ impl Default for StakeRequirements {
             federation_minimum: 10_000_000 * ONE_FAT,
             community_minimum: 1_000_000 * ONE_FAT,
             validator_maximum: 1_000_000_000 * ONE_FAT, // 1% of genesis
+            delegation_minimum: 100 * ONE_FAT,
+            max_commission_bps: 5_000, // 50%
         }
     }
 }
@@ -161,6 +191,14 @@ pub struct ValidatorStake {
 
     /// Slash count
     pub slash_count: u64,
+
+    /// Commission on rewards before they are shared with delegators (basis points)
+    #[serde(default = "default_commission")]
+    pub commission_bps: u16,
+
+    /// Stake delegated by others
+    #[serde(default)]
+    pub delegated_amount: u128,
 }
 
 impl ValidatorStake {
@@ -183,6 +221,8 @@ impl ValidatorStake {
             pending_rewards: 0,
             is_active: true,
             slash_count: 0,
+            commission_bps: DEFAULT_COMMISSION_BPS,
+            delegated_amount: 0,
         }
     }
 
@@ -201,6 +241,11 @@ impl ValidatorStake {
         self.staked_amount.saturating_sub(self.unbonding_amount)
     }
 
+    /// Own effective stake plus delegations
+    pub fn total_stake(&self) -> u128 {
+        self.effective_stake() + self.delegated_amount
+    }
+
     /// Add more stake
     pub fn add_stake(&mut self, amount: u128, timestamp: i64) {
         self.staked_amount += amount;
@@ -311,13 +356,130 @@ impl ValidatorStake {
     }
 }
 
+/// Stake delegated by a FAT holder to one validator
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Delegation {
+    /// Delegator address
+    pub delegator: [u8; 32],
+
+    /// Validator delegated to
+    pub validator_id: [u8; 32],
+
+    /// Bonded amount
+    pub amount: u128,
+
+    /// First delegation timestamp
+    pub delegated_at: i64,
+
+    /// Pending rewards (not yet claimed)
+    pub pending_rewards: u128,
+
+    /// Total rewards claimed
+    pub total_rewards_claimed: u128,
+}
+
+/// Undelegated stake waiting out the unbonding period
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UnbondingEntry {
+    /// Delegator address
+    pub delegator: [u8; 32],
+
+    /// Validator undelegated from
+    pub validator_id: [u8; 32],
+
+    /// Amount to be released
+    pub amount: u128,
+
+    /// Undelegation timestamp
+    pub start_time: i64,
+
+    /// Time the amount can be withdrawn
+    pub completion_time: i64,
+}
+
+impl UnbondingEntry {
+    /// Check if the entry can be withdrawn
+    pub fn is_mature(&self, timestamp: i64) -> bool {
+        timestamp >= self.completion_time
+    }
+}
+
+/// Stake moved from one validator to another
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Redelegation {
+    /// Delegator address
+    pub delegator: [u8; 32],
+
+    /// Source validator
+    pub src_validator: [u8; 32],
+
+    /// Destination validator
+    pub dst_validator: [u8; 32],
+
+    /// Amount moved
+    pub amount: u128,
+
+    /// Redelegation timestamp
+    pub start_time: i64,
+
+    /// Time the stake can be redelegated again
+    pub completion_time: i64,
+}
+
+/// How a validator reward was shared
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RewardSplit {
+    /// Validator commission
+    pub commission: u128,
+
+    /// Validator share on its own stake
+    pub validator_share: u128,
+
+    /// Total shared with delegators
+    pub delegator_share: u128,
+}
+
+/// Everything a delegator has staked, for account views
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DelegatorSummary {
+    /// Delegator address
+    pub delegator: [u8; 32],
+
+    /// Sum of bonded delegations
+    pub total_delegated: u128,
+
+    /// Sum of unbonding entries
+    pub total_unbonding: u128,
+
+    /// Sum of pending rewards
+    pub pending_rewards: u128,
+
+    /// Delegations, largest first
+    pub delegations: Vec<Delegation>,
+
+    /// Unbonding entries, soonest first
+    pub unbonding: Vec<UnbondingEntry>,
+
+    /// Redelegations still within their lock
+    pub redelegations: Vec<Redelegation>,
+}
+
 /// Stake manager
 #[derive(Default)]
 pub struct StakeManager {
     /// All validator stakes
     validators: HashMap<[u8; 32], ValidatorStake>,
 
-    /// Total staked
+    /// Delegations by (delegator, validator)
+    delegations: HashMap<([u8; 32], [u8; 32]), Delegation>,
+
+    /// Unbonding queue
+    unbonding: Vec<UnbondingEntry>,
+
+    /// Redelegations within their lock
+    redelegations: Vec<Redelegation>,
+
+    /// Total staked, including delegations
     pub total_staked: u128,
 
     /// Requirements
@@ -386,6 +548,368 @@ impl StakeManager {
         let validator_pool = annual_emission * 75 / 100;
         (validator_pool as f64 / self.total_staked as f64) * 100.0
     }
+
+    /// Set a validator's commission
+    pub fn set_commission(
+        &mut self,
+        validator_id: &[u8; 32],
+        commission_bps: u16,
+    ) -> Result<(), StakeError> {
+        if commission_bps > self.requirements.max_commission_bps {
+            return Err(StakeError::InvalidCommission);
+        }
+        let validator = self
+            .validators
+            .get_mut(validator_id)
+            .ok_or(StakeError::NotFound)?;
+        validator.commission_bps = commission_bps;
+        Ok(())
+    }
+
+    /// Delegate to an active validator
+    pub fn delegate(
+        &mut self,
+        delegator: [u8; 32],
+        validator_id: [u8; 32],
+        amount: u128,
+        timestamp: i64,
+    ) -> Result<Delegation, StakeError> {
+        if amount < self.requirements.delegation_minimum {
+            return Err(StakeError::BelowMinimum);
+        }
+        self.bond(delegator, validator_id, amount, timestamp)?;
+        self.total_staked += amount;
+        Ok(self.delegations[&(delegator, validator_id)].clone())
+    }
+
+    /// Undelegate, queueing the amount for the validator's unbonding period
+    pub fn undelegate(
+        &mut self,
+        delegator: [u8; 32],
+        validator_id: [u8; 32],
+        amount: u128,
+        timestamp: i64,
+    ) -> Result<UnbondingEntry, StakeError> {
+        let pending = self
+            .unbonding
+            .iter()
+            .filter(|e| e.delegator == delegator && e.validator_id == validator_id)
+            .count();
+        if pending >= MAX_UNBONDING_ENTRIES {
+            return Err(StakeError::TooManyUnbondingEntries);
+        }
+
+        let unbonding_period = self
+            .validators
+            .get(&validator_id)
+            .ok_or(StakeError::NotFound)?
+            .tier
+            .unbonding_period();
+        self.unbond(delegator, validator_id, amount)?;
+        self.total_staked -= amount;
+
+        let entry = UnbondingEntry {
+            delegator,
+            validator_id,
+            amount,
+            start_time: timestamp,
+            completion_time: timestamp + unbonding_period as i64,
+        };
+        self.unbonding.push(entry.clone());
+        Ok(entry)
+    }
+
+    /// Withdraw all of a delegator's matured unbonding entries
+    pub fn complete_undelegations(&mut self, delegator: &[u8; 32], timestamp: i64) -> u128 {
+        let mut released = 0;
+        self.unbonding.retain(|e| {
+            if &e.delegator == delegator && e.is_mature(timestamp) {
+                released += e.amount;
+                false
+            } else {
+                true
+            }
+        });
+        released
+    }
+
+    /// Move stake between validators without unbonding
+    pub fn redelegate(
+        &mut self,
+        delegator: [u8; 32],
+        src_validator: [u8; 32],
+        dst_validator: [u8; 32],
+        amount: u128,
+        timestamp: i64,
+    ) -> Result<Redelegation, StakeError> {
+        if src_validator == dst_validator {
+            return Err(StakeError::SameValidator);
+        }
+
+        // Stake redelegated into the source must serve its lock first
+        self.redelegations.retain(|r| timestamp < r.completion_time);
+        if self
+            .redelegations
+            .iter()
+            .any(|r| r.delegator == delegator && r.dst_validator == src_validator)
+        {
+            return Err(StakeError::RedelegationInProgress);
+        }
+
+        let unbonding_period = self
+            .validators
+            .get(&src_validator)
+            .ok_or(StakeError::NotFound)?
+            .tier
+            .unbonding_period();
+        self.check_bondable(&dst_validator, amount)?;
+        self.unbond(delegator, src_validator, amount)?;
+        self.bond(delegator, dst_validator, amount, timestamp)?;
+
+        let redelegation = Redelegation {
+            delegator,
+            src_validator,
+            dst_validator,
+            amount,
+            start_time: timestamp,
+            completion_time: timestamp + unbonding_period as i64,
+        };
+        self.redelegations.push(redelegation.clone());
+        Ok(redelegation)
+    }
+
+    /// Share a validator reward between the validator and its delegators
+    ///
+    /// The validator keeps its commission plus its pro rata share, and
+    /// rounding dust, so the parts always sum to `amount`.
+    pub fn distribute_rewards(
+        &mut self,
+        validator_id: &[u8; 32],
+        amount: u128,
+    ) -> Result<RewardSplit, StakeError> {
+        let validator = self
+            .validators
+            .get(validator_id)
+            .ok_or(StakeError::NotFound)?;
+        let commission = mul_div(amount, validator.commission_bps as u128, 10_000);
+        let pool = amount - commission;
+        let total_stake = validator.total_stake();
+
+        let mut delegator_share = 0;
+        if total_stake > 0 {
+            for delegation in self
+                .delegations
+                .values_mut()
+                .filter(|d| &d.validator_id == validator_id)
+            {
+                let share = mul_div(pool, delegation.amount, total_stake);
+                delegation.pending_rewards += share;
+                delegator_share += share;
+            }
+        }
+
+        let validator_share = pool - delegator_share;
+        if let Some(validator) = self.validators.get_mut(validator_id) {
+            validator.add_rewards(commission + validator_share);
+        }
+
+        Ok(RewardSplit {
+            commission,
+            validator_share,
+            delegator_share,
+        })
+    }
+
+    /// Claim pending rewards of a delegation
+    pub fn claim_delegation_rewards(
+        &mut self,
+        delegator: &[u8; 32],
+        validator_id: &[u8; 32],
+    ) -> Result<u128, StakeError> {
+        let key = (*delegator, *validator_id);
+        let delegation = self
+            .delegations
+            .get_mut(&key)
+            .ok_or(StakeError::DelegationNotFound)?;
+        let amount = delegation.pending_rewards;
+        delegation.pending_rewards = 0;
+        delegation.total_rewards_claimed += amount;
+        if delegation.amount == 0 {
+            self.delegations.remove(&key);
+        }
+        Ok(amount)
+    }
+
+    /// Get a delegation
+    pub fn get_delegation(
+        &self,
+        delegator: &[u8; 32],
+        validator_id: &[u8; 32],
+    ) -> Option<&Delegation> {
+        self.delegations.get(&(*delegator, *validator_id))
+    }
+
+    /// Get delegations of a delegator
+    pub fn delegations_of(&self, delegator: &[u8; 32]) -> Vec<&Delegation> {
+        self.delegations
+            .values()
+            .filter(|d| &d.delegator == delegator)
+            .collect()
+    }
+
+    /// Get delegations to a validator, largest first
+    pub fn delegators_of(&self, validator_id: &[u8; 32]) -> Vec<&Delegation> {
+        let mut delegations: Vec<_> = self
+            .delegations
+            .values()
+            .filter(|d| &d.validator_id == validator_id && d.amount > 0)
+            .collect();
+        delegations.sort_by_key(|d| Reverse(d.amount));
+        delegations
+    }
+
+    /// Get unbonding entries of a delegator, soonest first
+    pub fn unbonding_of(&self, delegator: &[u8; 32]) -> Vec<&UnbondingEntry> {
+        let mut entries: Vec<_> = self
+            .unbonding
+            .iter()
+            .filter(|e| &e.delegator == delegator)
+            .collect();
+        entries.sort_by_key(|e| e.completion_time);
+        entries
+    }
+
+    /// Summarize a delegator's stake at `timestamp`
+    pub fn delegator_summary(&self, delegator: &[u8; 32], timestamp: i64) -> DelegatorSummary {
+        let mut delegations: Vec<Delegation> = self
+            .delegations_of(delegator)
+            .into_iter()
+            .cloned()
+            .collect();
+        delegations.sort_by_key(|d| Reverse(d.amount));
+        let unbonding: Vec<UnbondingEntry> =
+            self.unbonding_of(delegator).into_iter().cloned().collect();
+        let redelegations = self
+            .redelegations
+            .iter()
+            .filter(|r| &r.delegator == delegator && timestamp < r.completion_time)
+            .cloned()
+            .collect();
+
+        DelegatorSummary {
+            delegator: *delegator,
+            total_delegated: delegations.iter().map(|d| d.amount).sum(),
+            total_unbonding: unbonding.iter().map(|e| e.amount).sum(),
+            pending_rewards: delegations.iter().map(|d| d.pending_rewards).sum(),
+            delegations,
+            unbonding,
+            redelegations,
+        }
+    }
+
+    /// Check a validator can take `amount` more delegated stake
+    fn check_bondable(&self, validator_id: &[u8; 32], amount: u128) -> Result<(), StakeError> {
+        let validator = self
+            .validators
+            .get(validator_id)
+            .ok_or(StakeError::NotFound)?;
+        if !validator.is_active {
+            return Err(StakeError::ValidatorInactive);
+        }
+        if validator.total_stake() + amount > self.requirements.validator_maximum {
+            return Err(StakeError::AboveMaximum);
+        }
+        Ok(())
+    }
+
+    fn bond(
+        &mut self,
+        delegator: [u8; 32],
+        validator_id: [u8; 32],
+        amount: u128,
+        timestamp: i64,
+    ) -> Result<(), StakeError> {
+        self.check_bondable(&validator_id, amount)?;
+        if let Some(validator) = self.validators.get_mut(&validator_id) {
+            validator.delegated_amount += amount;
+        }
+        self.delegations
+            .entry((delegator, validator_id))
+            .or_insert_with(|| Delegation {
+                delegator,
+                validator_id,
+                amount: 0,
+                delegated_at: timestamp,
+                pending_rewards: 0,
+                total_rewards_claimed: 0,
+            })
+            .amount += amount;
+        Ok(())
+    }
+
+    fn unbond(
+        &mut self,
+        delegator: [u8; 32],
+        validator_id: [u8; 32],
+        amount: u128,
+    ) -> Result<(), StakeError> {
+        let key = (delegator, validator_id);
+        let delegation = self
+            .delegations
+            .get_mut(&key)
+            .ok_or(StakeError::DelegationNotFound)?;
+        if amount == 0 || amount > delegation.amount {
+            return Err(StakeError::InsufficientBalance);
+        }
+
+        // Don't leave dust delegations behind
+        let remaining = delegation.amount - amount;
+        if remaining > 0 && remaining < self.requirements.delegation_minimum {
+            return Err(StakeError::BelowMinimum);
+        }
+
+        delegation.amount = remaining;
+        if remaining == 0 && delegation.pending_rewards == 0 {
+            self.delegations.remove(&key);
+        }
+        if let Some(validator) = self.validators.get_mut(&validator_id) {
+            validator.delegated_amount -= amount;
+        }
+        Ok(())
+    }
+}
+
+/// `a * b / c` rounded down, through a 256-bit product
+///
+/// Callers keep `b <= c`, so the quotient fits in a `u128`.
+fn mul_div(a: u128, b: u128, c: u128) -> u128 {
+    const LOW: u128 = u64::MAX as u128;
+    let (a1, a0) = (a >> 64, a & LOW);
+    let (b1, b0) = (b >> 64, b & LOW);
+    let (p00, p01, p10, p11) = (a0 * b0, a0 * b1, a1 * b0, a1 * b1);
+    let mid = (p00 >> 64) + (p01 & LOW) + (p10 & LOW);
+    let lo = (p00 & LOW) | (mid << 64);
+    let hi = p11 + (p01 >> 64) + (p10 >> 64) + (mid >> 64);
+
+    // Long division of hi:lo by c, one bit at a time
+    let mut remainder: u128 = 0;
+    let mut quotient: u128 = 0;
+    for i in (0..256).rev() {
+        let bit = if i >= 128 {
+            (hi >> (i - 128)) & 1
+        } else {
+            (lo >> i) & 1
+        };
+        let overflow = remainder >> 127 == 1;
+        remainder = (remainder << 1) | bit;
+        if overflow || remainder >= c {
+            remainder = remainder.wrapping_sub(c);
+            if i < 128 {
+                quotient |= 1 << i;
+            }
+        }
+    }
+    quotient
 }
 
 /// Stake errors
@@ -411,6 +935,27 @@ pub enum StakeError {
 
     #[error("Validator not found")]
     NotFound,
+
+    #[error("Delegation not found")]
+    DelegationNotFound,
+
+    #[error("Validator is not active")]
+    ValidatorInactive,
+
+    #[error("Stake above validator maximum")]
+    AboveMaximum,
+
+    #[error("Commission above maximum")]
+    InvalidCommission,
+
+    #[error("Cannot redelegate to the same validator")]
+    SameValidator,
+
+    #[error("Redelegated stake is still locked")]
+    RedelegationInProgress,
+
+    #[error("Too many unbonding entries")]
+    TooManyUnbondingEntries,
 }
 
 #[cfg(test)]
@@ -479,4 +1024,232 @@ mod tests {
 
         assert!(matches!(result, Err(StakeError::BelowMinimum)));
     }
+
+    fn manager_with_validators() -> StakeManager {
+        let mut manager = StakeManager::new();
+        manager
+            .register_validator([1u8; 32], [1u8; 32], 1_000_000 * ONE_FAT, 0)
+            .unwrap();
+        manager
+            .register_validator([2u8; 32], [2u8; 32], 1_000_000 * ONE_FAT, 0)
+            .unwrap();
+        manager
+    }
+
+    #[test]
+    fn test_delegate() {
+        let mut manager = manager_with_validators();
+        let delegator = [9u8; 32];
+
+        manager
+            .delegate(delegator, [1u8; 32], 1_000 * ONE_FAT, 10)
+            .unwrap();
+        let delegation = manager
+            .delegate(delegator, [1u8; 32], 500 * ONE_FAT, 20)
+            .unwrap();
+
+        assert_eq!(delegation.amount, 1_500 * ONE_FAT);
+        assert_eq!(delegation.delegated_at, 10);
+        assert_eq!(manager.total_staked, 2_000_000 * ONE_FAT + 1_500 * ONE_FAT);
+        assert_eq!(
+            manager.get_validator(&[1u8; 32]).unwrap().total_stake(),
+            1_001_500 * ONE_FAT
+        );
+
+        assert!(matches!(
+            manager.delegate(delegator, [1u8; 32], ONE_FAT, 30),
+            Err(StakeError::BelowMinimum)
+        ));
+        assert!(matches!(
+            manager.delegate(delegator, [3u8; 32], 1_000 * ONE_FAT, 30),
+            Err(StakeError::NotFound)
+        ));
+    }
+
+    #[test]
+    fn test_reward_sharing() {
+        let mut manager = manager_with_validators();
+        let delegator = [9u8; 32];
+        manager
+            .delegate(delegator, [1u8; 32], 1_000_000 * ONE_FAT, 0)
+            .unwrap();
+        manager.set_commission(&[1u8; 32], 2_000).unwrap();
+
+        let split = manager
+            .distribute_rewards(&[1u8; 32], 1_000 * ONE_FAT)
+            .unwrap();
+        assert_eq!(split.commission, 200 * ONE_FAT);
+        assert_eq!(split.delegator_share, 400 * ONE_FAT);
+        assert_eq!(
+            split.commission + split.validator_share + split.delegator_share,
+            1_000 * ONE_FAT
+        );
+        assert_eq!(
+            manager.get_validator(&[1u8; 32]).unwrap().pending_rewards,
+            600 * ONE_FAT
+        );
+
+        assert_eq!(
+            manager
+                .claim_delegation_rewards(&delegator, &[1u8; 32])
+                .unwrap(),
+            400 * ONE_FAT
+        );
+        assert_eq!(
+            manager
+                .claim_delegation_rewards(&delegator, &[1u8; 32])
+                .unwrap(),
+            0
+        );
+        assert!(matches!(
+            manager.set_commission(&[1u8; 32], 6_000),
+            Err(StakeError::InvalidCommission)
+        ));
+    }
+
+    #[test]
+    fn test_reward_sharing_is_exact() {
+        let mut manager = manager_with_validators();
+        manager
+            .delegate([8u8; 32], [1u8; 32], 500_000 * ONE_FAT, 0)
+            .unwrap();
+        manager
+            .delegate([9u8; 32], [1u8; 32], 700_000 * ONE_FAT, 0)
+            .unwrap();
+
+        // Far beyond f64's 53-bit mantissa
+        let amount = 1_000_000_007 * ONE_FAT + 11;
+        let split = manager.distribute_rewards(&[1u8; 32], amount).unwrap();
+        let pool = amount - split.commission;
+        assert_eq!(split.commission, amount / 10);
+        // Delegations hold 5/22 and 7/22 of the 2.2M FAT staked
+        for (delegator, parts) in [([8u8; 32], 5), ([9u8; 32], 7)] {
+            let delegation = manager.get_delegation(&delegator, &[1u8; 32]).unwrap();
+            assert_eq!(delegation.pending_rewards, pool * parts / 22);
+        }
+        assert_eq!(
+            split.commission + split.validator_share + split.delegator_share,
+            amount
+        );
+    }
+
+    #[test]
+    fn test_mul_div() {
+        assert_eq!(mul_div(1_000, 3, 7), 428);
+        assert_eq!(mul_div(u128::MAX, 1, 1), u128::MAX);
+        assert_eq!(mul_div(u128::MAX, u128::MAX - 1, u128::MAX), u128::MAX - 1);
+        assert_eq!(mul_div(u128::MAX, 1 << 100, 1 << 101), u128::MAX / 2);
+        assert_eq!(mul_div(0, 5, 9), 0);
+    }
+
+    #[test]
+    fn test_undelegate_unbonding_queue() {
+        let mut manager = manager_with_validators();
+        let delegator = [9u8; 32];
+        let unbonding_period = ValidatorTier::Standard.unbonding_period() as i64;
+        manager
+            .delegate(delegator, [1u8; 32], 1_000 * ONE_FAT, 0)
+            .unwrap();
+
+        let entry = manager
+            .undelegate(delegator, [1u8; 32], 400 * ONE_FAT, 100)
+            .unwrap();
+        assert_eq!(entry.completion_time, 100 + unbonding_period);
+        assert_eq!(
+            manager.get_validator(&[1u8; 32]).unwrap().delegated_amount,
+            600 * ONE_FAT
+        );
+
+        // Would leave a dust delegation
+        assert!(matches!(
+            manager.undelegate(delegator, [1u8; 32], 550 * ONE_FAT, 100),
+            Err(StakeError::BelowMinimum)
+        ));
+
+        // Time locked
+        assert_eq!(manager.complete_undelegations(&delegator, 100), 0);
+        let summary = manager.delegator_summary(&delegator, 100);
+        assert_eq!(summary.total_delegated, 600 * ONE_FAT);
+        assert_eq!(summary.total_unbonding, 400 * ONE_FAT);
+
+        assert_eq!(
+            manager.complete_undelegations(&delegator, 100 + unbonding_period),
+            400 * ONE_FAT
+        );
+        assert!(manager.unbonding_of(&delegator).is_empty());
+
+        // Full undelegation removes the delegation
+        manager
+            .undelegate(delegator, [1u8; 32], 600 * ONE_FAT, 200)
+            .unwrap();
+        assert!(manager.get_delegation(&delegator, &[1u8; 32]).is_none());
+    }
+
+    #[test]
+    fn test_unbonding_entry_limit() {
+        let mut manager = manager_with_validators();
+        let delegator = [9u8; 32];
+        manager
+            .delegate(delegator, [1u8; 32], 10_000 * ONE_FAT, 0)
+            .unwrap();
+
+        for i in 0..MAX_UNBONDING_ENTRIES {
+            manager
+                .undelegate(delegator, [1u8; 32], 100 * ONE_FAT, i as i64)
+                .unwrap();
+        }
+        assert!(matches!(
+            manager.undelegate(delegator, [1u8; 32], 100 * ONE_FAT, 10),
+            Err(StakeError::TooManyUnbondingEntries)
+        ));
+    }
+
+    #[test]
+    fn test_redelegate() {
+        let mut manager = StakeManager::new();
+        for id in 1..=3u8 {
+            manager
+                .register_validator([id; 32], [id; 32], 1_000_000 * ONE_FAT, 0)
+                .unwrap();
+        }
+        let delegator = [9u8; 32];
+        let total_staked = manager.total_staked;
+        manager
+            .delegate(delegator, [1u8; 32], 1_000 * ONE_FAT, 0)
+            .unwrap();
+
+        let redelegation = manager
+            .redelegate(delegator, [1u8; 32], [2u8; 32], 1_000 * ONE_FAT, 100)
+            .unwrap();
+        assert_eq!(manager.total_staked, total_staked + 1_000 * ONE_FAT);
+        assert!(manager.get_delegation(&delegator, &[1u8; 32]).is_none());
+        assert_eq!(
+            manager.get_validator(&[2u8; 32]).unwrap().delegated_amount,
+            1_000 * ONE_FAT
+        );
+
+        // No hopping on until the lock expires
+        assert!(matches!(
+            manager.redelegate(delegator, [2u8; 32], [3u8; 32], 1_000 * ONE_FAT, 200),
+            Err(StakeError::RedelegationInProgress)
+        ));
+        assert_eq!(
+            manager.delegator_summary(&delegator, 200).redelegations,
+            vec![redelegation.clone()]
+        );
+        manager
+            .redelegate(
+                delegator,
+                [2u8; 32],
+                [3u8; 32],
+                1_000 * ONE_FAT,
+                redelegation.completion_time,
+            )
+            .unwrap();
+
+        assert!(matches!(
+            manager.redelegate(delegator, [3u8; 32], [3u8; 32], ONE_FAT, 0),
+            Err(StakeError::SameValidator)
+        ));
+    }
 }
@@ -1,3 +1,227 @@
 //! API helpers and types
 
-// Placeholder module - handlers are in main.rs for now
+use crate::db::DbError;
+use crate::models::{
+    Cursor, InternalTransactionRow, Page, RewardRow, StringRow, TokenBalanceRow, TokenHolderRow,
+    TokenTransferRow, TransactionRow, ValidatorRow,
+};
+use crate::verify::DecodedCall;
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Json, Response},
+};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use utoipa::{IntoParams, ToSchema};
+
+/// Default page size
+pub const DEFAULT_PAGE_SIZE: u32 = 20;
+
+/// Maximum page size
+pub const MAX_PAGE_SIZE: u32 = 100;
+
+/// API errors
+#[derive(Error, Debug)]
+pub enum ApiError {
+    #[error("{0} not found")]
+    NotFound(String),
+
+    #[error("Bad request: {0}")]
+    BadRequest(String),
+
+    #[error("Unauthorized: {0}")]
+    Unauthorized(String),
+
+    #[error("Service unavailable: {0}")]
+    Unavailable(String),
+
+    #[error("Too many requests: {0}")]
+    TooManyRequests(String),
+
+    #[error(transparent)]
+    Database(#[from] DbError),
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let status = match &self {
+            ApiError::NotFound(_) => StatusCode::NOT_FOUND,
+            ApiError::BadRequest(_) => StatusCode::BAD_REQUEST,
+            ApiError::Unauthorized(_) => StatusCode::UNAUTHORIZED,
+            ApiError::Unavailable(_) => StatusCode::SERVICE_UNAVAILABLE,
+            ApiError::TooManyRequests(_) => StatusCode::TOO_MANY_REQUESTS,
+            ApiError::Database(e) => {
+                tracing::error!("{}", e);
+                StatusCode::INTERNAL_SERVER_ERROR
+            }
+        };
+        let message = match &self {
+            // Don't leak query details to clients
+            ApiError::Database(_) => "Internal error".to_string(),
+            other => other.to_string(),
+        };
+        (status, Json(ErrorBody { error: message })).into_response()
+    }
+}
+
+/// Error response body
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ErrorBody {
+    pub error: String,
+}
+
+/// Handler result
+pub type ApiResult<T = serde_json::Value> = Result<Json<T>, ApiError>;
+
+/// Cursor pagination query parameters
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct CursorParams {
+    /// Opaque cursor from the previous page's `pagination.nextCursor`
+    pub cursor: Option<String>,
+    /// Page size (1-100, default 20)
+    pub limit: Option<u32>,
+}
+
+impl CursorParams {
+    /// Requested page size, clamped to `1..=MAX_PAGE_SIZE`
+    pub fn limit(&self) -> usize {
+        self.limit
+            .unwrap_or(DEFAULT_PAGE_SIZE)
+            .clamp(1, MAX_PAGE_SIZE) as usize
+    }
+
+    /// Rows to fetch: one extra to detect a next page
+    pub fn fetch_limit(&self) -> i64 {
+        self.limit() as i64 + 1
+    }
+
+    /// Decoded cursor
+    pub fn cursor(&self) -> Result<Option<Cursor>, ApiError> {
+        match &self.cursor {
+            None => Ok(None),
+            Some(s) => Cursor::decode(s)
+                .map(Some)
+                .ok_or_else(|| ApiError::BadRequest(format!("invalid cursor '{}'", s))),
+        }
+    }
+}
+
+/// `pagination` object of list responses
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Pagination {
+    pub limit: usize,
+    /// Cursor for the next page, absent on the last page
+    pub next_cursor: Option<String>,
+}
+
+impl Pagination {
+    pub fn new<T>(page: &Page<T>, limit: usize) -> Self {
+        Self {
+            limit,
+            next_cursor: page.next_cursor.clone(),
+        }
+    }
+}
+
+/// Strings, newest first
+#[derive(Debug, Serialize, ToSchema)]
+pub struct StringList {
+    pub strings: Vec<StringRow>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pagination: Option<Pagination>,
+}
+
+/// String with its transactions
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct StringDetail {
+    pub number: i64,
+    pub hash: String,
+    pub creator: String,
+    pub string_type: String,
+    pub timestamp: i64,
+    /// Number of transactions
+    pub transactions: i32,
+    /// `Pending`, `Final` or `Rejected`
+    pub status: String,
+    pub rejection_reason: Option<String>,
+    pub anchor_id: Option<String>,
+    pub transaction_list: Vec<TransactionRow>,
+}
+
+/// Transaction with call data decoded against the verified target contract
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionDetail {
+    #[serde(flatten)]
+    pub transaction: TransactionRow,
+    /// Name of the verified contract called, if any
+    pub contract_name: Option<String>,
+    pub decoded_input: Option<DecodedCall>,
+    /// ERC-20 transfers emitted
+    pub token_transfers: Vec<TokenTransferRow>,
+    /// Bridge call, if the transaction invoked one
+    pub internal: Option<InternalTransactionRow>,
+}
+
+/// Transactions, newest first
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TransactionList {
+    pub transactions: Vec<TransactionRow>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pagination: Option<Pagination>,
+}
+
+/// Transactions of one account, newest first
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AccountTransactions {
+    pub address: String,
+    pub pagination: Pagination,
+    pub transactions: Vec<TransactionRow>,
+}
+
+/// Validators by stake
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidatorList {
+    pub pagination: Pagination,
+    pub validators: Vec<ValidatorRow>,
+    pub total_staked: i64,
+    pub active_count: i64,
+}
+
+/// Epoch rewards of one validator, newest first
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidatorRewards {
+    pub address: String,
+    pub pagination: Pagination,
+    /// Sum over all epochs, base units
+    pub total_rewards: String,
+    pub rewards: Vec<RewardRow>,
+}
+
+/// Token balances of one account
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AccountTokens {
+    pub address: String,
+    pub tokens: Vec<TokenBalanceRow>,
+}
+
+/// Holders of a token by balance
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TokenHolders {
+    pub token: String,
+    pub pagination: Pagination,
+    pub holders: Vec<TokenHolderRow>,
+}
+
+/// Transfers of a token, newest first
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TokenTransfers {
+    pub token: String,
+    pub pagination: Pagination,
+    pub transfers: Vec<TokenTransferRow>,
+}
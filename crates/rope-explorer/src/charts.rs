@@ -0,0 +1,287 @@
+//! Chart series
+//!
+//! The indexer maintains hourly and daily rollups (see
+//! [`Database::insert_string`](crate::db::Database)); the price fetcher
+//! adds FAT price samples to the same buckets. A chart request picks the
+//! resolution for its period, fills empty buckets and downsamples to at
+//! most [`MAX_POINTS`].
+//!
+//! | Period | Resolution | Buckets |
+//! |--------|------------|---------|
+//! | `24h`  | hour       | 24      |
+//! | `7d`   | hour       | 168     |
+//! | `30d`  | day        | 30      |
+//! | `1y`   | day        | 365     |
+
+use crate::models::RollupRow;
+use serde::Serialize;
+use utoipa::ToSchema;
+
+/// Rollup resolutions (seconds)
+pub const HOUR: i64 = 3_600;
+pub const DAY: i64 = 86_400;
+pub const RESOLUTIONS: [i64; 2] = [HOUR, DAY];
+
+/// Points returned per series at most
+pub const MAX_POINTS: usize = 120;
+
+/// Start of the bucket containing `timestamp`
+pub fn bucket_start(timestamp: i64, resolution: i64) -> i64 {
+    timestamp - timestamp.rem_euclid(resolution)
+}
+
+/// Chart period
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Period {
+    Day,
+    Week,
+    Month,
+    Year,
+}
+
+impl Period {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "24h" | "1d" => Some(Period::Day),
+            "7d" => Some(Period::Week),
+            "30d" => Some(Period::Month),
+            "1y" | "365d" => Some(Period::Year),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Period::Day => "24h",
+            Period::Week => "7d",
+            Period::Month => "30d",
+            Period::Year => "1y",
+        }
+    }
+
+    /// Rollup resolution used for the period
+    pub fn resolution(&self) -> i64 {
+        match self {
+            Period::Day | Period::Week => HOUR,
+            Period::Month | Period::Year => DAY,
+        }
+    }
+
+    /// Number of buckets covered
+    pub fn buckets(&self) -> i64 {
+        match self {
+            Period::Day => 24,
+            Period::Week => 24 * 7,
+            Period::Month => 30,
+            Period::Year => 365,
+        }
+    }
+
+    /// First and last bucket start for a period ending at `now`
+    pub fn range(&self, now: i64) -> (i64, i64) {
+        let resolution = self.resolution();
+        let last = bucket_start(now, resolution);
+        (last - (self.buckets() - 1) * resolution, last)
+    }
+}
+
+/// Charted metric
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Metric {
+    Transactions,
+    ActiveAccounts,
+    GasUsed,
+    Tps,
+    Price,
+}
+
+impl Metric {
+    /// Path names accepted by the charts endpoint
+    pub const ALL: [&'static str; 5] = [
+        "transactions",
+        "active-accounts",
+        "gas-used",
+        "tps",
+        "price",
+    ];
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "transactions" => Some(Metric::Transactions),
+            "active-accounts" | "accounts" => Some(Metric::ActiveAccounts),
+            "gas-used" | "gas" => Some(Metric::GasUsed),
+            "tps" => Some(Metric::Tps),
+            "price" => Some(Metric::Price),
+            _ => None,
+        }
+    }
+
+    /// Value of one (possibly merged) bucket spanning `seconds`
+    fn value(&self, row: &RollupRow, seconds: i64) -> Option<f64> {
+        match self {
+            Metric::Transactions => Some(row.transactions as f64),
+            Metric::ActiveAccounts => Some(row.active_accounts as f64),
+            Metric::GasUsed => Some(row.gas_used as f64),
+            Metric::Tps => Some(row.transactions as f64 / seconds as f64),
+            // No sample in the bucket: leave a gap rather than plot zero
+            Metric::Price => {
+                (row.price_samples > 0).then(|| row.price_sum / row.price_samples as f64)
+            }
+        }
+    }
+}
+
+/// One chart point
+#[derive(Clone, Debug, PartialEq, Serialize, ToSchema)]
+pub struct ChartPoint {
+    pub timestamp: i64,
+    /// Absent when there is no data (price only)
+    pub value: Option<f64>,
+}
+
+/// Chart response
+#[derive(Clone, Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ChartSeries {
+    pub chart_type: String,
+    pub period: String,
+    /// Seconds covered by each point
+    pub interval: i64,
+    /// Oldest first
+    pub data: Vec<ChartPoint>,
+}
+
+/// Build the series for `metric` from stored rollups between `first` and `last` (bucket starts)
+pub fn series(
+    metric: Metric,
+    rows: &[RollupRow],
+    first: i64,
+    last: i64,
+    resolution: i64,
+) -> (i64, Vec<ChartPoint>) {
+    let filled = fill(rows, first, last, resolution);
+    let per_point = filled.len().div_ceil(MAX_POINTS).max(1);
+    let interval = resolution * per_point as i64;
+    let points = filled
+        .chunks(per_point)
+        .map(|chunk| {
+            let merged = merge(chunk);
+            ChartPoint {
+                timestamp: merged.bucket,
+                value: metric.value(&merged, resolution * chunk.len() as i64),
+            }
+        })
+        .collect();
+    (interval, points)
+}
+
+/// One row per bucket in `first..=last`, zeroed where nothing was recorded
+fn fill(rows: &[RollupRow], first: i64, last: i64, resolution: i64) -> Vec<RollupRow> {
+    let mut rows = rows.iter().peekable();
+    let mut filled = Vec::new();
+    let mut bucket = first;
+    while bucket <= last {
+        while rows.peek().is_some_and(|row| row.bucket < bucket) {
+            rows.next();
+        }
+        match rows.peek() {
+            Some(row) if row.bucket == bucket => filled.push((*row).clone()),
+            _ => filled.push(RollupRow {
+                bucket,
+                ..Default::default()
+            }),
+        }
+        bucket += resolution;
+    }
+    filled
+}
+
+/// Combine consecutive buckets. Accounts active in several buckets would
+/// be counted more than once by summing, so the peak bucket is used.
+fn merge(chunk: &[RollupRow]) -> RollupRow {
+    chunk.iter().fold(
+        RollupRow {
+            bucket: chunk.first().map_or(0, |row| row.bucket),
+            ..Default::default()
+        },
+        |mut merged, row| {
+            merged.transactions += row.transactions;
+            merged.active_accounts = merged.active_accounts.max(row.active_accounts);
+            merged.gas_used += row.gas_used;
+            merged.price_sum += row.price_sum;
+            merged.price_samples += row.price_samples;
+            merged
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(bucket: i64, transactions: i64) -> RollupRow {
+        RollupRow {
+            bucket,
+            transactions,
+            active_accounts: transactions * 2,
+            gas_used: transactions * 21_000,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_period_range() {
+        let now = 10 * DAY + 5 * HOUR + 17;
+        assert_eq!(
+            Period::Day.range(now),
+            (9 * DAY + 6 * HOUR, 10 * DAY + 5 * HOUR)
+        );
+        assert_eq!(Period::Month.range(now), (-19 * DAY, 10 * DAY));
+        assert_eq!(Period::parse("7d"), Some(Period::Week));
+        assert_eq!(Period::parse("2w"), None);
+        assert_eq!(bucket_start(-1, HOUR), -HOUR);
+    }
+
+    #[test]
+    fn test_series_fills_gaps() {
+        let rows = vec![row(0, 3600), row(2 * HOUR, 7200)];
+        let (interval, points) = series(Metric::Transactions, &rows, 0, 3 * HOUR, HOUR);
+        assert_eq!(interval, HOUR);
+        let values: Vec<Option<f64>> = points.iter().map(|p| p.value).collect();
+        assert_eq!(
+            values,
+            vec![Some(3600.0), Some(0.0), Some(7200.0), Some(0.0)]
+        );
+
+        let (_, points) = series(Metric::Tps, &rows, 0, 3 * HOUR, HOUR);
+        assert_eq!(points[0].value, Some(1.0));
+        assert_eq!(points[2].value, Some(2.0));
+
+        // Buckets without price samples are gaps, not zeros
+        let (_, points) = series(Metric::Price, &rows, 0, HOUR, HOUR);
+        assert_eq!(points[0].value, None);
+    }
+
+    #[test]
+    fn test_series_downsamples() {
+        let (first, last) = Period::Week.range(30 * DAY);
+        let rows: Vec<RollupRow> = (0..168).map(|i| row(first + i * HOUR, 1)).collect();
+        let (interval, points) = series(Metric::Transactions, &rows, first, last, HOUR);
+
+        assert!(points.len() <= MAX_POINTS);
+        assert_eq!(interval, 2 * HOUR);
+        assert_eq!(points.len(), 84);
+        assert_eq!(points[0].timestamp, first);
+        assert_eq!(points[0].value, Some(2.0));
+
+        // Merged buckets report the peak active-account count
+        let (_, points) = series(Metric::ActiveAccounts, &rows, first, last, HOUR);
+        assert_eq!(points[0].value, Some(2.0));
+
+        let mut priced = row(first, 0);
+        priced.price_sum = 0.008;
+        priced.price_samples = 2;
+        let (_, points) = series(Metric::Price, &[priced], first, last, HOUR);
+        assert_eq!(points[0].value, Some(0.004));
+    }
+}
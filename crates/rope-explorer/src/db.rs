@@ -1,3 +1,1227 @@
 //! Database module
+//!
+//! PostgreSQL persistence for the indexer. Queries are built at runtime
+//! (no compile-time `DATABASE_URL`) and list queries use keyset
+//! pagination: callers fetch `limit + 1` rows and hand them to
+//! [`Page::from_rows`](crate::models::Page::from_rows).
 
-// Placeholder for PostgreSQL integration
+use crate::charts::{bucket_start, DAY, RESOLUTIONS};
+use crate::indexer::{
+    IndexStore, IndexedReward, IndexedString, IndexedTestimony, IndexedTransaction,
+    IndexedValidator,
+};
+use crate::keys::ApiKeyRecord;
+use crate::models::{
+    AccountRow, Cursor, InternalTransactionRow, PerformanceRow, RewardRow, RollupRow, SearchRow,
+    StringRow, TestimonyRow, TokenBalanceRow, TokenHolderRow, TokenStatsRow, TokenTransferRow,
+    TransactionRow, ValidatorRow,
+};
+use crate::models::{STATUS_FINAL, STATUS_PENDING, STATUS_REJECTED};
+use crate::performance::{heartbeat_slot, HEARTBEAT_SLOT};
+use crate::tokens::{decode_bridge_call, decode_transfer, ZERO_ADDRESS};
+use crate::verify::{VerificationRecord, KIND_CONTRACT, STATUS_VERIFIED};
+use chrono::NaiveDate;
+use sqlx::postgres::{PgPool, PgPoolOptions};
+use thiserror::Error;
+
+/// Schema, applied statement by statement on startup
+const SCHEMA: &[&str] = &[
+    "CREATE TABLE IF NOT EXISTS strings (
+        number BIGSERIAL PRIMARY KEY,
+        hash TEXT NOT NULL UNIQUE,
+        creator TEXT NOT NULL,
+        string_type TEXT NOT NULL,
+        transactions INTEGER NOT NULL DEFAULT 0,
+        status TEXT NOT NULL DEFAULT 'Pending',
+        anchor_id TEXT,
+        rejection_reason TEXT,
+        timestamp BIGINT NOT NULL
+    )",
+    "CREATE INDEX IF NOT EXISTS strings_status_timestamp ON strings (status, timestamp)",
+    "CREATE INDEX IF NOT EXISTS strings_creator ON strings (creator, timestamp)",
+    "CREATE INDEX IF NOT EXISTS strings_hash_prefix ON strings (hash text_pattern_ops)",
+    "CREATE TABLE IF NOT EXISTS transactions (
+        seq BIGSERIAL PRIMARY KEY,
+        hash TEXT NOT NULL UNIQUE,
+        string_hash TEXT NOT NULL REFERENCES strings (hash),
+        from_address TEXT NOT NULL,
+        to_address TEXT,
+        value NUMERIC(78, 0) NOT NULL,
+        fee NUMERIC(78, 0) NOT NULL,
+        status TEXT NOT NULL,
+        timestamp BIGINT NOT NULL
+    )",
+    "ALTER TABLE transactions ADD COLUMN IF NOT EXISTS input TEXT",
+    "CREATE INDEX IF NOT EXISTS transactions_from ON transactions (from_address, seq)",
+    "CREATE INDEX IF NOT EXISTS transactions_to ON transactions (to_address, seq)",
+    "CREATE INDEX IF NOT EXISTS transactions_timestamp ON transactions (timestamp)",
+    "CREATE INDEX IF NOT EXISTS transactions_hash_prefix ON transactions (hash text_pattern_ops)",
+    "CREATE TABLE IF NOT EXISTS accounts (
+        address TEXT PRIMARY KEY,
+        balance NUMERIC(78, 0) NOT NULL DEFAULT 0,
+        transaction_count BIGINT NOT NULL DEFAULT 0,
+        first_seen BIGINT NOT NULL,
+        last_seen BIGINT NOT NULL
+    )",
+    "CREATE INDEX IF NOT EXISTS accounts_address_prefix ON accounts (address text_pattern_ops)",
+    "CREATE TABLE IF NOT EXISTS validators (
+        address TEXT PRIMARY KEY,
+        name TEXT NOT NULL,
+        stake BIGINT NOT NULL DEFAULT 0,
+        strings_produced BIGINT NOT NULL DEFAULT 0,
+        active BOOLEAN NOT NULL DEFAULT TRUE,
+        last_seen BIGINT NOT NULL DEFAULT 0
+    )",
+    "ALTER TABLE validators ADD COLUMN IF NOT EXISTS missed_rounds BIGINT NOT NULL DEFAULT 0",
+    "ALTER TABLE validators ADD COLUMN IF NOT EXISTS first_heartbeat BIGINT",
+    "CREATE INDEX IF NOT EXISTS validators_stake ON validators (stake DESC, address DESC)",
+    "CREATE TABLE IF NOT EXISTS missed_rounds (
+        round BIGINT PRIMARY KEY,
+        producer TEXT NOT NULL,
+        timestamp BIGINT NOT NULL
+    )",
+    "CREATE INDEX IF NOT EXISTS missed_rounds_producer ON missed_rounds (producer, timestamp)",
+    "CREATE TABLE IF NOT EXISTS validator_heartbeats (
+        address TEXT NOT NULL,
+        slot BIGINT NOT NULL,
+        PRIMARY KEY (address, slot)
+    )",
+    "CREATE TABLE IF NOT EXISTS validator_rewards (
+        address TEXT NOT NULL,
+        epoch BIGINT NOT NULL,
+        proposer_rewards NUMERIC(78, 0) NOT NULL,
+        testimony_rewards NUMERIC(78, 0) NOT NULL,
+        performance_multiplier DOUBLE PRECISION NOT NULL,
+        final_reward NUMERIC(78, 0) NOT NULL,
+        timestamp BIGINT NOT NULL,
+        PRIMARY KEY (address, epoch)
+    )",
+    "CREATE TABLE IF NOT EXISTS token_transfers (
+        seq BIGSERIAL PRIMARY KEY,
+        transaction_hash TEXT NOT NULL,
+        log_index INTEGER NOT NULL,
+        token TEXT NOT NULL,
+        from_address TEXT NOT NULL,
+        to_address TEXT NOT NULL,
+        value NUMERIC(78, 0) NOT NULL,
+        timestamp BIGINT NOT NULL,
+        UNIQUE (transaction_hash, log_index)
+    )",
+    "CREATE INDEX IF NOT EXISTS token_transfers_token ON token_transfers (token, seq)",
+    "CREATE TABLE IF NOT EXISTS token_balances (
+        token TEXT NOT NULL,
+        holder TEXT NOT NULL,
+        balance NUMERIC(78, 0) NOT NULL DEFAULT 0,
+        PRIMARY KEY (token, holder)
+    )",
+    "CREATE INDEX IF NOT EXISTS token_balances_rank ON token_balances (token, balance DESC, holder)",
+    "CREATE INDEX IF NOT EXISTS token_balances_holder ON token_balances (holder)",
+    "CREATE INDEX IF NOT EXISTS token_balances_token_prefix ON token_balances \
+     (token text_pattern_ops)",
+    "CREATE TABLE IF NOT EXISTS internal_transactions (
+        transaction_hash TEXT PRIMARY KEY,
+        kind TEXT NOT NULL,
+        contract TEXT NOT NULL,
+        reference TEXT NOT NULL,
+        amount NUMERIC(78, 0) NOT NULL,
+        timestamp BIGINT NOT NULL
+    )",
+    "CREATE TABLE IF NOT EXISTS rollups (
+        resolution BIGINT NOT NULL,
+        bucket BIGINT NOT NULL,
+        transactions BIGINT NOT NULL DEFAULT 0,
+        active_accounts BIGINT NOT NULL DEFAULT 0,
+        gas_used BIGINT NOT NULL DEFAULT 0,
+        price_sum DOUBLE PRECISION NOT NULL DEFAULT 0,
+        price_samples BIGINT NOT NULL DEFAULT 0,
+        PRIMARY KEY (resolution, bucket)
+    )",
+    "CREATE TABLE IF NOT EXISTS rollup_accounts (
+        resolution BIGINT NOT NULL,
+        bucket BIGINT NOT NULL,
+        address TEXT NOT NULL,
+        PRIMARY KEY (resolution, bucket, address)
+    )",
+    "CREATE TABLE IF NOT EXISTS testimonies (
+        string_hash TEXT NOT NULL,
+        agent_id TEXT NOT NULL,
+        verdict TEXT NOT NULL,
+        confidence DOUBLE PRECISION NOT NULL,
+        timestamp BIGINT NOT NULL,
+        PRIMARY KEY (string_hash, agent_id)
+    )",
+    "CREATE INDEX IF NOT EXISTS testimonies_agent ON testimonies (agent_id, timestamp)",
+    "CREATE TABLE IF NOT EXISTS verifications (
+        target TEXT PRIMARY KEY,
+        kind TEXT NOT NULL,
+        status TEXT NOT NULL,
+        name TEXT NOT NULL,
+        compiler_version TEXT,
+        metadata JSONB,
+        decoded JSONB,
+        message TEXT,
+        submitted_at BIGINT NOT NULL
+    )",
+    "CREATE TABLE IF NOT EXISTS api_keys (
+        prefix TEXT PRIMARY KEY,
+        key_hash TEXT NOT NULL UNIQUE,
+        owner TEXT NOT NULL,
+        plan TEXT NOT NULL,
+        created_at BIGINT NOT NULL,
+        revoked BOOLEAN NOT NULL DEFAULT FALSE
+    )",
+    "CREATE TABLE IF NOT EXISTS api_key_usage (
+        prefix TEXT NOT NULL REFERENCES api_keys (prefix),
+        day DATE NOT NULL,
+        requests BIGINT NOT NULL DEFAULT 0,
+        PRIMARY KEY (prefix, day)
+    )",
+];
+
+const STRING_COLUMNS: &str = "number, hash, creator, string_type, transactions, status, \
+     anchor_id, rejection_reason, timestamp";
+
+const TRANSACTION_COLUMNS: &str = "seq, hash, string_hash, from_address, to_address, \
+     value::TEXT AS value, fee::TEXT AS fee, status, timestamp, input";
+
+const TOKEN_TRANSFER_COLUMNS: &str = "seq, transaction_hash, log_index, token, from_address, \
+     to_address, value::TEXT AS value, timestamp";
+
+const ACCOUNT_COLUMNS: &str = "a.address, a.balance::TEXT AS balance, a.transaction_count, \
+     EXISTS (SELECT 1 FROM validators v WHERE v.address = a.address) AS is_validator, \
+     a.first_seen, a.last_seen";
+
+/// Database errors
+#[derive(Error, Debug)]
+pub enum DbError {
+    #[error("Database error: {0}")]
+    Sqlx(#[from] sqlx::Error),
+}
+
+/// Chain-wide counters for the stats endpoint
+#[derive(Clone, Debug, Default, sqlx::FromRow)]
+pub struct ChainCounts {
+    pub strings: i64,
+    pub transactions: i64,
+    pub active_validators: i64,
+    pub accounts: i64,
+    /// Transactions in the last minute
+    pub recent_transactions: i64,
+}
+
+/// PostgreSQL store
+#[derive(Clone)]
+pub struct Database {
+    pool: PgPool,
+}
+
+impl Database {
+    /// Connect and apply the schema
+    pub async fn connect(url: &str, max_connections: u32) -> Result<Self, DbError> {
+        let pool = PgPoolOptions::new()
+            .max_connections(max_connections)
+            .connect(url)
+            .await?;
+        let db = Self { pool };
+        db.migrate().await?;
+        Ok(db)
+    }
+
+    /// Wrap an existing pool (the schema is not applied)
+    pub fn from_pool(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Create tables and indexes that do not exist yet
+    pub async fn migrate(&self) -> Result<(), DbError> {
+        for statement in SCHEMA {
+            sqlx::query(statement).execute(&self.pool).await?;
+        }
+        Ok(())
+    }
+
+    /// Strings, newest first
+    pub async fn list_strings(
+        &self,
+        cursor: Option<&Cursor>,
+        limit: i64,
+    ) -> Result<Vec<StringRow>, DbError> {
+        let sql = format!(
+            "SELECT {STRING_COLUMNS} FROM strings \
+             WHERE ($1::BIGINT IS NULL OR number < $1) \
+             ORDER BY number DESC LIMIT $2"
+        );
+        Ok(sqlx::query_as(&sql)
+            .bind(cursor.map(|c| c.key))
+            .bind(limit)
+            .fetch_all(&self.pool)
+            .await?)
+    }
+
+    /// Rejected strings, newest first
+    pub async fn rejected_strings(
+        &self,
+        cursor: Option<&Cursor>,
+        limit: i64,
+    ) -> Result<Vec<StringRow>, DbError> {
+        let sql = format!(
+            "SELECT {STRING_COLUMNS} FROM strings \
+             WHERE status = $1 AND ($2::BIGINT IS NULL OR number < $2) \
+             ORDER BY number DESC LIMIT $3"
+        );
+        Ok(sqlx::query_as(&sql)
+            .bind(STATUS_REJECTED)
+            .bind(cursor.map(|c| c.key))
+            .bind(limit)
+            .fetch_all(&self.pool)
+            .await?)
+    }
+
+    /// String by hash or number
+    pub async fn get_string(&self, id: &str) -> Result<Option<StringRow>, DbError> {
+        let number = id.parse::<i64>().ok();
+        let sql = format!(
+            "SELECT {STRING_COLUMNS} FROM strings \
+             WHERE ($1::BIGINT IS NOT NULL AND number = $1) OR hash = $2"
+        );
+        Ok(sqlx::query_as(&sql)
+            .bind(number)
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await?)
+    }
+
+    /// Transactions, newest first
+    pub async fn list_transactions(
+        &self,
+        cursor: Option<&Cursor>,
+        limit: i64,
+    ) -> Result<Vec<TransactionRow>, DbError> {
+        let sql = format!(
+            "SELECT {TRANSACTION_COLUMNS} FROM transactions \
+             WHERE ($1::BIGINT IS NULL OR seq < $1) \
+             ORDER BY seq DESC LIMIT $2"
+        );
+        Ok(sqlx::query_as(&sql)
+            .bind(cursor.map(|c| c.key))
+            .bind(limit)
+            .fetch_all(&self.pool)
+            .await?)
+    }
+
+    /// Transactions included in a string
+    pub async fn string_transactions(&self, hash: &str) -> Result<Vec<TransactionRow>, DbError> {
+        let sql = format!(
+            "SELECT {TRANSACTION_COLUMNS} FROM transactions WHERE string_hash = $1 ORDER BY seq"
+        );
+        Ok(sqlx::query_as(&sql)
+            .bind(hash)
+            .fetch_all(&self.pool)
+            .await?)
+    }
+
+    /// Strings by hash, in no particular order
+    pub async fn strings_by_hash(&self, hashes: &[String]) -> Result<Vec<StringRow>, DbError> {
+        let sql = format!("SELECT {STRING_COLUMNS} FROM strings WHERE hash = ANY($1)");
+        Ok(sqlx::query_as(&sql)
+            .bind(hashes)
+            .fetch_all(&self.pool)
+            .await?)
+    }
+
+    /// Testimonies on any of `hashes`, oldest first
+    pub async fn testimonies_for(&self, hashes: &[String]) -> Result<Vec<TestimonyRow>, DbError> {
+        Ok(sqlx::query_as(
+            "SELECT string_hash, agent_id, verdict, confidence, timestamp FROM testimonies \
+             WHERE string_hash = ANY($1) ORDER BY timestamp, agent_id",
+        )
+        .bind(hashes)
+        .fetch_all(&self.pool)
+        .await?)
+    }
+
+    /// Transaction by hash
+    pub async fn get_transaction(&self, hash: &str) -> Result<Option<TransactionRow>, DbError> {
+        let sql = format!("SELECT {TRANSACTION_COLUMNS} FROM transactions WHERE hash = $1");
+        Ok(sqlx::query_as(&sql)
+            .bind(hash)
+            .fetch_optional(&self.pool)
+            .await?)
+    }
+
+    /// Account by address
+    pub async fn get_account(&self, address: &str) -> Result<Option<AccountRow>, DbError> {
+        let sql = format!("SELECT {ACCOUNT_COLUMNS} FROM accounts a WHERE a.address = $1");
+        Ok(sqlx::query_as(&sql)
+            .bind(address)
+            .fetch_optional(&self.pool)
+            .await?)
+    }
+
+    /// Transactions sent or received by an account, newest first
+    pub async fn account_transactions(
+        &self,
+        address: &str,
+        cursor: Option<&Cursor>,
+        limit: i64,
+    ) -> Result<Vec<TransactionRow>, DbError> {
+        let sql = format!(
+            "SELECT {TRANSACTION_COLUMNS} FROM transactions \
+             WHERE (from_address = $1 OR to_address = $1) \
+             AND ($2::BIGINT IS NULL OR seq < $2) \
+             ORDER BY seq DESC LIMIT $3"
+        );
+        Ok(sqlx::query_as(&sql)
+            .bind(address)
+            .bind(cursor.map(|c| c.key))
+            .bind(limit)
+            .fetch_all(&self.pool)
+            .await?)
+    }
+
+    /// Validators by stake, highest first
+    pub async fn list_validators(
+        &self,
+        cursor: Option<&Cursor>,
+        limit: i64,
+    ) -> Result<Vec<ValidatorRow>, DbError> {
+        let (since, now) = uptime_window(chrono::Utc::now().timestamp() - DAY);
+        let sql = format!(
+            "SELECT {VALIDATOR_COLUMNS}, {} AS uptime_percent FROM validators v \
+             WHERE ($1::BIGINT IS NULL OR (stake, address) < ($1, $2)) \
+             ORDER BY stake DESC, address DESC LIMIT $3",
+            uptime_expr("$4", "$5")
+        );
+        Ok(sqlx::query_as(&sql)
+            .bind(cursor.map(|c| c.key))
+            .bind(cursor.and_then(|c| c.tie.clone()).unwrap_or_default())
+            .bind(limit)
+            .bind(since)
+            .bind(now)
+            .fetch_all(&self.pool)
+            .await?)
+    }
+
+    /// Validator by address
+    pub async fn get_validator(&self, address: &str) -> Result<Option<ValidatorRow>, DbError> {
+        let (since, now) = uptime_window(chrono::Utc::now().timestamp() - DAY);
+        let sql = format!(
+            "SELECT {VALIDATOR_COLUMNS}, {} AS uptime_percent FROM validators v \
+             WHERE address = $1",
+            uptime_expr("$2", "$3")
+        );
+        Ok(sqlx::query_as(&sql)
+            .bind(address)
+            .bind(since)
+            .bind(now)
+            .fetch_optional(&self.pool)
+            .await?)
+    }
+
+    /// Activity of a validator since `since`; `None` if unknown
+    pub async fn validator_performance(
+        &self,
+        address: &str,
+        since: i64,
+    ) -> Result<Option<PerformanceRow>, DbError> {
+        let (since_slot, now_slot) = uptime_window(since);
+        let sql = format!(
+            "SELECT \
+             (SELECT COUNT(*) FROM strings WHERE creator = $1 AND timestamp >= $2) \
+             AS strings_produced, \
+             (SELECT COUNT(*) FROM missed_rounds WHERE producer = $1 AND timestamp >= $2) \
+             AS missed_rounds, \
+             {} AS uptime_percent, \
+             l.samples AS latency_samples, l.percentiles AS latency_percentiles \
+             FROM validators v, LATERAL ( \
+             SELECT COUNT(*) AS samples, \
+             percentile_cont(ARRAY[0.5, 0.9, 0.99]) WITHIN GROUP \
+             (ORDER BY (GREATEST(t.timestamp - s.timestamp, 0) * 1000)::DOUBLE PRECISION) \
+             AS percentiles \
+             FROM testimonies t JOIN strings s ON s.hash = t.string_hash \
+             WHERE t.agent_id = $1 AND t.timestamp >= $2) l \
+             WHERE v.address = $1",
+            uptime_expr("$3", "$4")
+        );
+        Ok(sqlx::query_as(&sql)
+            .bind(address)
+            .bind(since)
+            .bind(since_slot)
+            .bind(now_slot)
+            .fetch_optional(&self.pool)
+            .await?)
+    }
+
+    /// Epoch rewards of a validator, newest first
+    pub async fn validator_rewards(
+        &self,
+        address: &str,
+        cursor: Option<&Cursor>,
+        limit: i64,
+    ) -> Result<Vec<RewardRow>, DbError> {
+        Ok(sqlx::query_as(
+            "SELECT epoch, proposer_rewards::TEXT, testimony_rewards::TEXT, \
+             performance_multiplier, final_reward::TEXT, timestamp FROM validator_rewards \
+             WHERE address = $1 AND ($2::BIGINT IS NULL OR epoch < $2) \
+             ORDER BY epoch DESC LIMIT $3",
+        )
+        .bind(address)
+        .bind(cursor.map(|c| c.key))
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?)
+    }
+
+    /// Sum of all epoch rewards paid to a validator
+    pub async fn validator_reward_total(&self, address: &str) -> Result<String, DbError> {
+        let (total,): (String,) = sqlx::query_as(
+            "SELECT COALESCE(SUM(final_reward), 0)::TEXT FROM validator_rewards \
+             WHERE address = $1",
+        )
+        .bind(address)
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(total)
+    }
+
+    /// Drop heartbeat slots older than `before`
+    pub async fn prune_heartbeats(&self, before: i64) -> Result<u64, DbError> {
+        let result = sqlx::query("DELETE FROM validator_heartbeats WHERE slot < $1")
+            .bind(before)
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Entities whose hash or address starts with `prefix`, at most `limit` per kind
+    pub async fn search_identifiers(
+        &self,
+        prefix: &str,
+        limit: i64,
+    ) -> Result<Vec<SearchRow>, DbError> {
+        Ok(sqlx::query_as(
+            "(SELECT 'transaction' AS kind, hash AS id, hash AS label FROM transactions \
+              WHERE hash LIKE $1 ORDER BY hash LIMIT $2) \
+             UNION ALL (SELECT 'string', hash, 'String #' || number FROM strings \
+              WHERE hash LIKE $1 ORDER BY hash LIMIT $2) \
+             UNION ALL (SELECT 'account', address, address FROM accounts \
+              WHERE address LIKE $1 ORDER BY address LIMIT $2) \
+             UNION ALL (SELECT 'validator', address, name FROM validators \
+              WHERE address LIKE $1 ORDER BY address LIMIT $2) \
+             UNION ALL (SELECT DISTINCT 'token', token, token FROM token_balances \
+              WHERE token LIKE $1 ORDER BY token LIMIT $2) \
+             UNION ALL (SELECT 'contract', target, name FROM verifications \
+              WHERE kind = $3 AND status = $4 AND target LIKE $1 ORDER BY target LIMIT $2)",
+        )
+        .bind(format!("{}%", prefix))
+        .bind(limit)
+        .bind(KIND_CONTRACT)
+        .bind(STATUS_VERIFIED)
+        .fetch_all(&self.pool)
+        .await?)
+    }
+
+    /// Validators and verified contracts whose name matches any `ILIKE` pattern
+    pub async fn search_names(
+        &self,
+        patterns: &[String],
+        limit: i64,
+    ) -> Result<Vec<SearchRow>, DbError> {
+        // Over-fetch: candidates are ranked and filtered by the caller
+        Ok(sqlx::query_as(
+            "(SELECT 'validator' AS kind, address AS id, name AS label FROM validators \
+              WHERE name ILIKE ANY($1) LIMIT $2) \
+             UNION ALL (SELECT 'contract', target, name FROM verifications \
+              WHERE kind = $3 AND status = $4 AND name ILIKE ANY($1) LIMIT $2)",
+        )
+        .bind(patterns)
+        .bind(limit * 4)
+        .bind(KIND_CONTRACT)
+        .bind(STATUS_VERIFIED)
+        .fetch_all(&self.pool)
+        .await?)
+    }
+
+    /// Total stake of active validators
+    pub async fn total_stake(&self) -> Result<i64, DbError> {
+        let (stake,): (Option<i64>,) =
+            sqlx::query_as("SELECT SUM(stake)::BIGINT FROM validators WHERE active")
+                .fetch_one(&self.pool)
+                .await?;
+        Ok(stake.unwrap_or(0))
+    }
+
+    /// Chain-wide counters
+    pub async fn counts(&self) -> Result<ChainCounts, DbError> {
+        let since = chrono::Utc::now().timestamp() - 60;
+        Ok(sqlx::query_as(
+            "SELECT \
+             (SELECT COUNT(*) FROM strings) AS strings, \
+             (SELECT COUNT(*) FROM transactions) AS transactions, \
+             (SELECT COUNT(*) FROM validators WHERE active) AS active_validators, \
+             (SELECT COUNT(*) FROM accounts) AS accounts, \
+             (SELECT COUNT(*) FROM transactions WHERE timestamp >= $1) AS recent_transactions",
+        )
+        .bind(since)
+        .fetch_one(&self.pool)
+        .await?)
+    }
+
+    /// Transfers of a token, newest first
+    pub async fn token_transfers(
+        &self,
+        token: &str,
+        cursor: Option<&Cursor>,
+        limit: i64,
+    ) -> Result<Vec<TokenTransferRow>, DbError> {
+        let sql = format!(
+            "SELECT {TOKEN_TRANSFER_COLUMNS} FROM token_transfers \
+             WHERE token = $1 AND ($2::BIGINT IS NULL OR seq < $2) \
+             ORDER BY seq DESC LIMIT $3"
+        );
+        Ok(sqlx::query_as(&sql)
+            .bind(token)
+            .bind(cursor.map(|c| c.key))
+            .bind(limit)
+            .fetch_all(&self.pool)
+            .await?)
+    }
+
+    /// Token transfers emitted by a transaction
+    pub async fn transaction_token_transfers(
+        &self,
+        hash: &str,
+    ) -> Result<Vec<TokenTransferRow>, DbError> {
+        let sql = format!(
+            "SELECT {TOKEN_TRANSFER_COLUMNS} FROM token_transfers \
+             WHERE transaction_hash = $1 ORDER BY log_index"
+        );
+        Ok(sqlx::query_as(&sql)
+            .bind(hash)
+            .fetch_all(&self.pool)
+            .await?)
+    }
+
+    /// Holders with a positive balance, largest first, skipping `offset`
+    pub async fn token_holders(
+        &self,
+        token: &str,
+        offset: i64,
+        limit: i64,
+    ) -> Result<Vec<TokenHolderRow>, DbError> {
+        Ok(sqlx::query_as(
+            "SELECT holder, balance::TEXT AS balance, \
+             ROUND(balance * 100 / SUM(balance) OVER (), 4)::TEXT AS percentage \
+             FROM token_balances WHERE token = $1 AND balance > 0 \
+             ORDER BY balance DESC, holder LIMIT $2 OFFSET $3",
+        )
+        .bind(token)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.pool)
+        .await?)
+    }
+
+    /// Holder and transfer counts of a token
+    pub async fn token_stats(&self, token: &str) -> Result<TokenStatsRow, DbError> {
+        Ok(sqlx::query_as(
+            "SELECT \
+             (SELECT COUNT(*) FROM token_balances WHERE token = $1 AND balance > 0) AS holders, \
+             (SELECT COUNT(*) FROM token_transfers WHERE token = $1) AS transfers, \
+             (SELECT COALESCE(SUM(balance), 0)::TEXT FROM token_balances \
+              WHERE token = $1 AND balance > 0) AS circulating",
+        )
+        .bind(token)
+        .fetch_one(&self.pool)
+        .await?)
+    }
+
+    /// Tokens held by an account
+    pub async fn account_token_balances(
+        &self,
+        address: &str,
+    ) -> Result<Vec<TokenBalanceRow>, DbError> {
+        Ok(sqlx::query_as(
+            "SELECT token, balance::TEXT AS balance FROM token_balances \
+             WHERE holder = $1 AND balance > 0 ORDER BY token",
+        )
+        .bind(address)
+        .fetch_all(&self.pool)
+        .await?)
+    }
+
+    /// Bridge call decoded from a transaction
+    pub async fn internal_transaction(
+        &self,
+        hash: &str,
+    ) -> Result<Option<InternalTransactionRow>, DbError> {
+        Ok(sqlx::query_as(
+            "SELECT transaction_hash, kind, contract, reference, amount::TEXT AS amount, \
+             timestamp FROM internal_transactions WHERE transaction_hash = $1",
+        )
+        .bind(hash)
+        .fetch_optional(&self.pool)
+        .await?)
+    }
+
+    /// Rollups at `resolution` with bucket starts in `first..=last`, oldest first
+    pub async fn rollups(
+        &self,
+        resolution: i64,
+        first: i64,
+        last: i64,
+    ) -> Result<Vec<RollupRow>, DbError> {
+        Ok(sqlx::query_as(
+            "SELECT bucket, transactions, active_accounts, gas_used, price_sum, price_samples \
+             FROM rollups WHERE resolution = $1 AND bucket BETWEEN $2 AND $3 ORDER BY bucket",
+        )
+        .bind(resolution)
+        .bind(first)
+        .bind(last)
+        .fetch_all(&self.pool)
+        .await?)
+    }
+
+    /// Add a FAT price sample to the rollups
+    pub async fn record_price(&self, timestamp: i64, price: f64) -> Result<(), DbError> {
+        for resolution in RESOLUTIONS {
+            sqlx::query(
+                "INSERT INTO rollups (resolution, bucket, price_sum, price_samples) \
+                 VALUES ($1, $2, $3, 1) ON CONFLICT (resolution, bucket) DO UPDATE SET \
+                 price_sum = rollups.price_sum + EXCLUDED.price_sum, \
+                 price_samples = rollups.price_samples + 1",
+            )
+            .bind(resolution)
+            .bind(bucket_start(timestamp, resolution))
+            .bind(price)
+            .execute(&self.pool)
+            .await?;
+        }
+        Ok(())
+    }
+
+    /// Drop active-account sets of buckets that closed before `before`
+    pub async fn prune_rollup_accounts(&self, before: i64) -> Result<u64, DbError> {
+        let result = sqlx::query("DELETE FROM rollup_accounts WHERE bucket + resolution < $1")
+            .bind(before)
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Store a verification result unless it would replace a successful one
+    pub async fn upsert_verification(&self, record: &VerificationRecord) -> Result<bool, DbError> {
+        let result = sqlx::query(
+            "INSERT INTO verifications (target, kind, status, name, compiler_version, metadata, \
+             decoded, message, submitted_at) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9) \
+             ON CONFLICT (target) DO UPDATE SET kind = EXCLUDED.kind, status = EXCLUDED.status, \
+             name = EXCLUDED.name, compiler_version = EXCLUDED.compiler_version, \
+             metadata = EXCLUDED.metadata, decoded = EXCLUDED.decoded, \
+             message = EXCLUDED.message, submitted_at = EXCLUDED.submitted_at \
+             WHERE verifications.status <> $10 OR EXCLUDED.status = $10",
+        )
+        .bind(&record.target)
+        .bind(&record.kind)
+        .bind(&record.status)
+        .bind(&record.name)
+        .bind(&record.compiler_version)
+        .bind(&record.metadata)
+        .bind(&record.decoded)
+        .bind(&record.message)
+        .bind(record.submitted_at)
+        .bind(STATUS_VERIFIED)
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Verification of a contract address or string hash
+    pub async fn get_verification(
+        &self,
+        target: &str,
+    ) -> Result<Option<VerificationRecord>, DbError> {
+        Ok(sqlx::query_as(
+            "SELECT target, kind, status, name, compiler_version, metadata, decoded, message, \
+             submitted_at FROM verifications WHERE target = $1",
+        )
+        .bind(target)
+        .fetch_optional(&self.pool)
+        .await?)
+    }
+
+    /// Active API keys
+    pub async fn api_keys(&self) -> Result<Vec<ApiKeyRecord>, DbError> {
+        Ok(sqlx::query_as(
+            "SELECT prefix, key_hash, owner, plan, created_at FROM api_keys WHERE NOT revoked",
+        )
+        .fetch_all(&self.pool)
+        .await?)
+    }
+
+    /// Store a newly issued key
+    pub async fn insert_api_key(&self, key: &ApiKeyRecord) -> Result<(), DbError> {
+        sqlx::query(
+            "INSERT INTO api_keys (prefix, key_hash, owner, plan, created_at) \
+             VALUES ($1, $2, $3, $4, $5)",
+        )
+        .bind(&key.prefix)
+        .bind(&key.key_hash)
+        .bind(&key.owner)
+        .bind(&key.plan)
+        .bind(key.created_at)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Revoke a key; `false` if unknown or already revoked
+    pub async fn revoke_api_key(&self, prefix: &str) -> Result<bool, DbError> {
+        let result =
+            sqlx::query("UPDATE api_keys SET revoked = TRUE WHERE prefix = $1 AND NOT revoked")
+                .bind(prefix)
+                .execute(&self.pool)
+                .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Add `requests` to a key's count for `day`
+    pub async fn record_key_usage(
+        &self,
+        prefix: &str,
+        day: NaiveDate,
+        requests: i64,
+    ) -> Result<(), DbError> {
+        sqlx::query(
+            "INSERT INTO api_key_usage (prefix, day, requests) VALUES ($1, $2, $3) \
+             ON CONFLICT (prefix, day) DO UPDATE \
+             SET requests = api_key_usage.requests + EXCLUDED.requests",
+        )
+        .bind(prefix)
+        .bind(day)
+        .bind(requests)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Requests per key on `day`
+    pub async fn key_usage_on(&self, day: NaiveDate) -> Result<Vec<(String, i64)>, DbError> {
+        Ok(
+            sqlx::query_as("SELECT prefix, requests FROM api_key_usage WHERE day = $1")
+                .bind(day)
+                .fetch_all(&self.pool)
+                .await?,
+        )
+    }
+
+    /// Daily request counts of a key, most recent first
+    pub async fn key_usage_history(
+        &self,
+        prefix: &str,
+        days: i64,
+    ) -> Result<Vec<(NaiveDate, i64)>, DbError> {
+        Ok(sqlx::query_as(
+            "SELECT day, requests FROM api_key_usage WHERE prefix = $1 \
+             ORDER BY day DESC LIMIT $2",
+        )
+        .bind(prefix)
+        .bind(days)
+        .fetch_all(&self.pool)
+        .await?)
+    }
+}
+
+#[async_trait::async_trait]
+impl IndexStore for Database {
+    async fn insert_string(&self, string: &IndexedString) -> Result<bool, DbError> {
+        let mut tx = self.pool.begin().await?;
+
+        let inserted = sqlx::query(
+            "INSERT INTO strings (hash, creator, string_type, transactions, status, timestamp) \
+             VALUES ($1, $2, $3, $4, $5, $6) ON CONFLICT (hash) DO NOTHING",
+        )
+        .bind(&string.id)
+        .bind(&string.creator)
+        .bind(&string.string_type)
+        .bind(string.transactions.len() as i32)
+        .bind(STATUS_PENDING)
+        .bind(string.timestamp)
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+        if inserted == 0 {
+            // Re-delivered after a reconnect
+            return Ok(false);
+        }
+
+        sqlx::query(
+            "UPDATE validators SET strings_produced = strings_produced + 1, \
+             last_seen = GREATEST(last_seen, $2) WHERE address = $1",
+        )
+        .bind(&string.creator)
+        .bind(string.timestamp)
+        .execute(&mut *tx)
+        .await?;
+
+        let mut new_transactions = Vec::with_capacity(string.transactions.len());
+        for transaction in &string.transactions {
+            let inserted = sqlx::query(
+                "INSERT INTO transactions \
+                 (hash, string_hash, from_address, to_address, value, fee, status, timestamp, \
+                 input) \
+                 VALUES ($1, $2, $3, $4, $5::TEXT::NUMERIC, $6::TEXT::NUMERIC, $7, $8, $9) \
+                 ON CONFLICT (hash) DO NOTHING",
+            )
+            .bind(&transaction.hash)
+            .bind(&string.id)
+            .bind(&transaction.from)
+            .bind(&transaction.to)
+            .bind(&transaction.value)
+            .bind(&transaction.fee)
+            .bind(transaction.status())
+            .bind(transaction.timestamp)
+            .bind(&transaction.input)
+            .execute(&mut *tx)
+            .await?
+            .rows_affected();
+            if inserted != 1 {
+                // Already indexed under another string; its effects are applied
+                continue;
+            }
+            new_transactions.push(transaction);
+
+            // Failed transactions still pay the fee
+            let (debit, credit) = if transaction.success {
+                (transaction.value.as_str(), transaction.value.as_str())
+            } else {
+                ("0", "0")
+            };
+            apply_account(
+                &mut tx,
+                &transaction.from,
+                debit,
+                &transaction.fee,
+                "0",
+                transaction.timestamp,
+            )
+            .await?;
+            if let Some(to) = &transaction.to {
+                apply_account(&mut tx, to, "0", "0", credit, transaction.timestamp).await?;
+            }
+            if transaction.success {
+                apply_token_activity(&mut tx, transaction).await?;
+            }
+        }
+        apply_rollups(&mut tx, &new_transactions).await?;
+
+        tx.commit().await?;
+        Ok(true)
+    }
+
+    async fn finalize(
+        &self,
+        anchor_id: &str,
+        string_ids: &[String],
+        up_to: i64,
+    ) -> Result<u64, DbError> {
+        let result = if string_ids.is_empty() {
+            sqlx::query(
+                "UPDATE strings SET status = $1, anchor_id = $2 \
+                 WHERE status = $3 AND timestamp <= $4",
+            )
+            .bind(STATUS_FINAL)
+            .bind(anchor_id)
+            .bind(STATUS_PENDING)
+            .bind(up_to)
+            .execute(&self.pool)
+            .await?
+        } else {
+            sqlx::query(
+                "UPDATE strings SET status = $1, anchor_id = $2 \
+                 WHERE status = $3 AND hash = ANY($4)",
+            )
+            .bind(STATUS_FINAL)
+            .bind(anchor_id)
+            .bind(STATUS_PENDING)
+            .bind(string_ids)
+            .execute(&self.pool)
+            .await?
+        };
+        Ok(result.rows_affected())
+    }
+
+    async fn reject(&self, string_id: &str, reason: &str) -> Result<bool, DbError> {
+        let result =
+            sqlx::query("UPDATE strings SET status = $1, rejection_reason = $2 WHERE hash = $3")
+                .bind(STATUS_REJECTED)
+                .bind(reason)
+                .bind(string_id)
+                .execute(&self.pool)
+                .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn upsert_validator(&self, validator: &IndexedValidator) -> Result<(), DbError> {
+        sqlx::query(
+            "INSERT INTO validators (address, name, stake, active, last_seen) \
+             VALUES ($1, $2, $3, $4, $5) \
+             ON CONFLICT (address) DO UPDATE SET name = EXCLUDED.name, \
+             stake = EXCLUDED.stake, active = EXCLUDED.active, \
+             last_seen = GREATEST(validators.last_seen, EXCLUDED.last_seen)",
+        )
+        .bind(&validator.address)
+        .bind(&validator.name)
+        .bind(validator.stake)
+        .bind(validator.active)
+        .bind(validator.timestamp)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn record_missed_round(
+        &self,
+        round: u64,
+        producer: &str,
+        timestamp: i64,
+    ) -> Result<bool, DbError> {
+        let mut tx = self.pool.begin().await?;
+        let inserted = sqlx::query(
+            "INSERT INTO missed_rounds (round, producer, timestamp) VALUES ($1, $2, $3) \
+             ON CONFLICT (round) DO NOTHING",
+        )
+        .bind(round as i64)
+        .bind(producer)
+        .bind(timestamp)
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+        if inserted == 0 {
+            return Ok(false);
+        }
+        sqlx::query("UPDATE validators SET missed_rounds = missed_rounds + 1 WHERE address = $1")
+            .bind(producer)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+        Ok(true)
+    }
+
+    async fn record_heartbeat(&self, validator: &str, timestamp: i64) -> Result<(), DbError> {
+        let slot = heartbeat_slot(timestamp);
+        sqlx::query(
+            "INSERT INTO validator_heartbeats (address, slot) VALUES ($1, $2) \
+             ON CONFLICT (address, slot) DO NOTHING",
+        )
+        .bind(validator)
+        .bind(slot)
+        .execute(&self.pool)
+        .await?;
+        sqlx::query(
+            "UPDATE validators SET first_heartbeat = LEAST(COALESCE(first_heartbeat, $2), $2), \
+             last_seen = GREATEST(last_seen, $3) WHERE address = $1",
+        )
+        .bind(validator)
+        .bind(slot)
+        .bind(timestamp)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn insert_reward(&self, reward: &IndexedReward) -> Result<bool, DbError> {
+        let result = sqlx::query(
+            "INSERT INTO validator_rewards (address, epoch, proposer_rewards, testimony_rewards, \
+             performance_multiplier, final_reward, timestamp) \
+             VALUES ($1, $2, $3::TEXT::NUMERIC, $4::TEXT::NUMERIC, $5, $6::TEXT::NUMERIC, $7) \
+             ON CONFLICT (address, epoch) DO NOTHING",
+        )
+        .bind(&reward.validator)
+        .bind(reward.epoch as i64)
+        .bind(&reward.proposer_rewards)
+        .bind(&reward.testimony_rewards)
+        .bind(reward.performance_multiplier)
+        .bind(&reward.final_reward)
+        .bind(reward.timestamp)
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn insert_testimony(&self, testimony: &IndexedTestimony) -> Result<bool, DbError> {
+        let result = sqlx::query(
+            "INSERT INTO testimonies (string_hash, agent_id, verdict, confidence, timestamp) \
+             VALUES ($1, $2, $3, $4, $5) ON CONFLICT (string_hash, agent_id) DO NOTHING",
+        )
+        .bind(&testimony.string_id)
+        .bind(&testimony.agent_id)
+        .bind(&testimony.verdict)
+        .bind(testimony.confidence)
+        .bind(testimony.timestamp)
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+}
+
+/// Columns of [`ValidatorRow`] other than the computed uptime
+const VALIDATOR_COLUMNS: &str =
+    "v.address, v.name, v.stake, v.strings_produced, v.missed_rounds, v.active, v.last_seen";
+
+/// First and current heartbeat slot of a window starting at `since`
+fn uptime_window(since: i64) -> (i64, i64) {
+    (
+        heartbeat_slot(since),
+        heartbeat_slot(chrono::Utc::now().timestamp()),
+    )
+}
+
+/// Uptime percent of validator `v` between two slot parameters: slots
+/// with a heartbeat over slots since the later of the window start and
+/// the first heartbeat. NULL before the first heartbeat.
+fn uptime_expr(since: &str, now: &str) -> String {
+    format!(
+        "CASE WHEN v.first_heartbeat IS NULL THEN NULL ELSE LEAST(100.0, 100.0 * \
+         (SELECT COUNT(*) FROM validator_heartbeats h \
+         WHERE h.address = v.address AND h.slot >= {since}) \
+         / GREATEST(({now} - GREATEST({since}, v.first_heartbeat)) / {HEARTBEAT_SLOT} + 1, 1)\
+         )::DOUBLE PRECISION END"
+    )
+}
+
+/// Record decoded token transfers and bridge calls, updating holder balances
+async fn apply_token_activity(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    transaction: &IndexedTransaction,
+) -> Result<(), DbError> {
+    for (index, log) in transaction.logs.iter().enumerate() {
+        let Some(transfer) = decode_transfer(log, index as i32) else {
+            continue;
+        };
+        let inserted = sqlx::query(
+            "INSERT INTO token_transfers \
+             (transaction_hash, log_index, token, from_address, to_address, value, timestamp) \
+             VALUES ($1, $2, $3, $4, $5, $6::TEXT::NUMERIC, $7) \
+             ON CONFLICT (transaction_hash, log_index) DO NOTHING",
+        )
+        .bind(&transaction.hash)
+        .bind(transfer.log_index)
+        .bind(&transfer.token)
+        .bind(&transfer.from)
+        .bind(&transfer.to)
+        .bind(&transfer.value)
+        .bind(transaction.timestamp)
+        .execute(&mut **tx)
+        .await?
+        .rows_affected();
+        if inserted == 0 {
+            continue;
+        }
+
+        // The zero address mints and burns; it is not a holder
+        for (holder, sign) in [(&transfer.from, "-"), (&transfer.to, "")] {
+            if holder == ZERO_ADDRESS {
+                continue;
+            }
+            sqlx::query(
+                "INSERT INTO token_balances (token, holder, balance) \
+                 VALUES ($1, $2, ($3 || $4)::NUMERIC) ON CONFLICT (token, holder) \
+                 DO UPDATE SET balance = token_balances.balance + EXCLUDED.balance",
+            )
+            .bind(&transfer.token)
+            .bind(holder)
+            .bind(sign)
+            .bind(&transfer.value)
+            .execute(&mut **tx)
+            .await?;
+        }
+    }
+
+    if let (Some(input), Some(contract)) = (&transaction.input, &transaction.to) {
+        if let Some(call) = decode_bridge_call(input) {
+            sqlx::query(
+                "INSERT INTO internal_transactions \
+                 (transaction_hash, kind, contract, reference, amount, timestamp) \
+                 VALUES ($1, $2, $3, $4, $5::TEXT::NUMERIC, $6) \
+                 ON CONFLICT (transaction_hash) DO NOTHING",
+            )
+            .bind(&transaction.hash)
+            .bind(call.kind)
+            .bind(contract)
+            .bind(&call.reference)
+            .bind(&call.amount)
+            .bind(transaction.timestamp)
+            .execute(&mut **tx)
+            .await?;
+        }
+    }
+    Ok(())
+}
+
+/// Count newly indexed transactions, gas and active accounts into the rollups
+async fn apply_rollups(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    transactions: &[&IndexedTransaction],
+) -> Result<(), DbError> {
+    for transaction in transactions {
+        let addresses = std::iter::once(&transaction.from).chain(transaction.to.as_ref());
+        for resolution in RESOLUTIONS {
+            let bucket = bucket_start(transaction.timestamp, resolution);
+            let mut new_accounts = 0i64;
+            for address in addresses.clone() {
+                new_accounts += sqlx::query(
+                    "INSERT INTO rollup_accounts (resolution, bucket, address) \
+                     VALUES ($1, $2, $3) ON CONFLICT DO NOTHING",
+                )
+                .bind(resolution)
+                .bind(bucket)
+                .bind(address)
+                .execute(&mut **tx)
+                .await?
+                .rows_affected() as i64;
+            }
+            sqlx::query(
+                "INSERT INTO rollups (resolution, bucket, transactions, active_accounts, gas_used) \
+                 VALUES ($1, $2, 1, $3, $4) ON CONFLICT (resolution, bucket) DO UPDATE SET \
+                 transactions = rollups.transactions + 1, \
+                 active_accounts = rollups.active_accounts + EXCLUDED.active_accounts, \
+                 gas_used = rollups.gas_used + EXCLUDED.gas_used",
+            )
+            .bind(resolution)
+            .bind(bucket)
+            .bind(new_accounts)
+            .bind(transaction.gas_used)
+            .execute(&mut **tx)
+            .await?;
+        }
+    }
+    Ok(())
+}
+
+/// Debit `value + fee` from and credit `credit` to an account, creating it if new
+async fn apply_account(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    address: &str,
+    value: &str,
+    fee: &str,
+    credit: &str,
+    timestamp: i64,
+) -> Result<(), DbError> {
+    sqlx::query(
+        "INSERT INTO accounts (address, balance, transaction_count, first_seen, last_seen) \
+         VALUES ($1, $4::TEXT::NUMERIC - $2::TEXT::NUMERIC - $3::TEXT::NUMERIC, 1, $5, $5) \
+         ON CONFLICT (address) DO UPDATE SET \
+         balance = accounts.balance + EXCLUDED.balance, \
+         transaction_count = accounts.transaction_count + 1, \
+         first_seen = LEAST(accounts.first_seen, EXCLUDED.first_seen), \
+         last_seen = GREATEST(accounts.last_seen, EXCLUDED.last_seen)",
+    )
+    .bind(address)
+    .bind(value)
+    .bind(fee)
+    .bind(credit)
+    .bind(timestamp)
+    .execute(&mut **tx)
+    .await?;
+    Ok(())
+}
@@ -0,0 +1,171 @@
+//! Network economics
+//!
+//! Supply, inflation and staking APY from the rope-economics
+//! [`EconomicsOracle`], with active stake taken from indexed validators.
+//! Amounts are base units as decimal strings, like the rest of the API.
+
+use crate::api::ApiResult;
+use crate::db::DbError;
+use crate::AppState;
+use axum::extract::{Query, State};
+use axum::response::Json;
+use rope_economics::{EconomicsOracle, EconomicsSnapshot, SupplyPoint, ONE_FAT};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use utoipa::{IntoParams, ToSchema};
+
+/// Years projected by default and at most
+pub const DEFAULT_PROJECTION_YEARS: u32 = 20;
+pub const MAX_PROJECTION_YEARS: u32 = 100;
+
+/// Supply at one point of a projection
+#[derive(Clone, Debug, PartialEq, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SupplyCurvePoint {
+    pub timestamp: i64,
+    pub era: u64,
+    /// Base units, decimal
+    pub total_supply: String,
+    /// Base units, decimal
+    pub annual_emission: String,
+    pub inflation_percent: f64,
+}
+
+impl From<&SupplyPoint> for SupplyCurvePoint {
+    fn from(point: &SupplyPoint) -> Self {
+        Self {
+            timestamp: point.timestamp,
+            era: point.era,
+            total_supply: point.total_supply.to_string(),
+            annual_emission: point.annual_emission.to_string(),
+            inflation_percent: point.inflation_percent,
+        }
+    }
+}
+
+/// Current economics with a supply projection
+#[derive(Clone, Debug, PartialEq, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct EconomicsStats {
+    pub timestamp: i64,
+    /// Emission era, from 0
+    pub era: u64,
+    /// Base units, decimal
+    pub total_supply: String,
+    /// Base units, decimal
+    pub annual_emission: String,
+    /// Emission per anchor, base units, decimal
+    pub anchor_reward: String,
+    pub inflation_percent: f64,
+    /// Stake of active validators, base units, decimal
+    pub active_stake: String,
+    pub staked_percent: f64,
+    pub staking_apy: f64,
+    /// Start of the next era
+    pub next_halving: i64,
+    /// Yearly points from now
+    pub supply_curve: Vec<SupplyCurvePoint>,
+}
+
+impl EconomicsStats {
+    fn new(snapshot: &EconomicsSnapshot, curve: &[SupplyPoint]) -> Self {
+        Self {
+            timestamp: snapshot.timestamp,
+            era: snapshot.supply.era,
+            total_supply: snapshot.supply.total_supply.to_string(),
+            annual_emission: snapshot.supply.annual_emission.to_string(),
+            anchor_reward: snapshot.supply.anchor_reward.to_string(),
+            inflation_percent: snapshot.supply.inflation_percent,
+            active_stake: snapshot.active_stake.to_string(),
+            staked_percent: snapshot.staked_percent,
+            staking_apy: snapshot.staking_apy,
+            next_halving: snapshot.next_halving,
+            supply_curve: curve.iter().map(SupplyCurvePoint::from).collect(),
+        }
+    }
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ProjectionQuery {
+    /// Years to project (1-100, default 20)
+    years: Option<u32>,
+}
+
+/// `GET /api/v1/economics`
+#[utoipa::path(
+    get,
+    path = "/api/v1/economics",
+    tag = "Stats",
+    params(ProjectionQuery),
+    responses((status = 200, description = "Supply, inflation and staking APY", body = EconomicsStats))
+)]
+pub async fn economics(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<ProjectionQuery>,
+) -> ApiResult<EconomicsStats> {
+    let now = chrono::Utc::now().timestamp();
+    let years = query
+        .years
+        .unwrap_or(DEFAULT_PROJECTION_YEARS)
+        .clamp(1, MAX_PROJECTION_YEARS);
+    let snapshot = snapshot(&state, now).await?;
+    let curve = state.economics.supply_curve(now, years);
+    Ok(Json(EconomicsStats::new(&snapshot, &curve)))
+}
+
+/// Economics at `now` with the indexed active stake
+pub async fn snapshot(state: &AppState, now: i64) -> Result<EconomicsSnapshot, DbError> {
+    // Validator stake is indexed in whole FAT
+    let stake = state.db.total_stake().await?.max(0) as u128 * ONE_FAT;
+    Ok(state.economics.snapshot(now, stake))
+}
+
+/// Whole FAT with thousands separators, e.g. `10,000,000,000`
+pub fn format_fat(amount: u128) -> String {
+    let digits = (amount / ONE_FAT).to_string();
+    let mut formatted = String::with_capacity(digits.len() + digits.len() / 3);
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i) % 3 == 0 {
+            formatted.push(',');
+        }
+        formatted.push(digit);
+    }
+    formatted
+}
+
+/// Total FAT supply now, formatted
+pub fn total_supply_display(oracle: &EconomicsOracle) -> String {
+    format_fat(
+        oracle
+            .emission()
+            .total_supply_at(chrono::Utc::now().timestamp()),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rope_economics::{EmissionSchedule, GENESIS_SUPPLY};
+
+    #[test]
+    fn test_format_fat() {
+        assert_eq!(format_fat(GENESIS_SUPPLY), "10,000,000,000");
+        assert_eq!(format_fat(999 * ONE_FAT), "999");
+        assert_eq!(format_fat(1_000 * ONE_FAT + ONE_FAT / 2), "1,000");
+        assert_eq!(format_fat(0), "0");
+    }
+
+    #[test]
+    fn test_economics_stats() {
+        let oracle = EconomicsOracle::new(EmissionSchedule::new(0));
+        let snapshot = oracle.snapshot(0, 7_500_000_000 * ONE_FAT);
+        let curve = oracle.supply_curve(0, 2);
+        let stats = EconomicsStats::new(&snapshot, &curve);
+
+        assert_eq!(stats.total_supply, GENESIS_SUPPLY.to_string());
+        assert!((stats.staking_apy - 5.0).abs() < 1e-9);
+        assert_eq!(stats.supply_curve.len(), 3);
+        assert_eq!(stats.supply_curve[0].total_supply, stats.total_supply);
+    }
+}
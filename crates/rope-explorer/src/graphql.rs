@@ -0,0 +1,376 @@
+//! GraphQL endpoint
+//!
+//! Serves composite queries over the indexed data at [`GRAPHQL_PATH`]
+//! (`POST` for queries, `GET` for the GraphiQL playground), so a dashboard
+//! can fetch e.g. an account with its transactions and their testimonies
+//! in one round trip:
+//!
+//! ```graphql
+//! {
+//!   account(address: "0x...") {
+//!     balance
+//!     transactions(first: 10) {
+//!       items { hash value string { status } testimonies { agentId verdict } }
+//!       nextCursor
+//!     }
+//!   }
+//! }
+//! ```
+//!
+//! Lists take `first` / `after` and page like the REST endpoints. Nested
+//! strings and testimonies are batched per request through data loaders,
+//! and query depth and complexity are capped.
+
+use crate::api::CursorParams;
+use crate::db::Database;
+use crate::models::{
+    AccountRow, Cursor, Page, StringRow, TestimonyRow, TransactionRow, ValidatorRow,
+};
+use crate::{federation_directory, AppState, FederationSummary};
+use async_graphql::dataloader::{DataLoader, Loader};
+use async_graphql::http::GraphiQLSource;
+use async_graphql::{
+    ComplexObject, Context, EmptyMutation, EmptySubscription, Error, Object, Result, Schema,
+};
+use async_graphql_axum::{GraphQLRequest, GraphQLResponse};
+use axum::extract::State;
+use axum::response::Html;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Endpoint path
+pub const GRAPHQL_PATH: &str = "/api/v1/graphql";
+
+/// Maximum query nesting
+const MAX_DEPTH: usize = 8;
+
+/// Maximum query complexity (list fields count `first` times their children)
+const MAX_COMPLEXITY: usize = 5_000;
+
+/// Explorer schema
+pub type ExplorerSchema = Schema<QueryRoot, EmptyMutation, EmptySubscription>;
+
+/// Build the schema over `db`
+pub fn schema(db: Database) -> ExplorerSchema {
+    Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+        .data(db)
+        .limit_depth(MAX_DEPTH)
+        .limit_complexity(MAX_COMPLEXITY)
+        .finish()
+}
+
+/// `POST /api/v1/graphql`
+#[utoipa::path(
+    post,
+    path = "/api/v1/graphql",
+    tag = "GraphQL",
+    request_body(content = Object, description = "GraphQL request: `query`, `variables`, `operationName`"),
+    responses((status = 200, description = "GraphQL response with `data` and/or `errors`"))
+)]
+pub async fn graphql_handler(
+    State(state): State<Arc<AppState>>,
+    request: GraphQLRequest,
+) -> GraphQLResponse {
+    // Loaders cache per request so results never go stale between queries
+    let request = request
+        .into_inner()
+        .data(DataLoader::new(
+            StringLoader(state.db.clone()),
+            tokio::spawn,
+        ))
+        .data(DataLoader::new(
+            TestimonyLoader(state.db.clone()),
+            tokio::spawn,
+        ));
+    state.graphql.execute(request).await.into()
+}
+
+/// `GET /api/v1/graphql`
+pub async fn graphiql() -> Html<String> {
+    Html(GraphiQLSource::build().endpoint(GRAPHQL_PATH).finish())
+}
+
+/// Root query
+pub struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    /// Strings, newest first
+    #[graphql(complexity = "page_size(first) * child_complexity")]
+    async fn strings(
+        &self,
+        ctx: &Context<'_>,
+        first: Option<u32>,
+        after: Option<String>,
+    ) -> Result<Page<StringRow>> {
+        let params = page_params(first, after);
+        let rows = db(ctx)
+            .list_strings(params.cursor()?.as_ref(), params.fetch_limit())
+            .await
+            .map_err(internal)?;
+        Ok(Page::from_rows(rows, params.limit(), |row| {
+            Cursor::key(row.number)
+        }))
+    }
+
+    /// String by hash or number
+    async fn string(&self, ctx: &Context<'_>, id: String) -> Result<Option<StringRow>> {
+        db(ctx).get_string(&id).await.map_err(internal)
+    }
+
+    /// Transactions, newest first
+    #[graphql(complexity = "page_size(first) * child_complexity")]
+    async fn transactions(
+        &self,
+        ctx: &Context<'_>,
+        first: Option<u32>,
+        after: Option<String>,
+    ) -> Result<Page<TransactionRow>> {
+        let params = page_params(first, after);
+        let rows = db(ctx)
+            .list_transactions(params.cursor()?.as_ref(), params.fetch_limit())
+            .await
+            .map_err(internal)?;
+        Ok(Page::from_rows(rows, params.limit(), |row| {
+            Cursor::key(row.seq)
+        }))
+    }
+
+    /// Transaction by hash
+    async fn transaction(&self, ctx: &Context<'_>, hash: String) -> Result<Option<TransactionRow>> {
+        db(ctx).get_transaction(&hash).await.map_err(internal)
+    }
+
+    /// Account by address
+    async fn account(&self, ctx: &Context<'_>, address: String) -> Result<Option<AccountRow>> {
+        db(ctx).get_account(&address).await.map_err(internal)
+    }
+
+    /// Validators by stake, highest first
+    #[graphql(complexity = "page_size(first) * child_complexity")]
+    async fn validators(
+        &self,
+        ctx: &Context<'_>,
+        first: Option<u32>,
+        after: Option<String>,
+    ) -> Result<Page<ValidatorRow>> {
+        let params = page_params(first, after);
+        let rows = db(ctx)
+            .list_validators(params.cursor()?.as_ref(), params.fetch_limit())
+            .await
+            .map_err(internal)?;
+        Ok(Page::from_rows(rows, params.limit(), |row| {
+            Cursor::with_tie(row.stake, row.address.clone())
+        }))
+    }
+
+    /// Validator by address
+    async fn validator(&self, ctx: &Context<'_>, address: String) -> Result<Option<ValidatorRow>> {
+        db(ctx).get_validator(&address).await.map_err(internal)
+    }
+
+    /// Federations
+    async fn federations(&self) -> Vec<FederationSummary> {
+        federation_directory()
+    }
+
+    /// Federation by id
+    async fn federation(&self, id: String) -> Option<FederationSummary> {
+        federation_directory().into_iter().find(|f| f.id == id)
+    }
+}
+
+#[ComplexObject]
+impl StringRow {
+    /// Transactions carried by the string
+    async fn transaction_list(&self, ctx: &Context<'_>) -> Result<Vec<TransactionRow>> {
+        db(ctx)
+            .string_transactions(&self.hash)
+            .await
+            .map_err(internal)
+    }
+
+    /// Validator testimonies on the string
+    async fn testimonies(&self, ctx: &Context<'_>) -> Result<Vec<TestimonyRow>> {
+        testimonies(ctx, &self.hash).await
+    }
+}
+
+#[ComplexObject]
+impl TransactionRow {
+    /// String that carried the transaction
+    async fn string(&self, ctx: &Context<'_>) -> Result<Option<StringRow>> {
+        ctx.data_unchecked::<DataLoader<StringLoader>>()
+            .load_one(self.string_hash.clone())
+            .await
+            .map_err(internal)
+    }
+
+    /// Testimonies on the carrying string
+    async fn testimonies(&self, ctx: &Context<'_>) -> Result<Vec<TestimonyRow>> {
+        testimonies(ctx, &self.string_hash).await
+    }
+}
+
+#[ComplexObject]
+impl AccountRow {
+    /// Transactions sent or received, newest first
+    #[graphql(complexity = "page_size(first) * child_complexity")]
+    async fn transactions(
+        &self,
+        ctx: &Context<'_>,
+        first: Option<u32>,
+        after: Option<String>,
+    ) -> Result<Page<TransactionRow>> {
+        let params = page_params(first, after);
+        let rows = db(ctx)
+            .account_transactions(
+                &self.address,
+                params.cursor()?.as_ref(),
+                params.fetch_limit(),
+            )
+            .await
+            .map_err(internal)?;
+        Ok(Page::from_rows(rows, params.limit(), |row| {
+            Cursor::key(row.seq)
+        }))
+    }
+}
+
+/// Batches string lookups by hash
+pub struct StringLoader(Database);
+
+impl Loader<String> for StringLoader {
+    type Value = StringRow;
+    type Error = Arc<crate::db::DbError>;
+
+    async fn load(&self, keys: &[String]) -> Result<HashMap<String, StringRow>, Self::Error> {
+        let rows = self.0.strings_by_hash(keys).await.map_err(Arc::new)?;
+        Ok(rows
+            .into_iter()
+            .map(|row| (row.hash.clone(), row))
+            .collect())
+    }
+}
+
+/// Batches testimony lookups by string hash
+pub struct TestimonyLoader(Database);
+
+impl Loader<String> for TestimonyLoader {
+    type Value = Vec<TestimonyRow>;
+    type Error = Arc<crate::db::DbError>;
+
+    async fn load(
+        &self,
+        keys: &[String],
+    ) -> Result<HashMap<String, Vec<TestimonyRow>>, Self::Error> {
+        let rows = self.0.testimonies_for(keys).await.map_err(Arc::new)?;
+        Ok(group_by_string(rows))
+    }
+}
+
+/// Group testimonies by string hash, keeping their order
+fn group_by_string(rows: Vec<TestimonyRow>) -> HashMap<String, Vec<TestimonyRow>> {
+    let mut grouped: HashMap<String, Vec<TestimonyRow>> = HashMap::new();
+    for row in rows {
+        grouped
+            .entry(row.string_hash.clone())
+            .or_default()
+            .push(row);
+    }
+    grouped
+}
+
+async fn testimonies(ctx: &Context<'_>, string_hash: &str) -> Result<Vec<TestimonyRow>> {
+    Ok(ctx
+        .data_unchecked::<DataLoader<TestimonyLoader>>()
+        .load_one(string_hash.to_string())
+        .await
+        .map_err(internal)?
+        .unwrap_or_default())
+}
+
+fn db<'a>(ctx: &Context<'a>) -> &'a Database {
+    ctx.data_unchecked::<Database>()
+}
+
+fn page_params(first: Option<u32>, after: Option<String>) -> CursorParams {
+    CursorParams {
+        cursor: after,
+        limit: first,
+    }
+}
+
+/// Rows a list field may return, for complexity accounting
+fn page_size(first: Option<u32>) -> usize {
+    page_params(first, None).limit()
+}
+
+/// Log a store error and hide its details from the client
+fn internal(e: impl std::fmt::Display) -> Error {
+    tracing::error!("{}", e);
+    Error::new("Internal error")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn testimony(string_hash: &str, agent_id: &str) -> TestimonyRow {
+        TestimonyRow {
+            string_hash: string_hash.to_string(),
+            agent_id: agent_id.to_string(),
+            verdict: "Valid".to_string(),
+            confidence: 0.9,
+            timestamp: 1,
+        }
+    }
+
+    #[test]
+    fn test_group_by_string() {
+        let grouped = group_by_string(vec![
+            testimony("0x01", "0xa"),
+            testimony("0x02", "0xa"),
+            testimony("0x01", "0xb"),
+        ]);
+        assert_eq!(grouped.len(), 2);
+        let agents: Vec<&str> = grouped["0x01"]
+            .iter()
+            .map(|t| t.agent_id.as_str())
+            .collect();
+        assert_eq!(agents, vec!["0xa", "0xb"]);
+    }
+
+    #[test]
+    fn test_page_size_is_clamped() {
+        assert_eq!(page_size(None), crate::api::DEFAULT_PAGE_SIZE as usize);
+        assert_eq!(page_size(Some(0)), 1);
+        assert_eq!(page_size(Some(10_000)), crate::api::MAX_PAGE_SIZE as usize);
+    }
+
+    #[tokio::test]
+    async fn test_schema_rejects_deep_queries() {
+        let pool = sqlx::postgres::PgPoolOptions::new()
+            .connect_lazy("postgres://localhost/explorer")
+            .unwrap();
+        let schema = schema(Database::from_pool(pool));
+
+        let sdl = schema.sdl();
+        for ty in ["type StringRow", "type TransactionPage", "type Federation"] {
+            assert!(sdl.contains(ty), "missing {}", ty);
+        }
+
+        // Nesting beyond MAX_DEPTH fails validation before touching the store
+        let query = "{ account(address: \"0x1\") { transactions { items { string { \
+                     transactionList { string { transactionList { string { hash } } } } } } } } }";
+        let response = schema.execute(query).await;
+        assert!(!response.errors.is_empty());
+
+        let response = schema
+            .execute("{ federation(id: \"fed-002\") { name type } }")
+            .await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        let data = response.data.into_json().unwrap();
+        assert_eq!(data["federation"]["type"], "structured");
+    }
+}
@@ -1,3 +1,876 @@
 //! Blockchain indexer
+//!
+//! Consumes lattice and consensus events and persists them through an
+//! [`IndexStore`] (PostgreSQL in production, see [`crate::db::Database`]).
+//!
+//! Events arrive on an mpsc channel. [`follow_node`] feeds that channel
+//! from a node's WebSocket event bridge, reconnecting on failure; other
+//! sources (e.g. a backfill job) push [`IndexerEvent`]s directly.
+//!
+//! Re-delivered strings are ignored, so replaying a range after a
+//! reconnect is safe.
 
-// Placeholder for indexer logic
+use crate::db::DbError;
+use crate::tokens::IndexedLog;
+use crate::ws::{LiveEvent, LiveHub, PendingTransaction};
+use rope_economics::FeeMarket;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{mpsc, RwLock};
+
+/// Delay before reconnecting to the node
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+
+/// String to index
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct IndexedString {
+    pub id: String,
+    pub creator: String,
+    pub string_type: String,
+    pub timestamp: i64,
+    /// Transactions carried by the string
+    #[serde(default)]
+    pub transactions: Vec<IndexedTransaction>,
+}
+
+/// Transaction to index
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct IndexedTransaction {
+    pub hash: String,
+    pub from: String,
+    pub to: Option<String>,
+    /// Base units, decimal
+    pub value: String,
+    /// Base units, decimal
+    pub fee: String,
+    #[serde(default)]
+    pub gas_used: i64,
+    pub success: bool,
+    pub timestamp: i64,
+    /// Call data, hex
+    #[serde(default)]
+    pub input: Option<String>,
+    /// Event logs, in emission order
+    #[serde(default)]
+    pub logs: Vec<IndexedLog>,
+}
+
+impl IndexedTransaction {
+    /// Effective price per gas, base units
+    pub fn gas_price(&self) -> Option<u128> {
+        let fee: u128 = self.fee.parse().ok()?;
+        (self.gas_used > 0).then(|| fee / self.gas_used as u128)
+    }
+
+    /// Status label
+    pub fn status(&self) -> &'static str {
+        if self.success {
+            "Success"
+        } else {
+            "Failed"
+        }
+    }
+}
+
+/// Validator registration or stake change
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct IndexedValidator {
+    pub address: String,
+    pub name: String,
+    pub stake: i64,
+    pub active: bool,
+    pub timestamp: i64,
+}
+
+/// Validator testimony on a string
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct IndexedTestimony {
+    pub string_id: String,
+    pub agent_id: String,
+    /// `Valid`, `Invalid` or `Uncertain`
+    pub verdict: String,
+    pub confidence: f64,
+    pub timestamp: i64,
+}
+
+/// Epoch reward paid to a validator
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct IndexedReward {
+    pub validator: String,
+    pub epoch: u64,
+    /// Base units, decimal
+    pub proposer_rewards: String,
+    /// Base units, decimal
+    pub testimony_rewards: String,
+    pub performance_multiplier: f64,
+    /// Base units, decimal
+    pub final_reward: String,
+    pub timestamp: i64,
+}
+
+impl From<&rope_economics::ValidatorReward> for IndexedReward {
+    fn from(reward: &rope_economics::ValidatorReward) -> Self {
+        Self {
+            validator: format!("0x{}", hex::encode(reward.validator_id)),
+            epoch: reward.epoch,
+            proposer_rewards: reward.proposer_rewards.to_string(),
+            testimony_rewards: reward.testimony_rewards.to_string(),
+            performance_multiplier: reward.performance_multiplier,
+            final_reward: reward.final_reward.to_string(),
+            timestamp: reward.timestamp,
+        }
+    }
+}
+
+/// Event consumed by the indexer
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum IndexerEvent {
+    /// New string (and its transactions)
+    String(IndexedString),
+
+    /// Strings finalized by an anchor. An empty `string_ids` finalizes
+    /// every pending string up to `timestamp`, which is all the node's
+    /// event stream reports.
+    Finalized {
+        anchor_id: String,
+        round: u64,
+        string_ids: Vec<String>,
+        timestamp: i64,
+    },
+
+    /// String rejected by consensus
+    Rejected { string_id: String, reason: String },
+
+    /// Validator set change
+    Validator(IndexedValidator),
+
+    /// Testimony received for a string
+    Testimony(IndexedTestimony),
+
+    /// Round closed by a view change without an anchor from `producer`
+    MissedRound {
+        round: u64,
+        producer: String,
+        timestamp: i64,
+    },
+
+    /// Validator seen alive on the gossip layer
+    Heartbeat { validator: String, timestamp: i64 },
+
+    /// Epoch reward paid to a validator
+    Reward(IndexedReward),
+}
+
+/// Persistence used by the indexer
+#[async_trait::async_trait]
+pub trait IndexStore: Send + Sync + 'static {
+    /// Insert a string with its transactions; `false` if already indexed
+    async fn insert_string(&self, string: &IndexedString) -> Result<bool, DbError>;
+
+    /// Mark strings final, returning how many changed
+    async fn finalize(
+        &self,
+        anchor_id: &str,
+        string_ids: &[String],
+        up_to: i64,
+    ) -> Result<u64, DbError>;
+
+    /// Mark a string rejected; `false` if unknown
+    async fn reject(&self, string_id: &str, reason: &str) -> Result<bool, DbError>;
+
+    /// Insert or update a validator
+    async fn upsert_validator(&self, validator: &IndexedValidator) -> Result<(), DbError>;
+
+    /// Record a testimony; `false` if this agent already testified on the string
+    async fn insert_testimony(&self, testimony: &IndexedTestimony) -> Result<bool, DbError>;
+
+    /// Charge a missed round to its producer; `false` if already recorded
+    async fn record_missed_round(
+        &self,
+        round: u64,
+        producer: &str,
+        timestamp: i64,
+    ) -> Result<bool, DbError>;
+
+    /// Record a validator heartbeat
+    async fn record_heartbeat(&self, validator: &str, timestamp: i64) -> Result<(), DbError>;
+
+    /// Record an epoch reward; `false` if already recorded
+    async fn insert_reward(&self, reward: &IndexedReward) -> Result<bool, DbError>;
+}
+
+/// Indexer counters
+#[derive(Debug, Default)]
+pub struct IndexerStats {
+    pub strings: AtomicU64,
+    pub transactions: AtomicU64,
+    pub finalized: AtomicU64,
+    pub rejected: AtomicU64,
+    pub testimonies: AtomicU64,
+    pub missed_rounds: AtomicU64,
+    pub rewards: AtomicU64,
+    pub errors: AtomicU64,
+}
+
+impl IndexerStats {
+    /// JSON view for the status endpoint
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "strings": self.strings.load(Ordering::Relaxed),
+            "transactions": self.transactions.load(Ordering::Relaxed),
+            "finalized": self.finalized.load(Ordering::Relaxed),
+            "rejected": self.rejected.load(Ordering::Relaxed),
+            "testimonies": self.testimonies.load(Ordering::Relaxed),
+            "missedRounds": self.missed_rounds.load(Ordering::Relaxed),
+            "rewards": self.rewards.load(Ordering::Relaxed),
+            "errors": self.errors.load(Ordering::Relaxed)
+        })
+    }
+}
+
+/// Event indexer
+pub struct Indexer<S: IndexStore> {
+    store: Arc<S>,
+    stats: Arc<IndexerStats>,
+    live: Option<LiveHub>,
+    fees: Option<Arc<RwLock<FeeMarket>>>,
+}
+
+impl<S: IndexStore> Indexer<S> {
+    /// Create an indexer writing to `store`
+    pub fn new(store: Arc<S>) -> Self {
+        Self {
+            store,
+            stats: Arc::new(IndexerStats::default()),
+            live: None,
+            fees: None,
+        }
+    }
+
+    /// Publish newly indexed data to WebSocket subscribers
+    pub fn with_live_hub(mut self, hub: LiveHub) -> Self {
+        self.live = Some(hub);
+        self
+    }
+
+    /// Replay indexed transactions through a fee market, closing an
+    /// anchor on each finalization, so its base fee and fee history track
+    /// the chain's
+    pub fn with_fee_market(mut self, fees: Arc<RwLock<FeeMarket>>) -> Self {
+        self.fees = Some(fees);
+        self
+    }
+
+    /// Shared counters
+    pub fn stats(&self) -> Arc<IndexerStats> {
+        self.stats.clone()
+    }
+
+    /// Persist one event
+    pub async fn apply(&self, event: IndexerEvent) -> Result<(), DbError> {
+        match event {
+            IndexerEvent::String(string) => {
+                if self.store.insert_string(&string).await? {
+                    self.stats.strings.fetch_add(1, Ordering::Relaxed);
+                    self.stats
+                        .transactions
+                        .fetch_add(string.transactions.len() as u64, Ordering::Relaxed);
+                    if let Some(fees) = &self.fees {
+                        let mut fees = fees.write().await;
+                        for transaction in &string.transactions {
+                            if let Some(price) = transaction.gas_price() {
+                                fees.record_transaction(transaction.gas_used as u64, price);
+                            }
+                        }
+                    }
+                    if let Some(live) = &self.live {
+                        live.publish(LiveEvent::NewString((&string).into()));
+                        for transaction in &string.transactions {
+                            live.publish(LiveEvent::PendingTransaction(PendingTransaction::new(
+                                &string.id,
+                                transaction,
+                            )));
+                        }
+                    }
+                }
+            }
+            IndexerEvent::Finalized {
+                anchor_id,
+                round,
+                string_ids,
+                timestamp,
+            } => {
+                let count = self
+                    .store
+                    .finalize(&anchor_id, &string_ids, timestamp)
+                    .await?;
+                tracing::debug!(
+                    "Anchor {} (round {}) finalized {} strings",
+                    anchor_id,
+                    round,
+                    count
+                );
+                self.stats.finalized.fetch_add(count, Ordering::Relaxed);
+                if let Some(fees) = &self.fees {
+                    fees.write().await.close_anchor(round);
+                }
+            }
+            IndexerEvent::Rejected { string_id, reason } => {
+                if self.store.reject(&string_id, &reason).await? {
+                    self.stats.rejected.fetch_add(1, Ordering::Relaxed);
+                }
+            }
+            IndexerEvent::Validator(validator) => {
+                self.store.upsert_validator(&validator).await?;
+                if let Some(live) = &self.live {
+                    live.publish(LiveEvent::ValidatorStatus(validator));
+                }
+            }
+            IndexerEvent::Testimony(testimony) => {
+                if self.store.insert_testimony(&testimony).await? {
+                    self.stats.testimonies.fetch_add(1, Ordering::Relaxed);
+                }
+            }
+            IndexerEvent::MissedRound {
+                round,
+                producer,
+                timestamp,
+            } => {
+                if self
+                    .store
+                    .record_missed_round(round, &producer, timestamp)
+                    .await?
+                {
+                    self.stats.missed_rounds.fetch_add(1, Ordering::Relaxed);
+                }
+            }
+            IndexerEvent::Heartbeat {
+                validator,
+                timestamp,
+            } => {
+                self.store.record_heartbeat(&validator, timestamp).await?;
+            }
+            IndexerEvent::Reward(reward) => {
+                if self.store.insert_reward(&reward).await? {
+                    self.stats.rewards.fetch_add(1, Ordering::Relaxed);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Consume events until the channel closes
+    pub async fn run(self, mut events: mpsc::Receiver<IndexerEvent>) {
+        while let Some(event) = events.recv().await {
+            if let Err(e) = self.apply(event).await {
+                self.stats.errors.fetch_add(1, Ordering::Relaxed);
+                tracing::warn!("Indexer write failed: {}", e);
+            }
+        }
+        tracing::info!("Indexer stopped: event channel closed");
+    }
+}
+
+/// Frame from the node's WebSocket event bridge (only the fields indexed)
+#[derive(Deserialize)]
+#[serde(tag = "type")]
+enum NodeFrame {
+    StringCreated {
+        id: String,
+        creator: String,
+        string_type: String,
+        timestamp: i64,
+    },
+    AnchorFinalized {
+        id: String,
+        round: u64,
+        timestamp: i64,
+    },
+    TestimonyReceived {
+        action_id: String,
+        agent_id: String,
+        verdict: String,
+        confidence: f64,
+    },
+    RoundMissed {
+        round: u64,
+        producer: String,
+        timestamp: i64,
+    },
+    ValidatorHeartbeat {
+        validator: String,
+        timestamp: i64,
+    },
+    RewardDistributed {
+        validator: String,
+        epoch: u64,
+        proposer_rewards: String,
+        testimony_rewards: String,
+        performance_multiplier: f64,
+        final_reward: String,
+        timestamp: i64,
+    },
+    #[serde(other)]
+    Other,
+}
+
+/// Map a node event frame to an indexer event
+pub fn parse_frame(text: &str) -> Option<IndexerEvent> {
+    match serde_json::from_str(text).ok()? {
+        NodeFrame::StringCreated {
+            id,
+            creator,
+            string_type,
+            timestamp,
+        } => Some(IndexerEvent::String(IndexedString {
+            id: normalize_hash(&id),
+            creator: normalize_hash(&creator),
+            string_type,
+            timestamp,
+            transactions: Vec::new(),
+        })),
+        NodeFrame::AnchorFinalized {
+            id,
+            round,
+            timestamp,
+        } => Some(IndexerEvent::Finalized {
+            anchor_id: normalize_hash(&id),
+            round,
+            string_ids: Vec::new(),
+            timestamp,
+        }),
+        // The bridge does not timestamp testimonies; use arrival time
+        NodeFrame::TestimonyReceived {
+            action_id,
+            agent_id,
+            verdict,
+            confidence,
+        } => Some(IndexerEvent::Testimony(IndexedTestimony {
+            string_id: normalize_hash(&action_id),
+            agent_id: normalize_hash(&agent_id),
+            verdict,
+            confidence,
+            timestamp: chrono::Utc::now().timestamp(),
+        })),
+        NodeFrame::RoundMissed {
+            round,
+            producer,
+            timestamp,
+        } => Some(IndexerEvent::MissedRound {
+            round,
+            producer: normalize_hash(&producer),
+            timestamp,
+        }),
+        NodeFrame::ValidatorHeartbeat {
+            validator,
+            timestamp,
+        } => Some(IndexerEvent::Heartbeat {
+            validator: normalize_hash(&validator),
+            timestamp,
+        }),
+        NodeFrame::RewardDistributed {
+            validator,
+            epoch,
+            proposer_rewards,
+            testimony_rewards,
+            performance_multiplier,
+            final_reward,
+            timestamp,
+        } => Some(IndexerEvent::Reward(IndexedReward {
+            validator: normalize_hash(&validator),
+            epoch,
+            proposer_rewards,
+            testimony_rewards,
+            performance_multiplier,
+            final_reward,
+            timestamp,
+        })),
+        NodeFrame::Other => None,
+    }
+}
+
+/// Lowercase `0x`-prefixed form used as the storage key
+pub fn normalize_hash(s: &str) -> String {
+    format!("0x{}", s.trim_start_matches("0x").to_lowercase())
+}
+
+/// Stream events from a node's WebSocket bridge into `events`, reconnecting until the channel closes
+pub async fn follow_node(url: String, events: mpsc::Sender<IndexerEvent>) {
+    use futures::{SinkExt, StreamExt};
+    use tokio_tungstenite::{connect_async, tungstenite::Message};
+
+    let subscribe = r#"{"type":"Subscribe","event_types":["StringCreated","AnchorFinalized","TestimonyReceived","RoundMissed","ValidatorHeartbeat","RewardDistributed"]}"#;
+
+    while !events.is_closed() {
+        match connect_async(url.as_str()).await {
+            Ok((mut ws, _)) => {
+                tracing::info!("Indexer following node events at {}", url);
+                if ws.send(Message::Text(subscribe.to_string())).await.is_ok() {
+                    while let Some(Ok(message)) = ws.next().await {
+                        let Message::Text(text) = message else {
+                            continue;
+                        };
+                        if let Some(event) = parse_frame(&text) {
+                            if events.send(event).await.is_err() {
+                                return;
+                            }
+                        }
+                    }
+                }
+                tracing::warn!("Node event stream at {} closed", url);
+            }
+            Err(e) => tracing::warn!("Cannot connect to node events at {}: {}", url, e),
+        }
+        tokio::time::sleep(RECONNECT_DELAY).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use tokio::sync::Mutex;
+
+    /// In-memory store: hash -> (status, timestamp)
+    #[derive(Default)]
+    struct MemoryStore {
+        strings: Mutex<HashMap<String, (String, i64)>>,
+        validators: Mutex<HashMap<String, IndexedValidator>>,
+        testimonies: Mutex<HashMap<(String, String), IndexedTestimony>>,
+        missed_rounds: Mutex<HashMap<u64, String>>,
+        heartbeats: Mutex<Vec<(String, i64)>>,
+        rewards: Mutex<HashMap<(String, u64), IndexedReward>>,
+    }
+
+    #[async_trait::async_trait]
+    impl IndexStore for MemoryStore {
+        async fn insert_string(&self, string: &IndexedString) -> Result<bool, DbError> {
+            let mut strings = self.strings.lock().await;
+            if strings.contains_key(&string.id) {
+                return Ok(false);
+            }
+            strings.insert(string.id.clone(), ("Pending".to_string(), string.timestamp));
+            Ok(true)
+        }
+
+        async fn finalize(
+            &self,
+            _anchor_id: &str,
+            string_ids: &[String],
+            up_to: i64,
+        ) -> Result<u64, DbError> {
+            let mut count = 0;
+            for (id, (status, timestamp)) in self.strings.lock().await.iter_mut() {
+                let selected = if string_ids.is_empty() {
+                    *timestamp <= up_to
+                } else {
+                    string_ids.contains(id)
+                };
+                if selected && status == "Pending" {
+                    *status = "Final".to_string();
+                    count += 1;
+                }
+            }
+            Ok(count)
+        }
+
+        async fn reject(&self, string_id: &str, _reason: &str) -> Result<bool, DbError> {
+            Ok(match self.strings.lock().await.get_mut(string_id) {
+                Some((status, _)) => {
+                    *status = "Rejected".to_string();
+                    true
+                }
+                None => false,
+            })
+        }
+
+        async fn upsert_validator(&self, validator: &IndexedValidator) -> Result<(), DbError> {
+            self.validators
+                .lock()
+                .await
+                .insert(validator.address.clone(), validator.clone());
+            Ok(())
+        }
+
+        async fn insert_testimony(&self, testimony: &IndexedTestimony) -> Result<bool, DbError> {
+            let key = (testimony.string_id.clone(), testimony.agent_id.clone());
+            let mut testimonies = self.testimonies.lock().await;
+            if testimonies.contains_key(&key) {
+                return Ok(false);
+            }
+            testimonies.insert(key, testimony.clone());
+            Ok(true)
+        }
+
+        async fn record_missed_round(
+            &self,
+            round: u64,
+            producer: &str,
+            _timestamp: i64,
+        ) -> Result<bool, DbError> {
+            let mut missed = self.missed_rounds.lock().await;
+            if missed.contains_key(&round) {
+                return Ok(false);
+            }
+            missed.insert(round, producer.to_string());
+            Ok(true)
+        }
+
+        async fn record_heartbeat(&self, validator: &str, timestamp: i64) -> Result<(), DbError> {
+            self.heartbeats
+                .lock()
+                .await
+                .push((validator.to_string(), timestamp));
+            Ok(())
+        }
+
+        async fn insert_reward(&self, reward: &IndexedReward) -> Result<bool, DbError> {
+            let key = (reward.validator.clone(), reward.epoch);
+            let mut rewards = self.rewards.lock().await;
+            if rewards.contains_key(&key) {
+                return Ok(false);
+            }
+            rewards.insert(key, reward.clone());
+            Ok(true)
+        }
+    }
+
+    fn string(id: &str, timestamp: i64, txs: usize) -> IndexerEvent {
+        IndexerEvent::String(IndexedString {
+            id: id.to_string(),
+            creator: "0xv1".to_string(),
+            string_type: "data".to_string(),
+            timestamp,
+            transactions: (0..txs)
+                .map(|i| IndexedTransaction {
+                    hash: format!("{}-{}", id, i),
+                    from: "0xa".to_string(),
+                    to: Some("0xb".to_string()),
+                    value: "100".to_string(),
+                    fee: "1".to_string(),
+                    gas_used: 21_000,
+                    success: true,
+                    timestamp,
+                    input: None,
+                    logs: Vec::new(),
+                })
+                .collect(),
+        })
+    }
+
+    #[tokio::test]
+    async fn test_indexer_applies_events() {
+        let store = Arc::new(MemoryStore::default());
+        let indexer = Indexer::new(store.clone());
+        let stats = indexer.stats();
+
+        indexer.apply(string("0x01", 10, 2)).await.unwrap();
+        indexer.apply(string("0x02", 20, 1)).await.unwrap();
+        indexer.apply(string("0x03", 30, 0)).await.unwrap();
+        // Re-delivery is ignored
+        indexer.apply(string("0x01", 10, 2)).await.unwrap();
+        assert_eq!(stats.strings.load(Ordering::Relaxed), 3);
+        assert_eq!(stats.transactions.load(Ordering::Relaxed), 3);
+
+        indexer
+            .apply(IndexerEvent::Finalized {
+                anchor_id: "0xaa".to_string(),
+                round: 1,
+                string_ids: Vec::new(),
+                timestamp: 20,
+            })
+            .await
+            .unwrap();
+        indexer
+            .apply(IndexerEvent::Rejected {
+                string_id: "0x03".to_string(),
+                reason: "conflict".to_string(),
+            })
+            .await
+            .unwrap();
+
+        let strings = store.strings.lock().await;
+        assert_eq!(strings["0x01"].0, "Final");
+        assert_eq!(strings["0x02"].0, "Final");
+        assert_eq!(strings["0x03"].0, "Rejected");
+        assert_eq!(stats.finalized.load(Ordering::Relaxed), 2);
+        assert_eq!(stats.rejected.load(Ordering::Relaxed), 1);
+        drop(strings);
+
+        let testimony = IndexedTestimony {
+            string_id: "0x01".to_string(),
+            agent_id: "0xv1".to_string(),
+            verdict: "Valid".to_string(),
+            confidence: 0.95,
+            timestamp: 11,
+        };
+        indexer
+            .apply(IndexerEvent::Testimony(testimony.clone()))
+            .await
+            .unwrap();
+        indexer
+            .apply(IndexerEvent::Testimony(testimony))
+            .await
+            .unwrap();
+        assert_eq!(stats.testimonies.load(Ordering::Relaxed), 1);
+    }
+
+    #[tokio::test]
+    async fn test_indexer_tracks_fee_market() {
+        let fees = Arc::new(RwLock::new(FeeMarket::default()));
+        let indexer = Indexer::new(Arc::new(MemoryStore::default())).with_fee_market(fees.clone());
+        let base_fee = fees.read().await.base_fee();
+
+        let mut event = string("0x01", 10, 2);
+        if let IndexerEvent::String(string) = &mut event {
+            string.transactions[0].fee = (21_000 * (base_fee + 2_000)).to_string();
+            string.transactions[1].fee = (21_000 * (base_fee + 4_000)).to_string();
+        }
+        indexer.apply(event.clone()).await.unwrap();
+        // Re-delivery isn't charged twice
+        indexer.apply(event).await.unwrap();
+        indexer
+            .apply(IndexerEvent::Finalized {
+                anchor_id: "0xaa".to_string(),
+                round: 1,
+                string_ids: Vec::new(),
+                timestamp: 20,
+            })
+            .await
+            .unwrap();
+
+        let fees = fees.read().await;
+        let anchor = fees.history().last().unwrap().clone();
+        assert_eq!(anchor.round, 1);
+        assert_eq!(anchor.gas_used, 42_000);
+        assert_eq!(anchor.tips, 21_000 * 6_000);
+        assert_eq!(anchor.priority_fees, Some([2_000, 2_000, 2_000]));
+        // Nearly empty anchor lowers the base fee
+        assert!(fees.base_fee() < base_fee);
+    }
+
+    #[tokio::test]
+    async fn test_indexer_applies_validator_activity() {
+        let store = Arc::new(MemoryStore::default());
+        let indexer = Indexer::new(store.clone());
+        let stats = indexer.stats();
+
+        let missed = IndexerEvent::MissedRound {
+            round: 7,
+            producer: "0xv1".to_string(),
+            timestamp: 70,
+        };
+        indexer.apply(missed.clone()).await.unwrap();
+        indexer.apply(missed).await.unwrap();
+        assert_eq!(stats.missed_rounds.load(Ordering::Relaxed), 1);
+
+        indexer
+            .apply(IndexerEvent::Heartbeat {
+                validator: "0xv1".to_string(),
+                timestamp: 71,
+            })
+            .await
+            .unwrap();
+        assert_eq!(store.heartbeats.lock().await.len(), 1);
+
+        let reward = IndexedReward::from(&rope_economics::ValidatorReward {
+            validator_id: [0xab; 32],
+            epoch: 3,
+            proposer_rewards: 10,
+            testimony_rewards: 5,
+            base_total: 15,
+            performance_multiplier: 1.2,
+            final_reward: 18,
+            timestamp: 72,
+        });
+        assert_eq!(reward.validator, format!("0x{}", "ab".repeat(32)));
+        assert_eq!(reward.final_reward, "18");
+        indexer
+            .apply(IndexerEvent::Reward(reward.clone()))
+            .await
+            .unwrap();
+        indexer.apply(IndexerEvent::Reward(reward)).await.unwrap();
+        assert_eq!(stats.rewards.load(Ordering::Relaxed), 1);
+    }
+
+    #[tokio::test]
+    async fn test_run_consumes_channel() {
+        let store = Arc::new(MemoryStore::default());
+        let indexer = Indexer::new(store.clone());
+        let (tx, rx) = mpsc::channel(8);
+
+        tx.send(IndexerEvent::Validator(IndexedValidator {
+            address: "0xv1".to_string(),
+            name: "Validator 1".to_string(),
+            stake: 1_000_000,
+            active: true,
+            timestamp: 1,
+        }))
+        .await
+        .unwrap();
+        tx.send(string("0x01", 1, 0)).await.unwrap();
+        drop(tx);
+
+        indexer.run(rx).await;
+        assert!(store.validators.lock().await.contains_key("0xv1"));
+        assert!(store.strings.lock().await.contains_key("0x01"));
+    }
+
+    #[test]
+    fn test_parse_node_frames() {
+        let frame = r#"{"type":"StringCreated","id":"ABCD","creator":"0xEF","string_type":"data","timestamp":5}"#;
+        match parse_frame(frame) {
+            Some(IndexerEvent::String(string)) => {
+                assert_eq!(string.id, "0xabcd");
+                assert_eq!(string.creator, "0xef");
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+
+        let frame =
+            r#"{"type":"AnchorFinalized","id":"aa","round":3,"strings_finalized":2,"timestamp":9}"#;
+        assert_eq!(
+            parse_frame(frame),
+            Some(IndexerEvent::Finalized {
+                anchor_id: "0xaa".to_string(),
+                round: 3,
+                string_ids: Vec::new(),
+                timestamp: 9,
+            })
+        );
+
+        let frame = r#"{"type":"TestimonyReceived","action_id":"0xABCD","agent_id":"v1","verdict":"Valid","confidence":0.9}"#;
+        match parse_frame(frame) {
+            Some(IndexerEvent::Testimony(testimony)) => {
+                assert_eq!(testimony.string_id, "0xabcd");
+                assert_eq!(testimony.agent_id, "0xv1");
+                assert_eq!(testimony.verdict, "Valid");
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+
+        let frame = r#"{"type":"RoundMissed","round":12,"view":1,"producer":"V1","timestamp":40}"#;
+        assert_eq!(
+            parse_frame(frame),
+            Some(IndexerEvent::MissedRound {
+                round: 12,
+                producer: "0xv1".to_string(),
+                timestamp: 40,
+            })
+        );
+
+        let frame = r#"{"type":"RewardDistributed","validator":"0xV1","epoch":2,"proposer_rewards":"100","testimony_rewards":"50","performance_multiplier":1.1,"final_reward":"165","timestamp":41}"#;
+        match parse_frame(frame) {
+            Some(IndexerEvent::Reward(reward)) => {
+                assert_eq!(reward.validator, "0xv1");
+                assert_eq!(reward.final_reward, "165");
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+
+        assert_eq!(parse_frame(r#"{"type":"Pong"}"#), None);
+        assert_eq!(parse_frame("not json"), None);
+    }
+}
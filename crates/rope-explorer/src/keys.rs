@@ -0,0 +1,916 @@
+//! API keys, quotas and rate limiting
+//!
+//! Every request under `/api/v1` passes through [`enforce`]. Callers
+//! present a key in the `x-api-key` header (or `?apikey=` for WebSocket
+//! upgrades, which browsers cannot add headers to); requests without a
+//! key are limited per client IP on the anonymous tier.
+//!
+//! | Plan         | Rate (req/s) | Burst | Daily quota |
+//! |--------------|--------------|-------|-------------|
+//! | Anonymous    | 2            | 5     | 5,000       |
+//! | `free`       | 5            | 10    | 100,000     |
+//! | `developer`  | 25           | 50    | 1,000,000   |
+//! | `enterprise` | 200          | 400   | unlimited   |
+//!
+//! Free keys are self-serve, capped per owner and per client IP; other
+//! plans are issued with the admin token.
+//!
+//! Only a BLAKE3 hash of each key is stored. Keys are identified in
+//! admin endpoints and usage records by their public prefix.
+
+use crate::api::{ApiError, ApiResult, ErrorBody};
+use crate::openapi::{DOCS_PATH, OPENAPI_PATH};
+use crate::AppState;
+use axum::{
+    extract::{ConnectInfo, Path, Request, State},
+    http::{HeaderMap, HeaderValue, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Json, Response},
+};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::{Mutex, RwLock};
+use utoipa::ToSchema;
+
+/// Header carrying the API key
+pub const API_KEY_HEADER: &str = "x-api-key";
+
+/// Query parameter carrying the API key
+const API_KEY_PARAM: &str = "apikey";
+
+/// Prefix of every issued key
+const KEY_PREFIX: &str = "dcs_";
+
+/// Characters of the key shown publicly
+const PUBLIC_PREFIX_LEN: usize = 16;
+
+/// Self-serve free keys one owner may hold
+const FREE_KEYS_PER_OWNER: usize = 3;
+
+/// Self-serve free keys one client IP may issue per UTC day
+const FREE_KEYS_PER_IP: u64 = 3;
+
+/// Paths that bypass limits
+const EXEMPT_PATHS: [&str; 2] = ["/", "/health"];
+
+/// Subscription plan
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum Plan {
+    Anonymous,
+    Free,
+    Developer,
+    Enterprise,
+}
+
+impl Plan {
+    /// Plans that can be issued as keys
+    pub const ISSUABLE: [Plan; 3] = [Plan::Free, Plan::Developer, Plan::Enterprise];
+
+    /// Limits for this plan
+    pub fn limits(&self) -> PlanLimits {
+        match self {
+            Plan::Anonymous => PlanLimits::new(2.0, 5, Some(5_000)),
+            Plan::Free => PlanLimits::new(5.0, 10, Some(100_000)),
+            Plan::Developer => PlanLimits::new(25.0, 50, Some(1_000_000)),
+            Plan::Enterprise => PlanLimits::new(200.0, 400, None),
+        }
+    }
+
+    /// Database label
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Plan::Anonymous => "anonymous",
+            Plan::Free => "free",
+            Plan::Developer => "developer",
+            Plan::Enterprise => "enterprise",
+        }
+    }
+
+    /// Parse a database label
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "anonymous" => Some(Plan::Anonymous),
+            "free" => Some(Plan::Free),
+            "developer" => Some(Plan::Developer),
+            "enterprise" => Some(Plan::Enterprise),
+            _ => None,
+        }
+    }
+}
+
+/// Rate and quota limits of a plan
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PlanLimits {
+    /// Sustained requests per second
+    pub rate: f64,
+    /// Requests allowed in a burst
+    pub burst: u32,
+    /// Requests per UTC day (`None` = unlimited)
+    pub daily_quota: Option<u64>,
+}
+
+impl PlanLimits {
+    const fn new(rate: f64, burst: u32, daily_quota: Option<u64>) -> Self {
+        Self {
+            rate,
+            burst,
+            daily_quota,
+        }
+    }
+}
+
+/// Issued key as stored
+#[derive(Clone, Debug, PartialEq, sqlx::FromRow)]
+pub struct ApiKeyRecord {
+    pub prefix: String,
+    pub key_hash: String,
+    pub owner: String,
+    pub plan: String,
+    pub created_at: i64,
+}
+
+impl ApiKeyRecord {
+    /// Parsed plan (unknown labels fall back to the free tier)
+    pub fn plan(&self) -> Plan {
+        Plan::parse(&self.plan).unwrap_or(Plan::Free)
+    }
+}
+
+/// Outcome of a limit check
+#[derive(Clone, Debug, PartialEq)]
+pub enum Decision {
+    Allowed { remaining: Option<u64> },
+    RateLimited { retry_after: Duration },
+    QuotaExceeded { quota: u64 },
+}
+
+/// Usage counters of one subject (key prefix or client IP)
+#[derive(Clone, Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Usage {
+    #[serde(skip)]
+    tokens: f64,
+    #[serde(skip)]
+    refilled: Option<Instant>,
+    #[serde(skip)]
+    day: Option<NaiveDate>,
+    /// Requests counted against today's quota
+    pub today: u64,
+    pub total: u64,
+    pub rate_limited: u64,
+    pub quota_exceeded: u64,
+    /// Requests not yet written to the database
+    #[serde(skip)]
+    unflushed: u64,
+}
+
+impl Usage {
+    fn new(burst: u32) -> Self {
+        Self {
+            tokens: burst as f64,
+            refilled: None,
+            day: None,
+            today: 0,
+            total: 0,
+            rate_limited: 0,
+            quota_exceeded: 0,
+            unflushed: 0,
+        }
+    }
+}
+
+/// Token buckets and daily quotas
+#[derive(Default)]
+pub struct RateLimiter {
+    usage: HashMap<String, Usage>,
+}
+
+impl RateLimiter {
+    /// Count one request by `subject`
+    pub fn check(
+        &mut self,
+        subject: &str,
+        limits: &PlanLimits,
+        now: Instant,
+        day: NaiveDate,
+    ) -> Decision {
+        let usage = self
+            .usage
+            .entry(subject.to_string())
+            .or_insert_with(|| Usage::new(limits.burst));
+
+        if usage.day != Some(day) {
+            usage.day = Some(day);
+            usage.today = 0;
+        }
+
+        if let Some(quota) = limits.daily_quota {
+            if usage.today >= quota {
+                usage.quota_exceeded += 1;
+                return Decision::QuotaExceeded { quota };
+            }
+        }
+
+        if let Some(refilled) = usage.refilled {
+            let elapsed = now.saturating_duration_since(refilled).as_secs_f64();
+            usage.tokens = (usage.tokens + elapsed * limits.rate).min(limits.burst as f64);
+        }
+        usage.refilled = Some(now);
+
+        if usage.tokens < 1.0 {
+            usage.rate_limited += 1;
+            let wait = (1.0 - usage.tokens) / limits.rate;
+            return Decision::RateLimited {
+                retry_after: Duration::from_secs_f64(wait),
+            };
+        }
+
+        usage.tokens -= 1.0;
+        usage.today += 1;
+        usage.total += 1;
+        usage.unflushed += 1;
+        Decision::Allowed {
+            remaining: limits.daily_quota.map(|quota| quota - usage.today),
+        }
+    }
+
+    /// Counters of one subject
+    pub fn usage(&self, subject: &str) -> Option<&Usage> {
+        self.usage.get(subject)
+    }
+
+    /// Seed today's count (e.g. from the database after a restart)
+    pub fn seed(&mut self, subject: &str, limits: &PlanLimits, day: NaiveDate, today: u64) {
+        let usage = self
+            .usage
+            .entry(subject.to_string())
+            .or_insert_with(|| Usage::new(limits.burst));
+        usage.day = Some(day);
+        usage.today = today;
+    }
+
+    /// Take unflushed request counts of key subjects
+    pub fn take_unflushed(&mut self) -> Vec<(String, u64)> {
+        self.usage
+            .iter_mut()
+            .filter(|(subject, usage)| subject.starts_with(KEY_PREFIX) && usage.unflushed > 0)
+            .map(|(subject, usage)| (subject.clone(), std::mem::take(&mut usage.unflushed)))
+            .collect()
+    }
+
+    /// Drop anonymous subjects not seen today
+    pub fn prune(&mut self, day: NaiveDate) {
+        self.usage
+            .retain(|subject, usage| subject.starts_with(KEY_PREFIX) || usage.day == Some(day));
+    }
+}
+
+/// Key registry and limiter
+pub struct ApiKeys {
+    /// Active keys by hash
+    keys: RwLock<HashMap<String, ApiKeyRecord>>,
+    limiter: Mutex<RateLimiter>,
+    /// Self-serve issuances per client IP
+    issuance: Mutex<RateLimiter>,
+    /// BLAKE3 hash of the admin token
+    admin_token: Option<blake3::Hash>,
+}
+
+impl ApiKeys {
+    /// Registry with `keys` already issued
+    pub fn new(keys: Vec<ApiKeyRecord>, admin_token: Option<&str>) -> Self {
+        Self {
+            keys: RwLock::new(keys.into_iter().map(|k| (k.key_hash.clone(), k)).collect()),
+            limiter: Mutex::new(RateLimiter::default()),
+            issuance: Mutex::new(RateLimiter::default()),
+            admin_token: admin_token.map(|token| blake3::hash(token.as_bytes())),
+        }
+    }
+
+    /// Whether `headers` carry the admin bearer token
+    pub fn is_admin(&self, headers: &HeaderMap) -> bool {
+        let Some(expected) = self.admin_token else {
+            return false;
+        };
+        headers
+            .get("authorization")
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.strip_prefix("Bearer "))
+            // blake3::Hash equality is constant-time
+            .is_some_and(|token| blake3::hash(token.as_bytes()) == expected)
+    }
+
+    /// Key record for a presented key
+    pub async fn lookup(&self, key: &str) -> Option<ApiKeyRecord> {
+        self.keys.read().await.get(&hash_key(key)).cloned()
+    }
+
+    /// Seed today's usage counts
+    pub async fn seed_usage(&self, usage: Vec<(String, u64)>) {
+        let keys = self.keys.read().await;
+        let mut limiter = self.limiter.lock().await;
+        let today = chrono::Utc::now().date_naive();
+        for (prefix, count) in usage {
+            if let Some(record) = keys.values().find(|k| k.prefix == prefix) {
+                limiter.seed(&prefix, &record.plan().limits(), today, count);
+            }
+        }
+    }
+
+    /// Check and count a request
+    pub async fn check(&self, subject: &str, plan: Plan) -> Decision {
+        self.limiter.lock().await.check(
+            subject,
+            &plan.limits(),
+            Instant::now(),
+            chrono::Utc::now().date_naive(),
+        )
+    }
+
+    /// Admit a self-serve free key for `owner`, requested from `ip`
+    pub async fn admit_free_key(&self, owner: &str, ip: &str) -> Result<(), ApiError> {
+        let held = self
+            .keys
+            .read()
+            .await
+            .values()
+            .filter(|k| k.owner == owner && k.plan() == Plan::Free)
+            .count();
+        if held >= FREE_KEYS_PER_OWNER {
+            return Err(ApiError::TooManyRequests(format!(
+                "{} already holds {} free keys",
+                owner, held
+            )));
+        }
+
+        let limits = PlanLimits::new(
+            FREE_KEYS_PER_IP as f64 / 86_400.0,
+            FREE_KEYS_PER_IP as u32,
+            Some(FREE_KEYS_PER_IP),
+        );
+        let decision = self.issuance.lock().await.check(
+            &format!("ip:{}", ip),
+            &limits,
+            Instant::now(),
+            chrono::Utc::now().date_naive(),
+        );
+        match decision {
+            Decision::Allowed { .. } => Ok(()),
+            _ => Err(ApiError::TooManyRequests(format!(
+                "at most {} free keys per client a day",
+                FREE_KEYS_PER_IP
+            ))),
+        }
+    }
+
+    /// Counters of a key
+    pub async fn usage(&self, prefix: &str) -> Option<Usage> {
+        self.limiter.lock().await.usage(prefix).cloned()
+    }
+
+    /// Persist request counts and forget idle anonymous clients
+    pub async fn flush(&self, state: &AppState) {
+        let unflushed = {
+            let mut limiter = self.limiter.lock().await;
+            limiter.prune(chrono::Utc::now().date_naive());
+            limiter.take_unflushed()
+        };
+        self.issuance
+            .lock()
+            .await
+            .prune(chrono::Utc::now().date_naive());
+        let day = chrono::Utc::now().date_naive();
+        for (prefix, count) in unflushed {
+            if let Err(e) = state.db.record_key_usage(&prefix, day, count as i64).await {
+                tracing::warn!("Failed to record usage of {}: {}", prefix, e);
+            }
+        }
+    }
+}
+
+/// Generate a new key
+pub fn generate_key() -> String {
+    format!(
+        "{}{}{}",
+        KEY_PREFIX,
+        uuid::Uuid::new_v4().simple(),
+        uuid::Uuid::new_v4().simple()
+    )
+}
+
+/// Stored hash of a key
+pub fn hash_key(key: &str) -> String {
+    blake3::hash(key.as_bytes()).to_hex().to_string()
+}
+
+/// Public prefix of a key
+pub fn public_prefix(key: &str) -> String {
+    key.chars().take(PUBLIC_PREFIX_LEN).collect()
+}
+
+fn presented_key(request: &Request) -> Option<String> {
+    if let Some(key) = request
+        .headers()
+        .get(API_KEY_HEADER)
+        .and_then(|v| v.to_str().ok())
+    {
+        return Some(key.to_string());
+    }
+    request.uri().query().and_then(|query| {
+        query.split('&').find_map(|pair| {
+            pair.strip_prefix(API_KEY_PARAM)
+                .and_then(|rest| rest.strip_prefix('='))
+                .map(str::to_string)
+        })
+    })
+}
+
+fn limit_response(status: StatusCode, message: String) -> Response {
+    (status, Json(serde_json::json!({ "error": message }))).into_response()
+}
+
+/// Middleware enforcing keys, rate limits and quotas
+pub async fn enforce(State(state): State<Arc<AppState>>, request: Request, next: Next) -> Response {
+    // The docs UI loads many assets at once
+    let path = request.uri().path();
+    if EXEMPT_PATHS.contains(&path) || path.starts_with(DOCS_PATH) || path == OPENAPI_PATH {
+        return next.run(request).await;
+    }
+
+    let (subject, plan) = match presented_key(&request) {
+        Some(key) => match state.keys.lookup(&key).await {
+            Some(record) => (record.prefix.clone(), record.plan()),
+            None => {
+                return limit_response(
+                    StatusCode::UNAUTHORIZED,
+                    "Unknown or revoked API key".to_string(),
+                )
+            }
+        },
+        None => {
+            let ip = request
+                .extensions()
+                .get::<ConnectInfo<SocketAddr>>()
+                .map(|ConnectInfo(addr)| addr.ip().to_string())
+                .unwrap_or_else(|| "unknown".to_string());
+            (format!("ip:{}", ip), Plan::Anonymous)
+        }
+    };
+
+    match state.keys.check(&subject, plan).await {
+        Decision::Allowed { remaining } => {
+            let mut response = next.run(request).await;
+            if let (Some(quota), Some(remaining)) = (plan.limits().daily_quota, remaining) {
+                let headers = response.headers_mut();
+                headers.insert("x-ratelimit-limit", HeaderValue::from(quota));
+                headers.insert("x-ratelimit-remaining", HeaderValue::from(remaining));
+            }
+            response
+        }
+        Decision::RateLimited { retry_after } => {
+            let mut response = limit_response(
+                StatusCode::TOO_MANY_REQUESTS,
+                format!("Rate limit of {} req/s exceeded", plan.limits().rate),
+            );
+            let seconds = retry_after.as_secs_f64().ceil().max(1.0) as u64;
+            response
+                .headers_mut()
+                .insert("retry-after", HeaderValue::from(seconds));
+            response
+        }
+        Decision::QuotaExceeded { quota } => limit_response(
+            StatusCode::TOO_MANY_REQUESTS,
+            format!("Daily quota of {} requests exhausted", quota),
+        ),
+    }
+}
+
+/// Key issuance request
+#[derive(Deserialize, ToSchema)]
+pub struct IssueKeyRequest {
+    owner: String,
+    /// `free` (default), `developer` or `enterprise`
+    #[serde(default = "default_plan")]
+    plan: Plan,
+}
+
+fn default_plan() -> Plan {
+    Plan::Free
+}
+
+/// Newly issued key
+#[derive(Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct IssuedKey {
+    /// Shown once; only the hash is stored
+    pub key: String,
+    pub prefix: String,
+    pub owner: String,
+    pub plan: Plan,
+    pub limits: PlanLimits,
+    pub created_at: i64,
+}
+
+/// Issued key with its usage
+#[derive(Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct KeySummary {
+    pub prefix: String,
+    pub owner: String,
+    pub plan: String,
+    pub created_at: i64,
+    pub usage: Option<Usage>,
+}
+
+/// Issued keys
+#[derive(Serialize, ToSchema)]
+pub struct KeyList {
+    pub total: usize,
+    pub keys: Vec<KeySummary>,
+}
+
+/// Revocation result
+#[derive(Serialize, ToSchema)]
+pub struct RevokedKey {
+    pub prefix: String,
+    pub revoked: bool,
+}
+
+/// Requests on one UTC day
+#[derive(Serialize, ToSchema)]
+pub struct DailyUsage {
+    /// `YYYY-MM-DD`
+    pub day: String,
+    pub requests: i64,
+}
+
+/// Usage of the presented key
+#[derive(Serialize, ToSchema)]
+pub struct KeyUsage {
+    pub prefix: String,
+    pub plan: Plan,
+    pub limits: PlanLimits,
+    pub usage: Option<Usage>,
+    /// Last 30 days, most recent first
+    pub daily: Vec<DailyUsage>,
+}
+
+/// Plan and its limits
+#[derive(Serialize, ToSchema)]
+pub struct PlanInfo {
+    pub plan: Plan,
+    pub limits: PlanLimits,
+}
+
+/// Available plans
+#[derive(Serialize, ToSchema)]
+pub struct PlanList {
+    pub plans: Vec<PlanInfo>,
+}
+
+/// Issue an API key; free keys are self-serve within per-owner and per-IP
+/// caps, other plans need the admin token
+#[utoipa::path(
+    post,
+    path = "/api/v1/keys",
+    tag = "API Keys",
+    request_body = IssueKeyRequest,
+    responses(
+        (status = 201, description = "Issued key", body = IssuedKey),
+        (status = 400, description = "Invalid owner or plan", body = ErrorBody),
+        (status = 401, description = "Admin token required for this plan", body = ErrorBody),
+        (status = 429, description = "Free key cap reached", body = ErrorBody)
+    )
+)]
+pub async fn issue_key(
+    State(state): State<Arc<AppState>>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    Json(payload): Json<IssueKeyRequest>,
+) -> Result<(StatusCode, Json<IssuedKey>), ApiError> {
+    if !Plan::ISSUABLE.contains(&payload.plan) {
+        return Err(ApiError::BadRequest(format!(
+            "plan '{}' cannot be issued",
+            payload.plan.as_str()
+        )));
+    }
+    let admin = state.keys.is_admin(&headers);
+    if payload.plan != Plan::Free && !admin {
+        return Err(ApiError::Unauthorized(
+            "paid plans are issued by an administrator".to_string(),
+        ));
+    }
+    let owner = payload.owner.trim();
+    if owner.is_empty() || owner.len() > 128 {
+        return Err(ApiError::BadRequest(
+            "owner must be 1-128 characters".to_string(),
+        ));
+    }
+    if !admin {
+        state
+            .keys
+            .admit_free_key(owner, &addr.ip().to_string())
+            .await?;
+    }
+
+    let key = generate_key();
+    let record = ApiKeyRecord {
+        prefix: public_prefix(&key),
+        key_hash: hash_key(&key),
+        owner: owner.to_string(),
+        plan: payload.plan.as_str().to_string(),
+        created_at: chrono::Utc::now().timestamp(),
+    };
+    state.db.insert_api_key(&record).await?;
+    state
+        .keys
+        .keys
+        .write()
+        .await
+        .insert(record.key_hash.clone(), record.clone());
+
+    Ok((
+        StatusCode::CREATED,
+        Json(IssuedKey {
+            key,
+            prefix: record.prefix,
+            owner: record.owner,
+            plan: payload.plan,
+            limits: payload.plan.limits(),
+            created_at: record.created_at,
+        }),
+    ))
+}
+
+/// List issued keys with usage (admin)
+#[utoipa::path(
+    get,
+    path = "/api/v1/keys",
+    tag = "API Keys",
+    security(("adminToken" = [])),
+    responses(
+        (status = 200, description = "Issued keys", body = KeyList),
+        (status = 401, description = "Admin token required", body = ErrorBody)
+    )
+)]
+pub async fn list_keys(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+) -> ApiResult<KeyList> {
+    if !state.keys.is_admin(&headers) {
+        return Err(ApiError::Unauthorized("admin token required".to_string()));
+    }
+    let records: Vec<ApiKeyRecord> = state.keys.keys.read().await.values().cloned().collect();
+    let mut keys = Vec::with_capacity(records.len());
+    for record in records {
+        let usage = state.keys.usage(&record.prefix).await;
+        keys.push(KeySummary {
+            prefix: record.prefix,
+            owner: record.owner,
+            plan: record.plan,
+            created_at: record.created_at,
+            usage,
+        });
+    }
+    Ok(Json(KeyList {
+        total: keys.len(),
+        keys,
+    }))
+}
+
+/// Revoke a key (admin)
+#[utoipa::path(
+    delete,
+    path = "/api/v1/keys/{prefix}",
+    tag = "API Keys",
+    security(("adminToken" = [])),
+    params(("prefix" = String, Path, description = "Public key prefix")),
+    responses(
+        (status = 200, description = "Key revoked", body = RevokedKey),
+        (status = 401, description = "Admin token required", body = ErrorBody),
+        (status = 404, description = "Unknown key", body = ErrorBody)
+    )
+)]
+pub async fn revoke_key(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Path(prefix): Path<String>,
+) -> ApiResult<RevokedKey> {
+    if !state.keys.is_admin(&headers) {
+        return Err(ApiError::Unauthorized("admin token required".to_string()));
+    }
+    if !state.db.revoke_api_key(&prefix).await? {
+        return Err(ApiError::NotFound(format!("API key {}", prefix)));
+    }
+    state
+        .keys
+        .keys
+        .write()
+        .await
+        .retain(|_, k| k.prefix != prefix);
+    Ok(Json(RevokedKey {
+        prefix,
+        revoked: true,
+    }))
+}
+
+/// Usage of the presented key
+#[utoipa::path(
+    get,
+    path = "/api/v1/keys/usage",
+    tag = "API Keys",
+    security(("apiKey" = [])),
+    responses(
+        (status = 200, description = "Counters and daily history", body = KeyUsage),
+        (status = 401, description = "Missing or unknown key", body = ErrorBody)
+    )
+)]
+pub async fn key_usage(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+) -> ApiResult<KeyUsage> {
+    let key = headers
+        .get(API_KEY_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .ok_or_else(|| ApiError::Unauthorized(format!("{} header required", API_KEY_HEADER)))?;
+    let record = state
+        .keys
+        .lookup(key)
+        .await
+        .ok_or_else(|| ApiError::Unauthorized("unknown or revoked API key".to_string()))?;
+    let plan = record.plan();
+    let history = state.db.key_usage_history(&record.prefix, 30).await?;
+
+    Ok(Json(KeyUsage {
+        usage: state.keys.usage(&record.prefix).await,
+        prefix: record.prefix,
+        plan,
+        limits: plan.limits(),
+        daily: history
+            .into_iter()
+            .map(|(day, requests)| DailyUsage {
+                day: day.to_string(),
+                requests,
+            })
+            .collect(),
+    }))
+}
+
+/// Plans with their rate limits and daily quotas
+#[utoipa::path(
+    get,
+    path = "/api/v1/plans",
+    tag = "API Keys",
+    responses((status = 200, description = "Plans", body = PlanList))
+)]
+pub async fn list_plans() -> Json<PlanList> {
+    let plans = [Plan::Anonymous]
+        .iter()
+        .chain(Plan::ISSUABLE.iter())
+        .map(|plan| PlanInfo {
+            plan: *plan,
+            limits: plan.limits(),
+        })
+        .collect();
+    Json(PlanList { plans })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day(d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 1, d).unwrap()
+    }
+
+    #[test]
+    fn test_rate_limit_and_refill() {
+        let mut limiter = RateLimiter::default();
+        let limits = PlanLimits::new(2.0, 3, None);
+        let start = Instant::now();
+
+        for _ in 0..3 {
+            assert!(matches!(
+                limiter.check("dcs_a", &limits, start, day(1)),
+                Decision::Allowed { remaining: None }
+            ));
+        }
+        assert!(matches!(
+            limiter.check("dcs_a", &limits, start, day(1)),
+            Decision::RateLimited { .. }
+        ));
+
+        // Half a second refills one token at 2 req/s
+        let later = start + Duration::from_millis(500);
+        assert!(matches!(
+            limiter.check("dcs_a", &limits, later, day(1)),
+            Decision::Allowed { .. }
+        ));
+
+        let usage = limiter.usage("dcs_a").unwrap();
+        assert_eq!(usage.total, 4);
+        assert_eq!(usage.rate_limited, 1);
+    }
+
+    #[test]
+    fn test_daily_quota_resets() {
+        let mut limiter = RateLimiter::default();
+        let limits = PlanLimits::new(1000.0, 1000, Some(2));
+        let now = Instant::now();
+
+        assert_eq!(
+            limiter.check("ip:1.2.3.4", &limits, now, day(1)),
+            Decision::Allowed { remaining: Some(1) }
+        );
+        assert_eq!(
+            limiter.check("ip:1.2.3.4", &limits, now, day(1)),
+            Decision::Allowed { remaining: Some(0) }
+        );
+        assert_eq!(
+            limiter.check("ip:1.2.3.4", &limits, now, day(1)),
+            Decision::QuotaExceeded { quota: 2 }
+        );
+        assert_eq!(
+            limiter.check("ip:1.2.3.4", &limits, now, day(2)),
+            Decision::Allowed { remaining: Some(1) }
+        );
+
+        limiter.prune(day(3));
+        assert!(limiter.usage("ip:1.2.3.4").is_none());
+    }
+
+    #[test]
+    fn test_unflushed_counts_only_keys() {
+        let mut limiter = RateLimiter::default();
+        let limits = Plan::Free.limits();
+        let now = Instant::now();
+        limiter.check("dcs_abc", &limits, now, day(1));
+        limiter.check("dcs_abc", &limits, now, day(1));
+        limiter.check("ip:1.2.3.4", &limits, now, day(1));
+
+        assert_eq!(limiter.take_unflushed(), vec![("dcs_abc".to_string(), 2)]);
+        assert!(limiter.take_unflushed().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_key_lookup_and_admin() {
+        let key = generate_key();
+        assert!(key.starts_with(KEY_PREFIX));
+        assert_eq!(public_prefix(&key).len(), PUBLIC_PREFIX_LEN);
+
+        let keys = ApiKeys::new(
+            vec![ApiKeyRecord {
+                prefix: public_prefix(&key),
+                key_hash: hash_key(&key),
+                owner: "dcscan-frontend".to_string(),
+                plan: "developer".to_string(),
+                created_at: 0,
+            }],
+            Some("s3cret"),
+        );
+        assert_eq!(keys.lookup(&key).await.unwrap().plan(), Plan::Developer);
+        assert!(keys.lookup(&generate_key()).await.is_none());
+
+        let mut headers = HeaderMap::new();
+        assert!(!keys.is_admin(&headers));
+        headers.insert("authorization", HeaderValue::from_static("Bearer s3cret"));
+        assert!(keys.is_admin(&headers));
+        headers.insert("authorization", HeaderValue::from_static("Bearer wrong"));
+        assert!(!keys.is_admin(&headers));
+    }
+
+    #[tokio::test]
+    async fn test_free_key_caps() {
+        let free = |owner: &str, n: usize| ApiKeyRecord {
+            prefix: format!("dcs_{}{}", owner, n),
+            key_hash: format!("{}{}", owner, n),
+            owner: owner.to_string(),
+            plan: "free".to_string(),
+            created_at: 0,
+        };
+        let keys = ApiKeys::new(
+            (0..FREE_KEYS_PER_OWNER).map(|n| free("alice", n)).collect(),
+            None,
+        );
+
+        assert!(matches!(
+            keys.admit_free_key("alice", "10.0.0.1").await,
+            Err(ApiError::TooManyRequests(_))
+        ));
+
+        for _ in 0..FREE_KEYS_PER_IP {
+            keys.admit_free_key("bob", "10.0.0.2").await.unwrap();
+        }
+        assert!(matches!(
+            keys.admit_free_key("carol", "10.0.0.2").await,
+            Err(ApiError::TooManyRequests(_))
+        ));
+        keys.admit_free_key("carol", "10.0.0.3").await.unwrap();
+    }
+}
@@ -5,22 +5,43 @@
 use axum::{
     extract::{Path, Query, State},
     http::StatusCode,
+    middleware,
     response::Json,
-    routing::{get, post},
+    routing::{delete, get, post},
     Router,
 };
+use rope_economics::{EconomicsOracle, FeeMarket};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use tower_http::cors::{Any, CorsLayer};
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, EnvFilter};
+use utoipa::{IntoParams, ToSchema};
 
 mod api;
+mod charts;
 mod db;
+mod economics;
+mod graphql;
 mod indexer;
+mod keys;
 mod models;
+mod openapi;
+mod performance;
+mod search;
+mod tokens;
+mod verify;
+mod ws;
 
 use api::*;
+use charts::{ChartSeries, Metric, Period};
+use db::Database;
+use indexer::{follow_node, normalize_hash, Indexer, IndexerStats};
+use keys::ApiKeys;
+use models::{AccountRow, Cursor, Page, TransactionRow, ValidatorRow};
+use performance::ValidatorPerformance;
+use verify::{NodeRpc, Solc, Verifier};
+use ws::{LiveEvent, LiveHub};
 
 // DC FAT Token contract address on XDC Network
 const DC_FAT_CONTRACT: &str = "0x20b59e6c5deb7d7ced2ca823c6ca81dd3f7e9a3a";
@@ -31,6 +52,29 @@ const PRICE_CACHE_TTL_SECS: u64 = 300;
 // Fallback price
 const FALLBACK_PRICE: f64 = 0.00390;
 
+// Indexer database, overridden by DATABASE_URL
+const DEFAULT_DATABASE_URL: &str = "postgres://localhost/dcscan";
+
+// Node event bridge, overridden by NODE_WS_URL
+const DEFAULT_NODE_WS_URL: &str = "ws://127.0.0.1:8546";
+
+// Node JSON-RPC, overridden by NODE_RPC_URL
+const DEFAULT_NODE_RPC_URL: &str = "http://127.0.0.1:8545";
+
+// Solidity compiler, overridden by SOLC_PATH
+const DEFAULT_SOLC_PATH: &str = "solc";
+
+const DB_MAX_CONNECTIONS: u32 = 16;
+
+// Events buffered between the node feed and the indexer
+const INDEXER_QUEUE: usize = 4096;
+
+// API key usage flush interval
+const USAGE_FLUSH_SECS: u64 = 60;
+
+// Interval between prunes of closed rollup buckets' account sets
+const ROLLUP_PRUNE_SECS: u64 = 3600;
+
 /// Price data structure
 #[derive(Clone, Serialize, Deserialize, Debug)]
 pub struct PriceData {
@@ -57,13 +101,28 @@ impl Default for PriceData {
 
 /// Application state
 pub struct AppState {
-    /// Database pool (placeholder for now)
     pub chain_id: u64,
     pub network_name: String,
     /// HTTP client for price fetching
     pub http_client: reqwest::Client,
     /// Cached price data
     pub price_cache: RwLock<Option<PriceData>>,
+    /// Indexed chain data
+    pub db: Database,
+    /// Indexer counters
+    pub indexer: Arc<IndexerStats>,
+    /// Live WebSocket subscriptions
+    pub live: LiveHub,
+    /// API keys and rate limits
+    pub keys: ApiKeys,
+    /// GraphQL schema
+    pub graphql: graphql::ExplorerSchema,
+    /// Contract and payload verification
+    pub verifier: Verifier,
+    /// Base fee and fee history, replayed by the indexer
+    pub fees: Arc<RwLock<FeeMarket>>,
+    /// Supply and APY calculations
+    pub economics: EconomicsOracle,
 }
 
 #[tokio::main]
@@ -86,11 +145,82 @@ async fn main() -> anyhow::Result<()> {
         .build()
         .expect("Failed to create HTTP client");
 
+    // Database and indexer
+    let database_url =
+        std::env::var("DATABASE_URL").unwrap_or_else(|_| DEFAULT_DATABASE_URL.to_string());
+    let node_ws_url =
+        std::env::var("NODE_WS_URL").unwrap_or_else(|_| DEFAULT_NODE_WS_URL.to_string());
+    let db = Database::connect(&database_url, DB_MAX_CONNECTIONS).await?;
+
+    let node_rpc_url =
+        std::env::var("NODE_RPC_URL").unwrap_or_else(|_| DEFAULT_NODE_RPC_URL.to_string());
+    let solc_path = std::env::var("SOLC_PATH").unwrap_or_else(|_| DEFAULT_SOLC_PATH.to_string());
+    let verifier = Verifier::new(
+        NodeRpc::new(http_client.clone(), node_rpc_url),
+        Solc::new(solc_path),
+    );
+
+    // API keys, with today's usage restored so quotas survive restarts
+    let admin_token = std::env::var("EXPLORER_ADMIN_TOKEN").ok();
+    let api_keys = ApiKeys::new(db.api_keys().await?, admin_token.as_deref());
+    let usage_today = db.key_usage_on(chrono::Utc::now().date_naive()).await?;
+    api_keys
+        .seed_usage(
+            usage_today
+                .into_iter()
+                .map(|(prefix, requests)| (prefix, requests as u64))
+                .collect(),
+        )
+        .await;
+
+    let live = LiveHub::new();
+    let fees = Arc::new(RwLock::new(FeeMarket::default()));
+    let indexer = Indexer::new(Arc::new(db.clone()))
+        .with_live_hub(live.clone())
+        .with_fee_market(fees.clone());
+    let indexer_stats = indexer.stats();
+    let (event_tx, event_rx) = tokio::sync::mpsc::channel(INDEXER_QUEUE);
+    tokio::spawn(indexer.run(event_rx));
+    tokio::spawn(follow_node(node_ws_url, event_tx));
+
     let state = Arc::new(AppState {
         chain_id: 271828,
         network_name: "Datachain Rope Mainnet".to_string(),
         http_client,
         price_cache: RwLock::new(None),
+        graphql: graphql::schema(db.clone()),
+        verifier,
+        db,
+        indexer: indexer_stats,
+        live,
+        keys: api_keys,
+        fees,
+        economics: EconomicsOracle::mainnet(),
+    });
+
+    // Persist API key usage
+    let usage_state = Arc::clone(&state);
+    tokio::spawn(async move {
+        loop {
+            tokio::time::sleep(std::time::Duration::from_secs(USAGE_FLUSH_SECS)).await;
+            usage_state.keys.flush(&usage_state).await;
+        }
+    });
+
+    // Account sets are only needed while late transactions can still land in a bucket
+    let prune_state = Arc::clone(&state);
+    tokio::spawn(async move {
+        loop {
+            tokio::time::sleep(std::time::Duration::from_secs(ROLLUP_PRUNE_SECS)).await;
+            let before = chrono::Utc::now().timestamp() - charts::DAY;
+            if let Err(e) = prune_state.db.prune_rollup_accounts(before).await {
+                tracing::warn!("Rollup prune failed: {}", e);
+            }
+            let before = chrono::Utc::now().timestamp() - performance::HEARTBEAT_RETENTION;
+            if let Err(e) = prune_state.db.prune_heartbeats(before).await {
+                tracing::warn!("Heartbeat prune failed: {}", e);
+            }
+        }
     });
 
     // Start background price fetching task
@@ -116,12 +246,30 @@ async fn main() -> anyhow::Result<()> {
         .route("/", get(root))
         .route("/health", get(health))
         .route("/api/v1/status", get(status))
+        // Live subscriptions
+        .route("/api/v1/ws", get(ws::ws_handler))
+        // Composite queries
+        .route(
+            graphql::GRAPHQL_PATH,
+            get(graphql::graphiql).post(graphql::graphql_handler),
+        )
+        // Verification
+        .route("/api/v1/verify/contract", post(verify::verify_contract))
+        .route("/api/v1/verify/string", post(verify::verify_string))
+        .route("/api/v1/verify/:target", get(verify::get_verification))
+        // API keys
+        .route("/api/v1/plans", get(keys::list_plans))
+        .route("/api/v1/keys", get(keys::list_keys).post(keys::issue_key))
+        .route("/api/v1/keys/usage", get(keys::key_usage))
+        .route("/api/v1/keys/:prefix", delete(keys::revoke_key))
         // Stats
         .route("/api/v1/stats", get(stats))
+        .route("/api/v1/economics", get(economics::economics))
         .route("/api/v1/stats/charts/:chart_type", get(chart_data))
         // Strings (Blocks)
         .route("/api/v1/strings", get(list_strings))
         .route("/api/v1/strings/latest", get(latest_strings))
+        .route("/api/v1/strings/rejected", get(rejected_strings))
         .route("/api/v1/strings/:id", get(get_string))
         // Transactions
         .route("/api/v1/transactions", get(list_transactions))
@@ -142,6 +290,14 @@ async fn main() -> anyhow::Result<()> {
         // Validators
         .route("/api/v1/validators", get(list_validators))
         .route("/api/v1/validators/:address", get(get_validator))
+        .route(
+            "/api/v1/validators/:address/performance",
+            get(validator_performance),
+        )
+        .route(
+            "/api/v1/validators/:address/rewards",
+            get(validator_rewards),
+        )
         // AI Agents
         .route("/api/v1/ai-agents", get(list_ai_agents))
         .route("/api/v1/ai-agents/:id", get(get_ai_agent))
@@ -151,7 +307,8 @@ async fn main() -> anyhow::Result<()> {
         .route("/api/v1/databoxes/:id", get(get_databox))
         .route("/api/v1/databoxes/map", get(databox_map))
         // Search
-        .route("/api/v1/search", get(search))
+        .route("/api/v1/search", get(search::search))
+        .route("/api/v1/search/autocomplete", get(search::autocomplete))
         // Gas & Prices
         .route("/api/v1/gas/price", get(gas_price))
         .route("/api/v1/gas/oracle", get(gas_oracle))
@@ -192,6 +349,12 @@ async fn main() -> anyhow::Result<()> {
             "/api/v1/votes/:target_type/:target_id",
             get(get_votes_for_target),
         )
+        // OpenAPI spec and Swagger UI
+        .merge(openapi::swagger_ui())
+        .layer(middleware::from_fn_with_state(
+            Arc::clone(&state),
+            keys::enforce,
+        ))
         .layer(cors)
         .with_state(state);
 
@@ -200,7 +363,11 @@ async fn main() -> anyhow::Result<()> {
     tracing::info!("API docs: http://{}/api/v1/status", addr);
 
     let listener = tokio::net::TcpListener::bind(addr).await?;
-    axum::serve(listener, app).await?;
+    axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<std::net::SocketAddr>(),
+    )
+    .await?;
 
     Ok(())
 }
@@ -314,9 +481,23 @@ async fn fetch_and_cache_price(state: &Arc<AppState>) -> Result<PriceData, anyho
         }
     };
 
+    // Fallback prices are not charted
+    if price_data.source != "fallback" {
+        if let Err(e) = state
+            .db
+            .record_price(price_data.timestamp, price_data.price)
+            .await
+        {
+            tracing::warn!("Price sample not recorded: {}", e);
+        }
+    }
+
     // Update cache
     let mut cache = state.price_cache.write().await;
     *cache = Some(price_data.clone());
+    state
+        .live
+        .publish(LiveEvent::PriceUpdate(price_data.clone()));
 
     Ok(price_data)
 }
@@ -335,6 +516,12 @@ fn rand_variation() -> f64 {
 // Route Handlers
 // ============================================================================
 
+#[utoipa::path(
+    get,
+    path = "/",
+    tag = "Status",
+    responses((status = 200, description = "Root"))
+)]
 async fn root() -> Json<serde_json::Value> {
     Json(serde_json::json!({
         "name": "DC Explorer API",
@@ -345,6 +532,12 @@ async fn root() -> Json<serde_json::Value> {
     }))
 }
 
+#[utoipa::path(
+    get,
+    path = "/health",
+    tag = "Status",
+    responses((status = 200, description = "Health"))
+)]
 async fn health() -> Json<serde_json::Value> {
     Json(serde_json::json!({
         "status": "healthy",
@@ -352,11 +545,18 @@ async fn health() -> Json<serde_json::Value> {
     }))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/v1/status",
+    tag = "Status",
+    responses((status = 200, description = "Status"))
+)]
 async fn status(State(state): State<Arc<AppState>>) -> Json<serde_json::Value> {
     Json(serde_json::json!({
         "chainId": state.chain_id,
         "networkName": state.network_name,
         "version": "1.0.0",
+        "indexer": state.indexer.to_json(),
         "endpoints": {
             "stats": "/api/v1/stats",
             "strings": "/api/v1/strings",
@@ -366,22 +566,42 @@ async fn status(State(state): State<Arc<AppState>>) -> Json<serde_json::Value> {
             "validators": "/api/v1/validators",
             "aiAgents": "/api/v1/ai-agents",
             "databoxes": "/api/v1/databoxes",
-            "search": "/api/v1/search"
-        }
+            "search": "/api/v1/search",
+            "ws": "/api/v1/ws",
+            "graphql": graphql::GRAPHQL_PATH,
+            "verify": "/api/v1/verify/{target}",
+            "openapi": openapi::OPENAPI_PATH,
+            "docs": openapi::DOCS_PATH
+        },
+        "wsClients": state.live.connections()
     }))
 }
 
-async fn stats(State(state): State<Arc<AppState>>) -> Json<serde_json::Value> {
+#[utoipa::path(
+    get,
+    path = "/api/v1/stats",
+    tag = "Stats",
+    responses((status = 200, description = "Stats"))
+)]
+async fn stats(State(state): State<Arc<AppState>>) -> ApiResult {
+    let counts = state.db.counts().await?;
+    let snapshot = economics::snapshot(&state, chrono::Utc::now().timestamp()).await?;
+    let total_supply = snapshot.supply.total_supply;
+
     // Get cached price data
     let price_cache = state.price_cache.read().await;
     let price_data = price_cache.clone().unwrap_or_default();
     let fat_price = format!("${:.6}", price_data.price);
-    let market_cap = format!("${:.0}", price_data.price * 10_000_000_000.0);
+    let market_cap = format!(
+        "${:.0}",
+        price_data.price * (total_supply / rope_economics::ONE_FAT) as f64
+    );
 
-    Json(serde_json::json!({
-        "totalStrings": 1247893,
-        "totalTransactions": 4892451,
-        "validators": 127,
+    Ok(Json(serde_json::json!({
+        "totalStrings": counts.strings,
+        "totalTransactions": counts.transactions,
+        "totalAccounts": counts.accounts,
+        "validators": counts.active_validators,
         "aiAgents": 5,
         "databoxes": 284,
         "gasPrice": "0.001 gwei",
@@ -390,14 +610,22 @@ async fn stats(State(state): State<Arc<AppState>>) -> Json<serde_json::Value> {
         "fatPriceChange24h": price_data.change_24h,
         "fatPriceSource": price_data.source,
         "marketCap": market_cap,
-        "circulatingSupply": "10,000,000,000 FAT",
-        "tps": 2847,
+        "circulatingSupply": format!("{} FAT", economics::format_fat(total_supply)),
+        "inflationPercent": snapshot.supply.inflation_percent,
+        "stakingApy": snapshot.staking_apy,
+        "tps": counts.recent_transactions as f64 / 60.0,
         "avgBlockTime": "2.8s",
         "finalityTime": "4.2s"
-    }))
+    })))
 }
 
 /// DC FAT Token Price endpoint
+#[utoipa::path(
+    get,
+    path = "/api/v1/dcfat/price",
+    tag = "Price",
+    responses((status = 200, description = "Dcfat price"))
+)]
 async fn dcfat_price(State(state): State<Arc<AppState>>) -> Json<serde_json::Value> {
     // Check cache first
     let cache = state.price_cache.read().await;
@@ -448,357 +676,600 @@ async fn dcfat_price(State(state): State<Arc<AppState>>) -> Json<serde_json::Val
     }))
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 struct ChartParams {
+    /// `24h`, `7d` (default), `30d` or `1y`
     period: Option<String>,
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/v1/stats/charts/{chart_type}",
+    tag = "Stats",
+    params(
+        ("chart_type" = String, Path, description = "transactions, active-accounts, gas-used, tps or price"),
+        ChartParams
+    ),
+    responses(
+        (status = 200, description = "Chart data", body = ChartSeries),
+        (status = 400, description = "Unknown chart or period", body = ErrorBody)
+    )
+)]
 async fn chart_data(
+    State(state): State<Arc<AppState>>,
     Path(chart_type): Path<String>,
     Query(params): Query<ChartParams>,
-) -> Json<serde_json::Value> {
-    let _period = params.period.unwrap_or_else(|| "7d".to_string());
-
-    // Generate sample chart data
-    let data: Vec<serde_json::Value> = (0..7)
-        .map(|i| {
-            serde_json::json!({
-                "timestamp": chrono::Utc::now().timestamp() - (i * 86400),
-                "value": 1000 + (i * 100)
-            })
-        })
-        .collect();
-
-    Json(serde_json::json!({
-        "chartType": chart_type,
-        "data": data
+) -> ApiResult<ChartSeries> {
+    let metric = Metric::parse(&chart_type).ok_or_else(|| {
+        ApiError::BadRequest(format!(
+            "unknown chart '{}'; expected one of {}",
+            chart_type,
+            Metric::ALL.join(", ")
+        ))
+    })?;
+    let period = params.period.as_deref().unwrap_or("7d");
+    let period = Period::parse(period)
+        .ok_or_else(|| ApiError::BadRequest(format!("unknown period '{}'", period)))?;
+
+    let resolution = period.resolution();
+    let (first, last) = period.range(chrono::Utc::now().timestamp());
+    let rows = state.db.rollups(resolution, first, last).await?;
+    let (interval, data) = charts::series(metric, &rows, first, last, resolution);
+
+    Ok(Json(ChartSeries {
+        chart_type,
+        period: period.as_str().to_string(),
+        interval,
+        data,
     }))
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 struct PaginationParams {
     page: Option<u32>,
     limit: Option<u32>,
 }
 
-async fn list_strings(Query(params): Query<PaginationParams>) -> Json<serde_json::Value> {
-    let page = params.page.unwrap_or(1);
-    let limit = params.limit.unwrap_or(20);
-
-    let strings: Vec<serde_json::Value> = (0..limit)
-        .map(|i| {
-            let num = 1247893 - (page - 1) * limit - i;
-            serde_json::json!({
-                "number": num,
-                "hash": format!("0x{:064x}", num),
-                "transactions": 15 + (i % 20),
-                "validator": format!("0x7f3a{}8d2e", i),
-                "timestamp": chrono::Utc::now().timestamp() - (i as i64 * 3),
-                "status": "Final",
-                "aiVerified": true
-            })
-        })
-        .collect();
-
-    Json(serde_json::json!({
-        "strings": strings,
-        "pagination": {
-            "page": page,
-            "limit": limit,
-            "total": 1247893
-        }
+#[utoipa::path(
+    get,
+    path = "/api/v1/strings",
+    tag = "Strings",
+    params(CursorParams),
+    responses(
+        (status = 200, description = "List strings", body = StringList),
+        (status = 400, description = "Invalid cursor", body = ErrorBody)
+    )
+)]
+async fn list_strings(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<CursorParams>,
+) -> ApiResult<StringList> {
+    let rows = state
+        .db
+        .list_strings(params.cursor()?.as_ref(), params.fetch_limit())
+        .await?;
+    let page = Page::from_rows(rows, params.limit(), |row| Cursor::key(row.number));
+
+    Ok(Json(StringList {
+        pagination: Some(Pagination::new(&page, params.limit())),
+        strings: page.items,
     }))
 }
 
-async fn latest_strings() -> Json<serde_json::Value> {
-    let strings: Vec<serde_json::Value> = (0..10)
-        .map(|i| {
-            serde_json::json!({
-                "number": 1247893 - i,
-                "hash": format!("0x{:064x}", 1247893 - i),
-                "transactions": 15 + (i % 20),
-                "validator": format!("0x7f3a{}8d2e", i),
-                "timestamp": chrono::Utc::now().timestamp() - (i as i64 * 3),
-                "status": "Final"
-            })
-        })
-        .collect();
-
-    Json(serde_json::json!({ "strings": strings }))
+#[utoipa::path(
+    get,
+    path = "/api/v1/strings/latest",
+    tag = "Strings",
+    responses((status = 200, description = "Latest strings", body = StringList))
+)]
+async fn latest_strings(State(state): State<Arc<AppState>>) -> ApiResult<StringList> {
+    let strings = state.db.list_strings(None, 10).await?;
+    Ok(Json(StringList {
+        strings,
+        pagination: None,
+    }))
 }
 
-async fn get_string(Path(id): Path<String>) -> Json<serde_json::Value> {
-    Json(serde_json::json!({
-        "number": id.parse::<u64>().unwrap_or(1247893),
-        "hash": format!("0x{:064x}", id.parse::<u64>().unwrap_or(1247893)),
-        "parentHash": format!("0x{:064x}", id.parse::<u64>().unwrap_or(1247892)),
-        "timestamp": chrono::Utc::now().timestamp(),
-        "transactions": 24,
-        "validator": "0x7f3a8d2e4b1c9f0a",
-        "gasUsed": "1,247,893",
-        "gasLimit": "30,000,000",
-        "status": "Final",
-        "aiTestimonies": 5,
-        "complementHash": format!("0x{:064x}", id.parse::<u64>().unwrap_or(1247893) + 1)
+#[utoipa::path(
+    get,
+    path = "/api/v1/strings/{id}",
+    tag = "Strings",
+    params(("id" = String, Path)),
+    responses(
+        (status = 200, description = "Get string", body = StringDetail),
+        (status = 404, description = "String not found", body = ErrorBody)
+    )
+)]
+async fn get_string(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+) -> ApiResult<StringDetail> {
+    let key = match id.parse::<u64>() {
+        Ok(_) => id.clone(),
+        Err(_) => normalize_hash(&id),
+    };
+    let string = state
+        .db
+        .get_string(&key)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("String {}", id)))?;
+    let transactions = state.db.string_transactions(&string.hash).await?;
+
+    Ok(Json(StringDetail {
+        number: string.number,
+        hash: string.hash,
+        creator: string.creator,
+        string_type: string.string_type,
+        timestamp: string.timestamp,
+        transactions: string.transactions,
+        status: string.status,
+        rejection_reason: string.rejection_reason,
+        anchor_id: string.anchor_id,
+        transaction_list: transactions,
     }))
 }
 
-async fn list_transactions(Query(params): Query<PaginationParams>) -> Json<serde_json::Value> {
-    let page = params.page.unwrap_or(1);
-    let limit = params.limit.unwrap_or(20);
-
-    let txs: Vec<serde_json::Value> = (0..limit)
-        .map(|i| {
-            serde_json::json!({
-                "hash": format!("0x8f2a9c3d{}4e7b1f8a", i),
-                "from": format!("0x7f3a{}8d2e", i),
-                "to": format!("0x2b9c{}4f1a", i + 1),
-                "value": format!("{}.00", 100 + i * 50),
-                "status": "Success",
-                "aiVerified": true,
-                "string": 1247893 - i,
-                "timestamp": chrono::Utc::now().timestamp() - (i as i64 * 5)
-            })
-        })
-        .collect();
-
-    Json(serde_json::json!({
-        "transactions": txs,
-        "pagination": {
-            "page": page,
-            "limit": limit,
-            "total": 4892451
-        }
+#[utoipa::path(
+    get,
+    path = "/api/v1/strings/rejected",
+    tag = "Strings",
+    params(CursorParams),
+    responses(
+        (status = 200, description = "Rejected strings", body = StringList),
+        (status = 400, description = "Invalid cursor", body = ErrorBody)
+    )
+)]
+async fn rejected_strings(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<CursorParams>,
+) -> ApiResult<StringList> {
+    let rows = state
+        .db
+        .rejected_strings(params.cursor()?.as_ref(), params.fetch_limit())
+        .await?;
+    let page = Page::from_rows(rows, params.limit(), |row| Cursor::key(row.number));
+
+    Ok(Json(StringList {
+        pagination: Some(Pagination::new(&page, params.limit())),
+        strings: page.items,
     }))
 }
 
-async fn latest_transactions() -> Json<serde_json::Value> {
-    let txs: Vec<serde_json::Value> = (0..10)
-        .map(|i| {
-            serde_json::json!({
-                "hash": format!("0x8f2a9c3d{}4e7b1f8a", i),
-                "from": format!("0x7f3a{}8d2e", i),
-                "to": format!("0x2b9c{}4f1a", i + 1),
-                "value": format!("{}.00", 100 + i * 50),
-                "status": "Success",
-                "aiVerified": i % 3 != 0
-            })
-        })
-        .collect();
-
-    Json(serde_json::json!({ "transactions": txs }))
+#[utoipa::path(
+    get,
+    path = "/api/v1/transactions",
+    tag = "Transactions",
+    params(CursorParams),
+    responses(
+        (status = 200, description = "List transactions", body = TransactionList),
+        (status = 400, description = "Invalid cursor", body = ErrorBody)
+    )
+)]
+async fn list_transactions(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<CursorParams>,
+) -> ApiResult<TransactionList> {
+    let rows = state
+        .db
+        .list_transactions(params.cursor()?.as_ref(), params.fetch_limit())
+        .await?;
+    let page = Page::from_rows(rows, params.limit(), |row| Cursor::key(row.seq));
+
+    Ok(Json(TransactionList {
+        pagination: Some(Pagination::new(&page, params.limit())),
+        transactions: page.items,
+    }))
 }
 
-async fn get_transaction(Path(hash): Path<String>) -> Json<serde_json::Value> {
-    Json(serde_json::json!({
-        "hash": hash,
-        "status": "Success",
-        "string": 1247893,
-        "from": "0x7f3a8d2e4b1c9f0a",
-        "to": "0x2b9c4f1a8e7d3b6c",
-        "value": "1,250.00 FAT",
-        "gasUsed": "21,000",
-        "gasPrice": "0.001 gwei",
-        "timestamp": chrono::Utc::now().timestamp(),
-        "aiTestimony": {
-            "verified": true,
-            "agents": ["ValidationAgent", "ComplianceAgent"],
-            "confidence": 0.98
-        },
-        "input": "0x",
-        "logs": []
+#[utoipa::path(
+    get,
+    path = "/api/v1/transactions/latest",
+    tag = "Transactions",
+    responses((status = 200, description = "Latest transactions", body = TransactionList))
+)]
+async fn latest_transactions(State(state): State<Arc<AppState>>) -> ApiResult<TransactionList> {
+    let transactions = state.db.list_transactions(None, 10).await?;
+    Ok(Json(TransactionList {
+        transactions,
+        pagination: None,
     }))
 }
 
-async fn get_account(Path(address): Path<String>) -> Json<serde_json::Value> {
-    Json(serde_json::json!({
-        "address": address,
-        "balance": "10,247.89 FAT",
-        "balanceUsd": "$868.00",
-        "transactionCount": 147,
-        "isContract": false,
-        "isValidator": false,
-        "firstSeen": chrono::Utc::now().timestamp() - 86400 * 30,
-        "lastSeen": chrono::Utc::now().timestamp()
+#[utoipa::path(
+    get,
+    path = "/api/v1/transactions/{hash}",
+    tag = "Transactions",
+    params(("hash" = String, Path)),
+    responses(
+        (status = 200, description = "Get transaction", body = TransactionDetail),
+        (status = 404, description = "Transaction not found", body = ErrorBody)
+    )
+)]
+async fn get_transaction(
+    State(state): State<Arc<AppState>>,
+    Path(hash): Path<String>,
+) -> ApiResult<TransactionDetail> {
+    let transaction = state
+        .db
+        .get_transaction(&normalize_hash(&hash))
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("Transaction {}", hash)))?;
+
+    // Decode calls into verified contracts
+    let contract = match &transaction.to_address {
+        Some(to) => state
+            .db
+            .get_verification(to)
+            .await?
+            .filter(|v| v.kind == verify::KIND_CONTRACT && v.is_verified()),
+        None => None,
+    };
+    let decoded_input = match (&contract, &transaction.input) {
+        (Some(contract), Some(input)) => contract
+            .metadata
+            .as_ref()
+            .and_then(|abi| verify::decode_call(&abi.0, input)),
+        _ => None,
+    };
+
+    let token_transfers = state
+        .db
+        .transaction_token_transfers(&transaction.hash)
+        .await?;
+    let internal = state.db.internal_transaction(&transaction.hash).await?;
+
+    Ok(Json(TransactionDetail {
+        contract_name: contract.map(|c| c.name),
+        decoded_input,
+        token_transfers,
+        internal,
+        transaction,
     }))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/v1/accounts/{address}",
+    tag = "Accounts",
+    params(("address" = String, Path)),
+    responses(
+        (status = 200, description = "Get account", body = AccountRow),
+        (status = 404, description = "Account not found", body = ErrorBody)
+    )
+)]
+async fn get_account(
+    State(state): State<Arc<AppState>>,
+    Path(address): Path<String>,
+) -> ApiResult<AccountRow> {
+    let account = state
+        .db
+        .get_account(&normalize_hash(&address))
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("Account {}", address)))?;
+    Ok(Json(account))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/accounts/{address}/transactions",
+    tag = "Accounts",
+    params(("address" = String, Path), CursorParams),
+    responses(
+        (status = 200, description = "Account transactions", body = AccountTransactions),
+        (status = 400, description = "Invalid cursor", body = ErrorBody)
+    )
+)]
 async fn account_transactions(
+    State(state): State<Arc<AppState>>,
     Path(address): Path<String>,
-    Query(params): Query<PaginationParams>,
-) -> Json<serde_json::Value> {
-    let txs: Vec<serde_json::Value> = (0..10)
-        .map(|i| {
-            serde_json::json!({
-                "hash": format!("0x{:064x}", i),
-                "from": if i % 2 == 0 { &address } else { "0x7f3a8d2e" },
-                "to": if i % 2 == 1 { &address } else { "0x2b9c4f1a" },
-                "value": format!("{}.00", 100 + i * 10),
-                "status": "Success"
-            })
-        })
-        .collect();
-
-    Json(serde_json::json!({
-        "address": address,
-        "transactions": txs
+    Query(params): Query<CursorParams>,
+) -> ApiResult<AccountTransactions> {
+    let address = normalize_hash(&address);
+    let rows = state
+        .db
+        .account_transactions(&address, params.cursor()?.as_ref(), params.fetch_limit())
+        .await?;
+    let page = Page::from_rows(rows, params.limit(), |row| Cursor::key(row.seq));
+
+    Ok(Json(AccountTransactions {
+        address,
+        pagination: Pagination::new(&page, params.limit()),
+        transactions: page.items,
     }))
 }
 
-async fn account_tokens(Path(address): Path<String>) -> Json<serde_json::Value> {
-    Json(serde_json::json!({
-        "address": address,
-        "tokens": [
-            {
-                "name": "DC FAT",
-                "symbol": "FAT",
-                "balance": "10,247.89",
-                "value": "$868.00"
-            },
-            {
-                "name": "Wrapped ETH",
-                "symbol": "WETH",
-                "balance": "1.5",
-                "value": "$3,750.00"
-            }
-        ]
-    }))
+#[utoipa::path(
+    get,
+    path = "/api/v1/accounts/{address}/tokens",
+    tag = "Accounts",
+    params(("address" = String, Path)),
+    responses((status = 200, description = "Account tokens", body = AccountTokens))
+)]
+async fn account_tokens(
+    State(state): State<Arc<AppState>>,
+    Path(address): Path<String>,
+) -> ApiResult<AccountTokens> {
+    let address = normalize_hash(&address);
+    let tokens = state.db.account_token_balances(&address).await?;
+    Ok(Json(AccountTokens { address, tokens }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/tokens",
+    tag = "Tokens",
+    responses((status = 200, description = "List tokens"))
+)]
+async fn list_tokens() -> Json<serde_json::Value> {
+    Json(serde_json::json!({ "tokens": token_directory() }))
 }
 
-async fn list_tokens() -> Json<serde_json::Value> {
-    Json(serde_json::json!({
-        "tokens": [
-            {
-                "address": "0x0000000000000000000000000000000000000001",
-                "name": "DC FAT",
-                "symbol": "FAT",
-                "decimals": 18,
-                "totalSupply": "10,000,000,000",
-                "holders": 147893,
-                "transfers": 4892451
-            },
-            {
-                "address": "0x0000000000000000000000000000000000000002",
-                "name": "Wrapped ETH",
-                "symbol": "WETH",
-                "decimals": 18,
-                "totalSupply": "1,000,000",
-                "holders": 8947,
-                "transfers": 247891
-            }
-        ]
-    }))
+/// Known tokens
+pub fn token_directory() -> Vec<serde_json::Value> {
+    let fat_supply = economics::total_supply_display(&EconomicsOracle::mainnet());
+    vec![
+        serde_json::json!({
+            "address": "0x0000000000000000000000000000000000000001",
+            "name": "DC FAT",
+            "symbol": "FAT",
+            "decimals": 18,
+            "totalSupply": fat_supply,
+            "holders": 147893,
+            "transfers": 4892451
+        }),
+        serde_json::json!({
+            "address": "0x0000000000000000000000000000000000000002",
+            "name": "Wrapped ETH",
+            "symbol": "WETH",
+            "decimals": 18,
+            "totalSupply": "1,000,000",
+            "holders": 8947,
+            "transfers": 247891
+        }),
+    ]
 }
 
-async fn get_token(
-    Path(address): Path<String>,
-    State(state): State<Arc<AppState>>,
-) -> Json<serde_json::Value> {
+#[utoipa::path(
+    get,
+    path = "/api/v1/tokens/{address}",
+    tag = "Tokens",
+    params(("address" = String, Path)),
+    responses((status = 200, description = "Get token"))
+)]
+async fn get_token(Path(address): Path<String>, State(state): State<Arc<AppState>>) -> ApiResult {
     // Check if this is the DC FAT token
     let is_dcfat = address.to_lowercase() == DC_FAT_CONTRACT.to_lowercase()
         || address == "0x0000000000000000000000000000000000000001";
 
+    let total_supply = state
+        .economics
+        .emission()
+        .total_supply_at(chrono::Utc::now().timestamp());
+    let supply_fat = (total_supply / rope_economics::ONE_FAT) as f64;
     let (price_str, market_cap_str) = if is_dcfat {
         let cache = state.price_cache.read().await;
         if let Some(price_data) = &*cache {
             (
                 format!("${:.6}", price_data.price),
-                format!("${:.0}", price_data.price * 10_000_000_000.0),
+                format!("${:.0}", price_data.price * supply_fat),
             )
         } else {
             (
                 format!("${:.6}", FALLBACK_PRICE),
-                format!("${:.0}", FALLBACK_PRICE * 10_000_000_000.0),
+                format!("${:.0}", FALLBACK_PRICE * supply_fat),
             )
         }
     } else {
         ("$0.00".to_string(), "$0".to_string())
     };
 
-    Json(serde_json::json!({
+    let token = state.db.token_stats(&normalize_hash(&address)).await?;
+
+    Ok(Json(serde_json::json!({
         "address": address,
         "name": if is_dcfat { "DC FAT" } else { "Unknown Token" },
         "symbol": if is_dcfat { "FAT" } else { "???" },
         "decimals": 18,
-        "totalSupply": if is_dcfat { "10,000,000,000" } else { "0" },
-        "holders": if is_dcfat { 147893 } else { 0 },
-        "transfers": if is_dcfat { 4892451 } else { 0 },
+        "totalSupply": if is_dcfat { economics::format_fat(total_supply) } else { "0".to_string() },
+        "circulating": token.circulating,
+        "holders": token.holders,
+        "transfers": token.transfers,
         "price": price_str,
         "marketCap": market_cap_str,
         "contract": if is_dcfat { DC_FAT_CONTRACT } else { &address },
         "network": "XDC Network"
+    })))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/tokens/{address}/holders",
+    tag = "Tokens",
+    params(("address" = String, Path)),
+    params(("address" = String, Path), CursorParams),
+    responses(
+        (status = 200, description = "Holders by balance", body = TokenHolders),
+        (status = 400, description = "Invalid cursor", body = ErrorBody)
+    )
+)]
+async fn token_holders(
+    State(state): State<Arc<AppState>>,
+    Path(address): Path<String>,
+    Query(params): Query<CursorParams>,
+) -> ApiResult<TokenHolders> {
+    // Balances change between requests, so holders page by rank
+    let offset = params.cursor()?.map_or(0, |c| c.key.max(0));
+    let token = normalize_hash(&address);
+    let rows = state
+        .db
+        .token_holders(&token, offset, params.fetch_limit())
+        .await?;
+    let next = offset + params.limit() as i64;
+    let page = Page::from_rows(rows, params.limit(), |_| Cursor::key(next));
+
+    Ok(Json(TokenHolders {
+        token,
+        pagination: Pagination::new(&page, params.limit()),
+        holders: page.items,
     }))
 }
 
-async fn token_holders(Path(address): Path<String>) -> Json<serde_json::Value> {
-    let holders: Vec<serde_json::Value> = (0..10)
-        .map(|i| {
-            serde_json::json!({
-                "address": format!("0x{:040x}", i + 1),
-                "balance": format!("{}", 1000000 - i * 50000),
-                "percentage": format!("{:.2}%", 10.0 - i as f64 * 0.5)
-            })
-        })
-        .collect();
-
-    Json(serde_json::json!({
-        "token": address,
-        "holders": holders
-    }))
-}
-
-async fn token_transfers(Path(address): Path<String>) -> Json<serde_json::Value> {
-    let transfers: Vec<serde_json::Value> = (0..10)
-        .map(|i| {
-            serde_json::json!({
-                "hash": format!("0x{:064x}", i),
-                "from": format!("0x{:040x}", i),
-                "to": format!("0x{:040x}", i + 1),
-                "value": format!("{}", 100 + i * 10)
-            })
-        })
-        .collect();
-
-    Json(serde_json::json!({
-        "token": address,
-        "transfers": transfers
+#[utoipa::path(
+    get,
+    path = "/api/v1/tokens/{address}/transfers",
+    tag = "Tokens",
+    params(("address" = String, Path), CursorParams),
+    responses(
+        (status = 200, description = "Token transfers, newest first", body = TokenTransfers),
+        (status = 400, description = "Invalid cursor", body = ErrorBody)
+    )
+)]
+async fn token_transfers(
+    State(state): State<Arc<AppState>>,
+    Path(address): Path<String>,
+    Query(params): Query<CursorParams>,
+) -> ApiResult<TokenTransfers> {
+    let token = normalize_hash(&address);
+    let rows = state
+        .db
+        .token_transfers(&token, params.cursor()?.as_ref(), params.fetch_limit())
+        .await?;
+    let page = Page::from_rows(rows, params.limit(), |row| Cursor::key(row.seq));
+
+    Ok(Json(TokenTransfers {
+        token,
+        pagination: Pagination::new(&page, params.limit()),
+        transfers: page.items,
     }))
 }
 
-async fn list_validators() -> Json<serde_json::Value> {
-    let validators: Vec<serde_json::Value> = (0..20)
-        .map(|i| {
-            serde_json::json!({
-                "address": format!("0x{:040x}", i + 1),
-                "name": format!("Validator {}", i + 1),
-                "stake": format!("{}", 1000000 + i * 100000),
-                "strings": 12478 + i * 100,
-                "uptime": format!("{:.1}%", 99.9 - i as f64 * 0.1),
-                "aiAgent": if i < 5 { true } else { false }
-            })
-        })
-        .collect();
-
-    Json(serde_json::json!({
-        "validators": validators,
-        "totalStaked": "127,000,000 FAT",
-        "activeCount": 127
+#[utoipa::path(
+    get,
+    path = "/api/v1/validators",
+    tag = "Validators",
+    params(CursorParams),
+    responses(
+        (status = 200, description = "List validators", body = ValidatorList),
+        (status = 400, description = "Invalid cursor", body = ErrorBody)
+    )
+)]
+async fn list_validators(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<CursorParams>,
+) -> ApiResult<ValidatorList> {
+    let rows = state
+        .db
+        .list_validators(params.cursor()?.as_ref(), params.fetch_limit())
+        .await?;
+    let page = Page::from_rows(rows, params.limit(), |row| {
+        Cursor::with_tie(row.stake, row.address.clone())
+    });
+    let counts = state.db.counts().await?;
+    let total_staked = state.db.total_stake().await?;
+
+    Ok(Json(ValidatorList {
+        pagination: Pagination::new(&page, params.limit()),
+        validators: page.items,
+        total_staked,
+        active_count: counts.active_validators,
     }))
 }
 
-async fn get_validator(Path(address): Path<String>) -> Json<serde_json::Value> {
-    Json(serde_json::json!({
-        "address": address,
-        "name": "Validator 1",
-        "stake": "1,000,000 FAT",
-        "stringsProduced": 12478,
-        "uptime": "99.9%",
-        "rewards": "24,789 FAT",
-        "delegators": 147,
-        "aiAgent": true
+#[utoipa::path(
+    get,
+    path = "/api/v1/validators/{address}",
+    tag = "Validators",
+    params(("address" = String, Path)),
+    responses(
+        (status = 200, description = "Get validator", body = ValidatorRow),
+        (status = 404, description = "Validator not found", body = ErrorBody)
+    )
+)]
+async fn get_validator(
+    State(state): State<Arc<AppState>>,
+    Path(address): Path<String>,
+) -> ApiResult<ValidatorRow> {
+    let validator = state
+        .db
+        .get_validator(&normalize_hash(&address))
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("Validator {}", address)))?;
+    Ok(Json(validator))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/validators/{address}/performance",
+    tag = "Validators",
+    params(("address" = String, Path), ChartParams),
+    responses(
+        (status = 200, description = "Validator performance", body = ValidatorPerformance),
+        (status = 400, description = "Unknown period", body = ErrorBody),
+        (status = 404, description = "Validator not found", body = ErrorBody)
+    )
+)]
+async fn validator_performance(
+    State(state): State<Arc<AppState>>,
+    Path(address): Path<String>,
+    Query(params): Query<ChartParams>,
+) -> ApiResult<ValidatorPerformance> {
+    let period = params.period.as_deref().unwrap_or("24h");
+    let period = Period::parse(period)
+        .ok_or_else(|| ApiError::BadRequest(format!("unknown period '{}'", period)))?;
+
+    let address = normalize_hash(&address);
+    let now = chrono::Utc::now().timestamp();
+    let row = state
+        .db
+        .validator_performance(&address, performance::window_start(period, now))
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("Validator {}", address)))?;
+    Ok(Json(ValidatorPerformance::new(address, period, now, &row)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/validators/{address}/rewards",
+    tag = "Validators",
+    params(("address" = String, Path), CursorParams),
+    responses(
+        (status = 200, description = "Validator reward history", body = ValidatorRewards),
+        (status = 400, description = "Invalid cursor", body = ErrorBody),
+        (status = 404, description = "Validator not found", body = ErrorBody)
+    )
+)]
+async fn validator_rewards(
+    State(state): State<Arc<AppState>>,
+    Path(address): Path<String>,
+    Query(params): Query<CursorParams>,
+) -> ApiResult<ValidatorRewards> {
+    let address = normalize_hash(&address);
+    if state.db.get_validator(&address).await?.is_none() {
+        return Err(ApiError::NotFound(format!("Validator {}", address)));
+    }
+    let rows = state
+        .db
+        .validator_rewards(&address, params.cursor()?.as_ref(), params.fetch_limit())
+        .await?;
+    let page = Page::from_rows(rows, params.limit(), |row| Cursor::key(row.epoch));
+    let total_rewards = state.db.validator_reward_total(&address).await?;
+
+    Ok(Json(ValidatorRewards {
+        address,
+        pagination: Pagination::new(&page, params.limit()),
+        total_rewards,
+        rewards: page.items,
     }))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/v1/ai-agents",
+    tag = "AI Agents",
+    responses((status = 200, description = "List ai agents"))
+)]
 async fn list_ai_agents() -> Json<serde_json::Value> {
     Json(serde_json::json!({
         "agents": [
@@ -846,6 +1317,13 @@ async fn list_ai_agents() -> Json<serde_json::Value> {
     }))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/v1/ai-agents/{id}",
+    tag = "AI Agents",
+    params(("id" = String, Path)),
+    responses((status = 200, description = "Get ai agent"))
+)]
 async fn get_ai_agent(Path(id): Path<String>) -> Json<serde_json::Value> {
     Json(serde_json::json!({
         "id": id,
@@ -860,6 +1338,13 @@ async fn get_ai_agent(Path(id): Path<String>) -> Json<serde_json::Value> {
     }))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/v1/ai-agents/{id}/testimonies",
+    tag = "AI Agents",
+    params(("id" = String, Path)),
+    responses((status = 200, description = "Agent testimonies"))
+)]
 async fn agent_testimonies(Path(id): Path<String>) -> Json<serde_json::Value> {
     let testimonies: Vec<serde_json::Value> = (0..10)
         .map(|i| {
@@ -879,6 +1364,12 @@ async fn agent_testimonies(Path(id): Path<String>) -> Json<serde_json::Value> {
     }))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/v1/databoxes",
+    tag = "Databoxes",
+    responses((status = 200, description = "List databoxes"))
+)]
 async fn list_databoxes() -> Json<serde_json::Value> {
     let databoxes: Vec<serde_json::Value> = (0..20)
         .map(|i| {
@@ -912,6 +1403,13 @@ async fn list_databoxes() -> Json<serde_json::Value> {
     }))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/v1/databoxes/{id}",
+    tag = "Databoxes",
+    params(("id" = String, Path)),
+    responses((status = 200, description = "Get databox"))
+)]
 async fn get_databox(Path(id): Path<String>) -> Json<serde_json::Value> {
     Json(serde_json::json!({
         "id": id,
@@ -930,6 +1428,12 @@ async fn get_databox(Path(id): Path<String>) -> Json<serde_json::Value> {
     }))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/v1/databoxes/map",
+    tag = "Databoxes",
+    responses((status = 200, description = "Databox map"))
+)]
 async fn databox_map() -> Json<serde_json::Value> {
     let markers: Vec<serde_json::Value> = vec![
         serde_json::json!({"city": "Paris", "lat": 48.8566, "lng": 2.3522, "count": 12}),
@@ -948,54 +1452,55 @@ async fn databox_map() -> Json<serde_json::Value> {
     }))
 }
 
-#[derive(Deserialize)]
-struct SearchQuery {
-    q: String,
-}
-
-async fn search(Query(query): Query<SearchQuery>) -> Json<serde_json::Value> {
-    let q = query.q.to_lowercase();
-
-    let result_type = if q.starts_with("0x") && q.len() == 66 {
-        "transaction"
-    } else if q.starts_with("0x") && q.len() == 42 {
-        "account"
-    } else if q.parse::<u64>().is_ok() {
-        "string"
-    } else {
-        "unknown"
-    };
-
+#[utoipa::path(
+    get,
+    path = "/api/v1/gas/price",
+    tag = "Gas",
+    responses((status = 200, description = "Gas price"))
+)]
+async fn gas_price(State(state): State<Arc<AppState>>) -> Json<serde_json::Value> {
+    let suggestion = state.fees.read().await.suggest();
+    let price = |tip: u128| format!("{} gwei", gwei(suggestion.base_fee + tip));
     Json(serde_json::json!({
-        "query": query.q,
-        "type": result_type,
-        "results": [
-            {
-                "type": result_type,
-                "value": query.q,
-                "url": format!("/api/v1/{}/{}", result_type, query.q)
-            }
-        ]
+        "slow": price(suggestion.slow),
+        "standard": price(suggestion.standard),
+        "fast": price(suggestion.fast),
+        "instant": price(suggestion.instant),
+        "baseFee": format!("{} gwei", gwei(suggestion.base_fee))
     }))
 }
 
-async fn gas_price() -> Json<serde_json::Value> {
+#[utoipa::path(
+    get,
+    path = "/api/v1/gas/oracle",
+    tag = "Gas",
+    responses((status = 200, description = "Gas oracle"))
+)]
+async fn gas_oracle(State(state): State<Arc<AppState>>) -> Json<serde_json::Value> {
+    let suggestion = state.fees.read().await.suggest();
+    let ratios: Vec<String> = suggestion
+        .gas_used_ratio
+        .iter()
+        .map(|r| format!("{:.2}", r))
+        .collect();
     Json(serde_json::json!({
-        "slow": "0.0005 gwei",
-        "standard": "0.001 gwei",
-        "fast": "0.002 gwei",
-        "instant": "0.005 gwei"
+        "SafeGasPrice": gwei(suggestion.base_fee + suggestion.slow),
+        "ProposeGasPrice": gwei(suggestion.base_fee + suggestion.standard),
+        "FastGasPrice": gwei(suggestion.base_fee + suggestion.fast),
+        "suggestBaseFee": gwei(suggestion.base_fee),
+        "gasUsedRatio": ratios.join(",")
     }))
 }
 
-async fn gas_oracle() -> Json<serde_json::Value> {
-    Json(serde_json::json!({
-        "SafeGasPrice": "0.0005",
-        "ProposeGasPrice": "0.001",
-        "FastGasPrice": "0.002",
-        "suggestBaseFee": "0.0003",
-        "gasUsedRatio": "0.4,0.5,0.3,0.6,0.5"
-    }))
+/// Decimal gwei from wei, without trailing zeros
+fn gwei(wei: u128) -> String {
+    let whole = wei / rope_economics::fees::GWEI;
+    let fraction = wei % rope_economics::fees::GWEI;
+    if fraction == 0 {
+        return whole.to_string();
+    }
+    let digits = format!("{:09}", fraction);
+    format!("{}.{}", whole, digits.trim_end_matches('0'))
 }
 
 // ============================================================================
@@ -1003,97 +1508,135 @@ async fn gas_oracle() -> Json<serde_json::Value> {
 // ============================================================================
 
 /// List all federations
+#[utoipa::path(
+    get,
+    path = "/api/v1/federations",
+    tag = "Federations",
+    params(PaginationParams),
+    responses((status = 200, description = "List federations"))
+)]
 async fn list_federations(Query(params): Query<PaginationParams>) -> Json<serde_json::Value> {
     let page = params.page.unwrap_or(1);
     let limit = params.limit.unwrap_or(20);
-
-    let federations: Vec<serde_json::Value> = vec![
-        serde_json::json!({
-            "id": "fed-001",
-            "name": "European Smart Cities Federation",
-            "description": "Federation for European smart city infrastructure and IoT management",
-            "type": "structured",
-            "structure": "multicellular",
-            "scope": "regional",
-            "industry": "public_institution",
-            "status": "active",
-            "dataWalletsGenerated": 1500000,
-            "dataWalletsTotal": 10000000,
-            "communitiesCount": 12,
-            "protocols": ["datachain", "ethereum", "hyperledger"],
-            "kycEnabled": true,
-            "createdAt": chrono::Utc::now().timestamp() - 86400 * 180,
-            "votesFor": 2847,
-            "votesAgainst": 421
-        }),
-        serde_json::json!({
-            "id": "fed-002",
-            "name": "Global Banking Consortium",
-            "description": "International banking federation for cross-border transactions",
-            "type": "structured",
-            "structure": "multicellular",
-            "scope": "global",
-            "industry": "banking",
-            "status": "active",
-            "dataWalletsGenerated": 5200000,
-            "dataWalletsTotal": 10000000,
-            "communitiesCount": 28,
-            "protocols": ["datachain", "swift", "sepa"],
-            "kycEnabled": true,
-            "createdAt": chrono::Utc::now().timestamp() - 86400 * 365,
-            "votesFor": 8924,
-            "votesAgainst": 1247
-        }),
-        serde_json::json!({
-            "id": "fed-003",
-            "name": "Healthcare Data Exchange",
-            "description": "Secure medical records and healthcare data federation",
-            "type": "structured",
-            "structure": "monocellular",
-            "scope": "regional",
-            "industry": "healthcare",
-            "status": "voting",
-            "dataWalletsGenerated": 0,
-            "dataWalletsTotal": 10000000,
-            "communitiesCount": 0,
-            "protocols": ["datachain", "hyperledger"],
-            "kycEnabled": true,
-            "createdAt": chrono::Utc::now().timestamp() - 86400 * 14,
-            "votesFor": 1892,
-            "votesAgainst": 847
-        }),
-        serde_json::json!({
-            "id": "fed-004",
-            "name": "AI Research Network",
-            "description": "Autonomous federation for AI/ML research and data sharing",
-            "type": "autonomous",
-            "structure": "multicellular",
-            "scope": "global",
-            "industry": "technology",
-            "status": "active",
-            "dataWalletsGenerated": 3100000,
-            "dataWalletsTotal": 10000000,
-            "communitiesCount": 45,
-            "protocols": ["datachain", "ipfs", "tangle"],
-            "kycEnabled": false,
-            "createdAt": chrono::Utc::now().timestamp() - 86400 * 90,
-            "votesFor": 5247,
-            "votesAgainst": 892
-        }),
-    ];
+    let federations = federation_directory();
 
     Json(serde_json::json!({
         "federations": federations,
         "pagination": {
             "page": page,
             "limit": limit,
-            "total": 4
+            "total": federations.len()
         }
     }))
 }
 
+/// Federation list entry
+#[derive(Clone, Debug, Serialize, async_graphql::SimpleObject)]
+#[serde(rename_all = "camelCase")]
+#[graphql(name = "Federation")]
+pub struct FederationSummary {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    #[serde(rename = "type")]
+    #[graphql(name = "type")]
+    pub federation_type: String,
+    pub structure: String,
+    pub scope: String,
+    pub industry: String,
+    pub status: String,
+    pub data_wallets_generated: u64,
+    pub data_wallets_total: u64,
+    pub communities_count: u32,
+    pub protocols: Vec<String>,
+    pub kyc_enabled: bool,
+    pub created_at: i64,
+    pub votes_for: u64,
+    pub votes_against: u64,
+}
+
+/// Known federations
+pub fn federation_directory() -> Vec<FederationSummary> {
+    let now = chrono::Utc::now().timestamp();
+    vec![
+        FederationSummary {
+            id: "fed-001".into(),
+            name: "European Smart Cities Federation".into(),
+            description: "Federation for European smart city infrastructure and IoT management"
+                .into(),
+            federation_type: "structured".into(),
+            structure: "multicellular".into(),
+            scope: "regional".into(),
+            industry: "public_institution".into(),
+            status: "active".into(),
+            data_wallets_generated: 1_500_000,
+            data_wallets_total: 10_000_000,
+            communities_count: 12,
+            protocols: vec!["datachain".into(), "ethereum".into(), "hyperledger".into()],
+            kyc_enabled: true,
+            created_at: now - 86400 * 180,
+            votes_for: 2847,
+            votes_against: 421,
+        },
+        FederationSummary {
+            id: "fed-002".into(),
+            name: "Global Banking Consortium".into(),
+            description: "International banking federation for cross-border transactions".into(),
+            federation_type: "structured".into(),
+            structure: "multicellular".into(),
+            scope: "global".into(),
+            industry: "banking".into(),
+            status: "active".into(),
+            data_wallets_generated: 5_200_000,
+            data_wallets_total: 10_000_000,
+            communities_count: 28,
+            protocols: vec!["datachain".into(), "swift".into(), "sepa".into()],
+            kyc_enabled: true,
+            created_at: now - 86400 * 365,
+            votes_for: 8924,
+            votes_against: 1247,
+        },
+        FederationSummary {
+            id: "fed-003".into(),
+            name: "Healthcare Data Exchange".into(),
+            description: "Secure medical records and healthcare data federation".into(),
+            federation_type: "structured".into(),
+            structure: "monocellular".into(),
+            scope: "regional".into(),
+            industry: "healthcare".into(),
+            status: "voting".into(),
+            data_wallets_generated: 0,
+            data_wallets_total: 10_000_000,
+            communities_count: 0,
+            protocols: vec!["datachain".into(), "hyperledger".into()],
+            kyc_enabled: true,
+            created_at: now - 86400 * 14,
+            votes_for: 1892,
+            votes_against: 847,
+        },
+        FederationSummary {
+            id: "fed-004".into(),
+            name: "AI Research Network".into(),
+            description: "Autonomous federation for AI/ML research and data sharing".into(),
+            federation_type: "autonomous".into(),
+            structure: "multicellular".into(),
+            scope: "global".into(),
+            industry: "technology".into(),
+            status: "active".into(),
+            data_wallets_generated: 3_100_000,
+            data_wallets_total: 10_000_000,
+            communities_count: 45,
+            protocols: vec!["datachain".into(), "ipfs".into(), "tangle".into()],
+            kyc_enabled: false,
+            created_at: now - 86400 * 90,
+            votes_for: 5247,
+            votes_against: 892,
+        },
+    ]
+}
+
 /// Create new federation (requires DC FAT stake)
-#[derive(Deserialize)]
+#[derive(Deserialize, ToSchema)]
 struct CreateFederationRequest {
     name: String,
     description: String,
@@ -1106,6 +1649,13 @@ struct CreateFederationRequest {
     kyc_enabled: bool,
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/v1/federations",
+    tag = "Federations",
+    request_body = CreateFederationRequest,
+    responses((status = 201, description = "Create federation"))
+)]
 async fn create_federation(
     Json(payload): Json<CreateFederationRequest>,
 ) -> (StatusCode, Json<serde_json::Value>) {
@@ -1149,6 +1699,13 @@ async fn create_federation(
 }
 
 /// Get federation by ID
+#[utoipa::path(
+    get,
+    path = "/api/v1/federations/{id}",
+    tag = "Federations",
+    params(("id" = String, Path)),
+    responses((status = 200, description = "Get federation"))
+)]
 async fn get_federation(Path(id): Path<String>) -> Json<serde_json::Value> {
     Json(serde_json::json!({
         "id": id,
@@ -1200,6 +1757,13 @@ async fn get_federation(Path(id): Path<String>) -> Json<serde_json::Value> {
 }
 
 /// Get communities in a federation
+#[utoipa::path(
+    get,
+    path = "/api/v1/federations/{id}/communities",
+    tag = "Federations",
+    params(("id" = String, Path)),
+    responses((status = 200, description = "Federation communities"))
+)]
 async fn federation_communities(Path(id): Path<String>) -> Json<serde_json::Value> {
     let communities: Vec<serde_json::Value> = (0..5)
         .map(|i| {
@@ -1222,12 +1786,20 @@ async fn federation_communities(Path(id): Path<String>) -> Json<serde_json::Valu
 }
 
 /// Vote on federation
-#[derive(Deserialize)]
+#[derive(Deserialize, ToSchema)]
 struct VoteRequest {
     vote_for: bool,
     comment: Option<String>,
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/v1/federations/{id}/vote",
+    tag = "Federations",
+    request_body = VoteRequest,
+    params(("id" = String, Path)),
+    responses((status = 200, description = "Vote federation"))
+)]
 async fn vote_federation(
     Path(id): Path<String>,
     Json(payload): Json<VoteRequest>,
@@ -1246,6 +1818,13 @@ async fn vote_federation(
 }
 
 /// List all communities
+#[utoipa::path(
+    get,
+    path = "/api/v1/communities",
+    tag = "Communities",
+    params(PaginationParams),
+    responses((status = 200, description = "List communities"))
+)]
 async fn list_communities(Query(params): Query<PaginationParams>) -> Json<serde_json::Value> {
     let page = params.page.unwrap_or(1);
     let limit = params.limit.unwrap_or(20);
@@ -1302,7 +1881,7 @@ async fn list_communities(Query(params): Query<PaginationParams>) -> Json<serde_
 }
 
 /// Create new community
-#[derive(Deserialize)]
+#[derive(Deserialize, ToSchema)]
 struct CreateCommunityRequest {
     name: String,
     description: String,
@@ -1312,6 +1891,13 @@ struct CreateCommunityRequest {
     protocols: Vec<String>,
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/v1/communities",
+    tag = "Communities",
+    request_body = CreateCommunityRequest,
+    responses((status = 201, description = "Create community"))
+)]
 async fn create_community(
     Json(payload): Json<CreateCommunityRequest>,
 ) -> (StatusCode, Json<serde_json::Value>) {
@@ -1347,6 +1933,13 @@ async fn create_community(
 }
 
 /// Get community by ID
+#[utoipa::path(
+    get,
+    path = "/api/v1/communities/{id}",
+    tag = "Communities",
+    params(("id" = String, Path)),
+    responses((status = 200, description = "Get community"))
+)]
 async fn get_community(Path(id): Path<String>) -> Json<serde_json::Value> {
     Json(serde_json::json!({
         "id": id,
@@ -1383,6 +1976,13 @@ async fn get_community(Path(id): Path<String>) -> Json<serde_json::Value> {
 }
 
 /// Get community wallets
+#[utoipa::path(
+    get,
+    path = "/api/v1/communities/{id}/wallets",
+    tag = "Communities",
+    params(("id" = String, Path)),
+    responses((status = 200, description = "Community wallets"))
+)]
 async fn community_wallets(Path(id): Path<String>) -> Json<serde_json::Value> {
     let wallets: Vec<serde_json::Value> = (0..10)
         .map(|i| {
@@ -1410,11 +2010,19 @@ async fn community_wallets(Path(id): Path<String>) -> Json<serde_json::Value> {
 }
 
 /// Generate wallets for community
-#[derive(Deserialize)]
+#[derive(Deserialize, ToSchema)]
 struct GenerateWalletsRequest {
     count: u64,
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/v1/communities/{id}/wallets/generate",
+    tag = "Communities",
+    request_body = GenerateWalletsRequest,
+    params(("id" = String, Path)),
+    responses((status = 200, description = "Generate wallets"))
+)]
 async fn generate_wallets(
     Path(id): Path<String>,
     Json(payload): Json<GenerateWalletsRequest>,
@@ -1440,6 +2048,14 @@ async fn generate_wallets(
 }
 
 /// Vote on community
+#[utoipa::path(
+    post,
+    path = "/api/v1/communities/{id}/vote",
+    tag = "Communities",
+    request_body = VoteRequest,
+    params(("id" = String, Path)),
+    responses((status = 200, description = "Vote community"))
+)]
 async fn vote_community(
     Path(id): Path<String>,
     Json(payload): Json<VoteRequest>,
@@ -1462,11 +2078,30 @@ async fn vote_community(
 // ============================================================================
 
 /// List all project submissions
+#[utoipa::path(
+    get,
+    path = "/api/v1/projects",
+    tag = "Projects",
+    params(PaginationParams),
+    responses((status = 200, description = "List projects"))
+)]
 async fn list_projects(Query(params): Query<PaginationParams>) -> Json<serde_json::Value> {
     let page = params.page.unwrap_or(1);
     let limit = params.limit.unwrap_or(20);
 
-    let projects: Vec<serde_json::Value> = vec![
+    Json(serde_json::json!({
+        "projects": project_directory(),
+        "pagination": {
+            "page": page,
+            "limit": limit,
+            "total": 4
+        }
+    }))
+}
+
+/// Submitted projects
+pub fn project_directory() -> Vec<serde_json::Value> {
+    vec![
         serde_json::json!({
             "id": "proj-001",
             "name": "DCSwap",
@@ -1524,20 +2159,11 @@ async fn list_projects(Query(params): Query<PaginationParams>) -> Json<serde_jso
             "fundingCurrency": "FAT",
             "createdAt": chrono::Utc::now().timestamp() - 86400 * 120
         }),
-    ];
-
-    Json(serde_json::json!({
-        "projects": projects,
-        "pagination": {
-            "page": page,
-            "limit": limit,
-            "total": 4
-        }
-    }))
+    ]
 }
 
 /// Submit new project (Start Building)
-#[derive(Deserialize)]
+#[derive(Deserialize, ToSchema)]
 struct SubmitProjectRequest {
     name: String,
     tagline: Option<String>,
@@ -1550,6 +2176,7 @@ struct SubmitProjectRequest {
     submitter_email: Option<String>,
     tech_stack: Vec<String>,
     architecture_description: Option<String>,
+    #[schema(value_type = Vec<Object>)]
     features: Vec<serde_json::Value>,
     use_cases: Option<String>,
     target_users: Option<String>,
@@ -1559,13 +2186,22 @@ struct SubmitProjectRequest {
     github_url: Option<String>,
     website_url: Option<String>,
     demo_url: Option<String>,
+    #[schema(value_type = Vec<Object>)]
     team_members: Vec<serde_json::Value>,
+    #[schema(value_type = Vec<Object>)]
     milestones: Vec<serde_json::Value>,
     funding_requested: u64,
     funding_currency: String,
     funding_breakdown: Option<String>,
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/v1/projects",
+    tag = "Projects",
+    request_body = SubmitProjectRequest,
+    responses((status = 201, description = "Submit project"))
+)]
 async fn submit_project(
     Json(payload): Json<SubmitProjectRequest>,
 ) -> (StatusCode, Json<serde_json::Value>) {
@@ -1615,6 +2251,13 @@ async fn submit_project(
 }
 
 /// Get project by ID
+#[utoipa::path(
+    get,
+    path = "/api/v1/projects/{id}",
+    tag = "Projects",
+    params(("id" = String, Path)),
+    responses((status = 200, description = "Get project"))
+)]
 async fn get_project(Path(id): Path<String>) -> Json<serde_json::Value> {
     Json(serde_json::json!({
         "id": id,
@@ -1670,6 +2313,14 @@ async fn get_project(Path(id): Path<String>) -> Json<serde_json::Value> {
 }
 
 /// Vote on project
+#[utoipa::path(
+    post,
+    path = "/api/v1/projects/{id}/vote",
+    tag = "Projects",
+    request_body = VoteRequest,
+    params(("id" = String, Path)),
+    responses((status = 200, description = "Vote project"))
+)]
 async fn vote_project(
     Path(id): Path<String>,
     Json(payload): Json<VoteRequest>,
@@ -1688,6 +2339,12 @@ async fn vote_project(
 }
 
 /// Get project categories
+#[utoipa::path(
+    get,
+    path = "/api/v1/projects/categories",
+    tag = "Projects",
+    responses((status = 200, description = "Project categories"))
+)]
 async fn project_categories() -> Json<serde_json::Value> {
     Json(serde_json::json!({
         "categories": [
@@ -1711,6 +2368,12 @@ async fn project_categories() -> Json<serde_json::Value> {
 }
 
 /// Get projects currently in voting
+#[utoipa::path(
+    get,
+    path = "/api/v1/projects/voting",
+    tag = "Projects",
+    responses((status = 200, description = "Voting projects"))
+)]
 async fn voting_projects() -> Json<serde_json::Value> {
     let projects: Vec<serde_json::Value> = vec![serde_json::json!({
         "id": "proj-002",
@@ -1734,6 +2397,13 @@ async fn voting_projects() -> Json<serde_json::Value> {
 }
 
 /// List all votes
+#[utoipa::path(
+    get,
+    path = "/api/v1/votes",
+    tag = "Votes",
+    params(PaginationParams),
+    responses((status = 200, description = "List votes"))
+)]
 async fn list_votes(Query(params): Query<PaginationParams>) -> Json<serde_json::Value> {
     let page = params.page.unwrap_or(1);
     let limit = params.limit.unwrap_or(20);
@@ -1763,6 +2433,13 @@ async fn list_votes(Query(params): Query<PaginationParams>) -> Json<serde_json::
 }
 
 /// Get votes for specific target
+#[utoipa::path(
+    get,
+    path = "/api/v1/votes/{target_type}/{target_id}",
+    tag = "Votes",
+    params(("target_type" = String, Path), ("target_id" = String, Path)),
+    responses((status = 200, description = "Get votes for target"))
+)]
 async fn get_votes_for_target(
     Path((target_type, target_id)): Path<(String, String)>,
 ) -> Json<serde_json::Value> {
@@ -1835,6 +2512,14 @@ mod tests {
         assert!(FALLBACK_PRICE > 0.0);
     }
 
+    #[test]
+    fn test_gwei() {
+        assert_eq!(gwei(1_000_000_000), "1");
+        assert_eq!(gwei(1_000_000), "0.001");
+        assert_eq!(gwei(2_500_000_000), "2.5");
+        assert_eq!(gwei(0), "0");
+    }
+
     #[test]
     fn test_rand_variation() {
         // rand_variation returns value between 0.0 and 1.0 (based on nanoseconds)
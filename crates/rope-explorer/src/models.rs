@@ -1,3 +1,310 @@
 //! Data models
+//!
+//! Rows persisted by the indexer and served by the API. Amounts are
+//! decimal strings in base units so they survive JSON and `NUMERIC`
+//! without precision loss.
 
-// Placeholder for data models
+use async_graphql::SimpleObject;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Finality status of an indexed string
+pub const STATUS_PENDING: &str = "Pending";
+pub const STATUS_FINAL: &str = "Final";
+pub const STATUS_REJECTED: &str = "Rejected";
+
+/// Indexed string
+#[derive(
+    Clone, Debug, PartialEq, Serialize, Deserialize, sqlx::FromRow, ToSchema, SimpleObject,
+)]
+#[serde(rename_all = "camelCase")]
+#[graphql(complex)]
+pub struct StringRow {
+    /// Insertion sequence, exposed as the string number
+    pub number: i64,
+    pub hash: String,
+    pub creator: String,
+    pub string_type: String,
+    pub transactions: i32,
+    pub status: String,
+    pub anchor_id: Option<String>,
+    pub rejection_reason: Option<String>,
+    pub timestamp: i64,
+}
+
+/// Indexed transaction
+#[derive(
+    Clone, Debug, PartialEq, Serialize, Deserialize, sqlx::FromRow, ToSchema, SimpleObject,
+)]
+#[serde(rename_all = "camelCase")]
+#[graphql(complex)]
+pub struct TransactionRow {
+    /// Insertion sequence (cursor key)
+    #[serde(skip)]
+    #[graphql(skip)]
+    pub seq: i64,
+    pub hash: String,
+    pub string_hash: String,
+    pub from_address: String,
+    pub to_address: Option<String>,
+    pub value: String,
+    pub fee: String,
+    pub status: String,
+    pub timestamp: i64,
+    /// Call data, hex
+    pub input: Option<String>,
+}
+
+/// Account derived from indexed transactions
+#[derive(
+    Clone, Debug, PartialEq, Serialize, Deserialize, sqlx::FromRow, ToSchema, SimpleObject,
+)]
+#[serde(rename_all = "camelCase")]
+#[graphql(complex)]
+pub struct AccountRow {
+    pub address: String,
+    pub balance: String,
+    pub transaction_count: i64,
+    pub is_validator: bool,
+    pub first_seen: i64,
+    pub last_seen: i64,
+}
+
+/// Indexed validator
+#[derive(
+    Clone, Debug, PartialEq, Serialize, Deserialize, sqlx::FromRow, ToSchema, SimpleObject,
+)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidatorRow {
+    pub address: String,
+    pub name: String,
+    pub stake: i64,
+    pub strings_produced: i64,
+    /// Rounds closed by a view change while this validator was producer
+    pub missed_rounds: i64,
+    /// Heartbeat uptime over the last 24 hours, absent before the first heartbeat
+    pub uptime_percent: Option<f64>,
+    pub active: bool,
+    pub last_seen: i64,
+}
+
+/// Validator testimony on a string
+#[derive(
+    Clone, Debug, PartialEq, Serialize, Deserialize, sqlx::FromRow, ToSchema, SimpleObject,
+)]
+#[serde(rename_all = "camelCase")]
+pub struct TestimonyRow {
+    pub string_hash: String,
+    pub agent_id: String,
+    pub verdict: String,
+    pub confidence: f64,
+    pub timestamp: i64,
+}
+
+/// Epoch reward paid to a validator
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, sqlx::FromRow, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RewardRow {
+    pub epoch: i64,
+    pub proposer_rewards: String,
+    pub testimony_rewards: String,
+    pub performance_multiplier: f64,
+    pub final_reward: String,
+    pub timestamp: i64,
+}
+
+/// Validator activity over a window
+#[derive(Clone, Debug, Default, PartialEq, sqlx::FromRow)]
+pub struct PerformanceRow {
+    pub strings_produced: i64,
+    pub missed_rounds: i64,
+    pub uptime_percent: Option<f64>,
+    pub latency_samples: i64,
+    /// p50, p90 and p99 testimony latency (ms); absent without samples
+    pub latency_percentiles: Option<Vec<f64>>,
+}
+
+/// Stored entity matched by a search
+#[derive(Clone, Debug, PartialEq, sqlx::FromRow)]
+pub struct SearchRow {
+    /// `transaction`, `string`, `account`, `validator`, `token` or `contract`
+    pub kind: String,
+    pub id: String,
+    pub label: String,
+}
+
+/// Decoded token transfer
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, sqlx::FromRow, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenTransferRow {
+    /// Insertion sequence (cursor key)
+    #[serde(skip)]
+    pub seq: i64,
+    pub transaction_hash: String,
+    pub log_index: i32,
+    pub token: String,
+    pub from_address: String,
+    pub to_address: String,
+    pub value: String,
+    pub timestamp: i64,
+}
+
+/// Token balance of one holder
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, sqlx::FromRow, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenHolderRow {
+    pub holder: String,
+    pub balance: String,
+    /// Share of all positive balances, percent
+    pub percentage: String,
+}
+
+/// Token held by an account
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, sqlx::FromRow, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenBalanceRow {
+    pub token: String,
+    pub balance: String,
+}
+
+/// Token activity summary
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, sqlx::FromRow, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenStatsRow {
+    pub holders: i64,
+    pub transfers: i64,
+    /// Sum of positive balances
+    pub circulating: String,
+}
+
+/// Bridge call decoded from a transaction
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, sqlx::FromRow, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct InternalTransactionRow {
+    pub transaction_hash: String,
+    /// `bridgeIn` or `bridgeOut`
+    pub kind: String,
+    /// Bridge contract called
+    pub contract: String,
+    /// String id or proof hash
+    pub reference: String,
+    pub amount: String,
+    pub timestamp: i64,
+}
+
+/// Aggregates over one hour or day
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, sqlx::FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct RollupRow {
+    /// Bucket start (unix seconds)
+    pub bucket: i64,
+    pub transactions: i64,
+    /// Distinct senders and recipients
+    pub active_accounts: i64,
+    pub gas_used: i64,
+    pub price_sum: f64,
+    pub price_samples: i64,
+}
+
+/// Keyset pagination cursor
+///
+/// Encoded as `key` or `key:tie` where `key` is the ordering column and
+/// `tie` breaks equal keys (e.g. validator address for equal stakes).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Cursor {
+    pub key: i64,
+    pub tie: Option<String>,
+}
+
+impl Cursor {
+    /// Cursor on a unique key
+    pub fn key(key: i64) -> Self {
+        Self { key, tie: None }
+    }
+
+    /// Cursor on a non-unique key with a tiebreaker
+    pub fn with_tie(key: i64, tie: impl Into<String>) -> Self {
+        Self {
+            key,
+            tie: Some(tie.into()),
+        }
+    }
+
+    /// Opaque string form
+    pub fn encode(&self) -> String {
+        match &self.tie {
+            Some(tie) => format!("{}:{}", self.key, tie),
+            None => self.key.to_string(),
+        }
+    }
+
+    /// Parse the string form
+    pub fn decode(s: &str) -> Option<Self> {
+        let (key, tie) = match s.split_once(':') {
+            Some((key, tie)) if !tie.is_empty() => (key, Some(tie.to_string())),
+            Some(_) => return None,
+            None => (s, None),
+        };
+        Some(Self {
+            key: key.parse().ok()?,
+            tie,
+        })
+    }
+}
+
+/// One page of results
+#[derive(Clone, Debug, Serialize, SimpleObject)]
+#[serde(rename_all = "camelCase")]
+#[graphql(concrete(name = "StringPage", params(StringRow)))]
+#[graphql(concrete(name = "TransactionPage", params(TransactionRow)))]
+#[graphql(concrete(name = "ValidatorPage", params(ValidatorRow)))]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    /// Cursor for the next page, absent on the last page
+    pub next_cursor: Option<String>,
+}
+
+impl<T> Page<T> {
+    /// Build a page from `limit + 1` fetched rows
+    pub fn from_rows(mut rows: Vec<T>, limit: usize, cursor: impl Fn(&T) -> Cursor) -> Self {
+        let next_cursor = if rows.len() > limit {
+            rows.truncate(limit);
+            rows.last().map(|row| cursor(row).encode())
+        } else {
+            None
+        };
+        Self {
+            items: rows,
+            next_cursor,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cursor_roundtrip() {
+        let cursor = Cursor::key(42);
+        assert_eq!(Cursor::decode(&cursor.encode()), Some(cursor));
+
+        let cursor = Cursor::with_tie(1_000_000, "0xabc");
+        assert_eq!(cursor.encode(), "1000000:0xabc");
+        assert_eq!(Cursor::decode("1000000:0xabc"), Some(cursor));
+
+        assert_eq!(Cursor::decode("abc"), None);
+        assert_eq!(Cursor::decode("5:"), None);
+    }
+
+    #[test]
+    fn test_page_from_rows() {
+        let page = Page::from_rows(vec![9, 8, 7, 6], 3, |n| Cursor::key(*n));
+        assert_eq!(page.items, vec![9, 8, 7]);
+        assert_eq!(page.next_cursor.as_deref(), Some("7"));
+
+        let page = Page::from_rows(vec![2, 1], 3, |n| Cursor::key(*n));
+        assert_eq!(page.items, vec![2, 1]);
+        assert_eq!(page.next_cursor, None);
+    }
+}
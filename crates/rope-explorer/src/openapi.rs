@@ -0,0 +1,168 @@
+//! OpenAPI 3.1 specification
+//!
+//! Generated from the `#[utoipa::path]` annotations on the handlers and
+//! the `ToSchema` types they return, served as JSON at [`OPENAPI_PATH`]
+//! with a Swagger UI at [`DOCS_PATH`].
+
+use utoipa::openapi::security::{ApiKey, ApiKeyValue, HttpAuthScheme, HttpBuilder, SecurityScheme};
+use utoipa::{Modify, OpenApi};
+use utoipa_swagger_ui::SwaggerUi;
+
+/// Spec location
+pub const OPENAPI_PATH: &str = "/api/v1/openapi.json";
+
+/// Swagger UI location
+pub const DOCS_PATH: &str = "/api/v1/docs";
+
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "Datachain Rope Explorer API",
+        description = "REST API for the Datachain Rope block explorer (dcscan.io). \
+                       Requests without an `x-api-key` are limited per IP; see `/api/v1/plans`.",
+        license(name = "Apache-2.0")
+    ),
+    servers(
+        (url = "https://api.dcscan.io", description = "Production"),
+        (url = "https://testnet-api.dcscan.io", description = "Testnet"),
+        (url = "http://localhost:3001", description = "Local development")
+    ),
+    paths(
+        crate::root,
+        crate::health,
+        crate::status,
+        crate::stats,
+        crate::economics::economics,
+        crate::chart_data,
+        crate::list_strings,
+        crate::latest_strings,
+        crate::rejected_strings,
+        crate::get_string,
+        crate::list_transactions,
+        crate::latest_transactions,
+        crate::get_transaction,
+        crate::get_account,
+        crate::account_transactions,
+        crate::account_tokens,
+        crate::list_tokens,
+        crate::get_token,
+        crate::token_holders,
+        crate::token_transfers,
+        crate::list_validators,
+        crate::get_validator,
+        crate::validator_performance,
+        crate::validator_rewards,
+        crate::list_ai_agents,
+        crate::get_ai_agent,
+        crate::agent_testimonies,
+        crate::list_databoxes,
+        crate::get_databox,
+        crate::databox_map,
+        crate::search::search,
+        crate::search::autocomplete,
+        crate::gas_price,
+        crate::gas_oracle,
+        crate::dcfat_price,
+        crate::list_federations,
+        crate::create_federation,
+        crate::get_federation,
+        crate::federation_communities,
+        crate::vote_federation,
+        crate::list_communities,
+        crate::create_community,
+        crate::get_community,
+        crate::community_wallets,
+        crate::generate_wallets,
+        crate::vote_community,
+        crate::list_projects,
+        crate::submit_project,
+        crate::get_project,
+        crate::vote_project,
+        crate::project_categories,
+        crate::voting_projects,
+        crate::list_votes,
+        crate::get_votes_for_target,
+        crate::ws::ws_handler,
+        crate::graphql::graphql_handler,
+        crate::verify::verify_contract,
+        crate::verify::verify_string,
+        crate::verify::get_verification,
+        crate::keys::list_plans,
+        crate::keys::issue_key,
+        crate::keys::list_keys,
+        crate::keys::key_usage,
+        crate::keys::revoke_key,
+    ),
+    modifiers(&SecuritySchemes),
+    security((), ("apiKey" = []))
+)]
+pub struct ApiDoc;
+
+/// Registers the `apiKey` and `adminToken` schemes
+struct SecuritySchemes;
+
+impl Modify for SecuritySchemes {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+        components.add_security_scheme(
+            "apiKey",
+            SecurityScheme::ApiKey(ApiKey::Header(ApiKeyValue::new(
+                crate::keys::API_KEY_HEADER,
+            ))),
+        );
+        components.add_security_scheme(
+            "adminToken",
+            SecurityScheme::Http(HttpBuilder::new().scheme(HttpAuthScheme::Bearer).build()),
+        );
+    }
+}
+
+/// Routes serving the spec and the UI
+pub fn swagger_ui() -> SwaggerUi {
+    SwaggerUi::new(DOCS_PATH).url(OPENAPI_PATH, ApiDoc::openapi())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spec_covers_typed_endpoints() {
+        let spec = serde_json::to_value(ApiDoc::openapi()).unwrap();
+        assert_eq!(spec["openapi"], "3.1.0");
+
+        let paths = spec["paths"].as_object().unwrap();
+        assert!(paths.contains_key("/api/v1/strings/{id}"));
+        assert!(paths.contains_key("/api/v1/keys/{prefix}"));
+        assert!(paths.contains_key("/api/v1/ws"));
+
+        let list = &paths["/api/v1/strings"]["get"];
+        let parameters: Vec<&str> = list["parameters"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|p| p["name"].as_str().unwrap())
+            .collect();
+        assert_eq!(parameters, vec!["cursor", "limit"]);
+
+        let schemas = spec["components"]["schemas"].as_object().unwrap();
+        for schema in [
+            "StringList",
+            "StringRow",
+            "TransactionRow",
+            "ValidatorList",
+            "IssuedKey",
+        ] {
+            assert!(schemas.contains_key(schema), "missing schema {}", schema);
+        }
+        // Field names match the serialized JSON
+        assert!(schemas["StringRow"]["properties"]
+            .as_object()
+            .unwrap()
+            .contains_key("stringType"));
+
+        let security = spec["components"]["securitySchemes"].as_object().unwrap();
+        assert!(security.contains_key("apiKey"));
+        assert!(security.contains_key("adminToken"));
+    }
+}
@@ -0,0 +1,182 @@
+//! Validator performance
+//!
+//! Built from the consensus events the indexer records:
+//!
+//! | Metric            | Source                                              |
+//! |-------------------|-----------------------------------------------------|
+//! | Strings produced  | `StringCreated`, by creator                         |
+//! | Missed rounds     | `RoundMissed` (view change to an empty anchor)      |
+//! | Testimony latency | `TestimonyReceived` arrival minus string timestamp  |
+//! | Uptime            | `ValidatorHeartbeat`, in one-minute slots           |
+//! | Rewards           | `RewardDistributed` (rope-economics epoch rewards)  |
+//!
+//! Uptime is the share of slots in the window with at least one
+//! heartbeat, counted from the validator's first heartbeat if it joined
+//! during the window. Latencies have second resolution, as do the
+//! timestamps they are derived from. Scores use the rope-economics
+//! performance curve so they match what reward multipliers are based on.
+
+use crate::charts::{bucket_start, Period, DAY};
+use crate::models::PerformanceRow;
+use rope_economics::{PerformanceMetrics, PerformanceScore};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+/// Heartbeat slot (seconds)
+pub const HEARTBEAT_SLOT: i64 = 60;
+
+/// Heartbeat slots kept, covering the longest period
+pub const HEARTBEAT_RETENTION: i64 = 366 * DAY;
+
+/// Slot containing `timestamp`
+pub fn heartbeat_slot(timestamp: i64) -> i64 {
+    bucket_start(timestamp, HEARTBEAT_SLOT)
+}
+
+/// Testimony latency percentiles (ms)
+#[derive(Clone, Debug, Default, PartialEq, Serialize, ToSchema)]
+pub struct LatencyPercentiles {
+    pub samples: i64,
+    pub p50: Option<f64>,
+    pub p90: Option<f64>,
+    pub p99: Option<f64>,
+}
+
+impl LatencyPercentiles {
+    fn from_row(row: &PerformanceRow) -> Self {
+        let percentile = |i: usize| {
+            row.latency_percentiles
+                .as_ref()
+                .and_then(|p| p.get(i).copied())
+        };
+        Self {
+            samples: row.latency_samples,
+            p50: percentile(0),
+            p90: percentile(1),
+            p99: percentile(2),
+        }
+    }
+}
+
+/// Normalized scores (0-1) from the rope-economics performance curve
+#[derive(Clone, Debug, PartialEq, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PerformanceScores {
+    pub uptime: f64,
+    /// Scored on the median latency
+    pub testimony_speed: f64,
+}
+
+/// Validator performance over a period
+#[derive(Clone, Debug, PartialEq, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidatorPerformance {
+    pub address: String,
+    pub period: String,
+    pub from: i64,
+    pub to: i64,
+    pub strings_produced: i64,
+    pub missed_rounds: i64,
+    /// Share of scheduled rounds produced, percent
+    pub production_rate: Option<f64>,
+    pub uptime_percent: Option<f64>,
+    pub testimony_latency_ms: LatencyPercentiles,
+    /// Absent until there is both uptime and latency data
+    pub scores: Option<PerformanceScores>,
+}
+
+impl ValidatorPerformance {
+    /// Assemble the response for `address` over `period` ending at `now`
+    pub fn new(address: String, period: Period, now: i64, row: &PerformanceRow) -> Self {
+        let latency = LatencyPercentiles::from_row(row);
+        let scores = row
+            .uptime_percent
+            .zip(latency.p50)
+            .map(|(uptime, p50)| scores(uptime, p50));
+        Self {
+            address,
+            period: period.as_str().to_string(),
+            from: window_start(period, now),
+            to: now,
+            strings_produced: row.strings_produced,
+            missed_rounds: row.missed_rounds,
+            production_rate: production_rate(row.strings_produced, row.missed_rounds),
+            uptime_percent: row.uptime_percent,
+            testimony_latency_ms: latency,
+            scores,
+        }
+    }
+}
+
+/// Start of the window covered by `period`
+pub fn window_start(period: Period, now: i64) -> i64 {
+    now - period.buckets() * period.resolution()
+}
+
+/// Produced share of the rounds a validator was scheduled for
+fn production_rate(produced: i64, missed: i64) -> Option<f64> {
+    let scheduled = produced + missed;
+    (scheduled > 0).then(|| 100.0 * produced as f64 / scheduled as f64)
+}
+
+fn scores(uptime_percent: f64, median_latency_ms: f64) -> PerformanceScores {
+    let score = PerformanceScore::from_metrics(&PerformanceMetrics {
+        uptime_percent,
+        testimony_latency_ms: median_latency_ms.max(0.0).round() as u64,
+        ..Default::default()
+    });
+    PerformanceScores {
+        uptime: score.uptime,
+        testimony_speed: score.testimony_speed,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_heartbeat_slot() {
+        assert_eq!(heartbeat_slot(59), 0);
+        assert_eq!(heartbeat_slot(60), 60);
+        assert_eq!(heartbeat_slot(3_601), 3_600);
+    }
+
+    #[test]
+    fn test_production_rate() {
+        assert_eq!(production_rate(0, 0), None);
+        assert_eq!(production_rate(3, 1), Some(75.0));
+        assert_eq!(production_rate(0, 2), Some(0.0));
+    }
+
+    #[test]
+    fn test_performance_from_row() {
+        let row = PerformanceRow {
+            strings_produced: 99,
+            missed_rounds: 1,
+            uptime_percent: Some(99.95),
+            latency_samples: 40,
+            latency_percentiles: Some(vec![0.0, 1_000.0, 2_000.0]),
+        };
+        let now = 10 * DAY;
+        let performance = ValidatorPerformance::new("0xv1".to_string(), Period::Day, now, &row);
+
+        assert_eq!(performance.from, 9 * DAY);
+        assert_eq!(performance.production_rate, Some(99.0));
+        assert_eq!(performance.testimony_latency_ms.p90, Some(1_000.0));
+        assert_eq!(
+            performance.scores,
+            Some(PerformanceScores {
+                uptime: 1.0,
+                testimony_speed: 1.0,
+            })
+        );
+
+        // No heartbeat or testimony yet
+        let performance =
+            ValidatorPerformance::new("0xv2".to_string(), Period::Week, now, &Default::default());
+        assert_eq!(performance.testimony_latency_ms.p50, None);
+        assert_eq!(performance.production_rate, None);
+        assert_eq!(performance.scores, None);
+    }
+}
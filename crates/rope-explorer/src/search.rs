@@ -0,0 +1,495 @@
+//! Search
+//!
+//! One query is matched against every kind of entity the explorer
+//! serves and the hits are ranked into a typed list:
+//!
+//! | Input                       | Matched against                                   |
+//! |-----------------------------|---------------------------------------------------|
+//! | `0x` + at least 4 hex chars | transaction and string hashes, accounts,          |
+//! |                             | validators, tokens, verified contracts (prefix)   |
+//! | Decimal number              | string number                                     |
+//! | Text                        | validator, token, contract, federation and        |
+//! |                             | project names (partial and fuzzy)                 |
+//!
+//! Identifier hits score by how much of the identifier the query
+//! covers, so a full hash ranks first. Names score exact > prefix >
+//! word prefix > substring > within a small edit distance. Candidates
+//! stored in the database are narrowed by the first characters of each
+//! query word and ranked here.
+
+use crate::api::{ApiError, ApiResult};
+use crate::db::{Database, DbError};
+use crate::indexer::normalize_hash;
+use crate::models::SearchRow;
+use crate::{federation_directory, project_directory, token_directory, AppState};
+use axum::extract::{Query, State};
+use axum::response::Json;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use utoipa::{IntoParams, ToSchema};
+
+/// Hex digits needed before an identifier prefix is searched
+pub const MIN_HEX_PREFIX: usize = 4;
+
+/// Results returned by default and at most
+pub const DEFAULT_RESULTS: usize = 20;
+pub const MAX_RESULTS: usize = 50;
+
+/// Suggestions returned by autocomplete
+pub const AUTOCOMPLETE_RESULTS: usize = 8;
+
+/// Characters of each query word used to fetch stored name candidates
+const NAME_PATTERN_CHARS: usize = 3;
+
+/// Kind of entity a result points at
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ResultKind {
+    Transaction,
+    String,
+    Account,
+    Validator,
+    Token,
+    Contract,
+    Federation,
+    Project,
+}
+
+impl ResultKind {
+    fn parse(s: &str) -> Option<Self> {
+        match s {
+            "transaction" => Some(ResultKind::Transaction),
+            "string" => Some(ResultKind::String),
+            "account" => Some(ResultKind::Account),
+            "validator" => Some(ResultKind::Validator),
+            "token" => Some(ResultKind::Token),
+            "contract" => Some(ResultKind::Contract),
+            "federation" => Some(ResultKind::Federation),
+            "project" => Some(ResultKind::Project),
+            _ => None,
+        }
+    }
+
+    /// API resource serving the entity
+    pub fn url(&self, id: &str) -> String {
+        let collection = match self {
+            ResultKind::Transaction => "transactions",
+            ResultKind::String => "strings",
+            ResultKind::Account | ResultKind::Contract => "accounts",
+            ResultKind::Validator => "validators",
+            ResultKind::Token => "tokens",
+            ResultKind::Federation => "federations",
+            ResultKind::Project => "projects",
+        };
+        format!("/api/v1/{}/{}", collection, id)
+    }
+}
+
+/// One search hit
+#[derive(Clone, Debug, PartialEq, Serialize, ToSchema)]
+pub struct SearchResult {
+    #[serde(rename = "type")]
+    pub kind: ResultKind,
+    pub id: String,
+    pub label: String,
+    /// Secondary text, e.g. a token symbol or project tagline
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+    pub url: String,
+    /// Relevance, 0-1
+    pub score: f64,
+}
+
+impl SearchResult {
+    fn new(kind: ResultKind, id: String, label: String, score: f64) -> Self {
+        Self {
+            kind,
+            url: kind.url(&id),
+            id,
+            label,
+            detail: None,
+            score,
+        }
+    }
+
+    fn with_detail(mut self, detail: Option<String>) -> Self {
+        self.detail = detail;
+        self
+    }
+}
+
+/// Search response
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SearchResults {
+    pub query: String,
+    /// Most relevant first
+    pub results: Vec<SearchResult>,
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct SearchQuery {
+    /// Hash, address, string number or name
+    q: String,
+    /// Results to return (1-50)
+    limit: Option<u32>,
+}
+
+/// `GET /api/v1/search`
+#[utoipa::path(
+    get,
+    path = "/api/v1/search",
+    tag = "Search",
+    params(SearchQuery),
+    responses(
+        (status = 200, description = "Ranked results", body = SearchResults),
+        (status = 400, description = "Empty query", body = crate::api::ErrorBody)
+    )
+)]
+pub async fn search(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<SearchQuery>,
+) -> ApiResult<SearchResults> {
+    if query.q.trim().is_empty() {
+        return Err(ApiError::BadRequest("empty query".to_string()));
+    }
+    let limit = query
+        .limit
+        .map_or(DEFAULT_RESULTS, |l| l as usize)
+        .clamp(1, MAX_RESULTS);
+    let results = find(&state.db, &query.q, limit).await?;
+    Ok(Json(SearchResults {
+        query: query.q,
+        results,
+    }))
+}
+
+/// `GET /api/v1/search/autocomplete`
+#[utoipa::path(
+    get,
+    path = "/api/v1/search/autocomplete",
+    tag = "Search",
+    params(SearchQuery),
+    responses((status = 200, description = "Suggestions", body = SearchResults))
+)]
+pub async fn autocomplete(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<SearchQuery>,
+) -> ApiResult<SearchResults> {
+    // One character matches too much to be useful
+    let results = if query.q.trim().chars().count() < 2 {
+        Vec::new()
+    } else {
+        let limit = query
+            .limit
+            .map_or(AUTOCOMPLETE_RESULTS, |l| l as usize)
+            .clamp(1, AUTOCOMPLETE_RESULTS);
+        find(&state.db, &query.q, limit).await?
+    };
+    Ok(Json(SearchResults {
+        query: query.q,
+        results,
+    }))
+}
+
+/// Ranked results for `query`
+pub async fn find(db: &Database, query: &str, limit: usize) -> Result<Vec<SearchResult>, DbError> {
+    let query = query.trim();
+    let mut results = Vec::new();
+    let prefix = hex_prefix(query);
+
+    if let Some(prefix) = &prefix {
+        for row in db.search_identifiers(prefix, limit as i64).await? {
+            let score = identifier_score(prefix, &row.id);
+            results.extend(from_row(row, score));
+        }
+        for token in token_directory() {
+            let address = token["address"].as_str().unwrap_or_default();
+            if address.starts_with(prefix.as_str()) {
+                results.push(token_result(&token, identifier_score(prefix, address)));
+            }
+        }
+    }
+
+    if query.parse::<u64>().is_ok() {
+        if let Some(string) = db.get_string(query).await? {
+            results.push(SearchResult::new(
+                ResultKind::String,
+                string.hash,
+                format!("String #{}", string.number),
+                1.0,
+            ));
+        }
+    }
+
+    if prefix.is_none() && query.chars().any(char::is_alphabetic) {
+        let patterns = name_patterns(query);
+        for row in db.search_names(&patterns, limit as i64).await? {
+            if let Some(score) = name_score(query, &row.label) {
+                results.extend(from_row(row, score));
+            }
+        }
+        for token in token_directory() {
+            let name = token["name"].as_str().unwrap_or_default();
+            let symbol = token["symbol"].as_str().unwrap_or_default();
+            let score = [name, symbol]
+                .iter()
+                .filter_map(|text| name_score(query, text))
+                .reduce(f64::max);
+            if let Some(score) = score {
+                results.push(token_result(&token, score));
+            }
+        }
+        for federation in federation_directory() {
+            if let Some(score) = name_score(query, &federation.name) {
+                results.push(
+                    SearchResult::new(
+                        ResultKind::Federation,
+                        federation.id,
+                        federation.name,
+                        score,
+                    )
+                    .with_detail(Some(federation.industry)),
+                );
+            }
+        }
+        for project in project_directory() {
+            let name = project["name"].as_str().unwrap_or_default();
+            if let Some(score) = name_score(query, name) {
+                let id = project["id"].as_str().unwrap_or_default();
+                results.push(
+                    SearchResult::new(ResultKind::Project, id.to_string(), name.to_string(), score)
+                        .with_detail(project["tagline"].as_str().map(str::to_string)),
+                );
+            }
+        }
+    }
+
+    Ok(rank(results, limit))
+}
+
+/// Normalized identifier prefix, if `query` looks like one
+fn hex_prefix(query: &str) -> Option<String> {
+    let digits = query
+        .strip_prefix("0x")
+        .or_else(|| query.strip_prefix("0X"))?;
+    (digits.len() >= MIN_HEX_PREFIX && digits.chars().all(|c| c.is_ascii_hexdigit()))
+        .then(|| normalize_hash(digits))
+}
+
+/// Share of the identifier the prefix covers: 1.0 for an exact match
+fn identifier_score(prefix: &str, id: &str) -> f64 {
+    if prefix == id {
+        1.0
+    } else {
+        0.6 + 0.3 * prefix.len() as f64 / id.len().max(1) as f64
+    }
+}
+
+fn from_row(row: SearchRow, score: f64) -> Option<SearchResult> {
+    let kind = ResultKind::parse(&row.kind)?;
+    Some(SearchResult::new(kind, row.id, row.label, score))
+}
+
+fn token_result(token: &serde_json::Value, score: f64) -> SearchResult {
+    let text = |field: &str| token[field].as_str().unwrap_or_default().to_string();
+    SearchResult::new(ResultKind::Token, text("address"), text("name"), score)
+        .with_detail(Some(text("symbol")))
+}
+
+/// `ILIKE` patterns narrowing stored names to plausible matches
+fn name_patterns(query: &str) -> Vec<String> {
+    words(query)
+        .iter()
+        .map(|word| {
+            // Words are alphanumeric, so nothing needs escaping
+            format!("%{}%", truncate(word, NAME_PATTERN_CHARS))
+        })
+        .collect()
+}
+
+fn words(s: &str) -> Vec<String> {
+    s.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Relevance of `name` for `query`, `None` if it does not match
+pub fn name_score(query: &str, name: &str) -> Option<f64> {
+    let query = query.trim().to_lowercase();
+    let name = name.to_lowercase();
+    if query.is_empty() || name.is_empty() {
+        return None;
+    }
+    if name == query {
+        return Some(1.0);
+    }
+    if name.starts_with(&query) {
+        return Some(0.9);
+    }
+    let name_words = words(&name);
+    if name_words.iter().any(|w| w.starts_with(&query)) {
+        return Some(0.8);
+    }
+    if name.contains(&query) {
+        return Some(0.7);
+    }
+
+    // Every query word close to some name word; the last may be unfinished
+    let query_words = words(&query);
+    let mut distance = 0;
+    for (i, word) in query_words.iter().enumerate() {
+        let last = i + 1 == query_words.len();
+        let best = name_words
+            .iter()
+            .map(|candidate| {
+                let candidate = if last {
+                    truncate(candidate, word.chars().count() + 1)
+                } else {
+                    candidate.clone()
+                };
+                let full = levenshtein(word, &candidate);
+                if last {
+                    full.min(levenshtein(
+                        word,
+                        &truncate(&candidate, word.chars().count()),
+                    ))
+                } else {
+                    full
+                }
+            })
+            .min()?;
+        if best > max_typos(word) {
+            return None;
+        }
+        distance += best;
+    }
+    Some(6usize.saturating_sub(distance).max(3) as f64 / 10.0)
+}
+
+/// Edits tolerated in a query word of this length
+fn max_typos(word: &str) -> usize {
+    match word.chars().count() {
+        0..=3 => 0,
+        4..=7 => 1,
+        _ => 2,
+    }
+}
+
+fn truncate(s: &str, chars: usize) -> String {
+    s.chars().take(chars).collect()
+}
+
+/// Edit distance between two strings
+pub fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+/// Keep the best hit per entity, most relevant first
+fn rank(results: Vec<SearchResult>, limit: usize) -> Vec<SearchResult> {
+    let mut best: HashMap<(ResultKind, String), SearchResult> = HashMap::new();
+    for result in results {
+        let key = (result.kind, result.id.clone());
+        match best.get(&key) {
+            Some(existing) if existing.score >= result.score => {}
+            _ => {
+                best.insert(key, result);
+            }
+        }
+    }
+    let mut ranked: Vec<SearchResult> = best.into_values().collect();
+    ranked.sort_by(|a, b| {
+        b.score
+            .total_cmp(&a.score)
+            .then(a.kind.cmp(&b.kind))
+            .then_with(|| a.label.cmp(&b.label))
+    });
+    ranked.truncate(limit);
+    ranked
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hex_prefix() {
+        assert_eq!(hex_prefix("0xABCD").as_deref(), Some("0xabcd"));
+        assert_eq!(hex_prefix("0xabc"), None);
+        assert_eq!(hex_prefix("0xabcz"), None);
+        // Bare hex could be a name
+        assert_eq!(hex_prefix("cafe"), None);
+    }
+
+    #[test]
+    fn test_identifier_score() {
+        let hash = format!("0x{}", "ab".repeat(32));
+        assert_eq!(identifier_score(&hash, &hash), 1.0);
+        let partial = identifier_score("0xabab", &hash);
+        assert!(partial > 0.6 && partial < 0.7);
+    }
+
+    #[test]
+    fn test_levenshtein() {
+        assert_eq!(levenshtein("", "abc"), 3);
+        assert_eq!(levenshtein("kitten", "sitting"), 3);
+        assert_eq!(levenshtein("bank", "bank"), 0);
+    }
+
+    #[test]
+    fn test_name_score_ordering() {
+        let name = "Global Banking Consortium";
+        assert_eq!(name_score("global banking consortium", name), Some(1.0));
+        assert_eq!(name_score("glob", name), Some(0.9));
+        assert_eq!(name_score("bank", name), Some(0.8));
+        assert_eq!(name_score("onsort", name), Some(0.7));
+
+        // Typos and an unfinished last word
+        assert_eq!(name_score("bankng", name), Some(0.5));
+        assert_eq!(name_score("globl bankin", name), Some(0.5));
+        assert_eq!(name_score("consortuim", name), Some(0.4));
+
+        assert_eq!(name_score("healthcare", name), None);
+        // Short words must match exactly
+        assert_eq!(name_score("glx", name), None);
+    }
+
+    #[test]
+    fn test_name_patterns() {
+        assert_eq!(name_patterns("DC_Swap 50%"), vec!["%dc%", "%swa%", "%50%"]);
+        assert_eq!(name_patterns("a_b"), vec!["%a%", "%b%"]);
+    }
+
+    #[test]
+    fn test_rank_dedupes_and_orders() {
+        let hit =
+            |kind, id: &str, score| SearchResult::new(kind, id.to_string(), id.to_string(), score);
+        let ranked = rank(
+            vec![
+                hit(ResultKind::Project, "proj-001", 0.7),
+                hit(ResultKind::Token, "0x01", 0.8),
+                hit(ResultKind::Project, "proj-001", 0.9),
+                hit(ResultKind::Federation, "fed-001", 0.8),
+            ],
+            2,
+        );
+        assert_eq!(ranked.len(), 2);
+        assert_eq!(ranked[0].id, "proj-001");
+        assert_eq!(ranked[0].score, 0.9);
+        assert_eq!(ranked[1].kind, ResultKind::Token);
+        assert_eq!(ranked[0].url, "/api/v1/projects/proj-001");
+    }
+}
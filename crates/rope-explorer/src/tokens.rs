@@ -0,0 +1,213 @@
+//! Token transfer and internal transaction decoding
+//!
+//! Applied by the indexer to each successful transaction of a string:
+//!
+//! | Source    | Match                                          | Indexed as           |
+//! |-----------|------------------------------------------------|----------------------|
+//! | Event log | `Transfer(address,address,uint256)`, 3 topics  | token transfer       |
+//! | Call data | `bridgeIn(bytes32,uint256,bytes)`              | internal `bridgeIn`  |
+//! | Call data | `bridgeOut(bytes32,uint256)`                   | internal `bridgeOut` |
+//!
+//! ERC-721 transfers share the event signature but index the token id as
+//! a fourth topic; they are skipped. Holder balances are maintained from
+//! decoded transfers, with the zero address acting as mint and burn.
+
+use serde::{Deserialize, Serialize};
+
+/// keccak256("Transfer(address,address,uint256)")
+pub const TRANSFER_TOPIC: &str =
+    "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef";
+
+/// Selectors emitted by the rope-bridge EVM encoder
+pub const BRIDGE_IN_SELECTOR: [u8; 4] = [0xb1, 0x2c, 0x4e, 0x8f];
+pub const BRIDGE_OUT_SELECTOR: [u8; 4] = [0xc2, 0x3d, 0x5f, 0xa0];
+
+/// Mint source and burn destination
+pub const ZERO_ADDRESS: &str = "0x0000000000000000000000000000000000000000";
+
+/// Event log emitted by a transaction
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct IndexedLog {
+    /// Emitting contract
+    pub address: String,
+    pub topics: Vec<String>,
+    /// Hex
+    #[serde(default)]
+    pub data: String,
+}
+
+/// Decoded ERC-20 transfer
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TokenTransfer {
+    pub token: String,
+    pub from: String,
+    pub to: String,
+    /// Base units, decimal
+    pub value: String,
+    pub log_index: i32,
+}
+
+/// Decoded bridge call
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InternalTransaction {
+    /// `bridgeIn` or `bridgeOut`
+    pub kind: &'static str,
+    /// String id (bridge in) or proof hash (bridge out)
+    pub reference: String,
+    /// Base units, decimal
+    pub amount: String,
+}
+
+/// Decode an ERC-20 `Transfer` log
+pub fn decode_transfer(log: &IndexedLog, log_index: i32) -> Option<TokenTransfer> {
+    if log.topics.len() != 3 || !log.topics[0].eq_ignore_ascii_case(TRANSFER_TOPIC) {
+        return None;
+    }
+    let from = topic_address(&log.topics[1])?;
+    let to = topic_address(&log.topics[2])?;
+    let data = decode_hex(&log.data)?;
+    let value: [u8; 32] = data.get(..32)?.try_into().ok()?;
+    Some(TokenTransfer {
+        token: normalize_address(&log.address),
+        from,
+        to,
+        value: word_to_decimal(&value),
+        log_index,
+    })
+}
+
+/// Decode a bridge invocation from call data
+pub fn decode_bridge_call(input: &str) -> Option<InternalTransaction> {
+    let data = decode_hex(input)?;
+    if data.len() < 68 {
+        return None;
+    }
+    let kind = if data[..4] == BRIDGE_IN_SELECTOR {
+        "bridgeIn"
+    } else if data[..4] == BRIDGE_OUT_SELECTOR {
+        "bridgeOut"
+    } else {
+        return None;
+    };
+    let amount: [u8; 32] = data[36..68].try_into().ok()?;
+    Some(InternalTransaction {
+        kind,
+        reference: format!("0x{}", hex::encode(&data[4..36])),
+        amount: word_to_decimal(&amount),
+    })
+}
+
+/// Address in the low 20 bytes of a 32-byte topic
+fn topic_address(topic: &str) -> Option<String> {
+    let bytes = decode_hex(topic)?;
+    if bytes.len() != 32 {
+        return None;
+    }
+    Some(format!("0x{}", hex::encode(&bytes[12..])))
+}
+
+fn normalize_address(address: &str) -> String {
+    format!("0x{}", address.trim_start_matches("0x").to_lowercase())
+}
+
+fn decode_hex(s: &str) -> Option<Vec<u8>> {
+    hex::decode(s.trim_start_matches("0x")).ok()
+}
+
+/// Unsigned 256-bit big-endian word as a decimal string
+pub fn word_to_decimal(word: &[u8; 32]) -> String {
+    // Repeated division by 10^9 over base-2^32 limbs
+    let mut limbs: Vec<u32> = word
+        .chunks(4)
+        .map(|c| u32::from_be_bytes([c[0], c[1], c[2], c[3]]))
+        .collect();
+    let mut groups = Vec::new();
+    while limbs.iter().any(|l| *l != 0) {
+        let mut remainder = 0u64;
+        for limb in limbs.iter_mut() {
+            let current = (remainder << 32) | *limb as u64;
+            *limb = (current / 1_000_000_000) as u32;
+            remainder = current % 1_000_000_000;
+        }
+        groups.push(remainder);
+    }
+    match groups.split_last() {
+        None => "0".to_string(),
+        Some((most, rest)) => {
+            let mut out = most.to_string();
+            for group in rest.iter().rev() {
+                out.push_str(&format!("{:09}", group));
+            }
+            out
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn topic(address: &str) -> String {
+        format!("0x{:0>64}", address.trim_start_matches("0x"))
+    }
+
+    #[test]
+    fn test_word_to_decimal() {
+        let mut word = [0u8; 32];
+        assert_eq!(word_to_decimal(&word), "0");
+        word[31] = 42;
+        assert_eq!(word_to_decimal(&word), "42");
+        word[16..].copy_from_slice(&u128::MAX.to_be_bytes());
+        assert_eq!(word_to_decimal(&word), u128::MAX.to_string());
+        assert_eq!(
+            word_to_decimal(&[0xff; 32]),
+            "115792089237316195423570985008687907853269984665640564039457584007913129639935"
+        );
+    }
+
+    #[test]
+    fn test_decode_transfer() {
+        let log = IndexedLog {
+            address: "0xAbC0000000000000000000000000000000000001".to_string(),
+            topics: vec![
+                TRANSFER_TOPIC.to_uppercase().replace("0X", "0x"),
+                topic(ZERO_ADDRESS),
+                topic("0x00000000000000000000000000000000000000b2"),
+            ],
+            data: format!("0x{:064x}", 1_000_000u64),
+        };
+        let transfer = decode_transfer(&log, 3).unwrap();
+        assert_eq!(transfer.token, "0xabc0000000000000000000000000000000000001");
+        assert_eq!(transfer.from, ZERO_ADDRESS);
+        assert_eq!(transfer.to, "0x00000000000000000000000000000000000000b2");
+        assert_eq!(transfer.value, "1000000");
+        assert_eq!(transfer.log_index, 3);
+
+        // ERC-721: token id indexed as a fourth topic
+        let mut nft = log.clone();
+        nft.topics.push(topic("0x07"));
+        nft.data = String::new();
+        assert_eq!(decode_transfer(&nft, 0), None);
+
+        let mut other = log;
+        other.topics[0] = topic("0x01");
+        assert_eq!(decode_transfer(&other, 0), None);
+    }
+
+    #[test]
+    fn test_decode_bridge_call() {
+        let input = format!("0xb12c4e8f{}{:064x}", "11".repeat(32), 500u64);
+        let call = decode_bridge_call(&input).unwrap();
+        assert_eq!(call.kind, "bridgeIn");
+        assert_eq!(call.reference, format!("0x{}", "11".repeat(32)));
+        assert_eq!(call.amount, "500");
+
+        let input = format!("0xc23d5fa0{}{:064x}", "22".repeat(32), 7u64);
+        assert_eq!(decode_bridge_call(&input).unwrap().kind, "bridgeOut");
+
+        // ERC-20 transfer and truncated input are not bridge calls
+        let input = format!("0xa9059cbb{}{:064x}", "33".repeat(32), 1u64);
+        assert_eq!(decode_bridge_call(&input), None);
+        assert_eq!(decode_bridge_call("0xb12c4e8f"), None);
+    }
+}
@@ -0,0 +1,837 @@
+//! Contract and string payload verification
+//!
+//! | Target   | Submitted                         | Checked against                          |
+//! |----------|-----------------------------------|------------------------------------------|
+//! | Contract | Solidity source, compiler options | `eth_getCode`, metadata trailer stripped |
+//! | String   | Payload (hex) and field schema    | BLAKE3 of `rope_getStringById` content   |
+//!
+//! Contracts are recompiled with a local `solc` (standard JSON I/O). The
+//! version it reports must match the submitted one; switching versions is
+//! left to the host (e.g. `solc-select`).
+//!
+//! The latest result per target is stored. A failed attempt never replaces
+//! a successful one, and the ABI of a verified contract is used to decode
+//! call data on transaction detail pages.
+
+use crate::api::{ApiError, ApiResult, ErrorBody};
+use crate::db::DbError;
+use crate::indexer::normalize_hash;
+use crate::AppState;
+use axum::extract::{Path, State};
+use axum::Json;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha3::{Digest, Keccak256};
+use std::path::PathBuf;
+use std::process::Stdio;
+use std::sync::Arc;
+use std::time::Duration;
+use thiserror::Error;
+use tokio::io::AsyncWriteExt;
+use utoipa::ToSchema;
+
+/// Verification kinds
+pub const KIND_CONTRACT: &str = "contract";
+pub const KIND_STRING: &str = "string";
+
+/// Verification outcomes
+pub const STATUS_VERIFIED: &str = "verified";
+pub const STATUS_FAILED: &str = "failed";
+
+/// Upper bound for one compilation
+const COMPILE_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Largest accepted source file
+const MAX_SOURCE_BYTES: usize = 512 * 1024;
+
+/// Source file name used in the compiler input
+const SOURCE_FILE: &str = "Contract.sol";
+
+/// Verification errors (infrastructure, not mismatches)
+#[derive(Error, Debug)]
+pub enum VerifyError {
+    #[error("Node RPC error: {0}")]
+    Node(String),
+
+    #[error("Compiler unavailable: {0}")]
+    Compiler(String),
+
+    #[error("Compilation timed out")]
+    Timeout,
+
+    #[error(transparent)]
+    Database(#[from] DbError),
+}
+
+impl From<VerifyError> for ApiError {
+    fn from(e: VerifyError) -> Self {
+        match e {
+            VerifyError::Database(e) => ApiError::Database(e),
+            other => ApiError::Unavailable(other.to_string()),
+        }
+    }
+}
+
+/// Contract source submission
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ContractSubmission {
+    pub address: String,
+    /// Contract to match within the source
+    pub contract_name: String,
+    pub source_code: String,
+    /// e.g. `0.8.24`
+    pub compiler_version: String,
+    #[serde(default)]
+    pub optimization: bool,
+    #[serde(default = "default_runs")]
+    pub runs: u32,
+}
+
+fn default_runs() -> u32 {
+    200
+}
+
+/// String payload submission
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct StringSubmission {
+    pub string_hash: String,
+    /// Payload bytes, hex
+    pub payload: String,
+    pub schema: PayloadSchema,
+}
+
+/// JSON payload layout
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct PayloadSchema {
+    pub name: String,
+    pub fields: Vec<SchemaField>,
+}
+
+/// Named, typed payload field
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct SchemaField {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub field_type: FieldType,
+}
+
+/// JSON value types
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum FieldType {
+    String,
+    Number,
+    Bool,
+    Object,
+    Array,
+}
+
+impl FieldType {
+    fn matches(self, value: &Value) -> bool {
+        match self {
+            FieldType::String => value.is_string(),
+            FieldType::Number => value.is_number(),
+            FieldType::Bool => value.is_boolean(),
+            FieldType::Object => value.is_object(),
+            FieldType::Array => value.is_array(),
+        }
+    }
+}
+
+/// Stored verification result
+#[derive(Clone, Debug, Serialize, sqlx::FromRow, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct VerificationRecord {
+    /// Contract address or string hash
+    pub target: String,
+    /// `contract` or `string`
+    pub kind: String,
+    /// `verified` or `failed`
+    pub status: String,
+    /// Contract or schema name
+    pub name: String,
+    pub compiler_version: Option<String>,
+    /// Contract ABI or payload schema
+    #[schema(value_type = Option<Object>)]
+    pub metadata: Option<sqlx::types::Json<Value>>,
+    /// Decoded payload fields (strings only)
+    #[schema(value_type = Option<Object>)]
+    pub decoded: Option<sqlx::types::Json<Value>>,
+    /// Why verification failed
+    pub message: Option<String>,
+    pub submitted_at: i64,
+}
+
+impl VerificationRecord {
+    fn new(target: String, kind: &str, name: String) -> Self {
+        Self {
+            target,
+            kind: kind.to_string(),
+            status: STATUS_FAILED.to_string(),
+            name,
+            compiler_version: None,
+            metadata: None,
+            decoded: None,
+            message: None,
+            submitted_at: chrono::Utc::now().timestamp(),
+        }
+    }
+
+    fn verified(mut self) -> Self {
+        self.status = STATUS_VERIFIED.to_string();
+        self
+    }
+
+    fn failed(mut self, message: impl Into<String>) -> Self {
+        self.status = STATUS_FAILED.to_string();
+        self.message = Some(message.into());
+        self
+    }
+
+    pub fn is_verified(&self) -> bool {
+        self.status == STATUS_VERIFIED
+    }
+}
+
+/// Output of a compilation
+#[derive(Clone, Debug)]
+pub struct CompiledContract {
+    pub abi: Value,
+    pub runtime_bytecode: Vec<u8>,
+}
+
+/// Outcome of compiling a submission
+#[derive(Debug)]
+pub enum Compilation {
+    Compiled(CompiledContract),
+    /// Source rejected by the compiler
+    Failed(String),
+}
+
+/// Node JSON-RPC client
+#[derive(Clone)]
+pub struct NodeRpc {
+    client: reqwest::Client,
+    url: String,
+}
+
+impl NodeRpc {
+    pub fn new(client: reqwest::Client, url: impl Into<String>) -> Self {
+        Self {
+            client,
+            url: url.into(),
+        }
+    }
+
+    async fn call(&self, method: &str, params: Value) -> Result<Value, VerifyError> {
+        let body = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": method,
+            "params": params
+        });
+        let response: Value = self
+            .client
+            .post(&self.url)
+            .json(&body)
+            .send()
+            .await
+            .map_err(|e| VerifyError::Node(e.to_string()))?
+            .json()
+            .await
+            .map_err(|e| VerifyError::Node(e.to_string()))?;
+        if let Some(error) = response.get("error") {
+            return Err(VerifyError::Node(error.to_string()));
+        }
+        Ok(response.get("result").cloned().unwrap_or(Value::Null))
+    }
+
+    /// Deployed bytecode (empty for accounts without code)
+    pub async fn code(&self, address: &str) -> Result<Vec<u8>, VerifyError> {
+        let result = self
+            .call("eth_getCode", serde_json::json!([address, "latest"]))
+            .await?;
+        let code = result.as_str().unwrap_or("0x");
+        decode_hex(code).ok_or_else(|| VerifyError::Node(format!("invalid code '{}'", code)))
+    }
+
+    /// Content of a string, `None` if the node does not have it
+    pub async fn string_content(&self, hash: &str) -> Result<Option<Vec<u8>>, VerifyError> {
+        let result = self
+            .call("rope_getStringById", serde_json::json!([hash]))
+            .await?;
+        match result.get("content").and_then(Value::as_str) {
+            Some(content) => decode_hex(content)
+                .map(Some)
+                .ok_or_else(|| VerifyError::Node("invalid string content".to_string())),
+            None => Ok(None),
+        }
+    }
+}
+
+/// Local `solc` binary
+#[derive(Clone)]
+pub struct Solc {
+    path: PathBuf,
+}
+
+impl Solc {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// Version string reported by the binary
+    pub async fn version(&self) -> Result<String, VerifyError> {
+        let output = tokio::process::Command::new(&self.path)
+            .arg("--version")
+            .output()
+            .await
+            .map_err(|e| VerifyError::Compiler(format!("{}: {}", self.path.display(), e)))?;
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        stdout
+            .lines()
+            .find_map(|line| line.strip_prefix("Version: "))
+            .map(|v| v.trim().to_string())
+            .ok_or_else(|| VerifyError::Compiler("unrecognized --version output".to_string()))
+    }
+
+    /// Compile `submission` and extract its runtime bytecode and ABI
+    pub async fn compile(
+        &self,
+        submission: &ContractSubmission,
+    ) -> Result<Compilation, VerifyError> {
+        let input = standard_json_input(submission);
+        let mut child = tokio::process::Command::new(&self.path)
+            .arg("--standard-json")
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|e| VerifyError::Compiler(format!("{}: {}", self.path.display(), e)))?;
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin
+                .write_all(input.to_string().as_bytes())
+                .await
+                .map_err(|e| VerifyError::Compiler(e.to_string()))?;
+        }
+        let output = tokio::time::timeout(COMPILE_TIMEOUT, child.wait_with_output())
+            .await
+            .map_err(|_| VerifyError::Timeout)?
+            .map_err(|e| VerifyError::Compiler(e.to_string()))?;
+        let output: Value = serde_json::from_slice(&output.stdout)
+            .map_err(|e| VerifyError::Compiler(format!("unreadable output: {}", e)))?;
+        Ok(parse_standard_json_output(
+            &output,
+            &submission.contract_name,
+        ))
+    }
+}
+
+/// Node and compiler access
+#[derive(Clone)]
+pub struct Verifier {
+    pub node: NodeRpc,
+    pub solc: Solc,
+}
+
+impl Verifier {
+    pub fn new(node: NodeRpc, solc: Solc) -> Self {
+        Self { node, solc }
+    }
+
+    /// Recompile a contract and compare it with the deployed code
+    pub async fn verify_contract(
+        &self,
+        submission: &ContractSubmission,
+    ) -> Result<VerificationRecord, VerifyError> {
+        let mut record = VerificationRecord::new(
+            normalize_hash(&submission.address),
+            KIND_CONTRACT,
+            submission.contract_name.clone(),
+        );
+        record.compiler_version = Some(submission.compiler_version.clone());
+
+        let wanted = submission.compiler_version.trim_start_matches('v');
+        let version = self.solc.version().await?;
+        if !version.starts_with(wanted) {
+            return Err(VerifyError::Compiler(format!(
+                "solc {} requested, {} installed",
+                wanted, version
+            )));
+        }
+
+        let onchain = self.node.code(&record.target).await?;
+        if onchain.is_empty() {
+            return Ok(record.failed("No contract code at this address"));
+        }
+
+        Ok(match self.solc.compile(submission).await? {
+            Compilation::Failed(message) => record.failed(message),
+            Compilation::Compiled(compiled) => {
+                if bytecode_matches(&onchain, &compiled.runtime_bytecode) {
+                    record.metadata = Some(sqlx::types::Json(compiled.abi));
+                    record.verified()
+                } else {
+                    record.failed("Compiled bytecode does not match the deployed code")
+                }
+            }
+        })
+    }
+
+    /// Hash-check a payload against the string's on-chain content
+    pub async fn verify_string(
+        &self,
+        submission: &StringSubmission,
+    ) -> Result<VerificationRecord, VerifyError> {
+        let mut record = VerificationRecord::new(
+            normalize_hash(&submission.string_hash),
+            KIND_STRING,
+            submission.schema.name.clone(),
+        );
+        record.metadata = serde_json::to_value(&submission.schema)
+            .ok()
+            .map(sqlx::types::Json);
+
+        let Some(payload) = decode_hex(&submission.payload) else {
+            return Ok(record.failed("Payload is not valid hex"));
+        };
+        let Some(content) = self.node.string_content(&record.target).await? else {
+            return Ok(record.failed("String content not available on the node"));
+        };
+        Ok(
+            match check_payload(&content, &payload, &submission.schema) {
+                Ok(decoded) => {
+                    record.decoded = Some(sqlx::types::Json(decoded));
+                    record.verified()
+                }
+                Err(message) => record.failed(message),
+            },
+        )
+    }
+}
+
+/// Compiler input for a single-file submission
+pub fn standard_json_input(submission: &ContractSubmission) -> Value {
+    serde_json::json!({
+        "language": "Solidity",
+        "sources": { SOURCE_FILE: { "content": submission.source_code } },
+        "settings": {
+            "optimizer": {
+                "enabled": submission.optimization,
+                "runs": submission.runs
+            },
+            "outputSelection": {
+                "*": { "*": ["abi", "evm.deployedBytecode.object"] }
+            }
+        }
+    })
+}
+
+/// Extract `contract_name` from compiler output
+pub fn parse_standard_json_output(output: &Value, contract_name: &str) -> Compilation {
+    let errors: Vec<&str> = output["errors"]
+        .as_array()
+        .map(|errors| {
+            errors
+                .iter()
+                .filter(|e| e["severity"] == "error")
+                .filter_map(|e| e["formattedMessage"].as_str().or(e["message"].as_str()))
+                .collect()
+        })
+        .unwrap_or_default();
+    if !errors.is_empty() {
+        return Compilation::Failed(errors.join("\n"));
+    }
+
+    let contract = &output["contracts"][SOURCE_FILE][contract_name];
+    if contract.is_null() {
+        return Compilation::Failed(format!("Contract '{}' not found in source", contract_name));
+    }
+    let bytecode = contract["evm"]["deployedBytecode"]["object"]
+        .as_str()
+        .and_then(decode_hex);
+    match bytecode {
+        Some(runtime_bytecode) if !runtime_bytecode.is_empty() => {
+            Compilation::Compiled(CompiledContract {
+                abi: contract["abi"].clone(),
+                runtime_bytecode,
+            })
+        }
+        // Abstract contracts and interfaces have no code
+        _ => Compilation::Failed(format!("Contract '{}' has no runtime code", contract_name)),
+    }
+}
+
+/// Bytecode without the trailing CBOR metadata (its length is the last two bytes)
+pub fn strip_metadata(code: &[u8]) -> &[u8] {
+    if code.len() < 2 {
+        return code;
+    }
+    let len = u16::from_be_bytes([code[code.len() - 2], code[code.len() - 1]]) as usize;
+    match code.len().checked_sub(len + 2) {
+        Some(end) => &code[..end],
+        None => code,
+    }
+}
+
+/// Whether compiled and deployed code match, ignoring metadata (source hashes and compiler tag)
+pub fn bytecode_matches(onchain: &[u8], compiled: &[u8]) -> bool {
+    !compiled.is_empty() && strip_metadata(onchain) == strip_metadata(compiled)
+}
+
+/// Check a payload against on-chain content and decode it with `schema`
+pub fn check_payload(
+    content: &[u8],
+    payload: &[u8],
+    schema: &PayloadSchema,
+) -> Result<Value, String> {
+    if blake3::hash(content) != blake3::hash(payload) {
+        return Err("Payload hash does not match the on-chain content".to_string());
+    }
+    let value: Value =
+        serde_json::from_slice(payload).map_err(|e| format!("Payload is not JSON: {}", e))?;
+    let object = value
+        .as_object()
+        .ok_or_else(|| "Payload is not a JSON object".to_string())?;
+
+    let mut decoded = serde_json::Map::new();
+    for field in &schema.fields {
+        let value = object
+            .get(&field.name)
+            .ok_or_else(|| format!("Missing field '{}'", field.name))?;
+        if !field.field_type.matches(value) {
+            return Err(format!(
+                "Field '{}' is not of type {:?}",
+                field.name, field.field_type
+            ));
+        }
+        decoded.insert(field.name.clone(), value.clone());
+    }
+    Ok(Value::Object(decoded))
+}
+
+/// Call decoded with a verified ABI
+#[derive(Clone, Debug, PartialEq, Serialize, ToSchema)]
+pub struct DecodedCall {
+    pub method: String,
+    /// Canonical signature, e.g. `transfer(address,uint256)`
+    pub signature: String,
+    pub selector: String,
+    pub arguments: Vec<DecodedArgument>,
+}
+
+/// One argument of a decoded call
+#[derive(Clone, Debug, PartialEq, Serialize, ToSchema)]
+pub struct DecodedArgument {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub arg_type: String,
+    /// Decoded value; raw 32-byte word (hex) for types not decoded
+    pub value: String,
+}
+
+/// Canonical type of an ABI parameter (tuples expanded)
+fn canonical_type(param: &Value) -> Option<String> {
+    let ty = param["type"].as_str()?;
+    match ty.strip_prefix("tuple") {
+        Some(suffix) => {
+            let components = param["components"]
+                .as_array()?
+                .iter()
+                .map(canonical_type)
+                .collect::<Option<Vec<_>>>()?;
+            Some(format!("({}){}", components.join(","), suffix))
+        }
+        None => Some(ty.to_string()),
+    }
+}
+
+/// `name(type,...)` of an ABI function entry
+pub fn function_signature(item: &Value) -> Option<String> {
+    let types = item["inputs"]
+        .as_array()?
+        .iter()
+        .map(canonical_type)
+        .collect::<Option<Vec<_>>>()?;
+    Some(format!("{}({})", item["name"].as_str()?, types.join(",")))
+}
+
+/// First four bytes of keccak256(signature)
+pub fn selector(signature: &str) -> [u8; 4] {
+    let hash = Keccak256::digest(signature.as_bytes());
+    [hash[0], hash[1], hash[2], hash[3]]
+}
+
+/// Decode call data against `abi`
+pub fn decode_call(abi: &Value, input: &str) -> Option<DecodedCall> {
+    let data = decode_hex(input)?;
+    if data.len() < 4 {
+        return None;
+    }
+    let item = abi.as_array()?.iter().find(|item| {
+        item["type"] == "function"
+            && function_signature(item).is_some_and(|sig| selector(&sig) == data[..4])
+    })?;
+    let signature = function_signature(item)?;
+
+    let words: Vec<&[u8]> = data[4..].chunks(32).collect();
+    let arguments = item["inputs"]
+        .as_array()?
+        .iter()
+        .enumerate()
+        .map(|(i, param)| {
+            let arg_type = canonical_type(param).unwrap_or_default();
+            let value = match words.get(i) {
+                Some(word) if word.len() == 32 => decode_word(&arg_type, word),
+                _ => String::new(),
+            };
+            DecodedArgument {
+                name: param["name"].as_str().unwrap_or_default().to_string(),
+                arg_type,
+                value,
+            }
+        })
+        .collect();
+
+    Some(DecodedCall {
+        method: item["name"].as_str()?.to_string(),
+        selector: format!("0x{}", hex::encode(&data[..4])),
+        signature,
+        arguments,
+    })
+}
+
+/// Static head word as display text
+fn decode_word(arg_type: &str, word: &[u8]) -> String {
+    let small = word[..16].iter().all(|b| *b == 0);
+    match arg_type {
+        "address" => format!("0x{}", hex::encode(&word[12..])),
+        "bool" => (word[31] != 0).to_string(),
+        t if t.starts_with("uint") && small => {
+            let mut low = [0u8; 16];
+            low.copy_from_slice(&word[16..]);
+            u128::from_be_bytes(low).to_string()
+        }
+        _ => format!("0x{}", hex::encode(word)),
+    }
+}
+
+fn decode_hex(s: &str) -> Option<Vec<u8>> {
+    hex::decode(s.trim_start_matches("0x")).ok()
+}
+
+/// Recompile and verify a deployed contract
+#[utoipa::path(
+    post,
+    path = "/api/v1/verify/contract",
+    tag = "Verification",
+    request_body = ContractSubmission,
+    responses(
+        (status = 200, description = "Verification result", body = VerificationRecord),
+        (status = 400, description = "Invalid submission", body = ErrorBody),
+        (status = 503, description = "Node or compiler unavailable", body = ErrorBody)
+    )
+)]
+pub async fn verify_contract(
+    State(state): State<Arc<AppState>>,
+    Json(submission): Json<ContractSubmission>,
+) -> ApiResult<VerificationRecord> {
+    if submission.source_code.len() > MAX_SOURCE_BYTES {
+        return Err(ApiError::BadRequest(format!(
+            "source exceeds {} bytes",
+            MAX_SOURCE_BYTES
+        )));
+    }
+    let record = state.verifier.verify_contract(&submission).await?;
+    Ok(Json(store(&state, record).await?))
+}
+
+/// Hash-check and decode a string payload
+#[utoipa::path(
+    post,
+    path = "/api/v1/verify/string",
+    tag = "Verification",
+    request_body = StringSubmission,
+    responses(
+        (status = 200, description = "Verification result", body = VerificationRecord),
+        (status = 503, description = "Node unavailable", body = ErrorBody)
+    )
+)]
+pub async fn verify_string(
+    State(state): State<Arc<AppState>>,
+    Json(submission): Json<StringSubmission>,
+) -> ApiResult<VerificationRecord> {
+    let record = state.verifier.verify_string(&submission).await?;
+    Ok(Json(store(&state, record).await?))
+}
+
+/// Verification status of a contract or string
+#[utoipa::path(
+    get,
+    path = "/api/v1/verify/{target}",
+    tag = "Verification",
+    params(("target" = String, Path, description = "Contract address or string hash")),
+    responses(
+        (status = 200, description = "Verification result", body = VerificationRecord),
+        (status = 404, description = "Never verified", body = ErrorBody)
+    )
+)]
+pub async fn get_verification(
+    State(state): State<Arc<AppState>>,
+    Path(target): Path<String>,
+) -> ApiResult<VerificationRecord> {
+    let record = state
+        .db
+        .get_verification(&normalize_hash(&target))
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("Verification of {}", target)))?;
+    Ok(Json(record))
+}
+
+/// Persist a result, returning what is now on record
+async fn store(
+    state: &AppState,
+    record: VerificationRecord,
+) -> Result<VerificationRecord, ApiError> {
+    if !state.db.upsert_verification(&record).await? {
+        // Already verified; report this attempt without replacing the record
+        tracing::debug!("Kept existing verification of {}", record.target);
+    }
+    Ok(record)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn erc20_abi() -> Value {
+        serde_json::json!([
+            {
+                "type": "function",
+                "name": "transfer",
+                "inputs": [
+                    { "name": "to", "type": "address" },
+                    { "name": "amount", "type": "uint256" }
+                ]
+            },
+            {
+                "type": "function",
+                "name": "submit",
+                "inputs": [
+                    { "name": "order", "type": "tuple", "components": [
+                        { "name": "id", "type": "uint64" },
+                        { "name": "tags", "type": "bytes32[]" }
+                    ]}
+                ]
+            },
+            { "type": "event", "name": "Transfer", "inputs": [] }
+        ])
+    }
+
+    #[test]
+    fn test_selector_and_signature() {
+        assert_eq!(
+            selector("transfer(address,uint256)"),
+            [0xa9, 0x05, 0x9c, 0xbb]
+        );
+        let abi = erc20_abi();
+        assert_eq!(
+            function_signature(&abi[1]).as_deref(),
+            Some("submit((uint64,bytes32[]))")
+        );
+    }
+
+    #[test]
+    fn test_decode_call() {
+        let input = format!(
+            "0xa9059cbb{:0>64}{:064x}",
+            "00000000000000000000000011223344556677889900aabbccddeeff00112233", 1_000u64
+        );
+        let call = decode_call(&erc20_abi(), &input).unwrap();
+        assert_eq!(call.method, "transfer");
+        assert_eq!(call.selector, "0xa9059cbb");
+        assert_eq!(
+            call.arguments[0].value,
+            "0x11223344556677889900aabbccddeeff00112233"
+        );
+        assert_eq!(call.arguments[1].arg_type, "uint256");
+        assert_eq!(call.arguments[1].value, "1000");
+
+        assert_eq!(decode_call(&erc20_abi(), "0xdeadbeef"), None);
+        assert_eq!(decode_call(&erc20_abi(), "0x12"), None);
+    }
+
+    #[test]
+    fn test_bytecode_match_ignores_metadata() {
+        // code ++ metadata(3 bytes) ++ len(3)
+        let onchain = [0x60, 0x80, 0x60, 0x40, 0xa1, 0x01, 0x02, 0x00, 0x03];
+        let compiled = [0x60, 0x80, 0x60, 0x40, 0xa1, 0xff, 0xfe, 0x00, 0x03];
+        assert_eq!(strip_metadata(&onchain), &[0x60, 0x80, 0x60, 0x40]);
+        assert!(bytecode_matches(&onchain, &compiled));
+
+        let other = [0x60, 0x81, 0x60, 0x40, 0xa1, 0x01, 0x02, 0x00, 0x03];
+        assert!(!bytecode_matches(&onchain, &other));
+        assert!(!bytecode_matches(&onchain, &[]));
+
+        // Length larger than the code: compared as-is
+        assert_eq!(strip_metadata(&[0x01, 0xff, 0xff]), &[0x01, 0xff, 0xff]);
+    }
+
+    #[test]
+    fn test_parse_compiler_output() {
+        let output = serde_json::json!({
+            "contracts": { "Contract.sol": { "Token": {
+                "abi": [],
+                "evm": { "deployedBytecode": { "object": "6080" } }
+            }}},
+            "errors": [{ "severity": "warning", "message": "unused variable" }]
+        });
+        match parse_standard_json_output(&output, "Token") {
+            Compilation::Compiled(c) => assert_eq!(c.runtime_bytecode, vec![0x60, 0x80]),
+            other => panic!("unexpected {:?}", other),
+        }
+        assert!(matches!(
+            parse_standard_json_output(&output, "Missing"),
+            Compilation::Failed(_)
+        ));
+
+        let output = serde_json::json!({
+            "errors": [{ "severity": "error", "formattedMessage": "ParserError: expected ';'" }]
+        });
+        match parse_standard_json_output(&output, "Token") {
+            Compilation::Failed(message) => assert!(message.contains("ParserError")),
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_check_payload() {
+        let schema = PayloadSchema {
+            name: "Reading".to_string(),
+            fields: vec![
+                SchemaField {
+                    name: "sensor".to_string(),
+                    field_type: FieldType::String,
+                },
+                SchemaField {
+                    name: "value".to_string(),
+                    field_type: FieldType::Number,
+                },
+            ],
+        };
+        let payload = br#"{"sensor":"t-1","value":21.5,"extra":true}"#;
+
+        let decoded = check_payload(payload, payload, &schema).unwrap();
+        assert_eq!(decoded, serde_json::json!({"sensor": "t-1", "value": 21.5}));
+
+        assert!(check_payload(b"other", payload, &schema)
+            .unwrap_err()
+            .contains("hash"));
+
+        let wrong_type = br#"{"sensor":"t-1","value":"warm"}"#;
+        assert!(check_payload(wrong_type, wrong_type, &schema).is_err());
+    }
+}
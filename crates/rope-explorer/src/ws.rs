@@ -0,0 +1,369 @@
+//! Live subscriptions over `/api/v1/ws`
+//!
+//! Clients send `{"type":"subscribe","topics":[...]}` and receive JSON
+//! events for the topics they chose:
+//!
+//! | Topic                 | Event                | Published by        |
+//! |-----------------------|----------------------|---------------------|
+//! | `strings`             | `newString`          | indexer             |
+//! | `pendingTransactions` | `pendingTransaction` | indexer             |
+//! | `validators`          | `validatorStatus`    | indexer             |
+//! | `price`               | `priceUpdate`        | price fetcher       |
+//!
+//! The server sends a `heartbeat` every [`HEARTBEAT_INTERVAL`] and drops
+//! connections that stay silent for [`CLIENT_TIMEOUT`]; any client frame,
+//! including `{"type":"ping"}`, counts as activity.
+
+use crate::indexer::{IndexedString, IndexedTransaction, IndexedValidator};
+use crate::AppState;
+use crate::PriceData;
+use axum::{
+    extract::{
+        ws::{Message, WebSocket, WebSocketUpgrade},
+        State,
+    },
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use futures::{SinkExt, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::broadcast;
+
+/// Server heartbeat interval
+pub const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(20);
+
+/// Silence after which a client is disconnected
+pub const CLIENT_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Maximum concurrent WebSocket clients
+const MAX_CONNECTIONS: usize = 4096;
+
+/// Events buffered per client before it is reported as lagging
+const EVENT_BUFFER: usize = 1024;
+
+/// Subscription topics
+pub const TOPICS: [&str; 4] = ["strings", "pendingTransactions", "validators", "price"];
+
+/// Newly indexed string
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StringSummary {
+    pub hash: String,
+    pub creator: String,
+    pub string_type: String,
+    pub transactions: usize,
+    pub timestamp: i64,
+}
+
+impl From<&IndexedString> for StringSummary {
+    fn from(string: &IndexedString) -> Self {
+        Self {
+            hash: string.id.clone(),
+            creator: string.creator.clone(),
+            string_type: string.string_type.clone(),
+            transactions: string.transactions.len(),
+            timestamp: string.timestamp,
+        }
+    }
+}
+
+/// Transaction in a string that is not final yet
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PendingTransaction {
+    pub hash: String,
+    pub string_hash: String,
+    pub from: String,
+    pub to: Option<String>,
+    pub value: String,
+    pub fee: String,
+    pub status: String,
+    pub timestamp: i64,
+}
+
+impl PendingTransaction {
+    /// Summary of `transaction` carried by `string_hash`
+    pub fn new(string_hash: &str, transaction: &IndexedTransaction) -> Self {
+        Self {
+            hash: transaction.hash.clone(),
+            string_hash: string_hash.to_string(),
+            from: transaction.from.clone(),
+            to: transaction.to.clone(),
+            value: transaction.value.clone(),
+            fee: transaction.fee.clone(),
+            status: transaction.status().to_string(),
+            timestamp: transaction.timestamp,
+        }
+    }
+}
+
+/// Event sent to clients
+#[derive(Clone, Debug, Serialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum LiveEvent {
+    NewString(StringSummary),
+    PendingTransaction(PendingTransaction),
+    ValidatorStatus(IndexedValidator),
+    PriceUpdate(PriceData),
+    Subscribed { topics: Vec<String> },
+    Heartbeat { timestamp: i64 },
+    Pong { timestamp: i64 },
+    Error { message: String },
+}
+
+impl LiveEvent {
+    /// Topic a published event belongs to (`None` for control frames)
+    pub fn topic(&self) -> Option<&'static str> {
+        match self {
+            LiveEvent::NewString(_) => Some("strings"),
+            LiveEvent::PendingTransaction(_) => Some("pendingTransactions"),
+            LiveEvent::ValidatorStatus(_) => Some("validators"),
+            LiveEvent::PriceUpdate(_) => Some("price"),
+            _ => None,
+        }
+    }
+}
+
+/// Message from a client
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum ClientMessage {
+    Subscribe { topics: Vec<String> },
+    Unsubscribe { topics: Vec<String> },
+    Ping,
+}
+
+/// Topics one connection is subscribed to
+#[derive(Debug, Default)]
+pub struct Subscriptions {
+    topics: HashSet<&'static str>,
+}
+
+impl Subscriptions {
+    /// Whether `event` should be forwarded
+    pub fn wants(&self, event: &LiveEvent) -> bool {
+        event
+            .topic()
+            .is_some_and(|topic| self.topics.contains(topic))
+    }
+
+    /// Apply a client message, returning the reply
+    pub fn handle(&mut self, message: ClientMessage) -> LiveEvent {
+        match message {
+            ClientMessage::Ping => LiveEvent::Pong {
+                timestamp: chrono::Utc::now().timestamp(),
+            },
+            ClientMessage::Subscribe { topics } | ClientMessage::Unsubscribe { topics }
+                if topics.iter().any(|t| !TOPICS.contains(&t.as_str())) =>
+            {
+                LiveEvent::Error {
+                    message: format!("Unknown topic; expected one of {}", TOPICS.join(", ")),
+                }
+            }
+            ClientMessage::Subscribe { topics } => {
+                for topic in TOPICS {
+                    if topics.iter().any(|s| s == topic) {
+                        self.topics.insert(topic);
+                    }
+                }
+                self.current()
+            }
+            ClientMessage::Unsubscribe { topics } => {
+                self.topics.retain(|t| !topics.iter().any(|s| s == t));
+                self.current()
+            }
+        }
+    }
+
+    fn current(&self) -> LiveEvent {
+        let mut topics: Vec<String> = self.topics.iter().map(|t| t.to_string()).collect();
+        topics.sort();
+        LiveEvent::Subscribed { topics }
+    }
+}
+
+/// Fan-out point for live events
+#[derive(Clone)]
+pub struct LiveHub {
+    events: broadcast::Sender<LiveEvent>,
+    connections: Arc<AtomicUsize>,
+}
+
+impl LiveHub {
+    pub fn new() -> Self {
+        let (events, _) = broadcast::channel(EVENT_BUFFER);
+        Self {
+            events,
+            connections: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    /// Publish to every subscribed client
+    pub fn publish(&self, event: LiveEvent) {
+        // No receivers simply means nobody is connected
+        let _ = self.events.send(event);
+    }
+
+    /// Connected clients
+    pub fn connections(&self) -> usize {
+        self.connections.load(Ordering::SeqCst)
+    }
+}
+
+impl Default for LiveHub {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Live subscriptions (WebSocket upgrade)
+#[utoipa::path(
+    get,
+    path = "/api/v1/ws",
+    tag = "Live",
+    responses(
+        (status = 101, description = "Switching to the WebSocket protocol"),
+        (status = 503, description = "Too many WebSocket clients")
+    )
+)]
+pub async fn ws_handler(ws: WebSocketUpgrade, State(state): State<Arc<AppState>>) -> Response {
+    let hub = state.live.clone();
+    if hub.connections() >= MAX_CONNECTIONS {
+        return (
+            StatusCode::SERVICE_UNAVAILABLE,
+            "Too many WebSocket clients",
+        )
+            .into_response();
+    }
+    ws.on_upgrade(move |socket| serve_socket(socket, hub))
+}
+
+async fn serve_socket(socket: WebSocket, hub: LiveHub) {
+    hub.connections.fetch_add(1, Ordering::SeqCst);
+    let mut events = hub.events.subscribe();
+    let (mut sender, mut receiver) = socket.split();
+    let mut subscriptions = Subscriptions::default();
+    let mut heartbeat = tokio::time::interval(HEARTBEAT_INTERVAL);
+    let mut last_seen = Instant::now();
+
+    loop {
+        let reply = tokio::select! {
+            incoming = receiver.next() => match incoming {
+                Some(Ok(Message::Text(text))) => {
+                    last_seen = Instant::now();
+                    Some(match serde_json::from_str::<ClientMessage>(&text) {
+                        Ok(message) => subscriptions.handle(message),
+                        Err(e) => LiveEvent::Error {
+                            message: format!("Invalid message: {}", e),
+                        },
+                    })
+                }
+                Some(Ok(Message::Close(_))) | None | Some(Err(_)) => break,
+                Some(Ok(_)) => {
+                    last_seen = Instant::now();
+                    None
+                }
+            },
+            event = events.recv() => match event {
+                Ok(event) if subscriptions.wants(&event) => Some(event),
+                Ok(_) => None,
+                Err(broadcast::error::RecvError::Lagged(n)) => Some(LiveEvent::Error {
+                    message: format!("Lagged by {} events", n),
+                }),
+                Err(broadcast::error::RecvError::Closed) => break,
+            },
+            _ = heartbeat.tick() => {
+                if last_seen.elapsed() > CLIENT_TIMEOUT {
+                    break;
+                }
+                Some(LiveEvent::Heartbeat {
+                    timestamp: chrono::Utc::now().timestamp(),
+                })
+            }
+        };
+
+        let Some(reply) = reply else {
+            continue;
+        };
+        let Ok(json) = serde_json::to_string(&reply) else {
+            continue;
+        };
+        if sender.send(Message::Text(json)).await.is_err() {
+            break;
+        }
+    }
+
+    hub.connections.fetch_sub(1, Ordering::SeqCst);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn new_string() -> LiveEvent {
+        LiveEvent::NewString(StringSummary {
+            hash: "0x01".to_string(),
+            creator: "0x02".to_string(),
+            string_type: "data".to_string(),
+            transactions: 3,
+            timestamp: 1_700_000_000,
+        })
+    }
+
+    #[test]
+    fn test_subscription_management() {
+        let mut subscriptions = Subscriptions::default();
+        assert!(!subscriptions.wants(&new_string()));
+
+        let reply = subscriptions.handle(ClientMessage::Subscribe {
+            topics: vec!["strings".to_string(), "price".to_string()],
+        });
+        match reply {
+            LiveEvent::Subscribed { topics } => assert_eq!(topics, vec!["price", "strings"]),
+            other => panic!("unexpected {:?}", other),
+        }
+        assert!(subscriptions.wants(&new_string()));
+        assert!(subscriptions.wants(&LiveEvent::PriceUpdate(PriceData::default())));
+
+        subscriptions.handle(ClientMessage::Unsubscribe {
+            topics: vec!["strings".to_string()],
+        });
+        assert!(!subscriptions.wants(&new_string()));
+
+        let reply = subscriptions.handle(ClientMessage::Subscribe {
+            topics: vec!["blocks".to_string()],
+        });
+        assert!(matches!(reply, LiveEvent::Error { .. }));
+
+        // Control frames are never forwarded from the hub
+        assert!(!subscriptions.wants(&LiveEvent::Heartbeat { timestamp: 0 }));
+        assert!(matches!(
+            subscriptions.handle(ClientMessage::Ping),
+            LiveEvent::Pong { .. }
+        ));
+    }
+
+    #[test]
+    fn test_wire_format() {
+        let json = serde_json::to_value(new_string()).unwrap();
+        assert_eq!(json["type"], "newString");
+        assert_eq!(json["stringType"], "data");
+
+        let message: ClientMessage =
+            serde_json::from_str(r#"{"type":"subscribe","topics":["validators"]}"#).unwrap();
+        assert_eq!(
+            message,
+            ClientMessage::Subscribe {
+                topics: vec!["validators".to_string()]
+            }
+        );
+        assert_eq!(
+            serde_json::from_str::<ClientMessage>(r#"{"type":"ping"}"#).unwrap(),
+            ClientMessage::Ping
+        );
+    }
+}
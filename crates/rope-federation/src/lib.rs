@@ -32,7 +32,9 @@ pub use community::{Community, CommunityConfig, CommunityType};
 pub use evolution::{FederationState, MembershipChange};
 pub use genesis::{FederationParams, GenesisConfig, GenesisValidator};
 pub use governance::{GovernanceState, Proposal, ProposalStatus, Vote, VoteDecision};
+pub use misbehavior::{MisbehaviorKind, MisbehaviorReport};
 pub use project::{ProjectCategory, ProjectStatus, ProjectSubmission};
+pub use treasury::{Grant, Payout, Treasury, TreasuryConfig, TreasuryError, VestingSchedule};
 
 // =============================================================================
 // Genesis Module - Federation Creation
@@ -118,6 +120,13 @@ pub mod evolution {
         UpdateParams {
             new_params: super::genesis::FederationParams,
         },
+        /// Grant from the treasury to an approved project
+        TreasurySpend {
+            project_id: [u8; 32],
+            recipient: [u8; 32],
+            amount: u128,
+            vesting: super::treasury::VestingSchedule,
+        },
     }
 
     /// Current federation state
@@ -137,6 +146,95 @@ pub mod evolution {
         pub fn is_validator(&self, node_id: &[u8; 32]) -> bool {
             self.validators.iter().any(|v| &v.node_id == node_id)
         }
+
+        pub fn stake_of(&self, node_id: &[u8; 32]) -> u64 {
+            self.validators
+                .iter()
+                .find(|v| &v.node_id == node_id)
+                .map(|v| v.stake)
+                .unwrap_or(0)
+        }
+
+        /// Build the membership change that penalises a misbehavior report
+        ///
+        /// Returns `None` if the offender is not a current validator. The
+        /// change still goes through governance before it is applied.
+        pub fn slashing_change(
+            &self,
+            report: &super::misbehavior::MisbehaviorReport,
+        ) -> Option<MembershipChange> {
+            if !self.is_validator(&report.offender) {
+                return None;
+            }
+
+            let stake = self.stake_of(&report.offender);
+            let penalty = stake * report.kind.slash_percent() / 100;
+            let new_stake = stake - penalty;
+
+            if new_stake == 0 {
+                Some(MembershipChange::RemoveValidator {
+                    node_id: report.offender,
+                    reason: format!("{} in round {}", report.kind.name(), report.round),
+                })
+            } else {
+                Some(MembershipChange::UpdateStake {
+                    node_id: report.offender,
+                    new_stake,
+                })
+            }
+        }
+    }
+}
+
+// =============================================================================
+// Misbehavior Module - Validator Fault Reports
+// =============================================================================
+
+pub mod misbehavior {
+    //! Validator misbehavior reports
+    //!
+    //! Consensus packages cryptographic evidence of faults (e.g. double
+    //! signing) into reports; the federation turns them into stake penalties.
+
+    use super::*;
+
+    /// Category of provable misbehavior
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+    pub enum MisbehaviorKind {
+        /// Two distinct gossip events from the same creator for the same round
+        EquivocatingEvents,
+        /// Two conflicting testimonies from the same validator for the same round
+        EquivocatingTestimonies,
+    }
+
+    impl MisbehaviorKind {
+        /// Percentage of stake slashed
+        pub fn slash_percent(&self) -> u64 {
+            match self {
+                Self::EquivocatingEvents => 5,
+                Self::EquivocatingTestimonies => 5,
+            }
+        }
+
+        pub fn name(&self) -> &'static str {
+            match self {
+                Self::EquivocatingEvents => "Equivocating gossip events",
+                Self::EquivocatingTestimonies => "Equivocating testimonies",
+            }
+        }
+    }
+
+    /// Report of a validator fault with attached evidence
+    #[derive(Clone, Debug, Serialize, Deserialize)]
+    pub struct MisbehaviorReport {
+        pub offender: [u8; 32],
+        pub kind: MisbehaviorKind,
+        pub round: u64,
+        /// Hash committing to the evidence payload
+        pub evidence_hash: [u8; 32],
+        /// Serialized evidence, verifiable by any node
+        pub evidence: Vec<u8>,
+        pub reported_at: i64,
     }
 }
 
@@ -238,6 +336,35 @@ pub mod governance {
 
             (yes, no, abstain)
         }
+
+        /// Close voting once the deadline has passed. A proposal passes
+        /// when yes stake reaches `quorum_stake` and outweighs no stake.
+        /// Returns `None` while voting is still open.
+        pub fn conclude(
+            &mut self,
+            proposal_id: &[u8; 32],
+            quorum_stake: u64,
+            now: u64,
+        ) -> Option<ProposalStatus> {
+            let (yes, no, _) = self.tally_votes(proposal_id);
+            let proposal = self.proposals.get_mut(proposal_id)?;
+            if !matches!(
+                proposal.status,
+                ProposalStatus::Pending | ProposalStatus::Active
+            ) {
+                return Some(proposal.status.clone());
+            }
+            if now < proposal.voting_deadline {
+                return None;
+            }
+
+            proposal.status = if yes >= quorum_stake && yes > no {
+                ProposalStatus::Passed
+            } else {
+                ProposalStatus::Rejected
+            };
+            Some(proposal.status.clone())
+        }
     }
 
     impl Default for GovernanceState {
@@ -866,6 +993,320 @@ pub mod project {
     }
 }
 
+// =============================================================================
+// Treasury Module - Grants
+// =============================================================================
+
+pub mod treasury {
+    //! Treasury and grants
+    //!
+    //! The treasury takes a configurable share of every anchor's emission
+    //! reward and of the unburned fees. Spending needs a governance proposal
+    //! carrying a [`MembershipChange::TreasurySpend`](super::evolution::MembershipChange)
+    //! for an approved project submission. Once the proposal passes and is
+    //! executed, the grant is set aside and paid out on its vesting schedule.
+
+    use super::governance::{GovernanceState, Proposal, ProposalStatus};
+    use super::project::{ProjectStatus, ProjectSubmission};
+    use super::*;
+    use rope_economics::ONE_FAT;
+    use thiserror::Error;
+
+    /// Seconds in a vesting month
+    const MONTH_SECONDS: u64 = 30 * 24 * 3600;
+
+    /// Treasury parameters
+    #[derive(Clone, Debug, Serialize, Deserialize)]
+    pub struct TreasuryConfig {
+        /// Share of anchor emission rewards (basis points)
+        pub emission_share_bps: u16,
+        /// Share of unburned fees (basis points)
+        pub fee_share_bps: u16,
+        /// Voting period of spend proposals
+        pub voting_period_seconds: u64,
+    }
+
+    impl Default for TreasuryConfig {
+        fn default() -> Self {
+            Self {
+                emission_share_bps: 500, // 5%
+                fee_share_bps: 1_000,    // 10%
+                voting_period_seconds: 7 * 24 * 3600,
+            }
+        }
+    }
+
+    /// Grant vesting: nothing before the cliff, then linear release in
+    /// whole intervals until the end of the duration
+    #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+    pub struct VestingSchedule {
+        pub cliff_seconds: u64,
+        pub duration_seconds: u64,
+        /// Release granularity; 0 releases continuously
+        pub interval_seconds: u64,
+    }
+
+    impl VestingSchedule {
+        /// Pay everything at once
+        pub fn immediate() -> Self {
+            Self {
+                cliff_seconds: 0,
+                duration_seconds: 0,
+                interval_seconds: 0,
+            }
+        }
+
+        /// Equal monthly releases over `months`
+        pub fn monthly(months: u64) -> Self {
+            Self {
+                cliff_seconds: 0,
+                duration_seconds: months * MONTH_SECONDS,
+                interval_seconds: MONTH_SECONDS,
+            }
+        }
+
+        /// Amount of `total` vested at `now` for a grant started at `start`
+        pub fn vested(&self, total: u128, start: u64, now: u64) -> u128 {
+            let elapsed = now.saturating_sub(start);
+            if elapsed < self.cliff_seconds {
+                return 0;
+            }
+            if elapsed >= self.duration_seconds {
+                return total;
+            }
+            let elapsed = match self.interval_seconds {
+                0 => elapsed,
+                interval => elapsed / interval * interval,
+            };
+            total * elapsed as u128 / self.duration_seconds as u128
+        }
+    }
+
+    /// Funded grant
+    #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+    pub struct Grant {
+        /// Id of the proposal that approved it
+        pub id: [u8; 32],
+        pub project_id: [u8; 32],
+        pub recipient: [u8; 32],
+        pub amount: u128,
+        pub paid: u128,
+        pub start: u64,
+        pub vesting: VestingSchedule,
+    }
+
+    impl Grant {
+        /// Amount not yet paid
+        pub fn remaining(&self) -> u128 {
+            self.amount - self.paid
+        }
+    }
+
+    /// Vested amount paid from a grant
+    #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+    pub struct Payout {
+        pub grant_id: [u8; 32],
+        pub project_id: [u8; 32],
+        pub recipient: [u8; 32],
+        pub amount: u128,
+        pub timestamp: u64,
+    }
+
+    /// Treasury errors
+    #[derive(Clone, Debug, PartialEq, Eq, Error)]
+    pub enum TreasuryError {
+        #[error("Project is not approved")]
+        ProjectNotApproved,
+
+        #[error("Grant of {requested} exceeds the {available} available")]
+        InsufficientFunds { requested: u128, available: u128 },
+
+        #[error("Proposal not found")]
+        ProposalNotFound,
+
+        #[error("Proposal has not passed")]
+        ProposalNotPassed,
+
+        #[error("Proposal is not a treasury spend")]
+        NotTreasurySpend,
+    }
+
+    /// Treasury state
+    #[derive(Clone, Debug, Default)]
+    pub struct Treasury {
+        pub config: TreasuryConfig,
+        /// Held funds, including funds committed to grants
+        balance: u128,
+        /// Granted but not yet paid
+        committed: u128,
+        pub total_received: u128,
+        pub total_paid: u128,
+        grants: HashMap<[u8; 32], Grant>,
+    }
+
+    impl Treasury {
+        pub fn new(config: TreasuryConfig) -> Self {
+            Self {
+                config,
+                ..Default::default()
+            }
+        }
+
+        /// Held funds
+        pub fn balance(&self) -> u128 {
+            self.balance
+        }
+
+        /// Funds not committed to grants
+        pub fn available(&self) -> u128 {
+            self.balance - self.committed
+        }
+
+        /// Take the treasury share of an anchor's emission reward and
+        /// unburned fees, returning the amount taken
+        pub fn accrue(&mut self, emission: u128, fees: u128) -> u128 {
+            let share = emission * self.config.emission_share_bps as u128 / 10_000
+                + fees * self.config.fee_share_bps as u128 / 10_000;
+            self.balance += share;
+            self.total_received += share;
+            share
+        }
+
+        /// Governance proposal funding an approved project with its
+        /// requested amount
+        pub fn propose_spend(
+            &self,
+            project: &ProjectSubmission,
+            proposer: [u8; 32],
+            vesting: VestingSchedule,
+            now: u64,
+        ) -> Result<Proposal, TreasuryError> {
+            if !matches!(
+                project.status,
+                ProjectStatus::Approved | ProjectStatus::Building
+            ) {
+                return Err(TreasuryError::ProjectNotApproved);
+            }
+            let amount = project.funding_requested as u128 * ONE_FAT;
+            if amount > self.available() {
+                return Err(TreasuryError::InsufficientFunds {
+                    requested: amount,
+                    available: self.available(),
+                });
+            }
+
+            let mut hasher = blake3::Hasher::new();
+            hasher.update(b"treasury-spend:");
+            hasher.update(&project.id);
+            hasher.update(&now.to_le_bytes());
+
+            Ok(Proposal {
+                id: *hasher.finalize().as_bytes(),
+                proposer,
+                title: format!("Fund {}", project.name),
+                description: project.description.clone(),
+                change: super::evolution::MembershipChange::TreasurySpend {
+                    project_id: project.id,
+                    recipient: project.submitter_id,
+                    amount,
+                    vesting,
+                },
+                created_at: now,
+                voting_deadline: now + self.config.voting_period_seconds,
+                status: ProposalStatus::Active,
+            })
+        }
+
+        /// Fund the grant of a passed spend proposal, marking it executed.
+        /// Vesting starts at `now`.
+        pub fn execute(
+            &mut self,
+            governance: &mut GovernanceState,
+            proposal_id: &[u8; 32],
+            now: u64,
+        ) -> Result<&Grant, TreasuryError> {
+            let proposal = governance
+                .proposals
+                .get_mut(proposal_id)
+                .ok_or(TreasuryError::ProposalNotFound)?;
+            if proposal.status != ProposalStatus::Passed {
+                return Err(TreasuryError::ProposalNotPassed);
+            }
+            let super::evolution::MembershipChange::TreasurySpend {
+                project_id,
+                recipient,
+                amount,
+                vesting,
+            } = proposal.change.clone()
+            else {
+                return Err(TreasuryError::NotTreasurySpend);
+            };
+
+            // Other grants may have been funded since the proposal was made
+            if amount > self.available() {
+                return Err(TreasuryError::InsufficientFunds {
+                    requested: amount,
+                    available: self.available(),
+                });
+            }
+
+            proposal.status = ProposalStatus::Executed;
+            self.committed += amount;
+            let grant = Grant {
+                id: *proposal_id,
+                project_id,
+                recipient,
+                amount,
+                paid: 0,
+                start: now,
+                vesting,
+            };
+            Ok(self.grants.entry(*proposal_id).or_insert(grant))
+        }
+
+        /// Get a grant
+        pub fn grant(&self, id: &[u8; 32]) -> Option<&Grant> {
+            self.grants.get(id)
+        }
+
+        /// Grants funding a project
+        pub fn grants_for(&self, project_id: &[u8; 32]) -> Vec<&Grant> {
+            self.grants
+                .values()
+                .filter(|g| &g.project_id == project_id)
+                .collect()
+        }
+
+        /// Pay everything vested by `now`
+        pub fn process_payouts(&mut self, now: u64) -> Vec<Payout> {
+            let mut payouts = Vec::new();
+            for grant in self.grants.values_mut() {
+                let due = grant
+                    .vesting
+                    .vested(grant.amount, grant.start, now)
+                    .saturating_sub(grant.paid);
+                if due == 0 {
+                    continue;
+                }
+                grant.paid += due;
+                payouts.push(Payout {
+                    grant_id: grant.id,
+                    project_id: grant.project_id,
+                    recipient: grant.recipient,
+                    amount: due,
+                    timestamp: now,
+                });
+            }
+
+            let total: u128 = payouts.iter().map(|p| p.amount).sum();
+            self.balance -= total;
+            self.committed -= total;
+            self.total_paid += total;
+            payouts
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -938,4 +1379,152 @@ mod tests {
         assert!(approved);
         assert_eq!(project.status, project::ProjectStatus::Approved);
     }
+
+    #[test]
+    fn test_slashing_change_from_misbehavior() {
+        let validator = genesis::GenesisValidator {
+            node_id: [3u8; 32],
+            public_key: Vec::new(),
+            name: "v3".to_string(),
+            stake: 1_000,
+        };
+        let state = evolution::FederationState {
+            epoch: 1,
+            validators: vec![validator],
+            total_stake: 1_000,
+            params: genesis::FederationParams::default(),
+        };
+
+        let report = misbehavior::MisbehaviorReport {
+            offender: [3u8; 32],
+            kind: misbehavior::MisbehaviorKind::EquivocatingEvents,
+            round: 9,
+            evidence_hash: [0u8; 32],
+            evidence: Vec::new(),
+            reported_at: 0,
+        };
+
+        match state.slashing_change(&report) {
+            Some(evolution::MembershipChange::UpdateStake { node_id, new_stake }) => {
+                assert_eq!(node_id, [3u8; 32]);
+                assert_eq!(new_stake, 950);
+            }
+            other => panic!("unexpected change: {:?}", other),
+        }
+
+        let unknown = misbehavior::MisbehaviorReport {
+            offender: [9u8; 32],
+            ..report
+        };
+        assert!(state.slashing_change(&unknown).is_none());
+    }
+
+    #[test]
+    fn test_vesting_schedule() {
+        let vesting = treasury::VestingSchedule::monthly(4);
+        let month = 30 * 24 * 3600;
+
+        assert_eq!(vesting.vested(400, 100, 100), 0);
+        assert_eq!(vesting.vested(400, 100, 100 + month - 1), 0);
+        assert_eq!(vesting.vested(400, 100, 100 + month), 100);
+        assert_eq!(vesting.vested(400, 100, 100 + 3 * month + 5), 300);
+        assert_eq!(vesting.vested(400, 100, 100 + 10 * month), 400);
+
+        let cliff = treasury::VestingSchedule {
+            cliff_seconds: 50,
+            duration_seconds: 100,
+            interval_seconds: 0,
+        };
+        assert_eq!(cliff.vested(100, 0, 49), 0);
+        assert_eq!(cliff.vested(100, 0, 50), 50);
+        assert_eq!(treasury::VestingSchedule::immediate().vested(7, 10, 10), 7);
+    }
+
+    #[test]
+    fn test_treasury_grant_lifecycle() {
+        let one_fat = rope_economics::ONE_FAT;
+        let mut treasury = treasury::Treasury::default();
+
+        // 5% of emission, 10% of fees
+        let taken = treasury.accrue(1_000 * one_fat, 100 * one_fat);
+        assert_eq!(taken, 60 * one_fat);
+        treasury.accrue(1_000 * one_fat, 100 * one_fat);
+        assert_eq!(treasury.balance(), 120 * one_fat);
+
+        let mut project = project::ProjectSubmission::new(
+            "Grantee".to_string(),
+            "Needs funding".to_string(),
+            project::ProjectCategory::Infrastructure,
+            [4u8; 32],
+            project::OrganizationType::Individual,
+        );
+        project.funding_requested = 100;
+
+        // Only approved projects can be funded
+        assert_eq!(
+            treasury
+                .propose_spend(
+                    &project,
+                    [1u8; 32],
+                    treasury::VestingSchedule::immediate(),
+                    0
+                )
+                .unwrap_err(),
+            treasury::TreasuryError::ProjectNotApproved
+        );
+        project.status = project::ProjectStatus::Approved;
+
+        let vesting = treasury::VestingSchedule::monthly(2);
+        let proposal = treasury
+            .propose_spend(&project, [1u8; 32], vesting, 1_000)
+            .unwrap();
+        let proposal_id = proposal.id;
+        let deadline = proposal.voting_deadline;
+        let mut governance = governance::GovernanceState::new();
+        governance.add_proposal(proposal);
+        governance.add_vote(governance::Vote {
+            proposal_id,
+            voter_id: [1u8; 32],
+            decision: governance::VoteDecision::Yes,
+            stake: 700,
+            timestamp: 1_001,
+        });
+
+        // Can't spend before the vote passes
+        assert_eq!(
+            treasury
+                .execute(&mut governance, &proposal_id, 1_002)
+                .unwrap_err(),
+            treasury::TreasuryError::ProposalNotPassed
+        );
+        assert_eq!(governance.conclude(&proposal_id, 667, 1_002), None);
+        assert_eq!(
+            governance.conclude(&proposal_id, 667, deadline),
+            Some(governance::ProposalStatus::Passed)
+        );
+
+        let grant = treasury
+            .execute(&mut governance, &proposal_id, deadline)
+            .unwrap()
+            .clone();
+        assert_eq!(grant.amount, 100 * one_fat);
+        assert_eq!(grant.recipient, [4u8; 32]);
+        assert_eq!(treasury.available(), 20 * one_fat);
+        assert_eq!(
+            governance.proposals[&proposal_id].status,
+            governance::ProposalStatus::Executed
+        );
+
+        // Monthly payouts
+        let month = 30 * 24 * 3600;
+        assert!(treasury.process_payouts(deadline + 10).is_empty());
+        let payouts = treasury.process_payouts(deadline + month);
+        assert_eq!(payouts.len(), 1);
+        assert_eq!(payouts[0].amount, 50 * one_fat);
+        treasury.process_payouts(deadline + 5 * month);
+        assert_eq!(treasury.grant(&proposal_id).unwrap().remaining(), 0);
+        assert_eq!(treasury.total_paid, 100 * one_fat);
+        assert_eq!(treasury.balance(), 20 * one_fat);
+        assert_eq!(treasury.available(), 20 * one_fat);
+    }
 }
@@ -0,0 +1,8 @@
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    tonic_build::configure()
+        .build_server(true)
+        .build_client(true)
+        .compile(&["proto/loadtest.proto"], &["proto"])?;
+    println!("cargo:rerun-if-changed=proto/loadtest.proto");
+    Ok(())
+}
@@ -0,0 +1,588 @@
+//! # Distributed Load Generation
+//!
+//! One machine cannot drive 10,000 TPS, so a run can be spread over
+//! several worker processes (`proto/loadtest.proto`):
+//!
+//! ```text
+//!                    ┌──────────────┐
+//!                    │ Coordinator  │  shards RPS, merges histograms
+//!                    └──────┬───────┘
+//!          RunShard (gRPC)  │  ◄── WorkerReport stream
+//!        ┌──────────────────┼──────────────────┐
+//!   ┌────▼────┐        ┌────▼────┐        ┌────▼────┐
+//!   │ worker  │        │ worker  │        │ worker  │ ──► target
+//!   └─────────┘        └─────────┘        └─────────┘
+//! ```
+//!
+//! Each worker gets an even share of the target RPS and concurrency, and
+//! all workers start at the same wall-clock instant. Workers stream
+//! snapshots every few seconds and a final report; since reports carry the
+//! full latency histogram, the merged percentiles are exact rather than
+//! averages of per-worker percentiles.
+
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use hdrhistogram::serialization::{Deserializer, Serializer, V2Serializer};
+use hdrhistogram::Histogram;
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+use tokio_stream::{Stream, StreamExt};
+use tonic::transport::Server;
+use tonic::{Request, Response, Status};
+use tracing::{info, warn};
+
+use crate::{LoadTestConfig, LoadTestRunner, MetricsSnapshot, MetricsSummary, ScenarioSnapshot};
+
+/// Generated protobuf types and gRPC stubs
+pub mod proto {
+    tonic::include_proto!("rope.loadtest.v1");
+}
+
+use proto::load_worker_client::LoadWorkerClient;
+use proto::load_worker_server::{LoadWorker, LoadWorkerServer};
+
+/// Default seconds between worker progress snapshots
+pub const DEFAULT_REPORT_INTERVAL_SECS: u32 = 5;
+
+// ============================================================================
+// SNAPSHOT ENCODING
+// ============================================================================
+
+/// Distributed run error
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DistributedError {
+    NoWorkers,
+    Connect { worker: String, reason: String },
+    Histogram(String),
+    Config(String),
+}
+
+impl std::fmt::Display for DistributedError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DistributedError::NoWorkers => write!(f, "No workers configured"),
+            DistributedError::Connect { worker, reason } => {
+                write!(f, "Cannot reach worker {}: {}", worker, reason)
+            }
+            DistributedError::Histogram(e) => write!(f, "Histogram encoding failed: {}", e),
+            DistributedError::Config(e) => write!(f, "Invalid shard config: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for DistributedError {}
+
+/// Encode a snapshot as a worker report
+pub fn encode_report(
+    worker_id: &str,
+    snapshot: &MetricsSnapshot,
+    is_final: bool,
+) -> Result<proto::WorkerReport, DistributedError> {
+    let mut scenarios = HashMap::with_capacity(snapshot.scenarios.len());
+    for (name, stats) in &snapshot.scenarios {
+        scenarios.insert(
+            name.clone(),
+            proto::ScenarioReport {
+                total_requests: stats.total_requests,
+                failed_requests: stats.failed_requests,
+                latency_histogram: encode_histogram(&stats.latency_histogram)?,
+            },
+        );
+    }
+
+    Ok(proto::WorkerReport {
+        worker_id: worker_id.to_string(),
+        r#final: is_final,
+        total_requests: snapshot.total_requests,
+        successful_requests: snapshot.successful_requests,
+        failed_requests: snapshot.failed_requests,
+        bytes_sent: snapshot.bytes_sent,
+        bytes_received: snapshot.bytes_received,
+        elapsed_secs: snapshot.duration_secs,
+        latency_histogram: encode_histogram(&snapshot.latency_histogram)?,
+        error_counts: snapshot.error_counts.clone(),
+        error: String::new(),
+        scenarios,
+    })
+}
+
+/// Decode a worker report back into a snapshot
+pub fn decode_report(report: &proto::WorkerReport) -> Result<MetricsSnapshot, DistributedError> {
+    let mut scenarios = HashMap::with_capacity(report.scenarios.len());
+    for (name, stats) in &report.scenarios {
+        scenarios.insert(
+            name.clone(),
+            ScenarioSnapshot {
+                total_requests: stats.total_requests,
+                failed_requests: stats.failed_requests,
+                latency_histogram: decode_histogram(&stats.latency_histogram)?,
+            },
+        );
+    }
+
+    Ok(MetricsSnapshot {
+        total_requests: report.total_requests,
+        successful_requests: report.successful_requests,
+        failed_requests: report.failed_requests,
+        bytes_sent: report.bytes_sent,
+        bytes_received: report.bytes_received,
+        latency_histogram: decode_histogram(&report.latency_histogram)?,
+        error_counts: report.error_counts.clone(),
+        scenarios,
+        duration_secs: report.elapsed_secs,
+    })
+}
+
+fn encode_histogram(histogram: &Histogram<u64>) -> Result<Vec<u8>, DistributedError> {
+    let mut bytes = Vec::new();
+    V2Serializer::new()
+        .serialize(histogram, &mut bytes)
+        .map_err(|e| DistributedError::Histogram(format!("{:?}", e)))?;
+    Ok(bytes)
+}
+
+fn decode_histogram(bytes: &[u8]) -> Result<Histogram<u64>, DistributedError> {
+    Deserializer::new()
+        .deserialize(&mut &bytes[..])
+        .map_err(|e| DistributedError::Histogram(format!("{:?}", e)))
+}
+
+// ============================================================================
+// SHARDING
+// ============================================================================
+
+/// Split `config` into one config per worker
+///
+/// RPS is divided evenly with the remainder going to the first workers, so
+/// the shards always add up to the requested total.
+pub fn shard_config(config: &LoadTestConfig, workers: usize) -> Vec<LoadTestConfig> {
+    let workers = workers.max(1);
+    let base_rps = config.target_rps / workers as u64;
+    let extra_rps = (config.target_rps % workers as u64) as usize;
+    let concurrency = config.max_concurrency.div_ceil(workers).max(1);
+
+    (0..workers)
+        .map(|i| LoadTestConfig {
+            target_rps: base_rps + u64::from(i < extra_rps),
+            max_concurrency: concurrency,
+            // Only the coordinator exports metrics
+            metrics_port: None,
+            ..config.clone()
+        })
+        .collect()
+}
+
+// ============================================================================
+// WORKER
+// ============================================================================
+
+/// gRPC worker running one shard at a time
+pub struct WorkerService {
+    worker_id: String,
+    busy: Arc<AtomicBool>,
+}
+
+impl WorkerService {
+    pub fn new(worker_id: &str) -> Self {
+        Self {
+            worker_id: worker_id.to_string(),
+            busy: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// Serve on `addr` until the process exits
+    pub async fn serve(self, addr: SocketAddr) -> Result<(), tonic::transport::Error> {
+        info!("Load test worker {} listening on {}", self.worker_id, addr);
+        Server::builder()
+            .add_service(LoadWorkerServer::new(self))
+            .serve(addr)
+            .await
+    }
+}
+
+type ReportStream = Pin<Box<dyn Stream<Item = Result<proto::WorkerReport, Status>> + Send>>;
+
+#[tonic::async_trait]
+impl LoadWorker for WorkerService {
+    type RunShardStream = ReportStream;
+
+    async fn run_shard(
+        &self,
+        request: Request<proto::ShardRequest>,
+    ) -> Result<Response<Self::RunShardStream>, Status> {
+        let shard = request.into_inner();
+        let config: LoadTestConfig = serde_json::from_str(&shard.config_json)
+            .map_err(|e| Status::invalid_argument(format!("config_json: {}", e)))?;
+
+        let mut runner = LoadTestRunner::new(config.clone());
+        if shard.scenario_yaml.is_empty() {
+            runner.add_default_scenarios();
+            if config.write_ratio > 0.0 {
+                runner.add_write_scenarios();
+            }
+        } else {
+            runner
+                .add_scenario_yaml(&shard.scenario_yaml)
+                .map_err(|e| Status::invalid_argument(e.to_string()))?;
+        }
+
+        if self.busy.swap(true, Ordering::SeqCst) {
+            return Err(Status::failed_precondition(
+                "Worker is already running a shard",
+            ));
+        }
+
+        let worker_id = if shard.worker_id.is_empty() {
+            self.worker_id.clone()
+        } else {
+            shard.worker_id
+        };
+        let interval = Duration::from_secs(u64::from(shard.report_interval_secs).max(1));
+        let (tx, rx) = mpsc::channel(16);
+        let busy = self.busy.clone();
+
+        tokio::spawn(async move {
+            let start_at = UNIX_EPOCH + Duration::from_millis(shard.start_at_unix_ms);
+            if let Ok(wait) = start_at.duration_since(SystemTime::now()) {
+                tokio::time::sleep(wait).await;
+            }
+            info!(
+                "Worker {} starting shard: {} RPS for {}s",
+                worker_id, config.target_rps, config.duration_secs
+            );
+
+            let runner = Arc::new(runner);
+            let metrics = runner.metrics();
+            let mut run = tokio::spawn({
+                let runner = runner.clone();
+                async move { runner.run().await }
+            });
+
+            let mut ticker = tokio::time::interval(interval);
+            ticker.tick().await;
+            loop {
+                tokio::select! {
+                    _ = &mut run => break,
+                    _ = ticker.tick() => {
+                        let report = encode_report(&worker_id, &metrics.snapshot(), false)
+                            .map_err(|e| Status::internal(e.to_string()));
+                        if tx.send(report).await.is_err() {
+                            // Coordinator went away; let the shard finish quietly
+                            warn!("Worker {}: coordinator disconnected", worker_id);
+                        }
+                    }
+                }
+            }
+
+            let report = encode_report(&worker_id, &metrics.snapshot(), true)
+                .map_err(|e| Status::internal(e.to_string()));
+            let _ = tx.send(report).await;
+            busy.store(false, Ordering::SeqCst);
+        });
+
+        Ok(Response::new(
+            Box::pin(ReceiverStream::new(rx)) as ReportStream
+        ))
+    }
+}
+
+// ============================================================================
+// COORDINATOR
+// ============================================================================
+
+/// Per-worker outcome of a distributed run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkerResult {
+    pub worker: String,
+    pub target_rps: u64,
+    pub summary: Option<MetricsSummary>,
+    pub error: Option<String>,
+}
+
+/// Merged result of a distributed run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DistributedSummary {
+    pub merged: MetricsSummary,
+    pub workers: Vec<WorkerResult>,
+}
+
+impl DistributedSummary {
+    /// Print the merged report followed by a per-worker breakdown
+    pub fn print_report(&self) {
+        self.merged.print_report();
+
+        println!("═══════════════════════════════════════════════════════════════");
+        println!("                    PER-WORKER BREAKDOWN");
+        println!("═══════════════════════════════════════════════════════════════");
+        println!(
+            "{:<24} {:>8} {:>10} {:>10} {:>12}",
+            "Worker", "Target", "Avg RPS", "Success%", "p99 (µs)"
+        );
+        println!("───────────────────────────────────────────────────────────────");
+        for worker in &self.workers {
+            match (&worker.summary, &worker.error) {
+                (Some(summary), _) => println!(
+                    "{:<24} {:>8} {:>10.2} {:>10.2} {:>12}",
+                    worker.worker,
+                    worker.target_rps,
+                    summary.avg_rps,
+                    summary.success_rate,
+                    summary.latency_p99_us
+                ),
+                (None, error) => println!(
+                    "{:<24} {:>8} FAILED: {}",
+                    worker.worker,
+                    worker.target_rps,
+                    error.as_deref().unwrap_or("no report")
+                ),
+            }
+        }
+        println!("═══════════════════════════════════════════════════════════════\n");
+    }
+}
+
+/// Shards a load test across workers and merges their reports
+pub struct Coordinator {
+    workers: Vec<String>,
+    config: LoadTestConfig,
+    scenario_yaml: Option<String>,
+    start_delay: Duration,
+    report_interval_secs: u32,
+}
+
+impl Coordinator {
+    /// Coordinator for worker endpoints such as `http://10.0.0.5:7070`
+    pub fn new(workers: Vec<String>, config: LoadTestConfig) -> Self {
+        Self {
+            workers,
+            config,
+            scenario_yaml: None,
+            start_delay: Duration::from_secs(3),
+            report_interval_secs: DEFAULT_REPORT_INTERVAL_SECS,
+        }
+    }
+
+    /// Run these YAML scenarios on every worker
+    pub fn with_scenario_yaml(mut self, yaml: String) -> Self {
+        self.scenario_yaml = Some(yaml);
+        self
+    }
+
+    /// Time between dispatch and the synchronized start
+    pub fn with_start_delay(mut self, delay: Duration) -> Self {
+        self.start_delay = delay;
+        self
+    }
+
+    pub fn with_report_interval(mut self, secs: u32) -> Self {
+        self.report_interval_secs = secs.max(1);
+        self
+    }
+
+    /// Run the test on every worker and merge the results
+    pub async fn run(&self) -> Result<DistributedSummary, DistributedError> {
+        if self.workers.is_empty() {
+            return Err(DistributedError::NoWorkers);
+        }
+
+        // Connect to everyone before starting anyone
+        let mut clients = Vec::with_capacity(self.workers.len());
+        for worker in &self.workers {
+            let client = LoadWorkerClient::connect(worker.clone())
+                .await
+                .map_err(|e| DistributedError::Connect {
+                    worker: worker.clone(),
+                    reason: e.to_string(),
+                })?;
+            clients.push(client);
+        }
+
+        let shards = shard_config(&self.config, self.workers.len());
+        let start_at = SystemTime::now() + self.start_delay;
+        let start_at_unix_ms = start_at
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis() as u64;
+        info!(
+            "Dispatching {} RPS across {} workers",
+            self.config.target_rps,
+            self.workers.len()
+        );
+
+        let latest: Arc<parking_lot::RwLock<HashMap<String, MetricsSnapshot>>> =
+            Arc::new(parking_lot::RwLock::new(HashMap::new()));
+        let mut runs = Vec::with_capacity(clients.len());
+        for ((worker, mut client), shard) in self.workers.iter().zip(clients).zip(shards) {
+            let request = proto::ShardRequest {
+                worker_id: worker.clone(),
+                config_json: serde_json::to_string(&shard)
+                    .map_err(|e| DistributedError::Config(e.to_string()))?,
+                scenario_yaml: self.scenario_yaml.clone().unwrap_or_default(),
+                start_at_unix_ms,
+                report_interval_secs: self.report_interval_secs,
+            };
+            let worker = worker.clone();
+            let target_rps = shard.target_rps;
+            let latest = latest.clone();
+
+            runs.push(tokio::spawn(async move {
+                let result = Self::follow(&mut client, request, &worker, &latest).await;
+                (worker, target_rps, result)
+            }));
+        }
+
+        let mut merged: Option<MetricsSnapshot> = None;
+        let mut workers = Vec::with_capacity(runs.len());
+        for run in runs {
+            let (worker, target_rps, result) = match run.await {
+                Ok(outcome) => outcome,
+                Err(e) => {
+                    warn!("Worker task panicked: {}", e);
+                    continue;
+                }
+            };
+            match result {
+                Ok(snapshot) => {
+                    workers.push(WorkerResult {
+                        worker,
+                        target_rps,
+                        summary: Some(snapshot.summary()),
+                        error: None,
+                    });
+                    match &mut merged {
+                        Some(merged) => merged.merge(&snapshot),
+                        None => merged = Some(snapshot),
+                    }
+                }
+                Err(error) => {
+                    warn!("Worker {} failed: {}", worker, error);
+                    workers.push(WorkerResult {
+                        worker,
+                        target_rps,
+                        summary: None,
+                        error: Some(error),
+                    });
+                }
+            }
+        }
+
+        let merged = match merged {
+            Some(merged) => merged.summary(),
+            None => crate::LoadTestMetrics::new().snapshot().summary(),
+        };
+        Ok(DistributedSummary { merged, workers })
+    }
+
+    /// Stream one worker's reports, returning its final snapshot
+    async fn follow(
+        client: &mut LoadWorkerClient<tonic::transport::Channel>,
+        request: proto::ShardRequest,
+        worker: &str,
+        latest: &parking_lot::RwLock<HashMap<String, MetricsSnapshot>>,
+    ) -> Result<MetricsSnapshot, String> {
+        let mut stream = client
+            .run_shard(request)
+            .await
+            .map_err(|e| e.message().to_string())?
+            .into_inner();
+
+        while let Some(report) = stream.next().await {
+            let report = report.map_err(|e| e.message().to_string())?;
+            if !report.error.is_empty() {
+                return Err(report.error);
+            }
+            let snapshot = decode_report(&report).map_err(|e| e.to_string())?;
+            if report.r#final {
+                return Ok(snapshot);
+            }
+
+            // Live aggregate across every worker heard from so far
+            let mut latest = latest.write();
+            latest.insert(worker.to_string(), snapshot);
+            let mut live: Option<MetricsSnapshot> = None;
+            for snapshot in latest.values() {
+                match &mut live {
+                    Some(live) => live.merge(snapshot),
+                    None => live = Some(snapshot.clone()),
+                }
+            }
+            if let Some(live) = live {
+                let summary = live.summary();
+                info!(
+                    "Progress ({} workers): {} requests, {:.2}% success, {:.0} RPS, p99 {}µs",
+                    latest.len(),
+                    summary.total_requests,
+                    summary.success_rate,
+                    summary.avg_rps,
+                    summary.latency_p99_us
+                );
+            }
+        }
+
+        Err("stream ended without a final report".to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::LoadTestMetrics;
+
+    #[test]
+    fn test_shard_config() {
+        let config = LoadTestConfig {
+            target_rps: 10_001,
+            max_concurrency: 50,
+            ..Default::default()
+        };
+        let shards = shard_config(&config, 4);
+
+        assert_eq!(shards.len(), 4);
+        assert_eq!(shards.iter().map(|s| s.target_rps).sum::<u64>(), 10_001);
+        assert_eq!(shards[0].target_rps, 2_501);
+        assert_eq!(shards[3].target_rps, 2_500);
+        assert!(shards.iter().all(|s| s.max_concurrency == 13));
+        assert!(shards.iter().all(|s| s.metrics_port.is_none()));
+    }
+
+    #[test]
+    fn test_report_roundtrip_and_merge() {
+        let a = LoadTestMetrics::new();
+        for latency in [100, 200, 300] {
+            a.record_success(latency, 10, 100);
+            a.record_scenario("health_check", latency, true);
+        }
+        a.record_failure("timeout", 5_000);
+
+        let b = LoadTestMetrics::new();
+        for latency in [10_000, 20_000] {
+            b.record_success(latency, 10, 100);
+        }
+
+        let report = encode_report("worker-a", &a.snapshot(), true).unwrap();
+        assert!(report.r#final);
+        let decoded = decode_report(&report).unwrap();
+        assert_eq!(decoded.total_requests, 4);
+        assert_eq!(decoded.error_counts["timeout"], 1);
+        assert_eq!(decoded.scenarios["health_check"].total_requests, 3);
+        assert_eq!(
+            decoded.latency_histogram.len(),
+            a.snapshot().latency_histogram.len()
+        );
+
+        let mut merged = decoded;
+        merged.merge(&b.snapshot());
+        let summary = merged.summary();
+        assert_eq!(summary.total_requests, 6);
+        assert_eq!(summary.successful_requests, 5);
+        assert_eq!(summary.bytes_received, 500);
+        // The slowest sample comes from the second worker
+        assert!(summary.latency_max_us >= 19_000);
+    }
+}
@@ -0,0 +1,276 @@
+//! # Live Prometheus Export
+//!
+//! While a test runs, `GET /metrics` on `metrics_port` serves the
+//! load generator's own view of the traffic so it can be lined up with the
+//! server-side dashboards in Grafana:
+//!
+//! | Metric | Type | Labels |
+//! |--------|------|--------|
+//! | `rope_loadtest_in_flight_requests` | gauge | |
+//! | `rope_loadtest_requests_total` | counter | `scenario`, `outcome` |
+//! | `rope_loadtest_errors_total` | counter | `error` |
+//! | `rope_loadtest_request_duration_seconds` | histogram | `scenario` |
+//! | `rope_loadtest_rps` | gauge | |
+//! | `rope_loadtest_target_rps` | gauge | |
+//!
+//! `rope_loadtest_rps` is the completion rate over the last
+//! [`RPS_WINDOW_SECS`] seconds, sampled once a second by the runner.
+
+use std::collections::VecDeque;
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use parking_lot::Mutex;
+use prometheus::{
+    Encoder, Gauge, HistogramOpts, HistogramVec, IntCounterVec, IntGauge, Opts, Registry,
+    TextEncoder,
+};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+use tracing::{debug, info};
+
+use crate::LoadTestMetrics;
+
+/// Seconds covered by the rolling RPS gauge
+pub const RPS_WINDOW_SECS: usize = 10;
+
+/// Latency buckets in seconds, dense around the 100ms p99 budget
+const LATENCY_BUCKETS: &[f64] = &[
+    0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.075, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0,
+];
+
+/// Prometheus collectors updated as requests complete
+pub struct LiveMetrics {
+    registry: Registry,
+    in_flight: IntGauge,
+    requests: IntCounterVec,
+    errors: IntCounterVec,
+    latency: HistogramVec,
+    rolling_rps: Gauge,
+    target_rps: IntGauge,
+    /// Completed-request totals, one sample per second
+    window: Mutex<VecDeque<u64>>,
+}
+
+impl std::fmt::Debug for LiveMetrics {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LiveMetrics")
+            .field("in_flight", &self.in_flight.get())
+            .field("rolling_rps", &self.rolling_rps.get())
+            .finish()
+    }
+}
+
+impl Default for LiveMetrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LiveMetrics {
+    pub fn new() -> Self {
+        let in_flight = IntGauge::new(
+            "rope_loadtest_in_flight_requests",
+            "Requests sent and awaiting a response",
+        )
+        .unwrap();
+        let requests = IntCounterVec::new(
+            Opts::new("rope_loadtest_requests_total", "Completed requests"),
+            &["scenario", "outcome"],
+        )
+        .unwrap();
+        let errors = IntCounterVec::new(
+            Opts::new(
+                "rope_loadtest_errors_total",
+                "Failed requests by error type",
+            ),
+            &["error"],
+        )
+        .unwrap();
+        let latency = HistogramVec::new(
+            HistogramOpts::new(
+                "rope_loadtest_request_duration_seconds",
+                "Request latency seen by the load generator",
+            )
+            .buckets(LATENCY_BUCKETS.to_vec()),
+            &["scenario"],
+        )
+        .unwrap();
+        let rolling_rps = Gauge::new(
+            "rope_loadtest_rps",
+            "Completed requests per second over the rolling window",
+        )
+        .unwrap();
+        let target_rps = IntGauge::new(
+            "rope_loadtest_target_rps",
+            "Request rate the current phase is aiming for",
+        )
+        .unwrap();
+
+        let registry = Registry::new();
+        registry.register(Box::new(in_flight.clone())).unwrap();
+        registry.register(Box::new(requests.clone())).unwrap();
+        registry.register(Box::new(errors.clone())).unwrap();
+        registry.register(Box::new(latency.clone())).unwrap();
+        registry.register(Box::new(rolling_rps.clone())).unwrap();
+        registry.register(Box::new(target_rps.clone())).unwrap();
+
+        Self {
+            registry,
+            in_flight,
+            requests,
+            errors,
+            latency,
+            rolling_rps,
+            target_rps,
+            window: Mutex::new(VecDeque::with_capacity(RPS_WINDOW_SECS + 1)),
+        }
+    }
+
+    /// Mark a request as in flight until the guard drops
+    pub fn start_request(&self) -> InFlightGuard<'_> {
+        self.in_flight.inc();
+        InFlightGuard(&self.in_flight)
+    }
+
+    /// Record a completed request
+    pub fn observe(&self, scenario: &str, latency_us: u64, error: Option<&str>) {
+        let outcome = if error.is_none() {
+            "success"
+        } else {
+            "failure"
+        };
+        self.requests.with_label_values(&[scenario, outcome]).inc();
+        self.latency
+            .with_label_values(&[scenario])
+            .observe(latency_us as f64 / 1_000_000.0);
+        if let Some(error) = error {
+            self.errors.with_label_values(&[error]).inc();
+        }
+    }
+
+    pub fn set_target_rps(&self, rps: u64) {
+        self.target_rps.set(rps as i64);
+    }
+
+    pub fn in_flight(&self) -> i64 {
+        self.in_flight.get()
+    }
+
+    /// Add a once-a-second sample of the completed total and return the rolling rate
+    pub fn sample(&self, total_requests: u64) -> f64 {
+        let mut window = self.window.lock();
+        window.push_back(total_requests);
+        while window.len() > RPS_WINDOW_SECS + 1 {
+            window.pop_front();
+        }
+
+        let rps = match (window.front(), window.back()) {
+            (Some(first), Some(last)) if window.len() > 1 => {
+                last.saturating_sub(*first) as f64 / (window.len() - 1) as f64
+            }
+            _ => 0.0,
+        };
+        self.rolling_rps.set(rps);
+        rps
+    }
+
+    /// Render every collector in the Prometheus text format
+    pub fn render(&self) -> String {
+        let mut buffer = Vec::new();
+        TextEncoder::new()
+            .encode(&self.registry.gather(), &mut buffer)
+            .expect("text encoding of gathered metrics");
+        String::from_utf8_lossy(&buffer).into_owned()
+    }
+}
+
+/// Decrements the in-flight gauge on drop
+pub struct InFlightGuard<'a>(&'a IntGauge);
+
+impl Drop for InFlightGuard<'_> {
+    fn drop(&mut self) {
+        self.0.dec();
+    }
+}
+
+/// Serve `/metrics` for `metrics` until the task is aborted
+pub async fn serve_metrics(addr: SocketAddr, metrics: Arc<LoadTestMetrics>) -> std::io::Result<()> {
+    let listener = TcpListener::bind(addr).await?;
+    info!("Serving load test metrics on http://{}/metrics", addr);
+
+    loop {
+        let (mut stream, peer) = listener.accept().await?;
+        let metrics = metrics.clone();
+
+        tokio::spawn(async move {
+            let mut buf = [0u8; 1024];
+            let Ok(n) = stream.read(&mut buf).await else {
+                return;
+            };
+            let request = String::from_utf8_lossy(&buf[..n]);
+
+            let response = if request.starts_with("GET /metrics") {
+                let body = metrics.live.render();
+                format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    TextEncoder::new().format_type(),
+                    body.len(),
+                    body
+                )
+            } else {
+                "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+                    .to_string()
+            };
+
+            if let Err(e) = stream.write_all(response.as_bytes()).await {
+                debug!("Metrics scrape from {} failed: {}", peer, e);
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_live_metrics_render() {
+        let live = LiveMetrics::new();
+        live.set_target_rps(500);
+        {
+            let _guard = live.start_request();
+            assert_eq!(live.in_flight(), 1);
+            live.observe("health_check", 2_000, None);
+        }
+        assert_eq!(live.in_flight(), 0);
+        live.observe("stats", 150_000, Some("timeout"));
+
+        let text = live.render();
+        assert!(text.contains("rope_loadtest_target_rps 500"));
+        assert!(text.contains(
+            "rope_loadtest_requests_total{outcome=\"success\",scenario=\"health_check\"} 1"
+        ));
+        assert!(text.contains("rope_loadtest_errors_total{error=\"timeout\"} 1"));
+        assert!(text.contains(
+            "rope_loadtest_request_duration_seconds_bucket{scenario=\"stats\",le=\"0.25\"} 1"
+        ));
+        assert!(text.contains("rope_loadtest_in_flight_requests 0"));
+    }
+
+    #[test]
+    fn test_rolling_rps_window() {
+        let live = LiveMetrics::new();
+        assert_eq!(live.sample(0), 0.0);
+        for second in 1..=5u64 {
+            live.sample(second * 100);
+        }
+        assert_eq!(live.sample(600), 100.0);
+
+        // Old samples fall out of the window
+        for second in 7..=(7 + RPS_WINDOW_SECS as u64) {
+            live.sample(600 + (second - 6) * 1_000);
+        }
+        assert_eq!(live.sample(600 + 12 * 1_000), 1_000.0);
+    }
+}
@@ -22,9 +22,23 @@
 //!
 //! # Run specific scenarios
 //! cargo run --package rope-loadtest -- --scenario strings --target https://api.dcscan.io
+//!
+//! # Mix in 20% writes, including signed transactions sent to a node's JSON-RPC
+//! cargo run --package rope-loadtest -- --write-ratio 0.2 --rpc-url http://localhost:8545
+//!
+//! # Run a load profile defined in YAML (see `scenario_dsl`)
+//! cargo run --package rope-loadtest -- --scenario-file scenarios/explorer-mixed.yaml
+//!
+//! # Spread 10,000 RPS over several worker hosts (see `distributed`)
+//! cargo run --package rope-loadtest -- worker --listen 0.0.0.0:7070
+//! cargo run --package rope-loadtest -- coordinate --workers http://w1:7070,http://w2:7070 --rps 10000
 //! ```
+//!
+//! While a run is in progress, live metrics are served at
+//! `http://<host>:<metrics_port>/metrics` (see `exporter`).
 
 use std::collections::HashMap;
+use std::net::SocketAddr;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
@@ -39,6 +53,23 @@ use tokio::sync::Semaphore;
 use tokio::time::sleep;
 use tracing::{debug, error, info, warn};
 
+pub mod distributed;
+pub mod exporter;
+pub mod scenario_dsl;
+pub mod slo;
+pub mod write_scenarios;
+
+pub use distributed::{
+    shard_config, Coordinator, DistributedError, DistributedSummary, WorkerResult, WorkerService,
+};
+pub use exporter::{serve_metrics, LiveMetrics};
+pub use scenario_dsl::{DeclarativeScenario, DslError, ScenarioFile};
+pub use slo::{exit_code, SloConfig, SloError, SloReport, SloThresholds};
+pub use write_scenarios::{
+    default_write_scenarios, CreatedResources, HttpWriteScenario, PayloadTemplate,
+    SubmitTransactionScenario, TemplateContext, TemplateError,
+};
+
 // ============================================================================
 // CONFIGURATION
 // ============================================================================
@@ -73,8 +104,21 @@ pub struct LoadTestConfig {
     /// Enable detailed logging
     pub verbose: bool,
 
-    /// Metrics export port
+    /// Port serving live Prometheus metrics during the run
     pub metrics_port: Option<u16>,
+
+    /// Fraction of requests drawn from write scenarios (0.0 - 1.0);
+    /// 0.0 selects from all scenarios by weight alone
+    pub write_ratio: f64,
+
+    /// Node JSON-RPC endpoint for transaction submission
+    pub rpc_url: Option<String>,
+
+    /// Chain ID signed into submitted transactions
+    pub chain_id: u64,
+
+    /// Remove resources created by write scenarios after the run
+    pub cleanup: bool,
 }
 
 impl Default for LoadTestConfig {
@@ -90,6 +134,10 @@ impl Default for LoadTestConfig {
             scenarios: vec!["all".to_string()],
             verbose: false,
             metrics_port: Some(9090),
+            write_ratio: 0.0,
+            rpc_url: None,
+            chain_id: 271828,
+            cleanup: true,
         }
     }
 }
@@ -127,6 +175,12 @@ pub struct LoadTestMetrics {
 
     /// Start time
     pub start_time: RwLock<Option<Instant>>,
+
+    /// Prometheus collectors for live export
+    pub live: LiveMetrics,
+
+    /// Per-scenario counters and latencies
+    pub scenarios: RwLock<HashMap<String, ScenarioSnapshot>>,
 }
 
 impl Default for LoadTestMetrics {
@@ -149,6 +203,23 @@ impl LoadTestMetrics {
             error_counts: RwLock::new(HashMap::new()),
             current_rps: AtomicU64::new(0),
             start_time: RwLock::new(None),
+            live: LiveMetrics::new(),
+            scenarios: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Record a request against its scenario
+    pub fn record_scenario(&self, scenario: &str, latency_us: u64, success: bool) {
+        let mut scenarios = self.scenarios.write();
+        let stats = scenarios
+            .entry(scenario.to_string())
+            .or_insert_with(ScenarioSnapshot::new);
+        stats.total_requests += 1;
+        if !success {
+            stats.failed_requests += 1;
+        }
+        if let Err(e) = stats.latency_histogram.record(latency_us) {
+            warn!("Failed to record latency: {}", e);
         }
     }
 
@@ -180,29 +251,141 @@ impl LoadTestMetrics {
 
     /// Get summary statistics
     pub fn summary(&self) -> MetricsSummary {
-        let hist = self.latency_histogram.read();
-        let duration = self
-            .start_time
-            .read()
-            .map(|t: Instant| t.elapsed().as_secs_f64())
-            .unwrap_or(1.0);
+        self.snapshot().summary()
+    }
 
-        let total = self.total_requests.load(Ordering::Relaxed);
-        let successful = self.successful_requests.load(Ordering::Relaxed);
-        let failed = self.failed_requests.load(Ordering::Relaxed);
+    /// Copy the current counters and histogram
+    pub fn snapshot(&self) -> MetricsSnapshot {
+        MetricsSnapshot {
+            total_requests: self.total_requests.load(Ordering::Relaxed),
+            successful_requests: self.successful_requests.load(Ordering::Relaxed),
+            failed_requests: self.failed_requests.load(Ordering::Relaxed),
+            bytes_sent: self.bytes_sent.load(Ordering::Relaxed),
+            bytes_received: self.bytes_received.load(Ordering::Relaxed),
+            latency_histogram: self.latency_histogram.read().clone(),
+            error_counts: self.error_counts.read().clone(),
+            scenarios: self.scenarios.read().clone(),
+            duration_secs: self
+                .start_time
+                .read()
+                .map(|t: Instant| t.elapsed().as_secs_f64())
+                .unwrap_or(1.0),
+        }
+    }
+}
+
+/// Point-in-time copy of [`LoadTestMetrics`], mergeable across workers
+#[derive(Debug, Clone)]
+pub struct MetricsSnapshot {
+    pub total_requests: u64,
+    pub successful_requests: u64,
+    pub failed_requests: u64,
+    pub bytes_sent: u64,
+    pub bytes_received: u64,
+    pub latency_histogram: Histogram<u64>,
+    pub error_counts: HashMap<String, u64>,
+    pub scenarios: HashMap<String, ScenarioSnapshot>,
+    pub duration_secs: f64,
+}
+
+/// Counters and latencies for one scenario
+#[derive(Debug, Clone)]
+pub struct ScenarioSnapshot {
+    pub total_requests: u64,
+    pub failed_requests: u64,
+    pub latency_histogram: Histogram<u64>,
+}
+
+impl Default for ScenarioSnapshot {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ScenarioSnapshot {
+    pub fn new() -> Self {
+        Self {
+            total_requests: 0,
+            failed_requests: 0,
+            latency_histogram: Histogram::new_with_bounds(1, 60_000_000, 3).unwrap(),
+        }
+    }
+
+    pub fn merge(&mut self, other: &ScenarioSnapshot) {
+        self.total_requests += other.total_requests;
+        self.failed_requests += other.failed_requests;
+        if let Err(e) = self.latency_histogram.add(&other.latency_histogram) {
+            warn!("Failed to merge latency histogram: {:?}", e);
+        }
+    }
+
+    /// Summarize over a run lasting `duration_secs`
+    pub fn summary(&self, duration_secs: f64) -> ScenarioSummary {
+        let hist = &self.latency_histogram;
+        let total = self.total_requests;
+        ScenarioSummary {
+            total_requests: total,
+            failed_requests: self.failed_requests,
+            success_rate: if total > 0 {
+                ((total - self.failed_requests) as f64 / total as f64) * 100.0
+            } else {
+                0.0
+            },
+            avg_rps: total as f64 / duration_secs,
+            latency_p50_us: hist.value_at_quantile(0.50),
+            latency_p99_us: hist.value_at_quantile(0.99),
+        }
+    }
+}
+
+impl MetricsSnapshot {
+    /// Fold in a snapshot from a concurrent run
+    ///
+    /// Runs overlap in time, so the merged duration is the longer of the
+    /// two rather than their sum.
+    pub fn merge(&mut self, other: &MetricsSnapshot) {
+        self.total_requests += other.total_requests;
+        self.successful_requests += other.successful_requests;
+        self.failed_requests += other.failed_requests;
+        self.bytes_sent += other.bytes_sent;
+        self.bytes_received += other.bytes_received;
+        if let Err(e) = self.latency_histogram.add(&other.latency_histogram) {
+            warn!("Failed to merge latency histogram: {:?}", e);
+        }
+        for (error_type, count) in &other.error_counts {
+            *self.error_counts.entry(error_type.clone()).or_insert(0) += count;
+        }
+        for (name, stats) in &other.scenarios {
+            self.scenarios
+                .entry(name.clone())
+                .or_insert_with(ScenarioSnapshot::new)
+                .merge(stats);
+        }
+        self.duration_secs = self.duration_secs.max(other.duration_secs);
+    }
+
+    /// Get summary statistics
+    pub fn summary(&self) -> MetricsSummary {
+        let hist = &self.latency_histogram;
+        let total = self.total_requests;
+        let duration = if self.duration_secs > 0.0 {
+            self.duration_secs
+        } else {
+            1.0
+        };
 
         MetricsSummary {
             total_requests: total,
-            successful_requests: successful,
-            failed_requests: failed,
+            successful_requests: self.successful_requests,
+            failed_requests: self.failed_requests,
             success_rate: if total > 0 {
-                (successful as f64 / total as f64) * 100.0
+                (self.successful_requests as f64 / total as f64) * 100.0
             } else {
                 0.0
             },
             avg_rps: total as f64 / duration,
-            bytes_sent: self.bytes_sent.load(Ordering::Relaxed),
-            bytes_received: self.bytes_received.load(Ordering::Relaxed),
+            bytes_sent: self.bytes_sent,
+            bytes_received: self.bytes_received,
             latency_p50_us: hist.value_at_quantile(0.50),
             latency_p90_us: hist.value_at_quantile(0.90),
             latency_p99_us: hist.value_at_quantile(0.99),
@@ -211,7 +394,12 @@ impl LoadTestMetrics {
             latency_min_us: hist.min(),
             latency_mean_us: hist.mean() as u64,
             duration_secs: duration,
-            error_counts: self.error_counts.read().clone(),
+            error_counts: self.error_counts.clone(),
+            scenarios: self
+                .scenarios
+                .iter()
+                .map(|(name, stats)| (name.clone(), stats.summary(duration)))
+                .collect(),
         }
     }
 }
@@ -235,6 +423,19 @@ pub struct MetricsSummary {
     pub latency_mean_us: u64,
     pub duration_secs: f64,
     pub error_counts: HashMap<String, u64>,
+    #[serde(default)]
+    pub scenarios: HashMap<String, ScenarioSummary>,
+}
+
+/// Per-scenario summary statistics
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScenarioSummary {
+    pub total_requests: u64,
+    pub failed_requests: u64,
+    pub success_rate: f64,
+    pub avg_rps: f64,
+    pub latency_p50_us: u64,
+    pub latency_p99_us: u64,
 }
 
 impl MetricsSummary {
@@ -319,48 +520,12 @@ impl MetricsSummary {
 
     /// Check if results meet specification requirements
     pub fn check_spec_requirements(&self) -> SpecCheckResult {
-        let mut result = SpecCheckResult {
-            passes: true,
-            checks: Vec::new(),
-        };
+        self.check_slos(&SloConfig::spec_defaults())
+    }
 
-        // Check latency p99 < 100ms (100,000 µs)
-        let latency_check = self.latency_p99_us < 100_000;
-        result.checks.push(SpecCheck {
-            name: "Latency p99 < 100ms".to_string(),
-            passed: latency_check,
-            actual: format!("{}µs", self.latency_p99_us),
-            expected: "<100,000µs".to_string(),
-        });
-        if !latency_check {
-            result.passes = false;
-        }
-
-        // Check success rate > 99%
-        let success_check = self.success_rate > 99.0;
-        result.checks.push(SpecCheck {
-            name: "Success rate > 99%".to_string(),
-            passed: success_check,
-            actual: format!("{:.2}%", self.success_rate),
-            expected: ">99%".to_string(),
-        });
-        if !success_check {
-            result.passes = false;
-        }
-
-        // Check throughput > 100 RPS (for basic test)
-        let throughput_check = self.avg_rps > 100.0;
-        result.checks.push(SpecCheck {
-            name: "Throughput > 100 RPS".to_string(),
-            passed: throughput_check,
-            actual: format!("{:.2} RPS", self.avg_rps),
-            expected: ">100 RPS".to_string(),
-        });
-        if !throughput_check {
-            result.passes = false;
-        }
-
-        result
+    /// Check results against run-specific SLOs
+    pub fn check_slos(&self, slo: &SloConfig) -> SpecCheckResult {
+        slo.evaluate(self)
     }
 }
 
@@ -375,6 +540,9 @@ pub struct SpecCheckResult {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SpecCheck {
     pub name: String,
+    /// Scenario the check applies to; `None` for whole-run checks
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scenario: Option<String>,
     pub passed: bool,
     pub actual: String,
     pub expected: String,
@@ -415,6 +583,26 @@ pub trait LoadTestScenario: Send + Sync {
 
     /// Execute a single request
     async fn execute(&self, client: &reqwest::Client, base_url: &str) -> ScenarioResult;
+
+    /// Whether the scenario mutates state
+    fn is_write(&self) -> bool {
+        false
+    }
+
+    /// Relative selection weight
+    fn weight(&self) -> u32 {
+        1
+    }
+
+    /// Pause after each request, holding its concurrency slot
+    fn think_time(&self) -> Duration {
+        Duration::ZERO
+    }
+
+    /// Remove anything the scenario created
+    async fn cleanup(&self, _client: &reqwest::Client, _base_url: &str) -> CleanupSummary {
+        CleanupSummary::default()
+    }
 }
 
 /// Result of a scenario execution
@@ -427,6 +615,26 @@ pub struct ScenarioResult {
     pub error: Option<String>,
 }
 
+impl ScenarioResult {
+    /// Failed request that started at `start`
+    pub fn failed(start: Instant, bytes_sent: u64, error: String) -> Self {
+        Self {
+            success: false,
+            latency_us: start.elapsed().as_micros() as u64,
+            bytes_sent,
+            bytes_received: 0,
+            error: Some(error),
+        }
+    }
+}
+
+/// Resources removed by a scenario's cleanup
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CleanupSummary {
+    pub attempted: usize,
+    pub removed: usize,
+}
+
 // ============================================================================
 // BUILT-IN SCENARIOS
 // ============================================================================
@@ -648,6 +856,32 @@ impl LoadTestRunner {
         self.scenarios.push(Arc::new(scenario));
     }
 
+    /// Add the scenarios defined in a YAML file, returning how many were added
+    pub fn add_scenario_file(&mut self, path: &str) -> Result<usize, DslError> {
+        let yaml = std::fs::read_to_string(path).map_err(|e| DslError::Io(e.to_string()))?;
+        self.add_scenario_yaml(&yaml)
+    }
+
+    /// Add the scenarios defined in YAML text, returning how many were added
+    pub fn add_scenario_yaml(&mut self, yaml: &str) -> Result<usize, DslError> {
+        let file = ScenarioFile::from_yaml(yaml)?;
+        let scenarios = file.build()?;
+        info!(
+            "Loaded {} scenarios ({})",
+            scenarios.len(),
+            file.name.as_deref().unwrap_or("unnamed")
+        );
+
+        let added = scenarios.len();
+        self.scenarios.extend(scenarios);
+        Ok(added)
+    }
+
+    /// Live metrics for this runner
+    pub fn metrics(&self) -> Arc<LoadTestMetrics> {
+        self.metrics.clone()
+    }
+
     /// Add default scenarios
     pub fn add_default_scenarios(&mut self) {
         self.add_scenario(HealthCheckScenario);
@@ -657,6 +891,14 @@ impl LoadTestRunner {
         self.add_scenario(MixedWorkloadScenario);
     }
 
+    /// Add write scenarios; transactions are included when `rpc_url` is set
+    pub fn add_write_scenarios(&mut self) {
+        self.scenarios.extend(default_write_scenarios(
+            self.config.rpc_url.as_deref(),
+            self.config.chain_id,
+        ));
+    }
+
     /// Run the load test
     pub async fn run(&self) -> MetricsSummary {
         info!(
@@ -666,6 +908,35 @@ impl LoadTestRunner {
 
         *self.metrics.start_time.write() = Some(Instant::now());
 
+        // Live export and the rolling RPS sampler run alongside the phases
+        let exporter = self
+            .config
+            .metrics_port
+            .filter(|port| *port != 0)
+            .map(|port| {
+                let metrics = self.metrics.clone();
+                tokio::spawn(async move {
+                    let addr = SocketAddr::from(([0, 0, 0, 0], port));
+                    if let Err(e) = serve_metrics(addr, metrics).await {
+                        warn!("Metrics endpoint on port {} unavailable: {}", port, e);
+                    }
+                })
+            });
+        let sampler = {
+            let metrics = self.metrics.clone();
+            tokio::spawn(async move {
+                let mut ticker = tokio::time::interval(Duration::from_secs(1));
+                loop {
+                    ticker.tick().await;
+                    let total = metrics.total_requests.load(Ordering::Relaxed);
+                    let rps = metrics.live.sample(total);
+                    metrics
+                        .current_rps
+                        .store(rps.round() as u64, Ordering::Relaxed);
+                }
+            })
+        };
+
         // Create HTTP client
         let client = reqwest::Client::builder()
             .timeout(Duration::from_secs(self.config.request_timeout_secs))
@@ -714,9 +985,67 @@ impl LoadTestRunner {
         .await;
 
         info!("Load test complete");
+
+        if self.config.cleanup {
+            self.cleanup(&client).await;
+        }
+
+        sampler.abort();
+        if let Some(exporter) = exporter {
+            exporter.abort();
+        }
+
         self.metrics.summary()
     }
 
+    /// Run every scenario's cleanup
+    async fn cleanup(&self, client: &reqwest::Client) -> CleanupSummary {
+        let mut total = CleanupSummary::default();
+        for scenario in &self.scenarios {
+            let summary = scenario.cleanup(client, &self.config.target_url).await;
+            total.attempted += summary.attempted;
+            total.removed += summary.removed;
+        }
+
+        if total.attempted > 0 {
+            info!(
+                "Cleanup removed {}/{} created resources",
+                total.removed, total.attempted
+            );
+        }
+        total
+    }
+
+    /// Pick a scenario by weight, drawing writes with probability `write_ratio`
+    fn select_scenario(&self) -> Option<Arc<dyn LoadTestScenario>> {
+        let mut pool: Vec<&Arc<dyn LoadTestScenario>> = self.scenarios.iter().collect();
+        if self.config.write_ratio > 0.0 {
+            let want_write = rand::random::<f64>() < self.config.write_ratio;
+            let matching: Vec<_> = pool
+                .iter()
+                .copied()
+                .filter(|s| s.is_write() == want_write)
+                .collect();
+            if !matching.is_empty() {
+                pool = matching;
+            }
+        }
+
+        let total: u64 = pool.iter().map(|s| s.weight() as u64).sum();
+        if total == 0 {
+            return None;
+        }
+        let mut pick = rand::random::<u64>() % total;
+        for scenario in pool {
+            let weight = scenario.weight() as u64;
+            if pick < weight {
+                return Some(scenario.clone());
+            }
+            pick -= weight;
+        }
+        None
+    }
+
     /// Run a single phase of the test
     async fn run_phase(
         &self,
@@ -728,6 +1057,7 @@ impl LoadTestRunner {
         let start = Instant::now();
         let target_duration = Duration::from_secs(duration_secs);
         let interval = Duration::from_secs_f64(1.0 / target_rps as f64);
+        self.metrics.live.set_target_rps(target_rps);
 
         let mut tasks = FuturesUnordered::new();
         let mut next_request = Instant::now();
@@ -740,11 +1070,9 @@ impl LoadTestRunner {
             next_request = Instant::now() + interval;
 
             // Select a random scenario
-            let scenario_idx = rand::random::<usize>() % self.scenarios.len().max(1);
-            if self.scenarios.is_empty() {
+            let Some(scenario) = self.select_scenario() else {
                 continue;
-            }
-            let scenario = self.scenarios[scenario_idx].clone();
+            };
 
             let client = client.clone();
             let semaphore = semaphore.clone();
@@ -754,9 +1082,15 @@ impl LoadTestRunner {
             let task = async move {
                 let _permit = semaphore.acquire().await.expect("Semaphore closed");
 
+                let in_flight = metrics.live.start_request();
                 let result = scenario.execute(&client, &base_url).await;
+                drop(in_flight);
+                metrics.record_scenario(scenario.name(), result.latency_us, result.success);
 
                 if result.success {
+                    metrics
+                        .live
+                        .observe(scenario.name(), result.latency_us, None);
                     metrics.record_success(
                         result.latency_us,
                         result.bytes_sent,
@@ -768,8 +1102,16 @@ impl LoadTestRunner {
                         .as_ref()
                         .map(|e| e.split(':').next().unwrap_or("unknown"))
                         .unwrap_or("unknown");
+                    metrics
+                        .live
+                        .observe(scenario.name(), result.latency_us, Some(error_type));
                     metrics.record_failure(error_type, result.latency_us);
                 }
+
+                let think_time = scenario.think_time();
+                if !think_time.is_zero() {
+                    sleep(think_time).await;
+                }
             };
 
             tasks.push(task);
@@ -13,9 +13,31 @@
 //!
 //! # Soak test for extended duration
 //! rope-loadtest soak --target https://dcscan.io --duration-hours 1 --rps 50
+//!
+//! # Load profile defined in YAML
+//! rope-loadtest --target http://localhost:3001 --scenario-file scenarios/explorer-mixed.yaml
+//!
+//! # Mixed read/write traffic with signed transactions
+//! rope-loadtest --target http://localhost:3001 --write-ratio 0.2 --rpc-url http://localhost:8545
+//!
+//! # Distributed run: start a worker on each load host, then coordinate
+//! rope-loadtest worker --listen 0.0.0.0:7070
+//! rope-loadtest coordinate --workers http://10.0.0.5:7070,http://10.0.0.6:7070 --rps 10000
+//!
+//! # CI gate: custom SLOs, JUnit output for the pipeline
+//! rope-loadtest --target http://staging:3001 --slo-file slo.yaml --max-p99-ms 80 --junit loadtest.xml
 //! ```
-
-use clap::{Parser, Subcommand};
+//!
+//! ## Exit codes
+//!
+//! | Code | Meaning |
+//! |------|---------|
+//! | 0 | Every SLO met |
+//! | 1 | Run completed but missed an SLO |
+//! | 2 | Invalid arguments, scenario file or SLO file |
+//! | 3 | Run failed (no requests completed, worker lost) |
+
+use clap::{Args, Parser, Subcommand};
 use rope_loadtest::*;
 use tracing::info;
 use tracing_subscriber::{fmt, prelude::*, EnvFilter};
@@ -60,6 +82,112 @@ struct Cli {
     /// Output results to JSON file
     #[arg(short, long)]
     output: Option<String>,
+
+    /// Fraction of requests that are writes (0.0 - 1.0)
+    #[arg(long, default_value = "0.0")]
+    write_ratio: f64,
+
+    /// Node JSON-RPC URL for transaction submission
+    #[arg(long)]
+    rpc_url: Option<String>,
+
+    /// Chain ID for submitted transactions
+    #[arg(long, default_value = "271828")]
+    chain_id: u64,
+
+    /// Keep resources created by write scenarios
+    #[arg(long)]
+    no_cleanup: bool,
+
+    /// YAML scenario definitions to run instead of the built-in scenarios
+    #[arg(long)]
+    scenario_file: Option<String>,
+
+    /// Port serving live Prometheus metrics during the run (0 disables)
+    #[arg(long, default_value = "9090")]
+    metrics_port: u16,
+
+    #[command(flatten)]
+    slo: SloArgs,
+}
+
+/// Pass/fail thresholds and machine-readable results
+#[derive(Args)]
+struct SloArgs {
+    /// YAML or JSON SLO definitions (defaults to the §8.2 thresholds)
+    #[arg(long)]
+    slo_file: Option<String>,
+
+    /// Override the overall p99 latency limit (ms)
+    #[arg(long)]
+    max_p99_ms: Option<f64>,
+
+    /// Override the overall error rate limit (percent)
+    #[arg(long)]
+    max_error_rate: Option<f64>,
+
+    /// Override the overall minimum throughput (RPS)
+    #[arg(long)]
+    min_rps: Option<f64>,
+
+    /// Write SLO results as JSON
+    #[arg(long)]
+    slo_json: Option<String>,
+
+    /// Write SLO results as JUnit XML
+    #[arg(long)]
+    junit: Option<String>,
+}
+
+impl SloArgs {
+    /// SLOs for this run, exiting on an invalid file
+    fn resolve(&self) -> SloConfig {
+        let mut slo = match &self.slo_file {
+            Some(path) => SloConfig::load(path).unwrap_or_else(|e| {
+                eprintln!("{}", e);
+                std::process::exit(exit_code::CONFIG_ERROR);
+            }),
+            None => SloConfig::spec_defaults(),
+        };
+        if let Some(max) = self.max_p99_ms {
+            slo.overall.max_p99_ms = Some(max);
+        }
+        if let Some(max) = self.max_error_rate {
+            slo.overall.max_error_rate = Some(max);
+        }
+        if let Some(min) = self.min_rps {
+            slo.overall.min_rps = Some(min);
+        }
+        if let Err(e) = slo.validate() {
+            eprintln!("{}", e);
+            std::process::exit(exit_code::CONFIG_ERROR);
+        }
+        slo
+    }
+
+    /// Check `summary`, write the requested reports and return the exit code
+    fn report(&self, slo: &SloConfig, summary: &MetricsSummary) -> i32 {
+        let result = summary.check_slos(slo);
+        result.print_report();
+
+        if let Some(path) = &self.slo_json {
+            let report = SloReport::new(slo, &result, summary);
+            let json = serde_json::to_string_pretty(&report).expect("Failed to serialize");
+            std::fs::write(path, json).expect("Failed to write SLO report");
+            info!("SLO results saved to {}", path);
+        }
+        if let Some(path) = &self.junit {
+            let xml = result.to_junit("rope-loadtest", summary.duration_secs);
+            std::fs::write(path, xml).expect("Failed to write JUnit report");
+            info!("JUnit results saved to {}", path);
+        }
+
+        if summary.total_requests == 0 {
+            eprintln!("No requests completed");
+            return exit_code::RUN_FAILED;
+        }
+        result.exit_code()
+    }
 }
 
 #[derive(Subcommand)]
@@ -119,6 +247,59 @@ enum Commands {
         #[arg(short, long, default_value = "https://dcscan.io")]
         target: String,
     },
+
+    /// Serve load shards to a coordinator over gRPC
+    Worker {
+        /// Address to listen on
+        #[arg(short, long, default_value = "0.0.0.0:7070")]
+        listen: std::net::SocketAddr,
+
+        /// Worker name used in reports (defaults to the listen address)
+        #[arg(long)]
+        id: Option<String>,
+    },
+
+    /// Shard a load test across workers and merge their results
+    Coordinate {
+        /// Comma-separated worker endpoints (e.g. http://10.0.0.5:7070)
+        #[arg(short, long, value_delimiter = ',', required = true)]
+        workers: Vec<String>,
+
+        /// Target base URL
+        #[arg(short, long, default_value = "https://dcscan.io")]
+        target: String,
+
+        /// Test duration in seconds
+        #[arg(short, long, default_value = "60")]
+        duration: u64,
+
+        /// Total requests per second across all workers
+        #[arg(short, long, default_value = "10000")]
+        rps: u64,
+
+        /// Total concurrent requests across all workers
+        #[arg(short, long, default_value = "500")]
+        concurrency: usize,
+
+        /// YAML scenario definitions sent to every worker
+        #[arg(long)]
+        scenario_file: Option<String>,
+
+        /// Fraction of requests that are writes (0.0 - 1.0)
+        #[arg(long, default_value = "0.0")]
+        write_ratio: f64,
+
+        /// Node JSON-RPC URL for transaction submission
+        #[arg(long)]
+        rpc_url: Option<String>,
+
+        /// Output merged and per-worker results to JSON file
+        #[arg(short, long)]
+        output: Option<String>,
+
+        #[command(flatten)]
+        slo: SloArgs,
+    },
 }
 
 #[tokio::main]
@@ -165,7 +346,75 @@ async fn main() {
         Some(Commands::SpecCheck { target }) => {
             run_spec_check(&target).await;
         }
+        Some(Commands::Worker { listen, id }) => {
+            let id = id.unwrap_or_else(|| listen.to_string());
+            if let Err(e) = WorkerService::new(&id).serve(listen).await {
+                eprintln!("Worker failed: {}", e);
+                std::process::exit(exit_code::RUN_FAILED);
+            }
+        }
+        Some(Commands::Coordinate {
+            workers,
+            target,
+            duration,
+            rps,
+            concurrency,
+            scenario_file,
+            write_ratio,
+            rpc_url,
+            output,
+            slo,
+        }) => {
+            let slo_config = slo.resolve();
+            let config = LoadTestConfig {
+                target_url: target,
+                duration_secs: duration,
+                target_rps: rps,
+                max_concurrency: concurrency,
+                write_ratio: write_ratio.clamp(0.0, 1.0),
+                rpc_url,
+                ..Default::default()
+            };
+
+            let mut coordinator = Coordinator::new(workers, config);
+            if let Some(path) = scenario_file {
+                match std::fs::read_to_string(&path) {
+                    Ok(yaml) => coordinator = coordinator.with_scenario_yaml(yaml),
+                    Err(e) => {
+                        eprintln!("Cannot read {}: {}", path, e);
+                        std::process::exit(exit_code::CONFIG_ERROR);
+                    }
+                }
+            }
+
+            let result = match coordinator.run().await {
+                Ok(result) => result,
+                Err(e @ DistributedError::Config(_)) => {
+                    eprintln!("{}", e);
+                    std::process::exit(exit_code::CONFIG_ERROR);
+                }
+                Err(e) => {
+                    eprintln!("{}", e);
+                    std::process::exit(exit_code::RUN_FAILED);
+                }
+            };
+            result.print_report();
+
+            if let Some(output_path) = output {
+                let json = serde_json::to_string_pretty(&result).expect("Failed to serialize");
+                std::fs::write(&output_path, json).expect("Failed to write output file");
+                info!("Results saved to {}", output_path);
+            }
+
+            let code = slo.report(&slo_config, &result.merged);
+            if result.workers.iter().any(|w| w.error.is_some()) {
+                std::process::exit(exit_code::RUN_FAILED);
+            }
+            std::process::exit(code);
+        }
         None => {
+            let slo = cli.slo.resolve();
+
             // Run default load test with CLI args
             let config = LoadTestConfig {
                 target_url: cli.target,
@@ -175,19 +424,33 @@ async fn main() {
                 warmup_secs: cli.warmup,
                 ramp_up_secs: cli.ramp_up,
                 verbose: cli.verbose,
+                write_ratio: cli.write_ratio.clamp(0.0, 1.0),
+                rpc_url: cli.rpc_url,
+                chain_id: cli.chain_id,
+                cleanup: !cli.no_cleanup,
+                metrics_port: Some(cli.metrics_port),
                 ..Default::default()
             };
 
             let mut runner = LoadTestRunner::new(config);
-            runner.add_default_scenarios();
+            match &cli.scenario_file {
+                Some(path) => {
+                    if let Err(e) = runner.add_scenario_file(path) {
+                        eprintln!("{}", e);
+                        std::process::exit(exit_code::CONFIG_ERROR);
+                    }
+                }
+                None => {
+                    runner.add_default_scenarios();
+                    if cli.write_ratio > 0.0 {
+                        runner.add_write_scenarios();
+                    }
+                }
+            }
 
             let summary = runner.run().await;
             summary.print_report();
 
-            // Check spec compliance
-            let spec_result = summary.check_spec_requirements();
-            spec_result.print_report();
-
             // Output JSON if requested
             if let Some(output_path) = cli.output {
                 let json = serde_json::to_string_pretty(&summary).expect("Failed to serialize");
@@ -196,11 +459,7 @@ async fn main() {
             }
 
             // Exit with appropriate code
-            if spec_result.passes {
-                std::process::exit(0);
-            } else {
-                std::process::exit(1);
-            }
+            std::process::exit(cli.slo.report(&slo, &summary));
         }
     }
 }
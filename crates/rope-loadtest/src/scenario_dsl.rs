@@ -0,0 +1,680 @@
+//! # Declarative Scenarios
+//!
+//! Load profiles defined in YAML and parsed at runtime, so operators can
+//! add endpoints, weights and pass criteria without recompiling:
+//!
+//! ```yaml
+//! name: explorer-mixed
+//! defaults:
+//!   headers:
+//!     X-Api-Key: "{{api_key}}"
+//!   assert:
+//!     max_latency_ms: 500
+//! scenarios:
+//!   - name: list_strings
+//!     weight: 10
+//!     path: /api/v1/strings?limit={{rand_int:10:50}}
+//!     think_time: { min_ms: 50, max_ms: 250 }
+//!     assert:
+//!       status: 200
+//!       json:
+//!         - pointer: /strings
+//!           exists: true
+//!   - name: submit_project
+//!     weight: 1
+//!     method: POST
+//!     path: /api/v1/projects
+//!     body: '{"name": "lt-{{seq}}", ...}'
+//!     capture: { kind: project, pointer: /project/id }
+//!     cleanup: /api/v1/projects/{{id}}
+//!   - name: vote_project
+//!     method: POST
+//!     path: /api/v1/projects/{{id}}/vote
+//!     target: { kind: project, fallback: proj-seed }
+//!     body: '{"vote_for": {{bool}}}'
+//! ```
+//!
+//! Paths, headers and bodies are [`PayloadTemplate`]s. Captured IDs are
+//! shared by every scenario in the file, so `target` can refer to resources
+//! another scenario created. Without an `assert` block a request passes on
+//! any 2xx status.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use crate::write_scenarios::{capture_id, delete_created};
+use crate::{
+    CleanupSummary, CreatedResources, LoadTestScenario, PayloadTemplate, ScenarioResult,
+    TemplateContext,
+};
+
+// ============================================================================
+// FILE FORMAT
+// ============================================================================
+
+/// A scenario definition file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ScenarioFile {
+    /// Profile name, for logs and reports
+    #[serde(default)]
+    pub name: Option<String>,
+
+    /// Settings applied to every scenario unless overridden
+    #[serde(default)]
+    pub defaults: ScenarioDefaults,
+
+    /// Template variables, e.g. API keys or fixed IDs
+    #[serde(default)]
+    pub vars: HashMap<String, String>,
+
+    pub scenarios: Vec<ScenarioSpec>,
+}
+
+/// File-wide scenario defaults
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ScenarioDefaults {
+    #[serde(default)]
+    pub headers: HashMap<String, String>,
+    #[serde(default)]
+    pub think_time: Option<ThinkTime>,
+    #[serde(default)]
+    pub assert: Option<AssertionSpec>,
+}
+
+/// One weighted request definition
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ScenarioSpec {
+    pub name: String,
+
+    /// HTTP method (default GET)
+    #[serde(default = "default_method")]
+    pub method: String,
+
+    /// Path template, appended to the target URL
+    pub path: String,
+
+    /// Relative selection weight (default 1)
+    #[serde(default = "default_weight")]
+    pub weight: u32,
+
+    #[serde(default)]
+    pub headers: HashMap<String, String>,
+
+    /// Body template
+    #[serde(default)]
+    pub body: Option<String>,
+
+    #[serde(default)]
+    pub think_time: Option<ThinkTime>,
+
+    #[serde(default)]
+    pub assert: Option<AssertionSpec>,
+
+    /// Record a created resource ID from the response
+    #[serde(default)]
+    pub capture: Option<CaptureSpec>,
+
+    /// Bind `{{id}}` to a captured resource
+    #[serde(default)]
+    pub target: Option<TargetSpec>,
+
+    /// Path template (with `{{id}}`) deleting captured resources after the run
+    #[serde(default)]
+    pub cleanup: Option<String>,
+
+    /// Count as a write for `write_ratio`; defaults to non-GET/HEAD methods
+    #[serde(default)]
+    pub write: Option<bool>,
+}
+
+fn default_method() -> String {
+    "GET".to_string()
+}
+
+fn default_weight() -> u32 {
+    1
+}
+
+/// Pause after a request, in milliseconds
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ThinkTime {
+    Fixed(u64),
+    Range { min_ms: u64, max_ms: u64 },
+}
+
+impl ThinkTime {
+    /// Draw a pause duration
+    pub fn sample(&self) -> Duration {
+        match self {
+            ThinkTime::Fixed(ms) => Duration::from_millis(*ms),
+            ThinkTime::Range { min_ms, max_ms } => {
+                let span = max_ms.saturating_sub(*min_ms) + 1;
+                Duration::from_millis(min_ms + rand::random::<u64>() % span)
+            }
+        }
+    }
+}
+
+/// Resource capture from a response body
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CaptureSpec {
+    pub kind: String,
+    /// JSON pointer to the ID, e.g. `/project/id`
+    pub pointer: String,
+}
+
+/// Resource a request targets through `{{id}}`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TargetSpec {
+    pub kind: String,
+    /// ID used until one has been captured
+    pub fallback: String,
+}
+
+// ============================================================================
+// ASSERTIONS
+// ============================================================================
+
+/// Pass criteria for a response
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AssertionSpec {
+    /// Accepted status codes (default: any 2xx)
+    #[serde(default)]
+    pub status: Option<StatusAssertion>,
+
+    #[serde(default)]
+    pub max_latency_ms: Option<u64>,
+
+    /// Substrings the body must contain
+    #[serde(default)]
+    pub body_contains: Vec<String>,
+
+    #[serde(default)]
+    pub json: Vec<JsonAssertion>,
+}
+
+/// One accepted status code or a list of them
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum StatusAssertion {
+    One(u16),
+    Any(Vec<u16>),
+}
+
+/// Check on a value in a JSON response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct JsonAssertion {
+    /// JSON pointer, e.g. `/success`
+    pub pointer: String,
+
+    #[serde(default)]
+    pub equals: Option<serde_json::Value>,
+
+    #[serde(default)]
+    pub exists: Option<bool>,
+}
+
+impl AssertionSpec {
+    /// Layer `self` over `defaults`, field by field
+    fn merged(&self, defaults: &AssertionSpec) -> AssertionSpec {
+        AssertionSpec {
+            status: self.status.clone().or_else(|| defaults.status.clone()),
+            max_latency_ms: self.max_latency_ms.or(defaults.max_latency_ms),
+            body_contains: [defaults.body_contains.clone(), self.body_contains.clone()].concat(),
+            json: [defaults.json.clone(), self.json.clone()].concat(),
+        }
+    }
+
+    /// Check a response; the error names the first failed assertion
+    pub fn check(&self, status: u16, latency: Duration, body: &[u8]) -> Result<(), String> {
+        let status_ok = match &self.status {
+            None => (200..300).contains(&status),
+            Some(StatusAssertion::One(expected)) => status == *expected,
+            Some(StatusAssertion::Any(expected)) => expected.contains(&status),
+        };
+        if !status_ok {
+            return Err(format!("status {}", status));
+        }
+
+        if let Some(max) = self.max_latency_ms {
+            let latency_ms = latency.as_millis() as u64;
+            if latency_ms > max {
+                return Err(format!("latency {}ms > {}ms", latency_ms, max));
+            }
+        }
+
+        if !self.body_contains.is_empty() {
+            let text = String::from_utf8_lossy(body);
+            if let Some(missing) = self
+                .body_contains
+                .iter()
+                .find(|s| !text.contains(s.as_str()))
+            {
+                return Err(format!("body missing {:?}", missing));
+            }
+        }
+
+        if !self.json.is_empty() {
+            let value: serde_json::Value =
+                serde_json::from_slice(body).map_err(|_| "body is not JSON".to_string())?;
+            for check in &self.json {
+                let found = value.pointer(&check.pointer);
+                if let Some(exists) = check.exists {
+                    if found.is_some() != exists {
+                        return Err(format!("{} exists != {}", check.pointer, exists));
+                    }
+                }
+                if let Some(expected) = &check.equals {
+                    if found != Some(expected) {
+                        return Err(format!("{} != {}", check.pointer, expected));
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// ============================================================================
+// LOADING
+// ============================================================================
+
+/// Scenario file error
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DslError {
+    Io(String),
+    Parse(String),
+    Invalid { scenario: String, reason: String },
+}
+
+impl std::fmt::Display for DslError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DslError::Io(e) => write!(f, "Cannot read scenario file: {}", e),
+            DslError::Parse(e) => write!(f, "Invalid scenario file: {}", e),
+            DslError::Invalid { scenario, reason } => {
+                write!(f, "Invalid scenario {}: {}", scenario, reason)
+            }
+        }
+    }
+}
+
+impl std::error::Error for DslError {}
+
+impl ScenarioFile {
+    pub fn from_yaml(yaml: &str) -> Result<Self, DslError> {
+        serde_yaml::from_str(yaml).map_err(|e| DslError::Parse(e.to_string()))
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, DslError> {
+        let yaml = std::fs::read_to_string(path).map_err(|e| DslError::Io(e.to_string()))?;
+        Self::from_yaml(&yaml)
+    }
+
+    /// Validate every definition and build runnable scenarios
+    pub fn build(&self) -> Result<Vec<Arc<dyn LoadTestScenario>>, DslError> {
+        if self.scenarios.is_empty() {
+            return Err(DslError::Parse("no scenarios defined".to_string()));
+        }
+
+        let resources = Arc::new(CreatedResources::new());
+        let mut names = std::collections::HashSet::new();
+        let mut built: Vec<Arc<dyn LoadTestScenario>> = Vec::with_capacity(self.scenarios.len());
+
+        for spec in &self.scenarios {
+            if !names.insert(spec.name.as_str()) {
+                return Err(DslError::Invalid {
+                    scenario: spec.name.clone(),
+                    reason: "duplicate name".to_string(),
+                });
+            }
+            built.push(Arc::new(DeclarativeScenario::new(
+                spec,
+                self,
+                resources.clone(),
+            )?));
+        }
+        Ok(built)
+    }
+}
+
+// ============================================================================
+// DECLARATIVE SCENARIO
+// ============================================================================
+
+/// Scenario built from a [`ScenarioSpec`]
+pub struct DeclarativeScenario {
+    name: String,
+    method: reqwest::Method,
+    path: PayloadTemplate,
+    headers: Vec<(String, PayloadTemplate)>,
+    body: Option<PayloadTemplate>,
+    weight: u32,
+    think_time: Option<ThinkTime>,
+    assert: AssertionSpec,
+    capture: Option<CaptureSpec>,
+    target: Option<TargetSpec>,
+    cleanup: Option<PayloadTemplate>,
+    write: bool,
+    vars: HashMap<String, String>,
+    resources: Arc<CreatedResources>,
+    seq: AtomicU64,
+}
+
+impl DeclarativeScenario {
+    fn new(
+        spec: &ScenarioSpec,
+        file: &ScenarioFile,
+        resources: Arc<CreatedResources>,
+    ) -> Result<Self, DslError> {
+        let invalid = |reason: String| DslError::Invalid {
+            scenario: spec.name.clone(),
+            reason,
+        };
+
+        let method = reqwest::Method::from_bytes(spec.method.to_uppercase().as_bytes())
+            .map_err(|_| invalid(format!("unknown method {}", spec.method)))?;
+        if spec.weight == 0 {
+            return Err(invalid("weight must be positive".to_string()));
+        }
+        if spec.cleanup.is_some() && spec.capture.is_none() {
+            return Err(invalid("cleanup requires capture".to_string()));
+        }
+
+        let mut headers = file.defaults.headers.clone();
+        headers.extend(spec.headers.clone());
+
+        let scenario = Self {
+            name: spec.name.clone(),
+            write: spec.write.unwrap_or(!matches!(
+                method,
+                reqwest::Method::GET | reqwest::Method::HEAD
+            )),
+            method,
+            path: PayloadTemplate::new(spec.path.as_str()),
+            headers: headers
+                .into_iter()
+                .map(|(k, v)| (k, PayloadTemplate::new(v)))
+                .collect(),
+            body: spec.body.as_deref().map(PayloadTemplate::new),
+            weight: spec.weight,
+            think_time: spec
+                .think_time
+                .clone()
+                .or_else(|| file.defaults.think_time.clone()),
+            assert: match (&spec.assert, &file.defaults.assert) {
+                (Some(own), Some(defaults)) => own.merged(defaults),
+                (Some(own), None) => own.clone(),
+                (None, defaults) => defaults.clone().unwrap_or_default(),
+            },
+            capture: spec.capture.clone(),
+            target: spec.target.clone(),
+            cleanup: spec.cleanup.as_deref().map(PayloadTemplate::new),
+            vars: file.vars.clone(),
+            resources,
+            seq: AtomicU64::new(0),
+        };
+
+        // Surface unknown placeholders now rather than on every request
+        let probe = scenario.context(0).with_var("id", "probe");
+        let templates = std::iter::once(&scenario.path)
+            .chain(scenario.headers.iter().map(|(_, v)| v))
+            .chain(scenario.body.iter())
+            .chain(scenario.cleanup.iter());
+        for template in templates {
+            template
+                .render(&probe)
+                .map_err(|e| invalid(e.to_string()))?;
+        }
+
+        Ok(scenario)
+    }
+
+    fn context(&self, seq: u64) -> TemplateContext {
+        let mut ctx = TemplateContext::new(seq);
+        ctx.vars = self.vars.clone();
+        match &self.target {
+            Some(target) => {
+                let id = self
+                    .resources
+                    .pick(&target.kind)
+                    .unwrap_or_else(|| target.fallback.clone());
+                ctx.with_var("id", id)
+            }
+            None => ctx,
+        }
+    }
+}
+
+#[async_trait]
+impl LoadTestScenario for DeclarativeScenario {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn is_write(&self) -> bool {
+        self.write
+    }
+
+    fn weight(&self) -> u32 {
+        self.weight
+    }
+
+    fn think_time(&self) -> Duration {
+        self.think_time
+            .as_ref()
+            .map_or(Duration::ZERO, ThinkTime::sample)
+    }
+
+    async fn execute(&self, client: &reqwest::Client, base_url: &str) -> ScenarioResult {
+        let start = Instant::now();
+        let ctx = self.context(self.seq.fetch_add(1, Ordering::Relaxed));
+
+        let rendered = (|| {
+            let path = self.path.render(&ctx)?;
+            let body = self.body.as_ref().map(|b| b.render(&ctx)).transpose()?;
+            let headers = self
+                .headers
+                .iter()
+                .map(|(k, v)| Ok((k.clone(), v.render(&ctx)?)))
+                .collect::<Result<Vec<_>, crate::TemplateError>>()?;
+            Ok::<_, crate::TemplateError>((path, body, headers))
+        })();
+        let (path, body, headers) = match rendered {
+            Ok(rendered) => rendered,
+            Err(e) => return ScenarioResult::failed(start, 0, format!("template: {}", e)),
+        };
+
+        let url = format!("{}{}", base_url, path);
+        let mut request = client.request(self.method.clone(), &url);
+        let mut has_content_type = false;
+        for (name, value) in &headers {
+            has_content_type |= name.eq_ignore_ascii_case("content-type");
+            request = request.header(name.as_str(), value.as_str());
+        }
+        let mut bytes_sent = url.len() as u64;
+        if let Some(body) = body {
+            if !has_content_type {
+                request = request.header(reqwest::header::CONTENT_TYPE, "application/json");
+            }
+            bytes_sent += body.len() as u64;
+            request = request.body(body);
+        }
+
+        let response = match request.send().await {
+            Ok(response) => response,
+            Err(e) => return ScenarioResult::failed(start, bytes_sent, e.to_string()),
+        };
+        let status = response.status().as_u16();
+        let bytes = response.bytes().await.unwrap_or_default();
+        let latency = start.elapsed();
+
+        if let Err(reason) = self.assert.check(status, latency, &bytes) {
+            return ScenarioResult {
+                success: false,
+                latency_us: latency.as_micros() as u64,
+                bytes_sent,
+                bytes_received: bytes.len() as u64,
+                error: Some(format!("assert: {}: {}", self.name, reason)),
+            };
+        }
+
+        if let Some(capture) = &self.capture {
+            capture_id(
+                &self.name,
+                &self.resources,
+                &capture.kind,
+                &capture.pointer,
+                &bytes,
+            );
+        }
+
+        ScenarioResult {
+            success: true,
+            latency_us: latency.as_micros() as u64,
+            bytes_sent,
+            bytes_received: bytes.len() as u64,
+            error: None,
+        }
+    }
+
+    async fn cleanup(&self, client: &reqwest::Client, base_url: &str) -> CleanupSummary {
+        match (&self.capture, &self.cleanup) {
+            (Some(capture), Some(path)) => {
+                delete_created(
+                    client,
+                    base_url,
+                    &self.name,
+                    &self.resources,
+                    &capture.kind,
+                    path,
+                )
+                .await
+            }
+            _ => CleanupSummary::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../scenarios/explorer-mixed.yaml");
+
+    #[test]
+    fn test_example_file_builds() {
+        let file = ScenarioFile::from_yaml(EXAMPLE).unwrap();
+        let scenarios = file.build().unwrap();
+        assert_eq!(scenarios.len(), file.scenarios.len());
+
+        let strings = scenarios
+            .iter()
+            .find(|s| s.name() == "list_strings")
+            .unwrap();
+        assert!(!strings.is_write());
+        assert_eq!(strings.weight(), 10);
+
+        let submit = scenarios
+            .iter()
+            .find(|s| s.name() == "submit_project")
+            .unwrap();
+        assert!(submit.is_write());
+    }
+
+    #[test]
+    fn test_invalid_definitions() {
+        let unknown_placeholder = r#"
+scenarios:
+  - name: broken
+    path: /api/v1/strings?limit={{nope}}
+"#;
+        assert!(matches!(
+            ScenarioFile::from_yaml(unknown_placeholder)
+                .unwrap()
+                .build(),
+            Err(DslError::Invalid { .. })
+        ));
+
+        let unknown_field = r#"
+scenarios:
+  - name: typo
+    path: /api/v1/stats
+    wieght: 3
+"#;
+        assert!(matches!(
+            ScenarioFile::from_yaml(unknown_field),
+            Err(DslError::Parse(_))
+        ));
+
+        let duplicate = r#"
+scenarios:
+  - { name: a, path: /x }
+  - { name: a, path: /y }
+"#;
+        assert!(ScenarioFile::from_yaml(duplicate).unwrap().build().is_err());
+    }
+
+    #[test]
+    fn test_assertions() {
+        let spec: AssertionSpec = serde_yaml::from_str(
+            r#"
+status: [200, 201]
+max_latency_ms: 100
+body_contains: ["ok"]
+json:
+  - { pointer: /success, equals: true }
+  - { pointer: /error, exists: false }
+"#,
+        )
+        .unwrap();
+        let body = br#"{"success": true, "status": "ok"}"#;
+
+        assert!(spec.check(201, Duration::from_millis(10), body).is_ok());
+        assert_eq!(
+            spec.check(500, Duration::from_millis(10), body),
+            Err("status 500".to_string())
+        );
+        assert!(spec.check(200, Duration::from_millis(150), body).is_err());
+        assert!(spec
+            .check(
+                200,
+                Duration::from_millis(10),
+                br#"{"success": false, "ok": 1}"#
+            )
+            .is_err());
+
+        // No assertions: any 2xx passes
+        let default = AssertionSpec::default();
+        assert!(default.check(204, Duration::from_secs(5), b"").is_ok());
+        assert!(default.check(404, Duration::ZERO, b"").is_err());
+    }
+
+    #[test]
+    fn test_think_time() {
+        assert_eq!(ThinkTime::Fixed(20).sample(), Duration::from_millis(20));
+        let range = ThinkTime::Range {
+            min_ms: 10,
+            max_ms: 12,
+        };
+        for _ in 0..20 {
+            let sample = range.sample();
+            assert!(sample >= Duration::from_millis(10) && sample <= Duration::from_millis(12));
+        }
+    }
+}
@@ -0,0 +1,437 @@
+//! # Service Level Objectives
+//!
+//! Pass/fail thresholds for a run, for the whole run and per scenario:
+//!
+//! ```yaml
+//! overall:
+//!   max_p99_ms: 100
+//!   max_error_rate: 1.0   # percent
+//!   min_rps: 100
+//! scenarios:
+//!   list_strings:
+//!     max_p99_ms: 50
+//!     min_rps: 20
+//! ```
+//!
+//! Without a file the §8.2 API thresholds apply ([`SloConfig::spec_defaults`]).
+//! Results can be written as JSON or JUnit XML, and the CLI exits with one
+//! of the [`exit_code`] values so CI gates can tell a missed SLO from a
+//! broken run.
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{MetricsSummary, SpecCheck, SpecCheckResult};
+
+/// Process exit codes of the `rope-loadtest` binary
+pub mod exit_code {
+    /// Every SLO met
+    pub const PASS: i32 = 0;
+    /// The run completed but missed at least one SLO
+    pub const SLO_FAILED: i32 = 1;
+    /// Invalid arguments, scenario file or SLO file
+    pub const CONFIG_ERROR: i32 = 2;
+    /// The run itself failed (no requests completed, workers lost)
+    pub const RUN_FAILED: i32 = 3;
+}
+
+/// Thresholds applied to one set of results; unset fields are not checked
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SloThresholds {
+    /// Highest acceptable p50 latency
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_p50_ms: Option<f64>,
+
+    /// Highest acceptable p99 latency
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_p99_ms: Option<f64>,
+
+    /// Highest acceptable share of failed requests, in percent
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_error_rate: Option<f64>,
+
+    /// Lowest acceptable average throughput
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_rps: Option<f64>,
+}
+
+impl SloThresholds {
+    fn is_empty(&self) -> bool {
+        self == &SloThresholds::default()
+    }
+
+    fn validate(&self, scope: &str) -> Result<(), SloError> {
+        let fields = [
+            ("max_p50_ms", self.max_p50_ms),
+            ("max_p99_ms", self.max_p99_ms),
+            ("max_error_rate", self.max_error_rate),
+            ("min_rps", self.min_rps),
+        ];
+        for (field, value) in fields {
+            if let Some(value) = value {
+                if !value.is_finite() || value < 0.0 {
+                    return Err(SloError::Invalid(format!(
+                        "{}: {} must be a non-negative number",
+                        scope, field
+                    )));
+                }
+            }
+        }
+        if self.max_error_rate.is_some_and(|rate| rate > 100.0) {
+            return Err(SloError::Invalid(format!(
+                "{}: max_error_rate is a percentage (0-100)",
+                scope
+            )));
+        }
+        Ok(())
+    }
+
+    /// Checks for one set of results
+    fn evaluate(
+        &self,
+        scenario: Option<&str>,
+        p50_us: u64,
+        p99_us: u64,
+        success_rate: f64,
+        avg_rps: f64,
+    ) -> Vec<SpecCheck> {
+        let prefix = scenario.map(|s| format!("[{}] ", s)).unwrap_or_default();
+        let check = |name: String, passed: bool, actual: String, expected: String| SpecCheck {
+            name: format!("{}{}", prefix, name),
+            scenario: scenario.map(str::to_string),
+            passed,
+            actual,
+            expected,
+        };
+
+        let mut checks = Vec::new();
+        if let Some(max) = self.max_p50_ms {
+            checks.push(check(
+                format!("Latency p50 <= {}ms", max),
+                p50_us as f64 <= max * 1_000.0,
+                format!("{}µs", p50_us),
+                format!("<={}µs", max * 1_000.0),
+            ));
+        }
+        if let Some(max) = self.max_p99_ms {
+            checks.push(check(
+                format!("Latency p99 <= {}ms", max),
+                p99_us as f64 <= max * 1_000.0,
+                format!("{}µs", p99_us),
+                format!("<={}µs", max * 1_000.0),
+            ));
+        }
+        if let Some(max) = self.max_error_rate {
+            let error_rate = 100.0 - success_rate;
+            checks.push(check(
+                format!("Error rate <= {}%", max),
+                error_rate <= max,
+                format!("{:.2}%", error_rate),
+                format!("<={}%", max),
+            ));
+        }
+        if let Some(min) = self.min_rps {
+            checks.push(check(
+                format!("Throughput >= {} RPS", min),
+                avg_rps >= min,
+                format!("{:.2} RPS", avg_rps),
+                format!(">={} RPS", min),
+            ));
+        }
+        checks
+    }
+}
+
+/// SLOs for a run
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SloConfig {
+    /// Thresholds for the run as a whole
+    #[serde(default)]
+    pub overall: SloThresholds,
+
+    /// Thresholds for individual scenarios, by scenario name
+    #[serde(default)]
+    pub scenarios: BTreeMap<String, SloThresholds>,
+}
+
+impl Default for SloConfig {
+    fn default() -> Self {
+        Self::spec_defaults()
+    }
+}
+
+/// SLO file error
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SloError {
+    Io(String),
+    Parse(String),
+    Invalid(String),
+}
+
+impl std::fmt::Display for SloError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SloError::Io(e) => write!(f, "Cannot read SLO file: {}", e),
+            SloError::Parse(e) => write!(f, "Invalid SLO file: {}", e),
+            SloError::Invalid(e) => write!(f, "Invalid SLO: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for SloError {}
+
+impl SloConfig {
+    /// The §8.2 API thresholds: p99 under 100ms, under 1% errors, 100 RPS
+    pub fn spec_defaults() -> Self {
+        Self {
+            overall: SloThresholds {
+                max_p50_ms: None,
+                max_p99_ms: Some(100.0),
+                max_error_rate: Some(1.0),
+                min_rps: Some(100.0),
+            },
+            scenarios: BTreeMap::new(),
+        }
+    }
+
+    /// Parse SLOs from YAML (or JSON)
+    pub fn from_yaml(yaml: &str) -> Result<Self, SloError> {
+        let config: SloConfig =
+            serde_yaml::from_str(yaml).map_err(|e| SloError::Parse(e.to_string()))?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Read SLOs from a YAML or JSON file
+    pub fn load(path: &str) -> Result<Self, SloError> {
+        let yaml = std::fs::read_to_string(path).map_err(|e| SloError::Io(e.to_string()))?;
+        Self::from_yaml(&yaml)
+    }
+
+    pub fn validate(&self) -> Result<(), SloError> {
+        self.overall.validate("overall")?;
+        for (name, thresholds) in &self.scenarios {
+            thresholds.validate(name)?;
+        }
+        Ok(())
+    }
+
+    /// Check `summary` against every configured threshold
+    ///
+    /// A scenario with thresholds but no recorded requests fails, since a
+    /// scenario that never ran cannot have met its SLO.
+    pub fn evaluate(&self, summary: &MetricsSummary) -> SpecCheckResult {
+        let mut checks = self.overall.evaluate(
+            None,
+            summary.latency_p50_us,
+            summary.latency_p99_us,
+            summary.success_rate,
+            summary.avg_rps,
+        );
+
+        for (name, thresholds) in &self.scenarios {
+            if thresholds.is_empty() {
+                continue;
+            }
+            match summary.scenarios.get(name) {
+                Some(stats) => checks.extend(thresholds.evaluate(
+                    Some(name),
+                    stats.latency_p50_us,
+                    stats.latency_p99_us,
+                    stats.success_rate,
+                    stats.avg_rps,
+                )),
+                None => checks.push(SpecCheck {
+                    name: format!("[{}] Scenario ran", name),
+                    scenario: Some(name.clone()),
+                    passed: false,
+                    actual: "0 requests".to_string(),
+                    expected: ">0 requests".to_string(),
+                }),
+            }
+        }
+
+        SpecCheckResult {
+            passes: checks.iter().all(|c| c.passed),
+            checks,
+        }
+    }
+}
+
+impl SpecCheckResult {
+    /// Exit code for this result
+    pub fn exit_code(&self) -> i32 {
+        if self.passes {
+            exit_code::PASS
+        } else {
+            exit_code::SLO_FAILED
+        }
+    }
+
+    /// Render as a JUnit XML test suite, one test case per check
+    pub fn to_junit(&self, suite: &str, duration_secs: f64) -> String {
+        let failures = self.checks.iter().filter(|c| !c.passed).count();
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        let _ = writeln!(
+            xml,
+            "<testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" time=\"{:.3}\">",
+            xml_escape(suite),
+            self.checks.len(),
+            failures,
+            duration_secs
+        );
+
+        for check in &self.checks {
+            let classname = match &check.scenario {
+                Some(scenario) => format!("{}.{}", suite, scenario),
+                None => format!("{}.overall", suite),
+            };
+            let _ = write!(
+                xml,
+                "  <testcase classname=\"{}\" name=\"{}\"",
+                xml_escape(&classname),
+                xml_escape(&check.name)
+            );
+            if check.passed {
+                xml.push_str("/>\n");
+            } else {
+                let message = format!("actual {}, expected {}", check.actual, check.expected);
+                let _ = writeln!(
+                    xml,
+                    ">\n    <failure message=\"{}\"/>\n  </testcase>",
+                    xml_escape(&message)
+                );
+            }
+        }
+
+        xml.push_str("</testsuite>\n");
+        xml
+    }
+}
+
+/// Machine-readable SLO outcome written by `--slo-json`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SloReport {
+    pub passed: bool,
+    pub exit_code: i32,
+    pub slo: SloConfig,
+    pub checks: Vec<SpecCheck>,
+    pub summary: MetricsSummary,
+}
+
+impl SloReport {
+    pub fn new(slo: &SloConfig, result: &SpecCheckResult, summary: &MetricsSummary) -> Self {
+        Self {
+            passed: result.passes,
+            exit_code: result.exit_code(),
+            slo: slo.clone(),
+            checks: result.checks.clone(),
+            summary: summary.clone(),
+        }
+    }
+}
+
+fn xml_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::LoadTestMetrics;
+
+    fn summary() -> MetricsSummary {
+        let metrics = LoadTestMetrics::new();
+        for _ in 0..99 {
+            metrics.record_success(20_000, 10, 100);
+            metrics.record_scenario("list_strings", 20_000, true);
+        }
+        metrics.record_failure("timeout", 400_000);
+        metrics.record_scenario("stats", 400_000, false);
+        metrics.snapshot().summary()
+    }
+
+    #[test]
+    fn test_overall_and_scenario_slos() {
+        let slo = SloConfig::from_yaml(
+            r#"
+overall:
+  max_p99_ms: 500
+  max_error_rate: 1.5
+scenarios:
+  list_strings:
+    max_p99_ms: 25
+  stats:
+    max_error_rate: 0
+  submit_project:
+    min_rps: 1
+"#,
+        )
+        .unwrap();
+
+        let result = slo.evaluate(&summary());
+        assert!(!result.passes);
+        assert_eq!(result.exit_code(), exit_code::SLO_FAILED);
+
+        let passed: Vec<_> = result
+            .checks
+            .iter()
+            .map(|c| (c.name.as_str(), c.passed))
+            .collect();
+        assert!(passed.contains(&("Latency p99 <= 500ms", true)));
+        assert!(passed.contains(&("Error rate <= 1.5%", true)));
+        assert!(passed.contains(&("[list_strings] Latency p99 <= 25ms", true)));
+        assert!(passed.contains(&("[stats] Error rate <= 0%", false)));
+        assert!(passed.contains(&("[submit_project] Scenario ran", false)));
+    }
+
+    #[test]
+    fn test_invalid_slo_file() {
+        assert!(matches!(
+            SloConfig::from_yaml("overall:\n  max_error_rate: 150\n"),
+            Err(SloError::Invalid(_))
+        ));
+        assert!(matches!(
+            SloConfig::from_yaml("overall:\n  max_p99: 10\n"),
+            Err(SloError::Parse(_))
+        ));
+    }
+
+    #[test]
+    fn test_junit_output() {
+        let slo = SloConfig {
+            overall: SloThresholds {
+                max_p99_ms: Some(10.0),
+                ..Default::default()
+            },
+            scenarios: BTreeMap::from([(
+                "stats".to_string(),
+                SloThresholds {
+                    max_error_rate: Some(0.0),
+                    ..Default::default()
+                },
+            )]),
+        };
+        let xml = slo.evaluate(&summary()).to_junit("rope-loadtest", 12.5);
+
+        assert!(xml.contains("tests=\"2\" failures=\"2\" time=\"12.500\""));
+        assert!(xml.contains("classname=\"rope-loadtest.overall\" name=\"Latency p99 &lt;= 10ms\""));
+        assert!(xml.contains("classname=\"rope-loadtest.stats\""));
+        assert!(xml.contains("<failure message=\"actual 100.00%, expected &lt;=0%\"/>"));
+    }
+}
@@ -0,0 +1,704 @@
+//! # Write-Path Scenarios
+//!
+//! Scenarios that mutate state: project submissions, community creation,
+//! votes and signed transactions. Request bodies and paths are rendered from
+//! [`PayloadTemplate`]s so every request carries distinct data, and the IDs
+//! of created resources are kept in a shared [`CreatedResources`] pool so
+//! vote scenarios can target them and [`LoadTestScenario::cleanup`] can
+//! remove them once the run ends.
+//!
+//! ## Template placeholders
+//!
+//! | Placeholder              | Expands to                                  |
+//! |--------------------------|---------------------------------------------|
+//! | `{{seq}}`                | Per-scenario request sequence number        |
+//! | `{{timestamp}}`          | Unix time in seconds                        |
+//! | `{{rand_hex:N}}`         | `N` random bytes, hex encoded               |
+//! | `{{rand_int:MIN:MAX}}`   | Random integer in `MIN..=MAX`               |
+//! | `{{choice:a\|b\|c}}`     | One of the listed values                    |
+//! | `{{bool}}`               | `true` or `false`                           |
+//! | `{{name}}`               | Context variable, e.g. `{{id}}`             |
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Instant;
+
+use async_trait::async_trait;
+use parking_lot::RwLock;
+use rope_bridge::evm_invocation::{BridgeAccount, Eip1559Transaction, FeeParams, NonceManager};
+use rope_crypto::keys::KeyStore;
+use tracing::{debug, warn};
+
+use crate::{CleanupSummary, LoadTestScenario, ScenarioResult};
+
+// ============================================================================
+// PAYLOAD TEMPLATES
+// ============================================================================
+
+/// Values available to a template while rendering
+#[derive(Debug, Clone, Default)]
+pub struct TemplateContext {
+    pub seq: u64,
+    pub vars: HashMap<String, String>,
+}
+
+impl TemplateContext {
+    pub fn new(seq: u64) -> Self {
+        Self {
+            seq,
+            vars: HashMap::new(),
+        }
+    }
+
+    /// Bind `{{name}}` to `value`
+    pub fn with_var(mut self, name: &str, value: impl Into<String>) -> Self {
+        self.vars.insert(name.to_string(), value.into());
+        self
+    }
+}
+
+/// Template rendering error
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TemplateError {
+    /// `{{` without a closing `}}`
+    Unterminated(usize),
+    /// Placeholder with no generator or variable
+    UnknownPlaceholder(String),
+    /// Generator arguments that do not parse
+    InvalidArguments(String),
+}
+
+impl std::fmt::Display for TemplateError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TemplateError::Unterminated(at) => write!(f, "Unterminated placeholder at {}", at),
+            TemplateError::UnknownPlaceholder(p) => write!(f, "Unknown placeholder: {}", p),
+            TemplateError::InvalidArguments(p) => write!(f, "Invalid arguments: {}", p),
+        }
+    }
+}
+
+impl std::error::Error for TemplateError {}
+
+/// Text with `{{...}}` placeholders, rendered per request
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PayloadTemplate {
+    source: String,
+}
+
+impl PayloadTemplate {
+    pub fn new(source: impl Into<String>) -> Self {
+        Self {
+            source: source.into(),
+        }
+    }
+
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Render the template, expanding every placeholder
+    pub fn render(&self, ctx: &TemplateContext) -> Result<String, TemplateError> {
+        let mut out = String::with_capacity(self.source.len());
+        let mut rest = self.source.as_str();
+        let mut offset = 0;
+
+        while let Some(start) = rest.find("{{") {
+            out.push_str(&rest[..start]);
+            let after = &rest[start + 2..];
+            let end = after
+                .find("}}")
+                .ok_or(TemplateError::Unterminated(offset + start))?;
+            out.push_str(&Self::expand(after[..end].trim(), ctx)?);
+
+            let consumed = start + 2 + end + 2;
+            rest = &rest[consumed..];
+            offset += consumed;
+        }
+        out.push_str(rest);
+        Ok(out)
+    }
+
+    fn expand(placeholder: &str, ctx: &TemplateContext) -> Result<String, TemplateError> {
+        let invalid = || TemplateError::InvalidArguments(placeholder.to_string());
+        let (name, args) = placeholder
+            .split_once(':')
+            .map_or((placeholder, None), |(n, a)| (n, Some(a)));
+
+        match (name, args) {
+            ("seq", None) => Ok(ctx.seq.to_string()),
+            ("timestamp", None) => Ok(chrono::Utc::now().timestamp().to_string()),
+            ("bool", None) => Ok(rand::random::<bool>().to_string()),
+            ("rand_hex", Some(len)) => {
+                let len: usize = len.parse().map_err(|_| invalid())?;
+                let bytes: Vec<u8> = (0..len).map(|_| rand::random::<u8>()).collect();
+                Ok(hex::encode(bytes))
+            }
+            ("rand_int", Some(range)) => {
+                let (min, max) = range.split_once(':').ok_or_else(invalid)?;
+                let min: i64 = min.parse().map_err(|_| invalid())?;
+                let max: i64 = max.parse().map_err(|_| invalid())?;
+                if max < min {
+                    return Err(invalid());
+                }
+                let span = (max - min) as u64 + 1;
+                Ok((min + (rand::random::<u64>() % span) as i64).to_string())
+            }
+            ("choice", Some(options)) => {
+                let options: Vec<&str> = options.split('|').collect();
+                Ok(options[rand::random::<usize>() % options.len()].to_string())
+            }
+            _ => ctx
+                .vars
+                .get(placeholder)
+                .cloned()
+                .ok_or_else(|| TemplateError::UnknownPlaceholder(placeholder.to_string())),
+        }
+    }
+}
+
+// ============================================================================
+// CREATED RESOURCES
+// ============================================================================
+
+/// IDs of resources created during a run, grouped by kind
+#[derive(Debug, Default)]
+pub struct CreatedResources {
+    ids: RwLock<HashMap<String, Vec<String>>>,
+}
+
+impl CreatedResources {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record(&self, kind: &str, id: String) {
+        self.ids
+            .write()
+            .entry(kind.to_string())
+            .or_default()
+            .push(id);
+    }
+
+    /// A random ID of `kind`, if any were created
+    pub fn pick(&self, kind: &str) -> Option<String> {
+        let ids = self.ids.read();
+        let ids = ids.get(kind).filter(|ids| !ids.is_empty())?;
+        Some(ids[rand::random::<usize>() % ids.len()].clone())
+    }
+
+    /// Remove and return all IDs of `kind`
+    pub fn drain(&self, kind: &str) -> Vec<String> {
+        self.ids.write().remove(kind).unwrap_or_default()
+    }
+
+    pub fn count(&self, kind: &str) -> usize {
+        self.ids.read().get(kind).map_or(0, Vec::len)
+    }
+}
+
+// ============================================================================
+// HTTP WRITE SCENARIO
+// ============================================================================
+
+/// Templated write request against the explorer API
+pub struct HttpWriteScenario {
+    name: String,
+    method: reqwest::Method,
+    path: PayloadTemplate,
+    body: Option<PayloadTemplate>,
+
+    /// Resource kind bound to `{{id}}`, with the ID used before any exist
+    target: Option<(String, String)>,
+
+    /// Resource kind recorded from the response, and its JSON pointer
+    creates: Option<(String, String)>,
+
+    /// Path template (with `{{id}}`) deleting a created resource
+    cleanup_path: Option<PayloadTemplate>,
+
+    resources: Arc<CreatedResources>,
+    seq: AtomicU64,
+}
+
+impl HttpWriteScenario {
+    /// POST `body` to `path`
+    pub fn post(
+        name: &str,
+        path: &str,
+        body: PayloadTemplate,
+        resources: Arc<CreatedResources>,
+    ) -> Self {
+        Self {
+            name: name.to_string(),
+            method: reqwest::Method::POST,
+            path: PayloadTemplate::new(path),
+            body: Some(body),
+            target: None,
+            creates: None,
+            cleanup_path: None,
+            resources,
+            seq: AtomicU64::new(0),
+        }
+    }
+
+    /// Record the ID at `pointer` in each response as a `kind` resource
+    pub fn creates(mut self, kind: &str, pointer: &str) -> Self {
+        self.creates = Some((kind.to_string(), pointer.to_string()));
+        self
+    }
+
+    /// Bind `{{id}}` to a created `kind` resource, or `fallback_id` before one exists
+    pub fn targets(mut self, kind: &str, fallback_id: &str) -> Self {
+        self.target = Some((kind.to_string(), fallback_id.to_string()));
+        self
+    }
+
+    /// Delete created resources at `path` when the run ends
+    pub fn cleanup_with(mut self, path: &str) -> Self {
+        self.cleanup_path = Some(PayloadTemplate::new(path));
+        self
+    }
+
+    /// `POST /api/v1/projects`
+    pub fn submit_project(resources: Arc<CreatedResources>) -> Self {
+        Self::post(
+            "submit_project",
+            "/api/v1/projects",
+            PayloadTemplate::new(SUBMIT_PROJECT_TEMPLATE),
+            resources,
+        )
+        .creates("project", "/project/id")
+        .cleanup_with("/api/v1/projects/{{id}}")
+    }
+
+    /// `POST /api/v1/communities`
+    pub fn create_community(resources: Arc<CreatedResources>) -> Self {
+        Self::post(
+            "create_community",
+            "/api/v1/communities",
+            PayloadTemplate::new(CREATE_COMMUNITY_TEMPLATE),
+            resources,
+        )
+        .creates("community", "/community/id")
+        .cleanup_with("/api/v1/communities/{{id}}")
+    }
+
+    /// `POST /api/v1/projects/{id}/vote`
+    pub fn vote_project(resources: Arc<CreatedResources>) -> Self {
+        Self::post(
+            "vote_project",
+            "/api/v1/projects/{{id}}/vote",
+            PayloadTemplate::new(VOTE_TEMPLATE),
+            resources,
+        )
+        .targets("project", "proj-loadtest")
+    }
+
+    /// `POST /api/v1/communities/{id}/vote`
+    pub fn vote_community(resources: Arc<CreatedResources>) -> Self {
+        Self::post(
+            "vote_community",
+            "/api/v1/communities/{{id}}/vote",
+            PayloadTemplate::new(VOTE_TEMPLATE),
+            resources,
+        )
+        .targets("community", "comm-loadtest")
+    }
+
+    fn context(&self) -> TemplateContext {
+        let ctx = TemplateContext::new(self.seq.fetch_add(1, Ordering::Relaxed));
+        match &self.target {
+            Some((kind, fallback)) => {
+                let id = self
+                    .resources
+                    .pick(kind)
+                    .unwrap_or_else(|| fallback.clone());
+                ctx.with_var("id", id)
+            }
+            None => ctx,
+        }
+    }
+}
+
+#[async_trait]
+impl LoadTestScenario for HttpWriteScenario {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn is_write(&self) -> bool {
+        true
+    }
+
+    async fn execute(&self, client: &reqwest::Client, base_url: &str) -> ScenarioResult {
+        let start = Instant::now();
+        let ctx = self.context();
+
+        let rendered = self.path.render(&ctx).and_then(|path| {
+            let body = self.body.as_ref().map(|b| b.render(&ctx)).transpose()?;
+            Ok((path, body))
+        });
+        let (path, body) = match rendered {
+            Ok(rendered) => rendered,
+            Err(e) => return ScenarioResult::failed(start, 0, format!("template: {}", e)),
+        };
+
+        let url = format!("{}{}", base_url, path);
+        let body = body.unwrap_or_default();
+        let bytes_sent = (url.len() + body.len()) as u64;
+
+        let response = client
+            .request(self.method.clone(), &url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(body)
+            .send()
+            .await;
+
+        match response {
+            Ok(response) => {
+                let status = response.status();
+                let bytes = response.bytes().await.unwrap_or_default();
+                if !status.is_success() {
+                    return ScenarioResult::failed(
+                        start,
+                        bytes_sent,
+                        format!("http {}: {}", status.as_u16(), self.name),
+                    );
+                }
+
+                if let Some((kind, pointer)) = &self.creates {
+                    capture_id(&self.name, &self.resources, kind, pointer, &bytes);
+                }
+
+                ScenarioResult {
+                    success: true,
+                    latency_us: start.elapsed().as_micros() as u64,
+                    bytes_sent,
+                    bytes_received: bytes.len() as u64,
+                    error: None,
+                }
+            }
+            Err(e) => ScenarioResult::failed(start, bytes_sent, e.to_string()),
+        }
+    }
+
+    async fn cleanup(&self, client: &reqwest::Client, base_url: &str) -> CleanupSummary {
+        match (&self.creates, &self.cleanup_path) {
+            (Some((kind, _)), Some(path)) => {
+                delete_created(client, base_url, &self.name, &self.resources, kind, path).await
+            }
+            _ => CleanupSummary::default(),
+        }
+    }
+}
+
+/// Record the string ID at `pointer` in a JSON response body
+pub(crate) fn capture_id(
+    scenario: &str,
+    resources: &CreatedResources,
+    kind: &str,
+    pointer: &str,
+    body: &[u8],
+) {
+    let id = serde_json::from_slice::<serde_json::Value>(body)
+        .ok()
+        .and_then(|v| {
+            v.pointer(pointer)
+                .and_then(|id| id.as_str())
+                .map(String::from)
+        });
+    match id {
+        Some(id) => resources.record(kind, id),
+        None => debug!("{}: no {} ID at {}", scenario, kind, pointer),
+    }
+}
+
+/// DELETE every created `kind` resource at the rendered `path`
+pub(crate) async fn delete_created(
+    client: &reqwest::Client,
+    base_url: &str,
+    scenario: &str,
+    resources: &CreatedResources,
+    kind: &str,
+    path: &PayloadTemplate,
+) -> CleanupSummary {
+    let mut summary = CleanupSummary::default();
+
+    for id in resources.drain(kind) {
+        summary.attempted += 1;
+        let path = match path.render(&TemplateContext::new(0).with_var("id", id.as_str())) {
+            Ok(path) => path,
+            Err(e) => {
+                warn!("{}: cleanup template failed: {}", scenario, e);
+                continue;
+            }
+        };
+
+        match client.delete(format!("{}{}", base_url, path)).send().await {
+            Ok(response) if response.status().is_success() => summary.removed += 1,
+            Ok(response) => debug!(
+                "{}: cleanup of {} returned {}",
+                scenario,
+                id,
+                response.status()
+            ),
+            Err(e) => debug!("{}: cleanup of {} failed: {}", scenario, id, e),
+        }
+    }
+    summary
+}
+
+const SUBMIT_PROJECT_TEMPLATE: &str = r#"{
+  "name": "loadtest-project-{{seq}}-{{rand_hex:4}}",
+  "tagline": "Load test submission",
+  "description": "Generated by rope-loadtest at {{timestamp}}",
+  "category": "{{choice:defi|identity|supply-chain|healthcare}}",
+  "stage": "{{choice:idea|prototype|mvp}}",
+  "organization_type": "individual",
+  "organization_name": null,
+  "submitter_name": "loadtest",
+  "submitter_email": "loadtest+{{seq}}@example.invalid",
+  "tech_stack": ["rust"],
+  "architecture_description": null,
+  "features": [],
+  "use_cases": null,
+  "target_users": null,
+  "requires_ai_testimony": {{bool}},
+  "whitepaper_url": null,
+  "documentation_url": null,
+  "github_url": null,
+  "website_url": null,
+  "demo_url": null,
+  "team_members": [],
+  "milestones": [],
+  "funding_requested": {{rand_int:1000:100000}},
+  "funding_currency": "FAT",
+  "funding_breakdown": null
+}"#;
+
+const CREATE_COMMUNITY_TEMPLATE: &str = r#"{
+  "name": "loadtest-community-{{seq}}-{{rand_hex:4}}",
+  "description": "Generated by rope-loadtest at {{timestamp}}",
+  "federation_id": null,
+  "community_type": "{{choice:research|enterprise|public}}",
+  "scale": "{{choice:local|regional|global}}",
+  "protocols": ["rope"]
+}"#;
+
+const VOTE_TEMPLATE: &str = r#"{
+  "vote_for": {{bool}},
+  "comment": "loadtest vote {{seq}}"
+}"#;
+
+// ============================================================================
+// TRANSACTION SUBMISSION
+// ============================================================================
+
+/// Submits signed EIP-1559 transfers via `eth_sendRawTransaction`
+///
+/// Signing accounts are derived from a freshly generated keystore, so each
+/// run uses its own accounts and nonces start from zero.
+pub struct SubmitTransactionScenario {
+    rpc_url: String,
+    chain_id: u64,
+    accounts: Vec<BridgeAccount>,
+    nonces: NonceManager,
+    fees: FeeParams,
+    next_account: AtomicUsize,
+    seq: AtomicU64,
+}
+
+impl SubmitTransactionScenario {
+    /// Scenario signing with `accounts` generated keys
+    pub fn new(rpc_url: &str, chain_id: u64, accounts: u32) -> Self {
+        let keystore = KeyStore::new();
+        let accounts = (0..accounts.max(1))
+            .map(|i| {
+                BridgeAccount::from_keystore(&keystore, chain_id, i)
+                    .expect("derived key is a valid secp256k1 scalar")
+            })
+            .collect();
+
+        Self {
+            rpc_url: rpc_url.to_string(),
+            chain_id,
+            accounts,
+            nonces: NonceManager::new(),
+            fees: FeeParams::default(),
+            next_account: AtomicUsize::new(0),
+            seq: AtomicU64::new(0),
+        }
+    }
+
+    /// Addresses of the signing accounts
+    pub fn addresses(&self) -> Vec<[u8; 20]> {
+        self.accounts.iter().map(BridgeAccount::address).collect()
+    }
+}
+
+#[async_trait]
+impl LoadTestScenario for SubmitTransactionScenario {
+    fn name(&self) -> &str {
+        "submit_transaction"
+    }
+
+    fn is_write(&self) -> bool {
+        true
+    }
+
+    async fn execute(&self, client: &reqwest::Client, _base_url: &str) -> ScenarioResult {
+        let start = Instant::now();
+        let account =
+            &self.accounts[self.next_account.fetch_add(1, Ordering::Relaxed) % self.accounts.len()];
+        let sender = account.address();
+        let nonce = self.nonces.reserve(&sender);
+
+        let mut tx = Eip1559Transaction {
+            chain_id: self.chain_id,
+            nonce,
+            max_priority_fee_per_gas: self.fees.max_priority_fee_per_gas,
+            max_fee_per_gas: self.fees.max_fee_per_gas,
+            gas_limit: 21_000,
+            to: Some(rand::random()),
+            value: 1,
+            data: Vec::new(),
+            access_list: Vec::new(),
+            signature: None,
+        };
+        let raw = match account.sign(&mut tx).and_then(|_| tx.encode()) {
+            Ok(raw) => raw,
+            Err(e) => {
+                self.nonces.release(&sender, nonce);
+                return ScenarioResult::failed(start, 0, format!("signing: {}", e));
+            }
+        };
+
+        let request = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": self.seq.fetch_add(1, Ordering::Relaxed),
+            "method": "eth_sendRawTransaction",
+            "params": [format!("0x{}", hex::encode(raw))],
+        });
+        let body = request.to_string();
+        let bytes_sent = body.len() as u64;
+
+        let response = client
+            .post(&self.rpc_url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(body)
+            .send()
+            .await;
+
+        match response {
+            Ok(response) => {
+                let bytes = response.bytes().await.unwrap_or_default();
+                let rpc_error = match serde_json::from_slice::<serde_json::Value>(&bytes) {
+                    Ok(v) if v.get("error").is_some() => Some(format!("rpc: {}", v["error"])),
+                    Ok(v) if v.get("result").is_some() => None,
+                    _ => Some("rpc: invalid response".to_string()),
+                };
+
+                match rpc_error {
+                    None => ScenarioResult {
+                        success: true,
+                        latency_us: start.elapsed().as_micros() as u64,
+                        bytes_sent,
+                        bytes_received: bytes.len() as u64,
+                        error: None,
+                    },
+                    Some(error) => {
+                        // Rejected transactions leave the nonce unused
+                        self.nonces.release(&sender, nonce);
+                        ScenarioResult::failed(start, bytes_sent, error)
+                    }
+                }
+            }
+            Err(e) => {
+                self.nonces.release(&sender, nonce);
+                ScenarioResult::failed(start, bytes_sent, e.to_string())
+            }
+        }
+    }
+}
+
+/// Write scenarios sharing one resource pool
+pub fn default_write_scenarios(
+    rpc_url: Option<&str>,
+    chain_id: u64,
+) -> Vec<Arc<dyn LoadTestScenario>> {
+    let resources = Arc::new(CreatedResources::new());
+    let mut scenarios: Vec<Arc<dyn LoadTestScenario>> = vec![
+        Arc::new(HttpWriteScenario::submit_project(resources.clone())),
+        Arc::new(HttpWriteScenario::create_community(resources.clone())),
+        Arc::new(HttpWriteScenario::vote_project(resources.clone())),
+        Arc::new(HttpWriteScenario::vote_community(resources)),
+    ];
+    if let Some(rpc_url) = rpc_url {
+        scenarios.push(Arc::new(SubmitTransactionScenario::new(
+            rpc_url, chain_id, 8,
+        )));
+    }
+    scenarios
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_template_rendering() {
+        let ctx = TemplateContext::new(7).with_var("id", "proj-1");
+        let template = PayloadTemplate::new(
+            r#"{"path":"/projects/{{id}}","seq":{{seq}},"hex":"{{rand_hex:4}}","n":{{rand_int:5:5}},"c":"{{choice:only}}"}"#,
+        );
+        let rendered: serde_json::Value =
+            serde_json::from_str(&template.render(&ctx).unwrap()).unwrap();
+
+        assert_eq!(rendered["path"], "/projects/proj-1");
+        assert_eq!(rendered["seq"], 7);
+        assert_eq!(rendered["hex"].as_str().unwrap().len(), 8);
+        assert_eq!(rendered["n"], 5);
+        assert_eq!(rendered["c"], "only");
+
+        assert_eq!(
+            PayloadTemplate::new("{{missing}}").render(&ctx),
+            Err(TemplateError::UnknownPlaceholder("missing".to_string()))
+        );
+        assert_eq!(
+            PayloadTemplate::new("a {{seq").render(&ctx),
+            Err(TemplateError::Unterminated(2))
+        );
+        assert!(matches!(
+            PayloadTemplate::new("{{rand_int:9:1}}").render(&ctx),
+            Err(TemplateError::InvalidArguments(_))
+        ));
+    }
+
+    #[test]
+    fn test_builtin_templates_render_valid_json() {
+        let ctx = TemplateContext::new(1);
+        for template in [
+            SUBMIT_PROJECT_TEMPLATE,
+            CREATE_COMMUNITY_TEMPLATE,
+            VOTE_TEMPLATE,
+        ] {
+            let rendered = PayloadTemplate::new(template).render(&ctx).unwrap();
+            assert!(serde_json::from_str::<serde_json::Value>(&rendered).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_created_resources() {
+        let resources = Arc::new(CreatedResources::new());
+        let vote = HttpWriteScenario::vote_project(resources.clone());
+        assert_eq!(vote.context().vars["id"], "proj-loadtest");
+
+        resources.record("project", "proj-abc".to_string());
+        assert_eq!(vote.context().vars["id"], "proj-abc");
+        assert_eq!(resources.count("project"), 1);
+        assert_eq!(resources.drain("project"), vec!["proj-abc".to_string()]);
+        assert_eq!(resources.pick("project"), None);
+    }
+}
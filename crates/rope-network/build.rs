@@ -0,0 +1,8 @@
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    tonic_build::configure()
+        .build_server(true)
+        .build_client(true)
+        .compile(&["proto/rope_rpc.proto"], &["proto"])?;
+    println!("cargo:rerun-if-changed=proto/rope_rpc.proto");
+    Ok(())
+}
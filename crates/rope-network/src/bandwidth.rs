@@ -0,0 +1,308 @@
+//! # Bandwidth Accounting
+//!
+//! Byte counters per peer and per channel. Every layer that moves bytes
+//! records them in a shared [`NetworkStats`]:
+//!
+//! | Channel            | Recorded by                     |
+//! |--------------------|---------------------------------|
+//! | `ValidatorGossip`  | swarm gossipsub publish/receive |
+//! | `Rdp`              | piece uploads and downloads     |
+//! | `Rpc`              | gRPC client service             |
+//! | `BridgeRelay`      | bridge relayers                 |
+//!
+//! Bytes received from a peer over RDP are that peer's *measured* upload.
+//! [`NetworkStats::take_measured_uploads`] drains them per epoch for the
+//! incentive ledger, so rewards follow observed traffic rather than the
+//! speeds peers advertise about themselves.
+//!
+//! Peers are keyed by 32-byte node IDs. Callers that only know a libp2p
+//! peer ID or an RPC identity use [`peer_key`].
+
+use crate::peer::PeerId;
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Traffic channel
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum TrafficChannel {
+    /// Validator gossip over libp2p
+    ValidatorGossip,
+    /// String distribution
+    Rdp,
+    /// Client RPC
+    Rpc,
+    /// Bridge relay
+    BridgeRelay,
+}
+
+impl TrafficChannel {
+    /// All channels
+    pub const ALL: [TrafficChannel; 4] = [
+        TrafficChannel::ValidatorGossip,
+        TrafficChannel::Rdp,
+        TrafficChannel::Rpc,
+        TrafficChannel::BridgeRelay,
+    ];
+}
+
+/// Stable key for a peer known by another identifier
+pub fn peer_key(id: &[u8]) -> PeerId {
+    *blake3::hash(id).as_bytes()
+}
+
+/// Byte and message counters
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ByteCounters {
+    pub bytes_sent: u64,
+    pub bytes_received: u64,
+    pub messages_sent: u64,
+    pub messages_received: u64,
+}
+
+impl ByteCounters {
+    fn add(&mut self, other: &ByteCounters) {
+        self.bytes_sent += other.bytes_sent;
+        self.bytes_received += other.bytes_received;
+        self.messages_sent += other.messages_sent;
+        self.messages_received += other.messages_received;
+    }
+}
+
+/// Traffic with one peer
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct PeerTraffic {
+    /// Counters by channel
+    pub channels: HashMap<TrafficChannel, ByteCounters>,
+}
+
+impl PeerTraffic {
+    /// Counters for one channel
+    pub fn channel(&self, channel: TrafficChannel) -> ByteCounters {
+        self.channels.get(&channel).cloned().unwrap_or_default()
+    }
+
+    /// Counters across all channels
+    pub fn total(&self) -> ByteCounters {
+        let mut total = ByteCounters::default();
+        for counters in self.channels.values() {
+            total.add(counters);
+        }
+        total
+    }
+}
+
+/// Serializable view of all counters
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct NetworkStatsSnapshot {
+    /// Totals by channel
+    pub channels: HashMap<TrafficChannel, ByteCounters>,
+    /// Per-peer traffic (hex peer ID)
+    pub peers: HashMap<String, PeerTraffic>,
+}
+
+#[derive(Default)]
+struct Counters {
+    peers: HashMap<PeerId, PeerTraffic>,
+    /// Channel totals, including traffic not attributable to a peer
+    channels: HashMap<TrafficChannel, ByteCounters>,
+    /// Bytes received per peer and channel since the last drain
+    unclaimed: HashMap<(PeerId, TrafficChannel), u64>,
+}
+
+/// Shared bandwidth counters
+#[derive(Default)]
+pub struct NetworkStats {
+    counters: RwLock<Counters>,
+}
+
+impl NetworkStats {
+    /// Create empty counters
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record bytes sent to `peer`
+    pub fn record_sent(&self, peer: &PeerId, channel: TrafficChannel, bytes: usize) {
+        let mut counters = self.counters.write();
+        let entry = counters
+            .peers
+            .entry(*peer)
+            .or_default()
+            .channels
+            .entry(channel)
+            .or_default();
+        entry.bytes_sent += bytes as u64;
+        entry.messages_sent += 1;
+
+        let total = counters.channels.entry(channel).or_default();
+        total.bytes_sent += bytes as u64;
+        total.messages_sent += 1;
+    }
+
+    /// Record bytes received from `peer`
+    pub fn record_received(&self, peer: &PeerId, channel: TrafficChannel, bytes: usize) {
+        let mut counters = self.counters.write();
+        let entry = counters
+            .peers
+            .entry(*peer)
+            .or_default()
+            .channels
+            .entry(channel)
+            .or_default();
+        entry.bytes_received += bytes as u64;
+        entry.messages_received += 1;
+
+        let total = counters.channels.entry(channel).or_default();
+        total.bytes_received += bytes as u64;
+        total.messages_received += 1;
+
+        *counters.unclaimed.entry((*peer, channel)).or_default() += bytes as u64;
+    }
+
+    /// Record bytes broadcast without a single recipient (e.g. gossipsub publish)
+    pub fn record_broadcast(&self, channel: TrafficChannel, bytes: usize) {
+        let mut counters = self.counters.write();
+        let total = counters.channels.entry(channel).or_default();
+        total.bytes_sent += bytes as u64;
+        total.messages_sent += 1;
+    }
+
+    /// Traffic with one peer
+    pub fn peer(&self, peer: &PeerId) -> Option<PeerTraffic> {
+        self.counters.read().peers.get(peer).cloned()
+    }
+
+    /// Totals for one channel
+    pub fn channel(&self, channel: TrafficChannel) -> ByteCounters {
+        self.counters
+            .read()
+            .channels
+            .get(&channel)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Totals across all channels
+    pub fn total(&self) -> ByteCounters {
+        let mut total = ByteCounters::default();
+        for counters in self.counters.read().channels.values() {
+            total.add(counters);
+        }
+        total
+    }
+
+    /// Peers ranked by bytes received from them on `channel`
+    pub fn top_uploaders(&self, channel: TrafficChannel, limit: usize) -> Vec<(PeerId, u64)> {
+        let mut peers: Vec<(PeerId, u64)> = self
+            .counters
+            .read()
+            .peers
+            .iter()
+            .map(|(peer, traffic)| (*peer, traffic.channel(channel).bytes_received))
+            .filter(|(_, bytes)| *bytes > 0)
+            .collect();
+        peers.sort_by(|a, b| b.1.cmp(&a.1));
+        peers.truncate(limit);
+        peers
+    }
+
+    /// Drain bytes each peer uploaded to us on `channel` since the last call
+    pub fn take_measured_uploads(&self, channel: TrafficChannel) -> HashMap<PeerId, u64> {
+        let mut counters = self.counters.write();
+        let mut uploads = HashMap::new();
+        counters.unclaimed.retain(|(peer, ch), bytes| {
+            if *ch != channel {
+                return true;
+            }
+            uploads.insert(*peer, *bytes);
+            false
+        });
+        uploads
+    }
+
+    /// Forget a peer's per-peer counters (channel totals are kept)
+    pub fn remove_peer(&self, peer: &PeerId) {
+        let mut counters = self.counters.write();
+        counters.peers.remove(peer);
+        counters.unclaimed.retain(|(p, _), _| p != peer);
+    }
+
+    /// Serializable view of all counters
+    pub fn snapshot(&self) -> NetworkStatsSnapshot {
+        let counters = self.counters.read();
+        NetworkStatsSnapshot {
+            channels: counters.channels.clone(),
+            peers: counters
+                .peers
+                .iter()
+                .map(|(peer, traffic)| (hex::encode(peer), traffic.clone()))
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_per_peer_and_channel_counters() {
+        let stats = NetworkStats::new();
+        let (a, b) = ([1u8; 32], [2u8; 32]);
+
+        stats.record_sent(&a, TrafficChannel::Rdp, 1000);
+        stats.record_received(&a, TrafficChannel::Rdp, 400);
+        stats.record_received(&a, TrafficChannel::ValidatorGossip, 50);
+        stats.record_sent(&b, TrafficChannel::Rpc, 10);
+        stats.record_broadcast(TrafficChannel::ValidatorGossip, 300);
+
+        let traffic = stats.peer(&a).unwrap();
+        assert_eq!(traffic.channel(TrafficChannel::Rdp).bytes_sent, 1000);
+        assert_eq!(traffic.total().bytes_received, 450);
+
+        let gossip = stats.channel(TrafficChannel::ValidatorGossip);
+        assert_eq!(gossip.bytes_sent, 300);
+        assert_eq!(gossip.bytes_received, 50);
+        assert_eq!(stats.total().bytes_sent, 1310);
+        assert_eq!(
+            stats.channel(TrafficChannel::BridgeRelay),
+            ByteCounters::default()
+        );
+
+        assert_eq!(stats.snapshot().peers.len(), 2);
+    }
+
+    #[test]
+    fn test_measured_uploads_drain_per_epoch() {
+        let stats = NetworkStats::new();
+        let (a, b) = ([1u8; 32], [2u8; 32]);
+
+        stats.record_received(&a, TrafficChannel::Rdp, 300);
+        stats.record_received(&a, TrafficChannel::Rdp, 200);
+        stats.record_received(&b, TrafficChannel::Rdp, 100);
+        stats.record_received(&b, TrafficChannel::ValidatorGossip, 999);
+
+        assert_eq!(stats.top_uploaders(TrafficChannel::Rdp, 1), vec![(a, 500)]);
+
+        let uploads = stats.take_measured_uploads(TrafficChannel::Rdp);
+        assert_eq!(uploads.get(&a), Some(&500));
+        assert_eq!(uploads.get(&b), Some(&100));
+        assert!(stats.take_measured_uploads(TrafficChannel::Rdp).is_empty());
+
+        // Other channels and cumulative counters are untouched
+        assert_eq!(
+            stats
+                .take_measured_uploads(TrafficChannel::ValidatorGossip)
+                .get(&b),
+            Some(&999)
+        );
+        assert_eq!(stats.channel(TrafficChannel::Rdp).bytes_received, 600);
+    }
+
+    #[test]
+    fn test_peer_key_is_stable() {
+        assert_eq!(peer_key(b"jwt:alice"), peer_key(b"jwt:alice"));
+        assert_ne!(peer_key(b"jwt:alice"), peer_key(b"jwt:bob"));
+    }
+}
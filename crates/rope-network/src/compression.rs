@@ -0,0 +1,645 @@
+//! # Wire Compression and Batching
+//!
+//! Gossip payloads are framed before they hit the wire:
+//!
+//! ```text
+//! ┌────────┬───────┬──────────────────────────────────────────┐
+//! │ 0xC5   │ flags │ body (optionally LZ4/zstd compressed)    │
+//! └────────┴───────┴──────────────────────────────────────────┘
+//! flags: bits 0-1 codec (0 none, 1 LZ4, 2 zstd), bit 7 batch
+//! batch body: u32 count, then (u32 len, bytes) per message
+//! ```
+//!
+//! Small messages published to the same topic within `batch_interval`
+//! are coalesced into one frame, and frames above the channel threshold
+//! are compressed. Channels are selected by topic prefix, so validator
+//! testimonies and string payloads can use different settings.
+//!
+//! Payloads without the magic byte are passed through unchanged, which
+//! keeps nodes that predate framing interoperable. Typed topic messages
+//! begin with a small bincode enum tag and legacy topics carry text, so
+//! neither starts with `0xC5`.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+use thiserror::Error;
+
+/// First byte of every framed payload
+const FRAME_MAGIC: u8 = 0xC5;
+
+/// Batch flag in the frame header
+const FLAG_BATCH: u8 = 0x80;
+
+/// Codec bits in the frame header
+const CODEC_MASK: u8 = 0x03;
+
+/// Compression algorithm
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Codec {
+    /// Uncompressed
+    None,
+    /// LZ4 (fast, moderate ratio)
+    Lz4,
+    /// Zstandard (slower, better ratio)
+    Zstd,
+}
+
+impl Codec {
+    fn bits(self) -> u8 {
+        match self {
+            Codec::None => 0,
+            Codec::Lz4 => 1,
+            Codec::Zstd => 2,
+        }
+    }
+
+    fn from_bits(bits: u8) -> Result<Self, CompressionError> {
+        match bits {
+            0 => Ok(Codec::None),
+            1 => Ok(Codec::Lz4),
+            2 => Ok(Codec::Zstd),
+            other => Err(CompressionError::UnknownCodec(other)),
+        }
+    }
+}
+
+/// Per-channel settings
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ChannelConfig {
+    /// Algorithm for frames above `compress_threshold`
+    pub codec: Codec,
+
+    /// Frames smaller than this are sent uncompressed
+    pub compress_threshold: usize,
+
+    /// Coalesce messages smaller than this (0 disables batching)
+    pub batch_threshold: usize,
+
+    /// Flush a batch once it reaches this many bytes
+    pub batch_max_bytes: usize,
+
+    /// Flush a batch once it holds this many messages
+    pub batch_max_messages: usize,
+}
+
+impl Default for ChannelConfig {
+    fn default() -> Self {
+        Self {
+            codec: Codec::Lz4,
+            compress_threshold: 512,
+            batch_threshold: 4 * 1024,
+            batch_max_bytes: 64 * 1024,
+            batch_max_messages: 64,
+        }
+    }
+}
+
+/// Wire compression configuration
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CompressionConfig {
+    /// Enable framing (receivers always accept framed payloads)
+    pub enabled: bool,
+
+    /// Settings for topics without a channel entry
+    pub default_channel: ChannelConfig,
+
+    /// Settings by topic prefix (longest prefix wins)
+    pub channels: HashMap<String, ChannelConfig>,
+
+    /// Maximum delay before a partial batch is flushed
+    pub batch_interval: Duration,
+
+    /// zstd compression level
+    pub zstd_level: i32,
+
+    /// Maximum decompressed frame size
+    pub max_decompressed_size: usize,
+}
+
+impl Default for CompressionConfig {
+    fn default() -> Self {
+        let mut channels = HashMap::new();
+        // Testimonies are tiny and frequent: batch aggressively
+        channels.insert(
+            "/rope/testimonies/".to_string(),
+            ChannelConfig {
+                batch_max_messages: 256,
+                ..ChannelConfig::default()
+            },
+        );
+        // String payloads are large and compress well
+        channels.insert(
+            "/rope/strings/".to_string(),
+            ChannelConfig {
+                codec: Codec::Zstd,
+                compress_threshold: 1024,
+                batch_threshold: 0,
+                ..ChannelConfig::default()
+            },
+        );
+        Self {
+            enabled: true,
+            default_channel: ChannelConfig::default(),
+            channels,
+            batch_interval: Duration::from_millis(20),
+            zstd_level: 3,
+            max_decompressed_size: 4 * 1024 * 1024,
+        }
+    }
+}
+
+impl CompressionConfig {
+    /// Settings for `topic`
+    pub fn channel(&self, topic: &str) -> &ChannelConfig {
+        self.channels
+            .iter()
+            .filter(|(prefix, _)| topic.starts_with(prefix.as_str()))
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map(|(_, channel)| channel)
+            .unwrap_or(&self.default_channel)
+    }
+}
+
+/// Compression errors
+#[derive(Debug, Error)]
+pub enum CompressionError {
+    #[error("Unknown codec {0}")]
+    UnknownCodec(u8),
+
+    #[error("Truncated frame")]
+    Truncated,
+
+    #[error("Decompressed size exceeds {0} bytes")]
+    TooLarge(usize),
+
+    #[error("Decompression failed: {0}")]
+    Decompress(String),
+
+    #[error("Compression failed: {0}")]
+    Compress(String),
+}
+
+/// Compression statistics
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct CompressionStats {
+    /// Payload bytes before framing
+    pub bytes_in: u64,
+    /// Frame bytes written to the wire
+    pub bytes_out: u64,
+    /// Frames compressed
+    pub frames_compressed: u64,
+    /// Batches flushed
+    pub batches_sent: u64,
+    /// Messages sent inside batches
+    pub messages_batched: u64,
+}
+
+impl CompressionStats {
+    /// Wire bytes per payload byte (below 1.0 means savings)
+    pub fn ratio(&self) -> f64 {
+        if self.bytes_in == 0 {
+            1.0
+        } else {
+            self.bytes_out as f64 / self.bytes_in as f64
+        }
+    }
+}
+
+/// Frame encoder and decoder
+pub struct WireCodec {
+    config: CompressionConfig,
+    stats: CompressionStats,
+}
+
+impl WireCodec {
+    /// Create a codec
+    pub fn new(config: CompressionConfig) -> Self {
+        Self {
+            config,
+            stats: CompressionStats::default(),
+        }
+    }
+
+    /// Configuration
+    pub fn config(&self) -> &CompressionConfig {
+        &self.config
+    }
+
+    /// Statistics
+    pub fn stats(&self) -> &CompressionStats {
+        &self.stats
+    }
+
+    /// Frame a single message for `topic`
+    pub fn encode(&mut self, topic: &str, data: &[u8]) -> Result<Vec<u8>, CompressionError> {
+        if !self.config.enabled {
+            return Ok(data.to_vec());
+        }
+        self.frame(topic, 0, data, data.len())
+    }
+
+    /// Frame several messages for `topic` as one batch
+    pub fn encode_batch(
+        &mut self,
+        topic: &str,
+        messages: &[Vec<u8>],
+    ) -> Result<Vec<u8>, CompressionError> {
+        let payload_len: usize = messages.iter().map(Vec::len).sum();
+        let mut body = Vec::with_capacity(4 + payload_len + 4 * messages.len());
+        body.extend_from_slice(&(messages.len() as u32).to_le_bytes());
+        for message in messages {
+            body.extend_from_slice(&(message.len() as u32).to_le_bytes());
+            body.extend_from_slice(message);
+        }
+
+        self.stats.batches_sent += 1;
+        self.stats.messages_batched += messages.len() as u64;
+        self.frame(topic, FLAG_BATCH, &body, payload_len)
+    }
+
+    fn frame(
+        &mut self,
+        topic: &str,
+        flags: u8,
+        body: &[u8],
+        payload_len: usize,
+    ) -> Result<Vec<u8>, CompressionError> {
+        let channel = self.config.channel(topic);
+        let codec = if body.len() >= channel.compress_threshold {
+            channel.codec
+        } else {
+            Codec::None
+        };
+
+        let compressed = match codec {
+            Codec::None => None,
+            Codec::Lz4 => Some(lz4_flex::compress_prepend_size(body)),
+            Codec::Zstd => Some(
+                zstd::bulk::compress(body, self.config.zstd_level)
+                    .map_err(|e| CompressionError::Compress(e.to_string()))?,
+            ),
+        };
+        // Keep the raw body when compression does not pay off
+        let (codec, body) = match &compressed {
+            Some(compressed) if compressed.len() < body.len() => (codec, compressed.as_slice()),
+            _ => (Codec::None, body),
+        };
+
+        let mut frame = Vec::with_capacity(2 + body.len());
+        frame.push(FRAME_MAGIC);
+        frame.push(flags | codec.bits());
+        frame.extend_from_slice(body);
+
+        self.stats.bytes_in += payload_len as u64;
+        self.stats.bytes_out += frame.len() as u64;
+        if codec != Codec::None {
+            self.stats.frames_compressed += 1;
+        }
+        Ok(frame)
+    }
+
+    /// Unframe a payload into its messages
+    pub fn decode(&self, data: &[u8]) -> Result<Vec<Vec<u8>>, CompressionError> {
+        let [FRAME_MAGIC, flags, body @ ..] = data else {
+            // Unframed payload from a legacy peer
+            return Ok(vec![data.to_vec()]);
+        };
+
+        let limit = self.config.max_decompressed_size;
+        let body = match Codec::from_bits(flags & CODEC_MASK)? {
+            Codec::None => body.to_vec(),
+            Codec::Lz4 => {
+                let size = body
+                    .get(..4)
+                    .ok_or(CompressionError::Truncated)?
+                    .try_into()
+                    .map(u32::from_le_bytes)
+                    .map_err(|_| CompressionError::Truncated)? as usize;
+                if size > limit {
+                    return Err(CompressionError::TooLarge(limit));
+                }
+                lz4_flex::decompress(&body[4..], size)
+                    .map_err(|e| CompressionError::Decompress(e.to_string()))?
+            }
+            Codec::Zstd => zstd::bulk::decompress(body, limit)
+                .map_err(|e| CompressionError::Decompress(e.to_string()))?,
+        };
+
+        if flags & FLAG_BATCH == 0 {
+            return Ok(vec![body]);
+        }
+        split_batch(&body)
+    }
+}
+
+impl Default for WireCodec {
+    fn default() -> Self {
+        Self::new(CompressionConfig::default())
+    }
+}
+
+fn split_batch(body: &[u8]) -> Result<Vec<Vec<u8>>, CompressionError> {
+    fn read_u32(body: &[u8], offset: usize) -> Result<usize, CompressionError> {
+        body.get(offset..offset + 4)
+            .and_then(|b| b.try_into().ok())
+            .map(|b| u32::from_le_bytes(b) as usize)
+            .ok_or(CompressionError::Truncated)
+    }
+
+    let count = read_u32(body, 0)?;
+    let mut offset = 4;
+    // Each entry needs at least its length prefix
+    let mut messages = Vec::with_capacity(count.min(body.len() / 4));
+    for _ in 0..count {
+        let len = read_u32(body, offset)?;
+        offset += 4;
+        let message = body
+            .get(offset..offset + len)
+            .ok_or(CompressionError::Truncated)?;
+        messages.push(message.to_vec());
+        offset += len;
+    }
+    Ok(messages)
+}
+
+/// Pending batch for one topic
+struct PendingBatch {
+    messages: Vec<Vec<u8>>,
+    bytes: usize,
+    opened_at: Instant,
+}
+
+/// Outgoing message batcher
+///
+/// Messages below the channel's batch threshold are held per topic until
+/// the batch fills up or `batch_interval` elapses; everything else is
+/// framed immediately.
+pub struct Batcher {
+    codec: WireCodec,
+    pending: HashMap<String, PendingBatch>,
+}
+
+impl Batcher {
+    /// Create a batcher
+    pub fn new(config: CompressionConfig) -> Self {
+        Self {
+            codec: WireCodec::new(config),
+            pending: HashMap::new(),
+        }
+    }
+
+    /// Underlying codec (for decoding and stats)
+    pub fn codec(&self) -> &WireCodec {
+        &self.codec
+    }
+
+    /// Queue a message; returns frames ready to publish
+    pub fn push(
+        &mut self,
+        topic: &str,
+        data: Vec<u8>,
+    ) -> Result<Vec<(String, Vec<u8>)>, CompressionError> {
+        let channel = self.codec.config().channel(topic).clone();
+        if !self.codec.config().enabled
+            || channel.batch_threshold == 0
+            || data.len() >= channel.batch_threshold
+        {
+            let frame = self.codec.encode(topic, &data)?;
+            return Ok(vec![(topic.to_string(), frame)]);
+        }
+
+        let mut ready = Vec::new();
+        // Flush first if this message would overflow the batch
+        if self
+            .pending
+            .get(topic)
+            .is_some_and(|b| b.bytes + data.len() > channel.batch_max_bytes)
+        {
+            ready.extend(self.flush_topic(topic)?);
+        }
+
+        let batch = self
+            .pending
+            .entry(topic.to_string())
+            .or_insert_with(|| PendingBatch {
+                messages: Vec::new(),
+                bytes: 0,
+                opened_at: Instant::now(),
+            });
+        batch.bytes += data.len();
+        batch.messages.push(data);
+
+        if batch.messages.len() >= channel.batch_max_messages {
+            ready.extend(self.flush_topic(topic)?);
+        }
+        Ok(ready)
+    }
+
+    /// Flush batches older than `batch_interval`
+    pub fn flush_due(&mut self) -> Vec<(String, Vec<u8>)> {
+        let interval = self.codec.config().batch_interval;
+        let due: Vec<String> = self
+            .pending
+            .iter()
+            .filter(|(_, b)| b.opened_at.elapsed() >= interval)
+            .map(|(topic, _)| topic.clone())
+            .collect();
+        self.flush_topics(due)
+    }
+
+    /// Flush every pending batch
+    pub fn flush_all(&mut self) -> Vec<(String, Vec<u8>)> {
+        let topics: Vec<String> = self.pending.keys().cloned().collect();
+        self.flush_topics(topics)
+    }
+
+    /// Whether any batch is waiting
+    pub fn has_pending(&self) -> bool {
+        !self.pending.is_empty()
+    }
+
+    /// Statistics
+    pub fn stats(&self) -> &CompressionStats {
+        self.codec.stats()
+    }
+
+    fn flush_topics(&mut self, topics: Vec<String>) -> Vec<(String, Vec<u8>)> {
+        let mut ready = Vec::new();
+        for topic in topics {
+            match self.flush_topic(&topic) {
+                Ok(frames) => ready.extend(frames),
+                Err(e) => tracing::warn!("Dropping batch for {}: {}", topic, e),
+            }
+        }
+        ready
+    }
+
+    fn flush_topic(&mut self, topic: &str) -> Result<Vec<(String, Vec<u8>)>, CompressionError> {
+        let Some(batch) = self.pending.remove(topic) else {
+            return Ok(Vec::new());
+        };
+        let frame = match batch.messages.as_slice() {
+            [single] => self.codec.encode(topic, single)?,
+            messages => self.codec.encode_batch(topic, messages)?,
+        };
+        Ok(vec![(topic.to_string(), frame)])
+    }
+}
+
+impl Default for Batcher {
+    fn default() -> Self {
+        Self::new(CompressionConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn compressible(len: usize) -> Vec<u8> {
+        b"rope-testimony-"
+            .iter()
+            .cycle()
+            .take(len)
+            .copied()
+            .collect()
+    }
+
+    #[test]
+    fn test_round_trip_each_codec() {
+        for codec in [Codec::None, Codec::Lz4, Codec::Zstd] {
+            let mut config = CompressionConfig::default();
+            config.default_channel.codec = codec;
+            let mut wire = WireCodec::new(config);
+
+            let data = compressible(8 * 1024);
+            let frame = wire.encode("/rope/gossip/1.0.0", &data).unwrap();
+            assert_eq!(wire.decode(&frame).unwrap(), vec![data]);
+            if codec != Codec::None {
+                assert!(frame.len() < 8 * 1024);
+                assert!(wire.stats().ratio() < 0.5);
+            }
+        }
+    }
+
+    #[test]
+    fn test_small_and_incompressible_frames_stay_raw() {
+        let mut wire = WireCodec::default();
+        let frame = wire.encode("/rope/gossip/1.0.0", b"tiny").unwrap();
+        assert_eq!(frame[1] & CODEC_MASK, 0);
+
+        let noise: Vec<u8> = (0..4096).map(|_| rand::random()).collect();
+        let frame = wire.encode("/rope/gossip/1.0.0", &noise).unwrap();
+        assert_eq!(frame[1] & CODEC_MASK, 0);
+        assert_eq!(wire.stats().frames_compressed, 0);
+    }
+
+    #[test]
+    fn test_legacy_payload_passes_through() {
+        let wire = WireCodec::default();
+        assert_eq!(
+            wire.decode(b"{\"legacy\":true}").unwrap(),
+            vec![b"{\"legacy\":true}".to_vec()]
+        );
+    }
+
+    #[test]
+    fn test_decode_rejects_bad_frames() {
+        let mut config = CompressionConfig::default();
+        config.max_decompressed_size = 1024;
+        config.default_channel.codec = Codec::Zstd;
+        let mut wire = WireCodec::new(config);
+
+        let frame = wire
+            .encode("/rope/gossip/1.0.0", &compressible(4096))
+            .unwrap();
+        assert!(matches!(
+            wire.decode(&frame),
+            Err(CompressionError::Decompress(_))
+        ));
+
+        // Claimed LZ4 size above the limit
+        let mut bomb = vec![FRAME_MAGIC, 1];
+        bomb.extend_from_slice(&u32::MAX.to_le_bytes());
+        assert!(matches!(
+            wire.decode(&bomb),
+            Err(CompressionError::TooLarge(_))
+        ));
+
+        // Batch claiming more messages than it holds
+        let mut truncated = vec![FRAME_MAGIC, FLAG_BATCH];
+        truncated.extend_from_slice(&3u32.to_le_bytes());
+        truncated.extend_from_slice(&2u32.to_le_bytes());
+        truncated.extend_from_slice(b"ab");
+        assert!(matches!(
+            wire.decode(&truncated),
+            Err(CompressionError::Truncated)
+        ));
+
+        assert!(matches!(
+            wire.decode(&[FRAME_MAGIC, 3]),
+            Err(CompressionError::UnknownCodec(3))
+        ));
+    }
+
+    #[test]
+    fn test_channel_prefix_match() {
+        let config = CompressionConfig::default();
+        assert_eq!(
+            config.channel("/rope/strings/text/1.0.0").codec,
+            Codec::Zstd
+        );
+        assert_eq!(
+            config.channel("/rope/testimonies/1.0.0").batch_max_messages,
+            256
+        );
+        assert_eq!(config.channel("/rope/gossip/1.0.0").codec, Codec::Lz4);
+    }
+
+    #[test]
+    fn test_batcher_coalesces_small_messages() {
+        let mut config = CompressionConfig::default();
+        config.default_channel.batch_max_messages = 3;
+        let mut batcher = Batcher::new(config);
+        let topic = "/rope/gossip/1.0.0";
+
+        assert!(batcher.push(topic, b"one".to_vec()).unwrap().is_empty());
+        assert!(batcher.push(topic, b"two".to_vec()).unwrap().is_empty());
+        let ready = batcher.push(topic, b"three".to_vec()).unwrap();
+        assert_eq!(ready.len(), 1);
+        assert!(!batcher.has_pending());
+
+        let messages = batcher.codec().decode(&ready[0].1).unwrap();
+        assert_eq!(
+            messages,
+            vec![b"one".to_vec(), b"two".to_vec(), b"three".to_vec()]
+        );
+        assert_eq!(batcher.stats().messages_batched, 3);
+
+        // Large messages bypass batching
+        let large = compressible(8 * 1024);
+        let ready = batcher.push(topic, large.clone()).unwrap();
+        assert_eq!(batcher.codec().decode(&ready[0].1).unwrap(), vec![large]);
+    }
+
+    #[test]
+    fn test_batcher_flushes_on_interval() {
+        let mut config = CompressionConfig::default();
+        config.batch_interval = Duration::from_millis(5);
+        let mut batcher = Batcher::new(config);
+
+        batcher.push("/rope/gossip/1.0.0", b"a".to_vec()).unwrap();
+        batcher.push("/rope/anchors/1.0.0", b"b".to_vec()).unwrap();
+        assert!(batcher.flush_due().is_empty());
+
+        std::thread::sleep(Duration::from_millis(10));
+        let mut ready = batcher.flush_due();
+        ready.sort();
+        assert_eq!(ready.len(), 2);
+        assert_eq!(
+            batcher.codec().decode(&ready[1].1).unwrap(),
+            vec![b"a".to_vec()]
+        );
+    }
+}
@@ -15,13 +15,47 @@
 //!
 //! - Maximum 1000 strings per gossip message
 //! - Gossip every 100ms or when batch is full
+//!
+//! ## Authentication
+//!
+//! Every message hash covers the full payload and is signed with the
+//! sender's hybrid key. Messages that fail verification are dropped and the
+//! transport peer that delivered them loses reputation; a peer that reaches
+//! zero is banned. The claimed `sender_id` is only credited once its
+//! signature verifies, so a forged message cannot be used to smear (or
+//! boost) another node.
+//!
+//! Validator-only channels wrap the payload in [`GossipMessageType::Sealed`],
+//! encrypted under a key derived from the channel key shared by the
+//! validator set and a random salt sent with each message.
 
+use crate::peer::{PeerId, PeerManager};
+use crate::score::{Offense, PeerScore};
 use parking_lot::RwLock;
 use rope_core::types::StringId;
+use rope_crypto::hybrid::{HybridPublicKey, HybridSignature, HybridSigner, HybridVerifier};
+use rope_crypto::secret::SecretBytes;
+use rope_crypto::stream::{decrypt_stream, encrypt_stream, DEFAULT_CHUNK_SIZE};
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::Arc;
 use std::time::{Duration, Instant};
 
+/// Domain separator for gossip signatures
+const GOSSIP_SIGNATURE_DOMAIN: &[u8] = b"rope-gossip-v1";
+
+/// KDF context for per-message keys of sealed payloads
+const SEAL_KEY_CONTEXT: &str = "rope gossip seal v1";
+
+/// Random salt prefixed to each sealed payload
+const SEAL_SALT_SIZE: usize = 32;
+
+/// Reputation lost per invalid message
+const INVALID_MESSAGE_PENALTY: i8 = -20;
+
+/// Ban length once reputation reaches zero
+const INVALID_MESSAGE_BAN: Duration = Duration::from_secs(3600);
+
 /// Gossip protocol configuration
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct GossipConfig {
@@ -39,6 +73,9 @@ pub struct GossipConfig {
 
     /// Enable gossip compression
     pub enable_compression: bool,
+
+    /// Drop messages that are unsigned or from senders without a known key
+    pub require_signatures: bool,
 }
 
 impl Default for GossipConfig {
@@ -49,6 +86,7 @@ impl Default for GossipConfig {
             fanout: 10,
             max_history: 10000,
             enable_compression: true,
+            require_signatures: true,
         }
     }
 }
@@ -73,6 +111,13 @@ pub enum GossipMessageType {
         strings: Vec<StringData>,
         round: u64,
     },
+
+    /// Payload encrypted under a key derived from the validator channel
+    /// key, prefixed with the derivation salt
+    Sealed {
+        #[serde(with = "serde_bytes")]
+        ciphertext: Vec<u8>,
+    },
 }
 
 /// String data for gossip
@@ -104,35 +149,124 @@ pub struct GossipMessage {
 
     /// Message hash
     pub hash: [u8; 32],
+
+    /// Sender signature over the hash
+    #[serde(default = "HybridSignature::empty")]
+    pub signature: HybridSignature,
 }
 
 impl GossipMessage {
-    /// Create a new gossip message
+    /// Create a new (unsigned) gossip message
     pub fn new(
         sender_id: [u8; 32],
         sequence: u64,
         parent_hashes: Vec<[u8; 32]>,
         message_type: GossipMessageType,
     ) -> Self {
-        let timestamp = chrono::Utc::now().timestamp();
-
-        // Calculate hash
-        let mut data = sender_id.to_vec();
-        data.extend_from_slice(&sequence.to_le_bytes());
-        data.extend_from_slice(&timestamp.to_le_bytes());
-        let hash = *blake3::hash(&data).as_bytes();
-
-        Self {
+        let mut msg = Self {
             sender_id,
             sequence,
             parent_hashes,
             message_type,
-            timestamp,
-            hash,
+            timestamp: chrono::Utc::now().timestamp(),
+            hash: [0u8; 32],
+            signature: HybridSignature::empty(),
+        };
+        msg.hash = msg.compute_hash();
+        msg
+    }
+
+    /// Hash over sender, sequence, timestamp, parents and payload
+    pub fn compute_hash(&self) -> [u8; 32] {
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(&self.sender_id);
+        hasher.update(&self.sequence.to_le_bytes());
+        hasher.update(&self.timestamp.to_le_bytes());
+        hasher.update(&(self.parent_hashes.len() as u64).to_le_bytes());
+        for parent in &self.parent_hashes {
+            hasher.update(parent);
+        }
+        hasher.update(&bincode::serialize(&self.message_type).unwrap_or_default());
+        *hasher.finalize().as_bytes()
+    }
+
+    /// Sign the message hash
+    pub fn sign(mut self, signer: &HybridSigner) -> Self {
+        self.signature = signer.sign(&signing_bytes(&self.hash));
+        self
+    }
+
+    /// Whether a signature is attached
+    pub fn is_signed(&self) -> bool {
+        !self.signature.is_empty()
+    }
+
+    /// Check the hash and the sender's signature
+    pub fn verify(&self, sender_key: &HybridPublicKey) -> Result<(), GossipError> {
+        if self.compute_hash() != self.hash {
+            return Err(GossipError::HashMismatch);
+        }
+        if !self.is_signed() {
+            return Err(GossipError::MissingSignature);
+        }
+        match HybridVerifier::verify(sender_key, &signing_bytes(&self.hash), &self.signature) {
+            Ok(true) => Ok(()),
+            _ => Err(GossipError::InvalidSignature),
+        }
+    }
+}
+
+fn signing_bytes(hash: &[u8; 32]) -> Vec<u8> {
+    let mut data = GOSSIP_SIGNATURE_DOMAIN.to_vec();
+    data.extend_from_slice(hash);
+    data
+}
+
+/// Gossip verification and decryption errors
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum GossipError {
+    /// Hash does not match the message contents
+    HashMismatch,
+    /// Message carries no signature
+    MissingSignature,
+    /// Signature does not verify against the sender's key
+    InvalidSignature,
+    /// No key registered for the sender
+    UnknownSender,
+    /// Sealed payload received without a channel key
+    NoChannelKey,
+    /// Sealed payload failed to decrypt or decode
+    Decryption(String),
+}
+
+impl GossipError {
+    /// Whether the sender should be penalized for this error
+    pub fn is_misbehaviour(&self) -> bool {
+        matches!(
+            self,
+            GossipError::HashMismatch
+                | GossipError::MissingSignature
+                | GossipError::InvalidSignature
+                | GossipError::Decryption(_)
+        )
+    }
+}
+
+impl std::fmt::Display for GossipError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GossipError::HashMismatch => write!(f, "Message hash mismatch"),
+            GossipError::MissingSignature => write!(f, "Message is not signed"),
+            GossipError::InvalidSignature => write!(f, "Invalid message signature"),
+            GossipError::UnknownSender => write!(f, "Unknown sender"),
+            GossipError::NoChannelKey => write!(f, "No channel key for sealed message"),
+            GossipError::Decryption(msg) => write!(f, "Sealed payload rejected: {}", msg),
         }
     }
 }
 
+impl std::error::Error for GossipError {}
+
 /// Gossip event for virtual voting
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct GossipEvent {
@@ -237,6 +371,21 @@ pub struct GossipProtocol {
 
     /// Statistics
     stats: RwLock<GossipStats>,
+
+    /// Our signing key
+    signer: Option<Arc<HybridSigner>>,
+
+    /// Known sender keys
+    peer_keys: RwLock<HashMap<[u8; 32], HybridPublicKey>>,
+
+    /// Peer manager for reputation penalties
+    peers: Option<Arc<PeerManager>>,
+
+    /// Peer score engine (replaces flat reputation penalties when set)
+    scores: Option<Arc<PeerScore>>,
+
+    /// Validator channel key for sealed payloads
+    channel_key: RwLock<Option<SecretBytes>>,
 }
 
 /// Gossip statistics
@@ -246,6 +395,7 @@ pub struct GossipStats {
     pub messages_received: u64,
     pub strings_propagated: u64,
     pub duplicates_received: u64,
+    pub invalid_messages: u64,
     pub current_round: u64,
 }
 
@@ -262,9 +412,42 @@ impl GossipProtocol {
             history: RwLock::new(GossipHistory::new(max_history)),
             last_gossip: RwLock::new(Instant::now()),
             stats: RwLock::new(GossipStats::default()),
+            signer: None,
+            peer_keys: RwLock::new(HashMap::new()),
+            peers: None,
+            scores: None,
+            channel_key: RwLock::new(None),
         }
     }
 
+    /// Sign outgoing messages with `signer`
+    pub fn with_signer(mut self, signer: Arc<HybridSigner>) -> Self {
+        self.signer = Some(signer);
+        self
+    }
+
+    /// Report invalid messages to `peers`
+    pub fn with_peer_manager(mut self, peers: Arc<PeerManager>) -> Self {
+        self.peers = Some(peers);
+        self
+    }
+
+    /// Report invalid messages to the `scores` engine
+    pub fn with_peer_score(mut self, scores: Arc<PeerScore>) -> Self {
+        self.scores = Some(scores);
+        self
+    }
+
+    /// Register the signing key of a sender
+    pub fn register_peer_key(&self, sender_id: [u8; 32], key: HybridPublicKey) {
+        self.peer_keys.write().insert(sender_id, key);
+    }
+
+    /// Set (or clear) the validator channel key
+    pub fn set_channel_key(&self, key: Option<[u8; 32]>) {
+        *self.channel_key.write() = key.map(SecretBytes::from);
+    }
+
     /// Add a string to propagate
     pub fn add_string(&self, data: StringData) {
         let mut known = self.known_strings.write();
@@ -294,24 +477,72 @@ impl GossipProtocol {
             .map(|s| s.id)
             .collect();
 
-        let mut seq = self.sequence.write();
-        *seq += 1;
+        drop(pending);
 
         let parent_hashes = self.get_recent_event_hashes();
+        Some(self.outgoing(parent_hashes, GossipMessageType::Have(string_ids)))
+    }
 
-        Some(GossipMessage::new(
-            self.node_id,
-            *seq,
-            parent_hashes,
-            GossipMessageType::Have(string_ids),
-        ))
+    /// Create a message for the validator-only channel
+    pub fn create_sealed_message(
+        &self,
+        message_type: GossipMessageType,
+    ) -> Result<GossipMessage, GossipError> {
+        let sealed = self.seal(&message_type)?;
+        let parent_hashes = self.get_recent_event_hashes();
+        Ok(self.outgoing(parent_hashes, sealed))
     }
 
-    /// Handle incoming gossip message
-    pub fn handle_message(&self, msg: GossipMessage) -> Option<GossipMessage> {
+    /// Check an incoming message against the sender's registered key
+    pub fn verify_message(&self, msg: &GossipMessage) -> Result<(), GossipError> {
+        let key = self.peer_keys.read().get(&msg.sender_id).cloned();
+        match key {
+            Some(key) => msg.verify(&key),
+            None if self.config.require_signatures => Err(GossipError::UnknownSender),
+            None if msg.compute_hash() != msg.hash => Err(GossipError::HashMismatch),
+            None => Ok(()),
+        }
+    }
+
+    /// Handle a gossip message delivered by transport peer `from`
+    ///
+    /// Messages that fail verification or decryption are dropped and
+    /// penalize `from`; the claimed sender is credited only when its
+    /// signature verifies.
+    pub fn handle_message(&self, from: PeerId, msg: GossipMessage) -> Option<GossipMessage> {
         self.stats.write().messages_received += 1;
 
-        match msg.message_type {
+        let sealed = matches!(msg.message_type, GossipMessageType::Sealed { .. });
+        let payload = self
+            .verify_message(&msg)
+            .and_then(|()| match msg.message_type {
+                GossipMessageType::Sealed { ciphertext } => self.open(&ciphertext),
+                other => Ok(other),
+            });
+        let payload = match payload {
+            Ok(payload) => payload,
+            Err(e) => {
+                self.reject(&from, &e);
+                return None;
+            }
+        };
+        if msg.is_signed() && self.peer_keys.read().contains_key(&msg.sender_id) {
+            if let Some(scores) = &self.scores {
+                scores.reward(&msg.sender_id);
+            }
+        }
+
+        let response = self.process(payload)?;
+        let response = if sealed {
+            self.seal(&response).ok()?
+        } else {
+            response
+        };
+        Some(self.outgoing(vec![msg.hash], response))
+    }
+
+    fn process(&self, message_type: GossipMessageType) -> Option<GossipMessageType> {
+        match message_type {
             GossipMessageType::Have(ids) => {
                 // Check which strings we need
                 let known = self.known_strings.read();
@@ -322,15 +553,7 @@ impl GossipProtocol {
                     return None;
                 }
 
-                let mut seq = self.sequence.write();
-                *seq += 1;
-
-                Some(GossipMessage::new(
-                    self.node_id,
-                    *seq,
-                    vec![msg.hash],
-                    GossipMessageType::Want(want),
-                ))
+                Some(GossipMessageType::Want(want))
             }
 
             GossipMessageType::Want(ids) => {
@@ -346,15 +569,7 @@ impl GossipProtocol {
                     return None;
                 }
 
-                let mut seq = self.sequence.write();
-                *seq += 1;
-
-                Some(GossipMessage::new(
-                    self.node_id,
-                    *seq,
-                    vec![msg.hash],
-                    GossipMessageType::Data(data),
-                ))
+                Some(GossipMessageType::Data(data))
             }
 
             GossipMessageType::Data(strings) => {
@@ -393,18 +608,10 @@ impl GossipProtocol {
                     }
                 }
 
-                let mut seq = self.sequence.write();
-                *seq += 1;
-
-                Some(GossipMessage::new(
-                    self.node_id,
-                    *seq,
-                    vec![msg.hash],
-                    GossipMessageType::SyncResponse {
-                        strings,
-                        round: history.latest_round(),
-                    },
-                ))
+                Some(GossipMessageType::SyncResponse {
+                    strings,
+                    round: history.latest_round(),
+                })
             }
 
             GossipMessageType::SyncResponse { strings, round: _ } => {
@@ -415,6 +622,109 @@ impl GossipProtocol {
                 }
                 None
             }
+
+            // Sealed payloads are opened before processing and never nest
+            GossipMessageType::Sealed { .. } => None,
+        }
+    }
+
+    /// Build and sign the next outgoing message
+    fn outgoing(
+        &self,
+        parent_hashes: Vec<[u8; 32]>,
+        message_type: GossipMessageType,
+    ) -> GossipMessage {
+        let mut seq = self.sequence.write();
+        *seq += 1;
+        let msg = GossipMessage::new(self.node_id, *seq, parent_hashes, message_type);
+        match &self.signer {
+            Some(signer) => msg.sign(signer),
+            None => msg,
+        }
+    }
+
+    /// Key for one sealed payload, derived from the channel key and `salt`
+    ///
+    /// A fresh key per message keeps the random stream nonces from ever
+    /// repeating under the long-lived channel key.
+    fn message_key(&self, salt: &[u8]) -> Result<SecretBytes, GossipError> {
+        let channel_key = self.channel_key.read();
+        let key = channel_key
+            .as_ref()
+            .and_then(|k| k.expose_array::<32>())
+            .ok_or(GossipError::NoChannelKey)?;
+        let material = SecretBytes::new([key.as_slice(), salt].concat());
+        Ok(SecretBytes::from(blake3::derive_key(
+            SEAL_KEY_CONTEXT,
+            material.expose_secret(),
+        )))
+    }
+
+    fn seal(&self, message_type: &GossipMessageType) -> Result<GossipMessageType, GossipError> {
+        let salt: [u8; SEAL_SALT_SIZE] = rand::random();
+        let key = self.message_key(&salt)?;
+        let key = key.expose_array::<32>().ok_or(GossipError::NoChannelKey)?;
+        let plaintext =
+            bincode::serialize(message_type).map_err(|e| GossipError::Decryption(e.to_string()))?;
+
+        let mut ciphertext = salt.to_vec();
+        encrypt_stream(
+            key,
+            &mut plaintext.as_slice(),
+            &mut ciphertext,
+            DEFAULT_CHUNK_SIZE,
+        )
+        .map_err(|e| GossipError::Decryption(e.to_string()))?;
+        Ok(GossipMessageType::Sealed { ciphertext })
+    }
+
+    fn open(&self, ciphertext: &[u8]) -> Result<GossipMessageType, GossipError> {
+        if ciphertext.len() < SEAL_SALT_SIZE {
+            return Err(GossipError::Decryption("Missing salt".to_string()));
+        }
+        let (salt, ciphertext) = ciphertext.split_at(SEAL_SALT_SIZE);
+        let key = self.message_key(salt)?;
+        let key = key.expose_array::<32>().ok_or(GossipError::NoChannelKey)?;
+
+        let mut buffer = Vec::new();
+        decrypt_stream(key, ciphertext, &mut buffer)
+            .map_err(|e| GossipError::Decryption(e.to_string()))?;
+        let plaintext = SecretBytes::new(buffer);
+
+        match bincode::deserialize(plaintext.expose_secret()) {
+            Ok(GossipMessageType::Sealed { .. }) => {
+                Err(GossipError::Decryption("Nested sealed payload".to_string()))
+            }
+            Ok(message_type) => Ok(message_type),
+            Err(e) => Err(GossipError::Decryption(e.to_string())),
+        }
+    }
+
+    /// Drop an invalid message and penalize the peer that delivered it
+    fn reject(&self, from: &PeerId, error: &GossipError) {
+        self.stats.write().invalid_messages += 1;
+        tracing::debug!(
+            "Dropping gossip from {}: {}",
+            hex::encode(&from[..8]),
+            error
+        );
+
+        if !error.is_misbehaviour() {
+            return;
+        }
+        if let Some(scores) = &self.scores {
+            let offense = match error {
+                GossipError::Decryption(_) => Offense::ProtocolViolation,
+                _ => Offense::InvalidMessage,
+            };
+            scores.record(from, offense);
+            return;
+        }
+        let Some(peers) = &self.peers else {
+            return;
+        };
+        if peers.adjust_reputation(from, INVALID_MESSAGE_PENALTY) == Some(0) {
+            peers.ban(from, INVALID_MESSAGE_BAN, error.to_string());
         }
     }
 
@@ -543,10 +853,16 @@ mod tests {
         assert!(matches!(msg.message_type, GossipMessageType::Have(_)));
     }
 
+    fn peer_signer(seed: u8) -> HybridSigner {
+        HybridSigner::from_seed(&[seed; 32]).0
+    }
+
     #[test]
     fn test_handle_want_message() {
         let protocol = GossipProtocol::new([1u8; 32], GossipConfig::default());
         let string_id = StringId::from_content(b"test");
+        let peer = peer_signer(2);
+        protocol.register_peer_key([2u8; 32], peer.public_key());
 
         // Add a string
         protocol.add_string(StringData {
@@ -562,13 +878,237 @@ mod tests {
             1,
             vec![],
             GossipMessageType::Want(vec![string_id]),
-        );
+        )
+        .sign(&peer);
 
         // Handle want message
-        let response = protocol.handle_message(want_msg);
+        let response = protocol.handle_message([2u8; 32], want_msg);
         assert!(response.is_some());
 
         let response = response.unwrap();
         assert!(matches!(response.message_type, GossipMessageType::Data(_)));
     }
+
+    #[test]
+    fn test_signed_message_verifies() {
+        let signer = peer_signer(1);
+        let msg = GossipMessage::new(
+            [1u8; 32],
+            1,
+            vec![[9u8; 32]],
+            GossipMessageType::Want(vec![StringId::from_content(b"test")]),
+        )
+        .sign(&signer);
+
+        assert!(msg.is_signed());
+        assert_eq!(msg.verify(&signer.public_key()), Ok(()));
+        assert_eq!(
+            msg.verify(&peer_signer(2).public_key()),
+            Err(GossipError::InvalidSignature)
+        );
+
+        let mut tampered = msg.clone();
+        tampered.message_type = GossipMessageType::Want(vec![StringId::from_content(b"other")]);
+        assert_eq!(
+            tampered.verify(&signer.public_key()),
+            Err(GossipError::HashMismatch)
+        );
+    }
+
+    #[test]
+    fn test_invalid_message_penalizes_sender() {
+        let peers = Arc::new(PeerManager::new([1u8; 32], 10));
+        peers.add_peer(crate::peer::PeerConnection::new(
+            [2u8; 32],
+            "/ip4/127.0.0.1/udp/9000/quic".to_string(),
+        ));
+        let protocol = GossipProtocol::new([1u8; 32], GossipConfig::default())
+            .with_peer_manager(peers.clone());
+        protocol.register_peer_key([2u8; 32], peer_signer(2).public_key());
+
+        // Signed by the wrong key
+        let forged = GossipMessage::new(
+            [2u8; 32],
+            1,
+            vec![],
+            GossipMessageType::Have(vec![StringId::from_content(b"test")]),
+        )
+        .sign(&peer_signer(3));
+
+        assert!(protocol.handle_message([2u8; 32], forged.clone()).is_none());
+        assert_eq!(protocol.stats().invalid_messages, 1);
+        assert_eq!(peers.get_peer(&[2u8; 32]).unwrap().reputation, 30);
+
+        for _ in 0..2 {
+            protocol.handle_message([2u8; 32], forged.clone());
+        }
+        assert!(peers.get_peer(&[2u8; 32]).unwrap().is_banned());
+    }
+
+    #[test]
+    fn test_invalid_message_feeds_peer_score() {
+        let scores = Arc::new(PeerScore::default());
+        let protocol =
+            GossipProtocol::new([1u8; 32], GossipConfig::default()).with_peer_score(scores.clone());
+        protocol.register_peer_key([2u8; 32], peer_signer(2).public_key());
+
+        let forged = GossipMessage::new(
+            [2u8; 32],
+            1,
+            vec![],
+            GossipMessageType::Have(vec![StringId::from_content(b"test")]),
+        )
+        .sign(&peer_signer(3));
+
+        for _ in 0..3 {
+            assert!(protocol.handle_message([2u8; 32], forged.clone()).is_none());
+        }
+        assert!(scores.is_greylisted(&[2u8; 32]));
+        assert_eq!(scores.snapshot()[0].offenses.invalid_messages, 3);
+    }
+
+    #[test]
+    fn test_forged_sender_penalizes_transport_peer() {
+        let scores = Arc::new(PeerScore::default());
+        let protocol =
+            GossipProtocol::new([1u8; 32], GossipConfig::default()).with_peer_score(scores.clone());
+        protocol.register_peer_key([2u8; 32], peer_signer(2).public_key());
+
+        // Peer 3 relays a message claiming to come from peer 2
+        let forged = GossipMessage::new(
+            [2u8; 32],
+            1,
+            vec![],
+            GossipMessageType::Have(vec![StringId::from_content(b"test")]),
+        )
+        .sign(&peer_signer(3));
+        for _ in 0..3 {
+            assert!(protocol.handle_message([3u8; 32], forged.clone()).is_none());
+        }
+        assert!(scores.is_greylisted(&[3u8; 32]));
+        assert_eq!(scores.score(&[2u8; 32]), 0.0);
+
+        // A genuine message from peer 2, relayed by peer 3, credits peer 2
+        let genuine = GossipMessage::new(
+            [2u8; 32],
+            2,
+            vec![],
+            GossipMessageType::Have(vec![StringId::from_content(b"test")]),
+        )
+        .sign(&peer_signer(2));
+        assert!(protocol.handle_message([3u8; 32], genuine).is_some());
+        assert!(scores.score(&[2u8; 32]) > 0.0);
+        assert!(scores.is_greylisted(&[3u8; 32]));
+    }
+
+    #[test]
+    fn test_unsigned_message_rejected() {
+        let protocol = GossipProtocol::new([1u8; 32], GossipConfig::default());
+        let msg = GossipMessage::new(
+            [2u8; 32],
+            1,
+            vec![],
+            GossipMessageType::Have(vec![StringId::from_content(b"test")]),
+        );
+        assert_eq!(
+            protocol.verify_message(&msg),
+            Err(GossipError::UnknownSender)
+        );
+
+        protocol.register_peer_key([2u8; 32], peer_signer(2).public_key());
+        assert_eq!(
+            protocol.verify_message(&msg),
+            Err(GossipError::MissingSignature)
+        );
+        assert!(protocol.handle_message([2u8; 32], msg).is_none());
+    }
+
+    #[test]
+    fn test_sealed_channel_round_trip() {
+        let alice_signer = Arc::new(peer_signer(1));
+        let bob_signer = Arc::new(peer_signer(2));
+        let alice = GossipProtocol::new([1u8; 32], GossipConfig::default())
+            .with_signer(alice_signer.clone());
+        let bob =
+            GossipProtocol::new([2u8; 32], GossipConfig::default()).with_signer(bob_signer.clone());
+        alice.register_peer_key([2u8; 32], bob_signer.public_key());
+        bob.register_peer_key([1u8; 32], alice_signer.public_key());
+        alice.set_channel_key(Some([7u8; 32]));
+        bob.set_channel_key(Some([7u8; 32]));
+
+        let string_id = StringId::from_content(b"validator only");
+        let have = alice
+            .create_sealed_message(GossipMessageType::Have(vec![string_id]))
+            .unwrap();
+        assert!(matches!(
+            have.message_type,
+            GossipMessageType::Sealed { .. }
+        ));
+
+        // Bob answers on the sealed channel as well
+        let want = bob.handle_message([1u8; 32], have).unwrap();
+        assert!(matches!(
+            want.message_type,
+            GossipMessageType::Sealed { .. }
+        ));
+        let GossipMessageType::Sealed { ciphertext } = &want.message_type else {
+            unreachable!()
+        };
+        assert!(matches!(
+            alice.open(ciphertext),
+            Ok(GossipMessageType::Want(ids)) if ids == vec![string_id]
+        ));
+    }
+
+    #[test]
+    fn test_sealed_message_requires_key() {
+        let signer = Arc::new(peer_signer(1));
+        let sender =
+            GossipProtocol::new([1u8; 32], GossipConfig::default()).with_signer(signer.clone());
+        sender.set_channel_key(Some([7u8; 32]));
+        let msg = sender
+            .create_sealed_message(GossipMessageType::SyncRequest { from_round: 0 })
+            .unwrap();
+
+        let outsider = GossipProtocol::new([3u8; 32], GossipConfig::default());
+        outsider.register_peer_key([1u8; 32], signer.public_key());
+        assert!(outsider.handle_message([1u8; 32], msg.clone()).is_none());
+
+        outsider.set_channel_key(Some([8u8; 32]));
+        let GossipMessageType::Sealed { ciphertext } = &msg.message_type else {
+            unreachable!()
+        };
+        assert!(matches!(
+            outsider.open(ciphertext),
+            Err(GossipError::Decryption(_))
+        ));
+    }
+
+    #[test]
+    fn test_sealed_messages_use_fresh_keys() {
+        let sender = GossipProtocol::new([1u8; 32], GossipConfig::default());
+        sender.set_channel_key(Some([7u8; 32]));
+        let message = GossipMessageType::SyncRequest { from_round: 3 };
+
+        let seal = || match sender.seal(&message).unwrap() {
+            GossipMessageType::Sealed { ciphertext } => ciphertext,
+            other => panic!("unexpected {:?}", other),
+        };
+        let (a, b) = (seal(), seal());
+        assert_ne!(a[..SEAL_SALT_SIZE], b[..SEAL_SALT_SIZE]);
+        assert_ne!(a[SEAL_SALT_SIZE..], b[SEAL_SALT_SIZE..]);
+        assert!(matches!(
+            sender.open(&b),
+            Ok(GossipMessageType::SyncRequest { from_round: 3 })
+        ));
+
+        // Another salt derives another key
+        let mut swapped = a[..SEAL_SALT_SIZE].to_vec();
+        swapped.extend_from_slice(&b[SEAL_SALT_SIZE..]);
+        assert!(matches!(
+            sender.open(&swapped),
+            Err(GossipError::Decryption(_))
+        ));
+        assert!(sender.open(&a[..8]).is_err());
+    }
 }
@@ -33,22 +33,38 @@
 //! | String Distribution | RDP over UDP | OES encryption |
 //! | Client RPC | gRPC + HTTP/2 | mTLS + JWT |
 //! | Bridge Relay | WebSocket | Threshold ECDSA |
+//! | Event Stream | WebSocket + JSON | TLS + JWT |
 
+pub mod bandwidth;
+pub mod compression;
 pub mod discovery;
 pub mod gossip;
 pub mod message;
+pub mod nat;
 pub mod peer;
+pub mod pubsub;
 pub mod rdp;
 pub mod rpc;
+pub mod score;
 pub mod swarm;
 pub mod transport;
+pub mod ws;
 
 // Re-exports
+pub use bandwidth::{ByteCounters, NetworkStats, NetworkStatsSnapshot, TrafficChannel};
+pub use compression::{Codec, CompressionConfig, CompressionStats};
 pub use discovery::{DhtConfig, DiscoveryService, PeerInfo};
-pub use gossip::{GossipConfig, GossipMessage, GossipProtocol};
+pub use gossip::{GossipConfig, GossipError, GossipMessage, GossipProtocol};
 pub use message::{MessageType, NetworkMessage};
+pub use nat::{NatConfig, NatStatus};
 pub use peer::{PeerId, PeerManager, PeerState};
+pub use pubsub::{
+    MessageValidator, PubsubConfig, PubsubError, PubsubHandle, PubsubMessage, Topic,
+    TopicValidator, ValidationResult,
+};
 pub use rdp::{RdpConfig, RopeDistributionProtocol, Swarm as RdpSwarm};
 pub use rpc::RpcConfig;
+pub use score::{Offense, PeerScore, PeerScoreSnapshot, PeerStanding, ScoreConfig};
 pub use swarm::{RopeSwarmRuntime, SwarmCommand, SwarmConfig, SwarmNetworkEvent, SwarmStats};
 pub use transport::{TransportConfig, TransportLayer};
+pub use ws::{EventBridge, WsCommand, WsConfig, WsEvent};
@@ -45,6 +45,10 @@ pub enum MessageType {
     Testimony,
     /// Anchor string
     Anchor,
+    /// Catch-up sync request
+    SyncRequest,
+    /// Catch-up sync response
+    SyncResponse,
 
     // === Control ===
     /// Ping
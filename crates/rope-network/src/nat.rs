@@ -0,0 +1,260 @@
+//! # NAT Traversal
+//!
+//! Lets databoxes behind home routers join swarms without port forwarding.
+//!
+//! 1. **AutoNAT** asks connected peers to dial us back and classifies our
+//!    reachability as public or private.
+//! 2. **Circuit relay v2**: once private, we reserve a slot on each
+//!    configured relay and listen on `<relay>/p2p-circuit`, so peers can
+//!    reach us through the relay.
+//! 3. **DCUtR** upgrades relayed connections to direct ones by
+//!    synchronised hole punching; the relay only carries the handshake.
+//!
+//! Public nodes can opt in to serving as relays for others.
+
+use libp2p::multiaddr::Protocol;
+use libp2p::{Multiaddr, PeerId};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::time::Duration;
+
+/// NAT traversal configuration
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct NatConfig {
+    /// Probe our reachability with AutoNAT
+    pub enable_autonat: bool,
+
+    /// Delay before the first probe
+    pub autonat_boot_delay: Duration,
+
+    /// Interval between probes once status is known
+    pub autonat_refresh_interval: Duration,
+
+    /// Successful probes needed before status is trusted
+    pub autonat_confidence: usize,
+
+    /// Relays to reserve on when private (multiaddrs ending in `/p2p/<id>`)
+    pub relays: Vec<String>,
+
+    /// Upgrade relayed connections by hole punching
+    pub enable_hole_punching: bool,
+
+    /// Serve as a relay for other peers (public nodes only)
+    pub act_as_relay: bool,
+
+    /// Reservations accepted when acting as a relay
+    pub max_reservations: usize,
+
+    /// Circuits relayed at once when acting as a relay
+    pub max_circuits: usize,
+}
+
+impl Default for NatConfig {
+    fn default() -> Self {
+        Self {
+            enable_autonat: true,
+            autonat_boot_delay: Duration::from_secs(15),
+            autonat_refresh_interval: Duration::from_secs(15 * 60),
+            autonat_confidence: 3,
+            relays: Vec::new(),
+            enable_hole_punching: true,
+            act_as_relay: false,
+            max_reservations: 128,
+            max_circuits: 16,
+        }
+    }
+}
+
+/// Our reachability as seen by other peers
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum NatStatus {
+    /// Not probed yet
+    #[default]
+    Unknown,
+    /// Directly dialable at `address`
+    Public { address: String },
+    /// Behind NAT or firewall
+    Private,
+}
+
+/// Parse relay multiaddrs, which must name the relay peer
+pub fn parse_relays(relays: &[String]) -> Result<Vec<(PeerId, Multiaddr)>, String> {
+    relays
+        .iter()
+        .map(|relay| {
+            let addr: Multiaddr = relay
+                .parse()
+                .map_err(|e| format!("Invalid relay address {}: {}", relay, e))?;
+            match addr.iter().last() {
+                Some(Protocol::P2p(peer_id)) => Ok((peer_id, addr)),
+                _ => Err(format!(
+                    "Relay address {} must end with /p2p/<peer id>",
+                    relay
+                )),
+            }
+        })
+        .collect()
+}
+
+/// NAT traversal state driven by swarm events
+pub struct NatTraversal {
+    relays: Vec<(PeerId, Multiaddr)>,
+    status: NatStatus,
+    /// Relays we have asked for a reservation
+    requested: HashSet<PeerId>,
+    /// Relays holding a reservation for us
+    reserved: HashSet<PeerId>,
+    hole_punch_successes: u64,
+    hole_punch_failures: u64,
+}
+
+impl NatTraversal {
+    /// Create from configuration
+    pub fn new(config: &NatConfig) -> Result<Self, String> {
+        Ok(Self {
+            relays: parse_relays(&config.relays)?,
+            status: NatStatus::Unknown,
+            requested: HashSet::new(),
+            reserved: HashSet::new(),
+            hole_punch_successes: 0,
+            hole_punch_failures: 0,
+        })
+    }
+
+    /// Configured relays
+    pub fn relays(&self) -> &[(PeerId, Multiaddr)] {
+        &self.relays
+    }
+
+    /// Record a new status; returns relay circuit addresses to listen on
+    pub fn on_status_changed(&mut self, status: NatStatus) -> Vec<Multiaddr> {
+        self.status = status;
+        if self.status != NatStatus::Private {
+            return Vec::new();
+        }
+        self.pending_circuits()
+    }
+
+    /// Circuits not yet requested (retried after a relay drops us)
+    pub fn pending_circuits(&mut self) -> Vec<Multiaddr> {
+        let mut circuits = Vec::new();
+        for (peer_id, addr) in &self.relays {
+            if self.requested.insert(*peer_id) {
+                circuits.push(addr.clone().with(Protocol::P2pCircuit));
+            }
+        }
+        circuits
+    }
+
+    /// A relay accepted our reservation
+    pub fn on_reservation_accepted(&mut self, relay: PeerId) {
+        if self.requested.contains(&relay) {
+            self.reserved.insert(relay);
+        }
+    }
+
+    /// Connection to a relay closed; its reservation is gone
+    pub fn on_relay_disconnected(&mut self, relay: &PeerId) {
+        self.requested.remove(relay);
+        self.reserved.remove(relay);
+    }
+
+    /// Outcome of a DCUtR hole punch
+    pub fn on_hole_punch(&mut self, success: bool) {
+        if success {
+            self.hole_punch_successes += 1;
+        } else {
+            self.hole_punch_failures += 1;
+        }
+    }
+
+    /// Current status
+    pub fn status(&self) -> &NatStatus {
+        &self.status
+    }
+
+    /// Active relay reservations
+    pub fn reservations(&self) -> usize {
+        self.reserved.len()
+    }
+
+    /// Hole punch (successes, failures)
+    pub fn hole_punch_stats(&self) -> (u64, u64) {
+        (self.hole_punch_successes, self.hole_punch_failures)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn relay_addr() -> (PeerId, String) {
+        let peer_id = PeerId::random();
+        (
+            peer_id,
+            format!("/ip4/203.0.113.7/tcp/4001/p2p/{}", peer_id),
+        )
+    }
+
+    fn traversal(relays: &[String]) -> NatTraversal {
+        NatTraversal::new(&NatConfig {
+            relays: relays.to_vec(),
+            ..NatConfig::default()
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_parse_relays() {
+        let (peer_id, addr) = relay_addr();
+        let parsed = parse_relays(&[addr]).unwrap();
+        assert_eq!(parsed[0].0, peer_id);
+
+        assert!(parse_relays(&["/ip4/203.0.113.7/tcp/4001".to_string()]).is_err());
+        assert!(parse_relays(&["not an address".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_private_reserves_each_relay_once() {
+        let (relay, addr) = relay_addr();
+        let mut nat = traversal(&[addr]);
+
+        assert!(nat
+            .on_status_changed(NatStatus::Public {
+                address: "/ip4/198.51.100.1/tcp/9000".to_string()
+            })
+            .is_empty());
+
+        let circuits = nat.on_status_changed(NatStatus::Private);
+        assert_eq!(circuits.len(), 1);
+        assert_eq!(circuits[0].iter().last(), Some(Protocol::P2pCircuit));
+        assert!(nat.on_status_changed(NatStatus::Private).is_empty());
+
+        nat.on_reservation_accepted(relay);
+        assert_eq!(nat.reservations(), 1);
+    }
+
+    #[test]
+    fn test_relay_disconnect_allows_retry() {
+        let (relay, addr) = relay_addr();
+        let mut nat = traversal(&[addr]);
+        nat.on_status_changed(NatStatus::Private);
+        nat.on_reservation_accepted(relay);
+
+        nat.on_relay_disconnected(&relay);
+        assert_eq!(nat.reservations(), 0);
+        assert_eq!(nat.pending_circuits().len(), 1);
+    }
+
+    #[test]
+    fn test_unrequested_reservation_ignored() {
+        let mut nat = traversal(&[]);
+        nat.on_reservation_accepted(PeerId::random());
+        assert_eq!(nat.reservations(), 0);
+
+        nat.on_hole_punch(true);
+        nat.on_hole_punch(false);
+        nat.on_hole_punch(true);
+        assert_eq!(nat.hole_punch_stats(), (2, 1));
+    }
+}
@@ -222,6 +222,17 @@ impl PeerManager {
         self.update_stats();
     }
 
+    /// Adjust a peer's reputation, returning the new score
+    pub fn adjust_reputation(&self, id: &PeerId, delta: i8) -> Option<u8> {
+        let mut peers = self.peers.write();
+        let peer = peers.get_mut(id)?;
+        peer.update_reputation(delta);
+        let reputation = peer.reputation;
+        drop(peers);
+        self.update_stats();
+        Some(reputation)
+    }
+
     /// Get connected peers
     pub fn connected_peers(&self) -> Vec<PeerId> {
         self.connected.read().clone()
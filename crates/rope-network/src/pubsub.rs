@@ -0,0 +1,759 @@
+//! # Topic Pub/Sub
+//!
+//! Common publish API over gossipsub for string families and federations.
+//!
+//! ## Topics
+//!
+//! | Topic | Name |
+//! |-------|------|
+//! | String family | `/rope/strings/<family>/1.0.0` |
+//! | Federation | `/rope/federation/<id>/1.0.0` |
+//!
+//! ## Validation
+//!
+//! Every message on a typed topic is a signed [`PubsubMessage`]. Before a
+//! message is forwarded, [`TopicValidator`] checks, in order:
+//!
+//! 1. Size against `max_message_size` (reject)
+//! 2. Encoding and topic binding (reject)
+//! 3. Duplicates by message id (ignore)
+//! 4. Publisher signature (reject; unknown publishers are ignored)
+//! 5. Registered [`MessageValidator`] hooks
+//!
+//! Rejections feed gossipsub peer scoring, so peers that keep forwarding
+//! invalid messages are graylisted.
+//!
+//! ## Backpressure
+//!
+//! [`PubsubHandle`] publishes through the bounded swarm command queue.
+//! `publish` waits for capacity; `try_publish` fails fast with
+//! [`PubsubError::Backpressure`] so callers can shed load.
+
+use crate::swarm::SwarmCommand;
+use libp2p::gossipsub::{self, IdentTopic, PeerScoreParams, PeerScoreThresholds};
+use parking_lot::{Mutex, RwLock};
+use rope_crypto::hybrid::{HybridPublicKey, HybridSignature, HybridSigner, HybridVerifier};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use thiserror::Error;
+use tokio::sync::mpsc;
+
+/// Topic protocol version suffix
+const TOPIC_VERSION: &str = "1.0.0";
+
+/// Domain separator for pub/sub signatures
+const PUBSUB_SIGNATURE_DOMAIN: &[u8] = b"rope-pubsub-v1";
+
+// ============================================================================
+// Topics
+// ============================================================================
+
+/// Typed pub/sub topic
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Topic {
+    /// Strings of one family
+    Strings { family: String },
+
+    /// Traffic of one federation
+    Federation { id: String },
+}
+
+impl Topic {
+    /// Topic for a string family
+    pub fn strings(family: impl Into<String>) -> Self {
+        Topic::Strings {
+            family: family.into(),
+        }
+    }
+
+    /// Topic for a federation
+    pub fn federation(id: impl Into<String>) -> Self {
+        Topic::Federation { id: id.into() }
+    }
+
+    /// Full topic name
+    pub fn name(&self) -> String {
+        self.to_string()
+    }
+
+    /// Gossipsub topic
+    pub fn ident(&self) -> IdentTopic {
+        IdentTopic::new(self.name())
+    }
+}
+
+impl fmt::Display for Topic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Topic::Strings { family } => write!(f, "/rope/strings/{}/{}", family, TOPIC_VERSION),
+            Topic::Federation { id } => write!(f, "/rope/federation/{}/{}", id, TOPIC_VERSION),
+        }
+    }
+}
+
+impl FromStr for Topic {
+    type Err = PubsubError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<&str> = s.split('/').collect();
+        // "", "rope", kind, name, version
+        match parts.as_slice() {
+            ["", "rope", kind, name, TOPIC_VERSION] if is_valid_segment(name) => match *kind {
+                "strings" => Ok(Topic::strings(*name)),
+                "federation" => Ok(Topic::federation(*name)),
+                _ => Err(PubsubError::UnknownTopic(s.to_string())),
+            },
+            _ => Err(PubsubError::UnknownTopic(s.to_string())),
+        }
+    }
+}
+
+fn is_valid_segment(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.')
+}
+
+// ============================================================================
+// Messages
+// ============================================================================
+
+/// Signed message on a typed topic
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PubsubMessage {
+    /// Publisher node ID
+    pub publisher: [u8; 32],
+
+    /// Topic name the message was published to
+    pub topic: String,
+
+    /// Publisher sequence number
+    pub sequence: u64,
+
+    /// Timestamp
+    pub timestamp: i64,
+
+    /// Application payload
+    #[serde(with = "serde_bytes")]
+    pub payload: Vec<u8>,
+
+    /// Publisher signature
+    pub signature: HybridSignature,
+}
+
+impl PubsubMessage {
+    /// Create and sign a message
+    pub fn new(
+        signer: &HybridSigner,
+        publisher: [u8; 32],
+        topic: &Topic,
+        sequence: u64,
+        payload: Vec<u8>,
+    ) -> Self {
+        let mut msg = Self {
+            publisher,
+            topic: topic.name(),
+            sequence,
+            timestamp: chrono::Utc::now().timestamp(),
+            payload,
+            signature: HybridSignature::empty(),
+        };
+        msg.signature = signer.sign(&msg.signing_bytes());
+        msg
+    }
+
+    /// Bytes covered by the signature
+    pub fn signing_bytes(&self) -> Vec<u8> {
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(PUBSUB_SIGNATURE_DOMAIN);
+        hasher.update(&self.publisher);
+        hasher.update(&(self.topic.len() as u64).to_le_bytes());
+        hasher.update(self.topic.as_bytes());
+        hasher.update(&self.sequence.to_le_bytes());
+        hasher.update(&self.timestamp.to_le_bytes());
+        hasher.update(&self.payload);
+        hasher.finalize().as_bytes().to_vec()
+    }
+
+    /// Verify against the publisher's key
+    pub fn verify(&self, key: &HybridPublicKey) -> bool {
+        matches!(
+            HybridVerifier::verify(key, &self.signing_bytes(), &self.signature),
+            Ok(true)
+        )
+    }
+
+    /// Message ID (hash of the encoded message)
+    pub fn id(&self) -> [u8; 32] {
+        message_id(&self.encode())
+    }
+
+    /// Encode for the wire
+    pub fn encode(&self) -> Vec<u8> {
+        bincode::serialize(self).unwrap_or_default()
+    }
+
+    /// Decode from the wire
+    pub fn decode(data: &[u8]) -> Option<Self> {
+        bincode::deserialize(data).ok()
+    }
+}
+
+/// Message ID of raw gossip data
+pub fn message_id(data: &[u8]) -> [u8; 32] {
+    *blake3::hash(data).as_bytes()
+}
+
+// ============================================================================
+// Validation
+// ============================================================================
+
+/// Outcome of message validation
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ValidationResult {
+    /// Deliver and forward
+    Accept,
+    /// Drop without penalizing the forwarder
+    Ignore,
+    /// Drop and penalize the forwarder
+    Reject,
+}
+
+impl From<ValidationResult> for gossipsub::MessageAcceptance {
+    fn from(result: ValidationResult) -> Self {
+        match result {
+            ValidationResult::Accept => gossipsub::MessageAcceptance::Accept,
+            ValidationResult::Ignore => gossipsub::MessageAcceptance::Ignore,
+            ValidationResult::Reject => gossipsub::MessageAcceptance::Reject,
+        }
+    }
+}
+
+/// Application validation hook
+pub trait MessageValidator: Send + Sync {
+    /// Validate a message that passed the built-in checks
+    fn validate(&self, topic: &Topic, message: &PubsubMessage) -> ValidationResult;
+}
+
+impl<F> MessageValidator for F
+where
+    F: Fn(&Topic, &PubsubMessage) -> ValidationResult + Send + Sync,
+{
+    fn validate(&self, topic: &Topic, message: &PubsubMessage) -> ValidationResult {
+        self(topic, message)
+    }
+}
+
+/// Pub/sub configuration
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PubsubConfig {
+    /// Maximum encoded message size
+    pub max_message_size: usize,
+
+    /// Message IDs remembered for duplicate detection
+    pub dedup_capacity: usize,
+
+    /// Publish queue depth before backpressure
+    pub publish_queue: usize,
+
+    /// Peer scoring parameters
+    pub scoring: PeerScoreConfig,
+}
+
+impl Default for PubsubConfig {
+    fn default() -> Self {
+        Self {
+            max_message_size: 512 * 1024,
+            dedup_capacity: 100_000,
+            publish_queue: 256,
+            scoring: PeerScoreConfig::default(),
+        }
+    }
+}
+
+/// Gossipsub peer scoring parameters
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PeerScoreConfig {
+    /// Weight of each typed topic in the peer score
+    pub topic_weight: f64,
+
+    /// Reward for first deliveries (positive)
+    pub first_delivery_weight: f64,
+
+    /// Penalty for rejected messages (negative)
+    pub invalid_message_weight: f64,
+
+    /// Below this score we stop gossiping to the peer
+    pub gossip_threshold: f64,
+
+    /// Below this score we do not publish to the peer
+    pub publish_threshold: f64,
+
+    /// Below this score all messages from the peer are dropped
+    pub graylist_threshold: f64,
+
+    /// Score decay interval
+    pub decay_interval: Duration,
+}
+
+impl Default for PeerScoreConfig {
+    fn default() -> Self {
+        Self {
+            topic_weight: 0.5,
+            first_delivery_weight: 1.0,
+            invalid_message_weight: -100.0,
+            gossip_threshold: -10.0,
+            publish_threshold: -50.0,
+            graylist_threshold: -80.0,
+            decay_interval: Duration::from_secs(1),
+        }
+    }
+}
+
+impl PeerScoreConfig {
+    /// Global score parameters
+    pub fn params(&self) -> PeerScoreParams {
+        PeerScoreParams {
+            decay_interval: self.decay_interval,
+            ..Default::default()
+        }
+    }
+
+    /// Per-topic score parameters
+    pub fn topic_params(&self) -> gossipsub::TopicScoreParams {
+        gossipsub::TopicScoreParams {
+            topic_weight: self.topic_weight,
+            first_message_deliveries_weight: self.first_delivery_weight,
+            invalid_message_deliveries_weight: self.invalid_message_weight,
+            ..Default::default()
+        }
+    }
+
+    /// Score thresholds
+    pub fn thresholds(&self) -> PeerScoreThresholds {
+        PeerScoreThresholds {
+            gossip_threshold: self.gossip_threshold,
+            publish_threshold: self.publish_threshold,
+            graylist_threshold: self.graylist_threshold,
+            ..Default::default()
+        }
+    }
+}
+
+/// Validation statistics
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct ValidationStats {
+    pub accepted: u64,
+    pub ignored: u64,
+    pub rejected: u64,
+    pub duplicates: u64,
+    pub oversized: u64,
+    pub invalid_signatures: u64,
+}
+
+/// Bounded set of recently seen message IDs
+struct SeenCache {
+    order: VecDeque<[u8; 32]>,
+    ids: HashSet<[u8; 32]>,
+    capacity: usize,
+}
+
+impl SeenCache {
+    fn new(capacity: usize) -> Self {
+        Self {
+            order: VecDeque::new(),
+            ids: HashSet::new(),
+            capacity,
+        }
+    }
+
+    fn contains(&self, id: &[u8; 32]) -> bool {
+        self.ids.contains(id)
+    }
+
+    fn insert(&mut self, id: [u8; 32]) {
+        if !self.ids.insert(id) {
+            return;
+        }
+        self.order.push_back(id);
+        while self.order.len() > self.capacity {
+            if let Some(old) = self.order.pop_front() {
+                self.ids.remove(&old);
+            }
+        }
+    }
+}
+
+/// Validator for typed topics
+pub struct TopicValidator {
+    config: PubsubConfig,
+    publisher_keys: RwLock<HashMap<[u8; 32], HybridPublicKey>>,
+    seen: Mutex<SeenCache>,
+    hooks: RwLock<Vec<Arc<dyn MessageValidator>>>,
+    stats: RwLock<ValidationStats>,
+}
+
+impl TopicValidator {
+    /// Create a validator
+    pub fn new(config: PubsubConfig) -> Self {
+        let seen = SeenCache::new(config.dedup_capacity);
+        Self {
+            config,
+            publisher_keys: RwLock::new(HashMap::new()),
+            seen: Mutex::new(seen),
+            hooks: RwLock::new(Vec::new()),
+            stats: RwLock::new(ValidationStats::default()),
+        }
+    }
+
+    /// Register a publisher's signing key
+    pub fn register_publisher(&self, publisher: [u8; 32], key: HybridPublicKey) {
+        self.publisher_keys.write().insert(publisher, key);
+    }
+
+    /// Remove a publisher
+    pub fn remove_publisher(&self, publisher: &[u8; 32]) {
+        self.publisher_keys.write().remove(publisher);
+    }
+
+    /// Add an application validation hook
+    pub fn add_hook(&self, hook: Arc<dyn MessageValidator>) {
+        self.hooks.write().push(hook);
+    }
+
+    /// Validate raw gossip data received on `topic`
+    pub fn validate(
+        &self,
+        topic: &Topic,
+        data: &[u8],
+    ) -> (ValidationResult, Option<PubsubMessage>) {
+        let (result, message) = self.check(topic, data);
+        let mut stats = self.stats.write();
+        match result {
+            ValidationResult::Accept => stats.accepted += 1,
+            ValidationResult::Ignore => stats.ignored += 1,
+            ValidationResult::Reject => stats.rejected += 1,
+        }
+        (result, message)
+    }
+
+    fn check(&self, topic: &Topic, data: &[u8]) -> (ValidationResult, Option<PubsubMessage>) {
+        if data.len() > self.config.max_message_size {
+            self.stats.write().oversized += 1;
+            return (ValidationResult::Reject, None);
+        }
+
+        let Some(message) = PubsubMessage::decode(data) else {
+            return (ValidationResult::Reject, None);
+        };
+        if message.topic != topic.name() {
+            return (ValidationResult::Reject, None);
+        }
+
+        let id = message_id(data);
+        if self.seen.lock().contains(&id) {
+            self.stats.write().duplicates += 1;
+            return (ValidationResult::Ignore, None);
+        }
+
+        let key = self.publisher_keys.read().get(&message.publisher).cloned();
+        let Some(key) = key else {
+            return (ValidationResult::Ignore, None);
+        };
+        if !message.verify(&key) {
+            self.stats.write().invalid_signatures += 1;
+            return (ValidationResult::Reject, None);
+        }
+
+        let hooks = self.hooks.read().clone();
+        for hook in hooks {
+            match hook.validate(topic, &message) {
+                ValidationResult::Accept => {}
+                other => return (other, None),
+            }
+        }
+
+        self.seen.lock().insert(id);
+        (ValidationResult::Accept, Some(message))
+    }
+
+    /// Configuration
+    pub fn config(&self) -> &PubsubConfig {
+        &self.config
+    }
+
+    /// Statistics
+    pub fn stats(&self) -> ValidationStats {
+        self.stats.read().clone()
+    }
+}
+
+impl Default for TopicValidator {
+    fn default() -> Self {
+        Self::new(PubsubConfig::default())
+    }
+}
+
+// ============================================================================
+// Publishing
+// ============================================================================
+
+/// Pub/sub errors
+#[derive(Error, Debug)]
+pub enum PubsubError {
+    #[error("Unknown topic: {0}")]
+    UnknownTopic(String),
+
+    #[error("Message too large: {size} bytes (max {max})")]
+    TooLarge { size: usize, max: usize },
+
+    #[error("Publish queue full")]
+    Backpressure,
+
+    #[error("Swarm stopped")]
+    Closed,
+}
+
+/// Cloneable publisher shared by consensus and distribution
+#[derive(Clone)]
+pub struct PubsubHandle {
+    node_id: [u8; 32],
+    signer: Arc<HybridSigner>,
+    commands: mpsc::Sender<SwarmCommand>,
+    sequence: Arc<AtomicU64>,
+    max_message_size: usize,
+}
+
+impl PubsubHandle {
+    /// Create a handle over a swarm command queue
+    pub fn new(
+        node_id: [u8; 32],
+        signer: Arc<HybridSigner>,
+        commands: mpsc::Sender<SwarmCommand>,
+        max_message_size: usize,
+    ) -> Self {
+        Self {
+            node_id,
+            signer,
+            commands,
+            sequence: Arc::new(AtomicU64::new(0)),
+            max_message_size,
+        }
+    }
+
+    /// Publish, waiting for queue capacity. Returns the message ID.
+    pub async fn publish(&self, topic: &Topic, payload: Vec<u8>) -> Result<[u8; 32], PubsubError> {
+        let (command, id) = self.prepare(topic, payload)?;
+        self.commands
+            .send(command)
+            .await
+            .map_err(|_| PubsubError::Closed)?;
+        Ok(id)
+    }
+
+    /// Publish without waiting; fails with `Backpressure` when the queue is full
+    pub fn try_publish(&self, topic: &Topic, payload: Vec<u8>) -> Result<[u8; 32], PubsubError> {
+        let (command, id) = self.prepare(topic, payload)?;
+        self.commands.try_send(command).map_err(|e| match e {
+            mpsc::error::TrySendError::Full(_) => PubsubError::Backpressure,
+            mpsc::error::TrySendError::Closed(_) => PubsubError::Closed,
+        })?;
+        Ok(id)
+    }
+
+    /// Subscribe to a topic
+    pub async fn subscribe(&self, topic: &Topic) -> Result<(), PubsubError> {
+        self.commands
+            .send(SwarmCommand::Subscribe {
+                topic: topic.name(),
+            })
+            .await
+            .map_err(|_| PubsubError::Closed)
+    }
+
+    /// Unsubscribe from a topic
+    pub async fn unsubscribe(&self, topic: &Topic) -> Result<(), PubsubError> {
+        self.commands
+            .send(SwarmCommand::Unsubscribe {
+                topic: topic.name(),
+            })
+            .await
+            .map_err(|_| PubsubError::Closed)
+    }
+
+    /// Free slots in the publish queue
+    pub fn capacity(&self) -> usize {
+        self.commands.capacity()
+    }
+
+    fn prepare(
+        &self,
+        topic: &Topic,
+        payload: Vec<u8>,
+    ) -> Result<(SwarmCommand, [u8; 32]), PubsubError> {
+        let sequence = self.sequence.fetch_add(1, Ordering::Relaxed) + 1;
+        let data =
+            PubsubMessage::new(&self.signer, self.node_id, topic, sequence, payload).encode();
+        if data.len() > self.max_message_size {
+            return Err(PubsubError::TooLarge {
+                size: data.len(),
+                max: self.max_message_size,
+            });
+        }
+        let id = message_id(&data);
+        Ok((
+            SwarmCommand::Publish {
+                topic: topic.name(),
+                data,
+            },
+            id,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn signer(seed: u8) -> HybridSigner {
+        HybridSigner::from_seed(&[seed; 32]).0
+    }
+
+    fn validator_with(publisher: &HybridSigner) -> TopicValidator {
+        let validator = TopicValidator::default();
+        validator.register_publisher([1u8; 32], publisher.public_key());
+        validator
+    }
+
+    #[test]
+    fn test_topic_names() {
+        let topic = Topic::strings("payments");
+        assert_eq!(topic.name(), "/rope/strings/payments/1.0.0");
+        assert_eq!(
+            "/rope/strings/payments/1.0.0".parse::<Topic>().unwrap(),
+            topic
+        );
+        assert_eq!(
+            "/rope/federation/eu-west/1.0.0".parse::<Topic>().unwrap(),
+            Topic::federation("eu-west")
+        );
+        assert!("/rope/anchors/1.0.0".parse::<Topic>().is_err());
+        assert!("/rope/strings/a/b/1.0.0".parse::<Topic>().is_err());
+    }
+
+    #[test]
+    fn test_valid_message_accepted_once() {
+        let publisher = signer(1);
+        let validator = validator_with(&publisher);
+        let topic = Topic::strings("payments");
+        let data = PubsubMessage::new(&publisher, [1u8; 32], &topic, 1, b"hello".to_vec()).encode();
+
+        let (result, message) = validator.validate(&topic, &data);
+        assert_eq!(result, ValidationResult::Accept);
+        assert_eq!(message.unwrap().payload, b"hello");
+
+        let (result, _) = validator.validate(&topic, &data);
+        assert_eq!(result, ValidationResult::Ignore);
+        assert_eq!(validator.stats().duplicates, 1);
+    }
+
+    #[test]
+    fn test_invalid_messages_rejected() {
+        let publisher = signer(1);
+        let validator = validator_with(&publisher);
+        let topic = Topic::strings("payments");
+
+        // Forged signature
+        let forged = PubsubMessage::new(&signer(2), [1u8; 32], &topic, 1, vec![1]).encode();
+        assert_eq!(
+            validator.validate(&topic, &forged).0,
+            ValidationResult::Reject
+        );
+
+        // Replayed onto another topic
+        let other = Topic::federation("eu");
+        let moved = PubsubMessage::new(&publisher, [1u8; 32], &other, 1, vec![1]).encode();
+        assert_eq!(
+            validator.validate(&topic, &moved).0,
+            ValidationResult::Reject
+        );
+
+        // Oversized
+        let big = vec![0u8; validator.config().max_message_size + 1];
+        assert_eq!(validator.validate(&topic, &big).0, ValidationResult::Reject);
+
+        // Garbage
+        assert_eq!(
+            validator.validate(&topic, b"junk").0,
+            ValidationResult::Reject
+        );
+
+        // Unknown publisher is ignored, not penalized
+        let unknown = PubsubMessage::new(&signer(3), [3u8; 32], &topic, 1, vec![1]).encode();
+        assert_eq!(
+            validator.validate(&topic, &unknown).0,
+            ValidationResult::Ignore
+        );
+
+        let stats = validator.stats();
+        assert_eq!(stats.rejected, 4);
+        assert_eq!(stats.invalid_signatures, 1);
+        assert_eq!(stats.oversized, 1);
+    }
+
+    #[test]
+    fn test_validation_hooks() {
+        let publisher = signer(1);
+        let validator = validator_with(&publisher);
+        validator.add_hook(Arc::new(|_: &Topic, msg: &PubsubMessage| {
+            if msg.payload.is_empty() {
+                ValidationResult::Reject
+            } else {
+                ValidationResult::Accept
+            }
+        }));
+
+        let topic = Topic::strings("payments");
+        let empty = PubsubMessage::new(&publisher, [1u8; 32], &topic, 1, Vec::new()).encode();
+        let full = PubsubMessage::new(&publisher, [1u8; 32], &topic, 2, vec![1]).encode();
+        assert_eq!(
+            validator.validate(&topic, &empty).0,
+            ValidationResult::Reject
+        );
+        assert_eq!(
+            validator.validate(&topic, &full).0,
+            ValidationResult::Accept
+        );
+    }
+
+    #[tokio::test]
+    async fn test_publish_backpressure() {
+        let (tx, mut rx) = mpsc::channel(2);
+        let handle = PubsubHandle::new([1u8; 32], Arc::new(signer(1)), tx, 1024);
+        let topic = Topic::strings("payments");
+
+        handle.try_publish(&topic, vec![1]).unwrap();
+        handle.publish(&topic, vec![2]).await.unwrap();
+        assert!(matches!(
+            handle.try_publish(&topic, vec![3]),
+            Err(PubsubError::Backpressure)
+        ));
+        assert!(matches!(
+            handle.try_publish(&topic, vec![0u8; 2048]),
+            Err(PubsubError::TooLarge { .. })
+        ));
+
+        let Some(SwarmCommand::Publish { topic: name, data }) = rx.recv().await else {
+            panic!("expected publish command");
+        };
+        assert_eq!(name, topic.name());
+        assert_eq!(PubsubMessage::decode(&data).unwrap().sequence, 1);
+        assert_eq!(handle.capacity(), 1);
+    }
+}
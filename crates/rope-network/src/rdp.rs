@@ -18,10 +18,13 @@
 //! 5. Complete string is verified against StringId
 //! 6. Client becomes seeder
 
+use crate::bandwidth::{NetworkStats, TrafficChannel};
+use crate::score::{Offense, PeerScore};
 use parking_lot::RwLock;
 use rope_core::types::StringId;
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::Arc;
 use std::time::Duration;
 
 /// RDP configuration
@@ -114,6 +117,29 @@ pub struct StringMetadata {
     pub creator: [u8; 32],
 }
 
+impl StringMetadata {
+    /// Metadata for seeding `data` in `piece_size` pieces
+    pub fn for_data(
+        string_id: StringId,
+        data: &[u8],
+        piece_size: usize,
+        creator: [u8; 32],
+    ) -> Self {
+        let piece_hashes: Vec<[u8; 32]> = data
+            .chunks(piece_size)
+            .map(|piece| *blake3::hash(piece).as_bytes())
+            .collect();
+        Self {
+            string_id,
+            total_size: data.len(),
+            piece_count: piece_hashes.len() as u32,
+            piece_hashes,
+            created_at: chrono::Utc::now().timestamp(),
+            creator,
+        }
+    }
+}
+
 /// Swarm member
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct SwarmMember {
@@ -349,6 +375,24 @@ impl Swarm {
         false
     }
 
+    /// When a piece request was sent, and to whom
+    pub fn download_started(&self, piece_idx: u32) -> Option<([u8; 32], i64)> {
+        match self.pieces.read().get(piece_idx as usize)?.state {
+            PieceState::Downloading { from, started_at } => Some((from, started_at)),
+            _ => None,
+        }
+    }
+
+    /// Members still missing pieces
+    pub fn leechers(&self) -> Vec<[u8; 32]> {
+        self.members
+            .read()
+            .values()
+            .filter(|m| !m.is_seeder)
+            .map(|m| m.node_id)
+            .collect()
+    }
+
     /// Check if download is complete
     pub fn is_complete(&self) -> bool {
         self.pieces
@@ -433,6 +477,12 @@ pub struct RopeDistributionProtocol {
     /// Upload history
     upload_history: RwLock<HashMap<StringId, u64>>,
 
+    /// Peer scores driving choking (optional)
+    scores: Option<Arc<PeerScore>>,
+
+    /// Per-peer byte counters (optional)
+    bandwidth: Option<Arc<NetworkStats>>,
+
     /// Statistics
     stats: RwLock<RdpStats>,
 }
@@ -457,10 +507,51 @@ impl RopeDistributionProtocol {
             swarms: RwLock::new(HashMap::new()),
             download_history: RwLock::new(HashMap::new()),
             upload_history: RwLock::new(HashMap::new()),
+            scores: None,
+            bandwidth: None,
             stats: RwLock::new(RdpStats::default()),
         }
     }
 
+    /// Choke greylisted peers and report failed transfers to `scores`
+    pub fn with_peer_score(mut self, scores: Arc<PeerScore>) -> Self {
+        self.scores = Some(scores);
+        self
+    }
+
+    /// Count piece traffic in `bandwidth`
+    pub fn with_network_stats(mut self, bandwidth: Arc<NetworkStats>) -> Self {
+        self.bandwidth = Some(bandwidth);
+        self
+    }
+
+    /// Size of the pieces this node seeds
+    pub fn piece_size(&self) -> usize {
+        self.config.piece_size
+    }
+
+    /// Whether uploads to `peer` are choked
+    pub fn is_choked(&self, peer: &[u8; 32]) -> bool {
+        self.scores
+            .as_ref()
+            .map(|scores| scores.is_greylisted(peer))
+            .unwrap_or(false)
+    }
+
+    /// Leechers to unchoke, best scored first, up to the upload slot limit
+    pub fn unchoked_peers(&self, string_id: &StringId) -> Vec<[u8; 32]> {
+        let Some(leechers) = self.swarms.read().get(string_id).map(|s| s.leechers()) else {
+            return Vec::new();
+        };
+        match &self.scores {
+            Some(scores) => scores.rank_for_unchoke(&leechers, self.config.max_upload_slots),
+            None => leechers
+                .into_iter()
+                .take(self.config.max_upload_slots)
+                .collect(),
+        }
+    }
+
     /// Start downloading a string
     pub fn start_download(&self, metadata: StringMetadata) -> StringId {
         let string_id = metadata.string_id;
@@ -564,6 +655,10 @@ impl RopeDistributionProtocol {
             }
 
             RdpMessage::Request { piece_idx } => {
+                if self.is_choked(&from) {
+                    return Some(RdpMessage::Choke);
+                }
+
                 // Send piece if we have it
                 let piece_data = {
                     let pieces = swarm.pieces.read();
@@ -576,6 +671,9 @@ impl RopeDistributionProtocol {
                         .entry(*string_id)
                         .and_modify(|v| *v += data.len() as u64)
                         .or_insert(data.len() as u64);
+                    if let Some(bandwidth) = &self.bandwidth {
+                        bandwidth.record_sent(&from, TrafficChannel::Rdp, data.len());
+                    }
                     self.update_stats();
                     RdpMessage::Piece { piece_idx, data }
                 })
@@ -585,7 +683,23 @@ impl RopeDistributionProtocol {
                 // Receive piece
                 drop(swarms);
                 if let Some(swarm) = self.swarms.read().get(string_id) {
-                    swarm.receive_piece(piece_idx, data.clone());
+                    let started = swarm.download_started(piece_idx);
+                    let verified = swarm.receive_piece(piece_idx, data.clone());
+
+                    // Only verified pieces count as the sender's upload
+                    if let (true, Some(bandwidth)) = (verified, &self.bandwidth) {
+                        bandwidth.record_received(&from, TrafficChannel::Rdp, data.len());
+                    }
+
+                    if let Some(scores) = &self.scores {
+                        if !verified {
+                            scores.record(&from, Offense::FailedChunk);
+                        } else if let Some((_, started_at)) = started {
+                            let elapsed = chrono::Utc::now().timestamp() - started_at;
+                            scores
+                                .record_response(&from, Duration::from_secs(elapsed.max(0) as u64));
+                        }
+                    }
 
                     self.download_history
                         .write()
@@ -784,4 +898,68 @@ mod tests {
         let (piece_idx, _) = swarm.next_piece_to_download().unwrap();
         assert!(piece_idx == 1 || piece_idx == 2);
     }
+
+    #[test]
+    fn test_choking_follows_peer_score() {
+        let data = vec![7u8; 1024];
+        let metadata = StringMetadata {
+            string_id: StringId::from_content(&data),
+            total_size: data.len(),
+            piece_count: 1,
+            piece_hashes: vec![*blake3::hash(&data).as_bytes()],
+            created_at: 0,
+            creator: [0u8; 32],
+        };
+        let string_id = metadata.string_id;
+
+        let scores = Arc::new(PeerScore::default());
+        let rdp = RopeDistributionProtocol::new([0u8; 32], RdpConfig::default())
+            .with_peer_score(scores.clone());
+        rdp.join_as_seeder(metadata, data);
+
+        let (good, bad) = ([1u8; 32], [2u8; 32]);
+        for peer in [good, bad] {
+            rdp.handle_message(&string_id, peer, RdpMessage::Join { string_id });
+        }
+        for _ in 0..5 {
+            scores.record(&bad, Offense::FailedChunk);
+        }
+
+        assert!(matches!(
+            rdp.handle_message(&string_id, good, RdpMessage::Request { piece_idx: 0 }),
+            Some(RdpMessage::Piece { .. })
+        ));
+        assert!(matches!(
+            rdp.handle_message(&string_id, bad, RdpMessage::Request { piece_idx: 0 }),
+            Some(RdpMessage::Choke)
+        ));
+        assert_eq!(rdp.unchoked_peers(&string_id), vec![good]);
+    }
+
+    #[test]
+    fn test_corrupt_piece_penalizes_sender() {
+        let metadata = StringMetadata {
+            string_id: StringId::from_content(b"test"),
+            total_size: 1024,
+            piece_count: 1,
+            piece_hashes: vec![[1u8; 32]],
+            created_at: 0,
+            creator: [0u8; 32],
+        };
+        let string_id = metadata.string_id;
+
+        let scores = Arc::new(PeerScore::default());
+        let rdp = RopeDistributionProtocol::new([0u8; 32], RdpConfig::default())
+            .with_peer_score(scores.clone());
+        rdp.start_download(metadata);
+
+        let piece = RdpMessage::Piece {
+            piece_idx: 0,
+            data: vec![0u8; 1024],
+        };
+        rdp.handle_message(&string_id, [3u8; 32], piece);
+
+        assert!(scores.score(&[3u8; 32]) < 0.0);
+        assert_eq!(scores.snapshot()[0].offenses.failed_chunks, 1);
+    }
 }
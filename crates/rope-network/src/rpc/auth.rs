@@ -0,0 +1,340 @@
+//! RPC authentication and per-identity rate limiting
+//!
+//! [`AuthInterceptor`] runs before every call. It resolves the caller's
+//! [`Identity`] from a JWT bearer token (HS256) or, failing that, from the
+//! client certificate presented over mTLS, charges that identity's token
+//! bucket, and stores the identity in the request extensions for the
+//! service to check scopes against.
+
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tonic::service::Interceptor;
+use tonic::{Request, Status};
+
+/// Scope required to submit strings
+pub const SCOPE_WRITE: &str = "strings:write";
+
+/// Buckets tracked before idle ones are pruned
+const MAX_TRACKED_IDENTITIES: usize = 10_000;
+
+/// JWT claims
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Claims {
+    /// Subject (client identity)
+    pub sub: String,
+
+    /// Expiry (unix seconds)
+    pub exp: u64,
+
+    /// Issuer
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub iss: Option<String>,
+
+    /// Space-separated scopes
+    #[serde(default)]
+    pub scope: String,
+}
+
+impl Claims {
+    /// Granted scopes
+    pub fn scopes(&self) -> Vec<String> {
+        self.scope.split_whitespace().map(str::to_string).collect()
+    }
+}
+
+/// HS256 JWT validator
+pub struct JwtValidator {
+    key: jsonwebtoken::DecodingKey,
+    validation: jsonwebtoken::Validation,
+}
+
+impl JwtValidator {
+    /// Create a validator for tokens signed with `secret`
+    pub fn new(secret: &[u8], issuer: Option<&str>) -> Self {
+        let mut validation = jsonwebtoken::Validation::new(jsonwebtoken::Algorithm::HS256);
+        if let Some(issuer) = issuer {
+            validation.set_issuer(&[issuer]);
+        }
+        Self {
+            key: jsonwebtoken::DecodingKey::from_secret(secret),
+            validation,
+        }
+    }
+
+    /// Validate a token and return its claims
+    pub fn validate(&self, token: &str) -> Result<Claims, jsonwebtoken::errors::Error> {
+        jsonwebtoken::decode::<Claims>(token, &self.key, &self.validation).map(|data| data.claims)
+    }
+}
+
+/// Authenticated caller
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Identity {
+    /// JWT bearer
+    Token {
+        subject: String,
+        scopes: Vec<String>,
+    },
+
+    /// Client certificate (blake3 fingerprint)
+    Certificate { fingerprint: String },
+
+    /// Unauthenticated caller (JWT disabled)
+    Address(Option<IpAddr>),
+}
+
+impl Identity {
+    /// Rate limit bucket key
+    pub fn key(&self) -> String {
+        match self {
+            Identity::Token { subject, .. } => format!("jwt:{}", subject),
+            Identity::Certificate { fingerprint } => format!("cert:{}", fingerprint),
+            Identity::Address(Some(ip)) => format!("ip:{}", ip),
+            Identity::Address(None) => "ip:unknown".to_string(),
+        }
+    }
+
+    /// Whether the caller holds `scope`
+    ///
+    /// Certificate holders are operators and hold every scope; so does
+    /// everyone when JWT auth is disabled.
+    pub fn has_scope(&self, scope: &str) -> bool {
+        match self {
+            Identity::Token { scopes, .. } => scopes.iter().any(|s| s == scope),
+            Identity::Certificate { .. } | Identity::Address(_) => true,
+        }
+    }
+}
+
+struct Bucket {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+/// Token bucket rate limiter keyed by identity
+pub struct IdentityRateLimiter {
+    rate: f64,
+    burst: f64,
+    buckets: Mutex<HashMap<String, Bucket>>,
+}
+
+impl IdentityRateLimiter {
+    /// `rate` requests per second with `burst` capacity; a rate of zero
+    /// disables limiting
+    pub fn new(rate: u32, burst: u32) -> Self {
+        Self {
+            rate: rate as f64,
+            burst: burst.max(1) as f64,
+            buckets: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Take one token for `key`
+    pub fn check(&self, key: &str) -> bool {
+        if self.rate == 0.0 {
+            return true;
+        }
+
+        let now = Instant::now();
+        let mut buckets = self.buckets.lock();
+        if buckets.len() >= MAX_TRACKED_IDENTITIES && !buckets.contains_key(key) {
+            self.prune_locked(&mut buckets, now);
+        }
+
+        let bucket = buckets.entry(key.to_string()).or_insert(Bucket {
+            tokens: self.burst,
+            last_refill: now,
+        });
+        let elapsed = now.duration_since(bucket.last_refill).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * self.rate).min(self.burst);
+        bucket.last_refill = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Number of tracked identities
+    pub fn tracked(&self) -> usize {
+        self.buckets.lock().len()
+    }
+
+    /// Drop buckets that have refilled completely
+    fn prune_locked(&self, buckets: &mut HashMap<String, Bucket>, now: Instant) {
+        let full_after = Duration::from_secs_f64(self.burst / self.rate);
+        buckets.retain(|_, b| now.duration_since(b.last_refill) < full_after);
+    }
+}
+
+/// Interceptor authenticating and rate limiting every call
+#[derive(Clone)]
+pub struct AuthInterceptor {
+    jwt: Option<Arc<JwtValidator>>,
+    limiter: Arc<IdentityRateLimiter>,
+}
+
+impl AuthInterceptor {
+    /// `jwt` of `None` disables token authentication
+    pub fn new(jwt: Option<Arc<JwtValidator>>, limiter: Arc<IdentityRateLimiter>) -> Self {
+        Self { jwt, limiter }
+    }
+
+    /// Resolve the caller's identity
+    pub fn authenticate<T>(&self, request: &Request<T>) -> Result<Identity, Status> {
+        let bearer = request
+            .metadata()
+            .get("authorization")
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.strip_prefix("Bearer "));
+
+        if let (Some(jwt), Some(token)) = (&self.jwt, bearer) {
+            let claims = jwt
+                .validate(token)
+                .map_err(|e| Status::unauthenticated(format!("Invalid token: {}", e)))?;
+            return Ok(Identity::Token {
+                scopes: claims.scopes(),
+                subject: claims.sub,
+            });
+        }
+
+        if let Some(cert) = request
+            .peer_certs()
+            .and_then(|certs| certs.first().cloned())
+        {
+            let fingerprint = hex::encode(&blake3::hash(cert.as_ref()).as_bytes()[..16]);
+            return Ok(Identity::Certificate { fingerprint });
+        }
+
+        if self.jwt.is_some() {
+            return Err(Status::unauthenticated("Missing bearer token"));
+        }
+        Ok(Identity::Address(request.remote_addr().map(|a| a.ip())))
+    }
+}
+
+impl Interceptor for AuthInterceptor {
+    fn call(&mut self, mut request: Request<()>) -> Result<Request<()>, Status> {
+        let identity = self.authenticate(&request)?;
+        if !self.limiter.check(&identity.key()) {
+            return Err(Status::resource_exhausted("Rate limit exceeded"));
+        }
+        request.extensions_mut().insert(identity);
+        Ok(request)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SECRET: &[u8] = b"test-secret";
+
+    fn token(sub: &str, scope: &str, exp_offset: i64, secret: &[u8]) -> String {
+        let claims = Claims {
+            sub: sub.to_string(),
+            exp: (chrono::Utc::now().timestamp() + exp_offset) as u64,
+            iss: Some("rope-auth".to_string()),
+            scope: scope.to_string(),
+        };
+        jsonwebtoken::encode(
+            &jsonwebtoken::Header::default(),
+            &claims,
+            &jsonwebtoken::EncodingKey::from_secret(secret),
+        )
+        .unwrap()
+    }
+
+    fn request_with(token: &str) -> Request<()> {
+        let mut request = Request::new(());
+        request.metadata_mut().insert(
+            "authorization",
+            format!("Bearer {}", token).parse().unwrap(),
+        );
+        request
+    }
+
+    fn interceptor(rate: u32, burst: u32) -> AuthInterceptor {
+        AuthInterceptor::new(
+            Some(Arc::new(JwtValidator::new(SECRET, Some("rope-auth")))),
+            Arc::new(IdentityRateLimiter::new(rate, burst)),
+        )
+    }
+
+    #[test]
+    fn test_jwt_validation() {
+        let jwt = JwtValidator::new(SECRET, Some("rope-auth"));
+        let claims = jwt
+            .validate(&token("alice", "strings:write read", 60, SECRET))
+            .unwrap();
+        assert_eq!(claims.sub, "alice");
+        assert_eq!(claims.scopes(), vec!["strings:write", "read"]);
+
+        assert!(jwt.validate(&token("alice", "", -120, SECRET)).is_err());
+        assert!(jwt.validate(&token("alice", "", 60, b"other")).is_err());
+        assert!(JwtValidator::new(SECRET, Some("elsewhere"))
+            .validate(&token("alice", "", 60, SECRET))
+            .is_err());
+    }
+
+    #[test]
+    fn test_interceptor_sets_identity() {
+        let mut auth = interceptor(100, 100);
+        let request = auth
+            .call(request_with(&token("alice", SCOPE_WRITE, 60, SECRET)))
+            .unwrap();
+        let identity = request.extensions().get::<Identity>().unwrap();
+        assert_eq!(identity.key(), "jwt:alice");
+        assert!(identity.has_scope(SCOPE_WRITE));
+
+        let status = auth.call(Request::new(())).unwrap_err();
+        assert_eq!(status.code(), tonic::Code::Unauthenticated);
+
+        let status = auth.call(request_with("not-a-jwt")).unwrap_err();
+        assert_eq!(status.code(), tonic::Code::Unauthenticated);
+    }
+
+    #[test]
+    fn test_jwt_disabled_falls_back_to_address() {
+        let mut auth = AuthInterceptor::new(None, Arc::new(IdentityRateLimiter::new(10, 10)));
+        let request = auth.call(Request::new(())).unwrap();
+        assert_eq!(
+            request.extensions().get::<Identity>(),
+            Some(&Identity::Address(None))
+        );
+    }
+
+    #[test]
+    fn test_rate_limit_per_identity() {
+        let mut auth = interceptor(1, 2);
+        let alice = token("alice", "", 60, SECRET);
+        let bob = token("bob", "", 60, SECRET);
+
+        assert!(auth.call(request_with(&alice)).is_ok());
+        assert!(auth.call(request_with(&alice)).is_ok());
+        let status = auth.call(request_with(&alice)).unwrap_err();
+        assert_eq!(status.code(), tonic::Code::ResourceExhausted);
+
+        // Separate bucket
+        assert!(auth.call(request_with(&bob)).is_ok());
+    }
+
+    #[test]
+    fn test_rate_limiter_refills() {
+        let limiter = IdentityRateLimiter::new(1000, 1);
+        assert!(limiter.check("a"));
+        assert!(!limiter.check("a"));
+        std::thread::sleep(Duration::from_millis(5));
+        assert!(limiter.check("a"));
+        assert_eq!(limiter.tracked(), 1);
+
+        let unlimited = IdentityRateLimiter::new(0, 0);
+        assert!((0..1000).all(|_| unlimited.check("a")));
+    }
+}
@@ -2,6 +2,31 @@
 //!
 //! gRPC API server for external clients.
 //! Provides HTTP/2 + mTLS with JWT authentication.
+//!
+//! ## Service (`proto/rope_rpc.proto`)
+//!
+//! | Method | Kind | Scope |
+//! |--------|------|-------|
+//! | `SubmitString` | unary | `strings:write` |
+//! | `GetFinality` | unary | any |
+//! | `SubscribeAnchors` | server stream | any |
+//!
+//! Callers are identified by their JWT subject, else by their client
+//! certificate, else by remote address; each identity has its own rate
+//! limit bucket.
+
+pub mod auth;
+pub mod service;
+
+/// Generated protobuf types and gRPC stubs
+pub mod proto {
+    tonic::include_proto!("rope.rpc.v1");
+}
+
+pub use auth::{AuthInterceptor, Claims, Identity, IdentityRateLimiter, JwtValidator};
+pub use service::{
+    AnchorNotice, Finality, RopeRpcService, RpcBackend, RpcError, RpcServer, StringSubmission,
+};
 
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
@@ -30,15 +55,24 @@ pub struct RpcConfig {
     /// TLS key path
     pub tls_key_path: Option<String>,
 
+    /// CA bundle for client certificates (enables mTLS)
+    pub tls_client_ca_path: Option<String>,
+
     /// Enable JWT authentication
     pub enable_jwt: bool,
 
     /// JWT secret (for HMAC)
     pub jwt_secret: Option<String>,
 
-    /// Rate limit (requests per second per IP)
+    /// Required JWT issuer
+    pub jwt_issuer: Option<String>,
+
+    /// Rate limit (requests per second per identity)
     pub rate_limit: u32,
 
+    /// Burst allowance per identity
+    pub rate_burst: u32,
+
     /// Enable request logging
     pub enable_logging: bool,
 }
@@ -53,9 +87,12 @@ impl Default for RpcConfig {
             enable_tls: true,
             tls_cert_path: None,
             tls_key_path: None,
+            tls_client_ca_path: None,
             enable_jwt: true,
             jwt_secret: None,
+            jwt_issuer: None,
             rate_limit: 100,
+            rate_burst: 200,
             enable_logging: true,
         }
     }
@@ -0,0 +1,532 @@
+//! gRPC client service
+//!
+//! [`RopeRpcService`] adapts the generated `RopeRpc` service to an
+//! [`RpcBackend`] supplied by the node, and [`RpcServer`] serves it over
+//! HTTP/2 with TLS (mTLS when a client CA is configured) behind the
+//! [`AuthInterceptor`].
+
+use super::auth::{AuthInterceptor, Identity, IdentityRateLimiter, JwtValidator, SCOPE_WRITE};
+use super::proto::{self, rope_rpc_server::RopeRpc, rope_rpc_server::RopeRpcServer};
+use super::RpcConfig;
+use crate::bandwidth::{peer_key, NetworkStats, TrafficChannel};
+use std::future::Future;
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::sync::Arc;
+use thiserror::Error;
+use tokio::sync::broadcast;
+use tokio_stream::wrappers::errors::BroadcastStreamRecvError;
+use tokio_stream::wrappers::BroadcastStream;
+use tokio_stream::{Stream, StreamExt};
+use tonic::transport::{Certificate, Server, ServerTlsConfig};
+use tonic::{Request, Response, Status};
+
+/// String submitted by a client
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StringSubmission {
+    pub content: Vec<u8>,
+    pub creator: [u8; 32],
+    pub signature: Vec<u8>,
+    pub parents: Vec<[u8; 32]>,
+}
+
+/// Finality of a string as reported to clients
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Finality {
+    Pending,
+    Tentative { confidence: u8 },
+    Final { anchor_id: [u8; 32] },
+    Rejected { reason: String },
+}
+
+/// Finalized anchor pushed to subscribers
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AnchorNotice {
+    pub round: u64,
+    pub anchor_id: [u8; 32],
+    pub finalized_strings: Vec<[u8; 32]>,
+    pub timestamp: i64,
+}
+
+/// Node-side implementation of the client API
+#[async_trait::async_trait]
+pub trait RpcBackend: Send + Sync + 'static {
+    /// Accept a string, returning its ID
+    async fn submit_string(&self, submission: StringSubmission) -> Result<[u8; 32], RpcError>;
+
+    /// Finality of a known string
+    async fn finality(&self, string_id: &[u8; 32]) -> Result<Finality, RpcError>;
+
+    /// Anchor feed
+    fn anchors(&self) -> broadcast::Receiver<AnchorNotice>;
+}
+
+/// RPC errors
+#[derive(Error, Debug)]
+pub enum RpcError {
+    #[error("Invalid request: {0}")]
+    InvalidRequest(String),
+
+    #[error("Not found")]
+    NotFound,
+
+    #[error("Rejected: {0}")]
+    Rejected(String),
+
+    #[error("Unavailable: {0}")]
+    Unavailable(String),
+
+    #[error("Configuration error: {0}")]
+    Config(String),
+
+    #[error("Transport error: {0}")]
+    Transport(#[from] tonic::transport::Error),
+
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+impl From<RpcError> for Status {
+    fn from(e: RpcError) -> Self {
+        match e {
+            RpcError::InvalidRequest(msg) => Status::invalid_argument(msg),
+            RpcError::NotFound => Status::not_found("Not found"),
+            RpcError::Rejected(msg) => Status::failed_precondition(msg),
+            RpcError::Unavailable(msg) => Status::unavailable(msg),
+            other => Status::internal(other.to_string()),
+        }
+    }
+}
+
+/// gRPC service over an [`RpcBackend`]
+pub struct RopeRpcService {
+    backend: Arc<dyn RpcBackend>,
+    bandwidth: Option<Arc<NetworkStats>>,
+}
+
+impl RopeRpcService {
+    /// Wrap a backend
+    pub fn new(backend: Arc<dyn RpcBackend>) -> Self {
+        Self {
+            backend,
+            bandwidth: None,
+        }
+    }
+
+    /// Count submitted bytes per caller in `bandwidth`
+    pub fn with_network_stats(mut self, bandwidth: Arc<NetworkStats>) -> Self {
+        self.bandwidth = Some(bandwidth);
+        self
+    }
+}
+
+fn identity<T>(request: &Request<T>) -> Result<&Identity, Status> {
+    request
+        .extensions()
+        .get::<Identity>()
+        .ok_or_else(|| Status::unauthenticated("No identity"))
+}
+
+fn to_id(bytes: &[u8], field: &str) -> Result<[u8; 32], Status> {
+    bytes
+        .try_into()
+        .map_err(|_| Status::invalid_argument(format!("{} must be 32 bytes", field)))
+}
+
+impl From<AnchorNotice> for proto::AnchorEvent {
+    fn from(notice: AnchorNotice) -> Self {
+        Self {
+            round: notice.round,
+            anchor_id: notice.anchor_id.to_vec(),
+            finalized_strings: notice
+                .finalized_strings
+                .iter()
+                .map(|id| id.to_vec())
+                .collect(),
+            timestamp: notice.timestamp,
+        }
+    }
+}
+
+impl From<Finality> for proto::GetFinalityResponse {
+    fn from(finality: Finality) -> Self {
+        let mut response = Self::default();
+        match finality {
+            Finality::Pending => response.set_state(proto::FinalityState::Pending),
+            Finality::Tentative { confidence } => {
+                response.set_state(proto::FinalityState::Tentative);
+                response.confidence = confidence as u32;
+            }
+            Finality::Final { anchor_id } => {
+                response.set_state(proto::FinalityState::Final);
+                response.anchor_id = anchor_id.to_vec();
+            }
+            Finality::Rejected { reason } => {
+                response.set_state(proto::FinalityState::Rejected);
+                response.reason = reason;
+            }
+        }
+        response
+    }
+}
+
+type AnchorStream = Pin<Box<dyn Stream<Item = Result<proto::AnchorEvent, Status>> + Send>>;
+
+#[tonic::async_trait]
+impl RopeRpc for RopeRpcService {
+    async fn submit_string(
+        &self,
+        request: Request<proto::SubmitStringRequest>,
+    ) -> Result<Response<proto::SubmitStringResponse>, Status> {
+        let caller = identity(&request)?;
+        if !caller.has_scope(SCOPE_WRITE) {
+            return Err(Status::permission_denied(format!(
+                "Scope '{}' required",
+                SCOPE_WRITE
+            )));
+        }
+        let caller = peer_key(caller.key().as_bytes());
+
+        let req = request.into_inner();
+        if let Some(bandwidth) = &self.bandwidth {
+            bandwidth.record_received(&caller, TrafficChannel::Rpc, req.content.len());
+        }
+        if req.content.is_empty() {
+            return Err(Status::invalid_argument("Empty content"));
+        }
+        let submission = StringSubmission {
+            content: req.content,
+            creator: to_id(&req.creator, "creator")?,
+            signature: req.signature,
+            parents: req
+                .parents
+                .iter()
+                .map(|p| to_id(p, "parent"))
+                .collect::<Result<_, _>>()?,
+        };
+
+        let string_id = self.backend.submit_string(submission).await?;
+        if let Some(bandwidth) = &self.bandwidth {
+            bandwidth.record_sent(&caller, TrafficChannel::Rpc, string_id.len());
+        }
+        Ok(Response::new(proto::SubmitStringResponse {
+            string_id: string_id.to_vec(),
+        }))
+    }
+
+    async fn get_finality(
+        &self,
+        request: Request<proto::GetFinalityRequest>,
+    ) -> Result<Response<proto::GetFinalityResponse>, Status> {
+        identity(&request)?;
+        let string_id = to_id(&request.get_ref().string_id, "string_id")?;
+        let finality = self.backend.finality(&string_id).await?;
+        Ok(Response::new(finality.into()))
+    }
+
+    type SubscribeAnchorsStream = AnchorStream;
+
+    async fn subscribe_anchors(
+        &self,
+        request: Request<proto::SubscribeAnchorsRequest>,
+    ) -> Result<Response<Self::SubscribeAnchorsStream>, Status> {
+        identity(&request)?;
+        let from_round = request.get_ref().from_round;
+
+        let stream = BroadcastStream::new(self.backend.anchors()).filter_map(move |item| {
+            match item {
+                Ok(notice) if notice.round >= from_round => Some(Ok(notice.into())),
+                Ok(_) => None,
+                // End the stream so the client resubscribes from its last round
+                Err(BroadcastStreamRecvError::Lagged(n)) => Some(Err(Status::data_loss(format!(
+                    "Subscriber lagged by {} anchors",
+                    n
+                )))),
+            }
+        });
+        Ok(Response::new(Box::pin(stream)))
+    }
+}
+
+/// gRPC server
+pub struct RpcServer {
+    config: RpcConfig,
+    backend: Arc<dyn RpcBackend>,
+    bandwidth: Option<Arc<NetworkStats>>,
+}
+
+impl RpcServer {
+    /// Create a server for `backend`
+    pub fn new(config: RpcConfig, backend: Arc<dyn RpcBackend>) -> Self {
+        Self {
+            config,
+            backend,
+            bandwidth: None,
+        }
+    }
+
+    /// Count client traffic in `bandwidth`
+    pub fn with_network_stats(mut self, bandwidth: Arc<NetworkStats>) -> Self {
+        self.bandwidth = Some(bandwidth);
+        self
+    }
+
+    /// Serve until the process exits
+    pub async fn serve(self) -> Result<(), RpcError> {
+        self.serve_with_shutdown(std::future::pending()).await
+    }
+
+    /// Serve until `signal` resolves
+    pub async fn serve_with_shutdown<F>(self, signal: F) -> Result<(), RpcError>
+    where
+        F: Future<Output = ()>,
+    {
+        let addr: SocketAddr = self
+            .config
+            .listen_addr
+            .parse()
+            .map_err(|e| RpcError::Config(format!("Invalid listen address: {}", e)))?;
+        let interceptor = self.interceptor()?;
+
+        let mut builder = Server::builder().timeout(self.config.request_timeout);
+        if let Some(tls) = self.tls_config()? {
+            builder = builder.tls_config(tls)?;
+        }
+
+        tracing::info!(
+            "gRPC server listening on {} (tls: {}, mtls: {}, jwt: {})",
+            addr,
+            self.config.enable_tls,
+            self.config.tls_client_ca_path.is_some(),
+            self.config.enable_jwt
+        );
+
+        let mut service = RopeRpcService::new(self.backend);
+        if let Some(bandwidth) = self.bandwidth {
+            service = service.with_network_stats(bandwidth);
+        }
+        builder
+            .add_service(RopeRpcServer::with_interceptor(service, interceptor))
+            .serve_with_shutdown(addr, signal)
+            .await?;
+        Ok(())
+    }
+
+    /// Authentication and rate limiting for every call
+    pub fn interceptor(&self) -> Result<AuthInterceptor, RpcError> {
+        let jwt = if self.config.enable_jwt {
+            let secret = self.config.jwt_secret.as_ref().ok_or_else(|| {
+                RpcError::Config("JWT enabled but no jwt_secret configured".to_string())
+            })?;
+            Some(Arc::new(JwtValidator::new(
+                secret.as_bytes(),
+                self.config.jwt_issuer.as_deref(),
+            )))
+        } else {
+            None
+        };
+        let limiter = IdentityRateLimiter::new(self.config.rate_limit, self.config.rate_burst);
+        Ok(AuthInterceptor::new(jwt, Arc::new(limiter)))
+    }
+
+    /// TLS settings; a client CA turns on mutual TLS
+    fn tls_config(&self) -> Result<Option<ServerTlsConfig>, RpcError> {
+        if !self.config.enable_tls {
+            return Ok(None);
+        }
+        let (Some(cert_path), Some(key_path)) =
+            (&self.config.tls_cert_path, &self.config.tls_key_path)
+        else {
+            return Err(RpcError::Config(
+                "TLS enabled but certificate or key path missing".to_string(),
+            ));
+        };
+
+        let identity = tonic::transport::Identity::from_pem(
+            std::fs::read(cert_path)?,
+            std::fs::read(key_path)?,
+        );
+        let mut tls = ServerTlsConfig::new().identity(identity);
+        if let Some(ca_path) = &self.config.tls_client_ca_path {
+            tls = tls.client_ca_root(Certificate::from_pem(std::fs::read(ca_path)?));
+        }
+        Ok(Some(tls))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parking_lot::RwLock;
+    use std::collections::HashMap;
+
+    struct MockBackend {
+        finality: RwLock<HashMap<[u8; 32], Finality>>,
+        anchors: broadcast::Sender<AnchorNotice>,
+    }
+
+    impl MockBackend {
+        fn new() -> Arc<Self> {
+            let (anchors, _) = broadcast::channel(16);
+            Arc::new(Self {
+                finality: RwLock::new(HashMap::new()),
+                anchors,
+            })
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl RpcBackend for MockBackend {
+        async fn submit_string(&self, submission: StringSubmission) -> Result<[u8; 32], RpcError> {
+            let id = *blake3::hash(&submission.content).as_bytes();
+            self.finality.write().insert(id, Finality::Pending);
+            Ok(id)
+        }
+
+        async fn finality(&self, string_id: &[u8; 32]) -> Result<Finality, RpcError> {
+            self.finality
+                .read()
+                .get(string_id)
+                .cloned()
+                .ok_or(RpcError::NotFound)
+        }
+
+        fn anchors(&self) -> broadcast::Receiver<AnchorNotice> {
+            self.anchors.subscribe()
+        }
+    }
+
+    fn with_identity<T>(message: T, identity: Identity) -> Request<T> {
+        let mut request = Request::new(message);
+        request.extensions_mut().insert(identity);
+        request
+    }
+
+    fn writer() -> Identity {
+        Identity::Token {
+            subject: "alice".to_string(),
+            scopes: vec![SCOPE_WRITE.to_string()],
+        }
+    }
+
+    fn submit_request(content: &[u8]) -> proto::SubmitStringRequest {
+        proto::SubmitStringRequest {
+            content: content.to_vec(),
+            creator: vec![1u8; 32],
+            signature: vec![0u8; 64],
+            parents: vec![vec![2u8; 32]],
+        }
+    }
+
+    #[tokio::test]
+    async fn test_submit_and_query_finality() {
+        let backend = MockBackend::new();
+        let service = RopeRpcService::new(backend.clone());
+
+        let response = service
+            .submit_string(with_identity(submit_request(b"hello"), writer()))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(
+            response.string_id,
+            blake3::hash(b"hello").as_bytes().to_vec()
+        );
+
+        let finality = service
+            .get_finality(with_identity(
+                proto::GetFinalityRequest {
+                    string_id: response.string_id,
+                },
+                writer(),
+            ))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(finality.state(), proto::FinalityState::Pending);
+
+        let status = service
+            .get_finality(with_identity(
+                proto::GetFinalityRequest {
+                    string_id: vec![9u8; 32],
+                },
+                writer(),
+            ))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::NotFound);
+    }
+
+    #[tokio::test]
+    async fn test_submit_requires_scope_and_valid_ids() {
+        let service = RopeRpcService::new(MockBackend::new());
+        let reader = Identity::Token {
+            subject: "bob".to_string(),
+            scopes: vec![],
+        };
+
+        let status = service
+            .submit_string(with_identity(submit_request(b"x"), reader))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::PermissionDenied);
+
+        let mut bad = submit_request(b"x");
+        bad.creator = vec![1u8; 31];
+        let status = service
+            .submit_string(with_identity(bad, writer()))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::InvalidArgument);
+
+        let status = service
+            .submit_string(Request::new(submit_request(b"x")))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::Unauthenticated);
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_anchors_filters_rounds() {
+        let backend = MockBackend::new();
+        let service = RopeRpcService::new(backend.clone());
+
+        let mut stream = service
+            .subscribe_anchors(with_identity(
+                proto::SubscribeAnchorsRequest { from_round: 5 },
+                writer(),
+            ))
+            .await
+            .unwrap()
+            .into_inner();
+
+        for round in [4, 5, 6] {
+            backend
+                .anchors
+                .send(AnchorNotice {
+                    round,
+                    anchor_id: [round as u8; 32],
+                    finalized_strings: vec![[1u8; 32]],
+                    timestamp: 0,
+                })
+                .unwrap();
+        }
+
+        let first = stream.next().await.unwrap().unwrap();
+        assert_eq!(first.round, 5);
+        assert_eq!(first.finalized_strings.len(), 1);
+        assert_eq!(stream.next().await.unwrap().unwrap().round, 6);
+    }
+
+    #[test]
+    fn test_server_config_checks() {
+        let config = RpcConfig {
+            enable_jwt: true,
+            jwt_secret: None,
+            ..RpcConfig::default()
+        };
+        let server = RpcServer::new(config, MockBackend::new());
+        assert!(matches!(server.interceptor(), Err(RpcError::Config(_))));
+        assert!(matches!(server.tls_config(), Err(RpcError::Config(_))));
+    }
+}
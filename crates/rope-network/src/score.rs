@@ -0,0 +1,528 @@
+//! # Peer Scoring
+//!
+//! Application-level misbehaviour tracking. Gossipsub already scores
+//! peers on mesh behaviour; [`PeerScore`] covers what only the node can
+//! judge: invalid messages, slow responses, failed chunk transfers and
+//! protocol violations.
+//!
+//! Scores start at zero, fall with each offense, rise slowly with good
+//! behaviour and decay back toward zero, so a peer that stops misbehaving
+//! recovers.
+//!
+//! | Score                  | Standing     | Effect                         |
+//! |------------------------|--------------|--------------------------------|
+//! | above greylist         | `Good`       | normal                         |
+//! | at or below greylist   | `Greylisted` | choked by RDP, ranked last     |
+//! | at or below ban        | `Banned`     | disconnected for `ban_duration`|
+
+use crate::peer::{PeerId, PeerManager};
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Misbehaviour kinds
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Offense {
+    /// Message failed hash or signature verification
+    InvalidMessage,
+    /// Request answered after the slow response threshold
+    SlowResponse,
+    /// Chunk did not match its hash, or the transfer failed
+    FailedChunk,
+    /// Message that breaks the protocol (undecodable, out of order)
+    ProtocolViolation,
+}
+
+/// Peer scoring configuration
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ScoreConfig {
+    /// Penalty per invalid message
+    pub invalid_message_penalty: f64,
+
+    /// Penalty per slow response
+    pub slow_response_penalty: f64,
+
+    /// Penalty per failed chunk transfer
+    pub failed_chunk_penalty: f64,
+
+    /// Penalty per protocol violation
+    pub protocol_violation_penalty: f64,
+
+    /// Reward per useful interaction
+    pub reward: f64,
+
+    /// Upper bound on score
+    pub max_score: f64,
+
+    /// Responses slower than this count as offenses
+    pub slow_response_threshold: Duration,
+
+    /// At or below this score the peer is greylisted
+    pub greylist_threshold: f64,
+
+    /// At or below this score the peer is banned
+    pub ban_threshold: f64,
+
+    /// Ban duration
+    pub ban_duration: Duration,
+
+    /// Time for a score to decay halfway to zero
+    pub decay_half_life: Duration,
+}
+
+impl Default for ScoreConfig {
+    fn default() -> Self {
+        Self {
+            invalid_message_penalty: 10.0,
+            slow_response_penalty: 2.0,
+            failed_chunk_penalty: 5.0,
+            protocol_violation_penalty: 25.0,
+            reward: 0.5,
+            max_score: 50.0,
+            slow_response_threshold: Duration::from_secs(5),
+            greylist_threshold: -25.0,
+            ban_threshold: -50.0,
+            ban_duration: Duration::from_secs(3600),
+            decay_half_life: Duration::from_secs(600),
+        }
+    }
+}
+
+impl ScoreConfig {
+    fn penalty(&self, offense: Offense) -> f64 {
+        match offense {
+            Offense::InvalidMessage => self.invalid_message_penalty,
+            Offense::SlowResponse => self.slow_response_penalty,
+            Offense::FailedChunk => self.failed_chunk_penalty,
+            Offense::ProtocolViolation => self.protocol_violation_penalty,
+        }
+    }
+}
+
+/// Peer standing derived from its score
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PeerStanding {
+    /// Served normally
+    Good,
+    /// Choked and ranked last
+    Greylisted,
+    /// Disconnected until `until` (unix seconds)
+    Banned { until: i64 },
+}
+
+/// Action triggered by an offense
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ScoreAction {
+    /// Standing unchanged
+    None,
+    /// Peer was just greylisted
+    Greylisted,
+    /// Peer was just banned
+    Banned,
+}
+
+/// Offense counters
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OffenseCounts {
+    pub invalid_messages: u64,
+    pub slow_responses: u64,
+    pub failed_chunks: u64,
+    pub protocol_violations: u64,
+}
+
+impl OffenseCounts {
+    fn increment(&mut self, offense: Offense) {
+        match offense {
+            Offense::InvalidMessage => self.invalid_messages += 1,
+            Offense::SlowResponse => self.slow_responses += 1,
+            Offense::FailedChunk => self.failed_chunks += 1,
+            Offense::ProtocolViolation => self.protocol_violations += 1,
+        }
+    }
+}
+
+/// Score of one peer, for the explorer and diagnostics
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PeerScoreSnapshot {
+    /// Peer ID (hex)
+    pub peer: String,
+    pub score: f64,
+    pub standing: PeerStanding,
+    pub offenses: OffenseCounts,
+}
+
+struct PeerRecord {
+    score: f64,
+    updated_at: Instant,
+    banned_until: Option<i64>,
+    offenses: OffenseCounts,
+}
+
+impl PeerRecord {
+    fn new() -> Self {
+        Self {
+            score: 0.0,
+            updated_at: Instant::now(),
+            banned_until: None,
+            offenses: OffenseCounts::default(),
+        }
+    }
+
+    /// Apply decay and expire bans
+    fn refresh(&mut self, config: &ScoreConfig) {
+        let elapsed = self.updated_at.elapsed().as_secs_f64();
+        let half_life = config.decay_half_life.as_secs_f64();
+        if half_life > 0.0 {
+            self.score *= 0.5f64.powf(elapsed / half_life);
+        }
+        self.updated_at = Instant::now();
+
+        if let Some(until) = self.banned_until {
+            if chrono::Utc::now().timestamp() >= until {
+                // Serve the rest of the sentence greylisted
+                self.banned_until = None;
+                self.score = self.score.max(config.greylist_threshold);
+            }
+        }
+    }
+
+    fn standing(&self, config: &ScoreConfig) -> PeerStanding {
+        match self.banned_until {
+            Some(until) => PeerStanding::Banned { until },
+            None if self.score <= config.greylist_threshold => PeerStanding::Greylisted,
+            None => PeerStanding::Good,
+        }
+    }
+}
+
+/// Peer scoring statistics
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct PeerScoreStats {
+    pub tracked_peers: usize,
+    pub greylisted_peers: usize,
+    pub banned_peers: usize,
+    pub total_offenses: u64,
+}
+
+/// Peer score engine
+pub struct PeerScore {
+    config: ScoreConfig,
+    peers: RwLock<HashMap<PeerId, PeerRecord>>,
+    peer_manager: Option<Arc<PeerManager>>,
+}
+
+impl PeerScore {
+    /// Create a score engine
+    pub fn new(config: ScoreConfig) -> Self {
+        Self {
+            config,
+            peers: RwLock::new(HashMap::new()),
+            peer_manager: None,
+        }
+    }
+
+    /// Apply bans to `peers` as well
+    pub fn with_peer_manager(mut self, peers: Arc<PeerManager>) -> Self {
+        self.peer_manager = Some(peers);
+        self
+    }
+
+    /// Configuration
+    pub fn config(&self) -> &ScoreConfig {
+        &self.config
+    }
+
+    /// Record an offense
+    pub fn record(&self, peer: &PeerId, offense: Offense) -> ScoreAction {
+        let (action, score) = {
+            let mut peers = self.peers.write();
+            let record = peers.entry(*peer).or_insert_with(PeerRecord::new);
+            record.refresh(&self.config);
+
+            let before = record.standing(&self.config);
+            record.offenses.increment(offense);
+            record.score -= self.config.penalty(offense);
+
+            if record.banned_until.is_none() && record.score <= self.config.ban_threshold {
+                record.banned_until = Some(
+                    chrono::Utc::now().timestamp() + self.config.ban_duration.as_secs() as i64,
+                );
+            }
+
+            let action = match (before, record.standing(&self.config)) {
+                (PeerStanding::Banned { .. }, _) => ScoreAction::None,
+                (_, PeerStanding::Banned { .. }) => ScoreAction::Banned,
+                (PeerStanding::Good, PeerStanding::Greylisted) => ScoreAction::Greylisted,
+                _ => ScoreAction::None,
+            };
+            (action, record.score)
+        };
+
+        match action {
+            ScoreAction::Banned => {
+                tracing::warn!(
+                    "Banning peer {} (score {:.1}, last offense {:?})",
+                    hex::encode(&peer[..8]),
+                    score,
+                    offense
+                );
+                if let Some(manager) = &self.peer_manager {
+                    manager.ban(
+                        peer,
+                        self.config.ban_duration,
+                        format!("Peer score {:.1}", score),
+                    );
+                }
+            }
+            ScoreAction::Greylisted => {
+                tracing::info!(
+                    "Greylisting peer {} (score {:.1})",
+                    hex::encode(&peer[..8]),
+                    score
+                );
+            }
+            ScoreAction::None => {}
+        }
+        action
+    }
+
+    /// Record how long a peer took to respond
+    pub fn record_response(&self, peer: &PeerId, elapsed: Duration) -> ScoreAction {
+        if elapsed > self.config.slow_response_threshold {
+            self.record(peer, Offense::SlowResponse)
+        } else {
+            self.reward(peer);
+            ScoreAction::None
+        }
+    }
+
+    /// Reward a useful interaction
+    pub fn reward(&self, peer: &PeerId) {
+        let mut peers = self.peers.write();
+        let record = peers.entry(*peer).or_insert_with(PeerRecord::new);
+        record.refresh(&self.config);
+        record.score = (record.score + self.config.reward).min(self.config.max_score);
+    }
+
+    /// Current score (zero for unknown peers)
+    pub fn score(&self, peer: &PeerId) -> f64 {
+        let mut peers = self.peers.write();
+        match peers.get_mut(peer) {
+            Some(record) => {
+                record.refresh(&self.config);
+                record.score
+            }
+            None => 0.0,
+        }
+    }
+
+    /// Current standing
+    pub fn standing(&self, peer: &PeerId) -> PeerStanding {
+        let mut peers = self.peers.write();
+        match peers.get_mut(peer) {
+            Some(record) => {
+                record.refresh(&self.config);
+                record.standing(&self.config)
+            }
+            None => PeerStanding::Good,
+        }
+    }
+
+    /// Whether the peer is greylisted or banned
+    pub fn is_greylisted(&self, peer: &PeerId) -> bool {
+        self.standing(peer) != PeerStanding::Good
+    }
+
+    /// Whether the peer is banned
+    pub fn is_banned(&self, peer: &PeerId) -> bool {
+        matches!(self.standing(peer), PeerStanding::Banned { .. })
+    }
+
+    /// Pick up to `slots` peers to unchoke, best score first
+    ///
+    /// Greylisted and banned peers are never unchoked.
+    pub fn rank_for_unchoke(&self, candidates: &[PeerId], slots: usize) -> Vec<PeerId> {
+        let mut ranked: Vec<(PeerId, f64)> = candidates
+            .iter()
+            .filter(|peer| !self.is_greylisted(peer))
+            .map(|peer| (*peer, self.score(peer)))
+            .collect();
+        ranked.sort_by(|a, b| b.1.total_cmp(&a.1));
+        ranked
+            .into_iter()
+            .take(slots)
+            .map(|(peer, _)| peer)
+            .collect()
+    }
+
+    /// Scores of all tracked peers, lowest first
+    pub fn snapshot(&self) -> Vec<PeerScoreSnapshot> {
+        let mut peers = self.peers.write();
+        let mut snapshot: Vec<PeerScoreSnapshot> = peers
+            .iter_mut()
+            .map(|(peer, record)| {
+                record.refresh(&self.config);
+                PeerScoreSnapshot {
+                    peer: hex::encode(peer),
+                    score: record.score,
+                    standing: record.standing(&self.config),
+                    offenses: record.offenses.clone(),
+                }
+            })
+            .collect();
+        snapshot.sort_by(|a, b| a.score.total_cmp(&b.score));
+        snapshot
+    }
+
+    /// Forget peers whose score has decayed to (near) zero
+    pub fn prune(&self) {
+        let mut peers = self.peers.write();
+        peers.retain(|_, record| {
+            record.refresh(&self.config);
+            record.banned_until.is_some() || record.score.abs() >= 0.01
+        });
+    }
+
+    /// Get statistics
+    pub fn stats(&self) -> PeerScoreStats {
+        let snapshot = self.snapshot();
+        PeerScoreStats {
+            tracked_peers: snapshot.len(),
+            greylisted_peers: snapshot
+                .iter()
+                .filter(|s| s.standing == PeerStanding::Greylisted)
+                .count(),
+            banned_peers: snapshot
+                .iter()
+                .filter(|s| matches!(s.standing, PeerStanding::Banned { .. }))
+                .count(),
+            total_offenses: snapshot
+                .iter()
+                .map(|s| {
+                    s.offenses.invalid_messages
+                        + s.offenses.slow_responses
+                        + s.offenses.failed_chunks
+                        + s.offenses.protocol_violations
+                })
+                .sum(),
+        }
+    }
+}
+
+impl Default for PeerScore {
+    fn default() -> Self {
+        Self::new(ScoreConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::peer::PeerConnection;
+
+    #[test]
+    fn test_offenses_greylist_then_ban() {
+        let scores = PeerScore::default();
+        let peer = [1u8; 32];
+
+        assert_eq!(
+            scores.record(&peer, Offense::InvalidMessage),
+            ScoreAction::None
+        );
+        assert_eq!(
+            scores.record(&peer, Offense::InvalidMessage),
+            ScoreAction::None
+        );
+        assert_eq!(
+            scores.record(&peer, Offense::InvalidMessage),
+            ScoreAction::Greylisted
+        );
+        assert!(scores.is_greylisted(&peer));
+        assert!(!scores.is_banned(&peer));
+
+        assert_eq!(
+            scores.record(&peer, Offense::ProtocolViolation),
+            ScoreAction::Banned
+        );
+        assert!(scores.is_banned(&peer));
+        // Already banned: no repeated action
+        assert_eq!(
+            scores.record(&peer, Offense::FailedChunk),
+            ScoreAction::None
+        );
+
+        let snapshot = scores.snapshot();
+        assert_eq!(snapshot[0].offenses.invalid_messages, 3);
+        assert_eq!(snapshot[0].offenses.protocol_violations, 1);
+        assert_eq!(scores.stats().banned_peers, 1);
+    }
+
+    #[test]
+    fn test_ban_applied_to_peer_manager() {
+        let manager = Arc::new(PeerManager::new([0u8; 32], 10));
+        let peer = [2u8; 32];
+        manager.add_peer(PeerConnection::new(peer, String::new()));
+        manager.connect(&peer).unwrap();
+
+        let scores = PeerScore::default().with_peer_manager(manager.clone());
+        for _ in 0..3 {
+            scores.record(&peer, Offense::ProtocolViolation);
+        }
+
+        assert!(manager.get_peer(&peer).unwrap().is_banned());
+        assert!(manager.connected_peers().is_empty());
+    }
+
+    #[test]
+    fn test_slow_responses_and_rewards() {
+        let scores = PeerScore::default();
+        let peer = [3u8; 32];
+
+        scores.record_response(&peer, Duration::from_millis(100));
+        assert!(scores.score(&peer) > 0.0);
+
+        scores.record_response(&peer, Duration::from_secs(30));
+        assert!(scores.score(&peer) < 0.0);
+        assert_eq!(scores.snapshot()[0].offenses.slow_responses, 1);
+    }
+
+    #[test]
+    fn test_decay() {
+        let scores = PeerScore::new(ScoreConfig {
+            decay_half_life: Duration::from_millis(10),
+            ..ScoreConfig::default()
+        });
+        let peer = [4u8; 32];
+        for _ in 0..3 {
+            scores.record(&peer, Offense::InvalidMessage);
+        }
+        assert!(scores.is_greylisted(&peer));
+
+        std::thread::sleep(Duration::from_millis(200));
+        assert!(!scores.is_greylisted(&peer));
+        scores.prune();
+        assert_eq!(scores.stats().tracked_peers, 0);
+    }
+
+    #[test]
+    fn test_rank_for_unchoke() {
+        let scores = PeerScore::default();
+        let (good, better, bad) = ([5u8; 32], [6u8; 32], [7u8; 32]);
+        scores.reward(&good);
+        scores.reward(&better);
+        scores.reward(&better);
+        for _ in 0..3 {
+            scores.record(&bad, Offense::FailedChunk);
+            scores.record(&bad, Offense::FailedChunk);
+        }
+
+        let unchoked = scores.rank_for_unchoke(&[good, bad, better], 5);
+        assert_eq!(unchoked, vec![better, good]);
+        assert_eq!(
+            scores.rank_for_unchoke(&[good, bad, better], 1),
+            vec![better]
+        );
+    }
+}
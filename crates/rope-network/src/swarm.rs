@@ -45,12 +45,13 @@ use std::time::Duration;
 
 use futures::StreamExt;
 use libp2p::{
+    autonat, dcutr,
     gossipsub::{self, IdentTopic, MessageAuthenticity, ValidationMode},
     identify,
     kad::{self, store::MemoryStore, Mode as KadMode},
-    noise,
+    noise, relay,
     request_response::{self, ProtocolSupport},
-    swarm::{NetworkBehaviour, SwarmEvent},
+    swarm::{behaviour::toggle::Toggle, NetworkBehaviour, SwarmEvent},
     tcp, yamux, Multiaddr, PeerId, StreamProtocol, Swarm, SwarmBuilder,
 };
 use parking_lot::RwLock;
@@ -59,6 +60,12 @@ use thiserror::Error;
 use tokio::sync::{broadcast, mpsc, oneshot};
 use tracing::{debug, error, info, warn};
 
+use super::bandwidth::{peer_key, NetworkStats, TrafficChannel};
+use super::compression::{Batcher, CompressionConfig, CompressionStats};
+use super::nat::{NatConfig, NatStatus, NatTraversal};
+use super::pubsub::{
+    message_id, PubsubConfig, PubsubHandle, PubsubMessage, Topic, TopicValidator, ValidationResult,
+};
 use super::transport::{ConnectionStats, RopeMessage, TransportConfig, TransportError};
 
 // ============================================================================
@@ -80,6 +87,15 @@ pub struct SwarmConfig {
     /// Request-Response configuration
     pub request_response: RequestResponseConfig,
 
+    /// Typed topic validation, scoring and publish queue
+    pub pubsub: PubsubConfig,
+
+    /// AutoNAT, relay and hole punching
+    pub nat: NatConfig,
+
+    /// Wire compression and batching
+    pub compression: CompressionConfig,
+
     /// Node identity seed (32 bytes)
     pub identity_seed: Option<[u8; 32]>,
 }
@@ -91,6 +107,9 @@ impl Default for SwarmConfig {
             gossipsub: GossipSubConfig::default(),
             kademlia: KademliaConfig::default(),
             request_response: RequestResponseConfig::default(),
+            pubsub: PubsubConfig::default(),
+            nat: NatConfig::default(),
+            compression: CompressionConfig::default(),
             identity_seed: None,
         }
     }
@@ -245,6 +264,14 @@ pub enum RopeBehaviourEvent {
     Identify(identify::Event),
     /// Request-Response event
     RequestResponse(request_response::Event<RopeRequest, RopeResponse>),
+    /// AutoNAT event
+    Autonat(autonat::Event),
+    /// Relay client event
+    RelayClient(relay::client::Event),
+    /// Relay server event
+    Relay(relay::Event),
+    /// Hole punching event
+    Dcutr(dcutr::Event),
 }
 
 impl From<gossipsub::Event> for RopeBehaviourEvent {
@@ -271,6 +298,30 @@ impl From<request_response::Event<RopeRequest, RopeResponse>> for RopeBehaviourE
     }
 }
 
+impl From<autonat::Event> for RopeBehaviourEvent {
+    fn from(event: autonat::Event) -> Self {
+        RopeBehaviourEvent::Autonat(event)
+    }
+}
+
+impl From<relay::client::Event> for RopeBehaviourEvent {
+    fn from(event: relay::client::Event) -> Self {
+        RopeBehaviourEvent::RelayClient(event)
+    }
+}
+
+impl From<relay::Event> for RopeBehaviourEvent {
+    fn from(event: relay::Event) -> Self {
+        RopeBehaviourEvent::Relay(event)
+    }
+}
+
+impl From<dcutr::Event> for RopeBehaviourEvent {
+    fn from(event: dcutr::Event) -> Self {
+        RopeBehaviourEvent::Dcutr(event)
+    }
+}
+
 /// Combined network behaviour for Datachain Rope
 #[derive(NetworkBehaviour)]
 #[behaviour(out_event = "RopeBehaviourEvent")]
@@ -286,6 +337,18 @@ pub struct RopeBehaviour {
 
     /// Request-Response for direct messaging
     pub request_response: request_response::cbor::Behaviour<RopeRequest, RopeResponse>,
+
+    /// AutoNAT reachability probes
+    pub autonat: Toggle<autonat::Behaviour>,
+
+    /// Circuit relay v2 client (listen via relays when private)
+    pub relay_client: relay::client::Behaviour,
+
+    /// Circuit relay v2 server (public nodes relaying for others)
+    pub relay: Toggle<relay::Behaviour>,
+
+    /// Direct connection upgrade through relay (hole punching)
+    pub dcutr: Toggle<dcutr::Behaviour>,
 }
 
 /// Request message type
@@ -431,9 +494,16 @@ pub struct SwarmStats {
     pub known_peers: usize,
     pub messages_published: u64,
     pub messages_received: u64,
+    pub messages_rejected: u64,
+    pub messages_ignored: u64,
     pub bytes_sent: u64,
     pub bytes_received: u64,
     pub dht_queries: u64,
+    pub nat_status: NatStatus,
+    pub relay_reservations: usize,
+    pub hole_punch_successes: u64,
+    pub hole_punch_failures: u64,
+    pub compression: CompressionStats,
     pub active_subscriptions: Vec<String>,
     pub uptime_secs: u64,
 }
@@ -468,6 +538,13 @@ pub enum SwarmNetworkEvent {
         source: PeerId,
     },
 
+    /// Validated message on a typed topic
+    TopicMessage {
+        topic: Topic,
+        message: PubsubMessage,
+        source: PeerId,
+    },
+
     /// Request received (note: channel is not Clone, so this variant is not clonable)
     RequestReceived {
         peer_id: PeerId,
@@ -483,6 +560,9 @@ pub enum SwarmNetworkEvent {
         key: Vec<u8>,
         providers: Vec<PeerId>,
     },
+
+    /// AutoNAT reachability changed
+    NatStatusChanged { status: NatStatus },
 }
 
 impl Clone for SwarmNetworkEvent {
@@ -503,6 +583,15 @@ impl Clone for SwarmNetworkEvent {
                 data: data.clone(),
                 source: *source,
             },
+            SwarmNetworkEvent::TopicMessage {
+                topic,
+                message,
+                source,
+            } => SwarmNetworkEvent::TopicMessage {
+                topic: topic.clone(),
+                message: message.clone(),
+                source: *source,
+            },
             SwarmNetworkEvent::RequestReceived { .. } => {
                 // Cannot clone RequestReceived due to oneshot channel
                 // This should never be called in practice as requests use mpsc
@@ -518,6 +607,9 @@ impl Clone for SwarmNetworkEvent {
                     providers: providers.clone(),
                 }
             }
+            SwarmNetworkEvent::NatStatusChanged { status } => SwarmNetworkEvent::NatStatusChanged {
+                status: status.clone(),
+            },
         }
     }
 }
@@ -526,6 +618,22 @@ impl Clone for SwarmNetworkEvent {
 // ROPE SWARM RUNTIME
 // ============================================================================
 
+/// Shared handles moved into the event loop
+struct EventLoopContext {
+    event_tx: broadcast::Sender<SwarmNetworkEvent>,
+    stats: Arc<RwLock<SwarmStats>>,
+    is_running: Arc<RwLock<bool>>,
+    subscriptions: Arc<RwLock<HashSet<String>>>,
+    validator: Arc<TopicValidator>,
+    wire: Wire,
+}
+
+/// Gossip framing and bandwidth accounting
+struct Wire {
+    batcher: Batcher,
+    bandwidth: Arc<NetworkStats>,
+}
+
 /// The main swarm runtime that manages the libp2p swarm
 pub struct RopeSwarmRuntime {
     /// Configuration
@@ -548,12 +656,19 @@ pub struct RopeSwarmRuntime {
 
     /// Subscribed topics
     subscriptions: Arc<RwLock<HashSet<String>>>,
+
+    /// Validator for typed topics
+    validator: Arc<TopicValidator>,
+
+    /// Per-peer and per-channel byte counters
+    bandwidth: Arc<NetworkStats>,
 }
 
 impl RopeSwarmRuntime {
     /// Create a new swarm runtime
     pub fn new(config: SwarmConfig) -> Self {
         let (event_tx, _) = broadcast::channel(1024);
+        let validator = Arc::new(TopicValidator::new(config.pubsub.clone()));
 
         Self {
             config,
@@ -563,6 +678,8 @@ impl RopeSwarmRuntime {
             is_running: Arc::new(RwLock::new(false)),
             local_peer_id: Arc::new(RwLock::new(None)),
             subscriptions: Arc::new(RwLock::new(HashSet::new())),
+            validator,
+            bandwidth: Arc::new(NetworkStats::new()),
         }
     }
 
@@ -590,11 +707,13 @@ impl RopeSwarmRuntime {
 
         info!("Local peer ID: {}", local_peer_id);
 
+        let nat = NatTraversal::new(&self.config.nat).map_err(SwarmError::Config)?;
+
         // Build the swarm
-        let swarm = self.build_swarm(local_keypair).await?;
+        let swarm = self.build_swarm(local_keypair, &nat).await?;
 
         // Create command channel
-        let (command_tx, command_rx) = mpsc::channel(256);
+        let (command_tx, command_rx) = mpsc::channel(self.config.pubsub.publish_queue);
         self.command_tx = Some(command_tx);
 
         // Clone what we need for the event loop
@@ -602,20 +721,23 @@ impl RopeSwarmRuntime {
         let is_running = self.is_running.clone();
         let event_tx = self.event_tx.clone();
         let subscriptions = self.subscriptions.clone();
+        let validator = self.validator.clone();
         let listen_addr = self.config.transport.listen_addr;
+        let context = EventLoopContext {
+            event_tx,
+            stats,
+            is_running,
+            subscriptions,
+            validator,
+            wire: Wire {
+                batcher: Batcher::new(self.config.compression.clone()),
+                bandwidth: self.bandwidth.clone(),
+            },
+        };
 
         // Spawn the event loop
         tokio::spawn(async move {
-            Self::run_event_loop(
-                swarm,
-                command_rx,
-                event_tx,
-                stats,
-                is_running,
-                subscriptions,
-                listen_addr,
-            )
-            .await;
+            Self::run_event_loop(swarm, command_rx, context, nat, listen_addr).await;
         });
 
         *self.is_running.write() = true;
@@ -628,6 +750,7 @@ impl RopeSwarmRuntime {
     async fn build_swarm(
         &self,
         local_keypair: libp2p::identity::Keypair,
+        nat: &NatTraversal,
     ) -> Result<Swarm<RopeBehaviour>, SwarmError> {
         // GossipSub configuration
         let gossipsub_config = gossipsub::ConfigBuilder::default()
@@ -643,15 +766,23 @@ impl RopeSwarmRuntime {
             .duplicate_cache_time(self.config.gossipsub.duplicate_cache_time)
             .flood_publish(self.config.gossipsub.flood_publish)
             .validation_mode(ValidationMode::Strict)
+            // Forward only after the application validates the message
+            .validate_messages()
+            .message_id_fn(|message| gossipsub::MessageId::from(message_id(&message.data).to_vec()))
             .build()
             .map_err(|e| SwarmError::Config(format!("GossipSub config error: {}", e)))?;
 
-        let gossipsub = gossipsub::Behaviour::new(
+        let mut gossipsub = gossipsub::Behaviour::new(
             MessageAuthenticity::Signed(local_keypair.clone()),
             gossipsub_config,
         )
         .map_err(|e| SwarmError::Config(format!("GossipSub init error: {}", e)))?;
 
+        let scoring = &self.config.pubsub.scoring;
+        gossipsub
+            .with_peer_score(scoring.params(), scoring.thresholds())
+            .map_err(|e| SwarmError::Config(format!("GossipSub scoring error: {}", e)))?;
+
         // Kademlia configuration
         let local_peer_id = PeerId::from(local_keypair.public());
         let kad_store = MemoryStore::new(local_peer_id);
@@ -696,15 +827,39 @@ impl RopeSwarmRuntime {
                 .with_request_timeout(self.config.request_response.request_timeout),
         );
 
-        // Create combined behaviour
-        let behaviour = RopeBehaviour {
-            gossipsub,
-            kademlia,
-            identify,
-            request_response,
-        };
+        // NAT traversal: AutoNAT probes, relay server, hole punching
+        let nat_config = &self.config.nat;
+        let autonat = nat_config.enable_autonat.then(|| {
+            let mut autonat = autonat::Behaviour::new(
+                local_peer_id,
+                autonat::Config {
+                    boot_delay: nat_config.autonat_boot_delay,
+                    refresh_interval: nat_config.autonat_refresh_interval,
+                    confidence_max: nat_config.autonat_confidence,
+                    ..Default::default()
+                },
+            );
+            // Relays are public by definition, so they make good probe servers
+            for (peer_id, addr) in nat.relays() {
+                autonat.add_server(*peer_id, Some(addr.clone()));
+            }
+            autonat
+        });
+        let relay_server = nat_config.act_as_relay.then(|| {
+            relay::Behaviour::new(
+                local_peer_id,
+                relay::Config {
+                    max_reservations: nat_config.max_reservations,
+                    max_circuits: nat_config.max_circuits,
+                    ..Default::default()
+                },
+            )
+        });
+        let dcutr = nat_config
+            .enable_hole_punching
+            .then(|| dcutr::Behaviour::new(local_peer_id));
 
-        // Build swarm with QUIC or TCP
+        // Build swarm with QUIC or TCP, plus relayed circuits
         let swarm = SwarmBuilder::with_existing_identity(local_keypair)
             .with_tokio()
             .with_tcp(
@@ -714,7 +869,18 @@ impl RopeSwarmRuntime {
             )
             .map_err(|e| SwarmError::Config(format!("TCP transport error: {}", e)))?
             .with_quic()
-            .with_behaviour(|_| behaviour)
+            .with_relay_client(noise::Config::new, yamux::Config::default)
+            .map_err(|e| SwarmError::Config(format!("Relay transport error: {}", e)))?
+            .with_behaviour(|_, relay_client| RopeBehaviour {
+                gossipsub,
+                kademlia,
+                identify,
+                request_response,
+                autonat: autonat.into(),
+                relay_client,
+                relay: relay_server.into(),
+                dcutr: dcutr.into(),
+            })
             .map_err(|e| SwarmError::Config(format!("Behaviour error: {}", e)))?
             .with_swarm_config(|cfg| {
                 cfg.with_idle_connection_timeout(self.config.transport.idle_timeout)
@@ -728,12 +894,19 @@ impl RopeSwarmRuntime {
     async fn run_event_loop(
         mut swarm: Swarm<RopeBehaviour>,
         mut command_rx: mpsc::Receiver<SwarmCommand>,
-        event_tx: broadcast::Sender<SwarmNetworkEvent>,
-        stats: Arc<RwLock<SwarmStats>>,
-        is_running: Arc<RwLock<bool>>,
-        subscriptions: Arc<RwLock<HashSet<String>>>,
+        context: EventLoopContext,
+        mut nat: NatTraversal,
         listen_addr: SocketAddr,
     ) {
+        let EventLoopContext {
+            event_tx,
+            stats,
+            is_running,
+            subscriptions,
+            validator,
+            mut wire,
+        } = context;
+
         // Start listening
         let multiaddr: Multiaddr = format!("/ip4/{}/tcp/{}", listen_addr.ip(), listen_addr.port())
             .parse()
@@ -760,6 +933,8 @@ impl RopeSwarmRuntime {
         info!("Swarm listening on {} and {:?}", multiaddr, quic_addr);
 
         let start_time = std::time::Instant::now();
+        let mut batch_timer = tokio::time::interval(wire.batcher.codec().config().batch_interval);
+        batch_timer.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
 
         // Main event loop
         loop {
@@ -771,7 +946,9 @@ impl RopeSwarmRuntime {
                         &mut swarm,
                         &event_tx,
                         &stats,
-                        &subscriptions,
+                        &validator,
+                        &wire,
+                        &mut nat,
                     ).await;
                 }
 
@@ -780,6 +957,8 @@ impl RopeSwarmRuntime {
                     match cmd {
                         SwarmCommand::Shutdown => {
                             info!("Swarm shutdown requested");
+                            let frames = wire.batcher.flush_all();
+                            Self::publish_frames(&mut swarm, &stats, &wire, frames);
                             break;
                         }
                         _ => {
@@ -788,12 +967,20 @@ impl RopeSwarmRuntime {
                                 &mut swarm,
                                 &stats,
                                 &subscriptions,
+                                &validator,
+                                &mut wire,
                                 start_time,
                             ).await;
                         }
                     }
                 }
 
+                // Flush partial batches
+                _ = batch_timer.tick(), if wire.batcher.has_pending() => {
+                    let frames = wire.batcher.flush_due();
+                    Self::publish_frames(&mut swarm, &stats, &wire, frames);
+                }
+
                 // Periodic stats update
                 _ = tokio::time::sleep(Duration::from_secs(10)) => {
                     let mut s = stats.write();
@@ -813,13 +1000,15 @@ impl RopeSwarmRuntime {
         swarm: &mut Swarm<RopeBehaviour>,
         event_tx: &broadcast::Sender<SwarmNetworkEvent>,
         stats: &Arc<RwLock<SwarmStats>>,
-        subscriptions: &Arc<RwLock<HashSet<String>>>,
+        validator: &Arc<TopicValidator>,
+        wire: &Wire,
+        nat: &mut NatTraversal,
     ) {
         match event {
             SwarmEvent::Behaviour(RopeBehaviourEvent::Gossipsub(gossipsub::Event::Message {
                 propagation_source,
+                message_id,
                 message,
-                ..
             })) => {
                 let topic = message.topic.to_string();
                 debug!(
@@ -829,12 +1018,59 @@ impl RopeSwarmRuntime {
 
                 stats.write().messages_received += 1;
                 stats.write().bytes_received += message.data.len() as u64;
+                wire.bandwidth.record_received(
+                    &peer_key(&propagation_source.to_bytes()),
+                    TrafficChannel::ValidatorGossip,
+                    message.data.len(),
+                );
 
-                let _ = event_tx.send(SwarmNetworkEvent::GossipMessage {
-                    topic,
-                    data: message.data,
-                    source: propagation_source,
-                });
+                // A frame may carry a batch; it is forwarded only if every
+                // message in it is valid
+                let (result, events) = match wire.batcher.codec().decode(&message.data) {
+                    Ok(payloads) => {
+                        let mut result = ValidationResult::Ignore;
+                        let mut events = Vec::new();
+                        for data in payloads {
+                            let (item, event) =
+                                Self::validate_payload(&topic, data, propagation_source, validator);
+                            result =
+                                match (result, item) {
+                                    (ValidationResult::Reject, _)
+                                    | (_, ValidationResult::Reject) => ValidationResult::Reject,
+                                    (ValidationResult::Accept, _)
+                                    | (_, ValidationResult::Accept) => ValidationResult::Accept,
+                                    _ => ValidationResult::Ignore,
+                                };
+                            events.extend(event);
+                        }
+                        if result == ValidationResult::Reject {
+                            events.clear();
+                        }
+                        (result, events)
+                    }
+                    Err(e) => {
+                        debug!("Undecodable frame from {}: {}", propagation_source, e);
+                        (ValidationResult::Reject, Vec::new())
+                    }
+                };
+
+                match result {
+                    ValidationResult::Accept => {}
+                    ValidationResult::Ignore => stats.write().messages_ignored += 1,
+                    ValidationResult::Reject => stats.write().messages_rejected += 1,
+                }
+                let _ = swarm
+                    .behaviour_mut()
+                    .gossipsub
+                    .report_message_validation_result(
+                        &message_id,
+                        &propagation_source,
+                        result.into(),
+                    );
+
+                for event in events {
+                    let _ = event_tx.send(event);
+                }
             }
 
             SwarmEvent::Behaviour(RopeBehaviourEvent::Gossipsub(
@@ -915,6 +1151,52 @@ impl RopeSwarmRuntime {
                 }
             }
 
+            SwarmEvent::Behaviour(RopeBehaviourEvent::Autonat(autonat::Event::StatusChanged {
+                new,
+                ..
+            })) => {
+                let status = match new {
+                    autonat::NatStatus::Public(addr) => NatStatus::Public {
+                        address: addr.to_string(),
+                    },
+                    autonat::NatStatus::Private => NatStatus::Private,
+                    autonat::NatStatus::Unknown => NatStatus::Unknown,
+                };
+                info!("NAT status: {:?}", status);
+
+                for circuit in nat.on_status_changed(status.clone()) {
+                    match swarm.listen_on(circuit.clone()) {
+                        Ok(_) => info!("Requesting relay reservation on {}", circuit),
+                        Err(e) => warn!("Failed to listen on relay {}: {}", circuit, e),
+                    }
+                }
+                stats.write().nat_status = status.clone();
+                let _ = event_tx.send(SwarmNetworkEvent::NatStatusChanged { status });
+            }
+
+            SwarmEvent::Behaviour(RopeBehaviourEvent::RelayClient(
+                relay::client::Event::ReservationReqAccepted { relay_peer_id, .. },
+            )) => {
+                info!("Relay reservation accepted by {}", relay_peer_id);
+                nat.on_reservation_accepted(relay_peer_id);
+                stats.write().relay_reservations = nat.reservations();
+            }
+
+            SwarmEvent::Behaviour(RopeBehaviourEvent::Dcutr(dcutr::Event {
+                remote_peer_id,
+                result,
+            })) => {
+                match &result {
+                    Ok(_) => info!("Hole punch to {} succeeded", remote_peer_id),
+                    Err(e) => debug!("Hole punch to {} failed: {}", remote_peer_id, e),
+                }
+                nat.on_hole_punch(result.is_ok());
+                let (successes, failures) = nat.hole_punch_stats();
+                let mut s = stats.write();
+                s.hole_punch_successes = successes;
+                s.hole_punch_failures = failures;
+            }
+
             SwarmEvent::ConnectionEstablished {
                 peer_id,
                 endpoint,
@@ -935,6 +1217,16 @@ impl RopeSwarmRuntime {
             } => {
                 if num_established == 0 {
                     info!("Disconnected from peer {}", peer_id);
+                    if nat.relays().iter().any(|(relay, _)| *relay == peer_id) {
+                        nat.on_relay_disconnected(&peer_id);
+                        stats.write().relay_reservations = nat.reservations();
+                        // Re-reserve while still private
+                        if *nat.status() == NatStatus::Private {
+                            for circuit in nat.pending_circuits() {
+                                let _ = swarm.listen_on(circuit);
+                            }
+                        }
+                    }
                     let _ = event_tx.send(SwarmNetworkEvent::PeerDisconnected { peer_id });
                 }
             }
@@ -947,12 +1239,79 @@ impl RopeSwarmRuntime {
         }
     }
 
+    /// Validate one decoded payload
+    fn validate_payload(
+        topic: &str,
+        data: Vec<u8>,
+        source: PeerId,
+        validator: &TopicValidator,
+    ) -> (ValidationResult, Option<SwarmNetworkEvent>) {
+        // Typed topics carry signed messages; other topics only get the
+        // size check
+        match topic.parse::<Topic>() {
+            Ok(typed) => match validator.validate(&typed, &data) {
+                (ValidationResult::Accept, Some(message)) => (
+                    ValidationResult::Accept,
+                    Some(SwarmNetworkEvent::TopicMessage {
+                        topic: typed,
+                        message,
+                        source,
+                    }),
+                ),
+                (result, _) => (result, None),
+            },
+            Err(_) if data.len() > validator.config().max_message_size => {
+                (ValidationResult::Reject, None)
+            }
+            Err(_) => (
+                ValidationResult::Accept,
+                Some(SwarmNetworkEvent::GossipMessage {
+                    topic: topic.to_string(),
+                    data,
+                    source,
+                }),
+            ),
+        }
+    }
+
+    /// Publish encoded frames
+    fn publish_frames(
+        swarm: &mut Swarm<RopeBehaviour>,
+        stats: &Arc<RwLock<SwarmStats>>,
+        wire: &Wire,
+        frames: Vec<(String, Vec<u8>)>,
+    ) {
+        for (topic, frame) in frames {
+            let len = frame.len();
+            match swarm
+                .behaviour_mut()
+                .gossipsub
+                .publish(IdentTopic::new(&topic), frame)
+            {
+                Ok(_) => {
+                    let mut s = stats.write();
+                    s.messages_published += 1;
+                    s.bytes_sent += len as u64;
+                    wire.bandwidth
+                        .record_broadcast(TrafficChannel::ValidatorGossip, len);
+                    debug!("Published {} bytes to {}", len, topic);
+                }
+                Err(e) => {
+                    warn!("Failed to publish to {}: {:?}", topic, e);
+                }
+            }
+        }
+        stats.write().compression = wire.batcher.stats().clone();
+    }
+
     /// Handle commands
     async fn handle_command(
         cmd: SwarmCommand,
         swarm: &mut Swarm<RopeBehaviour>,
         stats: &Arc<RwLock<SwarmStats>>,
         subscriptions: &Arc<RwLock<HashSet<String>>>,
+        validator: &Arc<TopicValidator>,
+        wire: &mut Wire,
         start_time: std::time::Instant,
     ) {
         match cmd {
@@ -960,6 +1319,16 @@ impl RopeSwarmRuntime {
                 let ident_topic = IdentTopic::new(&topic);
                 match swarm.behaviour_mut().gossipsub.subscribe(&ident_topic) {
                     Ok(_) => {
+                        if topic.parse::<Topic>().is_ok() {
+                            let params = validator.config().scoring.topic_params();
+                            if let Err(e) = swarm
+                                .behaviour_mut()
+                                .gossipsub
+                                .set_topic_params(ident_topic, params)
+                            {
+                                warn!("Failed to set score params for {}: {}", topic, e);
+                            }
+                        }
                         subscriptions.write().insert(topic.clone());
                         stats.write().active_subscriptions =
                             subscriptions.read().iter().cloned().collect();
@@ -986,23 +1355,10 @@ impl RopeSwarmRuntime {
                 }
             }
 
-            SwarmCommand::Publish { topic, data } => {
-                let ident_topic = IdentTopic::new(&topic);
-                match swarm
-                    .behaviour_mut()
-                    .gossipsub
-                    .publish(ident_topic, data.clone())
-                {
-                    Ok(_) => {
-                        stats.write().messages_published += 1;
-                        stats.write().bytes_sent += data.len() as u64;
-                        debug!("Published {} bytes to {}", data.len(), topic);
-                    }
-                    Err(e) => {
-                        warn!("Failed to publish to {}: {:?}", topic, e);
-                    }
-                }
-            }
+            SwarmCommand::Publish { topic, data } => match wire.batcher.push(&topic, data) {
+                Ok(frames) => Self::publish_frames(swarm, stats, wire, frames),
+                Err(e) => warn!("Failed to encode message for {}: {}", topic, e),
+            },
 
             SwarmCommand::Dial { addr } => match swarm.dial(addr.clone()) {
                 Ok(_) => {
@@ -1134,6 +1490,31 @@ impl RopeSwarmRuntime {
         self.stats.read().clone()
     }
 
+    /// Bandwidth counters (shared with RDP and RPC)
+    pub fn network_stats(&self) -> Arc<NetworkStats> {
+        self.bandwidth.clone()
+    }
+
+    /// Validator for typed topics (register publishers and hooks here)
+    pub fn validator(&self) -> Arc<TopicValidator> {
+        self.validator.clone()
+    }
+
+    /// Publisher for typed topics, signing with `signer`
+    pub fn pubsub_handle(
+        &self,
+        node_id: [u8; 32],
+        signer: Arc<rope_crypto::hybrid::HybridSigner>,
+    ) -> Result<PubsubHandle, SwarmError> {
+        let commands = self.command_tx.clone().ok_or(SwarmError::NotInitialized)?;
+        Ok(PubsubHandle::new(
+            node_id,
+            signer,
+            commands,
+            self.config.pubsub.max_message_size,
+        ))
+    }
+
     // ========================================================================
     // CONVENIENCE METHODS
     // ========================================================================
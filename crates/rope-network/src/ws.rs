@@ -0,0 +1,581 @@
+//! # WebSocket Event Bridge
+//!
+//! Streams lattice events to WebSocket clients as JSON text frames. This
+//! is the server behind `wss://ws.datachain.network` (TLS is terminated
+//! by the fronting proxy) and speaks the protocol of
+//! `rope_agent_runtime::LatticeWebSocketClient`:
+//!
+//! - Server frames are [`WsEvent`]s tagged by `type`, e.g.
+//!   `{"type":"StringCreated","id":"…","creator":"…",…}`
+//! - Client frames are [`WsCommand`]s: `Subscribe`/`Unsubscribe` with
+//!   `event_types`, `Ping` and `Authenticate { token }`
+//!
+//! A connection with no subscriptions receives every event; `"*"` does
+//! the same explicitly. When a JWT secret is configured, nothing is
+//! streamed until the client authenticates.
+
+use crate::rpc::auth::JwtValidator;
+use futures::{SinkExt, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::future::Future;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use thiserror::Error;
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::broadcast;
+use tokio_tungstenite::tungstenite::Message;
+
+/// Subscription wildcard
+pub const ALL_EVENTS: &str = "*";
+
+/// WebSocket bridge configuration
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct WsConfig {
+    /// Listen address
+    pub listen_addr: String,
+
+    /// Maximum concurrent connections
+    pub max_connections: usize,
+
+    /// Events buffered per connection before it is considered lagging
+    pub event_buffer: usize,
+
+    /// Server ping interval
+    pub ping_interval: Duration,
+
+    /// Maximum event types per connection
+    pub max_subscriptions: usize,
+
+    /// JWT secret (HS256); `None` leaves the feed public
+    pub jwt_secret: Option<String>,
+
+    /// Required JWT issuer
+    pub jwt_issuer: Option<String>,
+}
+
+impl Default for WsConfig {
+    fn default() -> Self {
+        Self {
+            listen_addr: "0.0.0.0:8546".to_string(),
+            max_connections: 1024,
+            event_buffer: 1024,
+            ping_interval: Duration::from_secs(30),
+            max_subscriptions: 32,
+            jwt_secret: None,
+            jwt_issuer: None,
+        }
+    }
+}
+
+/// Event pushed to clients
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum WsEvent {
+    /// New string added to the lattice
+    StringCreated {
+        id: String,
+        creator: String,
+        string_type: String,
+        timestamp: i64,
+    },
+
+    /// Testimony received
+    TestimonyReceived {
+        action_id: String,
+        agent_id: String,
+        verdict: String,
+        confidence: f64,
+    },
+
+    /// Consensus reached on an action
+    ConsensusReached {
+        action_id: String,
+        approved: bool,
+        testimonies: u32,
+    },
+
+    /// Anchor finalized
+    AnchorFinalized {
+        id: String,
+        round: u64,
+        strings_finalized: u32,
+        timestamp: i64,
+    },
+
+    /// Round closed by a view change because its producer sent no anchor
+    RoundMissed {
+        round: u64,
+        view: u64,
+        producer: String,
+        timestamp: i64,
+    },
+
+    /// Validator seen alive on the gossip layer
+    ValidatorHeartbeat {
+        validator: String,
+        timestamp: i64,
+    },
+
+    /// Epoch reward paid to a validator (amounts in base units, decimal)
+    RewardDistributed {
+        validator: String,
+        epoch: u64,
+        proposer_rewards: String,
+        testimony_rewards: String,
+        performance_multiplier: f64,
+        final_reward: String,
+        timestamp: i64,
+    },
+
+    /// Bridge lock, release or relay event
+    BridgeEvent {
+        chain: String,
+        event_type: String,
+        tx_hash: String,
+        details: String,
+    },
+
+    /// Error reported to this client only
+    Error {
+        message: String,
+    },
+
+    /// Heartbeat
+    Ping,
+    Pong,
+}
+
+impl WsEvent {
+    /// Name used in subscription filters
+    pub fn event_type(&self) -> &'static str {
+        match self {
+            WsEvent::StringCreated { .. } => "StringCreated",
+            WsEvent::TestimonyReceived { .. } => "TestimonyReceived",
+            WsEvent::ConsensusReached { .. } => "ConsensusReached",
+            WsEvent::AnchorFinalized { .. } => "AnchorFinalized",
+            WsEvent::RoundMissed { .. } => "RoundMissed",
+            WsEvent::ValidatorHeartbeat { .. } => "ValidatorHeartbeat",
+            WsEvent::RewardDistributed { .. } => "RewardDistributed",
+            WsEvent::BridgeEvent { .. } => "BridgeEvent",
+            WsEvent::Error { .. } => "Error",
+            WsEvent::Ping | WsEvent::Pong => "Heartbeat",
+        }
+    }
+}
+
+impl From<&crate::rpc::AnchorNotice> for WsEvent {
+    fn from(notice: &crate::rpc::AnchorNotice) -> Self {
+        WsEvent::AnchorFinalized {
+            id: hex::encode(notice.anchor_id),
+            round: notice.round,
+            strings_finalized: notice.finalized_strings.len() as u32,
+            timestamp: notice.timestamp,
+        }
+    }
+}
+
+/// Command sent by clients
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum WsCommand {
+    /// Subscribe to event types
+    Subscribe { event_types: Vec<String> },
+
+    /// Unsubscribe from event types
+    Unsubscribe { event_types: Vec<String> },
+
+    /// Keepalive
+    Ping,
+
+    /// Authenticate with a JWT
+    Authenticate { token: String },
+}
+
+/// WebSocket bridge errors
+#[derive(Error, Debug)]
+pub enum WsError {
+    #[error("Configuration error: {0}")]
+    Config(String),
+
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("WebSocket error: {0}")]
+    WebSocket(#[from] tokio_tungstenite::tungstenite::Error),
+}
+
+/// Per-connection session state
+pub struct WsSession {
+    subscriptions: HashSet<String>,
+    authenticated: bool,
+    max_subscriptions: usize,
+}
+
+impl WsSession {
+    /// New session; `authenticated` is false when a token is required
+    pub fn new(authenticated: bool, max_subscriptions: usize) -> Self {
+        Self {
+            subscriptions: HashSet::new(),
+            authenticated,
+            max_subscriptions,
+        }
+    }
+
+    /// Whether `event` should be sent on this connection
+    pub fn should_emit(&self, event: &WsEvent) -> bool {
+        self.authenticated
+            && (self.subscriptions.is_empty()
+                || self.subscriptions.contains(ALL_EVENTS)
+                || self.subscriptions.contains(event.event_type()))
+    }
+
+    /// Apply a client command, returning the reply (if any)
+    pub fn handle_command(
+        &mut self,
+        command: WsCommand,
+        jwt: Option<&JwtValidator>,
+    ) -> Option<WsEvent> {
+        match command {
+            WsCommand::Ping => Some(WsEvent::Pong),
+            WsCommand::Authenticate { token } => {
+                let jwt = jwt?;
+                match jwt.validate(&token) {
+                    Ok(_) => {
+                        self.authenticated = true;
+                        None
+                    }
+                    Err(e) => Some(WsEvent::Error {
+                        message: format!("Authentication failed: {}", e),
+                    }),
+                }
+            }
+            WsCommand::Subscribe { event_types } => {
+                for event_type in event_types {
+                    if self.subscriptions.len() >= self.max_subscriptions {
+                        return Some(WsEvent::Error {
+                            message: format!(
+                                "At most {} subscriptions allowed",
+                                self.max_subscriptions
+                            ),
+                        });
+                    }
+                    self.subscriptions.insert(event_type);
+                }
+                None
+            }
+            WsCommand::Unsubscribe { event_types } => {
+                for event_type in &event_types {
+                    self.subscriptions.remove(event_type);
+                }
+                None
+            }
+        }
+    }
+}
+
+/// WebSocket event bridge
+pub struct EventBridge {
+    config: WsConfig,
+    events: broadcast::Sender<WsEvent>,
+    jwt: Option<Arc<JwtValidator>>,
+    connections: Arc<AtomicUsize>,
+}
+
+impl EventBridge {
+    /// Create a bridge
+    pub fn new(config: WsConfig) -> Self {
+        let (events, _) = broadcast::channel(config.event_buffer.max(1));
+        let jwt = config.jwt_secret.as_ref().map(|secret| {
+            Arc::new(JwtValidator::new(
+                secret.as_bytes(),
+                config.jwt_issuer.as_deref(),
+            ))
+        });
+        Self {
+            config,
+            events,
+            jwt,
+            connections: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    /// Sender for node components to publish events
+    pub fn publisher(&self) -> broadcast::Sender<WsEvent> {
+        self.events.clone()
+    }
+
+    /// Publish an event to every matching connection
+    pub fn publish(&self, event: WsEvent) {
+        // No receivers simply means nobody is connected
+        let _ = self.events.send(event);
+    }
+
+    /// Open connections
+    pub fn connections(&self) -> usize {
+        self.connections.load(Ordering::SeqCst)
+    }
+
+    /// Bind the configured address and serve until `signal` resolves
+    pub async fn serve_with_shutdown<F>(&self, signal: F) -> Result<(), WsError>
+    where
+        F: Future<Output = ()>,
+    {
+        let listener = TcpListener::bind(&self.config.listen_addr).await?;
+        self.serve_listener(listener, signal).await
+    }
+
+    /// Serve on an already bound listener until `signal` resolves
+    pub async fn serve_listener<F>(&self, listener: TcpListener, signal: F) -> Result<(), WsError>
+    where
+        F: Future<Output = ()>,
+    {
+        tracing::info!(
+            "WebSocket bridge listening on {} (auth: {})",
+            listener.local_addr()?,
+            self.jwt.is_some()
+        );
+        tokio::pin!(signal);
+
+        loop {
+            tokio::select! {
+                _ = &mut signal => break,
+                accepted = listener.accept() => {
+                    let (stream, addr) = accepted?;
+                    if self.connections() >= self.config.max_connections {
+                        tracing::warn!("Rejecting WebSocket client {}: connection limit", addr);
+                        continue;
+                    }
+
+                    let connections = self.connections.clone();
+                    connections.fetch_add(1, Ordering::SeqCst);
+                    let events = self.events.subscribe();
+                    let jwt = self.jwt.clone();
+                    let config = self.config.clone();
+                    tokio::spawn(async move {
+                        if let Err(e) = serve_connection(stream, events, jwt, &config).await {
+                            tracing::debug!("WebSocket client {} closed: {}", addr, e);
+                        }
+                        connections.fetch_sub(1, Ordering::SeqCst);
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+async fn serve_connection(
+    stream: TcpStream,
+    mut events: broadcast::Receiver<WsEvent>,
+    jwt: Option<Arc<JwtValidator>>,
+    config: &WsConfig,
+) -> Result<(), WsError> {
+    let ws = tokio_tungstenite::accept_async(stream).await?;
+    let (mut write, mut read) = ws.split();
+    let mut session = WsSession::new(jwt.is_none(), config.max_subscriptions);
+    let mut ping = tokio::time::interval(config.ping_interval);
+    ping.tick().await;
+
+    loop {
+        let reply = tokio::select! {
+            incoming = read.next() => match incoming {
+                Some(Ok(Message::Text(text))) => match serde_json::from_str::<WsCommand>(&text) {
+                    Ok(command) => session.handle_command(command, jwt.as_deref()),
+                    Err(e) => Some(WsEvent::Error {
+                        message: format!("Invalid command: {}", e),
+                    }),
+                },
+                Some(Ok(Message::Close(_))) | None => break,
+                Some(Ok(_)) => None,
+                Some(Err(e)) => return Err(e.into()),
+            },
+            event = events.recv() => match event {
+                Ok(event) if session.should_emit(&event) => Some(event),
+                Ok(_) => None,
+                Err(broadcast::error::RecvError::Lagged(n)) => Some(WsEvent::Error {
+                    message: format!("Lagged by {} events", n),
+                }),
+                Err(broadcast::error::RecvError::Closed) => break,
+            },
+            _ = ping.tick() => {
+                write.send(Message::Ping(Vec::new())).await?;
+                None
+            }
+        };
+
+        if let Some(reply) = reply {
+            let json = serde_json::to_string(&reply)
+                .map_err(|e| WsError::Config(format!("Unserializable event: {}", e)))?;
+            write.send(Message::Text(json)).await?;
+        }
+    }
+
+    let _ = write.send(Message::Close(None)).await;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn string_created() -> WsEvent {
+        WsEvent::StringCreated {
+            id: "ab".repeat(32),
+            creator: "cd".repeat(32),
+            string_type: "data".to_string(),
+            timestamp: 1_700_000_000,
+        }
+    }
+
+    #[test]
+    fn test_wire_format_matches_client() {
+        let json = serde_json::to_value(string_created()).unwrap();
+        assert_eq!(json["type"], "StringCreated");
+        assert_eq!(json["string_type"], "data");
+
+        let command: WsCommand =
+            serde_json::from_str(r#"{"type":"Subscribe","event_types":["AnchorFinalized"]}"#)
+                .unwrap();
+        assert_eq!(
+            command,
+            WsCommand::Subscribe {
+                event_types: vec!["AnchorFinalized".to_string()]
+            }
+        );
+        assert_eq!(
+            serde_json::from_str::<WsCommand>(r#"{"type":"Ping"}"#).unwrap(),
+            WsCommand::Ping
+        );
+        assert_eq!(
+            serde_json::to_string(&WsEvent::Pong).unwrap(),
+            r#"{"type":"Pong"}"#
+        );
+    }
+
+    #[test]
+    fn test_subscription_filters() {
+        let mut session = WsSession::new(true, 4);
+        assert!(session.should_emit(&string_created()));
+
+        session.handle_command(
+            WsCommand::Subscribe {
+                event_types: vec!["BridgeEvent".to_string()],
+            },
+            None,
+        );
+        assert!(!session.should_emit(&string_created()));
+        assert!(session.should_emit(&WsEvent::BridgeEvent {
+            chain: "ethereum".to_string(),
+            event_type: "Locked".to_string(),
+            tx_hash: "0x01".to_string(),
+            details: String::new(),
+        }));
+
+        session.handle_command(
+            WsCommand::Subscribe {
+                event_types: vec![ALL_EVENTS.to_string()],
+            },
+            None,
+        );
+        assert!(session.should_emit(&string_created()));
+
+        let reply = session.handle_command(
+            WsCommand::Subscribe {
+                event_types: (0..10).map(|i| format!("T{}", i)).collect(),
+            },
+            None,
+        );
+        assert!(matches!(reply, Some(WsEvent::Error { .. })));
+        assert_eq!(
+            session.handle_command(WsCommand::Ping, None),
+            Some(WsEvent::Pong)
+        );
+    }
+
+    #[test]
+    fn test_authentication_gates_events() {
+        let secret = b"ws-secret";
+        let jwt = JwtValidator::new(secret, None);
+        let mut session = WsSession::new(false, 4);
+        assert!(!session.should_emit(&string_created()));
+
+        let reply = session.handle_command(
+            WsCommand::Authenticate {
+                token: "garbage".to_string(),
+            },
+            Some(&jwt),
+        );
+        assert!(matches!(reply, Some(WsEvent::Error { .. })));
+
+        let claims = crate::rpc::Claims {
+            sub: "agent-1".to_string(),
+            exp: (chrono::Utc::now().timestamp() + 60) as u64,
+            iss: None,
+            scope: String::new(),
+        };
+        let token = jsonwebtoken::encode(
+            &jsonwebtoken::Header::default(),
+            &claims,
+            &jsonwebtoken::EncodingKey::from_secret(secret),
+        )
+        .unwrap();
+        assert_eq!(
+            session.handle_command(WsCommand::Authenticate { token }, Some(&jwt)),
+            None
+        );
+        assert!(session.should_emit(&string_created()));
+    }
+
+    #[tokio::test]
+    async fn test_bridge_streams_filtered_events() {
+        use tokio_tungstenite::connect_async;
+
+        let bridge = Arc::new(EventBridge::new(WsConfig::default()));
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let (stop_tx, stop_rx) = tokio::sync::oneshot::channel::<()>();
+
+        let server = bridge.clone();
+        tokio::spawn(async move {
+            server
+                .serve_listener(listener, async {
+                    let _ = stop_rx.await;
+                })
+                .await
+        });
+
+        let (mut client, _) = connect_async(format!("ws://{}", addr)).await.unwrap();
+        let subscribe = r#"{"type":"Subscribe","event_types":["AnchorFinalized"]}"#;
+        client
+            .send(Message::Text(subscribe.to_string()))
+            .await
+            .unwrap();
+        client
+            .send(Message::Text(r#"{"type":"Ping"}"#.to_string()))
+            .await
+            .unwrap();
+
+        // The Pong proves the subscription was applied before publishing
+        let pong = client.next().await.unwrap().unwrap();
+        assert_eq!(pong, Message::Text(r#"{"type":"Pong"}"#.to_string()));
+
+        bridge.publish(string_created());
+        bridge.publish(WsEvent::AnchorFinalized {
+            id: "ef".repeat(32),
+            round: 7,
+            strings_finalized: 3,
+            timestamp: 1_700_000_000,
+        });
+
+        let Message::Text(text) = client.next().await.unwrap().unwrap() else {
+            panic!("expected text frame");
+        };
+        let event: WsEvent = serde_json::from_str(&text).unwrap();
+        assert_eq!(event.event_type(), "AnchorFinalized");
+        assert_eq!(bridge.connections(), 1);
+
+        let _ = stop_tx.send(());
+    }
+}
@@ -18,6 +18,9 @@ pub struct NodeConfig {
     pub rpc: RpcSettings,
     /// Metrics settings
     pub metrics: MetricsSettings,
+    /// Readiness thresholds
+    #[serde(default)]
+    pub health: HealthSettings,
 }
 
 /// Node settings
@@ -31,6 +34,33 @@ pub struct NodeSettings {
     pub chain_id: u64,
     /// External IP (for discovery)
     pub external_ip: Option<String>,
+    /// Encrypted keystore for the node identity key
+    #[serde(default)]
+    pub keystore: KeystoreSettings,
+}
+
+/// Keystore settings
+///
+/// The node key is only ever written encrypted. The passphrase is read
+/// from an environment variable so it never appears in config files.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct KeystoreSettings {
+    /// Keystore file, relative to the data directory's `keys/` folder
+    pub path: String,
+    /// Environment variable holding the passphrase
+    pub passphrase_env: String,
+    /// Optional keyfile used as a second factor
+    pub keyfile: Option<String>,
+}
+
+impl Default for KeystoreSettings {
+    fn default() -> Self {
+        Self {
+            path: "node.keystore.json".to_string(),
+            passphrase_env: "ROPE_KEYSTORE_PASSPHRASE".to_string(),
+            keyfile: None,
+        }
+    }
 }
 
 /// Node operation mode
@@ -60,6 +90,9 @@ pub struct NetworkSettings {
     pub enable_quic: bool,
     /// Enable NAT traversal
     pub enable_nat: bool,
+    /// Circuit relays to reserve on when behind NAT (`/p2p/<id>` multiaddrs)
+    #[serde(default)]
+    pub relays: Vec<String>,
 }
 
 /// Consensus settings
@@ -136,14 +169,162 @@ pub struct MetricsSettings {
     pub prometheus_addr: String,
 }
 
+/// Readiness thresholds for `/readyz`
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct HealthSettings {
+    /// Peers required before the node reports ready
+    pub min_peers: usize,
+    /// Rounds a consensus node may go without producing a string
+    pub max_rounds_behind: u64,
+}
+
+impl Default for HealthSettings {
+    fn default() -> Self {
+        Self {
+            min_peers: 1,
+            max_rounds_behind: 10,
+        }
+    }
+}
+
+/// Named network a node can join
+///
+/// Ties together everything that must agree across the nodes of one
+/// network: chain ID, bootstrap peers, genesis and its economics.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NetworkPreset {
+    /// Network name, as accepted by `--network`
+    pub name: &'static str,
+    /// Chain ID
+    pub chain_id: u64,
+    /// Bootstrap nodes
+    pub bootstrap_nodes: Vec<String>,
+    /// Genesis timestamp shared by every node, pinning the genesis hash;
+    /// `None` lets each data directory create its own genesis
+    pub genesis_timestamp: Option<i64>,
+    /// Whether nodes take part in consensus by default
+    pub consensus_enabled: bool,
+    /// Block time target (ms)
+    pub block_time_ms: u64,
+    /// Minimum testimonies for finality
+    pub min_testimonies: u32,
+    /// AI testimony agents required per string
+    pub ai_agents_required: u32,
+    /// Rounds per epoch
+    pub epoch_length: u64,
+}
+
+impl NetworkPreset {
+    /// Names of all presets
+    pub const NAMES: [&'static str; 3] = ["mainnet", "testnet", "devnet"];
+
+    /// Preset called `name`
+    pub fn named(name: &str) -> Option<Self> {
+        match name {
+            "mainnet" => Some(Self::mainnet()),
+            "testnet" => Some(Self::testnet()),
+            "devnet" => Some(Self::devnet()),
+            _ => None,
+        }
+    }
+
+    /// Preset using `chain_id`
+    pub fn for_chain_id(chain_id: u64) -> Option<Self> {
+        Self::NAMES
+            .iter()
+            .filter_map(|name| Self::named(name))
+            .find(|preset| preset.chain_id == chain_id)
+    }
+
+    /// Genesis hash every node of this network must share, if pinned
+    pub fn genesis_hash(&self) -> Option<[u8; 32]> {
+        self.genesis_timestamp
+            .map(|timestamp| crate::genesis::genesis_hash(self.chain_id, timestamp))
+    }
+
+    /// Mainnet (Chain ID 271828)
+    pub fn mainnet() -> Self {
+        Self {
+            name: "mainnet",
+            chain_id: rope_smartchain::CHAIN_ID_MAINNET,
+            bootstrap_nodes: vec![
+                // Primary bootstrap node on VPS
+                "/ip4/92.243.26.189/tcp/9000/p2p/12D3KooWBXNzc2E4Z9CLypkRXro5iSdbM5oTnTkmf8ncZAqjhAfM".to_string(),
+            ],
+            genesis_timestamp: Some(rope_smartchain::NetworkConfig::mainnet().genesis_timestamp),
+            consensus_enabled: false,
+            block_time_ms: 3000,
+            min_testimonies: 5,
+            ai_agents_required: 3,
+            epoch_length: 21600,
+        }
+    }
+
+    /// Public testnet (Chain ID 271829)
+    pub fn testnet() -> Self {
+        Self {
+            name: "testnet",
+            chain_id: rope_smartchain::CHAIN_ID_TESTNET,
+            bootstrap_nodes: vec![
+                // Primary testnet bootstrap node on VPS
+                "/ip4/92.243.26.189/tcp/9000/p2p/12D3KooWBXNzc2E4Z9CLypkRXro5iSdbM5oTnTkmf8ncZAqjhAfM".to_string(),
+            ],
+            genesis_timestamp: None,
+            consensus_enabled: true,
+            block_time_ms: 4200, // ~4.2 seconds per anchor
+            min_testimonies: 1,
+            ai_agents_required: 1,
+            epoch_length: 21600,
+        }
+    }
+
+    /// Local development network (Chain ID 271830): no bootstrap peers,
+    /// fast rounds and short epochs
+    pub fn devnet() -> Self {
+        Self {
+            name: "devnet",
+            chain_id: rope_smartchain::CHAIN_ID_DEVNET,
+            bootstrap_nodes: Vec::new(),
+            genesis_timestamp: None,
+            consensus_enabled: true,
+            block_time_ms: 1000,
+            min_testimonies: 1,
+            ai_agents_required: 1,
+            epoch_length: 100,
+        }
+    }
+}
+
 impl NodeConfig {
     /// Create config for a specific network
     pub fn for_network(network: &str) -> anyhow::Result<Self> {
-        match network {
-            "mainnet" => Ok(Self::mainnet()),
-            "testnet" => Ok(Self::testnet()),
-            _ => anyhow::bail!("Unknown network: {}", network),
-        }
+        NetworkPreset::named(network)
+            .map(|preset| Self::from_preset(&preset))
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Unknown network: {} (expected one of {})",
+                    network,
+                    NetworkPreset::NAMES.join(", ")
+                )
+            })
+    }
+
+    /// Defaults for the network described by `preset`
+    pub fn from_preset(preset: &NetworkPreset) -> Self {
+        let mut config = Self::mainnet();
+        config.node.name = format!("rope-{}-node", preset.name);
+        config.node.chain_id = preset.chain_id;
+        config.network.bootstrap_nodes = preset.bootstrap_nodes.clone();
+        config.storage.db_path = format!("~/.rope/{}/db", preset.name);
+        config.consensus.enabled = preset.consensus_enabled;
+        config.consensus.block_time_ms = preset.block_time_ms;
+        config.consensus.min_testimonies = preset.min_testimonies;
+        config
+    }
+
+    /// Preset of the network this config's chain ID belongs to
+    pub fn preset(&self) -> Option<NetworkPreset> {
+        NetworkPreset::for_chain_id(self.node.chain_id)
     }
 
     /// Mainnet configuration
@@ -154,6 +335,7 @@ impl NodeConfig {
                 mode: NodeMode::Relay,
                 chain_id: 271828,
                 external_ip: None,
+                keystore: KeystoreSettings::default(),
             },
             network: NetworkSettings {
                 listen_addr: "0.0.0.0:9000".to_string(),
@@ -164,6 +346,7 @@ impl NodeConfig {
                 max_peers: 50,
                 enable_quic: true,
                 enable_nat: true,
+                relays: Vec::new(),
             },
             consensus: ConsensusSettings {
                 enabled: false,
@@ -194,25 +377,18 @@ impl NodeConfig {
                 enabled: true,
                 prometheus_addr: "127.0.0.1:9090".to_string(),
             },
+            health: HealthSettings::default(),
         }
     }
 
     /// Testnet configuration
     pub fn testnet() -> Self {
-        let mut config = Self::mainnet();
-        config.node.name = "rope-testnet-node".to_string();
-        config.node.chain_id = 271829;
-        config.network.bootstrap_nodes = vec![
-            // Primary testnet bootstrap node on VPS
-            "/ip4/92.243.26.189/tcp/9000/p2p/12D3KooWBXNzc2E4Z9CLypkRXro5iSdbM5oTnTkmf8ncZAqjhAfM"
-                .to_string(),
-        ];
-        config.storage.db_path = "~/.rope/testnet/db".to_string();
-        // Enable consensus for testnet validators
-        config.consensus.enabled = true;
-        config.consensus.min_testimonies = 1;
-        config.consensus.block_time_ms = 4200; // ~4.2 seconds per anchor
-        config
+        Self::from_preset(&NetworkPreset::testnet())
+    }
+
+    /// Devnet configuration
+    pub fn devnet() -> Self {
+        Self::from_preset(&NetworkPreset::devnet())
     }
 }
 
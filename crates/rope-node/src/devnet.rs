@@ -0,0 +1,379 @@
+//! Local devnet
+//!
+//! Runs several validators inside one process so application developers
+//! can test against real consensus without any infrastructure. Validators
+//! share one devnet genesis that prefunds a set of developer accounts,
+//! produce strings with a single testimony (instant finality) and expose
+//! RPC on sequential ports.
+//!
+//! Validator keys and accounts are derived from fixed seeds, so peer IDs
+//! and addresses stay the same from one run to the next. They are public
+//! knowledge and must never hold real value.
+
+use crate::config::{NetworkPreset, NodeConfig, NodeMode};
+use crate::genesis::{self, tokenomics, Genesis, GenesisValidator, TokenAllocation};
+use crate::node::RopeNode;
+use crate::string_producer::ProducerHandle;
+use rope_crypto::keys::KeyStore;
+use rope_crypto::vrf::VrfSecretKey;
+use std::path::{Path, PathBuf};
+
+/// Devnet layout
+#[derive(Clone, Debug)]
+pub struct DevnetConfig {
+    /// Number of in-process validators
+    pub validators: usize,
+    /// Directory holding the shared genesis and one folder per validator
+    pub data_dir: PathBuf,
+    /// P2P port of the first validator; the others follow sequentially
+    pub p2p_port: u16,
+    /// HTTP RPC port of the first validator; the others follow sequentially
+    pub rpc_port: u16,
+    /// Metrics port of the first validator; the others follow sequentially
+    pub metrics_port: u16,
+    /// Number of prefunded developer accounts
+    pub accounts: usize,
+    /// Balance of each developer account (FAT wei)
+    pub account_balance: u128,
+    /// String interval (ms)
+    pub block_time_ms: u64,
+}
+
+impl Default for DevnetConfig {
+    fn default() -> Self {
+        Self {
+            validators: 4,
+            data_dir: PathBuf::from("~/.rope/devnet"),
+            p2p_port: 30300,
+            rpc_port: 8545,
+            metrics_port: 9100,
+            accounts: 10,
+            account_balance: 1_000_000 * tokenomics::FAT,
+            block_time_ms: 500,
+        }
+    }
+}
+
+/// Offsets of the WebSocket and gRPC ranges from the HTTP RPC range
+const WS_PORT_OFFSET: u16 = 100;
+const GRPC_PORT_OFFSET: u16 = 200;
+
+/// Prefunded developer account
+#[derive(Clone, Debug)]
+pub struct DevnetAccount {
+    /// Address credited in genesis
+    pub address: String,
+    /// Key seed (hex); `KeyStore::from_seed` recovers the account key
+    pub seed: String,
+}
+
+/// One in-process validator
+#[derive(Clone, Debug)]
+pub struct DevnetValidator {
+    pub name: String,
+    pub node_id: [u8; 32],
+    pub peer_id: String,
+    pub config: NodeConfig,
+    pub data_dir: PathBuf,
+    /// Submits strings to, and follows anchors of, the running validator
+    pub producer: ProducerHandle,
+    key_seed: [u8; 32],
+}
+
+impl DevnetValidator {
+    /// HTTP RPC endpoint
+    pub fn rpc_url(&self) -> String {
+        format!("http://{}", self.config.rpc.http_addr)
+    }
+
+    /// Validator key, recovered from its public devnet seed
+    pub fn keystore(&self) -> KeyStore {
+        KeyStore::from_seed(self.key_seed)
+    }
+
+    /// VRF key the validator claims anchor rounds with
+    ///
+    /// Nodes reuse their Ed25519 seed as VRF seed.
+    pub fn vrf_key(&self) -> VrfSecretKey {
+        VrfSecretKey::from_seed(
+            self.keystore()
+                .primary()
+                .signer()
+                .secret_key()
+                .ed25519_bytes(),
+        )
+    }
+
+    /// Address other validators dial
+    pub fn multiaddr(&self) -> String {
+        let (ip, port) = self
+            .config
+            .network
+            .listen_addr
+            .rsplit_once(':')
+            .unwrap_or(("127.0.0.1", "0"));
+        format!("/ip4/{}/tcp/{}/p2p/{}", ip, port, self.peer_id)
+    }
+}
+
+/// A prepared devnet
+pub struct Devnet {
+    pub genesis: Genesis,
+    pub validators: Vec<DevnetValidator>,
+    pub accounts: Vec<DevnetAccount>,
+}
+
+impl Devnet {
+    /// Prepare the devnet described by `config`
+    ///
+    /// The genesis is created on first use and reused afterwards, so chain
+    /// data survives restarts. A data directory created for a different
+    /// number of validators is refused; remove it to start over.
+    pub fn open(config: &DevnetConfig) -> anyhow::Result<Self> {
+        if config.validators == 0 {
+            anyhow::bail!("A devnet needs at least one validator");
+        }
+        let highest = [
+            u32::from(config.p2p_port),
+            u32::from(config.metrics_port),
+            u32::from(config.rpc_port) + u32::from(GRPC_PORT_OFFSET),
+        ]
+        .into_iter()
+        .max()
+        .unwrap_or_default()
+            + (config.validators as u32 - 1);
+        if highest > u32::from(u16::MAX) {
+            anyhow::bail!("Not enough ports for {} validators", config.validators);
+        }
+
+        let accounts: Vec<DevnetAccount> = (0..config.accounts).map(devnet_account).collect();
+
+        let mut validators = Vec::with_capacity(config.validators);
+        for index in 0..config.validators {
+            let key_seed = derive_seed("rope devnet validator", index);
+            let keystore = KeyStore::from_seed(key_seed);
+            let identity_seed = *keystore.primary().signer().secret_key().ed25519_bytes();
+            let peer_id = libp2p::identity::Keypair::ed25519_from_bytes(identity_seed)?
+                .public()
+                .to_peer_id()
+                .to_string();
+
+            let name = format!("validator-{}", index);
+            let data_dir = config.data_dir.join(&name);
+            validators.push(DevnetValidator {
+                config: node_config(config, index, &name),
+                name,
+                node_id: keystore.primary().node_id(),
+                peer_id,
+                data_dir,
+                producer: ProducerHandle::new(),
+                key_seed,
+            });
+        }
+
+        // Everyone bootstraps from the first validator
+        let bootstrap = validators[0].multiaddr();
+        for validator in validators.iter_mut().skip(1) {
+            validator.config.network.bootstrap_nodes = vec![bootstrap.clone()];
+        }
+
+        let genesis_path = config.data_dir.join("genesis.json");
+        let genesis = if genesis_path.exists() {
+            let genesis: Genesis = serde_json::from_str(&std::fs::read_to_string(&genesis_path)?)?;
+            genesis::check_network(&genesis, NetworkPreset::devnet().chain_id)?;
+            if genesis.validators.len() != validators.len() {
+                anyhow::bail!(
+                    "{:?} was created for {} validators; remove it to start a devnet with {}",
+                    config.data_dir,
+                    genesis.validators.len(),
+                    validators.len()
+                );
+            }
+            genesis
+        } else {
+            let genesis = devnet_genesis(config, &validators, &accounts)?;
+            std::fs::create_dir_all(&config.data_dir)?;
+            std::fs::write(&genesis_path, serde_json::to_string_pretty(&genesis)?)?;
+            genesis
+        };
+
+        Ok(Self {
+            genesis,
+            validators,
+            accounts,
+        })
+    }
+
+    /// Run every validator until Ctrl+C, or until one of them fails
+    pub async fn run(self) -> anyhow::Result<()> {
+        let genesis = serde_json::to_string_pretty(&self.genesis)?;
+        let mut nodes = Vec::with_capacity(self.validators.len());
+        for validator in self.validators {
+            std::fs::create_dir_all(&validator.data_dir)?;
+            write_if_changed(&validator.data_dir.join("genesis.json"), &genesis)?;
+
+            let mut node = RopeNode::new(validator.config, validator.data_dir).await?;
+            node.set_key_seed(validator.key_seed);
+            node.set_producer(validator.producer);
+            nodes.push(node);
+        }
+
+        futures::future::try_join_all(nodes.iter_mut().map(|node| node.run())).await?;
+        Ok(())
+    }
+}
+
+fn derive_seed(context: &str, index: usize) -> [u8; 32] {
+    blake3::derive_key(context, &(index as u64).to_le_bytes())
+}
+
+fn devnet_account(index: usize) -> DevnetAccount {
+    let seed = derive_seed("rope devnet account", index);
+    let node_id = KeyStore::from_seed(seed).primary().node_id();
+    DevnetAccount {
+        address: format!("0x{}", hex::encode(&node_id[12..])),
+        seed: hex::encode(seed),
+    }
+}
+
+/// Devnet defaults with loopback addresses and the validator's ports
+fn node_config(config: &DevnetConfig, index: usize, name: &str) -> NodeConfig {
+    let offset = index as u16;
+    let mut node = NodeConfig::devnet();
+    node.node.name = format!("rope-devnet-{}", name);
+    node.node.mode = NodeMode::Validator;
+    node.network.listen_addr = format!("127.0.0.1:{}", config.p2p_port + offset);
+    node.network.enable_nat = false;
+    node.consensus.enabled = true;
+    node.consensus.block_time_ms = config.block_time_ms;
+    node.consensus.min_testimonies = 1;
+    node.rpc.http_addr = format!("127.0.0.1:{}", config.rpc_port + offset);
+    node.rpc.ws_addr = format!("127.0.0.1:{}", config.rpc_port + WS_PORT_OFFSET + offset);
+    node.rpc.grpc_addr = format!("127.0.0.1:{}", config.rpc_port + GRPC_PORT_OFFSET + offset);
+    node.metrics.prometheus_addr = format!("127.0.0.1:{}", config.metrics_port + offset);
+    node.health.min_peers = 0;
+    node
+}
+
+/// Devnet genesis listing the in-process validators and prefunding the
+/// developer accounts; the rest of the supply stays with the foundation
+fn devnet_genesis(
+    config: &DevnetConfig,
+    validators: &[DevnetValidator],
+    accounts: &[DevnetAccount],
+) -> anyhow::Result<Genesis> {
+    let mut genesis = genesis::generate_for_preset(&NetworkPreset::devnet())?;
+    genesis.params.string_interval_ms = config.block_time_ms;
+
+    genesis.validators = validators
+        .iter()
+        .enumerate()
+        .map(|(index, validator)| GenesisValidator {
+            node_id: hex::encode(validator.node_id),
+            peer_id: validator.peer_id.clone(),
+            pubkey: String::new(),
+            name: format!("Devnet {}", validator.name),
+            stake: tokenomics::MIN_VALIDATOR_STAKE.to_string(),
+            multiaddr: validator.multiaddr(),
+            foundation: index == 0,
+            vrf_key: hex::encode(validator.vrf_key().public_key().as_bytes()),
+        })
+        .collect();
+
+    let funded = config.account_balance * accounts.len() as u128;
+    let remainder = tokenomics::GENESIS_SUPPLY
+        .checked_sub(funded)
+        .ok_or_else(|| anyhow::anyhow!("Prefunded balances exceed the genesis supply"))?;
+    genesis.allocations = accounts
+        .iter()
+        .enumerate()
+        .map(|(index, account)| TokenAllocation {
+            address: account.address.clone(),
+            amount: config.account_balance.to_string(),
+            label: format!("Devnet account {}", index),
+            vesting: None,
+        })
+        .collect();
+    genesis.allocations.push(TokenAllocation {
+        address: "0x0000000000000000000000000000000000000001".to_string(),
+        amount: remainder.to_string(),
+        label: "Genesis Allocation".to_string(),
+        vesting: None,
+    });
+
+    Ok(genesis)
+}
+
+fn write_if_changed(path: &Path, content: &str) -> anyhow::Result<()> {
+    if std::fs::read_to_string(path).ok().as_deref() != Some(content) {
+        std::fs::write(path, content)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(dir: &Path, validators: usize) -> DevnetConfig {
+        DevnetConfig {
+            validators,
+            data_dir: dir.to_path_buf(),
+            ..DevnetConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_devnet_layout() {
+        let dir = tempfile::tempdir().unwrap();
+        let devnet = Devnet::open(&config(dir.path(), 4)).unwrap();
+
+        let rpc: Vec<String> = devnet.validators.iter().map(|v| v.rpc_url()).collect();
+        assert_eq!(
+            rpc,
+            [
+                "http://127.0.0.1:8545",
+                "http://127.0.0.1:8546",
+                "http://127.0.0.1:8547",
+                "http://127.0.0.1:8548"
+            ]
+        );
+        let v0 = &devnet.validators[0];
+        assert!(v0.config.network.bootstrap_nodes.is_empty());
+        for validator in &devnet.validators[1..] {
+            assert_eq!(validator.config.network.bootstrap_nodes, [v0.multiaddr()]);
+            assert_ne!(validator.peer_id, v0.peer_id);
+        }
+
+        assert_eq!(devnet.genesis.chain_id, NetworkPreset::devnet().chain_id);
+        assert_eq!(devnet.genesis.validators.len(), 4);
+        assert_eq!(devnet.genesis.allocations.len(), 11);
+        let total: u128 = devnet
+            .genesis
+            .allocations
+            .iter()
+            .map(|a| a.amount.parse::<u128>().unwrap())
+            .sum();
+        assert_eq!(total, tokenomics::GENESIS_SUPPLY);
+
+        // Every validator is in the producer schedule
+        let schedule = genesis::producer_schedule(&devnet.genesis)
+            .unwrap()
+            .unwrap();
+        let producer = schedule.next_producer().unwrap();
+        assert!(devnet.validators.iter().any(|v| v.node_id == producer));
+    }
+
+    #[test]
+    fn test_devnet_reopens_same_genesis() {
+        let dir = tempfile::tempdir().unwrap();
+        let first = Devnet::open(&config(dir.path(), 2)).unwrap();
+        let second = Devnet::open(&config(dir.path(), 2)).unwrap();
+
+        assert_eq!(first.genesis.genesis_hash, second.genesis.genesis_hash);
+        assert_eq!(first.accounts[0].address, second.accounts[0].address);
+        assert_eq!(first.validators[1].peer_id, second.validators[1].peer_id);
+
+        assert!(Devnet::open(&config(dir.path(), 3)).is_err());
+    }
+}
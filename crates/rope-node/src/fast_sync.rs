@@ -0,0 +1,387 @@
+//! Snapshot fast-sync for new nodes
+//!
+//! Instead of replaying history from genesis, a new node asks peers for
+//! their latest [`SnapshotOffer`]: a testimony-backed [`SnapshotManifest`],
+//! the gossip checkpoint certificate it commits to, and RDP metadata for
+//! the storage snapshot. The snapshot is fetched over RDP like any string,
+//! checked against the manifest, restored into the data directory, and the
+//! node then catches up the remaining rounds and joins live gossip from the
+//! checkpoint.
+
+use crate::snapshot;
+use rope_consensus::{CatchUpSync, FinalityEngine, SnapshotManifest, SyncConfig};
+use rope_core::types::StringId;
+use rope_crypto::bls::BlsPublicKey;
+use rope_network::rdp::{RopeDistributionProtocol, StringMetadata};
+use rope_protocols::{CheckpointCertificate, GossipDag};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// What a peer serves to nodes bootstrapping from a snapshot
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SnapshotOffer {
+    /// Manifest attested by validators
+    pub manifest: SnapshotManifest,
+
+    /// Gossip checkpoint named by `manifest.checkpoint_hash`
+    pub checkpoint: CheckpointCertificate,
+
+    /// RDP metadata for the snapshot file
+    pub metadata: StringMetadata,
+}
+
+impl SnapshotOffer {
+    /// Seed `data` over RDP and describe it for bootstrapping peers
+    pub fn seed(
+        rdp: &RopeDistributionProtocol,
+        data: Vec<u8>,
+        manifest: SnapshotManifest,
+        checkpoint: CheckpointCertificate,
+        creator: [u8; 32],
+    ) -> anyhow::Result<Self> {
+        let digest = *blake3::hash(&data).as_bytes();
+        if digest != manifest.snapshot_digest || data.len() as u64 != manifest.snapshot_size {
+            anyhow::bail!(
+                "Snapshot does not match manifest for round {}",
+                manifest.round
+            );
+        }
+
+        let metadata =
+            StringMetadata::for_data(StringId::new(digest), &data, rdp.piece_size(), creator);
+        rdp.join_as_seeder(metadata.clone(), data);
+
+        Ok(Self {
+            manifest,
+            checkpoint,
+            metadata,
+        })
+    }
+
+    /// RDP swarm carrying the snapshot
+    pub fn string_id(&self) -> StringId {
+        self.metadata.string_id
+    }
+}
+
+/// Client side of snapshot fast-sync
+pub struct FastSync {
+    sync: CatchUpSync,
+    checkpoint_keys: HashMap<[u8; 32], BlsPublicKey>,
+    checkpoint_threshold: usize,
+    pending: Option<SnapshotOffer>,
+}
+
+impl FastSync {
+    /// Fast-sync a node with no local state
+    pub fn new(config: SyncConfig) -> Self {
+        let checkpoint_keys = config
+            .validator_set
+            .iter()
+            .copied()
+            .zip(config.validator_keys.iter().cloned())
+            .collect();
+        let checkpoint_threshold = config.testimony_threshold;
+        Self {
+            sync: CatchUpSync::new(config, 0),
+            checkpoint_keys,
+            checkpoint_threshold,
+            pending: None,
+        }
+    }
+
+    /// Offer being downloaded, if any
+    pub fn pending(&self) -> Option<&SnapshotOffer> {
+        self.pending.as_ref()
+    }
+
+    /// Check a peer's offer and start downloading the snapshot over RDP
+    ///
+    /// Nothing is downloaded unless the manifest carries enough anchor
+    /// testimonies and the checkpoint matches it.
+    pub fn on_offer(
+        &mut self,
+        offer: SnapshotOffer,
+        rdp: &RopeDistributionProtocol,
+    ) -> anyhow::Result<StringId> {
+        let manifest = &offer.manifest;
+        self.sync.verify_snapshot(manifest)?;
+
+        if offer.checkpoint.checkpoint.hash() != manifest.checkpoint_hash {
+            anyhow::bail!(
+                "Checkpoint does not match manifest for round {}",
+                manifest.round
+            );
+        }
+        if !offer
+            .checkpoint
+            .verify(&self.checkpoint_keys, self.checkpoint_threshold)
+        {
+            anyhow::bail!("Checkpoint certificate failed verification");
+        }
+        if offer.metadata.string_id != StringId::new(manifest.snapshot_digest)
+            || offer.metadata.total_size as u64 != manifest.snapshot_size
+        {
+            anyhow::bail!(
+                "RDP metadata does not match manifest for round {}",
+                manifest.round
+            );
+        }
+
+        let string_id = rdp.start_download(offer.metadata.clone());
+        tracing::info!(
+            "Fetching state snapshot for round {} ({} bytes)",
+            manifest.round,
+            manifest.snapshot_size
+        );
+        self.pending = Some(offer);
+        Ok(string_id)
+    }
+
+    /// Restore the downloaded snapshot into `data_dir`
+    ///
+    /// Returns the catch-up driver positioned at the snapshot round and a
+    /// gossip DAG bootstrapped from the checkpoint, ready for live gossip.
+    /// Creators still have to be registered on the DAG.
+    pub fn finish(
+        mut self,
+        rdp: &RopeDistributionProtocol,
+        data_dir: &Path,
+        finality: &FinalityEngine,
+    ) -> anyhow::Result<(CatchUpSync, GossipDag)> {
+        let offer = self
+            .pending
+            .take()
+            .ok_or_else(|| anyhow::anyhow!("No snapshot offer accepted"))?;
+        let data = rdp
+            .get_data(&offer.string_id())
+            .ok_or_else(|| anyhow::anyhow!("Snapshot download incomplete"))?;
+
+        if *blake3::hash(&data).as_bytes() != offer.manifest.snapshot_digest {
+            anyhow::bail!("Downloaded snapshot does not match manifest");
+        }
+
+        let summary = snapshot::import_bytes(&data, data_dir, true)?;
+        self.sync.apply_snapshot(&offer.manifest, finality)?;
+        tracing::info!(
+            "Restored {} files from snapshot at round {}",
+            summary.files,
+            offer.manifest.round
+        );
+
+        let dag = GossipDag::from_checkpoint(
+            offer.checkpoint,
+            &self.checkpoint_keys,
+            self.checkpoint_threshold,
+        )?;
+        Ok((self.sync, dag))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rope_consensus::{AggregateTestimony, FinalityConfig, Testimony, TestimonyCollection};
+    use rope_core::clock::LamportClock;
+    use rope_core::types::{AttestationType, NodeId};
+    use rope_crypto::bls::{BlsSecretKey, BlsSignature};
+    use rope_network::rdp::{RdpConfig, RdpMessage};
+    use rope_protocols::Checkpoint;
+
+    struct Validators {
+        ids: Vec<[u8; 32]>,
+        keys: Vec<BlsSecretKey>,
+    }
+
+    impl Validators {
+        fn new(n: usize) -> Self {
+            Self {
+                ids: (0..n).map(|i| [i as u8 + 1; 32]).collect(),
+                keys: (0..n)
+                    .map(|i| BlsSecretKey::from_seed(&[i as u8 + 100; 32]))
+                    .collect(),
+            }
+        }
+
+        fn config(&self) -> SyncConfig {
+            SyncConfig {
+                batch_size: 8,
+                testimony_threshold: 3,
+                validator_set: self.ids.clone(),
+                validator_keys: self.keys.iter().map(|k| k.public_key()).collect(),
+                validator_signing_keys: Vec::new(),
+            }
+        }
+
+        fn certify(&self, checkpoint: Checkpoint) -> CheckpointCertificate {
+            let hash = checkpoint.hash();
+            let sigs: Vec<BlsSignature> = self.keys.iter().map(|k| k.sign(&hash)).collect();
+            CheckpointCertificate {
+                checkpoint,
+                signers: self.ids.clone(),
+                signature: BlsSignature::aggregate(&sigs).unwrap(),
+            }
+        }
+
+        fn manifest(&self, data: &[u8], checkpoint: &Checkpoint, round: u64) -> SnapshotManifest {
+            let snapshot_digest = *blake3::hash(data).as_bytes();
+            let checkpoint_hash = checkpoint.hash();
+            let anchor = SnapshotManifest::commitment(
+                round,
+                &snapshot_digest,
+                data.len() as u64,
+                &checkpoint_hash,
+            );
+
+            let node_ids: Vec<NodeId> = self.ids.iter().map(|id| NodeId::new(*id)).collect();
+            let mut collection = TestimonyCollection::new(anchor);
+            for (id, key) in node_ids.iter().zip(&self.keys) {
+                let mut t = Testimony::new(
+                    anchor,
+                    *id,
+                    AttestationType::Finality,
+                    LamportClock::new(*id),
+                    1,
+                );
+                t.metadata.round = round;
+                t.set_bls_signature(key.sign(&t.aggregate_signing_data()).as_bytes().to_vec());
+                collection.add(t);
+            }
+
+            SnapshotManifest {
+                round,
+                snapshot_digest,
+                snapshot_size: data.len() as u64,
+                checkpoint_hash,
+                aggregate: AggregateTestimony::from_collection(
+                    &collection,
+                    AttestationType::Finality,
+                    round,
+                    &node_ids,
+                )
+                .unwrap(),
+            }
+        }
+    }
+
+    fn checkpoint() -> Checkpoint {
+        Checkpoint {
+            round: 40,
+            state_root: [5u8; 32],
+            frontier: vec![[6u8; 32], [7u8; 32]],
+            previous: None,
+        }
+    }
+
+    fn small_pieces(node_id: [u8; 32]) -> RopeDistributionProtocol {
+        RopeDistributionProtocol::new(
+            node_id,
+            RdpConfig {
+                piece_size: 64,
+                ..RdpConfig::default()
+            },
+        )
+    }
+
+    fn transfer(
+        seeder: &RopeDistributionProtocol,
+        client: &RopeDistributionProtocol,
+        offer: &SnapshotOffer,
+    ) {
+        let string_id = offer.string_id();
+        for piece_idx in 0..offer.metadata.piece_count {
+            let piece = seeder
+                .handle_message(&string_id, [0xcc; 32], RdpMessage::Request { piece_idx })
+                .unwrap();
+            client.handle_message(&string_id, [0xaa; 32], piece);
+        }
+    }
+
+    #[test]
+    fn test_fast_sync_from_snapshot() {
+        let validators = Validators::new(4);
+        let source = tempfile::tempdir().unwrap();
+        std::fs::write(source.path().join("genesis.json"), b"{}").unwrap();
+        std::fs::create_dir_all(source.path().join("db")).unwrap();
+        std::fs::write(source.path().join("db/state"), vec![3u8; 300]).unwrap();
+        let (data, _) = snapshot::export_bytes(source.path()).unwrap();
+
+        let seeder = small_pieces([0xaa; 32]);
+        let offer = SnapshotOffer::seed(
+            &seeder,
+            data.clone(),
+            validators.manifest(&data, &checkpoint(), 12),
+            validators.certify(checkpoint()),
+            [0xaa; 32],
+        )
+        .unwrap();
+        assert!(offer.metadata.piece_count > 1);
+
+        let client = small_pieces([0xcc; 32]);
+        let mut fast_sync = FastSync::new(validators.config());
+        fast_sync.on_offer(offer.clone(), &client).unwrap();
+        transfer(&seeder, &client, &offer);
+        assert!(client.is_complete(&offer.string_id()));
+
+        let target = tempfile::tempdir().unwrap();
+        let finality = FinalityEngine::new(FinalityConfig::default());
+        let (sync, dag) = fast_sync.finish(&client, target.path(), &finality).unwrap();
+
+        assert_eq!(sync.local_round(), 12);
+        assert_eq!(dag.current_round(), 40);
+        assert!(dag.latest_checkpoint().is_some());
+        assert_eq!(
+            std::fs::read(target.path().join("db/state")).unwrap(),
+            vec![3u8; 300]
+        );
+    }
+
+    #[test]
+    fn test_rejects_unattested_offer() {
+        let validators = Validators::new(4);
+        let data = b"snapshot bytes".to_vec();
+        let seeder = small_pieces([0xaa; 32]);
+        let client = small_pieces([0xcc; 32]);
+
+        // Checkpoint other than the one the testimonies cover
+        let mut other = checkpoint();
+        other.round = 41;
+        let offer = SnapshotOffer::seed(
+            &seeder,
+            data.clone(),
+            validators.manifest(&data, &checkpoint(), 12),
+            validators.certify(other),
+            [0xaa; 32],
+        )
+        .unwrap();
+        let mut fast_sync = FastSync::new(validators.config());
+        assert!(fast_sync.on_offer(offer, &client).is_err());
+        assert!(fast_sync.pending().is_none());
+
+        // Too few testimonies on the manifest
+        let mut manifest = validators.manifest(&data, &checkpoint(), 12);
+        manifest.aggregate.signers = rope_consensus::ValidatorBitmap::new(4);
+        let offer = SnapshotOffer {
+            manifest,
+            checkpoint: validators.certify(checkpoint()),
+            metadata: StringMetadata::for_data(
+                StringId::new(*blake3::hash(&data).as_bytes()),
+                &data,
+                64,
+                [0xaa; 32],
+            ),
+        };
+        assert!(fast_sync.on_offer(offer, &client).is_err());
+
+        // Snapshot bytes that differ from the manifest are never seeded
+        assert!(SnapshotOffer::seed(
+            &seeder,
+            b"other bytes".to_vec(),
+            validators.manifest(&data, &checkpoint(), 12),
+            validators.certify(checkpoint()),
+            [0xaa; 32],
+        )
+        .is_err());
+    }
+}
@@ -5,6 +5,9 @@
 //! - Asymptotic maximum: ~18 billion FAT (halving model)
 //! - Era 1 (2026-2029): 500M FAT/year distributed to validators
 
+use crate::config::NetworkPreset;
+use rope_consensus::{ProducerSchedule, StakeWeights};
+use rope_crypto::vrf::VrfPublicKey;
 use serde::{Deserialize, Serialize};
 
 /// Genesis configuration
@@ -45,6 +48,10 @@ pub struct GenesisValidator {
     pub multiaddr: String,
     /// Is this a foundation validator?
     pub foundation: bool,
+    /// VRF public key for anchor producer selection (hex), empty if the
+    /// validator does not produce anchors
+    #[serde(default)]
+    pub vrf_key: String,
 }
 
 /// Token allocation
@@ -71,6 +78,13 @@ pub struct VestingSchedule {
     pub duration: u64,
 }
 
+impl VestingSchedule {
+    /// Ledger lock holding `amount` under this schedule
+    pub fn lock(&self, amount: u128) -> rope_smartchain::VestingLock {
+        rope_smartchain::VestingLock::new(amount, self.start, self.cliff, self.duration)
+    }
+}
+
 /// Network parameters
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct NetworkParams {
@@ -140,6 +154,7 @@ pub fn generate_testnet_genesis() -> anyhow::Result<Genesis> {
             "/ip4/92.243.26.189/tcp/9000/p2p/12D3KooWBXNzc2E4Z9CLypkRXro5iSdbM5oTnTkmf8ncZAqjhAfM"
                 .to_string(),
         foundation: true,
+        vrf_key: String::new(),
     }];
 
     let allocations = vec![
@@ -175,16 +190,8 @@ pub fn generate_testnet_genesis() -> anyhow::Result<Genesis> {
         halving_factor: 0.5,
     };
 
-    // Compute genesis hash
-    let mut hash_input = Vec::new();
-    hash_input.extend_from_slice(&271829u64.to_le_bytes());
-    hash_input.extend_from_slice(&timestamp.to_le_bytes());
-    hash_input.extend_from_slice(b"DATACHAIN_ROPE_GENESIS_V1");
-    let genesis_hash = *blake3::hash(&hash_input).as_bytes();
-
-    let mut string_hash_input = genesis_hash.to_vec();
-    string_hash_input.extend_from_slice(b"GENESIS_STRING");
-    let genesis_string_id = *blake3::hash(&string_hash_input).as_bytes();
+    let genesis_hash = genesis_hash(271829, timestamp);
+    let genesis_string_id = genesis_string_id(&genesis_hash);
 
     Ok(Genesis {
         chain_id: 271829,
@@ -212,6 +219,7 @@ pub fn generate_genesis(validators: u32, chain_id: u64) -> anyhow::Result<Genesi
             stake: tokenomics::MIN_VALIDATOR_STAKE.to_string(),
             multiaddr: format!("/ip4/127.0.0.1/tcp/{}/p2p/12D3KooW{:040x}", 9000 + i, i),
             foundation: i == 0,
+            vrf_key: String::new(),
         });
     }
 
@@ -242,15 +250,8 @@ pub fn generate_genesis(validators: u32, chain_id: u64) -> anyhow::Result<Genesi
         halving_factor: 0.5,
     };
 
-    let mut hash_input = Vec::new();
-    hash_input.extend_from_slice(&chain_id.to_le_bytes());
-    hash_input.extend_from_slice(&timestamp.to_le_bytes());
-    hash_input.extend_from_slice(b"DATACHAIN_ROPE_GENESIS_V1");
-    let genesis_hash = *blake3::hash(&hash_input).as_bytes();
-
-    let mut string_hash_input = genesis_hash.to_vec();
-    string_hash_input.extend_from_slice(b"GENESIS_STRING");
-    let genesis_string_id = *blake3::hash(&string_hash_input).as_bytes();
+    let genesis_hash = genesis_hash(chain_id, timestamp);
+    let genesis_string_id = genesis_string_id(&genesis_hash);
 
     Ok(Genesis {
         chain_id,
@@ -263,3 +264,160 @@ pub fn generate_genesis(validators: u32, chain_id: u64) -> anyhow::Result<Genesi
         era_config,
     })
 }
+
+/// Genesis hash for `chain_id` created at `timestamp`
+pub fn genesis_hash(chain_id: u64, timestamp: i64) -> [u8; 32] {
+    let mut hash_input = Vec::new();
+    hash_input.extend_from_slice(&chain_id.to_le_bytes());
+    hash_input.extend_from_slice(&timestamp.to_le_bytes());
+    hash_input.extend_from_slice(b"DATACHAIN_ROPE_GENESIS_V1");
+    *blake3::hash(&hash_input).as_bytes()
+}
+
+fn genesis_string_id(genesis_hash: &[u8; 32]) -> [u8; 32] {
+    let mut string_hash_input = genesis_hash.to_vec();
+    string_hash_input.extend_from_slice(b"GENESIS_STRING");
+    *blake3::hash(&string_hash_input).as_bytes()
+}
+
+/// Generate the genesis of a named network
+///
+/// Economics come from the preset, and a pinned genesis timestamp makes
+/// every node derive the same genesis hash.
+pub fn generate_for_preset(preset: &NetworkPreset) -> anyhow::Result<Genesis> {
+    let mut genesis = if preset.chain_id == 271829 {
+        generate_testnet_genesis()?
+    } else {
+        generate_genesis(1, preset.chain_id)?
+    };
+
+    genesis.params.min_testimonies = preset.min_testimonies;
+    genesis.params.ai_agents_required = preset.ai_agents_required;
+    genesis.params.epoch_length = preset.epoch_length;
+
+    if let Some(timestamp) = preset.genesis_timestamp {
+        genesis.timestamp = timestamp;
+        genesis.genesis_hash = genesis_hash(preset.chain_id, timestamp);
+        genesis.genesis_string_id = genesis_string_id(&genesis.genesis_hash);
+    }
+
+    Ok(genesis)
+}
+
+/// Refuse a genesis that belongs to a different network than `chain_id`
+pub fn check_network(genesis: &Genesis, chain_id: u64) -> anyhow::Result<()> {
+    let describe = |chain_id: u64| match NetworkPreset::for_chain_id(chain_id) {
+        Some(preset) => format!("{} (chain {})", preset.name, chain_id),
+        None => format!("chain {}", chain_id),
+    };
+
+    if genesis.chain_id != chain_id {
+        anyhow::bail!(
+            "Data directory belongs to {}, but the node is configured for {}",
+            describe(genesis.chain_id),
+            describe(chain_id)
+        );
+    }
+
+    let pinned = NetworkPreset::for_chain_id(chain_id).and_then(|p| p.genesis_hash());
+    if let Some(expected) = pinned {
+        if genesis.genesis_hash != expected {
+            anyhow::bail!(
+                "Genesis {} does not match the {} genesis {}",
+                hex::encode(genesis.genesis_hash),
+                describe(chain_id),
+                hex::encode(expected)
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Anchor producer schedule over the genesis validators that registered
+/// a VRF key, or `None` if none did
+///
+/// Stake is weighted in whole FAT and the beacon is seeded with the
+/// genesis hash, so every node derives the same schedule.
+pub fn producer_schedule(genesis: &Genesis) -> anyhow::Result<Option<ProducerSchedule>> {
+    let mut stakes = std::collections::HashMap::new();
+    let mut keys = Vec::new();
+    for validator in genesis.validators.iter().filter(|v| !v.vrf_key.is_empty()) {
+        let node_id: [u8; 32] = hex::decode(&validator.node_id)?
+            .try_into()
+            .map_err(|_| anyhow::anyhow!("Invalid node ID of {}", validator.name))?;
+        let vrf_key: [u8; 32] = hex::decode(&validator.vrf_key)?
+            .try_into()
+            .map_err(|_| anyhow::anyhow!("Invalid VRF key of {}", validator.name))?;
+        let stake = validator.stake.parse::<u128>()? / tokenomics::FAT;
+
+        stakes.insert(node_id, u64::try_from(stake)?);
+        keys.push((node_id, VrfPublicKey::from_bytes(&vrf_key)?));
+    }
+    if keys.is_empty() {
+        return Ok(None);
+    }
+
+    let schedule = ProducerSchedule::new(StakeWeights::new(stakes, 0.667), 0, genesis.genesis_hash);
+    for (node_id, key) in keys {
+        schedule.register_key(node_id, key);
+    }
+    Ok(Some(schedule))
+}
+
+/// Load the genesis at `path`, generating and saving one for `chain_id`
+/// if the file does not exist yet
+pub fn load_or_create(path: &std::path::Path, chain_id: u64) -> anyhow::Result<Genesis> {
+    if path.exists() {
+        let content = std::fs::read_to_string(path)?;
+        return Ok(serde_json::from_str(&content)?);
+    }
+
+    let genesis = match NetworkPreset::for_chain_id(chain_id) {
+        Some(preset) => generate_for_preset(&preset)?,
+        None => generate_genesis(1, chain_id)?,
+    };
+
+    let content = serde_json::to_string_pretty(&genesis)?;
+    std::fs::write(path, &content)?;
+    tracing::info!("Genesis saved to {:?}", path);
+
+    Ok(genesis)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_preset_genesis_is_pinned() {
+        let mainnet = NetworkPreset::mainnet();
+        let genesis = generate_for_preset(&mainnet).unwrap();
+        assert_eq!(Some(genesis.genesis_hash), mainnet.genesis_hash());
+        assert_eq!(genesis.params.min_testimonies, 5);
+        assert!(check_network(&genesis, mainnet.chain_id).is_ok());
+
+        // A mainnet genesis made at any other time is refused
+        let stray = generate_genesis(1, mainnet.chain_id).unwrap();
+        assert!(check_network(&stray, mainnet.chain_id).is_err());
+
+        let devnet = generate_for_preset(&NetworkPreset::devnet()).unwrap();
+        assert_eq!(devnet.params.epoch_length, 100);
+    }
+
+    #[test]
+    fn test_refuses_other_network_data() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("genesis.json");
+
+        let testnet = NetworkPreset::testnet();
+        let genesis = load_or_create(&path, testnet.chain_id).unwrap();
+        assert!(check_network(&genesis, testnet.chain_id).is_ok());
+
+        // Reopening the same directory as devnet must fail
+        let reloaded = load_or_create(&path, NetworkPreset::devnet().chain_id).unwrap();
+        assert_eq!(reloaded.chain_id, testnet.chain_id);
+        let err = check_network(&reloaded, NetworkPreset::devnet().chain_id).unwrap_err();
+        assert!(err.to_string().contains("testnet"));
+    }
+}
@@ -0,0 +1,332 @@
+//! Liveness and readiness probes
+//!
+//! `/healthz` answers whether the process can still do useful work (its
+//! storage is writable). `/readyz` additionally checks the node's
+//! dependencies: enough peers, recent consensus participation for nodes
+//! that run consensus, and every registered bridge being connected. Both
+//! return a JSON report of component statuses with `200` when every check
+//! passes and `503` otherwise, which is what Kubernetes probes expect.
+
+use crate::config::HealthSettings;
+use parking_lot::RwLock;
+use rope_bridge::common::Bridge;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
+
+/// Outcome of one dependency check
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct ComponentStatus {
+    pub name: String,
+    pub healthy: bool,
+    pub detail: String,
+}
+
+impl ComponentStatus {
+    fn new(name: &str, healthy: bool, detail: String) -> Self {
+        Self {
+            name: name.to_string(),
+            healthy,
+            detail,
+        }
+    }
+}
+
+/// Probe response body
+#[derive(Clone, Debug, Serialize)]
+pub struct HealthReport {
+    /// `ok` when every component is healthy, `unavailable` otherwise
+    pub status: &'static str,
+    pub components: Vec<ComponentStatus>,
+}
+
+impl HealthReport {
+    fn from_components(components: Vec<ComponentStatus>) -> Self {
+        let healthy = components.iter().all(|c| c.healthy);
+        Self {
+            status: if healthy { "ok" } else { "unavailable" },
+            components,
+        }
+    }
+
+    pub fn is_healthy(&self) -> bool {
+        self.status == "ok"
+    }
+
+    /// Full HTTP/1.1 response: `200` if healthy, `503` otherwise
+    pub fn http_response(&self) -> String {
+        let body = serde_json::to_string(self).unwrap_or_default();
+        let status_line = if self.is_healthy() {
+            "200 OK"
+        } else {
+            "503 Service Unavailable"
+        };
+        format!(
+            "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+            status_line,
+            body.len(),
+            body
+        )
+    }
+}
+
+/// Live node state read by the probes
+pub struct HealthState {
+    settings: HealthSettings,
+    /// Directory that must stay writable
+    storage_dir: PathBuf,
+    /// Whether this node is expected to take part in consensus
+    consensus_expected: bool,
+    peers: AtomicUsize,
+    current_round: Arc<RwLock<u64>>,
+    /// Last round this node produced a string in (0 = never)
+    last_participation: AtomicU64,
+    /// Bridge name -> connected at last poll
+    bridges: RwLock<BTreeMap<String, bool>>,
+}
+
+impl HealthState {
+    pub fn new(
+        settings: HealthSettings,
+        storage_dir: PathBuf,
+        consensus_expected: bool,
+        current_round: Arc<RwLock<u64>>,
+    ) -> Self {
+        Self {
+            settings,
+            storage_dir,
+            consensus_expected,
+            peers: AtomicUsize::new(0),
+            current_round,
+            last_participation: AtomicU64::new(0),
+            bridges: RwLock::new(BTreeMap::new()),
+        }
+    }
+
+    pub fn peer_connected(&self) {
+        self.peers.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn peer_disconnected(&self) {
+        let _ = self
+            .peers
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |n| n.checked_sub(1));
+    }
+
+    pub fn peer_count(&self) -> usize {
+        self.peers.load(Ordering::Relaxed)
+    }
+
+    /// Note that this node took part in consensus at `round`
+    pub fn record_participation(&self, round: u64) {
+        self.last_participation.fetch_max(round, Ordering::Relaxed);
+    }
+
+    /// Poll every bridge and remember whether it is connected
+    pub async fn poll_bridges(&self, bridges: &[Arc<dyn Bridge>]) {
+        for bridge in bridges {
+            let connected = bridge.is_connected().await;
+            self.bridges
+                .write()
+                .insert(bridge.name().to_string(), connected);
+        }
+    }
+
+    /// Liveness: only what the process itself needs
+    pub fn liveness(&self) -> HealthReport {
+        HealthReport::from_components(vec![self.check_storage()])
+    }
+
+    /// Readiness: liveness plus peers, consensus and bridges
+    pub fn readiness(&self) -> HealthReport {
+        let mut components = vec![self.check_storage(), self.check_peers()];
+        if self.consensus_expected {
+            components.push(self.check_consensus());
+        }
+        components.extend(self.check_bridges());
+        HealthReport::from_components(components)
+    }
+
+    fn check_storage(&self) -> ComponentStatus {
+        let probe = self.storage_dir.join(".health-probe");
+        let result = std::fs::write(&probe, b"ok").and_then(|_| std::fs::remove_file(&probe));
+        match result {
+            Ok(()) => ComponentStatus::new(
+                "storage",
+                true,
+                format!("{} is writable", self.storage_dir.display()),
+            ),
+            Err(e) => ComponentStatus::new(
+                "storage",
+                false,
+                format!("{} is not writable: {}", self.storage_dir.display(), e),
+            ),
+        }
+    }
+
+    fn check_peers(&self) -> ComponentStatus {
+        let peers = self.peer_count();
+        ComponentStatus::new(
+            "peers",
+            peers >= self.settings.min_peers,
+            format!("{} connected, {} required", peers, self.settings.min_peers),
+        )
+    }
+
+    fn check_consensus(&self) -> ComponentStatus {
+        let current = *self.current_round.read();
+        let last = self.last_participation.load(Ordering::Relaxed);
+        let behind = current.saturating_sub(last);
+        let detail = if last == 0 {
+            format!("no participation yet at round {}", current)
+        } else {
+            format!("last participated in round {} of {}", last, current)
+        };
+        ComponentStatus::new(
+            "consensus",
+            behind <= self.settings.max_rounds_behind,
+            detail,
+        )
+    }
+
+    fn check_bridges(&self) -> Vec<ComponentStatus> {
+        self.bridges
+            .read()
+            .iter()
+            .map(|(name, connected)| {
+                let detail = if *connected {
+                    "connected"
+                } else {
+                    "disconnected"
+                };
+                ComponentStatus::new(&format!("bridge:{}", name), *connected, detail.to_string())
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use rope_bridge::common::{BridgeError, BridgeTransaction, ProtocolType};
+
+    struct StubBridge(bool);
+
+    #[async_trait]
+    impl Bridge for StubBridge {
+        fn name(&self) -> &str {
+            "stub"
+        }
+
+        fn protocol_type(&self) -> ProtocolType {
+            ProtocolType::Custom("stub".to_string())
+        }
+
+        async fn is_connected(&self) -> bool {
+            self.0
+        }
+
+        async fn sync_state(&mut self) -> Result<(), BridgeError> {
+            Ok(())
+        }
+
+        async fn submit_transaction(
+            &self,
+            _tx: BridgeTransaction,
+        ) -> Result<[u8; 32], BridgeError> {
+            Ok([0u8; 32])
+        }
+
+        async fn verify_proof(&self, _proof: &[u8]) -> Result<bool, BridgeError> {
+            Ok(true)
+        }
+    }
+
+    fn state(dir: &std::path::Path, consensus: bool) -> (HealthState, Arc<RwLock<u64>>) {
+        let round = Arc::new(RwLock::new(0));
+        let settings = HealthSettings {
+            min_peers: 2,
+            max_rounds_behind: 5,
+        };
+        (
+            HealthState::new(settings, dir.to_path_buf(), consensus, round.clone()),
+            round,
+        )
+    }
+
+    fn component<'a>(report: &'a HealthReport, name: &str) -> &'a ComponentStatus {
+        report.components.iter().find(|c| c.name == name).unwrap()
+    }
+
+    #[test]
+    fn test_readiness_tracks_peers_and_consensus() {
+        let dir = tempfile::tempdir().unwrap();
+        let (health, round) = state(dir.path(), true);
+
+        assert!(health.liveness().is_healthy());
+        let report = health.readiness();
+        assert!(!report.is_healthy());
+        assert!(!component(&report, "peers").healthy);
+        // Nothing to fall behind on yet
+        assert!(component(&report, "consensus").healthy);
+
+        health.peer_connected();
+        health.peer_connected();
+        health.peer_connected();
+        health.peer_disconnected();
+        assert!(health.readiness().is_healthy());
+
+        *round.write() = 20;
+        health.record_participation(12);
+        assert!(!component(&health.readiness(), "consensus").healthy);
+        health.record_participation(16);
+        assert!(health.readiness().is_healthy());
+
+        // Relays are not expected to take part in consensus
+        let (relay, _) = state(dir.path(), false);
+        assert!(relay
+            .readiness()
+            .components
+            .iter()
+            .all(|c| c.name != "consensus"));
+    }
+
+    #[test]
+    fn test_storage_and_http_status() {
+        let dir = tempfile::tempdir().unwrap();
+        let (health, _) = state(&dir.path().join("missing"), false);
+
+        let report = health.liveness();
+        assert!(!report.is_healthy());
+        let response = report.http_response();
+        assert!(response.starts_with("HTTP/1.1 503"));
+        assert!(response.contains("\"name\":\"storage\""));
+
+        let (health, _) = state(dir.path(), false);
+        assert!(health
+            .liveness()
+            .http_response()
+            .starts_with("HTTP/1.1 200"));
+    }
+
+    #[tokio::test]
+    async fn test_bridge_connectivity() {
+        let dir = tempfile::tempdir().unwrap();
+        let (health, _) = state(dir.path(), false);
+        health.peer_connected();
+        health.peer_connected();
+
+        let down: Arc<dyn Bridge> = Arc::new(StubBridge(false));
+        health.poll_bridges(&[down]).await;
+        let report = health.readiness();
+        assert!(!component(&report, "bridge:stub").healthy);
+        assert!(!report.is_healthy());
+
+        let up: Arc<dyn Bridge> = Arc::new(StubBridge(true));
+        health.poll_bridges(&[up]).await;
+        assert!(health.readiness().is_healthy());
+    }
+}
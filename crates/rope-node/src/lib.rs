@@ -3,12 +3,22 @@
 //! Full node implementation for the Datachain Rope network.
 
 pub mod config;
+pub mod devnet;
+pub mod fast_sync;
 pub mod genesis;
+pub mod health;
 pub mod metrics;
 pub mod node;
 pub mod rpc_server;
+pub mod services;
+pub mod snapshot;
 pub mod string_producer;
 
-pub use config::NodeConfig;
+pub use config::{NetworkPreset, NodeConfig};
+pub use devnet::{Devnet, DevnetConfig};
+pub use health::{HealthReport, HealthState};
 pub use node::RopeNode;
-pub use string_producer::{ProductionEvent, ProductionStats, StringProducer, StringProducerConfig};
+pub use services::{NodeService, ServicePlan, ServiceSupervisor};
+pub use string_producer::{
+    ProducerHandle, ProductionEvent, ProductionStats, StringProducer, StringProducerConfig,
+};
@@ -1,9 +1,14 @@
 //! Prometheus metrics server
+//!
+//! Also serves the `/healthz` and `/readyz` probes when given a
+//! [`HealthState`].
 
 use crate::config::MetricsSettings;
+use crate::health::HealthState;
 use prometheus::{Counter, Encoder, Gauge, Registry, TextEncoder};
 use std::io::Write;
 use std::net::SocketAddr;
+use std::sync::Arc;
 
 /// Metrics server
 pub struct MetricsServer {
@@ -11,6 +16,8 @@ pub struct MetricsServer {
     config: MetricsSettings,
     /// Prometheus registry
     registry: Registry,
+    /// Probe state
+    health: Option<Arc<HealthState>>,
 }
 
 impl MetricsServer {
@@ -34,9 +41,16 @@ impl MetricsServer {
         Ok(Self {
             config: config.clone(),
             registry,
+            health: None,
         })
     }
 
+    /// Serve liveness and readiness probes from `health`
+    pub fn with_health(mut self, health: Arc<HealthState>) -> Self {
+        self.health = Some(health);
+        self
+    }
+
     /// Run the metrics server
     pub async fn run(&self) -> anyhow::Result<()> {
         let addr: SocketAddr = self.config.prometheus_addr.parse()?;
@@ -51,6 +65,7 @@ impl MetricsServer {
             match listener.accept() {
                 Ok((mut stream, _)) => {
                     let registry = self.registry.clone();
+                    let health = self.health.clone();
 
                     // Handle request synchronously in a blocking task
                     tokio::task::spawn_blocking(move || {
@@ -70,6 +85,14 @@ impl MetricsServer {
                                     buffer.len(),
                                     String::from_utf8_lossy(&buffer)
                                 )
+                            } else if let (true, Some(health)) =
+                                (request.contains("GET /healthz"), &health)
+                            {
+                                health.liveness().http_response()
+                            } else if let (true, Some(health)) =
+                                (request.contains("GET /readyz"), &health)
+                            {
+                                health.readiness().http_response()
                             } else if request.contains("GET /health") {
                                 "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\n\r\n{\"status\":\"healthy\"}".to_string()
                             } else {
@@ -1,16 +1,25 @@
 //! Datachain Rope Node implementation
 //!
 //! Full node implementation with integrated libp2p swarm networking
-//! and string production.
+//! and string production. Services are chosen by role and supervised
+//! (see [`crate::services`]).
 
 use crate::config::{NodeConfig, NodeMode};
 use crate::genesis;
+use crate::health::HealthState;
 use crate::metrics::MetricsServer;
 use crate::rpc_server::RpcServer;
-use crate::string_producer::{ProductionEvent, StringProducer, StringProducerConfig};
+use crate::services::{NodeService, ServicePlan, ServiceSupervisor};
+use crate::string_producer::{
+    ProducerHandle, ProductionEvent, StringProducer, StringProducerConfig,
+};
 
 use parking_lot::RwLock;
+use rope_bridge::common::Bridge;
+use rope_consensus::{ProducerClaim, ProducerSchedule};
 use rope_core::types::{NodeId, StringId};
+use rope_crypto::vrf::{VrfProof, VrfSecretKey};
+use rope_economics::EmissionSchedule;
 use std::net::SocketAddr;
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -21,8 +30,16 @@ use tokio::sync::{broadcast, mpsc};
 // Import rope-network swarm runtime
 use rope_network::{
     swarm::{GossipSubConfig, KademliaConfig, RequestResponseConfig},
-    RopeSwarmRuntime, SwarmCommand, SwarmConfig, SwarmNetworkEvent, TransportConfig,
+    CompressionConfig, NatConfig, PubsubConfig, RopeSwarmRuntime, SwarmCommand, SwarmConfig,
+    SwarmNetworkEvent, TransportConfig,
 };
+use rope_network::{RdpConfig, RopeDistributionProtocol};
+
+/// Time each service gets to stop before it is aborted
+const SHUTDOWN_GRACE: Duration = Duration::from_secs(10);
+
+/// How often bridge connectivity is polled for `/readyz`
+const BRIDGE_POLL_INTERVAL: Duration = Duration::from_secs(15);
 
 /// Node state
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -55,12 +72,22 @@ pub struct RopeNode {
     network_event_rx: Arc<RwLock<Option<broadcast::Receiver<SwarmNetworkEvent>>>>,
     /// Identity seed for deterministic peer ID
     identity_seed: Option<[u8; 32]>,
+    /// Seed of a node key used instead of the keystore
+    key_seed: Option<[u8; 32]>,
     /// Node ID
     node_id: Option<NodeId>,
-    /// String producer shutdown channel
-    producer_shutdown_tx: Option<mpsc::Sender<()>>,
+    /// Rope Distribution Protocol, when the role runs it
+    rdp: Option<Arc<RopeDistributionProtocol>>,
     /// Current anchor/block number
     current_round: Arc<RwLock<u64>>,
+    /// Probe state, once the service plan is known
+    health: Option<Arc<HealthState>>,
+    /// External bridges checked by the readiness probe
+    bridges: Vec<Arc<dyn Bridge>>,
+    /// Pending pool and events of the string producer
+    producer: ProducerHandle,
+    /// Anchor producer schedule, when genesis registers VRF keys
+    schedule: Option<Arc<ProducerSchedule>>,
 }
 
 impl RopeNode {
@@ -74,9 +101,14 @@ impl RopeNode {
             swarm_runtime: Arc::new(RwLock::new(None)),
             network_event_rx: Arc::new(RwLock::new(None)),
             identity_seed: None,
+            key_seed: None,
             node_id: None,
-            producer_shutdown_tx: None,
+            rdp: None,
             current_round: Arc::new(RwLock::new(0)),
+            health: None,
+            bridges: Vec::new(),
+            producer: ProducerHandle::new(),
+            schedule: None,
         })
     }
 
@@ -98,102 +130,175 @@ impl RopeNode {
             .and_then(|s| s.command_sender())
     }
 
+    /// Rope Distribution Protocol, once started
+    pub fn rdp(&self) -> Option<Arc<RopeDistributionProtocol>> {
+        self.rdp.clone()
+    }
+
+    /// Probe state, once the node is running
+    pub fn health(&self) -> Option<Arc<HealthState>> {
+        self.health.clone()
+    }
+
+    /// Derive the node key from `seed` instead of loading the keystore
+    ///
+    /// For devnets and tests only: the key is as secret as the seed.
+    pub fn set_key_seed(&mut self, seed: [u8; 32]) {
+        self.key_seed = Some(seed);
+    }
+
+    /// Produce through `handle`, so its holder can submit strings and
+    /// follow anchors once the node runs
+    pub fn set_producer(&mut self, handle: ProducerHandle) {
+        self.producer = handle;
+    }
+
+    /// Require `bridge` to be connected for the node to report ready
+    pub fn add_bridge(&mut self, bridge: Arc<dyn Bridge>) {
+        self.bridges.push(bridge);
+    }
+
     /// Run the node
+    ///
+    /// Starts the services of the node's role in dependency order, then
+    /// runs until SIGINT/SIGTERM or until a service exits on its own, and
+    /// stops services in reverse order.
     pub async fn run(&mut self) -> anyhow::Result<()> {
         tracing::info!("Starting Datachain Rope node...");
 
         // Set state to starting
         *self.state.write() = NodeState::Starting;
 
-        // Initialize components
-        self.init_storage().await?;
+        let plan = ServicePlan::for_config(&self.config);
+        let names: Vec<&str> = plan.startup_order().iter().map(|s| s.name()).collect();
+        tracing::info!(
+            "Services for {:?} role: {}",
+            self.config.node.mode,
+            names.join(", ")
+        );
+
+        // Identity and genesis precede every service
         let (identity_seed, node_id) = self.init_crypto().await?;
         self.identity_seed = Some(identity_seed);
         self.node_id = Some(node_id.clone());
+        let genesis = self.init_genesis().await?;
+        self.schedule = genesis::producer_schedule(&genesis)?.map(Arc::new);
+
+        self.health = Some(Arc::new(HealthState::new(
+            self.config.health.clone(),
+            self.data_dir.join("db"),
+            plan.contains(NodeService::Consensus),
+            self.current_round.clone(),
+        )));
+
+        let mut supervisor = ServiceSupervisor::new();
+        for service in plan.startup_order() {
+            let started = self
+                .start_service(*service, &mut supervisor, &node_id, &genesis)
+                .await;
+            if let Err(e) = started {
+                tracing::error!("Failed to start {}: {}", service, e);
+                self.stop(supervisor).await?;
+                return Err(e);
+            }
+        }
 
-        // Initialize and start libp2p network
-        self.init_network(identity_seed).await?;
+        // Set state to running
+        *self.state.write() = NodeState::Running;
 
-        // Initialize genesis if needed
-        let genesis_string_id = self.init_genesis().await?;
+        self.print_startup_banner(&plan);
 
-        // Start string producer if validator
-        let producer_handle = if self.config.consensus.enabled
-            && matches!(self.config.node.mode, NodeMode::Validator)
-        {
-            Some(
-                self.start_string_producer(node_id, genesis_string_id)
-                    .await?,
-            )
-        } else {
-            tracing::info!("String production disabled (non-validator mode)");
-            None
-        };
+        // Run until a shutdown signal or a service exiting on its own
+        tokio::select! {
+            _ = self.wait_for_shutdown() => {}
+            (service, result) = supervisor.next_exit() => match result {
+                Ok(()) => tracing::warn!("Service {} exited unexpectedly", service),
+                Err(e) => tracing::error!("Service {} failed: {}", service, e),
+            },
+        }
 
-        // Start RPC server
-        let rpc_handle = if self.config.rpc.enabled {
-            let current_round = self.current_round.clone();
-            let chain_id = self.config.node.chain_id;
-            let rpc_server =
-                RpcServer::new_with_state(&self.config.rpc, chain_id, current_round).await?;
-            Some(tokio::spawn(async move {
-                if let Err(e) = rpc_server.run().await {
-                    tracing::error!("RPC server error: {}", e);
-                }
-            }))
-        } else {
-            None
-        };
+        self.stop(supervisor).await
+    }
 
-        // Start metrics server
-        let metrics_handle = if self.config.metrics.enabled {
-            let metrics_server = MetricsServer::new(&self.config.metrics)?;
-            Some(tokio::spawn(async move {
-                if let Err(e) = metrics_server.run().await {
-                    tracing::error!("Metrics server error: {}", e);
+    /// Start one service of the plan
+    async fn start_service(
+        &mut self,
+        service: NodeService,
+        supervisor: &mut ServiceSupervisor,
+        node_id: &NodeId,
+        genesis: &genesis::Genesis,
+    ) -> anyhow::Result<()> {
+        match service {
+            NodeService::Storage => self.init_storage().await,
+            NodeService::Network => {
+                let identity_seed = self
+                    .identity_seed
+                    .ok_or_else(|| anyhow::anyhow!("Identity not initialized"))?;
+                self.init_network(identity_seed).await?;
+                self.start_network_event_processor(supervisor);
+                Ok(())
+            }
+            NodeService::Consensus => {
+                self.start_string_producer(
+                    supervisor,
+                    node_id.clone(),
+                    StringId::new(genesis.genesis_string_id),
+                    EmissionSchedule::new(genesis.timestamp),
+                )
+                .await
+            }
+            NodeService::Rdp => {
+                self.start_rdp(supervisor, node_id);
+                Ok(())
+            }
+            NodeService::Rpc => {
+                let current_round = self.current_round.clone();
+                let chain_id = self.config.node.chain_id;
+                let rpc_server =
+                    RpcServer::new_with_state(&self.config.rpc, chain_id, current_round).await?;
+                supervisor
+                    .spawn_until_shutdown(NodeService::Rpc, async move { rpc_server.run().await });
+                Ok(())
+            }
+            NodeService::Metrics => {
+                let mut metrics_server = MetricsServer::new(&self.config.metrics)?;
+                if let Some(health) = self.health.clone() {
+                    metrics_server = metrics_server.with_health(health.clone());
+                    self.start_bridge_poller(supervisor, health);
                 }
-            }))
-        } else {
-            None
-        };
-
-        // Start network event processing
-        let network_handle = self.start_network_event_processor();
-
-        // Set state to running
-        *self.state.write() = NodeState::Running;
-
-        self.print_startup_banner();
+                supervisor.spawn_until_shutdown(NodeService::Metrics, async move {
+                    metrics_server.run().await
+                });
+                Ok(())
+            }
+        }
+    }
 
-        // Wait for shutdown signal
-        self.wait_for_shutdown().await;
+    /// Poll bridge connectivity for the readiness probe
+    fn start_bridge_poller(&self, supervisor: &mut ServiceSupervisor, health: Arc<HealthState>) {
+        if self.bridges.is_empty() {
+            return;
+        }
+        let bridges = self.bridges.clone();
+        supervisor.spawn_until_shutdown(NodeService::Metrics, async move {
+            let mut interval = tokio::time::interval(BRIDGE_POLL_INTERVAL);
+            loop {
+                interval.tick().await;
+                health.poll_bridges(&bridges).await;
+            }
+        });
+    }
 
-        // Graceful shutdown
+    /// Stop supervised services in reverse start order, then the swarm
+    async fn stop(&mut self, supervisor: ServiceSupervisor) -> anyhow::Result<()> {
         *self.state.write() = NodeState::Stopping;
         tracing::info!("Shutting down...");
 
-        // Stop string producer
-        if let Some(tx) = self.producer_shutdown_tx.take() {
-            let _ = tx.send(()).await;
-        }
+        supervisor.shutdown(SHUTDOWN_GRACE).await;
 
-        // Stop swarm
+        // The swarm runtime is owned by the node rather than a service task
         self.stop_network().await?;
 
-        // Stop other components
-        if let Some(handle) = rpc_handle {
-            handle.abort();
-        }
-        if let Some(handle) = metrics_handle {
-            handle.abort();
-        }
-        if let Some(handle) = network_handle {
-            handle.abort();
-        }
-        if let Some(handle) = producer_handle {
-            handle.abort();
-        }
-
         *self.state.write() = NodeState::Stopped;
         tracing::info!("Node stopped");
 
@@ -201,27 +306,11 @@ impl RopeNode {
     }
 
     /// Initialize genesis
-    async fn init_genesis(&self) -> anyhow::Result<StringId> {
+    async fn init_genesis(&self) -> anyhow::Result<genesis::Genesis> {
         let genesis_path = self.data_dir.join("genesis.json");
 
-        let genesis = if genesis_path.exists() {
-            let content = std::fs::read_to_string(&genesis_path)?;
-            serde_json::from_str(&content)?
-        } else {
-            // Generate genesis based on chain ID
-            let gen = if self.config.node.chain_id == 271829 {
-                genesis::generate_testnet_genesis()?
-            } else {
-                genesis::generate_genesis(1, self.config.node.chain_id)?
-            };
-
-            // Save genesis
-            let content = serde_json::to_string_pretty(&gen)?;
-            std::fs::write(&genesis_path, &content)?;
-            tracing::info!("Genesis saved to {:?}", genesis_path);
-
-            gen
-        };
+        let genesis = genesis::load_or_create(&genesis_path, self.config.node.chain_id)?;
+        genesis::check_network(&genesis, self.config.node.chain_id)?;
 
         tracing::info!("Genesis hash: {}", hex::encode(&genesis.genesis_hash[..8]));
         tracing::info!(
@@ -229,15 +318,17 @@ impl RopeNode {
             hex::encode(&genesis.genesis_string_id[..8])
         );
 
-        Ok(StringId::new(genesis.genesis_string_id))
+        Ok(genesis)
     }
 
     /// Start the string producer
     async fn start_string_producer(
         &mut self,
+        supervisor: &mut ServiceSupervisor,
         node_id: NodeId,
         genesis_string_id: StringId,
-    ) -> anyhow::Result<tokio::task::JoinHandle<()>> {
+        emission: EmissionSchedule,
+    ) -> anyhow::Result<()> {
         let config = StringProducerConfig {
             string_interval_ms: self.config.consensus.block_time_ms,
             min_testimonies: self.config.consensus.min_testimonies,
@@ -246,22 +337,39 @@ impl RopeNode {
             is_validator: matches!(self.config.node.mode, NodeMode::Validator),
         };
 
-        let mut producer = StringProducer::new(config, node_id);
+        let mut producer = StringProducer::with_handle(config, node_id, self.producer.clone());
         producer.set_genesis(genesis_string_id);
+        producer.set_emission(emission);
+        match (&self.schedule, self.identity_seed) {
+            // The VRF key reuses the Ed25519 seed
+            (Some(schedule), Some(seed)) => {
+                producer.set_schedule(schedule.clone(), VrfSecretKey::from_seed(&seed))
+            }
+            _ => tracing::warn!("Genesis registers no producer VRF keys; anchoring every round"),
+        }
 
         // Get event receiver for updating state
         let mut event_rx = producer.subscribe();
         let current_round = self.current_round.clone();
         let swarm = self.swarm_runtime.clone();
+        let health = self.health.clone();
 
         // Spawn event handler
         tokio::spawn(async move {
             while let Ok(event) = event_rx.recv().await {
                 match event {
+                    ProductionEvent::StringCreated { round, .. } => {
+                        if let Some(health) = &health {
+                            health.record_participation(round);
+                        }
+                    }
                     ProductionEvent::AnchorFinalized {
                         anchor_id,
                         round,
                         strings_included: _,
+                        reward: _,
+                        fees: _,
+                        claim,
                     } => {
                         *current_round.write() = round;
 
@@ -270,11 +378,18 @@ impl RopeNode {
                         let publish_result = {
                             let swarm_guard = swarm.read();
                             if let Some(sw) = swarm_guard.as_ref() {
-                                let msg = format!(
+                                let mut msg = format!(
                                     "anchor:{}:{}",
                                     round,
                                     hex::encode(&anchor_id.as_bytes()[..16])
                                 );
+                                if let Some(claim) = &claim {
+                                    msg.push_str(&format!(
+                                        ":{}:{}",
+                                        hex::encode(claim.producer),
+                                        hex::encode(claim.proof.as_bytes())
+                                    ));
+                                }
                                 Some((sw.command_sender(), msg))
                             } else {
                                 None
@@ -291,6 +406,14 @@ impl RopeNode {
                                 .await;
                         }
                     }
+                    ProductionEvent::GrantPaid { round, payout } => {
+                        tracing::info!(
+                            "Round {}: paid {} to grant {}",
+                            round,
+                            payout.amount,
+                            hex::encode(&payout.grant_id[..8])
+                        );
+                    }
                     ProductionEvent::ProductionError { round, error } => {
                         tracing::warn!("Production error at round {}: {}", round, error);
                     }
@@ -299,13 +422,20 @@ impl RopeNode {
             }
         });
 
-        // Create shutdown channel
-        let (shutdown_tx, shutdown_rx) = mpsc::channel(1);
-        self.producer_shutdown_tx = Some(shutdown_tx);
-
-        // Start producer
-        let handle = tokio::spawn(async move {
-            producer.run(shutdown_rx).await;
+        // Start producer, letting it finish its current round on shutdown
+        let mut shutdown = supervisor.shutdown_signal();
+        supervisor.spawn(NodeService::Consensus, async move {
+            let (stop_tx, stop_rx) = mpsc::channel(1);
+            let run = producer.run(stop_rx);
+            tokio::pin!(run);
+            tokio::select! {
+                _ = &mut run => {}
+                _ = shutdown.wait_for(|stop| *stop) => {
+                    let _ = stop_tx.send(()).await;
+                    run.await;
+                }
+            }
+            Ok(())
         });
 
         tracing::info!(
@@ -313,11 +443,40 @@ impl RopeNode {
             self.config.consensus.block_time_ms
         );
 
-        Ok(handle)
+        Ok(())
+    }
+
+    /// Start the Rope Distribution Protocol with a periodic stats report
+    fn start_rdp(&mut self, supervisor: &mut ServiceSupervisor, node_id: &NodeId) {
+        let rdp = Arc::new(RopeDistributionProtocol::new(
+            *node_id.as_bytes(),
+            RdpConfig::default(),
+        ));
+        self.rdp = Some(rdp.clone());
+
+        let mut shutdown = supervisor.shutdown_signal();
+        supervisor.spawn(NodeService::Rdp, async move {
+            let mut report = tokio::time::interval(Duration::from_secs(60));
+            while !*shutdown.borrow() {
+                tokio::select! {
+                    _ = report.tick() => {
+                        let stats = rdp.stats();
+                        tracing::debug!(
+                            "RDP: {} swarms, {} bytes down, {} bytes up",
+                            stats.active_swarms,
+                            stats.total_downloaded,
+                            stats.total_uploaded
+                        );
+                    }
+                    _ = shutdown.changed() => {}
+                }
+            }
+            Ok(())
+        });
     }
 
     /// Print startup banner with node information
-    fn print_startup_banner(&self) {
+    fn print_startup_banner(&self, plan: &ServicePlan) {
         tracing::info!("╔══════════════════════════════════════════════════════════════╗");
         tracing::info!("║              DATACHAIN ROPE NODE IS RUNNING                  ║");
         tracing::info!("╚══════════════════════════════════════════════════════════════╝");
@@ -338,20 +497,24 @@ impl RopeNode {
             self.config.network.bootstrap_nodes.len()
         );
 
-        if self.config.rpc.enabled {
+        if plan.contains(NodeService::Rpc) {
             tracing::info!("HTTP RPC: http://{}", self.config.rpc.http_addr);
             tracing::info!("gRPC: {}", self.config.rpc.grpc_addr);
             tracing::info!("WebSocket: ws://{}", self.config.rpc.ws_addr);
         }
 
-        if self.config.metrics.enabled {
+        if plan.contains(NodeService::Metrics) {
             tracing::info!(
                 "Metrics: http://{}/metrics",
                 self.config.metrics.prometheus_addr
             );
+            tracing::info!(
+                "Probes:  http://{0}/healthz, http://{0}/readyz",
+                self.config.metrics.prometheus_addr
+            );
         }
 
-        if self.config.consensus.enabled && matches!(self.config.node.mode, NodeMode::Validator) {
+        if plan.contains(NodeService::Consensus) {
             tracing::info!(
                 "String Production: ENABLED ({}ms interval)",
                 self.config.consensus.block_time_ms
@@ -374,57 +537,110 @@ impl RopeNode {
     }
 
     /// Initialize cryptography and return identity seed and node ID
+    ///
+    /// The node key lives in an encrypted keystore. A plaintext `node.key`
+    /// left by older versions is migrated into the keystore and deleted.
     async fn init_crypto(&self) -> anyhow::Result<([u8; 32], NodeId)> {
+        use rope_crypto::keys::KeyPair;
+        use rope_crypto::keystore::{KdfParams, KeyKind, KeystoreFile};
+
         tracing::info!("Initializing cryptography (OES with post-quantum support)...");
 
+        if let Some(seed) = self.key_seed {
+            let keystore = rope_crypto::keys::KeyStore::from_seed(seed);
+            return Ok(Self::identity(keystore.primary()));
+        }
+
         let keys_path = self.data_dir.join("keys");
         std::fs::create_dir_all(&keys_path)?;
 
-        // Load or generate keys
-        let node_key_path = keys_path.join("node.key");
-        let identity_seed: [u8; 32];
-        let node_id: NodeId;
+        let keystore_path = keys_path.join(&self.config.node.keystore.path);
+        let legacy_key_path = keys_path.join("node.key");
+        let credentials = self.keystore_credentials(&keys_path)?;
+
+        let keypair = if keystore_path.exists() {
+            KeystoreFile::load(&keystore_path)?.decrypt(&credentials)?
+        } else {
+            let keypair = if legacy_key_path.exists() {
+                tracing::warn!("Migrating plaintext node.key into encrypted keystore...");
+                Self::load_legacy_key(&legacy_key_path)?
+            } else {
+                tracing::info!("Generating node keys with hybrid post-quantum cryptography...");
+                KeyPair::generate_hybrid()?
+            };
 
-        if !node_key_path.exists() {
-            tracing::info!("Generating node keys with hybrid post-quantum cryptography...");
-            let keypair = rope_crypto::keys::KeyPair::generate_hybrid()?;
+            KeystoreFile::encrypt(
+                KeyKind::Validator,
+                &keypair,
+                &credentials,
+                KdfParams::recommended(),
+            )?
+            .save(&keystore_path)?;
 
-            // Save keys
-            let private_key_bytes = keypair.private_key_bytes();
-            std::fs::write(&node_key_path, &private_key_bytes)?;
+            if legacy_key_path.exists() {
+                std::fs::remove_file(&legacy_key_path)?;
+            }
             std::fs::write(keys_path.join("node.pub"), keypair.public_key_bytes())?;
             std::fs::write(keys_path.join("node.id"), hex::encode(keypair.node_id()))?;
+            tracing::info!("Keystore saved to {:?}", keystore_path);
 
-            // Use first 32 bytes of private key as identity seed for libp2p
-            identity_seed = private_key_bytes[..32]
-                .try_into()
-                .map_err(|_| anyhow::anyhow!("Failed to extract identity seed from keypair"))?;
+            keypair
+        };
 
-            node_id = NodeId::new(keypair.node_id());
+        Ok(Self::identity(&keypair))
+    }
 
-            tracing::info!("Node ID: {}", hex::encode(keypair.node_id()));
-            tracing::info!("Keys saved to {:?}", keys_path);
-        } else {
-            let private_key_bytes = std::fs::read(&node_key_path)?;
-            let id_bytes = std::fs::read(keys_path.join("node.id"))?;
-            let id_hex = String::from_utf8_lossy(&id_bytes);
-
-            // Extract identity seed from saved private key
-            identity_seed = private_key_bytes[..32]
-                .try_into()
-                .map_err(|_| anyhow::anyhow!("Invalid private key format"))?;
-
-            // Parse node ID from hex
-            let id_bytes = hex::decode(id_hex.trim())?;
-            let mut id_arr = [0u8; 32];
-            id_arr.copy_from_slice(&id_bytes[..32]);
-            node_id = NodeId::new(id_arr);
-
-            tracing::info!("Node ID: {}", id_hex.trim());
-        }
+    /// libp2p identity seed and node ID of `keypair`
+    fn identity(keypair: &rope_crypto::keys::KeyPair) -> ([u8; 32], NodeId) {
+        // Ed25519 secret doubles as the libp2p identity seed
+        let identity_seed = *keypair.signer().secret_key().ed25519_bytes();
+        let node_id = NodeId::new(keypair.node_id());
 
+        tracing::info!("Node ID: {}", hex::encode(keypair.node_id()));
         tracing::info!("Cryptography initialized (Ed25519 + Dilithium3 + Kyber768)");
-        Ok((identity_seed, node_id))
+        (identity_seed, node_id)
+    }
+
+    /// Build keystore credentials from the environment and config
+    fn keystore_credentials(
+        &self,
+        keys_path: &std::path::Path,
+    ) -> anyhow::Result<rope_crypto::keystore::Credentials> {
+        let settings = &self.config.node.keystore;
+        let passphrase = std::env::var(&settings.passphrase_env).map_err(|_| {
+            anyhow::anyhow!(
+                "Keystore passphrase not set; export {}",
+                settings.passphrase_env
+            )
+        })?;
+
+        let credentials = rope_crypto::keystore::Credentials::passphrase(&passphrase);
+        match &settings.keyfile {
+            Some(keyfile) => Ok(credentials.with_keyfile_path(keys_path.join(keyfile))?),
+            None => Ok(credentials),
+        }
+    }
+
+    /// Read a pre-keystore plaintext key (ed25519 || x25519 || dilithium)
+    fn load_legacy_key(path: &std::path::Path) -> anyhow::Result<rope_crypto::keys::KeyPair> {
+        let bytes = std::fs::read(path)?;
+        if bytes.len() < 64 {
+            anyhow::bail!("Invalid private key format");
+        }
+
+        let mut ed25519 = [0u8; 32];
+        let mut x25519 = [0u8; 32];
+        ed25519.copy_from_slice(&bytes[..32]);
+        x25519.copy_from_slice(&bytes[32..64]);
+
+        let secret = rope_crypto::hybrid::HybridSecretKey::new(
+            ed25519,
+            x25519,
+            bytes[64..].to_vec(),
+            Vec::new(),
+        );
+        let signer = rope_crypto::hybrid::HybridSigner::from_secret_key(&secret)?;
+        Ok(rope_crypto::keys::KeyPair::from_signer(signer))
     }
 
     /// Initialize networking with libp2p swarm
@@ -481,6 +697,15 @@ impl RopeNode {
                 request_timeout: Duration::from_secs(30),
                 max_concurrent_requests: 100,
             },
+            pubsub: PubsubConfig::default(),
+            nat: NatConfig {
+                enable_autonat: self.config.network.enable_nat,
+                enable_hole_punching: self.config.network.enable_nat,
+                relays: self.config.network.relays.clone(),
+                act_as_relay: matches!(self.config.node.mode, NodeMode::Relay),
+                ..NatConfig::default()
+            },
+            compression: CompressionConfig::default(),
             identity_seed: Some(identity_seed),
         };
 
@@ -541,14 +766,19 @@ impl RopeNode {
     }
 
     /// Start network event processor
-    fn start_network_event_processor(&self) -> Option<tokio::task::JoinHandle<()>> {
-        let event_rx = self.network_event_rx.write().take()?;
+    fn start_network_event_processor(&self, supervisor: &mut ServiceSupervisor) {
+        let Some(event_rx) = self.network_event_rx.write().take() else {
+            return;
+        };
         let state = self.state.clone();
         let current_round = self.current_round.clone();
+        let health = self.health.clone();
+        let schedule = self.schedule.clone();
 
-        Some(tokio::spawn(async move {
-            Self::process_network_events(event_rx, state, current_round).await;
-        }))
+        supervisor.spawn_until_shutdown(NodeService::Network, async move {
+            Self::process_network_events(event_rx, state, current_round, health, schedule).await;
+            Ok(())
+        });
     }
 
     /// Process network events from the swarm
@@ -556,6 +786,8 @@ impl RopeNode {
         mut event_rx: broadcast::Receiver<SwarmNetworkEvent>,
         state: Arc<RwLock<NodeState>>,
         current_round: Arc<RwLock<u64>>,
+        health: Option<Arc<HealthState>>,
+        schedule: Option<Arc<ProducerSchedule>>,
     ) {
         loop {
             // Check if we should stop
@@ -568,9 +800,15 @@ impl RopeNode {
                     match event {
                         SwarmNetworkEvent::PeerConnected { peer_id } => {
                             tracing::info!("Peer connected: {}", peer_id);
+                            if let Some(health) = &health {
+                                health.peer_connected();
+                            }
                         }
                         SwarmNetworkEvent::PeerDisconnected { peer_id } => {
                             tracing::info!("Peer disconnected: {}", peer_id);
+                            if let Some(health) = &health {
+                                health.peer_disconnected();
+                            }
                         }
                         SwarmNetworkEvent::GossipMessage {
                             topic,
@@ -584,8 +822,14 @@ impl RopeNode {
                                 data.len()
                             );
                             // Process message based on topic
-                            Self::handle_gossip_message(&topic, &data, &source, &current_round)
-                                .await;
+                            Self::handle_gossip_message(
+                                &topic,
+                                &data,
+                                &source,
+                                &current_round,
+                                schedule.as_deref(),
+                            )
+                            .await;
                         }
                         SwarmNetworkEvent::DhtRecordFound { key, value } => {
                             tracing::debug!(
@@ -621,6 +865,7 @@ impl RopeNode {
         data: &[u8],
         source: &libp2p::PeerId,
         current_round: &Arc<RwLock<u64>>,
+        schedule: Option<&ProducerSchedule>,
     ) {
         match topic {
             "/rope/strings/1.0.0" => {
@@ -639,6 +884,26 @@ impl RopeNode {
                         let parts: Vec<&str> = msg.split(':').collect();
                         if parts.len() >= 2 {
                             if let Ok(round) = parts[1].parse::<u64>() {
+                                // A scheduled round needs the producer's claim
+                                if let Some(schedule) = schedule {
+                                    let claim = Self::parse_claim(round, &parts);
+                                    let accepted = match &claim {
+                                        Some(claim) => {
+                                            schedule.on_anchor(claim).map_err(|e| e.to_string())
+                                        }
+                                        None => Err("missing producer claim".to_string()),
+                                    };
+                                    if let Err(e) = accepted {
+                                        tracing::warn!(
+                                            "Rejected anchor #{} from {}: {}",
+                                            round,
+                                            source,
+                                            e
+                                        );
+                                        return;
+                                    }
+                                }
+
                                 let local_round = *current_round.read();
                                 if round > local_round {
                                     tracing::info!(
@@ -647,6 +912,10 @@ impl RopeNode {
                                         source,
                                         local_round
                                     );
+                                    // A claimed anchor closes the round for us too
+                                    if schedule.is_some() {
+                                        *current_round.write() = round;
+                                    }
                                     // In a full implementation, we'd sync here
                                 }
                             }
@@ -660,6 +929,17 @@ impl RopeNode {
         }
     }
 
+    /// Producer claim in `anchor:<round>:<id>:<producer>:<proof>` parts
+    fn parse_claim(round: u64, parts: &[&str]) -> Option<ProducerClaim> {
+        let producer = hex::decode(parts.get(3)?).ok()?.try_into().ok()?;
+        let proof = VrfProof::from_bytes(&hex::decode(parts.get(4)?).ok()?).ok()?;
+        Some(ProducerClaim {
+            round,
+            producer,
+            proof,
+        })
+    }
+
     /// Stop the network
     async fn stop_network(&mut self) -> anyhow::Result<()> {
         if let Some(mut swarm) = self.swarm_runtime.write().take() {
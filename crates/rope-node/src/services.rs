@@ -0,0 +1,325 @@
+//! Role-based service wiring
+//!
+//! The services a node runs follow from its [`NodeMode`] and config. They
+//! start in dependency order under a [`ServiceSupervisor`], which notices
+//! services that exit early and stops the rest in reverse order.
+
+use crate::config::{NodeConfig, NodeMode};
+use std::future::Future;
+use std::time::Duration;
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+
+/// A long-running node service
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum NodeService {
+    /// Lattice, complement and gossip stores
+    Storage,
+    /// libp2p swarm and network event processing
+    Network,
+    /// String production and finality
+    Consensus,
+    /// Rope Distribution Protocol (piece swarms)
+    Rdp,
+    /// JSON-RPC / gRPC server
+    Rpc,
+    /// Prometheus exporter
+    Metrics,
+}
+
+impl NodeService {
+    /// Every service, in canonical order
+    pub const ALL: [NodeService; 6] = [
+        NodeService::Storage,
+        NodeService::Network,
+        NodeService::Consensus,
+        NodeService::Rdp,
+        NodeService::Rpc,
+        NodeService::Metrics,
+    ];
+
+    /// Services that must be running before this one starts
+    pub fn dependencies(&self) -> &'static [NodeService] {
+        match self {
+            NodeService::Storage | NodeService::Metrics => &[],
+            NodeService::Network => &[NodeService::Storage],
+            NodeService::Consensus | NodeService::Rdp => {
+                &[NodeService::Storage, NodeService::Network]
+            }
+            NodeService::Rpc => &[NodeService::Storage],
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            NodeService::Storage => "storage",
+            NodeService::Network => "network",
+            NodeService::Consensus => "consensus",
+            NodeService::Rdp => "rdp",
+            NodeService::Rpc => "rpc",
+            NodeService::Metrics => "metrics",
+        }
+    }
+}
+
+impl std::fmt::Display for NodeService {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Services to run, in startup order
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ServicePlan {
+    services: Vec<NodeService>,
+}
+
+impl ServicePlan {
+    /// Services for the node's role
+    ///
+    /// - Validator: storage, network, RDP and consensus (when enabled)
+    /// - Relay and Seeder: storage, network and RDP
+    /// - Light: storage and network only
+    ///
+    /// RPC and metrics follow their config switches, except that seeders
+    /// never serve RPC. Dependencies are pulled in automatically.
+    pub fn for_config(config: &NodeConfig) -> Self {
+        let mut wanted = vec![NodeService::Storage, NodeService::Network];
+        match config.node.mode {
+            NodeMode::Validator => {
+                wanted.push(NodeService::Rdp);
+                if config.consensus.enabled {
+                    wanted.push(NodeService::Consensus);
+                }
+            }
+            NodeMode::Relay | NodeMode::Seeder => wanted.push(NodeService::Rdp),
+            NodeMode::Light => {}
+        }
+        if config.rpc.enabled && config.node.mode != NodeMode::Seeder {
+            wanted.push(NodeService::Rpc);
+        }
+        if config.metrics.enabled {
+            wanted.push(NodeService::Metrics);
+        }
+        Self::with_services(&wanted)
+    }
+
+    /// Plan for `services` and their dependencies, ordered so every
+    /// service starts after what it depends on
+    pub fn with_services(services: &[NodeService]) -> Self {
+        let mut ordered = Vec::new();
+        for service in NodeService::ALL {
+            if services.contains(&service) {
+                Self::visit(service, &mut ordered);
+            }
+        }
+        Self { services: ordered }
+    }
+
+    fn visit(service: NodeService, ordered: &mut Vec<NodeService>) {
+        if ordered.contains(&service) {
+            return;
+        }
+        for dependency in service.dependencies() {
+            Self::visit(*dependency, ordered);
+        }
+        ordered.push(service);
+    }
+
+    pub fn contains(&self, service: NodeService) -> bool {
+        self.services.contains(&service)
+    }
+
+    pub fn startup_order(&self) -> &[NodeService] {
+        &self.services
+    }
+
+    pub fn shutdown_order(&self) -> impl Iterator<Item = NodeService> + '_ {
+        self.services.iter().rev().copied()
+    }
+}
+
+/// Supervises service tasks and shuts them down in reverse start order
+pub struct ServiceSupervisor {
+    shutdown_tx: watch::Sender<bool>,
+    tasks: Vec<(NodeService, JoinHandle<anyhow::Result<()>>)>,
+}
+
+impl ServiceSupervisor {
+    pub fn new() -> Self {
+        let (shutdown_tx, _) = watch::channel(false);
+        Self {
+            shutdown_tx,
+            tasks: Vec::new(),
+        }
+    }
+
+    /// Receiver that flips to `true` when shutdown begins
+    pub fn shutdown_signal(&self) -> watch::Receiver<bool> {
+        self.shutdown_tx.subscribe()
+    }
+
+    /// Spawn a task that stops on its own when shutdown is signalled
+    pub fn spawn<F>(&mut self, service: NodeService, task: F)
+    where
+        F: Future<Output = anyhow::Result<()>> + Send + 'static,
+    {
+        tracing::info!("Starting service: {}", service);
+        self.tasks.push((service, tokio::spawn(task)));
+    }
+
+    /// Spawn a task that is cancelled when shutdown is signalled
+    pub fn spawn_until_shutdown<F>(&mut self, service: NodeService, task: F)
+    where
+        F: Future<Output = anyhow::Result<()>> + Send + 'static,
+    {
+        let mut shutdown = self.shutdown_signal();
+        self.spawn(service, async move {
+            tokio::select! {
+                result = task => result,
+                _ = shutdown.wait_for(|stop| *stop) => Ok(()),
+            }
+        });
+    }
+
+    /// Services with a live task, in start order
+    pub fn running(&self) -> Vec<NodeService> {
+        self.tasks.iter().map(|(service, _)| *service).collect()
+    }
+
+    /// Wait for a service task to exit before shutdown; pending forever
+    /// when nothing is supervised
+    pub async fn next_exit(&mut self) -> (NodeService, anyhow::Result<()>) {
+        if self.tasks.is_empty() {
+            return std::future::pending().await;
+        }
+        let (result, index, _) =
+            futures::future::select_all(self.tasks.iter_mut().map(|(_, handle)| handle)).await;
+        let (service, _) = self.tasks.remove(index);
+        (service, Self::flatten(result))
+    }
+
+    /// Signal shutdown, then wait for each service in reverse start order,
+    /// aborting any that outlive `grace`
+    pub async fn shutdown(mut self, grace: Duration) {
+        self.shutdown_tx.send_replace(true);
+        while let Some((service, handle)) = self.tasks.pop() {
+            let abort = handle.abort_handle();
+            match tokio::time::timeout(grace, handle).await {
+                Ok(result) => match Self::flatten(result) {
+                    Ok(()) => tracing::info!("Stopped service: {}", service),
+                    Err(e) => tracing::warn!("Service {} stopped with error: {}", service, e),
+                },
+                Err(_) => {
+                    abort.abort();
+                    tracing::warn!("Service {} did not stop in {:?}; aborted", service, grace);
+                }
+            }
+        }
+    }
+
+    fn flatten(result: Result<anyhow::Result<()>, tokio::task::JoinError>) -> anyhow::Result<()> {
+        result.unwrap_or_else(|e| Err(anyhow::anyhow!("task failed: {}", e)))
+    }
+}
+
+impl Default for ServiceSupervisor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(mode: NodeMode) -> NodeConfig {
+        let mut config = NodeConfig::testnet();
+        config.node.mode = mode;
+        config
+    }
+
+    #[test]
+    fn test_plan_by_role() {
+        let validator = ServicePlan::for_config(&config(NodeMode::Validator));
+        assert_eq!(
+            validator.startup_order(),
+            &[
+                NodeService::Storage,
+                NodeService::Network,
+                NodeService::Consensus,
+                NodeService::Rdp,
+                NodeService::Rpc,
+                NodeService::Metrics,
+            ]
+        );
+
+        let relay = ServicePlan::for_config(&config(NodeMode::Relay));
+        assert!(!relay.contains(NodeService::Consensus));
+        assert!(relay.contains(NodeService::Rdp));
+        assert!(relay.contains(NodeService::Rpc));
+
+        let seeder = ServicePlan::for_config(&config(NodeMode::Seeder));
+        assert!(!seeder.contains(NodeService::Rpc));
+        assert!(seeder.contains(NodeService::Rdp));
+
+        let mut quiet = config(NodeMode::Validator);
+        quiet.consensus.enabled = false;
+        quiet.rpc.enabled = false;
+        quiet.metrics.enabled = false;
+        assert_eq!(
+            ServicePlan::for_config(&quiet).startup_order(),
+            &[NodeService::Storage, NodeService::Network, NodeService::Rdp]
+        );
+    }
+
+    #[test]
+    fn test_dependencies_start_first() {
+        let plan = ServicePlan::with_services(&[NodeService::Rpc, NodeService::Consensus]);
+        assert_eq!(
+            plan.startup_order(),
+            &[
+                NodeService::Storage,
+                NodeService::Network,
+                NodeService::Consensus,
+                NodeService::Rpc,
+            ]
+        );
+        for (i, service) in plan.startup_order().iter().enumerate() {
+            for dependency in service.dependencies() {
+                assert!(plan.startup_order()[..i].contains(dependency));
+            }
+        }
+        let shutdown: Vec<_> = plan.shutdown_order().collect();
+        assert_eq!(shutdown.first(), Some(&NodeService::Rpc));
+        assert_eq!(shutdown.last(), Some(&NodeService::Storage));
+    }
+
+    #[tokio::test]
+    async fn test_supervisor_reports_exit_and_shuts_down() {
+        let mut supervisor = ServiceSupervisor::new();
+        supervisor.spawn_until_shutdown(NodeService::Rpc, std::future::pending());
+        supervisor.spawn(NodeService::Metrics, async {
+            Err(anyhow::anyhow!("bind failed"))
+        });
+
+        let (service, result) = supervisor.next_exit().await;
+        assert_eq!(service, NodeService::Metrics);
+        assert!(result.is_err());
+        assert_eq!(supervisor.running(), vec![NodeService::Rpc]);
+
+        // A service that ignores the signal is aborted after the grace period
+        let mut stop = supervisor.shutdown_signal();
+        supervisor.spawn(NodeService::Network, async move {
+            let _ = stop.wait_for(|s| *s).await;
+            Ok(())
+        });
+        supervisor.spawn(NodeService::Rdp, std::future::pending());
+        tokio::time::timeout(
+            Duration::from_secs(5),
+            supervisor.shutdown(Duration::from_millis(50)),
+        )
+        .await
+        .unwrap();
+    }
+}
@@ -0,0 +1,273 @@
+//! Data directory snapshots
+//!
+//! A snapshot packs a node's data directory (genesis and the `db/` tree)
+//! into a single file so another node can start from the same state. The
+//! `keys/` folder is never included; node identities move separately.
+//!
+//! Layout: `ROPESNAP | version | count | entries | blake3(all preceding)`,
+//! where each entry is `path_len(u16) | path | len(u64) | blake3 | data`.
+
+use std::path::{Component, Path, PathBuf};
+
+const MAGIC: &[u8; 8] = b"ROPESNAP";
+const VERSION: u32 = 1;
+
+/// Top-level entries of the data directory left out of snapshots
+const EXCLUDED: &[&str] = &["keys"];
+
+/// What a snapshot export or import covered
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SnapshotSummary {
+    /// Number of files in the snapshot
+    pub files: usize,
+    /// Total size of file contents
+    pub bytes: u64,
+    /// BLAKE3 digest over the whole snapshot
+    pub digest: [u8; 32],
+}
+
+/// Write a snapshot of `data_dir` to `output`
+pub fn export(data_dir: &Path, output: &Path) -> anyhow::Result<SnapshotSummary> {
+    let (buf, summary) = encode(data_dir, Some(output))?;
+    std::fs::write(output, &buf)?;
+    Ok(summary)
+}
+
+/// Snapshot of `data_dir` held in memory, e.g. for seeding over RDP
+pub fn export_bytes(data_dir: &Path) -> anyhow::Result<(Vec<u8>, SnapshotSummary)> {
+    encode(data_dir, None)
+}
+
+fn encode(data_dir: &Path, output: Option<&Path>) -> anyhow::Result<(Vec<u8>, SnapshotSummary)> {
+    let mut files = Vec::new();
+    collect_files(data_dir, data_dir, output, &mut files)?;
+    files.sort();
+
+    let mut buf = Vec::new();
+    buf.extend_from_slice(MAGIC);
+    buf.extend_from_slice(&VERSION.to_le_bytes());
+    buf.extend_from_slice(&(files.len() as u32).to_le_bytes());
+
+    let mut bytes = 0u64;
+    for relative in &files {
+        let data = std::fs::read(data_dir.join(relative))?;
+        let name = relative
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        buf.extend_from_slice(&(name.len() as u16).to_le_bytes());
+        buf.extend_from_slice(name.as_bytes());
+        buf.extend_from_slice(&(data.len() as u64).to_le_bytes());
+        buf.extend_from_slice(blake3::hash(&data).as_bytes());
+        buf.extend_from_slice(&data);
+        bytes += data.len() as u64;
+    }
+
+    let digest = *blake3::hash(&buf).as_bytes();
+    buf.extend_from_slice(&digest);
+
+    let summary = SnapshotSummary {
+        files: files.len(),
+        bytes,
+        digest,
+    };
+    Ok((buf, summary))
+}
+
+/// Restore the snapshot at `input` into `data_dir`
+///
+/// Every entry is checked before anything is written. Existing files are
+/// only replaced when `overwrite` is set.
+pub fn import(input: &Path, data_dir: &Path, overwrite: bool) -> anyhow::Result<SnapshotSummary> {
+    let buf = std::fs::read(input)?;
+    import_bytes(&buf, data_dir, overwrite)
+        .map_err(|e| e.context(format!("Importing snapshot {:?}", input)))
+}
+
+/// Restore a snapshot held in memory into `data_dir`
+pub fn import_bytes(
+    buf: &[u8],
+    data_dir: &Path,
+    overwrite: bool,
+) -> anyhow::Result<SnapshotSummary> {
+    if buf.len() < MAGIC.len() + 8 + 32 || &buf[..MAGIC.len()] != MAGIC {
+        anyhow::bail!("Not a Rope snapshot");
+    }
+
+    let (body, trailer) = buf.split_at(buf.len() - 32);
+    let digest = *blake3::hash(body).as_bytes();
+    if digest[..] != trailer[..] {
+        anyhow::bail!("Snapshot digest mismatch");
+    }
+
+    let mut reader = Reader {
+        buf: body,
+        pos: MAGIC.len(),
+    };
+    let version = u32::from_le_bytes(reader.take(4)?.try_into()?);
+    if version != VERSION {
+        anyhow::bail!("Unsupported snapshot version {}", version);
+    }
+    let count = u32::from_le_bytes(reader.take(4)?.try_into()?) as usize;
+
+    let mut entries = Vec::with_capacity(count);
+    for _ in 0..count {
+        let name_len = u16::from_le_bytes(reader.take(2)?.try_into()?) as usize;
+        let name = std::str::from_utf8(reader.take(name_len)?)?;
+        let relative = safe_relative_path(name)?;
+        let len = u64::from_le_bytes(reader.take(8)?.try_into()?) as usize;
+        let hash = reader.take(32)?;
+        let data = reader.take(len)?;
+        if blake3::hash(data).as_bytes()[..] != hash[..] {
+            anyhow::bail!("Snapshot entry {} is corrupt", name);
+        }
+        if !overwrite && data_dir.join(&relative).exists() {
+            anyhow::bail!("{} already exists in {:?}", name, data_dir);
+        }
+        entries.push((relative, data));
+    }
+    if reader.pos != body.len() {
+        anyhow::bail!("Trailing data after snapshot entries");
+    }
+
+    let mut bytes = 0u64;
+    for (relative, data) in &entries {
+        let target = data_dir.join(relative);
+        if let Some(parent) = target.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&target, data)?;
+        bytes += data.len() as u64;
+    }
+
+    Ok(SnapshotSummary {
+        files: entries.len(),
+        bytes,
+        digest,
+    })
+}
+
+fn collect_files(
+    root: &Path,
+    dir: &Path,
+    output: Option<&Path>,
+    files: &mut Vec<PathBuf>,
+) -> anyhow::Result<()> {
+    if !dir.exists() {
+        return Ok(());
+    }
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        let relative = path.strip_prefix(root)?.to_path_buf();
+        if dir == root
+            && EXCLUDED
+                .iter()
+                .any(|excluded| relative == Path::new(excluded))
+        {
+            continue;
+        }
+        if Some(path.as_path()) == output {
+            continue;
+        }
+
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            collect_files(root, &path, output, files)?;
+        } else if file_type.is_file() {
+            files.push(relative);
+        }
+    }
+    Ok(())
+}
+
+/// Reject absolute paths, `..` and excluded folders in snapshot entries
+fn safe_relative_path(name: &str) -> anyhow::Result<PathBuf> {
+    let path = PathBuf::from(name);
+    let mut components = path.components().peekable();
+    match components.peek() {
+        Some(Component::Normal(first)) if !EXCLUDED.iter().any(|excluded| first == excluded) => {}
+        _ => anyhow::bail!("Invalid snapshot entry path: {}", name),
+    }
+    if !components.all(|c| matches!(c, Component::Normal(_))) {
+        anyhow::bail!("Invalid snapshot entry path: {}", name);
+    }
+    Ok(path)
+}
+
+struct Reader<'a> {
+    buf: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> anyhow::Result<&'a [u8]> {
+        let end = self
+            .pos
+            .checked_add(len)
+            .filter(|end| *end <= self.buf.len())
+            .ok_or_else(|| anyhow::anyhow!("Snapshot is truncated"))?;
+        let slice = &self.buf[self.pos..end];
+        self.pos = end;
+        Ok(slice)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snapshot_roundtrip_skips_keys() {
+        let source = tempfile::tempdir().unwrap();
+        std::fs::write(source.path().join("genesis.json"), b"{\"chain_id\":1}").unwrap();
+        std::fs::create_dir_all(source.path().join("db/lattice")).unwrap();
+        std::fs::write(source.path().join("db/lattice/000001.sst"), [7u8; 64]).unwrap();
+        std::fs::create_dir_all(source.path().join("keys")).unwrap();
+        std::fs::write(source.path().join("keys/node.keystore.json"), b"secret").unwrap();
+
+        let out = tempfile::tempdir().unwrap();
+        let snapshot = out.path().join("state.snap");
+        let exported = export(source.path(), &snapshot).unwrap();
+        assert_eq!(exported.files, 2);
+        assert_eq!(exported.bytes, 14 + 64);
+
+        let target = tempfile::tempdir().unwrap();
+        let imported = import(&snapshot, target.path(), false).unwrap();
+        assert_eq!(imported, exported);
+        assert_eq!(
+            std::fs::read(target.path().join("db/lattice/000001.sst")).unwrap(),
+            vec![7u8; 64]
+        );
+        assert!(!target.path().join("keys").exists());
+
+        // Existing state is kept unless overwriting is requested
+        assert!(import(&snapshot, target.path(), false).is_err());
+        assert!(import(&snapshot, target.path(), true).is_ok());
+    }
+
+    #[test]
+    fn test_snapshot_rejects_tampering() {
+        let source = tempfile::tempdir().unwrap();
+        std::fs::write(source.path().join("genesis.json"), b"genesis").unwrap();
+
+        let out = tempfile::tempdir().unwrap();
+        let snapshot = out.path().join("state.snap");
+        export(source.path(), &snapshot).unwrap();
+
+        let mut bytes = std::fs::read(&snapshot).unwrap();
+        let last_data = bytes.len() - 33;
+        bytes[last_data] ^= 0xff;
+        std::fs::write(&snapshot, &bytes).unwrap();
+
+        let target = tempfile::tempdir().unwrap();
+        assert!(import(&snapshot, target.path(), false).is_err());
+        assert!(!target.path().join("genesis.json").exists());
+
+        assert!(safe_relative_path("../etc/passwd").is_err());
+        assert!(safe_relative_path("/etc/passwd").is_err());
+        assert!(safe_relative_path("keys/node.keystore.json").is_err());
+        assert!(safe_relative_path("db/lattice/000001.sst").is_ok());
+    }
+}
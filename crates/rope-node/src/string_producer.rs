@@ -4,9 +4,13 @@
 //! This is the equivalent of "block production" in traditional blockchains.
 
 use parking_lot::RwLock;
+use rope_consensus::{ProducerClaim, ProducerSchedule};
 use rope_core::clock::LamportClock;
 use rope_core::string::{HybridSignature, PublicKey, RopeString};
 use rope_core::types::{MutabilityClass, NodeId, StringId};
+use rope_crypto::vrf::VrfSecretKey;
+use rope_economics::{AnchorFees, EmissionSchedule, FeeMarket};
+use rope_federation::{Payout, Treasury};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::sync::{broadcast, mpsc};
@@ -48,6 +52,14 @@ pub struct ProductionStats {
     pub anchors_produced: u64,
     /// Current round
     pub current_round: u64,
+    /// Anchor rewards emitted (smallest FAT units)
+    pub rewards_emitted: u128,
+    /// Priority fees earned by this producer
+    pub tips_earned: u128,
+    /// Base fees burned by produced anchors
+    pub fees_burned: u128,
+    /// Emission and fees set aside for the treasury
+    pub treasury_accrued: u128,
     /// Average production time (ms)
     pub avg_production_time_ms: f64,
     /// Last production timestamp
@@ -68,11 +80,55 @@ pub enum ProductionEvent {
         anchor_id: StringId,
         round: u64,
         strings_included: usize,
+        /// Emission reward minted by the anchor (smallest FAT units)
+        reward: u128,
+        /// Transaction fees collected by the anchor
+        fees: AnchorFees,
+        /// VRF claim on the round, when production follows a schedule
+        claim: Option<ProducerClaim>,
     },
+    /// Vested grant paid from the treasury
+    GrantPaid { round: u64, payout: Payout },
     /// Production error
     ProductionError { round: u64, error: String },
 }
 
+/// Pending pool and event stream of a producer
+///
+/// A node creates its producer only once it starts, so callers that need to
+/// submit strings or follow anchors before then share this handle with it.
+#[derive(Clone, Debug)]
+pub struct ProducerHandle {
+    pending_strings: Arc<RwLock<Vec<RopeString>>>,
+    event_tx: broadcast::Sender<ProductionEvent>,
+}
+
+impl ProducerHandle {
+    pub fn new() -> Self {
+        let (event_tx, _) = broadcast::channel(1000);
+        Self {
+            pending_strings: Arc::new(RwLock::new(Vec::new())),
+            event_tx,
+        }
+    }
+
+    /// Queue a string for the next anchor
+    pub fn add_pending_string(&self, string: RopeString) {
+        self.pending_strings.write().push(string);
+    }
+
+    /// Get event receiver
+    pub fn subscribe(&self) -> broadcast::Receiver<ProductionEvent> {
+        self.event_tx.subscribe()
+    }
+}
+
+impl Default for ProducerHandle {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// The String Producer - heart of the consensus engine
 pub struct StringProducer {
     config: StringProducerConfig,
@@ -89,26 +145,76 @@ pub struct StringProducer {
     genesis_string_id: Option<StringId>,
     /// Lamport clock for ordering
     clock: Arc<RwLock<LamportClock>>,
+    /// Anchor reward schedule
+    emission: EmissionSchedule,
+    /// Base fee and fees collected for the anchor being filled
+    fees: Arc<RwLock<FeeMarket>>,
+    /// Treasury funded by each anchor
+    treasury: Arc<RwLock<Treasury>>,
+    /// Producer schedule and the VRF key this node claims its rounds with
+    schedule: Option<(Arc<ProducerSchedule>, VrfSecretKey)>,
 }
 
 impl StringProducer {
     /// Create a new string producer
     pub fn new(config: StringProducerConfig, node_id: NodeId) -> Self {
-        let (event_tx, _) = broadcast::channel(1000);
+        Self::with_handle(config, node_id, ProducerHandle::new())
+    }
 
+    /// Create a producer that drains and reports through `handle`
+    pub fn with_handle(
+        config: StringProducerConfig,
+        node_id: NodeId,
+        handle: ProducerHandle,
+    ) -> Self {
         Self {
             config,
             node_id: node_id.clone(),
             stats: Arc::new(RwLock::new(ProductionStats::default())),
-            event_tx,
+            event_tx: handle.event_tx,
             current_round: Arc::new(RwLock::new(0)),
-            pending_strings: Arc::new(RwLock::new(Vec::new())),
+            pending_strings: handle.pending_strings,
             last_anchor_id: Arc::new(RwLock::new(None)),
             genesis_string_id: None,
             clock: Arc::new(RwLock::new(LamportClock::new(node_id))),
+            emission: EmissionSchedule::mainnet(),
+            fees: Arc::new(RwLock::new(FeeMarket::default())),
+            treasury: Arc::new(RwLock::new(Treasury::default())),
+            schedule: None,
+        }
+    }
+
+    /// Produce only the rounds `schedule` selects this node for, claiming
+    /// each with `vrf_key`
+    ///
+    /// Without a schedule the producer anchors every round.
+    pub fn set_schedule(&mut self, schedule: Arc<ProducerSchedule>, vrf_key: VrfSecretKey) {
+        self.schedule = Some((schedule, vrf_key));
+    }
+
+    /// Whether the next round is ours to produce
+    pub fn is_our_turn(&self) -> bool {
+        match &self.schedule {
+            Some((schedule, _)) => schedule.is_our_turn(self.node_id.as_bytes()),
+            None => true,
         }
     }
 
+    /// Set the emission schedule (defaults to mainnet)
+    pub fn set_emission(&mut self, emission: EmissionSchedule) {
+        self.emission = emission;
+    }
+
+    /// Fee market charged by transaction execution
+    pub fn fee_market(&self) -> Arc<RwLock<FeeMarket>> {
+        self.fees.clone()
+    }
+
+    /// Treasury, for governance to fund grants from
+    pub fn treasury(&self) -> Arc<RwLock<Treasury>> {
+        self.treasury.clone()
+    }
+
     /// Set genesis string ID
     pub fn set_genesis(&mut self, genesis_id: StringId) {
         self.genesis_string_id = Some(genesis_id);
@@ -170,6 +276,11 @@ impl StringProducer {
                 _ = tokio::time::sleep(interval.saturating_sub(last_production.elapsed())) => {
                     let start = Instant::now();
 
+                    if !self.is_our_turn() {
+                        last_production = Instant::now();
+                        continue;
+                    }
+
                     match self.produce_anchor() {
                         Ok(anchor_id) => {
                             let production_time = start.elapsed();
@@ -202,18 +313,22 @@ impl StringProducer {
 
     /// Produce an anchor string
     fn produce_anchor(&self) -> anyhow::Result<StringId> {
+        // Claim the round first; the schedule refuses it unless it is ours
+        let claim = match &self.schedule {
+            Some((schedule, vrf_key)) => {
+                let claim = schedule.claim(*self.node_id.as_bytes(), vrf_key);
+                schedule.on_anchor(&claim)?;
+                Some(claim)
+            }
+            None => None,
+        };
+
         let current_round = {
             let mut round = self.current_round.write();
-            *round += 1;
+            *round = claim.as_ref().map(|c| c.round).unwrap_or(*round + 1);
             *round
         };
 
-        // Get parent (last anchor or genesis)
-        let parent_id = self
-            .last_anchor_id
-            .read()
-            .unwrap_or_else(|| self.genesis_string_id.unwrap_or(StringId::ZERO));
-
         // Collect pending strings
         let pending = {
             let mut pending = self.pending_strings.write();
@@ -221,8 +336,29 @@ impl StringProducer {
         };
         let pending_count = pending.len();
 
+        // Reward minted by this anchor
+        let timestamp = chrono::Utc::now().timestamp();
+        let reward = self.emission.reward_at(timestamp);
+
+        // Close the anchor's fees; tips go to this producer
+        let fees = self.fees.write().close_anchor(current_round);
+
+        // Treasury share, then grant payouts vested by now
+        let (treasury_share, payouts) = {
+            let mut treasury = self.treasury.write();
+            let share = treasury.accrue(reward, fees.validator_pool);
+            (share, treasury.process_payouts(timestamp.max(0) as u64))
+        };
+
         // Create anchor string
-        let anchor = self.create_anchor_string(current_round, parent_id, &pending)?;
+        let anchor = self.create_anchor_string(
+            current_round,
+            &pending,
+            timestamp,
+            reward,
+            fees.base_fee,
+            claim.as_ref(),
+        )?;
         let anchor_id = anchor.id();
 
         // Update last anchor
@@ -234,7 +370,11 @@ impl StringProducer {
             stats.anchors_produced += 1;
             stats.strings_produced += 1 + pending_count as u64;
             stats.current_round = current_round;
-            stats.last_production = Some(chrono::Utc::now().timestamp());
+            stats.rewards_emitted += reward;
+            stats.tips_earned += fees.tips;
+            stats.fees_burned += fees.burned;
+            stats.treasury_accrued += treasury_share;
+            stats.last_production = Some(timestamp);
         }
 
         // Emit event
@@ -242,7 +382,16 @@ impl StringProducer {
             anchor_id,
             round: current_round,
             strings_included: pending_count,
+            reward,
+            fees,
+            claim,
         });
+        for payout in payouts {
+            let _ = self.event_tx.send(ProductionEvent::GrantPaid {
+                round: current_round,
+                payout,
+            });
+        }
 
         Ok(anchor_id)
     }
@@ -251,9 +400,18 @@ impl StringProducer {
     fn create_anchor_string(
         &self,
         round: u64,
-        parent: StringId,
         pending: &[RopeString],
+        timestamp: i64,
+        reward: u128,
+        base_fee: u128,
+        claim: Option<&ProducerClaim>,
     ) -> anyhow::Result<RopeString> {
+        // Get parent (last anchor or genesis)
+        let parent = self
+            .last_anchor_id
+            .read()
+            .unwrap_or_else(|| self.genesis_string_id.unwrap_or(StringId::ZERO));
+
         // Create anchor payload
         let mut payload = Vec::new();
 
@@ -264,12 +422,17 @@ impl StringProducer {
         payload.extend_from_slice(&round.to_le_bytes());
 
         // Timestamp
-        let timestamp = chrono::Utc::now().timestamp();
         payload.extend_from_slice(&timestamp.to_le_bytes());
 
         // Number of included strings
         payload.extend_from_slice(&(pending.len() as u32).to_le_bytes());
 
+        // Emission reward for the round
+        payload.extend_from_slice(&reward.to_le_bytes());
+
+        // Base fee the included transactions paid
+        payload.extend_from_slice(&base_fee.to_le_bytes());
+
         // Merkle root of included strings (simplified: just hash all IDs)
         let mut merkle_input = Vec::new();
         for s in pending {
@@ -281,6 +444,12 @@ impl StringProducer {
         let merkle_root = blake3::hash(&merkle_input);
         payload.extend_from_slice(merkle_root.as_bytes());
 
+        // Producer claim: producer ID and VRF proof
+        if let Some(claim) = claim {
+            payload.extend_from_slice(&claim.producer);
+            payload.extend_from_slice(claim.proof.as_bytes());
+        }
+
         // Get next clock tick
         let clock = {
             let mut c = self.clock.write();
@@ -331,4 +500,96 @@ mod tests {
 
         assert_eq!(producer.current_round(), 0);
     }
+
+    #[test]
+    fn test_anchor_carries_emission_reward() {
+        let mut producer =
+            StringProducer::new(StringProducerConfig::default(), NodeId::new([1u8; 32]));
+        let genesis = chrono::Utc::now().timestamp() - 60;
+        let emission = EmissionSchedule::new(genesis);
+        let expected = emission.reward_at(genesis + 60);
+        producer.set_emission(emission);
+        let mut events = producer.subscribe();
+
+        producer.produce_anchor().unwrap();
+
+        match events.try_recv().unwrap() {
+            ProductionEvent::AnchorFinalized { round, reward, .. } => {
+                assert_eq!(round, 1);
+                assert_eq!(reward, expected);
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+        assert_eq!(producer.stats().rewards_emitted, expected);
+        assert_eq!(producer.treasury().read().balance(), expected / 20);
+        assert_eq!(producer.stats().treasury_accrued, expected / 20);
+
+        // No emission before genesis
+        producer.set_emission(EmissionSchedule::new(i64::MAX));
+        producer.produce_anchor().unwrap();
+        assert_eq!(producer.stats().rewards_emitted, expected);
+    }
+
+    #[test]
+    fn test_anchor_pays_tips() {
+        let producer = StringProducer::new(StringProducerConfig::default(), NodeId::new([1u8; 32]));
+        let mut events = producer.subscribe();
+
+        producer
+            .fee_market()
+            .write()
+            .charge(21_000, 2_000_000_000, 1_000_000)
+            .unwrap();
+        producer.produce_anchor().unwrap();
+
+        match events.try_recv().unwrap() {
+            ProductionEvent::AnchorFinalized { fees, .. } => {
+                assert_eq!(fees.gas_used, 21_000);
+                assert_eq!(fees.tips, 21_000 * 1_000_000);
+                assert_eq!(producer.stats().tips_earned, fees.tips);
+                assert_eq!(producer.stats().fees_burned, fees.burned);
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+        assert_eq!(producer.fee_market().read().gas_available(), 30_000_000);
+    }
+
+    #[test]
+    fn test_only_scheduled_producer_anchors() {
+        let weights =
+            rope_consensus::StakeWeights::new([([1u8; 32], 10), ([2u8; 32], 10)].into(), 0.667);
+        let schedule = Arc::new(ProducerSchedule::new(weights, 0, [7u8; 32]));
+        let mut producers: Vec<StringProducer> = (1..=2u8)
+            .map(|i| {
+                let vrf_key = VrfSecretKey::from_seed(&[i; 32]);
+                schedule.register_key([i; 32], vrf_key.public_key());
+                let mut producer =
+                    StringProducer::new(StringProducerConfig::default(), NodeId::new([i; 32]));
+                producer.set_schedule(schedule.clone(), vrf_key);
+                producer
+            })
+            .collect();
+
+        for round in 1..=5 {
+            let (ours, theirs): (Vec<_>, Vec<_>) =
+                producers.iter_mut().partition(|p| p.is_our_turn());
+            assert_eq!(ours.len(), 1);
+            assert!(theirs[0].produce_anchor().is_err());
+
+            let mut events = ours[0].subscribe();
+            ours[0].produce_anchor().unwrap();
+            match events.try_recv().unwrap() {
+                ProductionEvent::AnchorFinalized {
+                    round: anchored,
+                    claim,
+                    ..
+                } => {
+                    assert_eq!(anchored, round);
+                    assert_eq!(claim.unwrap().producer, *ours[0].node_id.as_bytes());
+                }
+                other => panic!("unexpected {:?}", other),
+            }
+            assert_eq!(schedule.next_round(), round + 1);
+        }
+    }
 }
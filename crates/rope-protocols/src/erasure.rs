@@ -9,6 +9,14 @@
 //! - **Audit Trail**: Preserve proof of deletion without preserving content
 //! - **Authorization**: Only authorized parties can initiate deletion
 //!
+//! ## Authorization
+//!
+//! A request enters the pending queue only with an [`AuthorizationProof`]
+//! accepted by the coordinator's [`ErasureAuthorizer`]: a signature by the
+//! DataWallet key owning every targeted string, or a
+//! [`CourtOrderAttestation`] signed by a governance key. Legal orders always
+//! need the attestation.
+//!
 //! ## Erasure Flow
 //!
 //! ```text
@@ -19,6 +27,11 @@
 //! [Audit Record]    [Legal Check]      [Zero Memory]        [Confirm Peers]
 //! ```
 //!
+//! Once a request is authorized, [`ErasureEnforcer`] carries it out on the
+//! local node: complements are deleted, payload keys crypto-shredded and
+//! lattice entries tombstoned, and a signed [`ErasureConfirmation`] is
+//! recorded on the lattice.
+//!
 //! ## Compliance
 //!
 //! - GDPR Article 17 (Right to Erasure)
@@ -26,8 +39,14 @@
 //! - LGPD (Brazilian Data Protection Law)
 
 use parking_lot::RwLock;
+use rope_core::{
+    ClockManager, MutabilityClass, NodeId, PublicKey, RopeString, StringId, StringLattice,
+};
+use rope_crypto::hybrid::{HybridPublicKey, HybridSigner, HybridVerifier};
+use rope_storage::{ComplementStore, PayloadKeyStore};
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
 
 /// Erasure request
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -47,7 +66,7 @@ pub struct ErasureRequest {
     /// Timestamp
     pub timestamp: i64,
 
-    /// Authorization proof (signature)
+    /// Encoded [`AuthorizationProof`]
     pub authorization_proof: Vec<u8>,
 
     /// Legal reference (if applicable)
@@ -98,6 +117,235 @@ impl ErasureRequest {
         self.cascade = true;
         self
     }
+
+    /// Sign as the owner of the targeted strings
+    ///
+    /// Sign last: the signature covers the reason, legal reference and
+    /// cascade flag.
+    pub fn authorize_as_owner(self, wallet_id: [u8; 32], signer: &HybridSigner) -> Self {
+        let signature = sign_message(signer, &self.signing_message());
+        self.with_authorization(
+            AuthorizationProof::Owner {
+                wallet_id,
+                signature,
+            }
+            .to_bytes(),
+        )
+    }
+
+    /// Attach a signed court-order attestation
+    pub fn with_court_order(self, attestation: CourtOrderAttestation) -> Self {
+        self.with_authorization(AuthorizationProof::CourtOrder(attestation).to_bytes())
+    }
+
+    /// Decoded authorization proof, if any
+    pub fn authorization(&self) -> Option<AuthorizationProof> {
+        AuthorizationProof::from_bytes(&self.authorization_proof)
+    }
+
+    /// Message covered by authorization signatures
+    pub fn signing_message(&self) -> Vec<u8> {
+        let mut message = b"rope-erasure-request-v1".to_vec();
+        message.extend_from_slice(&self.id);
+        message.extend_from_slice(&self.requester_id);
+        message.extend_from_slice(&self.timestamp.to_be_bytes());
+        message.extend_from_slice(&(self.string_ids.len() as u64).to_be_bytes());
+        for string_id in &self.string_ids {
+            message.extend_from_slice(string_id);
+        }
+        let reason = serde_json::to_vec(&self.reason).unwrap_or_default();
+        message.extend_from_slice(&(reason.len() as u64).to_be_bytes());
+        message.extend_from_slice(&reason);
+        match &self.legal_reference {
+            Some(reference) => {
+                message.push(1);
+                message.extend_from_slice(&(reference.len() as u64).to_be_bytes());
+                message.extend_from_slice(reference.as_bytes());
+            }
+            None => message.push(0),
+        }
+        message.push(self.cascade as u8);
+        message
+    }
+
+    /// Digest of the signing message, bound by court-order attestations
+    pub fn digest(&self) -> [u8; 32] {
+        *blake3::hash(&self.signing_message()).as_bytes()
+    }
+}
+
+/// Proof that an erasure request is authorized
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AuthorizationProof {
+    /// Signature by the data owner's DataWallet key
+    Owner {
+        wallet_id: [u8; 32],
+        signature: Vec<u8>,
+    },
+
+    /// Court order attested by a governance key
+    CourtOrder(CourtOrderAttestation),
+}
+
+impl AuthorizationProof {
+    /// Encode for [`ErasureRequest::authorization_proof`]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        serde_json::to_vec(self).unwrap_or_default()
+    }
+
+    /// Decode; `None` if empty or malformed
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        serde_json::from_slice(bytes).ok()
+    }
+}
+
+/// Court order attestation for a specific erasure request
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CourtOrderAttestation {
+    /// [`ErasureRequest::digest`] of the attested request
+    pub request_digest: [u8; 32],
+
+    /// Legal reference of the order (must match the request)
+    pub legal_reference: String,
+
+    /// Node ID of the attesting governance key
+    pub governance_key_id: [u8; 32],
+
+    /// Issue time
+    pub issued_at: i64,
+
+    /// Governance signature (Ed25519 followed by Dilithium)
+    pub signature: Vec<u8>,
+}
+
+impl CourtOrderAttestation {
+    /// Attest `request` with the governance key held by `signer`
+    pub fn issue(
+        request: &ErasureRequest,
+        governance_key: &HybridPublicKey,
+        signer: &HybridSigner,
+    ) -> Self {
+        let mut attestation = Self {
+            request_digest: request.digest(),
+            legal_reference: request.legal_reference.clone().unwrap_or_default(),
+            governance_key_id: governance_key.node_id(),
+            issued_at: chrono::Utc::now().timestamp(),
+            signature: Vec::new(),
+        };
+        attestation.signature = sign_message(signer, &attestation.signing_message());
+        attestation
+    }
+
+    /// Message covered by the governance signature
+    pub fn signing_message(&self) -> Vec<u8> {
+        let mut message = b"rope-court-order-v1".to_vec();
+        message.extend_from_slice(&self.request_digest);
+        message.extend_from_slice(&(self.legal_reference.len() as u64).to_be_bytes());
+        message.extend_from_slice(self.legal_reference.as_bytes());
+        message.extend_from_slice(&self.governance_key_id);
+        message.extend_from_slice(&self.issued_at.to_be_bytes());
+        message
+    }
+
+    /// Verify the governance signature
+    pub fn verify(&self, public_key: &HybridPublicKey) -> bool {
+        public_key.node_id() == self.governance_key_id
+            && verify_message(public_key, &self.signing_message(), &self.signature)
+    }
+}
+
+/// Verifies authorization proofs before requests are queued
+#[derive(Default)]
+pub struct ErasureAuthorizer {
+    /// DataWallet keys by wallet ID
+    wallet_keys: RwLock<HashMap<[u8; 32], HybridPublicKey>>,
+
+    /// Owning wallet by string ID
+    owners: RwLock<HashMap<[u8; 32], [u8; 32]>>,
+
+    /// Governance keys by node ID
+    governance_keys: RwLock<HashMap<[u8; 32], HybridPublicKey>>,
+}
+
+impl ErasureAuthorizer {
+    /// Create an authorizer with no known keys
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a DataWallet key
+    pub fn register_wallet(&self, wallet_id: [u8; 32], public_key: HybridPublicKey) {
+        self.wallet_keys.write().insert(wallet_id, public_key);
+    }
+
+    /// Record the wallet owning a string
+    pub fn register_owner(&self, string_id: [u8; 32], wallet_id: [u8; 32]) {
+        self.owners.write().insert(string_id, wallet_id);
+    }
+
+    /// Register a governance key allowed to attest court orders
+    pub fn register_governance_key(&self, public_key: HybridPublicKey) {
+        self.governance_keys
+            .write()
+            .insert(public_key.node_id(), public_key);
+    }
+
+    /// Check a request's authorization proof
+    pub fn verify(&self, request: &ErasureRequest) -> Result<(), ErasureError> {
+        if request.authorization_proof.is_empty() {
+            return Err(ErasureError::MissingAuthorization);
+        }
+        let proof = request
+            .authorization()
+            .ok_or(ErasureError::AuthorizationFailed)?;
+
+        let authorized = match proof {
+            // Legal orders need a court order, not the owner's consent
+            AuthorizationProof::Owner { .. } if request.reason.requires_legal_auth() => false,
+            AuthorizationProof::Owner {
+                wallet_id,
+                signature,
+            } => {
+                let owners = self.owners.read();
+                let owns_all = request
+                    .string_ids
+                    .iter()
+                    .all(|id| owners.get(id) == Some(&wallet_id));
+                owns_all
+                    && self.wallet_keys.read().get(&wallet_id).is_some_and(|key| {
+                        verify_message(key, &request.signing_message(), &signature)
+                    })
+            }
+            AuthorizationProof::CourtOrder(attestation) => {
+                attestation.request_digest == request.digest()
+                    && request.legal_reference.as_ref() == Some(&attestation.legal_reference)
+                    && self
+                        .governance_keys
+                        .read()
+                        .get(&attestation.governance_key_id)
+                        .is_some_and(|key| attestation.verify(key))
+            }
+        };
+
+        if authorized {
+            Ok(())
+        } else {
+            Err(ErasureError::AuthorizationFailed)
+        }
+    }
+}
+
+/// Hybrid signature bytes: Ed25519 followed by Dilithium
+fn sign_message(signer: &HybridSigner, message: &[u8]) -> Vec<u8> {
+    signer.sign(message).to_bytes()
+}
+
+/// Verify signature bytes produced by [`sign_message`]
+fn verify_message(public_key: &HybridPublicKey, message: &[u8], signature: &[u8]) -> bool {
+    let Ok(signature) = rope_crypto::hybrid::HybridSignature::from_bytes(signature) else {
+        return false;
+    };
+    HybridVerifier::verify(public_key, message, &signature).unwrap_or(false)
 }
 
 /// Reason for erasure
@@ -237,6 +485,67 @@ pub struct ErasureConfirmation {
 
     /// Keys destroyed (proof without revealing keys)
     pub key_destruction_proofs: Vec<KeyDestructionProof>,
+
+    /// What remains of each erased string
+    #[serde(default)]
+    pub tombstones: Vec<ErasureTombstone>,
+}
+
+impl ErasureConfirmation {
+    /// Message covered by the confirmer's signature
+    pub fn signing_message(&self) -> Vec<u8> {
+        let mut message = b"rope-erasure-confirmation-v1".to_vec();
+        message.extend_from_slice(&self.request_id);
+        message.extend_from_slice(&self.confirmer_id);
+        message.extend_from_slice(&self.timestamp.to_be_bytes());
+        message.extend_from_slice(&(self.erased_strings.len() as u64).to_be_bytes());
+        for string_id in &self.erased_strings {
+            message.extend_from_slice(string_id);
+        }
+        message.extend_from_slice(&(self.key_destruction_proofs.len() as u64).to_be_bytes());
+        for proof in &self.key_destruction_proofs {
+            message.extend_from_slice(&proof.string_id);
+            message.extend_from_slice(&proof.key_hash);
+            message.extend_from_slice(&proof.destroyed_at.to_be_bytes());
+        }
+        message.extend_from_slice(&(self.tombstones.len() as u64).to_be_bytes());
+        for tombstone in &self.tombstones {
+            message.extend_from_slice(&tombstone.string_id);
+            message.extend_from_slice(&tombstone.content_hash);
+        }
+        message
+    }
+
+    /// Sign as the confirming node (Ed25519 signature followed by Dilithium)
+    pub fn sign(&mut self, signer: &HybridSigner) {
+        self.signature = sign_message(signer, &self.signing_message());
+    }
+
+    /// Verify the confirmer's signature
+    pub fn verify(&self, public_key: &HybridPublicKey) -> bool {
+        verify_message(public_key, &self.signing_message(), &self.signature)
+    }
+
+    /// Read a confirmation back from its lattice record
+    pub fn from_record(record: &RopeString) -> Option<Self> {
+        let content = record.content();
+        // Nucleotide padding is zeros, which never end a JSON document
+        let end = content.iter().rposition(|&b| b != 0)? + 1;
+        serde_json::from_slice(&content[..end]).ok()
+    }
+}
+
+/// Lattice entry left after erasure: identity and structure, no content
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ErasureTombstone {
+    /// Erased string ID (kept in the DAG and accumulator)
+    pub string_id: [u8; 32],
+
+    /// BLAKE3 of the erased content, for later integrity disputes
+    pub content_hash: [u8; 32],
+
+    /// Parent links at erasure time
+    pub parents: Vec<[u8; 32]>,
 }
 
 /// Proof that an encryption key was destroyed
@@ -325,6 +634,9 @@ pub struct ErasureCoordinator {
     /// Required confirmations for completion
     required_confirmations: u32,
 
+    /// Authorization proof verifier
+    authorizer: Arc<ErasureAuthorizer>,
+
     /// Statistics
     stats: RwLock<ErasureStats>,
 }
@@ -352,10 +664,22 @@ impl ErasureCoordinator {
             erased_strings: RwLock::new(HashSet::new()),
             audit_trail: RwLock::new(Vec::new()),
             required_confirmations,
+            authorizer: Arc::new(ErasureAuthorizer::new()),
             stats: RwLock::new(ErasureStats::default()),
         }
     }
 
+    /// Share an authorizer (wallet and governance keys) with other services
+    pub fn with_authorizer(mut self, authorizer: Arc<ErasureAuthorizer>) -> Self {
+        self.authorizer = authorizer;
+        self
+    }
+
+    /// Authorization proof verifier
+    pub fn authorizer(&self) -> &ErasureAuthorizer {
+        &self.authorizer
+    }
+
     /// Submit an erasure request
     pub fn submit_request(&self, request: ErasureRequest) -> Result<[u8; 32], ErasureError> {
         // Validate request
@@ -367,6 +691,7 @@ impl ErasureCoordinator {
         if request.reason.requires_legal_auth() && request.legal_reference.is_none() {
             return Err(ErasureError::MissingLegalReference);
         }
+        self.authorizer.verify(&request)?;
 
         let id = request.id;
 
@@ -548,6 +873,11 @@ impl ErasureCoordinator {
         Ok(status)
     }
 
+    /// Get a submitted request that has not completed yet
+    pub fn get_request(&self, request_id: &[u8; 32]) -> Option<ErasureRequest> {
+        self.pending_requests.read().get(request_id).cloned()
+    }
+
     /// Check if a string is erased
     pub fn is_erased(&self, string_id: &[u8; 32]) -> bool {
         self.erased_strings.read().contains(string_id)
@@ -582,8 +912,10 @@ pub enum ErasureError {
     RequestNotFound,
     InvalidState,
     MissingLegalReference,
+    MissingAuthorization,
     AuthorizationFailed,
     NetworkError,
+    Lattice(String),
 }
 
 impl std::fmt::Display for ErasureError {
@@ -595,8 +927,12 @@ impl std::fmt::Display for ErasureError {
             ErasureError::MissingLegalReference => {
                 write!(f, "Legal reference required for legal orders")
             }
+            ErasureError::MissingAuthorization => {
+                write!(f, "Erasure request carries no authorization proof")
+            }
             ErasureError::AuthorizationFailed => write!(f, "Authorization failed"),
             ErasureError::NetworkError => write!(f, "Network error during erasure"),
+            ErasureError::Lattice(e) => write!(f, "Lattice error during erasure: {}", e),
         }
     }
 }
@@ -742,6 +1078,189 @@ impl Default for CryptoKeyDestroyer {
     }
 }
 
+// ============================================================================
+// Erasure Enforcement
+// ============================================================================
+
+/// Result of enforcing an erasure request on this node
+#[derive(Clone, Debug)]
+pub struct EnforcementOutcome {
+    /// Signed confirmation, also recorded on the lattice
+    pub confirmation: ErasureConfirmation,
+
+    /// Lattice string recording the confirmation
+    pub record_id: StringId,
+
+    /// Coordinator status after adding the confirmation
+    pub status: ErasureStatus,
+}
+
+/// Carries out authorized erasure requests against local storage
+///
+/// For each targeted string (plus descendants when the request cascades)
+/// the enforcer deletes its complement, crypto-shreds its payload key and
+/// tombstones it in the lattice. The lattice keeps the string ID in its DAG
+/// and accumulator; the confirmation keeps parents and a content hash.
+/// Immutable strings are skipped and reported as failed by the coordinator.
+pub struct ErasureEnforcer {
+    lattice: Arc<StringLattice>,
+    complements: Arc<ComplementStore>,
+    keys: Arc<PayloadKeyStore>,
+    signer: HybridSigner,
+    public_key: HybridPublicKey,
+    clock: ClockManager,
+    destroyer: CryptoKeyDestroyer,
+}
+
+impl ErasureEnforcer {
+    /// Create an enforcer signing confirmations with the node key
+    pub fn new(
+        lattice: Arc<StringLattice>,
+        complements: Arc<ComplementStore>,
+        keys: Arc<PayloadKeyStore>,
+        signer: HybridSigner,
+    ) -> Self {
+        let public_key = signer.public_key();
+        let clock = ClockManager::new(NodeId::new(public_key.node_id()));
+        Self {
+            lattice,
+            complements,
+            keys,
+            signer,
+            public_key,
+            clock,
+            destroyer: CryptoKeyDestroyer::new(),
+        }
+    }
+
+    /// Node ID used as confirmer
+    pub fn node_id(&self) -> [u8; 32] {
+        self.public_key.node_id()
+    }
+
+    /// Enforce an authorized request and report to the coordinator
+    pub fn enforce(
+        &self,
+        coordinator: &ErasureCoordinator,
+        request_id: &[u8; 32],
+    ) -> Result<EnforcementOutcome, ErasureError> {
+        if !matches!(
+            coordinator.get_status(request_id),
+            Some(ErasureStatus::InProgress { .. })
+        ) {
+            return Err(ErasureError::InvalidState);
+        }
+        let request = coordinator
+            .get_request(request_id)
+            .ok_or(ErasureError::RequestNotFound)?;
+
+        let mut targets: Vec<StringId> = request
+            .string_ids
+            .iter()
+            .map(|id| StringId::new(*id))
+            .collect();
+        if request.cascade {
+            for id in targets.clone() {
+                targets.extend(self.lattice.descendants(&id));
+            }
+        }
+        let mut seen = HashSet::new();
+        targets.retain(|id| seen.insert(*id));
+
+        let mut erased_strings = Vec::new();
+        let mut tombstones = Vec::new();
+        let mut key_destruction_proofs = Vec::new();
+        for id in targets {
+            let Some(string) = self.lattice.get_string(&id) else {
+                continue;
+            };
+            if *string.mutability_class() == MutabilityClass::Immutable {
+                tracing::warn!("Refusing to erase immutable string {}", id);
+                continue;
+            }
+
+            let raw_id = *id.as_bytes();
+            let tombstone = ErasureTombstone {
+                string_id: raw_id,
+                content_hash: *blake3::hash(&string.content()).as_bytes(),
+                parents: string.parentage().iter().map(|p| *p.as_bytes()).collect(),
+            };
+
+            self.complements.erase_complement(&raw_id);
+            if let Some(mut key) = self.keys.take_key(&raw_id) {
+                let mut proof = self.destroyer.destroy_key(&mut key);
+                proof.string_id = raw_id;
+                key_destruction_proofs.push(proof);
+            }
+            self.lattice
+                .mark_erased(id)
+                .map_err(|e| ErasureError::Lattice(e.to_string()))?;
+
+            erased_strings.push(raw_id);
+            tombstones.push(tombstone);
+        }
+
+        let mut confirmation = ErasureConfirmation {
+            request_id: *request_id,
+            erased_strings,
+            confirmer_id: self.node_id(),
+            timestamp: chrono::Utc::now().timestamp(),
+            signature: Vec::new(),
+            key_destruction_proofs,
+            tombstones,
+        };
+        confirmation.sign(&self.signer);
+
+        let record_id = self.record(&confirmation)?;
+        let status = coordinator.add_confirmation(confirmation.clone())?;
+
+        Ok(EnforcementOutcome {
+            confirmation,
+            record_id,
+            status,
+        })
+    }
+
+    /// Record a confirmation on the lattice as an immutable signed string
+    fn record(&self, confirmation: &ErasureConfirmation) -> Result<StringId, ErasureError> {
+        let content =
+            serde_json::to_vec(confirmation).map_err(|e| ErasureError::Lattice(e.to_string()))?;
+        let parents: Vec<StringId> = self
+            .lattice
+            .latest_anchor()
+            .map(|anchor| anchor.id())
+            .filter(|id| self.lattice.contains(id))
+            .into_iter()
+            .collect();
+        let marker = self.clock.tick();
+        let creator = PublicKey::new(self.public_key.ed25519, self.public_key.dilithium.clone());
+
+        let builder = || {
+            RopeString::builder()
+                .content(content.clone())
+                .temporal_marker(marker.clone())
+                .parentage(parents.clone())
+                .mutability_class(MutabilityClass::Immutable)
+                .creator(creator.clone())
+        };
+        let unsigned = builder()
+            .build()
+            .map_err(|e| ErasureError::Lattice(e.to_string()))?;
+        let signature = self.signer.sign(&unsigned.compute_signing_message());
+        let record = builder()
+            .signature(rope_core::HybridSignature {
+                ed25519_sig: signature.ed25519_sig,
+                dilithium_sig: signature.dilithium_sig,
+            })
+            .build()
+            .map_err(|e| ErasureError::Lattice(e.to_string()))?;
+
+        self.lattice
+            .add_string(record)
+            .map_err(|e| ErasureError::Lattice(e.to_string()))
+    }
+}
+
 // ============================================================================
 // Network Erasure Propagation
 // ============================================================================
@@ -1076,6 +1595,17 @@ pub struct ComplianceReport {
 mod tests {
     use super::*;
 
+    /// Register a fresh DataWallet key owning `strings`
+    fn owner_wallet(coord: &ErasureCoordinator, strings: &[[u8; 32]]) -> ([u8; 32], HybridSigner) {
+        let (signer, public_key) = HybridSigner::generate_signing_only();
+        let wallet_id = public_key.node_id();
+        coord.authorizer().register_wallet(wallet_id, public_key);
+        for string_id in strings {
+            coord.authorizer().register_owner(*string_id, wallet_id);
+        }
+        (wallet_id, signer)
+    }
+
     #[test]
     fn test_erasure_request() {
         let request = ErasureRequest::new(
@@ -1093,8 +1623,10 @@ mod tests {
     #[test]
     fn test_erasure_coordinator() {
         let coord = ErasureCoordinator::new([0u8; 32], 1);
+        let (wallet_id, wallet) = owner_wallet(&coord, &[[1u8; 32]]);
 
-        let request = ErasureRequest::new(vec![[1u8; 32]], [0u8; 32], ErasureReason::OwnerRequest);
+        let request = ErasureRequest::new(vec![[1u8; 32]], [0u8; 32], ErasureReason::OwnerRequest)
+            .authorize_as_owner(wallet_id, &wallet);
 
         let id = coord.submit_request(request).unwrap();
 
@@ -1115,6 +1647,7 @@ mod tests {
             timestamp: 0,
             signature: vec![],
             key_destruction_proofs: vec![],
+            tombstones: vec![],
         };
 
         let status = coord.add_confirmation(confirmation).unwrap();
@@ -1128,6 +1661,10 @@ mod tests {
     #[test]
     fn test_legal_order_requires_reference() {
         let coord = ErasureCoordinator::new([0u8; 32], 1);
+        let (governance, governance_key) = HybridSigner::generate_signing_only();
+        coord
+            .authorizer()
+            .register_governance_key(governance_key.clone());
 
         let request = ErasureRequest::new(
             vec![[1u8; 32]],
@@ -1152,7 +1689,196 @@ mod tests {
             },
         )
         .with_legal_reference("COURT-ORDER-2024-001".to_string());
+        let attestation = CourtOrderAttestation::issue(&request, &governance_key, &governance);
+
+        assert!(coord
+            .submit_request(request.with_court_order(attestation))
+            .is_ok());
+    }
 
-        assert!(coord.submit_request(request).is_ok());
+    #[test]
+    fn test_authorization_proofs() {
+        let coord = ErasureCoordinator::new([0u8; 32], 1);
+        let (wallet_id, wallet) = owner_wallet(&coord, &[[1u8; 32], [2u8; 32]]);
+        let (other_id, other) = owner_wallet(&coord, &[[3u8; 32]]);
+        let request = || {
+            ErasureRequest::new(
+                vec![[1u8; 32], [2u8; 32]],
+                [0u8; 32],
+                ErasureReason::GdprRequest { data_subject: None },
+            )
+        };
+
+        // No proof, or garbage
+        assert_eq!(
+            coord.submit_request(request()),
+            Err(ErasureError::MissingAuthorization)
+        );
+        assert_eq!(
+            coord.submit_request(request().with_authorization(vec![1, 2, 3])),
+            Err(ErasureError::AuthorizationFailed)
+        );
+
+        // Signed by a wallet that does not own the strings
+        assert_eq!(
+            coord.submit_request(request().authorize_as_owner(other_id, &other)),
+            Err(ErasureError::AuthorizationFailed)
+        );
+
+        // Changed after signing
+        let widened = request()
+            .authorize_as_owner(wallet_id, &wallet)
+            .with_cascade();
+        assert_eq!(
+            coord.submit_request(widened),
+            Err(ErasureError::AuthorizationFailed)
+        );
+
+        // Court order from an unregistered governance key
+        let (governance, governance_key) = HybridSigner::generate_signing_only();
+        let ordered = request().with_legal_reference("ORDER-1".to_string());
+        let attestation = CourtOrderAttestation::issue(&ordered, &governance_key, &governance);
+        let ordered = ordered.with_court_order(attestation);
+        assert_eq!(
+            coord.submit_request(ordered.clone()),
+            Err(ErasureError::AuthorizationFailed)
+        );
+        coord.authorizer().register_governance_key(governance_key);
+        assert!(coord.submit_request(ordered).is_ok());
+
+        // Owners cannot stand in for a court on legal orders
+        let legal = ErasureRequest::new(
+            vec![[1u8; 32]],
+            [0u8; 32],
+            ErasureReason::LegalOrder {
+                reference: "CASE-9".to_string(),
+                jurisdiction: "EU".to_string(),
+            },
+        )
+        .with_legal_reference("CASE-9".to_string())
+        .authorize_as_owner(wallet_id, &wallet);
+        assert_eq!(
+            coord.submit_request(legal),
+            Err(ErasureError::AuthorizationFailed)
+        );
+
+        let signed = request().authorize_as_owner(wallet_id, &wallet);
+        assert!(matches!(
+            signed.authorization(),
+            Some(AuthorizationProof::Owner { .. })
+        ));
+        assert!(coord.submit_request(signed).is_ok());
+        assert_eq!(coord.stats().total_requests, 2);
+    }
+
+    #[test]
+    fn test_enforcer_erases_and_records_confirmation() {
+        use rope_core::LamportClock;
+
+        let lattice = Arc::new(StringLattice::new());
+        let complements = Arc::new(ComplementStore::new());
+        let keys = Arc::new(PayloadKeyStore::new());
+
+        let make = |content: &[u8], parents: Vec<StringId>, class: MutabilityClass| {
+            RopeString::builder()
+                .content(content.to_vec())
+                .temporal_marker(LamportClock::new(NodeId::new([0u8; 32])))
+                .creator(PublicKey::from_ed25519([0u8; 32]))
+                .parentage(parents)
+                .mutability_class(class)
+                .build()
+                .unwrap()
+        };
+        let genesis = make(b"genesis", vec![], MutabilityClass::Immutable);
+        let personal = make(
+            b"personal data",
+            vec![genesis.id()],
+            MutabilityClass::GDPRCompliant,
+        );
+        let derived = make(
+            b"derived",
+            vec![personal.id()],
+            MutabilityClass::OwnerErasable,
+        );
+        for string in [&genesis, &personal, &derived] {
+            lattice.add_string(string.clone()).unwrap();
+            complements.store_complement(*string.id().as_bytes(), vec![1, 2, 3]);
+        }
+        keys.store_key(*personal.id().as_bytes(), vec![0xAB; 32]);
+
+        let (signer, public_key) = HybridSigner::generate_signing_only();
+        let enforcer =
+            ErasureEnforcer::new(lattice.clone(), complements.clone(), keys.clone(), signer);
+
+        let coord = ErasureCoordinator::new([0u8; 32], 1);
+        let targets = [*genesis.id().as_bytes(), *personal.id().as_bytes()];
+        let (wallet_id, wallet) = owner_wallet(&coord, &targets);
+        let request = ErasureRequest::new(
+            targets.to_vec(),
+            [9u8; 32],
+            ErasureReason::GdprRequest { data_subject: None },
+        )
+        .with_cascade()
+        .authorize_as_owner(wallet_id, &wallet);
+        let id = coord.submit_request(request).unwrap();
+
+        // Not yet authorized
+        assert_eq!(
+            enforcer.enforce(&coord, &id).unwrap_err(),
+            ErasureError::InvalidState
+        );
+        coord.authorize(&id).unwrap();
+
+        let outcome = enforcer.enforce(&coord, &id).unwrap();
+        let erased = &outcome.confirmation.erased_strings;
+        assert_eq!(erased.len(), 2);
+        assert!(erased.contains(personal.id().as_bytes()));
+        assert!(erased.contains(derived.id().as_bytes()));
+
+        // Storage, keys and lattice content are gone; the genesis survives
+        assert!(complements
+            .get_complement(personal.id().as_bytes())
+            .is_none());
+        assert!(complements
+            .get_complement(genesis.id().as_bytes())
+            .is_some());
+        assert!(!keys.contains_key(personal.id().as_bytes()));
+        assert_eq!(outcome.confirmation.key_destruction_proofs.len(), 1);
+        assert!(lattice.get_string(&personal.id()).is_none());
+        assert!(lattice.contains(&genesis.id()));
+
+        // Tombstones keep structure and a content hash
+        let tombstone = outcome
+            .confirmation
+            .tombstones
+            .iter()
+            .find(|t| t.string_id == *personal.id().as_bytes())
+            .unwrap();
+        assert_eq!(tombstone.parents, vec![*genesis.id().as_bytes()]);
+        assert_eq!(
+            tombstone.content_hash,
+            *blake3::hash(&personal.content()).as_bytes()
+        );
+
+        // The immutable genesis could not be erased
+        assert!(matches!(
+            outcome.status,
+            ErasureStatus::PartiallyCompleted {
+                failed_count: 1,
+                ..
+            }
+        ));
+
+        // Confirmation is signed and recorded on the lattice
+        assert!(outcome.confirmation.verify(&public_key));
+        let record = lattice.get_string(&outcome.record_id).unwrap();
+        assert!(record.verify_signature());
+        let recorded = ErasureConfirmation::from_record(&record).unwrap();
+        assert_eq!(recorded.erased_strings, outcome.confirmation.erased_strings);
+        assert!(recorded.verify(&public_key));
+
+        let mut tampered = outcome.confirmation.clone();
+        tampered.erased_strings.pop();
+        assert!(!tampered.verify(&public_key));
     }
 }
@@ -2,9 +2,48 @@
 //!
 //! Nodes share communication history for virtual voting.
 //! Each gossip event references its parents, forming a DAG.
+//!
+//! ## Checkpoints
+//!
+//! The DAG would otherwise grow forever. Every `interval` rounds the
+//! validators sign a [`Checkpoint`] committing to all events up to that
+//! round. Once a [`CheckpointCertificate`] reaches the testimony threshold,
+//! events older than the checkpoint are pruned; only the per-creator
+//! frontier of the latest checkpoint is kept as known parents. New nodes
+//! start from the latest certificate instead of replaying full history.
+//! Certificates are verified against the validator BLS keys before they
+//! prune anything or seed a new DAG.
+//!
+//! ## Persistence
+//!
+//! A DAG attached to a [`GossipStore`] writes events (with their computed
+//! rounds), creator keys and the checkpoint through, and can be
+//! [restored](GossipDag::restore) after a restart. Each applied checkpoint
+//! also stores a [`GossipArchive`] of the rounds it closes, which auditors
+//! can verify offline against the validator BLS keys.
+//!
+//! ## Admission
+//!
+//! Events must be signed by a registered creator. Events whose parents are
+//! not yet known wait in a bounded orphan buffer and are admitted once the
+//! parents arrive. Claimed rounds far ahead of the DAG and creators flooding
+//! a round are rejected; rejections of relayed events are queued as
+//! penalties for the sending peer.
+//!
+//! ## Rounds
+//!
+//! The `round` an event claims is only used to bound admission. Each node
+//! computes rounds itself: an event starts in the highest round of its
+//! parents and advances when it strongly sees that round's witnesses from
+//! more than two thirds of the creators. The first event of each creator in
+//! a round is a witness; [`GossipDag::witnesses`] feeds virtual voting.
 
+use rope_crypto::bls::{BlsPublicKey, BlsSignature};
+use rope_crypto::hybrid::{HybridPublicKey, HybridSignature, HybridSigner, HybridVerifier};
+use rope_storage::GossipStore;
 use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::sync::Arc;
 
 /// A gossip event
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -15,7 +54,478 @@ pub struct GossipEvent {
     pub other_parent: Option<[u8; 32]>,
     pub payload: Vec<u8>,
     pub timestamp: u64,
+
+    /// Round claimed by the creator (see [`GossipDag::round_of`])
     pub round: u64,
+
+    /// Creator signature (Ed25519 followed by Dilithium)
+    #[serde(default)]
+    pub signature: Vec<u8>,
+}
+
+impl GossipEvent {
+    /// Message covered by the creator's signature
+    pub fn signing_message(&self) -> Vec<u8> {
+        let mut message = b"rope-gossip-event-v1".to_vec();
+        message.extend_from_slice(&self.id);
+        message.extend_from_slice(&self.creator_id);
+        for parent in [&self.self_parent, &self.other_parent] {
+            match parent {
+                Some(id) => {
+                    message.push(1);
+                    message.extend_from_slice(id);
+                }
+                None => message.push(0),
+            }
+        }
+        message.extend_from_slice(&(self.payload.len() as u64).to_be_bytes());
+        message.extend_from_slice(&self.payload);
+        message.extend_from_slice(&self.timestamp.to_be_bytes());
+        message.extend_from_slice(&self.round.to_be_bytes());
+        message
+    }
+
+    /// Sign as the creator
+    pub fn sign(&mut self, signer: &HybridSigner) {
+        self.signature = signer.sign(&self.signing_message()).to_bytes();
+    }
+
+    /// Verify the creator's signature
+    pub fn verify_signature(&self, creator: &HybridPublicKey) -> bool {
+        if creator.node_id() != self.creator_id {
+            return false;
+        }
+        let Ok(signature) = HybridSignature::from_bytes(&self.signature) else {
+            return false;
+        };
+        HybridVerifier::verify(creator, &self.signing_message(), &signature).unwrap_or(false)
+    }
+
+    fn parents(&self) -> impl Iterator<Item = [u8; 32]> {
+        self.self_parent.into_iter().chain(self.other_parent)
+    }
+}
+
+/// Admission limits for incoming events
+#[derive(Clone, Debug)]
+pub struct GossipLimits {
+    /// Rounds an event may claim beyond the current round
+    pub max_depth: u64,
+
+    /// Events accepted per creator per round
+    pub max_events_per_round: usize,
+
+    /// Orphans buffered in total
+    pub max_orphans: usize,
+
+    /// Orphans buffered per creator
+    pub max_orphans_per_creator: usize,
+}
+
+impl Default for GossipLimits {
+    fn default() -> Self {
+        Self {
+            max_depth: 1000,
+            max_events_per_round: 32,
+            max_orphans: 10_000,
+            max_orphans_per_creator: 256,
+        }
+    }
+}
+
+/// Event accepted by [`GossipDag::add_event`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum EventAdmission {
+    /// Event inserted, along with any orphans it unblocked
+    Accepted { released: Vec<[u8; 32]> },
+
+    /// Parents unknown; event buffered until they arrive
+    Buffered { missing: Vec<[u8; 32]> },
+}
+
+/// Why an event was rejected
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum GossipRejection {
+    /// Event already held or buffered
+    Duplicate,
+    /// Creator has no registered key
+    UnknownCreator,
+    /// Creator signature is missing or invalid
+    InvalidSignature,
+    /// Parents contradict the event (foreign self-parent, round regression)
+    InvalidAncestry,
+    /// Round too far ahead of the DAG
+    TooDeep { round: u64, max: u64 },
+    /// Creator exceeded its per-round event budget
+    RateLimited { round: u64 },
+    /// Orphan buffer full
+    OrphanBufferFull,
+}
+
+impl GossipRejection {
+    /// Whether relaying this event is the sender's fault
+    ///
+    /// Unknown creators and full buffers may reflect our own lag.
+    pub fn penalizes_sender(&self) -> bool {
+        matches!(
+            self,
+            GossipRejection::InvalidSignature
+                | GossipRejection::InvalidAncestry
+                | GossipRejection::TooDeep { .. }
+                | GossipRejection::RateLimited { .. }
+        )
+    }
+}
+
+impl std::fmt::Display for GossipRejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GossipRejection::Duplicate => write!(f, "Duplicate event"),
+            GossipRejection::UnknownCreator => write!(f, "Unknown event creator"),
+            GossipRejection::InvalidSignature => write!(f, "Invalid creator signature"),
+            GossipRejection::InvalidAncestry => write!(f, "Invalid event ancestry"),
+            GossipRejection::TooDeep { round, max } => {
+                write!(f, "Event round {} beyond maximum {}", round, max)
+            }
+            GossipRejection::RateLimited { round } => {
+                write!(f, "Creator exceeded event limit in round {}", round)
+            }
+            GossipRejection::OrphanBufferFull => write!(f, "Orphan buffer full"),
+        }
+    }
+}
+
+impl std::error::Error for GossipRejection {}
+
+/// Admission counters
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct GossipMetrics {
+    pub accepted: u64,
+    pub buffered: u64,
+    pub released: u64,
+    pub duplicates: u64,
+    pub unknown_creator: u64,
+    pub invalid_signature: u64,
+    pub invalid_ancestry: u64,
+    pub too_deep: u64,
+    pub rate_limited: u64,
+    pub orphans_dropped: u64,
+}
+
+impl GossipMetrics {
+    fn count(&mut self, rejection: &GossipRejection) {
+        match rejection {
+            GossipRejection::Duplicate => self.duplicates += 1,
+            GossipRejection::UnknownCreator => self.unknown_creator += 1,
+            GossipRejection::InvalidSignature => self.invalid_signature += 1,
+            GossipRejection::InvalidAncestry => self.invalid_ancestry += 1,
+            GossipRejection::TooDeep { .. } => self.too_deep += 1,
+            GossipRejection::RateLimited { .. } => self.rate_limited += 1,
+            GossipRejection::OrphanBufferFull => self.orphans_dropped += 1,
+        }
+    }
+}
+
+/// Buffered event and the peer that relayed it
+struct Orphan {
+    event: GossipEvent,
+    from: Option<[u8; 32]>,
+}
+
+/// Locally computed consensus position of an event
+struct EventMeta {
+    round: u64,
+    witness: bool,
+
+    /// Position in the creator's self-parent chain
+    seq: u64,
+
+    /// Latest seq per creator among the event's ancestors, itself included
+    last_ancestors: HashMap<[u8; 32], u64>,
+
+    /// Earliest seq per creator among the event's descendants, itself included
+    first_descendants: HashMap<[u8; 32], u64>,
+}
+
+/// Event as persisted, with the round this node computed for it
+#[derive(Serialize, Deserialize)]
+struct StoredEvent {
+    round: u64,
+    witness: bool,
+    event: GossipEvent,
+}
+
+/// DAG state persisted next to its events
+#[derive(Serialize, Deserialize)]
+struct StoredState {
+    checkpoint: Option<CheckpointCertificate>,
+    frontier: Vec<[u8; 32]>,
+    creators: Vec<HybridPublicKey>,
+}
+
+/// Consensus checkpoint over the gossip history
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Checkpoint {
+    /// Last round covered by this checkpoint
+    pub round: u64,
+
+    /// Hash chaining the previous root with every event ID up to `round`
+    pub state_root: [u8; 32],
+
+    /// Latest event per creator at or below `round`
+    pub frontier: Vec<[u8; 32]>,
+
+    /// Hash of the previous checkpoint, if any
+    pub previous: Option<[u8; 32]>,
+}
+
+impl Checkpoint {
+    /// State root over covered event IDs in (round, id) order
+    pub fn compute_state_root<'a>(
+        previous_state_root: Option<&[u8; 32]>,
+        event_ids: impl IntoIterator<Item = &'a [u8; 32]>,
+    ) -> [u8; 32] {
+        let mut hasher = blake3::Hasher::new();
+        if let Some(previous) = previous_state_root {
+            hasher.update(previous);
+        }
+        for id in event_ids {
+            hasher.update(id);
+        }
+        *hasher.finalize().as_bytes()
+    }
+
+    /// Checkpoint hash (the message validators sign)
+    pub fn hash(&self) -> [u8; 32] {
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(b"rope-gossip-checkpoint");
+        hasher.update(&self.round.to_le_bytes());
+        hasher.update(&self.state_root);
+        for id in &self.frontier {
+            hasher.update(id);
+        }
+        if let Some(prev) = &self.previous {
+            hasher.update(prev);
+        }
+        *hasher.finalize().as_bytes()
+    }
+}
+
+/// Checkpoint signed by a supermajority of validators
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CheckpointCertificate {
+    pub checkpoint: Checkpoint,
+
+    /// Validators whose signatures are aggregated
+    pub signers: Vec<[u8; 32]>,
+
+    /// BLS aggregate over `checkpoint.hash()`
+    pub signature: BlsSignature,
+}
+
+impl CheckpointCertificate {
+    /// Verify the certificate against validator BLS keys
+    pub fn verify(
+        &self,
+        validator_keys: &HashMap<[u8; 32], BlsPublicKey>,
+        threshold: usize,
+    ) -> bool {
+        let unique: HashSet<&[u8; 32]> = self.signers.iter().collect();
+        if unique.len() != self.signers.len() || unique.len() < threshold {
+            return false;
+        }
+
+        let keys: Option<Vec<BlsPublicKey>> = self
+            .signers
+            .iter()
+            .map(|id| validator_keys.get(id).cloned())
+            .collect();
+
+        match keys {
+            Some(keys) => self
+                .signature
+                .verify_aggregate(&self.checkpoint.hash(), &keys)
+                .is_ok(),
+            None => false,
+        }
+    }
+}
+
+/// Why a checkpoint certificate was not accepted
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CheckpointError {
+    /// Below threshold, unknown or repeated signers, or a bad aggregate
+    InvalidCertificate,
+    /// Does not advance past the current checkpoint
+    Stale { round: u64, current: u64 },
+    /// Covers rounds this DAG has not reached
+    AheadOfDag { round: u64, current: u64 },
+    /// Does not chain onto the current checkpoint
+    BrokenChain,
+}
+
+impl std::fmt::Display for CheckpointError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CheckpointError::InvalidCertificate => write!(f, "Invalid checkpoint certificate"),
+            CheckpointError::Stale { round, current } => write!(
+                f,
+                "Checkpoint for round {} does not advance past round {}",
+                round, current
+            ),
+            CheckpointError::AheadOfDag { round, current } => write!(
+                f,
+                "Checkpoint for round {} is ahead of local round {}",
+                round, current
+            ),
+            CheckpointError::BrokenChain => {
+                write!(f, "Checkpoint does not follow the current checkpoint")
+            }
+        }
+    }
+}
+
+impl std::error::Error for CheckpointError {}
+
+/// Rounds closed by a checkpoint, exported for offline audit
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GossipArchive {
+    /// Certificate of the checkpoint closing the archived rounds
+    pub certificate: CheckpointCertificate,
+
+    /// State root of the previous checkpoint, if any
+    pub previous_state_root: Option<[u8; 32]>,
+
+    /// Covered events with their computed rounds, in (round, id) order
+    pub events: Vec<ArchivedEvent>,
+
+    /// Keys of the creators of archived events
+    pub creators: Vec<HybridPublicKey>,
+}
+
+/// Archived event and the round it was ordered in
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ArchivedEvent {
+    pub round: u64,
+    pub event: GossipEvent,
+}
+
+/// Why an archive failed verification
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ArchiveError {
+    /// Checkpoint certificate does not verify
+    InvalidCertificate,
+    /// Events out of (round, id) order or past the checkpoint round
+    Unordered,
+    /// Event creator key missing from the archive
+    UnknownCreator([u8; 32]),
+    /// Event signature does not verify
+    InvalidSignature([u8; 32]),
+    /// Events do not hash to the certified state root
+    StateRootMismatch,
+}
+
+impl std::fmt::Display for ArchiveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ArchiveError::InvalidCertificate => write!(f, "Invalid checkpoint certificate"),
+            ArchiveError::Unordered => write!(f, "Archived events out of order"),
+            ArchiveError::UnknownCreator(id) => {
+                write!(f, "Unknown creator for event {:02x?}", &id[..4])
+            }
+            ArchiveError::InvalidSignature(id) => {
+                write!(f, "Invalid signature on event {:02x?}", &id[..4])
+            }
+            ArchiveError::StateRootMismatch => {
+                write!(f, "Archived events do not match the state root")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ArchiveError {}
+
+impl GossipArchive {
+    /// Verify the certificate, event signatures and state root
+    pub fn verify(
+        &self,
+        validator_keys: &HashMap<[u8; 32], BlsPublicKey>,
+        threshold: usize,
+    ) -> Result<(), ArchiveError> {
+        let checkpoint = &self.certificate.checkpoint;
+        if !self.certificate.verify(validator_keys, threshold) {
+            return Err(ArchiveError::InvalidCertificate);
+        }
+
+        let ordered = self
+            .events
+            .windows(2)
+            .all(|w| (w[0].round, w[0].event.id) < (w[1].round, w[1].event.id));
+        if !ordered || self.events.iter().any(|e| e.round > checkpoint.round) {
+            return Err(ArchiveError::Unordered);
+        }
+
+        let creators: HashMap<[u8; 32], &HybridPublicKey> =
+            self.creators.iter().map(|k| (k.node_id(), k)).collect();
+        for ArchivedEvent { event, .. } in &self.events {
+            let creator = creators
+                .get(&event.creator_id)
+                .ok_or(ArchiveError::UnknownCreator(event.id))?;
+            if !event.verify_signature(creator) {
+                return Err(ArchiveError::InvalidSignature(event.id));
+            }
+        }
+
+        let root = Checkpoint::compute_state_root(
+            self.previous_state_root.as_ref(),
+            self.events.iter().map(|e| &e.event.id),
+        );
+        if root != checkpoint.state_root {
+            return Err(ArchiveError::StateRootMismatch);
+        }
+        Ok(())
+    }
+
+    /// Whether this archive directly continues `previous`
+    pub fn verify_follows(&self, previous: &GossipArchive) -> bool {
+        let before = &previous.certificate.checkpoint;
+        self.certificate.checkpoint.previous == Some(before.hash())
+            && self.previous_state_root == Some(before.state_root)
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        serde_json::to_vec(self).unwrap_or_default()
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, serde_json::Error> {
+        serde_json::from_slice(bytes)
+    }
+}
+
+/// When to checkpoint and how much history to keep
+#[derive(Clone, Debug)]
+pub struct CheckpointPolicy {
+    /// Rounds between checkpoints
+    pub interval: u64,
+
+    /// Rounds of history retained behind the latest checkpoint
+    pub retain_rounds: u64,
+}
+
+impl Default for CheckpointPolicy {
+    fn default() -> Self {
+        Self {
+            interval: 100,
+            retain_rounds: 10,
+        }
+    }
+}
+
+impl CheckpointPolicy {
+    /// Whether `round` is a checkpoint boundary
+    pub fn is_checkpoint_round(&self, round: u64) -> bool {
+        self.interval > 0 && round > 0 && round % self.interval == 0
+    }
 }
 
 /// Gossip DAG for a node
@@ -23,6 +533,40 @@ pub struct GossipDag {
     events: HashMap<[u8; 32], GossipEvent>,
     heads: HashSet<[u8; 32]>,
     round: u64,
+
+    /// Latest accepted checkpoint certificate
+    checkpoint: Option<CheckpointCertificate>,
+
+    /// Frontier events kept as known parents after pruning
+    frontier: HashSet<[u8; 32]>,
+
+    /// Creator keys by node ID
+    creators: HashMap<[u8; 32], HybridPublicKey>,
+
+    limits: GossipLimits,
+
+    /// Events waiting for parents, by event ID
+    orphans: HashMap<[u8; 32], Orphan>,
+
+    /// Orphan IDs by the missing parent they wait on
+    waiting: HashMap<[u8; 32], Vec<[u8; 32]>>,
+
+    /// Computed rounds and ancestry, by event ID
+    meta: HashMap<[u8; 32], EventMeta>,
+
+    /// Witness event IDs by computed round
+    witnesses: BTreeMap<u64, Vec<[u8; 32]>>,
+
+    /// Accepted events per (creator, computed round)
+    per_round: HashMap<([u8; 32], u64), usize>,
+
+    /// Rejections of relayed events, for peer scoring
+    penalties: Vec<([u8; 32], GossipRejection)>,
+
+    metrics: GossipMetrics,
+
+    /// Write-through persistence, if attached
+    store: Option<Arc<GossipStore>>,
 }
 
 impl GossipDag {
@@ -31,26 +575,481 @@ impl GossipDag {
             events: HashMap::new(),
             heads: HashSet::new(),
             round: 0,
+            checkpoint: None,
+            frontier: HashSet::new(),
+            creators: HashMap::new(),
+            limits: GossipLimits::default(),
+            orphans: HashMap::new(),
+            waiting: HashMap::new(),
+            meta: HashMap::new(),
+            witnesses: BTreeMap::new(),
+            per_round: HashMap::new(),
+            penalties: Vec::new(),
+            metrics: GossipMetrics::default(),
+            store: None,
+        }
+    }
+
+    /// Set admission limits
+    pub fn with_limits(mut self, limits: GossipLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Register a creator key (its node ID is the creator ID)
+    pub fn register_creator(&mut self, public_key: HybridPublicKey) {
+        self.creators.insert(public_key.node_id(), public_key);
+        self.persist_state();
+    }
+
+    /// Persist the DAG to `store` and keep it in sync from now on
+    pub fn attach_store(&mut self, store: Arc<GossipStore>) {
+        self.store = Some(store);
+        self.persist_state();
+        for id in self.events.keys() {
+            self.persist_event(id);
+        }
+    }
+
+    /// Rebuild a DAG from `store`, keeping it attached
+    ///
+    /// Events keep the rounds computed before the restart. Orphans and
+    /// admission counters are not persisted; peers gossip orphans again.
+    pub fn restore(store: Arc<GossipStore>) -> Result<Self, serde_json::Error> {
+        let mut dag = match store.load_state() {
+            Some(bytes) => {
+                let state: StoredState = serde_json::from_slice(&bytes)?;
+                // Persisted certificates were verified before they were applied
+                let mut dag = match state.checkpoint {
+                    Some(certificate) => Self::bootstrap(certificate),
+                    None => Self::new(),
+                };
+                dag.frontier.extend(state.frontier);
+                for public_key in state.creators {
+                    dag.creators.insert(public_key.node_id(), public_key);
+                }
+                dag
+            }
+            None => Self::new(),
+        };
+
+        let mut pending = store
+            .events()
+            .into_iter()
+            .map(|(_, bytes)| serde_json::from_slice::<StoredEvent>(&bytes))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // Parents before children
+        while !pending.is_empty() {
+            let waiting: HashSet<[u8; 32]> = pending.iter().map(|s| s.event.id).collect();
+            let (ready, blocked): (Vec<_>, Vec<_>) = pending
+                .into_iter()
+                .partition(|s| s.event.parents().all(|p| !waiting.contains(&p)));
+            // Only a cycle leaves nothing ready; such events cannot be linked
+            if ready.is_empty() {
+                break;
+            }
+            for stored in ready {
+                dag.replay(stored);
+            }
+            pending = blocked;
+        }
+
+        dag.store = Some(store);
+        Ok(dag)
+    }
+
+    /// Re-link a persisted event, keeping its recorded round
+    fn replay(&mut self, stored: StoredEvent) {
+        let mut meta = self.compute_meta(&stored.event);
+        meta.round = stored.round;
+        meta.witness = stored.witness;
+        *self
+            .per_round
+            .entry((stored.event.creator_id, meta.round))
+            .or_insert(0) += 1;
+        self.link(stored.event, meta);
+    }
+
+    fn persist_event(&self, id: &[u8; 32]) {
+        let (Some(store), Some(event), Some(meta)) =
+            (&self.store, self.events.get(id), self.meta.get(id))
+        else {
+            return;
+        };
+        let stored = StoredEvent {
+            round: meta.round,
+            witness: meta.witness,
+            event: event.clone(),
+        };
+        if let Ok(bytes) = serde_json::to_vec(&stored) {
+            store.put_event(*id, bytes);
+        }
+    }
+
+    fn persist_state(&self) {
+        let Some(store) = &self.store else {
+            return;
+        };
+        let state = StoredState {
+            checkpoint: self.checkpoint.clone(),
+            frontier: self.frontier.iter().copied().collect(),
+            creators: self.creators.values().cloned().collect(),
+        };
+        if let Ok(bytes) = serde_json::to_vec(&state) {
+            store.save_state(bytes);
+        }
+    }
+
+    /// Bootstrap a new node from a checkpoint certificate
+    ///
+    /// The certificate must verify against `validator_keys` with at least
+    /// `threshold` signers.
+    pub fn from_checkpoint(
+        certificate: CheckpointCertificate,
+        validator_keys: &HashMap<[u8; 32], BlsPublicKey>,
+        threshold: usize,
+    ) -> Result<Self, CheckpointError> {
+        if !certificate.verify(validator_keys, threshold) {
+            return Err(CheckpointError::InvalidCertificate);
+        }
+        Ok(Self::bootstrap(certificate))
+    }
+
+    fn bootstrap(certificate: CheckpointCertificate) -> Self {
+        let mut dag = Self::new();
+        dag.round = certificate.checkpoint.round;
+        dag.frontier = certificate.checkpoint.frontier.iter().copied().collect();
+        dag.heads = dag.frontier.clone();
+        dag.checkpoint = Some(certificate);
+        dag
+    }
+
+    /// Validate and add an event created or received locally
+    pub fn add_event(&mut self, event: GossipEvent) -> Result<EventAdmission, GossipRejection> {
+        self.admit(event, None)
+    }
+
+    /// Validate and add an event relayed by `peer`
+    ///
+    /// Rejections that are the peer's fault are queued for
+    /// [`take_peer_penalties`](Self::take_peer_penalties).
+    pub fn add_event_from(
+        &mut self,
+        peer: [u8; 32],
+        event: GossipEvent,
+    ) -> Result<EventAdmission, GossipRejection> {
+        self.admit(event, Some(peer))
+    }
+
+    fn admit(
+        &mut self,
+        event: GossipEvent,
+        from: Option<[u8; 32]>,
+    ) -> Result<EventAdmission, GossipRejection> {
+        let result = self.check_event(&event).and_then(|()| {
+            let missing: Vec<[u8; 32]> = event.parents().filter(|p| !self.is_known(p)).collect();
+            if missing.is_empty() {
+                self.insert(event)
+            } else {
+                self.buffer(event, from, missing)
+            }
+        });
+        if let Err(rejection) = &result {
+            self.reject(rejection.clone(), from);
         }
+        result
     }
 
-    pub fn add_event(&mut self, event: GossipEvent) {
-        // Remove parents from heads
-        if let Some(p) = event.self_parent {
-            self.heads.remove(&p);
+    /// Checks that need no parents: identity, signature, depth
+    fn check_event(&self, event: &GossipEvent) -> Result<(), GossipRejection> {
+        if self.is_known(&event.id) || self.orphans.contains_key(&event.id) {
+            return Err(GossipRejection::Duplicate);
+        }
+        let creator = self
+            .creators
+            .get(&event.creator_id)
+            .ok_or(GossipRejection::UnknownCreator)?;
+        if !event.verify_signature(creator) {
+            return Err(GossipRejection::InvalidSignature);
         }
-        if let Some(p) = event.other_parent {
-            self.heads.remove(&p);
+        let max = self.round.saturating_add(self.limits.max_depth);
+        if event.round > max {
+            return Err(GossipRejection::TooDeep {
+                round: event.round,
+                max,
+            });
+        }
+        Ok(())
+    }
+
+    /// Place an event, then admit orphans it unblocked
+    fn insert(&mut self, event: GossipEvent) -> Result<EventAdmission, GossipRejection> {
+        let id = event.id;
+        self.place(event)?;
+
+        // Admit orphans waiting on this event, and on those in turn
+        let mut released = Vec::new();
+        let mut unblocked = vec![id];
+        while let Some(parent) = unblocked.pop() {
+            for orphan_id in self.waiting.remove(&parent).unwrap_or_default() {
+                // Pruned orphans leave stale entries behind
+                let Some(orphan) = self.orphans.remove(&orphan_id) else {
+                    continue;
+                };
+                if self.resume(orphan) {
+                    self.metrics.released += 1;
+                    released.push(orphan_id);
+                    unblocked.push(orphan_id);
+                }
+            }
+        }
+
+        Ok(EventAdmission::Accepted { released })
+    }
+
+    /// Retry an orphan whose parent arrived; true if it was placed
+    ///
+    /// Its signature was checked before buffering.
+    fn resume(&mut self, orphan: Orphan) -> bool {
+        let missing: Vec<[u8; 32]> = orphan
+            .event
+            .parents()
+            .filter(|p| !self.is_known(p))
+            .collect();
+        let result = if missing.is_empty() {
+            self.place(orphan.event).map(|()| true)
+        } else {
+            self.buffer(orphan.event, orphan.from, missing)
+                .map(|_| false)
+        };
+        result.unwrap_or_else(|rejection| {
+            self.reject(rejection, orphan.from);
+            false
+        })
+    }
+
+    /// Check ancestry and the creator's round budget, then link the event
+    fn place(&mut self, event: GossipEvent) -> Result<(), GossipRejection> {
+        self.check_ancestry(&event)?;
+        let meta = self.compute_meta(&event);
+        let count = self
+            .per_round
+            .entry((event.creator_id, meta.round))
+            .or_insert(0);
+        if *count >= self.limits.max_events_per_round {
+            return Err(GossipRejection::RateLimited { round: meta.round });
+        }
+        *count += 1;
+
+        self.link(event, meta);
+        self.metrics.accepted += 1;
+        Ok(())
+    }
+
+    /// A self-parent held in memory must belong to the same creator
+    ///
+    /// Parents pruned behind a checkpoint frontier are accepted as-is.
+    fn check_ancestry(&self, event: &GossipEvent) -> Result<(), GossipRejection> {
+        if let Some(parent) = event.self_parent.and_then(|p| self.events.get(&p)) {
+            if parent.creator_id != event.creator_id {
+                return Err(GossipRejection::InvalidAncestry);
+            }
+        }
+        Ok(())
+    }
+
+    /// Round, witness flag and ancestry of an event about to be linked
+    ///
+    /// Parents pruned behind the checkpoint count as the checkpoint round
+    /// and contribute no ancestry.
+    fn compute_meta(&self, event: &GossipEvent) -> EventMeta {
+        let base = self.base_round();
+        let self_parent = event.self_parent.and_then(|p| self.meta.get(&p));
+        let seq = self_parent.map(|m| m.seq + 1).unwrap_or(0);
+
+        let mut round = None;
+        let mut last_ancestors: HashMap<[u8; 32], u64> = HashMap::new();
+        for parent in event.parents() {
+            let Some(meta) = self.meta.get(&parent) else {
+                round = round.max(Some(base));
+                continue;
+            };
+            round = round.max(Some(meta.round));
+            for (creator, s) in &meta.last_ancestors {
+                let entry = last_ancestors.entry(*creator).or_insert(*s);
+                *entry = (*entry).max(*s);
+            }
+        }
+        last_ancestors.insert(event.creator_id, seq);
+        let mut round = round.unwrap_or(base);
+
+        if self.sees_supermajority(&event.creator_id, &last_ancestors, round) {
+            round += 1;
+        }
+        let witness = self_parent.map_or(true, |m| m.round < round);
+
+        EventMeta {
+            round,
+            witness,
+            seq,
+            last_ancestors,
+            first_descendants: HashMap::new(),
+        }
+    }
+
+    /// Whether a new event strongly sees `round` witnesses from more than
+    /// two thirds of the creators
+    fn sees_supermajority(
+        &self,
+        creator: &[u8; 32],
+        last_ancestors: &HashMap<[u8; 32], u64>,
+        round: u64,
+    ) -> bool {
+        let Some(witnesses) = self.witnesses.get(&round) else {
+            return false;
+        };
+        let seen: HashSet<[u8; 32]> = witnesses
+            .iter()
+            .filter_map(|id| Some((self.events.get(id)?, self.meta.get(id)?)))
+            .filter(|(witness, meta)| {
+                self.strongly_sees(creator, last_ancestors, &witness.creator_id, meta)
+            })
+            .map(|(witness, _)| witness.creator_id)
+            .collect();
+        self.is_supermajority(seen.len())
+    }
+
+    /// Whether a new event reaches `witness` through ancestors of more than
+    /// two thirds of the creators
+    fn strongly_sees(
+        &self,
+        creator: &[u8; 32],
+        last_ancestors: &HashMap<[u8; 32], u64>,
+        witness_creator: &[u8; 32],
+        witness: &EventMeta,
+    ) -> bool {
+        // The new event is not yet among the witness's descendants
+        let sees = last_ancestors
+            .get(witness_creator)
+            .is_some_and(|s| *s >= witness.seq);
+        let through = self
+            .creators
+            .keys()
+            .filter(|c| {
+                if *c == creator {
+                    return sees || witness.first_descendants.contains_key(*c);
+                }
+                match (witness.first_descendants.get(*c), last_ancestors.get(*c)) {
+                    (Some(first), Some(last)) => first <= last,
+                    _ => false,
+                }
+            })
+            .count();
+        self.is_supermajority(through)
+    }
+
+    fn is_supermajority(&self, count: usize) -> bool {
+        3 * count > 2 * self.creators.len()
+    }
+
+    /// Round that events without known ancestry start from
+    fn base_round(&self) -> u64 {
+        self.checkpoint
+            .as_ref()
+            .map(|c| c.checkpoint.round)
+            .unwrap_or(0)
+    }
+
+    /// Insert a validated event and update heads, ancestry and round
+    fn link(&mut self, event: GossipEvent, mut meta: EventMeta) {
+        for parent in event.parents() {
+            self.heads.remove(&parent);
         }
 
         let id = event.id;
         self.heads.insert(id);
 
-        if event.round > self.round {
-            self.round = event.round;
+        // The event is the first descendant from its creator of every
+        // ancestor not already reached by an earlier one
+        meta.first_descendants.insert(event.creator_id, meta.seq);
+        let mut stack: Vec<[u8; 32]> = event.parents().collect();
+        while let Some(ancestor) = stack.pop() {
+            let Some(ancestor_meta) = self.meta.get_mut(&ancestor) else {
+                continue;
+            };
+            if ancestor_meta
+                .first_descendants
+                .contains_key(&event.creator_id)
+            {
+                continue;
+            }
+            ancestor_meta
+                .first_descendants
+                .insert(event.creator_id, meta.seq);
+            if let Some(parent) = self.events.get(&ancestor) {
+                stack.extend(parent.parents());
+            }
         }
 
+        if meta.round > self.round {
+            self.round = meta.round;
+        }
+        if meta.witness {
+            self.witnesses.entry(meta.round).or_default().push(id);
+        }
+
+        self.meta.insert(id, meta);
         self.events.insert(id, event);
+        self.persist_event(&id);
+    }
+
+    fn buffer(
+        &mut self,
+        event: GossipEvent,
+        from: Option<[u8; 32]>,
+        missing: Vec<[u8; 32]>,
+    ) -> Result<EventAdmission, GossipRejection> {
+        let per_creator = self
+            .orphans
+            .values()
+            .filter(|o| o.event.creator_id == event.creator_id)
+            .count();
+        if self.orphans.len() >= self.limits.max_orphans
+            || per_creator >= self.limits.max_orphans_per_creator
+        {
+            return Err(GossipRejection::OrphanBufferFull);
+        }
+
+        let id = event.id;
+        self.waiting.entry(missing[0]).or_default().push(id);
+        self.orphans.insert(id, Orphan { event, from });
+        self.metrics.buffered += 1;
+        Ok(EventAdmission::Buffered { missing })
+    }
+
+    fn reject(&mut self, rejection: GossipRejection, from: Option<[u8; 32]>) {
+        self.metrics.count(&rejection);
+        if let Some(peer) = from {
+            if rejection.penalizes_sender() {
+                self.penalties.push((peer, rejection));
+            }
+        }
+    }
+
+    /// Drain queued peer penalties for the peer scorer
+    pub fn take_peer_penalties(&mut self) -> Vec<([u8; 32], GossipRejection)> {
+        std::mem::take(&mut self.penalties)
+    }
+
+    /// Admission counters
+    pub fn metrics(&self) -> &GossipMetrics {
+        &self.metrics
+    }
+
+    /// Number of events waiting for parents
+    pub fn orphan_count(&self) -> usize {
+        self.orphans.len()
     }
 
     pub fn get_event(&self, id: &[u8; 32]) -> Option<&GossipEvent> {
@@ -61,12 +1060,229 @@ impl GossipDag {
         self.round
     }
 
+    /// Locally computed round of an event held in memory
+    pub fn round_of(&self, id: &[u8; 32]) -> Option<u64> {
+        self.meta.get(id).map(|m| m.round)
+    }
+
+    /// Whether an event held in memory is the first of its creator's
+    /// events in its round
+    pub fn is_witness(&self, id: &[u8; 32]) -> bool {
+        self.meta.get(id).is_some_and(|m| m.witness)
+    }
+
+    /// Number of rounds created so far (highest computed round plus one)
+    pub fn rounds_created(&self) -> u64 {
+        self.witnesses
+            .keys()
+            .next_back()
+            .map(|r| r + 1)
+            .unwrap_or(0)
+    }
+
+    /// Witness event IDs of a round, sorted
+    pub fn witnesses(&self, round: u64) -> Vec<[u8; 32]> {
+        let mut witnesses = self.witnesses.get(&round).cloned().unwrap_or_default();
+        witnesses.sort();
+        witnesses
+    }
+
+    /// Computed round of an event held in memory
+    fn event_round(&self, event: &GossipEvent) -> u64 {
+        self.round_of(&event.id)
+            .unwrap_or_else(|| self.base_round())
+    }
+
     pub fn head_events(&self) -> Vec<&GossipEvent> {
         self.heads
             .iter()
             .filter_map(|id| self.events.get(id))
             .collect()
     }
+
+    /// Number of events held in memory
+    pub fn event_count(&self) -> usize {
+        self.events.len()
+    }
+
+    /// Whether an event is held or was pruned behind a checkpoint frontier
+    pub fn is_known(&self, id: &[u8; 32]) -> bool {
+        self.events.contains_key(id) || self.frontier.contains(id)
+    }
+
+    /// Latest accepted checkpoint certificate
+    pub fn latest_checkpoint(&self) -> Option<&CheckpointCertificate> {
+        self.checkpoint.as_ref()
+    }
+
+    /// Build the checkpoint for `round` from local history
+    ///
+    /// Events are hashed in (round, id) order so every honest node derives
+    /// the same state root.
+    pub fn create_checkpoint(&self, round: u64) -> Checkpoint {
+        let previous = self.checkpoint.as_ref().map(|c| c.checkpoint.hash());
+        let state_root = Checkpoint::compute_state_root(
+            self.checkpoint.as_ref().map(|c| &c.checkpoint.state_root),
+            self.covered_events(round).iter().map(|(_, e)| &e.id),
+        );
+
+        // Latest event per creator at or below the checkpoint round
+        let mut latest: BTreeMap<[u8; 32], (u64, &GossipEvent)> = BTreeMap::new();
+        for event in self.events.values() {
+            let event_round = self.event_round(event);
+            if event_round > round {
+                continue;
+            }
+            let key = (event_round, event.timestamp, event.id);
+            let entry = latest
+                .entry(event.creator_id)
+                .or_insert((event_round, event));
+            if key > (entry.0, entry.1.timestamp, entry.1.id) {
+                *entry = (event_round, event);
+            }
+        }
+
+        Checkpoint {
+            round,
+            state_root,
+            frontier: latest.values().map(|(_, e)| e.id).collect(),
+            previous,
+        }
+    }
+
+    /// Events after the current checkpoint up to `round`, in (round, id) order
+    fn covered_events(&self, round: u64) -> Vec<(u64, &GossipEvent)> {
+        let from_round = self
+            .checkpoint
+            .as_ref()
+            .map(|c| c.checkpoint.round + 1)
+            .unwrap_or(0);
+
+        let mut covered: Vec<(u64, &GossipEvent)> = self
+            .events
+            .values()
+            .map(|e| (self.event_round(e), e))
+            .filter(|(r, _)| *r >= from_round && *r <= round)
+            .collect();
+        covered.sort_by(|a, b| a.0.cmp(&b.0).then(a.1.id.cmp(&b.1.id)));
+        covered
+    }
+
+    /// Archive the rounds `certificate` closes, before they are pruned
+    ///
+    /// Export before applying the certificate: the archive covers events
+    /// after the current checkpoint.
+    pub fn export_archive(&self, certificate: &CheckpointCertificate) -> GossipArchive {
+        let events: Vec<ArchivedEvent> = self
+            .covered_events(certificate.checkpoint.round)
+            .into_iter()
+            .map(|(round, event)| ArchivedEvent {
+                round,
+                event: event.clone(),
+            })
+            .collect();
+        let creator_ids: BTreeSet<[u8; 32]> = events.iter().map(|e| e.event.creator_id).collect();
+
+        GossipArchive {
+            certificate: certificate.clone(),
+            previous_state_root: self.checkpoint.as_ref().map(|c| c.checkpoint.state_root),
+            events,
+            creators: creator_ids
+                .iter()
+                .filter_map(|id| self.creators.get(id).cloned())
+                .collect(),
+        }
+    }
+
+    /// Accept a certificate and prune history behind it
+    ///
+    /// The certificate must verify against `validator_keys` with at least
+    /// `threshold` signers, advance past and chain onto the current
+    /// checkpoint, and not cover rounds this DAG has not reached. Returns
+    /// the number of events pruned. With a store attached, the closed
+    /// rounds are archived first.
+    pub fn apply_checkpoint(
+        &mut self,
+        certificate: CheckpointCertificate,
+        validator_keys: &HashMap<[u8; 32], BlsPublicKey>,
+        threshold: usize,
+        retain_rounds: u64,
+    ) -> Result<usize, CheckpointError> {
+        let round = certificate.checkpoint.round;
+        if let Some(current) = &self.checkpoint {
+            if round <= current.checkpoint.round {
+                return Err(CheckpointError::Stale {
+                    round,
+                    current: current.checkpoint.round,
+                });
+            }
+        }
+        if round > self.round {
+            return Err(CheckpointError::AheadOfDag {
+                round,
+                current: self.round,
+            });
+        }
+        let previous = self.checkpoint.as_ref().map(|c| c.checkpoint.hash());
+        if certificate.checkpoint.previous != previous {
+            return Err(CheckpointError::BrokenChain);
+        }
+        if !certificate.verify(validator_keys, threshold) {
+            return Err(CheckpointError::InvalidCertificate);
+        }
+
+        if let Some(store) = &self.store {
+            let archive = self.export_archive(&certificate);
+            store.put_archive(certificate.checkpoint.round, archive.to_bytes());
+        }
+
+        // Older frontiers are behind this checkpoint and no longer parents
+        let prune_below = round.saturating_sub(retain_rounds);
+        self.frontier = certificate.checkpoint.frontier.iter().copied().collect();
+        self.checkpoint = Some(certificate);
+        self.persist_state();
+        Ok(self.prune_before(prune_below))
+    }
+
+    /// Drop events older than `round`, keeping frontier and head events
+    ///
+    /// Rounds are the locally computed ones. Orphans claiming rounds older
+    /// than `round` are dropped as well.
+    pub fn prune_before(&mut self, round: u64) -> usize {
+        let before = self.events.len();
+        let frontier = &self.frontier;
+        let heads = &self.heads;
+        let meta = &self.meta;
+        let mut pruned = Vec::new();
+        self.events.retain(|id, _| {
+            let keep = meta.get(id).map_or(true, |m| m.round >= round)
+                || frontier.contains(id)
+                || heads.contains(id);
+            if !keep {
+                pruned.push(*id);
+            }
+            keep
+        });
+        if let Some(store) = &self.store {
+            for id in &pruned {
+                store.delete_event(id);
+            }
+        }
+        let events = &self.events;
+        self.meta.retain(|id, _| events.contains_key(id));
+        self.witnesses = std::mem::take(&mut self.witnesses).split_off(&round);
+        for ids in self.witnesses.values_mut() {
+            ids.retain(|id| events.contains_key(id));
+        }
+
+        // Orphans this old will never be admitted
+        let orphans = self.orphans.len();
+        self.orphans.retain(|_, o| o.event.round >= round);
+        self.metrics.orphans_dropped += (orphans - self.orphans.len()) as u64;
+        self.per_round.retain(|(_, r), _| *r >= round);
+
+        before - self.events.len()
+    }
 }
 
 impl Default for GossipDag {
@@ -74,3 +1290,512 @@ impl Default for GossipDag {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rope_crypto::bls::BlsSecretKey;
+
+    fn creator(n: u8) -> (HybridSigner, HybridPublicKey) {
+        HybridSigner::from_seed(&[n; 32])
+    }
+
+    fn signed_event(
+        signer: &(HybridSigner, HybridPublicKey),
+        round: u64,
+        self_parent: Option<[u8; 32]>,
+        other_parent: Option<[u8; 32]>,
+    ) -> GossipEvent {
+        let creator_id = signer.1.node_id();
+        let mut id = [0u8; 32];
+        id[0] = creator_id[0];
+        id[1..9].copy_from_slice(&round.to_le_bytes());
+        id[9..17].copy_from_slice(&creator_id[..8]);
+        let mut event = GossipEvent {
+            id,
+            creator_id,
+            self_parent,
+            other_parent,
+            payload: Vec::new(),
+            timestamp: round,
+            round,
+            signature: Vec::new(),
+        };
+        event.sign(&signer.0);
+        event
+    }
+
+    /// Every step each creator syncs with a rotating peer's previous event
+    ///
+    /// Rounds advance every four steps with three creators and every two
+    /// with two.
+    fn build_dag(creators: u8, steps: u64) -> GossipDag {
+        let mut dag = GossipDag::new();
+        let signers: Vec<_> = (1..=creators).map(creator).collect();
+        for signer in &signers {
+            dag.register_creator(signer.1.clone());
+        }
+        let n = signers.len();
+        let mut previous: Vec<[u8; 32]> = Vec::new();
+        for step in 0..steps {
+            let mut current = Vec::new();
+            for (c, signer) in signers.iter().enumerate() {
+                let (self_parent, other_parent) = if previous.is_empty() {
+                    (None, None)
+                } else {
+                    let peer = (c + 1 + step as usize % (n - 1)) % n;
+                    (Some(previous[c]), Some(previous[peer]))
+                };
+                let e = signed_event(signer, step, self_parent, other_parent);
+                current.push(e.id);
+                dag.add_event(e).unwrap();
+            }
+            previous = current;
+        }
+        dag
+    }
+
+    fn certify(checkpoint: Checkpoint, keys: &[BlsSecretKey]) -> CheckpointCertificate {
+        let hash = checkpoint.hash();
+        let sigs: Vec<BlsSignature> = keys.iter().map(|k| k.sign(&hash)).collect();
+        CheckpointCertificate {
+            checkpoint,
+            signers: (0..keys.len()).map(|i| [i as u8; 32]).collect(),
+            signature: BlsSignature::aggregate(&sigs).unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_checkpoint_deterministic() {
+        let a = build_dag(3, 12);
+        let b = build_dag(3, 12);
+        assert_eq!(a.create_checkpoint(1), b.create_checkpoint(1));
+        assert_ne!(a.create_checkpoint(1).hash(), a.create_checkpoint(2).hash());
+    }
+
+    #[test]
+    fn test_checkpoint_frontier() {
+        let dag = build_dag(3, 12);
+        let checkpoint = dag.create_checkpoint(1);
+        assert_eq!(checkpoint.frontier.len(), 3);
+        for id in &checkpoint.frontier {
+            assert_eq!(dag.round_of(id), Some(1));
+        }
+    }
+
+    #[test]
+    fn test_apply_checkpoint_prunes() {
+        let mut dag = build_dag(3, 12);
+        let keys: Vec<BlsSecretKey> = (0..3).map(|_| BlsSecretKey::generate()).collect();
+        let registry = registry(&keys);
+        let cert = certify(dag.create_checkpoint(2), &keys);
+        let frontier = cert.checkpoint.frontier.clone();
+
+        let pruned = dag.apply_checkpoint(cert, &registry, 3, 1).unwrap();
+
+        // Round 0 (steps 0-3) pruned; frontier (round 2) and heads survive
+        assert_eq!(pruned, 3 * 4);
+        assert_eq!(dag.event_count(), 3 * 8);
+        for id in &frontier {
+            assert!(dag.is_known(id));
+        }
+        assert!(dag.witnesses(0).is_empty());
+        assert_eq!(dag.witnesses(1).len(), 3);
+
+        // Stale certificate is ignored
+        let stale = certify(dag.create_checkpoint(1), &keys);
+        assert_eq!(
+            dag.apply_checkpoint(stale, &registry, 3, 0),
+            Err(CheckpointError::Stale {
+                round: 1,
+                current: 2
+            })
+        );
+        assert_eq!(dag.latest_checkpoint().unwrap().checkpoint.round, 2);
+    }
+
+    #[test]
+    fn test_apply_checkpoint_requires_valid_certificate() {
+        let mut dag = build_dag(3, 12);
+        let keys: Vec<BlsSecretKey> = (0..4).map(|_| BlsSecretKey::generate()).collect();
+        let registry = registry(&keys);
+        let events = dag.event_count();
+
+        // Signed by keys outside the validator set
+        let outsiders: Vec<BlsSecretKey> = (0..4).map(|_| BlsSecretKey::generate()).collect();
+        let forged = certify(dag.create_checkpoint(2), &outsiders);
+        assert_eq!(
+            dag.apply_checkpoint(forged, &registry, 3, 0),
+            Err(CheckpointError::InvalidCertificate)
+        );
+
+        // Below the threshold
+        let short = certify(dag.create_checkpoint(2), &keys[..2]);
+        assert_eq!(
+            dag.apply_checkpoint(short, &registry, 3, 0),
+            Err(CheckpointError::InvalidCertificate)
+        );
+
+        // Past the local round
+        let ahead = certify(dag.create_checkpoint(9), &keys);
+        assert_eq!(
+            dag.apply_checkpoint(ahead, &registry, 3, 0),
+            Err(CheckpointError::AheadOfDag {
+                round: 9,
+                current: 2
+            })
+        );
+        assert_eq!(dag.event_count(), events);
+        assert!(dag.latest_checkpoint().is_none());
+
+        // Each checkpoint must chain onto the last, and only its frontier
+        // stays known once older events are pruned
+        let first = certify(dag.create_checkpoint(1), &keys);
+        let first_frontier = first.checkpoint.frontier.clone();
+        let unchained = certify(dag.create_checkpoint(2), &keys);
+        dag.apply_checkpoint(first, &registry, 3, 0).unwrap();
+        assert_eq!(
+            dag.apply_checkpoint(unchained, &registry, 3, 0),
+            Err(CheckpointError::BrokenChain)
+        );
+
+        let second = certify(dag.create_checkpoint(2), &keys);
+        let second_frontier = second.checkpoint.frontier.clone();
+        dag.apply_checkpoint(second, &registry, 3, 0).unwrap();
+        assert!(first_frontier.iter().all(|id| !dag.is_known(id)));
+        assert!(second_frontier.iter().all(|id| dag.is_known(id)));
+    }
+
+    #[test]
+    fn test_certificate_verification() {
+        let dag = build_dag(2, 4);
+        let keys: Vec<BlsSecretKey> = (0..4).map(|_| BlsSecretKey::generate()).collect();
+        let registry: HashMap<[u8; 32], BlsPublicKey> = keys
+            .iter()
+            .enumerate()
+            .map(|(i, k)| ([i as u8; 32], k.public_key()))
+            .collect();
+
+        let cert = certify(dag.create_checkpoint(3), &keys[..3]);
+        assert!(cert.verify(&registry, 3));
+        assert!(!cert.verify(&registry, 4));
+
+        let mut tampered = cert.clone();
+        tampered.checkpoint.round = 2;
+        assert!(!tampered.verify(&registry, 3));
+    }
+
+    #[test]
+    fn test_bootstrap_from_checkpoint() {
+        let source = build_dag(2, 6);
+        let keys: Vec<BlsSecretKey> = (0..2).map(|_| BlsSecretKey::generate()).collect();
+        let cert = certify(source.create_checkpoint(2), &keys);
+        let frontier = cert.checkpoint.frontier.clone();
+
+        let mut tampered = cert.clone();
+        tampered.checkpoint.frontier.pop();
+        assert!(matches!(
+            GossipDag::from_checkpoint(tampered, &registry(&keys), 2),
+            Err(CheckpointError::InvalidCertificate)
+        ));
+        assert!(matches!(
+            GossipDag::from_checkpoint(cert.clone(), &registry(&keys), 3),
+            Err(CheckpointError::InvalidCertificate)
+        ));
+
+        let mut dag = GossipDag::from_checkpoint(cert, &registry(&keys), 2).unwrap();
+        assert_eq!(dag.current_round(), 2);
+        assert_eq!(dag.event_count(), 0);
+        assert!(frontier.iter().all(|id| dag.is_known(id)));
+
+        // Pruned parents count as the checkpoint round
+        let alice = creator(1);
+        dag.register_creator(alice.1.clone());
+        let e = signed_event(&alice, 6, Some(frontier[0]), None);
+        dag.add_event(e.clone()).unwrap();
+        assert_eq!(dag.round_of(&e.id), Some(2));
+        assert_eq!(dag.witnesses(2), vec![e.id]);
+        assert_eq!(dag.current_round(), 2);
+    }
+
+    #[test]
+    fn test_rounds_computed_locally() {
+        let mut dag = build_dag(3, 12);
+        assert_eq!(dag.rounds_created(), 3);
+        assert_eq!(dag.current_round(), 2);
+        for round in 0..3 {
+            let witnesses = dag.witnesses(round);
+            assert_eq!(witnesses.len(), 3);
+            for id in &witnesses {
+                assert_eq!(dag.round_of(id), Some(round));
+                assert!(dag.is_witness(id));
+                // First step of each round
+                assert_eq!(dag.get_event(id).unwrap().round, round * 4);
+            }
+        }
+        assert!(dag.witnesses(3).is_empty());
+
+        // Later events of a round are not witnesses
+        let earlier = dag
+            .head_events()
+            .iter()
+            .map(|e| e.self_parent.unwrap())
+            .next()
+            .unwrap();
+        assert_eq!(dag.round_of(&earlier), Some(2));
+        assert!(!dag.is_witness(&earlier));
+
+        // A claimed round is not trusted
+        let alice = creator(1);
+        dag.register_creator(alice.1.clone());
+        let head = dag
+            .head_events()
+            .into_iter()
+            .find(|e| e.creator_id == alice.1.node_id())
+            .unwrap()
+            .id;
+        let e = signed_event(&alice, 500, Some(head), None);
+        dag.add_event(e.clone()).unwrap();
+        assert_eq!(dag.round_of(&e.id), Some(2));
+        assert!(!dag.is_witness(&e.id));
+        assert_eq!(dag.rounds_created(), 3);
+    }
+
+    fn registry(keys: &[BlsSecretKey]) -> HashMap<[u8; 32], BlsPublicKey> {
+        keys.iter()
+            .enumerate()
+            .map(|(i, k)| ([i as u8; 32], k.public_key()))
+            .collect()
+    }
+
+    #[test]
+    fn test_restore_from_store() {
+        let mut dag = build_dag(3, 12);
+        let store = Arc::new(GossipStore::new());
+        dag.attach_store(store.clone());
+        assert_eq!(store.event_count(), 36);
+
+        let keys: Vec<BlsSecretKey> = (0..3).map(|_| BlsSecretKey::generate()).collect();
+        let cert = certify(dag.create_checkpoint(2), &keys);
+        dag.apply_checkpoint(cert, &registry(&keys), 3, 1).unwrap();
+        assert_eq!(store.event_count(), dag.event_count());
+        assert_eq!(store.archive_rounds(), vec![2]);
+
+        let mut restored = GossipDag::restore(store.clone()).unwrap();
+        assert_eq!(restored.event_count(), dag.event_count());
+        assert_eq!(restored.current_round(), dag.current_round());
+        assert_eq!(restored.rounds_created(), dag.rounds_created());
+        assert_eq!(restored.witnesses(1), dag.witnesses(1));
+        assert_eq!(restored.latest_checkpoint().unwrap().checkpoint.round, 2);
+        for event in dag.head_events() {
+            assert_eq!(restored.round_of(&event.id), dag.round_of(&event.id));
+        }
+
+        // Still writing through after the restart
+        let alice = creator(1);
+        restored.register_creator(alice.1.clone());
+        let head = restored
+            .head_events()
+            .into_iter()
+            .find(|e| e.creator_id == alice.1.node_id())
+            .unwrap()
+            .id;
+        restored
+            .add_event(signed_event(&alice, 12, Some(head), None))
+            .unwrap();
+        assert_eq!(store.event_count(), dag.event_count() + 1);
+        let again = GossipDag::restore(store).unwrap();
+        assert_eq!(again.event_count(), restored.event_count());
+    }
+
+    #[test]
+    fn test_archive_verifies_offline() {
+        let mut dag = build_dag(3, 12);
+        let keys: Vec<BlsSecretKey> = (0..3).map(|_| BlsSecretKey::generate()).collect();
+        let registry = registry(&keys);
+
+        let first_cert = certify(dag.create_checkpoint(1), &keys);
+        let first = dag.export_archive(&first_cert);
+        dag.apply_checkpoint(first_cert, &registry, 3, 5).unwrap();
+        let second = dag.export_archive(&certify(dag.create_checkpoint(2), &keys));
+
+        // Rounds 0-1 are steps 0-7, round 2 steps 8-11
+        assert_eq!(first.events.len(), 24);
+        assert_eq!(second.events.len(), 12);
+        let decoded = GossipArchive::from_bytes(&second.to_bytes()).unwrap();
+        assert_eq!(decoded.verify(&registry, 3), Ok(()));
+        assert_eq!(first.verify(&registry, 3), Ok(()));
+        assert!(second.verify_follows(&first));
+        assert!(!first.verify_follows(&second));
+        assert_eq!(
+            second.verify(&registry, 4),
+            Err(ArchiveError::InvalidCertificate)
+        );
+
+        let mut missing = second.clone();
+        missing.events.remove(3);
+        assert_eq!(
+            missing.verify(&registry, 3),
+            Err(ArchiveError::StateRootMismatch)
+        );
+
+        let mut forged = second.clone();
+        forged.events[0].event.payload = b"rewritten".to_vec();
+        assert_eq!(
+            forged.verify(&registry, 3),
+            Err(ArchiveError::InvalidSignature(forged.events[0].event.id))
+        );
+
+        let mut shuffled = second;
+        shuffled.events.swap(0, 1);
+        assert_eq!(shuffled.verify(&registry, 3), Err(ArchiveError::Unordered));
+    }
+
+    #[test]
+    fn test_policy() {
+        let policy = CheckpointPolicy::default();
+        assert!(!policy.is_checkpoint_round(0));
+        assert!(policy.is_checkpoint_round(100));
+        assert!(!policy.is_checkpoint_round(150));
+    }
+
+    #[test]
+    fn test_rejects_unsigned_and_unknown_creators() {
+        let mut dag = GossipDag::new();
+        let alice = creator(1);
+
+        // Not registered yet
+        let e = signed_event(&alice, 0, None, None);
+        assert_eq!(
+            dag.add_event(e.clone()),
+            Err(GossipRejection::UnknownCreator)
+        );
+
+        dag.register_creator(alice.1.clone());
+        let mut unsigned = e.clone();
+        unsigned.signature.clear();
+        assert_eq!(
+            dag.add_event(unsigned),
+            Err(GossipRejection::InvalidSignature)
+        );
+        let mut tampered = e.clone();
+        tampered.payload = b"injected".to_vec();
+        assert_eq!(
+            dag.add_event(tampered),
+            Err(GossipRejection::InvalidSignature)
+        );
+
+        assert!(dag.add_event(e.clone()).is_ok());
+        assert_eq!(dag.add_event(e), Err(GossipRejection::Duplicate));
+        assert_eq!(dag.metrics().invalid_signature, 2);
+        assert_eq!(dag.metrics().accepted, 1);
+    }
+
+    #[test]
+    fn test_orphans_released_when_parents_arrive() {
+        let mut dag = GossipDag::new();
+        let alice = creator(1);
+        let bob = creator(2);
+        dag.register_creator(alice.1.clone());
+        dag.register_creator(bob.1.clone());
+
+        let a0 = signed_event(&alice, 0, None, None);
+        let b0 = signed_event(&bob, 0, None, None);
+        let a1 = signed_event(&alice, 1, Some(a0.id), Some(b0.id));
+        let a2 = signed_event(&alice, 2, Some(a1.id), None);
+
+        // Delivered children first
+        assert!(matches!(
+            dag.add_event(a2.clone()),
+            Ok(EventAdmission::Buffered { .. })
+        ));
+        assert_eq!(
+            dag.add_event(a1.clone()),
+            Ok(EventAdmission::Buffered {
+                missing: vec![a0.id, b0.id]
+            })
+        );
+        assert_eq!(dag.orphan_count(), 2);
+
+        // a1 still waits on b0
+        assert_eq!(
+            dag.add_event(a0.clone()),
+            Ok(EventAdmission::Accepted { released: vec![] })
+        );
+        assert_eq!(dag.orphan_count(), 2);
+
+        assert_eq!(
+            dag.add_event(b0),
+            Ok(EventAdmission::Accepted {
+                released: vec![a1.id, a2.id]
+            })
+        );
+        assert_eq!(dag.orphan_count(), 0);
+        assert_eq!(dag.event_count(), 4);
+        let heads: Vec<[u8; 32]> = dag.head_events().iter().map(|e| e.id).collect();
+        assert_eq!(heads, vec![a2.id]);
+    }
+
+    #[test]
+    fn test_limits_and_peer_penalties() {
+        let mut dag = GossipDag::new().with_limits(GossipLimits {
+            max_depth: 10,
+            max_events_per_round: 2,
+            max_orphans: 1,
+            max_orphans_per_creator: 1,
+        });
+        let alice = creator(1);
+        let bob = creator(2);
+        dag.register_creator(alice.1.clone());
+        dag.register_creator(bob.1.clone());
+        let peer = [9u8; 32];
+
+        // Far-future round
+        let deep = signed_event(&alice, 11, None, None);
+        assert_eq!(
+            dag.add_event_from(peer, deep),
+            Err(GossipRejection::TooDeep { round: 11, max: 10 })
+        );
+
+        // Two events per round, then rate limited
+        let a0 = signed_event(&alice, 0, None, None);
+        let mut second = a0.clone();
+        second.id[31] = 1;
+        second.sign(&alice.0);
+        let mut third = a0.clone();
+        third.id[31] = 2;
+        third.sign(&alice.0);
+        dag.add_event_from(peer, a0.clone()).unwrap();
+        dag.add_event_from(peer, second).unwrap();
+        assert_eq!(
+            dag.add_event_from(peer, third),
+            Err(GossipRejection::RateLimited { round: 0 })
+        );
+
+        // Self-parent created by someone else
+        let forged = signed_event(&bob, 1, Some(a0.id), None);
+        assert_eq!(
+            dag.add_event_from(peer, forged),
+            Err(GossipRejection::InvalidAncestry)
+        );
+
+        // Orphan buffer bound; a full buffer is not the peer's fault
+        dag.add_event_from(peer, signed_event(&bob, 1, Some([7u8; 32]), None))
+            .unwrap();
+        assert_eq!(
+            dag.add_event_from(peer, signed_event(&bob, 2, Some([8u8; 32]), None)),
+            Err(GossipRejection::OrphanBufferFull)
+        );
+
+        let penalties = dag.take_peer_penalties();
+        assert_eq!(penalties.len(), 3);
+        assert!(penalties.iter().all(|(p, _)| *p == peer));
+        assert!(dag.take_peer_penalties().is_empty());
+
+        let metrics = dag.metrics();
+        assert_eq!(metrics.too_deep, 1);
+        assert_eq!(metrics.rate_limited, 1);
+        assert_eq!(metrics.invalid_ancestry, 1);
+        assert_eq!(metrics.orphans_dropped, 1);
+    }
+}
@@ -14,5 +14,8 @@ pub mod regeneration;
 // Re-exports
 pub use erasure::*;
 pub use federation::*;
-pub use gossip::{GossipDag, GossipEvent};
+pub use gossip::{
+    Checkpoint, CheckpointCertificate, CheckpointError, CheckpointPolicy, GossipArchive, GossipDag,
+    GossipEvent,
+};
 pub use regeneration::*;
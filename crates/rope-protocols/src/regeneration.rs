@@ -28,10 +28,12 @@
 //! The protocol includes active damage detection:
 //! - **Checksum Verification**: Per-segment checksums for localized detection
 //! - **Complement Comparison**: Compare primary and complement strands
-//! - **Periodic Scanning**: Background integrity scans
+//! - **Periodic Scanning**: Background integrity scans and repair ([`IntegrityScrubber`])
 //! - **Access-Time Detection**: Detect corruption on read operations
 
 use parking_lot::RwLock;
+use rope_crypto::hash::merkle;
+use rope_crypto::hybrid::{HybridPublicKey, HybridSignature, HybridSigner, HybridVerifier};
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
@@ -146,11 +148,19 @@ pub struct RepairRequest {
 
     /// Retry count
     pub retry_count: u32,
+
+    /// Commitment to the intact content; repair data must prove against it
+    pub expected_root: ContentRoot,
 }
 
 impl RepairRequest {
     /// Create new repair request
-    pub fn new(string_id: [u8; 32], damage_type: DamageType, requester_id: [u8; 32]) -> Self {
+    pub fn new(
+        string_id: [u8; 32],
+        damage_type: DamageType,
+        requester_id: [u8; 32],
+        expected_root: ContentRoot,
+    ) -> Self {
         let strategy = damage_type.recommended_strategy();
         let priority = damage_type.severity();
         let timestamp = chrono::Utc::now().timestamp();
@@ -169,10 +179,154 @@ impl RepairRequest {
             timestamp,
             priority,
             retry_count: 0,
+            expected_root,
+        }
+    }
+}
+
+/// Merkle commitment to a string's content, one leaf per segment
+///
+/// Leaves are BLAKE3 hashes of fixed-size segments, padded with zero leaves
+/// to a power of two so every path has the same length.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContentRoot {
+    /// Merkle root over segment hashes
+    pub root: [u8; 32],
+
+    /// Segment size in bytes
+    pub segment_size: usize,
+
+    /// Number of segments in the intact content
+    pub segment_count: usize,
+}
+
+impl ContentRoot {
+    /// Commit to content split into `segment_size` segments
+    pub fn of(content: &[u8], segment_size: usize) -> Self {
+        let leaves: Vec<[u8; 32]> = content
+            .chunks(segment_size.max(1))
+            .map(|segment| *blake3::hash(segment).as_bytes())
+            .collect();
+        Self::from_leaves(&leaves, segment_size)
+    }
+
+    /// Commit to precomputed segment hashes
+    pub fn from_leaves(leaves: &[[u8; 32]], segment_size: usize) -> Self {
+        Self {
+            root: merkle::compute_root(&Self::padded(leaves)),
+            segment_size,
+            segment_count: leaves.len(),
         }
     }
+
+    fn padded(leaves: &[[u8; 32]]) -> Vec<[u8; 32]> {
+        let mut padded = leaves.to_vec();
+        padded.resize(leaves.len().next_power_of_two(), [0u8; 32]);
+        padded
+    }
+
+    /// Path length for a padded tree of this size
+    fn depth(&self) -> usize {
+        self.segment_count.next_power_of_two().trailing_zeros() as usize
+    }
+}
+
+/// Proof binding repair data to a [`ContentRoot`]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RepairProof {
+    /// Segment carried by the repair data, or `None` for full content
+    pub segment: Option<usize>,
+
+    /// Sibling hashes from the segment leaf up to the root
+    pub merkle_path: Vec<[u8; 32]>,
 }
 
+impl RepairProof {
+    /// Proof for the full content (the root is recomputed from the data)
+    pub fn full() -> Self {
+        Self::default()
+    }
+
+    /// Proof for one segment of intact content
+    pub fn for_segment(content: &[u8], segment_size: usize, index: usize) -> Self {
+        let leaves: Vec<[u8; 32]> = content
+            .chunks(segment_size.max(1))
+            .map(|segment| *blake3::hash(segment).as_bytes())
+            .collect();
+        Self {
+            segment: Some(index),
+            merkle_path: merkle::generate_proof(&ContentRoot::padded(&leaves), index),
+        }
+    }
+
+    /// Check repair data against the expected commitment
+    pub fn verify(&self, data: &[u8], expected: &ContentRoot) -> bool {
+        match self.segment {
+            None => ContentRoot::of(data, expected.segment_size) == *expected,
+            Some(index) => {
+                let is_last = index + 1 == expected.segment_count;
+                index < expected.segment_count
+                    && (data.len() == expected.segment_size
+                        || (is_last && !data.is_empty() && data.len() < expected.segment_size))
+                    && self.merkle_path.len() == expected.depth()
+                    && merkle::verify_proof(
+                        *blake3::hash(data).as_bytes(),
+                        &self.merkle_path,
+                        index,
+                        expected.root,
+                    )
+            }
+        }
+    }
+}
+
+/// Why a repair response was rejected
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RepairRejection {
+    /// No pending request with this ID
+    UnknownRequest,
+    /// Response is for a different string than requested
+    WrongString,
+    /// Provider's public key is not known
+    UnknownProvider,
+    /// Content hash does not match the repair data
+    HashMismatch,
+    /// Repair data does not prove against the expected root
+    InvalidProof,
+    /// Provider signature is invalid
+    InvalidSignature,
+}
+
+impl RepairRejection {
+    /// Whether the named provider is provably at fault
+    ///
+    /// Only rejections found after the provider's signature verified count;
+    /// anyone can put another node's ID on an unsigned or forged response.
+    pub fn is_provider_fault(&self) -> bool {
+        matches!(
+            self,
+            RepairRejection::WrongString
+                | RepairRejection::HashMismatch
+                | RepairRejection::InvalidProof
+        )
+    }
+}
+
+impl std::fmt::Display for RepairRejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RepairRejection::UnknownRequest => write!(f, "Unknown repair request"),
+            RepairRejection::WrongString => write!(f, "Response is for a different string"),
+            RepairRejection::UnknownProvider => write!(f, "Unknown repair provider"),
+            RepairRejection::HashMismatch => write!(f, "Content hash mismatch"),
+            RepairRejection::InvalidProof => write!(f, "Invalid repair proof"),
+            RepairRejection::InvalidSignature => write!(f, "Invalid provider signature"),
+        }
+    }
+}
+
+impl std::error::Error for RepairRejection {}
+
 /// Repair response from a peer
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct RepairResponse {
@@ -191,13 +345,52 @@ pub struct RepairResponse {
     /// Content hash for verification
     pub content_hash: [u8; 32],
 
-    /// Provider's signature
+    /// Proof binding the repair data to the expected content root
+    pub proof: RepairProof,
+
+    /// Provider's signature (Ed25519 followed by Dilithium)
     pub signature: Vec<u8>,
 
     /// Timestamp
     pub timestamp: i64,
 }
 
+impl RepairResponse {
+    /// Message covered by the provider's signature
+    pub fn signing_message(&self) -> Vec<u8> {
+        let mut message = b"rope-repair-response-v1".to_vec();
+        message.extend_from_slice(&self.request_id);
+        message.extend_from_slice(&self.string_id);
+        message.extend_from_slice(&self.provider_id);
+        message.extend_from_slice(&self.content_hash);
+        match self.proof.segment {
+            Some(index) => {
+                message.push(1);
+                message.extend_from_slice(&(index as u64).to_be_bytes());
+            }
+            None => message.push(0),
+        }
+        for sibling in &self.proof.merkle_path {
+            message.extend_from_slice(sibling);
+        }
+        message.extend_from_slice(&self.timestamp.to_be_bytes());
+        message
+    }
+
+    /// Sign as the provider
+    pub fn sign(&mut self, signer: &HybridSigner) {
+        self.signature = signer.sign(&self.signing_message()).to_bytes();
+    }
+
+    /// Verify the provider's signature
+    pub fn verify_signature(&self, public_key: &HybridPublicKey) -> bool {
+        let Ok(signature) = HybridSignature::from_bytes(&self.signature) else {
+            return false;
+        };
+        HybridVerifier::verify(public_key, &self.signing_message(), &signature).unwrap_or(false)
+    }
+}
+
 /// Repair result
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum RepairResult {
@@ -239,10 +432,30 @@ pub struct RegenerationCoordinator {
     /// Known providers for strings
     providers: RwLock<HashMap<[u8; 32], HashSet<[u8; 32]>>>,
 
+    /// Provider keys for response verification, by node ID
+    provider_keys: RwLock<HashMap<[u8; 32], HybridPublicKey>>,
+
+    /// Provider repair track records
+    reputation: RwLock<HashMap<[u8; 32], ProviderRecord>>,
+
     /// Statistics
     stats: RwLock<RegenerationStats>,
 }
 
+/// Repair track record of a provider
+#[derive(Clone, Copy, Debug, Default)]
+struct ProviderRecord {
+    successes: u64,
+    failures: u64,
+}
+
+impl ProviderRecord {
+    /// Laplace-smoothed success rate, so unknown providers start at 0.5
+    fn reliability(&self) -> f64 {
+        (self.successes + 1) as f64 / (self.successes + self.failures + 2) as f64
+    }
+}
+
 /// Regeneration statistics
 #[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct RegenerationStats {
@@ -253,6 +466,8 @@ pub struct RegenerationStats {
     pub bytes_recovered: u64,
     pub avg_sources_used: f64,
     pub avg_repair_time_ms: f64,
+    #[serde(default)]
+    pub rejected_responses: u64,
 }
 
 impl RegenerationCoordinator {
@@ -264,6 +479,8 @@ impl RegenerationCoordinator {
             completed: RwLock::new(Vec::new()),
             node_id,
             providers: RwLock::new(HashMap::new()),
+            provider_keys: RwLock::new(HashMap::new()),
+            reputation: RwLock::new(HashMap::new()),
             stats: RwLock::new(RegenerationStats::default()),
         }
     }
@@ -306,7 +523,24 @@ impl RegenerationCoordinator {
     }
 
     /// Add a response to a repair request
+    ///
+    /// Invalid responses are dropped. They count against the provider only
+    /// when its signature verifies; a forged response does not move the
+    /// named provider's score.
     pub fn add_response(&self, response: RepairResponse) -> bool {
+        if let Err(rejection) = self.verify_response(&response) {
+            tracing::warn!(
+                "Rejected repair response from {}: {}",
+                rope_core::NodeId::new(response.provider_id),
+                rejection
+            );
+            if rejection.is_provider_fault() {
+                self.record_provider(&response.provider_id, false);
+            }
+            self.stats.write().rejected_responses += 1;
+            return false;
+        }
+
         let mut responses = self.responses.write();
         if let Some(list) = responses.get_mut(&response.request_id) {
             list.push(response);
@@ -315,6 +549,39 @@ impl RegenerationCoordinator {
         false
     }
 
+    /// Check a response's provider signature, then its hash and repair proof
+    pub fn verify_response(&self, response: &RepairResponse) -> Result<(), RepairRejection> {
+        let request = self
+            .pending_repairs
+            .read()
+            .get(&response.request_id)
+            .cloned()
+            .ok_or(RepairRejection::UnknownRequest)?;
+
+        let keys = self.provider_keys.read();
+        let key = keys
+            .get(&response.provider_id)
+            .ok_or(RepairRejection::UnknownProvider)?;
+        if !response.verify_signature(key) {
+            return Err(RepairRejection::InvalidSignature);
+        }
+        drop(keys);
+
+        if response.string_id != request.string_id {
+            return Err(RepairRejection::WrongString);
+        }
+        if *blake3::hash(&response.repair_data).as_bytes() != response.content_hash {
+            return Err(RepairRejection::HashMismatch);
+        }
+        if !response
+            .proof
+            .verify(&response.repair_data, &request.expected_root)
+        {
+            return Err(RepairRejection::InvalidProof);
+        }
+        Ok(())
+    }
+
     /// Attempt to complete a repair
     pub fn try_complete_repair(&self, request_id: &[u8; 32]) -> Option<RepairResult> {
         let request = self.pending_repairs.read().get(request_id)?.clone();
@@ -334,10 +601,12 @@ impl RegenerationCoordinator {
             .collect();
 
         if valid_responses.is_empty() {
-            return Some(RepairResult::Failed {
+            let result = RepairResult::Failed {
                 string_id: request.string_id,
                 reason: "No valid responses".to_string(),
-            });
+            };
+            self.count_result(&result);
+            return Some(result);
         }
 
         // For multi-source verification, check majority agreement
@@ -354,11 +623,13 @@ impl RegenerationCoordinator {
             RepairStrategy::MultiSourceVerify | RepairStrategy::MultiSourceReconstruct
         ) {
             if *count < (valid_responses.len() + 1) / 2 {
-                return Some(RepairResult::Partial {
+                let result = RepairResult::Partial {
                     string_id: request.string_id,
                     completion_percentage: (*count as f64 / valid_responses.len() as f64) * 100.0,
                     missing_ranges: vec![],
-                });
+                };
+                self.count_result(&result);
+                return Some(result);
             }
         }
 
@@ -374,22 +645,47 @@ impl RegenerationCoordinator {
             sources_used: valid_responses.len(),
         };
 
-        // Update stats
-        {
-            let mut stats = self.stats.write();
-            stats.total_repairs += 1;
-            stats.successful_repairs += 1;
-            stats.bytes_recovered += repair_data.len() as u64;
+        // Credit providers that agreed, count dissent against the rest
+        for r in &valid_responses {
+            self.record_provider(&r.provider_id, &r.content_hash == consensus_hash);
         }
 
         // Move to completed
         self.pending_repairs.write().remove(request_id);
         self.responses.write().remove(request_id);
-        self.completed.write().push(result.clone());
+        self.record_result(result.clone());
 
         Some(result)
     }
 
+    /// Record the outcome of a repair carried out outside this coordinator
+    /// (e.g. by the integrity scrubber)
+    pub fn record_result(&self, result: RepairResult) {
+        self.count_result(&result);
+        self.completed.write().push(result);
+    }
+
+    fn count_result(&self, result: &RepairResult) {
+        let mut stats = self.stats.write();
+        stats.total_repairs += 1;
+        match result {
+            RepairResult::Success {
+                repaired_bytes,
+                sources_used,
+                ..
+            } => {
+                stats.successful_repairs += 1;
+                stats.bytes_recovered += *repaired_bytes as u64;
+                let n = stats.successful_repairs as f64;
+                stats.avg_sources_used += (*sources_used as f64 - stats.avg_sources_used) / n;
+            }
+            RepairResult::Partial { .. } => stats.partial_repairs += 1,
+            RepairResult::Failed { .. } | RepairResult::Unrecoverable { .. } => {
+                stats.failed_repairs += 1
+            }
+        }
+    }
+
     /// Mark repair as failed
     pub fn mark_failed(&self, request_id: &[u8; 32], reason: String) {
         if let Some(request) = self.pending_repairs.write().remove(request_id) {
@@ -400,8 +696,7 @@ impl RegenerationCoordinator {
                 reason,
             };
 
-            self.stats.write().failed_repairs += 1;
-            self.completed.write().push(result);
+            self.record_result(result);
         }
     }
 
@@ -414,6 +709,45 @@ impl RegenerationCoordinator {
             .insert(provider_id);
     }
 
+    /// Register a provider's public key (its node ID is derived from it)
+    pub fn register_provider_key(&self, public_key: HybridPublicKey) {
+        self.provider_keys
+            .write()
+            .insert(public_key.node_id(), public_key);
+    }
+
+    /// Record whether a provider's repair data held up
+    pub fn record_provider(&self, provider_id: &[u8; 32], success: bool) {
+        let mut reputation = self.reputation.write();
+        let record = reputation.entry(*provider_id).or_default();
+        if success {
+            record.successes += 1;
+        } else {
+            record.failures += 1;
+        }
+    }
+
+    /// Share of a provider's repairs that held up (0.5 for unknown providers)
+    pub fn provider_reliability(&self, provider_id: &[u8; 32]) -> f64 {
+        self.reputation
+            .read()
+            .get(provider_id)
+            .copied()
+            .unwrap_or_default()
+            .reliability()
+    }
+
+    /// Providers for a string, most reliable first
+    pub fn ranked_providers(&self, string_id: &[u8; 32]) -> Vec<[u8; 32]> {
+        let mut providers = self.get_providers(string_id);
+        providers.sort_by(|a, b| {
+            self.provider_reliability(b)
+                .total_cmp(&self.provider_reliability(a))
+                .then(a.cmp(b))
+        });
+        providers
+    }
+
     /// Get providers for a string
     pub fn get_providers(&self, string_id: &[u8; 32]) -> Vec<[u8; 32]> {
         self.providers
@@ -471,27 +805,33 @@ mod tests {
     #[test]
     fn test_repair_request() {
         let coord = RegenerationCoordinator::new([1u8; 32]);
+        let (provider, provider_key) = HybridSigner::generate_signing_only();
+        coord.register_provider_key(provider_key.clone());
 
         let damage = DamageType::MismatchError {
             computed: [0u8; 32],
             expected: [1u8; 32],
         };
 
-        let request = RepairRequest::new([2u8; 32], damage, [1u8; 32]);
+        let content = b"repaired content";
+        let root = ContentRoot::of(content, 4096);
+        let request = RepairRequest::new([2u8; 32], damage, [1u8; 32], root);
         let request_id = coord.request_repair(request);
 
         assert_eq!(coord.pending_count(), 1);
 
         // Add response
-        let response = RepairResponse {
+        let mut response = RepairResponse {
             request_id,
             string_id: [2u8; 32],
-            repair_data: b"repaired content".to_vec(),
-            provider_id: [3u8; 32],
-            content_hash: *blake3::hash(b"repaired content").as_bytes(),
+            repair_data: content.to_vec(),
+            provider_id: provider_key.node_id(),
+            content_hash: *blake3::hash(content).as_bytes(),
+            proof: RepairProof::full(),
             signature: vec![],
             timestamp: 0,
         };
+        response.sign(&provider);
 
         assert!(coord.add_response(response));
 
@@ -499,6 +839,112 @@ mod tests {
         let result = coord.try_complete_repair(&request_id);
         assert!(matches!(result, Some(RepairResult::Success { .. })));
         assert_eq!(coord.pending_count(), 0);
+        assert!(coord.provider_reliability(&provider_key.node_id()) > 0.5);
+    }
+
+    #[test]
+    fn test_segment_repair_proof() {
+        let content: Vec<u8> = (0..=255u8).cycle().take(1000).collect();
+        let root = ContentRoot::of(&content, 128);
+        assert_eq!(root.segment_count, 8);
+
+        for index in [0, 3, 7] {
+            let proof = RepairProof::for_segment(&content, 128, index);
+            let segment = content.chunks(128).nth(index).unwrap();
+            assert!(proof.verify(segment, &root));
+        }
+
+        // Wrong segment, wrong index and tampered data are rejected
+        let proof = RepairProof::for_segment(&content, 128, 2);
+        assert!(!proof.verify(&content[..128], &root));
+        let mut tampered = content[256..384].to_vec();
+        tampered[5] ^= 1;
+        assert!(!proof.verify(&tampered, &root));
+        let mut shifted = proof.clone();
+        shifted.segment = Some(3);
+        assert!(!shifted.verify(&content[256..384], &root));
+
+        // Full-content proofs recompute the root
+        assert!(RepairProof::full().verify(&content, &root));
+        assert!(!RepairProof::full().verify(&content[..999], &root));
+
+        // Matches the detector's integrity metadata
+        let integrity = StringIntegrity::new([0u8; 32], &content, 128);
+        assert_eq!(integrity.content_root(), root);
+    }
+
+    #[test]
+    fn test_bad_repairs_rejected_and_down_scored() {
+        let coord = RegenerationCoordinator::new([1u8; 32]);
+        let (honest, honest_key) = HybridSigner::generate_signing_only();
+        let (forger, forger_key) = HybridSigner::generate_signing_only();
+        coord.register_provider_key(honest_key.clone());
+        coord.register_provider_key(forger_key.clone());
+
+        let content = vec![42u8; 300];
+        let request = RepairRequest::new(
+            [2u8; 32],
+            DamageType::TotalLoss,
+            [1u8; 32],
+            ContentRoot::of(&content, 100),
+        );
+        let request_id = coord.request_repair(request);
+
+        let response = |signer: &HybridSigner, key: &HybridPublicKey, data: Vec<u8>| {
+            let mut response = RepairResponse {
+                request_id,
+                string_id: [2u8; 32],
+                content_hash: *blake3::hash(&data).as_bytes(),
+                repair_data: data,
+                provider_id: key.node_id(),
+                proof: RepairProof::full(),
+                signature: vec![],
+                timestamp: 0,
+            };
+            response.sign(signer);
+            response
+        };
+
+        // Consistent hash and valid signature, but not the committed content
+        let forged = response(&forger, &forger_key, vec![0u8; 300]);
+        assert_eq!(
+            coord.verify_response(&forged),
+            Err(RepairRejection::InvalidProof)
+        );
+        assert!(!coord.add_response(forged));
+
+        // Right data claimed under someone else's identity
+        let mut impersonated = response(&forger, &forger_key, content.clone());
+        impersonated.provider_id = honest_key.node_id();
+        assert_eq!(
+            coord.verify_response(&impersonated),
+            Err(RepairRejection::InvalidSignature)
+        );
+
+        // Bad data pinned on the honest provider does not touch its score
+        let before = coord.provider_reliability(&honest_key.node_id());
+        let mut framed = response(&forger, &forger_key, vec![0u8; 300]);
+        framed.provider_id = honest_key.node_id();
+        assert!(!coord.add_response(framed));
+        assert_eq!(coord.provider_reliability(&honest_key.node_id()), before);
+
+        // Unknown provider
+        let (stranger, stranger_key) = HybridSigner::generate_signing_only();
+        assert_eq!(
+            coord.verify_response(&response(&stranger, &stranger_key, content.clone())),
+            Err(RepairRejection::UnknownProvider)
+        );
+
+        assert!(coord.add_response(response(&honest, &honest_key, content.clone())));
+        assert_eq!(coord.stats().rejected_responses, 2);
+        assert!(
+            coord.provider_reliability(&forger_key.node_id())
+                < coord.provider_reliability(&honest_key.node_id())
+        );
+
+        coord.register_provider([2u8; 32], forger_key.node_id());
+        coord.register_provider([2u8; 32], honest_key.node_id());
+        assert_eq!(coord.ranked_providers(&[2u8; 32])[0], honest_key.node_id());
     }
 
     #[test]
@@ -612,6 +1058,12 @@ impl StringIntegrity {
         self.complement_id = Some(complement_id);
         self
     }
+
+    /// Merkle commitment over the segment checksums, for repair requests
+    pub fn content_root(&self) -> ContentRoot {
+        let leaves: Vec<[u8; 32]> = self.segments.iter().map(|s| s.checksum).collect();
+        ContentRoot::from_leaves(&leaves, self.segment_size)
+    }
 }
 
 /// Damage detection result
@@ -922,6 +1374,11 @@ impl DamageDetector {
         })
     }
 
+    /// Integrity metadata for a monitored string
+    pub fn integrity(&self, string_id: &[u8; 32]) -> Option<StringIntegrity> {
+        self.integrity_db.read().get(string_id).cloned()
+    }
+
     /// Get recent damage reports
     pub fn recent_reports(&self, limit: usize) -> Vec<DamageReport> {
         let reports = self.reports.read();
@@ -1158,6 +1615,27 @@ impl ReedSolomonCodec {
         Ok(recovered)
     }
 
+    /// Regenerate missing shards in place, restoring full redundancy
+    /// without reassembling the data. Returns the number of shards rebuilt.
+    pub fn repair(&self, rs_data: &mut ReedSolomonData) -> Result<usize, String> {
+        let missing = rs_data.missing_shards();
+        if missing == 0 {
+            return Ok(0);
+        }
+        if !rs_data.can_recover() {
+            return Err(format!(
+                "Cannot repair: {} missing shards, need at least {} available",
+                missing, self.params.data_shards
+            ));
+        }
+
+        self.encoder
+            .reconstruct(&mut rs_data.shards)
+            .map_err(|e| format!("Reed-Solomon repair failed: {:?}", e))?;
+
+        Ok(missing)
+    }
+
     /// Extract original data from shards
     fn extract_data(&self, rs_data: &ReedSolomonData) -> Result<Vec<u8>, String> {
         let mut data = Vec::with_capacity(rs_data.original_length);
@@ -1535,6 +2013,767 @@ impl Default for NetworkRepairCoordinator {
     }
 }
 
+// ============================================================================
+// Integrity Scrubbing
+// ============================================================================
+
+use rope_core::{CanonicalEncoding, Complement, RopeString, StringLattice};
+use rope_storage::{ComplementStore, LatticeStore};
+use std::collections::BTreeSet;
+
+/// Scrubber configuration
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ScrubConfig {
+    /// Seconds between sweeps
+    pub interval_secs: u64,
+
+    /// Strings checked per sweep
+    pub batch_size: usize,
+
+    /// Peers asked for multi-source repairs
+    pub repair_peers: usize,
+}
+
+impl Default for ScrubConfig {
+    fn default() -> Self {
+        Self {
+            interval_secs: 3600,
+            batch_size: 256,
+            repair_peers: 3,
+        }
+    }
+}
+
+/// Repair that needs data from peers
+#[derive(Clone, Debug)]
+pub struct PeerRepair {
+    /// Damage that triggered the repair
+    pub report: DamageReport,
+
+    /// Peers to send the request to, most reliable first
+    pub peers: Vec<[u8; 32]>,
+
+    /// Request to send (signature added by the caller)
+    pub request: NetworkRepairRequest,
+}
+
+/// Result of scrubbing one string
+#[derive(Clone, Debug)]
+pub enum ScrubOutcome {
+    /// Stored data matches its ID
+    Healthy { string_id: [u8; 32] },
+
+    /// Damage found and repaired; the stored copy has been re-verified
+    Repaired {
+        string_id: [u8; 32],
+        damage: DamageType,
+        strategy: RepairStrategy,
+    },
+
+    /// Local repair impossible; peers must provide the data
+    Scheduled(PeerRepair),
+
+    /// Neither local data nor peers can repair the string
+    Unrecoverable {
+        string_id: [u8; 32],
+        damage: DamageType,
+        reason: String,
+    },
+}
+
+/// Scrubber statistics
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct ScrubStats {
+    pub sweeps: u64,
+    pub strings_scrubbed: u64,
+    pub damage_found: u64,
+    pub repaired_locally: u64,
+    pub repaired_from_peers: u64,
+    pub peer_repairs_scheduled: u64,
+    pub unrecoverable: u64,
+}
+
+/// Background integrity scrubber for strings at rest
+///
+/// Each sweep decodes a batch of stored strings and compares the recomputed
+/// ID with the storage key. Damaged strings are classified against their
+/// complement's nucleotide digests and repaired locally from complement
+/// parity where possible; otherwise a repair is scheduled with providers
+/// known to the [`RegenerationCoordinator`], preferring peers with a good
+/// repair record. Stored complements are checked against intact strings and
+/// regenerated on desync. Every repair outcome is reported to the
+/// coordinator.
+pub struct IntegrityScrubber {
+    config: ScrubConfig,
+
+    /// Persisted strings (canonical encoding)
+    strings: Arc<LatticeStore>,
+
+    /// Persisted complements (canonical encoding)
+    complements: Arc<ComplementStore>,
+
+    /// Lattice, for in-memory complements
+    lattice: Arc<StringLattice>,
+
+    /// Repair accounting and string providers
+    regeneration: Arc<RegenerationCoordinator>,
+
+    /// Outstanding peer repairs
+    network: NetworkRepairCoordinator,
+
+    /// Damage reports for outstanding peer repairs
+    in_flight: RwLock<HashMap<[u8; 32], PeerRepair>>,
+
+    /// Strings expected in storage even if their bytes are gone
+    tracked: RwLock<BTreeSet<[u8; 32]>>,
+
+    /// Last string checked by the previous sweep
+    cursor: RwLock<Option<[u8; 32]>>,
+
+    stats: RwLock<ScrubStats>,
+}
+
+impl IntegrityScrubber {
+    /// Create a scrubber with the default configuration
+    pub fn new(
+        node_id: [u8; 32],
+        strings: Arc<LatticeStore>,
+        complements: Arc<ComplementStore>,
+        lattice: Arc<StringLattice>,
+        regeneration: Arc<RegenerationCoordinator>,
+    ) -> Self {
+        Self {
+            config: ScrubConfig::default(),
+            strings,
+            complements,
+            lattice,
+            regeneration,
+            network: NetworkRepairCoordinator::new(node_id),
+            in_flight: RwLock::new(HashMap::new()),
+            tracked: RwLock::new(BTreeSet::new()),
+            cursor: RwLock::new(None),
+            stats: RwLock::new(ScrubStats::default()),
+        }
+    }
+
+    /// Set configuration
+    pub fn with_config(mut self, config: ScrubConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Expect a string in storage; if its bytes disappear it is reported
+    /// as [`DamageType::TotalLoss`]
+    pub fn track(&self, string_id: [u8; 32]) {
+        self.tracked.write().insert(string_id);
+    }
+
+    /// Scrub the next batch of strings, resuming where the last sweep ended
+    pub fn scrub_batch(&self) -> Vec<ScrubOutcome> {
+        let mut ids: BTreeSet<[u8; 32]> = self.tracked.read().clone();
+        ids.extend(self.strings.keys());
+
+        let batch: Vec<[u8; 32]> = {
+            let cursor = *self.cursor.read();
+            let after = cursor.map_or(ids.range(..), |c| {
+                ids.range((std::ops::Bound::Excluded(c), std::ops::Bound::Unbounded))
+            });
+            // Wrap around to the start once the end is reached
+            after
+                .chain(ids.iter())
+                .take(self.config.batch_size.min(ids.len()))
+                .copied()
+                .collect()
+        };
+        *self.cursor.write() = batch.last().copied();
+
+        let outcomes: Vec<ScrubOutcome> = batch.iter().map(|id| self.scrub_string(id)).collect();
+        self.stats.write().sweeps += 1;
+        outcomes
+    }
+
+    /// Check one stored string and repair it if damaged
+    pub fn scrub_string(&self, string_id: &[u8; 32]) -> ScrubOutcome {
+        self.stats.write().strings_scrubbed += 1;
+
+        let bytes = self.strings.get(string_id);
+        let decoded = bytes
+            .as_ref()
+            .and_then(|b| RopeString::from_canonical_bytes(b).ok());
+
+        if let Some(string) = decoded.as_ref().filter(|s| s.id().as_bytes() == string_id) {
+            return match self.check_stored_complement(string) {
+                None => ScrubOutcome::Healthy {
+                    string_id: *string_id,
+                },
+                Some(report) => self.repair_complement(report, string),
+            };
+        }
+
+        let complement = self.complement(string_id);
+        let report = Self::classify(
+            string_id,
+            bytes.is_some(),
+            decoded.as_ref(),
+            complement.as_ref(),
+        );
+        self.stats.write().damage_found += 1;
+        tracing::warn!(
+            "Scrub found damage in string {}: {:?}",
+            rope_core::StringId::new(*string_id),
+            report.damage_type
+        );
+
+        // Sequence damage can be rebuilt from complement parity
+        if let (Some(damaged), Some(complement)) = (&decoded, &complement) {
+            if !report.damaged_segments.is_empty() {
+                if let Ok(sequence) = complement.repair_sequence(damaged.sequence()) {
+                    let repaired = damaged.with_repaired_sequence(sequence);
+                    if self.write_verified(string_id, &repaired) {
+                        return self.repaired_locally(report);
+                    }
+                }
+            }
+        }
+
+        self.schedule_peer_repair(report)
+    }
+
+    /// Hand over repair data received from a peer
+    pub fn receive_repair_data(&self, data: NetworkRepairData) -> bool {
+        self.network.receive_repair_data(data)
+    }
+
+    /// Finish a peer repair once enough responses agree
+    ///
+    /// Returns `None` while responses are still missing or disagree.
+    pub fn complete_peer_repair(&self, request_id: &[u8; 32]) -> Option<ScrubOutcome> {
+        let content = self.network.try_complete(request_id)?;
+        let repair = self.in_flight.write().remove(request_id)?;
+        let string_id = repair.report.string_id;
+        let damage = repair
+            .report
+            .damage_type
+            .clone()
+            .unwrap_or(DamageType::TotalLoss);
+
+        let repaired = RopeString::from_canonical_bytes(&content)
+            .ok()
+            .filter(|s| self.write_verified(&string_id, s));
+        self.record_peers(&repair.peers, repaired.is_some());
+
+        match repaired {
+            Some(_) => {
+                self.stats.write().repaired_from_peers += 1;
+                self.regeneration.record_result(RepairResult::Success {
+                    string_id,
+                    repaired_bytes: content.len(),
+                    sources_used: repair.peers.len(),
+                });
+                Some(ScrubOutcome::Repaired {
+                    string_id,
+                    strategy: damage.recommended_strategy(),
+                    damage,
+                })
+            }
+            None => Some(self.unrecoverable(
+                string_id,
+                damage,
+                "peer data does not match string ID".to_string(),
+            )),
+        }
+    }
+
+    /// Outstanding peer repairs
+    pub fn pending_peer_repairs(&self) -> Vec<PeerRepair> {
+        self.in_flight.read().values().cloned().collect()
+    }
+
+    /// Get statistics
+    pub fn stats(&self) -> ScrubStats {
+        self.stats.read().clone()
+    }
+
+    /// Sweep every `interval_secs`, forwarding peer repairs to the network
+    pub async fn run(self: Arc<Self>, repairs: tokio::sync::mpsc::Sender<PeerRepair>) {
+        let mut interval =
+            tokio::time::interval(std::time::Duration::from_secs(self.config.interval_secs));
+        loop {
+            interval.tick().await;
+            for outcome in self.scrub_batch() {
+                if let ScrubOutcome::Scheduled(repair) = outcome {
+                    if repairs.send(repair).await.is_err() {
+                        return;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Spawn [`run`](Self::run) as a background task
+    pub fn spawn(
+        self: Arc<Self>,
+        repairs: tokio::sync::mpsc::Sender<PeerRepair>,
+    ) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(self.run(repairs))
+    }
+
+    /// Persisted complement if it belongs to the string, else the lattice copy
+    fn complement(&self, string_id: &[u8; 32]) -> Option<Complement> {
+        self.complements
+            .get_complement(string_id)
+            .and_then(|b| Complement::from_canonical_bytes(&b).ok())
+            .filter(|c| c.primary_id().as_bytes() == string_id)
+            .or_else(|| {
+                self.lattice
+                    .get_complement(&rope_core::StringId::new(*string_id))
+            })
+    }
+
+    /// Classify damage in a string whose stored bytes do not match its ID
+    fn classify(
+        string_id: &[u8; 32],
+        present: bool,
+        decoded: Option<&RopeString>,
+        complement: Option<&Complement>,
+    ) -> DamageReport {
+        let mut damaged_segments = Vec::new();
+        let mut total_segments = 0;
+
+        let damage_type = match (decoded, complement) {
+            _ if !present => DamageType::TotalLoss,
+            (None, _) => DamageType::SevereCorruption {
+                recovery_chance_percent: 0,
+            },
+            (Some(string), Some(complement)) => {
+                damaged_segments = complement
+                    .damaged_positions(string.sequence())
+                    .into_iter()
+                    .map(|p| p as usize)
+                    .collect();
+                total_segments = complement.nucleotide_count() as usize;
+                Self::classify_positions(string, complement, &damaged_segments, string_id)
+            }
+            (Some(string), None) => DamageType::MismatchError {
+                computed: *string.id().as_bytes(),
+                expected: *string_id,
+            },
+        };
+
+        let corruption_percent = match (&damage_type, total_segments) {
+            (DamageType::TotalLoss | DamageType::SevereCorruption { .. }, _) => 100.0,
+            (_, 0) => 0.0,
+            (_, total) => damaged_segments.len() as f64 / total as f64 * 100.0,
+        };
+
+        DamageReport {
+            string_id: *string_id,
+            damage_type: Some(damage_type),
+            damaged_segments,
+            total_segments,
+            corruption_percent,
+            detected_at: chrono::Utc::now().timestamp(),
+            method: DetectionMethod::BackgroundScan,
+        }
+    }
+
+    /// Damage class from the positions of corrupted nucleotides
+    fn classify_positions(
+        string: &RopeString,
+        complement: &Complement,
+        positions: &[usize],
+        string_id: &[u8; 32],
+    ) -> DamageType {
+        const NUCLEOTIDE_SIZE: usize = 32;
+
+        let (first, last) = match (positions.first(), positions.last()) {
+            (Some(first), Some(last)) => (*first, *last),
+            // Content intact: metadata was corrupted
+            _ => {
+                return DamageType::MismatchError {
+                    computed: *string.id().as_bytes(),
+                    expected: *string_id,
+                }
+            }
+        };
+
+        let ratio = positions.len() as f64 / complement.nucleotide_count().max(1) as f64;
+        if positions.len() == 1 {
+            DamageType::SingleNucleotide {
+                offset: first * NUCLEOTIDE_SIZE,
+                expected_hash: *complement.verification_hash(),
+            }
+        } else if ratio > 0.5 {
+            DamageType::SevereCorruption {
+                recovery_chance_percent: ((1.0 - ratio) * 100.0) as u8,
+            }
+        } else {
+            DamageType::SegmentCorruption {
+                start: first * NUCLEOTIDE_SIZE,
+                end: (last + 1) * NUCLEOTIDE_SIZE,
+                severity_percent: (ratio * 100.0) as u8,
+            }
+        }
+    }
+
+    /// Check the persisted complement of an intact string
+    ///
+    /// Nodes that do not persist complements have nothing to check.
+    fn check_stored_complement(&self, string: &RopeString) -> Option<DamageReport> {
+        let string_id = *string.id().as_bytes();
+        let bytes = self.complements.get_complement(&string_id)?;
+        let in_sync = Complement::from_canonical_bytes(&bytes)
+            .is_ok_and(|c| c.primary_id() == string.id() && c.verify_content(&string.content()));
+        if in_sync {
+            return None;
+        }
+
+        self.stats.write().damage_found += 1;
+        Some(DamageReport {
+            string_id,
+            damage_type: Some(DamageType::ComplementDesync),
+            damaged_segments: vec![],
+            total_segments: 0,
+            corruption_percent: 100.0,
+            detected_at: chrono::Utc::now().timestamp(),
+            method: DetectionMethod::ComplementComparison,
+        })
+    }
+
+    /// Rewrite a desynced complement from the lattice copy or the string
+    fn repair_complement(&self, report: DamageReport, string: &RopeString) -> ScrubOutcome {
+        let complement = self
+            .lattice
+            .get_complement(&string.id())
+            .filter(|c| c.verify_entanglement(string) && c.verify_content(&string.content()))
+            .unwrap_or_else(|| {
+                Complement::generate(
+                    string,
+                    Complement::default_redundancy(string.replication_factor()),
+                )
+            });
+
+        let string_id = report.string_id;
+        self.complements
+            .store_complement(string_id, complement.to_canonical_bytes());
+        let verified = self
+            .complements
+            .get_complement(&string_id)
+            .and_then(|b| Complement::from_canonical_bytes(&b).ok())
+            .is_some_and(|c| c.verify_content(&string.content()));
+
+        if verified {
+            self.repaired_locally(report)
+        } else {
+            self.unrecoverable(
+                string_id,
+                DamageType::ComplementDesync,
+                "complement store rejected rewrite".to_string(),
+            )
+        }
+    }
+
+    /// Persist a repaired string and re-read it to verify the write
+    fn write_verified(&self, string_id: &[u8; 32], string: &RopeString) -> bool {
+        if string.id().as_bytes() != string_id {
+            return false;
+        }
+        self.strings.put(*string_id, string.to_canonical_bytes());
+        self.strings
+            .get(string_id)
+            .and_then(|b| RopeString::from_canonical_bytes(&b).ok())
+            .is_some_and(|s| s.id().as_bytes() == string_id)
+    }
+
+    fn repaired_locally(&self, report: DamageReport) -> ScrubOutcome {
+        let damage = report.damage_type.unwrap_or(DamageType::TotalLoss);
+        let repaired_bytes = self.strings.get(&report.string_id).map_or(0, |b| b.len());
+
+        self.stats.write().repaired_locally += 1;
+        self.regeneration.record_result(RepairResult::Success {
+            string_id: report.string_id,
+            repaired_bytes,
+            sources_used: 1,
+        });
+        ScrubOutcome::Repaired {
+            string_id: report.string_id,
+            strategy: damage.recommended_strategy(),
+            damage,
+        }
+    }
+
+    fn schedule_peer_repair(&self, report: DamageReport) -> ScrubOutcome {
+        let damage = report.damage_type.clone().unwrap_or(DamageType::TotalLoss);
+        let strategy = damage.recommended_strategy();
+        let peers = self.select_peers(&report.string_id, &strategy);
+        if peers.is_empty() {
+            return self.unrecoverable(
+                report.string_id,
+                damage,
+                "no complement repair possible and no known providers".to_string(),
+            );
+        }
+
+        let request = self.network.create_request(
+            report.string_id,
+            report.damaged_segments.clone(),
+            strategy,
+        );
+        let repair = PeerRepair {
+            report,
+            peers,
+            request,
+        };
+        self.in_flight
+            .write()
+            .insert(repair.request.id, repair.clone());
+        self.stats.write().peer_repairs_scheduled += 1;
+        ScrubOutcome::Scheduled(repair)
+    }
+
+    /// Providers of a string, most reliable first
+    ///
+    /// Multi-source strategies ask `repair_peers` peers so responses can be
+    /// cross-checked; other strategies ask the single best peer.
+    fn select_peers(&self, string_id: &[u8; 32], strategy: &RepairStrategy) -> Vec<[u8; 32]> {
+        let mut providers = self.regeneration.ranked_providers(string_id);
+        providers.retain(|p| *p != self.regeneration.node_id);
+
+        let wanted = match strategy {
+            RepairStrategy::MultiSourceVerify | RepairStrategy::MultiSourceReconstruct => {
+                self.config.repair_peers.max(1)
+            }
+            _ => 1,
+        };
+        providers.truncate(wanted);
+        providers
+    }
+
+    fn record_peers(&self, peers: &[[u8; 32]], success: bool) {
+        for peer in peers {
+            self.regeneration.record_provider(peer, success);
+        }
+    }
+
+    fn unrecoverable(
+        &self,
+        string_id: [u8; 32],
+        damage: DamageType,
+        reason: String,
+    ) -> ScrubOutcome {
+        self.stats.write().unrecoverable += 1;
+        self.regeneration
+            .record_result(RepairResult::Unrecoverable {
+                string_id,
+                reason: reason.clone(),
+            });
+        ScrubOutcome::Unrecoverable {
+            string_id,
+            damage,
+            reason,
+        }
+    }
+}
+
+#[cfg(test)]
+mod scrub_tests {
+    use super::*;
+    use rope_core::{LamportClock, NodeId, Nucleotide, PublicKey};
+
+    struct Fixture {
+        strings: Arc<LatticeStore>,
+        complements: Arc<ComplementStore>,
+        regeneration: Arc<RegenerationCoordinator>,
+        scrubber: IntegrityScrubber,
+        string: RopeString,
+    }
+
+    fn fixture() -> Fixture {
+        let strings = Arc::new(LatticeStore::new());
+        let complements = Arc::new(ComplementStore::new());
+        let lattice = Arc::new(StringLattice::new());
+        let regeneration = Arc::new(RegenerationCoordinator::new([1u8; 32]));
+
+        let string = RopeString::builder()
+            .content(vec![7u8; 32 * 20])
+            .temporal_marker(LamportClock::new(NodeId::new([0u8; 32])))
+            .creator(PublicKey::from_ed25519([0u8; 32]))
+            .build()
+            .unwrap();
+        let id = lattice.add_string(string.clone()).unwrap();
+        strings.put(*id.as_bytes(), string.to_canonical_bytes());
+        complements.store_complement(
+            *id.as_bytes(),
+            lattice.get_complement(&id).unwrap().to_canonical_bytes(),
+        );
+
+        let scrubber = IntegrityScrubber::new(
+            [1u8; 32],
+            strings.clone(),
+            complements.clone(),
+            lattice,
+            regeneration.clone(),
+        );
+        Fixture {
+            strings,
+            complements,
+            regeneration,
+            scrubber,
+            string,
+        }
+    }
+
+    fn damage(string: &RopeString, positions: &[usize]) -> Vec<u8> {
+        let mut sequence = string.sequence().clone();
+        for &p in positions {
+            *sequence.get_mut(p).unwrap() = Nucleotide::new([0xEE; 32], p as u64);
+        }
+        string.with_repaired_sequence(sequence).to_canonical_bytes()
+    }
+
+    #[test]
+    fn test_healthy_strings_pass() {
+        let f = fixture();
+        let outcomes = f.scrubber.scrub_batch();
+        assert_eq!(outcomes.len(), 1);
+        assert!(matches!(outcomes[0], ScrubOutcome::Healthy { .. }));
+        assert_eq!(f.regeneration.stats().total_repairs, 0);
+    }
+
+    #[test]
+    fn test_single_nucleotide_repaired_from_complement() {
+        let f = fixture();
+        let id = *f.string.id().as_bytes();
+        f.strings.put(id, damage(&f.string, &[3]));
+
+        let outcome = f.scrubber.scrub_string(&id);
+        assert!(matches!(
+            outcome,
+            ScrubOutcome::Repaired {
+                damage: DamageType::SingleNucleotide { offset: 96, .. },
+                strategy: RepairStrategy::ParityReconstruction,
+                ..
+            }
+        ));
+        assert_eq!(f.strings.get(&id).unwrap(), f.string.to_canonical_bytes());
+        assert_eq!(f.regeneration.stats().successful_repairs, 1);
+    }
+
+    #[test]
+    fn test_segment_corruption_classified_and_repaired() {
+        let f = fixture();
+        let id = *f.string.id().as_bytes();
+        f.strings.put(id, damage(&f.string, &[4, 5]));
+
+        match f.scrubber.scrub_string(&id) {
+            ScrubOutcome::Repaired {
+                damage, strategy, ..
+            } => {
+                assert_eq!(
+                    damage,
+                    DamageType::SegmentCorruption {
+                        start: 128,
+                        end: 192,
+                        severity_percent: 10
+                    }
+                );
+                assert_eq!(strategy, RepairStrategy::SegmentRequest);
+            }
+            other => panic!("unexpected outcome {:?}", other),
+        }
+        assert_eq!(f.strings.get(&id).unwrap(), f.string.to_canonical_bytes());
+    }
+
+    #[test]
+    fn test_complement_desync_rewritten() {
+        let f = fixture();
+        let id = *f.string.id().as_bytes();
+        f.complements.store_complement(id, vec![1, 2, 3]);
+
+        let outcome = f.scrubber.scrub_string(&id);
+        assert!(matches!(
+            outcome,
+            ScrubOutcome::Repaired {
+                damage: DamageType::ComplementDesync,
+                ..
+            }
+        ));
+        let stored = Complement::from_canonical_bytes(&f.complements.get_complement(&id).unwrap());
+        assert!(stored.unwrap().verify_content(&f.string.content()));
+    }
+
+    #[test]
+    fn test_total_loss_scheduled_with_reliable_peers() {
+        let f = fixture();
+        let id = *f.string.id().as_bytes();
+        f.scrubber.track(id);
+        f.strings.delete(&id);
+
+        // Without providers there is nothing to do
+        assert!(matches!(
+            f.scrubber.scrub_string(&id),
+            ScrubOutcome::Unrecoverable {
+                damage: DamageType::TotalLoss,
+                ..
+            }
+        ));
+        assert_eq!(f.regeneration.stats().failed_repairs, 1);
+
+        for peer in [[2u8; 32], [3u8; 32], [1u8; 32]] {
+            f.regeneration.register_provider(id, peer);
+        }
+        // A peer that served bad data before ranks last
+        f.regeneration.record_provider(&[2u8; 32], false);
+
+        let repair = match f.scrubber.scrub_string(&id) {
+            ScrubOutcome::Scheduled(repair) => repair,
+            other => panic!("unexpected outcome {:?}", other),
+        };
+        assert_eq!(repair.request.strategy, RepairStrategy::FullRegeneration);
+        assert_eq!(repair.peers, vec![[3u8; 32]]);
+
+        // Corrupt peer data is rejected
+        let bogus = damage(&f.string, &[0]);
+        assert!(f.scrubber.receive_repair_data(NetworkRepairData {
+            request_id: repair.request.id,
+            provider: [3u8; 32],
+            segments: HashMap::new(),
+            full_content: Some(bogus.clone()),
+            content_hash: *blake3::hash(&bogus).as_bytes(),
+            signature: vec![],
+        }));
+        assert!(matches!(
+            f.scrubber.complete_peer_repair(&repair.request.id),
+            Some(ScrubOutcome::Unrecoverable { .. })
+        ));
+        assert!(f.strings.get(&id).is_none());
+
+        // A good copy is verified and written back
+        let repair = match f.scrubber.scrub_string(&id) {
+            ScrubOutcome::Scheduled(repair) => repair,
+            other => panic!("unexpected outcome {:?}", other),
+        };
+        let good = f.string.to_canonical_bytes();
+        f.scrubber.receive_repair_data(NetworkRepairData {
+            request_id: repair.request.id,
+            provider: repair.peers[0],
+            segments: HashMap::new(),
+            full_content: Some(good.clone()),
+            content_hash: *blake3::hash(&good).as_bytes(),
+            signature: vec![],
+        });
+        assert!(matches!(
+            f.scrubber.complete_peer_repair(&repair.request.id),
+            Some(ScrubOutcome::Repaired {
+                damage: DamageType::TotalLoss,
+                ..
+            })
+        ));
+        assert_eq!(f.strings.get(&id), Some(good));
+        assert_eq!(f.scrubber.stats().repaired_from_peers, 1);
+    }
+}
+
 #[cfg(test)]
 mod damage_detection_tests {
     use super::*;
@@ -1642,6 +2881,22 @@ mod damage_detection_tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_reed_solomon_repair_restores_shards() {
+        let codec = ReedSolomonCodec::new().expect("Failed to create codec");
+        let original = b"Test data for Reed-Solomon shard repair after losing parity!";
+
+        let encoded = codec.encode(original).expect("Encoding failed");
+        let mut damaged = encoded.clone();
+        damaged.mark_missing(2);
+        damaged.mark_missing(5);
+
+        assert_eq!(codec.repair(&mut damaged), Ok(2));
+        assert_eq!(damaged.missing_shards(), 0);
+        assert_eq!(damaged.shards, encoded.shards);
+        assert!(codec.verify(&damaged).expect("Verify failed"));
+    }
+
     #[test]
     fn test_erasure_coding_manager() {
         let manager = ErasureCodingManager::new().expect("Failed to create manager");
@@ -0,0 +1,626 @@
+//! RustSec Advisories
+//!
+//! Advisories in the RustSec format (TOML front matter followed by a
+//! Markdown title and description), matched against `Cargo.lock`. A small
+//! set is bundled; load a checkout of `rustsec/advisory-db` with
+//! [`AdvisoryDatabase::load_dir`] for full coverage.
+
+use super::*;
+use std::path::Path;
+
+/// Semantic version (pre-release sorts before the release)
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Version {
+    pub major: u64,
+    pub minor: u64,
+    pub patch: u64,
+    pub pre: Option<String>,
+}
+
+impl Version {
+    /// Parse `1.2.3`, `1.2`, `1` or `1.2.3-alpha.1` (build metadata dropped)
+    pub fn parse(text: &str) -> Option<Self> {
+        let text = text.trim();
+        let text = text.split('+').next()?;
+        let (core, pre) = match text.split_once('-') {
+            Some((core, pre)) => (core, Some(pre.to_string())),
+            None => (text, None),
+        };
+        let mut parts = core.split('.');
+        let major = parts.next()?.parse().ok()?;
+        let minor = parts.next().map_or(Some(0), |p| p.parse().ok())?;
+        let patch = parts.next().map_or(Some(0), |p| p.parse().ok())?;
+        if parts.next().is_some() {
+            return None;
+        }
+        Some(Self {
+            major,
+            minor,
+            patch,
+            pre,
+        })
+    }
+}
+
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        (self.major, self.minor, self.patch)
+            .cmp(&(other.major, other.minor, other.patch))
+            .then_with(|| match (&self.pre, &other.pre) {
+                (None, None) => std::cmp::Ordering::Equal,
+                (None, Some(_)) => std::cmp::Ordering::Greater,
+                (Some(_), None) => std::cmp::Ordering::Less,
+                (Some(a), Some(b)) => a.cmp(b),
+            })
+    }
+}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl std::fmt::Display for Version {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)?;
+        if let Some(pre) = &self.pre {
+            write!(f, "-{}", pre)?;
+        }
+        Ok(())
+    }
+}
+
+/// Comparison operator in a version requirement
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VersionOp {
+    Exact,
+    Greater,
+    GreaterEq,
+    Less,
+    LessEq,
+    /// Cargo's default: same major (or minor for 0.x)
+    Caret,
+}
+
+/// Comma-separated comparators that must all hold, e.g. `>= 1.8.4, < 1.9.0`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VersionReq {
+    pub comparators: Vec<(VersionOp, Version)>,
+}
+
+impl VersionReq {
+    pub fn parse(text: &str) -> Option<Self> {
+        let comparators = text
+            .split(',')
+            .map(|part| {
+                let part = part.trim();
+                let (op, rest) = [
+                    (">=", VersionOp::GreaterEq),
+                    ("<=", VersionOp::LessEq),
+                    (">", VersionOp::Greater),
+                    ("<", VersionOp::Less),
+                    ("=", VersionOp::Exact),
+                    ("^", VersionOp::Caret),
+                ]
+                .iter()
+                .find_map(|(prefix, op)| part.strip_prefix(prefix).map(|rest| (*op, rest)))
+                .unwrap_or((VersionOp::Caret, part));
+                Some((op, Version::parse(rest)?))
+            })
+            .collect::<Option<Vec<_>>>()?;
+        Some(Self { comparators })
+    }
+
+    pub fn matches(&self, version: &Version) -> bool {
+        self.comparators.iter().all(|(op, bound)| match op {
+            VersionOp::Exact => version == bound,
+            VersionOp::Greater => version > bound,
+            VersionOp::GreaterEq => version >= bound,
+            VersionOp::Less => version < bound,
+            VersionOp::LessEq => version <= bound,
+            VersionOp::Caret => {
+                version >= bound
+                    && if bound.major > 0 {
+                        version.major == bound.major
+                    } else {
+                        version.major == 0 && version.minor == bound.minor
+                    }
+            }
+        })
+    }
+
+    /// Smallest version this requirement allows, if it has a lower bound
+    pub fn minimum(&self) -> Option<Version> {
+        let minimum = self
+            .comparators
+            .iter()
+            .filter_map(|(op, bound)| match op {
+                VersionOp::Exact | VersionOp::GreaterEq | VersionOp::Caret => Some(bound.clone()),
+                VersionOp::Greater => Some(Version {
+                    patch: bound.patch + 1,
+                    pre: None,
+                    ..bound.clone()
+                }),
+                VersionOp::Less | VersionOp::LessEq => None,
+            })
+            .max()?;
+        self.matches(&minimum).then_some(minimum)
+    }
+}
+
+impl std::fmt::Display for VersionReq {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let parts: Vec<String> = self
+            .comparators
+            .iter()
+            .map(|(op, version)| {
+                let op = match op {
+                    VersionOp::Exact => "=",
+                    VersionOp::Greater => ">",
+                    VersionOp::GreaterEq => ">=",
+                    VersionOp::Less => "<",
+                    VersionOp::LessEq => "<=",
+                    VersionOp::Caret => "^",
+                };
+                format!("{} {}", op, version)
+            })
+            .collect();
+        write!(f, "{}", parts.join(", "))
+    }
+}
+
+/// Security advisory for a crate
+#[derive(Clone, Debug)]
+pub struct Advisory {
+    /// `RUSTSEC-YYYY-NNNN`
+    pub id: String,
+    pub package: String,
+    pub title: String,
+    pub description: String,
+    pub date: String,
+    /// CVE and GHSA identifiers
+    pub aliases: Vec<String>,
+    pub categories: Vec<String>,
+    /// `unmaintained`, `unsound`, ... for non-vulnerability notices
+    pub informational: Option<String>,
+    pub url: Option<String>,
+    pub patched: Vec<VersionReq>,
+    pub unaffected: Vec<VersionReq>,
+}
+
+#[derive(Deserialize)]
+struct AdvisoryFile {
+    advisory: AdvisoryMeta,
+    #[serde(default)]
+    versions: AdvisoryVersions,
+}
+
+#[derive(Deserialize)]
+struct AdvisoryMeta {
+    id: String,
+    package: String,
+    #[serde(default)]
+    date: String,
+    #[serde(default)]
+    url: Option<String>,
+    #[serde(default)]
+    aliases: Vec<String>,
+    #[serde(default)]
+    categories: Vec<String>,
+    #[serde(default)]
+    informational: Option<String>,
+}
+
+#[derive(Default, Deserialize)]
+struct AdvisoryVersions {
+    #[serde(default)]
+    patched: Vec<String>,
+    #[serde(default)]
+    unaffected: Vec<String>,
+}
+
+impl Advisory {
+    /// Parse a RustSec advisory: a ```` ```toml ```` block, then `# Title`
+    /// and the description
+    pub fn parse(text: &str) -> Result<Self, SecurityError> {
+        let invalid = |reason: &str| SecurityError::InvalidTarget(format!("advisory: {}", reason));
+
+        let body = text
+            .trim_start()
+            .strip_prefix("```toml")
+            .ok_or_else(|| invalid("missing TOML front matter"))?;
+        let (front, rest) = body
+            .split_once("```")
+            .ok_or_else(|| invalid("unterminated front matter"))?;
+        let file: AdvisoryFile = toml::from_str(front).map_err(|e| invalid(&e.to_string()))?;
+
+        let reqs = |reqs: &[String]| -> Result<Vec<VersionReq>, SecurityError> {
+            reqs.iter()
+                .map(|r| VersionReq::parse(r).ok_or_else(|| invalid(&format!("bad version {}", r))))
+                .collect()
+        };
+
+        let rest = rest.trim();
+        let (title, description) = match rest.strip_prefix('#') {
+            Some(rest) => rest.split_once('\n').unwrap_or((rest, "")),
+            None => ("", rest),
+        };
+
+        Ok(Self {
+            patched: reqs(&file.versions.patched)?,
+            unaffected: reqs(&file.versions.unaffected)?,
+            id: file.advisory.id,
+            package: file.advisory.package,
+            title: title.trim().to_string(),
+            description: description.trim().to_string(),
+            date: file.advisory.date,
+            aliases: file.advisory.aliases,
+            categories: file.advisory.categories,
+            informational: file.advisory.informational,
+            url: file.advisory.url,
+        })
+    }
+
+    /// Whether `version` is neither patched nor unaffected
+    pub fn affects(&self, version: &Version) -> bool {
+        !self
+            .patched
+            .iter()
+            .chain(&self.unaffected)
+            .any(|req| req.matches(version))
+    }
+
+    /// Affected range, as the complement of patched and unaffected
+    pub fn affected_range(&self) -> String {
+        let safe: Vec<String> = self
+            .patched
+            .iter()
+            .chain(&self.unaffected)
+            .map(|req| req.to_string())
+            .collect();
+        if safe.is_empty() {
+            "all versions".to_string()
+        } else {
+            format!("all versions except {}", safe.join("; "))
+        }
+    }
+
+    /// Closest patched version above `current`, preferring a
+    /// semver-compatible upgrade
+    pub fn suggested_upgrade(&self, current: &Version) -> Option<Version> {
+        let mut candidates: Vec<Version> = self
+            .patched
+            .iter()
+            .filter_map(VersionReq::minimum)
+            .filter(|v| v > current)
+            .collect();
+        candidates.sort();
+        let compatible = VersionReq {
+            comparators: vec![(VersionOp::Caret, current.clone())],
+        };
+        candidates
+            .iter()
+            .find(|v| compatible.matches(v))
+            .or_else(|| candidates.first())
+            .cloned()
+    }
+
+    /// Severity from the advisory's categories
+    pub fn severity(&self) -> Severity {
+        let has = |category: &str| self.categories.iter().any(|c| c == category);
+        if self.informational.is_some() {
+            Severity::Low
+        } else if has("code-execution") {
+            Severity::Critical
+        } else if has("memory-corruption") || has("memory-exposure") || has("crypto-failure") {
+            Severity::High
+        } else {
+            Severity::Medium
+        }
+    }
+}
+
+/// Advisories by package name
+#[derive(Clone, Debug, Default)]
+pub struct AdvisoryDatabase {
+    advisories: HashMap<String, Vec<Advisory>>,
+}
+
+impl AdvisoryDatabase {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Database with the bundled advisories
+    pub fn bundled() -> Self {
+        let mut db = Self::new();
+        for text in BUNDLED_ADVISORIES {
+            if let Ok(advisory) = Advisory::parse(text) {
+                db.insert(advisory);
+            }
+        }
+        db
+    }
+
+    /// Load every `RUSTSEC-*.md` under `dir` (an advisory-db checkout),
+    /// returning how many were added
+    pub fn load_dir(&mut self, dir: &Path) -> Result<usize, SecurityError> {
+        let mut loaded = 0;
+        let entries = std::fs::read_dir(dir).map_err(|e| SecurityError::Internal(e.to_string()))?;
+        for entry in entries {
+            let path = entry
+                .map_err(|e| SecurityError::Internal(e.to_string()))?
+                .path();
+            if path.is_dir() {
+                loaded += self.load_dir(&path)?;
+                continue;
+            }
+            let is_advisory = path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| n.starts_with("RUSTSEC-") && n.ends_with(".md"));
+            if !is_advisory {
+                continue;
+            }
+            let text = std::fs::read_to_string(&path)
+                .map_err(|e| SecurityError::Internal(e.to_string()))?;
+            match Advisory::parse(&text) {
+                Ok(advisory) => {
+                    self.insert(advisory);
+                    loaded += 1;
+                }
+                Err(e) => tracing::warn!("Skipping advisory {}: {}", path.display(), e),
+            }
+        }
+        Ok(loaded)
+    }
+
+    /// Add an advisory, replacing one with the same ID
+    pub fn insert(&mut self, advisory: Advisory) {
+        let entries = self.advisories.entry(advisory.package.clone()).or_default();
+        entries.retain(|a| a.id != advisory.id);
+        entries.push(advisory);
+    }
+
+    /// Advisories affecting `package` at `version`
+    pub fn affecting(&self, package: &str, version: &Version) -> Vec<&Advisory> {
+        self.advisories
+            .get(package)
+            .map(|advisories| advisories.iter().filter(|a| a.affects(version)).collect())
+            .unwrap_or_default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.advisories.values().map(Vec::len).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Package pinned in `Cargo.lock`
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+pub struct LockedPackage {
+    pub name: String,
+    pub version: String,
+    #[serde(default)]
+    pub source: Option<String>,
+}
+
+impl LockedPackage {
+    /// Whether the package comes from a registry (workspace and path
+    /// crates have no source)
+    pub fn is_registry(&self) -> bool {
+        self.source
+            .as_deref()
+            .is_some_and(|s| s.starts_with("registry+") || s.starts_with("sparse+"))
+    }
+}
+
+#[derive(Deserialize)]
+struct Lockfile {
+    #[serde(default)]
+    package: Vec<LockedPackage>,
+}
+
+/// Packages listed in a `Cargo.lock`
+pub fn parse_lockfile(text: &str) -> Result<Vec<LockedPackage>, SecurityError> {
+    toml::from_str::<Lockfile>(text)
+        .map(|lockfile| lockfile.package)
+        .map_err(|e| SecurityError::InvalidTarget(format!("Cargo.lock: {}", e)))
+}
+
+/// Bundled subset of the RustSec advisory database
+const BUNDLED_ADVISORIES: &[&str] = &[
+    r#"```toml
+[advisory]
+id = "RUSTSEC-2021-0078"
+package = "hyper"
+date = "2021-07-07"
+url = "https://github.com/hyperium/hyper/security/advisories/GHSA-f3pg-qwvg-p99c"
+aliases = ["CVE-2021-32715"]
+categories = ["format-injection"]
+
+[versions]
+patched = [">= 0.14.10"]
+```
+
+# Lenient `hyper` header parsing of `Content-Length` could allow request smuggling
+
+`hyper`'s HTTP/1 server accepted `Content-Length` values with a leading `+`,
+which other proxies may interpret differently.
+"#,
+    r#"```toml
+[advisory]
+id = "RUSTSEC-2021-0124"
+package = "tokio"
+date = "2021-11-16"
+url = "https://github.com/tokio-rs/tokio/issues/4225"
+aliases = ["CVE-2021-45710"]
+categories = ["memory-corruption"]
+
+[versions]
+patched = [">= 1.8.4, < 1.9.0", ">= 1.13.1"]
+unaffected = ["< 0.1.14"]
+```
+
+# Data race when sending and receiving after closing a `oneshot` channel
+
+Calling `close` on a `oneshot::Receiver` and then awaiting it concurrently
+with a send could cause a data race.
+"#,
+    r#"```toml
+[advisory]
+id = "RUSTSEC-2022-0013"
+package = "regex"
+date = "2022-03-08"
+url = "https://groups.google.com/g/rustlang-security-announcements/c/NcNNL1Jq7Yw"
+aliases = ["CVE-2022-24713"]
+categories = ["denial-of-service"]
+
+[versions]
+patched = [">= 1.5.5"]
+```
+
+# Regexes with large repetitions on empty sub-expressions take a very long time to parse
+
+Untrusted regex patterns could take exponential time to compile.
+"#,
+    r#"```toml
+[advisory]
+id = "RUSTSEC-2020-0071"
+package = "time"
+date = "2020-11-18"
+url = "https://github.com/time-rs/time/issues/293"
+aliases = ["CVE-2020-26235"]
+categories = ["code-execution", "memory-corruption"]
+
+[versions]
+patched = [">= 0.2.23"]
+unaffected = ["= 0.2.0", "= 0.2.1", "= 0.2.2", "= 0.2.3", "= 0.2.4", "= 0.2.5", "= 0.2.6"]
+```
+
+# Potential segfault in the time crate
+
+Calls to `localtime_r` may segfault if another thread modifies the
+environment at the same time.
+"#,
+    r#"```toml
+[advisory]
+id = "RUSTSEC-2020-0159"
+package = "chrono"
+date = "2020-11-10"
+url = "https://github.com/chronotope/chrono/issues/499"
+categories = ["code-execution", "memory-corruption"]
+
+[versions]
+patched = [">= 0.4.20"]
+```
+
+# Potential segfault in `localtime_r` invocations
+
+Calls to `localtime_r` may segfault if another thread modifies the
+environment at the same time.
+"#,
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn v(text: &str) -> Version {
+        Version::parse(text).unwrap()
+    }
+
+    #[test]
+    fn test_version_requirements() {
+        assert!(v("1.0.0-alpha") < v("1.0.0"));
+        assert_eq!(v("0.2"), v("0.2.0"));
+
+        let req = VersionReq::parse(">= 1.8.4, < 1.9.0").unwrap();
+        assert!(req.matches(&v("1.8.5")));
+        assert!(!req.matches(&v("1.9.0")));
+        assert_eq!(req.minimum(), Some(v("1.8.4")));
+        assert_eq!(req.to_string(), ">= 1.8.4, < 1.9.0");
+
+        let caret = VersionReq::parse("0.4.3").unwrap();
+        assert!(caret.matches(&v("0.4.9")));
+        assert!(!caret.matches(&v("0.5.0")));
+        assert!(VersionReq::parse("< 1.x").is_none());
+    }
+
+    #[test]
+    fn test_bundled_advisories() {
+        let db = AdvisoryDatabase::bundled();
+        assert_eq!(db.len(), BUNDLED_ADVISORIES.len());
+
+        let tokio = db.affecting("tokio", &v("1.8.0"));
+        assert_eq!(tokio.len(), 1);
+        let advisory = tokio[0];
+        assert_eq!(advisory.id, "RUSTSEC-2021-0124");
+        assert_eq!(advisory.aliases, vec!["CVE-2021-45710"]);
+        assert!(advisory.title.starts_with("Data race"));
+        assert_eq!(advisory.severity(), Severity::High);
+
+        // Patched and unaffected versions are clean
+        assert!(db.affecting("tokio", &v("1.8.4")).is_empty());
+        assert!(db.affecting("tokio", &v("1.35.0")).is_empty());
+        assert!(db.affecting("tokio", &v("0.1.13")).is_empty());
+        assert!(db.affecting("time", &v("0.2.3")).is_empty());
+    }
+
+    #[test]
+    fn test_suggested_upgrade() {
+        let db = AdvisoryDatabase::bundled();
+        let advisory = db.affecting("tokio", &v("1.8.0"))[0];
+
+        // Same-major backport first, then the closest later release
+        assert_eq!(advisory.suggested_upgrade(&v("1.8.0")), Some(v("1.8.4")));
+        assert_eq!(advisory.suggested_upgrade(&v("1.10.0")), Some(v("1.13.1")));
+        assert!(advisory.affected_range().contains(">= 1.13.1"));
+    }
+
+    #[test]
+    fn test_parse_lockfile() {
+        let lockfile = r#"
+            version = 3
+
+            [[package]]
+            name = "rope-core"
+            version = "0.1.0"
+
+            [[package]]
+            name = "tokio"
+            version = "1.8.0"
+            source = "registry+https://github.com/rust-lang/crates.io-index"
+            checksum = "abc"
+        "#;
+        let packages = parse_lockfile(lockfile).unwrap();
+
+        assert_eq!(packages.len(), 2);
+        assert!(!packages[0].is_registry());
+        assert!(packages[1].is_registry());
+        assert!(parse_lockfile("[[package]]\nname = 1").is_err());
+    }
+
+    #[test]
+    fn test_load_dir() {
+        let dir = std::env::temp_dir().join(format!("rope-advisories-{}", std::process::id()));
+        let crate_dir = dir.join("crates").join("hyper");
+        std::fs::create_dir_all(&crate_dir).unwrap();
+        std::fs::write(
+            crate_dir.join("RUSTSEC-2021-0078.md"),
+            BUNDLED_ADVISORIES[0],
+        )
+        .unwrap();
+        std::fs::write(crate_dir.join("README.md"), "not an advisory").unwrap();
+
+        let mut db = AdvisoryDatabase::new();
+        assert_eq!(db.load_dir(&dir).unwrap(), 1);
+        assert_eq!(db.affecting("hyper", &v("0.14.9")).len(), 1);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,520 @@
+//! Baselines and Suppressions
+//!
+//! A [`Baseline`] snapshots the findings of a previous scan so later scans of
+//! the same target only surface what changed. Known issues can be suppressed
+//! from a TOML file or inline with a source comment:
+//!
+//! ```text
+//! // cerber-ignore: TXORIGIN-001 until=2026-12-31 legacy admin check
+//! ```
+//!
+//! Suppressions may carry an expiry date; once it passes the finding is
+//! reported again and the suppression is listed as expired.
+
+use super::*;
+use chrono::NaiveDate;
+use std::path::Path;
+
+/// Marker recognised in source comments
+pub const INLINE_SUPPRESSION_MARKER: &str = "cerber-ignore:";
+
+/// Stable identity of a finding across scans
+///
+/// Finding IDs include per-scan counters, so the fingerprint is derived from
+/// what the finding is and where it is instead.
+pub fn finding_fingerprint(finding: &SecurityFinding) -> String {
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(finding.title.as_bytes());
+    hasher.update(b"\0");
+    hasher.update(finding.category.as_bytes());
+    hasher.update(b"\0");
+    hasher.update(finding.location.as_deref().unwrap_or_default().as_bytes());
+    hex::encode(&hasher.finalize().as_bytes()[..16])
+}
+
+/// Rule ID of a finding with scanner prefixes and per-scan counters removed
+fn matches_rule(finding_id: &str, rule: &str) -> bool {
+    let id = finding_id.strip_prefix("SC-").unwrap_or(finding_id);
+    if id == rule {
+        return true;
+    }
+    id.strip_prefix(rule)
+        .and_then(|rest| rest.strip_prefix('-'))
+        .is_some_and(|counter| !counter.is_empty() && counter.chars().all(|c| c.is_ascii_digit()))
+}
+
+/// Finding recorded in a baseline
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BaselineEntry {
+    pub finding_id: String,
+    pub title: String,
+    pub category: String,
+    pub severity: Severity,
+    pub location: Option<String>,
+}
+
+/// Findings of a prior scan
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Baseline {
+    pub target: String,
+    pub report_id: String,
+    pub created_at: i64,
+    /// Entries keyed by [`finding_fingerprint`]
+    pub entries: HashMap<String, BaselineEntry>,
+}
+
+impl Baseline {
+    pub fn from_report(report: &SecurityReport) -> Self {
+        let entries = report
+            .findings
+            .iter()
+            .map(|f| {
+                (
+                    finding_fingerprint(f),
+                    BaselineEntry {
+                        finding_id: f.id.clone(),
+                        title: f.title.clone(),
+                        category: f.category.clone(),
+                        severity: f.severity,
+                        location: f.location.clone(),
+                    },
+                )
+            })
+            .collect();
+
+        Self {
+            target: report.target.clone(),
+            report_id: report.id.clone(),
+            created_at: report.timestamp,
+            entries,
+        }
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, SecurityError> {
+        let raw = std::fs::read_to_string(path.as_ref())
+            .map_err(|e| SecurityError::Internal(format!("{}: {}", path.as_ref().display(), e)))?;
+        serde_json::from_str(&raw).map_err(|e| SecurityError::Internal(e.to_string()))
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), SecurityError> {
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| SecurityError::Internal(e.to_string()))?;
+        std::fs::write(path.as_ref(), json)
+            .map_err(|e| SecurityError::Internal(format!("{}: {}", path.as_ref().display(), e)))
+    }
+
+    /// Compare a new report against this baseline
+    pub fn diff(&self, report: &SecurityReport, suppressions: &SuppressionSet) -> ScanDiff {
+        diff_findings(Some(self), report, suppressions)
+    }
+}
+
+/// Where a suppression was declared
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SuppressionSource {
+    File,
+    Inline,
+}
+
+/// Known issue to hide from scan results
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Suppression {
+    /// Rule ID (e.g. `TXORIGIN-001`), matched ignoring scanner prefixes and counters
+    #[serde(default)]
+    pub rule: Option<String>,
+    /// Exact [`finding_fingerprint`]
+    #[serde(default)]
+    pub fingerprint: Option<String>,
+    /// Restrict to one location, e.g. `line 12`
+    #[serde(default)]
+    pub location: Option<String>,
+    #[serde(default)]
+    pub reason: String,
+    /// Last day the suppression applies
+    #[serde(default)]
+    pub expires: Option<NaiveDate>,
+    #[serde(skip, default = "default_source")]
+    pub source: SuppressionSource,
+}
+
+fn default_source() -> SuppressionSource {
+    SuppressionSource::File
+}
+
+impl Suppression {
+    pub fn is_expired(&self, today: NaiveDate) -> bool {
+        self.expires.is_some_and(|expires| today > expires)
+    }
+
+    /// Whether this suppression covers `finding` (ignoring expiry)
+    pub fn matches(&self, finding: &SecurityFinding) -> bool {
+        if self.rule.is_none() && self.fingerprint.is_none() {
+            return false;
+        }
+        if let Some(rule) = &self.rule {
+            if !matches_rule(&finding.id, rule) {
+                return false;
+            }
+        }
+        if let Some(fingerprint) = &self.fingerprint {
+            if *fingerprint != finding_fingerprint(finding) {
+                return false;
+            }
+        }
+        if let Some(location) = &self.location {
+            if finding.location.as_deref() != Some(location.as_str()) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+#[derive(Deserialize)]
+struct SuppressionFile {
+    #[serde(default)]
+    suppress: Vec<Suppression>,
+}
+
+/// Collection of file-based and inline suppressions
+#[derive(Clone, Debug, Default)]
+pub struct SuppressionSet {
+    suppressions: Vec<Suppression>,
+}
+
+impl SuppressionSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parse a TOML suppression file with `[[suppress]]` entries
+    pub fn from_toml(input: &str) -> Result<Self, SecurityError> {
+        let file: SuppressionFile =
+            toml::from_str(input).map_err(|e| SecurityError::InvalidTarget(e.to_string()))?;
+        Ok(Self {
+            suppressions: file.suppress,
+        })
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, SecurityError> {
+        let raw = std::fs::read_to_string(path.as_ref())
+            .map_err(|e| SecurityError::Internal(format!("{}: {}", path.as_ref().display(), e)))?;
+        Self::from_toml(&raw)
+    }
+
+    /// Collect `cerber-ignore:` comments from source
+    ///
+    /// A comment after code applies to its own line; a comment on a line by
+    /// itself applies to the next line.
+    pub fn from_source(source: &str) -> Self {
+        let mut suppressions = Vec::new();
+
+        for (index, line) in source.lines().enumerate() {
+            let Some(pos) = line.find(INLINE_SUPPRESSION_MARKER) else {
+                continue;
+            };
+            let before = line[..pos].trim_end_matches(['/', '#', '*', ' ', '\t']);
+            let target_line = if before.trim().is_empty() {
+                index + 2
+            } else {
+                index + 1
+            };
+
+            let mut words = line[pos + INLINE_SUPPRESSION_MARKER.len()..].split_whitespace();
+            let Some(rule) = words.next() else {
+                continue;
+            };
+
+            let mut expires = None;
+            let mut reason = Vec::new();
+            for word in words {
+                match word.strip_prefix("until=") {
+                    Some(date) => expires = NaiveDate::parse_from_str(date, "%Y-%m-%d").ok(),
+                    None => reason.push(word),
+                }
+            }
+
+            suppressions.push(Suppression {
+                rule: Some(rule.to_string()),
+                fingerprint: None,
+                location: Some(format!("line {}", target_line)),
+                reason: reason.join(" "),
+                expires,
+                source: SuppressionSource::Inline,
+            });
+        }
+
+        Self { suppressions }
+    }
+
+    pub fn push(&mut self, suppression: Suppression) {
+        self.suppressions.push(suppression);
+    }
+
+    pub fn extend(&mut self, other: SuppressionSet) {
+        self.suppressions.extend(other.suppressions);
+    }
+
+    pub fn len(&self) -> usize {
+        self.suppressions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.suppressions.is_empty()
+    }
+
+    /// Active suppression covering `finding` on `today`
+    pub fn find(&self, finding: &SecurityFinding, today: NaiveDate) -> Option<&Suppression> {
+        self.suppressions
+            .iter()
+            .find(|s| !s.is_expired(today) && s.matches(finding))
+    }
+
+    /// Suppressions past their expiry date
+    pub fn expired(&self, today: NaiveDate) -> Vec<Suppression> {
+        self.suppressions
+            .iter()
+            .filter(|s| s.is_expired(today))
+            .cloned()
+            .collect()
+    }
+}
+
+/// Result of comparing a scan against a baseline
+#[derive(Clone, Debug, Default)]
+pub struct ScanDiff {
+    /// Findings not present in the baseline
+    pub new: Vec<SecurityFinding>,
+    /// Findings already present in the baseline
+    pub unchanged: Vec<SecurityFinding>,
+    /// Baseline findings no longer reported
+    pub removed: Vec<BaselineEntry>,
+    /// Findings hidden by an active suppression
+    pub suppressed: Vec<(SecurityFinding, Suppression)>,
+    /// Suppressions whose expiry date has passed
+    pub expired_suppressions: Vec<Suppression>,
+}
+
+impl ScanDiff {
+    /// True when the scan introduced nothing new
+    pub fn is_clean(&self) -> bool {
+        self.new.is_empty()
+    }
+
+    /// New findings as a report, for export or gating
+    pub fn new_findings_report(&self, target: &str) -> SecurityReport {
+        let mut report = SecurityReport::new(target);
+        for finding in &self.new {
+            report.add_finding(finding.clone());
+        }
+        report.calculate_risk_score();
+        report
+    }
+}
+
+fn diff_findings(
+    baseline: Option<&Baseline>,
+    report: &SecurityReport,
+    suppressions: &SuppressionSet,
+) -> ScanDiff {
+    let today = chrono::Utc::now().date_naive();
+    let mut diff = ScanDiff {
+        expired_suppressions: suppressions.expired(today),
+        ..Default::default()
+    };
+    let mut seen = HashSet::new();
+
+    for finding in &report.findings {
+        let fingerprint = finding_fingerprint(finding);
+        seen.insert(fingerprint.clone());
+
+        if let Some(suppression) = suppressions.find(finding, today) {
+            diff.suppressed.push((finding.clone(), suppression.clone()));
+        } else if baseline.is_some_and(|b| b.entries.contains_key(&fingerprint)) {
+            diff.unchanged.push(finding.clone());
+        } else {
+            diff.new.push(finding.clone());
+        }
+    }
+
+    if let Some(baseline) = baseline {
+        diff.removed = baseline
+            .entries
+            .iter()
+            .filter(|(fingerprint, _)| !seen.contains(*fingerprint))
+            .map(|(_, entry)| entry.clone())
+            .collect();
+    }
+
+    diff
+}
+
+impl CerberAgent {
+    /// Scan `target` and diff against the stored baseline for `key`
+    ///
+    /// Inline suppressions in source targets are applied alongside
+    /// `suppressions`. The new report becomes the baseline for the next scan.
+    pub async fn scan_against_baseline(
+        &self,
+        key: &str,
+        target: &ScanTarget,
+        suppressions: &SuppressionSet,
+    ) -> Result<ScanDiff, SecurityError> {
+        let report = self.scan(target).await?;
+
+        let mut all = suppressions.clone();
+        if let ScanTarget::SoliditySource(source) | ScanTarget::RustSource(source) = target {
+            all.extend(SuppressionSet::from_source(source));
+        }
+
+        let diff = diff_findings(self.baselines.read().get(key), &report, &all);
+        self.baselines
+            .write()
+            .insert(key.to_string(), Baseline::from_report(&report));
+        Ok(diff)
+    }
+
+    /// Stored baseline for `key`
+    pub fn baseline(&self, key: &str) -> Option<Baseline> {
+        self.baselines.read().get(key).cloned()
+    }
+
+    /// Replace the baseline for `key`, e.g. one loaded from disk
+    pub fn set_baseline(&self, key: &str, baseline: Baseline) {
+        self.baselines.write().insert(key.to_string(), baseline);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn finding(id: &str, title: &str, line: usize) -> SecurityFinding {
+        SecurityFinding {
+            id: id.to_string(),
+            title: title.to_string(),
+            description: String::new(),
+            severity: Severity::High,
+            category: "access-control".to_string(),
+            location: Some(format!("line {}", line)),
+            remediation: String::new(),
+            cwe_id: None,
+            confidence: 0.9,
+            timestamp: 0,
+            metadata: HashMap::new(),
+        }
+    }
+
+    fn report(findings: Vec<SecurityFinding>) -> SecurityReport {
+        let mut report = SecurityReport::new("contract");
+        for f in findings {
+            report.add_finding(f);
+        }
+        report
+    }
+
+    #[test]
+    fn test_diff_new_and_removed() {
+        let before = report(vec![
+            finding("TXORIGIN-001-0", "tx.origin", 3),
+            finding("DELEGATECALL-001-1", "Delegatecall", 9),
+        ]);
+        let baseline = Baseline::from_report(&before);
+
+        // Counters change between scans but fingerprints do not
+        let after = report(vec![
+            finding("TXORIGIN-001-4", "tx.origin", 3),
+            finding("SQLI-001-5", "SQL", 12),
+        ]);
+        let diff = baseline.diff(&after, &SuppressionSet::new());
+
+        assert_eq!(diff.unchanged.len(), 1);
+        assert_eq!(diff.new.len(), 1);
+        assert_eq!(diff.new[0].title, "SQL");
+        assert_eq!(diff.removed.len(), 1);
+        assert_eq!(diff.removed[0].title, "Delegatecall");
+    }
+
+    #[test]
+    fn test_file_suppressions_with_expiry() {
+        let suppressions = SuppressionSet::from_toml(
+            r#"
+            [[suppress]]
+            rule = "TXORIGIN-001"
+            reason = "legacy admin"
+            expires = "2999-01-01"
+
+            [[suppress]]
+            rule = "SQLI-001"
+            expires = "2000-01-01"
+            "#,
+        )
+        .unwrap();
+
+        let diff = Baseline::from_report(&report(Vec::new())).diff(
+            &report(vec![
+                finding("SC-TXORIGIN-001", "tx.origin", 3),
+                finding("SQLI-001-2", "SQL", 12),
+            ]),
+            &suppressions,
+        );
+
+        assert_eq!(diff.suppressed.len(), 1);
+        assert_eq!(diff.suppressed[0].1.reason, "legacy admin");
+        assert_eq!(diff.new.len(), 1);
+        assert_eq!(diff.expired_suppressions.len(), 1);
+    }
+
+    #[test]
+    fn test_inline_suppressions() {
+        let source = "contract A {\n    // cerber-ignore: TXORIGIN-001 until=2999-12-31 owner check\n    require(tx.origin == owner);\n    x.delegatecall(d); // cerber-ignore: DELEGATECALL-001\n}";
+        let set = SuppressionSet::from_source(source);
+        assert_eq!(set.len(), 2);
+
+        let today = chrono::Utc::now().date_naive();
+        assert!(set
+            .find(&finding("TXORIGIN-001-0", "tx", 3), today)
+            .is_some());
+        assert!(set
+            .find(&finding("TXORIGIN-001-0", "tx", 2), today)
+            .is_none());
+        assert!(set
+            .find(&finding("DELEGATECALL-001-1", "dc", 4), today)
+            .is_some());
+        assert!(!matches_rule("TXORIGIN-0010", "TXORIGIN-001"));
+    }
+
+    #[tokio::test]
+    async fn test_agent_scan_against_baseline() {
+        let agent = CerberAgent::default();
+        let target = ScanTarget::SoliditySource(
+            "pragma solidity ^0.8.0;\ncontract A { function f() public { require(tx.origin == msg.sender); } }"
+                .to_string(),
+        );
+
+        let first = agent
+            .scan_against_baseline("a", &target, &SuppressionSet::new())
+            .await
+            .unwrap();
+        assert!(!first.is_clean());
+
+        let second = agent
+            .scan_against_baseline("a", &target, &SuppressionSet::new())
+            .await
+            .unwrap();
+        assert!(second.is_clean());
+        assert!(second.removed.is_empty());
+        assert!(agent.baseline("a").is_some());
+
+        let ignored = ScanTarget::SoliditySource(
+            "pragma solidity ^0.8.0;\ncontract A { function f() public { require(tx.origin == msg.sender); } } // cerber-ignore: TXORIGIN-001"
+                .to_string(),
+        );
+        let diff = agent
+            .scan_against_baseline("b", &ignored, &SuppressionSet::new())
+            .await
+            .unwrap();
+        assert!(!diff.suppressed.is_empty());
+        assert!(!diff.new.iter().any(|f| f.title.contains("tx.origin")));
+    }
+}
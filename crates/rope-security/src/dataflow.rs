@@ -0,0 +1,837 @@
+//! Taint Analysis
+//!
+//! Lightweight dataflow pass for Solidity and Rust sources. Sources are
+//! tokenized and split into functions and statements, then values from
+//! user-controlled inputs (parameters of public entry points, `msg.data`,
+//! `env::args`, `env::var`) are followed through assignments to dangerous
+//! sinks. Unlike the regex patterns this works across lines, and in
+//! Solidity a `require`/`assert` on a value before it reaches a sink counts
+//! as validation.
+
+use super::*;
+
+/// Languages the dataflow pass understands
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SourceLanguage {
+    Solidity,
+    Rust,
+}
+
+/// Token kinds
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TokenKind {
+    Ident,
+    Number,
+    Str,
+    Punct,
+}
+
+/// Source token with its line
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SourceToken {
+    pub kind: TokenKind,
+    pub text: String,
+    pub line: usize,
+}
+
+impl SourceToken {
+    fn is(&self, text: &str) -> bool {
+        self.text == text && self.kind != TokenKind::Str
+    }
+}
+
+/// Split source into tokens, dropping comments and whitespace
+pub fn tokenize(source: &str) -> Vec<SourceToken> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut tokens = Vec::new();
+    let mut line = 1;
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+
+        if c == '\n' {
+            line += 1;
+            i += 1;
+        } else if c.is_whitespace() {
+            i += 1;
+        } else if c == '/' && next == Some('/') {
+            while i < chars.len() && chars[i] != '\n' {
+                i += 1;
+            }
+        } else if c == '/' && next == Some('*') {
+            i += 2;
+            while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
+                if chars[i] == '\n' {
+                    line += 1;
+                }
+                i += 1;
+            }
+            i += 2;
+        } else if c == '"' || (c == '\'' && chars.get(i + 2) == Some(&'\'')) {
+            let start_line = line;
+            let mut text = String::new();
+            i += 1;
+            while i < chars.len() && chars[i] != c {
+                if chars[i] == '\\' && i + 1 < chars.len() {
+                    text.push(chars[i]);
+                    i += 1;
+                }
+                if chars[i] == '\n' {
+                    line += 1;
+                }
+                text.push(chars[i]);
+                i += 1;
+            }
+            i += 1;
+            tokens.push(SourceToken {
+                kind: TokenKind::Str,
+                text,
+                line: start_line,
+            });
+        } else if c.is_alphabetic() || c == '_' {
+            let start = i;
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            tokens.push(SourceToken {
+                kind: TokenKind::Ident,
+                text: chars[start..i].iter().collect(),
+                line,
+            });
+        } else if c.is_ascii_digit() {
+            let start = i;
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            tokens.push(SourceToken {
+                kind: TokenKind::Number,
+                text: chars[start..i].iter().collect(),
+                line,
+            });
+        } else {
+            tokens.push(SourceToken {
+                kind: TokenKind::Punct,
+                text: c.to_string(),
+                line,
+            });
+            i += 1;
+        }
+    }
+
+    tokens
+}
+
+/// Statement in a function body
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Statement {
+    /// `target = value`, including declarations and compound assignment
+    Assign {
+        target: String,
+        value: Vec<SourceToken>,
+        line: usize,
+    },
+    /// Solidity `require(..)` / `assert(..)`
+    Guard {
+        tokens: Vec<SourceToken>,
+        line: usize,
+    },
+    /// Anything else
+    Expr {
+        tokens: Vec<SourceToken>,
+        line: usize,
+    },
+}
+
+impl Statement {
+    pub fn line(&self) -> usize {
+        match self {
+            Statement::Assign { line, .. }
+            | Statement::Guard { line, .. }
+            | Statement::Expr { line, .. } => *line,
+        }
+    }
+
+    fn tokens(&self) -> &[SourceToken] {
+        match self {
+            Statement::Assign { value, .. } => value,
+            Statement::Guard { tokens, .. } | Statement::Expr { tokens, .. } => tokens,
+        }
+    }
+}
+
+/// Function with its parameters and flattened body
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParsedFunction {
+    pub name: String,
+    pub params: Vec<String>,
+    /// `public`/`external` in Solidity, `pub` in Rust
+    pub public: bool,
+    pub line: usize,
+    pub body: Vec<Statement>,
+}
+
+/// Parse the functions with bodies out of `source`
+pub fn parse(source: &str, language: SourceLanguage) -> Vec<ParsedFunction> {
+    let tokens = tokenize(source);
+    let keyword = match language {
+        SourceLanguage::Solidity => "function",
+        SourceLanguage::Rust => "fn",
+    };
+
+    let mut functions = Vec::new();
+    let mut i = 0;
+    while i < tokens.len() {
+        if !tokens[i].is(keyword) || tokens.get(i + 1).map(|t| t.kind) != Some(TokenKind::Ident) {
+            i += 1;
+            continue;
+        }
+
+        let name = tokens[i + 1].text.clone();
+        let line = tokens[i].line;
+        let Some(open) = (i + 2..tokens.len()).find(|&j| tokens[j].is("(")) else {
+            break;
+        };
+        let close = matching(&tokens, open, "(", ")");
+        let params = parse_params(&tokens[open + 1..close], language);
+
+        // Header runs to the body, or to `;` for declarations
+        let Some(body_open) = (close + 1..tokens.len())
+            .find(|&j| tokens[j].is("{") || tokens[j].is(";"))
+            .filter(|&j| tokens[j].is("{"))
+        else {
+            i = close + 1;
+            continue;
+        };
+        let body_close = matching(&tokens, body_open, "{", "}");
+
+        let public = match language {
+            SourceLanguage::Solidity => tokens[close + 1..body_open]
+                .iter()
+                .any(|t| t.is("public") || t.is("external")),
+            SourceLanguage::Rust => is_rust_pub(&tokens, i),
+        };
+
+        functions.push(ParsedFunction {
+            name,
+            params,
+            public,
+            line,
+            body: parse_statements(&tokens[body_open + 1..body_close], language),
+        });
+        i = body_close + 1;
+    }
+
+    functions
+}
+
+/// Index of the token closing the group opened at `open`
+fn matching(tokens: &[SourceToken], open: usize, left: &str, right: &str) -> usize {
+    let mut depth = 0;
+    for (j, token) in tokens.iter().enumerate().skip(open) {
+        if token.is(left) {
+            depth += 1;
+        } else if token.is(right) {
+            depth -= 1;
+            if depth == 0 {
+                return j;
+            }
+        }
+    }
+    tokens.len()
+}
+
+/// Parameter names from the tokens between a function's parentheses
+fn parse_params(tokens: &[SourceToken], language: SourceLanguage) -> Vec<String> {
+    split_args(tokens)
+        .iter()
+        .filter_map(|segment| {
+            let name = match language {
+                // `address payable to`: the name comes last
+                SourceLanguage::Solidity => {
+                    segment.iter().rev().find(|t| t.kind == TokenKind::Ident)
+                }
+                // `mut name: Type`: the name comes right before the colon
+                SourceLanguage::Rust => {
+                    let colon = segment.iter().position(|t| t.is(":"))?;
+                    segment[..colon]
+                        .iter()
+                        .rev()
+                        .find(|t| t.kind == TokenKind::Ident)
+                }
+            }?;
+            (name.text != "self").then(|| name.text.clone())
+        })
+        .collect()
+}
+
+/// Split a parameter or argument list on top-level commas
+fn split_args(tokens: &[SourceToken]) -> Vec<&[SourceToken]> {
+    let mut segments = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+    for (j, token) in tokens.iter().enumerate() {
+        if token.kind != TokenKind::Punct {
+            continue;
+        }
+        match token.text.as_str() {
+            "(" | "<" | "[" | "{" => depth += 1,
+            // `->` and `=>` don't close a generic
+            ">" if j > 0 && (tokens[j - 1].is("-") || tokens[j - 1].is("=")) => {}
+            ")" | ">" | "]" | "}" => depth -= 1,
+            "," if depth == 0 => {
+                segments.push(&tokens[start..j]);
+                start = j + 1;
+            }
+            _ => {}
+        }
+    }
+    if start < tokens.len() {
+        segments.push(&tokens[start..]);
+    }
+    segments
+}
+
+/// Whether the `fn` at `index` is preceded by `pub`
+fn is_rust_pub(tokens: &[SourceToken], index: usize) -> bool {
+    let qualifiers = [
+        "pub", "crate", "super", "async", "unsafe", "const", "extern", "(", ")",
+    ];
+    tokens[..index]
+        .iter()
+        .rev()
+        .take_while(|t| t.kind == TokenKind::Str || qualifiers.contains(&t.text.as_str()))
+        .any(|t| t.is("pub"))
+}
+
+/// Split a body into statements; nested blocks are flattened in order
+fn parse_statements(tokens: &[SourceToken], language: SourceLanguage) -> Vec<Statement> {
+    let mut statements = Vec::new();
+    let mut current: Vec<SourceToken> = Vec::new();
+    let mut parens = 0;
+    let mut inline_braces = 0;
+
+    for (j, token) in tokens.iter().enumerate() {
+        match token.text.as_str() {
+            "(" | "[" if token.kind == TokenKind::Punct => parens += 1,
+            ")" | "]" if token.kind == TokenKind::Punct => parens -= 1,
+            // `addr.call{value: v}(..)` keeps its options in the statement
+            "{" if token.kind == TokenKind::Punct
+                && language == SourceLanguage::Solidity
+                && j >= 2
+                && tokens[j - 2].is(".") =>
+            {
+                inline_braces += 1;
+            }
+            "}" if token.kind == TokenKind::Punct && inline_braces > 0 => inline_braces -= 1,
+            ";" | "{" | "}"
+                if token.kind == TokenKind::Punct && parens <= 0 && inline_braces == 0 =>
+            {
+                if !current.is_empty() {
+                    statements.push(classify(std::mem::take(&mut current), language));
+                }
+                continue;
+            }
+            _ => {}
+        }
+        current.push(token.clone());
+    }
+    if !current.is_empty() {
+        statements.push(classify(current, language));
+    }
+
+    statements
+}
+
+fn classify(tokens: Vec<SourceToken>, language: SourceLanguage) -> Statement {
+    let line = tokens[0].line;
+
+    if language == SourceLanguage::Solidity && (tokens[0].is("require") || tokens[0].is("assert")) {
+        return Statement::Guard { tokens, line };
+    }
+
+    if let Some(eq) = assignment_index(&tokens) {
+        let lhs = &tokens[..eq];
+        let target = if lhs[0].is("let") {
+            lhs.iter()
+                .skip(1)
+                .find(|t| t.kind == TokenKind::Ident && t.text != "mut")
+        } else if lhs.iter().any(|t| t.is("[") || t.is(".")) {
+            // `balances[to] = ..` writes to `balances`
+            lhs.iter().find(|t| t.kind == TokenKind::Ident)
+        } else {
+            lhs.iter().rev().find(|t| t.kind == TokenKind::Ident)
+        };
+        if let Some(target) = target {
+            return Statement::Assign {
+                target: target.text.clone(),
+                value: tokens[eq + 1..].to_vec(),
+                line,
+            };
+        }
+    }
+
+    Statement::Expr { tokens, line }
+}
+
+/// Index of a top-level `=` that assigns (not `==`, `<=`, `=>`, ...)
+fn assignment_index(tokens: &[SourceToken]) -> Option<usize> {
+    let mut depth = 0;
+    for (j, token) in tokens.iter().enumerate() {
+        match token.text.as_str() {
+            "(" | "[" | "{" if token.kind == TokenKind::Punct => depth += 1,
+            ")" | "]" | "}" if token.kind == TokenKind::Punct => depth -= 1,
+            "=" if token.kind == TokenKind::Punct && depth == 0 && j > 0 => {
+                let prev = tokens[j - 1].text.as_str();
+                let next = tokens.get(j + 1).map(|t| t.text.as_str());
+                if !matches!(prev, "=" | "!" | "<" | ">") && !matches!(next, Some("=" | ">")) {
+                    return Some(j);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Dangerous sinks
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TaintSink {
+    /// Solidity `delegatecall` with user-controlled target or calldata
+    Delegatecall,
+    /// Solidity value transfer with a user-controlled, unchecked amount
+    ValueTransfer,
+    /// Solidity `selfdestruct` to a user-controlled beneficiary
+    Selfdestruct,
+    /// Rust SQL query built from user input
+    SqlQuery,
+    /// Rust process spawned from user input
+    Command,
+}
+
+impl TaintSink {
+    pub fn id(&self) -> &'static str {
+        match self {
+            TaintSink::Delegatecall => "TAINT-DELEGATECALL",
+            TaintSink::ValueTransfer => "TAINT-TRANSFER",
+            TaintSink::Selfdestruct => "TAINT-SELFDESTRUCT",
+            TaintSink::SqlQuery => "TAINT-SQL",
+            TaintSink::Command => "TAINT-COMMAND",
+        }
+    }
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            TaintSink::Delegatecall => "User-Controlled Delegatecall",
+            TaintSink::ValueTransfer => "Unchecked User-Controlled Transfer Amount",
+            TaintSink::Selfdestruct => "User-Controlled Selfdestruct Beneficiary",
+            TaintSink::SqlQuery => "SQL Injection",
+            TaintSink::Command => "Command Injection",
+        }
+    }
+
+    pub fn severity(&self) -> Severity {
+        match self {
+            TaintSink::Delegatecall => Severity::Critical,
+            _ => Severity::High,
+        }
+    }
+
+    pub fn category(&self) -> &'static str {
+        match self {
+            TaintSink::Delegatecall => "access-control",
+            TaintSink::ValueTransfer => "input-validation",
+            TaintSink::Selfdestruct => "denial-of-service",
+            TaintSink::SqlQuery | TaintSink::Command => "injection",
+        }
+    }
+
+    pub fn cwe_id(&self) -> u32 {
+        match self {
+            TaintSink::Delegatecall => 829,
+            TaintSink::ValueTransfer => 20,
+            TaintSink::Selfdestruct => 284,
+            TaintSink::SqlQuery => 89,
+            TaintSink::Command => 78,
+        }
+    }
+
+    /// Regex pattern this sink confirms or refutes
+    pub fn regex_pattern_id(&self) -> Option<&'static str> {
+        match self {
+            TaintSink::Delegatecall => Some("DELEGATECALL-001"),
+            TaintSink::SqlQuery => Some("SQLI-001"),
+            _ => None,
+        }
+    }
+}
+
+/// User-controlled value reaching a sink
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TaintFlow {
+    pub sink: TaintSink,
+    pub function: String,
+    pub line: usize,
+    /// Tainted variable at the sink
+    pub variable: String,
+    /// Where the value came from (parameter name, `msg.data`, ...)
+    pub source: String,
+}
+
+impl TaintFlow {
+    pub fn to_finding(&self) -> SecurityFinding {
+        SecurityFinding {
+            id: format!("{}-{}", self.sink.id(), self.line),
+            title: self.sink.title().to_string(),
+            description: format!(
+                "`{}` in `{}` is derived from user input `{}` and reaches a dangerous sink",
+                self.variable, self.function, self.source
+            ),
+            severity: self.sink.severity(),
+            category: self.sink.category().to_string(),
+            location: Some(format!("line {}", self.line)),
+            remediation: scanner::get_remediation(self.sink.category()),
+            cwe_id: Some(self.sink.cwe_id()),
+            confidence: 0.9,
+            timestamp: chrono::Utc::now().timestamp(),
+            metadata: HashMap::from([
+                ("function".to_string(), self.function.clone()),
+                ("source".to_string(), self.source.clone()),
+            ]),
+        }
+    }
+}
+
+/// Taint flows in `source`
+pub fn analyze(source: &str, language: SourceLanguage) -> Vec<TaintFlow> {
+    parse(source, language)
+        .iter()
+        .flat_map(|function| analyze_function(function, language))
+        .collect()
+}
+
+/// Taint state within one function
+struct TaintState {
+    /// Tainted variable -> original source
+    tainted: HashMap<String, String>,
+    /// Variables checked by a guard
+    validated: HashSet<String>,
+}
+
+impl TaintState {
+    /// First tainted value `tokens` read, with its source. Validated
+    /// variables are skipped when `honor_guards` is set.
+    fn taint_of(
+        &self,
+        tokens: &[SourceToken],
+        language: SourceLanguage,
+        honor_guards: bool,
+    ) -> Option<(String, String)> {
+        for j in 0..tokens.len() {
+            if let Some(source) = source_at(tokens, j, language) {
+                return Some((source.clone(), source));
+            }
+            for name in references(tokens, j, language) {
+                if honor_guards && self.validated.contains(&name) {
+                    continue;
+                }
+                if let Some(source) = self.tainted.get(&name) {
+                    return Some((name, source.clone()));
+                }
+            }
+        }
+        None
+    }
+}
+
+fn analyze_function(function: &ParsedFunction, language: SourceLanguage) -> Vec<TaintFlow> {
+    let mut state = TaintState {
+        tainted: HashMap::new(),
+        validated: HashSet::new(),
+    };
+    if function.public {
+        for param in &function.params {
+            state.tainted.insert(param.clone(), param.clone());
+        }
+    }
+
+    let mut flows = Vec::new();
+    for statement in &function.body {
+        for (sink, args) in sinks(statement.tokens(), language) {
+            if let Some((variable, source)) = state.taint_of(&args, language, true) {
+                flows.push(TaintFlow {
+                    sink,
+                    function: function.name.clone(),
+                    line: statement.line(),
+                    variable,
+                    source,
+                });
+            }
+        }
+
+        match statement {
+            Statement::Assign { target, value, .. } => {
+                match state.taint_of(value, language, false) {
+                    Some((_, source)) => {
+                        state.tainted.insert(target.clone(), source);
+                        state.validated.remove(target);
+                    }
+                    None => {
+                        state.tainted.remove(target);
+                    }
+                }
+            }
+            Statement::Guard { tokens, .. } => {
+                for j in 0..tokens.len() {
+                    state.validated.extend(references(tokens, j, language));
+                }
+            }
+            Statement::Expr { .. } => {}
+        }
+    }
+
+    flows
+}
+
+/// Input source starting at `tokens[j]`
+fn source_at(tokens: &[SourceToken], j: usize, language: SourceLanguage) -> Option<String> {
+    let text = |k: usize| tokens.get(k).map(|t| t.text.as_str());
+    match language {
+        SourceLanguage::Solidity => {
+            (text(j) == Some("msg") && text(j + 1) == Some(".") && text(j + 2) == Some("data"))
+                .then(|| "msg.data".to_string())
+        }
+        SourceLanguage::Rust => (text(j) == Some("env")
+            && text(j + 1) == Some(":")
+            && text(j + 2) == Some(":")
+            && matches!(text(j + 3), Some("args" | "var")))
+        .then(|| format!("env::{}", text(j + 3).unwrap_or_default())),
+    }
+}
+
+/// Variables read by `tokens[j]`: a plain identifier (not a field or
+/// method), or names captured by a Rust format string
+fn references(tokens: &[SourceToken], j: usize, language: SourceLanguage) -> Vec<String> {
+    let token = &tokens[j];
+    match token.kind {
+        TokenKind::Ident if j == 0 || !tokens[j - 1].is(".") => vec![token.text.clone()],
+        TokenKind::Str if language == SourceLanguage::Rust => token
+            .text
+            .split('{')
+            .skip(1)
+            .filter_map(|part| {
+                let name: String = part
+                    .chars()
+                    .take_while(|c| c.is_alphanumeric() || *c == '_')
+                    .collect();
+                (!name.is_empty() && !name.chars().next().unwrap().is_ascii_digit()).then_some(name)
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Sinks in a statement with the tokens whose taint matters
+fn sinks(tokens: &[SourceToken], language: SourceLanguage) -> Vec<(TaintSink, Vec<SourceToken>)> {
+    let mut found = Vec::new();
+    for (j, token) in tokens.iter().enumerate() {
+        if token.kind != TokenKind::Ident {
+            continue;
+        }
+        let after_dot = j > 0 && tokens[j - 1].is(".");
+        let args = || call_args(tokens, j + 1);
+
+        match (language, token.text.as_str()) {
+            (SourceLanguage::Solidity, "delegatecall") if after_dot => {
+                // Target and calldata both matter
+                let mut checked = receiver(tokens, j - 1);
+                checked.extend(args());
+                found.push((TaintSink::Delegatecall, checked));
+            }
+            (SourceLanguage::Solidity, "transfer" | "send") if after_dot => {
+                // Only the amount: ERC-20 `transfer(to, amount)` to any recipient is normal
+                let args = args();
+                let amount = split_args(&args).last().map(|a| a.to_vec());
+                found.push((TaintSink::ValueTransfer, amount.unwrap_or_default()));
+            }
+            (SourceLanguage::Solidity, "call")
+                if after_dot && tokens.get(j + 1).is_some_and(|t| t.is("{")) =>
+            {
+                let close = matching(tokens, j + 1, "{", "}");
+                found.push((TaintSink::ValueTransfer, tokens[j + 2..close].to_vec()));
+            }
+            (SourceLanguage::Solidity, "selfdestruct" | "suicide") => {
+                found.push((TaintSink::Selfdestruct, args()));
+            }
+            (
+                SourceLanguage::Rust,
+                "query" | "query_as" | "query_scalar" | "execute" | "sql_query",
+            ) => {
+                found.push((TaintSink::SqlQuery, args()));
+            }
+            (SourceLanguage::Rust, "Command") if tokens.get(j + 3).is_some_and(|t| t.is("new")) => {
+                found.push((TaintSink::Command, call_args(tokens, j + 4)));
+            }
+            _ => {}
+        }
+    }
+    found
+}
+
+/// Tokens inside the call parentheses starting at or after `from`,
+/// skipping a turbofish
+fn call_args(tokens: &[SourceToken], from: usize) -> Vec<SourceToken> {
+    let mut j = from;
+    if tokens.get(j).is_some_and(|t| t.is(":")) {
+        while j < tokens.len() && !tokens[j].is("(") {
+            j += 1;
+        }
+    }
+    if !tokens.get(j).is_some_and(|t| t.is("(")) {
+        return Vec::new();
+    }
+    let close = matching(tokens, j, "(", ")");
+    tokens[j + 1..close.min(tokens.len())].to_vec()
+}
+
+/// Receiver expression before the `.` at `dot`, e.g. `target` or `payable(to)`
+fn receiver(tokens: &[SourceToken], dot: usize) -> Vec<SourceToken> {
+    let mut depth = 0;
+    let mut start = dot;
+    while start > 0 {
+        let token = &tokens[start - 1];
+        if token.is(")") || token.is("]") {
+            depth += 1;
+        } else if token.is("(") || token.is("[") {
+            if depth == 0 {
+                break;
+            }
+            depth -= 1;
+        } else if depth == 0 && token.kind == TokenKind::Punct && !token.is(".") {
+            break;
+        }
+        start -= 1;
+    }
+    tokens[start..dot].to_vec()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_functions() {
+        let source = r#"
+            contract Proxy {
+                function forward(address target, bytes calldata data) external {
+                    bytes memory payload = data;
+                    require(owner == msg.sender, "owner");
+                }
+                function internalOnly(uint256 x) internal {}
+            }
+        "#;
+        let functions = parse(source, SourceLanguage::Solidity);
+
+        assert_eq!(functions.len(), 2);
+        assert_eq!(functions[0].name, "forward");
+        assert_eq!(functions[0].params, vec!["target", "data"]);
+        assert!(functions[0].public);
+        assert!(!functions[1].public);
+        assert!(matches!(
+            &functions[0].body[0],
+            Statement::Assign { target, .. } if target == "payload"
+        ));
+        assert!(matches!(functions[0].body[1], Statement::Guard { .. }));
+    }
+
+    #[test]
+    fn test_multiline_delegatecall_flow() {
+        let source = r#"
+            contract Proxy {
+                function forward(address impl, bytes calldata data) external {
+                    address target =
+                        impl;
+                    (bool ok, ) = target
+                        .delegatecall(data);
+                }
+
+                function safe() external {
+                    (bool ok, ) = implementation.delegatecall(msg.sig);
+                }
+            }
+        "#;
+        let flows = analyze(source, SourceLanguage::Solidity);
+
+        assert_eq!(flows.len(), 1);
+        assert_eq!(flows[0].sink, TaintSink::Delegatecall);
+        assert_eq!(flows[0].function, "forward");
+        assert_eq!(flows[0].variable, "target");
+        assert_eq!(flows[0].source, "impl");
+    }
+
+    #[test]
+    fn test_guard_validates_transfer_amount() {
+        let source = r#"
+            contract Vault {
+                function checked(uint256 amount) external {
+                    require(amount <= balances[msg.sender]);
+                    payable(msg.sender).transfer(amount);
+                }
+                function unchecked(uint256 amount) public {
+                    msg.sender.call{value: amount}("");
+                }
+            }
+        "#;
+        let flows = analyze(source, SourceLanguage::Solidity);
+
+        assert_eq!(flows.len(), 1);
+        assert_eq!(flows[0].sink, TaintSink::ValueTransfer);
+        assert_eq!(flows[0].function, "unchecked");
+    }
+
+    #[test]
+    fn test_rust_sql_flow() {
+        let source = r#"
+            pub async fn find_user(pool: &PgPool, name: &str) -> Result<User> {
+                let sql = format!("SELECT * FROM users WHERE name = '{}'", name);
+                sqlx::query_as::<_, User>(&sql).fetch_one(pool).await
+            }
+
+            pub async fn find_user_bound(pool: &PgPool, name: &str) -> Result<User> {
+                sqlx::query_as::<_, User>("SELECT * FROM users WHERE name = $1")
+                    .bind(name)
+                    .fetch_one(pool)
+                    .await
+            }
+
+            pub fn inline(name: String) {
+                let sql = format!("DELETE FROM t WHERE n = '{name}'");
+                conn.execute(&sql);
+            }
+        "#;
+        let flows = analyze(source, SourceLanguage::Rust);
+
+        assert_eq!(flows.len(), 2);
+        assert!(flows.iter().all(|f| f.sink == TaintSink::SqlQuery));
+        assert_eq!(flows[0].function, "find_user");
+        assert_eq!(flows[0].variable, "sql");
+        assert_eq!(flows[0].source, "name");
+        assert_eq!(flows[1].function, "inline");
+    }
+
+    #[test]
+    fn test_rust_env_source() {
+        let source = r#"
+            fn main() {
+                let cmd = std::env::args().nth(1).unwrap();
+                Command::new(cmd).spawn();
+            }
+        "#;
+        let flows = analyze(source, SourceLanguage::Rust);
+
+        assert_eq!(flows.len(), 1);
+        assert_eq!(flows[0].sink, TaintSink::Command);
+        assert_eq!(flows[0].source, "env::args");
+    }
+}
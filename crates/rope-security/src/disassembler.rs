@@ -0,0 +1,401 @@
+//! EVM Disassembler
+//!
+//! Decodes contract bytecode into instructions, skipping PUSH immediates
+//! and the Solidity CBOR metadata trailer, so byte patterns are only
+//! matched against real opcodes. Findings carry the program counter of
+//! the offending instruction.
+
+use super::*;
+use sha3::{Digest, Keccak256};
+
+pub const OP_POP: u8 = 0x50;
+pub const OP_PUSH1: u8 = 0x60;
+pub const OP_PUSH32: u8 = 0x7f;
+pub const OP_CALL: u8 = 0xf1;
+pub const OP_CALLCODE: u8 = 0xf2;
+pub const OP_DELEGATECALL: u8 = 0xf4;
+pub const OP_STATICCALL: u8 = 0xfa;
+pub const OP_SELFDESTRUCT: u8 = 0xff;
+
+/// Decoded instruction
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Instruction {
+    /// Program counter
+    pub pc: usize,
+    pub opcode: u8,
+    /// PUSH immediate, truncated if the code ends early
+    pub immediate: Vec<u8>,
+}
+
+impl Instruction {
+    pub fn name(&self) -> &'static str {
+        opcode_name(self.opcode)
+    }
+
+    /// Whether this is one of the CALL family
+    pub fn is_call(&self) -> bool {
+        matches!(
+            self.opcode,
+            OP_CALL | OP_CALLCODE | OP_DELEGATECALL | OP_STATICCALL
+        )
+    }
+}
+
+impl std::fmt::Display for Instruction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:#06x}: {}", self.pc, self.name())?;
+        if !self.immediate.is_empty() {
+            write!(f, " 0x{}", hex::encode(&self.immediate))?;
+        }
+        Ok(())
+    }
+}
+
+/// Mnemonic for an opcode
+pub fn opcode_name(opcode: u8) -> &'static str {
+    const PUSH: [&str; 32] = [
+        "PUSH1", "PUSH2", "PUSH3", "PUSH4", "PUSH5", "PUSH6", "PUSH7", "PUSH8", "PUSH9", "PUSH10",
+        "PUSH11", "PUSH12", "PUSH13", "PUSH14", "PUSH15", "PUSH16", "PUSH17", "PUSH18", "PUSH19",
+        "PUSH20", "PUSH21", "PUSH22", "PUSH23", "PUSH24", "PUSH25", "PUSH26", "PUSH27", "PUSH28",
+        "PUSH29", "PUSH30", "PUSH31", "PUSH32",
+    ];
+    const DUP: [&str; 16] = [
+        "DUP1", "DUP2", "DUP3", "DUP4", "DUP5", "DUP6", "DUP7", "DUP8", "DUP9", "DUP10", "DUP11",
+        "DUP12", "DUP13", "DUP14", "DUP15", "DUP16",
+    ];
+    const SWAP: [&str; 16] = [
+        "SWAP1", "SWAP2", "SWAP3", "SWAP4", "SWAP5", "SWAP6", "SWAP7", "SWAP8", "SWAP9", "SWAP10",
+        "SWAP11", "SWAP12", "SWAP13", "SWAP14", "SWAP15", "SWAP16",
+    ];
+
+    match opcode {
+        0x00 => "STOP",
+        0x01 => "ADD",
+        0x02 => "MUL",
+        0x03 => "SUB",
+        0x04 => "DIV",
+        0x05 => "SDIV",
+        0x06 => "MOD",
+        0x07 => "SMOD",
+        0x08 => "ADDMOD",
+        0x09 => "MULMOD",
+        0x0a => "EXP",
+        0x0b => "SIGNEXTEND",
+        0x10 => "LT",
+        0x11 => "GT",
+        0x12 => "SLT",
+        0x13 => "SGT",
+        0x14 => "EQ",
+        0x15 => "ISZERO",
+        0x16 => "AND",
+        0x17 => "OR",
+        0x18 => "XOR",
+        0x19 => "NOT",
+        0x1a => "BYTE",
+        0x1b => "SHL",
+        0x1c => "SHR",
+        0x1d => "SAR",
+        0x20 => "KECCAK256",
+        0x30 => "ADDRESS",
+        0x31 => "BALANCE",
+        0x32 => "ORIGIN",
+        0x33 => "CALLER",
+        0x34 => "CALLVALUE",
+        0x35 => "CALLDATALOAD",
+        0x36 => "CALLDATASIZE",
+        0x37 => "CALLDATACOPY",
+        0x38 => "CODESIZE",
+        0x39 => "CODECOPY",
+        0x3a => "GASPRICE",
+        0x3b => "EXTCODESIZE",
+        0x3c => "EXTCODECOPY",
+        0x3d => "RETURNDATASIZE",
+        0x3e => "RETURNDATACOPY",
+        0x3f => "EXTCODEHASH",
+        0x40 => "BLOCKHASH",
+        0x41 => "COINBASE",
+        0x42 => "TIMESTAMP",
+        0x43 => "NUMBER",
+        0x44 => "PREVRANDAO",
+        0x45 => "GASLIMIT",
+        0x46 => "CHAINID",
+        0x47 => "SELFBALANCE",
+        0x48 => "BASEFEE",
+        0x49 => "BLOBHASH",
+        0x4a => "BLOBBASEFEE",
+        0x50 => "POP",
+        0x51 => "MLOAD",
+        0x52 => "MSTORE",
+        0x53 => "MSTORE8",
+        0x54 => "SLOAD",
+        0x55 => "SSTORE",
+        0x56 => "JUMP",
+        0x57 => "JUMPI",
+        0x58 => "PC",
+        0x59 => "MSIZE",
+        0x5a => "GAS",
+        0x5b => "JUMPDEST",
+        0x5c => "TLOAD",
+        0x5d => "TSTORE",
+        0x5e => "MCOPY",
+        0x5f => "PUSH0",
+        0x60..=0x7f => PUSH[(opcode - OP_PUSH1) as usize],
+        0x80..=0x8f => DUP[(opcode - 0x80) as usize],
+        0x90..=0x9f => SWAP[(opcode - 0x90) as usize],
+        0xa0 => "LOG0",
+        0xa1 => "LOG1",
+        0xa2 => "LOG2",
+        0xa3 => "LOG3",
+        0xa4 => "LOG4",
+        0xf0 => "CREATE",
+        0xf1 => "CALL",
+        0xf2 => "CALLCODE",
+        0xf3 => "RETURN",
+        0xf4 => "DELEGATECALL",
+        0xf5 => "CREATE2",
+        0xfa => "STATICCALL",
+        0xfd => "REVERT",
+        0xff => "SELFDESTRUCT",
+        _ => "INVALID",
+    }
+}
+
+/// Drop the CBOR metadata Solidity appends to runtime code
+///
+/// The last two bytes give the metadata length; the map starts with
+/// `0xa1`/`0xa2`/`0xa3` (one to three entries).
+pub fn strip_metadata(bytecode: &[u8]) -> &[u8] {
+    if bytecode.len() < 2 {
+        return bytecode;
+    }
+    let len =
+        u16::from_be_bytes([bytecode[bytecode.len() - 2], bytecode[bytecode.len() - 1]]) as usize;
+    let Some(start) = bytecode.len().checked_sub(len + 2) else {
+        return bytecode;
+    };
+    if len > 0 && matches!(bytecode[start], 0xa1..=0xa3) {
+        &bytecode[..start]
+    } else {
+        bytecode
+    }
+}
+
+/// Decode bytecode into instructions
+pub fn disassemble(bytecode: &[u8]) -> Vec<Instruction> {
+    let mut instructions = Vec::new();
+    let mut pc = 0;
+
+    while pc < bytecode.len() {
+        let opcode = bytecode[pc];
+        let size = if (OP_PUSH1..=OP_PUSH32).contains(&opcode) {
+            (opcode - OP_PUSH1 + 1) as usize
+        } else {
+            0
+        };
+        let end = (pc + 1 + size).min(bytecode.len());
+        instructions.push(Instruction {
+            pc,
+            opcode,
+            immediate: bytecode[pc + 1..end].to_vec(),
+        });
+        pc += 1 + size;
+    }
+
+    instructions
+}
+
+/// Keccak-256 of the code, as `0x`-prefixed hex (the EVM `EXTCODEHASH`)
+pub fn code_hash(bytecode: &[u8]) -> String {
+    format!("0x{}", hex::encode(Keccak256::digest(bytecode)))
+}
+
+/// CALL-family instructions whose success flag is discarded
+///
+/// Solidity checks a low-level call with `ISZERO`/`JUMPI` on the flag; an
+/// immediate `POP` throws it away.
+pub fn unchecked_calls(instructions: &[Instruction]) -> Vec<&Instruction> {
+    instructions
+        .windows(2)
+        .filter(|w| w[0].is_call() && w[1].opcode == OP_POP)
+        .map(|w| &w[0])
+        .collect()
+}
+
+/// Bytecode findings with program-counter locations
+pub fn analyze_bytecode(bytecode: &[u8], vuln_db: &VulnerabilityDatabase) -> Vec<SecurityFinding> {
+    let mut findings = Vec::new();
+    let now = chrono::Utc::now().timestamp();
+    let hash = code_hash(bytecode);
+
+    let finding = |id: String,
+                   title: &str,
+                   description: String,
+                   severity: Severity,
+                   category: &str,
+                   cwe_id: u32,
+                   instruction: Option<&Instruction>| {
+        let mut metadata = HashMap::from([("code_hash".to_string(), hash.clone())]);
+        if let Some(instruction) = instruction {
+            metadata.insert("pc".to_string(), instruction.pc.to_string());
+            metadata.insert("opcode".to_string(), instruction.name().to_string());
+        }
+        SecurityFinding {
+            id,
+            title: title.to_string(),
+            description,
+            severity,
+            category: category.to_string(),
+            location: instruction.map(|i| format!("pc {:#06x} ({})", i.pc, i.name())),
+            remediation: scanner::get_remediation(category),
+            cwe_id: Some(cwe_id),
+            confidence: 0.9,
+            timestamp: now,
+            metadata,
+        }
+    };
+
+    // Known malicious code, by Keccak code hash or by leading bytes
+    let hex_code = hex::encode(bytecode);
+    if vuln_db.is_malicious(&hash) || vuln_db.is_malicious(&hex_code[..64.min(hex_code.len())]) {
+        let mut malicious = finding(
+            "BYTECODE-MALICIOUS".to_string(),
+            "Known Malicious Contract",
+            format!("Code hash {} matches known malicious code", hash),
+            Severity::Critical,
+            "malware",
+            506,
+            None,
+        );
+        malicious.remediation = "Do not interact with this contract".to_string();
+        malicious.confidence = 1.0;
+        findings.push(malicious);
+    }
+
+    let instructions = disassemble(strip_metadata(bytecode));
+
+    for instruction in &instructions {
+        match instruction.opcode {
+            OP_DELEGATECALL => findings.push(finding(
+                "BYTECODE-DELEGATECALL".to_string(),
+                "Delegatecall Detected in Bytecode",
+                "Contract uses delegatecall which may allow code injection".to_string(),
+                Severity::High,
+                "access-control",
+                829,
+                Some(instruction),
+            )),
+            OP_CALLCODE => findings.push(finding(
+                "BYTECODE-CALLCODE".to_string(),
+                "Deprecated Callcode in Bytecode",
+                "CALLCODE runs foreign code against this contract's storage".to_string(),
+                Severity::High,
+                "access-control",
+                829,
+                Some(instruction),
+            )),
+            OP_SELFDESTRUCT => findings.push(finding(
+                "BYTECODE-SELFDESTRUCT".to_string(),
+                "Selfdestruct Detected in Bytecode",
+                "Contract can be destroyed".to_string(),
+                Severity::Medium,
+                "denial-of-service",
+                400,
+                Some(instruction),
+            )),
+            _ => {}
+        }
+    }
+
+    for call in unchecked_calls(&instructions) {
+        findings.push(finding(
+            "BYTECODE-UNCHECKED-CALL".to_string(),
+            "Unchecked Call Result",
+            format!(
+                "{} success flag is popped without being checked",
+                call.name()
+            ),
+            Severity::Medium,
+            "unchecked-return",
+            252,
+            Some(call),
+        ));
+    }
+
+    findings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_disassemble_skips_push_data() {
+        // PUSH2 0xf4ff, STOP
+        let instructions = disassemble(&[0x61, 0xf4, 0xff, 0x00]);
+
+        assert_eq!(instructions.len(), 2);
+        assert_eq!(instructions[0].name(), "PUSH2");
+        assert_eq!(instructions[0].immediate, vec![0xf4, 0xff]);
+        assert_eq!(instructions[1].pc, 3);
+        assert_eq!(instructions[1].to_string(), "0x0003: STOP");
+
+        let db = VulnerabilityDatabase::new();
+        assert!(analyze_bytecode(&[0x61, 0xf4, 0xff, 0x00], &db).is_empty());
+    }
+
+    #[test]
+    fn test_strip_metadata() {
+        let mut code = vec![0x60, 0x00, 0xff];
+        code.extend_from_slice(&[0xa1, 0x64, 0xff, 0xf4]);
+        code.extend_from_slice(&4u16.to_be_bytes());
+
+        assert_eq!(strip_metadata(&code), &[0x60, 0x00, 0xff]);
+        assert_eq!(strip_metadata(&[0x00, 0x00]), &[0x00, 0x00]);
+    }
+
+    #[test]
+    fn test_opcode_locations() {
+        // PUSH1 0, DELEGATECALL, POP, CALL, ISZERO, PUSH1 0, JUMPI, CALLER, SELFDESTRUCT
+        let code = [
+            0x60, 0x00, 0xf4, 0x50, 0xf1, 0x15, 0x60, 0x00, 0x57, 0x33, 0xff,
+        ];
+        let db = VulnerabilityDatabase::new();
+        let findings = analyze_bytecode(&code, &db);
+
+        let delegatecall = findings
+            .iter()
+            .find(|f| f.id == "BYTECODE-DELEGATECALL")
+            .unwrap();
+        assert_eq!(
+            delegatecall.location.as_deref(),
+            Some("pc 0x0002 (DELEGATECALL)")
+        );
+        let selfdestruct = findings
+            .iter()
+            .find(|f| f.id == "BYTECODE-SELFDESTRUCT")
+            .unwrap();
+        assert_eq!(selfdestruct.metadata["pc"], "10");
+
+        // Only the popped DELEGATECALL result is unchecked; CALL feeds ISZERO
+        let unchecked: Vec<_> = findings
+            .iter()
+            .filter(|f| f.category == "unchecked-return")
+            .collect();
+        assert_eq!(unchecked.len(), 1);
+        assert_eq!(unchecked[0].metadata["pc"], "2");
+    }
+
+    #[test]
+    fn test_malicious_code_hash() {
+        let code = [0x33, 0xff];
+        let db = VulnerabilityDatabase::new();
+        assert!(analyze_bytecode(&code, &db)
+            .iter()
+            .all(|f| f.id != "BYTECODE-MALICIOUS"));
+
+        db.add_malicious_signature(code_hash(&code));
+        assert!(analyze_bytecode(&code, &db)
+            .iter()
+            .any(|f| f.id == "BYTECODE-MALICIOUS"));
+    }
+}
@@ -6,11 +6,14 @@
 //! ## Features
 //!
 //! - **Static Analysis**: Code pattern detection for vulnerabilities
+//! - **Taint Analysis**: User input tracked to dangerous sinks
 //! - **Dynamic Analysis**: Runtime behavior monitoring
 //! - **Anomaly Detection**: ML-based threat identification
 //! - **Smart Contract Audit**: Solidity/EVM vulnerability scanning
-//! - **Dependency Audit**: Known vulnerability checking
+//! - **Dependency Audit**: `Cargo.lock` checked against RustSec advisories
+//! - **Secrets Detection**: Credential formats, mnemonics and high-entropy strings
 //! - **Reputation Scoring**: Entity trust assessment
+//! - **CI Integration**: SARIF and JUnit report export, baselines and suppressions
 //!
 //! ## Architecture
 //!
@@ -44,16 +47,33 @@ use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use thiserror::Error;
 
+pub mod advisory;
 pub mod analyzer;
+pub mod baseline;
+pub mod dataflow;
+pub mod disassembler;
+pub mod model;
 pub mod monitor;
+pub mod quarantine;
+mod report;
 pub mod reputation;
+pub mod rulepack;
 pub mod scanner;
+pub mod secrets;
 
 // Re-exports
+pub use advisory::*;
 pub use analyzer::*;
+pub use baseline::*;
+pub use dataflow::*;
+pub use disassembler::*;
+pub use model::*;
 pub use monitor::*;
+pub use quarantine::*;
 pub use reputation::*;
+pub use rulepack::*;
 pub use scanner::*;
+pub use secrets::*;
 
 /// Security severity levels
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
@@ -177,7 +197,8 @@ impl SecurityReport {
         self.passed = self.summary.critical_count == 0 && self.summary.high_count == 0;
     }
 
-    /// Generate JSON report
+    /// Generate JSON report (see also [`to_sarif`](Self::to_sarif) and
+    /// [`to_junit`](Self::to_junit))
     pub fn to_json(&self) -> String {
         serde_json::to_string_pretty(self).unwrap_or_default()
     }
@@ -205,6 +226,8 @@ pub enum ScanTarget {
     SoliditySource(String),
     /// Rust source code
     RustSource(String),
+    /// Cargo workspace root (containing `Cargo.lock`)
+    Workspace(std::path::PathBuf),
     /// Transaction data
     Transaction {
         from: [u8; 20],
@@ -216,6 +239,13 @@ pub enum ScanTarget {
     Entity([u8; 32]),
     /// Network traffic pattern
     NetworkTraffic(Vec<NetworkEvent>),
+    /// Configuration file (skill definitions, env files, manifests)
+    Configuration {
+        path: String,
+        content: String,
+        /// Commit the file was read at, if known
+        commit: Option<String>,
+    },
 }
 
 /// Network event for traffic analysis
@@ -241,6 +271,9 @@ pub enum SecurityError {
     #[error("Pattern compilation error: {0}")]
     PatternError(String),
 
+    #[error("Model error: {0}")]
+    Model(String),
+
     #[error("Analysis timeout")]
     Timeout,
 
@@ -258,6 +291,10 @@ pub struct CerberAgent {
     config: CerberConfig,
     /// Known vulnerability database
     vuln_db: Arc<VulnerabilityDatabase>,
+    /// Quarantine list fed by critical findings
+    quarantine: Arc<QuarantineRegistry>,
+    /// Last report per baseline key, for differential scans
+    baselines: RwLock<HashMap<String, Baseline>>,
 }
 
 /// Cerber configuration
@@ -297,17 +334,36 @@ impl CerberAgent {
             history: RwLock::new(Vec::new()),
             config,
             vuln_db: vuln_db.clone(),
+            quarantine: Arc::new(QuarantineRegistry::default()),
+            baselines: RwLock::new(HashMap::new()),
         };
 
         // Register default scanners
-        agent.register_scanner(Arc::new(StaticAnalyzer::new()));
+        agent.register_scanner(Arc::new(StaticAnalyzer::with_database(vuln_db.clone())));
         agent.register_scanner(Arc::new(SmartContractScanner::new(vuln_db.clone())));
         agent.register_scanner(Arc::new(DependencyScanner::new()));
         agent.register_scanner(Arc::new(AnomalyDetector::new()));
+        agent.register_scanner(Arc::new(SecretsScanner::new()));
 
         agent
     }
 
+    /// Share a quarantine registry with the invocation engine and bridges
+    pub fn with_quarantine(mut self, quarantine: Arc<QuarantineRegistry>) -> Self {
+        self.quarantine = quarantine;
+        self
+    }
+
+    /// Quarantine registry used by [`scan_and_enforce`](Self::scan_and_enforce)
+    pub fn quarantine(&self) -> Arc<QuarantineRegistry> {
+        self.quarantine.clone()
+    }
+
+    /// Shared pattern database, e.g. for loading rule packs at startup
+    pub fn vulnerability_database(&self) -> Arc<VulnerabilityDatabase> {
+        self.vuln_db.clone()
+    }
+
     /// Register a scanner
     pub fn register_scanner(&mut self, scanner: Arc<dyn SecurityScanner>) {
         self.scanners.push(scanner);
@@ -320,9 +376,11 @@ impl CerberAgent {
             ScanTarget::ContractBytecode(_) => "contract-bytecode",
             ScanTarget::SoliditySource(_) => "solidity-source",
             ScanTarget::RustSource(_) => "rust-source",
+            ScanTarget::Workspace(_) => "workspace",
             ScanTarget::Transaction { .. } => "transaction",
             ScanTarget::Entity(_) => "entity",
             ScanTarget::NetworkTraffic(_) => "network-traffic",
+            ScanTarget::Configuration { path, .. } => path.as_str(),
         };
 
         let mut report = SecurityReport::new(target_name);
@@ -370,6 +428,19 @@ impl CerberAgent {
         self.history.read().clone()
     }
 
+    /// Scan and, if `auto_block_critical` is set, quarantine `subject` on critical findings
+    pub async fn scan_and_enforce(
+        &self,
+        target: &ScanTarget,
+        subject: rope_core::types::QuarantineSubject,
+    ) -> Result<SecurityReport, SecurityError> {
+        let report = self.scan(target).await?;
+        if self.config.auto_block_critical {
+            self.quarantine.enforce(subject, &report);
+        }
+        Ok(report)
+    }
+
     /// Quick check - returns true if target is safe
     pub async fn quick_check(&self, target: &ScanTarget) -> bool {
         match self.scan(target).await {
@@ -391,6 +462,8 @@ pub struct VulnerabilityDatabase {
     patterns: RwLock<Vec<VulnPattern>>,
     /// Known malicious signatures
     signatures: RwLock<HashSet<String>>,
+    /// Rule IDs switched off by the operator
+    disabled: RwLock<HashSet<String>>,
 }
 
 /// Vulnerability pattern
@@ -403,6 +476,8 @@ pub struct VulnPattern {
     pub category: String,
     pub description: String,
     pub cwe_id: Option<u32>,
+    /// Rule pack the pattern was loaded from; `None` for built-ins
+    pub provenance: Option<RuleProvenance>,
 }
 
 impl VulnerabilityDatabase {
@@ -411,6 +486,7 @@ impl VulnerabilityDatabase {
         let db = Self {
             patterns: RwLock::new(Vec::new()),
             signatures: RwLock::new(HashSet::new()),
+            disabled: RwLock::new(HashSet::new()),
         };
         db.load_builtin_patterns();
         db
@@ -430,6 +506,7 @@ impl VulnerabilityDatabase {
                 category: "reentrancy".to_string(),
                 description: "External call before state update may allow reentrancy".to_string(),
                 cwe_id: Some(841),
+                provenance: None,
             });
         }
 
@@ -443,6 +520,7 @@ impl VulnerabilityDatabase {
                 category: "integer-overflow".to_string(),
                 description: "Solidity version < 0.8.0 lacks built-in overflow checks".to_string(),
                 cwe_id: Some(190),
+                provenance: None,
             });
         }
 
@@ -456,6 +534,7 @@ impl VulnerabilityDatabase {
                 category: "unchecked-return".to_string(),
                 description: "Low-level call return value not checked".to_string(),
                 cwe_id: Some(252),
+                provenance: None,
             });
         }
 
@@ -470,6 +549,7 @@ impl VulnerabilityDatabase {
                 description: "tx.origin used for authorization is vulnerable to phishing"
                     .to_string(),
                 cwe_id: Some(284),
+                provenance: None,
             });
         }
 
@@ -483,6 +563,7 @@ impl VulnerabilityDatabase {
                 category: "secrets".to_string(),
                 description: "Potential hardcoded private key detected".to_string(),
                 cwe_id: Some(798),
+                provenance: None,
             });
         }
 
@@ -496,6 +577,7 @@ impl VulnerabilityDatabase {
                 category: "denial-of-service".to_string(),
                 description: "Contract can be destroyed, potentially locking funds".to_string(),
                 cwe_id: Some(400),
+                provenance: None,
             });
         }
 
@@ -509,6 +591,7 @@ impl VulnerabilityDatabase {
                 category: "access-control".to_string(),
                 description: "Delegatecall may allow arbitrary code execution".to_string(),
                 cwe_id: Some(829),
+                provenance: None,
             });
         }
 
@@ -522,6 +605,7 @@ impl VulnerabilityDatabase {
                 category: "memory-safety".to_string(),
                 description: "Unsafe block may bypass Rust's safety guarantees".to_string(),
                 cwe_id: Some(119),
+                provenance: None,
             });
         }
 
@@ -535,16 +619,38 @@ impl VulnerabilityDatabase {
                 category: "injection".to_string(),
                 description: "String interpolation in SQL query may allow injection".to_string(),
                 cwe_id: Some(89),
+                provenance: None,
             });
         }
     }
 
-    /// Get patterns
+    /// Get enabled patterns
     pub fn patterns(&self) -> Vec<VulnPattern> {
-        self.patterns.read().clone()
+        let disabled = self.disabled.read();
+        self.patterns
+            .read()
+            .iter()
+            .filter(|p| !disabled.contains(&p.id))
+            .cloned()
+            .collect()
+    }
+
+    /// Enable or disable a rule by ID, built-in or from a rule pack
+    pub fn set_rule_enabled(&self, id: &str, enabled: bool) {
+        if enabled {
+            self.disabled.write().remove(id);
+        } else {
+            self.disabled.write().insert(id.to_string());
+        }
+    }
+
+    /// Whether a rule is currently enabled
+    pub fn is_rule_enabled(&self, id: &str) -> bool {
+        !self.disabled.read().contains(id)
     }
 
-    /// Check if signature is known malicious
+    /// Check if signature is known malicious (leading code bytes as hex,
+    /// or a `0x`-prefixed Keccak code hash)
     pub fn is_malicious(&self, signature: &str) -> bool {
         self.signatures.read().contains(signature)
     }
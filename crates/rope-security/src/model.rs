@@ -0,0 +1,533 @@
+//! Anomaly Detection Models
+//!
+//! Pluggable scoring models for [`AnomalyDetector`]. A model turns a feature
+//! vector extracted from network events or transactions into an anomaly score;
+//! the detector compares that score against a threshold that is calibrated
+//! online from recently observed scores.
+//!
+//! Models are described by a [`ModelMetadata`] JSON file so deployments can
+//! swap detectors (or retrain them) without recompiling Cerber. The built-in
+//! [`ZScoreModel`] needs no runtime; ONNX models are available behind the
+//! `onnx` feature.
+
+use super::*;
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+
+/// Which extractor produces a model's input features
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FeatureSet {
+    Network,
+    Transaction,
+}
+
+impl FeatureSet {
+    /// Feature names, in vector order
+    pub fn names(&self) -> &'static [&'static str] {
+        match self {
+            FeatureSet::Network => &[
+                "request_rate",
+                "unique_source_ratio",
+                "top_source_share",
+                "mean_bytes_log",
+                "unusual_port_ratio",
+            ],
+            FeatureSet::Transaction => &[
+                "tx_count",
+                "mean_value_log",
+                "max_value_log",
+                "unique_sender_ratio",
+                "unique_recipient_ratio",
+            ],
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.names().len()
+    }
+}
+
+/// Extract the [`FeatureSet::Network`] vector from a traffic window
+pub fn network_features(events: &[NetworkEvent]) -> Vec<f32> {
+    if events.is_empty() {
+        return vec![0.0; FeatureSet::Network.width()];
+    }
+
+    let n = events.len() as f64;
+    let first = events.iter().map(|e| e.timestamp).min().unwrap_or(0);
+    let last = events.iter().map(|e| e.timestamp).max().unwrap_or(0);
+    let duration = (last - first).max(1) as f64;
+
+    let mut sources: HashMap<&str, usize> = HashMap::new();
+    for event in events {
+        *sources.entry(&event.source_ip).or_insert(0) += 1;
+    }
+    let top_source = sources.values().copied().max().unwrap_or(0) as f64;
+
+    let mean_bytes = events.iter().map(|e| e.bytes_sent as f64).sum::<f64>() / n;
+    let common_ports = [80, 443, 8080, 8443, 9000, 9001, 3001];
+    let unusual = events
+        .iter()
+        .filter(|e| !common_ports.contains(&e.port))
+        .count() as f64;
+
+    vec![
+        (n / duration) as f32,
+        (sources.len() as f64 / n) as f32,
+        (top_source / n) as f32,
+        (1.0 + mean_bytes).ln() as f32,
+        (unusual / n) as f32,
+    ]
+}
+
+/// Extract the [`FeatureSet::Transaction`] vector from a transaction window
+pub fn transaction_features(transactions: &[MonitoredTransaction]) -> Vec<f32> {
+    if transactions.is_empty() {
+        return vec![0.0; FeatureSet::Transaction.width()];
+    }
+
+    let n = transactions.len() as f64;
+    let mean_value = transactions.iter().map(|t| t.value as f64).sum::<f64>() / n;
+    let max_value = transactions.iter().map(|t| t.value).max().unwrap_or(0) as f64;
+    let senders: HashSet<&str> = transactions.iter().map(|t| t.from.as_str()).collect();
+    let recipients: HashSet<&str> = transactions.iter().map(|t| t.to.as_str()).collect();
+
+    vec![
+        n as f32,
+        (1.0 + mean_value).ln() as f32,
+        (1.0 + max_value).ln() as f32,
+        (senders.len() as f64 / n) as f32,
+        (recipients.len() as f64 / n) as f32,
+    ]
+}
+
+/// Model runtime
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ModelBackend {
+    /// Per-feature z-score using `means`/`std_devs` from the metadata parameters
+    ZScore,
+    /// ONNX graph taking a `[1, n]` f32 tensor and returning a scalar score
+    Onnx { path: PathBuf },
+}
+
+/// Persisted description of a trained model
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ModelMetadata {
+    pub name: String,
+    pub version: String,
+    pub backend: ModelBackend,
+    pub feature_set: FeatureSet,
+    /// Scores above this are anomalous; calibration never goes lower
+    pub threshold: f64,
+    /// Threshold reached by online calibration when the metadata was saved
+    #[serde(default)]
+    pub calibrated_threshold: Option<f64>,
+    #[serde(default)]
+    pub calibration: CalibrationConfig,
+    /// Backend-specific learned parameters
+    #[serde(default)]
+    pub parameters: HashMap<String, Vec<f64>>,
+    pub trained_at: i64,
+}
+
+impl ModelMetadata {
+    /// Read metadata from a JSON file; relative ONNX paths resolve against its directory
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, SecurityError> {
+        let path = path.as_ref();
+        let raw = std::fs::read_to_string(path)
+            .map_err(|e| SecurityError::Model(format!("{}: {}", path.display(), e)))?;
+        let mut metadata: Self = serde_json::from_str(&raw)
+            .map_err(|e| SecurityError::Model(format!("{}: {}", path.display(), e)))?;
+
+        if let ModelBackend::Onnx { path: model_path } = &mut metadata.backend {
+            if model_path.is_relative() {
+                if let Some(dir) = path.parent() {
+                    *model_path = dir.join(&*model_path);
+                }
+            }
+        }
+        Ok(metadata)
+    }
+
+    /// Write metadata as pretty-printed JSON
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), SecurityError> {
+        let json =
+            serde_json::to_string_pretty(self).map_err(|e| SecurityError::Model(e.to_string()))?;
+        std::fs::write(path.as_ref(), json)
+            .map_err(|e| SecurityError::Model(format!("{}: {}", path.as_ref().display(), e)))
+    }
+
+    fn parameter(&self, key: &str) -> Result<&[f64], SecurityError> {
+        let values = self
+            .parameters
+            .get(key)
+            .ok_or_else(|| SecurityError::Model(format!("missing parameter '{}'", key)))?;
+        if values.len() != self.feature_set.width() {
+            return Err(SecurityError::Model(format!(
+                "parameter '{}' has {} values, expected {}",
+                key,
+                values.len(),
+                self.feature_set.width()
+            )));
+        }
+        Ok(values)
+    }
+}
+
+/// Trained anomaly scoring model
+pub trait Model: Send + Sync {
+    fn metadata(&self) -> &ModelMetadata;
+
+    /// Score a feature vector; higher is more anomalous
+    fn score(&self, features: &[f32]) -> Result<f64, SecurityError>;
+
+    fn feature_set(&self) -> FeatureSet {
+        self.metadata().feature_set
+    }
+}
+
+/// Instantiate the backend named in `metadata`
+pub fn load_model(metadata: ModelMetadata) -> Result<Box<dyn Model>, SecurityError> {
+    match &metadata.backend {
+        ModelBackend::ZScore => Ok(Box::new(ZScoreModel::from_metadata(metadata)?)),
+        #[cfg(feature = "onnx")]
+        ModelBackend::Onnx { .. } => Ok(Box::new(onnx::OnnxModel::from_metadata(metadata)?)),
+        #[cfg(not(feature = "onnx"))]
+        ModelBackend::Onnx { .. } => Err(SecurityError::Model(
+            "ONNX models require the `onnx` feature".to_string(),
+        )),
+    }
+}
+
+/// Maximum absolute z-score across features
+pub struct ZScoreModel {
+    metadata: ModelMetadata,
+    means: Vec<f64>,
+    std_devs: Vec<f64>,
+}
+
+impl ZScoreModel {
+    pub fn from_metadata(metadata: ModelMetadata) -> Result<Self, SecurityError> {
+        let means = metadata.parameter("means")?.to_vec();
+        let std_devs = metadata.parameter("std_devs")?.to_vec();
+        Ok(Self {
+            metadata,
+            means,
+            std_devs,
+        })
+    }
+
+    /// Fit means and standard deviations from baseline samples
+    pub fn fit(
+        name: impl Into<String>,
+        feature_set: FeatureSet,
+        samples: &[Vec<f32>],
+    ) -> Result<Self, SecurityError> {
+        let width = feature_set.width();
+        if samples.is_empty() || samples.iter().any(|s| s.len() != width) {
+            return Err(SecurityError::Model(format!(
+                "need at least one sample of {} features",
+                width
+            )));
+        }
+
+        let n = samples.len() as f64;
+        let means: Vec<f64> = (0..width)
+            .map(|i| samples.iter().map(|s| s[i] as f64).sum::<f64>() / n)
+            .collect();
+        let std_devs: Vec<f64> = (0..width)
+            .map(|i| {
+                let var = samples
+                    .iter()
+                    .map(|s| (s[i] as f64 - means[i]).powi(2))
+                    .sum::<f64>()
+                    / n;
+                var.sqrt()
+            })
+            .collect();
+
+        let metadata = ModelMetadata {
+            name: name.into(),
+            version: "1".to_string(),
+            backend: ModelBackend::ZScore,
+            feature_set,
+            threshold: 3.0,
+            calibrated_threshold: None,
+            calibration: CalibrationConfig::default(),
+            parameters: HashMap::from([
+                ("means".to_string(), means.clone()),
+                ("std_devs".to_string(), std_devs.clone()),
+            ]),
+            trained_at: chrono::Utc::now().timestamp(),
+        };
+
+        Ok(Self {
+            metadata,
+            means,
+            std_devs,
+        })
+    }
+}
+
+impl Model for ZScoreModel {
+    fn metadata(&self) -> &ModelMetadata {
+        &self.metadata
+    }
+
+    fn score(&self, features: &[f32]) -> Result<f64, SecurityError> {
+        if features.len() != self.means.len() {
+            return Err(SecurityError::Model(format!(
+                "expected {} features, got {}",
+                self.means.len(),
+                features.len()
+            )));
+        }
+
+        Ok(features
+            .iter()
+            .zip(self.means.iter().zip(&self.std_devs))
+            .map(|(x, (mean, std))| (*x as f64 - mean).abs() / std.max(1e-6))
+            .fold(0.0, f64::max))
+    }
+}
+
+/// Online threshold calibration settings
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CalibrationConfig {
+    /// Quantile of recent scores to use as the threshold
+    pub quantile: f64,
+    /// Number of recent scores to keep
+    pub window: usize,
+    /// Scores needed before the threshold starts moving
+    pub min_samples: usize,
+}
+
+impl Default for CalibrationConfig {
+    fn default() -> Self {
+        Self {
+            quantile: 0.99,
+            window: 1000,
+            min_samples: 100,
+        }
+    }
+}
+
+/// Tracks recent scores and moves the threshold to the configured quantile
+///
+/// The threshold never drops below the model's trained threshold, so a burst of
+/// anomalous traffic cannot calibrate itself into looking normal.
+#[derive(Clone, Debug)]
+pub struct ThresholdCalibrator {
+    config: CalibrationConfig,
+    floor: f64,
+    threshold: f64,
+    scores: VecDeque<f64>,
+}
+
+impl ThresholdCalibrator {
+    pub fn new(threshold: f64, config: CalibrationConfig) -> Self {
+        Self {
+            config,
+            floor: threshold,
+            threshold,
+            scores: VecDeque::new(),
+        }
+    }
+
+    /// Resume calibration from persisted metadata
+    pub fn from_metadata(metadata: &ModelMetadata) -> Self {
+        let mut calibrator = Self::new(metadata.threshold, metadata.calibration.clone());
+        if let Some(threshold) = metadata.calibrated_threshold {
+            calibrator.threshold = threshold.max(metadata.threshold);
+        }
+        calibrator
+    }
+
+    pub fn threshold(&self) -> f64 {
+        self.threshold
+    }
+
+    pub fn samples(&self) -> usize {
+        self.scores.len()
+    }
+
+    /// Record a score and recalibrate
+    pub fn observe(&mut self, score: f64) {
+        if !score.is_finite() {
+            return;
+        }
+        self.scores.push_back(score);
+        while self.scores.len() > self.config.window {
+            self.scores.pop_front();
+        }
+        if self.scores.len() < self.config.min_samples {
+            return;
+        }
+
+        let mut sorted: Vec<f64> = self.scores.iter().copied().collect();
+        sorted.sort_by(|a, b| a.total_cmp(b));
+        let rank = ((sorted.len() - 1) as f64 * self.config.quantile.clamp(0.0, 1.0)).round();
+        self.threshold = sorted[rank as usize].max(self.floor);
+    }
+}
+
+#[cfg(feature = "onnx")]
+pub use self::onnx::OnnxModel;
+
+#[cfg(feature = "onnx")]
+mod onnx {
+    //! ONNX Runtime backend
+
+    use ndarray::Array2;
+    use ort::session::builder::GraphOptimizationLevel;
+    use ort::session::Session;
+    use ort::value::Tensor;
+
+    use super::{Model, ModelBackend, ModelMetadata};
+    use crate::SecurityError;
+
+    /// Model evaluated by ONNX Runtime
+    pub struct OnnxModel {
+        metadata: ModelMetadata,
+        session: Session,
+    }
+
+    impl OnnxModel {
+        pub fn from_metadata(metadata: ModelMetadata) -> Result<Self, SecurityError> {
+            let ModelBackend::Onnx { path } = &metadata.backend else {
+                return Err(SecurityError::Model("not an ONNX model".to_string()));
+            };
+
+            let session = Session::builder()
+                .map_err(ort_error)?
+                .with_optimization_level(GraphOptimizationLevel::Level1)
+                .map_err(ort_error)?
+                .commit_from_file(path)
+                .map_err(ort_error)?;
+
+            Ok(Self { metadata, session })
+        }
+    }
+
+    impl Model for OnnxModel {
+        fn metadata(&self) -> &ModelMetadata {
+            &self.metadata
+        }
+
+        fn score(&self, features: &[f32]) -> Result<f64, SecurityError> {
+            let input = Array2::from_shape_vec((1, features.len()), features.to_vec())
+                .map_err(|e| SecurityError::Model(e.to_string()))?;
+            let input = Tensor::from_array(input).map_err(ort_error)?;
+            let outputs = self
+                .session
+                .run(ort::inputs![input].map_err(ort_error)?)
+                .map_err(ort_error)?;
+
+            if outputs.len() == 0 {
+                return Err(SecurityError::Model("model produced no output".to_string()));
+            }
+            let tensor = outputs[0].try_extract_tensor::<f32>().map_err(ort_error)?;
+            let score = tensor
+                .iter()
+                .next()
+                .copied()
+                .ok_or_else(|| SecurityError::Model("empty output tensor".to_string()))?;
+            Ok(score as f64)
+        }
+    }
+
+    fn ort_error(e: ort::Error) -> SecurityError {
+        SecurityError::Model(e.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn baseline() -> Vec<Vec<f32>> {
+        (0..50)
+            .map(|i| {
+                let jitter = (i % 5) as f32 * 0.01;
+                vec![10.0 + jitter, 0.5, 0.1 + jitter, 7.0, 0.0]
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_zscore_model_scores_outliers() {
+        let model = ZScoreModel::fit("net", FeatureSet::Network, &baseline()).unwrap();
+
+        let normal = model.score(&[10.02, 0.5, 0.12, 7.0, 0.0]).unwrap();
+        let outlier = model.score(&[80.0, 0.01, 0.95, 7.0, 0.0]).unwrap();
+        assert!(normal < model.metadata().threshold);
+        assert!(outlier > model.metadata().threshold);
+        assert!(model.score(&[1.0]).is_err());
+    }
+
+    #[test]
+    fn test_model_metadata_roundtrip() {
+        let model = ZScoreModel::fit("net", FeatureSet::Network, &baseline()).unwrap();
+        let path = std::env::temp_dir().join(format!("cerber-model-{}.json", std::process::id()));
+
+        model.metadata().save(&path).unwrap();
+        let loaded = load_model(ModelMetadata::load(&path).unwrap()).unwrap();
+        std::fs::remove_file(&path).ok();
+
+        assert_eq!(loaded.feature_set(), FeatureSet::Network);
+        let features = [80.0, 0.01, 0.95, 7.0, 0.0];
+        assert_eq!(
+            loaded.score(&features).unwrap(),
+            model.score(&features).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_threshold_calibration() {
+        let mut calibrator = ThresholdCalibrator::new(
+            3.0,
+            CalibrationConfig {
+                quantile: 0.9,
+                window: 100,
+                min_samples: 10,
+            },
+        );
+
+        for _ in 0..9 {
+            calibrator.observe(10.0);
+        }
+        assert_eq!(calibrator.threshold(), 3.0);
+
+        calibrator.observe(10.0);
+        assert_eq!(calibrator.threshold(), 10.0);
+
+        // Never calibrates below the trained threshold
+        for _ in 0..100 {
+            calibrator.observe(0.5);
+        }
+        assert_eq!(calibrator.threshold(), 3.0);
+        assert_eq!(calibrator.samples(), 100);
+    }
+
+    #[test]
+    fn test_feature_extraction() {
+        let events: Vec<NetworkEvent> = (0..10)
+            .map(|i| NetworkEvent {
+                timestamp: 100 + i,
+                source_ip: "1.1.1.1".to_string(),
+                destination_ip: "2.2.2.2".to_string(),
+                port: if i < 5 { 443 } else { 31337 },
+                bytes_sent: 100,
+                request_type: "HTTP".to_string(),
+            })
+            .collect();
+
+        let features = network_features(&events);
+        assert_eq!(features.len(), FeatureSet::Network.width());
+        assert_eq!(features[2], 1.0);
+        assert_eq!(features[4], 0.5);
+        assert_eq!(transaction_features(&[]).len(), 5);
+    }
+}
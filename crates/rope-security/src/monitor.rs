@@ -1,6 +1,7 @@
 //! Security Monitoring
 //!
-//! Real-time monitoring and anomaly detection
+//! Real-time monitoring and anomaly detection, plus a streaming
+//! transaction monitor driven by user-defined rules
 
 use super::*;
 
@@ -14,6 +15,23 @@ pub struct AnomalyDetector {
     sensitivity: f64,
     /// Alert history
     alerts: RwLock<Vec<SecurityAlert>>,
+    /// Optional trained model with its calibrated threshold
+    model: RwLock<Option<ActiveModel>>,
+}
+
+struct ActiveModel {
+    model: Box<dyn Model>,
+    calibrator: ThresholdCalibrator,
+}
+
+/// Result of scoring a window with the active model
+#[derive(Clone, Debug, PartialEq)]
+pub struct ModelScore {
+    pub model: String,
+    pub version: String,
+    pub score: f64,
+    pub threshold: f64,
+    pub anomalous: bool,
 }
 
 /// Security alert
@@ -48,9 +66,87 @@ impl AnomalyDetector {
             error_rate_baseline: RwLock::new(0.01), // 1% default
             sensitivity: 2.0,
             alerts: RwLock::new(Vec::new()),
+            model: RwLock::new(None),
         }
     }
 
+    /// Use a trained model in addition to the baseline heuristics
+    pub fn with_model(self, model: Box<dyn Model>) -> Self {
+        self.set_model(model);
+        self
+    }
+
+    /// Swap the active model at runtime
+    pub fn set_model(&self, model: Box<dyn Model>) {
+        let calibrator = ThresholdCalibrator::from_metadata(model.metadata());
+        *self.model.write() = Some(ActiveModel { model, calibrator });
+    }
+
+    /// Load and activate the model described by a metadata file
+    pub fn load_model(&self, path: impl AsRef<std::path::Path>) -> Result<(), SecurityError> {
+        let model = load_model(ModelMetadata::load(path)?)?;
+        self.set_model(model);
+        Ok(())
+    }
+
+    /// Metadata of the active model, including its current calibrated threshold
+    pub fn model_metadata(&self) -> Option<ModelMetadata> {
+        self.model.read().as_ref().map(|active| {
+            let mut metadata = active.model.metadata().clone();
+            metadata.calibrated_threshold = Some(active.calibrator.threshold());
+            metadata
+        })
+    }
+
+    /// Persist the active model's metadata so calibration survives restarts
+    pub fn save_model_metadata(
+        &self,
+        path: impl AsRef<std::path::Path>,
+    ) -> Result<(), SecurityError> {
+        self.model_metadata()
+            .ok_or_else(|| SecurityError::Model("no model loaded".to_string()))?
+            .save(path)
+    }
+
+    /// Score a traffic window with the active network model
+    pub fn score_network(&self, events: &[NetworkEvent]) -> Option<ModelScore> {
+        self.score_features(FeatureSet::Network, &network_features(events))
+    }
+
+    /// Score a transaction window with the active transaction model
+    pub fn score_transactions(&self, transactions: &[MonitoredTransaction]) -> Option<ModelScore> {
+        self.score_features(FeatureSet::Transaction, &transaction_features(transactions))
+    }
+
+    fn score_features(&self, feature_set: FeatureSet, features: &[f32]) -> Option<ModelScore> {
+        let mut guard = self.model.write();
+        let active = guard.as_mut()?;
+        if active.model.feature_set() != feature_set {
+            return None;
+        }
+
+        let score = match active.model.score(features) {
+            Ok(score) => score,
+            Err(e) => {
+                tracing::warn!("Anomaly model scoring failed: {}", e);
+                return None;
+            }
+        };
+
+        // Compare against the threshold before this score moves it
+        let threshold = active.calibrator.threshold();
+        active.calibrator.observe(score);
+
+        let metadata = active.model.metadata();
+        Some(ModelScore {
+            model: metadata.name.clone(),
+            version: metadata.version.clone(),
+            score,
+            threshold,
+            anomalous: score > threshold,
+        })
+    }
+
     /// Update baseline from observations
     pub fn update_baseline(&self, tx_rate: f64, error_rate: f64) {
         // Exponential moving average
@@ -266,6 +362,33 @@ impl SecurityScanner for AnomalyDetector {
             }
         }
 
+        if let Some(result) = self.score_network(events) {
+            if result.anomalous {
+                findings.push(SecurityFinding {
+                    id: format!("NET-ML-ANOMALY-{}", result.model),
+                    title: "Model-Detected Traffic Anomaly".to_string(),
+                    description: format!(
+                        "Model {} v{} scored traffic at {:.2} (threshold {:.2})",
+                        result.model, result.version, result.score, result.threshold
+                    ),
+                    severity: Severity::Medium,
+                    category: "anomalous-traffic".to_string(),
+                    location: None,
+                    remediation: "Inspect the traffic window and retrain the model if benign"
+                        .to_string(),
+                    cwe_id: None,
+                    confidence: 0.75,
+                    timestamp: chrono::Utc::now().timestamp(),
+                    metadata: HashMap::from([
+                        ("model".to_string(), result.model.clone()),
+                        ("model_version".to_string(), result.version.clone()),
+                        ("score".to_string(), result.score.to_string()),
+                        ("threshold".to_string(), result.threshold.to_string()),
+                    ]),
+                });
+            }
+        }
+
         Ok(findings)
     }
 }
@@ -362,6 +485,398 @@ impl Default for RateLimitMonitor {
     }
 }
 
+/// Where a monitored transaction was observed
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum TransactionSource {
+    /// Native transaction on the string lattice
+    Lattice,
+    /// Cross-chain transfer relayed by a bridge
+    Bridge { chain: String },
+}
+
+impl std::fmt::Display for TransactionSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TransactionSource::Lattice => write!(f, "lattice"),
+            TransactionSource::Bridge { chain } => write!(f, "bridge:{}", chain),
+        }
+    }
+}
+
+/// Transaction fed into the runtime monitor
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MonitoredTransaction {
+    /// Transaction or string hash (hex)
+    pub hash: String,
+    pub source: TransactionSource,
+    /// Sender address (hex)
+    pub from: String,
+    /// Recipient address (hex)
+    pub to: String,
+    /// Transferred value in base units
+    pub value: u128,
+    pub timestamp: i64,
+}
+
+/// User-defined runtime monitoring rule
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum MonitorRule {
+    /// Flag any single transaction at or above `threshold`
+    ValueThreshold {
+        name: String,
+        #[serde(deserialize_with = "amount::deserialize")]
+        threshold: u128,
+        severity: Severity,
+    },
+    /// Flag senders exceeding a transaction count or total value within a sliding window
+    Velocity {
+        name: String,
+        window_seconds: u64,
+        max_transactions: usize,
+        #[serde(default, deserialize_with = "amount::deserialize_option")]
+        max_value: Option<u128>,
+        severity: Severity,
+    },
+    /// Flag transactions touching a blacklisted address on either side
+    BlacklistedCounterparty {
+        name: String,
+        addresses: HashSet<String>,
+        severity: Severity,
+    },
+}
+
+/// Rule amounts in configuration
+///
+/// Tagged enums buffer their fields, and the buffer has no room for `u128`,
+/// so amounts are read as `u64`; TOML integers do not go higher anyway.
+mod amount {
+    use serde::{Deserialize, Deserializer};
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u128, D::Error> {
+        u64::deserialize(deserializer).map(u128::from)
+    }
+
+    pub fn deserialize_option<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<u128>, D::Error> {
+        Option::<u64>::deserialize(deserializer).map(|value| value.map(u128::from))
+    }
+}
+
+impl MonitorRule {
+    pub fn name(&self) -> &str {
+        match self {
+            MonitorRule::ValueThreshold { name, .. }
+            | MonitorRule::Velocity { name, .. }
+            | MonitorRule::BlacklistedCounterparty { name, .. } => name,
+        }
+    }
+
+    pub fn severity(&self) -> Severity {
+        match self {
+            MonitorRule::ValueThreshold { severity, .. }
+            | MonitorRule::Velocity { severity, .. }
+            | MonitorRule::BlacklistedCounterparty { severity, .. } => *severity,
+        }
+    }
+}
+
+/// Rule set loaded from operator configuration
+///
+/// ```toml
+/// [[rules]]
+/// type = "value_threshold"
+/// name = "whale-transfer"
+/// threshold = 1000000000000000000
+/// severity = "High"
+/// ```
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct RuleSet {
+    #[serde(default)]
+    pub rules: Vec<MonitorRule>,
+}
+
+impl RuleSet {
+    pub fn new(rules: Vec<MonitorRule>) -> Self {
+        Self { rules }
+    }
+
+    /// Parse a rule set from TOML
+    pub fn from_toml(input: &str) -> Result<Self, SecurityError> {
+        toml::from_str(input).map_err(|e| SecurityError::InvalidTarget(e.to_string()))
+    }
+
+    /// Parse a rule set from JSON
+    pub fn from_json(input: &str) -> Result<Self, SecurityError> {
+        serde_json::from_str(input).map_err(|e| SecurityError::InvalidTarget(e.to_string()))
+    }
+
+    /// Longest velocity window, used to bound per-sender history
+    fn retention_seconds(&self) -> i64 {
+        self.rules
+            .iter()
+            .filter_map(|r| match r {
+                MonitorRule::Velocity { window_seconds, .. } => Some(*window_seconds as i64),
+                _ => None,
+            })
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+/// Posts findings as JSON to an HTTP endpoint
+#[derive(Clone)]
+pub struct WebhookNotifier {
+    client: reqwest::Client,
+    url: String,
+}
+
+impl WebhookNotifier {
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            url: url.into(),
+        }
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Deliver a single finding
+    pub async fn notify(&self, finding: &SecurityFinding) -> Result<(), SecurityError> {
+        let response = self
+            .client
+            .post(&self.url)
+            .json(finding)
+            .send()
+            .await
+            .map_err(|e| SecurityError::Internal(format!("webhook delivery failed: {}", e)))?;
+
+        if !response.status().is_success() {
+            return Err(SecurityError::Internal(format!(
+                "webhook returned {}",
+                response.status()
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Streaming transaction monitor
+///
+/// Consumes lattice and bridge transactions from a broadcast channel, evaluates
+/// the configured [`RuleSet`] against each one and publishes findings to
+/// subscribers and, if configured, a webhook.
+pub struct TransactionMonitor {
+    rules: RwLock<RuleSet>,
+    /// Recent (timestamp, value) pairs per sender for velocity rules
+    history: RwLock<HashMap<String, std::collections::VecDeque<(i64, u128)>>>,
+    findings_tx: tokio::sync::broadcast::Sender<SecurityFinding>,
+    webhook: Option<WebhookNotifier>,
+}
+
+impl TransactionMonitor {
+    pub fn new(rules: RuleSet) -> Self {
+        let (findings_tx, _) = tokio::sync::broadcast::channel(1024);
+        Self {
+            rules: RwLock::new(rules),
+            history: RwLock::new(HashMap::new()),
+            findings_tx,
+            webhook: None,
+        }
+    }
+
+    /// Send every finding to `url` in addition to broadcasting it
+    pub fn with_webhook(mut self, url: impl Into<String>) -> Self {
+        self.webhook = Some(WebhookNotifier::new(url));
+        self
+    }
+
+    /// Replace the active rule set without restarting the service
+    pub fn set_rules(&self, rules: RuleSet) {
+        *self.rules.write() = rules;
+    }
+
+    pub fn rules(&self) -> RuleSet {
+        self.rules.read().clone()
+    }
+
+    /// Subscribe to findings as they are raised
+    pub fn subscribe(&self) -> tokio::sync::broadcast::Receiver<SecurityFinding> {
+        self.findings_tx.subscribe()
+    }
+
+    /// Evaluate one transaction against all rules, recording it for velocity tracking
+    pub fn evaluate(&self, tx: &MonitoredTransaction) -> Vec<SecurityFinding> {
+        let rules = self.rules.read();
+        let sender = tx.from.to_lowercase();
+
+        let window = {
+            let mut history = self.history.write();
+            let entries = history.entry(sender.clone()).or_default();
+            entries.push_back((tx.timestamp, tx.value));
+            let cutoff = tx.timestamp - rules.retention_seconds();
+            while entries.front().is_some_and(|(ts, _)| *ts < cutoff) {
+                entries.pop_front();
+            }
+            entries.clone()
+        };
+
+        let mut findings = Vec::new();
+        for rule in &rules.rules {
+            match rule {
+                MonitorRule::ValueThreshold { threshold, .. } => {
+                    if tx.value >= *threshold {
+                        findings.push(Self::finding(
+                            rule,
+                            tx,
+                            "value-threshold",
+                            format!(
+                                "Transaction {} from {} moved {} (threshold {})",
+                                tx.hash, tx.from, tx.value, threshold
+                            ),
+                            "Review the transfer and confirm the sender authorized it",
+                            0.9,
+                        ));
+                    }
+                }
+                MonitorRule::Velocity {
+                    window_seconds,
+                    max_transactions,
+                    max_value,
+                    ..
+                } => {
+                    let since = tx.timestamp - *window_seconds as i64;
+                    let (count, total) = window
+                        .iter()
+                        .filter(|(ts, _)| *ts >= since)
+                        .fold((0usize, 0u128), |(c, v), (_, value)| {
+                            (c + 1, v.saturating_add(*value))
+                        });
+
+                    let over_count = count > *max_transactions;
+                    let over_value = max_value.is_some_and(|max| total > max);
+                    if over_count || over_value {
+                        findings.push(Self::finding(
+                            rule,
+                            tx,
+                            "transaction-velocity",
+                            format!(
+                                "Sender {} made {} transactions totalling {} in {}s",
+                                tx.from, count, total, window_seconds
+                            ),
+                            "Throttle or pause the sender pending investigation",
+                            0.8,
+                        ));
+                    }
+                }
+                MonitorRule::BlacklistedCounterparty { addresses, .. } => {
+                    let hit = [&tx.from, &tx.to].into_iter().find(|addr| {
+                        addresses
+                            .iter()
+                            .any(|blocked| blocked.eq_ignore_ascii_case(addr))
+                    });
+                    if let Some(addr) = hit {
+                        findings.push(Self::finding(
+                            rule,
+                            tx,
+                            "blacklisted-counterparty",
+                            format!(
+                                "Transaction {} involves blacklisted address {}",
+                                tx.hash, addr
+                            ),
+                            "Block the counterparty and escalate for compliance review",
+                            0.95,
+                        ));
+                    }
+                }
+            }
+        }
+
+        findings
+    }
+
+    /// Evaluate a transaction and publish the resulting findings
+    pub async fn process(&self, tx: &MonitoredTransaction) -> Vec<SecurityFinding> {
+        let findings = self.evaluate(tx);
+        for finding in &findings {
+            // No subscribers is not an error
+            let _ = self.findings_tx.send(finding.clone());
+            if let Some(webhook) = &self.webhook {
+                if let Err(e) = webhook.notify(finding).await {
+                    tracing::warn!("Monitor webhook to {} failed: {}", webhook.url(), e);
+                }
+            }
+        }
+        findings
+    }
+
+    /// Run until the transaction feed closes
+    pub async fn run(
+        self: Arc<Self>,
+        mut transactions: tokio::sync::broadcast::Receiver<MonitoredTransaction>,
+    ) {
+        use tokio::sync::broadcast::error::RecvError;
+
+        loop {
+            match transactions.recv().await {
+                Ok(tx) => {
+                    self.process(&tx).await;
+                }
+                Err(RecvError::Lagged(skipped)) => {
+                    tracing::warn!(
+                        "Transaction monitor lagged, skipped {} transactions",
+                        skipped
+                    );
+                }
+                Err(RecvError::Closed) => break,
+            }
+        }
+    }
+
+    /// Spawn [`run`](Self::run) as a background task
+    pub fn spawn(
+        self: Arc<Self>,
+        transactions: tokio::sync::broadcast::Receiver<MonitoredTransaction>,
+    ) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(self.run(transactions))
+    }
+
+    fn finding(
+        rule: &MonitorRule,
+        tx: &MonitoredTransaction,
+        category: &str,
+        description: String,
+        remediation: &str,
+        confidence: f64,
+    ) -> SecurityFinding {
+        let mut metadata = HashMap::new();
+        metadata.insert("rule".to_string(), rule.name().to_string());
+        metadata.insert("tx_hash".to_string(), tx.hash.clone());
+        metadata.insert("source".to_string(), tx.source.to_string());
+        metadata.insert("from".to_string(), tx.from.clone());
+        metadata.insert("to".to_string(), tx.to.clone());
+        metadata.insert("value".to_string(), tx.value.to_string());
+
+        SecurityFinding {
+            id: format!("MONITOR-{}-{}", rule.name(), tx.hash),
+            title: format!("Monitor rule '{}' triggered", rule.name()),
+            description,
+            severity: rule.severity(),
+            category: category.to_string(),
+            location: Some(format!("{} tx {}", tx.source, tx.hash)),
+            remediation: remediation.to_string(),
+            cwe_id: None,
+            confidence,
+            timestamp: chrono::Utc::now().timestamp(),
+            metadata,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -424,4 +939,155 @@ mod tests {
         // Should detect the IP flood from 1.1.1.1
         assert!(findings.iter().any(|f| f.category == "denial-of-service"));
     }
+
+    #[tokio::test]
+    async fn test_anomaly_detector_with_model() {
+        let baseline: Vec<Vec<f32>> = (0..20)
+            .map(|i| vec![1.0 + (i % 3) as f32 * 0.1, 1.0, 1.0, 6.9, 0.0])
+            .collect();
+        let model = ZScoreModel::fit("traffic", FeatureSet::Network, &baseline).unwrap();
+        let detector = AnomalyDetector::new().with_model(Box::new(model));
+
+        let flood: Vec<NetworkEvent> = (0..50)
+            .map(|i| NetworkEvent {
+                timestamp: 1000 + i / 10,
+                source_ip: "9.9.9.9".to_string(),
+                destination_ip: "3.3.3.3".to_string(),
+                port: 443,
+                bytes_sent: 1000,
+                request_type: "HTTP".to_string(),
+            })
+            .collect();
+
+        let findings = detector
+            .scan(&ScanTarget::NetworkTraffic(flood))
+            .await
+            .unwrap();
+        assert!(findings.iter().any(|f| f.category == "anomalous-traffic"));
+
+        // Transaction windows are ignored by a network model
+        assert!(detector.score_transactions(&[]).is_none());
+        assert_eq!(
+            detector.model_metadata().unwrap().calibrated_threshold,
+            Some(3.0)
+        );
+    }
+
+    fn monitored_tx(
+        hash: &str,
+        from: &str,
+        to: &str,
+        value: u128,
+        timestamp: i64,
+    ) -> MonitoredTransaction {
+        MonitoredTransaction {
+            hash: hash.to_string(),
+            source: TransactionSource::Lattice,
+            from: from.to_string(),
+            to: to.to_string(),
+            value,
+            timestamp,
+        }
+    }
+
+    #[test]
+    fn test_monitor_rules_from_toml() {
+        let rules = RuleSet::from_toml(
+            r#"
+            [[rules]]
+            type = "value_threshold"
+            name = "whale"
+            threshold = 1000
+            severity = "High"
+
+            [[rules]]
+            type = "velocity"
+            name = "burst"
+            window_seconds = 60
+            max_transactions = 2
+            severity = "Medium"
+
+            [[rules]]
+            type = "blacklisted_counterparty"
+            name = "ofac"
+            addresses = ["0xBAD"]
+            severity = "Critical"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(rules.rules.len(), 3);
+        assert_eq!(rules.rules[2].severity(), Severity::Critical);
+    }
+
+    #[test]
+    fn test_transaction_monitor_rules() {
+        let monitor = TransactionMonitor::new(RuleSet::new(vec![
+            MonitorRule::ValueThreshold {
+                name: "whale".to_string(),
+                threshold: 1_000,
+                severity: Severity::High,
+            },
+            MonitorRule::Velocity {
+                name: "burst".to_string(),
+                window_seconds: 60,
+                max_transactions: 2,
+                max_value: None,
+                severity: Severity::Medium,
+            },
+            MonitorRule::BlacklistedCounterparty {
+                name: "ofac".to_string(),
+                addresses: ["0xbad".to_string()].into_iter().collect(),
+                severity: Severity::Critical,
+            },
+        ]));
+
+        assert!(monitor
+            .evaluate(&monitored_tx("t1", "0xa", "0xb", 10, 100))
+            .is_empty());
+
+        let findings = monitor.evaluate(&monitored_tx("t2", "0xa", "0xBAD", 5_000, 110));
+        assert!(findings.iter().any(|f| f.category == "value-threshold"));
+        assert!(findings
+            .iter()
+            .any(|f| f.category == "blacklisted-counterparty"));
+        assert!(!findings
+            .iter()
+            .any(|f| f.category == "transaction-velocity"));
+
+        let findings = monitor.evaluate(&monitored_tx("t3", "0xa", "0xb", 10, 120));
+        assert!(findings
+            .iter()
+            .any(|f| f.category == "transaction-velocity"));
+
+        // Outside the window the sender's history no longer counts
+        assert!(monitor
+            .evaluate(&monitored_tx("t4", "0xa", "0xb", 10, 500))
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_transaction_monitor_stream() {
+        let monitor = Arc::new(TransactionMonitor::new(RuleSet::new(vec![
+            MonitorRule::ValueThreshold {
+                name: "whale".to_string(),
+                threshold: 1_000,
+                severity: Severity::High,
+            },
+        ])));
+        let mut findings = monitor.subscribe();
+
+        let (tx_feed, rx_feed) = tokio::sync::broadcast::channel(16);
+        let handle = monitor.clone().spawn(rx_feed);
+
+        let mut bridged = monitored_tx("b1", "0xa", "0xb", 2_000, 100);
+        bridged.source = TransactionSource::Bridge {
+            chain: "ethereum".to_string(),
+        };
+        tx_feed.send(bridged).unwrap();
+        drop(tx_feed);
+
+        let finding = findings.recv().await.unwrap();
+        assert_eq!(finding.metadata.get("source").unwrap(), "bridge:ethereum");
+        handle.await.unwrap();
+    }
 }
@@ -0,0 +1,421 @@
+//! Quarantine Enforcement
+//!
+//! Critical findings place the offending entity or contract on a quarantine
+//! list that the invocation engine and bridges consult through
+//! [`QuarantineList`]. Release requires approval from a threshold of
+//! governance members, and every state change is written to an audit log.
+
+use super::*;
+use rope_core::types::{QuarantineList, QuarantineSubject};
+
+/// Governance settings for releasing quarantined subjects
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct QuarantineConfig {
+    /// Members allowed to request and approve unblocks
+    pub governors: HashSet<[u8; 32]>,
+    /// Approvals needed to release a subject
+    pub approval_threshold: usize,
+    /// Unblock requests expire after this many seconds
+    pub request_ttl_seconds: u64,
+}
+
+impl Default for QuarantineConfig {
+    fn default() -> Self {
+        Self {
+            governors: HashSet::new(),
+            approval_threshold: 3,
+            request_ttl_seconds: 7 * 86400, // 7 days
+        }
+    }
+}
+
+/// Quarantine list entry
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct QuarantineEntry {
+    pub subject: QuarantineSubject,
+    /// IDs of the findings that triggered the quarantine
+    pub finding_ids: Vec<String>,
+    /// Report the findings came from
+    pub report_id: String,
+    pub reason: String,
+    pub quarantined_at: i64,
+}
+
+/// Pending governance request to release a subject
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct UnblockRequest {
+    pub id: String,
+    pub subject: QuarantineSubject,
+    pub requested_by: [u8; 32],
+    pub justification: String,
+    pub approvals: HashSet<[u8; 32]>,
+    pub created_at: i64,
+}
+
+/// Audited quarantine action
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum QuarantineAction {
+    Quarantined,
+    UnblockRequested,
+    UnblockApproved,
+    Released,
+    RequestExpired,
+}
+
+/// Audit log entry
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct QuarantineAuditEntry {
+    pub timestamp: i64,
+    pub subject: QuarantineSubject,
+    pub action: QuarantineAction,
+    /// Governor that acted, `None` for automated enforcement
+    pub actor: Option<[u8; 32]>,
+    pub detail: String,
+}
+
+/// Quarantine errors
+#[derive(Debug, Error)]
+pub enum QuarantineError {
+    #[error("Subject is not quarantined")]
+    NotQuarantined,
+
+    #[error("Not a governance member")]
+    NotGovernor,
+
+    #[error("Unblock request not found")]
+    RequestNotFound,
+
+    #[error("Unblock request expired")]
+    RequestExpired,
+
+    #[error("Already approved by this governor")]
+    AlreadyApproved,
+}
+
+/// Quarantine list with governance-approved release
+pub struct QuarantineRegistry {
+    config: QuarantineConfig,
+    entries: RwLock<HashMap<QuarantineSubject, QuarantineEntry>>,
+    requests: RwLock<HashMap<String, UnblockRequest>>,
+    audit: RwLock<Vec<QuarantineAuditEntry>>,
+}
+
+impl QuarantineRegistry {
+    pub fn new(config: QuarantineConfig) -> Self {
+        Self {
+            config,
+            entries: RwLock::new(HashMap::new()),
+            requests: RwLock::new(HashMap::new()),
+            audit: RwLock::new(Vec::new()),
+        }
+    }
+
+    /// Quarantine `subject` if the report has critical findings
+    ///
+    /// Returns the new entry, or `None` if the report had no critical findings.
+    /// Re-enforcing an already quarantined subject appends the new findings.
+    pub fn enforce(
+        &self,
+        subject: QuarantineSubject,
+        report: &SecurityReport,
+    ) -> Option<QuarantineEntry> {
+        let critical: Vec<String> = report
+            .findings
+            .iter()
+            .filter(|f| f.severity == Severity::Critical)
+            .map(|f| f.id.clone())
+            .collect();
+        if critical.is_empty() {
+            return None;
+        }
+
+        let now = chrono::Utc::now().timestamp();
+        let reason = format!(
+            "{} critical finding(s) in report {}",
+            critical.len(),
+            report.id
+        );
+
+        let entry = {
+            let mut entries = self.entries.write();
+            let entry = entries.entry(subject).or_insert_with(|| QuarantineEntry {
+                subject,
+                finding_ids: Vec::new(),
+                report_id: report.id.clone(),
+                reason: reason.clone(),
+                quarantined_at: now,
+            });
+            for id in &critical {
+                if !entry.finding_ids.contains(id) {
+                    entry.finding_ids.push(id.clone());
+                }
+            }
+            entry.clone()
+        };
+
+        tracing::warn!("Quarantined {}: {}", subject, reason);
+        self.log(
+            subject,
+            QuarantineAction::Quarantined,
+            None,
+            format!("{} ({})", reason, critical.join(", ")),
+        );
+        Some(entry)
+    }
+
+    /// Open a governance request to release `subject`
+    pub fn request_unblock(
+        &self,
+        subject: QuarantineSubject,
+        governor: [u8; 32],
+        justification: &str,
+    ) -> Result<String, QuarantineError> {
+        self.ensure_governor(&governor)?;
+        if !self.entries.read().contains_key(&subject) {
+            return Err(QuarantineError::NotQuarantined);
+        }
+
+        let now = chrono::Utc::now().timestamp();
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(subject.to_string().as_bytes());
+        hasher.update(&governor);
+        hasher.update(&now.to_le_bytes());
+        let id = hex::encode(&hasher.finalize().as_bytes()[..16]);
+
+        self.requests.write().insert(
+            id.clone(),
+            UnblockRequest {
+                id: id.clone(),
+                subject,
+                requested_by: governor,
+                justification: justification.to_string(),
+                approvals: HashSet::new(),
+                created_at: now,
+            },
+        );
+        self.log(
+            subject,
+            QuarantineAction::UnblockRequested,
+            Some(governor),
+            format!("request {}: {}", id, justification),
+        );
+        Ok(id)
+    }
+
+    /// Approve an unblock request; releases the subject once the threshold is met
+    ///
+    /// Returns `true` if this approval released the subject.
+    pub fn approve_unblock(
+        &self,
+        request_id: &str,
+        governor: [u8; 32],
+    ) -> Result<bool, QuarantineError> {
+        self.ensure_governor(&governor)?;
+
+        let now = chrono::Utc::now().timestamp();
+        let mut requests = self.requests.write();
+        let request = requests
+            .get_mut(request_id)
+            .ok_or(QuarantineError::RequestNotFound)?;
+
+        if now - request.created_at > self.config.request_ttl_seconds as i64 {
+            let subject = request.subject;
+            requests.remove(request_id);
+            drop(requests);
+            self.log(
+                subject,
+                QuarantineAction::RequestExpired,
+                Some(governor),
+                format!("request {}", request_id),
+            );
+            return Err(QuarantineError::RequestExpired);
+        }
+
+        if !request.approvals.insert(governor) {
+            return Err(QuarantineError::AlreadyApproved);
+        }
+
+        let subject = request.subject;
+        let approvals = request.approvals.len();
+        let released = approvals >= self.config.approval_threshold;
+        if released {
+            requests.remove(request_id);
+        }
+        drop(requests);
+
+        self.log(
+            subject,
+            QuarantineAction::UnblockApproved,
+            Some(governor),
+            format!(
+                "request {} ({}/{})",
+                request_id, approvals, self.config.approval_threshold
+            ),
+        );
+
+        if released {
+            self.entries.write().remove(&subject);
+            tracing::info!("Released {} from quarantine", subject);
+            self.log(
+                subject,
+                QuarantineAction::Released,
+                None,
+                format!("request {}", request_id),
+            );
+        }
+        Ok(released)
+    }
+
+    /// Current entry for `subject`
+    pub fn entry(&self, subject: &QuarantineSubject) -> Option<QuarantineEntry> {
+        self.entries.read().get(subject).cloned()
+    }
+
+    /// All quarantined subjects
+    pub fn entries(&self) -> Vec<QuarantineEntry> {
+        self.entries.read().values().cloned().collect()
+    }
+
+    /// Open unblock request
+    pub fn unblock_request(&self, request_id: &str) -> Option<UnblockRequest> {
+        self.requests.read().get(request_id).cloned()
+    }
+
+    /// Full audit log, oldest first
+    pub fn audit_log(&self) -> Vec<QuarantineAuditEntry> {
+        self.audit.read().clone()
+    }
+
+    /// Audit entries for one subject
+    pub fn audit_for(&self, subject: &QuarantineSubject) -> Vec<QuarantineAuditEntry> {
+        self.audit
+            .read()
+            .iter()
+            .filter(|e| e.subject == *subject)
+            .cloned()
+            .collect()
+    }
+
+    fn ensure_governor(&self, governor: &[u8; 32]) -> Result<(), QuarantineError> {
+        if self.config.governors.contains(governor) {
+            Ok(())
+        } else {
+            Err(QuarantineError::NotGovernor)
+        }
+    }
+
+    fn log(
+        &self,
+        subject: QuarantineSubject,
+        action: QuarantineAction,
+        actor: Option<[u8; 32]>,
+        detail: String,
+    ) {
+        self.audit.write().push(QuarantineAuditEntry {
+            timestamp: chrono::Utc::now().timestamp(),
+            subject,
+            action,
+            actor,
+            detail,
+        });
+    }
+}
+
+impl QuarantineList for QuarantineRegistry {
+    fn is_quarantined(&self, subject: &QuarantineSubject) -> bool {
+        self.entries.read().contains_key(subject)
+    }
+}
+
+impl Default for QuarantineRegistry {
+    fn default() -> Self {
+        Self::new(QuarantineConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn critical_report() -> SecurityReport {
+        let mut report = SecurityReport::new("contract");
+        report.add_finding(SecurityFinding {
+            id: "REENT-001".to_string(),
+            title: "Reentrancy".to_string(),
+            description: "Reentrancy".to_string(),
+            severity: Severity::Critical,
+            category: "reentrancy".to_string(),
+            location: None,
+            remediation: "Fix".to_string(),
+            cwe_id: None,
+            confidence: 0.9,
+            timestamp: 0,
+            metadata: HashMap::new(),
+        });
+        report
+    }
+
+    fn registry() -> QuarantineRegistry {
+        QuarantineRegistry::new(QuarantineConfig {
+            governors: [[1u8; 32], [2u8; 32], [3u8; 32]].into_iter().collect(),
+            approval_threshold: 2,
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_enforce_only_on_critical() {
+        let registry = registry();
+        let subject = QuarantineSubject::Contract([0xaa; 20]);
+
+        assert!(registry
+            .enforce(subject, &SecurityReport::new("clean"))
+            .is_none());
+        assert!(!registry.is_quarantined(&subject));
+
+        let entry = registry.enforce(subject, &critical_report()).unwrap();
+        assert_eq!(entry.finding_ids, vec!["REENT-001".to_string()]);
+        assert!(registry.is_quarantined(&subject));
+    }
+
+    #[test]
+    fn test_governance_unblock() {
+        let registry = registry();
+        let subject = QuarantineSubject::Entity([9u8; 32]);
+        registry.enforce(subject, &critical_report());
+
+        assert!(matches!(
+            registry.request_unblock(subject, [7u8; 32], "not a governor"),
+            Err(QuarantineError::NotGovernor)
+        ));
+
+        let request = registry
+            .request_unblock(subject, [1u8; 32], "false positive")
+            .unwrap();
+        assert!(!registry.approve_unblock(&request, [1u8; 32]).unwrap());
+        assert!(matches!(
+            registry.approve_unblock(&request, [1u8; 32]),
+            Err(QuarantineError::AlreadyApproved)
+        ));
+        assert!(registry.is_quarantined(&subject));
+
+        assert!(registry.approve_unblock(&request, [2u8; 32]).unwrap());
+        assert!(!registry.is_quarantined(&subject));
+
+        let actions: Vec<_> = registry
+            .audit_for(&subject)
+            .into_iter()
+            .map(|e| e.action)
+            .collect();
+        assert_eq!(
+            actions,
+            vec![
+                QuarantineAction::Quarantined,
+                QuarantineAction::UnblockRequested,
+                QuarantineAction::UnblockApproved,
+                QuarantineAction::UnblockApproved,
+                QuarantineAction::Released,
+            ]
+        );
+    }
+}
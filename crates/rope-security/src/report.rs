@@ -0,0 +1,358 @@
+//! Report Exporters
+//!
+//! SARIF 2.1.0 output for GitHub code scanning and JUnit XML for CI test
+//! dashboards.
+
+use super::*;
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+const TOOL_NAME: &str = "Cerber";
+const TOOL_URI: &str = "https://github.com/KazeONGUENE/rope";
+
+impl Severity {
+    /// SARIF result level
+    pub fn sarif_level(&self) -> &'static str {
+        match self {
+            Severity::Critical | Severity::High => "error",
+            Severity::Medium => "warning",
+            Severity::Low | Severity::Info => "note",
+        }
+    }
+
+    /// GitHub `security-severity` score (CVSS-like, 0.0-10.0)
+    pub fn security_severity(&self) -> &'static str {
+        match self {
+            Severity::Critical => "9.5",
+            Severity::High => "8.0",
+            Severity::Medium => "5.5",
+            Severity::Low => "3.0",
+            Severity::Info => "0.0",
+        }
+    }
+}
+
+impl SecurityReport {
+    /// Export as a SARIF 2.1.0 log
+    pub fn to_sarif(&self) -> String {
+        serde_json::to_string_pretty(&self.sarif_log()).unwrap_or_default()
+    }
+
+    /// SARIF log as a JSON value
+    pub fn sarif_log(&self) -> serde_json::Value {
+        let mut rule_ids: Vec<&str> = Vec::new();
+        let mut rules = Vec::new();
+        for finding in &self.findings {
+            if rule_ids.contains(&finding.id.as_str()) {
+                continue;
+            }
+            rule_ids.push(&finding.id);
+            rules.push(sarif_rule(finding));
+        }
+
+        let results: Vec<serde_json::Value> = self
+            .findings
+            .iter()
+            .map(|finding| {
+                let rule_index = rule_ids
+                    .iter()
+                    .position(|id| *id == finding.id)
+                    .unwrap_or_default();
+                serde_json::json!({
+                    "ruleId": finding.id,
+                    "ruleIndex": rule_index,
+                    "level": finding.severity.sarif_level(),
+                    "message": { "text": finding.description },
+                    "locations": [self.sarif_location(finding)],
+                    "partialFingerprints": {
+                        "cerberFindingHash/v1": finding_fingerprint(&self.target, finding),
+                    },
+                    "properties": {
+                        "severity": finding.severity.to_string(),
+                        "confidence": finding.confidence,
+                        "metadata": finding.metadata,
+                    },
+                })
+            })
+            .collect();
+
+        serde_json::json!({
+            "$schema": SARIF_SCHEMA,
+            "version": "2.1.0",
+            "runs": [{
+                "tool": {
+                    "driver": {
+                        "name": TOOL_NAME,
+                        "informationUri": TOOL_URI,
+                        "version": env!("CARGO_PKG_VERSION"),
+                        "rules": rules,
+                    }
+                },
+                "automationDetails": { "id": format!("cerber/{}", self.id) },
+                "results": results,
+            }]
+        })
+    }
+
+    fn sarif_location(&self, finding: &SecurityFinding) -> serde_json::Value {
+        let location = finding.location.as_deref().unwrap_or_default();
+
+        if let Some(line) = location
+            .strip_prefix("line ")
+            .and_then(|l| l.trim().parse::<u64>().ok())
+        {
+            return serde_json::json!({
+                "physicalLocation": {
+                    "artifactLocation": { "uri": self.target },
+                    "region": { "startLine": line.max(1) },
+                }
+            });
+        }
+
+        if location.starts_with("Cargo.lock") {
+            return serde_json::json!({
+                "physicalLocation": { "artifactLocation": { "uri": "Cargo.lock" } },
+                "message": { "text": location },
+            });
+        }
+
+        let name = if location.is_empty() {
+            self.target.as_str()
+        } else {
+            location
+        };
+        serde_json::json!({
+            "physicalLocation": { "artifactLocation": { "uri": self.target } },
+            "logicalLocations": [{ "fullyQualifiedName": name }],
+        })
+    }
+
+    /// Export as JUnit XML
+    ///
+    /// Each finding becomes a test case; critical and high findings fail, which
+    /// matches [`SecurityReport::passed`]. A clean scan yields one passing case.
+    pub fn to_junit(&self) -> String {
+        let failures = self
+            .findings
+            .iter()
+            .filter(|f| f.severity >= Severity::High)
+            .count();
+        let tests = self.findings.len().max(1);
+        let time = self.scan_duration_ms as f64 / 1000.0;
+        let timestamp = chrono::DateTime::from_timestamp(self.timestamp, 0)
+            .map(|t| t.format("%Y-%m-%dT%H:%M:%S").to_string())
+            .unwrap_or_default();
+
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        xml.push_str(&format!(
+            "<testsuites name=\"{}\" tests=\"{}\" failures=\"{}\" time=\"{:.3}\">\n",
+            TOOL_NAME, tests, failures, time
+        ));
+        xml.push_str(&format!(
+            "  <testsuite name=\"{}\" id=\"{}\" tests=\"{}\" failures=\"{}\" errors=\"0\" skipped=\"0\" time=\"{:.3}\" timestamp=\"{}\">\n",
+            xml_escape(&self.target),
+            xml_escape(&self.id),
+            tests,
+            failures,
+            time,
+            timestamp
+        ));
+        xml.push_str("    <properties>\n");
+        xml.push_str(&format!(
+            "      <property name=\"risk_score\" value=\"{}\"/>\n",
+            self.risk_score
+        ));
+        xml.push_str(&format!(
+            "      <property name=\"passed\" value=\"{}\"/>\n",
+            self.passed
+        ));
+        xml.push_str("    </properties>\n");
+
+        if self.findings.is_empty() {
+            xml.push_str(&format!(
+                "    <testcase classname=\"cerber\" name=\"{}\"/>\n",
+                xml_escape(&self.target)
+            ));
+        }
+
+        for finding in &self.findings {
+            let name = match &finding.location {
+                Some(location) => format!("{} ({})", finding.id, location),
+                None => finding.id.clone(),
+            };
+            xml.push_str(&format!(
+                "    <testcase classname=\"{}\" name=\"{}\">\n",
+                xml_escape(&finding.category),
+                xml_escape(&name)
+            ));
+
+            let body = format!(
+                "{}\n\nSeverity: {}\nConfidence: {:.2}{}\nRemediation: {}",
+                finding.description,
+                finding.severity,
+                finding.confidence,
+                finding
+                    .cwe_id
+                    .map(|cwe| format!("\nCWE: CWE-{}", cwe))
+                    .unwrap_or_default(),
+                finding.remediation
+            );
+
+            if finding.severity >= Severity::High {
+                xml.push_str(&format!(
+                    "      <failure message=\"{}\" type=\"{}\">{}</failure>\n",
+                    xml_escape(&finding.title),
+                    finding.severity,
+                    xml_escape(&body)
+                ));
+            } else {
+                xml.push_str(&format!(
+                    "      <system-out>{}</system-out>\n",
+                    xml_escape(&body)
+                ));
+            }
+            xml.push_str("    </testcase>\n");
+        }
+
+        xml.push_str("  </testsuite>\n</testsuites>\n");
+        xml
+    }
+}
+
+fn sarif_rule(finding: &SecurityFinding) -> serde_json::Value {
+    let mut tags = vec!["security".to_string(), finding.category.clone()];
+    if let Some(cwe) = finding.cwe_id {
+        tags.push(format!("external/cwe/cwe-{}", cwe));
+    }
+
+    let mut rule = serde_json::json!({
+        "id": finding.id,
+        "name": finding.title,
+        "shortDescription": { "text": finding.title },
+        "fullDescription": { "text": finding.description },
+        "help": { "text": finding.remediation },
+        "defaultConfiguration": { "level": finding.severity.sarif_level() },
+        "properties": {
+            "tags": tags,
+            "security-severity": finding.severity.security_severity(),
+            "precision": precision(finding.confidence),
+        },
+    });
+    if let Some(cwe) = finding.cwe_id {
+        rule["helpUri"] = serde_json::json!(format!(
+            "https://cwe.mitre.org/data/definitions/{}.html",
+            cwe
+        ));
+    }
+    rule
+}
+
+/// SARIF rule precision from scanner confidence
+fn precision(confidence: f64) -> &'static str {
+    if confidence >= 0.9 {
+        "very-high"
+    } else if confidence >= 0.75 {
+        "high"
+    } else if confidence >= 0.5 {
+        "medium"
+    } else {
+        "low"
+    }
+}
+
+/// Stable across runs so code scanning can track alerts
+fn finding_fingerprint(target: &str, finding: &SecurityFinding) -> String {
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(target.as_bytes());
+    hasher.update(finding.id.as_bytes());
+    hasher.update(finding.location.as_deref().unwrap_or_default().as_bytes());
+    hex::encode(&hasher.finalize().as_bytes()[..16])
+}
+
+fn xml_escape(input: &str) -> String {
+    let mut out = String::with_capacity(input.len());
+    for c in input.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            // Control characters other than tab/newline are invalid in XML 1.0
+            c if c.is_control() && c != '\n' && c != '\t' && c != '\r' => {}
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_report() -> SecurityReport {
+        let mut report = SecurityReport::new("contracts/Vault.sol");
+        report.add_finding(SecurityFinding {
+            id: "REENT-001".to_string(),
+            title: "Reentrancy".to_string(),
+            description: "External call before state update".to_string(),
+            severity: Severity::Critical,
+            category: "reentrancy".to_string(),
+            location: Some("line 42".to_string()),
+            remediation: "Use checks-effects-interactions".to_string(),
+            cwe_id: Some(841),
+            confidence: 0.9,
+            timestamp: 0,
+            metadata: HashMap::new(),
+        });
+        report.add_finding(SecurityFinding {
+            id: "TS-001".to_string(),
+            title: "Timestamp Dependence".to_string(),
+            description: "Uses block.timestamp & now <unsafe>".to_string(),
+            severity: Severity::Low,
+            category: "timestamp".to_string(),
+            location: None,
+            remediation: "Avoid".to_string(),
+            cwe_id: None,
+            confidence: 0.6,
+            timestamp: 0,
+            metadata: HashMap::new(),
+        });
+        report.calculate_risk_score();
+        report
+    }
+
+    #[test]
+    fn test_sarif_export() {
+        let sarif: serde_json::Value = serde_json::from_str(&sample_report().to_sarif()).unwrap();
+        assert_eq!(sarif["version"], "2.1.0");
+
+        let run = &sarif["runs"][0];
+        assert_eq!(run["tool"]["driver"]["name"], "Cerber");
+        assert_eq!(run["tool"]["driver"]["rules"].as_array().unwrap().len(), 2);
+        assert_eq!(
+            run["tool"]["driver"]["rules"][0]["properties"]["security-severity"],
+            "9.5"
+        );
+
+        let result = &run["results"][0];
+        assert_eq!(result["level"], "error");
+        assert_eq!(
+            result["locations"][0]["physicalLocation"]["region"]["startLine"],
+            42
+        );
+        assert_eq!(run["results"][1]["level"], "note");
+        assert_eq!(run["results"][1]["ruleIndex"], 1);
+    }
+
+    #[test]
+    fn test_junit_export() {
+        let xml = sample_report().to_junit();
+        assert!(xml.contains("tests=\"2\" failures=\"1\""));
+        assert!(xml.contains("<failure message=\"Reentrancy\" type=\"CRITICAL\">"));
+        assert!(xml.contains("Uses block.timestamp &amp; now &lt;unsafe&gt;"));
+
+        let clean = SecurityReport::new("clean").to_junit();
+        assert!(clean.contains("tests=\"1\" failures=\"0\""));
+        assert!(clean.contains("<testcase classname=\"cerber\" name=\"clean\"/>"));
+    }
+}
@@ -1,8 +1,11 @@
 //! On-Chain Reputation System
 //!
-//! Entity reputation tracking with slashing for misbehavior
+//! Entity reputation tracking with slashing for misbehavior, plus an
+//! evidence-based scoring engine that combines scan history, testimony
+//! accuracy, slashing and bridge behavior with time decay.
 
 use super::*;
+use std::cmp::Reverse;
 
 /// Reputation score (0-1000)
 pub type ReputationScore = u32;
@@ -487,6 +490,370 @@ impl Default for AgentReputationManager {
     }
 }
 
+/// Where a piece of reputation evidence came from
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EvidenceSource {
+    ScanHistory,
+    Testimony,
+    Slashing,
+    Bridge,
+}
+
+/// Outcome of a bridge transfer handled by an entity
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BridgeOutcome {
+    Completed,
+    Failed,
+    Fraudulent,
+}
+
+/// Observed behavior contributing to an entity's score
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum EvidenceKind {
+    /// Cerber scan of code or contracts the entity deployed
+    ScanResult { risk_score: u32, passed: bool },
+    /// Testimony later confirmed or contradicted by consensus
+    Testimony { correct: bool },
+    /// Slashing for a protocol violation
+    Slashed {
+        violation_type: ViolationType,
+        amount: u128,
+    },
+    /// Bridge transfer relayed or attested by the entity
+    BridgeTransfer { outcome: BridgeOutcome, value: u128 },
+}
+
+impl EvidenceKind {
+    pub fn source(&self) -> EvidenceSource {
+        match self {
+            EvidenceKind::ScanResult { .. } => EvidenceSource::ScanHistory,
+            EvidenceKind::Testimony { .. } => EvidenceSource::Testimony,
+            EvidenceKind::Slashed { .. } => EvidenceSource::Slashing,
+            EvidenceKind::BridgeTransfer { .. } => EvidenceSource::Bridge,
+        }
+    }
+
+    /// Undecayed score change in reputation points
+    pub fn impact(&self) -> f64 {
+        match self {
+            EvidenceKind::ScanResult { passed: true, .. } => 15.0,
+            EvidenceKind::ScanResult { risk_score, .. } => -2.0 * *risk_score as f64,
+            EvidenceKind::Testimony { correct: true } => 5.0,
+            EvidenceKind::Testimony { correct: false } => -25.0,
+            EvidenceKind::Slashed { violation_type, .. } => {
+                -(violation_type.reputation_penalty() as f64)
+            }
+            EvidenceKind::BridgeTransfer { outcome, .. } => match outcome {
+                BridgeOutcome::Completed => 10.0,
+                BridgeOutcome::Failed => -20.0,
+                BridgeOutcome::Fraudulent => -500.0,
+            },
+        }
+    }
+}
+
+/// Timestamped evidence about one entity
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Evidence {
+    pub entity_id: [u8; 32],
+    pub kind: EvidenceKind,
+    pub timestamp: i64,
+    /// Report ID, transaction hash, etc.
+    pub reference: String,
+}
+
+/// Time decay applied to evidence, per source
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DecayConfig {
+    pub scan_half_life_secs: u64,
+    pub testimony_half_life_secs: u64,
+    pub slashing_half_life_secs: u64,
+    pub bridge_half_life_secs: u64,
+    /// Evidence decayed below this fraction is pruned
+    pub prune_below: f64,
+}
+
+impl DecayConfig {
+    pub fn half_life(&self, source: EvidenceSource) -> u64 {
+        match source {
+            EvidenceSource::ScanHistory => self.scan_half_life_secs,
+            EvidenceSource::Testimony => self.testimony_half_life_secs,
+            EvidenceSource::Slashing => self.slashing_half_life_secs,
+            EvidenceSource::Bridge => self.bridge_half_life_secs,
+        }
+    }
+
+    /// Remaining weight of evidence of `age_secs`
+    pub fn factor(&self, source: EvidenceSource, age_secs: i64) -> f64 {
+        let half_life = self.half_life(source);
+        if half_life == 0 {
+            return 1.0;
+        }
+        0.5f64.powf(age_secs.max(0) as f64 / half_life as f64)
+    }
+}
+
+impl Default for DecayConfig {
+    fn default() -> Self {
+        Self {
+            scan_half_life_secs: 30 * 86400,      // 30 days
+            testimony_half_life_secs: 7 * 86400,  // 7 days
+            slashing_half_life_secs: 180 * 86400, // 180 days
+            bridge_half_life_secs: 30 * 86400,    // 30 days
+            prune_below: 0.01,
+        }
+    }
+}
+
+/// Contribution of one evidence source to a score
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ScoreContribution {
+    pub source: EvidenceSource,
+    pub evidence_count: usize,
+    /// Sum of undecayed impacts
+    pub raw_points: f64,
+    /// Sum of impacts after decay
+    pub decayed_points: f64,
+}
+
+/// Breakdown of how a score was reached
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ScoreExplanation {
+    pub entity_id: [u8; 32],
+    pub score: ReputationScore,
+    pub base: ReputationScore,
+    pub contributions: Vec<ScoreContribution>,
+    pub as_of: i64,
+}
+
+impl ScoreExplanation {
+    /// Human-readable summary, largest contributions first
+    pub fn summary(&self) -> String {
+        let mut lines = vec![format!(
+            "{}: {} (base {})",
+            hex::encode(&self.entity_id[..8]),
+            self.score,
+            self.base
+        )];
+        for c in &self.contributions {
+            lines.push(format!(
+                "  {:?}: {:+.1} from {} events ({:+.1} before decay)",
+                c.source, c.decayed_points, c.evidence_count, c.raw_points
+            ));
+        }
+        lines.join("\n")
+    }
+}
+
+/// Evidence-based reputation scoring
+///
+/// Scores start at `MAX_REPUTATION / 2` and move by the decayed impact of every
+/// piece of recorded evidence, clamped to `0..=MAX_REPUTATION`.
+pub struct ReputationEngine {
+    evidence: RwLock<HashMap<[u8; 32], Vec<Evidence>>>,
+    decay: DecayConfig,
+}
+
+impl ReputationEngine {
+    pub fn new(decay: DecayConfig) -> Self {
+        Self {
+            evidence: RwLock::new(HashMap::new()),
+            decay,
+        }
+    }
+
+    /// Record a piece of evidence
+    pub fn record(&self, evidence: Evidence) {
+        self.evidence
+            .write()
+            .entry(evidence.entity_id)
+            .or_default()
+            .push(evidence);
+    }
+
+    /// Record a Cerber scan of code attributed to `entity_id`
+    pub fn record_scan(&self, entity_id: [u8; 32], report: &SecurityReport) {
+        self.record(Evidence {
+            entity_id,
+            kind: EvidenceKind::ScanResult {
+                risk_score: report.risk_score,
+                passed: report.passed,
+            },
+            timestamp: report.timestamp,
+            reference: report.id.clone(),
+        });
+    }
+
+    /// Record whether a testimony matched the final consensus outcome
+    pub fn record_testimony(&self, entity_id: [u8; 32], correct: bool, reference: &str) {
+        self.record(Evidence {
+            entity_id,
+            kind: EvidenceKind::Testimony { correct },
+            timestamp: chrono::Utc::now().timestamp(),
+            reference: reference.to_string(),
+        });
+    }
+
+    /// Record a slash emitted by [`ReputationManager`]
+    pub fn record_slash(&self, event: &SlashEvent) {
+        self.record(Evidence {
+            entity_id: event.entity_id,
+            kind: EvidenceKind::Slashed {
+                violation_type: event.violation_type.clone(),
+                amount: event.slash_amount,
+            },
+            timestamp: event.timestamp,
+            reference: format!("slash-{}", event.timestamp),
+        });
+    }
+
+    /// Record the outcome of a bridge transfer
+    pub fn record_bridge(
+        &self,
+        entity_id: [u8; 32],
+        outcome: BridgeOutcome,
+        value: u128,
+        tx_hash: &str,
+    ) {
+        self.record(Evidence {
+            entity_id,
+            kind: EvidenceKind::BridgeTransfer { outcome, value },
+            timestamp: chrono::Utc::now().timestamp(),
+            reference: tx_hash.to_string(),
+        });
+    }
+
+    /// Current score
+    pub fn score(&self, entity_id: &[u8; 32]) -> ReputationScore {
+        self.score_at(entity_id, chrono::Utc::now().timestamp())
+    }
+
+    /// Score as of `now`
+    pub fn score_at(&self, entity_id: &[u8; 32], now: i64) -> ReputationScore {
+        self.explain_at(entity_id, now).score
+    }
+
+    /// Explain the current score
+    pub fn explain(&self, entity_id: &[u8; 32]) -> ScoreExplanation {
+        self.explain_at(entity_id, chrono::Utc::now().timestamp())
+    }
+
+    /// Explain the score as of `now`
+    pub fn explain_at(&self, entity_id: &[u8; 32], now: i64) -> ScoreExplanation {
+        let base = MAX_REPUTATION / 2;
+        let mut contributions: Vec<ScoreContribution> = Vec::new();
+
+        if let Some(evidence) = self.evidence.read().get(entity_id) {
+            for item in evidence.iter().filter(|e| e.timestamp <= now) {
+                let source = item.kind.source();
+                let impact = item.kind.impact();
+                let decayed = impact * self.decay.factor(source, now - item.timestamp);
+
+                match contributions.iter_mut().find(|c| c.source == source) {
+                    Some(c) => {
+                        c.evidence_count += 1;
+                        c.raw_points += impact;
+                        c.decayed_points += decayed;
+                    }
+                    None => contributions.push(ScoreContribution {
+                        source,
+                        evidence_count: 1,
+                        raw_points: impact,
+                        decayed_points: decayed,
+                    }),
+                }
+            }
+        }
+
+        contributions.sort_by(|a, b| b.decayed_points.abs().total_cmp(&a.decayed_points.abs()));
+        let total: f64 = contributions.iter().map(|c| c.decayed_points).sum();
+        let score = (base as f64 + total)
+            .round()
+            .clamp(0.0, MAX_REPUTATION as f64);
+
+        ScoreExplanation {
+            entity_id: *entity_id,
+            score: score as ReputationScore,
+            base,
+            contributions,
+            as_of: now,
+        }
+    }
+
+    /// Weight in `0.0..=1.0` for an entity's validations; zero below [`MIN_REPUTATION`]
+    pub fn validation_weight(&self, entity_id: &[u8; 32]) -> f64 {
+        let score = self.score(entity_id);
+        if score < MIN_REPUTATION {
+            0.0
+        } else {
+            score as f64 / MAX_REPUTATION as f64
+        }
+    }
+
+    /// Reputation-weighted share of approving validations
+    ///
+    /// Returns `None` when no validator carries any weight.
+    pub fn weighted_approval(&self, validations: &[([u8; 32], bool)]) -> Option<f64> {
+        let mut approve = 0.0;
+        let mut total = 0.0;
+        for (entity_id, approved) in validations {
+            let weight = self.validation_weight(entity_id);
+            total += weight;
+            if *approved {
+                approve += weight;
+            }
+        }
+        if total > 0.0 {
+            Some(approve / total)
+        } else {
+            None
+        }
+    }
+
+    /// Entities ordered by score, highest first
+    pub fn ranked(&self, count: usize) -> Vec<([u8; 32], ReputationScore)> {
+        let now = chrono::Utc::now().timestamp();
+        let ids: Vec<[u8; 32]> = self.evidence.read().keys().copied().collect();
+        let mut ranked: Vec<_> = ids
+            .into_iter()
+            .map(|id| (id, self.score_at(&id, now)))
+            .collect();
+        ranked.sort_by_key(|(_, score)| Reverse(*score));
+        ranked.truncate(count);
+        ranked
+    }
+
+    /// Drop evidence whose remaining weight is negligible
+    pub fn prune(&self, now: i64) -> usize {
+        let mut evidence = self.evidence.write();
+        let mut removed = 0;
+        for items in evidence.values_mut() {
+            let before = items.len();
+            items.retain(|e| {
+                self.decay.factor(e.kind.source(), now - e.timestamp) >= self.decay.prune_below
+            });
+            removed += before - items.len();
+        }
+        evidence.retain(|_, items| !items.is_empty());
+        removed
+    }
+
+    /// Evidence recorded for an entity
+    pub fn evidence(&self, entity_id: &[u8; 32]) -> Vec<Evidence> {
+        self.evidence
+            .read()
+            .get(entity_id)
+            .cloned()
+            .unwrap_or_default()
+    }
+}
+
+impl Default for ReputationEngine {
+    fn default() -> Self {
+        Self::new(DecayConfig::default())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -583,4 +950,112 @@ mod tests {
                 > ViolationType::Downtime.reputation_penalty()
         );
     }
+
+    fn evidence(entity_id: [u8; 32], kind: EvidenceKind, timestamp: i64) -> Evidence {
+        Evidence {
+            entity_id,
+            kind,
+            timestamp,
+            reference: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_engine_combines_sources() {
+        let engine = ReputationEngine::default();
+        let entity = [6u8; 32];
+        let now = 1_000_000;
+
+        assert_eq!(engine.score_at(&entity, now), 500);
+
+        engine.record(evidence(
+            entity,
+            EvidenceKind::Testimony { correct: true },
+            now,
+        ));
+        engine.record(evidence(
+            entity,
+            EvidenceKind::BridgeTransfer {
+                outcome: BridgeOutcome::Completed,
+                value: 1,
+            },
+            now,
+        ));
+        engine.record(evidence(
+            entity,
+            EvidenceKind::Slashed {
+                violation_type: ViolationType::Spam,
+                amount: 10,
+            },
+            now,
+        ));
+
+        // 500 + 5 + 10 - 100
+        let explanation = engine.explain_at(&entity, now);
+        assert_eq!(explanation.score, 415);
+        assert_eq!(
+            explanation.contributions[0].source,
+            EvidenceSource::Slashing
+        );
+        assert!(explanation.summary().contains("Slashing"));
+    }
+
+    #[test]
+    fn test_engine_time_decay() {
+        let engine = ReputationEngine::default();
+        let entity = [7u8; 32];
+        let week = 7 * 86400;
+
+        engine.record(evidence(
+            entity,
+            EvidenceKind::Testimony { correct: false },
+            0,
+        ));
+        assert_eq!(engine.score_at(&entity, 0), 475);
+
+        // One testimony half-life later the penalty has halved
+        assert_eq!(engine.score_at(&entity, week), 488);
+
+        assert_eq!(engine.prune(week * 10), 1);
+        assert!(engine.evidence(&entity).is_empty());
+    }
+
+    #[test]
+    fn test_weighted_approval() {
+        let engine = ReputationEngine::default();
+        let honest = [8u8; 32];
+        let fraud = [9u8; 32];
+        let now = chrono::Utc::now().timestamp();
+
+        engine.record(evidence(
+            fraud,
+            EvidenceKind::BridgeTransfer {
+                outcome: BridgeOutcome::Fraudulent,
+                value: 1,
+            },
+            now,
+        ));
+        assert_eq!(engine.validation_weight(&fraud), 0.0);
+
+        let approval = engine
+            .weighted_approval(&[(honest, true), (fraud, false)])
+            .unwrap();
+        assert_eq!(approval, 1.0);
+        assert!(engine.weighted_approval(&[(fraud, true)]).is_none());
+    }
+
+    #[test]
+    fn test_engine_records_slash_events() {
+        let manager = ReputationManager::default();
+        let engine = ReputationEngine::default();
+        let entity = [10u8; 32];
+
+        manager.register_entity(entity).unwrap();
+        let event = manager
+            .report_violation(&entity, ViolationType::DoubleVoting, 1000, "")
+            .unwrap();
+        engine.record_slash(&event);
+
+        assert_eq!(engine.score_at(&entity, event.timestamp), 300);
+    }
 }
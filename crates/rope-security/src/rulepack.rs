@@ -0,0 +1,522 @@
+//! Custom Rule Packs
+//!
+//! Operators can ship their own [`VulnPattern`] sets as versioned TOML or YAML
+//! files. Packs are validated before any rule is installed, can be reloaded
+//! in place (a new version replaces the old one atomically), and every finding
+//! produced by a pack rule carries the pack's provenance in its metadata.
+//!
+//! ```toml
+//! schema_version = 1
+//!
+//! [pack]
+//! name = "acme-defi"
+//! version = "1.2.0"
+//!
+//! [[rules]]
+//! id = "ACME-ORACLE-001"
+//! name = "Spot Price Oracle"
+//! pattern = "getReserves\\s*\\("
+//! severity = "High"
+//! category = "oracle-manipulation"
+//! description = "Spot AMM reserves used as a price oracle"
+//! cwe_id = 682
+//! ```
+
+use super::*;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+/// Rule pack schema versions this build understands
+pub const RULE_PACK_SCHEMA_VERSION: u32 = 1;
+
+/// Where a rule came from, recorded on findings
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RuleProvenance {
+    pub pack: String,
+    pub version: String,
+    /// File the pack was loaded from, if any
+    pub source: Option<String>,
+}
+
+impl RuleProvenance {
+    /// Finding metadata entries describing this provenance
+    pub fn metadata(&self) -> HashMap<String, String> {
+        let mut metadata = HashMap::from([
+            ("rule_pack".to_string(), self.pack.clone()),
+            ("rule_pack_version".to_string(), self.version.clone()),
+        ]);
+        if let Some(source) = &self.source {
+            metadata.insert("rule_source".to_string(), source.clone());
+        }
+        metadata
+    }
+}
+
+/// Pack header
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RulePackInfo {
+    pub name: String,
+    /// Semantic version of the pack
+    pub version: String,
+    #[serde(default)]
+    pub description: String,
+}
+
+/// A single rule as written in a pack file
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RuleDefinition {
+    pub id: String,
+    pub name: String,
+    /// Regular expression matched against source
+    pub pattern: String,
+    pub severity: Severity,
+    pub category: String,
+    pub description: String,
+    #[serde(default)]
+    pub cwe_id: Option<u32>,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+/// Versioned set of custom rules
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RulePack {
+    pub schema_version: u32,
+    pub pack: RulePackInfo,
+    #[serde(default)]
+    pub rules: Vec<RuleDefinition>,
+    /// File the pack was read from
+    #[serde(skip)]
+    pub source: Option<PathBuf>,
+}
+
+/// Rule pack errors
+#[derive(Debug, Error)]
+pub enum RulePackError {
+    #[error("Failed to read {0}: {1}")]
+    Io(String, String),
+
+    #[error("Failed to parse {0}: {1}")]
+    Parse(String, String),
+
+    #[error("Unsupported rule pack format: {0}")]
+    UnsupportedFormat(String),
+
+    #[error("Invalid rule pack '{pack}': {}", .errors.join("; "))]
+    Invalid { pack: String, errors: Vec<String> },
+}
+
+impl RulePack {
+    pub fn from_toml(input: &str) -> Result<Self, RulePackError> {
+        toml::from_str(input).map_err(|e| RulePackError::Parse("<toml>".to_string(), e.to_string()))
+    }
+
+    pub fn from_yaml(input: &str) -> Result<Self, RulePackError> {
+        serde_yaml::from_str(input)
+            .map_err(|e| RulePackError::Parse("<yaml>".to_string(), e.to_string()))
+    }
+
+    /// Read a pack, choosing the format from the file extension
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, RulePackError> {
+        let path = path.as_ref();
+        let display = path.display().to_string();
+        let raw = std::fs::read_to_string(path)
+            .map_err(|e| RulePackError::Io(display.clone(), e.to_string()))?;
+
+        let parsed = match path.extension().and_then(|e| e.to_str()) {
+            Some("toml") => toml::from_str(&raw).map_err(|e| e.to_string()),
+            Some("yaml") | Some("yml") => serde_yaml::from_str(&raw).map_err(|e| e.to_string()),
+            _ => return Err(RulePackError::UnsupportedFormat(display)),
+        };
+
+        let mut pack: Self = parsed.map_err(|e| RulePackError::Parse(display, e))?;
+        pack.source = Some(path.to_path_buf());
+        Ok(pack)
+    }
+
+    /// Check the pack against the schema, collecting every problem
+    pub fn validate(&self) -> Result<(), RulePackError> {
+        let mut errors = Vec::new();
+
+        if self.schema_version != RULE_PACK_SCHEMA_VERSION {
+            errors.push(format!(
+                "schema_version {} is not supported (expected {})",
+                self.schema_version, RULE_PACK_SCHEMA_VERSION
+            ));
+        }
+        if self.pack.name.trim().is_empty() {
+            errors.push("pack.name is empty".to_string());
+        }
+        if Version::parse(&self.pack.version).is_none() {
+            errors.push(format!(
+                "pack.version '{}' is not a semantic version",
+                self.pack.version
+            ));
+        }
+
+        let mut seen = HashSet::new();
+        for (i, rule) in self.rules.iter().enumerate() {
+            let at = format!("rules[{}] ({})", i, rule.id);
+            if !is_valid_rule_id(&rule.id) {
+                errors.push(format!(
+                    "{}: id must be uppercase letters, digits, '-' or '_'",
+                    at
+                ));
+            }
+            if !seen.insert(rule.id.as_str()) {
+                errors.push(format!("{}: duplicate id", at));
+            }
+            if rule.name.trim().is_empty() {
+                errors.push(format!("{}: name is empty", at));
+            }
+            if rule.category.trim().is_empty() {
+                errors.push(format!("{}: category is empty", at));
+            }
+            if let Err(e) = Regex::new(&rule.pattern) {
+                errors.push(format!("{}: invalid pattern: {}", at, e));
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(RulePackError::Invalid {
+                pack: self.pack.name.clone(),
+                errors,
+            })
+        }
+    }
+
+    pub fn provenance(&self) -> RuleProvenance {
+        RuleProvenance {
+            pack: self.pack.name.clone(),
+            version: self.pack.version.clone(),
+            source: self.source.as_ref().map(|p| p.display().to_string()),
+        }
+    }
+
+    /// Validate and compile enabled rules into patterns
+    pub fn compile(&self) -> Result<Vec<VulnPattern>, RulePackError> {
+        self.validate()?;
+        let provenance = self.provenance();
+
+        Ok(self
+            .rules
+            .iter()
+            .filter(|rule| rule.enabled)
+            .filter_map(|rule| {
+                Some(VulnPattern {
+                    id: rule.id.clone(),
+                    name: rule.name.clone(),
+                    pattern: Regex::new(&rule.pattern).ok()?,
+                    severity: rule.severity,
+                    category: rule.category.clone(),
+                    description: rule.description.clone(),
+                    cwe_id: rule.cwe_id,
+                    provenance: Some(provenance.clone()),
+                })
+            })
+            .collect())
+    }
+}
+
+fn is_valid_rule_id(id: &str) -> bool {
+    !id.is_empty()
+        && id
+            .chars()
+            .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '-' || c == '_')
+}
+
+impl VulnerabilityDatabase {
+    /// Install a rule pack, replacing any previously loaded version of it
+    ///
+    /// Returns the number of enabled rules installed. Nothing changes if the
+    /// pack fails validation or reuses a rule ID owned by another source.
+    pub fn load_rule_pack(&self, pack: &RulePack) -> Result<usize, RulePackError> {
+        let compiled = pack.compile()?;
+        let mut patterns = self.patterns.write();
+
+        let conflicts: Vec<String> = compiled
+            .iter()
+            .filter(|new| {
+                patterns.iter().any(|existing| {
+                    existing.id == new.id
+                        && existing.provenance.as_ref().map(|p| p.pack.as_str())
+                            != Some(pack.pack.name.as_str())
+                })
+            })
+            .map(|p| format!("{}: id already defined by another source", p.id))
+            .collect();
+        if !conflicts.is_empty() {
+            return Err(RulePackError::Invalid {
+                pack: pack.pack.name.clone(),
+                errors: conflicts,
+            });
+        }
+
+        patterns.retain(|p| {
+            p.provenance.as_ref().map(|prov| prov.pack.as_str()) != Some(pack.pack.name.as_str())
+        });
+        let count = compiled.len();
+        patterns.extend(compiled);
+
+        tracing::info!(
+            "Loaded rule pack {} v{} ({} rules)",
+            pack.pack.name,
+            pack.pack.version,
+            count
+        );
+        Ok(count)
+    }
+
+    /// Load a pack file
+    pub fn load_rule_pack_file(&self, path: impl AsRef<Path>) -> Result<usize, RulePackError> {
+        self.load_rule_pack(&RulePack::from_file(path)?)
+    }
+
+    /// Load every `.toml`/`.yaml`/`.yml` pack in a directory
+    ///
+    /// Invalid packs are logged and skipped so one bad file cannot disable the rest.
+    pub fn load_rule_pack_dir(&self, dir: impl AsRef<Path>) -> Result<usize, RulePackError> {
+        let mut total = 0;
+        for path in rule_pack_files(dir.as_ref())? {
+            match self.load_rule_pack_file(&path) {
+                Ok(count) => total += count,
+                Err(e) => tracing::warn!("Skipping rule pack {}: {}", path.display(), e),
+            }
+        }
+        Ok(total)
+    }
+
+    /// Remove all rules installed by a pack
+    pub fn unload_rule_pack(&self, name: &str) -> usize {
+        let mut patterns = self.patterns.write();
+        let before = patterns.len();
+        patterns.retain(|p| p.provenance.as_ref().map(|prov| prov.pack.as_str()) != Some(name));
+        before - patterns.len()
+    }
+
+    /// Loaded packs and their versions
+    pub fn rule_packs(&self) -> Vec<RuleProvenance> {
+        let mut packs: Vec<RuleProvenance> = Vec::new();
+        for pattern in self.patterns.read().iter() {
+            if let Some(prov) = &pattern.provenance {
+                if !packs.contains(prov) {
+                    packs.push(prov.clone());
+                }
+            }
+        }
+        packs
+    }
+
+    /// Poll `dir` and reload packs whose files change
+    ///
+    /// A pack that fails to reload keeps its previous version; a deleted file
+    /// unloads its pack.
+    pub fn watch_rule_packs(
+        self: Arc<Self>,
+        dir: impl Into<PathBuf>,
+        interval: Duration,
+    ) -> tokio::task::JoinHandle<()> {
+        let dir = dir.into();
+        tokio::spawn(async move {
+            let mut seen: HashMap<PathBuf, (SystemTime, String)> = HashMap::new();
+            let mut ticker = tokio::time::interval(interval);
+
+            loop {
+                ticker.tick().await;
+                let files = match rule_pack_files(&dir) {
+                    Ok(files) => files,
+                    Err(e) => {
+                        tracing::warn!("Rule pack watch failed: {}", e);
+                        continue;
+                    }
+                };
+
+                for path in &files {
+                    let Ok(modified) = std::fs::metadata(path).and_then(|m| m.modified()) else {
+                        continue;
+                    };
+                    if seen.get(path).is_some_and(|(at, _)| *at == modified) {
+                        continue;
+                    }
+
+                    match RulePack::from_file(path).and_then(|pack| {
+                        self.load_rule_pack(&pack)?;
+                        Ok(pack.pack.name)
+                    }) {
+                        Ok(name) => {
+                            seen.insert(path.clone(), (modified, name));
+                        }
+                        Err(e) => {
+                            tracing::warn!("Failed to reload {}: {}", path.display(), e);
+                            // Remember the bad revision so it is not retried every tick
+                            let name = seen.get(path).map(|(_, n)| n.clone()).unwrap_or_default();
+                            seen.insert(path.clone(), (modified, name));
+                        }
+                    }
+                }
+
+                let removed: Vec<PathBuf> = seen
+                    .keys()
+                    .filter(|path| !files.contains(path))
+                    .cloned()
+                    .collect();
+                for path in removed {
+                    if let Some((_, name)) = seen.remove(&path) {
+                        if !name.is_empty() {
+                            self.unload_rule_pack(&name);
+                            tracing::info!("Unloaded rule pack {}", name);
+                        }
+                    }
+                }
+            }
+        })
+    }
+}
+
+fn rule_pack_files(dir: &Path) -> Result<Vec<PathBuf>, RulePackError> {
+    let entries = std::fs::read_dir(dir)
+        .map_err(|e| RulePackError::Io(dir.display().to_string(), e.to_string()))?;
+    let mut files: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| {
+            matches!(
+                path.extension().and_then(|e| e.to_str()),
+                Some("toml") | Some("yaml") | Some("yml")
+            )
+        })
+        .collect();
+    files.sort();
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PACK: &str = r#"
+schema_version = 1
+
+[pack]
+name = "acme"
+version = "1.0.0"
+
+[[rules]]
+id = "ACME-001"
+name = "Spot Price Oracle"
+pattern = "getReserves\\s*\\("
+severity = "High"
+category = "oracle-manipulation"
+description = "Spot AMM reserves used as a price oracle"
+cwe_id = 682
+
+[[rules]]
+id = "ACME-002"
+name = "Disabled"
+pattern = "foo"
+severity = "Low"
+category = "style"
+description = "Off by default"
+enabled = false
+"#;
+
+    #[test]
+    fn test_load_toml_pack() {
+        let db = VulnerabilityDatabase::new();
+        let builtin = db.patterns().len();
+
+        let pack = RulePack::from_toml(PACK).unwrap();
+        assert_eq!(db.load_rule_pack(&pack).unwrap(), 1);
+        assert_eq!(db.patterns().len(), builtin + 1);
+
+        // Reloading a new version replaces the old rules
+        let mut v2 = pack.clone();
+        v2.pack.version = "1.1.0".to_string();
+        db.load_rule_pack(&v2).unwrap();
+        assert_eq!(db.patterns().len(), builtin + 1);
+        assert_eq!(db.rule_packs()[0].version, "1.1.0");
+
+        assert_eq!(db.unload_rule_pack("acme"), 1);
+        assert_eq!(db.patterns().len(), builtin);
+    }
+
+    #[test]
+    fn test_disable_rule() {
+        let db = VulnerabilityDatabase::new();
+        db.set_rule_enabled("TXORIGIN-001", false);
+        assert!(!db.is_rule_enabled("TXORIGIN-001"));
+        assert!(!db.patterns().iter().any(|p| p.id == "TXORIGIN-001"));
+
+        db.set_rule_enabled("TXORIGIN-001", true);
+        assert!(db.patterns().iter().any(|p| p.id == "TXORIGIN-001"));
+    }
+
+    #[test]
+    fn test_yaml_pack() {
+        let pack = RulePack::from_yaml(
+            r#"
+schema_version: 1
+pack:
+  name: ops
+  version: 0.1.0
+rules:
+  - id: OPS-001
+    name: Debug Endpoint
+    pattern: "debug_[a-z]+"
+    severity: Medium
+    category: exposure
+    description: Debug RPC exposed
+"#,
+        )
+        .unwrap();
+        assert_eq!(pack.compile().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_schema_validation() {
+        let mut pack = RulePack::from_toml(PACK).unwrap();
+        pack.schema_version = 2;
+        pack.pack.version = "latest".to_string();
+        pack.rules[0].pattern = "(".to_string();
+        pack.rules[1].id = "ACME-001".to_string();
+
+        match pack.validate() {
+            Err(RulePackError::Invalid { errors, .. }) => assert_eq!(errors.len(), 4),
+            other => panic!("expected validation errors, got {:?}", other),
+        }
+
+        // Rule IDs may not shadow built-in patterns
+        let mut shadow = RulePack::from_toml(PACK).unwrap();
+        shadow.rules[0].id = "REENT-001".to_string();
+        assert!(VulnerabilityDatabase::new()
+            .load_rule_pack(&shadow)
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_pack_findings_carry_provenance() {
+        let db = Arc::new(VulnerabilityDatabase::new());
+        db.load_rule_pack(&RulePack::from_toml(PACK).unwrap())
+            .unwrap();
+
+        let analyzer = StaticAnalyzer::with_database(db);
+        let findings = analyzer
+            .scan(&ScanTarget::SoliditySource(
+                "(uint a, uint b,) = pair.getReserves();".to_string(),
+            ))
+            .await
+            .unwrap();
+
+        let finding = findings
+            .iter()
+            .find(|f| f.title == "Spot Price Oracle")
+            .unwrap();
+        assert_eq!(finding.metadata["rule_pack"], "acme");
+        assert_eq!(finding.metadata["rule_pack_version"], "1.0.0");
+    }
+}
@@ -6,13 +6,21 @@ use super::*;
 
 /// Static code analyzer
 pub struct StaticAnalyzer {
+    /// Pattern database (built-ins plus loaded rule packs)
+    vuln_db: Arc<VulnerabilityDatabase>,
     /// Custom patterns
     custom_patterns: Vec<VulnPattern>,
 }
 
 impl StaticAnalyzer {
     pub fn new() -> Self {
+        Self::with_database(Arc::new(VulnerabilityDatabase::new()))
+    }
+
+    /// Analyze against a shared database so rule pack reloads take effect
+    pub fn with_database(vuln_db: Arc<VulnerabilityDatabase>) -> Self {
         Self {
+            vuln_db,
             custom_patterns: Vec::new(),
         }
     }
@@ -49,13 +57,38 @@ impl SecurityScanner for StaticAnalyzer {
             _ => return Ok(Vec::new()),
         };
 
-        let mut findings = Vec::new();
-        let db = VulnerabilityDatabase::new();
+        let language = match target {
+            ScanTarget::SoliditySource(_) => SourceLanguage::Solidity,
+            _ => SourceLanguage::Rust,
+        };
+        let flows = dataflow::analyze(source, language);
+        let confirmed: HashSet<&str> = flows
+            .iter()
+            .filter_map(|flow| flow.sink.regex_pattern_id())
+            .collect();
+
+        let mut findings: Vec<SecurityFinding> = flows.iter().map(TaintFlow::to_finding).collect();
+        for pattern in self
+            .vuln_db
+            .patterns()
+            .iter()
+            .chain(self.custom_patterns.iter())
+        {
+            // Patterns the dataflow pass covers only stay confident with a tainted flow
+            let refuted = is_taint_checked(&pattern.id) && !confirmed.contains(pattern.id.as_str());
 
-        for pattern in db.patterns().iter().chain(self.custom_patterns.iter()) {
             for mat in pattern.pattern.find_iter(source) {
                 // Calculate line number
-                let line_num = source[..mat.start()].lines().count() + 1;
+                let line_num = source[..mat.start()].matches('\n').count() + 1;
+
+                let mut metadata = pattern
+                    .provenance
+                    .as_ref()
+                    .map(RuleProvenance::metadata)
+                    .unwrap_or_default();
+                if refuted {
+                    metadata.insert("dataflow".to_string(), "no tainted input".to_string());
+                }
 
                 findings.push(SecurityFinding {
                     id: format!("{}-{}", pattern.id, findings.len()),
@@ -66,9 +99,9 @@ impl SecurityScanner for StaticAnalyzer {
                     location: Some(format!("line {}", line_num)),
                     remediation: get_remediation(&pattern.category),
                     cwe_id: pattern.cwe_id,
-                    confidence: 0.85,
+                    confidence: if refuted { 0.5 } else { 0.85 },
                     timestamp: chrono::Utc::now().timestamp(),
-                    metadata: HashMap::new(),
+                    metadata,
                 });
             }
         }
@@ -77,6 +110,13 @@ impl SecurityScanner for StaticAnalyzer {
     }
 }
 
+/// Whether a regex pattern's sink is also tracked by the dataflow pass
+fn is_taint_checked(pattern_id: &str) -> bool {
+    [TaintSink::Delegatecall, TaintSink::SqlQuery]
+        .iter()
+        .any(|sink| sink.regex_pattern_id() == Some(pattern_id))
+}
+
 /// Smart contract security scanner
 pub struct SmartContractScanner {
     vuln_db: Arc<VulnerabilityDatabase>,
@@ -87,67 +127,9 @@ impl SmartContractScanner {
         Self { vuln_db }
     }
 
-    /// Analyze bytecode for known patterns
+    /// Disassemble bytecode and check it for known patterns
     fn analyze_bytecode(&self, bytecode: &[u8]) -> Vec<SecurityFinding> {
-        let mut findings = Vec::new();
-        let hex_code = hex::encode(bytecode);
-
-        // Check for DELEGATECALL opcode (0xf4)
-        if bytecode.contains(&0xf4) {
-            findings.push(SecurityFinding {
-                id: "BYTECODE-DELEGATECALL".to_string(),
-                title: "Delegatecall Detected in Bytecode".to_string(),
-                description: "Contract uses delegatecall which may allow code injection"
-                    .to_string(),
-                severity: Severity::High,
-                category: "access-control".to_string(),
-                location: None,
-                remediation: "Verify delegatecall targets are trusted".to_string(),
-                cwe_id: Some(829),
-                confidence: 0.9,
-                timestamp: chrono::Utc::now().timestamp(),
-                metadata: HashMap::new(),
-            });
-        }
-
-        // Check for SELFDESTRUCT opcode (0xff)
-        if bytecode.contains(&0xff) {
-            findings.push(SecurityFinding {
-                id: "BYTECODE-SELFDESTRUCT".to_string(),
-                title: "Selfdestruct Detected in Bytecode".to_string(),
-                description: "Contract can be destroyed".to_string(),
-                severity: Severity::Medium,
-                category: "denial-of-service".to_string(),
-                location: None,
-                remediation: "Ensure selfdestruct is properly protected".to_string(),
-                cwe_id: Some(400),
-                confidence: 0.95,
-                timestamp: chrono::Utc::now().timestamp(),
-                metadata: HashMap::new(),
-            });
-        }
-
-        // Check for known malicious signatures
-        if self
-            .vuln_db
-            .is_malicious(&hex_code[..64.min(hex_code.len())])
-        {
-            findings.push(SecurityFinding {
-                id: "BYTECODE-MALICIOUS".to_string(),
-                title: "Known Malicious Contract".to_string(),
-                description: "Contract signature matches known malicious code".to_string(),
-                severity: Severity::Critical,
-                category: "malware".to_string(),
-                location: None,
-                remediation: "Do not interact with this contract".to_string(),
-                cwe_id: None,
-                confidence: 1.0,
-                timestamp: chrono::Utc::now().timestamp(),
-                metadata: HashMap::new(),
-            });
-        }
-
-        findings
+        disassembler::analyze_bytecode(bytecode, &self.vuln_db)
     }
 }
 
@@ -173,7 +155,7 @@ impl SecurityScanner for SmartContractScanner {
                 // Check for common vulnerabilities in source
                 for pattern in self.vuln_db.patterns() {
                     for mat in pattern.pattern.find_iter(source) {
-                        let line_num = source[..mat.start()].lines().count() + 1;
+                        let line_num = source[..mat.start()].matches('\n').count() + 1;
 
                         findings.push(SecurityFinding {
                             id: format!("SC-{}", pattern.id),
@@ -186,7 +168,11 @@ impl SecurityScanner for SmartContractScanner {
                             cwe_id: pattern.cwe_id,
                             confidence: 0.85,
                             timestamp: chrono::Utc::now().timestamp(),
-                            metadata: HashMap::new(),
+                            metadata: pattern
+                                .provenance
+                                .as_ref()
+                                .map(RuleProvenance::metadata)
+                                .unwrap_or_default(),
                         });
                     }
                 }
@@ -234,35 +220,84 @@ impl SecurityScanner for SmartContractScanner {
 }
 
 /// Dependency vulnerability scanner
+///
+/// Checks the registry packages pinned in a workspace's `Cargo.lock`
+/// against RustSec advisories.
 pub struct DependencyScanner {
-    /// Known vulnerable crate versions
-    vulnerable_crates: HashMap<String, Vec<(String, Severity, String)>>,
+    advisories: AdvisoryDatabase,
 }
 
 impl DependencyScanner {
+    /// Scanner over the bundled advisories
     pub fn new() -> Self {
-        let mut vulnerable_crates = HashMap::new();
-
-        // Known vulnerable crate versions (example data)
-        vulnerable_crates.insert(
-            "hyper".to_string(),
-            vec![(
-                "<0.14.10".to_string(),
-                Severity::High,
-                "HTTP request smuggling".to_string(),
-            )],
-        );
-
-        vulnerable_crates.insert(
-            "tokio".to_string(),
-            vec![(
-                "<1.8.4".to_string(),
-                Severity::Medium,
-                "Data race in JoinHandle".to_string(),
-            )],
-        );
-
-        Self { vulnerable_crates }
+        Self::with_database(AdvisoryDatabase::bundled())
+    }
+
+    /// Scanner over a loaded advisory database
+    pub fn with_database(advisories: AdvisoryDatabase) -> Self {
+        Self { advisories }
+    }
+
+    /// Findings for the packages in a `Cargo.lock`
+    pub fn scan_lockfile(&self, lockfile: &str) -> Result<Vec<SecurityFinding>, SecurityError> {
+        let mut findings = Vec::new();
+
+        for package in parse_lockfile(lockfile)?.iter().filter(|p| p.is_registry()) {
+            let Some(version) = Version::parse(&package.version) else {
+                continue;
+            };
+            for advisory in self.advisories.affecting(&package.name, &version) {
+                findings.push(advisory_finding(advisory, package, &version));
+            }
+        }
+
+        Ok(findings)
+    }
+}
+
+fn advisory_finding(
+    advisory: &Advisory,
+    package: &LockedPackage,
+    version: &Version,
+) -> SecurityFinding {
+    let upgrade = advisory.suggested_upgrade(version);
+    let remediation = match &upgrade {
+        Some(upgrade) => format!("Upgrade {} to {} or later", package.name, upgrade),
+        None => format!(
+            "No patched version of {} is available; replace the dependency",
+            package.name
+        ),
+    };
+
+    let mut metadata = HashMap::from([
+        ("advisory".to_string(), advisory.id.clone()),
+        ("package".to_string(), package.name.clone()),
+        ("version".to_string(), version.to_string()),
+        ("affected".to_string(), advisory.affected_range()),
+    ]);
+    if !advisory.aliases.is_empty() {
+        metadata.insert("aliases".to_string(), advisory.aliases.join(","));
+    }
+    if let Some(upgrade) = &upgrade {
+        metadata.insert("suggested_upgrade".to_string(), upgrade.to_string());
+    }
+    if let Some(url) = &advisory.url {
+        metadata.insert("url".to_string(), url.clone());
+    }
+
+    SecurityFinding {
+        id: advisory.id.clone(),
+        title: format!("{} {}: {}", package.name, version, advisory.title),
+        description: advisory.description.clone(),
+        severity: advisory.severity(),
+        category: "vulnerable-dependency".to_string(),
+        location: Some(format!("Cargo.lock: {} {}", package.name, version)),
+        remediation,
+        cwe_id: None,
+        // Version matches are exact
+        confidence: 1.0,
+        timestamp: chrono::Utc::now().timestamp(),
+        metadata,
     }
 }
 
@@ -279,18 +314,24 @@ impl SecurityScanner for DependencyScanner {
     }
 
     fn supports(&self, target: &ScanTarget) -> bool {
-        matches!(target, ScanTarget::RustSource(_))
+        matches!(target, ScanTarget::Workspace(_))
     }
 
-    async fn scan(&self, _target: &ScanTarget) -> Result<Vec<SecurityFinding>, SecurityError> {
-        // In a real implementation, this would parse Cargo.lock and check versions
-        // For now, return empty as this requires file system access
-        Ok(Vec::new())
+    async fn scan(&self, target: &ScanTarget) -> Result<Vec<SecurityFinding>, SecurityError> {
+        let ScanTarget::Workspace(root) = target else {
+            return Ok(Vec::new());
+        };
+
+        let path = root.join("Cargo.lock");
+        let lockfile = tokio::fs::read_to_string(&path)
+            .await
+            .map_err(|e| SecurityError::ScanFailed(format!("{}: {}", path.display(), e)))?;
+        self.scan_lockfile(&lockfile)
     }
 }
 
 /// Get remediation advice for a category
-fn get_remediation(category: &str) -> String {
+pub(crate) fn get_remediation(category: &str) -> String {
     match category {
         "reentrancy" => "Use checks-effects-interactions pattern or reentrancy guard".to_string(),
         "integer-overflow" => {
@@ -306,6 +347,8 @@ fn get_remediation(category: &str) -> String {
         "denial-of-service" => "Add access controls and consider removing selfdestruct".to_string(),
         "memory-safety" => "Minimize unsafe blocks and document safety invariants".to_string(),
         "injection" => "Use parameterized queries and input validation".to_string(),
+        "vulnerable-dependency" => "Upgrade to a patched version".to_string(),
+        "input-validation" => "Validate user-supplied values with require() before use".to_string(),
         _ => "Review and remediate according to security best practices".to_string(),
     }
 }
@@ -336,6 +379,85 @@ mod tests {
         assert!(findings.len() >= 2);
     }
 
+    #[tokio::test]
+    async fn test_static_analyzer_dataflow() {
+        let analyzer = StaticAnalyzer::new();
+
+        let source = r#"
+            pragma solidity ^0.8.0;
+            contract Proxy {
+                address implementation;
+
+                function upgradeAndCall(address impl, bytes calldata data) external {
+                    address target = impl;
+                    target.delegatecall(data);
+                }
+            }
+        "#;
+        let findings = analyzer
+            .scan(&ScanTarget::SoliditySource(source.to_string()))
+            .await
+            .unwrap();
+        assert!(findings
+            .iter()
+            .any(|f| f.id.starts_with("TAINT-DELEGATECALL") && f.severity == Severity::Critical));
+
+        // Fixed implementation: regex still matches, at low confidence
+        let source = r#"
+            contract Proxy {
+                address implementation;
+
+                fallback() external {
+                    implementation.delegatecall(msg.sig);
+                }
+            }
+        "#;
+        let findings = analyzer
+            .scan(&ScanTarget::SoliditySource(source.to_string()))
+            .await
+            .unwrap();
+        let regex = findings
+            .iter()
+            .find(|f| f.id.starts_with("DELEGATECALL-001"))
+            .unwrap();
+        assert!(regex.confidence < CerberConfig::default().min_confidence);
+        assert!(!findings.iter().any(|f| f.id.starts_with("TAINT-")));
+    }
+
+    #[tokio::test]
+    async fn test_dependency_scanner() {
+        let scanner = DependencyScanner::new();
+        let lockfile = r#"
+            [[package]]
+            name = "tokio"
+            version = "1.8.0"
+            source = "registry+https://github.com/rust-lang/crates.io-index"
+
+            [[package]]
+            name = "regex"
+            version = "1.10.2"
+            source = "registry+https://github.com/rust-lang/crates.io-index"
+
+            [[package]]
+            name = "hyper"
+            version = "0.14.2"
+        "#;
+        let findings = scanner.scan_lockfile(lockfile).unwrap();
+
+        // hyper is a path crate here, regex is patched
+        assert_eq!(findings.len(), 1);
+        let finding = &findings[0];
+        assert_eq!(finding.id, "RUSTSEC-2021-0124");
+        assert_eq!(finding.metadata["aliases"], "CVE-2021-45710");
+        assert_eq!(finding.metadata["suggested_upgrade"], "1.8.4");
+        assert_eq!(finding.remediation, "Upgrade tokio to 1.8.4 or later");
+
+        let missing = scanner
+            .scan(&ScanTarget::Workspace("/nonexistent".into()))
+            .await;
+        assert!(matches!(missing, Err(SecurityError::ScanFailed(_))));
+    }
+
     #[tokio::test]
     async fn test_contract_scanner_bytecode() {
         let db = Arc::new(VulnerabilityDatabase::new());
@@ -40,10 +40,51 @@
 //!
 //! **Security**: DC FAT minting MUST go through governance approval.
 //! Direct minting is ONLY allowed for custom tokens created by their owners.
-
-use parking_lot::RwLock;
+//!
+//! ## Vesting
+//!
+//! Genesis allocations and project grants can carry a [`VestingLock`]: the
+//! amount is held in the account but stays locked until the cliff, then
+//! releases linearly until the end of the schedule. Locked and frozen
+//! funds can't be transferred, burned or bonded for staking; only the
+//! liquid part of a balance can.
+//!
+//! ## Double-Entry Journal
+//!
+//! Every balance change is also posted to an append-only journal as a
+//! [`JournalEntry`] that debits one [`LedgerAccountId`] and credits
+//! another for the same amount:
+//!
+//! | Operation | Debit | Credit |
+//! |-----------|-------|--------|
+//! | Issuance (mint, initial supply) | `Issuance` | `Holder(to)` |
+//! | Transfer | `Holder(from)` | `Holder(to)` |
+//! | Spend (burn) | `Holder(from)` | `Retired` |
+//!
+//! The positions of all journal accounts for a token therefore always sum
+//! to zero. [`CreditsLedger::reconcile`] replays the journal against the
+//! account balances (for DC FAT or any other token) and reports every
+//! holder whose balance drifted from its journal position.
+//!
+//! Transfers carrying an idempotency key are applied at most once; a
+//! retry with the same key returns the original result.
+//!
+//! ## Settlement
+//!
+//! [`CreditsLedger::settle`] closes the journal entries posted since the
+//! previous settlement into one [`SettlementRecord`] per token, carrying
+//! the net position change of every account and a hash chain over the
+//! settled entries. The node drains records with
+//! [`CreditsLedger::take_settlements`] and writes them to the lattice as
+//! immutable strings.
+
+use parking_lot::{Mutex, RwLock};
+use rope_core::clock::LamportClock;
+use rope_core::string::{PublicKey, RopeString};
+use rope_core::types::MutabilityClass;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
 
 /// Token identifier (32 bytes)
 pub type TokenId = [u8; 32];
@@ -242,6 +283,67 @@ pub struct RateLimit {
     pub period_seconds: u64,
 }
 
+/// Cliff + linear release of an amount held in an account
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VestingLock {
+    /// Amount under the schedule
+    pub amount: Balance,
+
+    /// Schedule start (Unix seconds)
+    pub start: i64,
+
+    /// Nothing releases before `start + cliff_seconds`
+    pub cliff_seconds: u64,
+
+    /// Fully released at `start + duration_seconds`
+    pub duration_seconds: u64,
+}
+
+impl VestingLock {
+    pub fn new(amount: Balance, start: i64, cliff_seconds: u64, duration_seconds: u64) -> Self {
+        Self {
+            amount,
+            start,
+            cliff_seconds,
+            duration_seconds,
+        }
+    }
+
+    /// Amount released at `now`
+    pub fn vested_at(&self, now: i64) -> Balance {
+        let elapsed = now.saturating_sub(self.start);
+        if elapsed < 0 || (elapsed as u64) < self.cliff_seconds {
+            return 0;
+        }
+        if elapsed as u64 >= self.duration_seconds {
+            return self.amount;
+        }
+        self.amount * elapsed as u128 / self.duration_seconds as u128
+    }
+
+    /// Amount still locked at `now`
+    pub fn locked_at(&self, now: i64) -> Balance {
+        self.amount - self.vested_at(now)
+    }
+
+    /// Whether everything is released at `now`
+    pub fn is_complete(&self, now: i64) -> bool {
+        self.locked_at(now) == 0
+    }
+}
+
+/// Balance of one token split by what can be spent
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BalanceBreakdown {
+    pub total: Balance,
+    /// Spendable now
+    pub liquid: Balance,
+    /// Held by vesting locks
+    pub locked: Balance,
+    /// Frozen (e.g. bonded stake)
+    pub frozen: Balance,
+}
+
 /// Account with token balances
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Account {
@@ -257,6 +359,10 @@ pub struct Account {
     /// Allowances (delegated spending)
     pub allowances: HashMap<TokenId, HashMap<[u8; 32], Balance>>,
 
+    /// Vesting locks
+    #[serde(default)]
+    pub vesting: HashMap<TokenId, Vec<VestingLock>>,
+
     /// Account nonce (for replay protection)
     pub nonce: u64,
 
@@ -271,16 +377,41 @@ impl Account {
             balances: HashMap::new(),
             frozen: HashMap::new(),
             allowances: HashMap::new(),
+            vesting: HashMap::new(),
             nonce: 0,
             created_at: chrono::Utc::now().timestamp(),
         }
     }
 
-    /// Get available balance (total - frozen)
+    /// Get available balance (total - frozen - locked)
     pub fn available_balance(&self, token_id: &TokenId) -> Balance {
+        self.available_balance_at(token_id, chrono::Utc::now().timestamp())
+    }
+
+    /// Available balance at `now`
+    pub fn available_balance_at(&self, token_id: &TokenId, now: i64) -> Balance {
+        self.balance_breakdown(token_id, now).liquid
+    }
+
+    /// Amount held by vesting locks at `now`
+    pub fn locked_balance(&self, token_id: &TokenId, now: i64) -> Balance {
+        self.vesting
+            .get(token_id)
+            .map(|locks| locks.iter().map(|lock| lock.locked_at(now)).sum())
+            .unwrap_or(0)
+    }
+
+    /// Total, liquid, locked and frozen amounts at `now`
+    pub fn balance_breakdown(&self, token_id: &TokenId, now: i64) -> BalanceBreakdown {
         let total = self.balances.get(token_id).copied().unwrap_or(0);
         let frozen = self.frozen.get(token_id).copied().unwrap_or(0);
-        total.saturating_sub(frozen)
+        let locked = self.locked_balance(token_id, now);
+        BalanceBreakdown {
+            total,
+            liquid: total.saturating_sub(frozen).saturating_sub(locked),
+            locked,
+            frozen,
+        }
     }
 
     /// Check if account has sufficient balance
@@ -400,6 +531,231 @@ pub struct OperationResult {
     pub timestamp: i64,
 }
 
+/// Default interval between periodic settlements: 1 hour
+pub const DEFAULT_SETTLEMENT_INTERVAL_SECS: u64 = 3_600;
+
+/// Account in the double-entry journal
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum LedgerAccountId {
+    /// Balance held by a node
+    Holder([u8; 32]),
+    /// Source of newly issued credits; its position is minus the amount issued
+    Issuance,
+    /// Sink for spent (burned) credits
+    Retired,
+}
+
+impl LedgerAccountId {
+    /// Fixed-width encoding: tag byte, then the owner (zeroes if none)
+    pub fn to_bytes(&self) -> [u8; 33] {
+        let mut bytes = [0u8; 33];
+        match self {
+            LedgerAccountId::Holder(owner) => bytes[1..].copy_from_slice(owner),
+            LedgerAccountId::Issuance => bytes[0] = 1,
+            LedgerAccountId::Retired => bytes[0] = 2,
+        }
+        bytes
+    }
+}
+
+/// Kind of journal entry
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EntryKind {
+    Issuance,
+    Transfer,
+    Spend,
+}
+
+impl EntryKind {
+    fn tag(&self) -> u8 {
+        match self {
+            EntryKind::Issuance => 0,
+            EntryKind::Transfer => 1,
+            EntryKind::Spend => 2,
+        }
+    }
+}
+
+/// One balanced posting: `amount` moves from `debit` to `credit`
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JournalEntry {
+    /// Hash of the entry contents
+    pub id: [u8; 32],
+
+    /// Position in the journal
+    pub sequence: u64,
+
+    pub token_id: TokenId,
+    pub kind: EntryKind,
+    pub debit: LedgerAccountId,
+    pub credit: LedgerAccountId,
+    pub amount: Balance,
+    pub timestamp: i64,
+}
+
+impl JournalEntry {
+    /// Hash of every field except `id`
+    pub fn compute_id(&self) -> [u8; 32] {
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(b"RDC_JOURNAL_ENTRY");
+        hasher.update(&self.sequence.to_le_bytes());
+        hasher.update(&self.token_id);
+        hasher.update(&[self.kind.tag()]);
+        hasher.update(&self.debit.to_bytes());
+        hasher.update(&self.credit.to_bytes());
+        hasher.update(&self.amount.to_le_bytes());
+        hasher.update(&self.timestamp.to_le_bytes());
+        *hasher.finalize().as_bytes()
+    }
+}
+
+/// Net positions of journal accounts for one token
+///
+/// Credits count positive, debits negative.
+pub fn journal_positions<'a>(
+    entries: impl IntoIterator<Item = &'a JournalEntry>,
+    token_id: &TokenId,
+) -> BTreeMap<LedgerAccountId, i128> {
+    let mut positions = BTreeMap::new();
+    for entry in entries.into_iter().filter(|e| e.token_id == *token_id) {
+        *positions.entry(entry.debit).or_insert(0i128) -= entry.amount as i128;
+        *positions.entry(entry.credit).or_insert(0i128) += entry.amount as i128;
+    }
+    positions
+}
+
+/// Settlement of a range of journal entries for one token
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SettlementRecord {
+    /// Settlement sequence number, counted across all tokens
+    pub sequence: u64,
+
+    pub token_id: TokenId,
+
+    /// Journal sequence of the first settled entry
+    pub first_entry: u64,
+
+    /// Journal sequence of the last settled entry
+    pub last_entry: u64,
+
+    /// Number of entries for this token in the range
+    pub entry_count: u64,
+
+    /// Hash chain over the settled entry IDs
+    pub journal_root: [u8; 32],
+
+    /// Net position change of every account touched
+    pub net_positions: Vec<(LedgerAccountId, i128)>,
+
+    pub settled_at: i64,
+}
+
+impl SettlementRecord {
+    /// String content: tag, then fixed-width little-endian fields
+    pub fn payload(&self) -> Vec<u8> {
+        let mut payload = Vec::new();
+        payload.extend_from_slice(b"RDC_SETTLEMENT");
+        payload.extend_from_slice(&self.sequence.to_le_bytes());
+        payload.extend_from_slice(&self.token_id);
+        payload.extend_from_slice(&self.first_entry.to_le_bytes());
+        payload.extend_from_slice(&self.last_entry.to_le_bytes());
+        payload.extend_from_slice(&self.entry_count.to_le_bytes());
+        payload.extend_from_slice(&self.journal_root);
+        payload.extend_from_slice(&(self.net_positions.len() as u32).to_le_bytes());
+        for (account, delta) in &self.net_positions {
+            payload.extend_from_slice(&account.to_bytes());
+            payload.extend_from_slice(&delta.to_le_bytes());
+        }
+        payload.extend_from_slice(&self.settled_at.to_le_bytes());
+        payload
+    }
+
+    /// Immutable string for this settlement
+    pub fn to_rope_string(
+        &self,
+        creator: PublicKey,
+        clock: LamportClock,
+    ) -> Result<RopeString, &'static str> {
+        RopeString::builder()
+            .content(self.payload())
+            .temporal_marker(clock)
+            .mutability_class(MutabilityClass::Immutable)
+            .creator(creator)
+            .build()
+    }
+}
+
+/// Holder whose balance disagrees with its journal position
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BalanceMismatch {
+    pub account: [u8; 32],
+    /// Position according to the journal
+    pub journal: i128,
+    /// Balance held by the account
+    pub actual: Balance,
+}
+
+/// Journal replayed against account balances for one token
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ReconciliationReport {
+    pub token_id: TokenId,
+
+    /// Total supply recorded on the token
+    pub total_supply: Balance,
+
+    /// Sum of all account balances
+    pub holder_total: Balance,
+
+    /// Sum of all holder positions in the journal
+    pub journal_holder_total: i128,
+
+    /// Credits issued according to the journal
+    pub journal_issued: Balance,
+
+    /// Credits spent according to the journal
+    pub journal_retired: Balance,
+
+    /// Supply not held by any account (e.g. unallocated genesis supply)
+    pub unallocated_supply: Balance,
+
+    /// Sum of all journal positions; anything but zero is a broken posting
+    pub imbalance: i128,
+
+    /// Holders whose balance drifted from the journal
+    pub mismatches: Vec<BalanceMismatch>,
+
+    pub generated_at: i64,
+}
+
+impl ReconciliationReport {
+    /// Journal balances and every holder matches it
+    pub fn is_reconciled(&self) -> bool {
+        self.imbalance == 0
+            && self.mismatches.is_empty()
+            && self.journal_holder_total == self.holder_total as i128
+    }
+}
+
+/// Transfer applied under an idempotency key
+struct IdempotentTransfer {
+    /// Hash of the transfer parameters
+    request_hash: [u8; 32],
+    result: OperationResult,
+}
+
+/// Slot for one idempotency key, locked while its transfer runs
+type IdempotencySlot = Arc<Mutex<Option<IdempotentTransfer>>>;
+
+/// Settlement progress over the journal
+struct SettlementState {
+    /// Journal entries before this index are settled
+    settled_through: usize,
+    next_sequence: u64,
+    last_settled_at: i64,
+    /// Records not yet written to the lattice
+    pending: Vec<SettlementRecord>,
+}
+
 /// Digital Credits Ledger
 pub struct CreditsLedger {
     /// All registered tokens
@@ -413,6 +769,17 @@ pub struct CreditsLedger {
 
     /// Total value locked
     total_value_locked: RwLock<HashMap<TokenId, Balance>>,
+
+    /// Double-entry journal of every balance change
+    journal: RwLock<Vec<JournalEntry>>,
+
+    /// Transfers by idempotency key, each behind its own lock
+    idempotent_transfers: RwLock<HashMap<[u8; 32], IdempotencySlot>>,
+
+    settlement: RwLock<SettlementState>,
+
+    /// Minimum time between periodic settlements
+    settlement_interval_secs: u64,
 }
 
 impl CreditsLedger {
@@ -465,9 +832,24 @@ impl CreditsLedger {
             accounts: RwLock::new(HashMap::new()),
             history: RwLock::new(Vec::new()),
             total_value_locked: RwLock::new(HashMap::new()),
+            journal: RwLock::new(Vec::new()),
+            idempotent_transfers: RwLock::new(HashMap::new()),
+            settlement: RwLock::new(SettlementState {
+                settled_through: 0,
+                next_sequence: 0,
+                last_settled_at: chrono::Utc::now().timestamp(),
+                pending: Vec::new(),
+            }),
+            settlement_interval_secs: DEFAULT_SETTLEMENT_INTERVAL_SECS,
         }
     }
 
+    /// Settle every `interval_secs` instead of the default hour
+    pub fn with_settlement_interval(mut self, interval_secs: u64) -> Self {
+        self.settlement_interval_secs = interval_secs;
+        self
+    }
+
     /// Create a new custom token
     pub fn create_token(
         &self,
@@ -523,6 +905,13 @@ impl CreditsLedger {
         // Credit initial supply to creator
         if initial_supply > 0 {
             self.credit_account(&creator, &token_id, initial_supply)?;
+            self.post(
+                EntryKind::Issuance,
+                &token_id,
+                LedgerAccountId::Issuance,
+                LedgerAccountId::Holder(creator),
+                initial_supply,
+            );
         }
 
         Ok(token_id)
@@ -610,6 +999,13 @@ impl CreditsLedger {
 
         // Credit to account
         self.credit_account(to, token_id, amount)?;
+        self.post(
+            EntryKind::Issuance,
+            token_id,
+            LedgerAccountId::Issuance,
+            LedgerAccountId::Holder(*to),
+            amount,
+        );
 
         let operation_id =
             *blake3::hash(&[to.as_slice(), token_id.as_slice(), &amount.to_le_bytes()].concat())
@@ -657,12 +1053,20 @@ impl CreditsLedger {
     ) -> Result<OperationResult, LedgerError> {
         // Debit from account
         self.debit_account(from, token_id, amount)?;
+        self.post(
+            EntryKind::Spend,
+            token_id,
+            LedgerAccountId::Holder(*from),
+            LedgerAccountId::Retired,
+            amount,
+        );
 
         // Update total supply
         let mut tokens = self.tokens.write();
         if let Some(token) = tokens.get_mut(token_id) {
             token.total_supply = token.total_supply.saturating_sub(amount);
         }
+        drop(tokens);
 
         let operation_id = *blake3::hash(
             &[
@@ -705,11 +1109,34 @@ impl CreditsLedger {
         }
         drop(tokens);
 
-        // Debit from sender
-        self.debit_account(from, token_id, amount)?;
-
-        // Credit to receiver
-        self.credit_account(to, token_id, amount)?;
+        // Both balances and the journal entry change under one lock so no
+        // reader sees half a transfer (journal before accounts, as in
+        // `reconcile`)
+        let mut journal = self.journal.write();
+        let mut accounts = self.accounts.write();
+        let sender = accounts.get_mut(from).ok_or(LedgerError::AccountNotFound)?;
+        ensure_liquid(sender, token_id, amount)?;
+        *sender.balances.entry(*token_id).or_insert(0) -= amount;
+        *accounts
+            .entry(*to)
+            .or_insert_with(|| Account::new(*to))
+            .balances
+            .entry(*token_id)
+            .or_insert(0) += amount;
+        let new_balance = accounts
+            .get(from)
+            .and_then(|acc| acc.balances.get(token_id).copied())
+            .unwrap_or(0);
+        append_entry(
+            &mut journal,
+            EntryKind::Transfer,
+            token_id,
+            LedgerAccountId::Holder(*from),
+            LedgerAccountId::Holder(*to),
+            amount,
+        );
+        drop(accounts);
+        drop(journal);
 
         let operation_id = *blake3::hash(
             &[
@@ -725,7 +1152,7 @@ impl CreditsLedger {
         let result = OperationResult {
             operation_id,
             success: true,
-            new_balance: Some(self.balance_of(from, token_id)),
+            new_balance: Some(new_balance),
             string_id: None,
             error: None,
             timestamp: chrono::Utc::now().timestamp(),
@@ -736,6 +1163,55 @@ impl CreditsLedger {
         Ok(result)
     }
 
+    /// Transfer tokens at most once per idempotency `key`
+    ///
+    /// Retrying with the same key and parameters returns the original
+    /// result without moving funds again; reusing a key for a different
+    /// transfer fails. Failed transfers don't consume the key.
+    pub fn transfer_idempotent(
+        &self,
+        key: [u8; 32],
+        token_id: &TokenId,
+        from: &[u8; 32],
+        to: &[u8; 32],
+        amount: Balance,
+    ) -> Result<OperationResult, LedgerError> {
+        let request_hash = *blake3::hash(
+            &[
+                token_id.as_slice(),
+                from.as_slice(),
+                to.as_slice(),
+                &amount.to_le_bytes(),
+            ]
+            .concat(),
+        )
+        .as_bytes();
+
+        // The key's lock is held across the transfer so concurrent retries
+        // can't both apply; other keys proceed in parallel
+        let slot = self
+            .idempotent_transfers
+            .write()
+            .entry(key)
+            .or_default()
+            .clone();
+        let mut slot = slot.lock();
+        if let Some(existing) = slot.as_ref() {
+            if existing.request_hash != request_hash {
+                return Err(LedgerError::IdempotencyConflict);
+            }
+            return Ok(existing.result.clone());
+        }
+
+        let result = self.transfer(token_id, from, to, amount)?;
+        *slot = Some(IdempotentTransfer {
+            request_hash,
+            result: result.clone(),
+        });
+
+        Ok(result)
+    }
+
     /// Freeze tokens in an account
     pub fn freeze(
         &self,
@@ -748,10 +1224,7 @@ impl CreditsLedger {
             .get_mut(account)
             .ok_or(LedgerError::AccountNotFound)?;
 
-        let available = acc.available_balance(token_id);
-        if available < amount {
-            return Err(LedgerError::InsufficientBalance);
-        }
+        ensure_liquid(acc, token_id, amount)?;
 
         *acc.frozen.entry(*token_id).or_insert(0) += amount;
 
@@ -780,6 +1253,169 @@ impl CreditsLedger {
         Ok(())
     }
 
+    /// Release a frozen hold and pay `amount` of it to `to` in one step
+    ///
+    /// Used to settle fees reserved before the work they pay for; the rest
+    /// of the hold goes back to the account's liquid balance.
+    pub fn settle_hold(
+        &self,
+        token_id: &TokenId,
+        account: &[u8; 32],
+        held: Balance,
+        amount: Balance,
+        to: &[u8; 32],
+    ) -> Result<OperationResult, LedgerError> {
+        if amount > held {
+            return Err(LedgerError::InvalidAmount);
+        }
+
+        let mut accounts = self.accounts.write();
+        let acc = accounts
+            .get_mut(account)
+            .ok_or(LedgerError::AccountNotFound)?;
+        let frozen = acc.frozen.entry(*token_id).or_insert(0);
+        if *frozen < held {
+            return Err(LedgerError::InsufficientFrozen);
+        }
+        *frozen -= held;
+        // The hold was part of the balance, so this can't underflow
+        *acc.balances.entry(*token_id).or_insert(0) -= amount;
+        let new_balance = acc.balances.get(token_id).copied().unwrap_or(0);
+
+        *accounts
+            .entry(*to)
+            .or_insert_with(|| Account::new(*to))
+            .balances
+            .entry(*token_id)
+            .or_insert(0) += amount;
+        drop(accounts);
+
+        if amount > 0 {
+            self.post(
+                EntryKind::Transfer,
+                token_id,
+                LedgerAccountId::Holder(*account),
+                LedgerAccountId::Holder(*to),
+                amount,
+            );
+        }
+
+        let operation_id = *blake3::hash(
+            &[
+                account.as_slice(),
+                to.as_slice(),
+                token_id.as_slice(),
+                &amount.to_le_bytes(),
+                b"hold",
+            ]
+            .concat(),
+        )
+        .as_bytes();
+
+        let result = OperationResult {
+            operation_id,
+            success: true,
+            new_balance: Some(new_balance),
+            string_id: None,
+            error: None,
+            timestamp: chrono::Utc::now().timestamp(),
+        };
+
+        self.history.write().push(result.clone());
+
+        Ok(result)
+    }
+
+    /// Bond DC FAT for staking
+    ///
+    /// Only liquid FAT can be bonded; vesting and frozen funds can't.
+    pub fn bond(&self, account: &[u8; 32], amount: Balance) -> Result<(), LedgerError> {
+        self.freeze(&DC_FAT_TOKEN_ID, account, amount)
+    }
+
+    /// Release bonded DC FAT
+    pub fn unbond(&self, account: &[u8; 32], amount: Balance) -> Result<(), LedgerError> {
+        self.unfreeze(&DC_FAT_TOKEN_ID, account, amount)
+    }
+
+    /// Lock part of an account's existing balance under `lock`
+    ///
+    /// Used for genesis allocations, which are credited before their
+    /// schedules are applied.
+    pub fn lock_vesting(
+        &self,
+        token_id: &TokenId,
+        account: &[u8; 32],
+        lock: VestingLock,
+    ) -> Result<(), LedgerError> {
+        if lock.amount == 0 {
+            return Err(LedgerError::InvalidAmount);
+        }
+
+        let mut accounts = self.accounts.write();
+        let acc = accounts
+            .get_mut(account)
+            .ok_or(LedgerError::AccountNotFound)?;
+
+        ensure_liquid(acc, token_id, lock.amount)?;
+
+        let now = chrono::Utc::now().timestamp();
+        let locks = acc.vesting.entry(*token_id).or_default();
+        locks.retain(|existing| !existing.is_complete(now));
+        locks.push(lock);
+
+        Ok(())
+    }
+
+    /// Transfer `lock.amount` from `from` to `to`, locked in the recipient
+    /// account under `lock` (e.g. a project grant)
+    pub fn transfer_vested(
+        &self,
+        token_id: &TokenId,
+        from: &[u8; 32],
+        to: &[u8; 32],
+        lock: VestingLock,
+    ) -> Result<OperationResult, LedgerError> {
+        if lock.amount == 0 {
+            return Err(LedgerError::InvalidAmount);
+        }
+
+        let result = self.transfer(token_id, from, to, lock.amount)?;
+        self.accounts
+            .write()
+            .get_mut(to)
+            .ok_or(LedgerError::AccountNotFound)?
+            .vesting
+            .entry(*token_id)
+            .or_default()
+            .push(lock);
+
+        Ok(result)
+    }
+
+    /// Total, liquid, locked and frozen balance of an account at `now`
+    pub fn balance_breakdown(
+        &self,
+        account: &[u8; 32],
+        token_id: &TokenId,
+        now: i64,
+    ) -> BalanceBreakdown {
+        self.accounts
+            .read()
+            .get(account)
+            .map(|acc| acc.balance_breakdown(token_id, now))
+            .unwrap_or_default()
+    }
+
+    /// Vesting locks of an account
+    pub fn vesting_of(&self, account: &[u8; 32], token_id: &TokenId) -> Vec<VestingLock> {
+        self.accounts
+            .read()
+            .get(account)
+            .and_then(|acc| acc.vesting.get(token_id).cloned())
+            .unwrap_or_default()
+    }
+
     /// Get balance of an account
     pub fn balance_of(&self, account: &[u8; 32], token_id: &TokenId) -> Balance {
         self.accounts
@@ -808,8 +1444,164 @@ impl CreditsLedger {
             .unwrap_or(0)
     }
 
+    /// Journal entries from `from` onwards
+    pub fn journal_entries(&self, from: u64) -> Vec<JournalEntry> {
+        self.journal
+            .read()
+            .iter()
+            .skip(from as usize)
+            .cloned()
+            .collect()
+    }
+
+    /// Journal position of an account (credits minus debits)
+    pub fn journal_position(&self, account: &LedgerAccountId, token_id: &TokenId) -> i128 {
+        journal_positions(self.journal.read().iter(), token_id)
+            .get(account)
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Replay the journal against account balances for `token_id`
+    pub fn reconcile(&self, token_id: &TokenId) -> ReconciliationReport {
+        let total_supply = self.total_supply(token_id);
+        let journal = self.journal.read();
+        let accounts = self.accounts.read();
+        let positions = journal_positions(journal.iter(), token_id);
+
+        let mut mismatches = Vec::new();
+        let mut holder_total: Balance = 0;
+        for (owner, acc) in accounts.iter() {
+            let actual = acc.balances.get(token_id).copied().unwrap_or(0);
+            holder_total += actual;
+            let expected = positions
+                .get(&LedgerAccountId::Holder(*owner))
+                .copied()
+                .unwrap_or(0);
+            if expected != actual as i128 {
+                mismatches.push(BalanceMismatch {
+                    account: *owner,
+                    journal: expected,
+                    actual,
+                });
+            }
+        }
+
+        let mut journal_holder_total = 0i128;
+        for (account, position) in &positions {
+            if let LedgerAccountId::Holder(owner) = account {
+                journal_holder_total += position;
+                if *position != 0 && !accounts.contains_key(owner) {
+                    mismatches.push(BalanceMismatch {
+                        account: *owner,
+                        journal: *position,
+                        actual: 0,
+                    });
+                }
+            }
+        }
+        mismatches.sort_by_key(|m| m.account);
+
+        let position_of = |account| positions.get(&account).copied().unwrap_or(0);
+
+        ReconciliationReport {
+            token_id: *token_id,
+            total_supply,
+            holder_total,
+            journal_holder_total,
+            journal_issued: position_of(LedgerAccountId::Issuance).unsigned_abs(),
+            journal_retired: position_of(LedgerAccountId::Retired).unsigned_abs(),
+            unallocated_supply: total_supply.saturating_sub(holder_total),
+            imbalance: positions.values().sum(),
+            mismatches,
+            generated_at: chrono::Utc::now().timestamp(),
+        }
+    }
+
+    /// Settle all unsettled journal entries, one record per token
+    ///
+    /// Returns the new records; they are also queued for
+    /// [`take_settlements`](Self::take_settlements).
+    pub fn settle(&self, now: i64) -> Vec<SettlementRecord> {
+        let journal = self.journal.read();
+        let mut state = self.settlement.write();
+
+        let unsettled = &journal[state.settled_through..];
+        state.last_settled_at = now;
+        if unsettled.is_empty() {
+            return Vec::new();
+        }
+
+        let mut by_token: BTreeMap<TokenId, Vec<&JournalEntry>> = BTreeMap::new();
+        for entry in unsettled {
+            by_token.entry(entry.token_id).or_default().push(entry);
+        }
+
+        let mut records = Vec::new();
+        for (token_id, entries) in by_token {
+            let mut chain = blake3::Hasher::new();
+            for entry in &entries {
+                chain.update(&entry.id);
+            }
+            let net_positions = journal_positions(entries.iter().copied(), &token_id)
+                .into_iter()
+                .filter(|(_, delta)| *delta != 0)
+                .collect();
+
+            records.push(SettlementRecord {
+                sequence: state.next_sequence,
+                token_id,
+                first_entry: entries[0].sequence,
+                last_entry: entries[entries.len() - 1].sequence,
+                entry_count: entries.len() as u64,
+                journal_root: *chain.finalize().as_bytes(),
+                net_positions,
+                settled_at: now,
+            });
+            state.next_sequence += 1;
+        }
+
+        state.settled_through = journal.len();
+        state.pending.extend(records.iter().cloned());
+
+        records
+    }
+
+    /// Settle if the settlement interval has elapsed since the last one
+    pub fn settle_if_due(&self, now: i64) -> Vec<SettlementRecord> {
+        let last = self.settlement.read().last_settled_at;
+        if now.saturating_sub(last) < self.settlement_interval_secs as i64 {
+            return Vec::new();
+        }
+        self.settle(now)
+    }
+
+    /// Drain settlements waiting to be written to the lattice
+    pub fn take_settlements(&self) -> Vec<SettlementRecord> {
+        std::mem::take(&mut self.settlement.write().pending)
+    }
+
     // === Internal helpers ===
 
+    /// Append a balanced entry to the journal
+    fn post(
+        &self,
+        kind: EntryKind,
+        token_id: &TokenId,
+        debit: LedgerAccountId,
+        credit: LedgerAccountId,
+        amount: Balance,
+    ) {
+        append_entry(
+            &mut self.journal.write(),
+            kind,
+            token_id,
+            debit,
+            credit,
+            amount,
+        );
+    }
+
     fn credit_account(
         &self,
         account: &[u8; 32],
@@ -835,15 +1627,48 @@ impl CreditsLedger {
             .get_mut(account)
             .ok_or(LedgerError::AccountNotFound)?;
 
-        if acc.available_balance(token_id) < amount {
-            return Err(LedgerError::InsufficientBalance);
-        }
+        ensure_liquid(acc, token_id, amount)?;
 
         *acc.balances.get_mut(token_id).unwrap() -= amount;
         Ok(())
     }
 }
 
+/// Append a balanced entry to a locked journal
+fn append_entry(
+    journal: &mut Vec<JournalEntry>,
+    kind: EntryKind,
+    token_id: &TokenId,
+    debit: LedgerAccountId,
+    credit: LedgerAccountId,
+    amount: Balance,
+) {
+    let mut entry = JournalEntry {
+        id: [0u8; 32],
+        sequence: journal.len() as u64,
+        token_id: *token_id,
+        kind,
+        debit,
+        credit,
+        amount,
+        timestamp: chrono::Utc::now().timestamp(),
+    };
+    entry.id = entry.compute_id();
+    journal.push(entry);
+}
+
+/// Fail unless `amount` of the account's balance is liquid now
+fn ensure_liquid(acc: &Account, token_id: &TokenId, amount: Balance) -> Result<(), LedgerError> {
+    let breakdown = acc.balance_breakdown(token_id, chrono::Utc::now().timestamp());
+    if breakdown.liquid >= amount {
+        Ok(())
+    } else if breakdown.total.saturating_sub(breakdown.frozen) >= amount {
+        Err(LedgerError::VestingLocked)
+    } else {
+        Err(LedgerError::InsufficientBalance)
+    }
+}
+
 impl Default for CreditsLedger {
     fn default() -> Self {
         Self::new()
@@ -868,6 +1693,10 @@ pub enum LedgerError {
     RateLimitExceeded,
     /// DC FAT minting requires governance approval (12 approvals)
     GovernanceRequired,
+    /// Funds are held by a vesting lock
+    VestingLocked,
+    /// Idempotency key was already used for a different transfer
+    IdempotencyConflict,
 }
 
 impl std::fmt::Display for LedgerError {
@@ -887,6 +1716,10 @@ impl std::fmt::Display for LedgerError {
             LedgerError::InvalidAmount => write!(f, "Invalid amount"),
             LedgerError::RateLimitExceeded => write!(f, "Rate limit exceeded"),
             LedgerError::GovernanceRequired => write!(f, "DC FAT minting requires governance approval (12 approvals: 5 AI + 5 governors + 2 foundation)"),
+            LedgerError::VestingLocked => write!(f, "Balance is locked by a vesting schedule"),
+            LedgerError::IdempotencyConflict => {
+                write!(f, "Idempotency key already used for a different transfer")
+            }
         }
     }
 }
@@ -1085,4 +1918,279 @@ mod tests {
         // Now can transfer
         ledger.transfer(&token_id, &owner, &[2u8; 32], 600).unwrap();
     }
+
+    fn fat_account(ledger: &CreditsLedger, owner: [u8; 32], amount: Balance) {
+        ledger
+            .mint(&DC_FAT_TOKEN_ID, &owner, amount, &owner, true)
+            .unwrap();
+    }
+
+    #[test]
+    fn test_vesting_lock_release() {
+        let lock = VestingLock::new(1_000, 100, 50, 200);
+
+        // Nothing before the cliff
+        assert_eq!(lock.vested_at(0), 0);
+        assert_eq!(lock.vested_at(149), 0);
+        // Linear from the start once past the cliff
+        assert_eq!(lock.vested_at(150), 250);
+        assert_eq!(lock.locked_at(200), 500);
+        assert_eq!(lock.vested_at(300), 1_000);
+        assert!(lock.is_complete(1_000));
+    }
+
+    #[test]
+    fn test_vesting_blocks_transfers() {
+        let ledger = CreditsLedger::new();
+        let owner = [1u8; 32];
+        fat_account(&ledger, owner, 1_000);
+
+        let now = chrono::Utc::now().timestamp();
+        ledger
+            .lock_vesting(
+                &DC_FAT_TOKEN_ID,
+                &owner,
+                VestingLock::new(800, now, 3_600, 7_200),
+            )
+            .unwrap();
+
+        let breakdown = ledger.balance_breakdown(&owner, &DC_FAT_TOKEN_ID, now);
+        assert_eq!(breakdown.total, 1_000);
+        assert_eq!(breakdown.liquid, 200);
+        assert_eq!(breakdown.locked, 800);
+
+        // Later the lock has released
+        let later = ledger.balance_breakdown(&owner, &DC_FAT_TOKEN_ID, now + 7_200);
+        assert_eq!(later.liquid, 1_000);
+
+        let result = ledger.transfer(&DC_FAT_TOKEN_ID, &owner, &[2u8; 32], 500);
+        assert_eq!(result.unwrap_err(), LedgerError::VestingLocked);
+        let result = ledger.burn(&DC_FAT_TOKEN_ID, &owner, 500);
+        assert_eq!(result.unwrap_err(), LedgerError::VestingLocked);
+        let result = ledger.transfer(&DC_FAT_TOKEN_ID, &owner, &[2u8; 32], 1_500);
+        assert_eq!(result.unwrap_err(), LedgerError::InsufficientBalance);
+
+        ledger
+            .transfer(&DC_FAT_TOKEN_ID, &owner, &[2u8; 32], 200)
+            .unwrap();
+    }
+
+    #[test]
+    fn test_vesting_blocks_bonding() {
+        let ledger = CreditsLedger::new();
+        let owner = [1u8; 32];
+        fat_account(&ledger, owner, 1_000);
+
+        let now = chrono::Utc::now().timestamp();
+        ledger
+            .lock_vesting(
+                &DC_FAT_TOKEN_ID,
+                &owner,
+                VestingLock::new(600, now, 3_600, 7_200),
+            )
+            .unwrap();
+
+        assert_eq!(ledger.bond(&owner, 500), Err(LedgerError::VestingLocked));
+        ledger.bond(&owner, 400).unwrap();
+
+        let breakdown = ledger.balance_breakdown(&owner, &DC_FAT_TOKEN_ID, now);
+        assert_eq!(breakdown.frozen, 400);
+        assert_eq!(breakdown.liquid, 0);
+
+        ledger.unbond(&owner, 400).unwrap();
+        assert_eq!(
+            ledger
+                .balance_breakdown(&owner, &DC_FAT_TOKEN_ID, now)
+                .liquid,
+            400
+        );
+    }
+
+    #[test]
+    fn test_transfer_vested_grant() {
+        let ledger = CreditsLedger::new();
+        let treasury = [1u8; 32];
+        let project = [2u8; 32];
+        fat_account(&ledger, treasury, 1_000);
+
+        let now = chrono::Utc::now().timestamp();
+        ledger
+            .transfer_vested(
+                &DC_FAT_TOKEN_ID,
+                &treasury,
+                &project,
+                VestingLock::new(1_000, now, 0, 1_000),
+            )
+            .unwrap();
+
+        assert_eq!(ledger.balance_of(&project, &DC_FAT_TOKEN_ID), 1_000);
+        assert_eq!(ledger.vesting_of(&project, &DC_FAT_TOKEN_ID).len(), 1);
+
+        let halfway = ledger.balance_breakdown(&project, &DC_FAT_TOKEN_ID, now + 500);
+        assert_eq!(halfway.liquid, 500);
+        assert_eq!(halfway.locked, 500);
+    }
+
+    #[test]
+    fn test_journal_stays_balanced() {
+        let ledger = CreditsLedger::new();
+        let alice = [1u8; 32];
+        let bob = [2u8; 32];
+        fat_account(&ledger, alice, 1_000);
+
+        ledger
+            .transfer(&DC_FAT_TOKEN_ID, &alice, &bob, 300)
+            .unwrap();
+        ledger.burn(&DC_FAT_TOKEN_ID, &bob, 100).unwrap();
+
+        let entries = ledger.journal_entries(0);
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0].kind, EntryKind::Issuance);
+        assert_eq!(entries[2].credit, LedgerAccountId::Retired);
+        assert!(entries.iter().all(|e| e.id == e.compute_id()));
+
+        let holder =
+            |owner| ledger.journal_position(&LedgerAccountId::Holder(owner), &DC_FAT_TOKEN_ID);
+        assert_eq!(holder(alice), 700);
+        assert_eq!(holder(bob), 200);
+
+        let report = ledger.reconcile(&DC_FAT_TOKEN_ID);
+        assert!(report.is_reconciled());
+        assert_eq!(report.journal_issued, 1_000);
+        assert_eq!(report.journal_retired, 100);
+        assert_eq!(report.holder_total, 900);
+        // Genesis supply isn't held by any account
+        assert_eq!(report.unallocated_supply, report.total_supply - 900);
+    }
+
+    #[test]
+    fn test_reconcile_detects_drift() {
+        let ledger = CreditsLedger::new();
+        let alice = [1u8; 32];
+        fat_account(&ledger, alice, 1_000);
+
+        // Balance changed without a posting
+        *ledger
+            .accounts
+            .write()
+            .get_mut(&alice)
+            .unwrap()
+            .balances
+            .get_mut(&DC_FAT_TOKEN_ID)
+            .unwrap() += 50;
+
+        let report = ledger.reconcile(&DC_FAT_TOKEN_ID);
+        assert!(!report.is_reconciled());
+        assert_eq!(report.imbalance, 0);
+        assert_eq!(
+            report.mismatches,
+            vec![BalanceMismatch {
+                account: alice,
+                journal: 1_000,
+                actual: 1_050,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_idempotent_transfer() {
+        let ledger = CreditsLedger::new();
+        let alice = [1u8; 32];
+        let bob = [2u8; 32];
+        fat_account(&ledger, alice, 1_000);
+
+        let key = [9u8; 32];
+        let first = ledger
+            .transfer_idempotent(key, &DC_FAT_TOKEN_ID, &alice, &bob, 300)
+            .unwrap();
+        let retry = ledger
+            .transfer_idempotent(key, &DC_FAT_TOKEN_ID, &alice, &bob, 300)
+            .unwrap();
+        assert_eq!(first.operation_id, retry.operation_id);
+        assert_eq!(ledger.balance_of(&bob, &DC_FAT_TOKEN_ID), 300);
+        assert_eq!(ledger.journal_entries(0).len(), 2);
+
+        let result = ledger.transfer_idempotent(key, &DC_FAT_TOKEN_ID, &alice, &bob, 400);
+        assert_eq!(result.unwrap_err(), LedgerError::IdempotencyConflict);
+
+        // A failed transfer leaves the key unused
+        let other = [8u8; 32];
+        let result = ledger.transfer_idempotent(other, &DC_FAT_TOKEN_ID, &alice, &bob, 5_000);
+        assert_eq!(result.unwrap_err(), LedgerError::InsufficientBalance);
+        ledger
+            .transfer_idempotent(other, &DC_FAT_TOKEN_ID, &alice, &bob, 100)
+            .unwrap();
+    }
+
+    #[test]
+    fn test_concurrent_transfers_stay_reconciled() {
+        let ledger = CreditsLedger::new();
+        let alice = [1u8; 32];
+        let bob = [2u8; 32];
+        fat_account(&ledger, alice, 1_000);
+
+        std::thread::scope(|scope| {
+            for i in 0..8u8 {
+                let ledger = &ledger;
+                scope.spawn(move || {
+                    // Every thread retries the shared key and uses one of its own
+                    for _ in 0..5 {
+                        ledger
+                            .transfer_idempotent([9u8; 32], &DC_FAT_TOKEN_ID, &alice, &bob, 100)
+                            .unwrap();
+                    }
+                    ledger
+                        .transfer_idempotent([i; 32], &DC_FAT_TOKEN_ID, &alice, &bob, 10)
+                        .unwrap();
+                    assert_eq!(ledger.reconcile(&DC_FAT_TOKEN_ID).imbalance, 0);
+                });
+            }
+        });
+
+        assert_eq!(ledger.balance_of(&bob, &DC_FAT_TOKEN_ID), 100 + 8 * 10);
+        assert!(ledger.reconcile(&DC_FAT_TOKEN_ID).is_reconciled());
+    }
+
+    #[test]
+    fn test_settlement_records() {
+        let ledger = CreditsLedger::new().with_settlement_interval(60);
+        let alice = [1u8; 32];
+        let bob = [2u8; 32];
+        fat_account(&ledger, alice, 1_000);
+        ledger
+            .transfer(&DC_FAT_TOKEN_ID, &alice, &bob, 250)
+            .unwrap();
+
+        let now = chrono::Utc::now().timestamp();
+        assert!(ledger.settle_if_due(now).is_empty());
+
+        let records = ledger.settle_if_due(now + 60);
+        assert_eq!(records.len(), 1);
+        let record = &records[0];
+        assert_eq!((record.first_entry, record.last_entry), (0, 1));
+        assert_eq!(record.entry_count, 2);
+        assert_eq!(
+            record.net_positions,
+            vec![
+                (LedgerAccountId::Holder(alice), 750),
+                (LedgerAccountId::Holder(bob), 250),
+                (LedgerAccountId::Issuance, -1_000),
+            ]
+        );
+
+        // Nothing new to settle
+        assert!(ledger.settle(now + 120).is_empty());
+
+        let pending = ledger.take_settlements();
+        assert_eq!(pending, records);
+        assert!(ledger.take_settlements().is_empty());
+
+        let node = rope_core::types::NodeId::new(alice);
+        let string = record
+            .to_rope_string(PublicKey::from_ed25519(alice), LamportClock::new(node))
+            .unwrap();
+        // The sequence pads the payload to whole nucleotides
+        assert!(string.content().starts_with(&record.payload()));
+        assert!(record.payload().starts_with(b"RDC_SETTLEMENT"));
+    }
 }
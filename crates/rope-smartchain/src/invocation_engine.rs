@@ -8,16 +8,32 @@
 //! 3. Aggregates testimonies and checks thresholds
 //! 4. Invokes vetted tools to execute actions
 //! 5. Records results in the String Lattice
+//!
+//! Before any of this, each action can be dry-run against a registered
+//! [`ToolSimulator`]. The simulation reports go to the testimony agents as
+//! evidence, and a predicted failure stops the invocation before anything
+//! real is touched. [`InvocationEngine::dry_run`] stops after simulation.
+//!
+//! With a [`FeeMeter`] attached, every tool call is metered and the caller
+//! pays for it in FAT: the maximum fee is held before the call, and the
+//! metered fee is charged in the same step that records the invocation.
 
 // Invocation engine for executing vetted tools
 use parking_lot::RwLock;
+use rope_core::types::{QuarantineList, QuarantineSubject};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::Arc;
 
+use super::metering::*;
 use super::security_policy::*;
+use super::simulation::*;
 use super::testimony_agent::*;
 use super::tool_registry::*;
+use super::tool_vetting::*;
+
+/// Tool action, the tool chosen to run it and the calling party
+type PreparedAction = (ToolAction, Arc<dyn VettedTool>, [u8; 32]);
 
 /// The main invocation engine
 pub struct InvocationEngine {
@@ -35,6 +51,21 @@ pub struct InvocationEngine {
 
     /// Completed invocations (for audit)
     completed: RwLock<Vec<InvocationRecord>>,
+
+    /// Security quarantine list
+    quarantine: Option<Arc<dyn QuarantineList>>,
+
+    /// Simulators for dry-running tool calls
+    simulators: RwLock<Vec<Arc<dyn ToolSimulator>>>,
+
+    /// Refuse actions no simulator can dry-run
+    require_simulation: bool,
+
+    /// Governance pins checked before every tool call
+    tool_governance: Option<Arc<VettedToolRegistry>>,
+
+    /// Charges callers for tool calls
+    fee_meter: Option<Arc<FeeMeter>>,
 }
 
 impl InvocationEngine {
@@ -45,15 +76,73 @@ impl InvocationEngine {
             security_policy,
             pending: RwLock::new(HashMap::new()),
             completed: RwLock::new(Vec::new()),
+            quarantine: None,
+            simulators: RwLock::new(Vec::new()),
+            require_simulation: false,
+            tool_governance: None,
+            fee_meter: None,
         }
     }
 
+    /// Refuse contracts whose ID or parties are quarantined
+    pub fn with_quarantine(mut self, quarantine: Arc<dyn QuarantineList>) -> Self {
+        self.quarantine = Some(quarantine);
+        self
+    }
+
+    /// Refuse to execute actions that no registered simulator can dry-run
+    pub fn with_required_simulation(mut self, required: bool) -> Self {
+        self.require_simulation = required;
+        self
+    }
+
+    /// Only invoke tools whose pinned, unrevoked version matches
+    pub fn with_tool_governance(mut self, governance: Arc<VettedToolRegistry>) -> Self {
+        self.tool_governance = Some(governance);
+        self
+    }
+
+    /// Meter tool calls and charge callers through `meter`
+    pub fn with_fee_meter(mut self, meter: Arc<FeeMeter>) -> Self {
+        self.fee_meter = Some(meter);
+        self
+    }
+
+    /// Register a simulator for dry-running tool calls
+    pub fn register_simulator(&self, simulator: Arc<dyn ToolSimulator>) {
+        self.simulators.write().push(simulator);
+    }
+
+    /// First quarantined subject involved in `contract`, if any
+    fn quarantined_subject(&self, contract: &DigitizedContract) -> Option<QuarantineSubject> {
+        let quarantine = self.quarantine.as_ref()?;
+        std::iter::once(contract.contract_id)
+            .chain(contract.parties.iter().map(|p| p.node_id))
+            .map(QuarantineSubject::Entity)
+            .find(|subject| quarantine.is_quarantined(subject))
+    }
+
     /// Register an AI testimony agent
     pub fn register_agent(&self, agent: Arc<dyn TestimonyAgent>) {
         let id = agent.agent_id().to_bytes();
+        tracing::info!("Registered testimony agent running {}", agent.model().key());
         self.agents.write().insert(id, agent);
     }
 
+    /// Remove a testimony agent, e.g. when its plugin is unloaded
+    pub fn unregister_agent(&self, agent_id: &AgentId) -> bool {
+        self.agents.write().remove(&agent_id.to_bytes()).is_some()
+    }
+
+    /// Models of the registered agents
+    pub fn agent_models(&self) -> Vec<ModelVersion> {
+        self.agents
+            .read()
+            .values()
+            .map(|agent| agent.model().clone())
+            .collect()
+    }
+
     /// Process a contract for execution
     pub async fn process_contract(
         &self,
@@ -61,7 +150,24 @@ impl InvocationEngine {
     ) -> Result<InvocationResult, InvocationError> {
         let invocation_id = *blake3::hash(&contract.contract_id).as_bytes();
 
-        // 1. Initialize invocation state
+        if let Some(subject) = self.quarantined_subject(contract) {
+            tracing::warn!("Refusing invocation: {} is quarantined", subject);
+            return Err(InvocationError::Quarantined(subject.to_string()));
+        }
+
+        // 1. Dry-run the actions before asking anyone to testify
+        let simulations = self.simulate_actions(contract).await?;
+        if let Some(failed) = simulations.iter().find(|r| !r.simulation.success) {
+            tracing::warn!(
+                "Simulation of action {} predicts failure",
+                hex::encode(failed.action_id)
+            );
+            return Err(InvocationError::SimulationFailed(
+                failed.simulation.error.clone().unwrap_or_default(),
+            ));
+        }
+
+        // 2. Initialize invocation state
         let state = InvocationState {
             invocation_id,
             contract_id: contract.contract_id,
@@ -73,17 +179,9 @@ impl InvocationEngine {
         };
         self.pending.write().insert(invocation_id, state);
 
-        // 2. Validate all conditions with AI agents
-        let mut all_conditions_met = true;
-        let mut condition_results = Vec::new();
-
-        for condition in &contract.conditions {
-            let result = self.validate_condition(condition, contract).await?;
-            if !result.satisfied {
-                all_conditions_met = false;
-            }
-            condition_results.push(result);
-        }
+        // 3. Validate all conditions with AI agents
+        let condition_results = self.validate_conditions(contract, &simulations).await?;
+        let all_conditions_met = condition_results.iter().all(|r| r.satisfied);
 
         // Update phase
         if let Some(state) = self.pending.write().get_mut(&invocation_id) {
@@ -94,16 +192,30 @@ impl InvocationEngine {
             };
         }
 
-        // 3. If conditions met, execute actions
+        // 4. If conditions met, execute actions
         let mut action_results = Vec::new();
+        let mut metered_calls = Vec::new();
         if all_conditions_met {
             for action in &contract.actions {
-                let result = self.execute_action(action, contract).await?;
-                action_results.push(result);
+                let simulation = simulations.iter().find(|r| r.action_id == action.id);
+                match self.execute_action(action, contract, simulation).await {
+                    Ok((result, metered)) => {
+                        action_results.push(result);
+                        metered_calls.extend(metered);
+                    }
+                    Err(e) => {
+                        if let Some(meter) = &self.fee_meter {
+                            for call in &metered_calls {
+                                meter.release(&call.hold);
+                            }
+                        }
+                        return Err(e);
+                    }
+                }
             }
         }
 
-        // 4. Finalize
+        // 5. Finalize
         let now = chrono::Utc::now().timestamp();
         let final_status = if all_conditions_met && action_results.iter().all(|r| r.success) {
             InvocationStatus::Completed
@@ -118,13 +230,17 @@ impl InvocationEngine {
             state.completed_at = Some(now);
         }
 
-        // Record for audit
+        // Charge fees and record for audit in one step
+        let mut completed = self.completed.write();
+        let fees = self.charge_fees(metered_calls);
         let record = InvocationRecord {
             invocation_id,
             contract_id: contract.contract_id,
             status: final_status.clone(),
             condition_results: condition_results.clone(),
             action_results: action_results.clone(),
+            simulations: simulations.clone(),
+            fees: fees.clone(),
             started_at: self
                 .pending
                 .read()
@@ -133,7 +249,8 @@ impl InvocationEngine {
                 .unwrap_or(now),
             completed_at: now,
         };
-        self.completed.write().push(record);
+        completed.push(record);
+        drop(completed);
 
         // Remove from pending
         self.pending.write().remove(&invocation_id);
@@ -143,14 +260,133 @@ impl InvocationEngine {
             status: final_status,
             condition_results,
             action_results,
+            simulations,
+            fees,
+        })
+    }
+
+    /// Settle the fee of every metered call
+    ///
+    /// Holds cover the charge, so settling only fails if the ledger was
+    /// changed behind the meter's back; such calls are logged and left out.
+    fn charge_fees(&self, calls: Vec<MeteredCall>) -> Vec<FeeCharge> {
+        let Some(meter) = &self.fee_meter else {
+            return Vec::new();
+        };
+        calls
+            .into_iter()
+            .filter_map(|call| {
+                let action_id = call.hold.action_id;
+                meter
+                    .charge(call)
+                    .map_err(|e| {
+                        tracing::error!(
+                            "Failed to charge fee for action {}: {}",
+                            hex::encode(action_id),
+                            e
+                        )
+                    })
+                    .ok()
+            })
+            .collect()
+    }
+
+    /// Simulate a contract's actions and validate its conditions without
+    /// executing anything
+    pub async fn dry_run(
+        &self,
+        contract: &DigitizedContract,
+    ) -> Result<InvocationResult, InvocationError> {
+        if let Some(subject) = self.quarantined_subject(contract) {
+            return Err(InvocationError::Quarantined(subject.to_string()));
+        }
+
+        let simulations = self.simulate_actions(contract).await?;
+        let condition_results = self.validate_conditions(contract, &simulations).await?;
+
+        let status = if !condition_results.iter().all(|r| r.satisfied) {
+            InvocationStatus::ConditionsNotMet
+        } else if !simulations.iter().all(|r| r.simulation.success) {
+            InvocationStatus::Failed
+        } else {
+            InvocationStatus::Simulated
+        };
+
+        Ok(InvocationResult {
+            invocation_id: *blake3::hash(&contract.contract_id).as_bytes(),
+            status,
+            condition_results,
+            action_results: Vec::new(),
+            simulations,
+            fees: Vec::new(),
         })
     }
 
+    /// Dry-run every action of `contract` on a matching simulator
+    async fn simulate_actions(
+        &self,
+        contract: &DigitizedContract,
+    ) -> Result<Vec<SimulationReport>, InvocationError> {
+        let mut reports = Vec::new();
+
+        for action in &contract.actions {
+            let (tool_action, tool, caller) = self.prepare_action(action, contract)?;
+            let simulator = self
+                .simulators
+                .read()
+                .iter()
+                .find(|s| s.can_simulate(tool.metadata(), &tool_action))
+                .cloned();
+
+            let Some(simulator) = simulator else {
+                if self.require_simulation {
+                    return Err(InvocationError::SimulationFailed(format!(
+                        "No simulator for {}",
+                        tool.tool_id().name
+                    )));
+                }
+                continue;
+            };
+
+            let context = Self::execution_context(caller);
+            let simulation = simulator.simulate(&tool_action, &context).await;
+            reports.push(SimulationReport {
+                action_id: action.id,
+                tool_id: tool.tool_id().clone(),
+                simulator: simulator.name().to_string(),
+                simulation,
+                simulated_at: context.timestamp,
+            });
+        }
+
+        Ok(reports)
+    }
+
+    /// Validate all conditions, attaching the simulation reports
+    async fn validate_conditions(
+        &self,
+        contract: &DigitizedContract,
+        simulations: &[SimulationReport],
+    ) -> Result<Vec<ValidationResult>, InvocationError> {
+        let mut results = Vec::new();
+        for condition in &contract.conditions {
+            let mut result = self
+                .validate_condition(condition, contract, simulations)
+                .await?;
+            result
+                .evidence
+                .extend(simulations.iter().map(SimulationReport::to_evidence));
+            results.push(result);
+        }
+        Ok(results)
+    }
+
     /// Validate a single condition using AI agents
     async fn validate_condition(
         &self,
         condition: &ContractCondition,
         contract: &DigitizedContract,
+        simulations: &[SimulationReport],
     ) -> Result<ValidationResult, InvocationError> {
         let agents = self.agents.read();
 
@@ -172,7 +408,15 @@ impl InvocationEngine {
                 .first()
                 .map(|p| p.node_id)
                 .unwrap_or([0u8; 32]),
-            historical_data: HashMap::new(),
+            historical_data: simulations
+                .iter()
+                .map(|r| {
+                    (
+                        format!("simulation:{}", hex::encode(r.action_id)),
+                        serde_json::to_vec(r).unwrap_or_default(),
+                    )
+                })
+                .collect(),
             oracle_data: HashMap::new(),
             risk_score: None,
         };
@@ -180,6 +424,7 @@ impl InvocationEngine {
         // Collect testimonies from all agents
         let mut approvals = 0u32;
         let mut total_confidence = 0.0f64;
+        let mut verdicts = Vec::new();
 
         for agent in &suitable_agents {
             let result = agent.validate_condition(condition, &context).await;
@@ -187,6 +432,12 @@ impl InvocationEngine {
                 approvals += 1;
                 total_confidence += result.confidence;
             }
+            verdicts.push(AgentVerdict {
+                agent_id: agent.agent_id().clone(),
+                model: agent.model().clone(),
+                satisfied: result.satisfied,
+                confidence: result.confidence,
+            });
         }
 
         let approval_rate = approvals as f64 / suitable_agents.len() as f64;
@@ -209,17 +460,70 @@ impl InvocationEngine {
                     condition.approval_threshold * 100.0
                 )
             },
-            evidence: Vec::new(),
+            evidence: verdicts.iter().map(AgentVerdict::to_evidence).collect(),
             signature: Vec::new(),
+            model: None,
         })
     }
 
     /// Execute a single action using vetted tools
+    ///
+    /// With metering, the call is returned with its fee hold and measured
+    /// usage for settlement.
     async fn execute_action(
         &self,
         action: &ContractAction,
         contract: &DigitizedContract,
-    ) -> Result<ExecutionResult, InvocationError> {
+        simulation: Option<&SimulationReport>,
+    ) -> Result<(ExecutionResult, Option<MeteredCall>), InvocationError> {
+        let (tool_action, tool, caller) = self.prepare_action(action, contract)?;
+
+        // Check security policy
+        if !self.security_policy.can_execute(&caller, &tool_action) {
+            return Err(InvocationError::SecurityPolicyViolation);
+        }
+
+        // Create execution context with testimony signatures
+        let mut context = Self::execution_context(caller);
+        if let Some(report) = simulation {
+            context
+                .metadata
+                .insert("simulation".to_string(), hex::encode(report.hash()));
+        }
+
+        // Reserve the most this call can cost
+        let hold = match &self.fee_meter {
+            Some(meter) => Some(
+                meter
+                    .hold(&caller, tool.metadata(), &tool_action)
+                    .map_err(|e| InvocationError::InsufficientFunds(e.to_string()))?,
+            ),
+            None => None,
+        };
+
+        // Execute via tool
+        let started = std::time::Instant::now();
+        let result = tool.execute(&tool_action, &context).await;
+
+        let metered = hold.map(|hold| MeteredCall {
+            usage: Usage {
+                execution_time_ms: started.elapsed().as_millis() as u64,
+                cost_class: hold.cost_class,
+                bytes_in: serde_json::to_vec(&tool_action).unwrap_or_default().len() as u64,
+                bytes_out: result.data.as_ref().map_or(0, |d| d.len() as u64),
+            },
+            hold,
+        });
+
+        Ok((result, metered))
+    }
+
+    /// Convert a contract action and find the tool that would run it
+    fn prepare_action(
+        &self,
+        action: &ContractAction,
+        contract: &DigitizedContract,
+    ) -> Result<PreparedAction, InvocationError> {
         // Convert contract action to tool action
         let tool_action = self.convert_to_tool_action(action, contract)?;
 
@@ -229,29 +533,31 @@ impl InvocationEngine {
             .find_best_tool_for_action(&tool_action)
             .ok_or(InvocationError::NoSuitableTool)?;
 
-        // Check security policy
+        // Check the tool is still the version governance approved
+        if let Some(governance) = &self.tool_governance {
+            governance.verify(tool.as_ref()).map_err(|e| {
+                tracing::warn!("Refusing to invoke {}: {}", tool.tool_id().name, e);
+                InvocationError::ToolNotVetted(e.to_string())
+            })?;
+        }
+
         let caller = contract
             .parties
             .first()
             .map(|p| p.node_id)
             .unwrap_or([0u8; 32]);
-        if !self.security_policy.can_execute(&caller, &tool_action) {
-            return Err(InvocationError::SecurityPolicyViolation);
-        }
 
-        // Create execution context with testimony signatures
-        let context = ExecutionContext {
+        Ok((tool_action, tool, caller))
+    }
+
+    fn execution_context(caller: [u8; 32]) -> ExecutionContext {
+        ExecutionContext {
             caller,
             timestamp: chrono::Utc::now().timestamp(),
             fee_budget: None,
             testimony_signatures: Vec::new(),
             metadata: HashMap::new(),
-        };
-
-        // Execute via tool
-        let result = tool.execute(&tool_action, &context).await;
-
-        Ok(result)
+        }
     }
 
     /// Convert contract action to tool action
@@ -390,6 +696,10 @@ pub struct InvocationResult {
     pub status: InvocationStatus,
     pub condition_results: Vec<ValidationResult>,
     pub action_results: Vec<ExecutionResult>,
+    #[serde(default)]
+    pub simulations: Vec<SimulationReport>,
+    #[serde(default)]
+    pub fees: Vec<FeeCharge>,
 }
 
 /// Invocation status
@@ -399,6 +709,8 @@ pub enum InvocationStatus {
     ConditionsNotMet,
     PartialFailure,
     Failed,
+    /// Dry run only; nothing was executed
+    Simulated,
 }
 
 /// Record of completed invocation (for audit)
@@ -409,6 +721,10 @@ pub struct InvocationRecord {
     pub status: InvocationStatus,
     pub condition_results: Vec<ValidationResult>,
     pub action_results: Vec<ExecutionResult>,
+    #[serde(default)]
+    pub simulations: Vec<SimulationReport>,
+    #[serde(default)]
+    pub fees: Vec<FeeCharge>,
     pub started_at: i64,
     pub completed_at: i64,
 }
@@ -419,8 +735,12 @@ pub enum InvocationError {
     NoSuitableAgents,
     NoSuitableTool,
     SecurityPolicyViolation,
+    ToolNotVetted(String),
+    Quarantined(String),
     ValidationFailed(String),
+    SimulationFailed(String),
     ExecutionFailed(String),
+    InsufficientFunds(String),
     Timeout,
 }
 
@@ -430,8 +750,14 @@ impl std::fmt::Display for InvocationError {
             InvocationError::NoSuitableAgents => write!(f, "No suitable AI agents found"),
             InvocationError::NoSuitableTool => write!(f, "No suitable vetted tool found"),
             InvocationError::SecurityPolicyViolation => write!(f, "Security policy violation"),
+            InvocationError::ToolNotVetted(s) => write!(f, "Tool not vetted: {}", s),
+            InvocationError::Quarantined(s) => write!(f, "Quarantined: {}", s),
             InvocationError::ValidationFailed(s) => write!(f, "Validation failed: {}", s),
+            InvocationError::SimulationFailed(s) => write!(f, "Simulation failed: {}", s),
             InvocationError::ExecutionFailed(s) => write!(f, "Execution failed: {}", s),
+            InvocationError::InsufficientFunds(s) => {
+                write!(f, "Insufficient funds for tool fees: {}", s)
+            }
             InvocationError::Timeout => write!(f, "Operation timed out"),
         }
     }
@@ -452,4 +778,273 @@ mod tests {
         // Should start with no agents
         assert!(engine.agents.read().is_empty());
     }
+
+    fn contract(party: [u8; 32], actions: Vec<ContractAction>) -> DigitizedContract {
+        DigitizedContract {
+            contract_id: [1u8; 32],
+            parties: vec![ContractParty {
+                node_id: party,
+                public_key: Vec::new(),
+                role: PartyRole::Primary,
+                signature: Vec::new(),
+            }],
+            conditions: Vec::new(),
+            actions,
+            metadata: ContractMetadata {
+                name: "test".to_string(),
+                description: String::new(),
+                created_at: 0,
+                expires_at: None,
+                version: "1".to_string(),
+                tags: Vec::new(),
+            },
+            state: ContractState::Active,
+        }
+    }
+
+    fn audit() -> AuditInfo {
+        AuditInfo {
+            auditor: "Test Auditor".to_string(),
+            audit_date: chrono::Utc::now().timestamp(),
+            report_hash: [0u8; 32],
+            score: 95,
+            next_audit_due: chrono::Utc::now().timestamp() + 365 * 24 * 3600,
+        }
+    }
+
+    struct Blocked(Vec<QuarantineSubject>);
+
+    impl QuarantineList for Blocked {
+        fn is_quarantined(&self, subject: &QuarantineSubject) -> bool {
+            self.0.contains(subject)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_quarantined_party_is_refused() {
+        let party = [7u8; 32];
+        let engine = InvocationEngine::new(
+            Arc::new(ToolRegistry::new()),
+            Arc::new(SecurityPolicy::default()),
+        )
+        .with_quarantine(Arc::new(Blocked(vec![QuarantineSubject::Entity(party)])));
+
+        let contract = contract(party, Vec::new());
+
+        assert!(matches!(
+            engine.process_contract(&contract).await,
+            Err(InvocationError::Quarantined(_))
+        ));
+        assert!(engine.pending.read().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_simulation_gates_execution() {
+        let registry = Arc::new(ToolRegistry::new());
+        registry
+            .register_tool(Arc::new(EthereumTool::new(
+                "https://eth.example.com".to_string(),
+                audit(),
+            )))
+            .unwrap();
+        let engine = InvocationEngine::new(registry, Arc::new(SecurityPolicy::default()));
+
+        let party = [7u8; 32];
+        let payment = ContractAction {
+            id: [2u8; 32],
+            action_type: ActionType::Payment,
+            target_protocol: TargetProtocol::Ethereum {
+                chain_id: 1,
+                contract: "0xbob".to_string(),
+            },
+            parameters: HashMap::from([
+                (
+                    "asset".to_string(),
+                    ConditionValue::String("ETH".to_string()),
+                ),
+                (
+                    "amount".to_string(),
+                    ConditionValue::String("1".to_string()),
+                ),
+            ]),
+            status: ActionStatus::Pending,
+        };
+        let contract = contract(party, vec![payment]);
+
+        // Without a simulator the dry run has nothing to report
+        let result = engine.dry_run(&contract).await.unwrap();
+        assert_eq!(result.status, InvocationStatus::Simulated);
+        assert!(result.simulations.is_empty());
+
+        let simulator = Arc::new(EvmForkSimulator::new(EvmFork {
+            native_asset: "ETH".to_string(),
+            base_fee_wei: 1,
+            ..EvmFork::default()
+        }));
+        engine.register_simulator(simulator.clone());
+
+        let result = engine.dry_run(&contract).await.unwrap();
+        assert_eq!(result.status, InvocationStatus::Failed);
+        assert!(result.action_results.is_empty());
+        assert!(matches!(
+            engine.process_contract(&contract).await,
+            Err(InvocationError::SimulationFailed(_))
+        ));
+        assert!(engine.get_record(&result.invocation_id).is_none());
+
+        let mut fork = EvmFork {
+            native_asset: "ETH".to_string(),
+            base_fee_wei: 1,
+            ..EvmFork::default()
+        };
+        fork.set_balance(
+            "ETH",
+            &format!("0x{}", hex::encode(&party[12..])),
+            2 * 10u128.pow(18),
+        );
+        simulator.refork(fork);
+
+        let result = engine.dry_run(&contract).await.unwrap();
+        assert_eq!(result.status, InvocationStatus::Simulated);
+        assert_eq!(result.simulations[0].simulator, "evm-fork");
+        assert_eq!(
+            result.simulations[0].simulation.estimated_fee,
+            Some(TRANSFER_GAS)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_required_simulation() {
+        let registry = Arc::new(ToolRegistry::new());
+        registry
+            .register_tool(Arc::new(SwiftTool::new(audit())))
+            .unwrap();
+        let engine = InvocationEngine::new(registry, Arc::new(SecurityPolicy::default()))
+            .with_required_simulation(true);
+
+        let payment = ContractAction {
+            id: [3u8; 32],
+            action_type: ActionType::Payment,
+            target_protocol: TargetProtocol::Banking {
+                protocol: "SWIFT".to_string(),
+                account: "FR1420041010050500013M02606".to_string(),
+            },
+            parameters: HashMap::from([(
+                "amount".to_string(),
+                ConditionValue::String("250.00".to_string()),
+            )]),
+            status: ActionStatus::Pending,
+        };
+        let contract = contract([8u8; 32], vec![payment]);
+
+        assert!(matches!(
+            engine.dry_run(&contract).await,
+            Err(InvocationError::SimulationFailed(_))
+        ));
+
+        let simulator = Arc::new(MockBankingSimulator::new("MT103", 1_500));
+        simulator.set_balance(&format!("0x{}", hex::encode([8u8; 20])), 100_000);
+        engine.register_simulator(simulator);
+
+        let result = engine.dry_run(&contract).await.unwrap();
+        assert_eq!(result.status, InvocationStatus::Simulated);
+        assert_eq!(result.simulations[0].simulation.effects.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_revoked_tool_is_not_invoked() {
+        let registry = Arc::new(ToolRegistry::new());
+        let governance = Arc::new(VettedToolRegistry::new(registry.clone()));
+        governance.register_member([1u8; 32]);
+
+        // Registered directly, bypassing governance
+        let tool = Arc::new(SwiftTool::new(audit()));
+        registry.register_tool(tool.clone()).unwrap();
+
+        let engine = InvocationEngine::new(registry, Arc::new(SecurityPolicy::default()))
+            .with_tool_governance(governance.clone());
+        let payment = ContractAction {
+            id: [3u8; 32],
+            action_type: ActionType::Payment,
+            target_protocol: TargetProtocol::Banking {
+                protocol: "SWIFT".to_string(),
+                account: "FR1420041010050500013M02606".to_string(),
+            },
+            parameters: HashMap::new(),
+            status: ActionStatus::Pending,
+        };
+        let contract = contract([8u8; 32], vec![payment]);
+
+        assert!(matches!(
+            engine.dry_run(&contract).await,
+            Err(InvocationError::ToolNotVetted(_))
+        ));
+
+        governance
+            .revoke(tool.tool_id(), [1u8; 32], "Compromised gateway")
+            .unwrap();
+        assert!(matches!(
+            engine.dry_run(&contract).await,
+            Err(InvocationError::NoSuitableTool)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_tool_calls_are_charged() {
+        use crate::digital_credits::{CreditsLedger, DC_FAT_TOKEN_ID};
+
+        let registry = Arc::new(ToolRegistry::new());
+        registry
+            .register_tool(Arc::new(SwiftTool::new(audit())))
+            .unwrap();
+
+        let ledger = Arc::new(CreditsLedger::new());
+        let party = [8u8; 32];
+        let collector = [9u8; 32];
+        let fat = 10u128.pow(18);
+        ledger
+            .mint(&DC_FAT_TOKEN_ID, &party, fat, &party, true)
+            .unwrap();
+        let meter = Arc::new(FeeMeter::new(
+            ledger.clone(),
+            PriceTable::default(),
+            collector,
+        ));
+        let engine = InvocationEngine::new(registry, Arc::new(SecurityPolicy::default()))
+            .with_fee_meter(meter);
+
+        let payment = ContractAction {
+            id: [3u8; 32],
+            action_type: ActionType::Payment,
+            target_protocol: TargetProtocol::Banking {
+                protocol: "SWIFT".to_string(),
+                account: "FR1420041010050500013M02606".to_string(),
+            },
+            parameters: HashMap::new(),
+            status: ActionStatus::Pending,
+        };
+        let broke = contract([7u8; 32], vec![payment.clone()]);
+        let contract = contract(party, vec![payment]);
+
+        let result = engine.process_contract(&contract).await.unwrap();
+        assert_eq!(result.status, InvocationStatus::Completed);
+        assert_eq!(result.fees.len(), 1);
+
+        let charge = &result.fees[0];
+        assert_eq!(charge.usage.cost_class, ApiCostClass::Premium);
+        assert_eq!(charge.usage.bytes_out, b"SWIFT_REF_123456".len() as u64);
+        assert_eq!(ledger.balance_of(&collector, &DC_FAT_TOKEN_ID), charge.fee);
+        let breakdown =
+            ledger.balance_breakdown(&party, &DC_FAT_TOKEN_ID, chrono::Utc::now().timestamp());
+        assert_eq!(breakdown.liquid, fat - charge.fee);
+
+        let record = engine.get_record(&result.invocation_id).unwrap();
+        assert_eq!(record.fees[0].operation_id, charge.operation_id);
+
+        // A caller without funds is refused before the tool runs
+        assert!(matches!(
+            engine.process_contract(&broke).await,
+            Err(InvocationError::InsufficientFunds(_))
+        ));
+    }
 }
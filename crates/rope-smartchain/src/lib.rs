@@ -81,19 +81,25 @@
 pub mod digital_credits;
 pub mod governance;
 pub mod invocation_engine;
+pub mod metering;
 pub mod network_config;
 pub mod protocol_adapters;
 pub mod security_policy;
+pub mod simulation;
 pub mod testimony_agent;
 pub mod testimony_policy;
 pub mod tool_registry;
+pub mod tool_vetting;
 
 // Re-exports
 pub use digital_credits::*;
 pub use governance::*;
 pub use invocation_engine::*;
+pub use metering::*;
 pub use network_config::*;
 pub use security_policy::*;
+pub use simulation::*;
 pub use testimony_agent::*;
 pub use testimony_policy::*;
 pub use tool_registry::*;
+pub use tool_vetting::*;
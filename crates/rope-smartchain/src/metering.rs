@@ -0,0 +1,449 @@
+//! # Invocation Metering
+//!
+//! Every tool call the invocation engine makes is metered and paid for in
+//! FAT (or another digital credit named by the price table):
+//!
+//! 1. Before the call, the most it could cost under the published
+//!    [`PriceTable`] is frozen in the caller's account as a [`FeeHold`]
+//! 2. The engine measures the call: wall-clock execution time, the
+//!    [`ApiCostClass`] of the external API, and bytes sent and received
+//! 3. When the invocation is recorded, each hold is settled: the metered
+//!    fee goes to the fee collector and the rest is released
+//!
+//! Holds are taken at the price table in force when the call started, so
+//! publishing a new table never changes the price of a call in flight. A
+//! caller that can't cover the hold is refused before the tool runs.
+
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use super::digital_credits::*;
+use super::tool_registry::*;
+
+/// One FAT in base units
+const FAT: Balance = 1_000_000_000_000_000_000;
+
+/// Cost class of the external API a tool calls
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ApiCostClass {
+    /// Read-only queries
+    Basic,
+    /// Blockchain and other state-changing calls
+    Standard,
+    /// Banking and finance networks
+    Premium,
+}
+
+impl ApiCostClass {
+    /// Cost class of running `action` on a tool
+    pub fn of(metadata: &ToolMetadata, action: &ToolAction) -> Self {
+        if matches!(action.action_type, ToolActionType::Query { .. }) {
+            return ApiCostClass::Basic;
+        }
+        match metadata.category {
+            ToolCategory::Banking(_) | ToolCategory::Finance(_) => ApiCostClass::Premium,
+            _ => ApiCostClass::Standard,
+        }
+    }
+}
+
+/// Published fee schedule
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PriceTable {
+    /// Increases with every published table
+    pub version: u32,
+
+    /// Credit fees are paid in
+    pub token_id: TokenId,
+
+    /// Flat fee per call
+    pub base_fee: Balance,
+
+    /// Fee per started second of execution
+    pub per_second: Balance,
+
+    /// Fee per started KiB sent or received
+    pub per_kib: Balance,
+
+    /// Fee per call by cost class
+    pub class_fees: HashMap<ApiCostClass, Balance>,
+
+    /// Data volume above this is not billed
+    pub max_billable_kib: u64,
+
+    pub published_at: i64,
+}
+
+impl Default for PriceTable {
+    fn default() -> Self {
+        Self {
+            version: 1,
+            token_id: DC_FAT_TOKEN_ID,
+            base_fee: FAT / 1_000,
+            per_second: FAT / 10_000,
+            per_kib: FAT / 1_000_000,
+            class_fees: HashMap::from([
+                (ApiCostClass::Basic, 0),
+                (ApiCostClass::Standard, FAT / 1_000),
+                (ApiCostClass::Premium, FAT / 100),
+            ]),
+            max_billable_kib: 10_240,
+            published_at: chrono::Utc::now().timestamp(),
+        }
+    }
+}
+
+impl PriceTable {
+    /// Fee for a metered call
+    pub fn fee(&self, usage: &Usage) -> Balance {
+        let seconds = usage.execution_time_ms.div_ceil(1_000);
+        let kib = (usage.bytes_in + usage.bytes_out)
+            .div_ceil(1_024)
+            .min(self.max_billable_kib);
+        self.base_fee
+            + self.class_fee(usage.cost_class)
+            + self.per_second * seconds as Balance
+            + self.per_kib * kib as Balance
+    }
+
+    /// Most a call of `cost_class` can cost if it runs for `timeout_secs`
+    pub fn max_fee(&self, cost_class: ApiCostClass, timeout_secs: u64) -> Balance {
+        self.base_fee
+            + self.class_fee(cost_class)
+            + self.per_second * timeout_secs as Balance
+            + self.per_kib * self.max_billable_kib as Balance
+    }
+
+    fn class_fee(&self, cost_class: ApiCostClass) -> Balance {
+        self.class_fees.get(&cost_class).copied().unwrap_or(0)
+    }
+
+    /// Hash committing to the table contents
+    pub fn hash(&self) -> [u8; 32] {
+        let mut class_fees: Vec<_> = self.class_fees.iter().collect();
+        class_fees.sort_by_key(|(class, _)| **class as u8);
+
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(&self.version.to_le_bytes());
+        hasher.update(&self.token_id);
+        hasher.update(&self.base_fee.to_le_bytes());
+        hasher.update(&self.per_second.to_le_bytes());
+        hasher.update(&self.per_kib.to_le_bytes());
+        for (class, fee) in class_fees {
+            hasher.update(&[*class as u8]);
+            hasher.update(&fee.to_le_bytes());
+        }
+        hasher.update(&self.max_billable_kib.to_le_bytes());
+        hasher.update(&self.published_at.to_le_bytes());
+        *hasher.finalize().as_bytes()
+    }
+}
+
+/// What a tool call consumed
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Usage {
+    /// Wall-clock time measured by the engine, not reported by the tool
+    pub execution_time_ms: u64,
+    pub cost_class: ApiCostClass,
+    /// Serialized action sent to the tool
+    pub bytes_in: u64,
+    /// Result data returned by the tool
+    pub bytes_out: u64,
+}
+
+/// Fee reserved before a tool call
+#[derive(Clone, Debug)]
+pub struct FeeHold {
+    pub action_id: [u8; 32],
+    pub caller: [u8; 32],
+    pub cost_class: ApiCostClass,
+    /// Frozen amount
+    pub amount: Balance,
+    /// Table in force when the call started
+    pub prices: PriceTable,
+}
+
+/// Metered tool call whose fee is not yet settled
+#[derive(Clone, Debug)]
+pub struct MeteredCall {
+    pub hold: FeeHold,
+    pub usage: Usage,
+}
+
+/// Fee settled for one tool call, kept with the invocation record
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct FeeCharge {
+    pub action_id: [u8; 32],
+    pub caller: [u8; 32],
+    pub usage: Usage,
+    pub token_id: TokenId,
+    pub fee: Balance,
+    pub price_table_version: u32,
+    pub price_table_hash: [u8; 32],
+    /// Ledger operation that moved the fee
+    pub operation_id: [u8; 32],
+}
+
+/// Metering errors
+#[derive(Clone, Debug, PartialEq)]
+pub enum MeteringError {
+    /// Published table doesn't supersede the current one
+    StaleVersion {
+        current: u32,
+        published: u32,
+    },
+    Ledger(LedgerError),
+}
+
+impl std::fmt::Display for MeteringError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MeteringError::StaleVersion { current, published } => write!(
+                f,
+                "Price table version {} does not supersede {}",
+                published, current
+            ),
+            MeteringError::Ledger(e) => write!(f, "Ledger error: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for MeteringError {}
+
+impl From<LedgerError> for MeteringError {
+    fn from(e: LedgerError) -> Self {
+        MeteringError::Ledger(e)
+    }
+}
+
+/// Reserves and settles tool call fees against the credits ledger
+pub struct FeeMeter {
+    ledger: Arc<CreditsLedger>,
+    prices: RwLock<PriceTable>,
+    /// Account fees are paid to
+    collector: [u8; 32],
+}
+
+impl FeeMeter {
+    pub fn new(ledger: Arc<CreditsLedger>, prices: PriceTable, collector: [u8; 32]) -> Self {
+        Self {
+            ledger,
+            prices: RwLock::new(prices),
+            collector,
+        }
+    }
+
+    /// Current price table
+    pub fn prices(&self) -> PriceTable {
+        self.prices.read().clone()
+    }
+
+    /// Publish a new price table; applies to calls started afterwards
+    pub fn publish(&self, table: PriceTable) -> Result<(), MeteringError> {
+        let mut prices = self.prices.write();
+        if table.version <= prices.version {
+            return Err(MeteringError::StaleVersion {
+                current: prices.version,
+                published: table.version,
+            });
+        }
+        tracing::info!("Publishing fee price table v{}", table.version);
+        *prices = table;
+        Ok(())
+    }
+
+    /// Freeze the most `action` can cost in the caller's account
+    pub fn hold(
+        &self,
+        caller: &[u8; 32],
+        metadata: &ToolMetadata,
+        action: &ToolAction,
+    ) -> Result<FeeHold, MeteringError> {
+        let prices = self.prices();
+        let cost_class = ApiCostClass::of(metadata, action);
+        let amount = prices.max_fee(cost_class, action.timeout_secs);
+
+        self.ledger.freeze(&prices.token_id, caller, amount)?;
+
+        Ok(FeeHold {
+            action_id: action.id,
+            caller: *caller,
+            cost_class,
+            amount,
+            prices,
+        })
+    }
+
+    /// Release a hold without charging
+    pub fn release(&self, hold: &FeeHold) {
+        if let Err(e) = self
+            .ledger
+            .unfreeze(&hold.prices.token_id, &hold.caller, hold.amount)
+        {
+            tracing::error!(
+                "Failed to release fee hold for action {}: {}",
+                hex::encode(hold.action_id),
+                e
+            );
+        }
+    }
+
+    /// Charge the metered fee from a hold and release the rest
+    pub fn charge(&self, call: MeteredCall) -> Result<FeeCharge, MeteringError> {
+        let MeteredCall { hold, usage } = call;
+        let metered = hold.prices.fee(&usage);
+        // Calls that overrun their timeout aren't billed beyond the hold
+        let fee = metered.min(hold.amount);
+
+        let operation = self.ledger.settle_hold(
+            &hold.prices.token_id,
+            &hold.caller,
+            hold.amount,
+            fee,
+            &self.collector,
+        )?;
+
+        Ok(FeeCharge {
+            action_id: hold.action_id,
+            caller: hold.caller,
+            usage,
+            token_id: hold.prices.token_id,
+            fee,
+            price_table_version: hold.prices.version,
+            price_table_hash: hold.prices.hash(),
+            operation_id: operation.operation_id,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn usage(execution_time_ms: u64, bytes: u64) -> Usage {
+        Usage {
+            execution_time_ms,
+            cost_class: ApiCostClass::Premium,
+            bytes_in: bytes,
+            bytes_out: 0,
+        }
+    }
+
+    #[test]
+    fn test_price_table_fee() {
+        let prices = PriceTable::default();
+
+        let fee = prices.fee(&usage(1_500, 2_049));
+        assert_eq!(
+            fee,
+            prices.base_fee + FAT / 100 + 2 * prices.per_second + 3 * prices.per_kib
+        );
+
+        // Data volume is capped
+        let huge = prices.fee(&usage(0, u64::MAX / 2));
+        assert_eq!(huge, prices.max_fee(ApiCostClass::Premium, 0));
+    }
+
+    #[test]
+    fn test_hold_and_charge() {
+        let ledger = Arc::new(CreditsLedger::new());
+        let caller = [1u8; 32];
+        let collector = [2u8; 32];
+        ledger
+            .mint(&DC_FAT_TOKEN_ID, &caller, FAT, &caller, true)
+            .unwrap();
+
+        let meter = FeeMeter::new(ledger.clone(), PriceTable::default(), collector);
+        let tool = SwiftTool::new(AuditInfo {
+            auditor: "Test Auditor".to_string(),
+            audit_date: 0,
+            report_hash: [0u8; 32],
+            score: 95,
+            next_audit_due: i64::MAX,
+        });
+        let action = ToolAction {
+            id: [3u8; 32],
+            action_type: ToolActionType::Transfer {
+                asset: "EUR".to_string(),
+                amount: "10".to_string(),
+            },
+            from: caller,
+            to: "FR1420041010050500013M02606".to_string(),
+            parameters: HashMap::new(),
+            contract_ref: None,
+            priority: ActionPriority::Normal,
+            timeout_secs: 60,
+        };
+
+        let hold = meter.hold(&caller, tool.metadata(), &action).unwrap();
+        assert_eq!(hold.cost_class, ApiCostClass::Premium);
+        let now = chrono::Utc::now().timestamp();
+        let breakdown = ledger.balance_breakdown(&caller, &DC_FAT_TOKEN_ID, now);
+        assert_eq!(breakdown.frozen, hold.amount);
+
+        // A newer table doesn't reprice the call in flight
+        let mut pricier = PriceTable {
+            version: 2,
+            ..PriceTable::default()
+        };
+        pricier.base_fee *= 10;
+        meter.publish(pricier).unwrap();
+        assert!(matches!(
+            meter.publish(PriceTable::default()),
+            Err(MeteringError::StaleVersion { .. })
+        ));
+
+        let charge = meter
+            .charge(MeteredCall {
+                hold,
+                usage: usage(200, 512),
+            })
+            .unwrap();
+        assert_eq!(charge.price_table_version, 1);
+        assert_eq!(ledger.balance_of(&collector, &DC_FAT_TOKEN_ID), charge.fee);
+        let breakdown = ledger.balance_breakdown(&caller, &DC_FAT_TOKEN_ID, now);
+        assert_eq!(breakdown.frozen, 0);
+        assert_eq!(breakdown.total, FAT - charge.fee);
+        assert!(ledger.reconcile(&DC_FAT_TOKEN_ID).is_reconciled());
+    }
+
+    #[test]
+    fn test_hold_requires_funds() {
+        let ledger = Arc::new(CreditsLedger::new());
+        let caller = [1u8; 32];
+        ledger
+            .mint(&DC_FAT_TOKEN_ID, &caller, 1, &caller, true)
+            .unwrap();
+        let meter = FeeMeter::new(ledger, PriceTable::default(), [2u8; 32]);
+
+        let tool = EthereumTool::new(
+            "https://eth.example.com".to_string(),
+            AuditInfo {
+                auditor: "Test Auditor".to_string(),
+                audit_date: 0,
+                report_hash: [0u8; 32],
+                score: 95,
+                next_audit_due: i64::MAX,
+            },
+        );
+        let action = ToolAction {
+            id: [3u8; 32],
+            action_type: ToolActionType::Query {
+                query_type: "balance".to_string(),
+            },
+            from: caller,
+            to: "0xbob".to_string(),
+            parameters: HashMap::new(),
+            contract_ref: None,
+            priority: ActionPriority::Normal,
+            timeout_secs: 10,
+        };
+
+        assert_eq!(
+            meter.hold(&caller, tool.metadata(), &action).unwrap_err(),
+            MeteringError::Ledger(LedgerError::InsufficientBalance)
+        );
+    }
+}
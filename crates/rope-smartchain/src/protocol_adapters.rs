@@ -5,6 +5,14 @@
 //! - Banking systems
 //! - Finance protocols
 //! - Asset management platforms
+//!
+//! ## Writing an adapter
+//!
+//! Third parties add support for a new banking or finance protocol by
+//! implementing [`ProtocolAdapter`]. Before a banking or finance tool built
+//! on an adapter can be activated in the vetted registry, the adapter has
+//! to pass the [`conformance`] suite; the resulting report is attached to
+//! the tool's federation proposal.
 
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
@@ -433,6 +441,318 @@ impl ProtocolAdapter for AssetManagementAdapter {
     }
 }
 
+pub mod conformance {
+    //! Adapter conformance harness
+    //!
+    //! The adapter author implements [`ConformanceFixture`] to hand the
+    //! harness adapters wired to a test endpoint, plus transactions the
+    //! endpoint accepts and rejects. [`ConformanceHarness::run`] then checks:
+    //!
+    //! - **Connection handling**: nothing is submitted before `connect`,
+    //!   connect/disconnect/reconnect track `is_connected`, and an
+    //!   unreachable endpoint fails with `ConnectionFailed`
+    //! - **Idempotency**: resubmitting a transaction with the same ID
+    //!   returns the original receipt instead of executing twice
+    //! - **Timeouts**: a call to an endpoint that never answers fails with
+    //!   `Timeout` once the adapter's timeout elapses
+    //! - **Error mapping**: each rejection the author declares surfaces as
+    //!   the matching [`AdapterError`] variant
+    //!
+    //! Every call is bounded by the harness deadline, so an adapter that
+    //! hangs fails the check instead of stalling the run.
+
+    use super::*;
+    use std::future::Future;
+    use std::time::{Duration, Instant};
+
+    /// Version of the check suite, recorded in reports
+    pub const CONFORMANCE_SUITE_VERSION: u32 = 1;
+
+    /// Rejection the endpoint produces and the error it must map to
+    #[derive(Clone, Debug)]
+    pub struct ErrorCase {
+        /// Check name, recorded in the report
+        pub name: String,
+
+        /// Transaction the endpoint rejects
+        pub transaction: ProtocolTransaction,
+
+        /// Expected error; only the variant is compared
+        pub expected: AdapterError,
+    }
+
+    /// Fixtures supplied by the adapter author
+    pub trait ConformanceFixture: Send + Sync {
+        /// Fresh, disconnected adapter for a working endpoint
+        fn adapter(&self) -> Box<dyn ProtocolAdapter>;
+
+        /// Adapter for an endpoint that refuses connections
+        fn unreachable_adapter(&self) -> Box<dyn ProtocolAdapter>;
+
+        /// Adapter for an endpoint that accepts connections but never
+        /// answers, configured to give up after `timeout`
+        fn unresponsive_adapter(&self, timeout: Duration) -> Box<dyn ProtocolAdapter>;
+
+        /// Transaction the working endpoint accepts
+        fn transaction(&self) -> ProtocolTransaction;
+
+        /// Rejections to check error mapping against
+        fn error_cases(&self) -> Vec<ErrorCase>;
+    }
+
+    /// Harness timing
+    #[derive(Clone, Debug)]
+    pub struct HarnessConfig {
+        /// Timeout the unresponsive adapter is configured with
+        pub adapter_timeout: Duration,
+
+        /// Longest the harness waits for any single call
+        pub call_deadline: Duration,
+    }
+
+    impl Default for HarnessConfig {
+        fn default() -> Self {
+            Self {
+                adapter_timeout: Duration::from_secs(2),
+                call_deadline: Duration::from_secs(10),
+            }
+        }
+    }
+
+    /// Outcome of one check
+    #[derive(Clone, Debug, Serialize, Deserialize)]
+    pub struct ConformanceCheck {
+        pub name: String,
+        pub passed: bool,
+
+        /// Why the check failed
+        pub detail: Option<String>,
+
+        pub duration_ms: u64,
+    }
+
+    /// Conformance run over one adapter
+    #[derive(Clone, Debug, Serialize, Deserialize)]
+    pub struct ConformanceReport {
+        /// Adapter name
+        pub adapter: String,
+
+        pub protocol_type: ProtocolType,
+
+        pub suite_version: u32,
+
+        pub checks: Vec<ConformanceCheck>,
+
+        pub started_at: i64,
+        pub finished_at: i64,
+    }
+
+    impl ConformanceReport {
+        /// Every check passed
+        pub fn passed(&self) -> bool {
+            !self.checks.is_empty() && self.checks.iter().all(|c| c.passed)
+        }
+
+        /// Failed checks
+        pub fn failures(&self) -> Vec<&ConformanceCheck> {
+            self.checks.iter().filter(|c| !c.passed).collect()
+        }
+
+        /// Hash committing to the report contents
+        pub fn hash(&self) -> [u8; 32] {
+            *blake3::hash(&serde_json::to_vec(self).unwrap_or_default()).as_bytes()
+        }
+    }
+
+    /// Runs the conformance checks against a fixture
+    pub struct ConformanceHarness {
+        config: HarnessConfig,
+    }
+
+    impl ConformanceHarness {
+        pub fn new(config: HarnessConfig) -> Self {
+            Self { config }
+        }
+
+        /// Run every check and report the results
+        pub async fn run(&self, fixture: &dyn ConformanceFixture) -> ConformanceReport {
+            let started_at = chrono::Utc::now().timestamp();
+            let probe = fixture.adapter();
+
+            let mut checks = vec![
+                timed("connection_lifecycle", self.check_lifecycle(fixture)).await,
+                timed("connection_failure", self.check_unreachable(fixture)).await,
+                timed("idempotent_submission", self.check_idempotency(fixture)).await,
+                timed("timeout", self.check_timeout(fixture)).await,
+            ];
+            for case in fixture.error_cases() {
+                let name = format!("error_mapping:{}", case.name);
+                checks.push(timed(&name, self.check_error_case(fixture, &case)).await);
+            }
+
+            ConformanceReport {
+                adapter: probe.name().to_string(),
+                protocol_type: probe.protocol_type(),
+                suite_version: CONFORMANCE_SUITE_VERSION,
+                checks,
+                started_at,
+                finished_at: chrono::Utc::now().timestamp(),
+            }
+        }
+
+        async fn check_lifecycle(&self, fixture: &dyn ConformanceFixture) -> Result<(), String> {
+            let mut adapter = fixture.adapter();
+            if adapter.is_connected() {
+                return Err("reports connected before connect()".to_string());
+            }
+
+            let tx = fixture.transaction();
+            match self.bounded(adapter.submit_transaction(&tx)).await? {
+                Err(AdapterError::NotConnected) => {}
+                other => {
+                    return Err(format!(
+                        "submit before connect returned {:?}, expected NotConnected",
+                        other
+                    ))
+                }
+            }
+
+            self.bounded(adapter.connect())
+                .await?
+                .map_err(|e| format!("connect failed: {}", e))?;
+            if !adapter.is_connected() {
+                return Err("reports disconnected after connect()".to_string());
+            }
+
+            self.bounded(adapter.disconnect())
+                .await?
+                .map_err(|e| format!("disconnect failed: {}", e))?;
+            if adapter.is_connected() {
+                return Err("reports connected after disconnect()".to_string());
+            }
+
+            self.bounded(adapter.connect())
+                .await?
+                .map_err(|e| format!("reconnect failed: {}", e))?;
+            if !adapter.is_connected() {
+                return Err("reports disconnected after reconnecting".to_string());
+            }
+
+            Ok(())
+        }
+
+        async fn check_unreachable(&self, fixture: &dyn ConformanceFixture) -> Result<(), String> {
+            let mut adapter = fixture.unreachable_adapter();
+            match self.bounded(adapter.connect()).await? {
+                Err(AdapterError::ConnectionFailed(_)) if !adapter.is_connected() => Ok(()),
+                Err(AdapterError::ConnectionFailed(_)) => {
+                    Err("reports connected after a failed connect()".to_string())
+                }
+                other => Err(format!(
+                    "connect to an unreachable endpoint returned {:?}, expected ConnectionFailed",
+                    other
+                )),
+            }
+        }
+
+        async fn check_idempotency(&self, fixture: &dyn ConformanceFixture) -> Result<(), String> {
+            let mut adapter = fixture.adapter();
+            self.bounded(adapter.connect())
+                .await?
+                .map_err(|e| format!("connect failed: {}", e))?;
+
+            let tx = fixture.transaction();
+            let first = self
+                .bounded(adapter.submit_transaction(&tx))
+                .await?
+                .map_err(|e| format!("submission failed: {}", e))?;
+            let second = self
+                .bounded(adapter.submit_transaction(&tx))
+                .await?
+                .map_err(|e| format!("resubmission failed: {}", e))?;
+
+            if first.tx_hash != second.tx_hash {
+                return Err("resubmission produced a different transaction".to_string());
+            }
+            if let TransactionStatus::Failed { reason } = second.status {
+                return Err(format!("resubmission failed: {}", reason));
+            }
+            Ok(())
+        }
+
+        async fn check_timeout(&self, fixture: &dyn ConformanceFixture) -> Result<(), String> {
+            let mut adapter = fixture.unresponsive_adapter(self.config.adapter_timeout);
+
+            // An endpoint that never answers may already time out on connect
+            match self.bounded(adapter.connect()).await? {
+                Ok(()) => {}
+                Err(AdapterError::Timeout) => return Ok(()),
+                Err(e) => return Err(format!("connect failed: {}", e)),
+            }
+
+            let tx = fixture.transaction();
+            match self.bounded(adapter.submit_transaction(&tx)).await? {
+                Err(AdapterError::Timeout) => Ok(()),
+                other => Err(format!(
+                    "submit to an unresponsive endpoint returned {:?}, expected Timeout",
+                    other
+                )),
+            }
+        }
+
+        async fn check_error_case(
+            &self,
+            fixture: &dyn ConformanceFixture,
+            case: &ErrorCase,
+        ) -> Result<(), String> {
+            let mut adapter = fixture.adapter();
+            self.bounded(adapter.connect())
+                .await?
+                .map_err(|e| format!("connect failed: {}", e))?;
+
+            match self
+                .bounded(adapter.submit_transaction(&case.transaction))
+                .await?
+            {
+                Err(e) if std::mem::discriminant(&e) == std::mem::discriminant(&case.expected) => {
+                    Ok(())
+                }
+                other => Err(format!(
+                    "returned {:?}, expected {:?}",
+                    other, case.expected
+                )),
+            }
+        }
+
+        /// Await `call`, failing if it outlives the harness deadline
+        async fn bounded<T>(&self, call: impl Future<Output = T>) -> Result<T, String> {
+            tokio::time::timeout(self.config.call_deadline, call)
+                .await
+                .map_err(|_| format!("call did not return within {:?}", self.config.call_deadline))
+        }
+    }
+
+    impl Default for ConformanceHarness {
+        fn default() -> Self {
+            Self::new(HarnessConfig::default())
+        }
+    }
+
+    async fn timed(
+        name: &str,
+        check: impl Future<Output = Result<(), String>>,
+    ) -> ConformanceCheck {
+        let started = Instant::now();
+        let result = check.await;
+        ConformanceCheck {
+            name: name.to_string(),
+            passed: result.is_ok(),
+            detail: result.err(),
+            duration_ms: started.elapsed().as_millis() as u64,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -449,4 +769,193 @@ mod tests {
         adapter.disconnect().await.unwrap();
         assert!(!adapter.is_connected());
     }
+
+    mod conformance_harness {
+        use super::super::conformance::*;
+        use super::super::*;
+        use parking_lot::Mutex;
+        use std::time::Duration;
+
+        #[derive(Clone, Copy, PartialEq)]
+        enum Endpoint {
+            Working,
+            Unreachable,
+            Unresponsive,
+        }
+
+        /// Adapter over an in-memory endpoint
+        struct MockAdapter {
+            endpoint: Endpoint,
+            timeout: Option<Duration>,
+            connected: bool,
+            receipts: Mutex<HashMap<[u8; 32], TransactionReceipt>>,
+            /// Misbehave: execute resubmissions again
+            duplicate_submissions: bool,
+        }
+
+        #[async_trait]
+        impl ProtocolAdapter for MockAdapter {
+            fn name(&self) -> &str {
+                "Mock SEPA"
+            }
+
+            fn protocol_type(&self) -> ProtocolType {
+                ProtocolType::Banking(BankingType::Sepa)
+            }
+
+            async fn connect(&mut self) -> Result<(), AdapterError> {
+                if self.endpoint == Endpoint::Unreachable {
+                    return Err(AdapterError::ConnectionFailed("refused".to_string()));
+                }
+                self.connected = true;
+                Ok(())
+            }
+
+            async fn disconnect(&mut self) -> Result<(), AdapterError> {
+                self.connected = false;
+                Ok(())
+            }
+
+            fn is_connected(&self) -> bool {
+                self.connected
+            }
+
+            async fn submit_transaction(
+                &self,
+                tx: &ProtocolTransaction,
+            ) -> Result<TransactionReceipt, AdapterError> {
+                if !self.connected {
+                    return Err(AdapterError::NotConnected);
+                }
+                if self.endpoint == Endpoint::Unresponsive {
+                    let never = std::future::pending::<()>();
+                    match self.timeout {
+                        Some(timeout) => {
+                            let _ = tokio::time::timeout(timeout, never).await;
+                            return Err(AdapterError::Timeout);
+                        }
+                        None => never.await,
+                    }
+                }
+                if tx.to.is_empty() {
+                    return Err(AdapterError::InvalidParameter("missing IBAN".to_string()));
+                }
+
+                let mut receipts = self.receipts.lock();
+                if !self.duplicate_submissions {
+                    if let Some(receipt) = receipts.get(&tx.id) {
+                        return Ok(receipt.clone());
+                    }
+                }
+                let receipt = TransactionReceipt {
+                    tx_hash: *blake3::hash(&[&tx.id[..], &[receipts.len() as u8]].concat())
+                        .as_bytes(),
+                    status: TransactionStatus::Pending,
+                    block_number: None,
+                    gas_used: None,
+                    logs: Vec::new(),
+                    timestamp: chrono::Utc::now().timestamp(),
+                };
+                receipts.insert(tx.id, receipt.clone());
+                Ok(receipt)
+            }
+
+            async fn query(&self, _query: &ProtocolQuery) -> Result<QueryResult, AdapterError> {
+                Err(AdapterError::NotConnected)
+            }
+        }
+
+        struct MockFixture {
+            honours_timeout: bool,
+            duplicate_submissions: bool,
+        }
+
+        impl MockFixture {
+            fn mock(
+                &self,
+                endpoint: Endpoint,
+                timeout: Option<Duration>,
+            ) -> Box<dyn ProtocolAdapter> {
+                Box::new(MockAdapter {
+                    endpoint,
+                    timeout: timeout.filter(|_| self.honours_timeout),
+                    connected: false,
+                    receipts: Mutex::new(HashMap::new()),
+                    duplicate_submissions: self.duplicate_submissions,
+                })
+            }
+        }
+
+        impl ConformanceFixture for MockFixture {
+            fn adapter(&self) -> Box<dyn ProtocolAdapter> {
+                self.mock(Endpoint::Working, None)
+            }
+
+            fn unreachable_adapter(&self) -> Box<dyn ProtocolAdapter> {
+                self.mock(Endpoint::Unreachable, None)
+            }
+
+            fn unresponsive_adapter(&self, timeout: Duration) -> Box<dyn ProtocolAdapter> {
+                self.mock(Endpoint::Unresponsive, Some(timeout))
+            }
+
+            fn transaction(&self) -> ProtocolTransaction {
+                ProtocolTransaction {
+                    id: [7u8; 32],
+                    from: "DE89370400440532013000".to_string(),
+                    to: "FR1420041010050500013M02606".to_string(),
+                    operation: TransactionOperation::Transfer {
+                        asset: "EUR".to_string(),
+                        amount: "100.00".to_string(),
+                    },
+                    parameters: HashMap::new(),
+                    metadata: HashMap::new(),
+                }
+            }
+
+            fn error_cases(&self) -> Vec<ErrorCase> {
+                let mut transaction = self.transaction();
+                transaction.to.clear();
+                vec![ErrorCase {
+                    name: "missing_beneficiary".to_string(),
+                    transaction,
+                    expected: AdapterError::InvalidParameter(String::new()),
+                }]
+            }
+        }
+
+        fn harness() -> ConformanceHarness {
+            ConformanceHarness::new(HarnessConfig {
+                adapter_timeout: Duration::from_millis(20),
+                call_deadline: Duration::from_millis(200),
+            })
+        }
+
+        #[tokio::test]
+        async fn test_conforming_adapter_passes() {
+            let fixture = MockFixture {
+                honours_timeout: true,
+                duplicate_submissions: false,
+            };
+            let report = harness().run(&fixture).await;
+
+            assert!(report.passed(), "{:?}", report.failures());
+            assert_eq!(report.adapter, "Mock SEPA");
+            assert_eq!(report.checks.len(), 5);
+            assert_eq!(report.suite_version, CONFORMANCE_SUITE_VERSION);
+        }
+
+        #[tokio::test]
+        async fn test_non_conforming_adapter_fails() {
+            let fixture = MockFixture {
+                honours_timeout: false,
+                duplicate_submissions: true,
+            };
+            let report = harness().run(&fixture).await;
+
+            assert!(!report.passed());
+            let failed: Vec<_> = report.failures().iter().map(|c| c.name.as_str()).collect();
+            assert_eq!(failed, vec!["idempotent_submission", "timeout"]);
+        }
+    }
 }
@@ -0,0 +1,503 @@
+//! # Tool Simulation
+//!
+//! Before a vetted tool executes an action for real, the invocation engine
+//! can run it against a simulator: a fork of the target chain's state or a
+//! mock of the banking endpoint. The simulator predicts the action's
+//! effects and cost without touching the real system.
+//!
+//! The resulting [`SimulationReport`] is handed to the testimony agents
+//! with the contract's conditions and attached to their results as
+//! evidence, so every testimony records what the agents expected the
+//! action to do.
+//!
+//! ## Simulators
+//!
+//! - [`EvmForkSimulator`]: applies transfers and contract calls to a copy of
+//!   a forked EVM state (balances, base fee) and reports gas and balance
+//!   changes
+//! - [`MockBankingSimulator`]: checks account balances and fees the way a
+//!   banking gateway would, without sending a payment message
+
+use async_trait::async_trait;
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use super::testimony_agent::{Evidence, EvidenceType};
+use super::tool_registry::*;
+
+/// Gas for a plain value transfer
+pub const TRANSFER_GAS: u64 = 21_000;
+
+/// Gas assumed for a contract call
+pub const CONTRACT_CALL_GAS: u64 = 100_000;
+
+/// Tool simulator interface
+#[async_trait]
+pub trait ToolSimulator: Send + Sync {
+    /// Simulator name, recorded in reports
+    fn name(&self) -> &str;
+
+    /// Check if this simulator models `tool`
+    fn can_simulate(&self, tool: &ToolMetadata, action: &ToolAction) -> bool;
+
+    /// Predict the effects of `action` without executing it
+    async fn simulate(&self, action: &ToolAction, context: &ExecutionContext) -> Simulation;
+}
+
+/// Outcome predicted by a simulator
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Simulation {
+    /// Would the action succeed?
+    pub success: bool,
+
+    /// Predicted effects, in order
+    pub effects: Vec<PredictedEffect>,
+
+    /// Predicted gas/fee
+    pub estimated_fee: Option<u64>,
+
+    /// Why the action would fail
+    pub error: Option<String>,
+}
+
+impl Simulation {
+    fn failed(error: impl Into<String>) -> Self {
+        Self {
+            success: false,
+            error: Some(error.into()),
+            ..Self::default()
+        }
+    }
+}
+
+/// Effect an action is predicted to have
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PredictedEffect {
+    /// Balance change in base units (wei, cents)
+    BalanceChange {
+        account: String,
+        asset: String,
+        delta: i128,
+    },
+    /// Contract method call
+    ContractCall { contract: String, method: String },
+    /// Message sent to an external system
+    ExternalMessage {
+        endpoint: String,
+        message_type: String,
+    },
+}
+
+/// Simulation of one action, attached to testimonies
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SimulationReport {
+    /// Simulated action
+    pub action_id: [u8; 32],
+
+    /// Tool that would execute it
+    pub tool_id: ToolId,
+
+    /// Simulator that produced the report
+    pub simulator: String,
+
+    /// Predicted outcome
+    pub simulation: Simulation,
+
+    /// Simulation timestamp
+    pub simulated_at: i64,
+}
+
+impl SimulationReport {
+    /// Hash committing to the report contents
+    pub fn hash(&self) -> [u8; 32] {
+        *blake3::hash(&serde_json::to_vec(self).unwrap_or_default()).as_bytes()
+    }
+
+    /// Report as testimony evidence
+    pub fn to_evidence(&self) -> Evidence {
+        Evidence {
+            evidence_type: EvidenceType::Custom("simulation".to_string()),
+            data: serde_json::to_vec(self).unwrap_or_default(),
+            hash: self.hash(),
+            source: self.simulator.clone(),
+        }
+    }
+}
+
+/// Parse a decimal amount into base units
+///
+/// Fails on negative numbers and on more fractional digits than the
+/// asset has.
+pub fn parse_units(amount: &str, decimals: u32) -> Option<u128> {
+    let (whole, fraction) = amount.trim().split_once('.').unwrap_or((amount.trim(), ""));
+    if fraction.len() > decimals as usize || whole.starts_with('-') {
+        return None;
+    }
+    let whole: u128 = if whole.is_empty() {
+        0
+    } else {
+        whole.parse().ok()?
+    };
+    let fraction: u128 = if fraction.is_empty() {
+        0
+    } else {
+        fraction.parse::<u128>().ok()? * 10u128.pow(decimals - fraction.len() as u32)
+    };
+    whole
+        .checked_mul(10u128.pow(decimals))?
+        .checked_add(fraction)
+}
+
+/// Account an action is sent from: `from_account` in the context metadata,
+/// or the caller's node ID
+fn source_account(action: &ToolAction, context: &ExecutionContext) -> String {
+    context
+        .metadata
+        .get("from_account")
+        .cloned()
+        .unwrap_or_else(|| format!("0x{}", hex::encode(&action.from[12..])))
+}
+
+/// Snapshot of EVM state taken at a fork point
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct EvmFork {
+    /// Block the state was forked at
+    pub block_number: u64,
+
+    /// Base fee per gas (wei)
+    pub base_fee_wei: u128,
+
+    /// Native asset symbol
+    pub native_asset: String,
+
+    /// Balances in wei, keyed by `asset:account`
+    pub balances: HashMap<String, u128>,
+}
+
+impl EvmFork {
+    /// Balance of `account` in `asset`
+    pub fn balance(&self, asset: &str, account: &str) -> u128 {
+        self.balances
+            .get(&format!("{}:{}", asset, account.to_lowercase()))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Set the balance of `account` in `asset`
+    pub fn set_balance(&mut self, asset: &str, account: &str, wei: u128) {
+        self.balances
+            .insert(format!("{}:{}", asset, account.to_lowercase()), wei);
+    }
+}
+
+/// Simulates blockchain tools against a forked EVM state
+///
+/// Each simulation works on a copy of the fork, so simulations never
+/// affect each other.
+pub struct EvmForkSimulator {
+    fork: RwLock<EvmFork>,
+}
+
+impl EvmForkSimulator {
+    pub fn new(fork: EvmFork) -> Self {
+        Self {
+            fork: RwLock::new(fork),
+        }
+    }
+
+    /// Move the simulator to a newer fork point
+    pub fn refork(&self, fork: EvmFork) {
+        *self.fork.write() = fork;
+    }
+}
+
+#[async_trait]
+impl ToolSimulator for EvmForkSimulator {
+    fn name(&self) -> &str {
+        "evm-fork"
+    }
+
+    fn can_simulate(&self, tool: &ToolMetadata, _action: &ToolAction) -> bool {
+        matches!(tool.category, ToolCategory::Blockchain(_))
+    }
+
+    async fn simulate(&self, action: &ToolAction, context: &ExecutionContext) -> Simulation {
+        let mut state = self.fork.read().clone();
+        let from = source_account(action, context);
+        let native = state.native_asset.clone();
+
+        let (gas, mut effects, transfer) = match &action.action_type {
+            ToolActionType::Transfer { asset, amount } => {
+                let Some(value) = parse_units(amount, 18) else {
+                    return Simulation::failed(format!("Invalid amount {}", amount));
+                };
+                (TRANSFER_GAS, Vec::new(), Some((asset.clone(), value)))
+            }
+            ToolActionType::ContractCall { method } => (
+                CONTRACT_CALL_GAS,
+                vec![PredictedEffect::ContractCall {
+                    contract: action.to.clone(),
+                    method: method.clone(),
+                }],
+                None,
+            ),
+            ToolActionType::Query { .. } => {
+                return Simulation {
+                    success: true,
+                    estimated_fee: Some(0),
+                    ..Simulation::default()
+                }
+            }
+            other => return Simulation::failed(format!("{:?} cannot be simulated", other)),
+        };
+
+        let fee = gas as u128 * state.base_fee_wei;
+        if let Some(budget) = context.fee_budget {
+            if gas > budget {
+                return Simulation::failed(format!("Needs {} gas, budget is {}", gas, budget));
+            }
+        }
+
+        // Debit the fee, then the transfer, from the forked balances
+        let mut debit = |asset: &str, account: &str, amount: u128| {
+            let balance = state.balance(asset, account);
+            if balance < amount {
+                return Err(format!(
+                    "{} has {} {} wei, needs {}",
+                    account, balance, asset, amount
+                ));
+            }
+            state.set_balance(asset, account, balance - amount);
+            Ok(())
+        };
+        if let Err(error) = debit(&native, &from, fee) {
+            return Simulation::failed(error);
+        }
+        if let Some((asset, value)) = &transfer {
+            if let Err(error) = debit(asset, &from, *value) {
+                return Simulation::failed(error);
+            }
+        }
+
+        if fee > 0 {
+            effects.push(PredictedEffect::BalanceChange {
+                account: from.clone(),
+                asset: native,
+                delta: -(fee as i128),
+            });
+        }
+        if let Some((asset, value)) = transfer {
+            effects.push(PredictedEffect::BalanceChange {
+                account: from,
+                asset: asset.clone(),
+                delta: -(value as i128),
+            });
+            effects.push(PredictedEffect::BalanceChange {
+                account: action.to.clone(),
+                asset,
+                delta: value as i128,
+            });
+        }
+
+        Simulation {
+            success: true,
+            effects,
+            estimated_fee: Some(gas),
+            error: None,
+        }
+    }
+}
+
+/// Simulates banking tools against mock accounts
+pub struct MockBankingSimulator {
+    /// Balances in cents, keyed by account
+    accounts: RwLock<HashMap<String, u128>>,
+
+    /// Flat fee per payment (cents)
+    fee_cents: u64,
+
+    /// Message type the gateway would send (e.g. MT103)
+    message_type: String,
+}
+
+impl MockBankingSimulator {
+    pub fn new(message_type: &str, fee_cents: u64) -> Self {
+        Self {
+            accounts: RwLock::new(HashMap::new()),
+            fee_cents,
+            message_type: message_type.to_string(),
+        }
+    }
+
+    /// Set an account balance (cents)
+    pub fn set_balance(&self, account: &str, cents: u128) {
+        self.accounts.write().insert(account.to_string(), cents);
+    }
+}
+
+#[async_trait]
+impl ToolSimulator for MockBankingSimulator {
+    fn name(&self) -> &str {
+        "mock-banking"
+    }
+
+    fn can_simulate(&self, tool: &ToolMetadata, action: &ToolAction) -> bool {
+        matches!(tool.category, ToolCategory::Banking(_))
+            && matches!(action.action_type, ToolActionType::Transfer { .. })
+    }
+
+    async fn simulate(&self, action: &ToolAction, context: &ExecutionContext) -> Simulation {
+        let ToolActionType::Transfer { asset, amount } = &action.action_type else {
+            return Simulation::failed("Only payments can be simulated");
+        };
+        let Some(cents) = parse_units(amount, 2) else {
+            return Simulation::failed(format!("Invalid amount {}", amount));
+        };
+
+        let from = source_account(action, context);
+        let balance = self.accounts.read().get(&from).copied().unwrap_or(0);
+        let total = cents + self.fee_cents as u128;
+        if balance < total {
+            return Simulation::failed(format!(
+                "Insufficient funds in {}: {} available, {} needed",
+                from, balance, total
+            ));
+        }
+
+        Simulation {
+            success: true,
+            effects: vec![
+                PredictedEffect::BalanceChange {
+                    account: from,
+                    asset: asset.clone(),
+                    delta: -(total as i128),
+                },
+                PredictedEffect::BalanceChange {
+                    account: action.to.clone(),
+                    asset: asset.clone(),
+                    delta: cents as i128,
+                },
+                PredictedEffect::ExternalMessage {
+                    endpoint: action.to.clone(),
+                    message_type: self.message_type.clone(),
+                },
+            ],
+            estimated_fee: Some(self.fee_cents),
+            error: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn action(action_type: ToolActionType, to: &str) -> ToolAction {
+        ToolAction {
+            id: [1u8; 32],
+            action_type,
+            from: [2u8; 32],
+            to: to.to_string(),
+            parameters: HashMap::new(),
+            contract_ref: None,
+            priority: ActionPriority::Normal,
+            timeout_secs: 60,
+        }
+    }
+
+    fn context(from: &str) -> ExecutionContext {
+        ExecutionContext {
+            caller: [2u8; 32],
+            timestamp: 0,
+            fee_budget: None,
+            testimony_signatures: Vec::new(),
+            metadata: HashMap::from([("from_account".to_string(), from.to_string())]),
+        }
+    }
+
+    #[test]
+    fn test_parse_units() {
+        assert_eq!(parse_units("1.5", 18), Some(1_500_000_000_000_000_000));
+        assert_eq!(parse_units("100", 2), Some(10_000));
+        assert_eq!(parse_units(".05", 2), Some(5));
+        assert_eq!(parse_units("1.005", 2), None);
+        assert_eq!(parse_units("-1", 2), None);
+    }
+
+    #[tokio::test]
+    async fn test_evm_fork_simulation() {
+        let mut fork = EvmFork {
+            block_number: 19_000_000,
+            base_fee_wei: 10,
+            native_asset: "ETH".to_string(),
+            ..EvmFork::default()
+        };
+        fork.set_balance("ETH", "0xAlice", 2_000_000_000_000_000_000);
+        let simulator = EvmForkSimulator::new(fork);
+
+        let transfer = action(
+            ToolActionType::Transfer {
+                asset: "ETH".to_string(),
+                amount: "1".to_string(),
+            },
+            "0xbob",
+        );
+        let simulation = simulator.simulate(&transfer, &context("0xalice")).await;
+        assert!(simulation.success);
+        assert_eq!(simulation.estimated_fee, Some(TRANSFER_GAS));
+        assert!(simulation
+            .effects
+            .contains(&PredictedEffect::BalanceChange {
+                account: "0xbob".to_string(),
+                asset: "ETH".to_string(),
+                delta: 1_000_000_000_000_000_000,
+            }));
+
+        // The fork itself is untouched, so the same transfer fits again
+        assert!(
+            simulator
+                .simulate(&transfer, &context("0xalice"))
+                .await
+                .success
+        );
+
+        let too_much = action(
+            ToolActionType::Transfer {
+                asset: "ETH".to_string(),
+                amount: "5".to_string(),
+            },
+            "0xbob",
+        );
+        let simulation = simulator.simulate(&too_much, &context("0xalice")).await;
+        assert!(!simulation.success);
+        assert!(simulation.error.unwrap().contains("needs"));
+    }
+
+    #[tokio::test]
+    async fn test_mock_banking_simulation() {
+        let simulator = MockBankingSimulator::new("MT103", 2_500);
+        simulator.set_balance("DE89370400440532013000", 100_000);
+        let payment = |amount: &str| {
+            action(
+                ToolActionType::Transfer {
+                    asset: "EUR".to_string(),
+                    amount: amount.to_string(),
+                },
+                "FR1420041010050500013M02606",
+            )
+        };
+
+        let simulation = simulator
+            .simulate(&payment("500.00"), &context("DE89370400440532013000"))
+            .await;
+        assert!(simulation.success);
+        assert_eq!(simulation.estimated_fee, Some(2_500));
+        assert_eq!(simulation.effects.len(), 3);
+
+        let simulation = simulator
+            .simulate(&payment("990.00"), &context("DE89370400440532013000"))
+            .await;
+        assert!(!simulation.success);
+    }
+}
@@ -16,10 +16,24 @@
 //! - **AnomalyAgent**: Detects suspicious patterns and fraud
 //! - **ComplianceAgent**: Ensures regulatory compliance (KYC/AML/GDPR)
 //! - **OracleAgent**: Bridges external data for contract evaluation
+//!
+//! ## Plugins and Model Versions
+//!
+//! Specialized agents (sanctions screening, trade surveillance, ...) are
+//! shipped as [`AgentPlugin`]s and registered at runtime in an
+//! [`AgentPluginRegistry`]. Each plugin is keyed by its [`ModelVersion`];
+//! several versions of the same model can be loaded side by side, so a
+//! verdict can be re-run on the exact model that produced it.
+//!
+//! Every agent reports its model, and every [`Testimony`] and
+//! [`ValidationResult`] carries it, so any verdict can be traced to the
+//! model name, version and weights that produced it.
 
 use async_trait::async_trait;
+use parking_lot::RwLock;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
 use uuid::Uuid;
 
 /// AI Testimony Agent trait - All AI agents must implement this
@@ -34,6 +48,9 @@ pub trait TestimonyAgent: Send + Sync {
     /// Agent capabilities
     fn capabilities(&self) -> &[AgentCapability];
 
+    /// Model behind the agent's verdicts
+    fn model(&self) -> &ModelVersion;
+
     /// Validate a contract condition
     async fn validate_condition(
         &self,
@@ -52,6 +69,161 @@ pub trait TestimonyAgent: Send + Sync {
     async fn is_healthy(&self) -> bool;
 }
 
+/// Model and version an agent's verdicts come from
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ModelVersion {
+    /// Model name (e.g. "sanctions-screening")
+    pub name: String,
+
+    /// Model version
+    pub version: String,
+
+    /// Hash of the weights, or of the rule set for rule-based agents
+    pub weights_hash: [u8; 32],
+}
+
+impl ModelVersion {
+    pub fn new(name: &str, version: &str, weights_hash: [u8; 32]) -> Self {
+        Self {
+            name: name.to_string(),
+            version: version.to_string(),
+            weights_hash,
+        }
+    }
+
+    /// Version of a rule-based agent without weights
+    pub fn rule_based(name: &str, version: &str) -> Self {
+        let weights_hash = *blake3::hash(format!("{}@{}", name, version).as_bytes()).as_bytes();
+        Self::new(name, version, weights_hash)
+    }
+
+    /// `name@version`
+    pub fn key(&self) -> String {
+        format!("{}@{}", self.name, self.version)
+    }
+}
+
+/// Factory for a specialized testimony agent
+///
+/// Registered in an [`AgentPluginRegistry`] and instantiated per node.
+pub trait AgentPlugin: Send + Sync {
+    /// Model every agent from this plugin reports
+    fn model(&self) -> ModelVersion;
+
+    /// Type of agent the plugin creates
+    fn agent_type(&self) -> AgentType;
+
+    /// Create an agent for `node_id`
+    fn instantiate(&self, node_id: [u8; 32], public_key: Vec<u8>) -> Arc<dyn TestimonyAgent>;
+}
+
+/// Versions of one model's plugin
+type PluginVersions = BTreeMap<String, Arc<dyn AgentPlugin>>;
+
+/// Plugins loaded at runtime, keyed by model name and version
+#[derive(Default)]
+pub struct AgentPluginRegistry {
+    plugins: RwLock<BTreeMap<String, PluginVersions>>,
+}
+
+impl AgentPluginRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load a plugin; each model version can be loaded once
+    pub fn register(&self, plugin: Arc<dyn AgentPlugin>) -> Result<ModelVersion, PluginError> {
+        let model = plugin.model();
+        let mut plugins = self.plugins.write();
+        let versions = plugins.entry(model.name.clone()).or_default();
+        if versions.contains_key(&model.version) {
+            return Err(PluginError::AlreadyRegistered(model.key()));
+        }
+        versions.insert(model.version.clone(), plugin);
+
+        tracing::info!("Loaded testimony agent plugin {}", model.key());
+        Ok(model)
+    }
+
+    /// Unload a model version
+    pub fn unregister(&self, name: &str, version: &str) -> bool {
+        let mut plugins = self.plugins.write();
+        let Some(versions) = plugins.get_mut(name) else {
+            return false;
+        };
+        let removed = versions.remove(version).is_some();
+        if versions.is_empty() {
+            plugins.remove(name);
+        }
+        removed
+    }
+
+    /// Instantiate `name` at `version`, or its latest loaded version
+    pub fn instantiate(
+        &self,
+        name: &str,
+        version: Option<&str>,
+        node_id: [u8; 32],
+        public_key: Vec<u8>,
+    ) -> Result<Arc<dyn TestimonyAgent>, PluginError> {
+        let plugins = self.plugins.read();
+        let versions = plugins
+            .get(name)
+            .ok_or_else(|| PluginError::NotFound(name.to_string()))?;
+        let plugin = match version {
+            Some(version) => versions.get(version),
+            None => versions
+                .values()
+                .max_by(|a, b| compare_versions(&a.model().version, &b.model().version)),
+        }
+        .ok_or_else(|| PluginError::NotFound(format!("{}@{}", name, version.unwrap_or("*"))))?;
+
+        let agent = plugin.instantiate(node_id, public_key);
+        if agent.model() != &plugin.model() {
+            return Err(PluginError::ModelMismatch(plugin.model().key()));
+        }
+        Ok(agent)
+    }
+
+    /// Loaded model versions
+    pub fn models(&self) -> Vec<ModelVersion> {
+        self.plugins
+            .read()
+            .values()
+            .flat_map(|versions| versions.values().map(|p| p.model()))
+            .collect()
+    }
+}
+
+/// Compare dotted version strings numerically where possible
+fn compare_versions(a: &str, b: &str) -> std::cmp::Ordering {
+    let parts = |v: &str| -> Vec<u64> { v.split('.').map(|p| p.parse().unwrap_or(0)).collect() };
+    parts(a).cmp(&parts(b)).then_with(|| a.cmp(b))
+}
+
+/// Plugin registry errors
+#[derive(Clone, Debug, PartialEq)]
+pub enum PluginError {
+    AlreadyRegistered(String),
+    NotFound(String),
+    /// Agent reports a different model than its plugin
+    ModelMismatch(String),
+}
+
+impl std::fmt::Display for PluginError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PluginError::AlreadyRegistered(m) => write!(f, "Plugin {} already registered", m),
+            PluginError::NotFound(m) => write!(f, "Plugin {} not found", m),
+            PluginError::ModelMismatch(m) => {
+                write!(f, "Agent from plugin {} reports a different model", m)
+            }
+        }
+    }
+}
+
+impl std::error::Error for PluginError {}
+
 /// Unique agent identifier
 #[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct AgentId {
@@ -423,6 +595,32 @@ pub struct ValidationResult {
 
     /// Agent's signature on result
     pub signature: Vec<u8>,
+
+    /// Model that produced the verdict
+    #[serde(default)]
+    pub model: Option<ModelVersion>,
+}
+
+/// One agent's verdict within an aggregated validation
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AgentVerdict {
+    pub agent_id: AgentId,
+    pub model: ModelVersion,
+    pub satisfied: bool,
+    pub confidence: f64,
+}
+
+impl AgentVerdict {
+    /// Verdict as evidence on the aggregated result
+    pub fn to_evidence(&self) -> Evidence {
+        let data = serde_json::to_vec(self).unwrap_or_default();
+        Evidence {
+            evidence_type: EvidenceType::Custom("agent_verdict".to_string()),
+            hash: *blake3::hash(&data).as_bytes(),
+            data,
+            source: self.model.key(),
+        }
+    }
 }
 
 /// Evidence supporting a validation
@@ -478,6 +676,10 @@ pub struct Testimony {
     /// Reasoning (can be audited)
     pub reasoning: String,
 
+    /// Model that produced the decision
+    #[serde(default)]
+    pub model: ModelVersion,
+
     /// Timestamp
     pub timestamp: i64,
 
@@ -515,12 +717,14 @@ pub enum TestimonyDecision {
 pub struct ValidationAgent {
     id: AgentId,
     capabilities: Vec<AgentCapability>,
+    model: ModelVersion,
 }
 
 impl ValidationAgent {
     pub fn new(node_id: [u8; 32], public_key: Vec<u8>) -> Self {
         Self {
             id: AgentId::new(node_id, public_key),
+            model: ModelVersion::rule_based("validation-rules", "1.0.0"),
             capabilities: vec![
                 AgentCapability::FinancialTransaction,
                 AgentCapability::AssetTransfer,
@@ -543,6 +747,10 @@ impl TestimonyAgent for ValidationAgent {
         &self.capabilities
     }
 
+    fn model(&self) -> &ModelVersion {
+        &self.model
+    }
+
     async fn validate_condition(
         &self,
         condition: &ContractCondition,
@@ -583,6 +791,7 @@ impl TestimonyAgent for ValidationAgent {
             },
             evidence: Vec::new(),
             signature: Vec::new(),
+            model: Some(self.model.clone()),
         }
     }
 
@@ -598,6 +807,7 @@ impl TestimonyAgent for ValidationAgent {
             decision: TestimonyDecision::Approve,
             confidence: 0.95,
             reasoning: "Transaction validated by ValidationAgent".to_string(),
+            model: self.model.clone(),
             timestamp: chrono::Utc::now().timestamp(),
             signature: Vec::new(),
         }
@@ -612,12 +822,14 @@ impl TestimonyAgent for ValidationAgent {
 pub struct InsuranceAgent {
     id: AgentId,
     capabilities: Vec<AgentCapability>,
+    model: ModelVersion,
 }
 
 impl InsuranceAgent {
     pub fn new(node_id: [u8; 32], public_key: Vec<u8>) -> Self {
         Self {
             id: AgentId::new(node_id, public_key),
+            model: ModelVersion::rule_based("insurance-claims", "1.0.0"),
             capabilities: vec![
                 AgentCapability::InsuranceClaim,
                 AgentCapability::IdentityVerification,
@@ -640,6 +852,10 @@ impl TestimonyAgent for InsuranceAgent {
         &self.capabilities
     }
 
+    fn model(&self) -> &ModelVersion {
+        &self.model
+    }
+
     async fn validate_condition(
         &self,
         condition: &ContractCondition,
@@ -664,6 +880,7 @@ impl TestimonyAgent for InsuranceAgent {
                     ),
                     evidence: Vec::new(),
                     signature: Vec::new(),
+                    model: Some(self.model.clone()),
                 }
             }
             _ => ValidationResult {
@@ -672,6 +889,7 @@ impl TestimonyAgent for InsuranceAgent {
                 reason: "Not an insurance condition".to_string(),
                 evidence: Vec::new(),
                 signature: Vec::new(),
+                model: Some(self.model.clone()),
             },
         }
     }
@@ -688,6 +906,7 @@ impl TestimonyAgent for InsuranceAgent {
             decision: TestimonyDecision::Approve,
             confidence: 0.85,
             reasoning: "Insurance claim validated by InsuranceAgent".to_string(),
+            model: self.model.clone(),
             timestamp: chrono::Utc::now().timestamp(),
             signature: Vec::new(),
         }
@@ -702,12 +921,14 @@ impl TestimonyAgent for InsuranceAgent {
 pub struct ComplianceAgent {
     id: AgentId,
     capabilities: Vec<AgentCapability>,
+    model: ModelVersion,
 }
 
 impl ComplianceAgent {
     pub fn new(node_id: [u8; 32], public_key: Vec<u8>) -> Self {
         Self {
             id: AgentId::new(node_id, public_key),
+            model: ModelVersion::rule_based("compliance-screening", "1.0.0"),
             capabilities: vec![
                 AgentCapability::KycValidation,
                 AgentCapability::AmlScreening,
@@ -731,6 +952,10 @@ impl TestimonyAgent for ComplianceAgent {
         &self.capabilities
     }
 
+    fn model(&self) -> &ModelVersion {
+        &self.model
+    }
+
     async fn validate_condition(
         &self,
         condition: &ContractCondition,
@@ -745,6 +970,7 @@ impl TestimonyAgent for ComplianceAgent {
                     reason: format!("{:?} check passed", check_type),
                     evidence: Vec::new(),
                     signature: Vec::new(),
+                    model: Some(self.model.clone()),
                 }
             }
             _ => ValidationResult {
@@ -753,6 +979,7 @@ impl TestimonyAgent for ComplianceAgent {
                 reason: "No compliance requirements".to_string(),
                 evidence: Vec::new(),
                 signature: Vec::new(),
+                model: Some(self.model.clone()),
             },
         }
     }
@@ -769,6 +996,7 @@ impl TestimonyAgent for ComplianceAgent {
             decision: TestimonyDecision::Approve,
             confidence: 0.99,
             reasoning: "Compliance checks passed".to_string(),
+            model: self.model.clone(),
             timestamp: chrono::Utc::now().timestamp(),
             signature: Vec::new(),
         }
@@ -817,4 +1045,83 @@ mod tests {
         let result = agent.validate_condition(&condition, &context).await;
         assert!(result.satisfied);
     }
+
+    /// Sanctions screening plugin backed by the compliance agent
+    struct SanctionsPlugin {
+        version: &'static str,
+    }
+
+    impl AgentPlugin for SanctionsPlugin {
+        fn model(&self) -> ModelVersion {
+            ModelVersion::new(
+                "sanctions-screening",
+                self.version,
+                *blake3::hash(self.version.as_bytes()).as_bytes(),
+            )
+        }
+
+        fn agent_type(&self) -> AgentType {
+            AgentType::Custom("sanctions".to_string())
+        }
+
+        fn instantiate(&self, node_id: [u8; 32], public_key: Vec<u8>) -> Arc<dyn TestimonyAgent> {
+            let mut agent = ComplianceAgent::new(node_id, public_key);
+            agent.model = self.model();
+            Arc::new(agent)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_plugin_versions() {
+        let registry = AgentPluginRegistry::new();
+        registry
+            .register(Arc::new(SanctionsPlugin { version: "1.2.0" }))
+            .unwrap();
+        registry
+            .register(Arc::new(SanctionsPlugin { version: "1.10.0" }))
+            .unwrap();
+        assert_eq!(
+            registry.register(Arc::new(SanctionsPlugin { version: "1.2.0" })),
+            Err(PluginError::AlreadyRegistered(
+                "sanctions-screening@1.2.0".to_string()
+            ))
+        );
+        assert_eq!(registry.models().len(), 2);
+
+        // Latest by default, pinned version on request
+        let latest = registry
+            .instantiate("sanctions-screening", None, [1u8; 32], Vec::new())
+            .unwrap();
+        assert_eq!(latest.model().version, "1.10.0");
+        let pinned = registry
+            .instantiate("sanctions-screening", Some("1.2.0"), [1u8; 32], Vec::new())
+            .unwrap();
+        assert_eq!(pinned.model().version, "1.2.0");
+
+        // Verdicts carry the model that produced them
+        let transaction = TransactionRequest {
+            id: [2u8; 32],
+            contract_id: None,
+            from: [3u8; 32],
+            to: [4u8; 32],
+            action: ActionType::Payment,
+            parameters: HashMap::new(),
+            timestamp: 0,
+        };
+        let context = ValidationContext {
+            timestamp: 0,
+            requester: [3u8; 32],
+            historical_data: HashMap::new(),
+            oracle_data: HashMap::new(),
+            risk_score: None,
+        };
+        let testimony = pinned.provide_testimony(&transaction, &context).await;
+        assert_eq!(testimony.model, *pinned.model());
+
+        assert!(registry.unregister("sanctions-screening", "1.2.0"));
+        assert!(matches!(
+            registry.instantiate("sanctions-screening", Some("1.2.0"), [1u8; 32], Vec::new()),
+            Err(PluginError::NotFound(_))
+        ));
+    }
 }
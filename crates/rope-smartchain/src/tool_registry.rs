@@ -17,6 +17,9 @@
 //! 3. Security review by multiple AI agents
 //! 4. Staged rollout (testnet → mainnet)
 //! 5. Continuous monitoring and scoring
+//!
+//! Steps 1 and 2 are handled by [`VettedToolRegistry`](crate::VettedToolRegistry),
+//! which pins approved versions and can revoke a tool in an emergency.
 
 use async_trait::async_trait;
 use parking_lot::RwLock;
@@ -44,6 +47,13 @@ pub trait VettedTool: Send + Sync {
 
     /// Get current rate limits
     fn rate_limits(&self) -> &RateLimits;
+
+    /// Hash of the tool's code or image, pinned at registration
+    ///
+    /// In-process tools have no separate artifact.
+    fn artifact_hash(&self) -> [u8; 32] {
+        [0u8; 32]
+    }
 }
 
 /// Tool identifier
@@ -422,7 +432,8 @@ impl ToolRegistry {
         let mut best_score = 0u8;
 
         for (id, tool) in tools.iter() {
-            if tool.can_handle(action) {
+            let active = metadata.get(id).map(|m| m.is_active).unwrap_or(false);
+            if active && tool.can_handle(action) {
                 let trust = metadata.get(id).map(|m| m.trust_score).unwrap_or(0);
                 let healthy = health
                     .get(id)
@@ -483,6 +494,17 @@ pub enum RegistryError {
     AlreadyRegistered,
     NotFound,
     PermissionDenied,
+    AuditMismatch,
+    AuditScoreTooLow(u8),
+    ProposalNotFound,
+    AlreadyVoted,
+    NotApproved,
+    ProposalExpired,
+    ManifestMismatch,
+    NotPinned,
+    Revoked(String),
+    ConformanceRequired,
+    ConformanceFailed(String),
 }
 
 impl std::fmt::Display for RegistryError {
@@ -495,6 +517,25 @@ impl std::fmt::Display for RegistryError {
             RegistryError::AlreadyRegistered => write!(f, "Tool already registered"),
             RegistryError::NotFound => write!(f, "Tool not found"),
             RegistryError::PermissionDenied => write!(f, "Permission denied"),
+            RegistryError::AuditMismatch => {
+                write!(f, "Audit report does not match the manifest's report hash")
+            }
+            RegistryError::AuditScoreTooLow(s) => write!(f, "Audit score too low: {}", s),
+            RegistryError::ProposalNotFound => write!(f, "Tool proposal not found"),
+            RegistryError::AlreadyVoted => write!(f, "Already voted on this proposal"),
+            RegistryError::NotApproved => write!(f, "Tool proposal not approved"),
+            RegistryError::ProposalExpired => write!(f, "Tool proposal expired"),
+            RegistryError::ManifestMismatch => {
+                write!(f, "Tool does not match its pinned manifest")
+            }
+            RegistryError::NotPinned => write!(f, "No governance-approved version pinned"),
+            RegistryError::Revoked(reason) => write!(f, "Tool revoked: {}", reason),
+            RegistryError::ConformanceRequired => {
+                write!(f, "Adapter conformance report required for this tool")
+            }
+            RegistryError::ConformanceFailed(checks) => {
+                write!(f, "Adapter failed conformance checks: {}", checks)
+            }
         }
     }
 }
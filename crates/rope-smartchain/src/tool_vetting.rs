@@ -0,0 +1,765 @@
+//! # Tool Vetting
+//!
+//! Governance-gated lifecycle for vetted tools:
+//!
+//! 1. A developer submits a [`ToolManifest`] with the audit report it
+//!    references; the report is checked against the manifest's hash
+//! 2. The submission becomes a federation proposal, approved by a quorum of
+//!    federation members
+//! 3. The approved tool is registered and its version pinned to the
+//!    manifest hash; every invocation re-checks the live tool against the pin
+//! 4. Any federation member can revoke a tool in an emergency, which blocks
+//!    further invocations immediately
+//!
+//! Only one version of a tool is pinned at a time. Activating a new version
+//! retires the old one.
+//!
+//! Banking and finance tools talk to their endpoints through a protocol
+//! adapter, so they can only be activated once a passing adapter
+//! [`ConformanceReport`] is attached to their proposal.
+
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use super::protocol_adapters::conformance::ConformanceReport;
+use super::tool_registry::*;
+
+/// Tool vetting configuration
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ToolGovernanceConfig {
+    /// Federation approvals needed to accept a tool
+    pub required_approvals: u32,
+
+    /// Voting timeout in seconds
+    pub voting_timeout_secs: u64,
+
+    /// Minimum audit score accepted for submission
+    pub min_audit_score: u8,
+}
+
+impl Default for ToolGovernanceConfig {
+    fn default() -> Self {
+        Self {
+            required_approvals: 3,
+            voting_timeout_secs: 7 * 86400, // 1 week
+            min_audit_score: 70,
+        }
+    }
+}
+
+/// What a tool claims to be, submitted for vetting
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ToolManifest {
+    /// Tool name and version
+    pub tool_id: ToolId,
+
+    /// Category, permissions, audit info, etc.
+    pub metadata: ToolMetadata,
+
+    /// Hash of the tool's code or image
+    pub artifact_hash: [u8; 32],
+}
+
+impl ToolManifest {
+    /// Manifest describing a live tool
+    pub fn of(tool: &dyn VettedTool) -> Self {
+        Self {
+            tool_id: tool.tool_id().clone(),
+            metadata: tool.metadata().clone(),
+            artifact_hash: tool.artifact_hash(),
+        }
+    }
+
+    /// Hash the version is pinned to
+    pub fn hash(&self) -> [u8; 32] {
+        *blake3::hash(&serde_json::to_vec(self).unwrap_or_default()).as_bytes()
+    }
+}
+
+/// Tool proposal status
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ToolProposalStatus {
+    /// Awaiting federation votes
+    Voting,
+    /// Quorum reached, ready to activate
+    Approved,
+    /// Registered and pinned
+    Activated,
+    /// Rejected by the federation
+    Rejected,
+    /// Voting timed out
+    Expired,
+}
+
+/// Federation member vote on a tool
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ToolVote {
+    pub member_wallet: [u8; 32],
+    pub approved: bool,
+    pub comment: Option<String>,
+    pub timestamp: i64,
+    pub signature: Vec<u8>,
+}
+
+/// Federation proposal to accept a tool
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ToolProposal {
+    /// Proposal ID
+    pub id: [u8; 32],
+
+    /// Submitted manifest
+    pub manifest: ToolManifest,
+
+    /// Manifest hash
+    pub manifest_hash: [u8; 32],
+
+    /// Submitter wallet
+    pub submitter: [u8; 32],
+
+    /// Created timestamp
+    pub created_at: i64,
+
+    /// Voting deadline
+    pub expires_at: i64,
+
+    /// Current status
+    pub status: ToolProposalStatus,
+
+    /// Federation votes
+    pub votes: Vec<ToolVote>,
+
+    /// Adapter conformance report, required for banking and finance tools
+    #[serde(default)]
+    pub conformance: Option<ConformanceReport>,
+}
+
+/// Pinned tool version
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PinnedTool {
+    pub tool_id: ToolId,
+    pub manifest_hash: [u8; 32],
+    pub proposal_id: [u8; 32],
+    pub pinned_at: i64,
+}
+
+/// Emergency revocation record
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ToolRevocation {
+    pub tool_id: ToolId,
+    pub revoked_by: [u8; 32],
+    pub reason: String,
+    pub revoked_at: i64,
+}
+
+/// Governance-gated front end to the [`ToolRegistry`]
+pub struct VettedToolRegistry {
+    /// Configuration
+    config: ToolGovernanceConfig,
+
+    /// Underlying registry used for invocation
+    registry: Arc<ToolRegistry>,
+
+    /// Federation member wallets
+    federation: RwLock<HashSet<[u8; 32]>>,
+
+    /// Submitted proposals
+    proposals: RwLock<HashMap<[u8; 32], ToolProposal>>,
+
+    /// Pinned versions, keyed by tool name
+    pinned: RwLock<HashMap<String, PinnedTool>>,
+
+    /// Revoked tool IDs
+    revoked: RwLock<HashMap<[u8; 32], ToolRevocation>>,
+}
+
+impl VettedToolRegistry {
+    pub fn new(registry: Arc<ToolRegistry>) -> Self {
+        Self::with_config(registry, ToolGovernanceConfig::default())
+    }
+
+    pub fn with_config(registry: Arc<ToolRegistry>, config: ToolGovernanceConfig) -> Self {
+        Self {
+            config,
+            registry,
+            federation: RwLock::new(HashSet::new()),
+            proposals: RwLock::new(HashMap::new()),
+            pinned: RwLock::new(HashMap::new()),
+            revoked: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Underlying tool registry
+    pub fn registry(&self) -> &Arc<ToolRegistry> {
+        &self.registry
+    }
+
+    /// Register a federation member
+    pub fn register_member(&self, wallet: [u8; 32]) {
+        self.federation.write().insert(wallet);
+    }
+
+    /// Submit a tool for federation approval
+    ///
+    /// `audit_report` must hash to the manifest's audit report hash.
+    pub fn submit(
+        &self,
+        manifest: ToolManifest,
+        audit_report: &[u8],
+        submitter: [u8; 32],
+    ) -> Result<ToolProposal, RegistryError> {
+        let audit = &manifest.metadata.audit;
+        if *blake3::hash(audit_report).as_bytes() != audit.report_hash {
+            return Err(RegistryError::AuditMismatch);
+        }
+        if audit.score < self.config.min_audit_score {
+            return Err(RegistryError::AuditScoreTooLow(audit.score));
+        }
+        let now = chrono::Utc::now().timestamp();
+        if now > audit.next_audit_due {
+            return Err(RegistryError::AuditExpired);
+        }
+        if manifest.metadata.trust_score < 50 {
+            return Err(RegistryError::InsufficientTrustScore(
+                manifest.metadata.trust_score,
+            ));
+        }
+        if self.revoked.read().contains_key(&manifest.tool_id.id) {
+            return Err(RegistryError::Revoked(manifest.tool_id.name.clone()));
+        }
+
+        let manifest_hash = manifest.hash();
+        if self.proposals.read().values().any(|p| {
+            p.manifest_hash == manifest_hash
+                && matches!(
+                    p.status,
+                    ToolProposalStatus::Voting
+                        | ToolProposalStatus::Approved
+                        | ToolProposalStatus::Activated
+                )
+        }) {
+            return Err(RegistryError::AlreadyRegistered);
+        }
+
+        let id = *blake3::hash(&[&manifest_hash[..], &submitter[..], &now.to_le_bytes()].concat())
+            .as_bytes();
+        let proposal = ToolProposal {
+            id,
+            manifest,
+            manifest_hash,
+            submitter,
+            created_at: now,
+            expires_at: now + self.config.voting_timeout_secs as i64,
+            status: ToolProposalStatus::Voting,
+            votes: Vec::new(),
+            conformance: None,
+        };
+
+        tracing::info!(
+            "Tool {} {} submitted for vetting",
+            proposal.manifest.tool_id.name,
+            proposal.manifest.tool_id.version
+        );
+        self.proposals.write().insert(id, proposal.clone());
+
+        Ok(proposal)
+    }
+
+    /// Submit a federation member's vote
+    pub fn vote(&self, proposal_id: &[u8; 32], vote: ToolVote) -> Result<(), RegistryError> {
+        let members = self.federation.read();
+        if !members.contains(&vote.member_wallet) {
+            return Err(RegistryError::PermissionDenied);
+        }
+
+        let mut proposals = self.proposals.write();
+        let proposal = proposals
+            .get_mut(proposal_id)
+            .ok_or(RegistryError::ProposalNotFound)?;
+
+        if chrono::Utc::now().timestamp() > proposal.expires_at {
+            proposal.status = ToolProposalStatus::Expired;
+        }
+        match proposal.status {
+            ToolProposalStatus::Voting => {}
+            ToolProposalStatus::Expired => return Err(RegistryError::ProposalExpired),
+            _ => return Err(RegistryError::NotApproved),
+        }
+
+        if proposal
+            .votes
+            .iter()
+            .any(|v| v.member_wallet == vote.member_wallet)
+        {
+            return Err(RegistryError::AlreadyVoted);
+        }
+        proposal.votes.push(vote);
+
+        let approvals = proposal.votes.iter().filter(|v| v.approved).count();
+        let rejections = proposal.votes.len() - approvals;
+        let required = self.config.required_approvals as usize;
+
+        if approvals >= required {
+            proposal.status = ToolProposalStatus::Approved;
+        } else if members.len().saturating_sub(rejections) < required {
+            // Quorum can no longer be reached
+            proposal.status = ToolProposalStatus::Rejected;
+        }
+
+        Ok(())
+    }
+
+    /// Attach a passing adapter conformance report to a proposal
+    pub fn attach_conformance(
+        &self,
+        proposal_id: &[u8; 32],
+        report: ConformanceReport,
+    ) -> Result<(), RegistryError> {
+        if !report.passed() {
+            let failed: Vec<_> = report.failures().iter().map(|c| c.name.clone()).collect();
+            return Err(RegistryError::ConformanceFailed(failed.join(", ")));
+        }
+
+        let mut proposals = self.proposals.write();
+        let proposal = proposals
+            .get_mut(proposal_id)
+            .ok_or(RegistryError::ProposalNotFound)?;
+        if !matches!(
+            proposal.status,
+            ToolProposalStatus::Voting | ToolProposalStatus::Approved
+        ) {
+            return Err(RegistryError::NotApproved);
+        }
+
+        tracing::info!(
+            "Conformance report for adapter {} attached to tool {}",
+            report.adapter,
+            proposal.manifest.tool_id.name
+        );
+        proposal.conformance = Some(report);
+        Ok(())
+    }
+
+    /// Register and pin an approved tool
+    ///
+    /// The live tool must match the approved manifest exactly. A previously
+    /// pinned version of the same tool is deactivated.
+    pub fn activate(
+        &self,
+        proposal_id: &[u8; 32],
+        tool: Arc<dyn VettedTool>,
+    ) -> Result<PinnedTool, RegistryError> {
+        let mut proposals = self.proposals.write();
+        let proposal = proposals
+            .get_mut(proposal_id)
+            .ok_or(RegistryError::ProposalNotFound)?;
+
+        if proposal.status != ToolProposalStatus::Approved {
+            return Err(RegistryError::NotApproved);
+        }
+        if ToolManifest::of(tool.as_ref()).hash() != proposal.manifest_hash {
+            return Err(RegistryError::ManifestMismatch);
+        }
+        if requires_conformance(&proposal.manifest.metadata.category)
+            && proposal.conformance.is_none()
+        {
+            return Err(RegistryError::ConformanceRequired);
+        }
+        if self.revoked.read().contains_key(&tool.tool_id().id) {
+            return Err(RegistryError::Revoked(tool.tool_id().name.clone()));
+        }
+
+        self.registry.register_tool(tool.clone())?;
+
+        let pin = PinnedTool {
+            tool_id: tool.tool_id().clone(),
+            manifest_hash: proposal.manifest_hash,
+            proposal_id: *proposal_id,
+            pinned_at: chrono::Utc::now().timestamp(),
+        };
+        let previous = self
+            .pinned
+            .write()
+            .insert(pin.tool_id.name.clone(), pin.clone());
+        if let Some(previous) = previous.filter(|p| p.tool_id != pin.tool_id) {
+            self.registry.deactivate_tool(&previous.tool_id.id);
+        }
+        proposal.status = ToolProposalStatus::Activated;
+
+        tracing::info!(
+            "Tool {} pinned at version {}",
+            pin.tool_id.name,
+            pin.tool_id.version
+        );
+        Ok(pin)
+    }
+
+    /// Check a tool against its pin before invoking it
+    pub fn verify(&self, tool: &dyn VettedTool) -> Result<(), RegistryError> {
+        let tool_id = tool.tool_id();
+        if let Some(revocation) = self.revoked.read().get(&tool_id.id) {
+            return Err(RegistryError::Revoked(revocation.reason.clone()));
+        }
+
+        let pinned = self.pinned.read();
+        let pin = pinned.get(&tool_id.name).ok_or(RegistryError::NotPinned)?;
+        if pin.tool_id != *tool_id {
+            return Err(RegistryError::NotPinned);
+        }
+        if ToolManifest::of(tool).hash() != pin.manifest_hash {
+            return Err(RegistryError::ManifestMismatch);
+        }
+
+        Ok(())
+    }
+
+    /// Emergency revocation by any federation member
+    ///
+    /// Takes effect immediately; the tool has to go through vetting again
+    /// under a new version to return.
+    pub fn revoke(
+        &self,
+        tool_id: &ToolId,
+        member_wallet: [u8; 32],
+        reason: &str,
+    ) -> Result<ToolRevocation, RegistryError> {
+        if !self.federation.read().contains(&member_wallet) {
+            return Err(RegistryError::PermissionDenied);
+        }
+
+        let revocation = ToolRevocation {
+            tool_id: tool_id.clone(),
+            revoked_by: member_wallet,
+            reason: reason.to_string(),
+            revoked_at: chrono::Utc::now().timestamp(),
+        };
+        self.revoked.write().insert(tool_id.id, revocation.clone());
+        self.registry.deactivate_tool(&tool_id.id);
+
+        let mut pinned = self.pinned.write();
+        if pinned.get(&tool_id.name).map(|p| &p.tool_id) == Some(tool_id) {
+            pinned.remove(&tool_id.name);
+        }
+
+        tracing::warn!(
+            "Tool {} {} revoked: {}",
+            tool_id.name,
+            tool_id.version,
+            reason
+        );
+        Ok(revocation)
+    }
+
+    /// Get proposal details
+    pub fn get_proposal(&self, proposal_id: &[u8; 32]) -> Option<ToolProposal> {
+        self.proposals.read().get(proposal_id).cloned()
+    }
+
+    /// Pinned version of a tool
+    pub fn pinned(&self, name: &str) -> Option<PinnedTool> {
+        self.pinned.read().get(name).cloned()
+    }
+
+    /// Check if a tool has been revoked
+    pub fn is_revoked(&self, tool_id: &[u8; 32]) -> bool {
+        self.revoked.read().contains_key(tool_id)
+    }
+
+    /// Mark proposals past their voting deadline as expired
+    pub fn cleanup_expired(&self) -> usize {
+        let now = chrono::Utc::now().timestamp();
+        let mut expired = 0;
+        for proposal in self.proposals.write().values_mut() {
+            if proposal.status == ToolProposalStatus::Voting && now > proposal.expires_at {
+                proposal.status = ToolProposalStatus::Expired;
+                expired += 1;
+            }
+        }
+        expired
+    }
+}
+
+/// Banking and finance tools must pass adapter conformance
+fn requires_conformance(category: &ToolCategory) -> bool {
+    matches!(
+        category,
+        ToolCategory::Banking(_) | ToolCategory::Finance(_)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const REPORT: &[u8] = b"audit report";
+
+    struct TestTool {
+        id: ToolId,
+        metadata: ToolMetadata,
+        rate_limits: RateLimits,
+    }
+
+    #[async_trait::async_trait]
+    impl VettedTool for TestTool {
+        fn tool_id(&self) -> &ToolId {
+            &self.id
+        }
+
+        fn metadata(&self) -> &ToolMetadata {
+            &self.metadata
+        }
+
+        fn can_handle(&self, _action: &ToolAction) -> bool {
+            true
+        }
+
+        async fn execute(
+            &self,
+            _action: &ToolAction,
+            _context: &ExecutionContext,
+        ) -> ExecutionResult {
+            ExecutionResult {
+                success: true,
+                tx_hash: None,
+                data: None,
+                error: None,
+                fee_used: None,
+                execution_time_ms: 0,
+                proof: None,
+            }
+        }
+
+        async fn health_check(&self) -> ToolHealth {
+            ToolHealth {
+                is_healthy: true,
+                latency_ms: 0,
+                error_rate: 0.0,
+                last_success: None,
+                last_error: None,
+            }
+        }
+
+        fn rate_limits(&self) -> &RateLimits {
+            &self.rate_limits
+        }
+
+        fn artifact_hash(&self) -> [u8; 32] {
+            *blake3::hash(self.id.version.as_bytes()).as_bytes()
+        }
+    }
+
+    fn tool(version: &str) -> Arc<dyn VettedTool> {
+        tool_in(
+            "ethereum-bridge",
+            ToolCategory::Blockchain(BlockchainProtocol::Ethereum),
+            version,
+        )
+    }
+
+    fn tool_in(name: &str, category: ToolCategory, version: &str) -> Arc<dyn VettedTool> {
+        Arc::new(TestTool {
+            id: ToolId::new(name, version),
+            metadata: ToolMetadata {
+                category,
+                description: "Test bridge".to_string(),
+                developer: "Test".to_string(),
+                protocols: Vec::new(),
+                permissions: vec![ToolPermission::Read, ToolPermission::Write],
+                audit: AuditInfo {
+                    auditor: "Test Auditor".to_string(),
+                    audit_date: 1_700_000_000,
+                    report_hash: *blake3::hash(REPORT).as_bytes(),
+                    score: 95,
+                    next_audit_due: i64::MAX,
+                },
+                trust_score: 90,
+                is_active: true,
+            },
+            rate_limits: RateLimits::default(),
+        })
+    }
+
+    fn vetted() -> VettedToolRegistry {
+        let vetted = VettedToolRegistry::new(Arc::new(ToolRegistry::new()));
+        for i in 0..4 {
+            vetted.register_member([i as u8; 32]);
+        }
+        vetted
+    }
+
+    fn approve(vetted: &VettedToolRegistry, proposal_id: &[u8; 32]) {
+        for i in 0..3 {
+            vetted
+                .vote(
+                    proposal_id,
+                    ToolVote {
+                        member_wallet: [i as u8; 32],
+                        approved: true,
+                        comment: None,
+                        timestamp: chrono::Utc::now().timestamp(),
+                        signature: Vec::new(),
+                    },
+                )
+                .unwrap();
+        }
+    }
+
+    #[test]
+    fn test_submission_requires_matching_audit() {
+        let vetted = vetted();
+        let manifest = ToolManifest::of(tool("1.0.0").as_ref());
+
+        assert!(matches!(
+            vetted.submit(manifest.clone(), b"forged report", [9u8; 32]),
+            Err(RegistryError::AuditMismatch)
+        ));
+        assert!(vetted.submit(manifest.clone(), REPORT, [9u8; 32]).is_ok());
+        assert!(matches!(
+            vetted.submit(manifest, REPORT, [9u8; 32]),
+            Err(RegistryError::AlreadyRegistered)
+        ));
+    }
+
+    #[test]
+    fn test_approval_pins_version() {
+        let vetted = vetted();
+        let v1 = tool("1.0.0");
+        let proposal = vetted
+            .submit(ToolManifest::of(v1.as_ref()), REPORT, [9u8; 32])
+            .unwrap();
+
+        // Not usable until the federation approves
+        assert!(matches!(
+            vetted.activate(&proposal.id, v1.clone()),
+            Err(RegistryError::NotApproved)
+        ));
+        assert!(matches!(
+            vetted.verify(v1.as_ref()),
+            Err(RegistryError::NotPinned)
+        ));
+
+        approve(&vetted, &proposal.id);
+        assert_eq!(
+            vetted.get_proposal(&proposal.id).unwrap().status,
+            ToolProposalStatus::Approved
+        );
+
+        // A different build of the tool can't be activated in its place
+        assert!(matches!(
+            vetted.activate(&proposal.id, tool("1.0.1")),
+            Err(RegistryError::ManifestMismatch)
+        ));
+
+        vetted.activate(&proposal.id, v1.clone()).unwrap();
+        assert!(vetted.verify(v1.as_ref()).is_ok());
+        assert!(matches!(
+            vetted.verify(tool("1.0.1").as_ref()),
+            Err(RegistryError::NotPinned)
+        ));
+        assert_eq!(
+            vetted.pinned("ethereum-bridge").unwrap().tool_id.version,
+            "1.0.0"
+        );
+    }
+
+    #[test]
+    fn test_quorum_unreachable_rejects() {
+        let vetted = vetted();
+        let proposal = vetted
+            .submit(ToolManifest::of(tool("1.0.0").as_ref()), REPORT, [9u8; 32])
+            .unwrap();
+
+        for i in 0..2 {
+            vetted
+                .vote(
+                    &proposal.id,
+                    ToolVote {
+                        member_wallet: [i as u8; 32],
+                        approved: false,
+                        comment: Some("Unreviewed dependencies".to_string()),
+                        timestamp: chrono::Utc::now().timestamp(),
+                        signature: Vec::new(),
+                    },
+                )
+                .unwrap();
+        }
+
+        assert_eq!(
+            vetted.get_proposal(&proposal.id).unwrap().status,
+            ToolProposalStatus::Rejected
+        );
+    }
+
+    #[test]
+    fn test_emergency_revocation() {
+        let vetted = vetted();
+        let v1 = tool("1.0.0");
+        let proposal = vetted
+            .submit(ToolManifest::of(v1.as_ref()), REPORT, [9u8; 32])
+            .unwrap();
+        approve(&vetted, &proposal.id);
+        vetted.activate(&proposal.id, v1.clone()).unwrap();
+
+        assert!(matches!(
+            vetted.revoke(v1.tool_id(), [9u8; 32], "not a member"),
+            Err(RegistryError::PermissionDenied)
+        ));
+        vetted
+            .revoke(v1.tool_id(), [0u8; 32], "Key compromise")
+            .unwrap();
+
+        assert!(matches!(
+            vetted.verify(v1.as_ref()),
+            Err(RegistryError::Revoked(_))
+        ));
+        assert!(vetted.pinned("ethereum-bridge").is_none());
+        assert!(vetted.registry().list_active_tools().is_empty());
+    }
+
+    #[test]
+    fn test_banking_tool_requires_conformance() {
+        use crate::protocol_adapters::conformance::{ConformanceCheck, CONFORMANCE_SUITE_VERSION};
+        use crate::protocol_adapters::{BankingType, ProtocolType};
+
+        let vetted = vetted();
+        let sepa = tool_in(
+            "sepa-gateway",
+            ToolCategory::Banking(BankingProtocol::Sepa),
+            "1.0.0",
+        );
+        let proposal = vetted
+            .submit(ToolManifest::of(sepa.as_ref()), REPORT, [9u8; 32])
+            .unwrap();
+        approve(&vetted, &proposal.id);
+
+        assert!(matches!(
+            vetted.activate(&proposal.id, sepa.clone()),
+            Err(RegistryError::ConformanceRequired)
+        ));
+
+        let report = |passed| ConformanceReport {
+            adapter: "SEPA".to_string(),
+            protocol_type: ProtocolType::Banking(BankingType::Sepa),
+            suite_version: CONFORMANCE_SUITE_VERSION,
+            checks: vec![ConformanceCheck {
+                name: "timeout".to_string(),
+                passed,
+                detail: None,
+                duration_ms: 0,
+            }],
+            started_at: 0,
+            finished_at: 0,
+        };
+        assert!(matches!(
+            vetted.attach_conformance(&proposal.id, report(false)),
+            Err(RegistryError::ConformanceFailed(failed)) if failed == "timeout"
+        ));
+        vetted
+            .attach_conformance(&proposal.id, report(true))
+            .unwrap();
+
+        vetted.activate(&proposal.id, sepa.clone()).unwrap();
+        assert!(vetted.verify(sepa.as_ref()).is_ok());
+    }
+}
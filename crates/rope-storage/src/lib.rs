@@ -6,12 +6,15 @@
 //!
 //! - `lattice_db/` - String Lattice persistence
 //! - `complement_db/` - Complement storage (separate for security)
+//! - `key_db/` - Per-string payload encryption keys (crypto-shredding)
+//! - `gossip_db/` - Gossip DAG events, DAG state and pruned-round archives
 //! - `state_db/` - OES and federation state
 
 pub mod lattice_db {
     //! Lattice persistence layer
 
     use parking_lot::RwLock;
+    use rope_core::{Result, StringId};
     use std::collections::HashMap;
 
     /// Simple in-memory lattice storage (RocksDB will replace this in production)
@@ -41,6 +44,11 @@ pub mod lattice_db {
         pub fn contains(&self, key: &[u8; 32]) -> bool {
             self.data.read().contains_key(key)
         }
+
+        /// Keys of every stored string
+        pub fn keys(&self) -> Vec<[u8; 32]> {
+            self.data.read().keys().copied().collect()
+        }
     }
 
     impl Default for LatticeStore {
@@ -48,6 +56,23 @@ pub mod lattice_db {
             Self::new()
         }
     }
+
+    /// Backing store for strings evicted from the lattice cache
+    impl rope_core::StringStore for LatticeStore {
+        fn put(&self, id: &StringId, bytes: Vec<u8>) -> Result<()> {
+            LatticeStore::put(self, *id.as_bytes(), bytes);
+            Ok(())
+        }
+
+        fn get(&self, id: &StringId) -> Result<Option<Vec<u8>>> {
+            Ok(LatticeStore::get(self, id.as_bytes()))
+        }
+
+        fn delete(&self, id: &StringId) -> Result<()> {
+            LatticeStore::delete(self, id.as_bytes());
+            Ok(())
+        }
+    }
 }
 
 pub mod complement_db {
@@ -88,6 +113,127 @@ pub mod complement_db {
     }
 }
 
+pub mod key_db {
+    //! Payload key storage
+    //!
+    //! Payloads are encrypted under per-string keys; destroying the key makes
+    //! every replica of the ciphertext unreadable (crypto-shredding).
+
+    use parking_lot::RwLock;
+    use std::collections::HashMap;
+
+    /// Per-string payload key storage
+    pub struct PayloadKeyStore {
+        keys: RwLock<HashMap<[u8; 32], Vec<u8>>>,
+    }
+
+    impl PayloadKeyStore {
+        pub fn new() -> Self {
+            Self {
+                keys: RwLock::new(HashMap::new()),
+            }
+        }
+
+        pub fn store_key(&self, string_id: [u8; 32], key: Vec<u8>) {
+            self.keys.write().insert(string_id, key);
+        }
+
+        pub fn contains_key(&self, string_id: &[u8; 32]) -> bool {
+            self.keys.read().contains_key(string_id)
+        }
+
+        /// Remove a key and hand it over for destruction
+        pub fn take_key(&self, string_id: &[u8; 32]) -> Option<Vec<u8>> {
+            self.keys.write().remove(string_id)
+        }
+    }
+
+    impl Default for PayloadKeyStore {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+}
+
+pub mod gossip_db {
+    //! Gossip DAG persistence
+    //!
+    //! Values are encoded by the gossip layer; this store only keeps bytes.
+
+    use parking_lot::RwLock;
+    use std::collections::{BTreeMap, HashMap};
+
+    /// Gossip events, DAG state and archives of pruned rounds
+    pub struct GossipStore {
+        events: RwLock<HashMap<[u8; 32], Vec<u8>>>,
+        state: RwLock<Option<Vec<u8>>>,
+        archives: RwLock<BTreeMap<u64, Vec<u8>>>,
+    }
+
+    impl GossipStore {
+        pub fn new() -> Self {
+            Self {
+                events: RwLock::new(HashMap::new()),
+                state: RwLock::new(None),
+                archives: RwLock::new(BTreeMap::new()),
+            }
+        }
+
+        pub fn put_event(&self, id: [u8; 32], event: Vec<u8>) {
+            self.events.write().insert(id, event);
+        }
+
+        pub fn delete_event(&self, id: &[u8; 32]) -> bool {
+            self.events.write().remove(id).is_some()
+        }
+
+        pub fn contains_event(&self, id: &[u8; 32]) -> bool {
+            self.events.read().contains_key(id)
+        }
+
+        /// Every stored event, in no particular order
+        pub fn events(&self) -> Vec<([u8; 32], Vec<u8>)> {
+            self.events
+                .read()
+                .iter()
+                .map(|(id, event)| (*id, event.clone()))
+                .collect()
+        }
+
+        pub fn event_count(&self) -> usize {
+            self.events.read().len()
+        }
+
+        pub fn save_state(&self, state: Vec<u8>) {
+            *self.state.write() = Some(state);
+        }
+
+        pub fn load_state(&self) -> Option<Vec<u8>> {
+            self.state.read().clone()
+        }
+
+        /// Store the archive closed by the checkpoint at `round`
+        pub fn put_archive(&self, round: u64, archive: Vec<u8>) {
+            self.archives.write().insert(round, archive);
+        }
+
+        pub fn get_archive(&self, round: u64) -> Option<Vec<u8>> {
+            self.archives.read().get(&round).cloned()
+        }
+
+        /// Checkpoint rounds with a stored archive, ascending
+        pub fn archive_rounds(&self) -> Vec<u64> {
+            self.archives.read().keys().copied().collect()
+        }
+    }
+
+    impl Default for GossipStore {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+}
+
 pub mod state_db {
     //! OES and federation state persistence
 
@@ -136,6 +282,8 @@ pub mod state_db {
 
 // Re-export for convenience
 pub use complement_db::ComplementStore;
+pub use gossip_db::GossipStore;
+pub use key_db::PayloadKeyStore;
 pub use lattice_db::LatticeStore;
 pub use state_db::StateStore;
 
@@ -197,6 +345,37 @@ mod tests {
             let key = [5u8; 32];
             assert!(!store.contains(&key));
         }
+
+        #[test]
+        fn test_lattice_store_backs_string_cache() {
+            use rope_core::{
+                CacheConfig, LamportClock, NodeId, PublicKey, RopeString, StringId, StringLattice,
+            };
+            use std::sync::Arc;
+
+            let store = Arc::new(LatticeStore::new());
+            let config = CacheConfig {
+                max_strings: 1,
+                max_bytes: usize::MAX,
+            };
+            let lattice = StringLattice::with_cache(config, store.clone());
+
+            let ids: Vec<StringId> = (0u8..3)
+                .map(|n| {
+                    let string = RopeString::builder()
+                        .content(vec![n; 16])
+                        .temporal_marker(LamportClock::new(NodeId::new([0u8; 32])))
+                        .creator(PublicKey::from_ed25519([0u8; 32]))
+                        .build()
+                        .unwrap();
+                    lattice.add_string(string).unwrap()
+                })
+                .collect();
+
+            assert!(store.contains(ids[0].as_bytes()));
+            assert_eq!(lattice.get_string(&ids[0]).unwrap().id(), ids[0]);
+            assert_eq!(lattice.cache_stats().resident_strings, 1);
+        }
     }
 
     mod complement_store_tests {
@@ -244,6 +423,46 @@ mod tests {
         }
     }
 
+    mod payload_key_store_tests {
+        use super::*;
+
+        #[test]
+        fn test_take_key_removes_it() {
+            let store = PayloadKeyStore::new();
+            let string_id = [1u8; 32];
+            store.store_key(string_id, vec![9; 32]);
+            assert!(store.contains_key(&string_id));
+
+            assert_eq!(store.take_key(&string_id), Some(vec![9; 32]));
+            assert!(!store.contains_key(&string_id));
+            assert!(store.take_key(&string_id).is_none());
+        }
+    }
+
+    mod gossip_store_tests {
+        use super::*;
+
+        #[test]
+        fn test_gossip_store_events_and_archives() {
+            let store = GossipStore::new();
+            store.put_event([1u8; 32], vec![1]);
+            store.put_event([2u8; 32], vec![2]);
+            assert_eq!(store.event_count(), 2);
+            assert!(store.delete_event(&[1u8; 32]));
+            assert!(!store.contains_event(&[1u8; 32]));
+            assert_eq!(store.events(), vec![([2u8; 32], vec![2])]);
+
+            assert!(store.load_state().is_none());
+            store.save_state(vec![7]);
+            assert_eq!(store.load_state(), Some(vec![7]));
+
+            store.put_archive(20, vec![20]);
+            store.put_archive(10, vec![10]);
+            assert_eq!(store.archive_rounds(), vec![10, 20]);
+            assert_eq!(store.get_archive(10), Some(vec![10]));
+        }
+    }
+
     mod state_store_tests {
         use super::*;
 